@@ -0,0 +1,93 @@
+//! Analytics ingestion throughput into an in-memory DuckDB engine.
+//!
+//! Each iteration ingests a fresh batch with new IDs so the engagement
+//! upsert and rollup path does real work rather than hitting conflicts.
+
+use chrono::{DateTime, Duration, Utc};
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use drone_analytics::engine::EngagementRecord;
+use drone_analytics::{AnalyticsEngine, TelemetryRecord};
+use drone_domain::Environment;
+use uuid::Uuid;
+
+const CONVOY: Uuid = Uuid::from_u128(1);
+
+fn engagements(count: usize) -> Vec<EngagementRecord> {
+    (0..count)
+        .map(|i| EngagementRecord {
+            engagement_id: Uuid::new_v4(),
+            convoy_id: CONVOY,
+            drone_id: Uuid::from_u128(100 + (i % 8) as u128),
+            callsign: format!("REAPER-{:02}", i % 8),
+            platform_type: "MQ9_REAPER".to_string(),
+            hit: !i.is_multiple_of(3),
+            weapon_type: "AGM114_HELLFIRE".to_string(),
+            target_type: Some("VEHICLE".to_string()),
+            range_km: Some(4.5),
+            altitude_m: Some(4500.0),
+            timestamp: DateTime::<Utc>::UNIX_EPOCH + Duration::seconds(i as i64 * 30),
+            predicted_pk: None,
+            environment: Environment::Live,
+        })
+        .collect()
+}
+
+fn telemetry(count: usize) -> Vec<TelemetryRecord> {
+    // Fresh drones per batch so samples never collide on (drone, timestamp)
+    let drones: Vec<Uuid> = (0..8).map(|_| Uuid::new_v4()).collect();
+    (0..count)
+        .map(|i| TelemetryRecord {
+            drone_id: drones[i % drones.len()],
+            convoy_id: CONVOY,
+            platform_type: "MQ9_REAPER".to_string(),
+            mission_phase: "LOITER".to_string(),
+            recorded_at: DateTime::<Utc>::UNIX_EPOCH + Duration::seconds(i as i64),
+            latitude: 31.61 + i as f64 * 1e-4,
+            longitude: 65.71,
+            altitude_m: 4500.0,
+            speed_mps: 70.0,
+            fuel_remaining_pct: Some(80.0),
+        })
+        .collect()
+}
+
+fn bench_engagement_ingest(c: &mut Criterion) {
+    let engine = AnalyticsEngine::new_in_memory().expect("in-memory engine");
+
+    let mut group = c.benchmark_group("ingest_engagements");
+    for size in [100, 1_000] {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter_batched(
+                || engagements(size),
+                |batch| engine.ingest_engagements_batch(&batch).expect("ingest"),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn bench_telemetry_ingest(c: &mut Criterion) {
+    let engine = AnalyticsEngine::new_in_memory().expect("in-memory engine");
+
+    let mut group = c.benchmark_group("ingest_telemetry");
+    for size in [100, 1_000] {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter_batched(
+                || telemetry(size),
+                |batch| engine.ingest_telemetry_batch(&batch).expect("ingest"),
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(20);
+    targets = bench_engagement_ingest, bench_telemetry_ingest
+);
+criterion_main!(benches);
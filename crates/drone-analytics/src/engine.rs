@@ -2,21 +2,26 @@
 
 use crate::error::{AnalyticsError, Result};
 use chrono::{DateTime, Utc};
+use drone_domain::{wilson_interval, Environment};
 use duckdb::{params, Connection};
 use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal};
 use std::path::Path;
 use uuid::Uuid;
 
 /// DuckDB-based analytics engine for historical drone data analysis.
 pub struct AnalyticsEngine {
     pub(crate) conn: Connection,
+    /// Connection analyst SQL runs on; opened by
+    /// [`AnalyticsEngine::without_external_access`]
+    pub(crate) sandbox: Option<Connection>,
 }
 
 impl AnalyticsEngine {
     /// Create a new in-memory analytics engine.
     pub fn new_in_memory() -> Result<Self> {
         let conn = Connection::open_in_memory()?;
-        let engine = Self { conn };
+        let engine = Self { conn, sandbox: None };
         engine.initialize_schema()?;
         Ok(engine)
     }
@@ -24,7 +29,7 @@ impl AnalyticsEngine {
     /// Create analytics engine with persistent storage.
     pub fn new_persistent<P: AsRef<Path>>(path: P) -> Result<Self> {
         let conn = Connection::open(path)?;
-        let engine = Self { conn };
+        let engine = Self { conn, sandbox: None };
         engine.initialize_schema()?;
         Ok(engine)
     }
@@ -45,9 +50,17 @@ impl AnalyticsEngine {
                 target_type VARCHAR,
                 range_km DOUBLE,
                 altitude_m DOUBLE,
-                timestamp TIMESTAMP NOT NULL
+                timestamp TIMESTAMP NOT NULL,
+                predicted_pk DOUBLE,
+                environment VARCHAR NOT NULL DEFAULT 'LIVE'
             );
 
+            -- Databases created before predicted Pk was recorded
+            ALTER TABLE engagements ADD COLUMN IF NOT EXISTS predicted_pk DOUBLE;
+
+            -- Databases created before environments existed held live data only
+            ALTER TABLE engagements ADD COLUMN IF NOT EXISTS environment VARCHAR DEFAULT 'LIVE';
+
             -- Drone performance dimension
             CREATE TABLE IF NOT EXISTS drone_performance (
                 drone_id VARCHAR PRIMARY KEY,
@@ -75,24 +88,91 @@ impl AnalyticsEngine {
                 avg_accuracy_pct DOUBLE DEFAULT 0.0
             );
 
+            -- Waypoint visits (arrival/departure per drone)
+            CREATE TABLE IF NOT EXISTS waypoint_visits (
+                convoy_id VARCHAR NOT NULL,
+                drone_id VARCHAR NOT NULL,
+                waypoint_id VARCHAR NOT NULL,
+                waypoint_type VARCHAR NOT NULL,
+                arrived_at TIMESTAMP NOT NULL,
+                departed_at TIMESTAMP,
+                planned_loiter_min INTEGER,
+                PRIMARY KEY (drone_id, waypoint_id, arrived_at)
+            );
+
+            -- Target tracks handed from one drone to another
+            CREATE TABLE IF NOT EXISTS target_handoffs (
+                handoff_id VARCHAR PRIMARY KEY,
+                convoy_id VARCHAR NOT NULL,
+                target_id VARCHAR NOT NULL,
+                from_drone_id VARCHAR NOT NULL,
+                to_drone_id VARCHAR NOT NULL,
+                handed_off_at TIMESTAMP NOT NULL
+            );
+
+            -- Telemetry fact table (one row per drone sample)
+            CREATE TABLE IF NOT EXISTS telemetry (
+                drone_id VARCHAR NOT NULL,
+                convoy_id VARCHAR NOT NULL,
+                platform_type VARCHAR NOT NULL,
+                mission_phase VARCHAR NOT NULL,
+                recorded_at TIMESTAMP NOT NULL,
+                latitude DOUBLE NOT NULL,
+                longitude DOUBLE NOT NULL,
+                altitude_m DOUBLE NOT NULL,
+                speed_mps DOUBLE NOT NULL,
+                fuel_remaining_pct DOUBLE,
+                PRIMARY KEY (drone_id, recorded_at)
+            );
+
+            -- Per-drone daily engagement rollup
+            CREATE TABLE IF NOT EXISTS drone_daily_rollup (
+                drone_id VARCHAR NOT NULL,
+                day DATE NOT NULL,
+                callsign VARCHAR NOT NULL,
+                platform_type VARCHAR NOT NULL,
+                engagements BIGINT NOT NULL,
+                hits BIGINT NOT NULL,
+                PRIMARY KEY (drone_id, day)
+            );
+
+            -- Per-convoy weapon rollup
+            CREATE TABLE IF NOT EXISTS weapon_rollup (
+                convoy_id VARCHAR NOT NULL,
+                weapon_type VARCHAR NOT NULL,
+                engagements BIGINT NOT NULL,
+                hits BIGINT NOT NULL,
+                range_km_sum DOUBLE NOT NULL,
+                range_samples BIGINT NOT NULL,
+                PRIMARY KEY (convoy_id, weapon_type)
+            );
+
             -- Create indexes for common queries
             CREATE INDEX IF NOT EXISTS idx_engagements_convoy ON engagements(convoy_id);
             CREATE INDEX IF NOT EXISTS idx_engagements_drone ON engagements(drone_id);
             CREATE INDEX IF NOT EXISTS idx_engagements_timestamp ON engagements(timestamp);
             CREATE INDEX IF NOT EXISTS idx_engagements_weapon ON engagements(weapon_type);
+            CREATE INDEX IF NOT EXISTS idx_waypoint_visits_convoy ON waypoint_visits(convoy_id);
+            CREATE INDEX IF NOT EXISTS idx_target_handoffs_convoy ON target_handoffs(convoy_id);
+            CREATE INDEX IF NOT EXISTS idx_telemetry_convoy ON telemetry(convoy_id);
             "#,
         )?;
         Ok(())
     }
 
-    /// Ingest an engagement record.
+    /// Ingest an engagement record and fold it into the rollups.
+    ///
+    /// The fact row and the rollup updates commit together, so a failure
+    /// part way leaves neither.
     pub fn ingest_engagement(&self, engagement: &EngagementRecord) -> Result<()> {
-        self.conn.execute(
+        let tx = self.conn.unchecked_transaction()?;
+        let inserted = tx.execute(
             r#"
             INSERT INTO engagements (
                 engagement_id, convoy_id, drone_id, callsign, platform_type,
-                hit, weapon_type, target_type, range_km, altitude_m, timestamp
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                hit, weapon_type, target_type, range_km, altitude_m, timestamp,
+                predicted_pk, environment
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT (engagement_id) DO NOTHING
             "#,
             params![
@@ -107,8 +187,16 @@ impl AnalyticsEngine {
                 engagement.range_km,
                 engagement.altitude_m,
                 engagement.timestamp.to_rfc3339(),
+                engagement.predicted_pk,
+                engagement.environment.as_str(),
             ],
         )?;
+
+        // Duplicates were already counted when first ingested
+        if inserted > 0 {
+            Self::apply_to_rollups(&tx, engagement)?;
+        }
+        tx.commit()?;
         Ok(())
     }
 
@@ -122,16 +210,92 @@ impl AnalyticsEngine {
         Ok(count)
     }
 
+    /// Whether an engagement has already been ingested.
+    pub fn has_engagement(&self, engagement_id: Uuid) -> Result<bool> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM engagements WHERE engagement_id = ?",
+            params![engagement_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Ingest a waypoint visit.
+    pub fn ingest_waypoint_visit(&self, visit: &WaypointVisitRecord) -> Result<()> {
+        self.conn.execute(
+            r#"
+            INSERT INTO waypoint_visits (
+                convoy_id, drone_id, waypoint_id, waypoint_type,
+                arrived_at, departed_at, planned_loiter_min
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (drone_id, waypoint_id, arrived_at)
+            DO UPDATE SET departed_at = excluded.departed_at
+            "#,
+            params![
+                visit.convoy_id.to_string(),
+                visit.drone_id.to_string(),
+                visit.waypoint_id.to_string(),
+                visit.waypoint_type,
+                visit.arrived_at.to_rfc3339(),
+                visit.departed_at.map(|t| t.to_rfc3339()),
+                visit.planned_loiter_min,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Ingest a target hand-off between drones.
+    pub fn ingest_handoff(&self, handoff: &HandoffRecord) -> Result<()> {
+        self.conn.execute(
+            r#"
+            INSERT OR REPLACE INTO target_handoffs (
+                handoff_id, convoy_id, target_id, from_drone_id, to_drone_id, handed_off_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            params![
+                handoff.handoff_id.to_string(),
+                handoff.convoy_id.to_string(),
+                handoff.target_id.to_string(),
+                handoff.from_drone_id.to_string(),
+                handoff.to_drone_id.to_string(),
+                handoff.handed_off_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
     /// Get accuracy trend over time for a drone.
     pub fn accuracy_trend(
         &self,
         drone_id: Uuid,
         interval: &str,
     ) -> Result<Vec<AccuracyDataPoint>> {
+        self.accuracy_trend_with(drone_id, interval, &TrendOptions::default())
+    }
+
+    /// Get accuracy trend with optional rolling-window smoothing and
+    /// Wilson confidence bounds per point.
+    ///
+    /// Smoothing pools hits and engagements over the trailing window rather
+    /// than averaging percentages, so low-volume periods carry less weight.
+    /// Bounds are computed over the same pooled counts.
+    pub fn accuracy_trend_with(
+        &self,
+        drone_id: Uuid,
+        interval: &str,
+        options: &TrendOptions,
+    ) -> Result<Vec<AccuracyDataPoint>> {
+        if options.rolling_window == 0 {
+            return Err(AnalyticsError::InvalidParameter(
+                "rolling_window must be at least 1".to_string(),
+            ));
+        }
+        let z = options.confidence_level.map(z_score).transpose()?;
+
         let query = format!(
             r#"
             SELECT 
-                date_trunc('{}', timestamp) as period,
+                CAST(date_trunc('{}', timestamp) AS VARCHAR) as period,
                 COUNT(*) as total,
                 SUM(CASE WHEN hit THEN 1 ELSE 0 END) as hits,
                 ROUND(100.0 * SUM(CASE WHEN hit THEN 1 ELSE 0 END) / COUNT(*), 2) as accuracy
@@ -150,86 +314,122 @@ impl AnalyticsEngine {
                 total_engagements: row.get(1)?,
                 hits: row.get(2)?,
                 accuracy_pct: row.get(3)?,
+                smoothed_accuracy_pct: None,
+                lower_bound_pct: None,
+                upper_bound_pct: None,
             })
         })?;
 
-        rows.collect::<std::result::Result<Vec<_>, _>>()
-            .map_err(AnalyticsError::from)
+        let mut points = rows.collect::<std::result::Result<Vec<_>, _>>()?;
+        apply_trend_options(&mut points, options.rolling_window, z);
+
+        Ok(points)
+    }
+
+    /// Hits and misses per fixed-width time bucket for a drone.
+    ///
+    /// Buckets are aligned to multiples of `bucket_secs` since the Unix epoch
+    /// and cover `start..=end`; buckets without engagements are returned with
+    /// zero counts so the series can be plotted as stacked bars directly.
+    pub fn engagement_timeline(
+        &self,
+        drone_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        bucket_secs: i64,
+    ) -> Result<Vec<EngagementBucket>> {
+        if bucket_secs <= 0 {
+            return Err(AnalyticsError::InvalidParameter(format!(
+                "bucket_secs must be positive, got {bucket_secs}"
+            )));
+        }
+        if end < start {
+            return Err(AnalyticsError::InvalidParameter(
+                "end must not be before start".to_string(),
+            ));
+        }
+
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT
+                CAST(FLOOR(epoch(timestamp) / ?) AS BIGINT) as bucket,
+                SUM(CASE WHEN hit THEN 1 ELSE 0 END) as hits,
+                SUM(CASE WHEN hit THEN 0 ELSE 1 END) as misses
+            FROM engagements
+            WHERE drone_id = ? AND timestamp >= ?::TIMESTAMP AND timestamp <= ?::TIMESTAMP
+            GROUP BY bucket
+            ORDER BY bucket
+            "#,
+        )?;
+
+        let rows = stmt.query_map(
+            params![
+                bucket_secs,
+                drone_id.to_string(),
+                start.to_rfc3339(),
+                end.to_rfc3339(),
+            ],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?)),
+        )?;
+        let counts = rows.collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(fill_buckets(&counts, start, end, bucket_secs))
     }
 
     /// Get weapon effectiveness analysis.
+    ///
+    /// Served from the weapon rollup rather than the raw engagements.
     pub fn weapon_effectiveness(&self, convoy_id: Option<Uuid>) -> Result<Vec<WeaponStats>> {
-        let results = match convoy_id {
-            Some(id) => {
-                let mut stmt = self.conn.prepare(
-                    r#"
-                    SELECT 
-                        weapon_type,
-                        COUNT(*) as total,
-                        SUM(CASE WHEN hit THEN 1 ELSE 0 END) as hits,
-                        ROUND(100.0 * SUM(CASE WHEN hit THEN 1 ELSE 0 END) / COUNT(*), 2) as accuracy,
-                        ROUND(AVG(range_km), 2) as avg_range
-                    FROM engagements
-                    WHERE convoy_id = ?
-                    GROUP BY weapon_type
-                    ORDER BY accuracy DESC
-                    "#,
-                )?;
-                let rows = stmt.query_map(params![id.to_string()], |row: &duckdb::Row| {
-                    Ok(WeaponStats {
-                        weapon_type: row.get(0)?,
-                        total_engagements: row.get(1)?,
-                        hits: row.get(2)?,
-                        accuracy_pct: row.get(3)?,
-                        avg_range_km: row.get(4)?,
-                    })
-                })?;
-                rows.collect::<std::result::Result<Vec<_>, _>>()?
-            }
-            None => {
-                let mut stmt = self.conn.prepare(
-                    r#"
-                    SELECT 
-                        weapon_type,
-                        COUNT(*) as total,
-                        SUM(CASE WHEN hit THEN 1 ELSE 0 END) as hits,
-                        ROUND(100.0 * SUM(CASE WHEN hit THEN 1 ELSE 0 END) / COUNT(*), 2) as accuracy,
-                        ROUND(AVG(range_km), 2) as avg_range
-                    FROM engagements
-                    GROUP BY weapon_type
-                    ORDER BY accuracy DESC
-                    "#,
-                )?;
-                let rows = stmt.query_map([], |row: &duckdb::Row| {
-                    Ok(WeaponStats {
-                        weapon_type: row.get(0)?,
-                        total_engagements: row.get(1)?,
-                        hits: row.get(2)?,
-                        accuracy_pct: row.get(3)?,
-                        avg_range_km: row.get(4)?,
-                    })
-                })?;
-                rows.collect::<std::result::Result<Vec<_>, _>>()?
-            }
-        };
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT 
+                weapon_type,
+                SUM(engagements) as total,
+                SUM(hits) as total_hits,
+                ROUND(100.0 * SUM(hits) / SUM(engagements), 2) as accuracy,
+                ROUND(SUM(range_km_sum) / NULLIF(SUM(range_samples), 0), 2) as avg_range
+            FROM weapon_rollup
+            WHERE ?::VARCHAR IS NULL OR convoy_id = ?::VARCHAR
+            GROUP BY weapon_type
+            ORDER BY accuracy DESC
+            "#,
+        )?;
 
-        Ok(results)
+        let convoy_str = convoy_id.map(|id| id.to_string());
+        let rows = stmt.query_map(params![convoy_str, convoy_str], |row: &duckdb::Row| {
+            Ok(WeaponStats {
+                weapon_type: row.get(0)?,
+                total_engagements: row.get(1)?,
+                hits: row.get(2)?,
+                accuracy_pct: row.get(3)?,
+                avg_range_km: row.get(4)?,
+            })
+        })?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(AnalyticsError::from)
     }
 
     /// Get top performers by accuracy.
+    ///
+    /// Served from the per-drone daily rollup rather than the raw engagements.
+    /// Each drone is reported once, under its most recent callsign and
+    /// platform.
     pub fn top_performers(&self, limit: usize) -> Result<Vec<DronePerformance>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT 
-                drone_id,
-                callsign,
-                platform_type,
-                COUNT(*) as total,
-                SUM(CASE WHEN hit THEN 1 ELSE 0 END) as hits,
-                ROUND(100.0 * SUM(CASE WHEN hit THEN 1 ELSE 0 END) / COUNT(*), 2) as accuracy
-            FROM engagements
-            GROUP BY drone_id, callsign, platform_type
-            HAVING COUNT(*) >= 5
+                r.drone_id,
+                arg_max(r.callsign, r.day) as callsign,
+                arg_max(r.platform_type, r.day) as platform_type,
+                SUM(r.engagements) as total,
+                SUM(r.hits) as total_hits,
+                ROUND(100.0 * SUM(r.hits) / SUM(r.engagements), 2) as accuracy,
+                COALESCE(MAX(p.total_flight_hours), 0.0) as flight_hours
+            FROM drone_daily_rollup r
+            LEFT JOIN drone_performance p ON p.drone_id = r.drone_id
+            GROUP BY r.drone_id
+            HAVING SUM(r.engagements) >= 5
             ORDER BY accuracy DESC
             LIMIT ?
             "#,
@@ -243,6 +443,7 @@ impl AnalyticsEngine {
                 total_engagements: row.get(3)?,
                 hits: row.get(4)?,
                 accuracy_pct: row.get(5)?,
+                flight_hours: row.get(6)?,
             })
         })?;
 
@@ -289,12 +490,16 @@ impl AnalyticsEngine {
     }
 
     /// Import data from Parquet file.
+    ///
+    /// Bulk imports bypass incremental maintenance, so rollups are rebuilt
+    /// afterwards.
     pub fn import_from_parquet<P: AsRef<Path>>(&self, path: P) -> Result<usize> {
         let query = format!(
-            "INSERT INTO engagements SELECT * FROM read_parquet('{}')",
+            "INSERT INTO engagements BY NAME SELECT * FROM read_parquet('{}')",
             path.as_ref().display()
         );
         let count = self.conn.execute(&query, [])?;
+        self.refresh_rollups()?;
         Ok(count)
     }
 }
@@ -313,6 +518,37 @@ pub struct EngagementRecord {
     pub range_km: Option<f64>,
     pub altitude_m: Option<f64>,
     pub timestamp: DateTime<Utc>,
+    /// Pk the shooter predicted before firing (0-1)
+    #[serde(default)]
+    pub predicted_pk: Option<f64>,
+    /// Environment of the convoy that fired
+    #[serde(default)]
+    pub environment: Environment,
+}
+
+/// Waypoint visit record for analytics ingestion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaypointVisitRecord {
+    pub convoy_id: Uuid,
+    pub drone_id: Uuid,
+    pub waypoint_id: Uuid,
+    /// Waypoint type, e.g. `STRIKE` or `LOITER`
+    pub waypoint_type: String,
+    /// First telemetry inside the waypoint radius
+    pub arrived_at: DateTime<Utc>,
+    pub departed_at: Option<DateTime<Utc>>,
+    pub planned_loiter_min: Option<i32>,
+}
+
+/// Target hand-off record for analytics ingestion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffRecord {
+    pub handoff_id: Uuid,
+    pub convoy_id: Uuid,
+    pub target_id: Uuid,
+    pub from_drone_id: Uuid,
+    pub to_drone_id: Uuid,
+    pub handed_off_at: DateTime<Utc>,
 }
 
 /// Accuracy data point for trend analysis.
@@ -322,6 +558,101 @@ pub struct AccuracyDataPoint {
     pub total_engagements: i64,
     pub hits: i64,
     pub accuracy_pct: f64,
+    /// Pooled accuracy over the trailing rolling window
+    #[serde(default)]
+    pub smoothed_accuracy_pct: Option<f64>,
+    /// Lower Wilson bound (percent)
+    #[serde(default)]
+    pub lower_bound_pct: Option<f64>,
+    /// Upper Wilson bound (percent)
+    #[serde(default)]
+    pub upper_bound_pct: Option<f64>,
+}
+
+/// Accuracy trend options.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrendOptions {
+    /// Number of trailing periods pooled into each point (1 = no smoothing)
+    pub rolling_window: usize,
+    /// Confidence level for Wilson bounds, e.g. 0.95 (`None` = no bounds)
+    pub confidence_level: Option<f64>,
+}
+
+impl Default for TrendOptions {
+    fn default() -> Self {
+        Self {
+            rolling_window: 1,
+            confidence_level: None,
+        }
+    }
+}
+
+/// Two-sided z-score for a confidence level in (0, 1).
+fn z_score(confidence_level: f64) -> Result<f64> {
+    if !(confidence_level > 0.0 && confidence_level < 1.0) {
+        return Err(AnalyticsError::InvalidParameter(format!(
+            "confidence_level must be between 0 and 1, got {confidence_level}"
+        )));
+    }
+
+    let normal = Normal::standard();
+    Ok(normal.inverse_cdf(0.5 + confidence_level / 2.0))
+}
+
+/// Fill smoothed accuracy and confidence bounds on raw trend points.
+fn apply_trend_options(points: &mut [AccuracyDataPoint], window: usize, z: Option<f64>) {
+    for i in 0..points.len() {
+        let start = (i + 1).saturating_sub(window);
+        let (hits, total) = points[start..=i]
+            .iter()
+            .fold((0, 0), |(h, t), p| (h + p.hits, t + p.total_engagements));
+
+        if window > 1 && total > 0 {
+            points[i].smoothed_accuracy_pct = Some(100.0 * hits as f64 / total as f64);
+        }
+
+        if let Some(z) = z {
+            let (lower, upper) = wilson_interval(hits, total, z);
+            points[i].lower_bound_pct = Some(lower * 100.0);
+            points[i].upper_bound_pct = Some(upper * 100.0);
+        }
+    }
+}
+
+/// Zero-fill `(bucket index, hits, misses)` counts over `start..=end`.
+fn fill_buckets(
+    counts: &[(i64, i64, i64)],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    bucket_secs: i64,
+) -> Vec<EngagementBucket> {
+    let first = start.timestamp().div_euclid(bucket_secs);
+    let last = end.timestamp().div_euclid(bucket_secs);
+    let mut counts = counts.iter().peekable();
+
+    (first..=last)
+        .filter_map(|bucket| {
+            let (hits, misses) = match counts.next_if(|(b, _, _)| *b == bucket) {
+                Some(&(_, hits, misses)) => (hits, misses),
+                None => (0, 0),
+            };
+            let bucket_start = DateTime::from_timestamp(bucket * bucket_secs, 0)?;
+            Some(EngagementBucket {
+                bucket_start,
+                hits,
+                misses,
+            })
+        })
+        .collect()
+}
+
+/// Hit and miss counts for one time bucket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngagementBucket {
+    /// Start of the bucket (inclusive)
+    pub bucket_start: DateTime<Utc>,
+    pub hits: i64,
+    pub misses: i64,
 }
 
 /// Weapon effectiveness statistics.
@@ -343,6 +674,9 @@ pub struct DronePerformance {
     pub total_engagements: i64,
     pub hits: i64,
     pub accuracy_pct: f64,
+    /// Cumulative airborne hours from `drone_performance`
+    #[serde(default)]
+    pub flight_hours: f64,
 }
 
 /// Hourly engagement statistics.
@@ -380,13 +714,159 @@ mod tests {
             range_km: Some(5.5),
             altitude_m: Some(5000.0),
             timestamp: Utc::now(),
+            predicted_pk: None,
+            environment: Environment::Live,
         };
 
+        assert!(!engine.has_engagement(engagement.engagement_id).unwrap());
         engine.ingest_engagement(&engagement).unwrap();
+        assert!(engine.has_engagement(engagement.engagement_id).unwrap());
 
         let weapons = engine.weapon_effectiveness(None).unwrap();
         assert_eq!(weapons.len(), 1);
         assert_eq!(weapons[0].weapon_type, "AGM114_HELLFIRE");
         assert_eq!(weapons[0].accuracy_pct, 100.0);
     }
+
+    #[test]
+    fn test_accuracy_trend_smoothing_and_bounds() {
+        let engine = AnalyticsEngine::new_in_memory().unwrap();
+        let drone_id = Uuid::new_v4();
+        let start = Utc::now() - chrono::Duration::days(3);
+
+        // Day 0: 9/10 hits, day 1: 0/1, day 2: 10/10
+        let days: [(i64, usize, usize); 3] = [(0, 10, 9), (1, 1, 0), (2, 10, 10)];
+        for (day, total, hits) in days {
+            for n in 0..total {
+                engine
+                    .ingest_engagement(&EngagementRecord {
+                        engagement_id: Uuid::new_v4(),
+                        convoy_id: Uuid::new_v4(),
+                        drone_id,
+                        callsign: "REAPER-01".to_string(),
+                        platform_type: "MQ9_REAPER".to_string(),
+                        hit: n < hits,
+                        weapon_type: "AGM114_HELLFIRE".to_string(),
+                        target_type: None,
+                        range_km: None,
+                        altitude_m: None,
+                        timestamp: start + chrono::Duration::days(day),
+                        predicted_pk: None,
+                        environment: Environment::Live,
+                    })
+                    .unwrap();
+            }
+        }
+
+        let options = TrendOptions {
+            rolling_window: 2,
+            confidence_level: Some(0.95),
+        };
+        let trend = engine.accuracy_trend_with(drone_id, "day", &options).unwrap();
+
+        assert_eq!(trend.len(), 3);
+        // The single miss barely moves the pooled line
+        let smoothed = trend[1].smoothed_accuracy_pct.unwrap();
+        assert!((smoothed - 100.0 * 9.0 / 11.0).abs() < 1e-9);
+        for point in &trend {
+            let smoothed = point.smoothed_accuracy_pct.unwrap();
+            assert!(point.lower_bound_pct.unwrap() <= smoothed);
+            assert!(smoothed <= point.upper_bound_pct.unwrap());
+        }
+
+        let invalid = TrendOptions {
+            rolling_window: 0,
+            confidence_level: None,
+        };
+        assert!(engine.accuracy_trend_with(drone_id, "day", &invalid).is_err());
+    }
+
+    #[test]
+    fn test_pk_calibration_per_weapon() {
+        let engine = AnalyticsEngine::new_in_memory().unwrap();
+        let convoy_id = Uuid::new_v4();
+
+        // Hellfire calibrated at 9/10, JDAM optimistic at 2/4, one shot without a prediction
+        let shots: [(&str, Option<f64>, usize, usize); 3] = [
+            ("AGM114_HELLFIRE", Some(0.9), 10, 9),
+            ("GBU38_JDAM", Some(0.8), 4, 2),
+            ("GBU12_PAVEWAY", None, 1, 1),
+        ];
+        for (weapon, predicted_pk, total, hits) in shots {
+            for n in 0..total {
+                engine
+                    .ingest_engagement(&EngagementRecord {
+                        engagement_id: Uuid::new_v4(),
+                        convoy_id,
+                        drone_id: Uuid::new_v4(),
+                        callsign: "REAPER-01".to_string(),
+                        platform_type: "MQ9_REAPER".to_string(),
+                        hit: n < hits,
+                        weapon_type: weapon.to_string(),
+                        target_type: None,
+                        range_km: None,
+                        altitude_m: None,
+                        timestamp: Utc::now(),
+                        predicted_pk,
+                        environment: Environment::Live,
+                    })
+                    .unwrap();
+            }
+        }
+
+        let calibration = engine.pk_calibration(Some(convoy_id)).unwrap();
+        assert_eq!(calibration.len(), 2);
+
+        let hellfire = &calibration[0];
+        assert_eq!(hellfire.weapon_type, "AGM114_HELLFIRE");
+        assert_eq!(hellfire.engagements, 10);
+        assert!(hellfire.calibration_gap_pct.abs() < 1e-9);
+        assert!((hellfire.brier_score - 0.09).abs() < 1e-9);
+
+        let jdam = &calibration[1];
+        assert!((jdam.predicted_hit_pct - 80.0).abs() < 1e-9);
+        assert!((jdam.actual_hit_pct - 50.0).abs() < 1e-9);
+        assert!((jdam.calibration_gap_pct - 30.0).abs() < 1e-9);
+
+        assert!(engine.pk_calibration(Some(Uuid::new_v4())).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_engagement_timeline_zero_fills_buckets() {
+        let engine = AnalyticsEngine::new_in_memory().unwrap();
+        let drone_id = Uuid::new_v4();
+        let start = DateTime::from_timestamp(1_800_000_000, 0).unwrap();
+
+        // Minute 0: hit + miss, minute 2: two misses, minute 5 is outside the range
+        let shots: [(i64, bool); 5] = [(10, true), (50, false), (130, false), (170, false), (300, true)];
+        for (offset_secs, hit) in shots {
+            engine
+                .ingest_engagement(&EngagementRecord {
+                    engagement_id: Uuid::new_v4(),
+                    convoy_id: Uuid::new_v4(),
+                    drone_id,
+                    callsign: "REAPER-01".to_string(),
+                    platform_type: "MQ9_REAPER".to_string(),
+                    hit,
+                    weapon_type: "AGM114_HELLFIRE".to_string(),
+                    target_type: None,
+                    range_km: None,
+                    altitude_m: None,
+                    timestamp: start + chrono::Duration::seconds(offset_secs),
+                    predicted_pk: None,
+                    environment: Environment::Live,
+                })
+                .unwrap();
+        }
+
+        let end = start + chrono::Duration::seconds(239);
+        let timeline = engine.engagement_timeline(drone_id, start, end, 60).unwrap();
+
+        let counts: Vec<_> = timeline.iter().map(|b| (b.hits, b.misses)).collect();
+        assert_eq!(counts, [(1, 1), (0, 0), (0, 2), (0, 0)]);
+        assert_eq!(timeline[2].bucket_start, start + chrono::Duration::minutes(2));
+
+        assert!(engine.engagement_timeline(drone_id, start, end, 0).is_err());
+        assert!(engine.engagement_timeline(drone_id, end, start, 60).is_err());
+    }
 }
@@ -0,0 +1,373 @@
+//! Insight detection over recent engagements.
+//!
+//! Compares a convoy's engagements inside a recent window against a
+//! baseline and flags significant accuracy drops: the convoy as a whole
+//! against its own earlier record, each weapon against the same weapon in
+//! other convoys of the same environment, and each drone against its own
+//! earlier record. Callers evaluate periodically and surface the results.
+
+use crate::engine::AnalyticsEngine;
+use crate::error::{AnalyticsError, Result};
+use chrono::{DateTime, Duration, Utc};
+use duckdb::params;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What an insight is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InsightKind {
+    /// Convoy accuracy in the window departs from its earlier accuracy
+    AccuracyAnomaly,
+    /// A weapon hits less often than the same weapon in other convoys
+    WeaponUnderperforming,
+    /// A drone hits less often than it did earlier in the mission
+    DroneTrendingDown,
+}
+
+impl InsightKind {
+    /// Stable identifier, e.g. `DRONE_TRENDING_DOWN`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::AccuracyAnomaly => "ACCURACY_ANOMALY",
+            Self::WeaponUnderperforming => "WEAPON_UNDERPERFORMING",
+            Self::DroneTrendingDown => "DRONE_TRENDING_DOWN",
+        }
+    }
+}
+
+/// A detected deviation from baseline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Insight {
+    /// Convoy the insight concerns
+    pub convoy_id: Uuid,
+    /// What was detected
+    pub kind: InsightKind,
+    /// `convoy`, the weapon type, or the drone callsign
+    pub subject: String,
+    /// Drone concerned, for drone insights
+    pub drone_id: Option<Uuid>,
+    /// Accuracy inside the window (0-100)
+    pub recent_accuracy_pct: f64,
+    /// Accuracy of the baseline (0-100)
+    pub baseline_accuracy_pct: f64,
+    /// Engagements inside the window
+    pub recent_engagements: i64,
+    /// Engagements in the baseline
+    pub baseline_engagements: i64,
+    /// Human-readable summary
+    pub message: String,
+}
+
+/// Sensitivity of insight detection.
+#[derive(Debug, Clone)]
+pub struct InsightThresholds {
+    /// Recent window compared against the baseline
+    pub window: Duration,
+    /// Engagements required on both sides before comparing
+    pub min_samples: i64,
+    /// Accuracy change, in percentage points, that counts as significant
+    pub min_change_pct: f64,
+}
+
+impl Default for InsightThresholds {
+    fn default() -> Self {
+        Self {
+            window: Duration::hours(1),
+            min_samples: 10,
+            min_change_pct: 20.0,
+        }
+    }
+}
+
+/// Engagement and hit counts of one side of a comparison.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    engagements: i64,
+    hits: i64,
+}
+
+impl Sample {
+    fn accuracy_pct(self) -> f64 {
+        if self.engagements == 0 {
+            0.0
+        } else {
+            100.0 * self.hits as f64 / self.engagements as f64
+        }
+    }
+}
+
+impl AnalyticsEngine {
+    /// Insights for a convoy, comparing the window ending at `now` with
+    /// its baselines.
+    ///
+    /// Only changes backed by `min_samples` engagements on both sides are
+    /// reported. Convoy accuracy is flagged in either direction; weapons
+    /// and drones only when they fall behind.
+    pub fn convoy_insights(
+        &self,
+        convoy_id: Uuid,
+        now: DateTime<Utc>,
+        thresholds: &InsightThresholds,
+    ) -> Result<Vec<Insight>> {
+        if thresholds.window <= Duration::zero() {
+            return Err(AnalyticsError::InvalidParameter(
+                "window must be positive".to_string(),
+            ));
+        }
+        let since = (now - thresholds.window).to_rfc3339();
+        let now = now.to_rfc3339();
+        let convoy = convoy_id.to_string();
+        let mut insights = Vec::new();
+
+        let (recent, baseline) = self.conn.query_row(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE timestamp >= ?::TIMESTAMP),
+                COUNT(*) FILTER (WHERE timestamp >= ?::TIMESTAMP AND hit),
+                COUNT(*) FILTER (WHERE timestamp < ?::TIMESTAMP),
+                COUNT(*) FILTER (WHERE timestamp < ?::TIMESTAMP AND hit)
+            FROM engagements
+            WHERE convoy_id = ? AND timestamp <= ?::TIMESTAMP
+            "#,
+            params![since, since, since, since, convoy, now],
+            |row| Ok((sample(row, 0)?, sample(row, 2)?)),
+        )?;
+        insights.extend(compare(
+            thresholds,
+            recent,
+            baseline,
+            true,
+            |recent_pct, baseline_pct| Insight {
+                convoy_id,
+                kind: InsightKind::AccuracyAnomaly,
+                subject: "convoy".to_string(),
+                drone_id: None,
+                recent_accuracy_pct: recent_pct,
+                baseline_accuracy_pct: baseline_pct,
+                recent_engagements: recent.engagements,
+                baseline_engagements: baseline.engagements,
+                message: format!(
+                    "Convoy accuracy {recent_pct:.1}% over the last {} min, \
+                     against {baseline_pct:.1}% earlier",
+                    thresholds.window.num_minutes()
+                ),
+            },
+        ));
+
+        let mut stmt = self.conn.prepare(
+            r#"
+            WITH recent AS (
+                SELECT
+                    weapon_type,
+                    environment,
+                    COUNT(*) as engagements,
+                    SUM(CASE WHEN hit THEN 1 ELSE 0 END) as hits
+                FROM engagements
+                WHERE convoy_id = ?
+                  AND timestamp >= ?::TIMESTAMP AND timestamp <= ?::TIMESTAMP
+                GROUP BY weapon_type, environment
+            )
+            SELECT
+                r.weapon_type,
+                r.engagements,
+                r.hits,
+                COUNT(b.engagement_id),
+                COUNT(b.engagement_id) FILTER (WHERE b.hit)
+            FROM recent r
+            LEFT JOIN engagements b
+                ON b.weapon_type = r.weapon_type
+                AND b.environment = r.environment
+                AND b.convoy_id <> ?
+            GROUP BY r.weapon_type, r.engagements, r.hits
+            ORDER BY r.weapon_type
+            "#,
+        )?;
+        let rows = stmt.query_map(params![convoy, since, now, convoy], |row| {
+            Ok((row.get::<_, String>(0)?, sample(row, 1)?, sample(row, 3)?))
+        })?;
+        for row in rows {
+            let (weapon_type, recent, baseline) = row?;
+            insights.extend(compare(
+                thresholds,
+                recent,
+                baseline,
+                false,
+                |recent_pct, baseline_pct| Insight {
+                    convoy_id,
+                    kind: InsightKind::WeaponUnderperforming,
+                    subject: weapon_type.clone(),
+                    drone_id: None,
+                    recent_accuracy_pct: recent_pct,
+                    baseline_accuracy_pct: baseline_pct,
+                    recent_engagements: recent.engagements,
+                    baseline_engagements: baseline.engagements,
+                    message: format!(
+                        "{weapon_type} accuracy {recent_pct:.1}% against a fleet \
+                         baseline of {baseline_pct:.1}%"
+                    ),
+                },
+            ));
+        }
+
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT
+                drone_id,
+                MAX(callsign),
+                COUNT(*) FILTER (WHERE timestamp >= ?::TIMESTAMP),
+                COUNT(*) FILTER (WHERE timestamp >= ?::TIMESTAMP AND hit),
+                COUNT(*) FILTER (WHERE timestamp < ?::TIMESTAMP),
+                COUNT(*) FILTER (WHERE timestamp < ?::TIMESTAMP AND hit)
+            FROM engagements
+            WHERE convoy_id = ? AND timestamp <= ?::TIMESTAMP
+            GROUP BY drone_id
+            ORDER BY MAX(callsign)
+            "#,
+        )?;
+        let rows = stmt.query_map(params![since, since, since, since, convoy, now], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                sample(row, 2)?,
+                sample(row, 4)?,
+            ))
+        })?;
+        for row in rows {
+            let (drone_id, callsign, recent, baseline) = row?;
+            insights.extend(compare(
+                thresholds,
+                recent,
+                baseline,
+                false,
+                |recent_pct, baseline_pct| Insight {
+                    convoy_id,
+                    kind: InsightKind::DroneTrendingDown,
+                    subject: callsign.clone(),
+                    drone_id: Uuid::parse_str(&drone_id).ok(),
+                    recent_accuracy_pct: recent_pct,
+                    baseline_accuracy_pct: baseline_pct,
+                    recent_engagements: recent.engagements,
+                    baseline_engagements: baseline.engagements,
+                    message: format!(
+                        "{callsign} accuracy down to {recent_pct:.1}% from \
+                         {baseline_pct:.1}% earlier"
+                    ),
+                },
+            ));
+        }
+
+        Ok(insights)
+    }
+}
+
+/// Read an engagement count and the hit count in the next column
+fn sample(row: &duckdb::Row<'_>, idx: usize) -> duckdb::Result<Sample> {
+    Ok(Sample {
+        engagements: row.get(idx)?,
+        hits: row.get(idx + 1)?,
+    })
+}
+
+/// Build an insight when `recent` departs far enough from `baseline`
+fn compare(
+    thresholds: &InsightThresholds,
+    recent: Sample,
+    baseline: Sample,
+    either_direction: bool,
+    insight: impl FnOnce(f64, f64) -> Insight,
+) -> Option<Insight> {
+    if recent.engagements < thresholds.min_samples
+        || baseline.engagements < thresholds.min_samples
+    {
+        return None;
+    }
+    let (recent_pct, baseline_pct) = (recent.accuracy_pct(), baseline.accuracy_pct());
+    let change = recent_pct - baseline_pct;
+    let significant = if either_direction {
+        change.abs() >= thresholds.min_change_pct
+    } else {
+        -change >= thresholds.min_change_pct
+    };
+    significant.then(|| insight(recent_pct, baseline_pct))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::EngagementRecord;
+    use drone_domain::Environment;
+
+    fn engagement(
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        callsign: &str,
+        hit: bool,
+        timestamp: DateTime<Utc>,
+    ) -> EngagementRecord {
+        EngagementRecord {
+            engagement_id: Uuid::new_v4(),
+            convoy_id,
+            drone_id,
+            callsign: callsign.to_string(),
+            platform_type: "MQ9_REAPER".to_string(),
+            hit,
+            weapon_type: "AGM114_HELLFIRE".to_string(),
+            target_type: None,
+            range_km: None,
+            altitude_m: None,
+            timestamp,
+            predicted_pk: None,
+            environment: Environment::Live,
+        }
+    }
+
+    #[test]
+    fn test_convoy_insights_flag_recent_drops() {
+        let engine = AnalyticsEngine::new_in_memory().unwrap();
+        let now = Utc::now();
+        let (convoy, other_convoy) = (Uuid::new_v4(), Uuid::new_v4());
+        let (slumping, steady) = (Uuid::new_v4(), Uuid::new_v4());
+
+        for i in 0..10 {
+            let earlier = now - Duration::hours(3) + Duration::minutes(i);
+            let recent = now - Duration::minutes(30) + Duration::minutes(i);
+            for record in [
+                engagement(convoy, slumping, "REAPER-01", true, earlier),
+                engagement(convoy, slumping, "REAPER-01", i < 2, recent),
+                engagement(convoy, steady, "REAPER-02", i < 8, earlier),
+                engagement(convoy, steady, "REAPER-02", i < 8, recent),
+                engagement(other_convoy, Uuid::new_v4(), "GRAY-01", i < 9, earlier),
+                engagement(other_convoy, Uuid::new_v4(), "GRAY-02", i < 9, recent),
+            ] {
+                engine.ingest_engagement(&record).unwrap();
+            }
+        }
+
+        let insights = engine
+            .convoy_insights(convoy, now, &InsightThresholds::default())
+            .unwrap();
+        let kinds: Vec<_> = insights.iter().map(|i| (i.kind, i.subject.as_str())).collect();
+        assert_eq!(
+            kinds,
+            [
+                (InsightKind::AccuracyAnomaly, "convoy"),
+                (InsightKind::WeaponUnderperforming, "AGM114_HELLFIRE"),
+                (InsightKind::DroneTrendingDown, "REAPER-01"),
+            ]
+        );
+        assert_eq!(insights[0].recent_accuracy_pct, 50.0);
+        assert_eq!(insights[0].baseline_accuracy_pct, 90.0);
+        assert_eq!(insights[1].baseline_engagements, 20);
+        assert_eq!(insights[2].drone_id, Some(slumping));
+        assert_eq!(insights[2].recent_engagements, 10);
+
+        let defaults = InsightThresholds::default();
+        assert!(engine.convoy_insights(other_convoy, now, &defaults).unwrap().is_empty());
+        let strict = InsightThresholds {
+            min_samples: 21,
+            ..defaults
+        };
+        assert!(engine.convoy_insights(convoy, now, &strict).unwrap().is_empty());
+    }
+}
@@ -10,14 +10,25 @@
 //! - Drone performance comparisons
 //! - Mission efficiency metrics
 //! - Weapon effectiveness analysis
+//! - Insight detection over recent engagements
+//! - Flight-profile analysis from telemetry
+//! - Read-only ad-hoc SQL for analysts
 
 #![forbid(unsafe_code)]
 #![warn(clippy::all, missing_docs)]
 
+pub mod archive;
 pub mod engine;
 pub mod error;
+pub mod insights;
+pub mod passthrough;
 pub mod queries;
 pub mod reports;
+pub mod rollups;
+pub mod telemetry;
 
-pub use engine::AnalyticsEngine;
+pub use archive::{ParquetCompression, ParquetExportOptions, PartitionFilter};
+pub use engine::{AnalyticsEngine, EngagementBucket, TrendOptions};
 pub use error::AnalyticsError;
+pub use passthrough::ReadonlyLimits;
+pub use telemetry::TelemetryRecord;
@@ -0,0 +1,343 @@
+//! Ad-hoc read-only SQL for analysts.
+//!
+//! Statements are restricted to a single `SELECT` (or `WITH ... SELECT`),
+//! capped to a row limit and interrupted after a timeout. Results are
+//! returned as JSON records or an Arrow IPC stream.
+//!
+//! DuckDB reads files and URLs through replacement scans
+//! (`SELECT * FROM 'data.csv'`) as well as table functions, so statement
+//! text cannot be screened for file access. Analyst SQL only runs on an
+//! engine whose database refuses it; see
+//! [`AnalyticsEngine::without_external_access`].
+
+use crate::engine::AnalyticsEngine;
+use crate::error::{AnalyticsError, Result};
+use arrow_ipc::writer::StreamWriter;
+use duckdb::types::Value as DuckValue;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Limits applied to passthrough queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadonlyLimits {
+    /// Maximum rows returned
+    pub max_rows: usize,
+    /// Wall-clock budget before the query is interrupted
+    pub timeout: Duration,
+}
+
+impl Default for ReadonlyLimits {
+    fn default() -> Self {
+        Self {
+            max_rows: 1000,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl AnalyticsEngine {
+    /// Disable file and network access and open the connection analyst SQL
+    /// runs on.
+    ///
+    /// The settings apply to the whole database and are locked, so Parquet
+    /// export, import and archiving fail on this engine afterwards; use a
+    /// separate engine for those.
+    pub fn without_external_access(mut self) -> Result<Self> {
+        self.conn.execute_batch(
+            "SET enable_external_access = false; SET lock_configuration = true;",
+        )?;
+        self.sandbox = Some(self.conn.try_clone()?);
+        Ok(self)
+    }
+
+    /// Execute an analyst-supplied `SELECT` with default limits.
+    ///
+    /// Rows are returned as JSON objects keyed by column name.
+    pub fn execute_readonly_sql(&self, sql: &str, params: &[Value]) -> Result<Vec<Value>> {
+        self.execute_readonly_sql_with(sql, params, &ReadonlyLimits::default())
+    }
+
+    /// Execute an analyst-supplied `SELECT` with explicit limits.
+    pub fn execute_readonly_sql_with(
+        &self,
+        sql: &str,
+        params: &[Value],
+        limits: &ReadonlyLimits,
+    ) -> Result<Vec<Value>> {
+        let params = params
+            .iter()
+            .map(json_to_duck)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut stmt = self.prepare_readonly(sql, limits)?;
+        self.with_timeout(limits.timeout, || collect_rows(&mut stmt, &params))
+    }
+
+    /// Execute an analyst-supplied `SELECT` and encode the result set as an
+    /// Arrow IPC stream.
+    ///
+    /// Column types are preserved, so BI tools and dataframes can consume
+    /// the bytes without going through JSON.
+    pub fn execute_readonly_arrow(
+        &self,
+        sql: &str,
+        params: &[Value],
+        limits: &ReadonlyLimits,
+    ) -> Result<Vec<u8>> {
+        let params = params
+            .iter()
+            .map(json_to_duck)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut stmt = self.prepare_readonly(sql, limits)?;
+        self.with_timeout(limits.timeout, || {
+            let batches = stmt.query_arrow(duckdb::params_from_iter(&params))?;
+            let schema = batches.get_schema();
+
+            let mut buf = Vec::new();
+            let mut writer = StreamWriter::try_new(&mut buf, &schema)
+                .map_err(|e| AnalyticsError::Conversion(e.to_string()))?;
+            for batch in batches {
+                writer
+                    .write(&batch)
+                    .map_err(|e| AnalyticsError::Conversion(e.to_string()))?;
+            }
+            writer
+                .finish()
+                .map_err(|e| AnalyticsError::Conversion(e.to_string()))?;
+            drop(writer);
+
+            Ok(buf)
+        })
+    }
+
+    /// The analyst connection; analyst SQL is refused until external
+    /// access is disabled.
+    fn sandbox(&self) -> Result<&duckdb::Connection> {
+        self.sandbox.as_ref().ok_or_else(|| {
+            AnalyticsError::Query("analyst SQL is disabled while external access is on".to_string())
+        })
+    }
+
+    /// Validate `sql` and prepare it wrapped in the row limit.
+    ///
+    /// As a subquery the statement can only read; writes and DDL fail to
+    /// parse.
+    fn prepare_readonly(
+        &self,
+        sql: &str,
+        limits: &ReadonlyLimits,
+    ) -> Result<duckdb::Statement<'_>> {
+        let statement = validate_readonly(sql)?;
+        let wrapped = format!("SELECT * FROM ({statement}) LIMIT {}", limits.max_rows);
+        Ok(self.sandbox()?.prepare(&wrapped)?)
+    }
+
+    /// Run `f`, interrupting the analyst connection if it outlives `timeout`.
+    fn with_timeout<T>(&self, timeout: Duration, f: impl FnOnce() -> Result<T>) -> Result<T> {
+        let interrupt = self.sandbox()?.interrupt_handle();
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let watchdog = thread::spawn(move || {
+            if done_rx.recv_timeout(timeout) == Err(mpsc::RecvTimeoutError::Timeout) {
+                interrupt.interrupt();
+            }
+        });
+
+        let result = f();
+        let _ = done_tx.send(());
+        let _ = watchdog.join();
+
+        result.map_err(|e| match e {
+            AnalyticsError::DuckDb(ref inner)
+                if inner.to_string().to_ascii_lowercase().contains("interrupt") =>
+            {
+                AnalyticsError::Query(format!("Query exceeded {}s timeout", timeout.as_secs()))
+            }
+            other => other,
+        })
+    }
+}
+
+/// Validate that `sql` is a single read-only statement, returning it
+/// without any trailing semicolon.
+fn validate_readonly(sql: &str) -> Result<&str> {
+    let statement = sql.trim().trim_end_matches(';').trim();
+    let reject = |reason: &str| Err(AnalyticsError::InvalidParameter(reason.to_string()));
+
+    if statement.is_empty() {
+        return reject("empty statement");
+    }
+    if statement.contains(';') {
+        return reject("only a single statement is allowed");
+    }
+    if statement.contains("--") || statement.contains("/*") {
+        return reject("comments are not allowed");
+    }
+
+    let upper = statement.to_ascii_uppercase();
+    let first = upper
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .find(|w| !w.is_empty());
+    if !matches!(first, Some("SELECT" | "WITH")) {
+        return reject("statement must be a SELECT");
+    }
+
+    Ok(statement)
+}
+
+fn collect_rows(stmt: &mut duckdb::Statement<'_>, params: &[DuckValue]) -> Result<Vec<Value>> {
+    let mut rows = stmt.query(duckdb::params_from_iter(params))?;
+    let columns: Vec<String> = rows
+        .as_ref()
+        .map(duckdb::Statement::column_names)
+        .unwrap_or_default();
+
+    let mut records = Vec::new();
+    while let Some(row) = rows.next()? {
+        let mut record = Map::with_capacity(columns.len());
+        for (i, name) in columns.iter().enumerate() {
+            record.insert(name.clone(), duck_to_json(row.get::<_, DuckValue>(i)?));
+        }
+        records.push(Value::Object(record));
+    }
+
+    Ok(records)
+}
+
+fn json_to_duck(value: &Value) -> Result<DuckValue> {
+    match value {
+        Value::Null => Ok(DuckValue::Null),
+        Value::Bool(b) => Ok(DuckValue::Boolean(*b)),
+        Value::Number(n) => n
+            .as_i64()
+            .map(DuckValue::BigInt)
+            .or_else(|| n.as_f64().map(DuckValue::Double))
+            .ok_or_else(|| AnalyticsError::InvalidParameter(format!("unsupported number {n}"))),
+        Value::String(s) => Ok(DuckValue::Text(s.clone())),
+        Value::Array(_) | Value::Object(_) => Err(AnalyticsError::InvalidParameter(
+            "parameters must be scalars".to_string(),
+        )),
+    }
+}
+
+fn duck_to_json(value: DuckValue) -> Value {
+    match value {
+        DuckValue::Null => Value::Null,
+        DuckValue::Boolean(b) => Value::Bool(b),
+        DuckValue::TinyInt(i) => Value::from(i),
+        DuckValue::SmallInt(i) => Value::from(i),
+        DuckValue::Int(i) => Value::from(i),
+        DuckValue::BigInt(i) => Value::from(i),
+        DuckValue::UTinyInt(i) => Value::from(i),
+        DuckValue::USmallInt(i) => Value::from(i),
+        DuckValue::UInt(i) => Value::from(i),
+        DuckValue::UBigInt(i) => Value::from(i),
+        DuckValue::Float(f) => Number::from_f64(f64::from(f)).map_or(Value::Null, Value::Number),
+        DuckValue::Double(f) => Number::from_f64(f).map_or(Value::Null, Value::Number),
+        DuckValue::Decimal(d) => d
+            .to_string()
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map_or(Value::Null, Value::Number),
+        DuckValue::Timestamp(unit, v) => chrono::DateTime::from_timestamp_micros(unit.to_micros(v))
+            .map_or(Value::Null, |t| Value::String(t.to_rfc3339())),
+        DuckValue::Text(s) => Value::String(s),
+        other => Value::String(format!("{other:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_readonly() {
+        assert!(validate_readonly("SELECT 1;").is_ok());
+        assert!(validate_readonly("with t as (select 1) select * from t").is_ok());
+
+        assert!(validate_readonly("DELETE FROM engagements").is_err());
+        assert!(validate_readonly("SELECT 1; DROP TABLE engagements").is_err());
+        assert!(validate_readonly("SELECT 1 -- comment").is_err());
+    }
+
+    fn sandboxed() -> AnalyticsEngine {
+        AnalyticsEngine::new_in_memory().unwrap().without_external_access().unwrap()
+    }
+
+    #[test]
+    fn test_external_access_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.csv");
+        std::fs::write(&path, "key,value\nsecret,42\n").unwrap();
+        let path = path.display();
+
+        // Analyst SQL needs the sandbox
+        let open = AnalyticsEngine::new_in_memory().unwrap();
+        assert!(open.execute_readonly_sql("SELECT 1", &[]).is_err());
+
+        let engine = sandboxed();
+        for sql in [
+            format!("SELECT * FROM '{path}'"),
+            format!("SELECT * FROM read_csv('{path}')"),
+            "SELECT * FROM 'https://example.com/data.parquet'".to_string(),
+        ] {
+            assert!(engine.execute_readonly_sql(&sql, &[]).is_err(), "{sql}");
+        }
+        // Nothing can switch access back on
+        assert!(engine.conn.execute_batch("SET enable_external_access = true").is_err());
+    }
+
+    #[test]
+    fn test_execute_readonly_sql_returns_records() {
+        let engine = sandboxed();
+        let rows = engine
+            .execute_readonly_sql(
+                "SELECT ?::INTEGER + 1 AS answer, 'ok' AS status",
+                &[Value::from(41)],
+            )
+            .unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["answer"], Value::from(42));
+        assert_eq!(rows[0]["status"], Value::from("ok"));
+    }
+
+    #[test]
+    fn test_execute_readonly_sql_applies_row_limit() {
+        let engine = sandboxed();
+        let limits = ReadonlyLimits {
+            max_rows: 5,
+            ..ReadonlyLimits::default()
+        };
+        let rows = engine
+            .execute_readonly_sql_with("SELECT * FROM range(100)", &[], &limits)
+            .unwrap();
+
+        assert_eq!(rows.len(), 5);
+    }
+
+    #[test]
+    fn test_execute_readonly_arrow_streams_batches() {
+        let engine = sandboxed();
+        let limits = ReadonlyLimits {
+            max_rows: 10,
+            ..ReadonlyLimits::default()
+        };
+        let bytes = engine
+            .execute_readonly_arrow("SELECT range AS n FROM range(100)", &[], &limits)
+            .unwrap();
+
+        let reader = arrow_ipc::reader::StreamReader::try_new(bytes.as_slice(), None).unwrap();
+        assert_eq!(reader.schema().field(0).name(), "n");
+        let rows: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+        assert_eq!(rows, 10);
+
+        assert!(engine
+            .execute_readonly_arrow("DROP TABLE engagements", &[], &limits)
+            .is_err());
+    }
+}
@@ -27,6 +27,51 @@ pub struct PlatformComparison {
     pub avg_engagements_per_drone: f64,
 }
 
+/// Mission efficiency metrics.
+///
+/// Each engagement is counted as one munition expended. Ratios are `None`
+/// when their denominator is zero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissionEfficiency {
+    pub convoy_id: Uuid,
+    /// Summed per-drone time between first arrival and last departure
+    pub flight_hours: f64,
+    pub total_engagements: i64,
+    pub total_hits: i64,
+    pub engagements_per_flight_hour: Option<f64>,
+    pub hits_per_weapon_expended: Option<f64>,
+    /// Mean time from arriving at a strike waypoint to engaging
+    pub avg_detection_to_engagement_secs: Option<f64>,
+    /// Time on station at loiter waypoints vs. planned loiter time (0-100)
+    pub loiter_utilization_pct: Option<f64>,
+    /// Target tracks handed from one drone to another
+    #[serde(default)]
+    pub handoffs: i64,
+    #[serde(default)]
+    pub handoffs_per_flight_hour: Option<f64>,
+    /// Mean time from a hand-off to the receiving drone's next engagement;
+    /// hand-offs never followed by one are left out
+    #[serde(default)]
+    pub avg_time_to_reengage_secs: Option<f64>,
+}
+
+/// Predicted vs. actual hit rate for one weapon.
+///
+/// Only engagements that carried a pre-shot Pk are counted. A positive gap
+/// means the model is optimistic for this weapon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PkCalibration {
+    pub weapon_type: String,
+    pub engagements: i64,
+    /// Mean predicted Pk (0-100)
+    pub predicted_hit_pct: f64,
+    pub actual_hit_pct: f64,
+    /// Predicted minus actual, in percentage points
+    pub calibration_gap_pct: f64,
+    /// Mean squared error of the predictions; 0 is perfect, 0.25 is a coin flip
+    pub brier_score: f64,
+}
+
 impl AnalyticsEngine {
     /// Get comprehensive mission summary.
     pub fn mission_summary(&self, convoy_id: Uuid) -> Result<Option<MissionSummary>> {
@@ -89,6 +134,115 @@ impl AnalyticsEngine {
         }
     }
 
+    /// Get mission efficiency metrics for a convoy.
+    pub fn mission_efficiency(&self, convoy_id: Uuid) -> Result<MissionEfficiency> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            WITH visits AS (
+                SELECT * FROM waypoint_visits WHERE convoy_id = ?
+            ),
+            flight AS (
+                SELECT COALESCE(SUM(date_diff('second', first_seen, last_seen)), 0) / 3600.0
+                    as flight_hours
+                FROM (
+                    SELECT
+                        drone_id,
+                        MIN(arrived_at) as first_seen,
+                        MAX(COALESCE(departed_at, arrived_at)) as last_seen
+                    FROM visits
+                    GROUP BY drone_id
+                )
+            ),
+            eng AS (
+                SELECT
+                    COUNT(*) as total,
+                    COALESCE(SUM(CASE WHEN hit THEN 1 ELSE 0 END), 0) as hits
+                FROM engagements
+                WHERE convoy_id = ?
+            ),
+            detection AS (
+                SELECT AVG(date_diff('millisecond', detected_at, engaged_at)) / 1000.0
+                    as avg_secs
+                FROM (
+                    SELECT e.engagement_id, e.timestamp as engaged_at, MAX(v.arrived_at) as detected_at
+                    FROM engagements e
+                    JOIN visits v
+                      ON v.drone_id = e.drone_id
+                     AND v.waypoint_type = 'STRIKE'
+                     AND v.arrived_at <= e.timestamp
+                    WHERE e.convoy_id = ?
+                    GROUP BY e.engagement_id, e.timestamp
+                )
+            ),
+            loiter AS (
+                SELECT 100.0 * SUM(LEAST(date_diff('second', arrived_at, departed_at), planned_loiter_min * 60))
+                    / NULLIF(SUM(planned_loiter_min * 60), 0) as utilization
+                FROM visits
+                WHERE waypoint_type = 'LOITER'
+                  AND departed_at IS NOT NULL
+                  AND planned_loiter_min > 0
+            ),
+            handoff AS (
+                SELECT
+                    COUNT(*) as total,
+                    AVG(reengage_ms) / 1000.0 as avg_reengage_secs
+                FROM (
+                    SELECT h.handoff_id,
+                        MIN(date_diff('millisecond', h.handed_off_at, e.timestamp)) as reengage_ms
+                    FROM target_handoffs h
+                    LEFT JOIN engagements e
+                      ON e.drone_id = h.to_drone_id
+                     AND e.convoy_id = h.convoy_id
+                     AND e.timestamp >= h.handed_off_at
+                    WHERE h.convoy_id = ?
+                    GROUP BY h.handoff_id
+                )
+            )
+            SELECT
+                f.flight_hours,
+                e.total,
+                e.hits,
+                d.avg_secs,
+                l.utilization,
+                h.total,
+                h.avg_reengage_secs
+            FROM flight f, eng e, detection d, loiter l, handoff h
+            "#,
+        )?;
+
+        let convoy_str = convoy_id.to_string();
+        let params = duckdb::params![&convoy_str, &convoy_str, &convoy_str, &convoy_str];
+        let (
+            flight_hours, total_engagements, total_hits, avg_secs, utilization, handoffs, reengage,
+        ) = stmt.query_row(params, |row| {
+            Ok((
+                row.get::<_, f64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, Option<f64>>(3)?,
+                row.get::<_, Option<f64>>(4)?,
+                row.get::<_, i64>(5)?,
+                row.get::<_, Option<f64>>(6)?,
+            ))
+        })?;
+
+        Ok(MissionEfficiency {
+            convoy_id,
+            flight_hours,
+            total_engagements,
+            total_hits,
+            engagements_per_flight_hour: (flight_hours > 0.0)
+                .then(|| total_engagements as f64 / flight_hours),
+            hits_per_weapon_expended: (total_engagements > 0)
+                .then(|| total_hits as f64 / total_engagements as f64),
+            avg_detection_to_engagement_secs: avg_secs,
+            loiter_utilization_pct: utilization,
+            handoffs,
+            handoffs_per_flight_hour: (flight_hours > 0.0).then(|| handoffs as f64 / flight_hours),
+            avg_time_to_reengage_secs: reengage,
+        })
+    }
+
     /// Compare performance across platform types.
     pub fn platform_comparison(&self) -> Result<Vec<PlatformComparison>> {
         let mut stmt = self.conn.prepare(
@@ -198,4 +352,40 @@ impl AnalyticsEngine {
         rows.collect::<std::result::Result<Vec<_>, _>>()
             .map_err(crate::error::AnalyticsError::from)
     }
+
+    /// Compare predicted Pk with actual hit rate per weapon, optionally for one convoy.
+    pub fn pk_calibration(&self, convoy_id: Option<Uuid>) -> Result<Vec<PkCalibration>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT 
+                weapon_type,
+                COUNT(*) as engagements,
+                100.0 * AVG(predicted_pk) as predicted_pct,
+                100.0 * AVG(CASE WHEN hit THEN 1.0 ELSE 0.0 END) as actual_pct,
+                AVG(POWER(predicted_pk - CASE WHEN hit THEN 1.0 ELSE 0.0 END, 2)) as brier
+            FROM engagements
+            WHERE predicted_pk IS NOT NULL
+              AND (?::VARCHAR IS NULL OR convoy_id = ?::VARCHAR)
+            GROUP BY weapon_type
+            ORDER BY weapon_type
+            "#,
+        )?;
+
+        let convoy_str = convoy_id.map(|id| id.to_string());
+        let rows = stmt.query_map(duckdb::params![convoy_str, convoy_str], |row: &duckdb::Row| {
+            let predicted_hit_pct: f64 = row.get(2)?;
+            let actual_hit_pct: f64 = row.get(3)?;
+            Ok(PkCalibration {
+                weapon_type: row.get(0)?,
+                engagements: row.get(1)?,
+                predicted_hit_pct,
+                actual_hit_pct,
+                calibration_gap_pct: predicted_hit_pct - actual_hit_pct,
+                brier_score: row.get(4)?,
+            })
+        })?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(crate::error::AnalyticsError::from)
+    }
 }
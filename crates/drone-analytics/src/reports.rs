@@ -2,21 +2,41 @@
 
 use crate::engine::{AnalyticsEngine, DronePerformance, WeaponStats};
 use crate::error::Result;
-use crate::queries::{MissionSummary, PlatformComparison};
+use crate::queries::{MissionEfficiency, MissionSummary, PlatformComparison};
+use crate::telemetry::{AltitudeBin, FuelBurnRate, PhaseSpeedStats};
+use drone_domain::{Environment, LeaderboardEntry};
+use duckdb::params;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Altitude band width used for the report's altitude profile.
+const ALTITUDE_PROFILE_BIN_M: f64 = 1000.0;
+
 /// Comprehensive analytics report.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalyticsReport {
     pub generated_at: String,
     pub convoy_id: Option<Uuid>,
+    /// Environments the report's engagements came from; more than one means
+    /// exercise or test data is mixed in with live results
+    #[serde(default)]
+    pub environments: Vec<Environment>,
     pub mission_summary: Option<MissionSummary>,
+    pub mission_efficiency: Option<MissionEfficiency>,
     pub top_performers: Vec<DronePerformance>,
     pub weapon_stats: Vec<WeaponStats>,
     pub platform_comparison: Vec<PlatformComparison>,
     pub accuracy_by_altitude: Vec<(String, f64)>,
     pub accuracy_by_range: Vec<(String, f64)>,
+    /// Telemetry samples by altitude band
+    #[serde(default)]
+    pub altitude_profile: Vec<AltitudeBin>,
+    /// Fuel burn rate per platform
+    #[serde(default)]
+    pub fuel_burn: Vec<FuelBurnRate>,
+    /// Ground speed distribution per mission phase
+    #[serde(default)]
+    pub speed_by_phase: Vec<PhaseSpeedStats>,
 }
 
 impl AnalyticsEngine {
@@ -26,25 +46,54 @@ impl AnalyticsEngine {
             .map(|id| self.mission_summary(id))
             .transpose()?
             .flatten();
+        let mission_efficiency = convoy_id
+            .map(|id| self.mission_efficiency(id))
+            .transpose()?;
 
         let top_performers = self.top_performers(10)?;
         let weapon_stats = self.weapon_effectiveness(convoy_id)?;
         let platform_comparison = self.platform_comparison()?;
         let accuracy_by_altitude = self.accuracy_by_altitude()?;
         let accuracy_by_range = self.accuracy_by_range()?;
+        let altitude_profile = self.altitude_profile(convoy_id, ALTITUDE_PROFILE_BIN_M)?;
+        let fuel_burn = self.fuel_burn_by_platform(convoy_id)?;
+        let speed_by_phase = self.speed_by_phase(convoy_id)?;
+        let environments = self.engagement_environments(convoy_id)?;
 
         Ok(AnalyticsReport {
             generated_at: chrono::Utc::now().to_rfc3339(),
             convoy_id,
+            environments,
             mission_summary,
+            mission_efficiency,
             top_performers,
             weapon_stats,
             platform_comparison,
             accuracy_by_altitude,
             accuracy_by_range,
+            altitude_profile,
+            fuel_burn,
+            speed_by_phase,
         })
     }
 
+    /// Distinct environments among engagements, optionally for one convoy.
+    fn engagement_environments(&self, convoy_id: Option<Uuid>) -> Result<Vec<Environment>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT DISTINCT environment FROM engagements
+            WHERE ?::VARCHAR IS NULL OR convoy_id = ?::VARCHAR
+            ORDER BY environment
+            "#,
+        )?;
+        let convoy_str = convoy_id.map(|id| id.to_string());
+        let environments = stmt
+            .query_map(params![convoy_str, convoy_str], |row| row.get::<_, String>(0))?
+            .filter_map(|env| env.ok()?.parse().ok())
+            .collect();
+        Ok(environments)
+    }
+
     /// Generate report as JSON string.
     pub fn generate_report_json(&self, convoy_id: Option<Uuid>) -> Result<String> {
         let report = self.generate_report(convoy_id)?;
@@ -59,6 +108,10 @@ impl AnalyticsEngine {
         let mut md = String::new();
         md.push_str("# Drone Convoy Analytics Report\n\n");
         md.push_str(&format!("**Generated:** {}\n\n", report.generated_at));
+        if !report.environments.is_empty() {
+            let environments: Vec<_> = report.environments.iter().map(Environment::as_str).collect();
+            md.push_str(&format!("**Environment:** {}\n\n", environments.join(", ")));
+        }
 
         if let Some(ref summary) = report.mission_summary {
             md.push_str("## Mission Summary\n\n");
@@ -77,19 +130,57 @@ impl AnalyticsEngine {
             md.push_str("\n");
         }
 
+        if let Some(ref eff) = report.mission_efficiency {
+            let fmt = |v: Option<f64>, unit: &str| {
+                v.map(|v| format!("{:.2}{}", v, unit))
+                    .unwrap_or_else(|| "N/A".to_string())
+            };
+            md.push_str("## Mission Efficiency\n\n");
+            md.push_str("| Metric | Value |\n");
+            md.push_str("|--------|-------|\n");
+            md.push_str(&format!("| Flight Hours | {:.1} |\n", eff.flight_hours));
+            md.push_str(&format!(
+                "| Engagements / Flight Hour | {} |\n",
+                fmt(eff.engagements_per_flight_hour, "")
+            ));
+            md.push_str(&format!(
+                "| Hits / Weapon Expended | {} |\n",
+                fmt(eff.hits_per_weapon_expended, "")
+            ));
+            md.push_str(&format!(
+                "| Detection to Engagement | {} |\n",
+                fmt(eff.avg_detection_to_engagement_secs, " s")
+            ));
+            md.push_str(&format!(
+                "| Loiter Utilization | {} |\n",
+                fmt(eff.loiter_utilization_pct, "%")
+            ));
+            md.push_str(&format!("| Target Handoffs | {} |\n", eff.handoffs));
+            md.push_str(&format!(
+                "| Handoffs / Flight Hour | {} |\n",
+                fmt(eff.handoffs_per_flight_hour, "")
+            ));
+            md.push_str(&format!(
+                "| Time to Re-engage | {} |\n",
+                fmt(eff.avg_time_to_reengage_secs, " s")
+            ));
+            md.push_str("\n");
+        }
+
         if !report.top_performers.is_empty() {
             md.push_str("## Top Performers\n\n");
-            md.push_str("| Rank | Callsign | Platform | Engagements | Hits | Accuracy |\n");
-            md.push_str("|------|----------|----------|-------------|------|----------|\n");
+            md.push_str("| Rank | Callsign | Platform | Engagements | Hits | Accuracy | Flight Hours |\n");
+            md.push_str("|------|----------|----------|-------------|------|----------|--------------|\n");
             for (i, perf) in report.top_performers.iter().enumerate() {
                 md.push_str(&format!(
-                    "| {} | {} | {} | {} | {} | {:.1}% |\n",
+                    "| {} | {} | {} | {} | {} | {:.1}% | {:.1} |\n",
                     i + 1,
                     perf.callsign,
                     perf.platform_type,
                     perf.total_engagements,
                     perf.hits,
-                    perf.accuracy_pct
+                    perf.accuracy_pct,
+                    perf.flight_hours
                 ));
             }
             md.push_str("\n");
@@ -153,11 +244,180 @@ impl AnalyticsEngine {
             md.push_str("\n");
         }
 
+        if !report.altitude_profile.is_empty() {
+            md.push_str("## Altitude Profile\n\n");
+            md.push_str("| Altitude Band | Samples | Share |\n");
+            md.push_str("|---------------|---------|-------|\n");
+            for bin in &report.altitude_profile {
+                md.push_str(&format!(
+                    "| {:.0}-{:.0} m | {} | {:.1}% |\n",
+                    bin.floor_m, bin.ceiling_m, bin.samples, bin.share_pct
+                ));
+            }
+            md.push_str("\n");
+        }
+
+        if !report.fuel_burn.is_empty() {
+            md.push_str("## Fuel Burn by Platform\n\n");
+            md.push_str("| Platform | Drones | Flight Hours | Burn Rate |\n");
+            md.push_str("|----------|--------|--------------|-----------|\n");
+            for burn in &report.fuel_burn {
+                let rate_str = burn
+                    .burn_pct_per_hour
+                    .map(|r| format!("{:.1}%/h", r))
+                    .unwrap_or_else(|| "N/A".to_string());
+                md.push_str(&format!(
+                    "| {} | {} | {:.1} | {} |\n",
+                    burn.platform_type, burn.drone_count, burn.flight_hours, rate_str
+                ));
+            }
+            md.push_str("\n");
+        }
+
+        if !report.speed_by_phase.is_empty() {
+            md.push_str("## Speed by Mission Phase\n\n");
+            md.push_str("| Phase | Samples | Avg | Median | P90 | Max |\n");
+            md.push_str("|-------|---------|-----|--------|-----|-----|\n");
+            for phase in &report.speed_by_phase {
+                md.push_str(&format!(
+                    "| {} | {} | {:.1} m/s | {:.1} m/s | {:.1} m/s | {:.1} m/s |\n",
+                    phase.mission_phase,
+                    phase.samples,
+                    phase.avg_speed_mps,
+                    phase.median_speed_mps,
+                    phase.p90_speed_mps,
+                    phase.max_speed_mps
+                ));
+            }
+            md.push_str("\n");
+        }
+
         md.push_str("---\n");
         md.push_str("*Classification: UNCLASSIFIED // FOUO*\n");
 
         Ok(md)
     }
+
+    /// Convoy leaderboard from the OLAP store, ranked by accuracy then hits.
+    pub fn leaderboard_rows(&self, convoy_id: Uuid) -> Result<Vec<LeaderboardRow>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT
+                callsign,
+                platform_type,
+                COUNT(*) as total,
+                SUM(CASE WHEN hit THEN 1 ELSE 0 END) as hits,
+                ROUND(100.0 * SUM(CASE WHEN hit THEN 1 ELSE 0 END) / COUNT(*), 2) as accuracy
+            FROM engagements
+            WHERE convoy_id = ?
+            GROUP BY drone_id, callsign, platform_type
+            ORDER BY accuracy DESC, hits DESC, callsign
+            "#,
+        )?;
+
+        let rows = stmt.query_map(params![convoy_id.to_string()], |row| {
+            Ok(LeaderboardRow {
+                rank: 0,
+                callsign: row.get(0)?,
+                platform_type: row.get(1)?,
+                total_engagements: row.get(2)?,
+                hits: row.get(3)?,
+                accuracy_pct: row.get(4)?,
+            })
+        })?;
+
+        let mut ranked = Vec::new();
+        for (i, row) in rows.enumerate() {
+            ranked.push(LeaderboardRow { rank: i as i64 + 1, ..row? });
+        }
+        Ok(ranked)
+    }
+
+    /// Convoy leaderboard from the OLAP store as CSV.
+    pub fn export_leaderboard_csv(&self, convoy_id: Uuid) -> Result<String> {
+        Ok(leaderboard_csv(&self.leaderboard_rows(convoy_id)?))
+    }
+
+    /// Convoy leaderboard from the OLAP store as a Markdown table.
+    pub fn export_leaderboard_markdown(&self, convoy_id: Uuid) -> Result<String> {
+        Ok(leaderboard_markdown(&self.leaderboard_rows(convoy_id)?))
+    }
+}
+
+/// One leaderboard line for export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardRow {
+    pub rank: i64,
+    pub callsign: String,
+    pub platform_type: String,
+    pub total_engagements: i64,
+    pub hits: i64,
+    pub accuracy_pct: f64,
+}
+
+/// Live leaderboard entries from the persistence repository.
+impl From<&LeaderboardEntry> for LeaderboardRow {
+    fn from(entry: &LeaderboardEntry) -> Self {
+        Self {
+            rank: i64::from(entry.rank),
+            callsign: entry.callsign.clone(),
+            platform_type: entry.platform_type.as_str().to_string(),
+            total_engagements: i64::from(entry.total_engagements),
+            hits: i64::from(entry.successful_hits),
+            accuracy_pct: f64::from(entry.accuracy_pct),
+        }
+    }
+}
+
+/// Leaderboard as CSV with a header row.
+pub fn leaderboard_csv(rows: &[LeaderboardRow]) -> String {
+    let mut csv = String::from("rank,callsign,platform,engagements,hits,accuracy_pct\r\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{:.1}\r\n",
+            row.rank,
+            csv_field(&row.callsign),
+            csv_field(&row.platform_type),
+            row.total_engagements,
+            row.hits,
+            row.accuracy_pct
+        ));
+    }
+    csv
+}
+
+/// Leaderboard as a Markdown table, ready to paste into a brief.
+pub fn leaderboard_markdown(rows: &[LeaderboardRow]) -> String {
+    let mut md = String::new();
+    md.push_str("| Rank | Callsign | Platform | Engagements | Hits | Accuracy |\n");
+    md.push_str("|------|----------|----------|-------------|------|----------|\n");
+    for row in rows {
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {:.1}% |\n",
+            row.rank,
+            row.callsign.replace('|', "\\|"),
+            row.platform_type,
+            row.total_engagements,
+            row.hits,
+            row.accuracy_pct
+        ));
+    }
+    md
+}
+
+/// Quote a CSV field when needed, and defuse text a spreadsheet would
+/// evaluate as a formula.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
 }
 
 #[cfg(test)]
@@ -178,4 +438,110 @@ mod tests {
         let md = engine.generate_report_markdown(None).unwrap();
         assert!(md.contains("# Drone Convoy Analytics Report"));
     }
+
+    #[test]
+    fn test_mission_efficiency_section() {
+        use crate::engine::{EngagementRecord, HandoffRecord, WaypointVisitRecord};
+        use chrono::{Duration, Utc};
+
+        let engine = AnalyticsEngine::new_in_memory().unwrap();
+        let convoy_id = Uuid::new_v4();
+        let drone_id = Uuid::new_v4();
+        let t0 = Utc::now() - Duration::hours(4);
+
+        let visit = |waypoint_type: &str, start_min: i64, end_min: i64, loiter: Option<i32>| {
+            WaypointVisitRecord {
+                convoy_id,
+                drone_id,
+                waypoint_id: Uuid::new_v4(),
+                waypoint_type: waypoint_type.to_string(),
+                arrived_at: t0 + Duration::minutes(start_min),
+                departed_at: Some(t0 + Duration::minutes(end_min)),
+                planned_loiter_min: loiter,
+            }
+        };
+        engine.ingest_waypoint_visit(&visit("NAV", 0, 10, None)).unwrap();
+        engine.ingest_waypoint_visit(&visit("LOITER", 30, 45, Some(30))).unwrap();
+        engine.ingest_waypoint_visit(&visit("STRIKE", 60, 120, None)).unwrap();
+
+        for (offset_min, hit) in [(62, true), (66, false)] {
+            engine
+                .ingest_engagement(&EngagementRecord {
+                    engagement_id: Uuid::new_v4(),
+                    convoy_id,
+                    drone_id,
+                    callsign: "REAPER-01".to_string(),
+                    platform_type: "MQ9_REAPER".to_string(),
+                    hit,
+                    weapon_type: "AGM114_HELLFIRE".to_string(),
+                    target_type: None,
+                    range_km: None,
+                    altitude_m: None,
+                    timestamp: t0 + Duration::minutes(offset_min),
+                    predicted_pk: None,
+                    environment: Environment::Exercise,
+                })
+                .unwrap();
+        }
+
+        // Wingman hands the target over two minutes before the first shot
+        engine
+            .ingest_handoff(&HandoffRecord {
+                handoff_id: Uuid::new_v4(),
+                convoy_id,
+                target_id: Uuid::new_v4(),
+                from_drone_id: Uuid::new_v4(),
+                to_drone_id: drone_id,
+                handed_off_at: t0 + Duration::minutes(60),
+            })
+            .unwrap();
+
+        let report = engine.generate_report(Some(convoy_id)).unwrap();
+        assert_eq!(report.environments, [Environment::Exercise]);
+        let eff = report.mission_efficiency.unwrap();
+
+        assert!((eff.flight_hours - 2.0).abs() < 1e-9);
+        assert!((eff.engagements_per_flight_hour.unwrap() - 1.0).abs() < 1e-9);
+        assert!((eff.hits_per_weapon_expended.unwrap() - 0.5).abs() < 1e-9);
+        assert!((eff.avg_detection_to_engagement_secs.unwrap() - 240.0).abs() < 1e-6);
+        assert!((eff.loiter_utilization_pct.unwrap() - 50.0).abs() < 1e-9);
+        assert_eq!(eff.handoffs, 1);
+        assert!((eff.handoffs_per_flight_hour.unwrap() - 0.5).abs() < 1e-9);
+        assert!((eff.avg_time_to_reengage_secs.unwrap() - 120.0).abs() < 1e-6);
+
+        let md = engine.generate_report_markdown(Some(convoy_id)).unwrap();
+        assert!(md.contains("## Mission Efficiency"));
+        assert!(md.contains("**Environment:** EXERCISE"));
+    }
+
+    #[test]
+    fn test_leaderboard_exports() {
+        let rows = vec![
+            LeaderboardRow {
+                rank: 1,
+                callsign: "REAPER-01".to_string(),
+                platform_type: "MQ-9_REAPER".to_string(),
+                total_engagements: 8,
+                hits: 7,
+                accuracy_pct: 87.5,
+            },
+            LeaderboardRow {
+                rank: 2,
+                callsign: "=HYPERLINK(\"x\"),1".to_string(),
+                platform_type: "MQ-1C_GRAY_EAGLE".to_string(),
+                total_engagements: 3,
+                hits: 1,
+                accuracy_pct: 33.333,
+            },
+        ];
+
+        let csv = leaderboard_csv(&rows);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "rank,callsign,platform,engagements,hits,accuracy_pct");
+        assert_eq!(lines[1], "1,REAPER-01,MQ-9_REAPER,8,7,87.5");
+        assert_eq!(lines[2], "2,\"'=HYPERLINK(\"\"x\"\"),1\",MQ-1C_GRAY_EAGLE,3,1,33.3");
+
+        let md = leaderboard_markdown(&rows[..1]);
+        assert!(md.ends_with("| 1 | REAPER-01 | MQ-9_REAPER | 8 | 7 | 87.5% |\n"));
+    }
 }
@@ -0,0 +1,192 @@
+//! Incrementally maintained engagement rollups.
+//!
+//! `drone_daily_rollup` and `weapon_rollup` are updated on every ingested
+//! engagement so leaderboard and weapon queries never scan the fact table.
+//! Bulk loads that bypass [`AnalyticsEngine::ingest_engagement`] must call
+//! [`AnalyticsEngine::refresh_rollups`] afterwards.
+
+use crate::engine::{AnalyticsEngine, EngagementRecord};
+use crate::error::Result;
+use duckdb::{params, Connection};
+
+impl AnalyticsEngine {
+    /// Fold a newly ingested engagement into the rollup tables, on the
+    /// connection (or transaction) that inserted it.
+    pub(crate) fn apply_to_rollups(conn: &Connection, engagement: &EngagementRecord) -> Result<()> {
+        let hits = i64::from(engagement.hit);
+
+        conn.execute(
+            r#"
+            INSERT INTO drone_daily_rollup (
+                drone_id, day, callsign, platform_type, engagements, hits
+            ) VALUES (?, ?, ?, ?, 1, ?)
+            ON CONFLICT (drone_id, day) DO UPDATE SET
+                callsign = excluded.callsign,
+                platform_type = excluded.platform_type,
+                engagements = drone_daily_rollup.engagements + 1,
+                hits = drone_daily_rollup.hits + excluded.hits
+            "#,
+            params![
+                engagement.drone_id.to_string(),
+                engagement.timestamp.date_naive().to_string(),
+                engagement.callsign,
+                engagement.platform_type,
+                hits,
+            ],
+        )?;
+
+        conn.execute(
+            r#"
+            INSERT INTO weapon_rollup (
+                convoy_id, weapon_type, engagements, hits, range_km_sum, range_samples
+            ) VALUES (?, ?, 1, ?, ?, ?)
+            ON CONFLICT (convoy_id, weapon_type) DO UPDATE SET
+                engagements = weapon_rollup.engagements + 1,
+                hits = weapon_rollup.hits + excluded.hits,
+                range_km_sum = weapon_rollup.range_km_sum + excluded.range_km_sum,
+                range_samples = weapon_rollup.range_samples + excluded.range_samples
+            "#,
+            params![
+                engagement.convoy_id.to_string(),
+                engagement.weapon_type,
+                hits,
+                engagement.range_km.unwrap_or(0.0),
+                i64::from(engagement.range_km.is_some()),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Rebuild all rollups from the engagements fact table.
+    ///
+    /// Maintenance operation for after bulk imports or manual edits; regular
+    /// ingestion keeps the rollups current on its own.
+    pub fn refresh_rollups(&self) -> Result<()> {
+        self.conn.execute_batch(
+            r#"
+            BEGIN TRANSACTION;
+
+            DELETE FROM drone_daily_rollup;
+            INSERT INTO drone_daily_rollup
+            SELECT
+                drone_id,
+                CAST(timestamp AS DATE) as day,
+                arg_max(callsign, timestamp),
+                arg_max(platform_type, timestamp),
+                COUNT(*),
+                SUM(CASE WHEN hit THEN 1 ELSE 0 END)
+            FROM engagements
+            GROUP BY drone_id, day;
+
+            DELETE FROM weapon_rollup;
+            INSERT INTO weapon_rollup
+            SELECT
+                convoy_id,
+                weapon_type,
+                COUNT(*),
+                SUM(CASE WHEN hit THEN 1 ELSE 0 END),
+                COALESCE(SUM(range_km), 0.0),
+                COUNT(range_km)
+            FROM engagements
+            GROUP BY convoy_id, weapon_type;
+
+            COMMIT;
+            "#,
+        )?;
+
+        tracing::debug!("Analytics rollups refreshed");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use drone_domain::Environment;
+    use uuid::Uuid;
+
+    fn engagement(drone_id: Uuid, convoy_id: Uuid, hit: bool, days_ago: i64) -> EngagementRecord {
+        EngagementRecord {
+            engagement_id: Uuid::new_v4(),
+            convoy_id,
+            drone_id,
+            callsign: "REAPER-01".to_string(),
+            platform_type: "MQ9_REAPER".to_string(),
+            hit,
+            weapon_type: "AGM114_HELLFIRE".to_string(),
+            target_type: None,
+            range_km: hit.then_some(4.0),
+            altitude_m: None,
+            timestamp: Utc::now() - Duration::days(days_ago),
+            predicted_pk: None,
+            environment: Environment::Live,
+        }
+    }
+
+    #[test]
+    fn test_rollups_track_ingest_and_refresh() {
+        let engine = AnalyticsEngine::new_in_memory().unwrap();
+        let drone_id = Uuid::new_v4();
+        let convoy_id = Uuid::new_v4();
+
+        let records: Vec<_> = (0..6)
+            .map(|n| engagement(drone_id, convoy_id, n % 3 != 0, n % 2))
+            .collect();
+        engine.ingest_engagements_batch(&records).unwrap();
+        // Re-ingesting a duplicate must not double count
+        engine.ingest_engagement(&records[0]).unwrap();
+
+        let top = engine.top_performers(10).unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].total_engagements, 6);
+        assert_eq!(top[0].hits, 4);
+
+        let weapons = engine.weapon_effectiveness(Some(convoy_id)).unwrap();
+        assert_eq!(weapons[0].total_engagements, 6);
+        assert_eq!(weapons[0].avg_range_km, Some(4.0));
+
+        // Rows written behind the engine's back only show up after a refresh
+        engine
+            .conn
+            .execute(
+                "INSERT INTO engagements SELECT * REPLACE (gen_random_uuid()::VARCHAR AS engagement_id) FROM engagements",
+                [],
+            )
+            .unwrap();
+        assert_eq!(engine.top_performers(10).unwrap()[0].total_engagements, 6);
+
+        engine.refresh_rollups().unwrap();
+        let top = engine.top_performers(10).unwrap();
+        assert_eq!(top[0].total_engagements, 12);
+        assert_eq!(top[0].hits, 8);
+        let weapons = engine.weapon_effectiveness(None).unwrap();
+        assert_eq!(weapons[0].total_engagements, 12);
+        assert_eq!(weapons[0].avg_range_km, Some(4.0));
+    }
+
+    #[test]
+    fn test_top_performers_one_row_per_drone() {
+        let engine = AnalyticsEngine::new_in_memory().unwrap();
+        let drone_id = Uuid::new_v4();
+        let convoy_id = Uuid::new_v4();
+
+        // Renamed mid-deployment: older days carry the old callsign
+        let records: Vec<_> = (0..6)
+            .map(|n| {
+                let mut record = engagement(drone_id, convoy_id, true, n);
+                if n > 2 {
+                    record.callsign = "REAPER-99".to_string();
+                }
+                record
+            })
+            .collect();
+        engine.ingest_engagements_batch(&records).unwrap();
+
+        let top = engine.top_performers(10).unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].callsign, "REAPER-01");
+        assert_eq!(top[0].total_engagements, 6);
+    }
+}
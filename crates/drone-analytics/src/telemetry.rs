@@ -0,0 +1,426 @@
+//! Telemetry fact ingestion and flight-profile analysis.
+
+use crate::engine::AnalyticsEngine;
+use crate::error::{AnalyticsError, Result};
+use chrono::{DateTime, Utc};
+use drone_domain::flight_hours::MAX_SAMPLE_GAP_SECS;
+use drone_domain::Telemetry;
+use duckdb::params;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Telemetry sample for analytics ingestion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryRecord {
+    /// Reporting drone
+    pub drone_id: Uuid,
+    /// Convoy the drone was assigned to
+    pub convoy_id: Uuid,
+    /// Platform type, e.g. `MQ9_REAPER`
+    pub platform_type: String,
+    /// Mission phase at sample time, e.g. `INGRESS` or `LOITER`
+    pub mission_phase: String,
+    /// Sample timestamp
+    pub recorded_at: DateTime<Utc>,
+    /// Latitude in degrees
+    pub latitude: f64,
+    /// Longitude in degrees
+    pub longitude: f64,
+    /// Altitude above MSL in meters
+    pub altitude_m: f64,
+    /// Ground speed in m/s
+    pub speed_mps: f64,
+    /// Remaining fuel (0-100)
+    pub fuel_remaining_pct: Option<f64>,
+}
+
+impl TelemetryRecord {
+    /// Build a record from a telemetry row synced out of ScyllaDB.
+    ///
+    /// Telemetry rows carry no convoy or platform context, so the caller
+    /// supplies them along with the drone's current mission phase.
+    pub fn from_telemetry(
+        telemetry: &Telemetry,
+        convoy_id: Uuid,
+        platform_type: &str,
+        mission_phase: &str,
+    ) -> Self {
+        Self {
+            drone_id: telemetry.drone_id,
+            convoy_id,
+            platform_type: platform_type.to_string(),
+            mission_phase: mission_phase.to_string(),
+            recorded_at: telemetry.recorded_at,
+            latitude: telemetry.position.latitude,
+            longitude: telemetry.position.longitude,
+            altitude_m: telemetry.position.altitude_m,
+            speed_mps: f64::from(telemetry.position.speed_mps),
+            fuel_remaining_pct: Some(f64::from(telemetry.fuel_remaining_pct)),
+        }
+    }
+}
+
+/// Share of telemetry samples within an altitude band.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AltitudeBin {
+    /// Band floor in meters (inclusive)
+    pub floor_m: f64,
+    /// Band ceiling in meters (exclusive)
+    pub ceiling_m: f64,
+    /// Samples in the band
+    pub samples: i64,
+    /// Share of all samples (0-100)
+    pub share_pct: f64,
+}
+
+/// Fuel burn rate for a platform type.
+///
+/// Intervals where fuel rises (refuelling) are excluded from both burn and
+/// time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuelBurnRate {
+    /// Platform type
+    pub platform_type: String,
+    /// Drones contributing samples
+    pub drone_count: i64,
+    /// Summed time between consecutive samples
+    pub flight_hours: f64,
+    /// Fuel percentage points burned per flight hour
+    pub burn_pct_per_hour: Option<f64>,
+}
+
+/// Speed distribution within a mission phase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseSpeedStats {
+    /// Mission phase
+    pub mission_phase: String,
+    /// Samples in the phase
+    pub samples: i64,
+    /// Mean ground speed in m/s
+    pub avg_speed_mps: f64,
+    /// Median ground speed in m/s
+    pub median_speed_mps: f64,
+    /// 90th percentile ground speed in m/s
+    pub p90_speed_mps: f64,
+    /// Maximum ground speed in m/s
+    pub max_speed_mps: f64,
+}
+
+impl AnalyticsEngine {
+    /// Ingest a telemetry sample.
+    pub fn ingest_telemetry(&self, record: &TelemetryRecord) -> Result<()> {
+        self.conn.execute(
+            r#"
+            INSERT INTO telemetry (
+                drone_id, convoy_id, platform_type, mission_phase, recorded_at,
+                latitude, longitude, altitude_m, speed_mps, fuel_remaining_pct
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (drone_id, recorded_at) DO NOTHING
+            "#,
+            params![
+                record.drone_id.to_string(),
+                record.convoy_id.to_string(),
+                record.platform_type,
+                record.mission_phase,
+                record.recorded_at.to_rfc3339(),
+                record.latitude,
+                record.longitude,
+                record.altitude_m,
+                record.speed_mps,
+                record.fuel_remaining_pct,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Batch ingest telemetry samples and refresh flight hours.
+    pub fn ingest_telemetry_batch(&self, records: &[TelemetryRecord]) -> Result<usize> {
+        let mut count = 0;
+        for record in records {
+            self.ingest_telemetry(record)?;
+            count += 1;
+        }
+        self.refresh_flight_hours()?;
+        Ok(count)
+    }
+
+    /// Import telemetry from a Parquet file, matching columns by name.
+    ///
+    /// Samples already present for the same drone and timestamp are skipped.
+    /// Flight hours are refreshed afterwards.
+    pub fn import_telemetry_from_parquet<P: AsRef<Path>>(&self, path: P) -> Result<usize> {
+        let query = format!(
+            "INSERT OR IGNORE INTO telemetry BY NAME SELECT * FROM read_parquet('{}')",
+            path.as_ref().display()
+        );
+        let count = self.conn.execute(&query, [])?;
+        self.refresh_flight_hours()?;
+        Ok(count)
+    }
+
+    /// Recompute `drone_performance.total_flight_hours` from the telemetry
+    /// fact table, returning the number of drones updated.
+    ///
+    /// Matches the API's accounting: an interval counts when the drone was
+    /// airborne at both samples and they are at most
+    /// [`MAX_SAMPLE_GAP_SECS`] apart. Single-sample ingestion does not
+    /// refresh; call this after loading with
+    /// [`AnalyticsEngine::ingest_telemetry`].
+    pub fn refresh_flight_hours(&self) -> Result<usize> {
+        let count = self.conn.execute(
+            r#"
+            INSERT INTO drone_performance (drone_id, callsign, platform_type, total_flight_hours)
+            SELECT
+                h.drone_id,
+                COALESCE(
+                    (SELECT MAX(r.callsign) FROM drone_daily_rollup r WHERE r.drone_id = h.drone_id),
+                    ''
+                ),
+                h.platform_type,
+                h.hours
+            FROM (
+                SELECT
+                    drone_id,
+                    MAX(platform_type) as platform_type,
+                    COALESCE(SUM(CASE
+                        WHEN airborne AND prev_airborne AND gap_secs <= ? THEN gap_secs
+                    END), 0) / 3600.0 as hours
+                FROM (
+                    SELECT
+                        drone_id,
+                        platform_type,
+                        mission_phase NOT IN ('PREFLIGHT', 'LANDED', 'MAINTENANCE') as airborne,
+                        LAG(mission_phase NOT IN ('PREFLIGHT', 'LANDED', 'MAINTENANCE')) OVER w
+                            as prev_airborne,
+                        date_diff('second', LAG(recorded_at) OVER w, recorded_at) as gap_secs
+                    FROM telemetry
+                    WINDOW w AS (PARTITION BY drone_id ORDER BY recorded_at)
+                )
+                GROUP BY drone_id
+            ) h
+            ON CONFLICT (drone_id) DO UPDATE SET
+                callsign = COALESCE(NULLIF(excluded.callsign, ''), drone_performance.callsign),
+                platform_type = excluded.platform_type,
+                total_flight_hours = excluded.total_flight_hours
+            "#,
+            params![MAX_SAMPLE_GAP_SECS],
+        )?;
+        Ok(count)
+    }
+
+    /// Histogram of telemetry samples by altitude band.
+    pub fn altitude_profile(
+        &self,
+        convoy_id: Option<Uuid>,
+        bin_size_m: f64,
+    ) -> Result<Vec<AltitudeBin>> {
+        if !(bin_size_m.is_finite() && bin_size_m > 0.0) {
+            return Err(AnalyticsError::InvalidParameter(format!(
+                "bin_size_m must be positive, got {bin_size_m}"
+            )));
+        }
+
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT
+                FLOOR(altitude_m / ?) * ? as floor_m,
+                COUNT(*) as samples
+            FROM telemetry
+            WHERE ?::VARCHAR IS NULL OR convoy_id = ?::VARCHAR
+            GROUP BY floor_m
+            ORDER BY floor_m
+            "#,
+        )?;
+
+        let convoy_str = convoy_id.map(|id| id.to_string());
+        let rows = stmt.query_map(
+            params![bin_size_m, bin_size_m, convoy_str, convoy_str],
+            |row| Ok((row.get::<_, f64>(0)?, row.get::<_, i64>(1)?)),
+        )?;
+        let bins = rows.collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let total: i64 = bins.iter().map(|(_, samples)| samples).sum();
+        Ok(bins
+            .into_iter()
+            .map(|(floor_m, samples)| AltitudeBin {
+                floor_m,
+                ceiling_m: floor_m + bin_size_m,
+                samples,
+                share_pct: 100.0 * samples as f64 / total as f64,
+            })
+            .collect())
+    }
+
+    /// Fuel burn rate per platform type from consecutive fuel readings.
+    pub fn fuel_burn_by_platform(&self, convoy_id: Option<Uuid>) -> Result<Vec<FuelBurnRate>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            WITH intervals AS (
+                SELECT
+                    platform_type,
+                    drone_id,
+                    LAG(fuel_remaining_pct) OVER w - fuel_remaining_pct as burned,
+                    date_diff('millisecond', LAG(recorded_at) OVER w, recorded_at) / 3600000.0
+                        as hours
+                FROM telemetry
+                WHERE fuel_remaining_pct IS NOT NULL
+                  AND (?::VARCHAR IS NULL OR convoy_id = ?::VARCHAR)
+                WINDOW w AS (PARTITION BY drone_id ORDER BY recorded_at)
+            )
+            SELECT
+                platform_type,
+                COUNT(DISTINCT drone_id) as drone_count,
+                SUM(hours) as flight_hours,
+                SUM(burned) / NULLIF(SUM(hours), 0) as burn_rate
+            FROM intervals
+            WHERE burned >= 0
+            GROUP BY platform_type
+            ORDER BY platform_type
+            "#,
+        )?;
+
+        let convoy_str = convoy_id.map(|id| id.to_string());
+        let rows = stmt.query_map(params![convoy_str, convoy_str], |row| {
+            Ok(FuelBurnRate {
+                platform_type: row.get(0)?,
+                drone_count: row.get(1)?,
+                flight_hours: row.get(2)?,
+                burn_pct_per_hour: row.get(3)?,
+            })
+        })?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(AnalyticsError::from)
+    }
+
+    /// Ground speed distribution per mission phase.
+    pub fn speed_by_phase(&self, convoy_id: Option<Uuid>) -> Result<Vec<PhaseSpeedStats>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT
+                mission_phase,
+                COUNT(*) as samples,
+                AVG(speed_mps) as avg_speed,
+                quantile_cont(speed_mps, 0.5) as median_speed,
+                quantile_cont(speed_mps, 0.9) as p90_speed,
+                MAX(speed_mps) as max_speed
+            FROM telemetry
+            WHERE ?::VARCHAR IS NULL OR convoy_id = ?::VARCHAR
+            GROUP BY mission_phase
+            ORDER BY mission_phase
+            "#,
+        )?;
+
+        let convoy_str = convoy_id.map(|id| id.to_string());
+        let rows = stmt.query_map(params![convoy_str, convoy_str], |row| {
+            Ok(PhaseSpeedStats {
+                mission_phase: row.get(0)?,
+                samples: row.get(1)?,
+                avg_speed_mps: row.get(2)?,
+                median_speed_mps: row.get(3)?,
+                p90_speed_mps: row.get(4)?,
+                max_speed_mps: row.get(5)?,
+            })
+        })?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(AnalyticsError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_flight_profile_queries() {
+        let engine = AnalyticsEngine::new_in_memory().unwrap();
+        let convoy_id = Uuid::new_v4();
+        let drone_id = Uuid::new_v4();
+        let t0 = Utc::now() - Duration::hours(3);
+
+        // Fuel drops 10 pts/hour, with a refuel between the last two samples
+        let samples = [
+            (0, "INGRESS", 400.0, 60.0, 90.0),
+            (30, "INGRESS", 1200.0, 80.0, 85.0),
+            (60, "LOITER", 1400.0, 40.0, 80.0),
+            (90, "LOITER", 1450.0, 50.0, 75.0),
+            (120, "EGRESS", 900.0, 70.0, 95.0),
+        ];
+        for (minutes, phase, altitude_m, speed_mps, fuel) in samples {
+            engine
+                .ingest_telemetry(&TelemetryRecord {
+                    drone_id,
+                    convoy_id,
+                    platform_type: "MQ9_REAPER".to_string(),
+                    mission_phase: phase.to_string(),
+                    recorded_at: t0 + Duration::minutes(minutes),
+                    latitude: 31.6,
+                    longitude: 65.7,
+                    altitude_m,
+                    speed_mps,
+                    fuel_remaining_pct: Some(fuel),
+                })
+                .unwrap();
+        }
+
+        let profile = engine.altitude_profile(Some(convoy_id), 500.0).unwrap();
+        let floors: Vec<f64> = profile.iter().map(|b| b.floor_m).collect();
+        assert_eq!(floors, vec![0.0, 500.0, 1000.0]);
+        assert_eq!(profile[2].samples, 3);
+        assert!((profile[2].share_pct - 60.0).abs() < 1e-9);
+        assert!(engine.altitude_profile(None, 0.0).is_err());
+
+        let burn = engine.fuel_burn_by_platform(Some(convoy_id)).unwrap();
+        assert_eq!(burn.len(), 1);
+        assert!((burn[0].flight_hours - 1.5).abs() < 1e-9);
+        assert!((burn[0].burn_pct_per_hour.unwrap() - 10.0).abs() < 1e-9);
+
+        let speeds = engine.speed_by_phase(None).unwrap();
+        let loiter = speeds.iter().find(|s| s.mission_phase == "LOITER").unwrap();
+        assert_eq!(loiter.samples, 2);
+        assert!((loiter.avg_speed_mps - 45.0).abs() < 1e-9);
+        assert!((loiter.max_speed_mps - 50.0).abs() < 1e-9);
+
+        let md = engine.generate_report_markdown(Some(convoy_id)).unwrap();
+        assert!(md.contains("## Fuel Burn by Platform"));
+    }
+
+    #[test]
+    fn test_refresh_flight_hours() {
+        let engine = AnalyticsEngine::new_in_memory().unwrap();
+        let drone_id = Uuid::new_v4();
+        let t0 = Utc::now() - Duration::hours(1);
+
+        // 4 minutes of continuous flight, a link outage, then landing
+        let samples = [(0, "INGRESS"), (2, "INGRESS"), (4, "LOITER"), (30, "LOITER"), (32, "LANDED")];
+        let records: Vec<TelemetryRecord> = samples
+            .iter()
+            .map(|&(minutes, phase)| TelemetryRecord {
+                drone_id,
+                convoy_id: Uuid::new_v4(),
+                platform_type: "MQ9_REAPER".to_string(),
+                mission_phase: phase.to_string(),
+                recorded_at: t0 + Duration::minutes(minutes),
+                latitude: 31.6,
+                longitude: 65.7,
+                altitude_m: 1000.0,
+                speed_mps: 60.0,
+                fuel_remaining_pct: None,
+            })
+            .collect();
+        engine.ingest_telemetry_batch(&records).unwrap();
+
+        let hours: f64 = engine
+            .conn
+            .query_row(
+                "SELECT total_flight_hours FROM drone_performance WHERE drone_id = ?",
+                params![drone_id.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!((hours - 4.0 / 60.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,110 @@
+//! Geo math benchmarks: Haversine distance, track simplification,
+//! heatmap binning and conflict prediction.
+
+use chrono::{DateTime, Duration, Utc};
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use drone_domain::{
+    Coordinates, FlightPath, ImpactPoint, Km, SeparationMinimum, TrackPoint, bin_impacts,
+    predict_conflicts, simplify_track,
+};
+use uuid::Uuid;
+
+/// Deterministic point `i` of a wandering track around Kandahar
+fn position(i: usize) -> Coordinates {
+    let t = i as f64;
+    let mut c = Coordinates::new(
+        31.61 + 0.0004 * t + 0.01 * (t / 37.0).sin(),
+        65.71 + 0.0003 * t + 0.01 * (t / 53.0).cos(),
+        4500.0 + 300.0 * (t / 71.0).sin(),
+    );
+    c.heading_deg = (i * 7 % 360) as f32;
+    c.speed_mps = 70.0;
+    c
+}
+
+fn track(len: usize) -> Vec<TrackPoint> {
+    (0..len)
+        .map(|i| TrackPoint {
+            recorded_at: DateTime::<Utc>::UNIX_EPOCH + Duration::seconds(i as i64),
+            position: position(i),
+        })
+        .collect()
+}
+
+fn bench_haversine(c: &mut Criterion) {
+    let points: Vec<Coordinates> = (0..1_000).map(position).collect();
+
+    let mut group = c.benchmark_group("haversine");
+    group.throughput(Throughput::Elements(points.len() as u64 - 1));
+    group.bench_function("distance_to_km", |b| {
+        b.iter(|| {
+            points
+                .windows(2)
+                .map(|w| black_box(&w[0]).distance_to_km(black_box(&w[1])))
+                .sum::<Km>()
+        });
+    });
+    group.finish();
+}
+
+fn bench_simplify_track(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simplify_track");
+    for len in [1_000, 10_000, 86_400] {
+        let points = track(len);
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &points, |b, points| {
+            b.iter(|| simplify_track(black_box(points), 500));
+        });
+    }
+    group.finish();
+}
+
+fn bench_bin_impacts(c: &mut Criterion) {
+    let impacts: Vec<ImpactPoint> = (0..10_000)
+        .map(|i| {
+            let p = position(i);
+            ImpactPoint {
+                latitude: p.latitude,
+                longitude: p.longitude,
+                hit: !i.is_multiple_of(3),
+            }
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("bin_impacts");
+    group.throughput(Throughput::Elements(impacts.len() as u64));
+    for resolution_km in [0.5, 2.0] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(resolution_km),
+            &resolution_km,
+            |b, &resolution_km| b.iter(|| bin_impacts(black_box(&impacts), resolution_km)),
+        );
+    }
+    group.finish();
+}
+
+fn bench_predict_conflicts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("predict_conflicts");
+    for drones in [4, 16, 64] {
+        let paths: Vec<FlightPath> = (0..drones)
+            .map(|i| FlightPath {
+                drone_id: Uuid::from_u128(i as u128),
+                position: position(i * 40),
+                upcoming_waypoints: (1..=5).map(|w| position(i * 40 + w * 400)).collect(),
+            })
+            .collect();
+        group.bench_with_input(BenchmarkId::from_parameter(drones), &paths, |b, paths| {
+            b.iter(|| predict_conflicts(black_box(paths), SeparationMinimum::default()));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_haversine,
+    bench_simplify_track,
+    bench_bin_impacts,
+    bench_predict_conflicts
+);
+criterion_main!(benches);
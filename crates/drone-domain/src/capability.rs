@@ -0,0 +1,392 @@
+//! Platform capability registry.
+//!
+//! What each platform type can do: service ceiling, endurance, cruise
+//! speed, the weapons its stations accept and the sensors it carries, along
+//! with each weapon's release envelope. The built-in figures are nominal; a
+//! deployment can override any platform or weapon with its own figures (see
+//! [`CapabilityRegistry::with_overrides`] and
+//! [`CapabilityRegistry::with_envelope_overrides`]).
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    DomainError, FuelProfile, Km, Meters, Mps, PlatformType, SensorType, WeaponStatus, WeaponType,
+};
+
+/// Capabilities of one platform type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlatformCapabilities {
+    pub platform_type: PlatformType,
+    pub max_altitude_m: Meters,
+    /// Endurance at cruise on internal fuel, less the landing reserve
+    pub endurance_hrs: f64,
+    pub cruise_speed_mps: Mps,
+    /// Weapons the platform's stations accept; empty for unarmed platforms
+    pub compatible_weapons: Vec<WeaponType>,
+    /// Most rounds carried across all stations
+    pub max_rounds: i16,
+    pub sensors: Vec<SensorType>,
+}
+
+impl PlatformCapabilities {
+    /// Nominal capabilities of a platform type
+    #[must_use]
+    pub fn builtin(platform_type: PlatformType) -> Self {
+        let fuel = FuelProfile::for_platform(platform_type);
+        let (compatible_weapons, max_rounds, sensors): (&[WeaponType], i16, &[SensorType]) =
+            match platform_type {
+                PlatformType::Mq9Reaper => (
+                    &[
+                        WeaponType::Agm114Hellfire,
+                        WeaponType::Gbu12Paveway,
+                        WeaponType::Gbu38Jdam,
+                        WeaponType::Aim9xSidewinder,
+                    ],
+                    8,
+                    &[SensorType::EoIr, SensorType::Sar],
+                ),
+                PlatformType::Mq1cGrayEagle => (
+                    &[WeaponType::Agm114Hellfire, WeaponType::Agm176Griffin],
+                    8,
+                    &[SensorType::EoIr, SensorType::Sar],
+                ),
+                PlatformType::Rq4GlobalHawk => {
+                    (&[], 0, &[SensorType::EoIr, SensorType::Sar, SensorType::Sigint])
+                }
+                PlatformType::Mq25Stingray => (&[], 0, &[SensorType::EoIr]),
+            };
+        Self {
+            platform_type,
+            max_altitude_m: fuel.service_ceiling_m,
+            endurance_hrs: fuel.capacity_kg * (1.0 - fuel.reserve_fraction)
+                / fuel.cruise_burn_kg_per_hr,
+            cruise_speed_mps: fuel.cruise_speed_mps,
+            compatible_weapons: compatible_weapons.to_vec(),
+            max_rounds,
+            sensors: sensors.to_vec(),
+        }
+    }
+
+    /// Whether the platform's stations accept `weapon_type`
+    #[must_use]
+    pub fn accepts(&self, weapon_type: WeaponType) -> bool {
+        self.compatible_weapons.contains(&weapon_type)
+    }
+
+    /// Check a loadout fits this platform.
+    ///
+    /// Fails if any weapon with rounds left is incompatible or the loadout
+    /// carries more rounds than the platform's stations hold. Incompatible
+    /// weapons with no rounds left are allowed so they can be cleared.
+    pub fn check_loadout(&self, weapons: &[WeaponStatus]) -> Result<(), DomainError> {
+        let incompatible = weapons
+            .iter()
+            .find(|w| w.rounds_remaining > 0 && !self.accepts(w.weapon_type));
+        if let Some(weapon) = incompatible {
+            return Err(DomainError::InvalidLoadout(format!(
+                "{} cannot carry {}",
+                self.platform_type.as_str(),
+                weapon.weapon_type.as_str()
+            )));
+        }
+        let rounds: i32 = weapons.iter().map(|w| i32::from(w.rounds_remaining.max(0))).sum();
+        if rounds > i32::from(self.max_rounds) {
+            return Err(DomainError::InvalidLoadout(format!(
+                "{} carries at most {} rounds, got {rounds}",
+                self.platform_type.as_str(),
+                self.max_rounds
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Ranges and release heights a weapon can be employed from
+///
+/// Range is the ground distance from shooter to target; height is the
+/// shooter's altitude above the target.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeaponEnvelope {
+    pub weapon_type: WeaponType,
+    pub min_range_km: Km,
+    pub max_range_km: Km,
+    pub min_height_m: Meters,
+    pub max_height_m: Meters,
+}
+
+impl WeaponEnvelope {
+    /// Nominal envelope of a weapon type
+    #[must_use]
+    pub fn builtin(weapon_type: WeaponType) -> Self {
+        let (min_range_km, max_range_km, min_height_m, max_height_m) = match weapon_type {
+            WeaponType::Agm114Hellfire => (0.5, 11.0, 0.0, 7_600.0),
+            WeaponType::Agm176Griffin => (0.3, 15.0, 0.0, 6_100.0),
+            WeaponType::Gbu12Paveway => (1.0, 15.0, 600.0, 12_200.0),
+            WeaponType::Gbu38Jdam => (1.0, 28.0, 600.0, 13_700.0),
+            WeaponType::Aim9xSidewinder => (0.3, 35.0, 0.0, 15_200.0),
+        };
+        Self {
+            weapon_type,
+            min_range_km: Km(min_range_km),
+            max_range_km: Km(max_range_km),
+            min_height_m: Meters(min_height_m),
+            max_height_m: Meters(max_height_m),
+        }
+    }
+
+    /// Check an engagement at `range_km` from `height_m` above the target;
+    /// range is checked before height
+    pub fn check(&self, range_km: Km, height_m: Meters) -> Result<(), EnvelopeViolation> {
+        let weapon_type = self.weapon_type;
+        if range_km < self.min_range_km {
+            return Err(EnvelopeViolation::TooClose {
+                weapon_type,
+                range_km,
+                min_range_km: self.min_range_km,
+            });
+        }
+        if range_km > self.max_range_km {
+            return Err(EnvelopeViolation::OutOfRange {
+                weapon_type,
+                range_km,
+                max_range_km: self.max_range_km,
+            });
+        }
+        if height_m < self.min_height_m {
+            return Err(EnvelopeViolation::TooLow {
+                weapon_type,
+                height_m,
+                min_height_m: self.min_height_m,
+            });
+        }
+        if height_m > self.max_height_m {
+            return Err(EnvelopeViolation::TooHigh {
+                weapon_type,
+                height_m,
+                max_height_m: self.max_height_m,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// How an engagement falls outside its weapon's envelope
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EnvelopeViolation {
+    TooClose { weapon_type: WeaponType, range_km: Km, min_range_km: Km },
+    OutOfRange { weapon_type: WeaponType, range_km: Km, max_range_km: Km },
+    TooLow { weapon_type: WeaponType, height_m: Meters, min_height_m: Meters },
+    TooHigh { weapon_type: WeaponType, height_m: Meters, max_height_m: Meters },
+}
+
+impl EnvelopeViolation {
+    #[must_use]
+    pub fn weapon_type(&self) -> WeaponType {
+        match self {
+            Self::TooClose { weapon_type, .. }
+            | Self::OutOfRange { weapon_type, .. }
+            | Self::TooLow { weapon_type, .. }
+            | Self::TooHigh { weapon_type, .. } => *weapon_type,
+        }
+    }
+}
+
+impl fmt::Display for EnvelopeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let weapon = self.weapon_type().as_str();
+        match self {
+            Self::TooClose { range_km, min_range_km, .. } => write!(
+                f,
+                "{weapon} released at {range_km:.1}, inside its {min_range_km:.1} minimum range"
+            ),
+            Self::OutOfRange { range_km, max_range_km, .. } => write!(
+                f,
+                "{weapon} released at {range_km:.1}, beyond its {max_range_km:.1} maximum range"
+            ),
+            Self::TooLow { height_m, min_height_m, .. } => write!(
+                f,
+                "{weapon} released {height_m:.0} above the target, below its \
+                 {min_height_m:.0} minimum"
+            ),
+            Self::TooHigh { height_m, max_height_m, .. } => write!(
+                f,
+                "{weapon} released {height_m:.0} above the target, above its \
+                 {max_height_m:.0} maximum"
+            ),
+        }
+    }
+}
+
+/// Capabilities per platform type and envelopes per weapon type, falling
+/// back to [`PlatformCapabilities::builtin`] and [`WeaponEnvelope::builtin`]
+#[derive(Debug, Clone, Default)]
+pub struct CapabilityRegistry {
+    overrides: HashMap<PlatformType, PlatformCapabilities>,
+    envelopes: HashMap<WeaponType, WeaponEnvelope>,
+}
+
+impl CapabilityRegistry {
+    /// Replace the built-in capabilities of each overridden platform type
+    #[must_use]
+    pub fn with_overrides(
+        mut self,
+        overrides: impl IntoIterator<Item = PlatformCapabilities>,
+    ) -> Self {
+        self.overrides.extend(overrides.into_iter().map(|c| (c.platform_type, c)));
+        self
+    }
+
+    /// Replace the built-in envelope of each overridden weapon type
+    #[must_use]
+    pub fn with_envelope_overrides(
+        mut self,
+        overrides: impl IntoIterator<Item = WeaponEnvelope>,
+    ) -> Self {
+        self.envelopes.extend(overrides.into_iter().map(|e| (e.weapon_type, e)));
+        self
+    }
+
+    #[must_use]
+    pub fn get(&self, platform_type: PlatformType) -> PlatformCapabilities {
+        self.overrides
+            .get(&platform_type)
+            .cloned()
+            .unwrap_or_else(|| PlatformCapabilities::builtin(platform_type))
+    }
+
+    /// Check a loadout fits `platform_type` (see
+    /// [`PlatformCapabilities::check_loadout`])
+    pub fn check_loadout(
+        &self,
+        platform_type: PlatformType,
+        weapons: &[WeaponStatus],
+    ) -> Result<(), DomainError> {
+        self.get(platform_type).check_loadout(weapons)
+    }
+
+    #[must_use]
+    pub fn envelope(&self, weapon_type: WeaponType) -> WeaponEnvelope {
+        self.envelopes
+            .get(&weapon_type)
+            .cloned()
+            .unwrap_or_else(|| WeaponEnvelope::builtin(weapon_type))
+    }
+
+    /// Check an engagement against `weapon_type`'s envelope (see
+    /// [`WeaponEnvelope::check`])
+    pub fn check_engagement(
+        &self,
+        weapon_type: WeaponType,
+        range_km: Km,
+        height_m: Meters,
+    ) -> Result<(), EnvelopeViolation> {
+        self.envelope(weapon_type).check(range_km, height_m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::platform_loadout;
+    use crate::WeaponState;
+
+    const ALL: [PlatformType; 4] = [
+        PlatformType::Mq9Reaper,
+        PlatformType::Mq1cGrayEagle,
+        PlatformType::Rq4GlobalHawk,
+        PlatformType::Mq25Stingray,
+    ];
+
+    fn store(weapon_type: WeaponType, rounds_remaining: i16) -> WeaponStatus {
+        WeaponStatus {
+            weapon_type,
+            rounds_remaining,
+            status: WeaponState::Armed,
+        }
+    }
+
+    #[test]
+    fn test_default_loadouts_fit_their_platforms() {
+        let registry = CapabilityRegistry::default();
+        for platform in ALL {
+            assert_eq!(PlatformType::parse(platform.as_str()), Some(platform));
+            assert!(registry.check_loadout(platform, &platform_loadout(platform)).is_ok());
+        }
+        let reaper = registry.get(PlatformType::Mq9Reaper);
+        assert!((reaper.endurance_hrs - 24.9).abs() < 0.1);
+        assert_eq!(reaper.max_altitude_m, Meters(15_240.0));
+    }
+
+    #[test]
+    fn test_incompatible_or_oversized_loadouts_are_rejected() {
+        let registry = CapabilityRegistry::default();
+        let griffin = [store(WeaponType::Agm176Griffin, 2)];
+        assert!(registry.check_loadout(PlatformType::Mq9Reaper, &griffin).is_err());
+        assert!(registry.check_loadout(PlatformType::Mq1cGrayEagle, &griffin).is_ok());
+
+        let heavy = [store(WeaponType::Agm114Hellfire, 6), store(WeaponType::Gbu12Paveway, 4)];
+        let err = registry.check_loadout(PlatformType::Mq9Reaper, &heavy).unwrap_err();
+        assert!(err.to_string().contains("at most 8 rounds"));
+
+        let hellfire = [store(WeaponType::Agm114Hellfire, 1)];
+        assert!(registry.check_loadout(PlatformType::Rq4GlobalHawk, &hellfire).is_err());
+        let cleared = [store(WeaponType::Agm114Hellfire, 0)];
+        assert!(registry.check_loadout(PlatformType::Rq4GlobalHawk, &cleared).is_ok());
+    }
+
+    #[test]
+    fn test_overrides_replace_builtin_capabilities() {
+        let mut armed_hawk = PlatformCapabilities::builtin(PlatformType::Rq4GlobalHawk);
+        armed_hawk.compatible_weapons = vec![WeaponType::Aim9xSidewinder];
+        armed_hawk.max_rounds = 2;
+        let registry = CapabilityRegistry::default().with_overrides([armed_hawk.clone()]);
+
+        assert_eq!(registry.get(PlatformType::Rq4GlobalHawk), armed_hawk);
+        let sidewinders = [store(WeaponType::Aim9xSidewinder, 2)];
+        assert!(registry.check_loadout(PlatformType::Rq4GlobalHawk, &sidewinders).is_ok());
+        assert_eq!(
+            registry.get(PlatformType::Mq9Reaper),
+            PlatformCapabilities::builtin(PlatformType::Mq9Reaper)
+        );
+    }
+
+    #[test]
+    fn test_engagements_outside_the_envelope_are_caught() {
+        let registry = CapabilityRegistry::default();
+        let hellfire = WeaponType::Agm114Hellfire;
+        assert!(registry.check_engagement(hellfire, Km(8.0), Meters(3_000.0)).is_ok());
+
+        let err = registry.check_engagement(hellfire, Km(80.0), Meters(3_000.0)).unwrap_err();
+        assert!(matches!(err, EnvelopeViolation::OutOfRange { .. }));
+        assert_eq!(
+            err.to_string(),
+            "AGM-114_HELLFIRE released at 80.0 km, beyond its 11.0 km maximum range"
+        );
+        assert!(matches!(
+            registry.check_engagement(hellfire, Km(0.2), Meters(3_000.0)),
+            Err(EnvelopeViolation::TooClose { .. })
+        ));
+        assert!(matches!(
+            registry.check_engagement(WeaponType::Gbu12Paveway, Km(5.0), Meters(100.0)),
+            Err(EnvelopeViolation::TooLow { .. })
+        ));
+
+        let mut extended = WeaponEnvelope::builtin(hellfire);
+        extended.max_range_km = Km(100.0);
+        let registry = registry.with_envelope_overrides([extended]);
+        assert!(registry.check_engagement(hellfire, Km(80.0), Meters(3_000.0)).is_ok());
+    }
+
+    #[test]
+    fn test_capabilities_round_trip_as_json() {
+        let json = serde_json::to_string(&PlatformCapabilities::builtin(PlatformType::Mq9Reaper))
+            .unwrap();
+        assert!(json.contains("\"platform_type\":\"MQ9_REAPER\""));
+        assert!(json.contains("\"AGM114_HELLFIRE\""));
+        let parsed: PlatformCapabilities = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.platform_type, PlatformType::Mq9Reaper);
+    }
+}
@@ -0,0 +1,345 @@
+//! Flight path deconfliction.
+//!
+//! Projects each drone along its upcoming waypoint legs, or straight ahead
+//! on its current heading when no route is known, and finds drone pairs
+//! whose closest point of approach (CPA) infringes the separation minimum.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::formation::KM_PER_DEG_LAT;
+use crate::{Coordinates, Km, Meters, Mps};
+
+/// Below this airspeed a drone is treated as holding position
+const MIN_MOVING_SPEED: Mps = Mps(0.5);
+
+/// Required separation between drones
+///
+/// A conflict needs both the horizontal and the vertical minimum to be
+/// infringed at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SeparationMinimum {
+    pub horizontal_km: Km,
+    pub vertical_m: Meters,
+    /// How far ahead paths are projected
+    pub lookahead_secs: f64,
+}
+
+impl Default for SeparationMinimum {
+    fn default() -> Self {
+        Self {
+            horizontal_km: Km(1.0),
+            vertical_m: Meters(150.0),
+            lookahead_secs: 600.0,
+        }
+    }
+}
+
+/// Current state and remaining route of a drone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlightPath {
+    pub drone_id: Uuid,
+    /// Position with heading and speed
+    pub position: Coordinates,
+    /// Waypoints still to be flown, in order
+    pub upcoming_waypoints: Vec<Coordinates>,
+}
+
+/// Predicted loss of separation between two drones
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PredictedConflict {
+    pub drone_a: Uuid,
+    pub drone_b: Uuid,
+    /// Seconds from now to the closest point of approach
+    pub time_to_cpa_secs: f64,
+    pub horizontal_km: Km,
+    pub vertical_m: Meters,
+    pub position_a: Coordinates,
+    pub position_b: Coordinates,
+}
+
+/// Point on a projected path, in a local east/north plane
+#[derive(Debug, Clone, Copy)]
+struct PathPoint {
+    t: f64,
+    east_km: f64,
+    north_km: f64,
+    altitude: Meters,
+}
+
+impl PathPoint {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let span = other.t - self.t;
+        let f = if span > 0.0 {
+            ((t - self.t) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Self {
+            t,
+            east_km: self.east_km + (other.east_km - self.east_km) * f,
+            north_km: self.north_km + (other.north_km - self.north_km) * f,
+            altitude: self.altitude + (other.altitude - self.altitude) * f,
+        }
+    }
+}
+
+/// Local tangent plane centred on a reference point
+#[derive(Debug, Clone, Copy)]
+struct LocalFrame {
+    origin: Coordinates,
+    km_per_deg_lon: f64,
+}
+
+impl LocalFrame {
+    fn new(origin: Coordinates) -> Self {
+        Self {
+            origin,
+            km_per_deg_lon: KM_PER_DEG_LAT * origin.latitude.to_radians().cos(),
+        }
+    }
+
+    fn project(&self, t: f64, c: &Coordinates) -> PathPoint {
+        PathPoint {
+            t,
+            east_km: (c.longitude - self.origin.longitude) * self.km_per_deg_lon,
+            north_km: (c.latitude - self.origin.latitude) * KM_PER_DEG_LAT,
+            altitude: c.altitude(),
+        }
+    }
+
+    fn unproject(&self, p: &PathPoint) -> Coordinates {
+        Coordinates::new(
+            self.origin.latitude + p.north_km / KM_PER_DEG_LAT,
+            self.origin.longitude + p.east_km / self.km_per_deg_lon,
+            p.altitude.0,
+        )
+    }
+}
+
+/// Find drone pairs predicted to lose separation within the lookahead.
+///
+/// Each drone flies its waypoint legs at its current speed and holds at the
+/// last one; without waypoints it continues on its current heading. Results
+/// are ordered by time to CPA.
+#[must_use]
+pub fn predict_conflicts(
+    paths: &[FlightPath],
+    minimum: SeparationMinimum,
+) -> Vec<PredictedConflict> {
+    let Some(first) = paths.first() else {
+        return Vec::new();
+    };
+    let frame = LocalFrame::new(first.position);
+    let projected: Vec<Vec<PathPoint>> = paths
+        .iter()
+        .map(|p| project_path(&frame, p, minimum.lookahead_secs))
+        .collect();
+
+    let mut conflicts = Vec::new();
+    for i in 0..paths.len() {
+        for j in (i + 1)..paths.len() {
+            if let Some((a, b)) = closest_conflict(&projected[i], &projected[j], minimum) {
+                conflicts.push(PredictedConflict {
+                    drone_a: paths[i].drone_id,
+                    drone_b: paths[j].drone_id,
+                    time_to_cpa_secs: a.t,
+                    horizontal_km: horizontal_km(&a, &b),
+                    vertical_m: (a.altitude - b.altitude).abs(),
+                    position_a: frame.unproject(&a),
+                    position_b: frame.unproject(&b),
+                });
+            }
+        }
+    }
+
+    conflicts.sort_by(|a, b| a.time_to_cpa_secs.total_cmp(&b.time_to_cpa_secs));
+    conflicts
+}
+
+/// Timed points along a drone's projected path, ending at the lookahead
+fn project_path(frame: &LocalFrame, path: &FlightPath, lookahead_secs: f64) -> Vec<PathPoint> {
+    let start = frame.project(0.0, &path.position);
+    let speed = path.position.speed();
+    if speed < MIN_MOVING_SPEED {
+        return vec![start];
+    }
+    let speed_km_per_sec = Km::from(speed.distance_in(1.0)).0;
+
+    let mut points = vec![start];
+    if path.upcoming_waypoints.is_empty() {
+        let heading = f64::from(path.position.heading_deg).to_radians();
+        let reach_km = speed_km_per_sec * lookahead_secs;
+        points.push(PathPoint {
+            t: lookahead_secs,
+            east_km: start.east_km + reach_km * heading.sin(),
+            north_km: start.north_km + reach_km * heading.cos(),
+            altitude: start.altitude,
+        });
+        return points;
+    }
+
+    for waypoint in &path.upcoming_waypoints {
+        let previous = points[points.len() - 1];
+        let mut next = frame.project(0.0, waypoint);
+        next.t = previous.t + horizontal_km(&previous, &next).0 / speed_km_per_sec;
+        if next.t >= lookahead_secs {
+            points.push(previous.lerp(&next, lookahead_secs));
+            break;
+        }
+        points.push(next);
+    }
+    points
+}
+
+/// Position on a projected path at time `t`; holds at either end
+fn position_at(points: &[PathPoint], t: f64) -> PathPoint {
+    let last = points[points.len() - 1];
+    if t >= last.t {
+        return PathPoint { t, ..last };
+    }
+    points
+        .windows(2)
+        .find(|w| t <= w[1].t)
+        .map_or(PathPoint { t, ..points[0] }, |w| w[0].lerp(&w[1], t))
+}
+
+/// Closest point of approach among the moments both minima are infringed
+fn closest_conflict(
+    a: &[PathPoint],
+    b: &[PathPoint],
+    minimum: SeparationMinimum,
+) -> Option<(PathPoint, PathPoint)> {
+    // Both drones fly straight between consecutive breakpoints
+    let mut times: Vec<f64> = a.iter().chain(b).map(|p| p.t).collect();
+    times.push(0.0);
+    times.push(minimum.lookahead_secs);
+    times.retain(|t| (0.0..=minimum.lookahead_secs).contains(t));
+    times.sort_by(f64::total_cmp);
+    times.dedup();
+
+    let mut best: Option<(PathPoint, PathPoint)> = None;
+    let mut consider = |t: f64| {
+        let (pa, pb) = (position_at(a, t), position_at(b, t));
+        let horizontal = horizontal_km(&pa, &pb);
+        let conflicting = horizontal < minimum.horizontal_km
+            && (pa.altitude - pb.altitude).abs() < minimum.vertical_m;
+        if conflicting && best.is_none_or(|(ba, bb)| horizontal < horizontal_km(&ba, &bb)) {
+            best = Some((pa, pb));
+        }
+    };
+
+    consider(0.0);
+    for w in times.windows(2) {
+        let (t0, t1) = (w[0], w[1]);
+        let (a0, a1) = (position_at(a, t0), position_at(a, t1));
+        let (b0, b1) = (position_at(b, t0), position_at(b, t1));
+
+        // Relative position at t0 and its change over the interval
+        let (rx, ry) = (b0.east_km - a0.east_km, b0.north_km - a0.north_km);
+        let (dx, dy) = (
+            (b1.east_km - a1.east_km) - rx,
+            (b1.north_km - a1.north_km) - ry,
+        );
+        let closing = dx * dx + dy * dy;
+        let s = if closing > f64::EPSILON {
+            (-(rx * dx + ry * dy) / closing).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        consider(t0 + s * (t1 - t0));
+        consider(t1);
+    }
+
+    best
+}
+
+fn horizontal_km(a: &PathPoint, b: &PathPoint) -> Km {
+    Km((a.east_km - b.east_km).hypot(a.north_km - b.north_km))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moving(lat: f64, lon: f64, alt: f64, heading: f32, speed: f32) -> Coordinates {
+        let mut c = Coordinates::new(lat, lon, alt);
+        c.heading_deg = heading;
+        c.speed_mps = speed;
+        c
+    }
+
+    #[test]
+    fn test_head_on_trajectories_conflict() {
+        // ~11 km apart on the same meridian, closing at 200 m/s
+        let paths = vec![
+            FlightPath {
+                drone_id: Uuid::new_v4(),
+                position: moving(31.50, 65.80, 3000.0, 0.0, 100.0),
+                upcoming_waypoints: vec![],
+            },
+            FlightPath {
+                drone_id: Uuid::new_v4(),
+                position: moving(31.60, 65.80, 3050.0, 180.0, 100.0),
+                upcoming_waypoints: vec![],
+            },
+        ];
+
+        let conflicts = predict_conflicts(&paths, SeparationMinimum::default());
+        assert_eq!(conflicts.len(), 1);
+        let conflict = conflicts[0];
+        assert!(conflict.horizontal_km < Km(0.01));
+        assert!((conflict.vertical_m.0 - 50.0).abs() < 1e-6);
+        assert!((conflict.time_to_cpa_secs - 55.66).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_vertical_separation_prevents_conflict() {
+        let paths = vec![
+            FlightPath {
+                drone_id: Uuid::new_v4(),
+                position: moving(31.50, 65.80, 3000.0, 0.0, 100.0),
+                upcoming_waypoints: vec![],
+            },
+            FlightPath {
+                drone_id: Uuid::new_v4(),
+                position: moving(31.60, 65.80, 3500.0, 180.0, 100.0),
+                upcoming_waypoints: vec![],
+            },
+        ];
+
+        assert!(predict_conflicts(&paths, SeparationMinimum::default()).is_empty());
+    }
+
+    #[test]
+    fn test_waypoint_legs_override_heading() {
+        // Drone B heads away, but its route turns back across A's track
+        let crossing = Coordinates::new(31.55, 65.80, 3000.0);
+        let paths = vec![
+            FlightPath {
+                drone_id: Uuid::new_v4(),
+                position: moving(31.50, 65.80, 3000.0, 0.0, 100.0),
+                upcoming_waypoints: vec![crossing, Coordinates::new(31.70, 65.80, 3000.0)],
+            },
+            FlightPath {
+                drone_id: Uuid::new_v4(),
+                position: moving(31.55, 65.7412, 3000.0, 270.0, 100.0),
+                upcoming_waypoints: vec![crossing, Coordinates::new(31.55, 65.90, 3000.0)],
+            },
+        ];
+
+        let conflicts = predict_conflicts(&paths, SeparationMinimum::default());
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].horizontal_km < Km(0.2));
+
+        let heading_only: Vec<FlightPath> = paths
+            .into_iter()
+            .map(|p| FlightPath {
+                upcoming_waypoints: vec![],
+                ..p
+            })
+            .collect();
+        assert!(predict_conflicts(&heading_only, SeparationMinimum::default()).is_empty());
+    }
+}
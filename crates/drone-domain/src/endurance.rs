@@ -0,0 +1,196 @@
+//! Fuel burn and endurance estimation.
+//!
+//! Estimates fuel burn from platform, airspeed and altitude, and compares
+//! the remaining endurance with the time needed to fly home.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Coordinates, Km, Meters, Mps, Percent, PlatformType};
+
+/// Burn rate multiplier with the engine at loiter power (zero airspeed)
+const LOITER_BURN_FACTOR: f64 = 0.6;
+
+/// Extra burn at sea level relative to the service ceiling (denser air)
+const SEA_LEVEL_BURN_PENALTY: f64 = 0.2;
+
+/// Fuel characteristics of a platform
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FuelProfile {
+    /// Internal fuel capacity
+    pub capacity_kg: f64,
+    /// Burn at cruise speed and service ceiling
+    pub cruise_burn_kg_per_hr: f64,
+    pub cruise_speed_mps: Mps,
+    pub service_ceiling_m: Meters,
+    /// Fuel held back for landing, as a fraction of capacity
+    pub reserve_fraction: f64,
+}
+
+impl FuelProfile {
+    /// Nominal profile for a platform type
+    #[must_use]
+    pub fn for_platform(platform: PlatformType) -> Self {
+        match platform {
+            PlatformType::Mq9Reaper => Self {
+                capacity_kg: 1800.0,
+                cruise_burn_kg_per_hr: 65.0,
+                cruise_speed_mps: Mps(87.0),
+                service_ceiling_m: Meters(15_240.0),
+                reserve_fraction: 0.1,
+            },
+            PlatformType::Mq1cGrayEagle => Self {
+                capacity_kg: 260.0,
+                cruise_burn_kg_per_hr: 10.0,
+                cruise_speed_mps: Mps(77.0),
+                service_ceiling_m: Meters(8_840.0),
+                reserve_fraction: 0.1,
+            },
+            PlatformType::Rq4GlobalHawk => Self {
+                capacity_kg: 7850.0,
+                cruise_burn_kg_per_hr: 240.0,
+                cruise_speed_mps: Mps(160.0),
+                service_ceiling_m: Meters(18_290.0),
+                reserve_fraction: 0.08,
+            },
+            PlatformType::Mq25Stingray => Self {
+                capacity_kg: 7000.0,
+                cruise_burn_kg_per_hr: 550.0,
+                cruise_speed_mps: Mps(150.0),
+                service_ceiling_m: Meters(12_190.0),
+                reserve_fraction: 0.1,
+            },
+        }
+    }
+
+    /// Fuel burn at the given airspeed and altitude.
+    ///
+    /// Burn grows with the square of airspeed above a loiter floor and is
+    /// higher in the denser air below the service ceiling.
+    #[must_use]
+    pub fn burn_rate_kg_per_hr(&self, speed: Mps, altitude: Meters) -> f64 {
+        let speed_ratio = f64::from(speed / self.cruise_speed_mps).clamp(0.0, 2.0);
+        let speed_factor = LOITER_BURN_FACTOR + (1.0 - LOITER_BURN_FACTOR) * speed_ratio.powi(2);
+
+        let altitude_ratio = (altitude / self.service_ceiling_m).clamp(0.0, 1.0);
+        let altitude_factor = 1.0 + SEA_LEVEL_BURN_PENALTY * (1.0 - altitude_ratio);
+
+        self.cruise_burn_kg_per_hr * speed_factor * altitude_factor
+    }
+}
+
+/// Remaining endurance for a drone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnduranceEstimate {
+    pub drone_id: Uuid,
+    pub platform_type: PlatformType,
+    pub estimated_at: DateTime<Utc>,
+    pub fuel_remaining_kg: f64,
+    pub burn_rate_kg_per_hr: f64,
+    /// Minutes of flight left before reaching the landing reserve
+    pub endurance_min: f64,
+    pub home: Coordinates,
+    pub distance_to_home_km: Km,
+    /// Minutes to fly home at the current speed (or cruise when slower)
+    pub time_to_home_min: f64,
+    /// Minutes until the drone must turn for home
+    pub bingo_min: f64,
+    pub bingo_at: DateTime<Utc>,
+    /// Endurance no longer covers the flight home
+    pub rtb_recommended: bool,
+}
+
+impl EnduranceEstimate {
+    /// Estimate endurance from a telemetry point.
+    #[must_use]
+    pub fn compute(
+        drone_id: Uuid,
+        platform_type: PlatformType,
+        fuel_remaining: Percent,
+        position: &Coordinates,
+        home: Coordinates,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let profile = FuelProfile::for_platform(platform_type);
+        let speed = position.speed();
+
+        let fuel_remaining_kg = profile.capacity_kg * fuel_remaining.clamped().fraction();
+        let usable_kg =
+            (fuel_remaining_kg - profile.capacity_kg * profile.reserve_fraction).max(0.0);
+        let burn_rate_kg_per_hr = profile.burn_rate_kg_per_hr(speed, position.altitude());
+        let endurance_min = usable_kg / burn_rate_kg_per_hr * 60.0;
+
+        // Transit home is flown at no less than cruise speed
+        let distance_to_home_km = position.distance_to_km(&home);
+        let transit_speed = speed.max(profile.cruise_speed_mps);
+        let time_to_home_min = transit_speed
+            .seconds_to_cover(distance_to_home_km)
+            .unwrap_or(f64::INFINITY)
+            / 60.0;
+
+        let bingo_min = (endurance_min - time_to_home_min).max(0.0);
+
+        Self {
+            drone_id,
+            platform_type,
+            estimated_at: now,
+            fuel_remaining_kg,
+            burn_rate_kg_per_hr,
+            endurance_min,
+            home,
+            distance_to_home_km,
+            time_to_home_min,
+            bingo_min,
+            bingo_at: now + Duration::seconds((bingo_min * 60.0) as i64),
+            rtb_recommended: endurance_min <= time_to_home_min,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burn_rate_rises_with_speed_and_low_altitude() {
+        let profile = FuelProfile::for_platform(PlatformType::Mq9Reaper);
+        let cruise =
+            profile.burn_rate_kg_per_hr(profile.cruise_speed_mps, profile.service_ceiling_m);
+
+        assert!((cruise - profile.cruise_burn_kg_per_hr).abs() < 1e-9);
+        assert!(profile.burn_rate_kg_per_hr(Mps::ZERO, profile.service_ceiling_m) < cruise);
+        assert!(profile.burn_rate_kg_per_hr(profile.cruise_speed_mps, Meters::ZERO) > cruise);
+    }
+
+    #[test]
+    fn test_rtb_recommended_when_home_out_of_reach() {
+        let home = Coordinates::new(31.50, 65.85, 1000.0);
+        let mut position = Coordinates::new(31.60, 65.70, 7000.0);
+        position.speed_mps = 87.0;
+        let now = Utc::now();
+
+        let near = EnduranceEstimate::compute(
+            Uuid::new_v4(),
+            PlatformType::Mq9Reaper,
+            Percent(80.0),
+            &position,
+            home,
+            now,
+        );
+        assert!(!near.rtb_recommended);
+        assert!(near.bingo_min > 0.0 && near.bingo_at > now);
+
+        // Reserve alone left: no usable fuel, so any distance is too far
+        let dry = EnduranceEstimate::compute(
+            Uuid::new_v4(),
+            PlatformType::Mq9Reaper,
+            Percent(10.0),
+            &position,
+            home,
+            now,
+        );
+        assert!(dry.rtb_recommended);
+        assert_eq!(dry.bingo_min, 0.0);
+    }
+}
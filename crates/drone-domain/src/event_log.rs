@@ -0,0 +1,432 @@
+//! Append-only engagement event log and its projections.
+//!
+//! With event sourcing enabled, every engagement, BDA update and correction
+//! is appended to a per-convoy log. Leaderboard entries and per-drone
+//! engagement stats are folds over that log, so a bad in-place update can be
+//! repaired by appending a correction and replaying.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{AccuracyStats, DamageAssessment, LeaderboardEntry, PlatformType, ScoringModel, WeaponType};
+
+/// Event type, as stored alongside the serialized payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EngagementLogKind {
+    EngagementRecorded,
+    BdaUpdated,
+    CorrectionApplied,
+}
+
+impl EngagementLogKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::EngagementRecorded => "ENGAGEMENT_RECORDED",
+            Self::BdaUpdated => "BDA_UPDATED",
+            Self::CorrectionApplied => "CORRECTION_APPLIED",
+        }
+    }
+}
+
+/// What happened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EngagementLogPayload {
+    /// A drone fired; counts towards its accuracy
+    EngagementRecorded {
+        engagement_id: Uuid,
+        callsign: String,
+        platform_type: PlatformType,
+        weapon_type: Option<WeaponType>,
+        hit: bool,
+        /// Pre-shot probability of kill, kept for calibration
+        #[serde(default)]
+        predicted_pk: Option<f32>,
+    },
+    /// Battle damage assessment for an earlier engagement
+    BdaUpdated {
+        engagement_id: Uuid,
+        assessment: DamageAssessment,
+    },
+    /// An earlier engagement's hit/miss was recorded wrongly
+    CorrectionApplied {
+        engagement_id: Uuid,
+        hit: bool,
+        reason: String,
+        applied_by: String,
+    },
+}
+
+impl EngagementLogPayload {
+    #[must_use]
+    pub fn kind(&self) -> EngagementLogKind {
+        match self {
+            Self::EngagementRecorded { .. } => EngagementLogKind::EngagementRecorded,
+            Self::BdaUpdated { .. } => EngagementLogKind::BdaUpdated,
+            Self::CorrectionApplied { .. } => EngagementLogKind::CorrectionApplied,
+        }
+    }
+}
+
+/// One entry in a convoy's engagement event log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngagementLogEvent {
+    pub convoy_id: Uuid,
+    pub event_id: Uuid,
+    pub recorded_at: DateTime<Utc>,
+    pub drone_id: Uuid,
+    pub payload: EngagementLogPayload,
+}
+
+impl EngagementLogEvent {
+    /// Event stamped now with a fresh ID
+    #[must_use]
+    pub fn new(convoy_id: Uuid, drone_id: Uuid, payload: EngagementLogPayload) -> Self {
+        Self {
+            convoy_id,
+            event_id: Uuid::new_v4(),
+            recorded_at: Utc::now(),
+            drone_id,
+            payload,
+        }
+    }
+}
+
+/// Per-drone engagement statistics derived from the log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DroneEngagementStats {
+    pub drone_id: Uuid,
+    pub callsign: String,
+    pub platform_type: PlatformType,
+    pub accuracy: AccuracyStats,
+    /// Hits assessed as destroyed
+    pub destroyed: i32,
+    /// Hits assessed as damaged
+    pub damaged: i32,
+    /// Hits still awaiting BDA
+    pub pending_bda: i32,
+    pub last_engaged_at: DateTime<Utc>,
+}
+
+/// One engagement as the projection currently sees it
+#[derive(Debug, Clone)]
+struct ProjectedEngagement {
+    engagement_id: Uuid,
+    hit: bool,
+    assessment: Option<DamageAssessment>,
+}
+
+#[derive(Debug, Clone)]
+struct DroneLedger {
+    callsign: String,
+    platform_type: PlatformType,
+    /// In log order; streaks depend on it
+    engagements: Vec<ProjectedEngagement>,
+    last_engaged_at: DateTime<Utc>,
+}
+
+impl DroneLedger {
+    fn engagement_mut(&mut self, engagement_id: Uuid) -> Option<&mut ProjectedEngagement> {
+        self.engagements
+            .iter_mut()
+            .find(|e| e.engagement_id == engagement_id)
+    }
+
+    fn stats(&self, drone_id: Uuid) -> DroneEngagementStats {
+        let mut accuracy = AccuracyStats {
+            total_engagements: 0,
+            successful_hits: 0,
+            current_streak: 0,
+            best_streak: 0,
+        };
+        let (mut destroyed, mut damaged, mut pending_bda) = (0, 0, 0);
+
+        for engagement in &self.engagements {
+            accuracy.total_engagements += 1;
+            if engagement.hit {
+                accuracy.successful_hits += 1;
+                accuracy.current_streak += 1;
+                accuracy.best_streak = accuracy.best_streak.max(accuracy.current_streak);
+                match engagement.assessment {
+                    Some(DamageAssessment::Destroyed) => destroyed += 1,
+                    Some(DamageAssessment::Damaged) => damaged += 1,
+                    Some(DamageAssessment::Missed) => {}
+                    Some(DamageAssessment::PendingBda) | None => pending_bda += 1,
+                }
+            } else {
+                accuracy.current_streak = 0;
+            }
+        }
+
+        DroneEngagementStats {
+            drone_id,
+            callsign: self.callsign.clone(),
+            platform_type: self.platform_type,
+            accuracy,
+            destroyed,
+            damaged,
+            pending_bda,
+            last_engaged_at: self.last_engaged_at,
+        }
+    }
+}
+
+/// Fold of a convoy's engagement log.
+///
+/// Events can be applied incrementally; BDA updates and corrections for
+/// engagements the projection has not seen are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct EngagementProjection {
+    drones: HashMap<Uuid, DroneLedger>,
+    events_applied: usize,
+}
+
+impl EngagementProjection {
+    /// Replay events in log order
+    #[must_use]
+    pub fn replay<'a>(events: impl IntoIterator<Item = &'a EngagementLogEvent>) -> Self {
+        let mut projection = Self::default();
+        for event in events {
+            projection.apply(event);
+        }
+        projection
+    }
+
+    /// Apply the next event in the log
+    pub fn apply(&mut self, event: &EngagementLogEvent) {
+        self.events_applied += 1;
+        match &event.payload {
+            EngagementLogPayload::EngagementRecorded {
+                engagement_id,
+                callsign,
+                platform_type,
+                hit,
+                ..
+            } => {
+                let ledger = self.drones.entry(event.drone_id).or_insert_with(|| DroneLedger {
+                    callsign: callsign.clone(),
+                    platform_type: *platform_type,
+                    engagements: Vec::new(),
+                    last_engaged_at: event.recorded_at,
+                });
+                ledger.callsign.clone_from(callsign);
+                ledger.platform_type = *platform_type;
+                ledger.last_engaged_at = event.recorded_at;
+                ledger.engagements.push(ProjectedEngagement {
+                    engagement_id: *engagement_id,
+                    hit: *hit,
+                    assessment: None,
+                });
+            }
+            EngagementLogPayload::BdaUpdated {
+                engagement_id,
+                assessment,
+            } => {
+                if let Some(engagement) = self
+                    .drones
+                    .get_mut(&event.drone_id)
+                    .and_then(|ledger| ledger.engagement_mut(*engagement_id))
+                {
+                    engagement.assessment = Some(*assessment);
+                }
+            }
+            EngagementLogPayload::CorrectionApplied {
+                engagement_id, hit, ..
+            } => {
+                if let Some(engagement) = self
+                    .drones
+                    .get_mut(&event.drone_id)
+                    .and_then(|ledger| ledger.engagement_mut(*engagement_id))
+                {
+                    engagement.hit = *hit;
+                    if !hit {
+                        engagement.assessment = None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Events applied so far
+    #[must_use]
+    pub fn events_applied(&self) -> usize {
+        self.events_applied
+    }
+
+    /// Whether the log recorded `engagement_id` for `drone_id`
+    #[must_use]
+    pub fn contains(&self, drone_id: Uuid, engagement_id: Uuid) -> bool {
+        self.drones.get(&drone_id).is_some_and(|ledger| {
+            ledger
+                .engagements
+                .iter()
+                .any(|e| e.engagement_id == engagement_id)
+        })
+    }
+
+    /// Stats for every drone that has engaged, by drone ID
+    #[must_use]
+    pub fn drone_stats(&self) -> Vec<DroneEngagementStats> {
+        let mut stats: Vec<_> = self
+            .drones
+            .iter()
+            .map(|(drone_id, ledger)| ledger.stats(*drone_id))
+            .collect();
+        stats.sort_by_key(|s| s.drone_id);
+        stats
+    }
+
+    /// Leaderboard ranked by `model` score, best first.
+    ///
+    /// Ties go to the drone with more engagements, then by drone ID so
+    /// replays always rank identically.
+    #[must_use]
+    pub fn leaderboard(
+        &self,
+        convoy_id: Uuid,
+        model: ScoringModel,
+        updated_at: DateTime<Utc>,
+    ) -> Vec<LeaderboardEntry> {
+        let mut entries: Vec<LeaderboardEntry> = self
+            .drone_stats()
+            .into_iter()
+            .map(|stats| LeaderboardEntry {
+                convoy_id,
+                drone_id: stats.drone_id,
+                callsign: stats.callsign,
+                platform_type: stats.platform_type,
+                accuracy_pct: stats.accuracy.accuracy_pct().into(),
+                total_engagements: i32::try_from(stats.accuracy.total_engagements)
+                    .unwrap_or(i32::MAX),
+                successful_hits: i32::try_from(stats.accuracy.successful_hits).unwrap_or(i32::MAX),
+                current_streak: stats.accuracy.current_streak,
+                best_streak: stats.accuracy.best_streak,
+                score: model.score(
+                    stats.accuracy.successful_hits,
+                    stats.accuracy.total_engagements,
+                ),
+                rank: 0,
+                updated_at,
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(b.total_engagements.cmp(&a.total_engagements))
+                .then(a.drone_id.cmp(&b.drone_id))
+        });
+        for (entry, rank) in entries.iter_mut().zip(1_i16..) {
+            entry.rank = rank;
+        }
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONVOY: Uuid = Uuid::from_u128(1);
+
+    fn recorded(drone: u128, engagement: u128, hit: bool) -> EngagementLogEvent {
+        EngagementLogEvent::new(
+            CONVOY,
+            Uuid::from_u128(drone),
+            EngagementLogPayload::EngagementRecorded {
+                engagement_id: Uuid::from_u128(engagement),
+                callsign: format!("REAPER-{drone:02}"),
+                platform_type: PlatformType::Mq9Reaper,
+                weapon_type: Some(WeaponType::Agm114Hellfire),
+                hit,
+                predicted_pk: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_leaderboard_replays_hits_and_streaks() {
+        let events = [
+            recorded(10, 1, true),
+            recorded(10, 2, true),
+            recorded(10, 3, false),
+            recorded(10, 4, true),
+            recorded(20, 5, false),
+            recorded(20, 6, true),
+        ];
+
+        let projection = EngagementProjection::replay(&events);
+        let board = projection.leaderboard(CONVOY, ScoringModel::Accuracy, Utc::now());
+
+        assert_eq!(projection.events_applied(), 6);
+        assert_eq!(board.len(), 2);
+        let top = &board[0];
+        assert_eq!((top.drone_id, top.rank), (Uuid::from_u128(10), 1));
+        assert_eq!((top.total_engagements, top.successful_hits), (4, 3));
+        assert_eq!((top.current_streak, top.best_streak), (1, 2));
+        assert!((top.accuracy_pct - 75.0).abs() < f32::EPSILON);
+        assert_eq!((board[1].drone_id, board[1].rank), (Uuid::from_u128(20), 2));
+    }
+
+    #[test]
+    fn test_corrections_and_bda_rewrite_history() {
+        let drone = Uuid::from_u128(10);
+        let events = [
+            recorded(10, 1, true),
+            recorded(10, 2, false),
+            recorded(10, 3, true),
+            EngagementLogEvent::new(
+                CONVOY,
+                drone,
+                EngagementLogPayload::BdaUpdated {
+                    engagement_id: Uuid::from_u128(1),
+                    assessment: DamageAssessment::Destroyed,
+                },
+            ),
+            EngagementLogEvent::new(
+                CONVOY,
+                drone,
+                EngagementLogPayload::CorrectionApplied {
+                    engagement_id: Uuid::from_u128(2),
+                    hit: true,
+                    reason: "Sensor replay confirmed impact".to_string(),
+                    applied_by: "cdr".to_string(),
+                },
+            ),
+            // Unknown engagement: ignored
+            EngagementLogEvent::new(
+                CONVOY,
+                drone,
+                EngagementLogPayload::CorrectionApplied {
+                    engagement_id: Uuid::from_u128(99),
+                    hit: false,
+                    reason: String::new(),
+                    applied_by: "cdr".to_string(),
+                },
+            ),
+        ];
+
+        let projection = EngagementProjection::replay(&events);
+        let stats = &projection.drone_stats()[0];
+
+        assert_eq!(stats.accuracy.successful_hits, 3);
+        assert_eq!((stats.accuracy.current_streak, stats.accuracy.best_streak), (3, 3));
+        assert_eq!((stats.destroyed, stats.damaged, stats.pending_bda), (1, 0, 2));
+        assert!(projection.contains(drone, Uuid::from_u128(2)));
+        assert!(!projection.contains(drone, Uuid::from_u128(99)));
+    }
+
+    #[test]
+    fn test_payload_serializes_with_type_tag() {
+        let event = recorded(10, 1, true);
+        let json = serde_json::to_string(&event.payload).unwrap();
+
+        assert!(json.contains(r#""type":"ENGAGEMENT_RECORDED""#));
+        assert_eq!(event.payload.kind().as_str(), "ENGAGEMENT_RECORDED");
+        let decoded: EngagementLogPayload = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, event.payload);
+    }
+}
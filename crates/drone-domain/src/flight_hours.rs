@@ -0,0 +1,184 @@
+//! Flight hours accounting.
+//!
+//! Accrues airborne time from successive telemetry reports and flags the
+//! inspection intervals and sortie length limits a drone crosses.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Longest gap between reports that still counts as continuous flight;
+/// time across a longer link outage is not accrued
+pub const MAX_SAMPLE_GAP_SECS: i64 = 300;
+
+/// Soft limits that raise advisories as flight hours accrue
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FlightHoursLimits {
+    /// Cumulative hours between scheduled inspections
+    pub inspection_interval_hrs: f64,
+    /// Longest single sortie before the drone should be recovered
+    pub sortie_limit_hrs: f64,
+}
+
+impl Default for FlightHoursLimits {
+    fn default() -> Self {
+        Self {
+            inspection_interval_hrs: 50.0,
+            sortie_limit_hrs: 24.0,
+        }
+    }
+}
+
+/// Limit crossed by an accrual
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FlightHoursAdvisory {
+    /// Cumulative hours reached an inspection threshold
+    InspectionDue { threshold_hrs: f64 },
+    /// The current sortie reached the sortie limit
+    SortieLimit { limit_hrs: f64 },
+}
+
+impl FlightHoursLimits {
+    /// Limits crossed going from `before` to `after`
+    #[must_use]
+    pub fn crossed(&self, before: &FlightHours, after: &FlightHours) -> Vec<FlightHoursAdvisory> {
+        let mut advisories = Vec::new();
+        if self.inspection_interval_hrs > 0.0 {
+            let threshold = (after.total_hrs / self.inspection_interval_hrs).floor();
+            if threshold > (before.total_hrs / self.inspection_interval_hrs).floor() {
+                advisories.push(FlightHoursAdvisory::InspectionDue {
+                    threshold_hrs: threshold * self.inspection_interval_hrs,
+                });
+            }
+        }
+        if self.sortie_limit_hrs > 0.0
+            && before.sortie_hrs < self.sortie_limit_hrs
+            && after.sortie_hrs >= self.sortie_limit_hrs
+        {
+            advisories.push(FlightHoursAdvisory::SortieLimit {
+                limit_hrs: self.sortie_limit_hrs,
+            });
+        }
+        advisories
+    }
+
+    /// Cumulative hours at which the next inspection falls due
+    #[must_use]
+    pub fn next_inspection_hrs(&self, total_hrs: f64) -> Option<f64> {
+        (self.inspection_interval_hrs > 0.0).then(|| {
+            ((total_hrs / self.inspection_interval_hrs).floor() + 1.0) * self.inspection_interval_hrs
+        })
+    }
+}
+
+/// Running flight hours for one drone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlightHours {
+    pub drone_id: Uuid,
+    /// Cumulative airborne hours over the airframe's life
+    pub total_hrs: f64,
+    /// Airborne hours since the current sortie began; zero on the ground
+    pub sortie_hrs: f64,
+    /// When the current sortie began
+    pub sortie_started_at: Option<DateTime<Utc>>,
+    /// Time of the last report
+    pub last_sample_at: Option<DateTime<Utc>>,
+    /// Whether the drone was airborne at the last report
+    pub airborne: bool,
+}
+
+impl FlightHours {
+    /// Start accounting from previously recorded hours
+    #[must_use]
+    pub fn new(drone_id: Uuid, total_hrs: f64) -> Self {
+        Self {
+            drone_id,
+            total_hrs,
+            sortie_hrs: 0.0,
+            sortie_started_at: None,
+            last_sample_at: None,
+            airborne: false,
+        }
+    }
+
+    /// Fold in a report, returning the hours accrued
+    ///
+    /// Time counts only when the drone was airborne at both this report and
+    /// the previous one, and the gap between them is at most
+    /// [`MAX_SAMPLE_GAP_SECS`]. Landing ends the sortie.
+    pub fn accrue(&mut self, airborne: bool, at: DateTime<Utc>) -> f64 {
+        let gap_secs = self
+            .last_sample_at
+            .map_or(0, |last| (at - last).num_seconds());
+        let accrued = if self.airborne && airborne && (0..=MAX_SAMPLE_GAP_SECS).contains(&gap_secs) {
+            gap_secs as f64 / 3600.0
+        } else {
+            0.0
+        };
+
+        if airborne {
+            self.sortie_started_at.get_or_insert(at);
+            self.sortie_hrs += accrued;
+            self.total_hrs += accrued;
+        } else {
+            self.sortie_started_at = None;
+            self.sortie_hrs = 0.0;
+        }
+        self.airborne = airborne;
+        self.last_sample_at = Some(self.last_sample_at.map_or(at, |last| last.max(at)));
+        accrued
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_accrual_skips_ground_time_and_outages() {
+        let t0 = Utc::now();
+        let mut hours = FlightHours::new(Uuid::new_v4(), 10.0);
+
+        assert!(hours.accrue(true, t0).abs() < 1e-9);
+        assert!((hours.accrue(true, t0 + Duration::minutes(3)) - 0.05).abs() < 1e-9);
+
+        // A link outage longer than the gap limit is not counted
+        assert!(hours.accrue(true, t0 + Duration::minutes(30)).abs() < 1e-9);
+        assert!((hours.total_hrs - 10.05).abs() < 1e-9);
+        assert!((hours.sortie_hrs - 0.05).abs() < 1e-9);
+        assert_eq!(hours.sortie_started_at, Some(t0));
+
+        hours.accrue(false, t0 + Duration::minutes(33));
+        assert!((hours.total_hrs - 10.05).abs() < 1e-9);
+        assert!(hours.sortie_hrs.abs() < 1e-9);
+        assert!(hours.sortie_started_at.is_none());
+    }
+
+    #[test]
+    fn test_limits_crossed() {
+        let limits = FlightHoursLimits {
+            inspection_interval_hrs: 50.0,
+            sortie_limit_hrs: 2.0,
+        };
+        let before = FlightHours {
+            sortie_hrs: 1.95,
+            ..FlightHours::new(Uuid::new_v4(), 49.95)
+        };
+        let after = FlightHours {
+            total_hrs: 50.0,
+            sortie_hrs: 2.0,
+            ..before.clone()
+        };
+
+        assert_eq!(
+            limits.crossed(&before, &after),
+            vec![
+                FlightHoursAdvisory::InspectionDue { threshold_hrs: 50.0 },
+                FlightHoursAdvisory::SortieLimit { limit_hrs: 2.0 },
+            ]
+        );
+        assert!(limits.crossed(&after, &after).is_empty());
+        assert_eq!(limits.next_inspection_hrs(50.0), Some(100.0));
+    }
+}
@@ -0,0 +1,185 @@
+//! Convoy formation geometry.
+//!
+//! Computes inter-drone spacing, the formation centroid and per-drone
+//! offsets from the latest known positions of a convoy's drones.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Coordinates, Km, Meters};
+
+/// Kilometres per degree of latitude
+pub(crate) const KM_PER_DEG_LAT: f64 = 111.32;
+
+/// Allowed inter-drone spacing for a formation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FormationBounds {
+    /// Minimum separation between any two drones (deconfliction)
+    pub min_spacing_km: Km,
+    /// Maximum separation between any two drones (mutual support)
+    pub max_spacing_km: Km,
+}
+
+impl Default for FormationBounds {
+    fn default() -> Self {
+        Self {
+            min_spacing_km: Km(0.5),
+            max_spacing_km: Km(25.0),
+        }
+    }
+}
+
+/// Position of a drone relative to the formation centroid
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FormationOffset {
+    pub drone_id: Uuid,
+    pub north_km: Km,
+    pub east_km: Km,
+    pub altitude_offset_m: Meters,
+    pub distance_km: Km,
+}
+
+/// Pair of drones whose spacing is outside the formation bounds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpacingViolation {
+    pub drone_a: Uuid,
+    pub drone_b: Uuid,
+    pub distance_km: Km,
+}
+
+/// Formation snapshot for a convoy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Formation {
+    pub centroid: Coordinates,
+    pub offsets: Vec<FormationOffset>,
+    /// Mean distance from the centroid
+    pub spread_km: Km,
+    pub min_spacing_km: Km,
+    pub max_spacing_km: Km,
+    /// Fraction of drone pairs within bounds (0.0 - 1.0)
+    pub integrity: f64,
+    pub violations: Vec<SpacingViolation>,
+}
+
+impl Formation {
+    /// Compute the formation from drone positions.
+    ///
+    /// Returns `None` when no positions are available.
+    #[must_use]
+    pub fn compute(positions: &[(Uuid, Coordinates)], bounds: FormationBounds) -> Option<Self> {
+        if positions.is_empty() {
+            return None;
+        }
+
+        let n = positions.len() as f64;
+        let centroid = Coordinates::new(
+            positions.iter().map(|(_, p)| p.latitude).sum::<f64>() / n,
+            positions.iter().map(|(_, p)| p.longitude).sum::<f64>() / n,
+            positions.iter().map(|(_, p)| p.altitude_m).sum::<f64>() / n,
+        );
+
+        let km_per_deg_lon = KM_PER_DEG_LAT * centroid.latitude.to_radians().cos();
+        let offsets: Vec<FormationOffset> = positions
+            .iter()
+            .map(|(drone_id, p)| FormationOffset {
+                drone_id: *drone_id,
+                north_km: Km((p.latitude - centroid.latitude) * KM_PER_DEG_LAT),
+                east_km: Km((p.longitude - centroid.longitude) * km_per_deg_lon),
+                altitude_offset_m: p.altitude() - centroid.altitude(),
+                distance_km: centroid.distance_to_km(p),
+            })
+            .collect();
+
+        let spread_km = offsets.iter().map(|o| o.distance_km).sum::<Km>() / n;
+
+        let mut min_spacing_km = Km(f64::INFINITY);
+        let mut max_spacing_km = Km::ZERO;
+        let mut pairs = 0_u32;
+        let mut violations = Vec::new();
+
+        for (i, (id_a, a)) in positions.iter().enumerate() {
+            for (id_b, b) in &positions[i + 1..] {
+                let distance_km = a.distance_to_km(b);
+                min_spacing_km = min_spacing_km.min(distance_km);
+                max_spacing_km = max_spacing_km.max(distance_km);
+                pairs += 1;
+
+                if distance_km < bounds.min_spacing_km || distance_km > bounds.max_spacing_km {
+                    violations.push(SpacingViolation {
+                        drone_a: *id_a,
+                        drone_b: *id_b,
+                        distance_km,
+                    });
+                }
+            }
+        }
+
+        // A lone drone is trivially in formation
+        let integrity = if pairs == 0 {
+            min_spacing_km = Km::ZERO;
+            1.0
+        } else {
+            1.0 - violations.len() as f64 / f64::from(pairs)
+        };
+
+        Some(Self {
+            centroid,
+            offsets,
+            spread_km,
+            min_spacing_km,
+            max_spacing_km,
+            integrity,
+            violations,
+        })
+    }
+
+    /// Whether every pair of drones is within bounds
+    #[must_use]
+    pub fn is_intact(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formation_centroid_and_offsets() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let positions = vec![
+            (a, Coordinates::new(31.60, 65.70, 5000.0)),
+            (b, Coordinates::new(31.62, 65.70, 5200.0)),
+        ];
+
+        let formation = Formation::compute(&positions, FormationBounds::default()).unwrap();
+
+        assert!((formation.centroid.latitude - 31.61).abs() < 1e-9);
+        assert!((formation.offsets[0].north_km + formation.offsets[1].north_km).0.abs() < 1e-9);
+        assert!((formation.offsets[1].altitude_offset_m.0 - 100.0).abs() < 1e-9);
+        assert!(formation.is_intact());
+        assert!((formation.integrity - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_formation_spacing_violation() {
+        let positions = vec![
+            (Uuid::new_v4(), Coordinates::new(31.60, 65.70, 5000.0)),
+            (Uuid::new_v4(), Coordinates::new(31.61, 65.70, 5000.0)),
+            (Uuid::new_v4(), Coordinates::new(32.60, 65.70, 5000.0)),
+        ];
+
+        let formation = Formation::compute(&positions, FormationBounds::default()).unwrap();
+
+        // Third drone is ~110 km from the others
+        assert_eq!(formation.violations.len(), 2);
+        assert!(formation.integrity < 0.5);
+        assert!(formation.max_spacing_km > Km(100.0));
+    }
+
+    #[test]
+    fn test_formation_empty() {
+        assert!(Formation::compute(&[], FormationBounds::default()).is_none());
+    }
+}
@@ -0,0 +1,296 @@
+//! Engagement geofencing.
+//!
+//! Checks a target position against the convoy's area of responsibility
+//! (AOR) and its no-strike zones. Zones are simple polygons tested by ray
+//! casting in degrees, which holds for zones that neither cross the
+//! antimeridian nor enclose a pole.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{Convoy, Coordinates, Km};
+
+/// Fewest vertices a zone polygon may have
+pub const MIN_ZONE_VERTICES: usize = 3;
+
+/// Most vertices a zone polygon may have
+pub const MAX_ZONE_VERTICES: usize = 256;
+
+/// A polygon vertex
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// How an engagement targeting inside a zone is handled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ZoneEnforcement {
+    /// The engagement is rejected
+    #[default]
+    Block,
+    /// The engagement is recorded and an alert raised for review
+    Flag,
+}
+
+impl ZoneEnforcement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Block => "BLOCK",
+            Self::Flag => "FLAG",
+        }
+    }
+}
+
+/// Area a convoy must not engage into, such as a hospital or a protected
+/// site
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoStrikeZone {
+    pub convoy_id: Uuid,
+    pub zone_id: Uuid,
+    pub name: String,
+    /// Polygon vertices in order; the ring closes implicitly
+    pub vertices: Vec<GeoPoint>,
+    pub enforcement: ZoneEnforcement,
+    pub notes: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl NoStrikeZone {
+    /// Whether a position lies inside the zone
+    #[must_use]
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        let mut inside = false;
+        let mut prev = match self.vertices.last() {
+            Some(last) => *last,
+            None => return false,
+        };
+        for &vertex in &self.vertices {
+            if (vertex.latitude > latitude) != (prev.latitude > latitude) {
+                let crossing = vertex.longitude
+                    + (latitude - vertex.latitude) / (prev.latitude - vertex.latitude)
+                        * (prev.longitude - vertex.longitude);
+                if longitude < crossing {
+                    inside = !inside;
+                }
+            }
+            prev = vertex;
+        }
+        inside
+    }
+}
+
+/// Check that vertices describe a usable zone polygon
+///
+/// # Errors
+///
+/// Describes the first problem: too few or too many vertices, a vertex
+/// out of range, or a polygon enclosing no area.
+pub fn validate_polygon(vertices: &[GeoPoint]) -> Result<(), String> {
+    if !(MIN_ZONE_VERTICES..=MAX_ZONE_VERTICES).contains(&vertices.len()) {
+        return Err(format!(
+            "a zone needs {MIN_ZONE_VERTICES} to {MAX_ZONE_VERTICES} vertices, got {}",
+            vertices.len()
+        ));
+    }
+    if let Some((i, _)) = vertices.iter().enumerate().find(|(_, v)| {
+        !((-90.0..=90.0).contains(&v.latitude) && (-180.0..=180.0).contains(&v.longitude))
+    }) {
+        return Err(format!("vertex {i} is not a valid latitude and longitude"));
+    }
+    if signed_area(vertices).abs() < f64::EPSILON {
+        return Err("zone vertices enclose no area".to_string());
+    }
+    Ok(())
+}
+
+/// Shoelace area in square degrees; positive when counterclockwise
+#[must_use]
+pub fn signed_area(vertices: &[GeoPoint]) -> f64 {
+    let Some(&last) = vertices.last() else {
+        return 0.0;
+    };
+    let mut prev = last;
+    let mut twice_area = 0.0;
+    for &vertex in vertices {
+        twice_area += prev.longitude * vertex.latitude - vertex.longitude * prev.latitude;
+        prev = vertex;
+    }
+    twice_area / 2.0
+}
+
+/// A geofence a target position breaks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GeoViolation {
+    /// Target lies outside the convoy's AOR
+    OutsideAor { distance_km: Km, radius_km: Km },
+    /// Target lies inside a no-strike zone
+    InNoStrikeZone {
+        zone_id: Uuid,
+        name: String,
+        enforcement: ZoneEnforcement,
+    },
+}
+
+impl GeoViolation {
+    /// Whether the engagement must be rejected rather than flagged
+    #[must_use]
+    pub fn blocks(&self) -> bool {
+        match self {
+            Self::OutsideAor { .. } => true,
+            Self::InNoStrikeZone { enforcement, .. } => *enforcement == ZoneEnforcement::Block,
+        }
+    }
+}
+
+impl fmt::Display for GeoViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutsideAor {
+                distance_km,
+                radius_km,
+            } => write!(
+                f,
+                "target is {distance_km} from the AOR center, outside its {radius_km} radius"
+            ),
+            Self::InNoStrikeZone { name, .. } => {
+                write!(f, "target is inside no-strike zone '{name}'")
+            }
+        }
+    }
+}
+
+/// Every geofence a target position breaks, the AOR first
+///
+/// Convoys without an AOR radius are not bounded.
+#[must_use]
+pub fn check_target(
+    convoy: &Convoy,
+    zones: &[NoStrikeZone],
+    target: &Coordinates,
+) -> Vec<GeoViolation> {
+    let mut violations = Vec::new();
+
+    let radius_km = Km(f64::from(convoy.aor_radius_km));
+    if radius_km > Km::ZERO {
+        let distance_km = convoy.aor_center.distance_to_km(target);
+        if distance_km > radius_km {
+            violations.push(GeoViolation::OutsideAor {
+                distance_km,
+                radius_km,
+            });
+        }
+    }
+
+    violations.extend(
+        zones
+            .iter()
+            .filter(|zone| zone.contains(target.latitude, target.longitude))
+            .map(|zone| GeoViolation::InNoStrikeZone {
+                zone_id: zone.zone_id,
+                name: zone.name.clone(),
+                enforcement: zone.enforcement,
+            }),
+    );
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConvoyTemplate, TemplateKind};
+
+    fn point(latitude: f64, longitude: f64) -> GeoPoint {
+        GeoPoint {
+            latitude,
+            longitude,
+        }
+    }
+
+    fn zone(enforcement: ZoneEnforcement, vertices: Vec<GeoPoint>) -> NoStrikeZone {
+        NoStrikeZone {
+            convoy_id: Uuid::new_v4(),
+            zone_id: Uuid::new_v4(),
+            name: "HOSPITAL".to_string(),
+            vertices,
+            enforcement,
+            notes: None,
+            created_by: "PLANS".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_contains_concave_polygon() {
+        // U shape opening north
+        let u = zone(
+            ZoneEnforcement::Block,
+            vec![
+                point(31.0, 65.0),
+                point(31.0, 65.3),
+                point(31.3, 65.3),
+                point(31.3, 65.2),
+                point(31.1, 65.2),
+                point(31.1, 65.1),
+                point(31.3, 65.1),
+                point(31.3, 65.0),
+            ],
+        );
+
+        assert!(u.contains(31.05, 65.15));
+        assert!(u.contains(31.2, 65.05));
+        assert!(!u.contains(31.2, 65.15));
+        assert!(!u.contains(30.9, 65.15));
+    }
+
+    #[test]
+    fn test_validate_polygon() {
+        let square = [point(0.0, 0.0), point(0.0, 1.0), point(1.0, 1.0), point(1.0, 0.0)];
+        assert!(validate_polygon(&square).is_ok());
+        assert!(signed_area(&square) > 0.0);
+        assert!(validate_polygon(&square[..2]).is_err());
+        assert!(validate_polygon(&[point(0.0, 0.0), point(1.0, 1.0), point(2.0, 2.0)]).is_err());
+        assert!(validate_polygon(&[point(0.0, 0.0), point(95.0, 1.0), point(1.0, 0.0)]).is_err());
+    }
+
+    #[test]
+    fn test_check_target_reports_aor_and_zones() {
+        let mut convoy = ConvoyTemplate::builtin(TemplateKind::Strike4Ship)
+            .provision("PLANS")
+            .convoy;
+        convoy.aor_center = Coordinates::new(31.0, 65.0, 0.0);
+        convoy.aor_radius_km = 25.0;
+        let square = vec![
+            point(31.0, 65.0),
+            point(31.0, 65.1),
+            point(31.1, 65.1),
+            point(31.1, 65.0),
+        ];
+        let zones = [
+            zone(ZoneEnforcement::Flag, square.clone()),
+            zone(ZoneEnforcement::Block, square),
+        ];
+
+        let clear = check_target(&convoy, &zones, &Coordinates::new(30.95, 65.0, 0.0));
+        assert!(clear.is_empty());
+
+        let inside = check_target(&convoy, &zones, &Coordinates::new(31.05, 65.05, 0.0));
+        assert_eq!(inside.len(), 2);
+        assert!(!inside[0].blocks());
+        assert!(inside[1].blocks());
+
+        let outside = check_target(&convoy, &[], &Coordinates::new(32.0, 65.0, 0.0));
+        assert!(matches!(outside[..], [GeoViolation::OutsideAor { .. }]));
+        assert!(outside[0].blocks());
+
+        convoy.aor_radius_km = 0.0;
+        assert!(check_target(&convoy, &[], &Coordinates::new(32.0, 65.0, 0.0)).is_empty());
+    }
+}
@@ -0,0 +1,286 @@
+//! Drone health scoring.
+//!
+//! Folds fuel, datalink quality, sensor availability, engine temperature
+//! and maintenance state into a single 0–100 score, and summarizes a
+//! convoy's scores into a readiness picture.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{DroneStatus, Percent};
+
+/// Factor weights; they sum to 100
+const FUEL_WEIGHT: f64 = 30.0;
+const LINK_WEIGHT: f64 = 25.0;
+const SENSOR_WEIGHT: f64 = 20.0;
+const ENGINE_WEIGHT: f64 = 15.0;
+const MAINTENANCE_WEIGHT: f64 = 10.0;
+
+/// Engine temperature up to which the engine scores full marks
+const ENGINE_NOMINAL_C: f64 = 100.0;
+
+/// Engine temperature at which the engine scores zero
+const ENGINE_LIMIT_C: f64 = 130.0;
+
+/// Highest score a drone in maintenance can have; it is not mission capable
+const MAINTENANCE_SCORE_CAP: u8 = 20;
+
+/// Lowest score counted as GREEN
+const GREEN_MIN_SCORE: u8 = 75;
+
+/// Lowest score counted as AMBER
+const AMBER_MIN_SCORE: u8 = 50;
+
+/// Color band of a health score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HealthBand {
+    /// Mission capable
+    Green,
+    /// Degraded; watch closely
+    Amber,
+    /// Recall or ground
+    Red,
+}
+
+impl HealthBand {
+    /// Band a score falls in
+    #[must_use]
+    pub fn for_score(score: u8) -> Self {
+        if score >= GREEN_MIN_SCORE {
+            Self::Green
+        } else if score >= AMBER_MIN_SCORE {
+            Self::Amber
+        } else {
+            Self::Red
+        }
+    }
+}
+
+/// Readings a health score is computed from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReadings {
+    pub fuel_remaining: Percent,
+    /// Datalink quality, 0.0 - 1.0
+    pub link_quality: f64,
+    /// Operational flag per onboard sensor; empty when none are reported
+    pub sensors_operational: Vec<bool>,
+    pub engine_temp_c: Option<f32>,
+    /// Last known status; `None` when the drone is not registered
+    pub status: Option<DroneStatus>,
+}
+
+/// Per-factor scores, each 0 - 100
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HealthFactors {
+    pub fuel: f64,
+    pub link: f64,
+    pub sensors: f64,
+    pub engine: f64,
+    pub maintenance: f64,
+}
+
+impl HealthFactors {
+    /// Score each factor; unreported sensors and engine temperature are
+    /// not held against the drone
+    #[must_use]
+    pub fn from_readings(readings: &HealthReadings) -> Self {
+        let sensors = if readings.sensors_operational.is_empty() {
+            100.0
+        } else {
+            let up = readings.sensors_operational.iter().filter(|&&ok| ok).count();
+            up as f64 * 100.0 / readings.sensors_operational.len() as f64
+        };
+        let engine = readings.engine_temp_c.map_or(100.0, |t| {
+            let over = (f64::from(t) - ENGINE_NOMINAL_C) / (ENGINE_LIMIT_C - ENGINE_NOMINAL_C);
+            (1.0 - over.clamp(0.0, 1.0)) * 100.0
+        });
+        let maintenance = if readings.status == Some(DroneStatus::Maintenance) {
+            0.0
+        } else {
+            100.0
+        };
+
+        Self {
+            fuel: f64::from(readings.fuel_remaining.clamped().0),
+            link: readings.link_quality.clamp(0.0, 1.0) * 100.0,
+            sensors,
+            engine,
+            maintenance,
+        }
+    }
+
+    /// Weighted overall score, 0 - 100
+    #[must_use]
+    pub fn score(&self) -> u8 {
+        let weighted = (self.fuel * FUEL_WEIGHT
+            + self.link * LINK_WEIGHT
+            + self.sensors * SENSOR_WEIGHT
+            + self.engine * ENGINE_WEIGHT
+            + self.maintenance * MAINTENANCE_WEIGHT)
+            / 100.0;
+        weighted.round().clamp(0.0, 100.0) as u8
+    }
+}
+
+/// Health score for one drone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DroneHealth {
+    pub drone_id: Uuid,
+    pub computed_at: DateTime<Utc>,
+    /// Overall score, 0 - 100
+    pub score: u8,
+    pub band: HealthBand,
+    pub factors: HealthFactors,
+    pub readings: HealthReadings,
+}
+
+impl DroneHealth {
+    /// Score a drone from its latest readings
+    #[must_use]
+    pub fn compute(drone_id: Uuid, readings: HealthReadings, now: DateTime<Utc>) -> Self {
+        let factors = HealthFactors::from_readings(&readings);
+        let score = if readings.status == Some(DroneStatus::Maintenance) {
+            factors.score().min(MAINTENANCE_SCORE_CAP)
+        } else {
+            factors.score()
+        };
+        Self {
+            drone_id,
+            computed_at: now,
+            score,
+            band: HealthBand::for_score(score),
+            factors,
+            readings,
+        }
+    }
+
+    /// Rescore after a status change, keeping the other readings
+    #[must_use]
+    pub fn with_status(self, status: DroneStatus, now: DateTime<Utc>) -> Self {
+        let readings = HealthReadings {
+            status: Some(status),
+            ..self.readings
+        };
+        Self::compute(self.drone_id, readings, now)
+    }
+}
+
+/// Health distribution across a convoy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FleetReadiness {
+    pub convoy_id: Uuid,
+    /// Drones in the GREEN band
+    pub green: usize,
+    /// Drones in the AMBER band
+    pub amber: usize,
+    /// Drones in the RED band
+    pub red: usize,
+    /// Roster drones without a score yet
+    pub unscored: usize,
+    /// Mean score of the scored drones; `None` when none are scored
+    pub average_score: Option<f64>,
+    /// Lowest-scoring drones, worst first
+    pub lowest: Vec<DroneHealth>,
+}
+
+impl FleetReadiness {
+    /// Summarize scored drones, keeping the `lowest_count` worst
+    #[must_use]
+    pub fn compute(
+        convoy_id: Uuid,
+        mut scores: Vec<DroneHealth>,
+        unscored: usize,
+        lowest_count: usize,
+    ) -> Self {
+        let count_band = |band| scores.iter().filter(|h| h.band == band).count();
+        let (green, amber, red) = (
+            count_band(HealthBand::Green),
+            count_band(HealthBand::Amber),
+            count_band(HealthBand::Red),
+        );
+        let average_score = (!scores.is_empty()).then(|| {
+            scores.iter().map(|h| f64::from(h.score)).sum::<f64>() / scores.len() as f64
+        });
+
+        scores.sort_by_key(|h| (h.score, h.drone_id));
+        scores.truncate(lowest_count);
+
+        Self {
+            convoy_id,
+            green,
+            amber,
+            red,
+            unscored,
+            average_score,
+            lowest: scores,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn readings(fuel: f32, link: f64) -> HealthReadings {
+        HealthReadings {
+            fuel_remaining: Percent(fuel),
+            link_quality: link,
+            sensors_operational: Vec::new(),
+            engine_temp_c: None,
+            status: Some(DroneStatus::Airborne),
+        }
+    }
+
+    #[test]
+    fn test_score_weights_and_bands() {
+        let now = Utc::now();
+        let healthy = DroneHealth::compute(Uuid::new_v4(), readings(100.0, 1.0), now);
+        assert_eq!(healthy.score, 100);
+        assert_eq!(healthy.band, HealthBand::Green);
+
+        // Half the sensors down and an engine at its limit
+        let degraded = DroneHealth::compute(
+            Uuid::new_v4(),
+            HealthReadings {
+                sensors_operational: vec![true, false],
+                engine_temp_c: Some(130.0),
+                ..readings(100.0, 1.0)
+            },
+            now,
+        );
+        assert_eq!(degraded.score, 75);
+        assert_eq!(degraded.band, HealthBand::Green);
+
+        let low_fuel = DroneHealth::compute(Uuid::new_v4(), readings(10.0, 0.5), now);
+        assert_eq!(low_fuel.score, 61);
+        assert_eq!(low_fuel.band, HealthBand::Amber);
+
+        let grounded = healthy.with_status(DroneStatus::Maintenance, now);
+        assert_eq!(grounded.score, 20);
+        assert_eq!(grounded.band, HealthBand::Red);
+    }
+
+    #[test]
+    fn test_fleet_readiness() {
+        let now = Utc::now();
+        let convoy_id = Uuid::new_v4();
+        let scores = vec![
+            DroneHealth::compute(Uuid::new_v4(), readings(100.0, 1.0), now),
+            DroneHealth::compute(Uuid::new_v4(), readings(10.0, 0.5), now),
+            DroneHealth::compute(Uuid::new_v4(), readings(0.0, 0.0), now),
+        ];
+        let worst = scores[2].drone_id;
+
+        let readiness = FleetReadiness::compute(convoy_id, scores, 1, 2);
+        assert_eq!((readiness.green, readiness.amber, readiness.red), (1, 1, 1));
+        assert_eq!(readiness.unscored, 1);
+        assert_eq!(readiness.lowest.len(), 2);
+        assert_eq!(readiness.lowest[0].drone_id, worst);
+        assert_eq!(readiness.lowest[1].score, 61);
+
+        let empty = FleetReadiness::compute(convoy_id, Vec::new(), 3, 5);
+        assert!(empty.average_score.is_none());
+    }
+}
@@ -0,0 +1,128 @@
+//! Engagement heatmap binning.
+//!
+//! Bins engagement impact points into an approximately square lat/lon grid
+//! for rendering as a map heat layer.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Kilometres per degree of latitude
+const KM_PER_DEG_LAT: f64 = 111.32;
+
+/// Smallest supported grid cell edge
+pub const MIN_GRID_RESOLUTION_KM: f64 = 0.1;
+
+/// Engagement impact location
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImpactPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub hit: bool,
+}
+
+/// Aggregated grid cell
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeatmapCell {
+    /// Cell center latitude
+    pub latitude: f64,
+    /// Cell center longitude
+    pub longitude: f64,
+    pub count: u32,
+    pub hits: u32,
+}
+
+impl HeatmapCell {
+    /// Fraction of engagements in the cell that hit (0.0 - 1.0)
+    #[must_use]
+    pub fn hit_ratio(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            f64::from(self.hits) / f64::from(self.count)
+        }
+    }
+}
+
+/// Bin impact points into cells of roughly `resolution_km` per side.
+///
+/// Longitude spacing is scaled by the mean latitude so cells stay square
+/// across the AOR. Cells are returned busiest first.
+#[must_use]
+pub fn bin_impacts(points: &[ImpactPoint], resolution_km: f64) -> Vec<HeatmapCell> {
+    if points.is_empty() {
+        return Vec::new();
+    }
+
+    let resolution_km = resolution_km.max(MIN_GRID_RESOLUTION_KM);
+    let mean_lat = points.iter().map(|p| p.latitude).sum::<f64>() / points.len() as f64;
+    let lat_step = resolution_km / KM_PER_DEG_LAT;
+    let lon_step = resolution_km / (KM_PER_DEG_LAT * mean_lat.to_radians().cos().max(0.01));
+
+    let mut grid: HashMap<(i64, i64), (u32, u32)> = HashMap::new();
+    for point in points {
+        let key = (
+            (point.latitude / lat_step).floor() as i64,
+            (point.longitude / lon_step).floor() as i64,
+        );
+        let cell = grid.entry(key).or_default();
+        cell.0 += 1;
+        if point.hit {
+            cell.1 += 1;
+        }
+    }
+
+    let mut cells: Vec<HeatmapCell> = grid
+        .into_iter()
+        .map(|((i, j), (count, hits))| HeatmapCell {
+            latitude: (i as f64 + 0.5) * lat_step,
+            longitude: (j as f64 + 0.5) * lon_step,
+            count,
+            hits,
+        })
+        .collect();
+
+    cells.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(a.latitude.total_cmp(&b.latitude))
+            .then(a.longitude.total_cmp(&b.longitude))
+    });
+
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(latitude: f64, longitude: f64, hit: bool) -> ImpactPoint {
+        ImpactPoint {
+            latitude,
+            longitude,
+            hit,
+        }
+    }
+
+    #[test]
+    fn test_bin_impacts_groups_nearby_points() {
+        let points = vec![
+            point(31.6001, 65.7001, true),
+            point(31.6002, 65.7002, false),
+            point(31.6003, 65.7003, true),
+            point(31.9000, 65.9000, true),
+        ];
+
+        let cells = bin_impacts(&points, 1.0);
+
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].count, 3);
+        assert_eq!(cells[0].hits, 2);
+        assert!((cells[0].hit_ratio() - 2.0 / 3.0).abs() < 1e-9);
+        assert!((cells[0].latitude - 31.6).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_bin_impacts_empty() {
+        assert!(bin_impacts(&[], 1.0).is_empty());
+    }
+}
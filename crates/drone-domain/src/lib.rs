@@ -10,6 +10,44 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+pub mod capability;
+pub mod deconfliction;
+pub mod endurance;
+pub mod event_log;
+pub mod flight_hours;
+pub mod formation;
+pub mod geofence;
+pub mod health;
+pub mod heatmap;
+pub mod search;
+pub mod status;
+pub mod template;
+pub mod track;
+pub mod units;
+
+pub use capability::{
+    CapabilityRegistry, EnvelopeViolation, PlatformCapabilities, WeaponEnvelope,
+};
+pub use deconfliction::{predict_conflicts, FlightPath, PredictedConflict, SeparationMinimum};
+pub use endurance::{EnduranceEstimate, FuelProfile};
+pub use event_log::{
+    DroneEngagementStats, EngagementLogEvent, EngagementLogKind, EngagementLogPayload,
+    EngagementProjection,
+};
+pub use flight_hours::{FlightHours, FlightHoursAdvisory, FlightHoursLimits};
+pub use formation::{Formation, FormationBounds, FormationOffset, SpacingViolation};
+pub use geofence::{GeoPoint, GeoViolation, NoStrikeZone, ZoneEnforcement};
+pub use health::{DroneHealth, FleetReadiness, HealthBand, HealthFactors, HealthReadings};
+pub use heatmap::{bin_impacts, HeatmapCell, ImpactPoint};
+pub use search::{normalize_search_term, rank_entries, SearchEntry, SearchHit, SearchKind};
+pub use status::DroneStatusChange;
+pub use template::{
+    ConvoyTemplate, ProvisionPlan, ProvisionedDrone, TemplateKind, TemplateOverrides,
+    MAX_TEMPLATE_DRONES,
+};
+pub use track::{simplify_track, TrackPoint};
+pub use units::{Km, Meters, Mps, Percent};
+
 // =============================================================================
 // VALUE OBJECTS
 // =============================================================================
@@ -35,9 +73,21 @@ impl Coordinates {
         }
     }
 
+    /// Altitude above mean sea level
+    #[must_use]
+    pub fn altitude(&self) -> Meters {
+        Meters(self.altitude_m)
+    }
+
+    /// Ground speed
+    #[must_use]
+    pub fn speed(&self) -> Mps {
+        Mps(self.speed_mps)
+    }
+
     /// Calculate great-circle distance to another point (Haversine formula)
     #[must_use]
-    pub fn distance_to_km(&self, other: &Coordinates) -> f64 {
+    pub fn distance_to_km(&self, other: &Coordinates) -> Km {
         const EARTH_RADIUS_KM: f64 = 6371.0;
 
         let lat1 = self.latitude.to_radians();
@@ -49,7 +99,7 @@ impl Coordinates {
             + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
         let c = 2.0 * a.sqrt().asin();
 
-        EARTH_RADIUS_KM * c
+        Km(EARTH_RADIUS_KM * c)
     }
 }
 
@@ -89,6 +139,19 @@ impl PlatformType {
             Self::Mq25Stingray => "MQ-25_STINGRAY",
         }
     }
+
+    /// Parse either the [`Self::as_str`] form (`MQ-9_REAPER`) or the wire
+    /// form (`MQ9_REAPER`)
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "MQ-9_REAPER" | "MQ9_REAPER" => Some(Self::Mq9Reaper),
+            "MQ-1C_GRAY_EAGLE" | "MQ1C_GRAY_EAGLE" => Some(Self::Mq1cGrayEagle),
+            "RQ-4_GLOBAL_HAWK" | "RQ4_GLOBAL_HAWK" => Some(Self::Rq4GlobalHawk),
+            "MQ-25_STINGRAY" | "MQ25_STINGRAY" => Some(Self::Mq25Stingray),
+            _ => None,
+        }
+    }
 }
 
 /// Drone operational status
@@ -105,6 +168,21 @@ pub enum DroneStatus {
     Maintenance,
 }
 
+impl DroneStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Preflight => "PREFLIGHT",
+            Self::Airborne => "AIRBORNE",
+            Self::Loiter => "LOITER",
+            Self::Ingress => "INGRESS",
+            Self::Egress => "EGRESS",
+            Self::Rtb => "RTB",
+            Self::Landed => "LANDED",
+            Self::Maintenance => "MAINTENANCE",
+        }
+    }
+}
+
 /// Convoy mission status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -127,6 +205,45 @@ pub enum MissionType {
     Sar, // Search and Rescue
 }
 
+/// Data environment a convoy's records belong to.
+///
+/// Exercise, live and test data share storage but are never mixed: every
+/// read is scoped to one environment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Environment {
+    /// Training and rehearsal missions
+    Exercise,
+    /// Operational missions
+    #[default]
+    Live,
+    /// Integration and load testing
+    Test,
+}
+
+impl Environment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exercise => "EXERCISE",
+            Self::Live => "LIVE",
+            Self::Test => "TEST",
+        }
+    }
+}
+
+impl std::str::FromStr for Environment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "EXERCISE" => Ok(Self::Exercise),
+            "LIVE" => Ok(Self::Live),
+            "TEST" => Ok(Self::Test),
+            other => Err(format!("unknown environment '{other}'")),
+        }
+    }
+}
+
 /// Waypoint types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -170,6 +287,20 @@ impl WeaponType {
             Self::Agm176Griffin => "AGM-176_GRIFFIN",
         }
     }
+
+    /// Parse either the [`Self::as_str`] form (`AGM-114_HELLFIRE`) or the
+    /// wire form (`AGM114_HELLFIRE`)
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "AGM-114_HELLFIRE" | "AGM114_HELLFIRE" => Some(Self::Agm114Hellfire),
+            "GBU-12_PAVEWAY" | "GBU12_PAVEWAY" => Some(Self::Gbu12Paveway),
+            "AIM-9X_SIDEWINDER" | "AIM9X_SIDEWINDER" => Some(Self::Aim9xSidewinder),
+            "GBU-38_JDAM" | "GBU38_JDAM" => Some(Self::Gbu38Jdam),
+            "AGM-176_GRIFFIN" | "AGM176_GRIFFIN" => Some(Self::Agm176Griffin),
+            _ => None,
+        }
+    }
 }
 
 /// Weapon status
@@ -253,6 +384,168 @@ pub enum AlertSeverity {
     Info,
 }
 
+impl AlertSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Critical => "CRITICAL",
+            Self::Warning => "WARNING",
+            Self::Info => "INFO",
+        }
+    }
+
+    /// Whether this severity is at least as severe as `minimum`
+    #[must_use]
+    pub fn at_least(self, minimum: Self) -> bool {
+        self.level() >= minimum.level()
+    }
+
+    fn level(self) -> u8 {
+        match self {
+            Self::Info => 0,
+            Self::Warning => 1,
+            Self::Critical => 2,
+        }
+    }
+}
+
+/// Outcome of forwarding an alert to an external channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DeliveryStatus {
+    Delivered,
+    /// Every retry failed
+    Failed,
+    /// Dropped by the channel's rate limit
+    RateLimited,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Delivered => "DELIVERED",
+            Self::Failed => "FAILED",
+            Self::RateLimited => "RATE_LIMITED",
+        }
+    }
+}
+
+/// Target lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TargetStatus {
+    /// Reported once
+    Detected,
+    /// Re-acquired by a later report
+    Tracked,
+    /// Engaged at least once, not confirmed destroyed
+    Engaged,
+    Destroyed,
+}
+
+/// Engagement authorization lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuthorizationStatus {
+    /// Awaiting a commander's decision
+    Pending,
+    Approved,
+    Denied,
+    /// Approval consumed by an engagement
+    Executed,
+}
+
+/// Leaderboard scoring models
+///
+/// Raw accuracy rewards a drone with 1/1 hits over one with 45/50, so the
+/// leaderboard ranks on a score that accounts for engagement volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ScoringModel {
+    /// Raw hit percentage (legacy behaviour)
+    Accuracy,
+    /// Lower bound of the 95% Wilson score interval
+    #[default]
+    WilsonLowerBound,
+    /// Accuracy shrunk towards a fleet-wide prior
+    BayesianAverage,
+    /// Accuracy weighted by log engagement volume
+    WeightedVolume,
+}
+
+impl ScoringModel {
+    /// z-score for a 95% confidence interval
+    pub const WILSON_Z: f64 = 1.96;
+    /// Prior hit rate used by the Bayesian model
+    pub const BAYESIAN_PRIOR_ACCURACY: f64 = 0.85;
+    /// Number of pseudo-engagements the prior is worth
+    pub const BAYESIAN_PRIOR_WEIGHT: f64 = 10.0;
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accuracy => "ACCURACY",
+            Self::WilsonLowerBound => "WILSON_LOWER_BOUND",
+            Self::BayesianAverage => "BAYESIAN_AVERAGE",
+            Self::WeightedVolume => "WEIGHTED_VOLUME",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ACCURACY" => Some(Self::Accuracy),
+            "WILSON_LOWER_BOUND" => Some(Self::WilsonLowerBound),
+            "BAYESIAN_AVERAGE" => Some(Self::BayesianAverage),
+            "WEIGHTED_VOLUME" => Some(Self::WeightedVolume),
+            _ => None,
+        }
+    }
+
+    /// Compute the ranking score for a hit/engagement count.
+    ///
+    /// Accuracy, Wilson and Bayesian scores are on the 0-100 scale of
+    /// `accuracy_pct`; the weighted volume score grows with `ln(1 + n)`.
+    #[must_use]
+    pub fn score(&self, successful_hits: i64, total_engagements: i64) -> f64 {
+        if total_engagements <= 0 {
+            return 0.0;
+        }
+
+        let n = total_engagements as f64;
+        let p = successful_hits as f64 / n;
+
+        match self {
+            Self::Accuracy => p * 100.0,
+            Self::WilsonLowerBound => {
+                wilson_interval(successful_hits, total_engagements, Self::WILSON_Z).0 * 100.0
+            }
+            Self::BayesianAverage => {
+                let prior_hits = Self::BAYESIAN_PRIOR_ACCURACY * Self::BAYESIAN_PRIOR_WEIGHT;
+                (successful_hits as f64 + prior_hits) / (n + Self::BAYESIAN_PRIOR_WEIGHT) * 100.0
+            }
+            Self::WeightedVolume => p * 100.0 * n.ln_1p(),
+        }
+    }
+}
+
+/// Wilson score interval for a hit rate, as fractions (0.0 - 1.0).
+///
+/// Returns `(0.0, 1.0)` when there are no engagements.
+#[must_use]
+pub fn wilson_interval(successful_hits: i64, total_engagements: i64, z: f64) -> (f64, f64) {
+    if total_engagements <= 0 {
+        return (0.0, 1.0);
+    }
+
+    let n = total_engagements as f64;
+    let p = successful_hits as f64 / n;
+    let z2 = z * z;
+    let center = p + z2 / (2.0 * n);
+    let margin = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    let denom = 1.0 + z2 / n;
+
+    (((center - margin) / denom).max(0.0), ((center + margin) / denom).min(1.0))
+}
+
 // =============================================================================
 // NESTED VALUE OBJECTS
 // =============================================================================
@@ -265,6 +558,32 @@ pub struct WeaponStatus {
     pub status: WeaponState,
 }
 
+impl WeaponStatus {
+    /// Fire one round, marking the weapon expended when it runs dry.
+    ///
+    /// Fails if the weapon is jammed or has no rounds left.
+    pub fn expend_round(&mut self) -> Result<(), DomainError> {
+        if self.status == WeaponState::Jammed {
+            return Err(DomainError::EngagementValidation(format!(
+                "{} is jammed",
+                self.weapon_type.as_str()
+            )));
+        }
+        if self.rounds_remaining <= 0 || self.status == WeaponState::Expended {
+            return Err(DomainError::EngagementValidation(format!(
+                "{} has no rounds remaining",
+                self.weapon_type.as_str()
+            )));
+        }
+
+        self.rounds_remaining -= 1;
+        if self.rounds_remaining == 0 {
+            self.status = WeaponState::Expended;
+        }
+        Ok(())
+    }
+}
+
 /// Target information
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TargetInfo {
@@ -292,6 +611,32 @@ pub struct SensorStatus {
     pub mode: String,
 }
 
+/// Sensor mode tasked for a drone's arrival at a waypoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorTask {
+    pub drone_id: Uuid,
+    pub sequence_number: i16,
+    pub sensor_type: SensorType,
+    pub mode: String,
+    pub assigned_by: String,
+    pub assigned_at: DateTime<Utc>,
+}
+
+impl SensorTask {
+    /// Switch the matching sensor into the tasked mode.
+    ///
+    /// Returns `false` if the drone carries no sensor of this type.
+    pub fn apply(&self, sensors: &mut [SensorStatus]) -> bool {
+        match sensors.iter_mut().find(|s| s.sensor_type == self.sensor_type) {
+            Some(sensor) => {
+                sensor.mode.clone_from(&self.mode);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
 /// Communication link status
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CommLink {
@@ -328,6 +673,9 @@ pub struct Convoy {
     pub commanding_unit: String,
     pub authorization_level: String,
     pub roe_profile: String,
+    /// Absent in documents written before environments existed
+    #[serde(default)]
+    pub environment: Environment,
 
     // Drone roster
     pub drone_ids: Vec<Uuid>,
@@ -372,6 +720,48 @@ pub struct Drone {
 }
 
 impl Drone {
+    /// Register a new drone on the ground with no loadout or history.
+    ///
+    /// Callsign and tail number are normalized with
+    /// [`Drone::normalize_identifier`].
+    pub fn register(
+        convoy_id: Uuid,
+        callsign: &str,
+        tail_number: &str,
+        platform_type: PlatformType,
+        serial_number: String,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            convoy_id,
+            drone_id: Uuid::new_v4(),
+            tail_number: Self::normalize_identifier(tail_number),
+            callsign: Self::normalize_identifier(callsign),
+            platform_type,
+            serial_number,
+            status: DroneStatus::Preflight,
+            current_position: Coordinates::new(0.0, 0.0, 0.0),
+            fuel_remaining_pct: 100.0,
+            flight_time_hrs: 0.0,
+            weapons: Vec::new(),
+            sensors: Vec::new(),
+            primary_link: None,
+            backup_link: None,
+            mesh_neighbors: Vec::new(),
+            total_engagements: 0,
+            successful_hits: 0,
+            accuracy_pct: 0.0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Canonical form of a callsign or tail number: trimmed and upper case,
+    /// so `reaper-01 ` and `REAPER-01` collide
+    pub fn normalize_identifier(value: &str) -> String {
+        value.trim().to_uppercase()
+    }
+
     /// Recalculate accuracy percentage
     pub fn calculate_accuracy(&mut self) {
         if self.total_engagements > 0 {
@@ -406,6 +796,14 @@ pub struct Waypoint {
     pub status: WaypointStatus,
 }
 
+/// Telemetry envelope version this server models.
+///
+/// Version 1 is the original field set without an envelope; version 2 adds
+/// `schemaVersion` and the `extensions` passthrough. Frames from newer
+/// gateways are accepted, and fields this server does not know are kept in
+/// [`Telemetry::extensions`].
+pub const TELEMETRY_SCHEMA_VERSION: i16 = 2;
+
 /// Telemetry entity - time-series position/sensor data
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Telemetry {
@@ -440,6 +838,19 @@ pub struct Telemetry {
     // Comms health
     pub link_status: Option<CommLink>,
     pub mesh_connectivity: f32,
+
+    // Schema evolution
+    /// Envelope version the sender reported
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: i16,
+    /// Fields this server version does not model, as sent
+    #[serde(default)]
+    pub extensions: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+/// Serde default for telemetry recorded before the envelope existed
+fn legacy_schema_version() -> i16 {
+    1
 }
 
 impl Telemetry {
@@ -481,10 +892,270 @@ pub struct Engagement {
     pub waypoint_number: i16,
     pub shooter_position: Coordinates,
     pub range_to_target_km: f32,
+    /// Probability of kill predicted before the shot, when the shooter reported one
+    #[serde(default)]
+    pub predicted_pk: Option<f32>,
 
     // BDA
     pub bda_status: String,
     pub bda_notes: Option<String>,
+
+    /// Per-drone sequence number; leaderboard updates apply in this order
+    #[serde(default)]
+    pub sequence: i64,
+}
+
+/// Target entity - a detection tracked across engagements
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Target {
+    pub convoy_id: Uuid,
+    pub target_id: Uuid,
+
+    pub target_type: TargetType,
+    pub coordinates: Coordinates,
+    pub confidence: f32,
+    pub threat_level: ThreatLevel,
+
+    pub status: TargetStatus,
+    pub reported_by: String,
+    pub first_detected_at: DateTime<Utc>,
+    pub last_updated_at: DateTime<Utc>,
+
+    /// Engagements fired at this target
+    pub engagement_ids: Vec<Uuid>,
+}
+
+impl Target {
+    /// Update the track from a new detection report.
+    ///
+    /// A detected target becomes tracked; engaged or destroyed targets keep
+    /// their status.
+    pub fn redetect(
+        &mut self,
+        coordinates: Coordinates,
+        confidence: f32,
+        threat_level: ThreatLevel,
+        now: DateTime<Utc>,
+    ) {
+        self.coordinates = coordinates;
+        self.confidence = confidence;
+        self.threat_level = threat_level;
+        if self.status == TargetStatus::Detected {
+            self.status = TargetStatus::Tracked;
+        }
+        self.last_updated_at = now;
+    }
+
+    /// Status after an engagement is fired at the target
+    #[must_use]
+    pub fn status_after_engagement(&self) -> TargetStatus {
+        match self.status {
+            TargetStatus::Destroyed => TargetStatus::Destroyed,
+            _ => TargetStatus::Engaged,
+        }
+    }
+
+    /// Status after a battle damage assessment of one of its engagements
+    #[must_use]
+    pub fn status_after_assessment(&self, assessment: DamageAssessment) -> TargetStatus {
+        match assessment {
+            DamageAssessment::Destroyed => TargetStatus::Destroyed,
+            _ => self.status_after_engagement(),
+        }
+    }
+}
+
+/// Hand-off of a target track from one drone to another, e.g. when the
+/// tracking drone goes bingo fuel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetHandoff {
+    pub convoy_id: Uuid,
+    pub target_id: Uuid,
+    pub handed_off_at: DateTime<Utc>,
+    pub handoff_id: Uuid,
+
+    pub from_drone_id: Uuid,
+    pub to_drone_id: Uuid,
+    pub reason: Option<String>,
+    pub handed_off_by: String,
+}
+
+/// Alert entity - operational alert raised against a convoy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub convoy_id: Uuid,
+    pub alert_time: DateTime<Utc>,
+    pub alert_id: Uuid,
+
+    pub severity: AlertSeverity,
+    pub alert_type: String,
+    pub source_drone_id: Option<Uuid>,
+    pub message: String,
+
+    // Acknowledgement
+    pub acknowledged: bool,
+    pub acknowledged_by: Option<String>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+
+    /// External notification outcomes, one per channel
+    #[serde(default)]
+    pub deliveries: Vec<AlertDelivery>,
+}
+
+/// Delivery of an alert to one external notification channel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertDelivery {
+    /// Channel name, e.g. `slack`
+    pub channel: String,
+    pub status: DeliveryStatus,
+    /// Send attempts made, including the first
+    pub attempts: i32,
+    /// Error from the last failed attempt
+    pub last_error: Option<String>,
+}
+
+/// Longest accepted journal entry text, in characters
+pub const MAX_JOURNAL_TEXT_LEN: usize = 2000;
+
+/// Operator annotation on a convoy's mission timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub convoy_id: Uuid,
+    pub entry_time: DateTime<Utc>,
+    pub entry_id: Uuid,
+
+    pub author: String,
+    pub text: String,
+    /// Engagement, drone, target or alert the note refers to
+    pub linked_entity_id: Option<Uuid>,
+}
+
+/// Document generated when a convoy completes its mission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ArtifactKind {
+    /// Analytics report as Markdown
+    AnalyticsReport,
+    /// Final leaderboard as CSV
+    Leaderboard,
+    /// Routes, engagements and journal as KML
+    Kml,
+}
+
+impl ArtifactKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AnalyticsReport => "ANALYTICS_REPORT",
+            Self::Leaderboard => "LEADERBOARD",
+            Self::Kml => "KML",
+        }
+    }
+
+    /// Parse the name returned by [`Self::as_str`]
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ANALYTICS_REPORT" => Some(Self::AnalyticsReport),
+            "LEADERBOARD" => Some(Self::Leaderboard),
+            "KML" => Some(Self::Kml),
+            _ => None,
+        }
+    }
+}
+
+/// Stored end-of-mission document.
+///
+/// The content is kept once per SHA-256 digest; a convoy holds the latest
+/// artifact of each kind.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissionArtifact {
+    pub convoy_id: Uuid,
+    pub kind: ArtifactKind,
+    /// Lowercase hex SHA-256 of the content
+    pub digest: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What a machine-client API key may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApiKeyScope {
+    /// Telemetry ingest only
+    Ingest,
+    /// Read-only queries
+    ReadOnly,
+    /// Full access, including key management
+    Admin,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ingest => "INGEST",
+            Self::ReadOnly => "READ_ONLY",
+            Self::Admin => "ADMIN",
+        }
+    }
+}
+
+/// API key issued to a machine client such as a simulator or gateway.
+///
+/// Only a keyed hash of the secret is stored; the secret itself is shown
+/// once when the key is issued or rotated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Public identifier, the non-secret part of the presented key
+    pub key_id: String,
+    pub name: String,
+    pub scope: ApiKeyScope,
+    /// Commanding unit the key is confined to; `None` grants every unit
+    #[serde(default)]
+    pub commanding_unit: Option<String>,
+    /// Environment the key is confined to
+    #[serde(default)]
+    pub environment: Environment,
+    pub secret_hash: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+    pub rotated_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// Whether the key can still authenticate
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+}
+
+/// Engagement authorization - approval requested before weapons release
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngagementAuthorization {
+    pub convoy_id: Uuid,
+    pub request_id: Uuid,
+    pub drone_id: Uuid,
+
+    // Requested engagement
+    pub weapon_type: WeaponType,
+    pub target_type: TargetType,
+    pub target_coordinates: Coordinates,
+    pub requested_by: String,
+    pub justification: String,
+    pub requested_at: DateTime<Utc>,
+
+    // Decision
+    pub status: AuthorizationStatus,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub decision_notes: Option<String>,
+    /// Approved authorizations must be executed before this
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Engagement that consumed the approval
+    pub engagement_id: Option<Uuid>,
 }
 
 // =============================================================================
@@ -503,6 +1174,9 @@ pub struct LeaderboardEntry {
     pub successful_hits: i32,
     pub current_streak: i32,
     pub best_streak: i32,
+    /// Ranking score under the convoy's scoring model
+    #[serde(default)]
+    pub score: f64,
     pub rank: i16,
     pub updated_at: DateTime<Utc>,
 }
@@ -518,11 +1192,102 @@ pub struct AccuracyStats {
 
 impl AccuracyStats {
     #[must_use]
-    pub fn accuracy_pct(&self) -> f32 {
-        if self.total_engagements > 0 {
-            (self.successful_hits as f32 / self.total_engagements as f32) * 100.0
-        } else {
-            0.0
+    pub fn accuracy_pct(&self) -> Percent {
+        Percent::of(self.successful_hits, self.total_engagements)
+    }
+}
+
+/// Point-in-time convoy statistics, recorded periodically for charting
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConvoyStatsSnapshot {
+    pub convoy_id: Uuid,
+    pub recorded_at: DateTime<Utc>,
+    pub drone_count: i32,
+    pub airborne_count: i32,
+    pub average_fuel_pct: f32,
+    /// Engagements since mission start
+    pub total_engagements: i32,
+    /// Hits since mission start
+    pub total_hits: i32,
+}
+
+impl ConvoyStatsSnapshot {
+    /// Hit rate over all engagements so far
+    #[must_use]
+    pub fn cumulative_accuracy_pct(&self) -> Percent {
+        Percent::of(i64::from(self.total_hits), i64::from(self.total_engagements))
+    }
+
+    /// Merge time-ordered snapshots into one point per `resolution` window.
+    ///
+    /// Gauges (drone counts, fuel) are averaged over the window; the
+    /// cumulative engagement counters keep their last value. Each point is
+    /// stamped with the start of its window.
+    #[must_use]
+    pub fn downsample(snapshots: &[Self], resolution: chrono::Duration) -> Vec<Self> {
+        let window_ms = resolution.num_milliseconds().max(1);
+        let mut points: Vec<Self> = Vec::new();
+        let mut samples = 0;
+
+        for snapshot in snapshots {
+            let ms = snapshot.recorded_at.timestamp_millis();
+            let window_start = DateTime::from_timestamp_millis(ms - ms.rem_euclid(window_ms))
+                .unwrap_or(snapshot.recorded_at);
+
+            match points.last_mut() {
+                Some(point) if point.recorded_at == window_start => {
+                    samples += 1;
+                    let n = samples as f32;
+                    point.drone_count = mean_of(point.drone_count, snapshot.drone_count, samples);
+                    point.airborne_count =
+                        mean_of(point.airborne_count, snapshot.airborne_count, samples);
+                    point.average_fuel_pct +=
+                        (snapshot.average_fuel_pct - point.average_fuel_pct) / n;
+                    point.total_engagements = snapshot.total_engagements;
+                    point.total_hits = snapshot.total_hits;
+                }
+                _ => {
+                    samples = 1;
+                    points.push(Self {
+                        recorded_at: window_start,
+                        ..*snapshot
+                    });
+                }
+            }
+        }
+
+        points
+    }
+}
+
+/// Running integer mean after adding the `samples`-th value
+fn mean_of(mean: i32, value: i32, samples: i32) -> i32 {
+    let total = mean as f32 * (samples - 1) as f32 + value as f32;
+    (total / samples as f32).round() as i32
+}
+
+/// A drone's leaderboard rank at the moment it changed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RankHistoryEntry {
+    pub convoy_id: Uuid,
+    pub drone_id: Uuid,
+    pub recorded_at: DateTime<Utc>,
+    pub rank: i16,
+    /// Set when the drone's own engagement moved it; `None` when another
+    /// drone's engagement shifted it without changing its stats
+    pub accuracy_pct: Option<f32>,
+}
+
+impl RankHistoryEntry {
+    /// Fill entries without accuracy from the latest earlier one, starting
+    /// from `initial`. Entries must be oldest first.
+    pub fn carry_accuracy_forward(entries: &mut [Self], initial: Option<f32>) {
+        let mut last = initial;
+        for entry in entries {
+            match entry.accuracy_pct {
+                Some(accuracy) => last = Some(accuracy),
+                None => entry.accuracy_pct = last,
+            }
         }
     }
 }
@@ -572,4 +1337,193 @@ pub enum DomainError {
 
     #[error("Engagement validation failed: {0}")]
     EngagementValidation(String),
+
+    #[error("Invalid drone status transition: {} -> {}", from.as_str(), to.as_str())]
+    InvalidStatusTransition { from: DroneStatus, to: DroneStatus },
+
+    #[error("Invalid convoy template: {0}")]
+    InvalidTemplate(String),
+
+    #[error("Invalid loadout: {0}")]
+    InvalidLoadout(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_lifecycle() {
+        let now = Utc::now();
+        let mut target = Target {
+            convoy_id: Uuid::new_v4(),
+            target_id: Uuid::new_v4(),
+            target_type: TargetType::Vehicle,
+            coordinates: Coordinates::new(31.6, 65.7, 1000.0),
+            confidence: 0.6,
+            threat_level: ThreatLevel::Medium,
+            status: TargetStatus::Detected,
+            reported_by: "REAPER-01".to_string(),
+            first_detected_at: now,
+            last_updated_at: now,
+            engagement_ids: vec![],
+        };
+
+        target.redetect(Coordinates::new(31.61, 65.71, 1000.0), 0.9, ThreatLevel::High, now);
+        assert_eq!(target.status, TargetStatus::Tracked);
+
+        target.status = target.status_after_assessment(DamageAssessment::Damaged);
+        assert_eq!(target.status, TargetStatus::Engaged);
+
+        target.status = target.status_after_assessment(DamageAssessment::Destroyed);
+        assert_eq!(target.status, TargetStatus::Destroyed);
+        assert_eq!(target.status_after_engagement(), TargetStatus::Destroyed);
+    }
+
+    #[test]
+    fn test_stats_downsample() {
+        let start = DateTime::from_timestamp(1_700_000_400, 0).unwrap();
+        let snapshot = |secs: i64, airborne: i32, fuel: f32, engagements: i32, hits: i32| {
+            ConvoyStatsSnapshot {
+                convoy_id: Uuid::nil(),
+                recorded_at: start + chrono::Duration::seconds(secs),
+                drone_count: 4,
+                airborne_count: airborne,
+                average_fuel_pct: fuel,
+                total_engagements: engagements,
+                total_hits: hits,
+            }
+        };
+        let snapshots = [
+            snapshot(0, 2, 90.0, 1, 1),
+            snapshot(60, 4, 80.0, 3, 2),
+            snapshot(300, 3, 70.0, 4, 3),
+        ];
+
+        let points = ConvoyStatsSnapshot::downsample(&snapshots, chrono::Duration::minutes(5));
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].recorded_at, start);
+        assert_eq!(points[0].airborne_count, 3);
+        assert!((points[0].average_fuel_pct - 85.0).abs() < 1e-4);
+        assert_eq!((points[0].total_engagements, points[0].total_hits), (3, 2));
+        assert!((points[1].cumulative_accuracy_pct().0 - 75.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_wilson_prefers_volume() {
+        let model = ScoringModel::WilsonLowerBound;
+        assert!(model.score(45, 50) > model.score(1, 1));
+        assert_eq!(model.score(0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_bayesian_shrinks_towards_prior() {
+        let model = ScoringModel::BayesianAverage;
+        let perfect_single = model.score(1, 1);
+        assert!(perfect_single < 100.0);
+        assert!(perfect_single > ScoringModel::BAYESIAN_PRIOR_ACCURACY * 100.0);
+    }
+
+    #[test]
+    fn test_accuracy_matches_raw_pct() {
+        assert!((ScoringModel::Accuracy.score(9, 10) - 90.0).abs() < f64::EPSILON);
+        assert!(ScoringModel::WeightedVolume.score(9, 10) > ScoringModel::WeightedVolume.score(1, 1));
+    }
+
+    #[test]
+    fn test_scoring_model_parse_round_trips() {
+        for model in [
+            ScoringModel::Accuracy,
+            ScoringModel::WilsonLowerBound,
+            ScoringModel::BayesianAverage,
+            ScoringModel::WeightedVolume,
+        ] {
+            assert_eq!(ScoringModel::parse(model.as_str()), Some(model));
+        }
+        assert_eq!(ScoringModel::parse("wilson"), None);
+    }
+
+    #[test]
+    fn test_rank_history_carries_accuracy_forward() {
+        let entry = |rank, accuracy_pct| RankHistoryEntry {
+            convoy_id: Uuid::nil(),
+            drone_id: Uuid::nil(),
+            recorded_at: DateTime::UNIX_EPOCH,
+            rank,
+            accuracy_pct,
+        };
+        let mut entries = [entry(3, None), entry(2, Some(80.0)), entry(3, None)];
+
+        RankHistoryEntry::carry_accuracy_forward(&mut entries, None);
+
+        let accuracy: Vec<_> = entries.iter().map(|e| e.accuracy_pct).collect();
+        assert_eq!(accuracy, [None, Some(80.0), Some(80.0)]);
+    }
+
+    #[test]
+    fn test_expend_round_until_empty() {
+        let mut weapon = WeaponStatus {
+            weapon_type: WeaponType::Agm114Hellfire,
+            rounds_remaining: 1,
+            status: WeaponState::Armed,
+        };
+        assert!(weapon.expend_round().is_ok());
+        assert_eq!((weapon.rounds_remaining, weapon.status), (0, WeaponState::Expended));
+        assert!(weapon.expend_round().is_err());
+
+        weapon.rounds_remaining = 4;
+        weapon.status = WeaponState::Jammed;
+        assert!(weapon.expend_round().is_err());
+        assert_eq!(weapon.rounds_remaining, 4);
+    }
+
+    #[test]
+    fn test_register_normalizes_identifiers() {
+        let drone = Drone::register(
+            Uuid::new_v4(),
+            " reaper-01",
+            "af-0042 ",
+            PlatformType::Mq9Reaper,
+            String::new(),
+        );
+        assert_eq!(drone.callsign, "REAPER-01");
+        assert_eq!(drone.tail_number, "AF-0042");
+        assert_eq!(drone.status, DroneStatus::Preflight);
+        assert_eq!(drone.callsign, Drone::normalize_identifier("Reaper-01"));
+    }
+
+    #[test]
+    fn test_sensor_task_sets_mode() {
+        let mut sensors = vec![SensorStatus {
+            sensor_type: SensorType::EoIr,
+            operational: true,
+            mode: "WIDE_AREA".to_string(),
+        }];
+        let mut task = SensorTask {
+            drone_id: Uuid::new_v4(),
+            sequence_number: 3,
+            sensor_type: SensorType::EoIr,
+            mode: "SPOT_TRACK".to_string(),
+            assigned_by: "OPERATOR".to_string(),
+            assigned_at: Utc::now(),
+        };
+
+        assert!(task.apply(&mut sensors));
+        assert_eq!(sensors[0].mode, "SPOT_TRACK");
+
+        task.sensor_type = SensorType::Sar;
+        assert!(!task.apply(&mut sensors));
+    }
+
+    #[test]
+    fn test_wilson_interval_brackets_rate() {
+        let (lower, upper) = wilson_interval(45, 50, ScoringModel::WILSON_Z);
+        assert!(lower < 0.9 && 0.9 < upper);
+
+        // Fewer engagements at the same rate give a wider interval
+        let (small_lower, small_upper) = wilson_interval(9, 10, ScoringModel::WILSON_Z);
+        assert!(small_upper - small_lower > upper - lower);
+
+        assert_eq!(wilson_interval(0, 0, ScoringModel::WILSON_Z), (0.0, 1.0));
+    }
 }
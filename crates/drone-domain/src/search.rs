@@ -0,0 +1,211 @@
+//! Quick-jump search matching.
+//!
+//! Callsigns, tail numbers and convoy names are indexed as normalized terms
+//! (lower case, separators removed) so `reaper 01`, `REAPER-01` and
+//! `reaper01` all find the same drone. Terms are ranked exact, then prefix,
+//! then substring, then by edit distance for typos.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Minimum query length before typo-tolerant matching kicks in
+const MIN_FUZZY_LEN: usize = 3;
+
+/// Kind of entity a search entry points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SearchKind {
+    Drone,
+    Convoy,
+    Engagement,
+}
+
+impl SearchKind {
+    pub const ALL: [SearchKind; 3] = [Self::Drone, Self::Convoy, Self::Engagement];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Drone => "drone",
+            Self::Convoy => "convoy",
+            Self::Engagement => "engagement",
+        }
+    }
+
+    /// Whether misspelled queries should match; engagement IDs only match
+    /// by prefix
+    #[must_use]
+    pub fn is_fuzzy(self) -> bool {
+        !matches!(self, Self::Engagement)
+    }
+}
+
+/// One searchable term pointing at an entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchEntry {
+    pub kind: SearchKind,
+    /// Normalized term, see [`normalize_search_term`]
+    pub term: String,
+    pub entity_id: Uuid,
+    /// Convoy the entity belongs to; a convoy's own ID for convoys
+    pub convoy_id: Uuid,
+    /// Display text for the result
+    pub label: String,
+}
+
+impl SearchEntry {
+    pub fn new(
+        kind: SearchKind,
+        term: &str,
+        entity_id: Uuid,
+        convoy_id: Uuid,
+        label: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            term: normalize_search_term(term),
+            entity_id,
+            convoy_id,
+            label: label.into(),
+        }
+    }
+}
+
+/// A ranked search match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub entry: SearchEntry,
+    /// Relevance in `0.0..=1.0`, higher is better
+    pub score: f64,
+}
+
+/// Lower-case a term and drop separators and whitespace
+pub fn normalize_search_term(term: &str) -> String {
+    term.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Score a normalized `term` against a normalized `query`
+///
+/// `None` when the term does not match at all.
+pub fn match_score(query: &str, term: &str) -> Option<f64> {
+    if query.is_empty() {
+        return None;
+    }
+    // Shorter remainders rank higher among prefix and substring matches
+    let coverage = query.len() as f64 / term.len().max(1) as f64;
+
+    if term == query {
+        Some(1.0)
+    } else if term.starts_with(query) {
+        Some(0.7 + 0.2 * coverage)
+    } else if term.contains(query) {
+        Some(0.5 + 0.1 * coverage)
+    } else {
+        let query_len = query.chars().count();
+        if query_len < MIN_FUZZY_LEN {
+            return None;
+        }
+        let allowed = if query_len >= 8 { 2 } else { 1 };
+        // Typing is usually partial, so compare with the term's prefix too
+        let prefix: String = term.chars().take(query_len).collect();
+        let distance = edit_distance(query, term).min(edit_distance(query, &prefix));
+        (distance <= allowed).then_some(0.4 - 0.1 * distance as f64)
+    }
+}
+
+/// Rank entries against a raw query, best first, keeping at most `limit`
+pub fn rank_entries(
+    query: &str,
+    entries: impl IntoIterator<Item = SearchEntry>,
+    limit: usize,
+) -> Vec<SearchHit> {
+    let query = normalize_search_term(query);
+    let mut hits: Vec<SearchHit> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            if !entry.kind.is_fuzzy() && !entry.term.starts_with(&query) {
+                return None;
+            }
+            let score = match_score(&query, &entry.term)?;
+            Some(SearchHit { entry, score })
+        })
+        .collect();
+
+    // One hit per entity (a drone matches on callsign and tail number)
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.entry.term.cmp(&b.entry.term))
+    });
+    let mut seen = std::collections::HashSet::new();
+    hits.retain(|hit| seen.insert((hit.entry.kind, hit.entry.entity_id)));
+    hits.truncate(limit);
+    hits
+}
+
+/// Damerau-Levenshtein distance (optimal string alignment)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drone(term: &str) -> SearchEntry {
+        SearchEntry::new(SearchKind::Drone, term, Uuid::new_v4(), Uuid::new_v4(), term)
+    }
+
+    #[test]
+    fn test_rank_prefers_exact_then_prefix_then_typos() {
+        let entries = vec![
+            drone("REAPER-10"),
+            drone("REAPER-01"),
+            drone("GRIM REAPER-01"),
+            drone("RAEPER-01"),
+            drone("HAWK-02"),
+        ];
+
+        let hits = rank_entries("reaper 01", entries.clone(), 10);
+        let labels: Vec<_> = hits.iter().map(|h| h.entry.label.as_str()).collect();
+        assert_eq!(labels, vec!["REAPER-01", "GRIM REAPER-01", "RAEPER-01", "REAPER-10"]);
+
+        let hits = rank_entries("REAP", entries, 2);
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|h| h.entry.term.starts_with("reap")));
+    }
+
+    #[test]
+    fn test_engagements_match_by_prefix_only() {
+        let id = Uuid::parse_str("3f2a9c1b-0000-4000-8000-000000000000").unwrap();
+        let entry = SearchEntry::new(SearchKind::Engagement, "3f2a9c1b", id, Uuid::new_v4(), "x");
+
+        assert_eq!(rank_entries("3f2a", vec![entry.clone()], 5).len(), 1);
+        assert!(rank_entries("3f2b9c1b", vec![entry], 5).is_empty());
+        assert_eq!(edit_distance("raeper", "reaper"), 1);
+    }
+}
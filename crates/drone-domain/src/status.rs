@@ -0,0 +1,105 @@
+//! Drone status transitions.
+//!
+//! A drone moves through its sortie as PREFLIGHT → AIRBORNE → mission
+//! phases → RTB → LANDED, with MAINTENANCE reachable only on the ground.
+//! Jumps that skip a phase (LANDED → INGRESS) are rejected.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{DomainError, DroneStatus};
+
+impl DroneStatus {
+    /// Statuses reachable in one step from this one
+    #[must_use]
+    pub fn allowed_transitions(self) -> &'static [DroneStatus] {
+        use DroneStatus::*;
+        match self {
+            Preflight => &[Airborne, Maintenance],
+            Airborne => &[Loiter, Ingress, Egress, Rtb],
+            Loiter => &[Airborne, Ingress, Egress, Rtb],
+            Ingress => &[Airborne, Loiter, Egress, Rtb],
+            Egress => &[Airborne, Loiter, Rtb],
+            // Re-tasked or holding short of the airfield
+            Rtb => &[Airborne, Loiter, Landed],
+            Landed => &[Preflight, Maintenance],
+            Maintenance => &[Preflight],
+        }
+    }
+
+    /// Whether a drone in this status may move to `next`; staying put is
+    /// always allowed
+    #[must_use]
+    pub fn can_transition_to(self, next: DroneStatus) -> bool {
+        self == next || self.allowed_transitions().contains(&next)
+    }
+
+    /// Validate a move to `next`
+    pub fn transition_to(self, next: DroneStatus) -> Result<DroneStatus, DomainError> {
+        if self.can_transition_to(next) {
+            Ok(next)
+        } else {
+            Err(DomainError::InvalidStatusTransition {
+                from: self,
+                to: next,
+            })
+        }
+    }
+
+    /// Whether the drone is flying
+    #[must_use]
+    pub fn is_airborne(self) -> bool {
+        matches!(
+            self,
+            Self::Airborne | Self::Loiter | Self::Ingress | Self::Egress | Self::Rtb
+        )
+    }
+}
+
+/// One applied status transition, kept as drone status history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DroneStatusChange {
+    pub convoy_id: Uuid,
+    pub drone_id: Uuid,
+    pub old_status: DroneStatus,
+    pub new_status: DroneStatus,
+    pub changed_at: DateTime<Utc>,
+    pub changed_by: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sortie_transitions() {
+        let sortie = [
+            DroneStatus::Preflight,
+            DroneStatus::Airborne,
+            DroneStatus::Ingress,
+            DroneStatus::Egress,
+            DroneStatus::Rtb,
+            DroneStatus::Landed,
+            DroneStatus::Maintenance,
+            DroneStatus::Preflight,
+        ];
+        for pair in sortie.windows(2) {
+            assert_eq!(pair[0].transition_to(pair[1]).unwrap(), pair[1]);
+        }
+        assert!(DroneStatus::Loiter.can_transition_to(DroneStatus::Loiter));
+
+        let err = DroneStatus::Landed
+            .transition_to(DroneStatus::Ingress)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::InvalidStatusTransition {
+                from: DroneStatus::Landed,
+                to: DroneStatus::Ingress,
+            }
+        ));
+        assert!(!DroneStatus::Ingress.can_transition_to(DroneStatus::Landed));
+        assert!(!DroneStatus::Airborne.can_transition_to(DroneStatus::Maintenance));
+    }
+}
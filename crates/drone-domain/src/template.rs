@@ -0,0 +1,448 @@
+//! Convoy templates for quick-start provisioning.
+//!
+//! A template fixes a mission type, an area of responsibility and a list of
+//! drone slots. Provisioning one yields the convoy, its drones with
+//! platform-appropriate loadouts and sensors, and a generated waypoint plan
+//! per drone, so a demo convoy can be written in a single pass.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::formation::KM_PER_DEG_LAT;
+use crate::{
+    Convoy, ConvoyStatus, Coordinates, DomainError, Drone, Environment, Km, MissionType,
+    PlatformCapabilities, PlatformType, SensorStatus, SensorType, WeaponState, WeaponStatus,
+    WeaponType, Waypoint, WaypointStatus, WaypointType,
+};
+
+/// Most drones a template may provision
+pub const MAX_TEMPLATE_DRONES: usize = 16;
+
+/// Vertical separation between consecutive drones' plans
+const ALTITUDE_STEP_M: f64 = 150.0;
+
+/// Lateral spacing between consecutive drones' tracks
+const TRACK_SPACING_KM: f64 = 1.0;
+
+/// Loiter time at each ISR/SAR orbit point
+const ORBIT_LOITER_MIN: i32 = 30;
+
+const WAYPOINT_NAMES: [&str; 10] = [
+    "ALPHA", "BRAVO", "CHARLIE", "DELTA", "ECHO", "FOXTROT", "GOLF", "HOTEL", "INDIA", "JULIET",
+];
+
+/// Built-in convoy templates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TemplateKind {
+    /// Four MQ-9 Reapers on a strike mission
+    Strike4Ship,
+    /// RQ-4 Global Hawk and MQ-1C Gray Eagle on an ISR mission
+    Isr2Ship,
+    /// A single MQ-9 Reaper, shaped by overrides
+    Custom,
+}
+
+/// One drone position in a template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSlot {
+    pub platform_type: PlatformType,
+    pub callsign_prefix: String,
+}
+
+impl TemplateSlot {
+    fn new(platform_type: PlatformType, callsign_prefix: &str) -> Self {
+        Self {
+            platform_type,
+            callsign_prefix: callsign_prefix.to_string(),
+        }
+    }
+}
+
+/// Convoy shape a template provisions
+///
+/// Deployments can replace the built-in definitions with their own, loaded
+/// as JSON in this shape.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConvoyTemplate {
+    pub kind: TemplateKind,
+    pub callsign: String,
+    pub mission_type: MissionType,
+    pub aor_name: String,
+    pub aor_center: Coordinates,
+    pub aor_radius_km: Km,
+    pub roe_profile: String,
+    pub slots: Vec<TemplateSlot>,
+}
+
+/// Caller adjustments applied on top of a template
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateOverrides {
+    pub callsign: Option<String>,
+    pub mission_type: Option<MissionType>,
+    pub aor_name: Option<String>,
+    pub aor_center: Option<Coordinates>,
+    pub aor_radius_km: Option<Km>,
+    pub roe_profile: Option<String>,
+    /// Resize the roster, repeating the template's slots in order
+    pub drone_count: Option<usize>,
+    /// Fly every slot on this platform
+    pub platform_type: Option<PlatformType>,
+    /// Callsign prefix for every drone
+    pub callsign_prefix: Option<String>,
+}
+
+/// A drone ready to register, with its loadout and route
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvisionedDrone {
+    pub drone: Drone,
+    pub waypoints: Vec<Waypoint>,
+}
+
+/// Everything a template provisions
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvisionPlan {
+    pub convoy: Convoy,
+    pub drones: Vec<ProvisionedDrone>,
+}
+
+impl ConvoyTemplate {
+    /// The built-in definition of `kind`
+    #[must_use]
+    pub fn builtin(kind: TemplateKind) -> Self {
+        let base = Self {
+            kind,
+            callsign: "DEMO".to_string(),
+            mission_type: MissionType::Strike,
+            aor_name: "KANDAHAR".to_string(),
+            aor_center: Coordinates::new(31.6289, 65.7372, 0.0),
+            aor_radius_km: Km(25.0),
+            roe_profile: "STANDARD".to_string(),
+            slots: vec![TemplateSlot::new(PlatformType::Mq9Reaper, "REAPER")],
+        };
+        match kind {
+            TemplateKind::Strike4Ship => Self {
+                callsign: "HAMMER".to_string(),
+                slots: vec![TemplateSlot::new(PlatformType::Mq9Reaper, "REAPER"); 4],
+                ..base
+            },
+            TemplateKind::Isr2Ship => Self {
+                callsign: "OVERWATCH".to_string(),
+                mission_type: MissionType::Isr,
+                aor_radius_km: Km(40.0),
+                roe_profile: "OBSERVE_ONLY".to_string(),
+                slots: vec![
+                    TemplateSlot::new(PlatformType::Rq4GlobalHawk, "HAWK"),
+                    TemplateSlot::new(PlatformType::Mq1cGrayEagle, "EAGLE"),
+                ],
+                ..base
+            },
+            TemplateKind::Custom => base,
+        }
+    }
+
+    /// Apply `overrides`, rejecting an empty roster, too many drones or a
+    /// non-positive AOR radius
+    pub fn with_overrides(mut self, overrides: TemplateOverrides) -> Result<Self, DomainError> {
+        let invalid = |msg: String| Err(DomainError::InvalidTemplate(msg));
+
+        if let Some(callsign) = overrides.callsign {
+            self.callsign = Drone::normalize_identifier(&callsign);
+        }
+        if self.callsign.is_empty() {
+            return invalid("convoy callsign is required".to_string());
+        }
+        if let Some(mission_type) = overrides.mission_type {
+            self.mission_type = mission_type;
+        }
+        if let Some(aor_name) = overrides.aor_name {
+            self.aor_name = aor_name;
+        }
+        if let Some(center) = overrides.aor_center {
+            self.aor_center = center;
+        }
+        if let Some(radius) = overrides.aor_radius_km {
+            if radius.value() <= 0.0 {
+                return invalid(format!("AOR radius must be positive, got {radius}"));
+            }
+            self.aor_radius_km = radius;
+        }
+        if let Some(roe_profile) = overrides.roe_profile {
+            self.roe_profile = roe_profile;
+        }
+        if let Some(count) = overrides.drone_count {
+            if !(1..=MAX_TEMPLATE_DRONES).contains(&count) {
+                return invalid(format!(
+                    "drone count must be 1-{MAX_TEMPLATE_DRONES}, got {count}"
+                ));
+            }
+            self.slots = self.slots.iter().cycle().take(count).cloned().collect();
+        }
+        for slot in &mut self.slots {
+            if let Some(platform_type) = overrides.platform_type {
+                slot.platform_type = platform_type;
+            }
+            if let Some(prefix) = &overrides.callsign_prefix {
+                slot.callsign_prefix = Drone::normalize_identifier(prefix);
+            }
+        }
+        if self.slots.is_empty() {
+            return invalid("template has no drone slots".to_string());
+        }
+        Ok(self)
+    }
+
+    /// Build the convoy, drones and waypoint plans for `commanding_unit`.
+    ///
+    /// Callsigns are `<prefix>-<nn>` numbered across the roster; tail
+    /// numbers derive from the drone ID so they do not collide with other
+    /// convoys.
+    #[must_use]
+    pub fn provision(&self, commanding_unit: &str) -> ProvisionPlan {
+        let convoy_id = Uuid::new_v4();
+        let drones: Vec<ProvisionedDrone> = self
+            .slots
+            .iter()
+            .enumerate()
+            .map(|(index, slot)| {
+                let callsign = format!("{}-{:02}", slot.callsign_prefix, index + 1);
+                let serial = Uuid::new_v4();
+                let mut drone = Drone::register(
+                    convoy_id,
+                    &callsign,
+                    &format!("DG-{}", &serial.simple().to_string()[..6]),
+                    slot.platform_type,
+                    serial.to_string(),
+                );
+                drone.current_position = self.aor_center;
+                drone.weapons = platform_loadout(slot.platform_type);
+                drone.sensors = platform_sensors(slot.platform_type, self.mission_type);
+                let waypoints = self.route(&drone, index);
+                ProvisionedDrone { drone, waypoints }
+            })
+            .collect();
+
+        let convoy = Convoy {
+            convoy_id,
+            convoy_callsign: self.callsign.clone(),
+            mission_id: Uuid::new_v4(),
+            mission_type: self.mission_type,
+            status: ConvoyStatus::Planning,
+            created_at: Utc::now(),
+            mission_start: None,
+            mission_end: None,
+            aor_name: self.aor_name.clone(),
+            aor_center: self.aor_center,
+            aor_radius_km: self.aor_radius_km.value() as f32,
+            commanding_unit: commanding_unit.to_string(),
+            authorization_level: "STANDARD".to_string(),
+            roe_profile: self.roe_profile.clone(),
+            environment: Environment::default(),
+            drone_ids: drones.iter().map(|d| d.drone.drone_id).collect(),
+            drone_count: i16::try_from(drones.len()).unwrap_or(i16::MAX),
+        };
+
+        ProvisionPlan { convoy, drones }
+    }
+
+    /// Waypoint plan for the `index`th drone: ingress from the south, the
+    /// mission-specific legs over the AOR, then egress and return.
+    ///
+    /// Each drone flies its own track and altitude so the plans start out
+    /// deconflicted.
+    fn route(&self, drone: &Drone, index: usize) -> Vec<Waypoint> {
+        let r = self.aor_radius_km.value();
+        // Alternate tracks either side of the centreline
+        let lane = (index / 2 + 1) as f64 * if index.is_multiple_of(2) { 1.0 } else { -1.0 };
+        let east = lane * TRACK_SPACING_KM;
+        let altitude = cruise_altitude_m(drone.platform_type) + index as f64 * ALTITUDE_STEP_M;
+        let point = |north: f64, east: f64| offset(self.aor_center, north, east, altitude);
+
+        let mut legs = vec![
+            (WaypointType::Nav, point(-2.0 * r, east), None),
+            (WaypointType::Checkpoint, point(-r, east), None),
+        ];
+        match self.mission_type {
+            MissionType::Strike => {
+                legs.push((WaypointType::Strike, point(0.0, east * 0.5), None));
+            }
+            MissionType::Isr | MissionType::Sar => {
+                // Orbit at two-thirds radius, drones starting a quarter apart
+                let orbit = r * 2.0 / 3.0;
+                for step in 0..4 {
+                    let bearing = (index as f64 * 90.0 / self.slots.len() as f64
+                        + f64::from(step) * 90.0)
+                        .to_radians();
+                    legs.push((
+                        WaypointType::Loiter,
+                        point(orbit * bearing.cos(), orbit * bearing.sin()),
+                        Some(ORBIT_LOITER_MIN),
+                    ));
+                }
+            }
+            MissionType::Escort => {
+                legs.push((WaypointType::Rendezvous, point(-r / 2.0, east), None));
+                legs.push((WaypointType::Nav, point(0.0, east), None));
+            }
+            MissionType::Resupply => {
+                legs.push((WaypointType::Refuel, point(0.0, east), None));
+            }
+        }
+        legs.push((WaypointType::Nav, point(-r, east + r / 2.0), None));
+        legs.push((WaypointType::Checkpoint, point(-2.0 * r, east), None));
+
+        legs.into_iter()
+            .zip(1_i16..)
+            .map(|((waypoint_type, coordinates, loiter), sequence_number)| Waypoint {
+                drone_id: drone.drone_id,
+                sequence_number,
+                waypoint_id: Uuid::new_v4(),
+                waypoint_name: WAYPOINT_NAMES
+                    .get(usize::from(sequence_number.unsigned_abs()) - 1)
+                    .map_or_else(|| format!("WP{sequence_number:02}"), |n| (*n).to_string()),
+                waypoint_type,
+                coordinates,
+                planned_arrival: None,
+                actual_arrival: None,
+                planned_departure: None,
+                actual_departure: None,
+                loiter_duration_min: loiter,
+                authorized_actions: authorized_actions(waypoint_type),
+                status: WaypointStatus::Pending,
+            })
+            .collect()
+    }
+}
+
+/// Weapons a platform is loaded with on provisioning
+#[must_use]
+pub fn platform_loadout(platform_type: PlatformType) -> Vec<WeaponStatus> {
+    let stores: &[(WeaponType, i16)] = match platform_type {
+        PlatformType::Mq9Reaper => &[(WeaponType::Agm114Hellfire, 4), (WeaponType::Gbu12Paveway, 2)],
+        PlatformType::Mq1cGrayEagle => {
+            &[(WeaponType::Agm114Hellfire, 4), (WeaponType::Agm176Griffin, 2)]
+        }
+        PlatformType::Rq4GlobalHawk | PlatformType::Mq25Stingray => &[],
+    };
+    stores
+        .iter()
+        .map(|&(weapon_type, rounds_remaining)| WeaponStatus {
+            weapon_type,
+            rounds_remaining,
+            status: WeaponState::Armed,
+        })
+        .collect()
+}
+
+/// Sensors a platform carries, in the mode the mission starts them in
+#[must_use]
+pub fn platform_sensors(platform_type: PlatformType, mission_type: MissionType) -> Vec<SensorStatus> {
+    let wide_area = matches!(mission_type, MissionType::Isr | MissionType::Sar);
+    PlatformCapabilities::builtin(platform_type)
+        .sensors
+        .into_iter()
+        .map(|sensor_type| SensorStatus {
+            sensor_type,
+            operational: true,
+            mode: match (sensor_type, wide_area) {
+                (SensorType::Sar, true) => "GMTI",
+                (_, true) | (SensorType::EoIr, false) => "WIDE_AREA",
+                _ => "STANDBY",
+            }
+            .to_string(),
+        })
+        .collect()
+}
+
+fn cruise_altitude_m(platform_type: PlatformType) -> f64 {
+    match platform_type {
+        PlatformType::Mq9Reaper => 7500.0,
+        PlatformType::Mq1cGrayEagle => 5500.0,
+        PlatformType::Rq4GlobalHawk => 16_000.0,
+        PlatformType::Mq25Stingray => 9000.0,
+    }
+}
+
+fn authorized_actions(waypoint_type: WaypointType) -> Vec<String> {
+    let actions: &[&str] = match waypoint_type {
+        WaypointType::Strike => &["ENGAGE", "OBSERVE"],
+        WaypointType::Loiter => &["OBSERVE", "RELAY"],
+        WaypointType::Refuel => &["REFUEL"],
+        WaypointType::Rendezvous => &["RELAY"],
+        WaypointType::Nav | WaypointType::Checkpoint => &[],
+    };
+    actions.iter().map(|a| (*a).to_string()).collect()
+}
+
+/// `center` moved `north_km` and `east_km` at `altitude_m`
+fn offset(center: Coordinates, north_km: f64, east_km: f64, altitude_m: f64) -> Coordinates {
+    let km_per_deg_lon = KM_PER_DEG_LAT * center.latitude.to_radians().cos().max(0.01);
+    Coordinates::new(
+        center.latitude + north_km / KM_PER_DEG_LAT,
+        center.longitude + east_km / km_per_deg_lon,
+        altitude_m,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strike_template_provisions_armed_roster() {
+        let plan = ConvoyTemplate::builtin(TemplateKind::Strike4Ship).provision("VMU-1");
+
+        assert_eq!(plan.convoy.drone_count, 4);
+        assert_eq!(plan.convoy.mission_type, MissionType::Strike);
+        assert_eq!(plan.drones[3].drone.callsign, "REAPER-04");
+        for provisioned in &plan.drones {
+            assert_eq!(provisioned.drone.convoy_id, plan.convoy.convoy_id);
+            assert_eq!(provisioned.drone.weapons.len(), 2);
+            assert!(provisioned
+                .waypoints
+                .iter()
+                .any(|w| w.waypoint_type == WaypointType::Strike));
+        }
+        // Each drone flies its own altitude
+        let alt = |i: usize| plan.drones[i].waypoints[0].coordinates.altitude_m;
+        assert!(alt(1) > alt(0));
+    }
+
+    #[test]
+    fn test_overrides_reshape_template() {
+        let template = ConvoyTemplate::builtin(TemplateKind::Isr2Ship)
+            .with_overrides(TemplateOverrides {
+                callsign: Some("sentry".to_string()),
+                drone_count: Some(3),
+                ..TemplateOverrides::default()
+            })
+            .unwrap();
+        let plan = template.provision("VMU-1");
+
+        assert_eq!(plan.convoy.convoy_callsign, "SENTRY");
+        let platforms: Vec<_> = plan.drones.iter().map(|d| d.drone.platform_type).collect();
+        assert_eq!(
+            platforms,
+            [PlatformType::Rq4GlobalHawk, PlatformType::Mq1cGrayEagle, PlatformType::Rq4GlobalHawk]
+        );
+        assert!(plan.drones[0].drone.weapons.is_empty());
+        assert_eq!(plan.drones[0].waypoints.iter().filter(|w| w.loiter_duration_min.is_some()).count(), 4);
+
+        // Configured definitions load from JSON
+        let json = serde_json::to_string(&template).unwrap();
+        assert_eq!(serde_json::from_str::<ConvoyTemplate>(&json).unwrap(), template);
+
+        let custom = ConvoyTemplate::builtin(TemplateKind::Custom);
+        let too_many = TemplateOverrides {
+            drone_count: Some(MAX_TEMPLATE_DRONES + 1),
+            ..TemplateOverrides::default()
+        };
+        assert!(custom.clone().with_overrides(too_many).is_err());
+        let ground = TemplateOverrides {
+            aor_radius_km: Some(Km(0.0)),
+            ..TemplateOverrides::default()
+        };
+        assert!(custom.with_overrides(ground).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! Flight track simplification.
+//!
+//! Reduces a recorded telemetry track to a renderable polyline with the
+//! Ramer-Douglas-Peucker algorithm, keeping the most significant points
+//! first so the result can be capped at a point budget.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::formation::KM_PER_DEG_LAT;
+use crate::Coordinates;
+
+/// Points closer than this to the simplified line are never kept
+const MIN_DEVIATION_M: f64 = 1.0;
+
+/// Recorded position on a drone's track
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrackPoint {
+    pub recorded_at: DateTime<Utc>,
+    pub position: Coordinates,
+}
+
+/// Segment of the simplified track awaiting a split
+struct Split {
+    /// Distance of the farthest point from the segment
+    deviation_m: f64,
+    start: usize,
+    end: usize,
+    farthest: usize,
+}
+
+impl PartialEq for Split {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Split {}
+
+impl PartialOrd for Split {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Split {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deviation_m.total_cmp(&other.deviation_m)
+    }
+}
+
+/// Simplify a time-ordered track to at most `max_points` points.
+///
+/// Starts from the two endpoints and repeatedly adds the point that
+/// deviates most from the current polyline (in 3D, altitude included),
+/// stopping at the budget or once no point deviates by a metre. The result
+/// keeps the input order.
+#[must_use]
+pub fn simplify_track(points: &[TrackPoint], max_points: usize) -> Vec<TrackPoint> {
+    let max_points = max_points.max(2);
+    if points.len() <= 2 {
+        return points.to_vec();
+    }
+
+    let origin = points[0].position;
+    let km_per_deg_lon = KM_PER_DEG_LAT * origin.latitude.to_radians().cos();
+    let local: Vec<[f64; 3]> = points
+        .iter()
+        .map(|p| {
+            [
+                (p.position.longitude - origin.longitude) * km_per_deg_lon * 1000.0,
+                (p.position.latitude - origin.latitude) * KM_PER_DEG_LAT * 1000.0,
+                p.position.altitude_m,
+            ]
+        })
+        .collect();
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut kept = 2;
+
+    let mut heap = BinaryHeap::new();
+    heap.extend(split(&local, 0, points.len() - 1));
+    while kept < max_points {
+        let Some(next) = heap.pop() else {
+            break;
+        };
+        if next.deviation_m < MIN_DEVIATION_M {
+            break;
+        }
+        keep[next.farthest] = true;
+        kept += 1;
+        heap.extend(split(&local, next.start, next.farthest));
+        heap.extend(split(&local, next.farthest, next.end));
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(p, keep)| keep.then_some(*p))
+        .collect()
+}
+
+/// Farthest interior point of a segment, if it has any
+fn split(local: &[[f64; 3]], start: usize, end: usize) -> Option<Split> {
+    ((start + 1)..end)
+        .map(|i| Split {
+            deviation_m: distance_to_segment(local[i], local[start], local[end]),
+            start,
+            end,
+            farthest: i,
+        })
+        .max()
+}
+
+/// Distance from `p` to the segment `a`-`b`
+fn distance_to_segment(p: [f64; 3], a: [f64; 3], b: [f64; 3]) -> f64 {
+    let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let ap = [p[0] - a[0], p[1] - a[1], p[2] - a[2]];
+    let len_sq = ab.iter().map(|v| v * v).sum::<f64>();
+    let t = if len_sq > 0.0 {
+        (ap.iter().zip(&ab).map(|(x, y)| x * y).sum::<f64>() / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    ap.iter()
+        .zip(&ab)
+        .map(|(x, y)| (x - t * y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn track(coords: &[(f64, f64)]) -> Vec<TrackPoint> {
+        let start = Utc::now();
+        coords
+            .iter()
+            .enumerate()
+            .map(|(i, &(lat, lon))| TrackPoint {
+                recorded_at: start + Duration::seconds(i as i64),
+                position: Coordinates::new(lat, lon, 3000.0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_straight_track_collapses_to_endpoints() {
+        let points = track(&[
+            (31.50, 65.80),
+            (31.51, 65.80),
+            (31.52, 65.80),
+            (31.53, 65.80),
+        ]);
+        let simplified = simplify_track(&points, 100);
+
+        assert_eq!(simplified, vec![points[0], points[3]]);
+    }
+
+    #[test]
+    fn test_budget_keeps_most_significant_points() {
+        // An L-shaped track with a small wiggle on the first leg
+        let points = track(&[
+            (31.500, 65.800),
+            (31.510, 65.8001),
+            (31.520, 65.800),
+            (31.520, 65.820),
+            (31.520, 65.840),
+        ]);
+
+        let simplified = simplify_track(&points, 3);
+        assert_eq!(simplified, vec![points[0], points[2], points[4]]);
+
+        let all = simplify_track(&points, 10);
+        assert_eq!(all.len(), 4);
+        assert!(all.windows(2).all(|w| w[0].recorded_at < w[1].recorded_at));
+    }
+}
@@ -0,0 +1,236 @@
+//! Unit-safe measurement types.
+//!
+//! Distances, speeds and percentages wrapped in newtypes so that a range in
+//! kilometres cannot be compared with an altitude in metres by accident.
+//! Conversions between units are explicit (`Meters::from(km)`), and each
+//! type serializes as its bare number so stored and wire formats are
+//! unchanged.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use serde::{Deserialize, Serialize};
+
+/// Metres per kilometre
+const METERS_PER_KM: f64 = 1000.0;
+
+macro_rules! unit {
+    ($(#[$meta:meta])* $name:ident($inner:ty), $suffix:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub $inner);
+
+        impl $name {
+            pub const ZERO: Self = Self(0.0);
+
+            /// Raw value in this unit
+            #[must_use]
+            pub const fn value(self) -> $inner {
+                self.0
+            }
+
+            #[must_use]
+            pub fn abs(self) -> Self {
+                Self(self.0.abs())
+            }
+
+            #[must_use]
+            pub fn min(self, other: Self) -> Self {
+                Self(self.0.min(other.0))
+            }
+
+            #[must_use]
+            pub fn max(self, other: Self) -> Self {
+                Self(self.0.max(other.0))
+            }
+
+            /// Total ordering, for sorting (see `f64::total_cmp`)
+            #[must_use]
+            pub fn total_cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<$inner> for $name {
+            type Output = Self;
+            fn mul(self, rhs: $inner) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Div<$inner> for $name {
+            type Output = Self;
+            fn div(self, rhs: $inner) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        /// Ratio of two quantities in the same unit
+        impl Div for $name {
+            type Output = $inner;
+            fn div(self, rhs: Self) -> $inner {
+                self.0 / rhs.0
+            }
+        }
+
+        impl Sum for $name {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self(iter.map(|v| v.0).sum())
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match f.precision() {
+                    Some(p) => write!(f, "{:.*} {}", p, self.0, $suffix),
+                    None => write!(f, "{} {}", self.0, $suffix),
+                }
+            }
+        }
+    };
+}
+
+unit!(
+    /// Distance in kilometres (ranges, spacing, ground track)
+    Km(f64),
+    "km"
+);
+
+unit!(
+    /// Distance in metres (altitude, vertical separation)
+    Meters(f64),
+    "m"
+);
+
+unit!(
+    /// Speed in metres per second
+    Mps(f32),
+    "m/s"
+);
+
+unit!(
+    /// Percentage, 0 - 100
+    Percent(f32),
+    "%"
+);
+
+impl From<Meters> for Km {
+    fn from(m: Meters) -> Self {
+        Self(m.0 / METERS_PER_KM)
+    }
+}
+
+impl From<Km> for Meters {
+    fn from(km: Km) -> Self {
+        Self(km.0 * METERS_PER_KM)
+    }
+}
+
+impl Mps {
+    /// Seconds needed to cover `distance`; `None` when not moving
+    #[must_use]
+    pub fn seconds_to_cover(self, distance: impl Into<Meters>) -> Option<f64> {
+        let speed = f64::from(self.0);
+        (speed > 0.0).then(|| distance.into().0 / speed)
+    }
+
+    /// Distance covered in `secs` seconds
+    #[must_use]
+    pub fn distance_in(self, secs: f64) -> Meters {
+        Meters(f64::from(self.0) * secs)
+    }
+}
+
+impl Percent {
+    /// `part` as a percentage of `whole`; zero when `whole` is zero
+    #[must_use]
+    pub fn of(part: i64, whole: i64) -> Self {
+        if whole > 0 {
+            Self(part as f32 / whole as f32 * 100.0)
+        } else {
+            Self::ZERO
+        }
+    }
+
+    /// Fraction in 0.0 - 1.0 (unclamped)
+    #[must_use]
+    pub fn fraction(self) -> f64 {
+        f64::from(self.0) / 100.0
+    }
+
+    /// Clamped to 0 - 100
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        Self(self.0.clamp(0.0, 100.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions_and_arithmetic() {
+        assert_eq!(Meters::from(Km(1.5)), Meters(1500.0));
+        assert_eq!(Km::from(Meters(250.0)), Km(0.25));
+        assert_eq!(Km(2.0) + Km::from(Meters(500.0)), Km(2.5));
+        assert_eq!(Km(3.0) / Km(1.5), 2.0);
+        assert_eq!([Km(1.0), Km(2.0)].into_iter().sum::<Km>(), Km(3.0));
+
+        assert_eq!(Mps(100.0).seconds_to_cover(Km(1.0)), Some(10.0));
+        assert_eq!(Mps(0.0).seconds_to_cover(Meters(1.0)), None);
+
+        assert_eq!(Percent::of(3, 4), Percent(75.0));
+        assert_eq!(Percent::of(1, 0), Percent::ZERO);
+        assert_eq!(Percent(140.0).clamped().fraction(), 1.0);
+        assert_eq!(format!("{:.1}", Km(1.26)), "1.3 km");
+        assert_eq!(serde_json::to_string(&Meters(120.0)).unwrap(), "120.0");
+    }
+}
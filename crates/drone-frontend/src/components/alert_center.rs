@@ -0,0 +1,161 @@
+//! # Alert Center Component
+//!
+//! Active and historical alerts with severity filters and acknowledgement.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::services::{acknowledge_alert, fetch_alerts};
+use crate::state::{use_app_state, Alert, AlertSeverity};
+
+/// Alerts loaded when a convoy is selected
+const ALERT_HISTORY_LIMIT: u32 = 100;
+
+/// Alert center panel
+#[component]
+pub fn AlertCenterPanel() -> impl IntoView {
+    let state = use_app_state();
+    let show_info = RwSignal::new(true);
+    let show_warning = RwSignal::new(true);
+    let show_critical = RwSignal::new(true);
+    let show_acknowledged = RwSignal::new(false);
+
+    // Reload history whenever the convoy changes
+    let load_state = state.clone();
+    Effect::new(move |_| {
+        let Some(convoy_id) = load_state.selected_convoy.get() else {
+            return;
+        };
+        let state = load_state.clone();
+        spawn_local(async move {
+            match fetch_alerts(convoy_id, ALERT_HISTORY_LIMIT, true).await {
+                Ok(alerts) => state.alert_log.set(alerts),
+                Err(e) => log::warn!("Alert history fetch failed: {}", e),
+            }
+        });
+    });
+
+    let visible = move || {
+        state
+            .alert_log
+            .get()
+            .into_iter()
+            .filter(|a| show_acknowledged.get() || !a.acknowledged)
+            .filter(|a| match a.severity {
+                AlertSeverity::Info => show_info.get(),
+                AlertSeverity::Warning => show_warning.get(),
+                AlertSeverity::Critical => show_critical.get(),
+            })
+            .collect::<Vec<_>>()
+    };
+    let open_count = move || state.alert_log.get().iter().filter(|a| !a.acknowledged).count();
+
+    let sound = state.alert_sound;
+    let toggle_sound = {
+        let state = state.clone();
+        move |_| state.set_alert_sound(!sound.get_untracked())
+    };
+
+    view! {
+        <div class="panel">
+            <div class="panel-header">
+                <span class="panel-title">"ALERT CENTER"</span>
+                <span class="panel-badge">{open_count}" OPEN"</span>
+            </div>
+            <div class="alert-filters">
+                <SeverityToggle severity=AlertSeverity::Info enabled=show_info />
+                <SeverityToggle severity=AlertSeverity::Warning enabled=show_warning />
+                <SeverityToggle severity=AlertSeverity::Critical enabled=show_critical />
+                <button
+                    class="btn btn-sm"
+                    class:active=move || show_acknowledged.get()
+                    on:click=move |_| show_acknowledged.update(|v| *v = !*v)
+                >
+                    "ACK'D"
+                </button>
+                <button class="btn btn-sm" class:active=move || sound.get() on:click=toggle_sound>
+                    {move || if sound.get() { "AUDIO ON" } else { "AUDIO OFF" }}
+                </button>
+            </div>
+            <div class="panel-body no-padding">
+                <div class="alert-list">
+                    <For
+                        each=visible
+                        key=|alert| (alert.id, alert.acknowledged)
+                        children=move |alert| view! { <AlertRow alert=alert /> }
+                    />
+                    {move || visible().is_empty().then(|| view! {
+                        <div style="padding: 24px; text-align: center; color: var(--text-muted);">
+                            "No alerts"
+                        </div>
+                    })}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// Filter button for one severity
+#[component]
+fn SeverityToggle(severity: AlertSeverity, enabled: RwSignal<bool>) -> impl IntoView {
+    view! {
+        <button
+            class=format!("btn btn-sm severity-toggle {}", severity.class())
+            class:active=move || enabled.get()
+            on:click=move |_| enabled.update(|v| *v = !*v)
+        >
+            {severity.label()}
+        </button>
+    }
+}
+
+/// Single alert with its acknowledge button
+#[component]
+fn AlertRow(alert: Alert) -> impl IntoView {
+    let state = use_app_state();
+    let alert_id = alert.id;
+    let pending = RwSignal::new(false);
+
+    let on_ack = move |_| {
+        let Some(convoy_id) = state.selected_convoy.get_untracked() else {
+            return;
+        };
+        let state = state.clone();
+        pending.set(true);
+        spawn_local(async move {
+            match acknowledge_alert(convoy_id, alert_id).await {
+                Ok(acked) => state.acknowledge_alert(alert_id, acked.acknowledged_by),
+                Err(e) => {
+                    log::warn!("Acknowledge failed for alert {}: {}", alert_id, e);
+                    pending.set(false);
+                }
+            }
+        });
+    };
+
+    let time_str = alert.timestamp.format("%H:%M:%S").to_string();
+    let acked_by = alert.acknowledged_by.clone().unwrap_or_default();
+
+    view! {
+        <div class="alert-item" class:acknowledged=alert.acknowledged>
+            <span class=format!("status-dot {}", alert.severity.class())></span>
+            <div class="alert-info">
+                <div class="alert-type">{alert.alert_type.clone()}</div>
+                <div class="alert-message">{alert.message.clone()}</div>
+            </div>
+            <div class="alert-meta">
+                <div class="engagement-time">{time_str}"Z"</div>
+                {if alert.acknowledged {
+                    view! { <span class="text-xs text-muted">"ACK "{acked_by}</span> }.into_any()
+                } else {
+                    view! {
+                        <button class="btn btn-sm" disabled=move || pending.get() on:click=on_ack>
+                            "ACK"
+                        </button>
+                    }
+                    .into_any()
+                }}
+            </div>
+        </div>
+    }
+}
@@ -3,133 +3,305 @@
 //! Real-time charts using Charming (ECharts wrapper).
 
 use charming::{
-    component::{Axis, Grid, Legend, Title},
+    component::{
+        Axis, DataZoom, DataZoomType, Feature, Grid, Restore, SaveAsImage, SaveAsImageType, Title,
+        Toolbox, ToolboxDataZoom,
+    },
     element::{AreaStyle, AxisType, LineStyle, Tooltip, Trigger},
     series::Line,
-    Chart, WasmRenderer,
+    Chart, Echarts, WasmRenderer,
 };
+use chrono::{Duration, Utc};
 use leptos::prelude::*;
+use leptos::task::spawn_local;
 
-use crate::state::use_app_state;
+use crate::services::fetch_telemetry_history;
+use crate::state::{use_app_state, ConvoyOverview, TelemetrySample};
+
+/// Telemetry history loaded when a drone is selected
+const HISTORY_WINDOW_MIN: i64 = 60;
+
+/// Server-side aggregation bucket for the history window
+const HISTORY_RESOLUTION_SEC: u32 = 10;
+
+/// Maximum history points requested
+const HISTORY_LIMIT: u32 = 720;
+
+/// Metric plotted by the telemetry chart
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TelemetryMetric {
+    Altitude,
+    Fuel,
+    Speed,
+    EngineTemp,
+}
+
+impl TelemetryMetric {
+    pub const ALL: [Self; 4] = [Self::Altitude, Self::Fuel, Self::Speed, Self::EngineTemp];
+
+    /// Selector button label
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Altitude => "ALT",
+            Self::Fuel => "FUEL",
+            Self::Speed => "SPD",
+            Self::EngineTemp => "TEMP",
+        }
+    }
+
+    /// Series and axis name
+    pub fn series_name(self) -> &'static str {
+        match self {
+            Self::Altitude => "Altitude (m)",
+            Self::Fuel => "Fuel (%)",
+            Self::Speed => "Speed (m/s)",
+            Self::EngineTemp => "Engine Temp (C)",
+        }
+    }
+
+    /// Line and area fill colors
+    fn colors(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Altitude => ("#00ff41", "rgba(0, 255, 65, 0.1)"),
+            Self::Fuel => ("#ffaa00", "rgba(255, 170, 0, 0.1)"),
+            Self::Speed => ("#00ccff", "rgba(0, 204, 255, 0.1)"),
+            Self::EngineTemp => ("#ff4444", "rgba(255, 68, 68, 0.1)"),
+        }
+    }
+
+    /// Value of this metric for a sample, if reported
+    pub fn value(self, sample: &TelemetrySample) -> Option<f64> {
+        match self {
+            Self::Altitude => Some(sample.altitude_m),
+            Self::Fuel => Some(f64::from(sample.fuel_pct)),
+            Self::Speed => Some(f64::from(sample.speed_mps)),
+            Self::EngineTemp => sample.engine_temp_c.map(f64::from),
+        }
+    }
+}
 
 /// Telemetry chart panel
+///
+/// Loads aggregated history for the selected drone, then appends live
+/// points from the telemetry subscription. Scroll or drag to pan and zoom;
+/// the toolbox exports the current view as PNG.
 #[component]
 pub fn TelemetryChartPanel() -> impl IntoView {
     let state = use_app_state();
     let chart_id = "telemetry-chart";
+    let selected_drone = state.selected_drone;
+    let telemetry = state.telemetry;
+    let resync = state.resync;
+    let metric = RwSignal::new(TelemetryMetric::Altitude);
+    let chart = StoredValue::new_local(None::<Echarts>);
 
-    // Sample data for demonstration
-    let altitude_data: Vec<f64> = vec![
-        4800.0, 4900.0, 5000.0, 5100.0, 5050.0, 5000.0, 4950.0, 5000.0,
-        5100.0, 5200.0, 5150.0, 5100.0, 5000.0, 4900.0, 5000.0, 5100.0,
-    ];
-
-    let fuel_data: Vec<f64> = vec![
-        95.0, 93.0, 91.0, 89.0, 87.0, 85.0, 83.0, 81.0,
-        79.0, 77.0, 75.0, 73.0, 71.0, 69.0, 67.0, 65.0,
-    ];
+    // Reload history whenever the selected drone changes or connectivity
+    // returns; points already held for the drone (e.g. from the offline
+    // cache) stay visible until the history arrives
+    Effect::new(move |_| {
+        resync.track();
+        let selected = selected_drone.get();
+        if telemetry.with_untracked(|series| series.first().is_some_and(|p| Some(p.drone_id) != selected)) {
+            telemetry.set(Vec::new());
+        }
+        let Some(drone_id) = selected else {
+            return;
+        };
+        spawn_local(async move {
+            let end = Utc::now();
+            let start = end - Duration::minutes(HISTORY_WINDOW_MIN);
+            let history = match fetch_telemetry_history(
+                drone_id,
+                start,
+                end,
+                Some(HISTORY_RESOLUTION_SEC),
+                HISTORY_LIMIT,
+            )
+            .await
+            {
+                Ok(history) => history,
+                Err(e) => {
+                    log::warn!("Telemetry history fetch failed for drone {}: {}", drone_id, e);
+                    return;
+                }
+            };
+            if selected_drone.get_untracked() != Some(drone_id) {
+                return;
+            }
 
-    let waypoints: Vec<String> = (1..=16).map(|i| format!("WP{}", i)).collect();
+            // Keep live points that arrived while history was loading
+            telemetry.update(|series| {
+                let live = std::mem::replace(series, history);
+                let last = series.last().map(|s| s.recorded_at);
+                series.extend(live.into_iter().filter(|p| last.is_none_or(|t| p.recorded_at > t)));
+            });
+        });
+    });
 
-    // Build chart on mount
+    // Redraw on new points or metric changes; updates keep the zoom window
     Effect::new(move |_| {
-        let chart = Chart::new()
-            .title(
-                Title::new()
-                    .text("FLIGHT TELEMETRY")
-                    .text_style(charming::element::TextStyle::new().color("#00ff41").font_size(12))
-                    .left("center"),
-            )
-            .tooltip(Tooltip::new().trigger(Trigger::Axis))
-            .legend(
-                Legend::new()
-                    .data(vec!["Altitude (m)", "Fuel (%)"])
-                    .text_style(charming::element::TextStyle::new().color("#99cc99"))
-                    .bottom(0),
-            )
-            .grid(
-                Grid::new()
-                    .left("10%")
-                    .right("10%")
-                    .top("15%")
-                    .bottom("20%"),
-            )
-            .x_axis(
-                Axis::new()
-                    .type_(AxisType::Category)
-                    .data(waypoints.clone())
-                    .axis_line(charming::element::AxisLine::new().line_style((1.0, "#557755")))
-                    .axis_label(charming::element::AxisLabel::new().color("#557755")),
-            )
-            .y_axis(
-                Axis::new()
-                    .type_(AxisType::Value)
-                    .name("Altitude (m)")
-                    .axis_line(charming::element::AxisLine::new().line_style((1.0, "#557755")))
-                    .axis_label(charming::element::AxisLabel::new().color("#557755"))
-                    .split_line(charming::element::SplitLine::new().line_style(LineStyle::new().color("#1a2a1a"))),
-            )
-            .series(
-                Line::new()
-                    .name("Altitude (m)")
-                    .data(altitude_data.clone())
-                    .smooth(true)
-                    .line_style(LineStyle::new().color("#00ff41").width(2))
-                    .area_style(AreaStyle::new().color("rgba(0, 255, 65, 0.1)")),
-            )
-            .series(
-                Line::new()
-                    .name("Fuel (%)")
-                    .data(fuel_data.clone())
-                    .smooth(true)
-                    .line_style(LineStyle::new().color("#ffaa00").width(2))
-                    .area_style(AreaStyle::new().color("rgba(255, 170, 0, 0.1)")),
-            );
-
-        let renderer = WasmRenderer::new(400, 200);
-        if let Err(e) = renderer.render(chart_id, &chart) {
-            log::error!("Chart render error: {:?}", e);
-        }
+        let options = telemetry_chart(metric.get(), &telemetry.get());
+        chart.update_value(|instance| match instance {
+            Some(echarts) => WasmRenderer::update(echarts, &options),
+            None => match WasmRenderer::new(400, 200).render(chart_id, &options) {
+                Ok(echarts) => *instance = Some(echarts),
+                Err(e) => log::error!("Chart render error: {:?}", e),
+            },
+        });
     });
 
     view! {
         <div class="panel">
             <div class="panel-header">
                 <span class="panel-title">"TELEMETRY"</span>
-                {move || state.selected_drone.get().map(|_| view! {
+                {move || selected_drone.get().map(|_| view! {
                     <span class="panel-badge">"LIVE"</span>
                 })}
             </div>
-            <div class="panel-body no-padding">
+            <div class="chart-controls">
+                {TelemetryMetric::ALL
+                    .into_iter()
+                    .map(|m| view! {
+                        <button
+                            class="btn btn-sm"
+                            class:active=move || metric.get() == m
+                            on:click=move |_| metric.set(m)
+                        >
+                            {m.label()}
+                        </button>
+                    })
+                    .collect_view()}
+            </div>
+            <div class="panel-body no-padding chart-body">
                 <div id=chart_id class="chart-container"></div>
+                {move || selected_drone.get().is_none().then(|| view! {
+                    <div class="chart-empty text-muted">"SELECT A DRONE"</div>
+                })}
             </div>
+            {move || {
+                let extensions = telemetry.with(|series| {
+                    series.last().map(|s| s.extensions.clone()).unwrap_or_default()
+                });
+                (!extensions.is_empty()).then(|| view! {
+                    <div class="telemetry-extensions">
+                        {extensions
+                            .into_iter()
+                            .map(|(key, value)| view! {
+                                <span class="telemetry-extension">
+                                    <span class="text-muted">{key}</span>
+                                    " "
+                                    {value}
+                                </span>
+                            })
+                            .collect_view()}
+                    </div>
+                })
+            }}
         </div>
     }
 }
 
+/// Chart options for one metric over the given samples
+fn telemetry_chart(metric: TelemetryMetric, samples: &[TelemetrySample]) -> Chart {
+    let (times, values): (Vec<String>, Vec<f64>) = samples
+        .iter()
+        .filter_map(|s| Some((s.recorded_at.format("%H:%M:%S").to_string(), metric.value(s)?)))
+        .unzip();
+    let (line_color, area_color) = metric.colors();
+
+    Chart::new()
+        .title(
+            Title::new()
+                .text("FLIGHT TELEMETRY")
+                .text_style(charming::element::TextStyle::new().color("#00ff41").font_size(12))
+                .left("center"),
+        )
+        .tooltip(Tooltip::new().trigger(Trigger::Axis))
+        .toolbox(
+            Toolbox::new().right(8).feature(
+                Feature::new()
+                    .data_zoom(ToolboxDataZoom::new().y_axis_index("none"))
+                    .restore(Restore::new().title("Reset zoom"))
+                    .save_as_image(
+                        SaveAsImage::new()
+                            .type_(SaveAsImageType::Png)
+                            .name(format!("telemetry-{}", metric.label().to_lowercase()))
+                            .background_color("#0a0f0a"),
+                    ),
+            ),
+        )
+        .data_zoom(DataZoom::new().type_(DataZoomType::Inside))
+        .data_zoom(
+            DataZoom::new()
+                .type_(DataZoomType::Slider)
+                .bottom(4)
+                .border_color("#1a2a1a")
+                .filler_color("rgba(0, 255, 65, 0.15)"),
+        )
+        .grid(
+            Grid::new()
+                .left("12%")
+                .right("6%")
+                .top("18%")
+                .bottom("28%"),
+        )
+        .x_axis(
+            Axis::new()
+                .type_(AxisType::Category)
+                .data(times)
+                .axis_line(charming::element::AxisLine::new().line_style((1.0, "#557755")))
+                .axis_label(charming::element::AxisLabel::new().color("#557755")),
+        )
+        .y_axis(
+            Axis::new()
+                .type_(AxisType::Value)
+                .name(metric.series_name())
+                .scale(true)
+                .axis_line(charming::element::AxisLine::new().line_style((1.0, "#557755")))
+                .axis_label(charming::element::AxisLabel::new().color("#557755"))
+                .split_line(charming::element::SplitLine::new().line_style(LineStyle::new().color("#1a2a1a"))),
+        )
+        .series(
+            Line::new()
+                .name(metric.series_name())
+                .data(values)
+                .smooth(true)
+                .show_symbol(false)
+                .line_style(LineStyle::new().color(line_color).width(2))
+                .area_style(AreaStyle::new().color(area_color)),
+        )
+}
+
 /// Stats summary panel
+///
+/// Shows the server-computed statistics kept on the convoy cards, pushed by
+/// the `convoyStatsUpdates` subscription for the selected convoy. The
+/// overview sums the cards, weighting averages by drone count.
 #[component]
 pub fn ConvoyStatsPanel() -> impl IntoView {
     let state = use_app_state();
 
     let stats = move || {
-        let drones = state.drones.get();
-        let leaderboard = state.leaderboard.get();
-
-        let total = drones.len();
-        let airborne = drones.values().filter(|d| d.status.status_class() == "nominal").count();
-        let avg_fuel: f32 = if total > 0 {
-            drones.values().map(|d| d.fuel_pct).sum::<f32>() / total as f32
-        } else {
-            0.0
-        };
-        let avg_accuracy: f32 = if !leaderboard.is_empty() {
-            leaderboard.iter().map(|e| e.accuracy_pct).sum::<f32>() / leaderboard.len() as f32
-        } else {
-            0.0
+        let selected = state.selected_convoy.get();
+        let convoys = state.convoys.get();
+        let cards: Vec<_> = convoys
+            .iter()
+            .filter(|c| selected.is_none_or(|id| c.convoy_id == id))
+            .collect();
+
+        let total: u32 = cards.iter().map(|c| c.drone_count).sum();
+        let airborne: u32 = cards.iter().map(|c| c.airborne_count).sum();
+        let weighted = |value: fn(&ConvoyOverview) -> f32| -> f32 {
+            if total > 0 {
+                cards.iter().map(|c| value(c) * c.drone_count as f32).sum::<f32>() / total as f32
+            } else {
+                0.0
+            }
         };
-        let total_engagements: u32 = leaderboard.iter().map(|e| e.total_engagements).sum();
-        let total_hits: u32 = leaderboard.iter().map(|e| e.successful_hits).sum();
+        let avg_fuel = weighted(|c| c.avg_fuel_pct);
+        let avg_accuracy = weighted(|c| c.avg_accuracy_pct);
+        let total_engagements: u32 = cards.iter().map(|c| c.total_engagements).sum();
+        let total_hits: u32 = cards.iter().map(|c| c.total_hits).sum();
 
         (total, airborne, avg_fuel, avg_accuracy, total_engagements, total_hits)
     };
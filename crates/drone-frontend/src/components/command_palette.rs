@@ -0,0 +1,398 @@
+//! # Command Palette
+//!
+//! Keyboard-first quick actions. Ctrl+K (Cmd+K on macOS) opens a fuzzy
+//! search over HUD actions, the drones and convoys already loaded, and the
+//! global `search` API; arrow keys move, Enter runs, Escape closes. Common
+//! actions also have Alt shortcuts that work with the palette closed.
+
+use leptos::ev;
+use leptos::html;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use uuid::Uuid;
+
+use crate::components::map::locate_on_map;
+use crate::services::{acknowledge_alert, search_entities, SearchMatch};
+use crate::state::{use_app_state, AppState};
+
+/// Entries shown at once
+const MAX_ENTRIES: usize = 12;
+
+/// Server-side matches requested per query
+const SEARCH_LIMIT: u32 = 10;
+
+/// Something the palette can do
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Command {
+    /// Select a drone and center the map on it
+    SelectDrone(Uuid),
+    /// Center the map on a drone without changing the selection
+    CenterDrone(Uuid),
+    /// Switch convoys; `None` is the all-convoys overview
+    SelectConvoy(Option<Uuid>),
+    /// Switch to an engagement's convoy and center the map on its target
+    ShowEngagement { convoy_id: Uuid, engagement_id: Uuid },
+    AcknowledgeAlerts,
+    ToggleStrikes,
+    ToggleReplay,
+    ToggleAlertSound,
+}
+
+/// Alt shortcut: `KeyboardEvent.code` and the hint shown for it
+type Shortcut = (&'static str, &'static str);
+
+/// HUD-wide actions and their shortcuts
+const ACTIONS: [(Command, &str, Option<Shortcut>); 5] = [
+    (Command::AcknowledgeAlerts, "Acknowledge all alerts", Some(("KeyA", "Alt+A"))),
+    (Command::ToggleStrikes, "Toggle strike layer", Some(("KeyS", "Alt+S"))),
+    (Command::ToggleReplay, "Toggle mission replay", Some(("KeyR", "Alt+R"))),
+    (Command::ToggleAlertSound, "Toggle alert audio", Some(("KeyM", "Alt+M"))),
+    (Command::SelectConvoy(None), "Show all convoys", None),
+];
+
+/// One palette row
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    command: Command,
+    label: String,
+    /// Category shown beside the label
+    kind: &'static str,
+    shortcut: Option<&'static str>,
+    score: i32,
+}
+
+/// Subsequence match of `query` in `text`, ignoring case; higher is
+/// better. Consecutive characters and matches at word starts score extra.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    if query.trim().is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let offset = text[pos..].iter().position(|&c| c == q)?;
+        let at = pos + offset;
+        score += 1;
+        if prev_match.is_some_and(|p| p + 1 == at) {
+            score += 5;
+        }
+        if at == 0 || !text[at - 1].is_alphanumeric() {
+            score += 3;
+        }
+        prev_match = Some(at);
+        pos = at + 1;
+    }
+    Some(score - i32::try_from(text.len()).unwrap_or(i32::MAX) / 10)
+}
+
+/// Actions, loaded drones and convoys matching `query`
+fn local_entries(state: &AppState, query: &str) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = ACTIONS
+        .iter()
+        .map(|&(command, label, shortcut)| Entry {
+            command,
+            label: label.to_string(),
+            kind: "ACTION",
+            shortcut: shortcut.map(|(_, hint)| hint),
+            score: fuzzy_score(query, label).unwrap_or(i32::MIN),
+        })
+        .collect();
+
+    state.drones.with_untracked(|drones| {
+        for drone in drones.values() {
+            let name = format!("{} {}", drone.callsign, drone.tail_number);
+            entries.push(Entry {
+                command: Command::SelectDrone(drone.drone_id),
+                label: format!("Select {} ({})", drone.callsign, drone.tail_number),
+                kind: "DRONE",
+                shortcut: None,
+                score: fuzzy_score(query, &name).unwrap_or(i32::MIN),
+            });
+            entries.push(Entry {
+                command: Command::CenterDrone(drone.drone_id),
+                label: format!("Center map on {}", drone.callsign),
+                kind: "MAP",
+                shortcut: None,
+                score: fuzzy_score(query, &name).map_or(i32::MIN, |s| s - 1),
+            });
+        }
+    });
+
+    state.convoys.with_untracked(|convoys| {
+        for convoy in convoys {
+            entries.push(Entry {
+                command: Command::SelectConvoy(Some(convoy.convoy_id)),
+                label: format!("Switch to convoy {}", convoy.callsign),
+                kind: "CONVOY",
+                shortcut: None,
+                score: fuzzy_score(query, &convoy.callsign).unwrap_or(i32::MIN),
+            });
+        }
+    });
+
+    entries.retain(|e| e.score > i32::MIN);
+    entries
+}
+
+/// Palette rows for server matches not already covered locally
+fn remote_entries(matches: &[SearchMatch], local: &[Entry]) -> Vec<Entry> {
+    matches
+        .iter()
+        .filter_map(|m| {
+            let id = Uuid::parse_str(&m.id).ok()?;
+            let convoy_id = Uuid::parse_str(&m.convoy_id).ok()?;
+            let (command, label, kind) = match m.result_type.as_str() {
+                "DRONE" => (Command::SelectDrone(id), format!("Select {}", m.label), "DRONE"),
+                "CONVOY" => (
+                    Command::SelectConvoy(Some(id)),
+                    format!("Switch to convoy {}", m.label),
+                    "CONVOY",
+                ),
+                "ENGAGEMENT" => (
+                    Command::ShowEngagement { convoy_id, engagement_id: id },
+                    format!("Show engagement {}", m.label),
+                    "STRIKE",
+                ),
+                _ => return None,
+            };
+            if local.iter().any(|e| e.command == command) {
+                return None;
+            }
+            // Server scores run 0-1; weight them against local fuzzy scores
+            let score = (m.score * 20.0).round() as i32;
+            Some(Entry { command, label, kind, shortcut: None, score })
+        })
+        .collect()
+}
+
+/// Acknowledge every open alert of the selected convoy
+fn acknowledge_all(state: &AppState) {
+    let Some(convoy_id) = state.selected_convoy.get_untracked() else {
+        return;
+    };
+    let open: Vec<Uuid> = state
+        .alert_log
+        .with_untracked(|log| log.iter().filter(|a| !a.acknowledged).map(|a| a.id).collect());
+
+    for alert_id in open {
+        let state = state.clone();
+        spawn_local(async move {
+            match acknowledge_alert(convoy_id, alert_id).await {
+                Ok(acked) => state.acknowledge_alert(alert_id, acked.acknowledged_by),
+                Err(e) => log::warn!("Acknowledge failed for alert {}: {}", alert_id, e),
+            }
+        });
+    }
+}
+
+/// Center the map on a loaded drone
+fn center_on_drone(state: &AppState, drone_id: Uuid) {
+    state.drones.with_untracked(|drones| {
+        if let Some(drone) = drones.get(&drone_id) {
+            locate_on_map(drone.position.latitude, drone.position.longitude, &drone.callsign);
+        }
+    });
+}
+
+/// Carry out a command
+fn run(state: &AppState, command: Command) {
+    match command {
+        Command::SelectDrone(drone_id) => {
+            state.selected_drone.set(Some(drone_id));
+            center_on_drone(state, drone_id);
+        }
+        Command::CenterDrone(drone_id) => center_on_drone(state, drone_id),
+        Command::SelectConvoy(convoy_id) => state.select_convoy(convoy_id),
+        Command::ShowEngagement { convoy_id, engagement_id } => {
+            if state.selected_convoy.get_untracked() != Some(convoy_id) {
+                state.select_convoy(Some(convoy_id));
+            }
+            let target = state.engagements.with_untracked(|events| {
+                events
+                    .iter()
+                    .find(|e| e.id == engagement_id)
+                    .and_then(|e| e.target_position.map(|pos| (pos, e.callsign.clone())))
+            });
+            if let Some(((latitude, longitude), callsign)) = target {
+                locate_on_map(latitude, longitude, &format!("{callsign} STRIKE"));
+            }
+        }
+        Command::AcknowledgeAlerts => acknowledge_all(state),
+        Command::ToggleStrikes => state.strikes_visible.update(|v| *v = !*v),
+        Command::ToggleReplay => state.replay_active.update(|v| *v = !*v),
+        Command::ToggleAlertSound => state.set_alert_sound(!state.alert_sound.get_untracked()),
+    }
+}
+
+/// Ctrl+K command palette
+#[component]
+pub fn CommandPalette() -> impl IntoView {
+    let state = use_app_state();
+    let open = state.palette_open;
+    let query = RwSignal::new(String::new());
+    let remote = RwSignal::new(Vec::<SearchMatch>::new());
+    let highlighted = RwSignal::new(0_usize);
+    let input_ref = NodeRef::<html::Input>::new();
+
+    // Open/close and Alt shortcuts
+    let key_state = state.clone();
+    let _ = window_event_listener(ev::keydown, move |ev| {
+        if (ev.ctrl_key() || ev.meta_key()) && ev.code() == "KeyK" {
+            ev.prevent_default();
+            open.update(|o| *o = !*o);
+            return;
+        }
+        if open.get_untracked() {
+            if ev.key() == "Escape" {
+                open.set(false);
+            }
+            return;
+        }
+        if ev.alt_key() && !ev.ctrl_key() && !ev.meta_key() {
+            let code = ev.code();
+            if let Some(&(command, _, _)) =
+                ACTIONS.iter().find(|(_, _, shortcut)| shortcut.is_some_and(|(c, _)| c == code))
+            {
+                ev.prevent_default();
+                run(&key_state, command);
+            }
+        }
+    });
+
+    // Start from a blank query whenever the palette opens
+    Effect::new(move |_| {
+        if open.get() {
+            query.set(String::new());
+            remote.set(Vec::new());
+            highlighted.set(0);
+        }
+    });
+
+    // Focus the input once it is mounted
+    Effect::new(move |_| {
+        if let Some(input) = input_ref.get() {
+            let _ = input.focus();
+        }
+    });
+
+    // Ask the server for matches beyond what is loaded; late answers for an
+    // older query are dropped
+    Effect::new(move |_| {
+        let q = query.get();
+        if q.trim().is_empty() {
+            remote.set(Vec::new());
+            return;
+        }
+        spawn_local(async move {
+            match search_entities(&q, SEARCH_LIMIT).await {
+                Ok(matches) if query.get_untracked() == q => remote.set(matches),
+                Ok(_) => {}
+                Err(e) => log::warn!("Search failed: {}", e),
+            }
+        });
+    });
+
+    let entries_state = state.clone();
+    let entries = Memo::new(move |_| {
+        let q = query.get();
+        let mut entries = local_entries(&entries_state, &q);
+        remote.with(|matches| {
+            let extra = remote_entries(matches, &entries);
+            entries.extend(extra);
+        });
+        entries.sort_by_key(|e| std::cmp::Reverse(e.score));
+        entries.truncate(MAX_ENTRIES);
+        entries
+    });
+
+    let run_state = state.clone();
+    let run_entry = move |index: usize| {
+        if let Some(entry) = entries.with_untracked(|e| e.get(index).cloned()) {
+            open.set(false);
+            run(&run_state, entry.command);
+        }
+    };
+
+    let on_keydown = {
+        let run_entry = run_entry.clone();
+        move |ev: ev::KeyboardEvent| {
+            let count = entries.with_untracked(Vec::len);
+            match ev.key().as_str() {
+                "ArrowDown" if count > 0 => {
+                    ev.prevent_default();
+                    highlighted.update(|h| *h = (*h + 1) % count);
+                }
+                "ArrowUp" if count > 0 => {
+                    ev.prevent_default();
+                    highlighted.update(|h| *h = (*h + count - 1) % count);
+                }
+                "Enter" => {
+                    ev.prevent_default();
+                    run_entry(highlighted.get_untracked());
+                }
+                _ => {}
+            }
+        }
+    };
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="palette-backdrop" on:click=move |_| open.set(false)>
+                <div class="palette" on:click=|ev| ev.stop_propagation()>
+                    <input
+                        class="palette-input"
+                        type="text"
+                        placeholder="Search drones, convoys, actions..."
+                        node_ref=input_ref
+                        prop:value=move || query.get()
+                        on:input=move |ev| {
+                            query.set(event_target_value(&ev));
+                            highlighted.set(0);
+                        }
+                        on:keydown=on_keydown.clone()
+                    />
+                    <div class="palette-list">
+                        {
+                            let run_entry = run_entry.clone();
+                            move || {
+                                let run_entry = run_entry.clone();
+                                entries
+                                    .get()
+                                    .into_iter()
+                                    .enumerate()
+                                    .map(|(index, entry)| {
+                                        let run_entry = run_entry.clone();
+                                        view! {
+                                            <div
+                                                class="palette-item"
+                                                class:highlighted=move || highlighted.get() == index
+                                                on:mouseenter=move |_| highlighted.set(index)
+                                                on:click=move |_| run_entry(index)
+                                            >
+                                                <span class="palette-kind">{entry.kind}</span>
+                                                <span class="palette-label">{entry.label}</span>
+                                                {entry.shortcut.map(|hint| view! { <kbd>{hint}</kbd> })}
+                                            </div>
+                                        }
+                                    })
+                                    .collect_view()
+                            }
+                        }
+                        {move || entries.with(Vec::is_empty).then(|| view! {
+                            <div class="palette-empty">"No matches"</div>
+                        })}
+                    </div>
+                    <div class="palette-hints">
+                        <span><kbd>"↑↓"</kbd>" navigate"</span>
+                        <span><kbd>"Enter"</kbd>" run"</span>
+                        <span><kbd>"Esc"</kbd>" close"</span>
+                        <span><kbd>"Ctrl+K"</kbd>" toggle"</span>
+                    </div>
+                </div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,78 @@
+//! # Convoy Overview Component
+//!
+//! All-convoys mode: one mini stat card per active convoy.
+
+use leptos::prelude::*;
+
+use crate::state::{use_app_state, ConvoyOverview};
+
+/// Grid of per-convoy stat cards
+#[component]
+pub fn ConvoyOverviewPanel() -> impl IntoView {
+    let state = use_app_state();
+
+    let convoys = move || state.convoys.get();
+    let total = move || convoys().len();
+    let has_convoys = move || total() > 0;
+
+    view! {
+        <div class="panel hud-overview">
+            <div class="panel-header">
+                <span class="panel-title">"ALL CONVOYS"</span>
+                <span class="panel-badge">{total}" ACTIVE"</span>
+            </div>
+            <div class="panel-body">
+                <Show
+                    when=has_convoys
+                    fallback=|| view! { <div class="text-muted">"NO ACTIVE CONVOYS"</div> }
+                >
+                    <div class="convoy-overview-grid">
+                        <For
+                            each=convoys
+                            key=|convoy| (convoy.convoy_id, convoy.total_engagements, convoy.airborne_count)
+                            children=move |convoy| view! { <ConvoyCard convoy=convoy /> }
+                        />
+                    </div>
+                </Show>
+            </div>
+        </div>
+    }
+}
+
+/// Mini stat card; clicking drills into the convoy
+#[component]
+fn ConvoyCard(convoy: ConvoyOverview) -> impl IntoView {
+    let state = use_app_state();
+    let convoy_id = convoy.convoy_id;
+    let on_click = move |_| state.select_convoy(Some(convoy_id));
+
+    view! {
+        <div class="convoy-card" on:click=on_click>
+            <div class="flex justify-between items-center">
+                <span class="font-bold text-accent">{convoy.callsign}</span>
+                <span class="text-xs text-muted">{convoy.status}</span>
+            </div>
+            <div class="text-xs text-muted uppercase tracking-wide">{convoy.mission_type}</div>
+            <div class="convoy-card-stats">
+                <div>
+                    <div class="text-xs text-muted">"AIRBORNE"</div>
+                    <div class="font-bold">{convoy.airborne_count}"/"{convoy.drone_count}</div>
+                </div>
+                <div>
+                    <div class="text-xs text-muted">"ACCURACY"</div>
+                    <div class="font-bold text-accent">{format!("{:.1}%", convoy.avg_accuracy_pct)}</div>
+                </div>
+                <div>
+                    <div class="text-xs text-muted">"HITS"</div>
+                    <div class="font-bold">{convoy.total_hits}"/"{convoy.total_engagements}</div>
+                </div>
+                <div>
+                    <div class="text-xs text-muted">"FUEL"</div>
+                    <div class="font-bold" class:text-warning=convoy.avg_fuel_pct < 40.0>
+                        {format!("{:.0}%", convoy.avg_fuel_pct)}
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
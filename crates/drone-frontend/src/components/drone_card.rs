@@ -63,6 +63,17 @@ pub fn DroneCard(drone: DroneState) -> impl IntoView {
         ""
     };
 
+    // Server-computed score, shown once the drone has reported telemetry
+    let health = move || {
+        state.readiness.with(|r| {
+            r.as_ref()?
+                .lowest
+                .iter()
+                .find(|h| h.drone_id == drone_id.to_string())
+                .map(|h| (h.score, health_class(&h.band)))
+        })
+    };
+
     let progress_pct = (drone.current_waypoint as f32 / drone.total_waypoints as f32) * 100.0;
 
     let platform_icon = match drone.platform_type.as_str() {
@@ -104,6 +115,15 @@ pub fn DroneCard(drone: DroneState) -> impl IntoView {
                         {format!("{:.0}%", drone.fuel_pct)}
                     </span>
                 </div>
+                <div class="metric" title="Health: fuel, link, sensors, engine, maintenance">
+                    <span class="metric-label">"HLTH"</span>
+                    {move || match health() {
+                        Some((score, class)) => view! {
+                            <span class=format!("metric-value {}", class)>{score}</span>
+                        }.into_any(),
+                        None => view! { <span class="metric-value text-muted">"--"</span> }.into_any(),
+                    }}
+                </div>
                 <div class="metric">
                     <span class="metric-label">"ACC"</span>
                     <span class="metric-value text-accent">
@@ -115,6 +135,15 @@ pub fn DroneCard(drone: DroneState) -> impl IntoView {
     }
 }
 
+/// CSS class for a health band
+fn health_class(band: &str) -> &'static str {
+    match band {
+        "GREEN" => "health-green",
+        "AMBER" => "health-amber",
+        _ => "health-red",
+    }
+}
+
 /// Empty state for drone list
 #[component]
 pub fn DroneListEmpty() -> impl IntoView {
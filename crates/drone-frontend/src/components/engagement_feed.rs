@@ -1,35 +1,172 @@
 //! # Engagement Feed Component
 //!
-//! Real-time feed of weapon engagements.
+//! Real-time feed of weapon engagements, backed by paged history.
+//!
+//! Only the rows inside the scroll viewport (plus a small overscan) are
+//! rendered, so the feed stays responsive with thousands of events.
 
 use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::map::locate_on_map;
+use crate::services::fetch_engagements;
+use crate::state::{use_app_state, EngagementEvent, ENGAGEMENT_CAPACITY};
+
+/// Row pitch in px (44px row plus 1px gap); must match `.engagement-rows`
+const ROW_HEIGHT_PX: usize = 45;
+
+/// Visible height of the feed in px; must match `.engagement-feed`
+const VIEWPORT_HEIGHT_PX: usize = 300;
+
+/// Rows rendered above and below the viewport
+const OVERSCAN_ROWS: usize = 5;
+
+/// Engagements requested per history page
+const PAGE_SIZE: usize = 100;
 
-use crate::state::{use_app_state, EngagementEvent};
+/// Distance from the bottom (px) at which the next page is requested
+const LOAD_THRESHOLD_PX: i32 = 200;
 
 /// Engagement feed panel
 #[component]
 pub fn EngagementFeedPanel() -> impl IntoView {
     let state = use_app_state();
+    let engagements = state.engagements;
+    let selected_convoy = state.selected_convoy;
+    let drones = state.drones;
+    let resync = state.resync;
+
+    let scroll_top = RwSignal::new(0usize);
+    let loading = RwSignal::new(false);
+    let has_more = RwSignal::new(true);
+    let next_cursor = RwSignal::new(None::<String>);
+    let selected = RwSignal::new(None::<EngagementEvent>);
+
+    // Request the next page of history after the last loaded cursor; live
+    // events arrive ahead of the loaded pages, so they don't shift it
+    let load_page = move |first: bool| {
+        let Some(convoy_id) = selected_convoy.get_untracked() else {
+            return;
+        };
+        if loading.get_untracked() || (!first && !has_more.get_untracked()) {
+            return;
+        }
+        if !first && engagements.with_untracked(Vec::len) >= ENGAGEMENT_CAPACITY {
+            has_more.set(false);
+            return;
+        }
+        let after = if first { None } else { next_cursor.get_untracked() };
+
+        loading.set(true);
+        spawn_local(async move {
+            let result = fetch_engagements(convoy_id, after, PAGE_SIZE).await;
+            loading.set(false);
+            if selected_convoy.get_untracked() != Some(convoy_id) {
+                return;
+            }
+            match result {
+                Ok((page, next)) => {
+                    has_more.set(next.is_some());
+                    next_cursor.set(next);
+                    engagements.update(|events| merge_page(events, page, first));
+                }
+                Err(e) => {
+                    has_more.set(false);
+                    log::warn!("Engagement history fetch failed: {}", e);
+                }
+            }
+        });
+    };
+
+    // Start over with the first page whenever the convoy changes or
+    // connectivity returns
+    Effect::new(move |_| {
+        selected_convoy.track();
+        resync.track();
+        scroll_top.set(0);
+        has_more.set(true);
+        next_cursor.set(None);
+        selected.set(None);
+        load_page(true);
+    });
 
-    let events = move || state.engagements.get();
-    let hit_count = move || events().iter().filter(|e| e.hit).count();
-    let total_count = move || events().len();
+    let total_count = move || engagements.with(Vec::len);
+    let hit_count = move || engagements.with(|events| events.iter().filter(|e| e.hit).count());
+
+    // Slice of rows intersecting the viewport
+    let window = move || {
+        let first = (scroll_top.get() / ROW_HEIGHT_PX).saturating_sub(OVERSCAN_ROWS);
+        let count = VIEWPORT_HEIGHT_PX / ROW_HEIGHT_PX + 2 * OVERSCAN_ROWS;
+        (first, count)
+    };
+    let visible = move || {
+        let (first, count) = window();
+        engagements.with(|events| {
+            events.iter().skip(first).take(count).cloned().collect::<Vec<_>>()
+        })
+    };
+
+    let on_scroll = move |ev: leptos::ev::Event| {
+        let el = event_target::<web_sys::Element>(&ev);
+        scroll_top.set(usize::try_from(el.scroll_top()).unwrap_or(0));
+        if el.scroll_height() - el.scroll_top() - el.client_height() < LOAD_THRESHOLD_PX {
+            load_page(false);
+        }
+    };
+
+    let locate = move |event: &EngagementEvent| {
+        let position = event.target_position.or_else(|| {
+            drones.with_untracked(|drones| {
+                drones.get(&event.drone_id).map(|d| (d.position.latitude, d.position.longitude))
+            })
+        });
+        match position {
+            Some((lat, lon)) => locate_on_map(lat, lon, &event.callsign),
+            None => log::warn!("No position known for engagement {}", event.id),
+        }
+    };
 
     view! {
-        <div class="panel">
+        <div class="panel engagement-panel">
             <div class="panel-header">
                 <span class="panel-title">"ENGAGEMENT FEED"</span>
-                <span class="panel-badge">{hit_count}"/"{ total_count}</span>
+                <span class="panel-badge">{hit_count}"/"{total_count}</span>
             </div>
             <div class="panel-body no-padding">
-                <div class="engagement-feed">
-                    <For
-                        each=events
-                        key=|event| event.id
-                        children=move |event| view! { <EngagementItem event=event /> }
-                    />
+                <div class="engagement-feed" on:scroll=on_scroll>
+                    <div
+                        class="engagement-spacer"
+                        style=move || format!("height: {}px;", total_count() * ROW_HEIGHT_PX)
+                    >
+                        <div
+                            class="engagement-rows"
+                            style=move || format!("transform: translateY({}px);", window().0 * ROW_HEIGHT_PX)
+                        >
+                            <For
+                                each=visible
+                                key=|event| event.id
+                                children=move |event| {
+                                    let id = event.id;
+                                    let is_selected = move || selected.with(|s| s.as_ref().is_some_and(|s| s.id == id));
+                                    let clicked = event.clone();
+                                    view! {
+                                        <div
+                                            class="engagement-row"
+                                            class:selected=is_selected
+                                            on:click=move |_| {
+                                                let toggled = (!is_selected()).then(|| clicked.clone());
+                                                selected.set(toggled);
+                                            }
+                                        >
+                                            <EngagementItem event=event />
+                                        </div>
+                                    }
+                                }
+                            />
+                        </div>
+                    </div>
                     {move || {
-                        if events().is_empty() {
+                        if total_count() == 0 && !loading.get() {
                             Some(view! {
                                 <div style="padding: 24px; text-align: center; color: var(--text-muted);">
                                     "Awaiting engagement data..."
@@ -40,11 +177,53 @@ pub fn EngagementFeedPanel() -> impl IntoView {
                         }
                     }}
                 </div>
+                <Show when=move || loading.get()>
+                    <div class="engagement-loading">"LOADING HISTORY..."</div>
+                </Show>
+                {move || selected.get().map(|event| {
+                    let target = event.clone();
+                    view! {
+                        <EngagementPopover
+                            event=event
+                            on_locate=Callback::new(move |()| locate(&target))
+                            on_close=Callback::new(move |()| selected.set(None))
+                        />
+                    }
+                })}
             </div>
         </div>
     }
 }
 
+/// Merge a history page into the feed. The first page replaces history but
+/// keeps live events newer than anything in it.
+fn merge_page(events: &mut Vec<EngagementEvent>, page: Vec<EngagementEvent>, first: bool) {
+    if first {
+        let newest = page.first().map(|e| e.timestamp);
+        events.retain(|e| e.damage_assessment.is_none() && newest.is_none_or(|t| e.timestamp > t));
+    }
+    for event in page {
+        if events.len() >= ENGAGEMENT_CAPACITY {
+            break;
+        }
+        if !events.iter().any(|e| e.id == event.id) {
+            events.push(event);
+        }
+    }
+}
+
+/// Short designation for a weapon type
+fn weapon_short(weapon_type: &str) -> &str {
+    match weapon_type {
+        "AGM114_HELLFIRE" => "AGM-114",
+        "GBU12_PAVEWAY" => "GBU-12",
+        "AIM9X_SIDEWINDER" => "AIM-9X",
+        "GBU38_JDAM" => "GBU-38",
+        "AGM176_GRIFFIN" => "AGM-176",
+        other => other,
+    }
+}
+
 /// Single engagement item
 #[component]
 fn EngagementItem(event: EngagementEvent) -> impl IntoView {
@@ -52,13 +231,10 @@ fn EngagementItem(event: EngagementEvent) -> impl IntoView {
     let result_text = if event.hit { "HIT" } else { "MISS" };
     let result_color = if event.hit { "var(--status-nominal)" } else { "var(--status-critical)" };
 
-    let weapon_short = match event.weapon_type.as_str() {
-        "AGM114_HELLFIRE" => "AGM-114",
-        "GBU12_PAVEWAY" => "GBU-12",
-        "AIM9X_SIDEWINDER" => "AIM-9X",
-        "GBU38_JDAM" => "GBU-38",
-        "AGM176_GRIFFIN" => "AGM-176",
-        _ => &event.weapon_type,
+    let detail = match (event.new_accuracy_pct, event.target_type.as_deref()) {
+        (Some(accuracy), _) => format!("{:.1}%", accuracy),
+        (None, Some(target)) => target.replace('_', " "),
+        (None, None) => "—".to_string(),
     };
 
     let time_str = event.timestamp.format("%H:%M:%S").to_string();
@@ -73,10 +249,43 @@ fn EngagementItem(event: EngagementEvent) -> impl IntoView {
                     <span style=format!("color: {};", result_color)>{result_text}</span>
                 </div>
                 <div class="engagement-weapon">
-                    {weapon_short.to_string()}" → "{format!("{:.1}%", event.new_accuracy_pct)}
+                    {weapon_short(&event.weapon_type).to_string()}" → "{detail}
                 </div>
             </div>
             <div class="engagement-time">{time_str}"Z"</div>
         </div>
     }
 }
+
+/// Detail card for a clicked engagement
+#[component]
+fn EngagementPopover(
+    event: EngagementEvent,
+    on_locate: Callback<()>,
+    on_close: Callback<()>,
+) -> impl IntoView {
+    let unknown = || "—".to_string();
+    let target_type = event.target_type.as_deref().map_or_else(unknown, |t| t.replace('_', " "));
+    let range = event.range_km.map_or_else(unknown, |r| format!("{:.1} KM", r));
+    let bda = event.damage_assessment.as_deref().map_or_else(unknown, |b| b.replace('_', " "));
+    let result_text = if event.hit { "HIT" } else { "MISS" };
+    let short_id = event.id.to_string()[..8].to_uppercase();
+
+    view! {
+        <div class="engagement-popover">
+            <div class="engagement-popover-header">
+                <span>{event.callsign.clone()}" • "{result_text}</span>
+                <button class="btn btn-sm" on:click=move |_| on_close.run(())>"✕"</button>
+            </div>
+            <dl class="engagement-popover-details">
+                <dt>"ENGAGEMENT"</dt><dd>{short_id}</dd>
+                <dt>"WEAPON"</dt><dd>{weapon_short(&event.weapon_type).to_string()}</dd>
+                <dt>"TARGET"</dt><dd>{target_type}</dd>
+                <dt>"RANGE"</dt><dd>{range}</dd>
+                <dt>"BDA"</dt><dd>{bda}</dd>
+                <dt>"TIME"</dt><dd>{event.timestamp.format("%H:%M:%SZ").to_string()}</dd>
+            </dl>
+            <button class="btn btn-sm" on:click=move |_| on_locate.run(())>"LOCATE ON MAP"</button>
+        </div>
+    }
+}
@@ -1,9 +1,10 @@
 //! # Footer Component
 //!
-//! Status bar with system information.
+//! Status bar with system information and connection quality.
 
 use leptos::prelude::*;
 
+use crate::services::{diagnosis, missed_heartbeats, now_ms};
 use crate::state::use_app_state;
 
 /// Footer status bar
@@ -11,14 +12,6 @@ use crate::state::use_app_state;
 pub fn Footer() -> impl IntoView {
     let state = use_app_state();
 
-    let connection_status = move || {
-        if state.ws_connected.get() {
-            ("nominal", "CONNECTED")
-        } else {
-            ("critical", "DISCONNECTED")
-        }
-    };
-
     let drone_count = move || state.drones.get().len();
     let alert_count = move || state.alerts.get().len();
 
@@ -47,13 +40,54 @@ pub fn Footer() -> impl IntoView {
                     }
                 }}
 
-                <span class="flex items-center gap-xs">
-                    <span class=move || format!("status-dot {}", connection_status().0)></span>
-                    <span class="text-sm">{move || connection_status().1}</span>
-                </span>
+                <ConnectionIndicator />
 
                 <span class="text-muted">"CLASSIFICATION: UNCLASSIFIED // FOUO"</span>
             </div>
         </footer>
     }
 }
+
+/// Connection quality with the measurements behind it
+#[component]
+fn ConnectionIndicator() -> impl IntoView {
+    let state = use_app_state();
+    let quality = state.connection_quality;
+    let connection = state.connection;
+
+    let format_ms =
+        |ms: Option<f64>| ms.map_or_else(|| "--".to_string(), |ms| format!("{ms:.0}ms"));
+    let api = move || format_ms(connection.get().api_latency_ms);
+    let rtt = move || format_ms(connection.get().ws_rtt_ms);
+
+    // Re-rendered with `quality`, which is reassessed every second
+    let details = move || {
+        quality.track();
+        let metrics = connection.get_untracked();
+        let now = now_ms();
+        let heartbeat = match missed_heartbeats(&metrics, now) {
+            None => "no live stream".to_string(),
+            Some(0) => "on time".to_string(),
+            Some(n) => format!("{n} missed"),
+        };
+        let mut text = format!(
+            "API {} | WS round trip {} | heartbeat {}",
+            format_ms(metrics.api_latency_ms),
+            format_ms(metrics.ws_rtt_ms),
+            heartbeat
+        );
+        if let Some(cause) = diagnosis(&metrics, now) {
+            text.push('\n');
+            text.push_str(cause);
+        }
+        text
+    };
+
+    view! {
+        <span class="flex items-center gap-xs" title=details>
+            <span class=move || format!("status-dot {}", quality.get().class())></span>
+            <span class="text-sm">{move || format!("LINK {}", quality.get().label())}</span>
+            <span class="text-muted text-sm">"API "{api}" | WS "{rtt}</span>
+        </span>
+    }
+}
@@ -1,12 +1,16 @@
 //! # Header Component
 //!
-//! Top navigation bar with logo, mission clock, and status.
+//! Top navigation bar with logo, convoy picker, mission clock, and status.
 
 use chrono::{DateTime, Timelike, Utc};
 use leptos::prelude::*;
+use uuid::Uuid;
 
 use crate::state::use_app_state;
 
+/// Select option value for the all-convoys overview
+const ALL_CONVOYS_OPTION: &str = "all";
+
 /// Header component with logo and mission clock
 #[component]
 pub fn Header() -> impl IntoView {
@@ -59,6 +63,8 @@ pub fn Header() -> impl IntoView {
                 </div>
             </div>
 
+            <ConvoySelector />
+
             <div class="mission-clock">
                 <div class="clock-segment">
                     <div class="clock-label">"ZULU"</div>
@@ -79,6 +85,13 @@ pub fn Header() -> impl IntoView {
             </div>
 
             <div class="flex items-center gap-md">
+                <button
+                    class="btn btn-sm palette-hint"
+                    title="Command palette"
+                    on:click=move |_| state.palette_open.set(true)
+                >
+                    <kbd>"Ctrl+K"</kbd>
+                </button>
                 <div class="status-badge" class:nominal=move || ws_status().0 == "nominal" class:critical=move || ws_status().0 == "critical">
                     <span class="status-dot" class:nominal=move || ws_status().0 == "nominal" class:critical=move || ws_status().0 == "critical"></span>
                     {move || ws_status().1}
@@ -87,3 +100,64 @@ pub fn Header() -> impl IntoView {
         </header>
     }
 }
+
+/// Convoy picker populated from `activeConvoys`
+#[component]
+fn ConvoySelector() -> impl IntoView {
+    let state = use_app_state();
+
+    // Re-apply once options arrive so the stored selection shows
+    let selected_value = move || {
+        state.convoys.track();
+        state
+            .selected_convoy
+            .get()
+            .map_or_else(|| ALL_CONVOYS_OPTION.to_string(), |id| id.to_string())
+    };
+
+    let on_change = {
+        let state = state.clone();
+        move |ev| {
+            let value = event_target_value(&ev);
+            state.select_convoy(Uuid::parse_str(&value).ok());
+        }
+    };
+
+    view! {
+        <div class="convoy-selector">
+            <div class="clock-label">"CONVOY"</div>
+            <select class="convoy-select" prop:value=selected_value on:change=on_change>
+                <option value=ALL_CONVOYS_OPTION>"ALL CONVOYS"</option>
+                <For
+                    each=move || state.convoys.get()
+                    key=|convoy| convoy.convoy_id
+                    children=move |convoy| view! {
+                        <option value=convoy.convoy_id.to_string()>
+                            {format!("{} ({})", convoy.callsign, convoy.status)}
+                        </option>
+                    }
+                />
+            </select>
+        </div>
+    }
+}
+
+/// Banner shown while the API is unreachable and panels show cached data
+#[component]
+pub fn StaleDataBanner() -> impl IntoView {
+    let state = use_app_state();
+
+    let message = move || match state.last_update.get() {
+        Some(at) => format!("STALE DATA — last update {}Z", at.format("%H:%M:%S")),
+        None => "STALE DATA — no cached state available".to_string(),
+    };
+
+    view! {
+        <Show when=move || state.offline.get()>
+            <div class="stale-banner" role="alert">
+                <span class="status-dot critical"></span>
+                {message}
+            </div>
+        </Show>
+    }
+}
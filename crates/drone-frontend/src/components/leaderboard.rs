@@ -1,30 +1,188 @@
 //! # Leaderboard Component
 //!
-//! Real-time accuracy rankings display.
+//! Real-time accuracy rankings with column sorting and filters.
 
 use leptos::prelude::*;
+use std::cmp::Ordering;
 
 use crate::state::{use_app_state, LeaderboardEntry};
 
+/// Sortable leaderboard columns
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SortColumn {
+    Rank,
+    Accuracy,
+    Engagements,
+    Streak,
+}
+
+impl SortColumn {
+    const ALL: [Self; 4] = [Self::Rank, Self::Accuracy, Self::Engagements, Self::Streak];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Rank => "RANK",
+            Self::Accuracy => "ACC",
+            Self::Engagements => "ENG",
+            Self::Streak => "STREAK",
+        }
+    }
+
+    /// Best-first ordering for the column
+    fn compare(self, a: &LeaderboardEntry, b: &LeaderboardEntry) -> Ordering {
+        match self {
+            Self::Rank => a.rank.cmp(&b.rank),
+            Self::Accuracy => b.accuracy_pct.total_cmp(&a.accuracy_pct),
+            Self::Engagements => b.total_engagements.cmp(&a.total_engagements),
+            Self::Streak => b.current_streak.cmp(&a.current_streak),
+        }
+    }
+}
+
+/// Client-side mirror of the API's `LeaderboardFilter`
+#[derive(Clone, Debug, Default, PartialEq)]
+struct LeaderboardFilter {
+    min_engagements: u32,
+    platform_type: Option<String>,
+}
+
+impl LeaderboardFilter {
+    fn matches(&self, entry: &LeaderboardEntry) -> bool {
+        entry.total_engagements >= self.min_engagements
+            && self
+                .platform_type
+                .as_ref()
+                .is_none_or(|p| *p == entry.platform_type)
+    }
+}
+
 /// Leaderboard panel component
 #[component]
 pub fn LeaderboardPanel() -> impl IntoView {
     let state = use_app_state();
+    let leaderboard = state.leaderboard;
+    let sort = RwSignal::new((SortColumn::Rank, false));
+    let filter = RwSignal::new(LeaderboardFilter::default());
 
-    let entries = move || state.leaderboard.get();
+    let entries = move || {
+        let filter = filter.get();
+        let (column, reversed) = sort.get();
+        let mut entries: Vec<LeaderboardEntry> = leaderboard
+            .get()
+            .into_iter()
+            .filter(|e| filter.matches(e))
+            .collect();
+        entries.sort_by(|a, b| {
+            let ord = column.compare(a, b).then(a.rank.cmp(&b.rank));
+            if reversed { ord.reverse() } else { ord }
+        });
+        entries
+    };
     let total = move || entries().len();
 
+    let platforms = move || {
+        let mut platforms: Vec<String> = leaderboard
+            .get()
+            .into_iter()
+            .map(|e| e.platform_type)
+            .collect();
+        platforms.sort();
+        platforms.dedup();
+        platforms
+    };
+    let max_engagements = move || {
+        leaderboard
+            .get()
+            .iter()
+            .map(|e| e.total_engagements)
+            .max()
+            .unwrap_or(0)
+    };
+
+    // Clicking the active column flips the direction
+    let sort_by = move |column: SortColumn| {
+        sort.update(|(current, reversed)| {
+            if *current == column {
+                *reversed = !*reversed;
+            } else {
+                *current = column;
+                *reversed = false;
+            }
+        });
+    };
+
     view! {
         <div class="panel">
             <div class="panel-header">
                 <span class="panel-title">"ACCURACY LEADERBOARD"</span>
                 <span class="panel-badge">{total}</span>
             </div>
+            <div class="leaderboard-controls">
+                <div class="leaderboard-sort">
+                    {SortColumn::ALL
+                        .into_iter()
+                        .map(|column| view! {
+                            <button
+                                class="btn btn-sm"
+                                class:active=move || sort.get().0 == column
+                                on:click=move |_| sort_by(column)
+                            >
+                                {column.label()}
+                                {move || {
+                                    let (current, reversed) = sort.get();
+                                    (current == column).then_some(if reversed { " ▲" } else { " ▼" })
+                                }}
+                            </button>
+                        })
+                        .collect_view()}
+                </div>
+                <div class="leaderboard-chips">
+                    <button
+                        class="btn btn-sm"
+                        class:active=move || filter.get().platform_type.is_none()
+                        on:click=move |_| filter.update(|f| f.platform_type = None)
+                    >
+                        "ALL"
+                    </button>
+                    <For
+                        each=platforms
+                        key=|platform| platform.clone()
+                        children=move |platform| {
+                            let label = platform_short(&platform).to_string();
+                            let chip = platform.clone();
+                            view! {
+                                <button
+                                    class="btn btn-sm"
+                                    class:active=move || filter.get().platform_type.as_ref() == Some(&platform)
+                                    on:click=move |_| filter.update(|f| f.platform_type = Some(chip.clone()))
+                                >
+                                    {label}
+                                </button>
+                            }
+                        }
+                    />
+                </div>
+                <label class="leaderboard-slider">
+                    <span class="text-xs text-muted">
+                        "MIN ENG " {move || filter.get().min_engagements}
+                    </span>
+                    <input
+                        type="range"
+                        min="0"
+                        max=move || max_engagements().to_string()
+                        prop:value=move || filter.get().min_engagements.to_string()
+                        on:input=move |ev| {
+                            let min = event_target_value(&ev).parse().unwrap_or(0);
+                            filter.update(|f| f.min_engagements = min);
+                        }
+                    />
+                </label>
+            </div>
             <div class="panel-body no-padding">
                 <div class="leaderboard">
                     <For
                         each=entries
-                        key=|entry| entry.drone_id
+                        key=|entry| (entry.drone_id, entry.rank, entry.total_engagements, entry.current_streak)
                         children=move |entry| view! { <LeaderboardRow entry=entry /> }
                     />
                 </div>
@@ -33,9 +191,18 @@ pub fn LeaderboardPanel() -> impl IntoView {
     }
 }
 
-/// Single leaderboard row
+/// Single leaderboard row; the selected drone's row stays pinned in view
 #[component]
 fn LeaderboardRow(entry: LeaderboardEntry) -> impl IntoView {
+    let state = use_app_state();
+    let drone_id = entry.drone_id;
+    let is_selected = move || state.selected_drone.get() == Some(drone_id);
+    let on_click = move |_| {
+        state.selected_drone.update(|selected| {
+            *selected = if *selected == Some(drone_id) { None } else { Some(drone_id) };
+        });
+    };
+
     let rank_class = match entry.rank {
         1 => "rank-1",
         2 => "rank-2",
@@ -61,16 +228,14 @@ fn LeaderboardRow(entry: LeaderboardEntry) -> impl IntoView {
         }
     };
 
-    let platform_short = match entry.platform_type.as_str() {
-        "MQ9_REAPER" => "MQ-9",
-        "MQ1C_GRAY_EAGLE" => "MQ-1C",
-        "RQ4_GLOBAL_HAWK" => "RQ-4",
-        "MQ25_STINGRAY" => "MQ-25",
-        _ => &entry.platform_type,
-    };
+    let platform_short = platform_short(&entry.platform_type);
 
     view! {
-        <div class=format!("leaderboard-entry {}", rank_class)>
+        <div
+            class=format!("leaderboard-entry {}", rank_class)
+            class:selected=is_selected
+            on:click=on_click
+        >
             <div class="leaderboard-rank">
                 {entry.rank}
             </div>
@@ -93,6 +258,17 @@ fn LeaderboardRow(entry: LeaderboardEntry) -> impl IntoView {
     }
 }
 
+/// Short display name for a GraphQL platform type
+fn platform_short(platform_type: &str) -> &str {
+    match platform_type {
+        "MQ9_REAPER" => "MQ-9",
+        "MQ1C_GRAY_EAGLE" => "MQ-1C",
+        "RQ4_GLOBAL_HAWK" => "RQ-4",
+        "MQ25_STINGRAY" => "MQ-25",
+        _ => platform_type,
+    }
+}
+
 /// Loading skeleton for leaderboard
 #[component]
 pub fn LeaderboardSkeleton() -> impl IntoView {
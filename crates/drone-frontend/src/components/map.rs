@@ -2,10 +2,32 @@
 //!
 //! Afghanistan tactical map with drone markers using Leaflet.js.
 
+use chrono::Utc;
 use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::cell::RefCell;
 use wasm_bindgen::prelude::*;
 
-use crate::state::use_app_state;
+use crate::components::ReplayControl;
+use crate::services::{fetch_engagement_heatmap, HeatmapCell};
+use crate::state::{use_app_state, EngagementEvent};
+
+/// Heatmap grid cell edge (km)
+const HEATMAP_RESOLUTION_KM: f64 = 1.0;
+
+/// Zoom level used when locating a point
+const LOCATE_ZOOM: u32 = 12;
+
+thread_local! {
+    /// Map handle and strike overlay, set once the map is initialized
+    static STRIKE_LAYER: RefCell<Option<(Map, LayerGroup)>> = const { RefCell::new(None) };
+
+    /// Marker for the most recently located point
+    static LOCATE_LAYER: RefCell<Option<LayerGroup>> = const { RefCell::new(None) };
+
+    /// Historical drone positions and strikes for the current replay frame
+    static REPLAY_LAYER: RefCell<Option<LayerGroup>> = const { RefCell::new(None) };
+}
 
 /// Leaflet map wrapper
 #[wasm_bindgen]
@@ -60,6 +82,43 @@ extern "C" {
     
     #[wasm_bindgen(method, js_name = addTo)]
     fn circle_add_to(this: &Circle, map: &Map);
+
+    #[wasm_bindgen(method, js_name = addLayer)]
+    fn add_layer(this: &Map, layer: &LayerGroup);
+
+    #[wasm_bindgen(method, js_name = removeLayer)]
+    fn remove_layer(this: &Map, layer: &LayerGroup);
+
+    #[wasm_bindgen(js_namespace = L)]
+    type LayerGroup;
+
+    #[wasm_bindgen(js_namespace = L, js_name = layerGroup)]
+    fn create_layer_group() -> LayerGroup;
+
+    #[wasm_bindgen(method, js_name = clearLayers)]
+    fn clear_layers(this: &LayerGroup);
+
+    #[wasm_bindgen(js_namespace = L)]
+    type CircleMarker;
+
+    #[wasm_bindgen(js_namespace = L, js_name = circleMarker)]
+    fn create_circle_marker(lat_lng: &JsValue, options: &JsValue) -> CircleMarker;
+
+    #[wasm_bindgen(method, js_name = bindPopup)]
+    fn circle_marker_bind_popup(this: &CircleMarker, content: &str) -> CircleMarker;
+
+    #[wasm_bindgen(method, js_name = addTo)]
+    fn circle_marker_add_to(this: &CircleMarker, group: &LayerGroup);
+
+    // Provided by the leaflet.heat plugin
+    #[wasm_bindgen(js_namespace = L)]
+    type HeatLayer;
+
+    #[wasm_bindgen(js_namespace = L, js_name = heatLayer)]
+    fn create_heat_layer(lat_lngs: &JsValue, options: &JsValue) -> HeatLayer;
+
+    #[wasm_bindgen(method, js_name = addTo)]
+    fn heat_layer_add_to(this: &HeatLayer, group: &LayerGroup);
 }
 
 /// Check if Leaflet is loaded
@@ -75,6 +134,302 @@ fn leaflet_available() -> bool {
     }
 }
 
+/// Check if the leaflet.heat plugin is loaded
+fn heat_plugin_available() -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+
+    js_sys::Reflect::get(&window, &JsValue::from_str("L"))
+        .and_then(|l| js_sys::Reflect::get(&l, &JsValue::from_str("heatLayer")))
+        .map(|f| f.is_function())
+        .unwrap_or(false)
+}
+
+/// Show or hide the strike overlay
+fn set_strike_layer_visible(visible: bool) {
+    STRIKE_LAYER.with(|layer| {
+        if let Some((map, strikes)) = layer.borrow().as_ref() {
+            if visible {
+                map.add_layer(strikes);
+            } else {
+                map.remove_layer(strikes);
+            }
+        }
+    });
+}
+
+/// Redraw strike markers and heatmap from aggregated cells
+fn render_strike_layer(cells: &[HeatmapCell]) {
+    STRIKE_LAYER.with(|layer| {
+        let layer = layer.borrow();
+        let Some((map, strikes)) = layer.as_ref() else {
+            return;
+        };
+        strikes.clear_layers();
+
+        let max_count = cells.iter().map(|c| c.count).max().unwrap_or(1).max(1);
+
+        if heat_plugin_available() {
+            let points = js_sys::Array::new();
+            for cell in cells {
+                let point = js_sys::Array::new();
+                point.push(&JsValue::from_f64(cell.latitude));
+                point.push(&JsValue::from_f64(cell.longitude));
+                point.push(&JsValue::from_f64(f64::from(cell.count) / f64::from(max_count)));
+                points.push(&point);
+            }
+
+            let heat_options = js_sys::Object::new();
+            js_sys::Reflect::set(&heat_options, &"radius".into(), &JsValue::from_f64(25.0)).unwrap();
+            js_sys::Reflect::set(&heat_options, &"blur".into(), &JsValue::from_f64(15.0)).unwrap();
+            create_heat_layer(&points.into(), &heat_options.into()).heat_layer_add_to(strikes);
+        }
+
+        // Hit/miss markers: green where hits dominate, red where misses do
+        for cell in cells {
+            let pos = js_sys::Array::new();
+            pos.push(&JsValue::from_f64(cell.latitude));
+            pos.push(&JsValue::from_f64(cell.longitude));
+
+            let color = if cell.hit_ratio >= 0.5 { "#00ff41" } else { "#ff3333" };
+            let radius = 4.0 + 8.0 * f64::from(cell.count) / f64::from(max_count);
+
+            let options = js_sys::Object::new();
+            js_sys::Reflect::set(&options, &"radius".into(), &JsValue::from_f64(radius)).unwrap();
+            js_sys::Reflect::set(&options, &"color".into(), &color.into()).unwrap();
+            js_sys::Reflect::set(&options, &"fillColor".into(), &color.into()).unwrap();
+            js_sys::Reflect::set(&options, &"fillOpacity".into(), &JsValue::from_f64(0.6)).unwrap();
+            js_sys::Reflect::set(&options, &"weight".into(), &JsValue::from_f64(1.0)).unwrap();
+
+            let popup_content = format!(
+                "<div style='font-family: monospace; color: #00ff41; background: #0a0f0d; padding: 8px; border: 1px solid #00ff41;'>\
+                <b>{} ENGAGEMENTS</b><br/>\
+                <span style='color: #557755;'>HITS:</span> {}<br/>\
+                <span style='color: #557755;'>RATIO:</span> {:.0}%\
+                </div>",
+                cell.count, cell.hits, cell.hit_ratio * 100.0
+            );
+
+            create_circle_marker(&pos.into(), &options.into())
+                .circle_marker_bind_popup(&popup_content)
+                .circle_marker_add_to(strikes);
+        }
+
+        map.add_layer(strikes);
+    });
+}
+
+/// Center the map on a point and mark it, replacing any previous marker
+pub fn locate_on_map(latitude: f64, longitude: f64, label: &str) {
+    STRIKE_LAYER.with(|layer| {
+        let layer = layer.borrow();
+        let Some((map, _)) = layer.as_ref() else {
+            log::warn!("Map not initialized; cannot locate {:.4},{:.4}", latitude, longitude);
+            return;
+        };
+
+        let pos = js_sys::Array::new();
+        pos.push(&JsValue::from_f64(latitude));
+        pos.push(&JsValue::from_f64(longitude));
+        map.set_view(&pos, LOCATE_ZOOM);
+
+        LOCATE_LAYER.with(|located| {
+            let mut located = located.borrow_mut();
+            let group = located.get_or_insert_with(|| {
+                let group = create_layer_group();
+                map.add_layer(&group);
+                group
+            });
+            group.clear_layers();
+
+            let options = js_sys::Object::new();
+            js_sys::Reflect::set(&options, &"radius".into(), &JsValue::from_f64(10.0)).unwrap();
+            js_sys::Reflect::set(&options, &"color".into(), &"#ffaa00".into()).unwrap();
+            js_sys::Reflect::set(&options, &"fillOpacity".into(), &JsValue::from_f64(0.2)).unwrap();
+            js_sys::Reflect::set(&options, &"weight".into(), &JsValue::from_f64(2.0)).unwrap();
+
+            let popup_content = format!(
+                "<div style='font-family: monospace; color: #ffaa00; background: #0a0f0d; padding: 8px; border: 1px solid #ffaa00;'>\
+                <b>{}</b><br/>{:.4}°N {:.4}°E\
+                </div>",
+                label, latitude, longitude
+            );
+
+            create_circle_marker(&pos.into(), &options.into())
+                .circle_marker_bind_popup(&popup_content)
+                .circle_marker_add_to(group);
+        });
+    });
+}
+
+/// Drone shown in a replay frame
+pub struct ReplayDrone<'a> {
+    pub callsign: &'a str,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Draw one replay frame: drones at their historical positions and the
+/// engagements that had happened by then, replacing the previous frame
+pub fn render_replay_frame(drones: &[ReplayDrone<'_>], engagements: &[&EngagementEvent]) {
+    STRIKE_LAYER.with(|layer| {
+        let layer = layer.borrow();
+        let Some((map, _)) = layer.as_ref() else {
+            return;
+        };
+
+        REPLAY_LAYER.with(|replay| {
+            let mut replay = replay.borrow_mut();
+            let group = replay.get_or_insert_with(|| {
+                let group = create_layer_group();
+                map.add_layer(&group);
+                group
+            });
+            group.clear_layers();
+
+            for engagement in engagements {
+                let Some((latitude, longitude)) = engagement.target_position else {
+                    continue;
+                };
+                let pos = js_sys::Array::new();
+                pos.push(&JsValue::from_f64(latitude));
+                pos.push(&JsValue::from_f64(longitude));
+
+                let color = if engagement.hit { "#00ff41" } else { "#ff3333" };
+                let options = js_sys::Object::new();
+                js_sys::Reflect::set(&options, &"radius".into(), &JsValue::from_f64(6.0)).unwrap();
+                js_sys::Reflect::set(&options, &"color".into(), &color.into()).unwrap();
+                js_sys::Reflect::set(&options, &"fillColor".into(), &color.into()).unwrap();
+                js_sys::Reflect::set(&options, &"fillOpacity".into(), &JsValue::from_f64(0.5)).unwrap();
+                js_sys::Reflect::set(&options, &"weight".into(), &JsValue::from_f64(1.0)).unwrap();
+
+                let popup_content = format!(
+                    "<div style='font-family: monospace; color: {color}; background: #0a0f0d; padding: 8px; border: 1px solid {color};'>\
+                    <b>{} {}</b><br/>\
+                    <span style='color: #557755;'>WPN:</span> {}<br/>\
+                    <span style='color: #557755;'>TIME:</span> {}Z\
+                    </div>",
+                    engagement.callsign,
+                    if engagement.hit { "HIT" } else { "MISS" },
+                    engagement.weapon_type,
+                    engagement.timestamp.format("%H:%M:%S"),
+                );
+
+                create_circle_marker(&pos.into(), &options.into())
+                    .circle_marker_bind_popup(&popup_content)
+                    .circle_marker_add_to(group);
+            }
+
+            for drone in drones {
+                let pos = js_sys::Array::new();
+                pos.push(&JsValue::from_f64(drone.latitude));
+                pos.push(&JsValue::from_f64(drone.longitude));
+
+                let options = js_sys::Object::new();
+                js_sys::Reflect::set(&options, &"radius".into(), &JsValue::from_f64(7.0)).unwrap();
+                js_sys::Reflect::set(&options, &"color".into(), &"#ffaa00".into()).unwrap();
+                js_sys::Reflect::set(&options, &"fillColor".into(), &"#ffaa00".into()).unwrap();
+                js_sys::Reflect::set(&options, &"fillOpacity".into(), &JsValue::from_f64(0.9)).unwrap();
+                js_sys::Reflect::set(&options, &"weight".into(), &JsValue::from_f64(2.0)).unwrap();
+
+                let popup_content = format!(
+                    "<div style='font-family: monospace; color: #ffaa00; background: #0a0f0d; padding: 8px; border: 1px solid #ffaa00;'>\
+                    <b>{}</b><br/>{:.4}°N {:.4}°E\
+                    </div>",
+                    drone.callsign, drone.latitude, drone.longitude
+                );
+
+                create_circle_marker(&pos.into(), &options.into())
+                    .circle_marker_bind_popup(&popup_content)
+                    .circle_marker_add_to(group);
+            }
+        });
+    });
+}
+
+/// Remove the replay frame from the map
+pub fn clear_replay_layer() {
+    REPLAY_LAYER.with(|replay| {
+        if let Some(group) = replay.borrow().as_ref() {
+            group.clear_layers();
+        }
+    });
+}
+
+/// Toggle and time-scrub control for the strike overlay
+#[component]
+fn StrikeLayerControl() -> impl IntoView {
+    let state = use_app_state();
+    let enabled = state.strikes_visible;
+    let scrub_pct = RwSignal::new(100_i32);
+    let cell_count = RwSignal::new(0_usize);
+
+    // Refetch whenever the overlay, convoy or scrub position changes
+    Effect::new(move |_| {
+        let visible = enabled.get();
+        let convoy = state.selected_convoy.get();
+        let mission_start = state.mission_start.get();
+        let pct = scrub_pct.get();
+
+        if !visible {
+            set_strike_layer_visible(false);
+            return;
+        }
+
+        let (Some(convoy_id), Some(start)) = (convoy, mission_start) else {
+            return;
+        };
+        let end = start + (Utc::now() - start) * pct / 100;
+
+        spawn_local(async move {
+            match fetch_engagement_heatmap(convoy_id, start, end, HEATMAP_RESOLUTION_KM).await {
+                Ok(cells) => {
+                    cell_count.set(cells.len());
+                    render_strike_layer(&cells);
+                }
+                Err(e) => log::warn!("Engagement heatmap fetch failed: {}", e),
+            }
+        });
+    });
+
+    let window_label = move || {
+        state
+            .mission_start
+            .get()
+            .map(|start| {
+                let end = start + (Utc::now() - start) * scrub_pct.get() / 100;
+                format!("{}Z | {} CELLS", end.format("%H:%M"), cell_count.get())
+            })
+            .unwrap_or_else(|| "NO MISSION".to_string())
+    };
+
+    view! {
+        <div class="map-control strike-control">
+            <label class="strike-toggle">
+                <input
+                    type="checkbox"
+                    prop:checked=move || enabled.get()
+                    on:change=move |ev| enabled.set(event_target_checked(&ev))
+                />
+                "STRIKES"
+            </label>
+            <Show when=move || enabled.get()>
+                <input
+                    class="time-slider"
+                    type="range"
+                    min="0"
+                    max="100"
+                    step="5"
+                    prop:value=move || scrub_pct.get().to_string()
+                    on:change=move |ev| scrub_pct.set(event_target_value(&ev).parse().unwrap_or(100))
+                />
+                <span class="text-muted">{window_label}</span>
+            </Show>
+        </div>
+    }
+}
+
 /// Afghanistan map panel
 #[component]
 pub fn MapPanel() -> impl IntoView {
@@ -172,6 +527,9 @@ pub fn MapPanel() -> impl IntoView {
             }
 
             log::info!("Map initialized with {} drone markers", drones.len());
+
+            // Strike overlay is attached on demand by StrikeLayerControl
+            STRIKE_LAYER.with(|layer| *layer.borrow_mut() = Some((map, create_layer_group())));
         }) as Box<dyn FnOnce()>);
 
         let window = web_sys::window().unwrap();
@@ -195,12 +553,16 @@ pub fn MapPanel() -> impl IntoView {
         <div class="map-container">
             <div id=map_id class="leaflet-map"></div>
 
+            <ReplayControl />
+
             <div class="map-overlay">
                 <div class="map-control">
                     <span class="status-dot nominal"></span>
                     "KANDAHAR AOR"
                 </div>
 
+                <StrikeLayerControl />
+
                 {move || drone_position().map(|pos| view! {
                     <div class="map-control">
                         <span class="text-accent">"SEL:"</span>
@@ -2,18 +2,26 @@
 //!
 //! Reusable Leptos components for the tactical HUD.
 
+pub mod alert_center;
 pub mod charts;
+pub mod command_palette;
+pub mod convoy_overview;
 pub mod drone_card;
 pub mod engagement_feed;
 pub mod footer;
 pub mod header;
 pub mod leaderboard;
 pub mod map;
+pub mod replay;
 
+pub use alert_center::*;
 pub use charts::*;
+pub use command_palette::*;
+pub use convoy_overview::*;
 pub use drone_card::*;
 pub use engagement_feed::*;
 pub use footer::*;
 pub use header::*;
 pub use leaderboard::*;
 pub use map::*;
+pub use replay::*;
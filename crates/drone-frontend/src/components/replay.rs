@@ -0,0 +1,336 @@
+//! # Replay Component
+//!
+//! After-action replay of the selected convoy: drones animated along their
+//! recorded tracks, engagements appearing on the map as they happened, and
+//! journal entries pinned on a scrubbable timeline.
+
+use chrono::{DateTime, Duration, Utc};
+use futures::future::join_all;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use uuid::Uuid;
+
+use crate::components::map::{clear_replay_layer, render_replay_frame, ReplayDrone};
+use crate::services::{fetch_drone_track, fetch_engagement_replay};
+use crate::state::{use_app_state, EngagementEvent, JournalNote, TrackSample};
+
+/// Longest window replayed; drone tracks are served for at most a day
+const MAX_REPLAY_HOURS: i64 = 24;
+
+/// Window replayed when the mission start is unknown
+const DEFAULT_REPLAY_HOURS: i64 = 1;
+
+/// Track points requested per drone
+const TRACK_POINTS: u32 = 1000;
+
+/// Playback tick (ms)
+const TICK_MS: u32 = 200;
+
+/// Playback speeds, as multiples of real time
+const SPEEDS: [u32; 4] = [1, 10, 60, 300];
+
+/// Loaded replay window
+#[derive(Clone, Debug)]
+struct Replay {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    /// Callsign and track per drone, oldest point first
+    tracks: Vec<(String, Vec<TrackSample>)>,
+    /// Engagements, oldest first
+    engagements: Vec<EngagementEvent>,
+    /// Journal entries, oldest first
+    journal: Vec<JournalNote>,
+}
+
+impl Replay {
+    /// Window length in milliseconds
+    fn duration_ms(&self) -> i64 {
+        (self.end - self.start).num_milliseconds().max(1)
+    }
+
+    /// Milliseconds from the window start to `t`, clamped to the window
+    fn offset_ms(&self, t: DateTime<Utc>) -> i64 {
+        (t - self.start).num_milliseconds().clamp(0, self.duration_ms())
+    }
+
+    /// Position of `t` along the timeline as a percentage
+    fn pct(&self, t: DateTime<Utc>) -> f64 {
+        self.offset_ms(t) as f64 * 100.0 / self.duration_ms() as f64
+    }
+
+    /// Draw the frame at `offset_ms` into the window
+    fn render(&self, offset_ms: i64) {
+        let t = self.start + Duration::milliseconds(offset_ms);
+        let drones: Vec<ReplayDrone<'_>> = self
+            .tracks
+            .iter()
+            .filter_map(|(callsign, track)| {
+                position_at(track, t).map(|(latitude, longitude)| ReplayDrone {
+                    callsign,
+                    latitude,
+                    longitude,
+                })
+            })
+            .collect();
+        let strikes: Vec<&EngagementEvent> =
+            self.engagements.iter().take_while(|e| e.timestamp <= t).collect();
+        render_replay_frame(&drones, &strikes);
+    }
+}
+
+/// Position along a track at `t`, interpolated between samples; `None`
+/// before the first sample and held at the last one after the track ends
+fn position_at(track: &[TrackSample], t: DateTime<Utc>) -> Option<(f64, f64)> {
+    let next = track.partition_point(|p| p.recorded_at <= t);
+    let prev = track.get(next.checked_sub(1)?)?;
+    let Some(after) = track.get(next) else {
+        return Some((prev.latitude, prev.longitude));
+    };
+
+    let span = (after.recorded_at - prev.recorded_at).num_milliseconds();
+    let frac = if span > 0 {
+        (t - prev.recorded_at).num_milliseconds() as f64 / span as f64
+    } else {
+        0.0
+    };
+    Some((
+        prev.latitude + (after.latitude - prev.latitude) * frac,
+        prev.longitude + (after.longitude - prev.longitude) * frac,
+    ))
+}
+
+/// Replay toggle, playback controls and timeline for the map
+#[component]
+pub fn ReplayControl() -> impl IntoView {
+    let state = use_app_state();
+    let enabled = state.replay_active;
+    let replay = RwSignal::new(None::<Replay>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let cursor_ms = RwSignal::new(0_i64);
+    let playing = RwSignal::new(false);
+    let speed = RwSignal::new(SPEEDS[1]);
+
+    // Load the mission window whenever replay is switched on or the convoy changes
+    Effect::new(move |_| {
+        let visible = enabled.get();
+        let convoy = state.selected_convoy.get();
+
+        playing.set(false);
+        replay.set(None);
+        loading.set(false);
+        error.set(None);
+        if !visible {
+            clear_replay_layer();
+            return;
+        }
+        let Some(convoy_id) = convoy else {
+            error.set(Some("SELECT A CONVOY".to_string()));
+            return;
+        };
+
+        let end = Utc::now();
+        let start = state
+            .mission_start
+            .get_untracked()
+            .unwrap_or(end - Duration::hours(DEFAULT_REPLAY_HOURS))
+            .max(end - Duration::hours(MAX_REPLAY_HOURS));
+        let drones: Vec<(Uuid, String)> = state.drones.with_untracked(|drones| {
+            drones
+                .values()
+                .filter(|d| d.convoy_id == convoy_id)
+                .map(|d| (d.drone_id, d.callsign.clone()))
+                .collect()
+        });
+
+        loading.set(true);
+        spawn_local(async move {
+            let tracks = join_all(drones.into_iter().map(|(drone_id, callsign)| async move {
+                match fetch_drone_track(drone_id, start, end, TRACK_POINTS).await {
+                    Ok(track) => Some((callsign, track)),
+                    Err(e) => {
+                        log::warn!("Track fetch for {} failed: {}", callsign, e);
+                        None
+                    }
+                }
+            }))
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+            let events = fetch_engagement_replay(convoy_id, start, end).await;
+
+            // Drop results for a convoy or toggle state that has since changed
+            if !enabled.get_untracked() || state.selected_convoy.get_untracked() != Some(convoy_id) {
+                return;
+            }
+            loading.set(false);
+            match events {
+                Ok((engagements, journal)) => {
+                    cursor_ms.set(0);
+                    replay.set(Some(Replay { start, end, tracks, engagements, journal }));
+                }
+                Err(e) => {
+                    log::warn!("Replay fetch failed: {}", e);
+                    error.set(Some(e));
+                }
+            }
+        });
+    });
+
+    // Advance the cursor while playing
+    gloo_timers::callback::Interval::new(TICK_MS, move || {
+        if !playing.try_get_untracked().unwrap_or(false) {
+            return;
+        }
+        let Some(total) = replay.with_untracked(|r| r.as_ref().map(Replay::duration_ms)) else {
+            return;
+        };
+        let next = (cursor_ms.get_untracked() + i64::from(TICK_MS) * i64::from(speed.get_untracked())).min(total);
+        cursor_ms.set(next);
+        if next >= total {
+            playing.set(false);
+        }
+    })
+    .forget();
+
+    // Redraw the map whenever the cursor moves
+    Effect::new(move |_| {
+        let offset_ms = cursor_ms.get();
+        replay.with(|r| match r {
+            Some(r) => r.render(offset_ms),
+            None => clear_replay_layer(),
+        });
+    });
+
+    let toggle_playing = move |_| {
+        let at_end = replay.with_untracked(|r| {
+            r.as_ref().is_some_and(|r| cursor_ms.get_untracked() >= r.duration_ms())
+        });
+        if at_end {
+            cursor_ms.set(0);
+        }
+        playing.update(|p| *p = !*p);
+    };
+
+    let status_label = move || {
+        if loading.get() {
+            return "LOADING...".to_string();
+        }
+        if let Some(e) = error.get() {
+            return e;
+        }
+        replay.with(|r| {
+            r.as_ref().map_or_else(String::new, |r| {
+                let t = r.start + Duration::milliseconds(cursor_ms.get());
+                let strikes = r.engagements.iter().take_while(|e| e.timestamp <= t).count();
+                format!("{}Z | {} STRIKES", t.format("%H:%M:%S"), strikes)
+            })
+        })
+    };
+
+    let journal_pins = move || {
+        replay.with(|r| {
+            r.as_ref().map(|r| {
+                r.journal
+                    .iter()
+                    .map(|note| {
+                        let offset_ms = r.offset_ms(note.timestamp);
+                        let title = format!(
+                            "{}Z {}: {}",
+                            note.timestamp.format("%H:%M:%S"),
+                            note.author,
+                            note.text
+                        );
+                        view! {
+                            <button
+                                class="replay-pin"
+                                class:passed=Signal::derive(move || cursor_ms.get() >= offset_ms)
+                                style=format!("left: {:.2}%", r.pct(note.timestamp))
+                                title=title
+                                on:click=move |_| cursor_ms.set(offset_ms)
+                            ></button>
+                        }
+                    })
+                    .collect_view()
+            })
+        })
+    };
+
+    let engagement_ticks = move || {
+        replay.with(|r| {
+            r.as_ref().map(|r| {
+                r.engagements
+                    .iter()
+                    .map(|e| {
+                        view! {
+                            <span
+                                class="replay-tick"
+                                class:hit=e.hit
+                                style=format!("left: {:.2}%", r.pct(e.timestamp))
+                            ></span>
+                        }
+                    })
+                    .collect_view()
+            })
+        })
+    };
+
+    view! {
+        <div class="replay-panel">
+            <div class="map-control replay-control">
+                <label class="strike-toggle">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || enabled.get()
+                        on:change=move |ev| enabled.set(event_target_checked(&ev))
+                    />
+                    "REPLAY"
+                </label>
+                <Show when=move || enabled.get()>
+                    <button
+                        class="btn"
+                        disabled=move || replay.with(Option::is_none)
+                        on:click=toggle_playing
+                    >
+                        {move || if playing.get() { "PAUSE" } else { "PLAY" }}
+                    </button>
+                    {SPEEDS
+                        .iter()
+                        .map(|&s| view! {
+                            <button
+                                class="btn"
+                                class:active=move || speed.get() == s
+                                on:click=move |_| speed.set(s)
+                            >
+                                {format!("{s}x")}
+                            </button>
+                        })
+                        .collect_view()}
+                    <span class="text-muted">{status_label}</span>
+                </Show>
+            </div>
+
+            <Show when=move || enabled.get() && replay.with(Option::is_some)>
+                <div class="map-control replay-timeline">
+                    <div class="replay-track">
+                        {engagement_ticks}
+                        {journal_pins}
+                    </div>
+                    <input
+                        class="replay-scrubber"
+                        type="range"
+                        min="0"
+                        max=move || replay.with(|r| r.as_ref().map_or(1, Replay::duration_ms).to_string())
+                        step="1000"
+                        prop:value=move || cursor_ms.get().to_string()
+                        on:input=move |ev| {
+                            playing.set(false);
+                            cursor_ms.set(event_target_value(&ev).parse().unwrap_or_default());
+                        }
+                    />
+                </div>
+            </Show>
+        </div>
+    }
+}
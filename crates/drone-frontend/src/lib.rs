@@ -14,32 +14,48 @@ use leptos::prelude::*;
 use uuid::Uuid;
 
 use components::*;
+use services::{
+    use_connection_quality, use_convoys, use_fleet_health, use_offline_cache, use_websocket,
+};
 use state::*;
 
 #[component]
 pub fn App() -> impl IntoView {
     provide_app_state();
     load_mock_data();
+    use_convoys();
+    use_fleet_health();
+    use_offline_cache();
+    use_connection_quality();
+
+    let selected_convoy = use_app_state().selected_convoy;
+    use_websocket(selected_convoy.into());
+    let convoy_selected = move || selected_convoy.get().is_some();
 
     view! {
         <div class="scanlines"></div>
         <div class="hud-container">
             <Header />
-            <div class="hud-left-panel">
-                <LeaderboardPanel />
-                <DroneListPanel />
-            </div>
-            <div class="hud-main">
-                <MapPanel />
-            </div>
-            <div class="hud-right-panel">
-                <ConvoyStatsPanel />
-                <TelemetryChartPanel />
-                <EngagementFeedPanel />
-            </div>
+            <Show when=convoy_selected fallback=|| view! { <ConvoyOverviewPanel /> }>
+                <div class="hud-left-panel">
+                    <LeaderboardPanel />
+                    <DroneListPanel />
+                </div>
+                <div class="hud-main">
+                    <MapPanel />
+                </div>
+                <div class="hud-right-panel">
+                    <ConvoyStatsPanel />
+                    <TelemetryChartPanel />
+                    <EngagementFeedPanel />
+                    <AlertCenterPanel />
+                </div>
+            </Show>
             <Footer />
         </div>
+        <StaleDataBanner />
         <ToastContainer />
+        <CommandPalette />
     }
 }
 
@@ -78,6 +94,7 @@ fn load_mock_data() {
     let state = use_app_state();
     state.mission_start.set(Some(Utc::now() - chrono::Duration::hours(2)));
 
+    // Shown until `activeConvoys` answers and the stored selection is restored
     let convoy_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
     state.selected_convoy.set(Some(convoy_id));
 
@@ -153,9 +170,9 @@ fn load_mock_data() {
     state.drones.update(|map| { for d in drones { map.insert(d.drone_id, d); } });
 
     let engagements = vec![
-        EngagementEvent { id: Uuid::new_v4(), drone_id: Uuid::new_v4(), callsign: "REAPER-01".into(), hit: true, weapon_type: "AGM114_HELLFIRE".into(), new_accuracy_pct: 94.5, timestamp: Utc::now() - chrono::Duration::minutes(5) },
-        EngagementEvent { id: Uuid::new_v4(), drone_id: Uuid::new_v4(), callsign: "HAWK-07".into(), hit: true, weapon_type: "GBU12_PAVEWAY".into(), new_accuracy_pct: 91.2, timestamp: Utc::now() - chrono::Duration::minutes(12) },
-        EngagementEvent { id: Uuid::new_v4(), drone_id: Uuid::new_v4(), callsign: "SHADOW-12".into(), hit: false, weapon_type: "AGM114_HELLFIRE".into(), new_accuracy_pct: 88.9, timestamp: Utc::now() - chrono::Duration::minutes(18) },
+        EngagementEvent { id: Uuid::new_v4(), drone_id: Uuid::new_v4(), callsign: "REAPER-01".into(), hit: true, weapon_type: "AGM114_HELLFIRE".into(), new_accuracy_pct: Some(94.5), timestamp: Utc::now() - chrono::Duration::minutes(5), ..Default::default() },
+        EngagementEvent { id: Uuid::new_v4(), drone_id: Uuid::new_v4(), callsign: "HAWK-07".into(), hit: true, weapon_type: "GBU12_PAVEWAY".into(), new_accuracy_pct: Some(91.2), timestamp: Utc::now() - chrono::Duration::minutes(12), ..Default::default() },
+        EngagementEvent { id: Uuid::new_v4(), drone_id: Uuid::new_v4(), callsign: "SHADOW-12".into(), hit: false, weapon_type: "AGM114_HELLFIRE".into(), new_accuracy_pct: Some(88.9), timestamp: Utc::now() - chrono::Duration::minutes(18), ..Default::default() },
     ];
     state.engagements.set(engagements);
     state.ws_connected.set(true);
@@ -165,5 +182,10 @@ pub fn main() {
     console_error_panic_hook::set_once();
     let _ = console_log::init_with_level(log::Level::Debug);
     log::info!("Drone Convoy Tracker v{}", env!("CARGO_PKG_VERSION"));
-    leptos::mount::mount_to_body(App);
+
+    // Endpoints must be known before the first query or subscription
+    wasm_bindgen_futures::spawn_local(async {
+        services::load_runtime_config().await;
+        leptos::mount::mount_to_body(App);
+    });
 }
@@ -2,28 +2,46 @@
 //!
 //! GraphQL HTTP client for queries and mutations.
 
-use crate::state::LeaderboardEntry;
+use crate::state::{
+    Alert, AlertSeverity, EngagementEvent, JournalNote, LeaderboardEntry, TelemetrySample, TrackSample,
+};
+use crate::services::config::runtime_config;
+use chrono::{DateTime, Utc};
+use drone_graphql_client::operations::{
+    AcknowledgeAlert, AcknowledgeAlertVariables, GetActiveAlerts, GetActiveAlertsVariables,
+    EngagementRecord, GetActiveConvoys, GetConvoyStats, GetConvoyStatsVariables, GetDroneTrack,
+    GetDroneTrackVariables, GetEngagementHeatmap, GetEngagementReplay, GetEngagementReplayVariables,
+    GetEngagements, GetEngagementsVariables, GetEngagementHeatmapVariables, GetFleetReadiness,
+    GetFleetReadinessVariables, GetLeaderboard, Health,
+    GetLeaderboardVariables, GetTelemetryHistory, GetTelemetryHistoryVariables, RecordEngagement,
+    RecordEngagementInput, RecordEngagementVariables, Search, SearchVariables, TelemetryPoint,
+    TimeRange,
+};
+use drone_graphql_client::{ClientError, GraphQLOperation, GraphQLResponse};
 use gloo_net::http::Request;
-use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-const API_URL: &str = "http://localhost:8080/graphql";
+pub use drone_graphql_client::operations::{
+    ConvoyStats, ConvoySummary, DroneHealthScore, FleetReadiness, HeatmapCell, RecordEngagementResult,
+    SearchMatch,
+};
 
-#[derive(Serialize)]
-struct GraphQLRequest<V: Serialize> {
-    query: &'static str,
-    variables: V,
-}
+/// Execute a typed operation against the API
+async fn execute<O: GraphQLOperation>(variables: O::Variables) -> Result<O::ResponseData, String> {
+    let response = Request::post(&runtime_config().api_url)
+        .header("Content-Type", "application/json")
+        .json(&O::build(variables))
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| ClientError::Transport(e.to_string()).to_string())?;
 
-#[derive(Deserialize, Debug)]
-struct GraphQLResponse<T> {
-    data: Option<T>,
-    errors: Option<Vec<GraphQLError>>,
-}
+    let result: GraphQLResponse<O::ResponseData> = response
+        .json()
+        .await
+        .map_err(|e| ClientError::Decode(e.to_string()).to_string())?;
 
-#[derive(Deserialize, Debug)]
-struct GraphQLError {
-    message: String,
+    result.into_result().map_err(|e| e.to_string())
 }
 
 /// Fetch leaderboard for a convoy
@@ -31,88 +49,20 @@ pub async fn fetch_leaderboard(
     convoy_id: Uuid,
     limit: u32,
 ) -> Result<Vec<LeaderboardEntry>, String> {
-    #[derive(Serialize)]
-    struct Variables {
-        convoy_id: String,
-        limit: u32,
-    }
-
-    #[derive(Deserialize)]
-    #[serde(rename_all = "camelCase")]
-    struct LeaderboardResponse {
-        leaderboard: LeaderboardData,
-    }
-
-    #[derive(Deserialize)]
-    struct LeaderboardData {
-        entries: Vec<LeaderboardEntryData>,
-    }
-
-    #[derive(Deserialize)]
-    #[serde(rename_all = "camelCase")]
-    struct LeaderboardEntryData {
-        drone_id: String,
-        callsign: String,
-        platform_type: String,
-        rank: u32,
-        accuracy_pct: f32,
-        total_engagements: u32,
-        successful_hits: u32,
-        current_streak: i32,
-        best_streak: i32,
-    }
-
-    let request = GraphQLRequest {
-        query: r#"
-            query GetLeaderboard($convoyId: ID!, $limit: Int!) {
-                leaderboard(convoyId: $convoyId, limit: $limit) {
-                    entries {
-                        droneId
-                        callsign
-                        platformType
-                        rank
-                        accuracyPct
-                        totalEngagements
-                        successfulHits
-                        currentStreak
-                        bestStreak
-                    }
-                }
-            }
-        "#,
-        variables: Variables {
-            convoy_id: convoy_id.to_string(),
-            limit,
-        },
-    };
-
-    let response = Request::post(API_URL)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    let result: GraphQLResponse<LeaderboardResponse> = response
-        .json()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if let Some(errors) = result.errors {
-        return Err(errors.into_iter().map(|e| e.message).collect::<Vec<_>>().join(", "));
-    }
+    let data = execute::<GetLeaderboard>(GetLeaderboardVariables {
+        convoy_id: convoy_id.to_string(),
+        limit: i32::try_from(limit).unwrap_or(i32::MAX),
+    })
+    .await?;
 
-    let data = result.data.ok_or("No data in response")?;
-    
     Ok(data.leaderboard.entries.into_iter().map(|e| LeaderboardEntry {
         drone_id: Uuid::parse_str(&e.drone_id).unwrap_or_default(),
         callsign: e.callsign,
         platform_type: e.platform_type,
-        rank: e.rank,
+        rank: e.rank.max(0) as u32,
         accuracy_pct: e.accuracy_pct,
-        total_engagements: e.total_engagements,
-        successful_hits: e.successful_hits,
+        total_engagements: e.total_engagements.max(0) as u32,
+        successful_hits: e.successful_hits.max(0) as u32,
         current_streak: e.current_streak,
         best_streak: e.best_streak,
         rank_change: 0,
@@ -126,122 +76,270 @@ pub async fn record_engagement(
     hit: bool,
     weapon_type: &str,
 ) -> Result<RecordEngagementResult, String> {
-    #[derive(Serialize)]
-    #[serde(rename_all = "camelCase")]
-    struct Variables {
-        input: RecordEngagementInput,
-    }
+    let data = execute::<RecordEngagement>(RecordEngagementVariables {
+        input: RecordEngagementInput {
+            convoy_id: convoy_id.to_string(),
+            drone_id: drone_id.to_string(),
+            hit,
+            weapon_type: Some(weapon_type.to_string()),
+            ..Default::default()
+        },
+    })
+    .await?;
 
-    #[derive(Serialize)]
-    #[serde(rename_all = "camelCase")]
-    struct RecordEngagementInput {
-        convoy_id: String,
-        drone_id: String,
-        hit: bool,
-        weapon_type: String,
-    }
+    Ok(data.record_engagement)
+}
 
-    #[derive(Deserialize)]
-    #[serde(rename_all = "camelCase")]
-    struct Response {
-        record_engagement: RecordEngagementResult,
+/// Fetch active convoys
+pub async fn fetch_active_convoys() -> Result<Vec<ConvoySummary>, String> {
+    let data = execute::<GetActiveConvoys>(()).await?;
+    Ok(data.active_convoys)
+}
+
+/// Run the `health` query, failing unless the API reports `OK`
+pub async fn check_health() -> Result<(), String> {
+    let data = execute::<Health>(()).await?;
+    if data.health == "OK" {
+        Ok(())
+    } else {
+        Err(format!("API reported {}", data.health))
     }
+}
 
-    let request = GraphQLRequest {
-        query: r#"
-            mutation RecordEngagement($input: RecordEngagementInput!) {
-                recordEngagement(input: $input) {
-                    success
-                    newRank
-                    rankChange
-                    newAccuracyPct
-                }
-            }
-        "#,
-        variables: Variables {
-            input: RecordEngagementInput {
-                convoy_id: convoy_id.to_string(),
-                drone_id: drone_id.to_string(),
-                hit,
-                weapon_type: weapon_type.to_string(),
-            },
-        },
-    };
+/// Fetch a convoy's alerts, newest first
+pub async fn fetch_alerts(
+    convoy_id: Uuid,
+    limit: u32,
+    include_acknowledged: bool,
+) -> Result<Vec<Alert>, String> {
+    let data = execute::<GetActiveAlerts>(GetActiveAlertsVariables {
+        convoy_id: convoy_id.to_string(),
+        limit: i32::try_from(limit).unwrap_or(i32::MAX),
+        include_acknowledged,
+    })
+    .await?;
 
-    let response = Request::post(API_URL)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    Ok(data.active_alerts.into_iter().map(alert_from).collect())
+}
 
-    let result: GraphQLResponse<Response> = response
-        .json()
-        .await
-        .map_err(|e| e.to_string())?;
+/// Acknowledge an alert
+pub async fn acknowledge_alert(convoy_id: Uuid, alert_id: Uuid) -> Result<Alert, String> {
+    let data = execute::<AcknowledgeAlert>(AcknowledgeAlertVariables {
+        convoy_id: convoy_id.to_string(),
+        alert_id: alert_id.to_string(),
+    })
+    .await?;
+
+    Ok(alert_from(data.acknowledge_alert))
+}
 
-    if let Some(errors) = result.errors {
-        return Err(errors.into_iter().map(|e| e.message).collect::<Vec<_>>().join(", "));
+fn alert_from(a: drone_graphql_client::operations::Alert) -> Alert {
+    Alert {
+        id: Uuid::parse_str(&a.alert_id).unwrap_or_default(),
+        severity: AlertSeverity::from_graphql(&a.severity),
+        alert_type: a.alert_type,
+        message: a.message,
+        timestamp: a.timestamp,
+        acknowledged: a.acknowledged,
+        acknowledged_by: a.acknowledged_by,
     }
+}
 
-    result.data.map(|d| d.record_engagement).ok_or("No data".to_string())
+/// Fetch aggregate statistics for a convoy
+pub async fn fetch_convoy_stats(convoy_id: Uuid) -> Result<ConvoyStats, String> {
+    let data = execute::<GetConvoyStats>(GetConvoyStatsVariables {
+        convoy_id: convoy_id.to_string(),
+    })
+    .await?;
+    Ok(data.convoy_stats)
 }
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct RecordEngagementResult {
-    pub success: bool,
-    pub new_rank: i32,
-    pub rank_change: i32,
-    pub new_accuracy_pct: f32,
+/// Fetch the health distribution of a convoy with up to `lowest` drone scores
+pub async fn fetch_fleet_readiness(convoy_id: Uuid, lowest: u32) -> Result<FleetReadiness, String> {
+    let data = execute::<GetFleetReadiness>(GetFleetReadinessVariables {
+        convoy_id: convoy_id.to_string(),
+        lowest: i32::try_from(lowest).unwrap_or(i32::MAX),
+    })
+    .await?;
+    Ok(data.fleet_readiness)
 }
 
-/// Fetch active convoys
-pub async fn fetch_active_convoys() -> Result<Vec<ConvoySummary>, String> {
-    #[derive(Deserialize)]
-    #[serde(rename_all = "camelCase")]
-    struct Response {
-        active_convoys: Vec<ConvoySummary>,
+/// Search drones, convoys and engagements the caller can see, best match first
+pub async fn search_entities(query: &str, limit: u32) -> Result<Vec<SearchMatch>, String> {
+    let data = execute::<Search>(SearchVariables {
+        query: query.to_string(),
+        limit: i32::try_from(limit).unwrap_or(i32::MAX),
+    })
+    .await?;
+
+    Ok(data.search)
+}
+
+/// Fetch engagement heatmap cells for a convoy within a time window
+pub async fn fetch_engagement_heatmap(
+    convoy_id: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    grid_resolution_km: f64,
+) -> Result<Vec<HeatmapCell>, String> {
+    let data = execute::<GetEngagementHeatmap>(GetEngagementHeatmapVariables {
+        convoy_id: convoy_id.to_string(),
+        time_range: TimeRange { start, end },
+        grid_resolution_km,
+    })
+    .await?;
+
+    Ok(data.engagement_heatmap.cells)
+}
+
+/// Fetch a page of a convoy's engagements, newest first, continuing after
+/// `after`; also returns the cursor for the next page while more remain
+pub async fn fetch_engagements(
+    convoy_id: Uuid,
+    after: Option<String>,
+    limit: usize,
+) -> Result<(Vec<EngagementEvent>, Option<String>), String> {
+    let data = execute::<GetEngagements>(GetEngagementsVariables {
+        convoy_id: convoy_id.to_string(),
+        first: i32::try_from(limit).unwrap_or(i32::MAX),
+        after,
+    })
+    .await?;
+
+    let page = data.engagements;
+    let next = page.page_info.next_cursor().map(str::to_string);
+    let events = page
+        .edges
+        .into_iter()
+        .map(|edge| engagement_event(edge.node))
+        .collect();
+
+    Ok((events, next))
+}
+
+/// Convert an engagement selection into a feed event
+fn engagement_event(e: EngagementRecord) -> EngagementEvent {
+    EngagementEvent {
+        id: Uuid::parse_str(&e.engagement_id).unwrap_or_default(),
+        drone_id: Uuid::parse_str(&e.drone_id).unwrap_or_default(),
+        callsign: e.drone_callsign,
+        hit: e.hit,
+        weapon_type: e.weapon_type,
+        target_type: Some(e.target_type),
+        range_km: Some(e.range_km),
+        damage_assessment: Some(e.damage_assessment),
+        target_position: Some((e.target_coordinates.latitude, e.target_coordinates.longitude)),
+        new_accuracy_pct: None,
+        timestamp: e.engaged_at,
     }
+}
 
-    let request = GraphQLRequest {
-        query: r#"
-            query GetActiveConvoys {
-                activeConvoys {
-                    convoyId
-                    callsign
-                    missionType
-                    status
-                    droneCount
-                }
-            }
-        "#,
-        variables: (),
-    };
-
-    let response = Request::post(API_URL)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+/// Fetch a convoy's engagements and journal entries within a window, oldest first
+pub async fn fetch_engagement_replay(
+    convoy_id: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<(Vec<EngagementEvent>, Vec<JournalNote>), String> {
+    let data = execute::<GetEngagementReplay>(GetEngagementReplayVariables {
+        convoy_id: convoy_id.to_string(),
+        time_range: TimeRange { start, end },
+    })
+    .await?;
 
-    let result: GraphQLResponse<Response> = response
-        .json()
-        .await
-        .map_err(|e| e.to_string())?;
+    let replay = data.engagement_replay;
+    if replay.truncated {
+        log::warn!("Replay window for convoy {} was truncated", convoy_id);
+    }
+
+    let mut engagements = Vec::new();
+    let mut journal = Vec::new();
+    for event in replay.events {
+        if let Some(e) = event.engagement {
+            engagements.push(engagement_event(e));
+        }
+        if let Some(j) = event.journal_entry {
+            journal.push(JournalNote {
+                id: Uuid::parse_str(&j.entry_id).unwrap_or_default(),
+                author: j.author,
+                text: j.text,
+                linked_entity_id: j.linked_entity_id.and_then(|id| Uuid::parse_str(&id).ok()),
+                timestamp: j.timestamp,
+            });
+        }
+    }
 
-    result.data.map(|d| d.active_convoys).ok_or("No data".to_string())
+    Ok((engagements, journal))
 }
 
-#[derive(Deserialize, Clone, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct ConvoySummary {
-    pub convoy_id: String,
-    pub callsign: String,
-    pub mission_type: String,
-    pub status: String,
-    pub drone_count: u32,
+/// Fetch a drone's simplified flight track within a window, oldest first
+pub async fn fetch_drone_track(
+    drone_id: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    max_points: u32,
+) -> Result<Vec<TrackSample>, String> {
+    let data = execute::<GetDroneTrack>(GetDroneTrackVariables {
+        drone_id: drone_id.to_string(),
+        time_range: TimeRange { start, end },
+        max_points: i32::try_from(max_points).unwrap_or(i32::MAX),
+    })
+    .await?;
+
+    Ok(data
+        .drone_track
+        .points
+        .into_iter()
+        .map(|p| TrackSample {
+            recorded_at: p.recorded_at,
+            latitude: p.position.latitude,
+            longitude: p.position.longitude,
+        })
+        .collect())
+}
+
+/// Fetch a drone's telemetry history, averaged into `resolution_sec` buckets
+pub async fn fetch_telemetry_history(
+    drone_id: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    resolution_sec: Option<u32>,
+    limit: u32,
+) -> Result<Vec<TelemetrySample>, String> {
+    let data = execute::<GetTelemetryHistory>(GetTelemetryHistoryVariables {
+        drone_id: drone_id.to_string(),
+        time_range: TimeRange { start, end },
+        resolution_sec: resolution_sec.map(|s| i32::try_from(s).unwrap_or(i32::MAX)),
+        first: i32::try_from(limit).unwrap_or(i32::MAX),
+        after: None,
+    })
+    .await?;
+
+    Ok(data
+        .telemetry_history
+        .edges
+        .into_iter()
+        .map(|edge| telemetry_sample(edge.node))
+        .collect())
+}
+
+/// Convert a telemetry selection into a chart sample
+pub fn telemetry_sample(p: TelemetryPoint) -> TelemetrySample {
+    TelemetrySample {
+        drone_id: Uuid::parse_str(&p.drone_id).unwrap_or_default(),
+        recorded_at: p.recorded_at,
+        altitude_m: p.position.altitude_m,
+        fuel_pct: p.fuel_remaining_pct,
+        speed_mps: p.position.speed_mps,
+        engine_temp_c: p.engine_temp_c,
+        extensions: match p.extensions {
+            Some(serde_json::Value::Object(map)) => map
+                .into_iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(s) => (key, s),
+                    other => (key, other.to_string()),
+                })
+                .collect(),
+            _ => Vec::new(),
+        },
+    }
 }
@@ -0,0 +1,36 @@
+//! # Audio Cues
+//!
+//! Short synthesized tones for alerts; no audio assets are shipped.
+
+use web_sys::{AudioContext, OscillatorType};
+
+/// Critical alert tone frequency (Hz)
+const ALERT_TONE_HZ: f32 = 880.0;
+
+/// Critical alert tone length (seconds)
+const ALERT_TONE_SECS: f64 = 0.25;
+
+/// Play the critical-alert tone; failures (e.g. autoplay policy) are logged
+pub fn play_alert_tone() {
+    if let Err(e) = try_play_tone(ALERT_TONE_HZ, ALERT_TONE_SECS) {
+        log::warn!("Alert tone failed: {:?}", e);
+    }
+}
+
+fn try_play_tone(frequency_hz: f32, duration_secs: f64) -> Result<(), wasm_bindgen::JsValue> {
+    let ctx = AudioContext::new()?;
+    let oscillator = ctx.create_oscillator()?;
+    let gain = ctx.create_gain()?;
+
+    oscillator.set_type(OscillatorType::Square);
+    oscillator.frequency().set_value(frequency_hz);
+    gain.gain().set_value(0.1);
+
+    oscillator.connect_with_audio_node(&gain)?;
+    gain.connect_with_audio_node(&ctx.destination())?;
+
+    let now = ctx.current_time();
+    oscillator.start_with_when(now)?;
+    oscillator.stop_with_when(now + duration_secs)?;
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! # Runtime Configuration
+//!
+//! Endpoint URLs resolved once at startup so the same WASM bundle deploys
+//! anywhere. Each endpoint takes the first value found in:
+//!
+//! 1. `window.__DRONE_CONFIG__`, injected by the hosting page
+//! 2. `/config.json`, served by the API
+//! 3. Build-time defaults from `DRONE_API_URL`, `DRONE_WS_URL` and
+//!    `DRONE_EVENTS_URL`, else paths on the page's origin
+//!
+//! Paths resolve against the page's origin. Schemes follow the page: `wss`
+//! and `https` on a secure page, `ws` and `http` otherwise.
+
+use gloo_net::http::Request;
+use serde::Deserialize;
+use std::sync::OnceLock;
+use wasm_bindgen::JsValue;
+
+const DEFAULT_API_URL: &str = match option_env!("DRONE_API_URL") {
+    Some(url) => url,
+    None => "/graphql",
+};
+
+const DEFAULT_WS_URL: &str = match option_env!("DRONE_WS_URL") {
+    Some(url) => url,
+    None => "/graphql/ws",
+};
+
+const DEFAULT_EVENTS_URL: &str = match option_env!("DRONE_EVENTS_URL") {
+    Some(url) => url,
+    None => "/events",
+};
+
+/// Served by the API alongside the GraphQL endpoint
+const CONFIG_PATH: &str = "/config.json";
+
+/// Global the hosting page may set before the bundle loads
+const WINDOW_CONFIG_KEY: &str = "__DRONE_CONFIG__";
+
+static CONFIG: OnceLock<RuntimeConfig> = OnceLock::new();
+
+/// Resolved endpoint URLs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    /// GraphQL HTTP endpoint
+    pub api_url: String,
+    /// GraphQL subscription WebSocket endpoint
+    pub ws_url: String,
+    /// Server-sent events endpoint; `/{convoy_id}` is appended
+    pub events_url: String,
+}
+
+/// Endpoints from one configuration source; unset ones fall through
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigOverrides {
+    api_url: Option<String>,
+    ws_url: Option<String>,
+    events_url: Option<String>,
+}
+
+impl ConfigOverrides {
+    fn complete(&self) -> bool {
+        self.api_url.is_some() && self.ws_url.is_some() && self.events_url.is_some()
+    }
+
+    /// Fill unset endpoints from `fallback`
+    fn or(self, fallback: Self) -> Self {
+        Self {
+            api_url: self.api_url.or(fallback.api_url),
+            ws_url: self.ws_url.or(fallback.ws_url),
+            events_url: self.events_url.or(fallback.events_url),
+        }
+    }
+}
+
+/// Scheme and host of the page serving the bundle
+struct PageOrigin {
+    secure: bool,
+    host: String,
+}
+
+impl PageOrigin {
+    fn current() -> Self {
+        let location = web_sys::window().map(|w| w.location());
+        let protocol = location.as_ref().and_then(|l| l.protocol().ok());
+        let host = location.and_then(|l| l.host().ok()).filter(|h| !h.is_empty());
+        Self {
+            secure: protocol.as_deref() == Some("https:"),
+            host: host.unwrap_or_else(|| "localhost:8080".to_string()),
+        }
+    }
+
+    /// Absolute URL for `url`, with the scheme following the page's
+    fn resolve(&self, url: &str, websocket: bool) -> String {
+        let url = url.trim();
+        let (secure, rest) = match url.split_once("://") {
+            // Keep an explicit TLS choice, but never downgrade a secure page,
+            // which the browser would block as mixed content
+            Some((given, rest)) => {
+                (self.secure || matches!(given, "https" | "wss"), rest.to_string())
+            }
+            None => match url.strip_prefix("//") {
+                Some(rest) => (self.secure, rest.to_string()),
+                None => (self.secure, format!("{}/{}", self.host, url.trim_start_matches('/'))),
+            },
+        };
+        let scheme = match (websocket, secure) {
+            (true, true) => "wss",
+            (true, false) => "ws",
+            (false, true) => "https",
+            (false, false) => "http",
+        };
+        format!("{scheme}://{rest}")
+    }
+}
+
+impl RuntimeConfig {
+    fn resolve(overrides: ConfigOverrides, origin: &PageOrigin) -> Self {
+        let ConfigOverrides {
+            api_url,
+            ws_url,
+            events_url,
+        } = overrides;
+        Self {
+            api_url: origin.resolve(api_url.as_deref().unwrap_or(DEFAULT_API_URL), false),
+            ws_url: origin.resolve(ws_url.as_deref().unwrap_or(DEFAULT_WS_URL), true),
+            events_url: origin
+                .resolve(events_url.as_deref().unwrap_or(DEFAULT_EVENTS_URL), false)
+                .trim_end_matches('/')
+                .to_string(),
+        }
+    }
+}
+
+/// Endpoints injected by the hosting page, if any
+fn window_overrides() -> ConfigOverrides {
+    let Some(window) = web_sys::window() else {
+        return ConfigOverrides::default();
+    };
+    js_sys::Reflect::get(&window, &JsValue::from_str(WINDOW_CONFIG_KEY))
+        .ok()
+        .filter(JsValue::is_object)
+        .and_then(|value| js_sys::JSON::stringify(&value).ok())
+        .map(String::from)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Endpoints served by the API; static hosts without it answer 404
+async fn served_overrides() -> ConfigOverrides {
+    let response = match Request::get(CONFIG_PATH).send().await {
+        Ok(response) if response.ok() => response,
+        Ok(response) => {
+            log::debug!("No runtime config at {} ({})", CONFIG_PATH, response.status());
+            return ConfigOverrides::default();
+        }
+        Err(e) => {
+            log::debug!("Runtime config fetch failed: {}", e);
+            return ConfigOverrides::default();
+        }
+    };
+    response.json().await.unwrap_or_else(|e| {
+        log::warn!("Ignoring malformed {}: {}", CONFIG_PATH, e);
+        ConfigOverrides::default()
+    })
+}
+
+/// Resolve the endpoints; call once before mounting the app
+pub async fn load_runtime_config() {
+    let injected = window_overrides();
+    let overrides = if injected.complete() {
+        injected
+    } else {
+        injected.or(served_overrides().await)
+    };
+
+    let config = RuntimeConfig::resolve(overrides, &PageOrigin::current());
+    log::info!("API endpoint {}, subscriptions {}", config.api_url, config.ws_url);
+    let _ = CONFIG.set(config);
+}
+
+/// Resolved endpoints; build-time defaults until [`load_runtime_config`] ran
+pub fn runtime_config() -> &'static RuntimeConfig {
+    CONFIG.get_or_init(|| {
+        RuntimeConfig::resolve(ConfigOverrides::default(), &PageOrigin::current())
+    })
+}
@@ -0,0 +1,153 @@
+//! # Connection Quality
+//!
+//! Measures the HUD's link to the backend three ways: how long a `health`
+//! query takes over HTTP, the WebSocket ping round trip, and gaps in the
+//! once-a-second `heartbeat` subscription. Comparing them separates a local
+//! network problem (everything slow or failing) from a backend one (HTTP
+//! fine but the live stream stalled, or the reverse).
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use futures::future::{select, Either};
+use gloo_timers::future::TimeoutFuture;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::services::api::check_health;
+use crate::state::{use_app_state, AppState, ConnectionMetrics, ConnectionQuality};
+
+/// How often the `health` query is timed (ms)
+const HEALTH_INTERVAL_MS: u32 = 5_000;
+
+/// A `health` query taking longer than this counts as failed (ms)
+const HEALTH_TIMEOUT_MS: u32 = 3_000;
+
+/// How often the WebSocket is pinged (ms)
+pub const PING_INTERVAL_MS: u32 = 5_000;
+
+/// How often the quality is reassessed, so missed heartbeats show (ms)
+const ASSESS_INTERVAL_MS: u32 = 1_000;
+
+/// Server heartbeat period (ms)
+const HEARTBEAT_PERIOD_MS: f64 = 1_000.0;
+
+/// API latency or ping round trip above which the link is degraded (ms)
+const DEGRADED_LATENCY_MS: f64 = 500.0;
+
+/// Missed heartbeats before the live stream counts as degraded
+const DEGRADED_MISSED_HEARTBEATS: u32 = 2;
+
+/// Missed heartbeats before the live stream counts as lost
+const LOST_MISSED_HEARTBEATS: u32 = 5;
+
+/// Consecutive failed `health` queries before the API counts as lost
+const LOST_API_FAILURES: u32 = 2;
+
+/// Milliseconds on the page's monotonic clock
+pub fn now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|w| w.performance())
+        .map_or_else(js_sys::Date::now, |p| p.now())
+}
+
+/// Whole heartbeat periods gone by without one, beyond the expected one;
+/// `None` while no WebSocket is open
+pub fn missed_heartbeats(metrics: &ConnectionMetrics, now: f64) -> Option<u32> {
+    let age = now - metrics.last_heartbeat_at?;
+    Some(((age - HEARTBEAT_PERIOD_MS).max(0.0) / HEARTBEAT_PERIOD_MS) as u32)
+}
+
+/// Grade the connection. `live` is false when a convoy is selected but its
+/// subscriptions are not connected.
+pub fn assess(metrics: &ConnectionMetrics, live: bool, now: f64) -> ConnectionQuality {
+    let missed = missed_heartbeats(metrics, now);
+    let api_lost = metrics.api_failures >= LOST_API_FAILURES;
+    let stream_lost = missed.is_none_or(|n| n >= LOST_MISSED_HEARTBEATS);
+    if api_lost && stream_lost {
+        return ConnectionQuality::Lost;
+    }
+
+    let slow = [metrics.api_latency_ms, metrics.ws_rtt_ms]
+        .into_iter()
+        .flatten()
+        .any(|ms| ms > DEGRADED_LATENCY_MS);
+    let stalled = missed.is_some_and(|n| n >= DEGRADED_MISSED_HEARTBEATS);
+    if !live || metrics.api_failures > 0 || slow || stalled {
+        ConnectionQuality::Degraded
+    } else {
+        ConnectionQuality::Good
+    }
+}
+
+/// Likely cause of a degraded or lost connection, for the indicator tooltip
+pub fn diagnosis(metrics: &ConnectionMetrics, now: f64) -> Option<&'static str> {
+    let api_down = metrics.api_failures > 0;
+    let stalled = missed_heartbeats(metrics, now)
+        .is_some_and(|n| n >= DEGRADED_MISSED_HEARTBEATS);
+    let slow = |ms: Option<f64>| ms.is_some_and(|ms| ms > DEGRADED_LATENCY_MS);
+    match (api_down, stalled) {
+        (true, true) => Some("No response over HTTP or WebSocket; check the local network"),
+        (true, false) => Some("API not answering while the live stream is up; backend issue"),
+        (false, true) => Some("Live stream stalled while the API answers; backend issue"),
+        _ if slow(metrics.api_latency_ms) && slow(metrics.ws_rtt_ms) => {
+            Some("HTTP and WebSocket both slow; likely the local network")
+        }
+        _ if slow(metrics.api_latency_ms) => Some("API slow to answer; backend under load"),
+        _ if slow(metrics.ws_rtt_ms) => Some("WebSocket round trips slow"),
+        _ => None,
+    }
+}
+
+/// Time `health` queries and keep `AppState::connection_quality` current
+pub fn use_connection_quality() {
+    let state = use_app_state();
+
+    let probing = Rc::new(Cell::new(false));
+    let probe_state = state.clone();
+    gloo_timers::callback::Interval::new(HEALTH_INTERVAL_MS, move || {
+        if probing.replace(true) {
+            return;
+        }
+        let state = probe_state.clone();
+        let probing = Rc::clone(&probing);
+        spawn_local(async move {
+            let started = now_ms();
+            let outcome = match select(
+                Box::pin(check_health()),
+                TimeoutFuture::new(HEALTH_TIMEOUT_MS),
+            )
+            .await
+            {
+                Either::Left((result, _)) => result,
+                Either::Right(_) => Err("health query timed out".to_string()),
+            };
+            let latency = now_ms() - started;
+            state.connection.update(|c| match outcome {
+                Ok(()) => {
+                    c.api_latency_ms = Some(latency);
+                    c.api_failures = 0;
+                }
+                Err(e) => {
+                    log::debug!("Health probe failed: {}", e);
+                    c.api_latency_ms = None;
+                    c.api_failures += 1;
+                }
+            });
+            reassess(&state);
+            probing.set(false);
+        });
+    })
+    .forget();
+
+    gloo_timers::callback::Interval::new(ASSESS_INTERVAL_MS, move || reassess(&state)).forget();
+}
+
+fn reassess(state: &AppState) {
+    let live =
+        state.selected_convoy.get_untracked().is_none() || state.ws_connected.get_untracked();
+    let quality = state.connection.with_untracked(|c| assess(c, live, now_ms()));
+    if state.connection_quality.get_untracked() != quality {
+        state.connection_quality.set(quality);
+    }
+}
@@ -0,0 +1,149 @@
+//! # Convoy Selection
+//!
+//! Loads the active convoy list, restores the persisted selection and
+//! refreshes convoy-scoped state when the operator switches convoys or
+//! connectivity returns.
+
+use crate::services::api::{fetch_active_convoys, fetch_convoy_stats, fetch_leaderboard};
+use crate::services::offline::{enter_offline_mode, HudSnapshot};
+use crate::state::{stored_convoy_selection, use_app_state, AppState, ConvoyOverview};
+use drone_graphql_client::operations::ConvoyStats;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use uuid::Uuid;
+
+/// Leaderboard rows fetched when a convoy is selected
+const LEADERBOARD_LIMIT: u32 = 25;
+
+/// Populate the convoy list and keep convoy-scoped state in step with the
+/// selection
+pub fn use_convoys() {
+    let state = use_app_state();
+
+    // Initial load, repeated as a full refetch whenever connectivity returns
+    let load_state = state.clone();
+    Effect::new(move |_| {
+        load_state.resync.track();
+        let state = load_state.clone();
+        spawn_local(async move { load_convoys(&state).await });
+    });
+
+    // Convoy-scoped panels start empty for the new convoy
+    Effect::new(move |previous: Option<Option<Uuid>>| {
+        let selected = state.selected_convoy.get();
+        let switched = previous.is_some_and(|prev| prev != selected);
+
+        // Offline mode may have just restored this convoy from the cache
+        let restored = state.offline.get_untracked()
+            && HudSnapshot::load().is_some_and(|s| Some(s.convoy_id) == selected);
+
+        if switched && !restored {
+            state.leaderboard.set(Vec::new());
+            state.engagements.set(Vec::new());
+            state.drones.update(|drones| {
+                drones.retain(|_, d| Some(d.convoy_id) == selected);
+            });
+        }
+
+        let state = state.clone();
+        match selected {
+            Some(convoy_id) if switched && !restored => spawn_local(async move {
+                match fetch_leaderboard(convoy_id, LEADERBOARD_LIMIT).await {
+                    Ok(entries) => state.leaderboard.set(entries),
+                    Err(e) => log::warn!("Leaderboard fetch failed: {}", e),
+                }
+            }),
+            None => spawn_local(async move { refresh_convoy_stats(&state).await }),
+            Some(_) => {}
+        }
+
+        selected
+    });
+}
+
+/// Fetch the convoy list and restore the selection. When the selection is
+/// unchanged its leaderboard is refetched, reconciling any cached view.
+async fn load_convoys(state: &AppState) {
+    let convoys = match fetch_active_convoys().await {
+        Ok(convoys) => convoys,
+        Err(e) => {
+            log::warn!("Active convoy fetch failed: {}", e);
+            enter_offline_mode(state);
+            return;
+        }
+    };
+
+    let overviews: Vec<ConvoyOverview> = convoys
+        .into_iter()
+        .filter_map(|c| {
+            Some(ConvoyOverview {
+                convoy_id: Uuid::parse_str(&c.convoy_id).ok()?,
+                callsign: c.callsign,
+                mission_type: c.mission_type,
+                status: c.status,
+                drone_count: c.drone_count.max(0) as u32,
+                airborne_count: 0,
+                total_engagements: 0,
+                total_hits: 0,
+                avg_accuracy_pct: 0.0,
+                avg_fuel_pct: 0.0,
+            })
+        })
+        .collect();
+
+    // Restore the stored choice if it still exists, else the first convoy
+    let selection = match stored_convoy_selection() {
+        Some(None) => None,
+        Some(Some(id)) if overviews.iter().any(|c| c.convoy_id == id) => Some(id),
+        _ => overviews.first().map(|c| c.convoy_id),
+    };
+
+    state.offline.set(false);
+    state.convoys.set(overviews);
+    let unchanged = state.selected_convoy.get_untracked() == selection;
+    state.selected_convoy.set(selection);
+
+    if let Some(convoy_id) = selection.filter(|_| unchanged) {
+        match fetch_leaderboard(convoy_id, LEADERBOARD_LIMIT).await {
+            Ok(entries) => state.leaderboard.set(entries),
+            Err(e) => log::warn!("Leaderboard fetch failed: {}", e),
+        }
+    }
+    refresh_convoy_stats(state).await;
+}
+
+/// Refresh the per-convoy statistics shown on the overview cards
+async fn refresh_convoy_stats(state: &AppState) {
+    let ids: Vec<Uuid> = state
+        .convoys
+        .get_untracked()
+        .iter()
+        .map(|c| c.convoy_id)
+        .collect();
+
+    for convoy_id in ids {
+        let stats = match fetch_convoy_stats(convoy_id).await {
+            Ok(stats) => stats,
+            Err(e) => {
+                log::warn!("Stats fetch failed for convoy {}: {}", convoy_id, e);
+                continue;
+            }
+        };
+
+        apply_convoy_stats(state, convoy_id, &stats);
+    }
+}
+
+/// Copy server-computed statistics onto the convoy's overview card
+pub fn apply_convoy_stats(state: &AppState, convoy_id: Uuid, stats: &ConvoyStats) {
+    state.convoys.update(|convoys| {
+        if let Some(card) = convoys.iter_mut().find(|c| c.convoy_id == convoy_id) {
+            card.drone_count = stats.drone_count.max(0) as u32;
+            card.airborne_count = stats.airborne_count.max(0) as u32;
+            card.total_engagements = stats.total_engagements.max(0) as u32;
+            card.total_hits = stats.total_hits.max(0) as u32;
+            card.avg_accuracy_pct = stats.average_accuracy_pct;
+            card.avg_fuel_pct = stats.average_fuel_pct;
+        }
+    });
+}
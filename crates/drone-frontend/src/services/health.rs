@@ -0,0 +1,54 @@
+//! # Fleet Health
+//!
+//! Polls the selected convoy's health scores for the drone list.
+
+use crate::services::api::fetch_fleet_readiness;
+use crate::state::use_app_state;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+/// Refresh interval (ms); scores only change as telemetry arrives
+const REFRESH_INTERVAL_MS: u32 = 10_000;
+
+/// Drone scores requested; the server maximum, so every scored drone is listed
+const SCORES_LIMIT: u32 = 100;
+
+/// Keep `AppState::readiness` current for the selected convoy
+pub fn use_fleet_health() {
+    let state = use_app_state();
+
+    let refresh = {
+        let state = state.clone();
+        move || {
+            let Some(convoy_id) = state.selected_convoy.get_untracked() else {
+                state.readiness.set(None);
+                return;
+            };
+            if state.offline.get_untracked() {
+                return;
+            }
+            let state = state.clone();
+            spawn_local(async move {
+                match fetch_fleet_readiness(convoy_id, SCORES_LIMIT).await {
+                    // Drop results for a convoy the operator has since left
+                    Ok(readiness) if state.selected_convoy.get_untracked() == Some(convoy_id) => {
+                        state.readiness.set(Some(readiness));
+                    }
+                    Ok(_) => {}
+                    Err(e) => log::warn!("Fleet readiness fetch failed: {}", e),
+                }
+            });
+        }
+    };
+
+    // Immediately on a convoy switch or resync, then on a timer
+    let on_change = refresh.clone();
+    Effect::new(move |_| {
+        state.selected_convoy.track();
+        state.resync.track();
+        state.readiness.set(None);
+        on_change();
+    });
+
+    gloo_timers::callback::Interval::new(REFRESH_INTERVAL_MS, refresh).forget();
+}
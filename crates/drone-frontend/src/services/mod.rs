@@ -3,7 +3,19 @@
 //! API and WebSocket services for backend communication.
 
 pub mod api;
+pub mod audio;
+pub mod config;
+pub mod connection;
+pub mod convoys;
+pub mod health;
+pub mod offline;
 pub mod websocket;
 
 pub use api::*;
+pub use audio::*;
+pub use config::*;
+pub use connection::*;
+pub use convoys::*;
+pub use health::*;
+pub use offline::*;
 pub use websocket::*;
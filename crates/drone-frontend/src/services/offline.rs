@@ -0,0 +1,147 @@
+//! # Offline Cache
+//!
+//! Persists the last known HUD state to local storage, falls back to it when
+//! the API is unreachable and triggers a full refetch once it is back.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+use gloo_storage::{LocalStorage, Storage};
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::services::api::fetch_active_convoys;
+use crate::state::{use_app_state, AppState, DroneState, LeaderboardEntry, TelemetrySample};
+
+/// Local storage key holding the last known HUD state
+const SNAPSHOT_STORAGE_KEY: &str = "drone_hud_snapshot";
+
+/// How often live state is written to the cache (ms)
+const SNAPSHOT_INTERVAL_MS: u32 = 5_000;
+
+/// How often connectivity is probed while disconnected (ms)
+const PROBE_INTERVAL_MS: u32 = 10_000;
+
+/// Last known state of a convoy's HUD
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HudSnapshot {
+    pub convoy_id: Uuid,
+    pub saved_at: DateTime<Utc>,
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub drones: Vec<DroneState>,
+    pub selected_drone: Option<Uuid>,
+    pub telemetry: Vec<TelemetrySample>,
+}
+
+impl HudSnapshot {
+    /// Capture the selected convoy's state; `None` on the overview
+    fn capture(state: &AppState) -> Option<Self> {
+        let convoy_id = state.selected_convoy.get_untracked()?;
+        Some(Self {
+            convoy_id,
+            saved_at: Utc::now(),
+            leaderboard: state.leaderboard.get_untracked(),
+            drones: state.drones.with_untracked(|drones| {
+                drones
+                    .values()
+                    .filter(|d| d.convoy_id == convoy_id)
+                    .cloned()
+                    .collect()
+            }),
+            selected_drone: state.selected_drone.get_untracked(),
+            telemetry: state.telemetry.get_untracked(),
+        })
+    }
+
+    /// Load the cached snapshot, if any
+    pub fn load() -> Option<Self> {
+        LocalStorage::get(SNAPSHOT_STORAGE_KEY).ok()
+    }
+
+    fn save(&self) {
+        if let Err(e) = LocalStorage::set(SNAPSHOT_STORAGE_KEY, self) {
+            log::warn!("Failed to cache HUD state: {}", e);
+        }
+    }
+}
+
+/// Cache live state periodically and watch for connectivity loss and recovery
+pub fn use_offline_cache() {
+    let state = use_app_state();
+
+    // Only cache while connected so a stale view never overwrites good data
+    let cache_state = state.clone();
+    gloo_timers::callback::Interval::new(SNAPSHOT_INTERVAL_MS, move || {
+        if cache_state.offline.get_untracked() || !cache_state.ws_connected.get_untracked() {
+            return;
+        }
+        if let Some(snapshot) = HudSnapshot::capture(&cache_state) {
+            snapshot.save();
+            cache_state.last_update.set(Some(snapshot.saved_at));
+        }
+    })
+    .forget();
+
+    let probing = Rc::new(Cell::new(false));
+    gloo_timers::callback::Interval::new(PROBE_INTERVAL_MS, move || {
+        let offline = state.offline.get_untracked();
+        let disconnected =
+            state.selected_convoy.get_untracked().is_some() && !state.ws_connected.get_untracked();
+        if !(offline || disconnected) || probing.replace(true) {
+            return;
+        }
+
+        let state = state.clone();
+        let probing = Rc::clone(&probing);
+        spawn_local(async move {
+            match fetch_active_convoys().await {
+                Ok(_) => {
+                    log::info!("API reachable again; resynchronizing");
+                    state.offline.set(false);
+                    state.resync.update(|n| *n += 1);
+                }
+                Err(e) => {
+                    log::warn!("Connectivity probe failed: {}", e);
+                    enter_offline_mode(&state);
+                }
+            }
+            probing.set(false);
+        });
+    })
+    .forget();
+}
+
+/// Switch to the cached view. Panels keep whatever they already show; if
+/// they are empty the cached snapshot is restored.
+pub fn enter_offline_mode(state: &AppState) {
+    if state.offline.get_untracked() {
+        return;
+    }
+    state.offline.set(true);
+
+    let empty = state.leaderboard.with_untracked(Vec::is_empty)
+        && state.drones.with_untracked(|d| d.is_empty());
+    if !empty {
+        return;
+    }
+    let Some(snapshot) = HudSnapshot::load() else {
+        log::warn!("API unreachable and no cached HUD state");
+        return;
+    };
+
+    state.leaderboard.set(snapshot.leaderboard);
+    state.drones.set(
+        snapshot
+            .drones
+            .into_iter()
+            .map(|d| (d.drone_id, d))
+            .collect(),
+    );
+    state.telemetry.set(snapshot.telemetry);
+    state.selected_drone.set(snapshot.selected_drone);
+    state.last_update.set(Some(snapshot.saved_at));
+    state.selected_convoy.set(Some(snapshot.convoy_id));
+}
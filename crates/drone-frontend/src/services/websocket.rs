@@ -1,156 +1,136 @@
 //! # WebSocket Service
 //!
-//! GraphQL subscription client for real-time updates.
+//! GraphQL subscription client for real-time updates. Falls back to the
+//! server-sent events endpoint when the WebSocket handshake fails.
 
-use crate::state::{use_app_state, EngagementEvent, LeaderboardEntry};
-use chrono::Utc;
+use crate::state::{use_app_state, Alert, AlertSeverity, DroneStatus, EngagementEvent};
+use crate::services::api::telemetry_sample;
+use crate::services::config::runtime_config;
+use crate::services::connection::{now_ms, PING_INTERVAL_MS};
+use crate::services::convoys::apply_convoy_stats;
+use drone_graphql_client::subscriptions::{
+    Alerts, ConvoyStatsUpdates, ConvoyStatsVariables, ConvoyVariables, DroneStatusChanges,
+    DroneTelemetry, DroneVariables, EngagementEvents, Heartbeat, LeaderboardUpdates,
+};
+use drone_graphql_client::ws::{decode_next, ClientMessage, ServerMessage, SUBPROTOCOL};
+use drone_graphql_client::GraphQLResponse;
+use gloo_storage::{LocalStorage, Storage};
 use leptos::prelude::*;
-use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use uuid::Uuid;
 use wasm_bindgen::prelude::*;
-use web_sys::{CloseEvent, MessageEvent, WebSocket};
-
-const WS_URL: &str = "ws://localhost:8080/graphql/ws";
-
-/// GraphQL WebSocket message types
-#[derive(Serialize)]
-#[serde(tag = "type")]
-enum WsClientMessage {
-    #[serde(rename = "connection_init")]
-    ConnectionInit { payload: serde_json::Value },
-    #[serde(rename = "subscribe")]
-    Subscribe { id: String, payload: SubscribePayload },
-}
+use web_sys::{CloseEvent, EventSource, MessageEvent, WebSocket};
 
-#[derive(Serialize)]
-struct SubscribePayload {
-    query: String,
-    variables: serde_json::Value,
-}
+const ENGAGEMENT_SUB: &str = "engagement-sub";
+const LEADERBOARD_SUB: &str = "leaderboard-sub";
+const ALERT_SUB: &str = "alert-sub";
+const TELEMETRY_SUB: &str = "telemetry-sub";
+const STATS_SUB: &str = "stats-sub";
+const STATUS_SUB: &str = "status-sub";
+const HEARTBEAT_SUB: &str = "heartbeat-sub";
 
-#[derive(Deserialize, Debug)]
-#[serde(tag = "type")]
-enum WsServerMessage {
-    #[serde(rename = "connection_ack")]
-    ConnectionAck,
-    #[serde(rename = "next")]
-    Next { id: String, payload: NextPayload },
-    #[serde(rename = "error")]
-    Error { id: String, payload: Vec<GraphQLError> },
-    #[serde(rename = "complete")]
-    Complete { id: String },
-}
+/// Minimum seconds between convoy statistics pushes
+const STATS_INTERVAL_SECS: i32 = 2;
 
-#[derive(Deserialize, Debug)]
-struct NextPayload {
-    data: serde_json::Value,
-}
-
-#[derive(Deserialize, Debug)]
-struct GraphQLError {
-    message: String,
-}
+/// Local storage key holding the API bearer token
+const TOKEN_STORAGE_KEY: &str = "drone_api_token";
 
 /// WebSocket connection manager
 pub struct WsClient {
     ws: WebSocket,
+    /// Event stream opened when the WebSocket handshake failed
+    fallback: Rc<RefCell<Option<EventSource>>>,
+    /// Pings the server to measure round trips; stops when dropped
+    _pinger: gloo_timers::callback::Interval,
 }
 
 impl WsClient {
     pub fn connect(convoy_id: Uuid) -> Result<Self, JsValue> {
-        let ws = WebSocket::new(WS_URL)?;
+        let ws = WebSocket::new_with_str(&runtime_config().ws_url, SUBPROTOCOL)?;
         ws.set_binary_type(web_sys::BinaryType::Arraybuffer);
 
         let state = use_app_state();
         let convoy_id_str = convoy_id.to_string();
+        let opened = Rc::new(Cell::new(false));
+        let fallback = Rc::new(RefCell::new(None));
+        // When the outstanding ping was sent
+        let ping_sent = Rc::new(Cell::new(None::<f64>));
 
         // Connection opened
         let ws_clone = ws.clone();
         let convoy_id_clone = convoy_id_str.clone();
+        let opened_flag = opened.clone();
         let onopen = Closure::wrap(Box::new(move |_| {
             log::info!("WebSocket connected");
+            opened_flag.set(true);
             state.ws_connected.set(true);
+            // Heartbeats are missed from here until the first arrives
+            state.connection.update(|c| c.last_heartbeat_at = Some(now_ms()));
 
-            // Send connection init
-            let init = WsClientMessage::ConnectionInit {
-                payload: serde_json::json!({}),
-            };
-            let msg = serde_json::to_string(&init).unwrap();
-            let _ = ws_clone.send_with_str(&msg);
-
-            // Subscribe to engagement events
-            let subscribe_engagements = WsClientMessage::Subscribe {
-                id: "engagement-sub".to_string(),
-                payload: SubscribePayload {
-                    query: r#"
-                        subscription EngagementEvents($convoyId: ID!) {
-                            engagementEvents(convoyId: $convoyId) {
-                                convoyId
-                                droneId
-                                callsign
-                                hit
-                                weaponType
-                                newAccuracyPct
-                                timestamp
-                            }
-                        }
-                    "#.to_string(),
-                    variables: serde_json::json!({
-                        "convoyId": convoy_id_clone
-                    }),
-                },
+            // Send connection init, authenticating with the stored token if any
+            let token: Option<String> = LocalStorage::get(TOKEN_STORAGE_KEY).ok();
+            let init = ClientMessage::connection_init(token.as_deref());
+            let _ = ws_clone.send_with_str(&init.to_text());
+
+            // Subscribe to engagement events, leaderboard updates, alerts, stats,
+            // drone status changes and the server heartbeat
+            let variables = ConvoyVariables {
+                convoy_id: convoy_id_clone.clone(),
             };
-            let msg = serde_json::to_string(&subscribe_engagements).unwrap();
-            let _ = ws_clone.send_with_str(&msg);
-
-            // Subscribe to leaderboard updates
-            let subscribe_leaderboard = WsClientMessage::Subscribe {
-                id: "leaderboard-sub".to_string(),
-                payload: SubscribePayload {
-                    query: r#"
-                        subscription LeaderboardUpdates($convoyId: ID!) {
-                            leaderboardUpdates(convoyId: $convoyId) {
-                                convoyId
-                                droneId
-                                callsign
-                                newRank
-                                oldRank
-                                accuracyPct
-                                changeType
-                                timestamp
-                            }
-                        }
-                    "#.to_string(),
-                    variables: serde_json::json!({
-                        "convoyId": convoy_id_clone
-                    }),
-                },
+            let stats_variables = ConvoyStatsVariables {
+                convoy_id: convoy_id_clone.clone(),
+                interval_sec: STATS_INTERVAL_SECS,
             };
-            let msg = serde_json::to_string(&subscribe_leaderboard).unwrap();
-            let _ = ws_clone.send_with_str(&msg);
+            let subscriptions = [
+                ClientMessage::subscribe::<EngagementEvents>(ENGAGEMENT_SUB, variables.clone()),
+                ClientMessage::subscribe::<LeaderboardUpdates>(LEADERBOARD_SUB, variables.clone()),
+                ClientMessage::subscribe::<Alerts>(ALERT_SUB, variables.clone()),
+                ClientMessage::subscribe::<DroneStatusChanges>(STATUS_SUB, variables),
+                ClientMessage::subscribe::<ConvoyStatsUpdates>(STATS_SUB, stats_variables),
+                ClientMessage::subscribe::<Heartbeat>(HEARTBEAT_SUB, ()),
+            ];
+            for msg in subscriptions.into_iter().flatten() {
+                let _ = ws_clone.send_with_str(&msg.to_text());
+            }
+            if let Some(drone_id) = state.selected_drone.get_untracked() {
+                send_drone_subscription(&ws_clone, drone_id);
+            }
         }) as Box<dyn FnMut(JsValue)>);
         ws.set_onopen(Some(onopen.as_ref().unchecked_ref()));
         onopen.forget();
 
         // Message received
         let state_clone = state.clone();
+        let ws_message = ws.clone();
+        let ping_answered = ping_sent.clone();
         let onmessage = Closure::wrap(Box::new(move |e: MessageEvent| {
             if let Ok(txt) = e.data().dyn_into::<js_sys::JsString>() {
                 let msg_str: String = txt.into();
-                if let Ok(msg) = serde_json::from_str::<WsServerMessage>(&msg_str) {
+                if let Ok(msg) = ServerMessage::from_text(&msg_str) {
                     match msg {
-                        WsServerMessage::ConnectionAck => {
+                        ServerMessage::ConnectionAck => {
                             log::info!("WebSocket connection acknowledged");
                         }
-                        WsServerMessage::Next { id, payload } => {
-                            handle_subscription_data(&state_clone, &id, payload.data);
+                        ServerMessage::Next { id, payload } => {
+                            handle_subscription_data(&state_clone, &id, payload);
                         }
-                        WsServerMessage::Error { id, payload } => {
+                        ServerMessage::Error { id, payload } => {
                             log::error!("Subscription error for {}: {:?}", id, payload);
                         }
-                        WsServerMessage::Complete { id } => {
+                        ServerMessage::Complete { id } => {
                             log::info!("Subscription {} completed", id);
                         }
+                        ServerMessage::Ping => {
+                            let _ = ws_message.send_with_str(&ClientMessage::Pong.to_text());
+                        }
+                        ServerMessage::Pong => {
+                            if let Some(sent) = ping_answered.take() {
+                                let rtt = now_ms() - sent;
+                                state_clone.connection.update(|c| c.ws_rtt_ms = Some(rtt));
+                            }
+                        }
                     }
                 }
             }
@@ -160,9 +140,23 @@ impl WsClient {
 
         // Connection closed
         let state_close = state.clone();
+        let fallback_slot = fallback.clone();
         let onclose = Closure::wrap(Box::new(move |e: CloseEvent| {
             log::warn!("WebSocket closed: code={}, reason={}", e.code(), e.reason());
             state_close.ws_connected.set(false);
+            state_close.connection.update(|c| {
+                c.last_heartbeat_at = None;
+                c.ws_rtt_ms = None;
+            });
+
+            // Never opened: the network likely blocks WebSockets
+            if !opened.get() && fallback_slot.borrow().is_none() {
+                log::warn!("WebSocket handshake failed; falling back to server-sent events");
+                match open_event_stream(&state_close, convoy_id) {
+                    Ok(source) => *fallback_slot.borrow_mut() = Some(source),
+                    Err(e) => log::error!("Failed to open event stream: {:?}", e),
+                }
+            }
         }) as Box<dyn FnMut(CloseEvent)>);
         ws.set_onclose(Some(onclose.as_ref().unchecked_ref()));
         onclose.forget();
@@ -174,73 +168,266 @@ impl WsClient {
         ws.set_onerror(Some(onerror.as_ref().unchecked_ref()));
         onerror.forget();
 
-        Ok(Self { ws })
+        // Ping while open; an unanswered ping is left outstanding so a late
+        // pong still measures from when it was sent
+        let ws_ping = ws.clone();
+        let pinger = gloo_timers::callback::Interval::new(PING_INTERVAL_MS, move || {
+            if ws_ping.ready_state() != WebSocket::OPEN || ping_sent.get().is_some() {
+                return;
+            }
+            ping_sent.set(Some(now_ms()));
+            let _ = ws_ping.send_with_str(&ClientMessage::Ping.to_text());
+        });
+
+        Ok(Self {
+            ws,
+            fallback,
+            _pinger: pinger,
+        })
+    }
+
+    /// Move the live telemetry subscription to another drone (or none)
+    pub fn watch_drone(&self, drone_id: Option<Uuid>) {
+        if self.ws.ready_state() != WebSocket::OPEN {
+            // onopen subscribes to the drone selected at that point
+            return;
+        }
+        let complete = ClientMessage::Complete {
+            id: TELEMETRY_SUB.to_string(),
+        };
+        let _ = self.ws.send_with_str(&complete.to_text());
+        if let Some(drone_id) = drone_id {
+            send_drone_subscription(&self.ws, drone_id);
+        }
     }
 
+    /// Detach handlers and close, ending this connection's subscriptions
     pub fn close(&self) {
+        self.ws.set_onopen(None);
+        self.ws.set_onmessage(None);
+        self.ws.set_onclose(None);
+        self.ws.set_onerror(None);
         let _ = self.ws.close();
+        if let Some(source) = self.fallback.borrow_mut().take() {
+            source.set_onopen(None);
+            source.set_onerror(None);
+            source.close();
+        }
+    }
+}
+
+/// Open the SSE stream for a convoy and feed its events through the same
+/// handlers as the subscriptions.
+///
+/// Live telemetry is not relayed over SSE. The browser reconnects on its
+/// own, resuming via `Last-Event-ID`.
+fn open_event_stream(state: &crate::state::AppState, convoy_id: Uuid) -> Result<EventSource, JsValue> {
+    let mut url = format!("{}/{}", runtime_config().events_url, convoy_id);
+    if let Ok(token) = LocalStorage::get::<String>(TOKEN_STORAGE_KEY) {
+        url.push_str("?token=");
+        url.push_str(&String::from(js_sys::encode_uri_component(&token)));
+    }
+    let source = EventSource::new(&url)?;
+
+    let state_open = state.clone();
+    let onopen = Closure::wrap(Box::new(move |_| {
+        log::info!("Event stream connected");
+        state_open.ws_connected.set(true);
+    }) as Box<dyn FnMut(JsValue)>);
+    source.set_onopen(Some(onopen.as_ref().unchecked_ref()));
+    onopen.forget();
+
+    let state_error = state.clone();
+    let onerror = Closure::wrap(Box::new(move |_| {
+        log::warn!("Event stream interrupted; browser will retry");
+        state_error.ws_connected.set(false);
+    }) as Box<dyn FnMut(JsValue)>);
+    source.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+    onerror.forget();
+
+    // SSE event name, subscription it stands in for, and its response field
+    let relays = [
+        ("engagement", ENGAGEMENT_SUB, "engagementEvents"),
+        ("leaderboard", LEADERBOARD_SUB, "leaderboardUpdates"),
+        ("alert", ALERT_SUB, "alerts"),
+    ];
+    for (event_name, subscription_id, field) in relays {
+        let state = state.clone();
+        let listener = Closure::wrap(Box::new(move |e: MessageEvent| {
+            let Some(text) = e.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str::<Value>(&text) {
+                Ok(event) => {
+                    let payload = GraphQLResponse {
+                        data: Some(serde_json::json!({ field: event })),
+                        errors: None,
+                    };
+                    handle_subscription_data(&state, subscription_id, payload);
+                }
+                Err(e) => log::warn!("Bad {} event frame: {}", event_name, e),
+            }
+        }) as Box<dyn FnMut(MessageEvent)>);
+        source.add_event_listener_with_callback(event_name, listener.as_ref().unchecked_ref())?;
+        listener.forget();
+    }
+
+    Ok(source)
+}
+
+fn send_drone_subscription(ws: &WebSocket, drone_id: Uuid) {
+    let variables = DroneVariables {
+        drone_id: drone_id.to_string(),
+    };
+    match ClientMessage::subscribe::<DroneTelemetry>(TELEMETRY_SUB, variables) {
+        Ok(msg) => {
+            let _ = ws.send_with_str(&msg.to_text());
+        }
+        Err(e) => log::warn!("Failed to build telemetry subscription: {}", e),
     }
 }
 
 fn handle_subscription_data(
     state: &crate::state::AppState,
     subscription_id: &str,
-    data: serde_json::Value,
+    payload: GraphQLResponse<Value>,
 ) {
     match subscription_id {
-        "engagement-sub" => {
-            if let Some(event_data) = data.get("engagementEvents") {
-                if let Ok(event) = serde_json::from_value::<EngagementEventData>(event_data.clone()) {
-                    let engagement = EngagementEvent {
-                        id: Uuid::new_v4(),
-                        drone_id: Uuid::parse_str(&event.drone_id).unwrap_or_default(),
-                        callsign: event.callsign,
-                        hit: event.hit,
-                        weapon_type: event.weapon_type,
-                        new_accuracy_pct: event.new_accuracy_pct,
-                        timestamp: Utc::now(),
-                    };
-                    state.engagements.update(|events| {
-                        events.insert(0, engagement);
-                        if events.len() > 50 {
-                            events.truncate(50);
-                        }
-                    });
+        ENGAGEMENT_SUB => match decode_next::<EngagementEvents>(payload) {
+            Ok(data) => {
+                let event = data.engagement_events;
+                let engagement = EngagementEvent {
+                    id: Uuid::new_v4(),
+                    drone_id: Uuid::parse_str(&event.drone_id).unwrap_or_default(),
+                    callsign: event.callsign,
+                    hit: event.hit,
+                    weapon_type: event.weapon_type,
+                    target_type: event.target_type,
+                    range_km: event.range_km,
+                    new_accuracy_pct: Some(event.new_accuracy_pct),
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                };
+                state.push_engagement(engagement);
+            }
+            Err(e) => log::warn!("Bad engagement event: {}", e),
+        },
+        LEADERBOARD_SUB => match decode_next::<LeaderboardUpdates>(payload) {
+            Ok(data) => {
+                let update = data.leaderboard_updates;
+                let drone_id = Uuid::parse_str(&update.drone_id).unwrap_or_default();
+                let new_rank = update.new_rank.max(0) as u32;
+                let rank_change = update.old_rank.map_or(0, |old| old - update.new_rank);
+                state.leaderboard.update(|entries| {
+                    if let Some(entry) = entries.iter_mut().find(|e| e.drone_id == drone_id) {
+                        entry.rank = new_rank;
+                        entry.rank_change = rank_change;
+                        entry.accuracy_pct = update.accuracy_pct;
+                    }
+                    entries.sort_by_key(|e| e.rank);
+                });
+            }
+            Err(e) => log::warn!("Bad leaderboard update: {}", e),
+        },
+        ALERT_SUB => match decode_next::<Alerts>(payload) {
+            Ok(data) => {
+                let event = data.alerts;
+                state.push_alert(Alert {
+                    id: Uuid::parse_str(&event.alert_id).unwrap_or_else(|_| Uuid::new_v4()),
+                    severity: AlertSeverity::from_graphql(&event.severity),
+                    alert_type: event.alert_type,
+                    message: event.message,
+                    timestamp: event.timestamp,
+                    acknowledged: false,
+                    acknowledged_by: None,
+                });
+            }
+            Err(e) => log::warn!("Bad alert event: {}", e),
+        },
+        STATS_SUB => match decode_next::<ConvoyStatsUpdates>(payload) {
+            Ok(data) => {
+                let stats = data.convoy_stats_updates;
+                match Uuid::parse_str(&stats.convoy_id) {
+                    Ok(convoy_id) => apply_convoy_stats(state, convoy_id, &stats),
+                    Err(e) => log::warn!("Bad convoy id in stats update: {}", e),
                 }
             }
-        }
-        "leaderboard-sub" => {
-            if let Some(update_data) = data.get("leaderboardUpdates") {
-                log::debug!("Leaderboard update: {:?}", update_data);
-                // Trigger leaderboard refresh
+            Err(e) => log::warn!("Bad convoy stats update: {}", e),
+        },
+        STATUS_SUB => match decode_next::<DroneStatusChanges>(payload) {
+            Ok(data) => {
+                let event = data.drone_status_changes;
+                let drone_id = Uuid::parse_str(&event.drone_id).unwrap_or_default();
+                let Some(status) = DroneStatus::from_graphql(&event.new_status) else {
+                    log::warn!("Unknown drone status {}", event.new_status);
+                    return;
+                };
+                state.drones.update(|drones| {
+                    if let Some(drone) = drones.get_mut(&drone_id) {
+                        drone.status = status;
+                    }
+                });
             }
+            Err(e) => log::warn!("Bad drone status event: {}", e),
+        },
+        HEARTBEAT_SUB => {
+            state.connection.update(|c| c.last_heartbeat_at = Some(now_ms()));
         }
+        TELEMETRY_SUB => match decode_next::<DroneTelemetry>(payload) {
+            Ok(data) => state.push_telemetry(telemetry_sample(data.drone_telemetry)),
+            Err(e) => log::warn!("Bad telemetry event: {}", e),
+        },
         _ => {}
     }
 }
 
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct EngagementEventData {
-    drone_id: String,
-    callsign: String,
-    hit: bool,
-    weapon_type: String,
-    new_accuracy_pct: f32,
-}
-
-/// Initialize WebSocket on mount
+/// Keep one subscription connection open for the selected convoy,
+/// reconnecting whenever the selection changes or connectivity returns
 pub fn use_websocket(convoy_id: Signal<Option<Uuid>>) {
+    let state = use_app_state();
+    let selected_drone = state.selected_drone;
+    let resync = state.resync;
+    let current = StoredValue::new_local(None::<WsClient>);
+
     Effect::new(move |_| {
-        if let Some(id) = convoy_id.get() {
-            match WsClient::connect(id) {
-                Ok(_client) => {
-                    log::info!("WebSocket client initialized for convoy {}", id);
-                }
-                Err(e) => {
-                    log::error!("Failed to connect WebSocket: {:?}", e);
-                }
+        let selected = convoy_id.get();
+        resync.track();
+
+        // Tear down the previous convoy's subscriptions before switching
+        current.update_value(|client| {
+            if let Some(client) = client.take() {
+                client.close();
+            }
+        });
+        state.ws_connected.set(false);
+        state.connection.update(|c| {
+            c.last_heartbeat_at = None;
+            c.ws_rtt_ms = None;
+        });
+
+        // The all-convoys overview has no live subscriptions
+        let Some(id) = selected else {
+            return;
+        };
+
+        match WsClient::connect(id) {
+            Ok(client) => {
+                log::info!("WebSocket client initialized for convoy {}", id);
+                current.set_value(Some(client));
+            }
+            Err(e) => {
+                log::error!("Failed to connect WebSocket: {:?}", e);
             }
         }
     });
+
+    // Follow the selected drone on the open connection
+    Effect::new(move |_| {
+        let drone_id = selected_drone.get();
+        current.with_value(|client| {
+            if let Some(client) = client {
+                client.watch_drone(drone_id);
+            }
+        });
+    });
 }
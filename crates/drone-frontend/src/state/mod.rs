@@ -3,28 +3,76 @@
 //! Reactive state management for the drone convoy HUD.
 
 use chrono::{DateTime, Utc};
+use gloo_storage::{LocalStorage, Storage};
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Local storage key holding the selected convoy
+const CONVOY_STORAGE_KEY: &str = "drone_selected_convoy";
+
+/// Stored selection value for the all-convoys overview
+const ALL_CONVOYS: &str = "all";
+
+/// Local storage key holding the critical-alert audio preference
+const ALERT_SOUND_STORAGE_KEY: &str = "drone_alert_sound";
+
+/// Alerts kept in the alert center history
+const ALERT_LOG_CAPACITY: usize = 200;
+
+/// Telemetry points kept for the selected drone's chart
+const TELEMETRY_CAPACITY: usize = 720;
+
+/// Engagements kept for the feed, live and paged history combined
+pub const ENGAGEMENT_CAPACITY: usize = 10_000;
+
 /// Global application state
 #[derive(Clone, Debug)]
 pub struct AppState {
+    /// Convoy driving the HUD; `None` shows the all-convoys overview
     pub selected_convoy: RwSignal<Option<Uuid>>,
+    pub convoys: RwSignal<Vec<ConvoyOverview>>,
     pub selected_drone: RwSignal<Option<Uuid>>,
     pub leaderboard: RwSignal<Vec<LeaderboardEntry>>,
     pub drones: RwSignal<HashMap<Uuid, DroneState>>,
+    /// Engagement feed, newest first
     pub engagements: RwSignal<Vec<EngagementEvent>>,
     pub ws_connected: RwSignal<bool>,
     pub mission_start: RwSignal<Option<DateTime<Utc>>>,
+    /// Undismissed toasts
     pub alerts: RwSignal<Vec<Alert>>,
+    /// Alert center history for the selected convoy, newest first
+    pub alert_log: RwSignal<Vec<Alert>>,
+    /// Play an audible cue for critical alerts
+    pub alert_sound: RwSignal<bool>,
+    /// Chart series for the selected drone, oldest first
+    pub telemetry: RwSignal<Vec<TelemetrySample>>,
+    /// API unreachable; panels show the last cached state
+    pub offline: RwSignal<bool>,
+    /// When the shown data was last known to be current
+    pub last_update: RwSignal<Option<DateTime<Utc>>>,
+    /// Bumped when connectivity returns so panels refetch everything
+    pub resync: RwSignal<u32>,
+    /// Strike heatmap overlay shown on the map
+    pub strikes_visible: RwSignal<bool>,
+    /// After-action replay shown on the map
+    pub replay_active: RwSignal<bool>,
+    /// Command palette open
+    pub palette_open: RwSignal<bool>,
+    /// Health distribution of the selected convoy
+    pub readiness: RwSignal<Option<crate::services::FleetReadiness>>,
+    /// Latest connection measurements for the footer indicator
+    pub connection: RwSignal<ConnectionMetrics>,
+    /// Connection quality assessed from `connection`
+    pub connection_quality: RwSignal<ConnectionQuality>,
 }
 
 impl AppState {
     pub fn new() -> Self {
         Self {
             selected_convoy: RwSignal::new(None),
+            convoys: RwSignal::new(Vec::new()),
             selected_drone: RwSignal::new(None),
             leaderboard: RwSignal::new(Vec::new()),
             drones: RwSignal::new(HashMap::new()),
@@ -32,7 +80,93 @@ impl AppState {
             ws_connected: RwSignal::new(false),
             mission_start: RwSignal::new(None),
             alerts: RwSignal::new(Vec::new()),
+            alert_log: RwSignal::new(Vec::new()),
+            alert_sound: RwSignal::new(LocalStorage::get(ALERT_SOUND_STORAGE_KEY).unwrap_or(false)),
+            telemetry: RwSignal::new(Vec::new()),
+            offline: RwSignal::new(false),
+            last_update: RwSignal::new(None),
+            resync: RwSignal::new(0),
+            strikes_visible: RwSignal::new(false),
+            replay_active: RwSignal::new(false),
+            palette_open: RwSignal::new(false),
+            readiness: RwSignal::new(None),
+            connection: RwSignal::new(ConnectionMetrics::default()),
+            connection_quality: RwSignal::new(ConnectionQuality::Good),
+        }
+    }
+
+    /// Show a newly raised alert as a toast and record it in the alert center
+    pub fn push_alert(&self, alert: Alert) {
+        if alert.severity == AlertSeverity::Critical && self.alert_sound.get_untracked() {
+            crate::services::play_alert_tone();
+        }
+        self.alert_log.update(|log| {
+            if log.iter().all(|a| a.id != alert.id) {
+                log.insert(0, alert.clone());
+                log.truncate(ALERT_LOG_CAPACITY);
+            }
+        });
+        self.alerts.update(|alerts| alerts.push(alert));
+    }
+
+    /// Mark an alert acknowledged everywhere it is shown
+    pub fn acknowledge_alert(&self, id: Uuid, acknowledged_by: Option<String>) {
+        self.alert_log.update(|log| {
+            if let Some(alert) = log.iter_mut().find(|a| a.id == id) {
+                alert.acknowledged = true;
+                alert.acknowledged_by = acknowledged_by;
+            }
+        });
+        self.alerts.update(|alerts| alerts.retain(|a| a.id != id));
+    }
+
+    /// Toggle the critical-alert audio cue and remember the choice
+    pub fn set_alert_sound(&self, enabled: bool) {
+        let _ = LocalStorage::set(ALERT_SOUND_STORAGE_KEY, enabled);
+        self.alert_sound.set(enabled);
+    }
+
+    /// Append a live telemetry point if it belongs to the selected drone
+    pub fn push_telemetry(&self, sample: TelemetrySample) {
+        if self.selected_drone.get_untracked() != Some(sample.drone_id) {
+            return;
+        }
+        self.telemetry.update(|series| {
+            if series.last().is_some_and(|last| last.recorded_at >= sample.recorded_at) {
+                return;
+            }
+            series.push(sample);
+            if series.len() > TELEMETRY_CAPACITY {
+                series.drain(..series.len() - TELEMETRY_CAPACITY);
+            }
+        });
+    }
+
+    /// Prepend a live engagement to the feed
+    pub fn push_engagement(&self, event: EngagementEvent) {
+        self.engagements.update(|events| {
+            events.insert(0, event);
+            events.truncate(ENGAGEMENT_CAPACITY);
+        });
+    }
+
+    /// Switch convoys (or the overview) and remember the choice
+    pub fn select_convoy(&self, convoy_id: Option<Uuid>) {
+        let stored = convoy_id.map_or_else(|| ALL_CONVOYS.to_string(), |id| id.to_string());
+        if let Err(e) = LocalStorage::set(CONVOY_STORAGE_KEY, stored) {
+            log::warn!("Failed to persist convoy selection: {}", e);
         }
+        self.selected_convoy.set(convoy_id);
+    }
+}
+
+/// Previously persisted convoy selection; `None` if nothing was stored
+pub fn stored_convoy_selection() -> Option<Option<Uuid>> {
+    let stored: String = LocalStorage::get(CONVOY_STORAGE_KEY).ok()?;
+    if stored == ALL_CONVOYS {
+        Some(None)
+    } else {
+        Uuid::parse_str(&stored).ok().map(Some)
     }
 }
 
@@ -56,6 +190,21 @@ pub struct LeaderboardEntry {
     pub rank_change: i32,
 }
 
+/// Per-convoy summary for the selector and overview cards
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConvoyOverview {
+    pub convoy_id: Uuid,
+    pub callsign: String,
+    pub mission_type: String,
+    pub status: String,
+    pub drone_count: u32,
+    pub airborne_count: u32,
+    pub total_engagements: u32,
+    pub total_hits: u32,
+    pub avg_accuracy_pct: f32,
+    pub avg_fuel_pct: f32,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DroneState {
     pub drone_id: Uuid,
@@ -99,6 +248,21 @@ impl DroneStatus {
         }
     }
 
+    /// Parse the GraphQL enum name
+    pub fn from_graphql(value: &str) -> Option<Self> {
+        match value {
+            "PREFLIGHT" => Some(Self::Preflight),
+            "AIRBORNE" => Some(Self::Airborne),
+            "LOITER" => Some(Self::Loiter),
+            "INGRESS" => Some(Self::Ingress),
+            "EGRESS" => Some(Self::Egress),
+            "RTB" => Some(Self::Rtb),
+            "LANDED" => Some(Self::Landed),
+            "MAINTENANCE" => Some(Self::Maintenance),
+            _ => None,
+        }
+    }
+
     pub fn status_class(&self) -> &'static str {
         match self {
             Self::Airborne | Self::Loiter | Self::Ingress | Self::Egress => "nominal",
@@ -117,26 +281,69 @@ pub struct Coordinates {
     pub speed_mps: f32,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// One charted telemetry point
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TelemetrySample {
+    pub drone_id: Uuid,
+    pub recorded_at: DateTime<Utc>,
+    pub altitude_m: f64,
+    pub fuel_pct: f32,
+    pub speed_mps: f32,
+    pub engine_temp_c: Option<f32>,
+    /// Envelope extensions as display-ready key/value pairs
+    #[serde(default)]
+    pub extensions: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct EngagementEvent {
     pub id: Uuid,
     pub drone_id: Uuid,
     pub callsign: String,
     pub hit: bool,
     pub weapon_type: String,
-    pub new_accuracy_pct: f32,
+    pub target_type: Option<String>,
+    pub range_km: Option<f32>,
+    /// BDA status; only known for engagements loaded from history
+    pub damage_assessment: Option<String>,
+    /// Target (latitude, longitude); only known for engagements loaded from history
+    pub target_position: Option<(f64, f64)>,
+    /// Shooter accuracy after this engagement; only known for live events
+    pub new_accuracy_pct: Option<f32>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Operator journal entry, pinned on the replay timeline
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct JournalNote {
+    pub id: Uuid,
+    pub author: String,
+    pub text: String,
+    /// Engagement, drone, target or alert the entry refers to
+    pub linked_entity_id: Option<Uuid>,
     pub timestamp: DateTime<Utc>,
 }
 
+/// Recorded drone position, used to animate replays
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TrackSample {
+    pub recorded_at: DateTime<Utc>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Alert {
     pub id: Uuid,
     pub severity: AlertSeverity,
+    pub alert_type: String,
     pub message: String,
     pub timestamp: DateTime<Utc>,
+    pub acknowledged: bool,
+    pub acknowledged_by: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum AlertSeverity {
     Info,
     Warning,
@@ -151,6 +358,63 @@ impl AlertSeverity {
             Self::Critical => "critical",
         }
     }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Info => "INFO",
+            Self::Warning => "WARNING",
+            Self::Critical => "CRITICAL",
+        }
+    }
+
+    /// Parse the GraphQL enum name, treating unknown values as info
+    pub fn from_graphql(value: &str) -> Self {
+        match value {
+            "CRITICAL" => Self::Critical,
+            "WARNING" => Self::Warning,
+            _ => Self::Info,
+        }
+    }
+}
+
+/// Connection measurements; times are `performance.now()` milliseconds
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ConnectionMetrics {
+    /// Round trip of the last `health` query; `None` if it failed
+    pub api_latency_ms: Option<f64>,
+    /// Consecutive failed `health` queries
+    pub api_failures: u32,
+    /// Round trip of the last answered WebSocket ping
+    pub ws_rtt_ms: Option<f64>,
+    /// Last heartbeat, or when the WebSocket opened if none arrived yet;
+    /// `None` while no WebSocket is open
+    pub last_heartbeat_at: Option<f64>,
+}
+
+/// Footer connection indicator state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionQuality {
+    Good,
+    Degraded,
+    Lost,
+}
+
+impl ConnectionQuality {
+    pub fn class(&self) -> &'static str {
+        match self {
+            Self::Good => "nominal",
+            Self::Degraded => "warning",
+            Self::Lost => "critical",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Good => "GOOD",
+            Self::Degraded => "DEGRADED",
+            Self::Lost => "LOST",
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
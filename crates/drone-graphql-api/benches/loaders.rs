@@ -0,0 +1,71 @@
+//! DataLoader batch strategy benchmarks.
+//!
+//! A batch of `(convoy_id, drone_id)` keys for 100-drone convoys is loaded
+//! from a stand-in store that answers every query after a fixed round
+//! trip, the way a Scylla node on the same network would. `per_key` issues
+//! one query per key, as a loader without partition grouping must;
+//! `per_partition` is [`fan_out`] with one query per convoy, run
+//! concurrently up to [`MAX_PARTITION_QUERIES`].
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use drone_graphql_api::error::ApiResult;
+use drone_graphql_api::loaders::{fan_out, MAX_PARTITION_QUERIES};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+
+/// Drones per convoy
+const DRONES: u128 = 100;
+
+/// Round trip of one query
+const ROUND_TRIP: Duration = Duration::from_millis(1);
+
+/// One query on a convoy partition returning the requested drones
+async fn query(drone_ids: Vec<Uuid>) -> ApiResult<Vec<(Uuid, u128)>> {
+    tokio::time::sleep(ROUND_TRIP).await;
+    Ok(drone_ids.into_iter().map(|id| (id, id.as_u128())).collect())
+}
+
+fn keys(convoys: u128) -> Vec<(Uuid, Uuid)> {
+    (0..convoys)
+        .flat_map(|c| {
+            (0..DRONES).map(move |d| (Uuid::from_u128(c), Uuid::from_u128((c << 32) | d)))
+        })
+        .collect()
+}
+
+fn bench_batch_load(c: &mut Criterion) {
+    let rt = Runtime::new().expect("tokio runtime");
+
+    let mut group = c.benchmark_group("loader_batch");
+    group.sample_size(10);
+    for convoys in [1_u128, 4] {
+        let keys = keys(convoys);
+        group.throughput(Throughput::Elements(keys.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("per_key", convoys), &keys, |b, keys| {
+            b.to_async(&rt).iter(|| async {
+                let mut merged = HashMap::with_capacity(keys.len());
+                for &(convoy_id, drone_id) in keys {
+                    for (id, value) in query(vec![drone_id]).await.expect("query") {
+                        merged.insert((convoy_id, id), value);
+                    }
+                }
+                merged
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("per_partition", convoys), &keys, |b, keys| {
+            b.to_async(&rt).iter(|| async {
+                fan_out(keys, MAX_PARTITION_QUERIES, |_, drone_ids| query(drone_ids))
+                    .await
+                    .expect("fan out")
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch_load);
+criterion_main!(benches);
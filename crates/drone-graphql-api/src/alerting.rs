@@ -0,0 +1,496 @@
+//! # Alert Routing
+//!
+//! Forwards raised alerts to external channels: a generic JSON webhook, a
+//! Slack incoming webhook and SMTP email. Each route filters on a minimum
+//! severity and, optionally, a set of convoys. Sends are retried with
+//! exponential backoff and rate limited per channel, and the outcome for
+//! every matching route is returned for recording on the alert.
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use drone_domain::{Alert, AlertDelivery, AlertSeverity, DeliveryStatus};
+
+/// Default sends per channel per minute
+pub const DEFAULT_MAX_PER_MINUTE: u32 = 10;
+
+/// Default send attempts per alert, including the first
+pub const DEFAULT_RETRY_ATTEMPTS: u32 = 3;
+
+/// Default backoff before the first retry, doubled for each further retry
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Window the per-channel rate limit is counted over
+const RATE_WINDOW: Duration = Duration::from_mins(1);
+
+/// External alert notification channel
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Channel name recorded against deliveries, e.g. `slack`
+    fn name(&self) -> &'static str;
+
+    /// Send one alert
+    async fn send(&self, alert: &Alert) -> ApiResult<()>;
+}
+
+/// Shared handle to a notifier
+pub type SharedNotifier = Arc<dyn Notifier>;
+
+/// One-line summary used by the chat and email channels
+fn summary(alert: &Alert) -> String {
+    format!(
+        "[{}] {}: {} (convoy {})",
+        alert.severity.as_str(),
+        alert.alert_type,
+        alert.message,
+        alert.convoy_id
+    )
+}
+
+/// POSTs the alert as JSON to an arbitrary endpoint
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn send(&self, alert: &Alert) -> ApiResult<()> {
+        self.client
+            .post(&self.url)
+            .json(alert)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| ApiError::Internal(format!("Webhook delivery failed: {e}")))?;
+        Ok(())
+    }
+}
+
+/// Posts a message to a Slack incoming webhook
+pub struct SlackNotifier {
+    client: reqwest::Client,
+    webhook_url: String,
+}
+
+impl SlackNotifier {
+    pub fn new(webhook_url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            webhook_url: webhook_url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &'static str {
+        "slack"
+    }
+
+    async fn send(&self, alert: &Alert) -> ApiResult<()> {
+        self.client
+            .post(&self.webhook_url)
+            .json(&serde_json::json!({ "text": summary(alert) }))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| ApiError::Internal(format!("Slack delivery failed: {e}")))?;
+        Ok(())
+    }
+}
+
+/// SMTP relay settings
+#[derive(Debug, Clone)]
+pub struct SmtpSettings {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Upgrade the connection with STARTTLS
+    pub starttls: bool,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+/// Emails the alert through an SMTP relay
+pub struct SmtpNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl SmtpNotifier {
+    /// Build a notifier; fails on an invalid relay host or address
+    pub fn new(settings: &SmtpSettings) -> ApiResult<Self> {
+        let mut builder = if settings.starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.host)
+                .map_err(|e| ApiError::Internal(format!("Invalid SMTP relay: {e}")))?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&settings.host)
+        };
+        builder = builder.port(settings.port);
+        if let (Some(username), Some(password)) = (&settings.username, &settings.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        let parse = |address: &str| {
+            address
+                .trim()
+                .parse::<Mailbox>()
+                .map_err(|e| ApiError::Internal(format!("Invalid email address `{address}`: {e}")))
+        };
+        let to = settings
+            .to
+            .iter()
+            .map(|address| parse(address))
+            .collect::<ApiResult<Vec<_>>>()?;
+        if to.is_empty() {
+            return Err(ApiError::Internal("SMTP notifier has no recipients".to_string()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: parse(&settings.from)?,
+            to,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for SmtpNotifier {
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    async fn send(&self, alert: &Alert) -> ApiResult<()> {
+        let mut message = Message::builder()
+            .from(self.from.clone())
+            .subject(format!("[{}] {}", alert.severity.as_str(), alert.alert_type))
+            .header(ContentType::TEXT_PLAIN);
+        for recipient in &self.to {
+            message = message.to(recipient.clone());
+        }
+        let message = message
+            .body(format!(
+                "{}\n\nAlert ID: {}\nRaised at: {}\n",
+                summary(alert),
+                alert.alert_id,
+                alert.alert_time.to_rfc3339()
+            ))
+            .map_err(|e| ApiError::Internal(format!("Invalid alert email: {e}")))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| ApiError::Internal(format!("Email delivery failed: {e}")))?;
+        Ok(())
+    }
+}
+
+/// Sliding-window send counter
+struct RateLimiter {
+    max_per_window: u32,
+    sent: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(max_per_window: u32) -> Self {
+        Self {
+            max_per_window,
+            sent: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Take a slot at `now`; false when the window is full
+    fn try_acquire(&self, now: Instant) -> bool {
+        let Ok(mut sent) = self.sent.lock() else {
+            return true;
+        };
+        while sent.front().is_some_and(|t| now.duration_since(*t) >= RATE_WINDOW) {
+            sent.pop_front();
+        }
+        if sent.len() >= self.max_per_window as usize {
+            return false;
+        }
+        sent.push_back(now);
+        true
+    }
+}
+
+/// A notifier with the alerts it should receive
+pub struct AlertRoute {
+    notifier: SharedNotifier,
+    min_severity: AlertSeverity,
+    /// Convoys routed to this channel; every convoy when `None`
+    convoy_ids: Option<HashSet<Uuid>>,
+    limiter: RateLimiter,
+}
+
+impl AlertRoute {
+    /// Route every alert to `notifier`
+    pub fn new(notifier: SharedNotifier) -> Self {
+        Self {
+            notifier,
+            min_severity: AlertSeverity::Info,
+            convoy_ids: None,
+            limiter: RateLimiter::new(DEFAULT_MAX_PER_MINUTE),
+        }
+    }
+
+    /// Only route alerts at least this severe
+    #[must_use]
+    pub fn with_min_severity(mut self, min_severity: AlertSeverity) -> Self {
+        self.min_severity = min_severity;
+        self
+    }
+
+    /// Only route alerts for these convoys; an empty set routes every convoy
+    #[must_use]
+    pub fn with_convoys(mut self, convoy_ids: impl IntoIterator<Item = Uuid>) -> Self {
+        let convoy_ids: HashSet<Uuid> = convoy_ids.into_iter().collect();
+        self.convoy_ids = (!convoy_ids.is_empty()).then_some(convoy_ids);
+        self
+    }
+
+    /// Cap sends on this channel per minute
+    #[must_use]
+    pub fn with_rate_limit(mut self, max_per_minute: u32) -> Self {
+        self.limiter = RateLimiter::new(max_per_minute);
+        self
+    }
+
+    /// Channel name
+    pub fn channel(&self) -> &'static str {
+        self.notifier.name()
+    }
+
+    /// Whether `alert` should go to this channel
+    pub fn matches(&self, alert: &Alert) -> bool {
+        alert.severity.at_least(self.min_severity)
+            && self
+                .convoy_ids
+                .as_ref()
+                .is_none_or(|ids| ids.contains(&alert.convoy_id))
+    }
+}
+
+/// Fans alerts out to every matching route
+pub struct AlertRouter {
+    routes: Vec<AlertRoute>,
+    retry_attempts: u32,
+    retry_backoff: Duration,
+}
+
+impl AlertRouter {
+    #[must_use]
+    pub fn new(routes: Vec<AlertRoute>) -> Self {
+        Self {
+            routes,
+            retry_attempts: DEFAULT_RETRY_ATTEMPTS,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+
+    /// Set send attempts per alert (at least one) and the initial backoff
+    #[must_use]
+    pub fn with_retry(mut self, attempts: u32, backoff: Duration) -> Self {
+        self.retry_attempts = attempts.max(1);
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Whether no channels are configured
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// Configured channel names
+    pub fn channels(&self) -> Vec<&'static str> {
+        self.routes.iter().map(AlertRoute::channel).collect()
+    }
+
+    /// Send `alert` to every matching route concurrently, returning one
+    /// delivery outcome per route
+    pub async fn dispatch(&self, alert: &Alert) -> Vec<AlertDelivery> {
+        let sends = self
+            .routes
+            .iter()
+            .filter(|route| route.matches(alert))
+            .map(|route| self.deliver(route, alert));
+        join_all(sends).await
+    }
+
+    async fn deliver(&self, route: &AlertRoute, alert: &Alert) -> AlertDelivery {
+        let channel = route.channel().to_string();
+        if !route.limiter.try_acquire(Instant::now()) {
+            tracing::warn!(alert_id = %alert.alert_id, channel, "Alert notification rate limited");
+            return AlertDelivery {
+                channel,
+                status: DeliveryStatus::RateLimited,
+                attempts: 0,
+                last_error: None,
+            };
+        }
+
+        let mut backoff = self.retry_backoff;
+        let mut last_error = None;
+        for attempt in 1..=self.retry_attempts {
+            match route.notifier.send(alert).await {
+                Ok(()) => {
+                    return AlertDelivery {
+                        channel,
+                        status: DeliveryStatus::Delivered,
+                        attempts: attempt as i32,
+                        last_error: None,
+                    };
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        alert_id = %alert.alert_id, channel, attempt, error = %e,
+                        "Alert notification failed"
+                    );
+                    last_error = Some(e.to_string());
+                }
+            }
+            if attempt < self.retry_attempts {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+
+        AlertDelivery {
+            channel,
+            status: DeliveryStatus::Failed,
+            attempts: self.retry_attempts as i32,
+            last_error,
+        }
+    }
+}
+
+/// Parse a severity name (`CRITICAL`, `WARNING`, `INFO`), ignoring case
+#[must_use]
+pub fn parse_severity(name: &str) -> Option<AlertSeverity> {
+    match name.trim().to_ascii_uppercase().as_str() {
+        "CRITICAL" => Some(AlertSeverity::Critical),
+        "WARNING" => Some(AlertSeverity::Warning),
+        "INFO" => Some(AlertSeverity::Info),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails the first `failures` sends, then succeeds
+    struct FlakyNotifier {
+        failures: u32,
+        calls: AtomicU32,
+    }
+
+    #[async_trait]
+    impl Notifier for FlakyNotifier {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        async fn send(&self, _alert: &Alert) -> ApiResult<()> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                Err(ApiError::Internal("connection refused".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn alert(convoy_id: Uuid, severity: AlertSeverity) -> Alert {
+        Alert {
+            convoy_id,
+            alert_time: Utc::now(),
+            alert_id: Uuid::new_v4(),
+            severity,
+            alert_type: "LINK_LOST".to_string(),
+            source_drone_id: None,
+            message: "Datalink lost".to_string(),
+            acknowledged: false,
+            acknowledged_by: None,
+            acknowledged_at: None,
+            deliveries: Vec::new(),
+        }
+    }
+
+    fn flaky(failures: u32) -> SharedNotifier {
+        Arc::new(FlakyNotifier {
+            failures,
+            calls: AtomicU32::new(0),
+        })
+    }
+
+    #[test]
+    fn test_route_filters_severity_and_convoy() {
+        let convoy = Uuid::new_v4();
+        let route = AlertRoute::new(flaky(0))
+            .with_min_severity(AlertSeverity::Warning)
+            .with_convoys([convoy]);
+
+        assert!(route.matches(&alert(convoy, AlertSeverity::Critical)));
+        assert!(!route.matches(&alert(convoy, AlertSeverity::Info)));
+        assert!(!route.matches(&alert(Uuid::new_v4(), AlertSeverity::Critical)));
+        assert!(AlertRoute::new(flaky(0))
+            .with_convoys([])
+            .matches(&alert(Uuid::new_v4(), AlertSeverity::Info)));
+        assert_eq!(parse_severity(" warning"), Some(AlertSeverity::Warning));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_retries_then_records_outcome() {
+        let convoy = Uuid::new_v4();
+        let router = AlertRouter::new(vec![AlertRoute::new(flaky(1))])
+            .with_retry(3, Duration::ZERO);
+        let deliveries = router.dispatch(&alert(convoy, AlertSeverity::Critical)).await;
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status, DeliveryStatus::Delivered);
+        assert_eq!(deliveries[0].attempts, 2);
+
+        let router = AlertRouter::new(vec![AlertRoute::new(flaky(5))])
+            .with_retry(2, Duration::ZERO);
+        let failed = &router.dispatch(&alert(convoy, AlertSeverity::Critical)).await[0];
+        assert_eq!(failed.status, DeliveryStatus::Failed);
+        assert_eq!(failed.last_error.as_deref(), Some("Internal server error: connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_rate_limits_per_channel() {
+        let router = AlertRouter::new(vec![AlertRoute::new(flaky(0)).with_rate_limit(1)]);
+        let a = alert(Uuid::new_v4(), AlertSeverity::Warning);
+
+        assert_eq!(router.dispatch(&a).await[0].status, DeliveryStatus::Delivered);
+        assert_eq!(router.dispatch(&a).await[0].status, DeliveryStatus::RateLimited);
+    }
+}
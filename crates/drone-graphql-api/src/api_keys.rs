@@ -0,0 +1,445 @@
+//! # API Keys
+//!
+//! Credentials for machine clients such as simulators and telemetry
+//! gateways, kept apart from the bearer tokens operators use.
+//!
+//! Keys are presented in the `X-API-Key` header as `dgk_<key-id>.<secret>`.
+//! Only an HMAC-SHA256 of the secret, keyed with a server-side secret, is
+//! stored, so a leaked table does not leak usable keys. Each key carries a
+//! scope: `INGEST` keys may only post to the telemetry ingest endpoint,
+//! `READ_ONLY` keys get viewer access and `ADMIN` keys full access. Keys are
+//! also confined to one commanding unit and environment, like bearer tokens;
+//! only `ADMIN` keys may be issued without a unit. Admins manage only the
+//! keys of their own unit and environment.
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::Utc;
+use ring::hmac;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::auth::{self, Claims, Role};
+use crate::error::{ApiError, ApiResult};
+use crate::AppState;
+use drone_domain::{ApiKey, ApiKeyScope, Environment};
+use crate::store::ApiKeyRepository;
+
+/// Header machine clients present their key in
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Prefix marking a presented value as an API key
+const KEY_PREFIX: &str = "dgk_";
+
+/// Random bytes in a key ID
+const KEY_ID_BYTES: usize = 8;
+
+/// Random bytes in a key secret
+const SECRET_BYTES: usize = 32;
+
+/// How long a verified key is trusted before it is re-read; bounds how long
+/// a revocation on another replica takes to apply
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Computes and checks keyed hashes of key secrets
+pub struct ApiKeyHasher {
+    key: hmac::Key,
+}
+
+impl ApiKeyHasher {
+    /// Hasher using a shared secret, so keys survive restarts and validate
+    /// on every replica
+    #[must_use]
+    pub fn new(secret: &[u8]) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret),
+        }
+    }
+
+    /// Hasher with a random key; issued keys stop working on restart
+    #[must_use]
+    pub fn ephemeral() -> Self {
+        let key = hmac::Key::generate(hmac::HMAC_SHA256, &SystemRandom::new())
+            .expect("system random source unavailable");
+        Self { key }
+    }
+
+    /// Keyed hash of a secret
+    #[must_use]
+    pub fn hash(&self, secret: &str) -> Vec<u8> {
+        hmac::sign(&self.key, secret.as_bytes()).as_ref().to_vec()
+    }
+
+    /// Constant-time check of a secret against a stored hash
+    #[must_use]
+    pub fn verify(&self, secret: &str, hash: &[u8]) -> bool {
+        hmac::verify(&self.key, secret.as_bytes(), hash).is_ok()
+    }
+}
+
+/// Split a presented `dgk_<key-id>.<secret>` value
+#[must_use]
+pub fn parse_key(raw: &str) -> Option<(&str, &str)> {
+    let (key_id, secret) = raw.trim().strip_prefix(KEY_PREFIX)?.split_once('.')?;
+    (!key_id.is_empty() && !secret.is_empty()).then_some((key_id, secret))
+}
+
+fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("system random source unavailable");
+    bytes
+}
+
+fn new_key_id() -> String {
+    random_bytes(KEY_ID_BYTES)
+        .iter()
+        .fold(String::with_capacity(KEY_ID_BYTES * 2), |mut id, b| {
+            let _ = write!(id, "{b:02x}");
+            id
+        })
+}
+
+fn new_secret() -> String {
+    URL_SAFE_NO_PAD.encode(random_bytes(SECRET_BYTES))
+}
+
+/// Issues, rotates, revokes and authenticates API keys
+pub struct ApiKeyStore {
+    repo: Arc<ApiKeyRepository>,
+    hasher: ApiKeyHasher,
+    cache: RwLock<HashMap<String, (Instant, ApiKey)>>,
+}
+
+impl ApiKeyStore {
+    #[must_use]
+    pub fn new(repo: Arc<ApiKeyRepository>, hasher: ApiKeyHasher) -> Self {
+        Self {
+            repo,
+            hasher,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Issue a key, returning it with the full key value to hand to the
+    /// client; the value cannot be recovered later
+    ///
+    /// The key cannot reach beyond the issuing caller: its unit must be one
+    /// the caller can access, only callers spanning every unit may omit it,
+    /// and its environment must be the caller's.
+    pub async fn issue(
+        &self,
+        claims: &Claims,
+        name: &str,
+        scope: ApiKeyScope,
+        commanding_unit: Option<&str>,
+        environment: Environment,
+    ) -> ApiResult<(ApiKey, String)> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ApiError::InvalidInput("API key name is required".to_string()));
+        }
+        let commanding_unit = commanding_unit
+            .map(str::trim)
+            .filter(|unit| !unit.is_empty())
+            .map(str::to_string);
+        if commanding_unit.is_none() && scope != ApiKeyScope::Admin {
+            return Err(ApiError::InvalidInput(format!(
+                "{} API keys must name a commanding unit",
+                scope.as_str()
+            )));
+        }
+        let unit_allowed = match &commanding_unit {
+            Some(unit) => claims.can_access_unit(unit),
+            None => claims.all_units(),
+        };
+        if !unit_allowed {
+            return Err(ApiError::Unauthorized(
+                "cannot issue API keys for another commanding unit".to_string(),
+            ));
+        }
+        if environment != claims.environment {
+            return Err(ApiError::Unauthorized(
+                "cannot issue API keys in another environment".to_string(),
+            ));
+        }
+
+        let secret = new_secret();
+        let key = ApiKey {
+            key_id: new_key_id(),
+            name: name.to_string(),
+            scope,
+            commanding_unit,
+            environment,
+            secret_hash: self.hasher.hash(&secret),
+            created_at: Utc::now(),
+            rotated_at: None,
+            revoked_at: None,
+        };
+        self.repo.create(&key).await?;
+
+        let value = format!("{KEY_PREFIX}{}.{secret}", key.key_id);
+        Ok((key, value))
+    }
+
+    /// Replace an active key's secret; the old value stops working at once on
+    /// this replica and within the cache TTL on others
+    pub async fn rotate(&self, claims: &Claims, key_id: &str) -> ApiResult<(ApiKey, String)> {
+        let mut key = self.active(claims, key_id).await?;
+
+        let secret = new_secret();
+        let now = Utc::now();
+        key.secret_hash = self.hasher.hash(&secret);
+        key.rotated_at = Some(now);
+        self.repo.rotate(key_id, &key.secret_hash, now).await?;
+        self.evict(key_id);
+
+        let value = format!("{KEY_PREFIX}{key_id}.{secret}");
+        Ok((key, value))
+    }
+
+    /// Revoke a key; revoking an already revoked key is a no-op
+    pub async fn revoke(&self, claims: &Claims, key_id: &str) -> ApiResult<ApiKey> {
+        let mut key = self.find(claims, key_id).await?;
+        if key.revoked_at.is_none() {
+            let now = Utc::now();
+            self.repo.revoke(key_id, now).await?;
+            key.revoked_at = Some(now);
+        }
+        self.evict(key_id);
+        Ok(key)
+    }
+
+    /// Every key the caller may manage, oldest first
+    pub async fn list(&self, claims: &Claims) -> ApiResult<Vec<ApiKey>> {
+        let mut keys = self.repo.list().await?;
+        keys.retain(|key| can_manage(claims, key));
+        Ok(keys)
+    }
+
+    /// Resolve a presented key value to its active key
+    pub async fn authenticate(&self, raw: &str) -> ApiResult<ApiKey> {
+        let invalid = || ApiError::Unauthorized("invalid API key".to_string());
+        let (key_id, secret) = parse_key(raw).ok_or_else(invalid)?;
+
+        let cached = self.cache.read().ok().and_then(|cache| {
+            cache
+                .get(key_id)
+                .filter(|(fetched_at, _)| fetched_at.elapsed() < CACHE_TTL)
+                .map(|(_, key)| key.clone())
+        });
+        let key = if let Some(key) = cached {
+            key
+        } else {
+            let key = self.repo.get(key_id).await?.ok_or_else(invalid)?;
+            if let Ok(mut cache) = self.cache.write() {
+                cache.insert(key_id.to_string(), (Instant::now(), key.clone()));
+            }
+            key
+        };
+
+        if !key.is_active() || !self.hasher.verify(secret, &key.secret_hash) {
+            return Err(invalid());
+        }
+        Ok(key)
+    }
+
+    /// Key the caller may manage; other units' and environments' keys are
+    /// indistinguishable from missing ones
+    async fn find(&self, claims: &Claims, key_id: &str) -> ApiResult<ApiKey> {
+        self.repo
+            .get(key_id)
+            .await?
+            .filter(|key| can_manage(claims, key))
+            .ok_or_else(|| ApiError::NotFound {
+                entity_type: "ApiKey".to_string(),
+                id: key_id.to_string(),
+            })
+    }
+
+    async fn active(&self, claims: &Claims, key_id: &str) -> ApiResult<ApiKey> {
+        let key = self.find(claims, key_id).await?;
+        if !key.is_active() {
+            return Err(ApiError::InvalidInput(format!("API key {key_id} is revoked")));
+        }
+        Ok(key)
+    }
+
+    fn evict(&self, key_id: &str) {
+        if let Ok(mut cache) = self.cache.write() {
+            cache.remove(key_id);
+        }
+    }
+}
+
+/// Whether `claims` may list, rotate or revoke `key`: same environment, and
+/// a unit the caller can access; unit-less keys only for callers spanning
+/// every unit
+#[must_use]
+pub fn can_manage(claims: &Claims, key: &ApiKey) -> bool {
+    key.environment == claims.environment
+        && match &key.commanding_unit {
+            Some(unit) => claims.can_access_unit(unit),
+            None => claims.all_units(),
+        }
+}
+
+/// Claims an API key grants: the role its scope maps to, confined to the
+/// key's commanding unit and environment
+#[must_use]
+pub fn scope_claims(key: &ApiKey) -> Claims {
+    let role = match key.scope {
+        ApiKeyScope::Ingest => Role::Operator,
+        ApiKeyScope::ReadOnly => Role::Viewer,
+        ApiKeyScope::Admin => Role::Admin,
+    };
+    Claims {
+        commanding_unit: key.commanding_unit.clone(),
+        environment: key.environment,
+        ..Claims::new(role)
+    }
+}
+
+/// Authenticated caller, resolved from an `X-API-Key` header or, failing
+/// that, an `Authorization: Bearer` token.
+///
+/// A presented but invalid API key rejects the request rather than falling
+/// back to anonymous access. Callers with neither are anonymous viewers
+/// that see no commanding unit.
+#[derive(Debug, Clone, Default)]
+pub struct Principal {
+    claims: Claims,
+    /// ID and scope of the API key the caller presented
+    pub api_key: Option<(String, ApiKeyScope)>,
+}
+
+impl Principal {
+    /// Claims for GraphQL and REST endpoints; ingest-only keys are refused
+    pub fn claims(self) -> ApiResult<Claims> {
+        match self.api_key {
+            Some((key_id, ApiKeyScope::Ingest)) => Err(ApiError::Unauthorized(format!(
+                "API key {key_id} is limited to telemetry ingest"
+            ))),
+            _ => Ok(self.claims),
+        }
+    }
+
+    /// Claims for the telemetry ingest endpoint
+    #[must_use]
+    pub fn ingest_claims(self) -> Claims {
+        self.claims
+    }
+}
+
+impl FromRequestParts<AppState> for Principal {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> ApiResult<Self> {
+        let Some(raw) = parts.headers.get(API_KEY_HEADER) else {
+            return Ok(Self {
+                claims: auth::claims_from_headers(&parts.headers, &state.ctx.role_tokens)
+                    .unwrap_or_else(|| Claims::anonymous(Role::Viewer)),
+                api_key: None,
+            });
+        };
+
+        let raw = raw
+            .to_str()
+            .map_err(|_| ApiError::Unauthorized("invalid API key".to_string()))?;
+        let key = state.ctx.api_keys.authenticate(raw).await?;
+        Ok(Self {
+            claims: scope_claims(&key),
+            api_key: Some((key.key_id, key.scope)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_format_and_hash() {
+        let key_id = new_key_id();
+        let secret = new_secret();
+        let value = format!("{KEY_PREFIX}{key_id}.{secret}");
+        assert_eq!(key_id.len(), KEY_ID_BYTES * 2);
+        assert_eq!(parse_key(&value), Some((key_id.as_str(), secret.as_str())));
+        assert_eq!(parse_key("Bearer abc"), None);
+        assert_eq!(parse_key("dgk_.secret"), None);
+
+        let hasher = ApiKeyHasher::new(b"pepper");
+        let hash = hasher.hash(&secret);
+        assert!(hasher.verify(&secret, &hash));
+        assert!(!hasher.verify("guess", &hash));
+        assert!(!ApiKeyHasher::new(b"other").verify(&secret, &hash));
+    }
+
+    fn key(scope: ApiKeyScope, commanding_unit: Option<&str>) -> ApiKey {
+        ApiKey {
+            key_id: "k1".to_string(),
+            name: "gateway".to_string(),
+            scope,
+            commanding_unit: commanding_unit.map(str::to_string),
+            environment: Environment::Exercise,
+            secret_hash: Vec::new(),
+            created_at: Utc::now(),
+            rotated_at: None,
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_ingest_keys_limited_to_ingest() {
+        let principal = |scope| Principal {
+            claims: scope_claims(&key(scope, Some("1-ID"))),
+            api_key: Some(("k1".to_string(), scope)),
+        };
+
+        assert!(principal(ApiKeyScope::Ingest).claims().is_err());
+        assert_eq!(principal(ApiKeyScope::Ingest).ingest_claims().role, Role::Operator);
+        assert_eq!(principal(ApiKeyScope::ReadOnly).claims().unwrap().role, Role::Viewer);
+        assert_eq!(principal(ApiKeyScope::Admin).claims().unwrap().role, Role::Admin);
+        assert_eq!(Principal::default().claims().unwrap().role, Role::Viewer);
+    }
+
+    #[test]
+    fn test_key_claims_confined_to_unit_and_environment() {
+        let claims = scope_claims(&key(ApiKeyScope::ReadOnly, Some("1-ID")));
+        assert_eq!(claims.commanding_unit.as_deref(), Some("1-ID"));
+        assert_eq!(claims.environment, Environment::Exercise);
+        assert!(claims.can_access_unit("1-ID"));
+        assert!(!claims.can_access_unit("2-ID"));
+
+        let admin = scope_claims(&key(ApiKeyScope::Admin, None));
+        assert!(admin.can_access_unit("2-ID"));
+        assert_eq!(admin.environment, Environment::Exercise);
+    }
+
+    #[test]
+    fn test_admins_manage_only_their_unit_keys() {
+        let unit_admin = scope_claims(&key(ApiKeyScope::Admin, Some("1-ID")));
+        let global_admin = scope_claims(&key(ApiKeyScope::Admin, None));
+
+        let own = key(ApiKeyScope::Ingest, Some("1-ID"));
+        let other = key(ApiKeyScope::Ingest, Some("2-ID"));
+        let unscoped = key(ApiKeyScope::Admin, None);
+        assert!(can_manage(&unit_admin, &own));
+        assert!(!can_manage(&unit_admin, &other));
+        assert!(!can_manage(&unit_admin, &unscoped));
+        assert!(can_manage(&global_admin, &other));
+        assert!(can_manage(&global_admin, &unscoped));
+
+        let live = ApiKey {
+            environment: Environment::Live,
+            ..own
+        };
+        assert!(!can_manage(&unit_admin, &live));
+    }
+}
@@ -0,0 +1,188 @@
+//! # Mission Artifacts
+//!
+//! Documents kept when a convoy completes its mission: the analytics report
+//! (when an analytics engine is attached), the final leaderboard as CSV and
+//! the KML export. Each is stored under the SHA-256 of its content, listed
+//! by `missionArtifacts` and served from `/artifacts/{convoy_id}/{digest}`.
+//!
+//! Generation runs in the background once `updateConvoyStatus` moves a
+//! convoy to `COMPLETE`; an artifact that fails to render is logged and the
+//! others are still stored.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use ring::digest::{digest, SHA256};
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::ApiResult;
+use crate::kml;
+use drone_domain::{ArtifactKind, MissionArtifact};
+
+/// Media type for the Markdown analytics report
+pub const MARKDOWN_CONTENT_TYPE: &str = "text/markdown; charset=utf-8";
+
+/// Media type for the leaderboard CSV
+pub const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+
+/// A rendered artifact before it is stored
+struct Document {
+    kind: ArtifactKind,
+    file_name: String,
+    content_type: &'static str,
+    content: Vec<u8>,
+}
+
+impl Document {
+    fn into_artifact(
+        self,
+        convoy_id: Uuid,
+        created_at: DateTime<Utc>,
+    ) -> (MissionArtifact, Vec<u8>) {
+        let artifact = MissionArtifact {
+            convoy_id,
+            kind: self.kind,
+            digest: content_digest(&self.content),
+            file_name: self.file_name,
+            content_type: self.content_type.to_string(),
+            size_bytes: i64::try_from(self.content.len()).unwrap_or(i64::MAX),
+            created_at,
+        };
+        (artifact, self.content)
+    }
+}
+
+/// Lowercase hex SHA-256 of `content`
+#[must_use]
+pub fn content_digest(content: &[u8]) -> String {
+    digest(&SHA256, content)
+        .as_ref()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, b| {
+            let _ = write!(hex, "{b:02x}");
+            hex
+        })
+}
+
+/// Artifact kinds generated for a completed convoy
+fn kinds(ctx: &ApiContext) -> Vec<ArtifactKind> {
+    let mut kinds = vec![ArtifactKind::Leaderboard, ArtifactKind::Kml];
+    if ctx.analytics.is_some() {
+        kinds.insert(0, ArtifactKind::AnalyticsReport);
+    }
+    kinds
+}
+
+async fn render(ctx: &ApiContext, convoy_id: Uuid, kind: ArtifactKind) -> ApiResult<Document> {
+    let (file_name, content_type, content) = match kind {
+        ArtifactKind::AnalyticsReport => {
+            let report = ctx
+                .run_analytics(move |engine| engine.generate_report_markdown(Some(convoy_id)))
+                .await?;
+            (format!("report-{convoy_id}.md"), MARKDOWN_CONTENT_TYPE, report.into_bytes())
+        }
+        ArtifactKind::Leaderboard => {
+            let entries = ctx
+                .leaderboard_repo
+                .get_leaderboard(convoy_id, crate::MAX_EXPORTED_LEADERBOARD_ENTRIES)
+                .await?;
+            let rows: Vec<_> = entries
+                .iter()
+                .map(drone_analytics::reports::LeaderboardRow::from)
+                .collect();
+            let csv = drone_analytics::reports::leaderboard_csv(&rows);
+            (format!("leaderboard-{convoy_id}.csv"), CSV_CONTENT_TYPE, csv.into_bytes())
+        }
+        ArtifactKind::Kml => {
+            let document = kml::export_convoy(ctx, convoy_id).await?;
+            (format!("convoy-{convoy_id}.kml"), kml::KML_CONTENT_TYPE, document.into_bytes())
+        }
+    };
+    Ok(Document {
+        kind,
+        file_name,
+        content_type,
+        content,
+    })
+}
+
+async fn store(
+    ctx: &ApiContext,
+    convoy_id: Uuid,
+    kind: ArtifactKind,
+) -> ApiResult<MissionArtifact> {
+    let document = render(ctx, convoy_id, kind).await?;
+    let (artifact, content) = document.into_artifact(convoy_id, Utc::now());
+    ctx.convoy_repo.put_artifact(&artifact, &content).await?;
+    Ok(artifact)
+}
+
+/// Generate and store a convoy's artifacts, returning those stored
+pub async fn generate(ctx: &ApiContext, convoy_id: Uuid) -> Vec<MissionArtifact> {
+    let mut stored = Vec::new();
+    for kind in kinds(ctx) {
+        match store(ctx, convoy_id, kind).await {
+            Ok(artifact) => stored.push(artifact),
+            Err(e) => tracing::warn!(
+                convoy_id = %convoy_id, kind = kind.as_str(), error = %e,
+                "Failed to generate mission artifact"
+            ),
+        }
+    }
+    stored
+}
+
+/// Generate a convoy's artifacts without holding up the caller
+pub fn spawn_generation(ctx: ApiContext, convoy_id: Uuid) {
+    tokio::spawn(async move {
+        let stored = generate(&ctx, convoy_id).await;
+        tracing::info!(convoy_id = %convoy_id, count = stored.len(), "Stored mission artifacts");
+    });
+}
+
+/// One of a convoy's artifacts and its content, `None` unless the convoy
+/// holds an artifact with `digest`
+pub async fn download(
+    ctx: &ApiContext,
+    convoy_id: Uuid,
+    digest: &str,
+) -> ApiResult<Option<(MissionArtifact, Vec<u8>)>> {
+    // Digests are shared across convoys; only serve one listed for this convoy
+    let artifacts = ctx.convoy_repo.list_artifacts(convoy_id).await?;
+    let Some(artifact) = artifacts.into_iter().find(|a| a.digest == digest) else {
+        return Ok(None);
+    };
+    let content = ctx.convoy_repo.get_artifact_content(digest).await?;
+    Ok(content.map(|content| (artifact, content)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_digest_is_hex_sha256() {
+        assert_eq!(
+            content_digest(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(content_digest(b"").len(), 64);
+    }
+
+    #[test]
+    fn test_document_becomes_addressed_artifact() {
+        let convoy_id = Uuid::new_v4();
+        let document = Document {
+            kind: ArtifactKind::Leaderboard,
+            file_name: "leaderboard.csv".to_string(),
+            content_type: CSV_CONTENT_TYPE,
+            content: b"abc".to_vec(),
+        };
+        let (artifact, content) = document.into_artifact(convoy_id, DateTime::UNIX_EPOCH);
+        assert_eq!(artifact.convoy_id, convoy_id);
+        assert_eq!(artifact.digest, content_digest(b"abc"));
+        assert_eq!(artifact.size_bytes, 3);
+        assert_eq!(content, b"abc");
+    }
+}
@@ -0,0 +1,406 @@
+//! # Roles
+//!
+//! Bearer-token roles, per-token convoy scopes, commanding-unit tenancy,
+//! data environments and the GraphQL guards that enforce them.
+
+use async_graphql::{Context, Guard};
+use axum::http::{header, HeaderMap};
+use drone_domain::Environment;
+use drone_persistence::ConvoyOwner;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use crate::error::ApiError;
+
+/// Caller role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Role {
+    /// Read-only dashboard access (unauthenticated callers)
+    #[default]
+    Viewer,
+    /// Mission operators
+    Operator,
+    /// Analysts with ad-hoc query access
+    Analyst,
+    /// Commanders who approve engagements; also hold operator access
+    Commander,
+    /// Full access
+    Admin,
+}
+
+impl Role {
+    /// Whether this role satisfies `required`
+    #[must_use]
+    pub fn permits(self, required: Role) -> bool {
+        self == Role::Admin
+            || self == required
+            || required == Role::Viewer
+            || (self == Role::Commander && required == Role::Operator)
+    }
+
+    /// Fail with `Unauthorized` unless this role satisfies `required`
+    pub fn require(self, required: Role) -> Result<(), ApiError> {
+        if self.permits(required) {
+            Ok(())
+        } else {
+            Err(ApiError::Unauthorized(format!("{required:?} role required")))
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "VIEWER" => Ok(Self::Viewer),
+            "OPERATOR" => Ok(Self::Operator),
+            "ANALYST" => Ok(Self::Analyst),
+            "COMMANDER" => Ok(Self::Commander),
+            "ADMIN" => Ok(Self::Admin),
+            other => Err(format!("unknown role '{other}'")),
+        }
+    }
+}
+
+/// What a bearer token grants.
+///
+/// The default is an anonymous caller: a viewer that belongs to no
+/// commanding unit and so sees none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Claims {
+    /// Granted role
+    pub role: Role,
+    /// Convoys the token is limited to; `None` grants every convoy
+    pub convoys: Option<HashSet<String>>,
+    /// Commanding unit (tenant) the caller belongs to; `None` on an
+    /// authenticated caller sees every unit
+    pub commanding_unit: Option<String>,
+    /// Environment every read and write is confined to
+    pub environment: Environment,
+    /// Whether a token or API key granted these claims
+    pub authenticated: bool,
+}
+
+impl Claims {
+    /// Unscoped claims granted to an authenticated caller
+    #[must_use]
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            convoys: None,
+            commanding_unit: None,
+            environment: Environment::default(),
+            authenticated: true,
+        }
+    }
+
+    /// Claims for a caller that presented no known token
+    #[must_use]
+    pub fn anonymous(role: Role) -> Self {
+        Self {
+            role,
+            ..Self::default()
+        }
+    }
+
+    /// Whether these claims span every commanding unit
+    #[must_use]
+    pub fn all_units(&self) -> bool {
+        self.authenticated && self.commanding_unit.is_none()
+    }
+
+    /// Whether a convoy owned by `unit` belongs to the caller's tenant
+    #[must_use]
+    pub fn can_access_unit(&self, unit: &str) -> bool {
+        self.authenticated
+            && self
+                .commanding_unit
+                .as_deref()
+                .is_none_or(|own| own.eq_ignore_ascii_case(unit.trim()))
+    }
+
+    /// Whether a convoy with this owner is visible to the caller.
+    ///
+    /// Unknown convoys (`None`) are visible only to callers spanning every
+    /// commanding unit, and count as LIVE; the lookup that follows reports
+    /// them missing.
+    #[must_use]
+    pub fn can_access_owner(&self, owner: Option<&ConvoyOwner>) -> bool {
+        match owner {
+            Some(owner) => {
+                owner.environment == self.environment
+                    && self.can_access_unit(&owner.commanding_unit)
+            }
+            None => self.all_units() && self.environment == Environment::Live,
+        }
+    }
+
+    /// Whether these claims cover `convoy_id`
+    #[must_use]
+    pub fn can_access_convoy(&self, convoy_id: &str) -> bool {
+        self.role == Role::Admin
+            || self
+                .convoys
+                .as_ref()
+                .is_none_or(|convoys| convoys.contains(convoy_id))
+    }
+
+    /// Fail with `Unauthorized` unless these claims cover `convoy_id`
+    pub fn require_convoy(&self, convoy_id: &str) -> Result<(), ApiError> {
+        if self.can_access_convoy(convoy_id) {
+            Ok(())
+        } else {
+            Err(ApiError::Unauthorized(format!("no access to convoy {convoy_id}")))
+        }
+    }
+
+    /// Fail with `Unauthorized` for anonymous and tenant-scoped callers;
+    /// used by endpoints that read across every commanding unit
+    pub fn require_all_units(&self) -> Result<(), ApiError> {
+        if !self.authenticated {
+            return Err(ApiError::Unauthorized("valid token required".to_string()));
+        }
+        match &self.commanding_unit {
+            None => Ok(()),
+            Some(unit) => Err(ApiError::Unauthorized(format!(
+                "token is scoped to commanding unit {unit}"
+            ))),
+        }
+    }
+}
+
+/// Static bearer token to claims mapping
+pub type RoleTokens = HashMap<String, Claims>;
+
+/// Parse `token:ROLE[@UNIT][/ENVIRONMENT][:convoy|convoy]` entries separated
+/// by commas, skipping malformed entries; the environment defaults to LIVE
+#[must_use]
+pub fn parse_role_tokens(spec: &str) -> RoleTokens {
+    spec.split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.trim().splitn(3, ':');
+            let token = parts.next()?.trim();
+            let grant = parts.next()?;
+            let (grant, environment) = match grant.rsplit_once('/') {
+                Some((grant, environment)) => (grant, environment.parse().ok()?),
+                None => (grant, Environment::default()),
+            };
+            let (role, commanding_unit) = match grant.split_once('@') {
+                Some((role, unit)) => (role, Some(unit.trim().to_string())),
+                None => (grant, None),
+            };
+            let role = role.parse().ok()?;
+            let commanding_unit = commanding_unit.filter(|unit| !unit.is_empty());
+            let convoys = parts.next().map(|scope| {
+                scope
+                    .split('|')
+                    .map(|id| id.trim().to_string())
+                    .filter(|id| !id.is_empty())
+                    .collect()
+            });
+            Some((
+                token.to_string(),
+                Claims {
+                    role,
+                    convoys,
+                    commanding_unit,
+                    environment,
+                    authenticated: true,
+                },
+            ))
+        })
+        .filter(|(token, _)| !token.is_empty())
+        .collect()
+}
+
+/// Look up the claims for a raw `Bearer <token>` value or bare token
+fn claims_for(value: &str, tokens: &RoleTokens) -> Option<Claims> {
+    let token = value.strip_prefix("Bearer ").unwrap_or(value).trim();
+    tokens.get(token).cloned()
+}
+
+/// Resolve claims from a bare token or `Bearer <token>` value, e.g. one
+/// passed as a query parameter
+#[must_use]
+pub fn claims_from_token(value: &str, tokens: &RoleTokens) -> Option<Claims> {
+    claims_for(value, tokens)
+}
+
+/// Resolve the caller's claims from the `Authorization: Bearer` header
+#[must_use]
+pub fn claims_from_headers(headers: &HeaderMap, tokens: &RoleTokens) -> Option<Claims> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| v.starts_with("Bearer "))
+        .and_then(|v| claims_for(v, tokens))
+}
+
+/// Resolve the caller's role from the `Authorization: Bearer` header
+#[must_use]
+pub fn role_from_headers(headers: &HeaderMap, tokens: &RoleTokens) -> Role {
+    claims_from_headers(headers, tokens)
+        .map(|c| c.role)
+        .unwrap_or_default()
+}
+
+/// Resolve claims from a WebSocket `connection_init` payload.
+///
+/// Accepts `{"authorization": "Bearer <token>"}` (any header casing) or
+/// `{"token": "<token>"}`.
+#[must_use]
+pub fn claims_from_init_payload(payload: &Value, tokens: &RoleTokens) -> Option<Claims> {
+    token_from_init_payload(payload).and_then(|value| claims_for(value, tokens))
+}
+
+/// Raw token a `connection_init` payload presents, known or not
+#[must_use]
+pub fn token_from_init_payload(payload: &Value) -> Option<&str> {
+    payload
+        .as_object()?
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("authorization") || key.as_str() == "token")
+        .and_then(|(_, value)| value.as_str())
+}
+
+/// Guard restricting a field to callers holding a role
+pub struct RoleGuard {
+    required: Role,
+}
+
+impl RoleGuard {
+    #[must_use]
+    pub fn new(required: Role) -> Self {
+        Self { required }
+    }
+}
+
+impl Guard for RoleGuard {
+    async fn check(&self, ctx: &Context<'_>) -> async_graphql::Result<()> {
+        let role = ctx.data_opt::<Role>().copied().unwrap_or_default();
+        role.require(self.required).map_err(Into::into)
+    }
+}
+
+/// Claims attached to the current request or connection
+#[must_use]
+pub fn claims(ctx: &Context<'_>) -> Claims {
+    ctx.data_opt::<Claims>()
+        .cloned()
+        .unwrap_or_else(|| Claims::anonymous(ctx.data_opt::<Role>().copied().unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_tokens_resolve_from_bearer_header() {
+        let tokens = parse_role_tokens("abc:ANALYST, def:operator, broken, ghi:NOPE");
+        assert_eq!(tokens.len(), 2);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(role_from_headers(&headers, &tokens), Role::Viewer);
+
+        headers.insert(header::AUTHORIZATION, "Bearer abc".parse().unwrap());
+        assert_eq!(role_from_headers(&headers, &tokens), Role::Analyst);
+
+        assert!(Role::Admin.permits(Role::Analyst));
+        assert!(!Role::Operator.permits(Role::Analyst));
+        assert!(Role::Commander.permits(Role::Operator));
+        assert!(!Role::Operator.permits(Role::Commander));
+    }
+
+    #[test]
+    fn test_convoy_scoped_tokens() {
+        let tokens = parse_role_tokens("ops:OPERATOR:c1|c2, root:ADMIN:c1, any:VIEWER");
+
+        let ops = &tokens["ops"];
+        assert_eq!(ops.role, Role::Operator);
+        assert!(ops.can_access_convoy("c2"));
+        assert!(!ops.can_access_convoy("c3"));
+
+        // Admins ignore convoy scope; unscoped tokens see everything
+        assert!(tokens["root"].can_access_convoy("c3"));
+        assert!(tokens["any"].can_access_convoy("c3"));
+    }
+
+    #[test]
+    fn test_commanding_unit_tokens() {
+        let tokens = parse_role_tokens("a:OPERATOR@432nd Wing:c1, b:ADMIN@, c:ANALYST@VMU-1");
+
+        let ops = &tokens["a"];
+        assert_eq!(ops.role, Role::Operator);
+        assert_eq!(ops.commanding_unit.as_deref(), Some("432nd Wing"));
+        assert!(ops.can_access_convoy("c1"));
+        assert!(ops.can_access_unit("432ND WING"));
+        assert!(!ops.can_access_unit("VMU-1"));
+
+        // An empty unit is unscoped
+        assert!(tokens["b"].commanding_unit.is_none());
+        assert!(tokens["b"].can_access_unit("VMU-1"));
+        assert!(!tokens["c"].can_access_unit("432nd Wing"));
+    }
+
+    #[test]
+    fn test_environment_tokens() {
+        let tokens = parse_role_tokens(
+            "a:OPERATOR@VMU-1/exercise:c1, b:ANALYST/TEST, c:VIEWER, d:OPERATOR/SANDBOX",
+        );
+
+        assert_eq!(tokens["a"].environment, Environment::Exercise);
+        assert_eq!(tokens["a"].commanding_unit.as_deref(), Some("VMU-1"));
+        assert!(tokens["a"].can_access_convoy("c1"));
+        assert_eq!(tokens["b"].environment, Environment::Test);
+        assert_eq!(tokens["c"].environment, Environment::Live);
+
+        // An unknown environment makes the entry malformed
+        assert!(!tokens.contains_key("d"));
+
+        let exercise = ConvoyOwner {
+            commanding_unit: "VMU-1".to_string(),
+            environment: Environment::Exercise,
+        };
+        assert!(tokens["a"].can_access_owner(Some(&exercise)));
+        assert!(!tokens["b"].can_access_owner(Some(&exercise)));
+        assert!(!tokens["c"].can_access_owner(Some(&exercise)));
+        assert!(tokens["c"].can_access_owner(None));
+        assert!(!tokens["b"].can_access_owner(None));
+    }
+
+    #[test]
+    fn test_claims_from_init_payload() {
+        let tokens = parse_role_tokens("abc:OPERATOR:c1");
+
+        let payload = serde_json::json!({ "Authorization": "Bearer abc" });
+        assert_eq!(claims_from_init_payload(&payload, &tokens).unwrap().role, Role::Operator);
+
+        let payload = serde_json::json!({ "token": "abc" });
+        assert!(claims_from_init_payload(&payload, &tokens).is_some());
+
+        let payload = serde_json::json!({ "token": "nope" });
+        assert!(claims_from_init_payload(&payload, &tokens).is_none());
+        assert!(claims_from_init_payload(&Value::Null, &tokens).is_none());
+    }
+
+    #[test]
+    fn test_anonymous_claims_see_no_unit() {
+        let anonymous = Claims::default();
+        assert_eq!(anonymous, Claims::anonymous(Role::Viewer));
+        assert!(anonymous.commanding_unit.is_none());
+        assert!(!anonymous.all_units());
+        assert!(!anonymous.can_access_unit("VMU-1"));
+        assert!(!anonymous.can_access_owner(None));
+        assert!(anonymous.require_all_units().is_err());
+
+        let owner = ConvoyOwner {
+            commanding_unit: "VMU-1".to_string(),
+            environment: Environment::Live,
+        };
+        assert!(!anonymous.can_access_owner(Some(&owner)));
+        assert!(Claims::new(Role::Viewer).can_access_owner(Some(&owner)));
+        assert!(Claims::new(Role::Admin).require_all_units().is_ok());
+    }
+}
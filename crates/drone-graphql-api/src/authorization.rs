@@ -0,0 +1,175 @@
+//! # Engagement Authorization Codes
+//!
+//! Signs approved engagement authorizations so `createEngagement` can check
+//! that a code was issued by this service for exactly the engagement being
+//! recorded, and that the approval has not expired.
+//!
+//! Codes are `<request-id>.<signature>`, where the signature is an
+//! HMAC-SHA256 over the request ID, convoy, drone, weapon, target type and
+//! expiry.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use drone_domain::{AuthorizationStatus, EngagementAuthorization};
+use ring::hmac;
+use ring::rand::SystemRandom;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+
+/// Default time an approval stays valid
+pub const DEFAULT_CODE_TTL_SECS: u64 = 900;
+
+/// Issues and checks signed authorization codes
+pub struct AuthorizationSigner {
+    key: hmac::Key,
+    ttl: Duration,
+}
+
+impl AuthorizationSigner {
+    /// Signer using a shared secret, so codes survive restarts and validate
+    /// on every replica
+    #[must_use]
+    pub fn new(secret: &[u8], ttl: Duration) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, secret),
+            ttl,
+        }
+    }
+
+    /// Signer with a random key; outstanding codes are invalidated on restart
+    #[must_use]
+    pub fn ephemeral(ttl: Duration) -> Self {
+        let key = hmac::Key::generate(hmac::HMAC_SHA256, &SystemRandom::new())
+            .expect("system random source unavailable");
+        Self { key, ttl }
+    }
+
+    /// Expiry for an approval granted at `now`
+    #[must_use]
+    pub fn expiry(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now + chrono::Duration::from_std(self.ttl).unwrap_or(chrono::Duration::MAX)
+    }
+
+    /// Authorization code for an approved request; `None` for any other status
+    #[must_use]
+    pub fn issue(&self, auth: &EngagementAuthorization) -> Option<String> {
+        if auth.status != AuthorizationStatus::Approved {
+            return None;
+        }
+        let expires_at = auth.expires_at?;
+        let tag = hmac::sign(&self.key, signing_input(auth, expires_at).as_bytes());
+        Some(format!("{}.{}", auth.request_id, URL_SAFE_NO_PAD.encode(tag.as_ref())))
+    }
+
+    /// Check that `code` was issued for `auth` and is still within its
+    /// validity window
+    pub fn verify(
+        &self,
+        code: &str,
+        auth: &EngagementAuthorization,
+        now: DateTime<Utc>,
+    ) -> ApiResult<()> {
+        let invalid = || ApiError::Unauthorized("invalid authorization code".to_string());
+
+        let (request_id, signature) = code.trim().split_once('.').ok_or_else(invalid)?;
+        if Uuid::parse_str(request_id).ok() != Some(auth.request_id) {
+            return Err(invalid());
+        }
+        let expires_at = auth.expires_at.ok_or_else(invalid)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+        hmac::verify(&self.key, signing_input(auth, expires_at).as_bytes(), &signature)
+            .map_err(|_| invalid())?;
+
+        if now > expires_at {
+            return Err(ApiError::Unauthorized(format!(
+                "authorization {} expired at {}",
+                auth.request_id,
+                expires_at.to_rfc3339()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Request ID a code claims to authorize; the signature is checked by
+/// [`AuthorizationSigner::verify`] once the request is loaded
+pub fn request_id(code: &str) -> ApiResult<Uuid> {
+    code.trim()
+        .split_once('.')
+        .and_then(|(id, _)| Uuid::parse_str(id).ok())
+        .ok_or_else(|| ApiError::Unauthorized("invalid authorization code".to_string()))
+}
+
+fn signing_input(auth: &EngagementAuthorization, expires_at: DateTime<Utc>) -> String {
+    format!(
+        "{}|{}|{}|{}|{:?}|{}",
+        auth.request_id,
+        auth.convoy_id,
+        auth.drone_id,
+        auth.weapon_type.as_str(),
+        auth.target_type,
+        expires_at.timestamp()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use drone_domain::{Coordinates, TargetType, WeaponType};
+
+    fn approved(signer: &AuthorizationSigner, now: DateTime<Utc>) -> EngagementAuthorization {
+        EngagementAuthorization {
+            convoy_id: Uuid::new_v4(),
+            request_id: Uuid::new_v4(),
+            drone_id: Uuid::new_v4(),
+            weapon_type: WeaponType::Agm114Hellfire,
+            target_type: TargetType::Vehicle,
+            target_coordinates: Coordinates::default(),
+            requested_by: "OPERATOR".to_string(),
+            justification: "Hostile technical".to_string(),
+            requested_at: now,
+            status: AuthorizationStatus::Approved,
+            decided_by: Some("COMMANDER".to_string()),
+            decided_at: Some(now),
+            decision_notes: None,
+            expires_at: Some(signer.expiry(now)),
+            engagement_id: None,
+        }
+    }
+
+    #[test]
+    fn test_issued_code_verifies_for_its_request() {
+        let signer = AuthorizationSigner::new(b"secret", Duration::from_secs(60));
+        let now = Utc::now();
+        let auth = approved(&signer, now);
+
+        let code = signer.issue(&auth).unwrap();
+        assert_eq!(request_id(&code).unwrap(), auth.request_id);
+        assert!(signer.verify(&code, &auth, now).is_ok());
+
+        // Bound to the approved weapon and to this service's key
+        let mut other_weapon = auth.clone();
+        other_weapon.weapon_type = WeaponType::Gbu12Paveway;
+        assert!(signer.verify(&code, &other_weapon, now).is_err());
+        let other_key = AuthorizationSigner::new(b"other", Duration::from_secs(60));
+        assert!(other_key.verify(&code, &auth, now).is_err());
+    }
+
+    #[test]
+    fn test_code_expires_and_requires_approval() {
+        let signer = AuthorizationSigner::ephemeral(Duration::from_secs(60));
+        let now = Utc::now();
+        let mut auth = approved(&signer, now);
+
+        let code = signer.issue(&auth).unwrap();
+        let later = now + chrono::Duration::seconds(61);
+        assert!(signer.verify(&code, &auth, later).is_err());
+        assert!(signer.verify("not-a-code", &auth, now).is_err());
+
+        auth.status = AuthorizationStatus::Denied;
+        assert!(signer.issue(&auth).is_none());
+    }
+}
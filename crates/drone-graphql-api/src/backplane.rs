@@ -0,0 +1,326 @@
+//! # Event Back-plane
+//!
+//! Redis Pub/Sub fan-out of broadcast events between API replicas.
+//!
+//! Broadcast channels only reach subscribers connected to the replica that
+//! handled the mutation. With a back-plane configured, every event published
+//! through [`ApiContext::publish`] is also sent to a shared Redis channel,
+//! and each replica re-broadcasts the events other replicas published, so
+//! WebSocket and SSE clients see the same stream whichever replica they hit.
+//!
+//! Events are tagged with the publishing replica's ID so a replica never
+//! re-broadcasts its own events. Pub/Sub is fire-and-forget: events
+//! published while a replica is disconnected from Redis are not replayed.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_graphql::ID;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::schema::{
+    AlertEvent, DroneStatusEvent, EngagementAuthorization, EngagementEvent,
+    LeaderboardUpdateEvent, SensorTask, TelemetrySnapshot,
+};
+
+/// Redis channel shared by all replicas by default
+pub const DEFAULT_BACKPLANE_CHANNEL: &str = "drone:events";
+
+/// Events queued for Redis before new ones are dropped
+const OUTBOUND_CAPACITY: usize = 4096;
+
+/// Delay before resubscribing after the Redis connection drops
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(2);
+
+/// Any event carried by the API broadcast channels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "event", rename_all = "camelCase")]
+pub enum BroadcastEvent {
+    Engagement(EngagementEvent),
+    Leaderboard(LeaderboardUpdateEvent),
+    DroneStatus(DroneStatusEvent),
+    Alert(AlertEvent),
+    Telemetry(TelemetrySnapshot),
+    Authorization(EngagementAuthorization),
+    SensorTask(SensorTask),
+}
+
+macro_rules! broadcast_event_from {
+    ($($variant:ident($ty:ty)),+ $(,)?) => {
+        $(
+            impl From<$ty> for BroadcastEvent {
+                fn from(event: $ty) -> Self {
+                    Self::$variant(event)
+                }
+            }
+        )+
+    };
+}
+
+impl BroadcastEvent {
+    /// Event ID, once [`ApiContext::publish`] has stamped one
+    #[must_use]
+    pub fn event_id(&self) -> Option<&ID> {
+        match self {
+            Self::Engagement(e) => e.event_id.as_ref(),
+            Self::Leaderboard(e) => e.event_id.as_ref(),
+            Self::DroneStatus(e) => e.event_id.as_ref(),
+            Self::Alert(e) => e.event_id.as_ref(),
+            Self::Telemetry(e) => e.event_id.as_ref(),
+            Self::Authorization(e) => e.event_id.as_ref(),
+            Self::SensorTask(e) => e.event_id.as_ref(),
+        }
+    }
+
+    /// Give the event a new ID unless it already carries one
+    pub fn stamp(&mut self) {
+        let slot = match self {
+            Self::Engagement(e) => &mut e.event_id,
+            Self::Leaderboard(e) => &mut e.event_id,
+            Self::DroneStatus(e) => &mut e.event_id,
+            Self::Alert(e) => &mut e.event_id,
+            Self::Telemetry(e) => &mut e.event_id,
+            Self::Authorization(e) => &mut e.event_id,
+            Self::SensorTask(e) => &mut e.event_id,
+        };
+        slot.get_or_insert_with(crate::replay::new_event_id);
+    }
+
+    /// Convoy the event belongs to; `None` for drone-scoped events
+    #[must_use]
+    pub fn convoy_id(&self) -> Option<&ID> {
+        match self {
+            Self::Engagement(e) => Some(&e.convoy_id),
+            Self::Leaderboard(e) => Some(&e.convoy_id),
+            Self::DroneStatus(e) => Some(&e.convoy_id),
+            Self::Alert(e) => Some(&e.convoy_id),
+            Self::Authorization(e) => Some(&e.convoy_id),
+            Self::Telemetry(_) | Self::SensorTask(_) => None,
+        }
+    }
+}
+
+broadcast_event_from!(
+    Engagement(EngagementEvent),
+    Leaderboard(LeaderboardUpdateEvent),
+    DroneStatus(DroneStatusEvent),
+    Alert(AlertEvent),
+    Telemetry(TelemetrySnapshot),
+    Authorization(EngagementAuthorization),
+    SensorTask(SensorTask),
+);
+
+/// Wire format: the event and the replica that published it
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    origin: Uuid,
+    #[serde(flatten)]
+    event: BroadcastEvent,
+}
+
+/// Redis Pub/Sub link between this replica and its peers
+pub struct Backplane {
+    instance_id: Uuid,
+    channel: String,
+    outbound: mpsc::Sender<BroadcastEvent>,
+    /// Taken by [`Backplane::start`]
+    pending: Mutex<Option<mpsc::Receiver<BroadcastEvent>>>,
+}
+
+impl Backplane {
+    /// Back-plane on `channel` with a fresh replica ID
+    #[must_use]
+    pub fn new(channel: impl Into<String>) -> Self {
+        let (outbound, pending) = mpsc::channel(OUTBOUND_CAPACITY);
+        Self {
+            instance_id: Uuid::new_v4(),
+            channel: channel.into(),
+            outbound,
+            pending: Mutex::new(Some(pending)),
+        }
+    }
+
+    /// ID stamped on events this replica publishes
+    #[must_use]
+    pub fn instance_id(&self) -> Uuid {
+        self.instance_id
+    }
+
+    /// Redis channel events are exchanged on
+    #[must_use]
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// Queue a locally published event for the other replicas.
+    ///
+    /// Never blocks the publisher; the event is dropped with a warning when
+    /// Redis cannot keep up.
+    pub fn forward(&self, event: BroadcastEvent) {
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.outbound.try_send(event) {
+            tracing::warn!(channel = %self.channel, "Back-plane queue full; event not shared");
+        }
+    }
+
+    /// Encode an event published by this replica
+    fn encode(&self, event: BroadcastEvent) -> serde_json::Result<String> {
+        serde_json::to_string(&Envelope {
+            origin: self.instance_id,
+            event,
+        })
+    }
+
+    /// Decode a Redis message; `None` for this replica's own events and
+    /// for messages that are not events
+    fn decode(&self, payload: &str) -> Option<BroadcastEvent> {
+        match serde_json::from_str::<Envelope>(payload) {
+            Ok(envelope) if envelope.origin == self.instance_id => None,
+            Ok(envelope) => Some(envelope.event),
+            Err(e) => {
+                tracing::warn!(channel = %self.channel, error = %e, "Ignoring malformed back-plane message");
+                None
+            }
+        }
+    }
+
+    /// Publish queued events to Redis and re-broadcast peers' events locally.
+    ///
+    /// Resubscribes after Redis connection drops; call once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the back-plane was already started.
+    pub fn start(self: Arc<Self>, ctx: &ApiContext) -> JoinHandle<()> {
+        let mut outbound = self
+            .pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take()
+            .expect("back-plane already started");
+        let ctx = ctx.clone();
+
+        tokio::spawn(async move {
+            tracing::info!(channel = %self.channel, instance_id = %self.instance_id, "Event back-plane started");
+            let publisher = async {
+                while let Some(event) = outbound.recv().await {
+                    let payload = match self.encode(event) {
+                        Ok(payload) => payload,
+                        Err(e) => {
+                            tracing::warn!(error = %e, "Failed to encode back-plane event");
+                            continue;
+                        }
+                    };
+                    if let Err(e) = ctx.cache.publish(&self.channel, &payload).await {
+                        tracing::warn!(channel = %self.channel, error = %e, "Failed to publish back-plane event");
+                    }
+                }
+            };
+            let subscriber = async {
+                loop {
+                    match ctx.cache.subscribe(&self.channel).await {
+                        Ok(messages) => {
+                            let mut messages = std::pin::pin!(messages);
+                            while let Some(payload) = messages.next().await {
+                                if let Some(event) = self.decode(&payload) {
+                                    ctx.broadcast_local(event);
+                                }
+                            }
+                            tracing::warn!(channel = %self.channel, "Back-plane subscription dropped");
+                        }
+                        Err(e) => {
+                            tracing::warn!(channel = %self.channel, error = %e, "Back-plane subscribe failed");
+                        }
+                    }
+                    tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+                }
+            };
+
+            // Both halves run for the life of the process
+            tokio::join!(publisher, subscriber);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{RankChangeType, WeaponType};
+    use async_graphql::ID;
+    use chrono::Utc;
+
+    fn engagement() -> EngagementEvent {
+        EngagementEvent {
+            convoy_id: ID::from("convoy-1"),
+            drone_id: ID::from("drone-1"),
+            callsign: "REAPER-01".to_string(),
+            hit: true,
+            weapon_type: WeaponType::Agm114Hellfire,
+            target_type: None,
+            range_km: Some(4.5),
+            predicted_pk: None,
+            new_accuracy_pct: 75.0,
+            timestamp: Utc::now(),
+            event_id: None,
+        }
+    }
+
+    #[test]
+    fn test_peer_events_round_trip() {
+        let (local, peer) = (Backplane::new("events"), Backplane::new("events"));
+        let leaderboard = LeaderboardUpdateEvent {
+            convoy_id: ID::from("convoy-1"),
+            drone_id: ID::from("drone-1"),
+            callsign: "REAPER-01".to_string(),
+            new_rank: 1,
+            old_rank: Some(2),
+            accuracy_pct: 75.0,
+            change_type: RankChangeType::RankUp,
+            timestamp: Utc::now(),
+            event_id: None,
+        };
+
+        let mut event = BroadcastEvent::from(engagement());
+        event.stamp();
+        let event_id = event.event_id().cloned();
+        assert!(event_id.is_some());
+
+        let payload = peer.encode(event).unwrap();
+        assert!(payload.contains(r#""kind":"engagement""#));
+        assert!(payload.contains(r#""weaponType":"AGM_114_HELLFIRE""#));
+        match local.decode(&payload) {
+            Some(BroadcastEvent::Engagement(event)) => {
+                assert_eq!(event.callsign, "REAPER-01");
+                assert_eq!(event.weapon_type, WeaponType::Agm114Hellfire);
+                // Peers keep the publisher's ID so replay cursors match
+                assert_eq!(event.event_id, event_id);
+            }
+            other => panic!("expected engagement, got {other:?}"),
+        }
+
+        let payload = peer.encode(leaderboard.into()).unwrap();
+        match local.decode(&payload) {
+            Some(BroadcastEvent::Leaderboard(event)) => {
+                assert_eq!(event.change_type, RankChangeType::RankUp);
+                assert_eq!(event.old_rank, Some(2));
+            }
+            other => panic!("expected leaderboard update, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_own_and_malformed_events_are_skipped() {
+        let backplane = Backplane::new("events");
+
+        let own = backplane.encode(engagement().into()).unwrap();
+        assert!(backplane.decode(&own).is_none());
+        assert!(backplane.decode("not json").is_none());
+        assert!(backplane
+            .decode(r#"{"origin":"00000000-0000-0000-0000-000000000001","kind":"unknown","event":{}}"#)
+            .is_none());
+    }
+}
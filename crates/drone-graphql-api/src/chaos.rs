@@ -0,0 +1,59 @@
+//! # HTTP Chaos Injection
+//!
+//! Applies `http.*` chaos rules to incoming requests: latency before the
+//! handler runs, and 503s in place of the handler or its response. Rules
+//! are keyed by route template with `/` as `.`, so `http.geojson` covers
+//! every `/geojson/...` route and `http.graphql` the GraphQL endpoint.
+
+use std::sync::Arc;
+
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use drone_persistence::{Chaos, PersistenceError};
+
+use crate::error::ApiError;
+
+/// Chaos operation name for a route, e.g. `http.export.kml.{convoy_id}`
+fn route_op(path: &str) -> String {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .fold("http".to_string(), |op, segment| op + "." + segment)
+}
+
+/// Inject the faults configured for the request's route
+pub async fn inject(chaos: Arc<Chaos>, req: Request, next: Next) -> Response {
+    if !chaos.is_enabled() {
+        return next.run(req).await;
+    }
+
+    let path = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| req.uri().path().to_string(), |p| p.as_str().to_string());
+    let op = route_op(&path);
+
+    if let Err(e) = chaos.before(&op).await {
+        return ApiError::Persistence(e).into_response();
+    }
+    let response = next.run(req).await;
+    if chaos.drop_response(&op) {
+        return ApiError::Persistence(PersistenceError::Timeout(format!(
+            "chaos: dropped response from {op}"
+        )))
+        .into_response();
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_op() {
+        assert_eq!(route_op("/graphql"), "http.graphql");
+        assert_eq!(route_op("/export/kml/{convoy_id}"), "http.export.kml.{convoy_id}");
+        assert_eq!(route_op("/"), "http");
+    }
+}
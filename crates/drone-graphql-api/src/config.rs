@@ -2,6 +2,7 @@
 //!
 //! Environment-based configuration for the GraphQL API service.
 
+use serde::Serialize;
 use std::env;
 use std::net::SocketAddr;
 
@@ -14,18 +15,36 @@ pub struct Config {
     /// Enable GraphQL Playground
     pub enable_playground: bool,
 
-    /// Enable GraphQL introspection
+    /// `user:password` required to load the playground
+    pub playground_basic_auth: Option<String>,
+
+    /// Enable GraphQL introspection for every caller; admins always may
     pub enable_introspection: bool,
 
+    /// Serve the schema SDL at `/schema.graphql`
+    pub enable_schema_endpoint: bool,
+
     /// Maximum query depth
     pub max_query_depth: usize,
 
     /// Maximum query complexity
     pub max_query_complexity: usize,
 
+    /// Maximum request body size in bytes
+    pub max_body_bytes: usize,
+
+    /// Maximum items in a list input
+    pub max_batch_items: usize,
+
+    /// Maximum operations in a batched GraphQL request
+    pub max_batch_operations: usize,
+
     /// ScyllaDB configuration
     pub scylla: ScyllaConfig,
 
+    /// Database file used instead of the Scylla cluster by `embedded` builds
+    pub sqlite_path: String,
+
     /// Redis configuration
     pub redis: RedisConfig,
 
@@ -34,6 +53,90 @@ pub struct Config {
 
     /// CORS allowed origins
     pub cors_origins: Vec<String>,
+
+    /// Endpoints the dashboard is told to use via `/config.json`
+    pub frontend: FrontendConfig,
+
+    /// Convoy formation spacing bounds
+    pub formation: FormationConfig,
+
+    /// Flight path separation minimums
+    pub deconfliction: DeconflictionConfig,
+
+    /// Flight hours advisory limits
+    pub flight_hours: FlightHoursConfig,
+
+    /// Weather provider configuration
+    pub weather: WeatherConfig,
+
+    /// Bearer tokens as `token:ROLE[@UNIT][/ENVIRONMENT][:convoys]` entries
+    pub api_tokens: String,
+
+    /// Secret keying API key hashes; a random key is used when unset
+    pub api_key_hash_secret: Option<String>,
+
+    /// Engagement authorization configuration
+    pub engagement_auth: EngagementAuthConfig,
+
+    /// Rounds at or below which a weapon raises a low munitions alert
+    pub low_munitions_rounds: i16,
+
+    /// How long an engagement waits for earlier ones for the same drone (ms)
+    pub engagement_reorder_window_ms: u64,
+
+    /// JSON file of convoy template definitions replacing the built-in ones
+    pub convoy_templates_path: Option<String>,
+
+    /// JSON file of platform capabilities replacing the built-in ones
+    pub platform_capabilities_path: Option<String>,
+
+    /// Append engagement events to the event log
+    pub event_sourcing_enabled: bool,
+
+    /// Analytics engine configuration
+    pub analytics: AnalyticsConfig,
+
+    /// Subscription WebSocket configuration
+    pub ws: WsConfig,
+
+    /// Persistence strategy hot-reload configuration
+    pub strategy: StrategyConfig,
+
+    /// Convoy statistics snapshot configuration
+    pub stats: StatsConfig,
+
+    /// Background task runner configuration
+    pub tasks: TasksConfig,
+
+    /// Redis/ScyllaDB circuit breaker configuration
+    pub breaker: BreakerConfig,
+
+    /// Multi-replica event back-plane configuration
+    pub backplane: BackplaneConfig,
+
+    /// External alert notification configuration
+    pub alerts: AlertRoutingConfig,
+
+    /// Fault injection rules; requires a build with the `chaos` feature
+    pub chaos_rules: String,
+}
+
+/// Endpoints served to the dashboard at `/config.json`.
+///
+/// Unset endpoints are omitted so the dashboard falls back to its build-time
+/// defaults. Values may be absolute URLs or paths on the page's origin.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontendConfig {
+    /// GraphQL HTTP endpoint
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// GraphQL subscription WebSocket endpoint
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ws_url: Option<String>,
+    /// Server-sent events endpoint; the convoy ID is appended
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events_url: Option<String>,
 }
 
 /// ScyllaDB connection configuration
@@ -43,13 +146,223 @@ pub struct ScyllaConfig {
     pub keyspace: String,
     pub username: Option<String>,
     pub password: Option<String>,
+    /// Attempts per idempotent query, including the first
+    pub retry_max_attempts: u32,
+    /// Backoff before the first retry, doubled for each further retry
+    pub retry_base_delay_ms: u64,
+    /// Upper bound on a single retry backoff
+    pub retry_max_delay_ms: u64,
+    /// Statements taking at least this long are logged; 0 disables
+    pub slow_query_ms: u64,
 }
 
 /// Redis connection configuration
 #[derive(Debug, Clone)]
 pub struct RedisConfig {
+    /// Cache backend: `redis`, or `memory` to run without a Redis server
+    pub backend: String,
     pub url: String,
     pub pool_size: usize,
+    /// Cache value codec: `json`, or `msgpack` in builds with the
+    /// `cache-msgpack` feature
+    pub codec: String,
+}
+
+/// Convoy formation configuration
+#[derive(Debug, Clone)]
+pub struct FormationConfig {
+    pub min_spacing_km: f64,
+    pub max_spacing_km: f64,
+}
+
+/// Flight path deconfliction configuration
+#[derive(Debug, Clone)]
+pub struct DeconflictionConfig {
+    pub min_horizontal_km: f64,
+    pub min_vertical_m: f64,
+    /// How far ahead flight paths are projected
+    pub lookahead_secs: f64,
+}
+
+/// Flight hours advisory configuration
+#[derive(Debug, Clone)]
+pub struct FlightHoursConfig {
+    /// Cumulative hours between inspections; 0 disables the advisory
+    pub inspection_interval_hrs: f64,
+    /// Longest sortie before an advisory; 0 disables the advisory
+    pub sortie_limit_hrs: f64,
+}
+
+/// Weather provider configuration
+#[derive(Debug, Clone)]
+pub struct WeatherConfig {
+    /// Provider name: "static" or "open-meteo"
+    pub provider: String,
+    pub open_meteo_url: String,
+    pub cache_ttl_secs: u64,
+    /// Milliseconds allowed to connect to the weather API
+    pub connect_timeout_ms: u64,
+    /// Milliseconds allowed for a whole weather API request
+    pub request_timeout_ms: u64,
+    /// Mission minimum visibility in km
+    pub min_visibility_km: f64,
+    /// Fixed conditions for the static provider
+    pub static_wind_speed_mps: f64,
+    pub static_wind_direction_deg: f64,
+    pub static_visibility_km: f64,
+    pub static_temperature_c: f64,
+}
+
+/// Engagement authorization configuration
+#[derive(Debug, Clone)]
+pub struct EngagementAuthConfig {
+    /// Secret for signing authorization codes; a random key is used when unset
+    pub signing_key: Option<String>,
+    /// Seconds an approval stays valid
+    pub code_ttl_secs: u64,
+}
+
+/// Analytics engine configuration
+#[derive(Debug, Clone)]
+pub struct AnalyticsConfig {
+    /// DuckDB database file; analytics is disabled when unset
+    pub db_path: Option<String>,
+    /// Row cap for analyst ad-hoc SQL
+    pub sql_max_rows: usize,
+    /// Wall-clock budget for analyst ad-hoc SQL
+    pub sql_timeout_secs: u64,
+    /// Interval between insight evaluations of active convoys; 0 disables
+    pub insights_interval_secs: u64,
+    /// Recent window compared against each insight's baseline
+    pub insights_window_mins: i64,
+    /// Engagements required on both sides before an insight is raised
+    pub insights_min_samples: i64,
+    /// Accuracy change, in percentage points, that raises an insight
+    pub insights_min_change_pct: f64,
+}
+
+/// Subscription WebSocket configuration
+#[derive(Debug, Clone)]
+pub struct WsConfig {
+    /// Interval between server keep-alive pings
+    pub ping_interval_secs: u64,
+    /// Close connections silent for this long
+    pub idle_timeout_secs: u64,
+    /// Maximum concurrent connections
+    pub max_connections: usize,
+    /// Maximum concurrent connections per client IP
+    pub max_connections_per_ip: usize,
+    /// Reject connections whose `connection_init` carries no valid token;
+    /// only the development-only `WS_DEV_ALLOW_ANONYMOUS` turns this off
+    pub require_auth: bool,
+    /// Recent events kept for SSE `Last-Event-ID` resume
+    pub sse_replay_events: usize,
+    /// Seconds convoy events are kept for `replayEvents`
+    pub event_replay_secs: u64,
+}
+
+/// Persistence strategy hot-reload configuration
+#[derive(Debug, Clone)]
+pub struct StrategyConfig {
+    /// JSON file with per-repository strategy overrides
+    pub config_path: Option<String>,
+    /// Redis key holding the same JSON (takes precedence over the file)
+    pub redis_key: Option<String>,
+    /// Poll interval for the config source
+    pub reload_secs: u64,
+}
+
+/// Convoy statistics snapshot configuration
+#[derive(Debug, Clone)]
+pub struct StatsConfig {
+    /// Interval between snapshots of active convoys; 0 disables snapshotting
+    pub snapshot_interval_secs: u64,
+}
+
+/// Background task runner configuration
+#[derive(Debug, Clone)]
+pub struct TasksConfig {
+    /// Seconds to wait for running jobs at shutdown before aborting them
+    pub shutdown_grace_secs: u64,
+}
+
+/// Circuit breaker configuration, shared by the Redis and ScyllaDB clients
+#[derive(Debug, Clone)]
+pub struct BreakerConfig {
+    /// Failure ratio over the window that opens a breaker
+    pub failure_rate: f64,
+    /// Calls recorded before the failure rate is evaluated
+    pub min_calls: usize,
+    /// Number of recent calls the failure rate is computed over
+    pub window_size: usize,
+    /// Seconds a breaker stays open before probing
+    pub open_secs: u64,
+    /// Probes admitted (and required to succeed) while half-open
+    pub half_open_probes: usize,
+}
+
+/// Multi-replica event back-plane configuration
+#[derive(Debug, Clone)]
+pub struct BackplaneConfig {
+    /// Share broadcasts with other replicas over Redis Pub/Sub
+    pub enabled: bool,
+    /// Redis channel shared by every replica
+    pub channel: String,
+}
+
+/// External alert notification configuration
+#[derive(Debug, Clone)]
+pub struct AlertRoutingConfig {
+    /// Generic JSON webhook; disabled when unset
+    pub webhook_url: Option<String>,
+    pub webhook: AlertChannelFilter,
+    /// Slack incoming webhook; disabled when unset
+    pub slack_webhook_url: Option<String>,
+    pub slack: AlertChannelFilter,
+    /// SMTP relay host; email is disabled when unset
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    /// Upgrade SMTP connections with STARTTLS
+    pub smtp_starttls: bool,
+    pub smtp_from: String,
+    pub smtp_to: Vec<String>,
+    pub email: AlertChannelFilter,
+    /// Sends per channel per minute before alerts are dropped
+    pub max_per_minute: u32,
+    /// Send attempts per alert, including the first
+    pub retry_attempts: u32,
+    /// Backoff before the first retry, doubled for each further retry
+    pub retry_backoff_ms: u64,
+}
+
+/// Which alerts a notification channel receives
+#[derive(Debug, Clone)]
+pub struct AlertChannelFilter {
+    /// Minimum severity: CRITICAL, WARNING or INFO
+    pub min_severity: String,
+    /// Convoys routed to the channel; every convoy when empty
+    pub convoy_ids: Vec<String>,
+}
+
+impl AlertChannelFilter {
+    /// Read `{prefix}_MIN_SEVERITY` and `{prefix}_CONVOYS`
+    fn from_env(prefix: &str, default_min_severity: &str) -> Self {
+        Self {
+            min_severity: env::var(format!("{prefix}_MIN_SEVERITY"))
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| default_min_severity.to_string()),
+            convoy_ids: env::var(format!("{prefix}_CONVOYS"))
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
 }
 
 impl Config {
@@ -65,10 +378,16 @@ impl Config {
                 .map(|v| v == "true" || v == "1")
                 .unwrap_or(true),
 
+            playground_basic_auth: env::var("PLAYGROUND_BASIC_AUTH").ok().filter(|v| !v.is_empty()),
+
             enable_introspection: env::var("ENABLE_INTROSPECTION")
                 .map(|v| v == "true" || v == "1")
                 .unwrap_or(true),
 
+            enable_schema_endpoint: env::var("ENABLE_SCHEMA_ENDPOINT")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+
             max_query_depth: env::var("MAX_QUERY_DEPTH")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -79,6 +398,21 @@ impl Config {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(1000),
 
+            max_body_bytes: env::var("MAX_BODY_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(crate::limits::DEFAULT_MAX_BODY_BYTES),
+
+            max_batch_items: env::var("MAX_BATCH_ITEMS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(crate::limits::DEFAULT_MAX_BATCH_ITEMS),
+
+            max_batch_operations: env::var("MAX_BATCH_OPERATIONS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(crate::limits::DEFAULT_MAX_BATCH_OPERATIONS),
+
             scylla: ScyllaConfig {
                 hosts: env::var("SCYLLA_HOSTS")
                     .unwrap_or_else(|_| "127.0.0.1:9042".to_string())
@@ -89,15 +423,35 @@ impl Config {
                     .unwrap_or_else(|_| "drone_ops".to_string()),
                 username: env::var("SCYLLA_USERNAME").ok(),
                 password: env::var("SCYLLA_PASSWORD").ok(),
+                retry_max_attempts: env::var("SCYLLA_RETRY_MAX_ATTEMPTS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(3),
+                retry_base_delay_ms: env::var("SCYLLA_RETRY_BASE_DELAY_MS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(50),
+                retry_max_delay_ms: env::var("SCYLLA_RETRY_MAX_DELAY_MS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(1000),
+                slow_query_ms: env::var("SCYLLA_SLOW_QUERY_MS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(500),
             },
 
+            sqlite_path: env::var("SQLITE_PATH").unwrap_or_else(|_| "drone_ops.sqlite".to_string()),
+
             redis: RedisConfig {
+                backend: env::var("CACHE_BACKEND").unwrap_or_else(|_| "redis".to_string()),
                 url: env::var("REDIS_URL")
                     .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string()),
                 pool_size: env::var("REDIS_POOL_SIZE")
                     .ok()
                     .and_then(|v| v.parse().ok())
                     .unwrap_or(10),
+                codec: env::var("CACHE_CODEC").unwrap_or_else(|_| "json".to_string()),
             },
 
             log_level: env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string()),
@@ -107,6 +461,274 @@ impl Config {
                 .split(',')
                 .map(String::from)
                 .collect(),
+
+            frontend: FrontendConfig {
+                api_url: env::var("FRONTEND_API_URL").ok().filter(|u| !u.is_empty()),
+                ws_url: env::var("FRONTEND_WS_URL").ok().filter(|u| !u.is_empty()),
+                events_url: env::var("FRONTEND_EVENTS_URL").ok().filter(|u| !u.is_empty()),
+            },
+
+            formation: FormationConfig {
+                min_spacing_km: env::var("FORMATION_MIN_SPACING_KM")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0.5),
+                max_spacing_km: env::var("FORMATION_MAX_SPACING_KM")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(25.0),
+            },
+
+            deconfliction: DeconflictionConfig {
+                min_horizontal_km: env::var("SEPARATION_MIN_HORIZONTAL_KM")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(1.0),
+                min_vertical_m: env::var("SEPARATION_MIN_VERTICAL_M")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(150.0),
+                lookahead_secs: env::var("CONFLICT_LOOKAHEAD_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(600.0),
+            },
+
+            flight_hours: FlightHoursConfig {
+                inspection_interval_hrs: env::var("FLIGHT_INSPECTION_INTERVAL_HRS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(50.0),
+                sortie_limit_hrs: env::var("FLIGHT_SORTIE_LIMIT_HRS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(24.0),
+            },
+
+            weather: WeatherConfig {
+                provider: env::var("WEATHER_PROVIDER").unwrap_or_else(|_| "static".to_string()),
+                open_meteo_url: env::var("OPEN_METEO_URL")
+                    .unwrap_or_else(|_| crate::weather::OPEN_METEO_URL.to_string()),
+                cache_ttl_secs: env::var("WEATHER_CACHE_TTL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(600),
+                connect_timeout_ms: env::var("WEATHER_CONNECT_TIMEOUT_MS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(1000),
+                request_timeout_ms: env::var("WEATHER_REQUEST_TIMEOUT_MS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(3000),
+                min_visibility_km: env::var("WEATHER_MIN_VISIBILITY_KM")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(5.0),
+                static_wind_speed_mps: env::var("WEATHER_STATIC_WIND_SPEED_MPS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0.0),
+                static_wind_direction_deg: env::var("WEATHER_STATIC_WIND_DIRECTION_DEG")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0.0),
+                static_visibility_km: env::var("WEATHER_STATIC_VISIBILITY_KM")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10.0),
+                static_temperature_c: env::var("WEATHER_STATIC_TEMPERATURE_C")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(15.0),
+            },
+
+            api_tokens: env::var("API_TOKENS").unwrap_or_default(),
+
+            api_key_hash_secret: env::var("API_KEY_HASH_SECRET").ok().filter(|k| !k.is_empty()),
+
+            engagement_auth: EngagementAuthConfig {
+                signing_key: env::var("ENGAGEMENT_SIGNING_KEY").ok().filter(|k| !k.is_empty()),
+                code_ttl_secs: env::var("ENGAGEMENT_AUTH_TTL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(crate::authorization::DEFAULT_CODE_TTL_SECS),
+            },
+
+            low_munitions_rounds: env::var("LOW_MUNITIONS_ROUNDS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1),
+
+            engagement_reorder_window_ms: env::var("ENGAGEMENT_REORDER_WINDOW_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(250),
+
+            convoy_templates_path: env::var("CONVOY_TEMPLATES_PATH").ok().filter(|p| !p.is_empty()),
+
+            platform_capabilities_path: env::var("PLATFORM_CAPABILITIES_PATH")
+                .ok()
+                .filter(|p| !p.is_empty()),
+
+            event_sourcing_enabled: env::var("EVENT_SOURCING_ENABLED")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+
+            analytics: AnalyticsConfig {
+                db_path: env::var("ANALYTICS_DB_PATH").ok().filter(|p| !p.is_empty()),
+                sql_max_rows: env::var("ANALYTICS_SQL_MAX_ROWS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(1000),
+                sql_timeout_secs: env::var("ANALYTICS_SQL_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10),
+                insights_interval_secs: env::var("ANALYTICS_INSIGHTS_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(300),
+                insights_window_mins: env::var("ANALYTICS_INSIGHTS_WINDOW_MINS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(60),
+                insights_min_samples: env::var("ANALYTICS_INSIGHTS_MIN_SAMPLES")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10),
+                insights_min_change_pct: env::var("ANALYTICS_INSIGHTS_MIN_CHANGE_PCT")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(20.0),
+            },
+
+            ws: WsConfig {
+                ping_interval_secs: env::var("WS_PING_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(15),
+                idle_timeout_secs: env::var("WS_IDLE_TIMEOUT_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(60),
+                max_connections: env::var("WS_MAX_CONNECTIONS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(1000),
+                max_connections_per_ip: env::var("WS_MAX_CONNECTIONS_PER_IP")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(20),
+                require_auth: !env::var("WS_DEV_ALLOW_ANONYMOUS")
+                    .map(|v| v == "true" || v == "1")
+                    .unwrap_or(false),
+                sse_replay_events: env::var("SSE_REPLAY_EVENTS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(1024),
+                event_replay_secs: env::var("EVENT_REPLAY_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(300),
+            },
+
+            strategy: StrategyConfig {
+                config_path: env::var("STRATEGY_CONFIG_PATH").ok().filter(|p| !p.is_empty()),
+                redis_key: env::var("STRATEGY_CONFIG_REDIS_KEY").ok().filter(|k| !k.is_empty()),
+                reload_secs: env::var("STRATEGY_RELOAD_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10),
+            },
+
+            stats: StatsConfig {
+                snapshot_interval_secs: env::var("STATS_SNAPSHOT_INTERVAL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(60),
+            },
+
+            tasks: TasksConfig {
+                shutdown_grace_secs: env::var("TASK_SHUTDOWN_GRACE_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10),
+            },
+
+            breaker: BreakerConfig {
+                failure_rate: env::var("BREAKER_FAILURE_RATE")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0.5),
+                min_calls: env::var("BREAKER_MIN_CALLS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(10),
+                window_size: env::var("BREAKER_WINDOW_SIZE")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(20),
+                open_secs: env::var("BREAKER_OPEN_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(30),
+                half_open_probes: env::var("BREAKER_HALF_OPEN_PROBES")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(3),
+            },
+
+            backplane: BackplaneConfig {
+                enabled: env::var("BACKPLANE_ENABLED")
+                    .map(|v| v == "true" || v == "1")
+                    .unwrap_or(false),
+                channel: env::var("BACKPLANE_CHANNEL")
+                    .ok()
+                    .filter(|c| !c.is_empty())
+                    .unwrap_or_else(|| crate::backplane::DEFAULT_BACKPLANE_CHANNEL.to_string()),
+            },
+
+            alerts: AlertRoutingConfig {
+                webhook_url: env::var("ALERT_WEBHOOK_URL").ok().filter(|u| !u.is_empty()),
+                webhook: AlertChannelFilter::from_env("ALERT_WEBHOOK", "WARNING"),
+                slack_webhook_url: env::var("ALERT_SLACK_WEBHOOK_URL").ok().filter(|u| !u.is_empty()),
+                slack: AlertChannelFilter::from_env("ALERT_SLACK", "WARNING"),
+                smtp_host: env::var("ALERT_SMTP_HOST").ok().filter(|h| !h.is_empty()),
+                smtp_port: env::var("ALERT_SMTP_PORT")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(587),
+                smtp_username: env::var("ALERT_SMTP_USERNAME").ok().filter(|u| !u.is_empty()),
+                smtp_password: env::var("ALERT_SMTP_PASSWORD").ok().filter(|p| !p.is_empty()),
+                smtp_starttls: env::var("ALERT_SMTP_STARTTLS")
+                    .map(|v| v == "true" || v == "1")
+                    .unwrap_or(true),
+                smtp_from: env::var("ALERT_SMTP_FROM")
+                    .unwrap_or_else(|_| "dronegrid-alerts@localhost".to_string()),
+                smtp_to: env::var("ALERT_SMTP_TO")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|to| !to.is_empty())
+                    .map(String::from)
+                    .collect(),
+                email: AlertChannelFilter::from_env("ALERT_SMTP", "CRITICAL"),
+                max_per_minute: env::var("ALERT_MAX_PER_MINUTE")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(crate::alerting::DEFAULT_MAX_PER_MINUTE),
+                retry_attempts: env::var("ALERT_RETRY_ATTEMPTS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(crate::alerting::DEFAULT_RETRY_ATTEMPTS),
+                retry_backoff_ms: env::var("ALERT_RETRY_BACKOFF_MS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(500),
+            },
+
+            chaos_rules: env::var("CHAOS_RULES").unwrap_or_default(),
         }
     }
 }
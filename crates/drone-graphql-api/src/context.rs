@@ -2,25 +2,90 @@
 //!
 //! Application state and dependency injection for GraphQL resolvers.
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
+use uuid::Uuid;
 
+use crate::alerting::AlertRouter;
+use crate::api_keys::{ApiKeyHasher, ApiKeyStore};
+use crate::auth::{Claims, RoleTokens};
+use crate::backplane::{Backplane, BroadcastEvent};
+use crate::authorization::{AuthorizationSigner, DEFAULT_CODE_TTL_SECS};
+use crate::config::FrontendConfig;
+use crate::error::{ApiError, ApiResult};
+use crate::ingest::IngestMetrics;
+use crate::limits::RequestLimits;
+use crate::playground::PlaygroundCredentials;
+use crate::replay::EventHistory;
 use crate::schema::*;
-use drone_persistence::{
-    CacheClient, ScyllaClient, ScyllaLeaderboardRepository, SharedCacheClient,
+use crate::sequencing::EngagementSequencer;
+use crate::sse::{EventLog, DEFAULT_REPLAY_CAPACITY};
+use crate::store::{
+    AlertRepository, ApiKeyRepository, AuthorizationRepository, ConvoyRepository, DroneRepository,
+    EngagementLogRepository, EngagementRepository, JournalRepository, LeaderboardRepository,
+    StoreClient, TargetRepository, TelemetryRepository, WaypointRepository, WeaponsRepository,
 };
+use crate::tasks::TaskRunner;
+use crate::weather::{SharedWeatherProvider, StaticWeatherProvider};
+use crate::ws::{ConnectionTracker, WsLimits};
+use drone_analytics::{AnalyticsEngine, AnalyticsError, ReadonlyLimits};
+use drone_domain::{
+    CapabilityRegistry, ConvoyTemplate, FlightHoursLimits, FormationBounds, PlatformCapabilities,
+    SeparationMinimum, TemplateKind,
+};
+use drone_persistence::{BreakerSnapshot, Chaos, CacheClient, SharedCacheClient, StrategyRegistry};
 
 /// Broadcast channel capacity
 const CHANNEL_CAPACITY: usize = 1024;
 
+/// Default mission minimum visibility (km)
+const DEFAULT_MIN_VISIBILITY_KM: f64 = 5.0;
+
+/// Default rounds at or below which a weapon is low on munitions
+const DEFAULT_LOW_MUNITIONS_ROUNDS: i16 = 1;
+
 /// Application context shared across all GraphQL resolvers
 #[derive(Clone)]
 pub struct ApiContext {
     /// Leaderboard repository
-    pub leaderboard_repo: Arc<ScyllaLeaderboardRepository>,
+    pub leaderboard_repo: Arc<LeaderboardRepository>,
+
+    /// Engagement repository
+    pub engagement_repo: Arc<EngagementRepository>,
+
+    /// Append-only engagement event log
+    pub engagement_log_repo: Arc<EngagementLogRepository>,
+
+    /// Telemetry repository
+    pub telemetry_repo: Arc<TelemetryRepository>,
+
+    /// Convoy repository
+    pub convoy_repo: Arc<ConvoyRepository>,
+
+    /// Waypoint repository
+    pub waypoint_repo: Arc<WaypointRepository>,
+
+    /// Alert repository
+    pub alert_repo: Arc<AlertRepository>,
+
+    /// Operator journal repository
+    pub journal_repo: Arc<JournalRepository>,
+
+    /// Engagement authorization repository
+    pub authorization_repo: Arc<AuthorizationRepository>,
 
-    /// ScyllaDB client
-    pub scylla: Arc<ScyllaClient>,
+    /// Weapons inventory repository
+    pub weapons_repo: Arc<WeaponsRepository>,
+
+    /// Target track repository
+    pub target_repo: Arc<TargetRepository>,
+
+    /// Drone repository
+    pub drone_repo: Arc<DroneRepository>,
+
+    /// Database client (Scylla, or a local file with the `embedded` feature)
+    pub store: Arc<StoreClient>,
 
     /// Redis cache client
     pub cache: SharedCacheClient,
@@ -39,19 +104,142 @@ pub struct ApiContext {
 
     /// Telemetry broadcaster
     pub telemetry_tx: broadcast::Sender<TelemetrySnapshot>,
+
+    /// Engagement authorization request/decision broadcaster
+    pub authorization_tx: broadcast::Sender<EngagementAuthorization>,
+
+    /// Sensor task assignment broadcaster
+    pub sensor_task_tx: broadcast::Sender<SensorTask>,
+
+    /// Analytics insight broadcaster; local to this replica
+    pub insight_tx: broadcast::Sender<InsightEvent>,
+
+    /// Shares broadcasts with other API replicas
+    pub backplane: Option<Arc<Backplane>>,
+
+    /// Signs and checks engagement authorization codes
+    pub authorization_signer: Arc<AuthorizationSigner>,
+
+    /// Rounds at or below which a weapon raises a low munitions alert
+    pub low_munitions_rounds: i16,
+
+    /// Convoy formation spacing bounds
+    pub formation_bounds: FormationBounds,
+
+    /// Flight path separation minimums
+    pub separation_minimum: SeparationMinimum,
+
+    /// Flight hours advisory limits
+    pub flight_hours_limits: FlightHoursLimits,
+
+    /// Ambient conditions provider
+    pub weather: SharedWeatherProvider,
+
+    /// Mission minimum visibility in km
+    pub min_visibility_km: f64,
+
+    /// Bearer token to role mapping
+    pub role_tokens: Arc<RoleTokens>,
+
+    /// Historical analytics engine (DuckDB connections are not `Sync`)
+    pub analytics: Option<Arc<Mutex<AnalyticsEngine>>>,
+
+    /// Limits for analyst ad-hoc SQL
+    pub analytics_limits: ReadonlyLimits,
+
+    /// Serve the schema SDL over HTTP
+    pub schema_endpoint: bool,
+
+    /// Serve the GraphQL Playground at `GET /graphql`
+    pub playground: bool,
+
+    /// Basic auth required to load the playground
+    pub playground_auth: Option<Arc<PlaygroundCredentials>>,
+
+    /// Let every caller introspect the schema, not only admins
+    pub introspection: bool,
+
+    /// Endpoints served to the dashboard at `/config.json`
+    pub frontend_config: FrontendConfig,
+
+    /// Open subscription connections and their limits
+    pub ws_connections: Arc<ConnectionTracker>,
+
+    /// Reject subscription connections without a valid token
+    pub ws_require_auth: bool,
+
+    /// Recent broadcast events for the SSE endpoint
+    pub event_log: Arc<EventLog>,
+
+    /// Stores convoy events for `replayEvents`
+    pub event_history: Arc<EventHistory>,
+
+    /// Runtime-switchable repository strategies
+    pub strategies: Arc<StrategyRegistry>,
+
+    /// Periodic background jobs
+    pub tasks: Arc<TaskRunner>,
+
+    /// Append engagement events to the log so projections can be rebuilt
+    pub event_sourcing: bool,
+
+    /// Request body and list input size limits
+    pub request_limits: RequestLimits,
+
+    /// Telemetry ingest payload sizes by encoding
+    pub ingest_metrics: Arc<IngestMetrics>,
+
+    /// Forwards raised alerts to external channels
+    pub alert_router: Option<Arc<AlertRouter>>,
+
+    /// API key repository
+    pub api_key_repo: Arc<ApiKeyRepository>,
+
+    /// Machine-client API keys
+    pub api_keys: Arc<ApiKeyStore>,
+
+    /// Orders leaderboard updates per drone
+    pub engagement_sequencer: Arc<EngagementSequencer>,
+
+    /// Convoy template definitions replacing the built-in ones
+    pub convoy_templates: Arc<HashMap<TemplateKind, ConvoyTemplate>>,
+
+    /// Platform capabilities, with configured overrides
+    pub platform_capabilities: Arc<CapabilityRegistry>,
+
+    /// Faults injected into HTTP requests, keyed `http.<route>`
+    pub chaos: Arc<Chaos>,
 }
 
 impl ApiContext {
     /// Create a new API context with real dependencies
-    pub fn new(scylla: ScyllaClient, cache: CacheClient) -> Self {
-        let scylla = Arc::new(scylla);
+    pub fn new(store: StoreClient, cache: CacheClient) -> Self {
+        let store = Arc::new(store);
         let cache = Arc::new(cache);
 
         // Create leaderboard repository with cache
-        let leaderboard_repo = Arc::new(ScyllaLeaderboardRepository::new(
-            scylla.clone(),
+        let leaderboard_repo = Arc::new(LeaderboardRepository::new(
+            store.clone(),
             Some(cache.clone()),
         ));
+        let engagement_repo = Arc::new(EngagementRepository::new(store.clone()));
+        let engagement_log_repo = Arc::new(EngagementLogRepository::new(store.clone()));
+        let telemetry_repo = Arc::new(TelemetryRepository::new(store.clone()));
+        let convoy_repo = Arc::new(ConvoyRepository::new(store.clone()));
+        let waypoint_repo = Arc::new(WaypointRepository::new(store.clone()));
+        let alert_repo = Arc::new(AlertRepository::new(store.clone()));
+        let journal_repo = Arc::new(JournalRepository::new(store.clone()));
+        let authorization_repo = Arc::new(AuthorizationRepository::new(store.clone()));
+        let weapons_repo = Arc::new(WeaponsRepository::new(store.clone()));
+        let target_repo = Arc::new(TargetRepository::new(store.clone()));
+        let drone_repo = Arc::new(DroneRepository::new(store.clone()));
+        let api_key_repo = Arc::new(ApiKeyRepository::new(store.clone()));
+        let api_keys = Arc::new(ApiKeyStore::new(api_key_repo.clone(), ApiKeyHasher::ephemeral()));
+        let engagement_sequencer = Arc::new(EngagementSequencer::new(Some(cache.clone())));
+
+        // Expose cache-backed repositories for hot strategy switching
+        let strategies = Arc::new(StrategyRegistry::new());
+        strategies.register("leaderboard", leaderboard_repo.strategy());
 
         // Create broadcast channels
         let (engagement_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
@@ -59,16 +247,404 @@ impl ApiContext {
         let (drone_status_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
         let (alert_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
         let (telemetry_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (authorization_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (sensor_task_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (insight_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
 
         Self {
             leaderboard_repo,
-            scylla,
+            engagement_repo,
+            engagement_log_repo,
+            telemetry_repo,
+            convoy_repo,
+            waypoint_repo,
+            alert_repo,
+            journal_repo,
+            authorization_repo,
+            weapons_repo,
+            target_repo,
+            drone_repo,
+            store,
             cache,
             engagement_tx,
             leaderboard_tx,
             drone_status_tx,
             alert_tx,
             telemetry_tx,
+            authorization_tx,
+            sensor_task_tx,
+            insight_tx,
+            backplane: None,
+            authorization_signer: Arc::new(AuthorizationSigner::ephemeral(
+                std::time::Duration::from_secs(DEFAULT_CODE_TTL_SECS),
+            )),
+            low_munitions_rounds: DEFAULT_LOW_MUNITIONS_ROUNDS,
+            formation_bounds: FormationBounds::default(),
+            separation_minimum: SeparationMinimum::default(),
+            flight_hours_limits: FlightHoursLimits::default(),
+            weather: Arc::new(StaticWeatherProvider::default()),
+            min_visibility_km: DEFAULT_MIN_VISIBILITY_KM,
+            role_tokens: Arc::new(RoleTokens::new()),
+            analytics: None,
+            analytics_limits: ReadonlyLimits::default(),
+            schema_endpoint: false,
+            playground: true,
+            playground_auth: None,
+            introspection: true,
+            frontend_config: FrontendConfig::default(),
+            ws_connections: Arc::new(ConnectionTracker::new(WsLimits::default())),
+            ws_require_auth: true,
+            event_log: Arc::new(EventLog::new(DEFAULT_REPLAY_CAPACITY)),
+            event_history: Arc::new(EventHistory::new()),
+            strategies,
+            tasks: Arc::new(TaskRunner::new()),
+            event_sourcing: false,
+            request_limits: RequestLimits::default(),
+            ingest_metrics: Arc::new(IngestMetrics::new()),
+            alert_router: None,
+            api_key_repo,
+            api_keys,
+            engagement_sequencer,
+            convoy_templates: Arc::new(HashMap::new()),
+            platform_capabilities: Arc::new(CapabilityRegistry::default()),
+            chaos: Arc::new(Chaos::default()),
+        }
+    }
+
+    /// Override the convoy formation spacing bounds
+    #[must_use]
+    pub fn with_formation_bounds(mut self, bounds: FormationBounds) -> Self {
+        self.formation_bounds = bounds;
+        self
+    }
+
+    /// Override the flight path separation minimums
+    #[must_use]
+    pub fn with_separation_minimum(mut self, minimum: SeparationMinimum) -> Self {
+        self.separation_minimum = minimum;
+        self
+    }
+
+    /// Override the flight hours advisory limits
+    #[must_use]
+    pub fn with_flight_hours_limits(mut self, limits: FlightHoursLimits) -> Self {
+        self.flight_hours_limits = limits;
+        self
+    }
+
+    /// Forward raised alerts to external channels; an empty router is ignored
+    #[must_use]
+    pub fn with_alert_router(mut self, router: AlertRouter) -> Self {
+        self.alert_router = (!router.is_empty()).then(|| Arc::new(router));
+        self
+    }
+
+    /// Replace the API key hasher; keys hashed by a different secret no
+    /// longer authenticate
+    #[must_use]
+    pub fn with_api_key_hasher(mut self, hasher: ApiKeyHasher) -> Self {
+        self.api_keys = Arc::new(ApiKeyStore::new(self.api_key_repo.clone(), hasher));
+        self
+    }
+
+    /// Set how long an engagement waits for earlier ones for the same drone
+    /// before updating the leaderboard
+    #[must_use]
+    pub fn with_engagement_reorder_window(mut self, window: std::time::Duration) -> Self {
+        self.engagement_sequencer = Arc::new(
+            EngagementSequencer::new(Some(self.cache.clone())).with_window(window),
+        );
+        self
+    }
+
+    /// Replace built-in convoy templates with configured definitions, keyed
+    /// by their kind
+    #[must_use]
+    pub fn with_convoy_templates(mut self, templates: Vec<ConvoyTemplate>) -> Self {
+        self.convoy_templates = Arc::new(
+            templates
+                .into_iter()
+                .map(|template| (template.kind, template))
+                .collect(),
+        );
+        self
+    }
+
+    /// The configured definition of a template, or the built-in one
+    #[must_use]
+    pub fn convoy_template(&self, kind: TemplateKind) -> ConvoyTemplate {
+        self.convoy_templates
+            .get(&kind)
+            .cloned()
+            .unwrap_or_else(|| ConvoyTemplate::builtin(kind))
+    }
+
+    /// Replace the built-in capabilities of the configured platform types
+    #[must_use]
+    pub fn with_platform_capabilities(mut self, overrides: Vec<PlatformCapabilities>) -> Self {
+        self.platform_capabilities =
+            Arc::new(CapabilityRegistry::default().with_overrides(overrides));
+        self
+    }
+
+    /// Replace the weather provider and mission visibility minimum
+    #[must_use]
+    pub fn with_weather(mut self, provider: SharedWeatherProvider, min_visibility_km: f64) -> Self {
+        self.weather = provider;
+        self.min_visibility_km = min_visibility_km;
+        self
+    }
+
+    /// Set the bearer token to role mapping
+    #[must_use]
+    pub fn with_role_tokens(mut self, tokens: RoleTokens) -> Self {
+        self.role_tokens = Arc::new(tokens);
+        self
+    }
+
+    /// Replace the engagement authorization code signer
+    #[must_use]
+    pub fn with_authorization_signer(mut self, signer: AuthorizationSigner) -> Self {
+        self.authorization_signer = Arc::new(signer);
+        self
+    }
+
+    /// Set the rounds at or below which a low munitions alert is raised
+    #[must_use]
+    pub fn with_low_munitions_rounds(mut self, rounds: i16) -> Self {
+        self.low_munitions_rounds = rounds;
+        self
+    }
+
+    /// Attach the analytics engine used for analyst queries
+    #[must_use]
+    pub fn with_analytics(mut self, engine: AnalyticsEngine, limits: ReadonlyLimits) -> Self {
+        self.analytics = Some(Arc::new(Mutex::new(engine)));
+        self.analytics_limits = limits;
+        self
+    }
+
+    /// Expose the schema SDL at `/schema.graphql`
+    #[must_use]
+    pub fn with_schema_endpoint(mut self, enabled: bool) -> Self {
+        self.schema_endpoint = enabled;
+        self
+    }
+
+    /// Serve the GraphQL Playground, behind basic auth when `credentials`
+    /// are given
+    #[must_use]
+    pub fn with_playground(
+        mut self,
+        enabled: bool,
+        credentials: Option<PlaygroundCredentials>,
+    ) -> Self {
+        self.playground = enabled;
+        self.playground_auth = credentials.map(Arc::new);
+        self
+    }
+
+    /// Allow introspection for every caller; when off only admins may
+    /// introspect
+    #[must_use]
+    pub fn with_introspection(mut self, enabled: bool) -> Self {
+        self.introspection = enabled;
+        self
+    }
+
+    /// Set the endpoints served to the dashboard at `/config.json`
+    #[must_use]
+    pub fn with_frontend_config(mut self, config: FrontendConfig) -> Self {
+        self.frontend_config = config;
+        self
+    }
+
+    /// Set subscription keep-alive and connection limits
+    #[must_use]
+    pub fn with_ws_limits(mut self, limits: WsLimits) -> Self {
+        self.ws_connections = Arc::new(ConnectionTracker::new(limits));
+        self
+    }
+
+    /// Set how many recent events the SSE endpoint keeps for resume
+    #[must_use]
+    pub fn with_sse_replay(mut self, capacity: usize) -> Self {
+        self.event_log = Arc::new(EventLog::new(capacity));
+        self
+    }
+
+    /// Share broadcasts with other replicas over a Redis Pub/Sub channel
+    #[must_use]
+    pub fn with_backplane(mut self, channel: impl Into<String>) -> Self {
+        self.backplane = Some(Arc::new(Backplane::new(channel)));
+        self
+    }
+
+    /// Append engagements, BDA updates and corrections to the event log
+    #[must_use]
+    pub fn with_event_sourcing(mut self, enabled: bool) -> Self {
+        self.event_sourcing = enabled;
+        self
+    }
+
+    /// Inject faults into HTTP requests
+    #[must_use]
+    pub fn with_chaos(mut self, chaos: Chaos) -> Self {
+        self.chaos = Arc::new(chaos);
+        self
+    }
+
+    /// Set request body and list input size limits
+    #[must_use]
+    pub fn with_request_limits(mut self, limits: RequestLimits) -> Self {
+        self.request_limits = limits;
+        self
+    }
+
+    /// Current state of the cache and database circuit breakers
+    #[must_use]
+    pub fn breakers(&self) -> [BreakerSnapshot; 2] {
+        [self.cache.breaker().snapshot(), self.store.breaker().snapshot()]
+    }
+
+    /// Require a valid token in `connection_init` for subscriptions
+    #[must_use]
+    pub fn with_ws_auth(mut self, required: bool) -> Self {
+        self.ws_require_auth = required;
+        self
+    }
+
+    /// Fail with `NotFound` unless the convoy belongs to the caller's
+    /// commanding unit and environment, so other tenants' and other
+    /// environments' convoy IDs are indistinguishable from missing ones
+    pub async fn authorize_convoy(&self, claims: &Claims, convoy_id: Uuid) -> ApiResult<()> {
+        let owner = self.convoy_repo.owner(convoy_id).await?;
+        if claims.can_access_owner(owner.as_ref()) {
+            Ok(())
+        } else {
+            Err(ApiError::NotFound {
+                entity_type: "Convoy".to_string(),
+                id: convoy_id.to_string(),
+            })
+        }
+    }
+
+    /// Fail with `NotFound` unless the drone's convoy belongs to the
+    /// caller's commanding unit and environment
+    pub async fn authorize_drone(&self, claims: &Claims, drone_id: Uuid) -> ApiResult<()> {
+        let owner = self.convoy_repo.owner_for_drone(drone_id).await?;
+        if claims.can_access_owner(owner.as_ref()) {
+            Ok(())
+        } else {
+            Err(ApiError::NotFound {
+                entity_type: "Drone".to_string(),
+                id: drone_id.to_string(),
+            })
+        }
+    }
+
+    /// Fail with `NotFound` unless the convoy passes [`Self::authorize_convoy`]
+    /// and the drone is assigned to it, so a caller cannot act on another
+    /// unit's drone through a convoy of their own
+    pub async fn authorize_convoy_drone(
+        &self,
+        claims: &Claims,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+    ) -> ApiResult<()> {
+        self.authorize_convoy(claims, convoy_id).await?;
+        let assigned = self.convoy_repo.convoy_for_drone(drone_id).await?;
+        ensure_assigned(assigned, convoy_id, drone_id)
+    }
+
+    /// Run a blocking job against the analytics engine off the async runtime
+    pub async fn run_analytics<T, F>(&self, job: F) -> ApiResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&AnalyticsEngine) -> Result<T, AnalyticsError> + Send + 'static,
+    {
+        let analytics = self
+            .analytics
+            .clone()
+            .ok_or_else(|| ApiError::Internal("Analytics engine not configured".to_string()))?;
+
+        tokio::task::spawn_blocking(move || {
+            let engine = analytics
+                .lock()
+                .map_err(|_| ApiError::Internal("Analytics engine unavailable".to_string()))?;
+            Ok(job(&engine)?)
+        })
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?
+    }
+
+    /// Persist an alert, broadcast it to subscribers and forward it to any
+    /// configured external channels.
+    ///
+    /// Persistence failures are logged; the broadcast always goes out.
+    /// External delivery runs in the background and its outcome per channel
+    /// is recorded on the alert.
+    pub async fn raise_alert(&self, event: AlertEvent) {
+        let alert = drone_domain::Alert {
+            convoy_id: Uuid::parse_str(&event.convoy_id).unwrap_or_default(),
+            alert_time: event.timestamp,
+            alert_id: Uuid::parse_str(&event.alert_id).unwrap_or_else(|_| Uuid::new_v4()),
+            severity: event.severity.into(),
+            alert_type: event.alert_type.clone(),
+            source_drone_id: event.drone_id.as_ref().and_then(|id| Uuid::parse_str(id).ok()),
+            message: event.message.clone(),
+            acknowledged: false,
+            acknowledged_by: None,
+            acknowledged_at: None,
+            deliveries: Vec::new(),
+        };
+
+        if let Err(e) = self.alert_repo.record(&alert).await {
+            tracing::warn!(alert_id = %alert.alert_id, error = %e, "Failed to persist alert");
+        }
+
+        if let Some(router) = self.alert_router.clone() {
+            let alert_repo = self.alert_repo.clone();
+            tokio::spawn(async move {
+                for delivery in router.dispatch(&alert).await {
+                    if let Err(e) = alert_repo.record_delivery(&alert, &delivery).await {
+                        tracing::warn!(
+                            alert_id = %alert.alert_id, channel = %delivery.channel, error = %e,
+                            "Failed to record alert delivery"
+                        );
+                    }
+                }
+            });
+        }
+
+        self.publish(event);
+    }
+
+    /// Broadcast an event to this replica's subscribers and, with a
+    /// back-plane configured, to every other replica's
+    ///
+    /// The event is stamped with an ID and kept for `replayEvents`.
+    pub fn publish(&self, event: impl Into<BroadcastEvent>) {
+        let mut event = event.into();
+        event.stamp();
+        if let Some(backplane) = &self.backplane {
+            backplane.forward(event.clone());
+        }
+        self.event_history.record(&event);
+        self.broadcast_local(event);
+    }
+
+    /// Broadcast an event to this replica's subscribers only
+    pub fn broadcast_local(&self, event: BroadcastEvent) {
+        // Sends fail only when nobody is subscribed
+        match event {
+            BroadcastEvent::Engagement(e) => drop(self.engagement_tx.send(e)),
+            BroadcastEvent::Leaderboard(e) => drop(self.leaderboard_tx.send(e)),
+            BroadcastEvent::DroneStatus(e) => drop(self.drone_status_tx.send(e)),
+            BroadcastEvent::Alert(e) => drop(self.alert_tx.send(e)),
+            BroadcastEvent::Telemetry(e) => drop(self.telemetry_tx.send(e)),
+            BroadcastEvent::Authorization(e) => drop(self.authorization_tx.send(e)),
+            BroadcastEvent::SensorTask(e) => drop(self.sensor_task_tx.send(e)),
         }
     }
 
@@ -76,11 +652,11 @@ impl ApiContext {
     #[cfg(test)]
     pub fn mock() -> Self {
         // For testing without real DB connections
-        let (engagement_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
-        let (leaderboard_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
-        let (drone_status_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
-        let (alert_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
-        let (telemetry_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (_engagement_tx, _) = broadcast::channel::<EngagementEvent>(CHANNEL_CAPACITY);
+        let (_leaderboard_tx, _) = broadcast::channel::<LeaderboardUpdateEvent>(CHANNEL_CAPACITY);
+        let (_drone_status_tx, _) = broadcast::channel::<DroneStatusEvent>(CHANNEL_CAPACITY);
+        let (_alert_tx, _) = broadcast::channel::<AlertEvent>(CHANNEL_CAPACITY);
+        let (_telemetry_tx, _) = broadcast::channel::<TelemetrySnapshot>(CHANNEL_CAPACITY);
 
         // Would need mock implementations of repos
         unimplemented!("Mock context not yet implemented")
@@ -89,20 +665,20 @@ impl ApiContext {
 
 /// Builder for ApiContext
 pub struct ApiContextBuilder {
-    scylla: Option<ScyllaClient>,
+    store: Option<StoreClient>,
     cache: Option<CacheClient>,
 }
 
 impl ApiContextBuilder {
     pub fn new() -> Self {
         Self {
-            scylla: None,
+            store: None,
             cache: None,
         }
     }
 
-    pub fn with_scylla(mut self, scylla: ScyllaClient) -> Self {
-        self.scylla = Some(scylla);
+    pub fn with_store(mut self, store: StoreClient) -> Self {
+        self.store = Some(store);
         self
     }
 
@@ -112,9 +688,9 @@ impl ApiContextBuilder {
     }
 
     pub fn build(self) -> Result<ApiContext, &'static str> {
-        let scylla = self.scylla.ok_or("ScyllaDB client required")?;
+        let store = self.store.ok_or("Database client required")?;
         let cache = self.cache.ok_or("Redis cache client required")?;
-        Ok(ApiContext::new(scylla, cache))
+        Ok(ApiContext::new(store, cache))
     }
 }
 
@@ -123,3 +699,33 @@ impl Default for ApiContextBuilder {
         Self::new()
     }
 }
+
+/// Fail with `NotFound` unless a drone's assigned convoy is `convoy_id`
+fn ensure_assigned(assigned: Option<Uuid>, convoy_id: Uuid, drone_id: Uuid) -> ApiResult<()> {
+    if assigned == Some(convoy_id) {
+        Ok(())
+    } else {
+        Err(ApiError::NotFound {
+            entity_type: "Drone".to_string(),
+            id: drone_id.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_foreign_drone_rejected() {
+        let (own, foreign) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let drone_id = Uuid::from_u128(10);
+
+        assert!(ensure_assigned(Some(own), own, drone_id).is_ok());
+        assert!(matches!(
+            ensure_assigned(Some(foreign), own, drone_id),
+            Err(ApiError::NotFound { .. })
+        ));
+        assert!(ensure_assigned(None, own, drone_id).is_err());
+    }
+}
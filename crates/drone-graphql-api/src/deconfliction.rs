@@ -0,0 +1,94 @@
+//! # Flight Path Deconfliction
+//!
+//! Builds a convoy's flight paths from cached telemetry and planned
+//! waypoints, predicts separation conflicts, and alerts on new ones.
+
+use async_graphql::ID;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::ApiResult;
+use crate::schema::{AlertEvent, AlertSeverity, TelemetrySnapshot};
+use drone_domain::{predict_conflicts, Coordinates, FlightPath, PredictedConflict, WaypointStatus};
+
+/// Predict separation conflicts between a convoy's drones
+///
+/// Raises a CRITICAL alert for each pair that was not already in conflict
+/// at the previous scan.
+pub async fn scan_convoy(ctx: &ApiContext, convoy_id: Uuid) -> ApiResult<Vec<PredictedConflict>> {
+    let roster = ctx.cache.get_convoy_roster(convoy_id).await?;
+
+    let mut paths = Vec::with_capacity(roster.len());
+    for drone_id in roster {
+        let latest: Option<TelemetrySnapshot> = ctx.cache.get_latest_telemetry(drone_id).await?;
+        let Some(snapshot) = latest else {
+            continue;
+        };
+
+        let mut position = Coordinates::new(
+            snapshot.position.latitude,
+            snapshot.position.longitude,
+            snapshot.position.altitude_m,
+        );
+        position.heading_deg = snapshot.position.heading_deg;
+        position.speed_mps = snapshot.position.speed_mps;
+
+        // The current waypoint is the one being flown towards
+        let mut route: Vec<_> = ctx
+            .waypoint_repo
+            .get_waypoints(drone_id)
+            .await?
+            .into_iter()
+            .filter(|w| i32::from(w.sequence_number) >= snapshot.current_waypoint)
+            .filter(|w| matches!(w.status, WaypointStatus::Pending | WaypointStatus::Active))
+            .collect();
+        route.sort_by_key(|w| w.sequence_number);
+
+        paths.push(FlightPath {
+            drone_id,
+            position,
+            upcoming_waypoints: route.into_iter().map(|w| w.coordinates).collect(),
+        });
+    }
+
+    let conflicts = predict_conflicts(&paths, ctx.separation_minimum);
+
+    let previous: Vec<(Uuid, Uuid)> = ctx
+        .cache
+        .get_path_conflicts(convoy_id)
+        .await?
+        .unwrap_or_default();
+    let pairs: Vec<(Uuid, Uuid)> = conflicts
+        .iter()
+        .map(|c| (c.drone_a.min(c.drone_b), c.drone_a.max(c.drone_b)))
+        .collect();
+
+    for (conflict, pair) in conflicts.iter().zip(&pairs) {
+        if previous.contains(pair) {
+            continue;
+        }
+        ctx.raise_alert(AlertEvent {
+            alert_id: ID(Uuid::new_v4().to_string()),
+            convoy_id: ID(convoy_id.to_string()),
+            drone_id: Some(ID(conflict.drone_a.to_string())),
+            severity: AlertSeverity::Critical,
+            alert_type: "PATH_CONFLICT".to_string(),
+            message: format!(
+                "Drones {} and {} predicted {:.2} / {:.0} apart in {:.0} s",
+                conflict.drone_a,
+                conflict.drone_b,
+                conflict.horizontal_km,
+                conflict.vertical_m,
+                conflict.time_to_cpa_secs,
+            ),
+            timestamp: Utc::now(),
+            event_id: None,
+        })
+        .await;
+    }
+
+    ctx.cache.set_path_conflicts(convoy_id, &pairs).await?;
+
+    Ok(conflicts)
+}
@@ -5,8 +5,12 @@
 use async_graphql::{Error as GraphQLError, ErrorExtensions};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use drone_domain::{EnvelopeViolation, GeoViolation};
+use drone_persistence::PersistenceError;
 use thiserror::Error;
 
+use crate::validation::FieldViolation;
+
 /// API-level errors
 #[derive(Debug, Error)]
 pub enum ApiError {
@@ -16,15 +20,41 @@ pub enum ApiError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    /// Input fields outside their allowed range, with their paths
+    #[error("Invalid input: {}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    InvalidFields(Vec<FieldViolation>),
+
+    /// Request exceeds a configured size limit
+    #[error("Validation failed: {0}")]
+    Validation(String),
+
+    #[error("Request body exceeds {limit_bytes} bytes")]
+    PayloadTooLarge { limit_bytes: usize },
+
+    #[error("Unsupported {0}")]
+    UnsupportedMediaType(String),
+
+    /// A unique value is already held by another entity
+    #[error("{message}")]
+    Conflict { message: String, existing_id: String },
+
     #[error("Invalid UUID format: {0}")]
     InvalidUuid(#[from] uuid::Error),
 
     #[error("Persistence error: {0}")]
-    Persistence(#[from] drone_persistence::PersistenceError),
+    Persistence(#[from] PersistenceError),
 
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
 
+    /// Engagement target breaks the convoy's AOR or a no-strike zone
+    #[error("Geofence violation: {0}")]
+    Geofence(GeoViolation),
+
+    /// Engagement range or release height is outside the weapon's envelope
+    #[error("Weapon envelope violation: {0}")]
+    Envelope(EnvelopeViolation),
+
     #[error("Rate limited: retry after {retry_after_secs} seconds")]
     RateLimited { retry_after_secs: u64 },
 
@@ -37,9 +67,19 @@ impl ApiError {
     pub fn status_code(&self) -> StatusCode {
         match self {
             Self::NotFound { .. } => StatusCode::NOT_FOUND,
-            Self::InvalidInput(_) | Self::InvalidUuid(_) => StatusCode::BAD_REQUEST,
+            Self::InvalidInput(_)
+            | Self::InvalidFields(_)
+            | Self::Validation(_)
+            | Self::InvalidUuid(_) => StatusCode::BAD_REQUEST,
+            Self::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Conflict { .. } => StatusCode::CONFLICT,
             Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Geofence(_) | Self::Envelope(_) => StatusCode::UNPROCESSABLE_ENTITY,
             Self::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::Persistence(PersistenceError::NotFound { .. }) => StatusCode::NOT_FOUND,
+            Self::Persistence(e) if e.is_retryable() => StatusCode::SERVICE_UNAVAILABLE,
+            Self::Persistence(PersistenceError::CircuitOpen(_)) => StatusCode::SERVICE_UNAVAILABLE,
             Self::Persistence(_) | Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -48,10 +88,20 @@ impl ApiError {
     pub fn error_code(&self) -> &'static str {
         match self {
             Self::NotFound { .. } => "NOT_FOUND",
-            Self::InvalidInput(_) => "INVALID_INPUT",
+            Self::InvalidInput(_) | Self::InvalidFields(_) => "INVALID_INPUT",
+            Self::Validation(_) => "VALIDATION",
+            Self::PayloadTooLarge { .. } => "PAYLOAD_TOO_LARGE",
+            Self::UnsupportedMediaType(_) => "UNSUPPORTED_MEDIA_TYPE",
+            Self::Conflict { .. } => "CONFLICT",
             Self::InvalidUuid(_) => "INVALID_UUID",
             Self::Unauthorized(_) => "UNAUTHORIZED",
+            Self::Geofence(GeoViolation::OutsideAor { .. }) => "OUTSIDE_AOR",
+            Self::Geofence(GeoViolation::InNoStrikeZone { .. }) => "NO_STRIKE_ZONE",
+            Self::Envelope(_) => "OUTSIDE_WEAPON_ENVELOPE",
             Self::RateLimited { .. } => "RATE_LIMITED",
+            Self::Persistence(PersistenceError::NotFound { .. }) => "NOT_FOUND",
+            Self::Persistence(e) if e.is_retryable() => "SERVICE_UNAVAILABLE",
+            Self::Persistence(PersistenceError::CircuitOpen(_)) => "SERVICE_UNAVAILABLE",
             Self::Persistence(_) => "PERSISTENCE_ERROR",
             Self::Internal(_) => "INTERNAL_ERROR",
         }
@@ -69,9 +119,35 @@ impl ErrorExtensions for ApiError {
                     e.set("entity_type", entity_type.as_str());
                     e.set("entity_id", id.as_str());
                 }
+                Self::InvalidFields(violations) => {
+                    e.set("fields", async_graphql::to_value(violations).unwrap_or_default());
+                }
+                Self::Conflict { existing_id, .. } => {
+                    e.set("existing_id", existing_id.as_str());
+                }
+                Self::PayloadTooLarge { limit_bytes } => {
+                    e.set("limit_bytes", *limit_bytes);
+                }
                 Self::RateLimited { retry_after_secs } => {
                     e.set("retry_after_secs", *retry_after_secs);
                 }
+                Self::Geofence(GeoViolation::OutsideAor {
+                    distance_km,
+                    radius_km,
+                }) => {
+                    e.set("distance_km", distance_km.value());
+                    e.set("radius_km", radius_km.value());
+                }
+                Self::Geofence(GeoViolation::InNoStrikeZone { zone_id, name, .. }) => {
+                    e.set("zone_id", zone_id.to_string());
+                    e.set("zone_name", name.as_str());
+                }
+                Self::Envelope(violation) => {
+                    e.set("weapon_type", violation.weapon_type().as_str());
+                }
+                Self::Persistence(err) => {
+                    e.set("retryable", err.is_retryable());
+                }
                 _ => {}
             }
         })
@@ -85,16 +161,28 @@ impl ErrorExtensions for ApiError {
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let status = self.status_code();
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "error": {
                 "message": self.to_string(),
                 "code": self.error_code(),
             }
         });
+        if let Self::InvalidFields(violations) = &self {
+            body["error"]["fields"] = serde_json::json!(violations);
+        }
 
         (status, axum::Json(body)).into_response()
     }
 }
 
+impl From<drone_analytics::AnalyticsError> for ApiError {
+    fn from(e: drone_analytics::AnalyticsError) -> Self {
+        match e {
+            drone_analytics::AnalyticsError::InvalidParameter(msg) => Self::InvalidInput(msg),
+            other => Self::Internal(other.to_string()),
+        }
+    }
+}
+
 /// Result type alias for API operations
 pub type ApiResult<T> = Result<T, ApiError>;
@@ -0,0 +1,89 @@
+//! # Convoy Formation
+//!
+//! Builds a convoy's formation geometry from cached telemetry and alerts
+//! when drone pairs leave the configured spacing bounds.
+
+use async_graphql::ID;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::ApiResult;
+use crate::schema::{AlertEvent, AlertSeverity, TelemetrySnapshot};
+use drone_domain::{Coordinates, Formation};
+
+/// Compute a convoy's formation from the latest telemetry of its drones
+///
+/// Returns `None` when no drone in the roster has reported a position.
+pub async fn convoy_formation(ctx: &ApiContext, convoy_id: Uuid) -> ApiResult<Option<Formation>> {
+    let roster = ctx.cache.get_convoy_roster(convoy_id).await?;
+
+    let mut positions = Vec::with_capacity(roster.len());
+    for drone_id in roster {
+        let latest: Option<TelemetrySnapshot> = ctx.cache.get_latest_telemetry(drone_id).await?;
+        let Some(snapshot) = latest else {
+            continue;
+        };
+
+        let mut position = Coordinates::new(
+            snapshot.position.latitude,
+            snapshot.position.longitude,
+            snapshot.position.altitude_m,
+        );
+        position.heading_deg = snapshot.position.heading_deg;
+        position.speed_mps = snapshot.position.speed_mps;
+        positions.push((drone_id, position));
+    }
+
+    Ok(Formation::compute(&positions, ctx.formation_bounds))
+}
+
+/// Check a convoy's formation spacing
+///
+/// Raises a WARNING alert when a drone pair that was within bounds at the
+/// previous scan is now outside them.
+pub async fn scan_convoy(ctx: &ApiContext, convoy_id: Uuid) -> ApiResult<()> {
+    let violations = convoy_formation(ctx, convoy_id)
+        .await?
+        .map(|f| f.violations)
+        .unwrap_or_default();
+
+    let previous: Vec<(Uuid, Uuid)> = ctx
+        .cache
+        .get_spacing_violations(convoy_id)
+        .await?
+        .unwrap_or_default();
+    let pairs: Vec<(Uuid, Uuid)> = violations
+        .iter()
+        .map(|v| (v.drone_a.min(v.drone_b), v.drone_a.max(v.drone_b)))
+        .collect();
+
+    let bounds = ctx.formation_bounds;
+    for (violation, pair) in violations.iter().zip(&pairs) {
+        if previous.contains(pair) {
+            continue;
+        }
+        ctx.raise_alert(AlertEvent {
+            alert_id: ID(Uuid::new_v4().to_string()),
+            convoy_id: ID(convoy_id.to_string()),
+            drone_id: Some(ID(violation.drone_a.to_string())),
+            severity: AlertSeverity::Warning,
+            alert_type: "FORMATION_SPACING".to_string(),
+            message: format!(
+                "Drones {} and {} are {:.1} apart, outside spacing bounds {:.1}-{:.1}",
+                violation.drone_a,
+                violation.drone_b,
+                violation.distance_km,
+                bounds.min_spacing_km,
+                bounds.max_spacing_km,
+            ),
+            timestamp: Utc::now(),
+            event_id: None,
+        })
+        .await;
+    }
+
+    ctx.cache.set_spacing_violations(convoy_id, &pairs).await?;
+
+    Ok(())
+}
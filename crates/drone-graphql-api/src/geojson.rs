@@ -0,0 +1,316 @@
+//! # GeoJSON
+//!
+//! RFC 7946 FeatureCollections of drone routes, convoy areas of
+//! responsibility and flown tracks, for mapping tools such as Leaflet.
+//!
+//! Positions are `[longitude, latitude, altitude_m]`. Each collection holds
+//! one line or polygon feature plus point markers, and every feature carries
+//! a `kind` property so clients can style them without inspecting geometry.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::{ApiError, ApiResult};
+use crate::resolvers::query::check_track_range;
+use drone_domain::geofence::signed_area;
+use drone_domain::{Convoy, Coordinates, GeoPoint, NoStrikeZone, TrackPoint, Waypoint};
+
+/// Media type for GeoJSON responses
+pub const GEOJSON_CONTENT_TYPE: &str = "application/geo+json";
+
+/// Vertices in the polygon approximating an AOR circle
+pub const AOR_SEGMENTS: usize = 64;
+
+/// Default point budget for track collections
+pub const DEFAULT_TRACK_POINTS: usize = 500;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// A GeoJSON FeatureCollection
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "FeatureCollection")]
+pub struct FeatureCollection {
+    pub features: Vec<Feature>,
+}
+
+/// A GeoJSON Feature
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename = "Feature")]
+pub struct Feature {
+    pub geometry: Geometry,
+    pub properties: Map<String, Value>,
+}
+
+/// The geometry types the exports use
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "coordinates")]
+pub enum Geometry {
+    Point(Vec<f64>),
+    LineString(Vec<Vec<f64>>),
+    Polygon(Vec<Vec<Vec<f64>>>),
+}
+
+impl Feature {
+    fn new(geometry: Geometry, properties: Value) -> Self {
+        let Value::Object(properties) = properties else {
+            unreachable!("feature properties are built as objects");
+        };
+        Self {
+            geometry,
+            properties,
+        }
+    }
+}
+
+fn position(c: &Coordinates) -> Vec<f64> {
+    vec![c.longitude, c.latitude, c.altitude_m]
+}
+
+/// A drone's planned route: the path as a LineString and a Point per
+/// waypoint, in sequence order
+#[must_use]
+pub fn route_collection(drone_id: Uuid, waypoints: &[Waypoint]) -> FeatureCollection {
+    let mut waypoints: Vec<&Waypoint> = waypoints.iter().collect();
+    waypoints.sort_by_key(|w| w.sequence_number);
+
+    let mut features = Vec::with_capacity(waypoints.len() + 1);
+    if waypoints.len() >= 2 {
+        features.push(Feature::new(
+            Geometry::LineString(waypoints.iter().map(|w| position(&w.coordinates)).collect()),
+            json!({
+                "kind": "route",
+                "droneId": drone_id,
+                "waypointCount": waypoints.len(),
+            }),
+        ));
+    }
+    features.extend(waypoints.iter().map(|w| {
+        Feature::new(
+            Geometry::Point(position(&w.coordinates)),
+            json!({
+                "kind": "waypoint",
+                "droneId": drone_id,
+                "waypointId": w.waypoint_id,
+                "sequence": w.sequence_number,
+                "name": w.waypoint_name,
+                "waypointType": w.waypoint_type,
+                "status": w.status,
+                "plannedArrival": w.planned_arrival,
+                "loiterDurationMin": w.loiter_duration_min,
+            }),
+        )
+    }));
+    FeatureCollection { features }
+}
+
+/// A convoy's AOR: the circle as a Polygon and its centre as a Point, plus
+/// a Polygon per no-strike zone
+#[must_use]
+pub fn aor_collection(convoy: &Convoy, zones: &[NoStrikeZone]) -> FeatureCollection {
+    let radius_km = f64::from(convoy.aor_radius_km);
+    let mut collection = FeatureCollection {
+        features: vec![
+            Feature::new(
+                Geometry::Polygon(vec![circle_ring(&convoy.aor_center, radius_km, AOR_SEGMENTS)]),
+                json!({
+                    "kind": "aor",
+                    "convoyId": convoy.convoy_id,
+                    "callsign": convoy.convoy_callsign,
+                    "aorName": convoy.aor_name,
+                    "radiusKm": radius_km,
+                }),
+            ),
+            Feature::new(
+                Geometry::Point(vec![convoy.aor_center.longitude, convoy.aor_center.latitude]),
+                json!({
+                    "kind": "aorCenter",
+                    "convoyId": convoy.convoy_id,
+                    "aorName": convoy.aor_name,
+                }),
+            ),
+        ],
+    };
+    collection.features.extend(zones.iter().map(|zone| {
+        Feature::new(
+            Geometry::Polygon(vec![zone_ring(&zone.vertices)]),
+            json!({
+                "kind": "noStrikeZone",
+                "convoyId": convoy.convoy_id,
+                "zoneId": zone.zone_id,
+                "name": zone.name,
+                "enforcement": zone.enforcement,
+            }),
+        )
+    }));
+    collection
+}
+
+/// A flown track: the path as a LineString and a timestamped Point per
+/// position, oldest first
+#[must_use]
+pub fn track_collection(drone_id: Uuid, points: &[TrackPoint]) -> FeatureCollection {
+    let mut features = Vec::with_capacity(points.len() + 1);
+    if let [first, .., last] = points {
+        features.push(Feature::new(
+            Geometry::LineString(points.iter().map(|p| position(&p.position)).collect()),
+            json!({
+                "kind": "track",
+                "droneId": drone_id,
+                "start": first.recorded_at,
+                "end": last.recorded_at,
+                "pointCount": points.len(),
+            }),
+        ));
+    }
+    features.extend(points.iter().map(|p| {
+        Feature::new(
+            Geometry::Point(position(&p.position)),
+            json!({
+                "kind": "trackPoint",
+                "droneId": drone_id,
+                "recordedAt": p.recorded_at,
+                "altitudeM": p.position.altitude_m,
+                "headingDeg": p.position.heading_deg,
+                "speedMps": p.position.speed_mps,
+            }),
+        )
+    }));
+    FeatureCollection { features }
+}
+
+/// Closed `[lon, lat]` ring approximating a circle, wound counterclockwise
+/// as RFC 7946 requires for exterior rings
+pub(crate) fn circle_ring(center: &Coordinates, radius_km: f64, segments: usize) -> Vec<Vec<f64>> {
+    let lat = center.latitude.to_radians();
+    let lon = center.longitude.to_radians();
+    let angular = radius_km / EARTH_RADIUS_KM;
+
+    let mut ring: Vec<Vec<f64>> = (0..segments)
+        .map(|i| {
+            // Decreasing bearings trace north, west, south, east
+            let bearing = -std::f64::consts::TAU * i as f64 / segments as f64;
+            let lat2 = (lat.sin() * angular.cos() + lat.cos() * angular.sin() * bearing.cos()).asin();
+            let lon2 = lon
+                + (bearing.sin() * angular.sin() * lat.cos())
+                    .atan2(angular.cos() - lat.sin() * lat2.sin());
+            vec![lon2.to_degrees(), lat2.to_degrees()]
+        })
+        .collect();
+    if let Some(first) = ring.first().cloned() {
+        ring.push(first);
+    }
+    ring
+}
+
+/// Closed `[lon, lat]` ring through a zone's vertices, wound
+/// counterclockwise whichever way they were drawn
+fn zone_ring(vertices: &[GeoPoint]) -> Vec<Vec<f64>> {
+    let mut ring: Vec<Vec<f64>> = vertices.iter().map(|v| vec![v.longitude, v.latitude]).collect();
+    if signed_area(vertices) < 0.0 {
+        ring.reverse();
+    }
+    if let Some(first) = ring.first().cloned() {
+        ring.push(first);
+    }
+    ring
+}
+
+/// Route collection for a drone
+pub async fn route(ctx: &ApiContext, drone_id: Uuid) -> ApiResult<FeatureCollection> {
+    let waypoints = ctx.waypoint_repo.get_waypoints(drone_id).await?;
+    Ok(route_collection(drone_id, &waypoints))
+}
+
+/// AOR collection for a convoy
+pub async fn aor(ctx: &ApiContext, convoy_id: Uuid) -> ApiResult<FeatureCollection> {
+    let convoy = ctx
+        .convoy_repo
+        .get(convoy_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            entity_type: "Convoy".to_string(),
+            id: convoy_id.to_string(),
+        })?;
+    let zones = ctx.convoy_repo.list_no_strike_zones(convoy_id).await?;
+    Ok(aor_collection(&convoy, &zones))
+}
+
+/// Track collection for a drone over a window, simplified to `max_points`
+pub async fn track(
+    ctx: &ApiContext,
+    drone_id: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    max_points: usize,
+) -> ApiResult<FeatureCollection> {
+    check_track_range(start, end)?;
+    let raw = ctx.telemetry_repo.get_track(drone_id, start, end).await?;
+    let points = drone_domain::simplify_track(&raw, max_points);
+    Ok(track_collection(drone_id, &points))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aor_is_closed_counterclockwise_ring() {
+        let center = Coordinates::new(31.6, 65.7, 0.0);
+        let ring = circle_ring(&center, 25.0, AOR_SEGMENTS);
+
+        assert_eq!(ring.len(), AOR_SEGMENTS + 1);
+        assert_eq!(ring.first(), ring.last());
+        for vertex in &ring {
+            let distance = center.distance_to_km(&Coordinates::new(vertex[1], vertex[0], 0.0));
+            assert!((distance.value() - 25.0).abs() < 0.01);
+        }
+
+        // Shoelace sum is positive for a counterclockwise ring
+        let area: f64 = ring
+            .windows(2)
+            .map(|w| w[0][0] * w[1][1] - w[1][0] * w[0][1])
+            .sum();
+        assert!(area > 0.0);
+    }
+
+    #[test]
+    fn test_collections_serialize_as_geojson() {
+        let drone_id = Uuid::new_v4();
+        let points: Vec<TrackPoint> = (0..3)
+            .map(|i| TrackPoint {
+                recorded_at: Utc::now(),
+                position: Coordinates::new(31.0 + f64::from(i) * 0.01, 65.0, 1000.0),
+            })
+            .collect();
+
+        let value = serde_json::to_value(track_collection(drone_id, &points)).unwrap();
+        assert_eq!(value["type"], "FeatureCollection");
+        assert_eq!(value["features"].as_array().unwrap().len(), 4);
+        assert_eq!(value["features"][0]["type"], "Feature");
+        assert_eq!(value["features"][0]["geometry"]["type"], "LineString");
+        assert_eq!(value["features"][0]["geometry"]["coordinates"][0], json!([65.0, 31.0, 1000.0]));
+        assert_eq!(value["features"][1]["geometry"]["type"], "Point");
+        assert_eq!(value["features"][1]["properties"]["kind"], "trackPoint");
+
+        assert!(route_collection(drone_id, &[]).features.is_empty());
+    }
+
+    #[test]
+    fn test_zone_ring_is_closed_counterclockwise() {
+        // Drawn clockwise
+        let vertices = [(31.0, 65.0), (31.1, 65.0), (31.1, 65.1), (31.0, 65.1)]
+            .map(|(latitude, longitude)| GeoPoint { latitude, longitude });
+        let ring = zone_ring(&vertices);
+
+        assert_eq!(ring.len(), vertices.len() + 1);
+        assert_eq!(ring.first(), ring.last());
+        let area: f64 = ring
+            .windows(2)
+            .map(|w| w[0][0] * w[1][1] - w[1][0] * w[0][1])
+            .sum();
+        assert!(area > 0.0);
+    }
+}
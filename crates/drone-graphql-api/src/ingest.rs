@@ -0,0 +1,418 @@
+//! # Telemetry Ingest
+//!
+//! Bulk telemetry endpoint for aircraft and gateways that post frames
+//! directly rather than through the `recordTelemetry` mutation.
+//!
+//! Frames are the `CreateTelemetryInput` fields as a JSON array or as a
+//! CBOR array (`Content-Type: application/cbor`), optionally compressed
+//! with `Content-Encoding: gzip` or `deflate`. The reply follows `Accept`.
+//! Wire sizes are counted per encoding next to the size the same frames
+//! would have as plain JSON, so operators can see what each encoding saves.
+//!
+//! Gateways may be ahead of the server: top-level frame fields the server
+//! does not know are folded into the frame's `extensions` rather than
+//! dropped, so they are stored and served back as sent.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::sync::Mutex;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::header::{ACCEPT, CONTENT_ENCODING, CONTENT_TYPE};
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Json, Response};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ApiError, ApiResult};
+use crate::schema::CreateTelemetryInput;
+use crate::api_keys::Principal;
+use crate::{resolvers, validation, AppState};
+
+/// CBOR media type
+pub const CBOR_CONTENT_TYPE: &str = "application/cbor";
+
+/// Serialization of the frame array
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngestFormat {
+    Json,
+    Cbor,
+}
+
+impl IngestFormat {
+    /// Format named by a `Content-Type` header; JSON when absent
+    pub fn from_content_type(value: Option<&str>) -> ApiResult<Self> {
+        let Some(value) = value else {
+            return Ok(Self::Json);
+        };
+        match media_type(value).as_str() {
+            "application/json" => Ok(Self::Json),
+            CBOR_CONTENT_TYPE => Ok(Self::Cbor),
+            other => Err(ApiError::UnsupportedMediaType(format!(
+                "content type `{other}`; send application/json or {CBOR_CONTENT_TYPE}"
+            ))),
+        }
+    }
+
+    /// Preferred reply format from an `Accept` header; JSON unless CBOR is listed
+    #[must_use]
+    pub fn from_accept(value: Option<&str>) -> Self {
+        let wants_cbor = value.is_some_and(|accept| {
+            accept.split(',').any(|range| media_type(range) == CBOR_CONTENT_TYPE)
+        });
+        if wants_cbor { Self::Cbor } else { Self::Json }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Cbor => "cbor",
+        }
+    }
+}
+
+/// Compression applied to the request body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentCoding {
+    Identity,
+    Gzip,
+    Deflate,
+}
+
+impl ContentCoding {
+    /// Coding named by a `Content-Encoding` header; identity when absent
+    pub fn from_header(value: Option<&str>) -> ApiResult<Self> {
+        match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("" | "identity") => Ok(Self::Identity),
+            Some("gzip" | "x-gzip") => Ok(Self::Gzip),
+            Some("deflate") => Ok(Self::Deflate),
+            Some(other) => Err(ApiError::UnsupportedMediaType(format!(
+                "content encoding `{other}`; send gzip, deflate or identity"
+            ))),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Identity => "identity",
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+        }
+    }
+}
+
+/// `type/subtype` of a media type, lowercased and without parameters
+fn media_type(value: &str) -> String {
+    value
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// One ingest frame: the known fields plus any the server does not model
+#[derive(Debug, Deserialize)]
+struct TelemetryFrame {
+    #[serde(flatten)]
+    input: CreateTelemetryInput,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, serde_json::Value>,
+}
+
+impl TelemetryFrame {
+    /// Fold unknown fields into `extensions`; explicit extensions win
+    fn into_input(self) -> CreateTelemetryInput {
+        let Self { mut input, unknown } = self;
+        if !unknown.is_empty() {
+            let extensions = input.extensions.get_or_insert_with(Default::default);
+            for (key, value) in unknown {
+                extensions.0.entry(key).or_insert(value);
+            }
+        }
+        input
+    }
+}
+
+/// Decompress and decode a request body into telemetry frames.
+///
+/// Decompressed bodies larger than `max_decoded_bytes` are rejected rather
+/// than inflated in full. Unknown frame fields become extensions.
+pub fn decode_frames(
+    format: IngestFormat,
+    coding: ContentCoding,
+    body: &[u8],
+    max_decoded_bytes: usize,
+) -> ApiResult<Vec<CreateTelemetryInput>> {
+    let inflated;
+    let raw: &[u8] = match coding {
+        ContentCoding::Identity => body,
+        ContentCoding::Gzip => {
+            inflated = inflate(flate2::read::GzDecoder::new(body), max_decoded_bytes)?;
+            &inflated
+        }
+        ContentCoding::Deflate => {
+            inflated = inflate(flate2::read::ZlibDecoder::new(body), max_decoded_bytes)?;
+            &inflated
+        }
+    };
+
+    let frames: Vec<TelemetryFrame> = match format {
+        IngestFormat::Json => serde_json::from_slice(raw)
+            .map_err(|e| ApiError::InvalidInput(format!("malformed JSON telemetry: {e}")))?,
+        IngestFormat::Cbor => ciborium::from_reader(raw)
+            .map_err(|e| ApiError::InvalidInput(format!("malformed CBOR telemetry: {e}")))?,
+    };
+    Ok(frames.into_iter().map(TelemetryFrame::into_input).collect())
+}
+
+fn inflate(decoder: impl Read, max_decoded_bytes: usize) -> ApiResult<Vec<u8>> {
+    let mut out = Vec::new();
+    let limit = u64::try_from(max_decoded_bytes).unwrap_or(u64::MAX);
+    decoder
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut out)
+        .map_err(|e| ApiError::InvalidInput(format!("malformed compressed body: {e}")))?;
+    if out.len() > max_decoded_bytes {
+        return Err(ApiError::PayloadTooLarge {
+            limit_bytes: max_decoded_bytes,
+        });
+    }
+    Ok(out)
+}
+
+/// Payload size totals for one encoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IngestCounters {
+    pub requests: u64,
+    pub frames: u64,
+    /// Bytes as received
+    pub wire_bytes: u64,
+    /// Bytes the same frames take as uncompressed JSON
+    pub json_bytes: u64,
+}
+
+/// Ingest payload sizes by encoding, e.g. `cbor+gzip`
+#[derive(Debug, Default)]
+pub struct IngestMetrics {
+    by_encoding: Mutex<BTreeMap<String, IngestCounters>>,
+}
+
+impl IngestMetrics {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one accepted request
+    pub fn record(
+        &self,
+        format: IngestFormat,
+        coding: ContentCoding,
+        frames: usize,
+        wire_bytes: usize,
+        json_bytes: usize,
+    ) {
+        let label = match coding {
+            ContentCoding::Identity => format.as_str().to_string(),
+            _ => format!("{}+{}", format.as_str(), coding.as_str()),
+        };
+        let mut by_encoding = self
+            .by_encoding
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let counters = by_encoding.entry(label).or_default();
+        counters.requests += 1;
+        counters.frames += frames as u64;
+        counters.wire_bytes += wire_bytes as u64;
+        counters.json_bytes += json_bytes as u64;
+    }
+
+    /// Totals per encoding label
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<(String, IngestCounters)> {
+        self.by_encoding
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .map(|(label, counters)| (label.clone(), *counters))
+            .collect()
+    }
+}
+
+/// Reply to an ingest request
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngestResult {
+    /// Frames recorded
+    pub accepted: usize,
+}
+
+/// Telemetry ingest endpoint
+///
+/// Applies the same access checks and batch limit as `recordTelemetryBatch`.
+/// Accepts `INGEST` scoped API keys as well as bearer tokens.
+/// Frames are recorded in order; the first failure aborts the rest.
+pub async fn ingest_telemetry(
+    State(state): State<AppState>,
+    principal: Principal,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    let format = IngestFormat::from_content_type(header(CONTENT_TYPE))?;
+    let coding = ContentCoding::from_header(header(CONTENT_ENCODING))?;
+    let reply_format = IngestFormat::from_accept(header(ACCEPT));
+    let claims = principal.ingest_claims();
+
+    let limits = state.ctx.request_limits;
+    let frames = decode_frames(format, coding, &body, limits.max_body_bytes)?;
+    limits.check_batch("telemetry", frames.len())?;
+    validation::check_each("telemetry", &frames)?;
+    let json_bytes = serde_json::to_vec(&frames).map_or(0, |v| v.len());
+
+    let accepted = frames.len();
+    for frame in frames {
+        resolvers::mutation::record_telemetry_point(&state.ctx, &claims, frame).await?;
+    }
+    state
+        .ctx
+        .ingest_metrics
+        .record(format, coding, accepted, body.len(), json_bytes);
+
+    let result = IngestResult { accepted };
+    Ok(match reply_format {
+        IngestFormat::Json => Json(result).into_response(),
+        IngestFormat::Cbor => {
+            let mut buf = Vec::new();
+            ciborium::into_writer(&result, &mut buf)
+                .map_err(|e| ApiError::Internal(format!("CBOR encoding failed: {e}")))?;
+            ([(CONTENT_TYPE, CBOR_CONTENT_TYPE)], buf).into_response()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn frames() -> serde_json::Value {
+        serde_json::json!([
+            {
+                "droneId": "00000000-0000-0000-0000-000000000010",
+                "position": { "latitude": 31.61, "longitude": 65.71, "altitudeM": 4500.0 },
+                "fuelPct": 82.5,
+                "platformType": "MQ_9_REAPER",
+                "currentWaypoint": 3
+            },
+            {
+                "droneId": "00000000-0000-0000-0000-000000000020",
+                "position": { "latitude": 31.62, "longitude": 65.72 },
+                "fuelPct": 64.0,
+                "currentWaypoint": 1,
+                "meshConnectivity": 0.5
+            }
+        ])
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_json_and_cbor_decode_to_the_same_frames() {
+        let json = serde_json::to_vec(&frames()).unwrap();
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&frames(), &mut cbor).unwrap();
+
+        let from_json =
+            decode_frames(IngestFormat::Json, ContentCoding::Identity, &json, 1 << 20).unwrap();
+        let from_cbor =
+            decode_frames(IngestFormat::Cbor, ContentCoding::Gzip, &gzip(&cbor), 1 << 20).unwrap();
+
+        assert_eq!(from_json.len(), 2);
+        assert_eq!(from_cbor.len(), 2);
+        assert_eq!(from_cbor[0].drone_id, from_json[0].drone_id);
+        assert_eq!(from_cbor[0].platform_type, Some(crate::schema::PlatformType::Mq9Reaper));
+        assert!((from_cbor[1].mesh_connectivity - 0.5).abs() < f64::EPSILON);
+        // GraphQL defaults apply to omitted fields
+        assert!((from_json[0].mesh_connectivity - 1.0).abs() < f64::EPSILON);
+        assert!(from_json[1].position.altitude_m.abs() < f64::EPSILON);
+        assert!(cbor.len() < json.len());
+    }
+
+    #[test]
+    fn test_unknown_fields_become_extensions() {
+        let body = serde_json::to_vec(&serde_json::json!([{
+            "droneId": "00000000-0000-0000-0000-000000000010",
+            "position": { "latitude": 31.61, "longitude": 65.71 },
+            "fuelPct": 82.5,
+            "currentWaypoint": 3,
+            "schemaVersion": 3,
+            "icingRisk": 0.4,
+            "extensions": { "satcomBeam": 7, "icingRisk": 0.9 }
+        }]))
+        .unwrap();
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&frames(), &mut cbor).unwrap();
+
+        let frames = decode_frames(IngestFormat::Json, ContentCoding::Identity, &body, 1 << 20).unwrap();
+        assert_eq!(frames[0].schema_version, Some(3));
+        let extensions = &frames[0].extensions.as_ref().unwrap().0;
+        assert_eq!(extensions.len(), 2);
+        assert_eq!(extensions["satcomBeam"], 7);
+        // An explicit extension is not overwritten by a top-level field
+        assert_eq!(extensions["icingRisk"], 0.9);
+
+        let known_only =
+            decode_frames(IngestFormat::Cbor, ContentCoding::Identity, &cbor, 1 << 20).unwrap();
+        assert!(known_only.iter().all(|f| f.extensions.is_none()));
+    }
+
+    #[test]
+    fn test_oversized_inflation_is_rejected() {
+        let body = gzip(&vec![b' '; 4096]);
+        let err = decode_frames(IngestFormat::Json, ContentCoding::Gzip, &body, 1024).unwrap_err();
+        assert_eq!(err.error_code(), "PAYLOAD_TOO_LARGE");
+    }
+
+    #[test]
+    fn test_negotiation() {
+        assert_eq!(
+            IngestFormat::from_content_type(Some("application/cbor")).unwrap(),
+            IngestFormat::Cbor
+        );
+        assert_eq!(
+            IngestFormat::from_content_type(Some("application/json; charset=utf-8")).unwrap(),
+            IngestFormat::Json
+        );
+        assert!(IngestFormat::from_content_type(Some("application/x-protobuf")).is_err());
+        assert_eq!(ContentCoding::from_header(Some("GZIP")).unwrap(), ContentCoding::Gzip);
+        assert!(ContentCoding::from_header(Some("br")).is_err());
+        assert_eq!(
+            IngestFormat::from_accept(Some("text/html, application/cbor;q=0.9")),
+            IngestFormat::Cbor
+        );
+        assert_eq!(IngestFormat::from_accept(None), IngestFormat::Json);
+    }
+
+    #[test]
+    fn test_metrics_group_by_encoding() {
+        let metrics = IngestMetrics::new();
+        metrics.record(IngestFormat::Cbor, ContentCoding::Gzip, 10, 300, 1500);
+        metrics.record(IngestFormat::Cbor, ContentCoding::Gzip, 5, 200, 750);
+        metrics.record(IngestFormat::Json, ContentCoding::Identity, 1, 150, 150);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].0, "cbor+gzip");
+        assert_eq!(
+            snapshot[0].1,
+            IngestCounters { requests: 2, frames: 15, wire_bytes: 500, json_bytes: 2250 }
+        );
+        assert_eq!(snapshot[1].0, "json");
+    }
+}
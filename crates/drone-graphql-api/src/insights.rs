@@ -0,0 +1,137 @@
+//! # Analytics Insights
+//!
+//! Periodically evaluates active convoys with the analytics engine and
+//! publishes what it finds to `insights` subscribers, so passive dashboards
+//! surface accuracy slumps without anyone running reports. An insight is
+//! sent when first detected and again only after it has cleared, so
+//! subscribers are not told about the same slump on every evaluation.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, PoisonError};
+
+use chrono::Utc;
+use drone_analytics::insights::{Insight, InsightKind, InsightThresholds};
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::ApiResult;
+use crate::schema::InsightEvent;
+use crate::stats::ACTIVE_WINDOW;
+
+/// Identity of an insight across evaluations
+type InsightKey = (InsightKind, String);
+
+fn key(insight: &Insight) -> InsightKey {
+    (insight.kind, insight.subject.clone())
+}
+
+/// Evaluates convoys and remembers which insights are already raised
+pub struct InsightMonitor {
+    thresholds: InsightThresholds,
+    raised: Mutex<HashMap<Uuid, HashSet<InsightKey>>>,
+}
+
+impl InsightMonitor {
+    pub fn new(thresholds: InsightThresholds) -> Self {
+        Self {
+            thresholds,
+            raised: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Evaluate every convoy that reported telemetry recently and publish
+    /// newly raised insights
+    pub async fn evaluate_active_convoys(&self, ctx: &ApiContext) -> ApiResult<()> {
+        let since = Utc::now().timestamp_millis() - ACTIVE_WINDOW.as_millis() as i64;
+        let active = ctx.cache.get_active_convoys(since).await?;
+
+        for &convoy_id in &active {
+            let thresholds = self.thresholds.clone();
+            let insights = match ctx
+                .run_analytics(move |engine| {
+                    engine.convoy_insights(convoy_id, Utc::now(), &thresholds)
+                })
+                .await
+            {
+                Ok(insights) => insights,
+                Err(e) => {
+                    tracing::warn!(
+                        convoy_id = %convoy_id,
+                        error = %e,
+                        "Failed to evaluate insights"
+                    );
+                    continue;
+                }
+            };
+
+            for insight in self.newly_raised(convoy_id, insights) {
+                tracing::info!(
+                    convoy_id = %convoy_id,
+                    kind = insight.kind.as_str(),
+                    subject = %insight.subject,
+                    "Analytics insight raised"
+                );
+                let _ = ctx.insight_tx.send(InsightEvent::from(insight));
+            }
+        }
+
+        // Convoys that went quiet start afresh when they resume
+        self.lock().retain(|convoy_id, _| active.contains(convoy_id));
+        Ok(())
+    }
+
+    /// Record the convoy's current insights, returning those not raised on
+    /// the previous evaluation
+    fn newly_raised(&self, convoy_id: Uuid, insights: Vec<Insight>) -> Vec<Insight> {
+        let current: HashSet<InsightKey> = insights.iter().map(key).collect();
+        let previous = self.lock().insert(convoy_id, current).unwrap_or_default();
+        insights
+            .into_iter()
+            .filter(|insight| !previous.contains(&key(insight)))
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, HashSet<InsightKey>>> {
+        self.raised.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insight(convoy_id: Uuid, kind: InsightKind, subject: &str) -> Insight {
+        Insight {
+            convoy_id,
+            kind,
+            subject: subject.to_string(),
+            drone_id: None,
+            recent_accuracy_pct: 40.0,
+            baseline_accuracy_pct: 80.0,
+            recent_engagements: 10,
+            baseline_engagements: 10,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_insights_are_raised_once_until_cleared() {
+        let monitor = InsightMonitor::new(InsightThresholds::default());
+        let convoy = Uuid::new_v4();
+        let slump = || insight(convoy, InsightKind::DroneTrendingDown, "REAPER-01");
+        let weapon = || insight(convoy, InsightKind::WeaponUnderperforming, "AGM114_HELLFIRE");
+
+        assert_eq!(monitor.newly_raised(convoy, vec![slump()]).len(), 1);
+
+        let raised = monitor.newly_raised(convoy, vec![slump(), weapon()]);
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].kind, InsightKind::WeaponUnderperforming);
+
+        assert!(monitor.newly_raised(convoy, vec![weapon()]).is_empty());
+        assert_eq!(monitor.newly_raised(convoy, vec![slump(), weapon()]).len(), 1);
+
+        let other = Uuid::new_v4();
+        let anomaly = insight(other, InsightKind::AccuracyAnomaly, "convoy");
+        assert_eq!(monitor.newly_raised(other, vec![anomaly]).len(), 1);
+    }
+}
@@ -0,0 +1,87 @@
+//! # Introspection Access
+//!
+//! With `ENABLE_INTROSPECTION` off, the [`IntrospectionAccess`] extension
+//! leaves `__schema` and `__type` to ADMIN callers; for everyone else they
+//! are unknown fields. The schema is also built without "did you mean"
+//! suggestions in validation errors, so it cannot be recovered field by field
+//! from mistyped queries either.
+
+use std::any::TypeId;
+use std::sync::Arc;
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest,
+};
+use async_graphql::{Request, ServerResult};
+
+use crate::auth::Role;
+
+/// Schema extension disabling introspection below the ADMIN role
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IntrospectionAccess;
+
+impl ExtensionFactory for IntrospectionAccess {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(IntrospectionAccessExtension)
+    }
+}
+
+struct IntrospectionAccessExtension;
+
+#[async_trait::async_trait]
+impl Extension for IntrospectionAccessExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        // HTTP requests carry the role; subscription connections keep it in
+        // their session data
+        let role = request_role(&request)
+            .or_else(|| ctx.data_opt::<Role>().copied())
+            .unwrap_or_default();
+        let request = if role == Role::Admin {
+            request
+        } else {
+            request.disable_introspection()
+        };
+        next.run(ctx, request).await
+    }
+}
+
+/// Role attached to the request itself, which extensions cannot read
+/// through the context until the request is prepared
+fn request_role(request: &Request) -> Option<Role> {
+    request.data.get(&TypeId::of::<Role>())?.downcast_ref().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn ping(&self) -> bool {
+            true
+        }
+    }
+
+    async fn introspect(role: Role) -> bool {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(IntrospectionAccess)
+            .finish();
+        let request = Request::new("{ __schema { queryType { name } } }").data(role);
+        schema.execute(request).await.errors.is_empty()
+    }
+
+    #[tokio::test]
+    async fn test_only_admins_introspect() {
+        assert!(introspect(Role::Admin).await);
+        assert!(!introspect(Role::Commander).await);
+        assert!(!introspect(Role::Viewer).await);
+    }
+}
@@ -0,0 +1,324 @@
+//! # KML Export
+//!
+//! Renders a convoy's mission for Google Earth and other GIS tools: the AOR
+//! as a polygon, each drone's planned route extruded to the ground at its
+//! flight altitude, engagement placemarks styled by hit or miss, and the
+//! operators' journal. Routes carry a `TimeSpan` and engagements and journal
+//! entries a `TimeStamp`, so the time slider plays the mission back.
+//!
+//! KMZ packs the same document as `doc.kml` in a deflated zip archive.
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::{ApiError, ApiResult};
+use crate::geojson::{circle_ring, AOR_SEGMENTS};
+use drone_domain::{Convoy, Coordinates, Engagement, JournalEntry, Waypoint};
+
+/// Media type for KML documents
+pub const KML_CONTENT_TYPE: &str = "application/vnd.google-earth.kml+xml";
+
+/// Media type for KMZ archives
+pub const KMZ_CONTENT_TYPE: &str = "application/vnd.google-earth.kmz";
+
+/// Most recent engagements placed in an export
+const MAX_EXPORT_ENGAGEMENTS: usize = 5000;
+
+/// Most recent journal entries placed in an export
+const MAX_EXPORT_JOURNAL_ENTRIES: usize = 1000;
+
+/// A drone's planned route for export
+#[derive(Debug, Clone)]
+pub struct DroneRoute {
+    pub drone_id: Uuid,
+    pub callsign: String,
+    pub waypoints: Vec<Waypoint>,
+}
+
+/// Escape text for XML content and attributes
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn timestamp(t: DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn coordinate(c: &Coordinates) -> String {
+    format!("{},{},{}", c.longitude, c.latitude, c.altitude_m)
+}
+
+/// Render a convoy's AOR, routes, engagements and journal as a KML document
+#[must_use]
+pub fn render(
+    convoy: &Convoy,
+    routes: &[DroneRoute],
+    engagements: &[Engagement],
+    journal: &[JournalEntry],
+) -> String {
+    let mut kml = String::new();
+    let _ = writeln!(kml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(kml, r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#);
+    let _ = writeln!(kml, "<Document>");
+    let _ = writeln!(kml, "<name>{}</name>", escape(&convoy.convoy_callsign));
+    let _ = writeln!(
+        kml,
+        "<description>{}, {}</description>",
+        escape(&convoy.commanding_unit),
+        escape(&convoy.aor_name)
+    );
+
+    // Colours are aabbggrr
+    write_style(&mut kml, "aor", "<LineStyle><color>ff00ffff</color><width>2</width></LineStyle><PolyStyle><color>3300ffff</color></PolyStyle>");
+    write_style(&mut kml, "route", "<LineStyle><color>ffffaa00</color><width>3</width></LineStyle><PolyStyle><color>40ffaa00</color></PolyStyle>");
+    write_style(&mut kml, "hit", "<IconStyle><color>ff00ff00</color></IconStyle>");
+    write_style(&mut kml, "miss", "<IconStyle><color>ff0000ff</color></IconStyle>");
+    write_style(&mut kml, "journal", "<IconStyle><color>ffffffff</color></IconStyle>");
+
+    write_aor(&mut kml, convoy);
+
+    let _ = writeln!(kml, "<Folder><name>Routes</name>");
+    for route in routes {
+        write_route(&mut kml, route);
+    }
+    let _ = writeln!(kml, "</Folder>");
+
+    let _ = writeln!(kml, "<Folder><name>Engagements</name>");
+    for engagement in engagements {
+        write_engagement(&mut kml, engagement);
+    }
+    let _ = writeln!(kml, "</Folder>");
+
+    let _ = writeln!(kml, "<Folder><name>Journal</name>");
+    for entry in journal {
+        write_journal_entry(&mut kml, entry, engagements);
+    }
+    let _ = writeln!(kml, "</Folder>");
+
+    let _ = writeln!(kml, "</Document>");
+    let _ = writeln!(kml, "</kml>");
+    kml
+}
+
+fn write_style(kml: &mut String, id: &str, body: &str) {
+    let _ = writeln!(kml, r#"<Style id="{id}">{body}</Style>"#);
+}
+
+fn write_aor(kml: &mut String, convoy: &Convoy) {
+    let ring: Vec<String> = circle_ring(&convoy.aor_center, f64::from(convoy.aor_radius_km), AOR_SEGMENTS)
+        .iter()
+        .map(|p| format!("{},{},0", p[0], p[1]))
+        .collect();
+    let _ = writeln!(kml, "<Placemark>");
+    let _ = writeln!(kml, "<name>{} AOR</name>", escape(&convoy.aor_name));
+    let _ = writeln!(kml, "<styleUrl>#aor</styleUrl>");
+    let _ = writeln!(
+        kml,
+        "<Polygon><tessellate>1</tessellate><outerBoundaryIs><LinearRing><coordinates>{}</coordinates></LinearRing></outerBoundaryIs></Polygon>",
+        ring.join(" ")
+    );
+    let _ = writeln!(kml, "</Placemark>");
+}
+
+fn write_route(kml: &mut String, route: &DroneRoute) {
+    let mut waypoints: Vec<&Waypoint> = route.waypoints.iter().collect();
+    waypoints.sort_by_key(|w| w.sequence_number);
+    if waypoints.len() < 2 {
+        return;
+    }
+
+    let times: Vec<DateTime<Utc>> = waypoints
+        .iter()
+        .filter_map(|w| w.actual_arrival.or(w.planned_arrival))
+        .collect();
+    let coordinates: Vec<String> = waypoints.iter().map(|w| coordinate(&w.coordinates)).collect();
+
+    let _ = writeln!(kml, "<Placemark>");
+    let _ = writeln!(kml, "<name>{}</name>", escape(&route.callsign));
+    let _ = writeln!(kml, "<description>Drone {}, {} waypoints</description>", route.drone_id, waypoints.len());
+    if let (Some(begin), Some(end)) = (times.iter().min(), times.iter().max()) {
+        let _ = writeln!(
+            kml,
+            "<TimeSpan><begin>{}</begin><end>{}</end></TimeSpan>",
+            timestamp(*begin),
+            timestamp(*end)
+        );
+    }
+    let _ = writeln!(kml, "<styleUrl>#route</styleUrl>");
+    let _ = writeln!(
+        kml,
+        "<LineString><extrude>1</extrude><altitudeMode>absolute</altitudeMode><coordinates>{}</coordinates></LineString>",
+        coordinates.join(" ")
+    );
+    let _ = writeln!(kml, "</Placemark>");
+}
+
+fn write_engagement(kml: &mut String, engagement: &Engagement) {
+    let outcome = if engagement.hit { "hit" } else { "miss" };
+    let _ = writeln!(kml, "<Placemark>");
+    let _ = writeln!(
+        kml,
+        "<name>{} {}</name>",
+        escape(&engagement.drone_callsign),
+        outcome.to_uppercase()
+    );
+    let _ = writeln!(
+        kml,
+        "<description>{} at {:.1} km, waypoint {}</description>",
+        engagement.weapon_type.as_str(),
+        engagement.range_to_target_km,
+        engagement.waypoint_number
+    );
+    let _ = writeln!(kml, "<TimeStamp><when>{}</when></TimeStamp>", timestamp(engagement.engaged_at));
+    let _ = writeln!(kml, "<styleUrl>#{outcome}</styleUrl>");
+    let _ = writeln!(
+        kml,
+        "<Point><coordinates>{}</coordinates></Point>",
+        coordinate(&engagement.result.impact_coords)
+    );
+    let _ = writeln!(kml, "</Placemark>");
+}
+
+/// Journal entries linked to an exported engagement sit at its impact
+/// point; others have no geometry and appear only in the document tree.
+fn write_journal_entry(kml: &mut String, entry: &JournalEntry, engagements: &[Engagement]) {
+    let linked = entry
+        .linked_entity_id
+        .and_then(|id| engagements.iter().find(|e| e.engagement_id == id));
+    let _ = writeln!(kml, "<Placemark>");
+    let _ = writeln!(kml, "<name>{}</name>", escape(&entry.author));
+    let _ = writeln!(kml, "<description>{}</description>", escape(&entry.text));
+    let _ = writeln!(kml, "<TimeStamp><when>{}</when></TimeStamp>", timestamp(entry.entry_time));
+    let _ = writeln!(kml, "<styleUrl>#journal</styleUrl>");
+    if let Some(engagement) = linked {
+        let _ = writeln!(
+            kml,
+            "<Point><coordinates>{}</coordinates></Point>",
+            coordinate(&engagement.result.impact_coords)
+        );
+    }
+    let _ = writeln!(kml, "</Placemark>");
+}
+
+/// Pack a KML document as a KMZ archive
+pub fn to_kmz(kml: &str) -> ApiResult<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file("doc.kml", options)
+        .and_then(|()| zip.write_all(kml.as_bytes()).map_err(Into::into))
+        .and_then(|()| zip.finish())
+        .map(std::io::Cursor::into_inner)
+        .map_err(|e| ApiError::Internal(format!("failed to write KMZ: {e}")))
+}
+
+/// Load a convoy's mission and render it as KML
+pub async fn export_convoy(ctx: &ApiContext, convoy_id: Uuid) -> ApiResult<String> {
+    let convoy = ctx
+        .convoy_repo
+        .get(convoy_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            entity_type: "Convoy".to_string(),
+            id: convoy_id.to_string(),
+        })?;
+
+    let drone_ids = ctx.cache.get_convoy_roster(convoy_id).await?;
+    let mut routes = Vec::with_capacity(drone_ids.len());
+    for drone_id in drone_ids {
+        let callsign = ctx
+            .drone_repo
+            .get(convoy_id, drone_id)
+            .await?
+            .map_or_else(|| drone_id.to_string(), |drone| drone.callsign);
+        routes.push(DroneRoute {
+            drone_id,
+            callsign,
+            waypoints: ctx.waypoint_repo.get_waypoints(drone_id).await?,
+        });
+    }
+
+    let engagements = ctx
+        .engagement_repo
+        .get_recent(convoy_id, MAX_EXPORT_ENGAGEMENTS)
+        .await?;
+    let mut journal = ctx
+        .journal_repo
+        .get_recent(convoy_id, MAX_EXPORT_JOURNAL_ENTRIES)
+        .await?;
+    journal.reverse();
+
+    Ok(render(&convoy, &routes, &engagements, &journal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_routes_with_extrusion_and_time_span() {
+        let plan = drone_domain::ConvoyTemplate::builtin(drone_domain::TemplateKind::Isr2Ship)
+            .provision("VMU-1");
+        let start = Utc::now();
+        let routes: Vec<DroneRoute> = plan
+            .drones
+            .iter()
+            .map(|d| {
+                // Templates leave arrivals unplanned; schedule one per minute
+                let mut waypoints = d.waypoints.clone();
+                for (i, w) in waypoints.iter_mut().enumerate() {
+                    w.planned_arrival = Some(start + chrono::Duration::minutes(i as i64));
+                }
+                DroneRoute {
+                    drone_id: d.drone.drone_id,
+                    callsign: d.drone.callsign.clone(),
+                    waypoints,
+                }
+            })
+            .collect();
+
+        let journal = [JournalEntry {
+            convoy_id: plan.convoy.convoy_id,
+            entry_time: start,
+            entry_id: Uuid::new_v4(),
+            author: "OPS".to_string(),
+            text: "Weather hold <14:02>".to_string(),
+            linked_entity_id: None,
+        }];
+
+        let kml = render(&plan.convoy, &routes, &[], &journal);
+        assert!(kml.starts_with("<?xml"));
+        assert_eq!(kml.matches("<Placemark>").count(), 4);
+        assert!(kml.contains("<description>Weather hold &lt;14:02&gt;</description>"));
+        assert_eq!(kml.matches("<extrude>1</extrude>").count(), 2);
+        assert_eq!(kml.matches("<TimeSpan>").count(), 2);
+        assert!(kml.contains("<name>HAWK-01</name>"));
+    }
+
+    #[test]
+    fn test_escape_and_kmz_archive() {
+        assert_eq!(escape(r#"A&B <"x">"#), "A&amp;B &lt;&quot;x&quot;&gt;");
+
+        let kml = "<kml/>";
+        let kmz = to_kmz(kml).unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(kmz)).unwrap();
+        let mut doc = String::new();
+        std::io::Read::read_to_string(&mut archive.by_name("doc.kml").unwrap(), &mut doc).unwrap();
+        assert_eq!(doc, kml);
+    }
+}
@@ -6,7 +6,8 @@
 //!
 //! - **Leaderboard Queries**: Real-time accuracy rankings for drone convoy
 //! - **Engagement Tracking**: Record and query weapon engagement history
-//! - **Subscriptions**: Real-time updates via WebSocket
+//! - **Engagement Authorization**: Commander approval before weapons release
+//! - **Subscriptions**: Real-time updates via WebSocket, with an SSE fallback
 //! - **DataLoader**: N+1 query prevention for efficient data fetching
 //!
 //! ## Architecture
@@ -40,22 +41,60 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod alerting;
+pub mod api_keys;
+pub mod artifacts;
+pub mod auth;
+pub mod authorization;
+pub mod backplane;
+pub mod chaos;
 pub mod config;
 pub mod context;
+pub mod deconfliction;
 pub mod error;
+pub mod formation;
+pub mod geojson;
+pub mod ingest;
+pub mod insights;
+pub mod introspection;
+pub mod kml;
+pub mod limits;
+pub mod live;
 pub mod loaders;
+pub mod masking;
+pub mod merge;
+pub mod pagination;
+pub mod playground;
+pub mod projections;
+pub mod rankings;
+pub mod replay;
+pub mod request_id;
 pub mod resolvers;
 pub mod schema;
+pub mod search;
+pub mod sequencing;
+pub mod snapshot;
+pub mod sse;
+pub mod stats;
+pub mod store;
+pub mod tasks;
+pub mod validation;
+pub mod weather;
+pub mod ws;
 
-use async_graphql::Schema;
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use async_graphql::{BatchRequest, Schema};
+use async_graphql_axum::{GraphQLBatchRequest, GraphQLResponse};
 use axum::{
-    extract::State,
-    http::Method,
-    response::{Html, IntoResponse},
-    routing::get,
-    Router,
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{header, Method, StatusCode},
+    middleware,
+    response::{IntoResponse, Json},
+    routing::{get, post},
+    Extension, Router,
 };
+use api_keys::Principal;
+use drone_persistence::metrics::Histogram;
+use drone_persistence::BreakerState;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
@@ -67,67 +106,575 @@ pub use resolvers::{MutationRoot, QueryRoot, SubscriptionRoot};
 pub type ApiSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
 
 /// Build the GraphQL schema with context
+///
+/// Without `ctx.introspection`, only admins may introspect and validation
+/// errors carry no field suggestions.
 pub fn build_schema(ctx: ApiContext) -> ApiSchema {
-    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+    let introspection = ctx.introspection;
+    let mut builder = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
         .data(ctx)
+        .extension(masking::FieldMasking)
+        .extension(request_id::ErrorRequestId)
         .enable_subscription_in_federation()
         .limit_depth(10)
-        .limit_complexity(1000)
+        .limit_complexity(1000);
+    if !introspection {
+        builder = builder
+            .extension(introspection::IntrospectionAccess)
+            .disable_suggestions();
+    }
+    builder.finish()
+}
+
+/// Render the schema as SDL.
+///
+/// Does not need a live context, so it can run from `--print-schema`
+/// without connecting to ScyllaDB or Redis.
+pub fn schema_sdl() -> String {
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .enable_subscription_in_federation()
         .finish()
+        .sdl()
 }
 
 /// Application state for Axum handlers
 #[derive(Clone)]
 pub struct AppState {
     pub schema: ApiSchema,
+    pub ctx: ApiContext,
 }
 
 /// GraphQL endpoint handler
+///
+/// Accepts a single operation or a JSON array of operations; arrays are
+/// executed in order and answered with an array of responses.
 pub async fn graphql_handler(
     State(state): State<AppState>,
-    req: GraphQLRequest,
-) -> GraphQLResponse {
-    state.schema.execute(req.into_inner()).await.into()
-}
-
-/// GraphQL Playground HTML
-pub async fn graphql_playground() -> impl IntoResponse {
-    Html(
-        async_graphql::http::playground_source(
-            async_graphql::http::GraphQLPlaygroundConfig::new("/graphql")
-                .subscription_endpoint("/graphql/ws"),
-        ),
+    principal: Principal,
+    Extension(request_id): Extension<request_id::RequestId>,
+    req: GraphQLBatchRequest,
+) -> Result<GraphQLResponse, error::ApiError> {
+    let claims = principal.claims()?;
+    let batch = req.into_inner();
+    if let BatchRequest::Batch(requests) = &batch {
+        state.ctx.request_limits.check_operations(requests.len())?;
+    }
+    let batch = batch.data(claims.role).data(claims).data(request_id);
+    Ok(state.schema.execute_batch(batch).await.into())
+}
+
+/// Convoy snapshot export endpoint
+pub async fn export_convoy_snapshot(
+    State(state): State<AppState>,
+    principal: Principal,
+    Path(convoy_id): Path<String>,
+) -> Result<Json<snapshot::ConvoySnapshot>, error::ApiError> {
+    let convoy_id = uuid::Uuid::parse_str(&convoy_id)?;
+    let claims = principal.claims()?;
+    state.ctx.authorize_convoy(&claims, convoy_id).await?;
+    let snapshot = snapshot::export_convoy(&state.ctx, convoy_id).await?;
+    Ok(Json(snapshot))
+}
+
+/// Query string for the KML export endpoint
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct KmlQuery {
+    /// `kml` (default) or `kmz`
+    pub format: Option<String>,
+}
+
+/// Convoy mission KML/KMZ export endpoint
+pub async fn export_convoy_kml(
+    State(state): State<AppState>,
+    principal: Principal,
+    Path(convoy_id): Path<String>,
+    Query(params): Query<KmlQuery>,
+) -> Result<impl IntoResponse, error::ApiError> {
+    let convoy_id = uuid::Uuid::parse_str(&convoy_id)?;
+    state.ctx.authorize_convoy(&principal.claims()?, convoy_id).await?;
+
+    let kmz = match params.format.as_deref() {
+        None | Some("kml") => false,
+        Some("kmz") => true,
+        Some(other) => {
+            return Err(error::ApiError::InvalidInput(format!(
+                "unsupported export format `{other}`; expected kml or kmz"
+            )));
+        }
+    };
+
+    let document = kml::export_convoy(&state.ctx, convoy_id).await?;
+    let (content_type, extension, body) = if kmz {
+        (kml::KMZ_CONTENT_TYPE, "kmz", kml::to_kmz(&document)?)
+    } else {
+        (kml::KML_CONTENT_TYPE, "kml", document.into_bytes())
+    };
+    let disposition = format!("attachment; filename=\"convoy-{convoy_id}.{extension}\"");
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    ))
+}
+
+/// Query string for the leaderboard CSV export endpoint
+pub async fn download_mission_artifact(
+    State(state): State<AppState>,
+    principal: Principal,
+    Path((convoy_id, digest)): Path<(String, String)>,
+) -> Result<impl IntoResponse, error::ApiError> {
+    let convoy_id = uuid::Uuid::parse_str(&convoy_id)?;
+    state.ctx.authorize_convoy(&principal.claims()?, convoy_id).await?;
+
+    let (artifact, content) = artifacts::download(&state.ctx, convoy_id, &digest)
+        .await?
+        .ok_or_else(|| error::ApiError::NotFound {
+            entity_type: "MissionArtifact".to_string(),
+            id: digest,
+        })?;
+    let disposition = format!("attachment; filename=\"{}\"", artifact.file_name);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, artifact.content_type),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        content,
+    ))
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardExportQuery {
+    pub convoy_id: String,
+}
+
+/// Most leaderboard rows exported
+const MAX_EXPORTED_LEADERBOARD_ENTRIES: i32 = 1000;
+
+/// Convoy leaderboard CSV export endpoint
+///
+/// Reads the live leaderboard, so it works without the analytics store.
+pub async fn export_leaderboard_csv(
+    State(state): State<AppState>,
+    principal: Principal,
+    Query(params): Query<LeaderboardExportQuery>,
+) -> Result<impl IntoResponse, error::ApiError> {
+    let convoy_id = uuid::Uuid::parse_str(&params.convoy_id)?;
+    state.ctx.authorize_convoy(&principal.claims()?, convoy_id).await?;
+
+    let entries = state
+        .ctx
+        .leaderboard_repo
+        .get_leaderboard(convoy_id, MAX_EXPORTED_LEADERBOARD_ENTRIES)
+        .await?;
+    let rows: Vec<_> = entries
+        .iter()
+        .map(drone_analytics::reports::LeaderboardRow::from)
+        .collect();
+    let disposition = format!("attachment; filename=\"leaderboard-{convoy_id}.csv\"");
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        drone_analytics::reports::leaderboard_csv(&rows),
+    ))
+}
+
+/// GeoJSON response with the `application/geo+json` media type
+fn geojson_response(collection: geojson::FeatureCollection) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, geojson::GEOJSON_CONTENT_TYPE)],
+        Json(collection),
+    )
+}
+
+/// Planned route GeoJSON endpoint
+pub async fn geojson_route(
+    State(state): State<AppState>,
+    principal: Principal,
+    Path(drone_id): Path<String>,
+) -> Result<impl IntoResponse, error::ApiError> {
+    let drone_id = uuid::Uuid::parse_str(&drone_id)?;
+    state.ctx.authorize_drone(&principal.claims()?, drone_id).await?;
+    let collection = geojson::route(&state.ctx, drone_id).await?;
+    Ok(geojson_response(collection))
+}
+
+/// AOR GeoJSON endpoint
+pub async fn geojson_aor(
+    State(state): State<AppState>,
+    principal: Principal,
+    Path(convoy_id): Path<String>,
+) -> Result<impl IntoResponse, error::ApiError> {
+    let convoy_id = uuid::Uuid::parse_str(&convoy_id)?;
+    state.ctx.authorize_convoy(&principal.claims()?, convoy_id).await?;
+    let collection = geojson::aor(&state.ctx, convoy_id).await?;
+    Ok(geojson_response(collection))
+}
+
+/// Query string for the track GeoJSON endpoint
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackQuery {
+    /// Window start (RFC 3339)
+    pub start: chrono::DateTime<chrono::Utc>,
+    /// Window end (RFC 3339)
+    pub end: chrono::DateTime<chrono::Utc>,
+    /// Point budget after simplification
+    pub max_points: Option<usize>,
+}
+
+/// Flown track GeoJSON endpoint
+pub async fn geojson_track(
+    State(state): State<AppState>,
+    principal: Principal,
+    Path(drone_id): Path<String>,
+    Query(params): Query<TrackQuery>,
+) -> Result<impl IntoResponse, error::ApiError> {
+    let drone_id = uuid::Uuid::parse_str(&drone_id)?;
+    state.ctx.authorize_drone(&principal.claims()?, drone_id).await?;
+    let max_points = params
+        .max_points
+        .unwrap_or(geojson::DEFAULT_TRACK_POINTS)
+        .clamp(2, 5000);
+    let collection =
+        geojson::track(&state.ctx, drone_id, params.start, params.end, max_points).await?;
+    Ok(geojson_response(collection))
+}
+
+/// Query string for the Arrow analytics endpoint
+#[derive(Debug, serde::Deserialize)]
+pub struct ArrowQuery {
+    /// Single read-only SELECT statement
+    pub query: String,
+}
+
+/// Analyst SQL endpoint returning an Arrow IPC stream
+///
+/// Applies the same read-only guardrails and limits as the `analyticsSql`
+/// field. Requires the ANALYST role and a token not scoped to a commanding
+/// unit.
+pub async fn analytics_arrow(
+    State(state): State<AppState>,
+    principal: Principal,
+    Query(params): Query<ArrowQuery>,
+) -> Result<impl IntoResponse, error::ApiError> {
+    let claims = principal.claims()?;
+    claims.role.require(auth::Role::Analyst)?;
+    claims.require_all_units()?;
+
+    tracing::info!(sql = %params.query, "Executing analyst SQL (arrow)");
+
+    let limits = state.ctx.analytics_limits;
+    let bytes = state
+        .ctx
+        .run_analytics(move |engine| engine.execute_readonly_arrow(&params.query, &[], &limits))
+        .await?;
+
+    Ok(([(header::CONTENT_TYPE, "application/vnd.apache.arrow.stream")], bytes))
+}
+
+/// Schema SDL endpoint for client codegen
+pub async fn schema_graphql(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "application/graphql; charset=utf-8")],
+        state.schema.sdl(),
+    )
+}
+
+/// Dashboard runtime configuration
+///
+/// Lets one frontend bundle find the API wherever it is deployed; endpoints
+/// not configured are left to the dashboard's defaults.
+pub async fn frontend_config(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CACHE_CONTROL, "no-cache")],
+        Json(state.ctx.frontend_config.clone()),
     )
 }
 
 /// Health check endpoint
-pub async fn health_check() -> impl IntoResponse {
-    "OK"
+///
+/// Reports circuit breaker state. An open database breaker fails the check
+/// with 503; an open Redis breaker only marks the service degraded since
+/// reads fall back to the database.
+pub async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
+    let breakers = state.ctx.breakers();
+    let db_open = breakers
+        .iter()
+        .any(|b| b.name == store::BREAKER_NAME && b.state == BreakerState::Open);
+    let degraded = breakers.iter().any(|b| b.state != BreakerState::Closed);
+
+    let status = if db_open {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    let breaker_states: serde_json::Map<_, _> = breakers
+        .iter()
+        .map(|b| (b.name.to_string(), b.state.as_str().into()))
+        .collect();
+
+    (
+        status,
+        Json(serde_json::json!({
+            "status": if db_open { "unavailable" } else if degraded { "degraded" } else { "ok" },
+            "breakers": breaker_states,
+        })),
+    )
+}
+
+/// Prometheus text-format metrics
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    use std::fmt::Write;
+
+    let tracker = &state.ctx.ws_connections;
+    let mut body = String::new();
+    let _ = writeln!(body, "# HELP drone_api_ws_connections Open subscription connections");
+    let _ = writeln!(body, "# TYPE drone_api_ws_connections gauge");
+    let _ = writeln!(body, "drone_api_ws_connections {}", tracker.active());
+    let _ = writeln!(body, "# HELP drone_api_ws_connections_max Subscription connection limit");
+    let _ = writeln!(body, "# TYPE drone_api_ws_connections_max gauge");
+    let _ = writeln!(body, "drone_api_ws_connections_max {}", tracker.limits().max_connections);
+
+    let breakers = state.ctx.breakers();
+    let _ = writeln!(body, "# HELP drone_persistence_breaker_state Circuit breaker state (0 closed, 1 half-open, 2 open)");
+    let _ = writeln!(body, "# TYPE drone_persistence_breaker_state gauge");
+    for b in &breakers {
+        let _ = writeln!(body, "drone_persistence_breaker_state{{breaker=\"{}\"}} {}", b.name, b.state.as_gauge());
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_breaker_failure_rate Failure rate over the breaker window");
+    let _ = writeln!(body, "# TYPE drone_persistence_breaker_failure_rate gauge");
+    for b in &breakers {
+        let _ = writeln!(body, "drone_persistence_breaker_failure_rate{{breaker=\"{}\"}} {}", b.name, b.failure_rate);
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_breaker_rejected_total Calls rejected while open");
+    let _ = writeln!(body, "# TYPE drone_persistence_breaker_rejected_total counter");
+    for b in &breakers {
+        let _ = writeln!(body, "drone_persistence_breaker_rejected_total{{breaker=\"{}\"}} {}", b.name, b.rejected_total);
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_breaker_opened_total Times the breaker has opened");
+    let _ = writeln!(body, "# TYPE drone_persistence_breaker_opened_total counter");
+    for b in &breakers {
+        let _ = writeln!(body, "drone_persistence_breaker_opened_total{{breaker=\"{}\"}} {}", b.name, b.opened_total);
+    }
+
+    let repo = state.ctx.store.metrics().snapshot();
+    let _ = writeln!(body, "# HELP drone_persistence_method_calls_total Repository method calls");
+    let _ = writeln!(body, "# TYPE drone_persistence_method_calls_total counter");
+    for (method, m) in &repo.methods {
+        let _ = writeln!(body, "drone_persistence_method_calls_total{{method=\"{method}\"}} {}", m.calls);
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_method_duration_seconds Repository method latency");
+    let _ = writeln!(body, "# TYPE drone_persistence_method_duration_seconds histogram");
+    for (method, m) in &repo.methods {
+        let name = "drone_persistence_method_duration_seconds";
+        write_histogram(&mut body, name, "method", method, &m.latency);
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_cache_lookups_total Cache lookups by repository methods");
+    let _ = writeln!(body, "# TYPE drone_persistence_cache_lookups_total counter");
+    for (method, m) in repo.methods.iter().filter(|(_, m)| m.cache_hits + m.cache_misses > 0) {
+        let _ = writeln!(body, "drone_persistence_cache_lookups_total{{method=\"{method}\",result=\"hit\"}} {}", m.cache_hits);
+        let _ = writeln!(body, "drone_persistence_cache_lookups_total{{method=\"{method}\",result=\"miss\"}} {}", m.cache_misses);
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_queries_total Statements executed");
+    let _ = writeln!(body, "# TYPE drone_persistence_queries_total counter");
+    for (op, q) in &repo.statements {
+        let _ = writeln!(body, "drone_persistence_queries_total{{op=\"{op}\"}} {}", q.calls);
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_query_errors_total Statements that failed after retries");
+    let _ = writeln!(body, "# TYPE drone_persistence_query_errors_total counter");
+    for (op, q) in &repo.statements {
+        let _ = writeln!(body, "drone_persistence_query_errors_total{{op=\"{op}\"}} {}", q.errors);
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_query_rows_total Rows returned by statements");
+    let _ = writeln!(body, "# TYPE drone_persistence_query_rows_total counter");
+    for (op, q) in &repo.statements {
+        let _ = writeln!(body, "drone_persistence_query_rows_total{{op=\"{op}\"}} {}", q.rows);
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_slow_queries_total Statements over the slow-query threshold");
+    let _ = writeln!(body, "# TYPE drone_persistence_slow_queries_total counter");
+    for (op, q) in &repo.statements {
+        let _ = writeln!(body, "drone_persistence_slow_queries_total{{op=\"{op}\"}} {}", q.slow);
+    }
+    let _ = writeln!(body, "# HELP drone_persistence_query_duration_seconds Statement latency, retries included");
+    let _ = writeln!(body, "# TYPE drone_persistence_query_duration_seconds histogram");
+    for (op, q) in &repo.statements {
+        let name = "drone_persistence_query_duration_seconds";
+        write_histogram(&mut body, name, "op", op, &q.latency);
+    }
+
+    let ingest = state.ctx.ingest_metrics.snapshot();
+    let _ = writeln!(body, "# HELP drone_api_ingest_requests_total Accepted telemetry ingest requests");
+    let _ = writeln!(body, "# TYPE drone_api_ingest_requests_total counter");
+    for (encoding, c) in &ingest {
+        let _ = writeln!(body, "drone_api_ingest_requests_total{{encoding=\"{encoding}\"}} {}", c.requests);
+    }
+    let _ = writeln!(body, "# HELP drone_api_ingest_frames_total Telemetry frames ingested");
+    let _ = writeln!(body, "# TYPE drone_api_ingest_frames_total counter");
+    for (encoding, c) in &ingest {
+        let _ = writeln!(body, "drone_api_ingest_frames_total{{encoding=\"{encoding}\"}} {}", c.frames);
+    }
+    let _ = writeln!(body, "# HELP drone_api_ingest_wire_bytes_total Ingest request bytes as received");
+    let _ = writeln!(body, "# TYPE drone_api_ingest_wire_bytes_total counter");
+    for (encoding, c) in &ingest {
+        let _ = writeln!(body, "drone_api_ingest_wire_bytes_total{{encoding=\"{encoding}\"}} {}", c.wire_bytes);
+    }
+    let _ = writeln!(body, "# HELP drone_api_ingest_json_bytes_total Size of the same frames as uncompressed JSON");
+    let _ = writeln!(body, "# TYPE drone_api_ingest_json_bytes_total counter");
+    for (encoding, c) in &ingest {
+        let _ = writeln!(body, "drone_api_ingest_json_bytes_total{{encoding=\"{encoding}\"}} {}", c.json_bytes);
+    }
+
+    let jobs = state.ctx.tasks.metrics();
+    let _ = writeln!(body, "# HELP drone_api_job_runs_total Completed background job runs");
+    let _ = writeln!(body, "# TYPE drone_api_job_runs_total counter");
+    for j in &jobs {
+        let _ = writeln!(body, "drone_api_job_runs_total{{job=\"{}\"}} {}", j.name, j.runs);
+    }
+    let _ = writeln!(body, "# HELP drone_api_job_failures_total Background job runs that returned an error");
+    let _ = writeln!(body, "# TYPE drone_api_job_failures_total counter");
+    for j in &jobs {
+        let _ = writeln!(body, "drone_api_job_failures_total{{job=\"{}\"}} {}", j.name, j.failures);
+    }
+    let _ = writeln!(body, "# HELP drone_api_job_panics_total Background job runs that panicked");
+    let _ = writeln!(body, "# TYPE drone_api_job_panics_total counter");
+    for j in &jobs {
+        let _ = writeln!(body, "drone_api_job_panics_total{{job=\"{}\"}} {}", j.name, j.panics);
+    }
+    let _ = writeln!(body, "# HELP drone_api_job_last_duration_seconds Duration of the last background job run");
+    let _ = writeln!(body, "# TYPE drone_api_job_last_duration_seconds gauge");
+    for j in &jobs {
+        if let Some(d) = j.last_duration {
+            let _ = writeln!(body, "drone_api_job_last_duration_seconds{{job=\"{}\"}} {}", j.name, d.as_secs_f64());
+        }
+    }
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body)
+}
+
+/// Prometheus buckets, sum and count of one labelled histogram
+fn write_histogram(body: &mut String, name: &str, label: &str, value: &str, h: &Histogram) {
+    use std::fmt::Write;
+
+    for (le, count) in h.cumulative() {
+        let _ = writeln!(body, "{name}_bucket{{{label}=\"{value}\",le=\"{le}\"}} {count}");
+    }
+    let _ = writeln!(body, "{name}_bucket{{{label}=\"{value}\",le=\"+Inf\"}} {}", h.count);
+    let _ = writeln!(body, "{name}_sum{{{label}=\"{value}\"}} {}", h.sum);
+    let _ = writeln!(body, "{name}_count{{{label}=\"{value}\"}} {}", h.count);
 }
 
 /// Build the Axum router
-pub fn build_router(schema: ApiSchema) -> Router {
-    let state = AppState { schema: schema.clone() };
+pub fn build_router(schema: ApiSchema, ctx: ApiContext) -> Router {
+    let schema_endpoint = ctx.schema_endpoint;
+    let playground = ctx.playground;
+    let max_body_bytes = ctx.request_limits.max_body_bytes;
+    let chaos = ctx.chaos.clone();
+    let state = AppState {
+        schema,
+        ctx,
+    };
 
     // CORS configuration
     let cors = CorsLayer::new()
         .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
         .allow_origin(Any)
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([request_id::REQUEST_ID_HEADER]);
 
-    Router::new()
+    // The playground answers GET only when enabled
+    let graphql = if playground {
+        get(playground::graphql_playground).post(graphql_handler)
+    } else {
+        post(graphql_handler)
+    };
+
+    let mut router = Router::new()
         // GraphQL endpoints
-        .route("/graphql", get(graphql_playground).post(graphql_handler))
-        // TODO: WebSocket subscriptions disabled until async-graphql-axum supports axum 0.8
-        // .route("/graphql/ws", any(GraphQLSubscription::new(schema)))
-        // Health check
+        .route("/graphql", graphql)
+        .route("/graphql/ws", get(ws::graphql_ws))
+        // Event stream for clients that cannot open WebSockets
+        .route("/events/{convoy_id}", get(sse::convoy_events))
+        // Bulk telemetry as JSON or CBOR, optionally compressed
+        .route("/ingest/telemetry", post(ingest::ingest_telemetry))
+        // Shift handover export
+        .route("/export/convoy/{id}", get(export_convoy_snapshot))
+        // KML/KMZ for Google Earth debriefs
+        .route("/export/kml/{convoy_id}", get(export_convoy_kml))
+        // Leaderboard for commanders' briefs
+        .route("/export/leaderboard.csv", get(export_leaderboard_csv))
+        // Documents stored when a mission completes
+        .route("/artifacts/{convoy_id}/{digest}", get(download_mission_artifact))
+        // GeoJSON for direct map consumption
+        .route("/geojson/route/{drone_id}", get(geojson_route))
+        .route("/geojson/aor/{convoy_id}", get(geojson_aor))
+        .route("/geojson/track/{drone_id}", get(geojson_track))
+        // Columnar analytics for BI tools
+        .route("/analytics/arrow", get(analytics_arrow))
+        // Runtime endpoints for the dashboard
+        .route("/config.json", get(frontend_config))
+        // Health check and metrics
         .route("/health", get(health_check))
-        .route("/", get(|| async { "Drone Convoy Tracker API" }))
+        .route("/metrics", get(metrics))
+        .route("/", get(|| async { "Drone Convoy Tracker API" }));
+
+    // SDL for client codegen
+    if schema_endpoint {
+        router = router.route("/schema.graphql", get(schema_graphql));
+    }
+
+    router
         // State and middleware
         .with_state(state)
+        // limit_body enforces the configured size in place of axum's default
+        .layer(DefaultBodyLimit::disable())
+        .layer(middleware::from_fn(move |req, next| {
+            limits::limit_body(max_body_bytes, req, next)
+        }))
+        .layer(middleware::from_fn(move |req, next| {
+            chaos::inject(chaos.clone(), req, next)
+        }))
         .layer(cors)
-        .layer(TraceLayer::new_for_http())
+        .layer(TraceLayer::new_for_http().make_span_with(request_id::make_span))
+        // Outermost, so the request span and every response carry the ID
+        .layer(middleware::from_fn(request_id::assign))
 }
 
 /// Crate version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA_SNAPSHOT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../schema/graphql/schema.graphql"
+    );
+
+    /// Fails when the schema drifts from the committed SDL.
+    ///
+    /// Regenerate with `UPDATE_SCHEMA=1 cargo test -p drone-graphql-api schema_sdl`.
+    #[test]
+    fn test_schema_sdl_matches_snapshot() {
+        let sdl = schema_sdl();
+
+        if std::env::var_os("UPDATE_SCHEMA").is_some() {
+            std::fs::write(SCHEMA_SNAPSHOT, &sdl).unwrap();
+            return;
+        }
+
+        let snapshot = std::fs::read_to_string(SCHEMA_SNAPSHOT).unwrap_or_default();
+        assert!(
+            snapshot == sdl,
+            "GraphQL schema changed; review and regenerate schema/graphql/schema.graphql"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! # Request Limits
+//!
+//! Caps on HTTP request body size, on the number of operations in a batched
+//! GraphQL request and on the length of list inputs, so a single oversized
+//! request cannot exhaust server memory.
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::header::CONTENT_LENGTH;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::error::{ApiError, ApiResult};
+
+/// Default maximum request body size (2 MiB)
+pub const DEFAULT_MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Default maximum items in a list input
+pub const DEFAULT_MAX_BATCH_ITEMS: usize = 500;
+
+/// Default maximum operations in one batched GraphQL request
+pub const DEFAULT_MAX_BATCH_OPERATIONS: usize = 50;
+
+/// Request size limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    /// Largest accepted request body
+    pub max_body_bytes: usize,
+    /// Most items accepted in one list input (waypoints, telemetry, weapons)
+    pub max_batch_items: usize,
+    /// Most operations accepted in one batched GraphQL request
+    pub max_batch_operations: usize,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_batch_items: DEFAULT_MAX_BATCH_ITEMS,
+            max_batch_operations: DEFAULT_MAX_BATCH_OPERATIONS,
+        }
+    }
+}
+
+impl RequestLimits {
+    /// Fail with a `VALIDATION` error when list input `field` is too long
+    pub fn check_batch(&self, field: &str, len: usize) -> ApiResult<()> {
+        if len > self.max_batch_items {
+            return Err(ApiError::Validation(format!(
+                "`{field}` has {len} items; at most {} are accepted per request",
+                self.max_batch_items,
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fail with a `VALIDATION` error when a batched request has too many operations
+    pub fn check_operations(&self, len: usize) -> ApiResult<()> {
+        if len > self.max_batch_operations {
+            return Err(ApiError::Validation(format!(
+                "batch has {len} operations; at most {} are accepted per request",
+                self.max_batch_operations,
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Reject request bodies larger than `max_body_bytes`.
+///
+/// Bodies that declare their length are refused with 413 before being
+/// read; chunked bodies fail once the limit is crossed while reading.
+pub async fn limit_body(max_body_bytes: usize, req: Request, next: Next) -> Response {
+    let declared = req
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared.is_some_and(|len| len > max_body_bytes) {
+        return ApiError::PayloadTooLarge {
+            limit_bytes: max_body_bytes,
+        }
+        .into_response();
+    }
+
+    let req = req.map(|body| Body::new(http_body_util::Limited::new(body, max_body_bytes)));
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_batch_rejects_oversized_lists() {
+        let limits = RequestLimits {
+            max_body_bytes: 1024,
+            max_batch_items: 3,
+            max_batch_operations: 2,
+        };
+
+        assert!(limits.check_batch("waypoints", 3).is_ok());
+        let err = limits.check_batch("waypoints", 4).unwrap_err();
+        assert_eq!(err.error_code(), "VALIDATION");
+        assert!(err.to_string().contains("`waypoints` has 4 items"));
+    }
+
+    #[test]
+    fn test_check_operations_rejects_oversized_batches() {
+        let limits = RequestLimits {
+            max_batch_operations: 2,
+            ..RequestLimits::default()
+        };
+
+        assert!(limits.check_operations(2).is_ok());
+        let err = limits.check_operations(3).unwrap_err();
+        assert_eq!(err.error_code(), "VALIDATION");
+        assert!(err.to_string().contains("batch has 3 operations"));
+    }
+}
@@ -0,0 +1,144 @@
+//! # Live Leaderboard
+//!
+//! Snapshot-then-delta state behind the `leaderboardLive` subscription.
+//!
+//! Each subscriber starts from a full snapshot and then receives only the
+//! entries that changed, numbered so a client can detect a missed message
+//! and resubscribe. Other drones shifting rank because of one engagement
+//! show up in the same delta.
+
+use std::collections::HashMap;
+
+use async_graphql::ID;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::schema::{LeaderboardEntry, LeaderboardLiveKind, LeaderboardLiveMessage};
+use drone_domain::LeaderboardEntry as DomainEntry;
+
+/// A subscriber's view of one convoy's leaderboard
+#[derive(Debug)]
+pub struct LiveLeaderboard {
+    convoy_id: Uuid,
+    sequence: i32,
+    entries: HashMap<Uuid, DomainEntry>,
+}
+
+impl LiveLeaderboard {
+    #[must_use]
+    pub fn new(convoy_id: Uuid) -> Self {
+        Self {
+            convoy_id,
+            sequence: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Replace the client's state with `entries`
+    pub fn snapshot(&mut self, entries: Vec<DomainEntry>) -> LeaderboardLiveMessage {
+        self.entries = entries.iter().map(|e| (e.drone_id, e.clone())).collect();
+        self.message(
+            LeaderboardLiveKind::Snapshot,
+            entries.into_iter().map(LeaderboardEntry::from).collect(),
+            Vec::new(),
+        )
+    }
+
+    /// Entries in `entries` that differ from what the client last saw, and
+    /// drones no longer present; `None` when nothing changed
+    pub fn delta(&mut self, entries: Vec<DomainEntry>) -> Option<LeaderboardLiveMessage> {
+        let mut previous = std::mem::take(&mut self.entries);
+        let mut changed = Vec::new();
+        for entry in entries {
+            if previous.remove(&entry.drone_id).as_ref() != Some(&entry) {
+                changed.push(LeaderboardEntry::from(entry.clone()));
+            }
+            self.entries.insert(entry.drone_id, entry);
+        }
+
+        let mut removed: Vec<Uuid> = previous.into_keys().collect();
+        if changed.is_empty() && removed.is_empty() {
+            return None;
+        }
+        removed.sort_unstable();
+        changed.sort_by_key(|e| e.rank);
+        Some(self.message(
+            LeaderboardLiveKind::Delta,
+            changed,
+            removed.into_iter().map(ID::from).collect(),
+        ))
+    }
+
+    fn message(
+        &mut self,
+        kind: LeaderboardLiveKind,
+        entries: Vec<LeaderboardEntry>,
+        removed_drone_ids: Vec<ID>,
+    ) -> LeaderboardLiveMessage {
+        self.sequence += 1;
+        LeaderboardLiveMessage {
+            convoy_id: self.convoy_id.into(),
+            sequence: self.sequence,
+            kind,
+            entries,
+            removed_drone_ids,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use drone_domain::PlatformType;
+
+    const CONVOY: Uuid = Uuid::from_u128(1);
+
+    fn entry(drone: u128, rank: i16, hits: u8) -> DomainEntry {
+        DomainEntry {
+            convoy_id: CONVOY,
+            drone_id: Uuid::from_u128(drone),
+            callsign: format!("REAPER-{drone:02}"),
+            platform_type: PlatformType::Mq9Reaper,
+            accuracy_pct: f32::from(hits) * 10.0,
+            total_engagements: 10,
+            successful_hits: i32::from(hits),
+            current_streak: 0,
+            best_streak: 0,
+            score: f64::from(hits) / 10.0,
+            rank,
+            updated_at: chrono::DateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_then_changed_entries_only() {
+        let mut live = LiveLeaderboard::new(CONVOY);
+
+        let snapshot = live.snapshot(vec![entry(10, 1, 8), entry(20, 2, 6), entry(30, 3, 4)]);
+        assert_eq!((snapshot.sequence, snapshot.kind), (1, LeaderboardLiveKind::Snapshot));
+        assert_eq!(snapshot.entries.len(), 3);
+
+        // Drone 20 overtakes drone 10; drone 30 is untouched
+        let delta = live
+            .delta(vec![entry(20, 1, 9), entry(10, 2, 8), entry(30, 3, 4)])
+            .unwrap();
+        assert_eq!((delta.sequence, delta.kind), (2, LeaderboardLiveKind::Delta));
+        let ranks: Vec<_> = delta.entries.iter().map(|e| (e.drone_id.as_str(), e.rank)).collect();
+        assert_eq!(
+            ranks,
+            [
+                (Uuid::from_u128(20).to_string().as_str(), 1),
+                (Uuid::from_u128(10).to_string().as_str(), 2),
+            ]
+        );
+        assert!(delta.removed_drone_ids.is_empty());
+
+        // Nothing changed: no message and no sequence gap
+        assert!(live.delta(vec![entry(20, 1, 9), entry(10, 2, 8), entry(30, 3, 4)]).is_none());
+        let delta = live.delta(vec![entry(20, 1, 9), entry(10, 2, 8)]).unwrap();
+        assert_eq!(delta.sequence, 3);
+        assert!(delta.entries.is_empty());
+        assert_eq!(delta.removed_drone_ids, [ID::from(Uuid::from_u128(30))]);
+    }
+}
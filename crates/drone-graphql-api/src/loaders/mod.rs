@@ -1,15 +1,84 @@
 //! # DataLoaders
 //!
 //! Batch data loading for N+1 query prevention in GraphQL resolvers.
+//!
+//! Scylla cannot serve an `IN` across partition keys efficiently, so a
+//! batch of `(convoy_id, drone_id)` keys is grouped by convoy partition
+//! and each partition is read with one query. The partition queries run
+//! concurrently, at most [`MAX_PARTITION_QUERIES`] at a time, and their
+//! rows are merged into the batch result.
 
 use async_graphql::dataloader::Loader;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::sync::Arc;
+use tokio::task::JoinSet;
 use uuid::Uuid;
 
-use crate::error::ApiError;
+use crate::error::{ApiError, ApiResult};
 use crate::schema::{Drone, LeaderboardEntry};
-use drone_persistence::ScyllaClient;
+use crate::store::{ConvoyRepository, DroneRepository, LeaderboardRepository};
+
+/// Most partition queries one batch runs at once
+pub const MAX_PARTITION_QUERIES: usize = 8;
+
+/// Group `(convoy_id, drone_id)` keys by convoy partition, dropping
+/// duplicate keys
+#[must_use]
+pub fn group_by_partition(keys: &[(Uuid, Uuid)]) -> HashMap<Uuid, Vec<Uuid>> {
+    let mut partitions: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for &(convoy_id, drone_id) in keys {
+        let ids = partitions.entry(convoy_id).or_default();
+        if !ids.contains(&drone_id) {
+            ids.push(drone_id);
+        }
+    }
+    partitions
+}
+
+/// Run `fetch` once per convoy partition in `keys`, at most `limit` at a
+/// time, and merge the `(drone_id, value)` pairs it returns
+///
+/// # Errors
+///
+/// Returns the first error a partition query fails with; queries still
+/// running are cancelled.
+pub async fn fan_out<V, F, Fut>(
+    keys: &[(Uuid, Uuid)],
+    limit: usize,
+    fetch: F,
+) -> ApiResult<HashMap<(Uuid, Uuid), V>>
+where
+    V: Send + 'static,
+    F: Fn(Uuid, Vec<Uuid>) -> Fut,
+    Fut: Future<Output = ApiResult<Vec<(Uuid, V)>>> + Send + 'static,
+{
+    let mut merged = HashMap::with_capacity(keys.len());
+    let mut queries = JoinSet::new();
+    for (convoy_id, drone_ids) in group_by_partition(keys) {
+        if queries.len() >= limit.max(1) {
+            if let Some(done) = queries.join_next().await {
+                merge(&mut merged, done)?;
+            }
+        }
+        let query = fetch(convoy_id, drone_ids);
+        queries.spawn(async move { (convoy_id, query.await) });
+    }
+    while let Some(done) = queries.join_next().await {
+        merge(&mut merged, done)?;
+    }
+    Ok(merged)
+}
+
+fn merge<V>(
+    merged: &mut HashMap<(Uuid, Uuid), V>,
+    done: Result<(Uuid, ApiResult<Vec<(Uuid, V)>>), tokio::task::JoinError>,
+) -> ApiResult<()> {
+    let (convoy_id, rows) =
+        done.map_err(|e| ApiError::Internal(format!("partition query failed: {e}")))?;
+    merged.extend(rows?.into_iter().map(|(drone_id, value)| ((convoy_id, drone_id), value)));
+    Ok(())
+}
 
 // =============================================================================
 // DRONE LOADER
@@ -17,12 +86,12 @@ use drone_persistence::ScyllaClient;
 
 /// Batch loader for drones by ID
 pub struct DroneLoader {
-    scylla: Arc<ScyllaClient>,
+    drones: Arc<DroneRepository>,
 }
 
 impl DroneLoader {
-    pub fn new(scylla: Arc<ScyllaClient>) -> Self {
-        Self { scylla }
+    pub fn new(drones: Arc<DroneRepository>) -> Self {
+        Self { drones }
     }
 }
 
@@ -36,9 +105,15 @@ impl Loader<(Uuid, Uuid)> for DroneLoader {
     ) -> Result<HashMap<(Uuid, Uuid), Self::Value>, Self::Error> {
         tracing::debug!(count = keys.len(), "Batch loading drones");
 
-        // TODO: Implement batch query
-        // For now, return empty map
-        Ok(HashMap::new())
+        fan_out(keys, MAX_PARTITION_QUERIES, |convoy_id, drone_ids| {
+            let drones = self.drones.clone();
+            async move {
+                let found = drones.get_many(convoy_id, &drone_ids).await?;
+                Ok(found.into_iter().map(|d| (d.drone_id, Drone::from(d))).collect())
+            }
+        })
+        .await
+        .map_err(Arc::new)
     }
 }
 
@@ -48,12 +123,12 @@ impl Loader<(Uuid, Uuid)> for DroneLoader {
 
 /// Batch loader for leaderboard entries
 pub struct LeaderboardEntryLoader {
-    scylla: Arc<ScyllaClient>,
+    leaderboard: Arc<LeaderboardRepository>,
 }
 
 impl LeaderboardEntryLoader {
-    pub fn new(scylla: Arc<ScyllaClient>) -> Self {
-        Self { scylla }
+    pub fn new(leaderboard: Arc<LeaderboardRepository>) -> Self {
+        Self { leaderboard }
     }
 }
 
@@ -67,8 +142,22 @@ impl Loader<(Uuid, Uuid)> for LeaderboardEntryLoader {
     ) -> Result<HashMap<(Uuid, Uuid), Self::Value>, Self::Error> {
         tracing::debug!(count = keys.len(), "Batch loading leaderboard entries");
 
-        // TODO: Implement batch query
-        Ok(HashMap::new())
+        // The leaderboard clusters on accuracy before drone ID, so drone IDs
+        // cannot be restricted with IN; read the whole (small) partition
+        fan_out(keys, MAX_PARTITION_QUERIES, |convoy_id, drone_ids| {
+            let leaderboard = self.leaderboard.clone();
+            async move {
+                let wanted: HashSet<Uuid> = drone_ids.into_iter().collect();
+                let entries = leaderboard.get_leaderboard(convoy_id, i32::MAX).await?;
+                Ok(entries
+                    .into_iter()
+                    .filter(|e| wanted.contains(&e.drone_id))
+                    .map(|e| (e.drone_id, LeaderboardEntry::from(e)))
+                    .collect())
+            }
+        })
+        .await
+        .map_err(Arc::new)
     }
 }
 
@@ -78,12 +167,16 @@ impl Loader<(Uuid, Uuid)> for LeaderboardEntryLoader {
 
 /// Batch loader for convoys by ID
 pub struct ConvoyLoader {
-    scylla: Arc<ScyllaClient>,
+    convoys: Arc<ConvoyRepository>,
+    leaderboard: Arc<LeaderboardRepository>,
 }
 
 impl ConvoyLoader {
-    pub fn new(scylla: Arc<ScyllaClient>) -> Self {
-        Self { scylla }
+    pub fn new(convoys: Arc<ConvoyRepository>, leaderboard: Arc<LeaderboardRepository>) -> Self {
+        Self {
+            convoys,
+            leaderboard,
+        }
     }
 }
 
@@ -97,7 +190,84 @@ impl Loader<Uuid> for ConvoyLoader {
     ) -> Result<HashMap<Uuid, Self::Value>, Self::Error> {
         tracing::debug!(count = keys.len(), "Batch loading convoys");
 
-        // TODO: Implement batch query
-        Ok(HashMap::new())
+        // Each convoy is its own partition
+        let keys: Vec<(Uuid, Uuid)> = keys.iter().map(|&id| (id, id)).collect();
+        let convoys = fan_out(&keys, MAX_PARTITION_QUERIES, |convoy_id, _| {
+            let repo = self.convoys.clone();
+            let leaderboard = self.leaderboard.clone();
+            async move {
+                let Some(convoy) = repo.get(convoy_id).await? else {
+                    return Ok(Vec::new());
+                };
+                let model = leaderboard.scoring_model(convoy_id).await?.into();
+                Ok(vec![(convoy_id, crate::schema::Convoy::from_domain(convoy, model))])
+            }
+        })
+        .await
+        .map_err(Arc::new)?;
+
+        Ok(convoys
+            .into_iter()
+            .map(|((convoy_id, _), convoy)| (convoy_id, convoy))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_keys_group_by_partition() {
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let (d1, d2) = (Uuid::from_u128(10), Uuid::from_u128(11));
+        let groups = group_by_partition(&[(a, d1), (b, d1), (a, d2), (a, d1)]);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&a], vec![d1, d2]);
+        assert_eq!(groups[&b], vec![d1]);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_bounds_concurrency_and_merges() {
+        let keys: Vec<(Uuid, Uuid)> = (0..40)
+            .map(|i| (Uuid::from_u128(i % 10), Uuid::from_u128(100 + i)))
+            .collect();
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let queries = Arc::new(AtomicUsize::new(0));
+
+        let merged = fan_out(&keys, 3, |_, drone_ids| {
+            let (running, peak, queries) = (running.clone(), peak.clone(), queries.clone());
+            async move {
+                queries.fetch_add(1, Ordering::SeqCst);
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(drone_ids.into_iter().map(|id| (id, id.as_u128())).collect())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(merged.len(), 40);
+        assert_eq!(merged[&keys[7]], keys[7].1.as_u128());
+        assert_eq!(queries.load(Ordering::SeqCst), 10);
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_fails_on_any_partition_error() {
+        let keys = [(Uuid::from_u128(1), Uuid::from_u128(2)), (Uuid::from_u128(3), Uuid::nil())];
+        let result = fan_out(&keys, 2, |convoy_id, drone_ids| async move {
+            if convoy_id == Uuid::from_u128(3) {
+                return Err(ApiError::Internal("down".to_string()));
+            }
+            Ok(drone_ids.into_iter().map(|id| (id, ())).collect())
+        })
+        .await;
+        assert!(result.is_err());
     }
 }
@@ -3,13 +3,47 @@
 //! Binary entry point for the GraphQL API service.
 
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use drone_graphql_api::{build_router, build_schema, ApiContext, Config};
-use drone_persistence::{CacheClient, CacheConfig, ScyllaClient, ScyllaConfig};
+use drone_analytics::insights::InsightThresholds;
+use drone_analytics::{AnalyticsEngine, ReadonlyLimits};
+use drone_domain::{
+    ConvoyTemplate, FlightHoursLimits, FormationBounds, Km, Meters, PlatformCapabilities,
+    SeparationMinimum,
+};
+use drone_graphql_api::alerting::{
+    parse_severity, AlertRoute, AlertRouter, SharedNotifier, SlackNotifier, SmtpNotifier,
+    SmtpSettings, WebhookNotifier,
+};
+use drone_graphql_api::api_keys::ApiKeyHasher;
+use drone_graphql_api::auth::parse_role_tokens;
+use drone_graphql_api::authorization::AuthorizationSigner;
+use drone_graphql_api::insights::InsightMonitor;
+use drone_graphql_api::limits::RequestLimits;
+use drone_graphql_api::playground::PlaygroundCredentials;
+use drone_graphql_api::stats;
+use drone_graphql_api::store::StoreClient;
+use drone_graphql_api::weather::{
+    Conditions, OpenMeteoProvider, SharedWeatherProvider, StaticWeatherProvider,
+};
+use drone_graphql_api::ws::WsLimits;
+use drone_graphql_api::config::{AlertChannelFilter, AlertRoutingConfig};
+use drone_graphql_api::{build_router, build_schema, schema_sdl, ApiContext, Config};
+use drone_persistence::{
+    BreakerConfig, CacheBackend, CacheClient, CacheCodec, CacheConfig, CacheTtl, Chaos, ChaosConfig,
+    StrategySource,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // Emit the SDL for client codegen and exit
+    if std::env::args().any(|arg| arg == "--print-schema") {
+        print!("{}", schema_sdl());
+        return Ok(());
+    }
+
     // Load environment variables
     dotenvy::dotenv().ok();
 
@@ -30,43 +64,271 @@ async fn main() -> anyhow::Result<()> {
         "Starting Drone Convoy GraphQL API"
     );
 
-    // Initialize ScyllaDB client
-    tracing::info!(
-        hosts = ?config.scylla.hosts,
-        keyspace = %config.scylla.keyspace,
-        "Connecting to ScyllaDB"
-    );
-
-    let scylla_config = ScyllaConfig {
-        hosts: config.scylla.hosts.clone(),
-        keyspace: config.scylla.keyspace.clone(),
-        username: config.scylla.username.clone(),
-        password: config.scylla.password.clone(),
+    let breaker = BreakerConfig {
+        failure_rate_threshold: config.breaker.failure_rate,
+        minimum_calls: config.breaker.min_calls,
+        window_size: config.breaker.window_size,
+        open_duration: Duration::from_secs(config.breaker.open_secs),
+        half_open_probes: config.breaker.half_open_probes,
     };
 
-    let scylla = ScyllaClient::new(scylla_config).await?;
-    tracing::info!("ScyllaDB connected");
+    let chaos = ChaosConfig::parse(&config.chaos_rules)?;
+    if !chaos.is_empty() {
+        if !cfg!(feature = "chaos") {
+            anyhow::bail!("CHAOS_RULES is set but drone-api was built without the `chaos` feature");
+        }
+        tracing::warn!(rules = %config.chaos_rules, "Chaos fault injection enabled");
+    }
 
-    // Initialize Redis cache
-    tracing::info!(url = %config.redis.url, "Connecting to Redis");
+    // Initialize database client
+    let store = connect_store(&config, breaker, chaos.clone()).await?;
+
+    // Initialize cache
+    let backend = match config.redis.backend.as_str() {
+        "redis" => CacheBackend::Redis,
+        "memory" => CacheBackend::Memory,
+        other => anyhow::bail!("CACHE_BACKEND must be `redis` or `memory`, got `{other}`"),
+    };
+    let codec = CacheCodec::parse(&config.redis.codec).ok_or_else(|| {
+        let known: Vec<_> = CacheCodec::ALL.iter().map(|c| c.as_str()).collect();
+        anyhow::anyhow!(
+            "CACHE_CODEC must be one of `{}` in this build, got `{}`",
+            known.join("`, `"),
+            config.redis.codec
+        )
+    })?;
+    match backend {
+        CacheBackend::Redis => tracing::info!(url = %config.redis.url, "Connecting to Redis"),
+        CacheBackend::Memory => tracing::warn!(
+            "Using the in-memory cache; cached state is lost on restart and not shared between replicas"
+        ),
+    }
 
     let cache_config = CacheConfig {
+        backend,
         url: config.redis.url.clone(),
         pool_size: config.redis.pool_size,
+        ttl: CacheTtl {
+            event_stream: Duration::from_secs(config.ws.event_replay_secs),
+            ..Default::default()
+        },
+        codec,
+        breaker,
+        chaos: chaos.clone(),
         ..Default::default()
     };
 
     let cache = CacheClient::new(cache_config).await?;
-    tracing::info!("Redis connected");
+    if backend == CacheBackend::Redis {
+        tracing::info!("Redis connected");
+    }
 
     // Build API context
-    let api_ctx = ApiContext::new(scylla, cache);
+    let weather: SharedWeatherProvider = match config.weather.provider.as_str() {
+        "open-meteo" => Arc::new(OpenMeteoProvider::new(
+            config.weather.open_meteo_url.clone(),
+            Duration::from_secs(config.weather.cache_ttl_secs),
+            Duration::from_millis(config.weather.connect_timeout_ms),
+            Duration::from_millis(config.weather.request_timeout_ms),
+        )?),
+        _ => Arc::new(StaticWeatherProvider::new(Conditions {
+            wind_speed_mps: config.weather.static_wind_speed_mps,
+            wind_direction_deg: config.weather.static_wind_direction_deg,
+            visibility_km: config.weather.static_visibility_km,
+            temperature_c: config.weather.static_temperature_c,
+            ..Conditions::default()
+        })),
+    };
+    tracing::info!(provider = weather.name(), "Weather provider configured");
+
+    let code_ttl = Duration::from_secs(config.engagement_auth.code_ttl_secs);
+    let signer = match config.engagement_auth.signing_key {
+        Some(ref key) => AuthorizationSigner::new(key.as_bytes(), code_ttl),
+        None => {
+            tracing::warn!(
+                "ENGAGEMENT_SIGNING_KEY not set; authorization codes will not survive a restart"
+            );
+            AuthorizationSigner::ephemeral(code_ttl)
+        }
+    };
+
+    let api_key_hasher = match config.api_key_hash_secret {
+        Some(ref secret) => ApiKeyHasher::new(secret.as_bytes()),
+        None => {
+            tracing::warn!("API_KEY_HASH_SECRET not set; issued API keys will not survive a restart");
+            ApiKeyHasher::ephemeral()
+        }
+    };
+
+    let playground_auth = config.playground_basic_auth.as_deref().map(|raw| {
+        PlaygroundCredentials::parse(raw).expect("PLAYGROUND_BASIC_AUTH must be user:password")
+    });
+    if config.enable_playground && playground_auth.is_none() {
+        tracing::warn!("GraphQL Playground enabled without PLAYGROUND_BASIC_AUTH");
+    }
+    if !config.ws.require_auth {
+        tracing::warn!("WS_DEV_ALLOW_ANONYMOUS set; subscriptions admit anonymous viewers");
+    }
+
+    let api_ctx = ApiContext::new(store, cache)
+        .with_formation_bounds(FormationBounds {
+            min_spacing_km: Km(config.formation.min_spacing_km),
+            max_spacing_km: Km(config.formation.max_spacing_km),
+        })
+        .with_separation_minimum(SeparationMinimum {
+            horizontal_km: Km(config.deconfliction.min_horizontal_km),
+            vertical_m: Meters(config.deconfliction.min_vertical_m),
+            lookahead_secs: config.deconfliction.lookahead_secs,
+        })
+        .with_flight_hours_limits(FlightHoursLimits {
+            inspection_interval_hrs: config.flight_hours.inspection_interval_hrs,
+            sortie_limit_hrs: config.flight_hours.sortie_limit_hrs,
+        })
+        .with_weather(weather, config.weather.min_visibility_km)
+        .with_role_tokens(parse_role_tokens(&config.api_tokens))
+        .with_api_key_hasher(api_key_hasher)
+        .with_authorization_signer(signer)
+        .with_low_munitions_rounds(config.low_munitions_rounds)
+        .with_engagement_reorder_window(Duration::from_millis(config.engagement_reorder_window_ms))
+        .with_event_sourcing(config.event_sourcing_enabled)
+        .with_schema_endpoint(config.enable_schema_endpoint)
+        .with_playground(config.enable_playground, playground_auth)
+        .with_introspection(config.enable_introspection)
+        .with_frontend_config(config.frontend.clone())
+        .with_ws_limits(WsLimits {
+            ping_interval: Duration::from_secs(config.ws.ping_interval_secs),
+            idle_timeout: Duration::from_secs(config.ws.idle_timeout_secs),
+            max_connections: config.ws.max_connections,
+            max_connections_per_ip: config.ws.max_connections_per_ip,
+        })
+        .with_ws_auth(config.ws.require_auth)
+        .with_request_limits(RequestLimits {
+            max_body_bytes: config.max_body_bytes,
+            max_batch_items: config.max_batch_items,
+            max_batch_operations: config.max_batch_operations,
+        })
+        .with_sse_replay(config.ws.sse_replay_events)
+        .with_alert_router(alert_router(&config.alerts)?)
+        .with_chaos(Chaos::new(chaos));
+
+    let api_ctx = if config.backplane.enabled {
+        tracing::info!(channel = %config.backplane.channel, "Event back-plane enabled");
+        api_ctx.with_backplane(config.backplane.channel.clone())
+    } else {
+        api_ctx
+    };
+
+    let api_ctx = match config.analytics.db_path {
+        Some(ref path) => {
+            tracing::info!(path = %path, "Opening analytics engine");
+            let limits = ReadonlyLimits {
+                max_rows: config.analytics.sql_max_rows,
+                timeout: Duration::from_secs(config.analytics.sql_timeout_secs),
+            };
+            let engine = AnalyticsEngine::new_persistent(path)?.without_external_access()?;
+            api_ctx.with_analytics(engine, limits)
+        }
+        None => api_ctx,
+    };
+
+    let api_ctx = match config.convoy_templates_path {
+        Some(ref path) => {
+            let raw = std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read convoy templates {path}: {e}"))?;
+            let templates: Vec<ConvoyTemplate> = serde_json::from_str(&raw)
+                .map_err(|e| anyhow::anyhow!("Invalid convoy templates in {path}: {e}"))?;
+            tracing::info!(path = %path, templates = templates.len(), "Loaded convoy templates");
+            api_ctx.with_convoy_templates(templates)
+        }
+        None => api_ctx,
+    };
+
+    let api_ctx = match config.platform_capabilities_path {
+        Some(ref path) => {
+            let raw = std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read platform capabilities {path}: {e}"))?;
+            let capabilities: Vec<PlatformCapabilities> = serde_json::from_str(&raw)
+                .map_err(|e| anyhow::anyhow!("Invalid platform capabilities in {path}: {e}"))?;
+            tracing::info!(
+                path = %path,
+                platforms = capabilities.len(),
+                "Loaded platform capabilities"
+            );
+            api_ctx.with_platform_capabilities(capabilities)
+        }
+        None => api_ctx,
+    };
+
+    // Watch for persistence strategy overrides
+    let strategy_source = match (&config.strategy.redis_key, &config.strategy.config_path) {
+        (Some(key), _) => Some(StrategySource::Redis {
+            cache: api_ctx.cache.clone(),
+            key: key.clone(),
+        }),
+        (None, Some(path)) => Some(StrategySource::File(path.into())),
+        (None, None) => None,
+    };
+    if let Some(source) = strategy_source {
+        tracing::info!(reload_secs = config.strategy.reload_secs, "Watching persistence strategy config");
+        // Apply overrides before serving; the job only picks up later changes
+        if let Err(e) = api_ctx.strategies.reload(&source).await {
+            tracing::warn!(error = %e, "Failed to load persistence strategies");
+        }
+        let strategies = api_ctx.strategies.clone();
+        api_ctx.tasks.register(
+            "strategy_reload",
+            Duration::from_secs(config.strategy.reload_secs),
+            move || {
+                let (strategies, source) = (strategies.clone(), source.clone());
+                async move { strategies.reload(&source).await.map(|_| ()) }
+            },
+        );
+    }
+
+    // Record convoy statistics for the HUD timeline
+    let stats_ctx = api_ctx.clone();
+    api_ctx.tasks.register(
+        "stats_snapshot",
+        Duration::from_secs(config.stats.snapshot_interval_secs),
+        move || {
+            let ctx = stats_ctx.clone();
+            async move { stats::record_active_convoys(&ctx).await }
+        },
+    );
+
+    // Push analytics insights to passive dashboards
+    if api_ctx.analytics.is_some() {
+        let monitor = Arc::new(InsightMonitor::new(InsightThresholds {
+            window: chrono::Duration::minutes(config.analytics.insights_window_mins),
+            min_samples: config.analytics.insights_min_samples,
+            min_change_pct: config.analytics.insights_min_change_pct,
+        }));
+        let insights_ctx = api_ctx.clone();
+        api_ctx.tasks.register(
+            "analytics_insights",
+            Duration::from_secs(config.analytics.insights_interval_secs),
+            move || {
+                let (ctx, monitor) = (insights_ctx.clone(), monitor.clone());
+                async move { monitor.evaluate_active_convoys(&ctx).await }
+            },
+        );
+    }
+
+    // Feed the SSE event log from the broadcast channels
+    let _relay = api_ctx.event_log.clone().relay(&api_ctx);
+
+    // Keep convoy events for clients catching up after a reconnect
+    let _event_history = api_ctx.event_history.clone().start(&api_ctx);
+
+    // Exchange broadcasts with the other replicas
+    let _backplane = api_ctx.backplane.clone().map(|backplane| backplane.start(&api_ctx));
 
     // Build GraphQL schema
-    let schema = build_schema(api_ctx);
+    let schema = build_schema(api_ctx.clone());
 
     tracing::info!(
         playground = config.enable_playground,
+        playground_auth = config.playground_basic_auth.is_some(),
         introspection = config.enable_introspection,
         max_depth = config.max_query_depth,
         max_complexity = config.max_query_complexity,
@@ -74,7 +336,8 @@ async fn main() -> anyhow::Result<()> {
     );
 
     // Build router
-    let app = build_router(schema);
+    let tasks = api_ctx.tasks.clone();
+    let app = build_router(schema, api_ctx);
 
     // Start server
     let addr = config.server_addr;
@@ -90,15 +353,76 @@ async fn main() -> anyhow::Result<()> {
         "WebSocket subscriptions at ws://{}/graphql/ws",
         addr
     );
+    tracing::info!(
+        "Server-sent events at http://{}/events/{{convoy_id}}",
+        addr
+    );
 
-    axum::serve(listener, app)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
+    // Let in-flight background jobs finish before exiting
+    tasks
+        .shutdown(Duration::from_secs(config.tasks.shutdown_grace_secs))
+        .await;
+
     tracing::info!("Server shut down gracefully");
     Ok(())
 }
 
+/// Build alert routes for every configured notification channel
+fn alert_router(config: &AlertRoutingConfig) -> anyhow::Result<AlertRouter> {
+    let mut channels: Vec<(SharedNotifier, &AlertChannelFilter)> = Vec::new();
+    if let Some(url) = &config.webhook_url {
+        channels.push((Arc::new(WebhookNotifier::new(url.clone())), &config.webhook));
+    }
+    if let Some(url) = &config.slack_webhook_url {
+        channels.push((Arc::new(SlackNotifier::new(url.clone())), &config.slack));
+    }
+    if let Some(host) = &config.smtp_host {
+        let notifier = SmtpNotifier::new(&SmtpSettings {
+            host: host.clone(),
+            port: config.smtp_port,
+            username: config.smtp_username.clone(),
+            password: config.smtp_password.clone(),
+            starttls: config.smtp_starttls,
+            from: config.smtp_from.clone(),
+            to: config.smtp_to.clone(),
+        })?;
+        channels.push((Arc::new(notifier), &config.email));
+    }
+
+    let mut routes = Vec::new();
+    for (notifier, filter) in channels {
+        let min_severity = parse_severity(&filter.min_severity).ok_or_else(|| {
+            anyhow::anyhow!("Invalid alert severity `{}` for {}", filter.min_severity, notifier.name())
+        })?;
+        let convoy_ids = filter
+            .convoy_ids
+            .iter()
+            .map(|id| id.parse())
+            .collect::<Result<Vec<uuid::Uuid>, _>>()?;
+        tracing::info!(
+            channel = notifier.name(),
+            min_severity = %filter.min_severity,
+            convoys = convoy_ids.len(),
+            "Alert notification channel configured"
+        );
+        routes.push(
+            AlertRoute::new(notifier)
+                .with_min_severity(min_severity)
+                .with_convoys(convoy_ids)
+                .with_rate_limit(config.max_per_minute),
+        );
+    }
+
+    Ok(AlertRouter::new(routes).with_retry(
+        config.retry_attempts,
+        Duration::from_millis(config.retry_backoff_ms),
+    ))
+}
+
 /// Graceful shutdown signal handler
 async fn shutdown_signal() {
     let ctrl_c = async {
@@ -127,3 +451,59 @@ async fn shutdown_signal() {
         }
     }
 }
+
+/// Connect to the ScyllaDB cluster
+#[cfg(not(feature = "embedded"))]
+async fn connect_store(
+    config: &Config,
+    breaker: BreakerConfig,
+    chaos: ChaosConfig,
+) -> anyhow::Result<StoreClient> {
+    use drone_persistence::{RetryConfig, ScyllaConfig};
+
+    tracing::info!(
+        hosts = ?config.scylla.hosts,
+        keyspace = %config.scylla.keyspace,
+        "Connecting to ScyllaDB"
+    );
+
+    let scylla_config = ScyllaConfig {
+        hosts: config.scylla.hosts.clone(),
+        keyspace: config.scylla.keyspace.clone(),
+        username: config.scylla.username.clone(),
+        password: config.scylla.password.clone(),
+        breaker,
+        retry: RetryConfig {
+            max_attempts: config.scylla.retry_max_attempts.max(1),
+            base_delay: Duration::from_millis(config.scylla.retry_base_delay_ms),
+            max_delay: Duration::from_millis(config.scylla.retry_max_delay_ms),
+        },
+        chaos,
+        slow_query_threshold: (config.scylla.slow_query_ms > 0)
+            .then(|| Duration::from_millis(config.scylla.slow_query_ms)),
+    };
+
+    let scylla = StoreClient::new(scylla_config).await?;
+    tracing::info!("ScyllaDB connected");
+    Ok(scylla)
+}
+
+/// Open the embedded SQLite database in place of ScyllaDB
+#[cfg(feature = "embedded")]
+#[allow(clippy::unused_async)] // Same signature as the ScyllaDB variant
+async fn connect_store(
+    config: &Config,
+    breaker: BreakerConfig,
+    _chaos: ChaosConfig,
+) -> anyhow::Result<StoreClient> {
+    use drone_persistence::SqliteConfig;
+
+    tracing::warn!(
+        path = %config.sqlite_path,
+        "Using embedded SQLite storage; for local development only"
+    );
+    Ok(StoreClient::open(SqliteConfig {
+        path: config.sqlite_path.clone(),
+        breaker,
+    })?)
+}
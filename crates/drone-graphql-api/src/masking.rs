@@ -0,0 +1,104 @@
+//! # Field Masking
+//!
+//! Some fields are sensitive even on objects every caller may read, such as
+//! authorization codes, ROE profiles and BDA notes. [`FIELD_POLICY`] lists
+//! them with the roles allowed to read them, and the [`FieldMasking`]
+//! extension resolves them to `null` for everyone else, so resolvers need no
+//! checks of their own and the rest of the object is still returned.
+//!
+//! Masked fields must be nullable in the schema.
+
+use std::sync::Arc;
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo,
+};
+use async_graphql::{ServerResult, Value};
+
+use crate::auth::Role;
+
+/// Sensitive fields as `Type.field`, with the roles that may read them.
+///
+/// Admins read every field; commanders read what operators may.
+pub const FIELD_POLICY: &[(&str, &[Role])] = &[
+    ("Convoy.roeProfile", &[Role::Operator, Role::Analyst]),
+    ("Engagement.authorizationCode", &[Role::Operator]),
+    ("Engagement.bdaNotes", &[Role::Operator, Role::Analyst]),
+    ("EngagementAuthorization.authorizationCode", &[Role::Operator]),
+];
+
+/// Whether `role` may read `field` of `parent_type`
+#[must_use]
+pub fn can_read(role: Role, parent_type: &str, field: &str) -> bool {
+    FIELD_POLICY
+        .iter()
+        .find(|(path, _)| path.split_once('.') == Some((parent_type, field)))
+        .is_none_or(|(_, readers)| readers.iter().any(|&reader| role.permits(reader)))
+}
+
+/// Schema extension applying [`FIELD_POLICY`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FieldMasking;
+
+impl ExtensionFactory for FieldMasking {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(FieldMaskingExtension)
+    }
+}
+
+struct FieldMaskingExtension;
+
+#[async_trait::async_trait]
+impl Extension for FieldMaskingExtension {
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let role = ctx.data_opt::<Role>().copied().unwrap_or_default();
+        if can_read(role, info.parent_type, info.name) {
+            next.run(ctx, info).await
+        } else {
+            Ok(Some(Value::Null))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_masks_by_role() {
+        let code = ("Engagement", "authorizationCode");
+        assert!(!can_read(Role::Viewer, code.0, code.1));
+        assert!(!can_read(Role::Analyst, code.0, code.1));
+        assert!(can_read(Role::Operator, code.0, code.1));
+        assert!(can_read(Role::Commander, code.0, code.1));
+        assert!(can_read(Role::Admin, code.0, code.1));
+
+        assert!(can_read(Role::Analyst, "Engagement", "bdaNotes"));
+        assert!(!can_read(Role::Viewer, "Convoy", "roeProfile"));
+        assert!(can_read(Role::Viewer, "Engagement", "hit"));
+    }
+
+    #[test]
+    fn test_policy_fields_are_nullable() {
+        let sdl = crate::schema_sdl();
+        for (path, _) in FIELD_POLICY {
+            let (parent_type, field) = path.split_once('.').unwrap();
+            let body = sdl
+                .split(&format!("type {parent_type} {{"))
+                .nth(1)
+                .and_then(|rest| rest.split("\n}").next())
+                .unwrap_or_else(|| panic!("{parent_type} is not in the schema"));
+            let line = body
+                .lines()
+                .map(str::trim)
+                .find(|line| line.starts_with(&format!("{field}:")))
+                .unwrap_or_else(|| panic!("{path} is not in the schema"));
+            assert!(!line.ends_with('!'), "{path} must be nullable to be masked");
+        }
+    }
+}
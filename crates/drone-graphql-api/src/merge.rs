@@ -0,0 +1,119 @@
+//! # Event Merging
+//!
+//! Orders events merged from several broadcast channels by their own
+//! timestamps. Publishers stamp events before sending them on separate
+//! channels, so a subscriber can receive a later event first; each event is
+//! held for a short window and released in timestamp order once it expires.
+//! An event arriving after a newer one was released is sent immediately,
+//! still out of order.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::time::Instant;
+
+/// How long the unified `events` subscription holds an event for ordering
+pub const DEFAULT_REORDER_WINDOW: Duration = Duration::from_millis(200);
+
+struct Pending<T> {
+    at: DateTime<Utc>,
+    /// Arrival order, breaking timestamp ties
+    seq: u64,
+    release_at: Instant,
+    item: T,
+}
+
+/// Holds items briefly and releases them in timestamp order
+pub struct ReorderBuffer<T> {
+    window: Duration,
+    pending: Vec<Pending<T>>,
+    next_seq: u64,
+}
+
+impl<T> ReorderBuffer<T> {
+    #[must_use]
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            pending: Vec::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Hold `item`, stamped `at`, received at `now`
+    pub fn push(&mut self, at: DateTime<Utc>, item: T, now: Instant) {
+        self.pending.push(Pending {
+            at,
+            seq: self.next_seq,
+            release_at: now + self.window,
+            item,
+        });
+        self.next_seq += 1;
+    }
+
+    /// When the next held item is due, `None` when nothing is held
+    #[must_use]
+    pub fn next_release(&self) -> Option<Instant> {
+        self.pending.iter().map(|p| p.release_at).min()
+    }
+
+    /// Items due by `now`, oldest first, along with any held item stamped
+    /// no later than them
+    pub fn release(&mut self, now: Instant) -> Vec<T> {
+        let Some(cutoff) = self
+            .pending
+            .iter()
+            .filter(|p| p.release_at <= now)
+            .map(|p| p.at)
+            .max()
+        else {
+            return Vec::new();
+        };
+
+        let (mut due, held) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition::<Vec<_>, _>(|p| p.at <= cutoff);
+        self.pending = held;
+        due.sort_by_key(|p| (p.at, p.seq));
+        due.into_iter().map(|p| p.item).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(200);
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH + chrono::Duration::seconds(secs)
+    }
+
+    #[test]
+    fn test_releases_in_timestamp_order_after_window() {
+        let start = Instant::now();
+        let mut buffer = ReorderBuffer::new(WINDOW);
+        buffer.push(at(3), "c", start);
+        buffer.push(at(1), "a", start + Duration::from_millis(50));
+        buffer.push(at(2), "b", start + Duration::from_millis(60));
+
+        assert_eq!(buffer.next_release(), Some(start + WINDOW));
+        assert!(buffer.release(start + Duration::from_millis(100)).is_empty());
+        // "c" is due, and the earlier-stamped items go out ahead of it
+        assert_eq!(buffer.release(start + WINDOW), vec!["a", "b", "c"]);
+        assert_eq!(buffer.next_release(), None);
+    }
+
+    #[test]
+    fn test_newer_items_stay_held() {
+        let start = Instant::now();
+        let mut buffer = ReorderBuffer::new(WINDOW);
+        buffer.push(at(1), "a", start);
+        buffer.push(at(5), "e", start + Duration::from_millis(150));
+        buffer.push(at(1), "a2", start + Duration::from_millis(150));
+
+        assert_eq!(buffer.release(start + WINDOW), vec!["a", "a2"]);
+        assert_eq!(buffer.next_release(), Some(start + Duration::from_millis(350)));
+        assert_eq!(buffer.release(start + Duration::from_millis(350)), vec!["e"]);
+    }
+}
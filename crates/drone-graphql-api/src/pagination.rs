@@ -0,0 +1,246 @@
+//! # Cursor Pagination
+//!
+//! Opaque Relay-style cursors for list queries. A cursor names the partition
+//! it was issued for and a position inside it, so a page resumes exactly
+//! where the previous one stopped instead of re-reading and skipping rows.
+//!
+//! Scylla-backed lists record the paging state of the page a row was read
+//! from and how many rows of that page precede the next one; resuming fetches
+//! from that paging state and drops those rows. Lists read from the Redis
+//! history record the timestamp of the last point instead.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::schema::{Connection, Edge};
+use drone_persistence::{Page, PersistenceError};
+
+/// Page size when `first` is not given
+pub const DEFAULT_PAGE_SIZE: i32 = 20;
+
+/// Position inside a partition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Position {
+    /// Scylla paging state of a page (`None` for the first page) and rows of
+    /// it already returned
+    Paged { state: Option<Vec<u8>>, skip: usize },
+    /// Milliseconds since the epoch of the last item returned
+    Timestamp(i64),
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::Paged {
+            state: None,
+            skip: 0,
+        }
+    }
+}
+
+/// Decoded cursor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Partition the cursor was issued for (convoy or drone)
+    pub partition: Uuid,
+    pub position: Position,
+}
+
+impl Cursor {
+    #[must_use]
+    pub fn new(partition: Uuid, position: Position) -> Self {
+        Self {
+            partition,
+            position,
+        }
+    }
+
+    /// Opaque string form handed to clients
+    #[must_use]
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    /// Parse a client's `after` cursor, checking it belongs to `partition`
+    pub fn decode(raw: &str, partition: Uuid) -> ApiResult<Self> {
+        let invalid = || ApiError::InvalidInput("invalid cursor".to_string());
+        let bytes = URL_SAFE_NO_PAD.decode(raw).map_err(|_| invalid())?;
+        let cursor: Self = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+        if cursor.partition != partition {
+            return Err(ApiError::InvalidInput(
+                "cursor belongs to a different list".to_string(),
+            ));
+        }
+        Ok(cursor)
+    }
+
+    /// Start position for a list, from an optional `after` cursor
+    pub fn resume(after: Option<&str>, partition: Uuid) -> ApiResult<Position> {
+        after.map_or(Ok(Position::default()), |raw| {
+            Self::decode(raw, partition).map(|cursor| cursor.position)
+        })
+    }
+
+    /// Timestamp to resume a time-ordered list after, from an optional
+    /// `after` cursor
+    pub fn resume_timestamp(after: Option<&str>, partition: Uuid) -> ApiResult<Option<i64>> {
+        match after {
+            None => Ok(None),
+            Some(raw) => match Self::decode(raw, partition)?.position {
+                Position::Timestamp(ms) => Ok(Some(ms)),
+                Position::Paged { .. } => {
+                    Err(ApiError::InvalidInput("invalid cursor".to_string()))
+                }
+            },
+        }
+    }
+}
+
+/// Validate `first`, defaulting it and capping it at `max`
+pub fn page_size(first: Option<i32>, max: i32) -> ApiResult<usize> {
+    let first = first.unwrap_or(DEFAULT_PAGE_SIZE);
+    if first < 0 {
+        return Err(ApiError::InvalidInput("first must not be negative".to_string()));
+    }
+    Ok(usize::try_from(first.min(max)).unwrap_or_default())
+}
+
+/// Items read from a paged partition with the position after each
+#[derive(Debug)]
+pub struct PagedRead<T> {
+    pub items: Vec<(T, Position)>,
+    /// Whether matching items remain after the last one returned
+    pub has_next_page: bool,
+}
+
+impl<T: async_graphql::OutputType> PagedRead<T> {
+    /// Connection over the items, with cursors issued for `partition`
+    #[must_use]
+    pub fn into_connection(
+        self,
+        partition: Uuid,
+        has_previous_page: bool,
+        total_count: Option<i32>,
+    ) -> Connection<T>
+    where
+        Edge<T>: async_graphql::OutputType,
+    {
+        let edges = self
+            .items
+            .into_iter()
+            .map(|(item, position)| (item, Cursor::new(partition, position).encode()))
+            .collect();
+        Connection::new(edges, self.has_next_page, has_previous_page, total_count)
+    }
+}
+
+/// Read up to `first` items that `keep` accepts, starting at `start`.
+///
+/// `fetch` reads one Scylla page of about the requested size from a paging
+/// state. Pages are read until `first` items are kept, the partition ends
+/// or `scan_limit` rows have been examined. One item beyond `first` is
+/// looked for so `has_next_page` is exact.
+pub async fn read_paged<T, F, Fut>(
+    start: Position,
+    first: usize,
+    scan_limit: usize,
+    keep: impl Fn(&T) -> bool,
+    mut fetch: F,
+) -> ApiResult<PagedRead<T>>
+where
+    F: FnMut(Option<Vec<u8>>, usize) -> Fut,
+    Fut: Future<Output = Result<Page<T>, PersistenceError>>,
+{
+    let Position::Paged { mut state, mut skip } = start else {
+        return Err(ApiError::InvalidInput("invalid cursor".to_string()));
+    };
+
+    let mut items = Vec::new();
+    let mut scanned = 0;
+    loop {
+        let wanted = skip + first + 1 - items.len();
+        let page = fetch(state.clone(), wanted).await?;
+        let rows = page.items.len();
+
+        for (index, item) in page.items.into_iter().enumerate().skip(skip) {
+            scanned += 1;
+            if !keep(&item) {
+                continue;
+            }
+            if items.len() == first {
+                return Ok(PagedRead {
+                    items,
+                    has_next_page: true,
+                });
+            }
+            let position = Position::Paged {
+                state: state.clone(),
+                skip: index + 1,
+            };
+            items.push((item, position));
+        }
+
+        match page.paging_state {
+            Some(next) if scanned < scan_limit => {
+                skip = skip.saturating_sub(rows);
+                state = Some(next);
+            }
+            next => {
+                return Ok(PagedRead {
+                    items,
+                    has_next_page: next.is_some(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve `rows` in pages of at most `max_page` rows
+    fn pages(
+        rows: &[u32],
+        max_page: usize,
+    ) -> impl FnMut(Option<Vec<u8>>, usize) -> std::future::Ready<Result<Page<u32>, PersistenceError>> + '_
+    {
+        move |state, size| {
+            let offset = state.map_or(0, |s| usize::from(s[0]));
+            let end = (offset + size.min(max_page)).min(rows.len());
+            std::future::ready(Ok(Page {
+                items: rows[offset..end].to_vec(),
+                paging_state: (end < rows.len()).then(|| vec![u8::try_from(end).unwrap()]),
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cursor_resumes_after_last_item() {
+        let rows: Vec<u32> = (0..10).collect();
+
+        // Short pages force reads to continue across paging states
+        let first = read_paged(Position::default(), 4, 100, |_| true, pages(&rows, 3))
+            .await
+            .unwrap();
+        let got: Vec<u32> = first.items.iter().map(|(r, _)| *r).collect();
+        assert_eq!(got, [0, 1, 2, 3]);
+        assert!(first.has_next_page);
+
+        let partition = Uuid::new_v4();
+        let cursor = Cursor::new(partition, first.items[3].1.clone()).encode();
+        let start = Cursor::resume(Some(&cursor), partition).unwrap();
+        let rest = read_paged(start, 10, 100, |r| r % 2 == 0, pages(&rows, 3))
+            .await
+            .unwrap();
+        let got: Vec<u32> = rest.items.iter().map(|(r, _)| *r).collect();
+        assert_eq!(got, [4, 6, 8]);
+        assert!(!rest.has_next_page);
+
+        assert!(Cursor::resume(Some(&cursor), Uuid::new_v4()).is_err());
+        assert!(Cursor::resume(Some("not-a-cursor"), partition).is_err());
+    }
+}
@@ -0,0 +1,96 @@
+//! # GraphQL Playground
+//!
+//! Served at `GET /graphql` only when `ENABLE_PLAYGROUND` is set. Outside
+//! local development it can be put behind HTTP basic auth with
+//! `PLAYGROUND_BASIC_AUTH=user:password`; browsers cannot attach the bearer
+//! tokens the API uses to a page load, so it has its own credentials.
+
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::api_keys::ApiKeyHasher;
+use crate::AppState;
+
+/// Realm shown by the browser's login prompt
+const REALM: &str = "Basic realm=\"GraphQL Playground\", charset=\"UTF-8\"";
+
+/// Basic auth credentials guarding the playground
+///
+/// Only a keyed hash of `user:password` is kept, so checks take the same
+/// time whichever byte differs.
+pub struct PlaygroundCredentials {
+    hasher: ApiKeyHasher,
+    expected: Vec<u8>,
+}
+
+impl PlaygroundCredentials {
+    /// Parse `user:password`; `None` when either part is empty
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (user, password) = raw.split_once(':')?;
+        if user.is_empty() || password.is_empty() {
+            return None;
+        }
+        let hasher = ApiKeyHasher::ephemeral();
+        let expected = hasher.hash(raw);
+        Some(Self { hasher, expected })
+    }
+
+    /// Whether the request carries these credentials in `Authorization`
+    #[must_use]
+    pub fn check(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Basic "))
+            .and_then(|encoded| STANDARD.decode(encoded.trim()).ok())
+            .and_then(|decoded| String::from_utf8(decoded).ok())
+            .is_some_and(|pair| self.hasher.verify(&pair, &self.expected))
+    }
+}
+
+/// GraphQL Playground HTML
+pub async fn graphql_playground(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let authorized = state.ctx.playground_auth.as_ref().is_none_or(|c| c.check(&headers));
+    if !authorized {
+        return (StatusCode::UNAUTHORIZED, [(header::WWW_AUTHENTICATE, REALM)]).into_response();
+    }
+
+    Html(async_graphql::http::playground_source(
+        async_graphql::http::GraphQLPlaygroundConfig::new("/graphql")
+            .subscription_endpoint("/graphql/ws"),
+    ))
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn basic(pair: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let value = format!("Basic {}", STANDARD.encode(pair));
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_str(&value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_credentials_check() {
+        let credentials = PlaygroundCredentials::parse("ops:s3cret:with-colon").unwrap();
+
+        assert!(credentials.check(&basic("ops:s3cret:with-colon")));
+        assert!(!credentials.check(&basic("ops:s3cret")));
+        assert!(!credentials.check(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn test_parse_rejects_incomplete_credentials() {
+        assert!(PlaygroundCredentials::parse("ops").is_none());
+        assert!(PlaygroundCredentials::parse("ops:").is_none());
+        assert!(PlaygroundCredentials::parse(":s3cret").is_none());
+    }
+}
@@ -0,0 +1,59 @@
+//! # Engagement Projections
+//!
+//! Rebuilds a convoy's leaderboard from its engagement event log, replacing
+//! whatever in-place updates left behind.
+
+use std::time::Instant;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::{ApiError, ApiResult};
+use crate::schema::RebuildProjectionsResult;
+use drone_domain::{EngagementLogEvent, EngagementProjection};
+
+/// Append an event when event sourcing is enabled
+pub async fn record(ctx: &ApiContext, event: EngagementLogEvent) -> ApiResult<()> {
+    if ctx.event_sourcing {
+        ctx.engagement_log_repo.append(&event).await?;
+    }
+    Ok(())
+}
+
+/// Replay the convoy's log and overwrite its leaderboard entries.
+///
+/// Drones with leaderboard rows but no logged engagements are left as they
+/// are; rows are only ever written, never removed.
+pub async fn rebuild_convoy(ctx: &ApiContext, convoy_id: Uuid) -> ApiResult<RebuildProjectionsResult> {
+    if !ctx.event_sourcing {
+        return Err(ApiError::InvalidInput(
+            "event sourcing is disabled; set EVENT_SOURCING_ENABLED to rebuild projections"
+                .to_string(),
+        ));
+    }
+
+    let started = Instant::now();
+    let events = ctx.engagement_log_repo.events(convoy_id).await?;
+    let projection = EngagementProjection::replay(&events);
+    let model = ctx.leaderboard_repo.scoring_model(convoy_id).await?;
+    let entries = projection.leaderboard(convoy_id, model, Utc::now());
+
+    for entry in &entries {
+        ctx.leaderboard_repo.restore_entry(entry).await?;
+    }
+
+    tracing::info!(
+        convoy_id = %convoy_id,
+        events = events.len(),
+        entries = entries.len(),
+        "Rebuilt leaderboard projection"
+    );
+
+    Ok(RebuildProjectionsResult {
+        convoy_id: convoy_id.into(),
+        events_replayed: events.len() as i32,
+        entries_written: entries.len() as i32,
+        duration_ms: started.elapsed().as_millis() as i32,
+    })
+}
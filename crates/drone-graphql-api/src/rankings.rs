@@ -0,0 +1,127 @@
+//! # Accuracy Rankings
+//!
+//! Weapon types ranked by hit rate within a convoy (`weaponLeaderboard`) and
+//! convoys ranked by aggregate accuracy (`convoyLeaderboard`), kept in their
+//! own Redis sorted sets beside the drone leaderboard.
+//!
+//! `createEngagement` counts each engagement once it is in the engagement
+//! store. Engagements written around the API (e.g. by `drone-importer`), or
+//! counts lost with the cache, are picked up by rebuilding the convoy from
+//! that store with `rebuildAccuracyRankings`.
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::ApiResult;
+use crate::schema::RebuildAccuracyRankingsResult;
+use drone_domain::{Engagement, WeaponType};
+use drone_persistence::AccuracyTally;
+
+/// Engagements read per page while rebuilding
+const REBUILD_PAGE_SIZE: usize = 500;
+
+/// Count a recorded engagement towards its weapon type and convoy
+///
+/// A cache failure is logged rather than failing the engagement; a rebuild
+/// restores the counts.
+pub async fn record(ctx: &ApiContext, engagement: &Engagement) {
+    if let Err(e) = ctx
+        .cache
+        .record_ranked_engagement(
+            engagement.convoy_id,
+            engagement.weapon_type.as_str(),
+            engagement.hit,
+        )
+        .await
+    {
+        tracing::warn!(
+            convoy_id = %engagement.convoy_id,
+            engagement_id = %engagement.engagement_id,
+            error = %e,
+            "Failed to update accuracy rankings"
+        );
+    }
+}
+
+/// Engagement counts per weapon type from `(weapon, hit)` outcomes, in
+/// weapon name order
+fn tally(outcomes: impl IntoIterator<Item = (WeaponType, bool)>) -> Vec<AccuracyTally> {
+    let mut counts: BTreeMap<&'static str, (i64, i64)> = BTreeMap::new();
+    for (weapon_type, hit) in outcomes {
+        let (total, hits) = counts.entry(weapon_type.as_str()).or_default();
+        *total += 1;
+        *hits += i64::from(hit);
+    }
+    counts
+        .into_iter()
+        .map(|(weapon, (total, hits))| AccuracyTally {
+            member: weapon.to_string(),
+            total_engagements: total,
+            successful_hits: hits,
+        })
+        .collect()
+}
+
+/// Recount a convoy's engagements and replace its weapon rankings and its
+/// entry in the convoy ranking
+pub async fn rebuild_convoy(
+    ctx: &ApiContext,
+    convoy_id: Uuid,
+) -> ApiResult<RebuildAccuracyRankingsResult> {
+    let started = Instant::now();
+    let mut outcomes = Vec::new();
+    let mut paging_state = None;
+    loop {
+        let page = ctx
+            .engagement_repo
+            .get_page(convoy_id, REBUILD_PAGE_SIZE, paging_state.as_deref())
+            .await?;
+        outcomes.extend(page.items.iter().map(|e| (e.weapon_type, e.hit)));
+        paging_state = page.paging_state;
+        if paging_state.is_none() {
+            break;
+        }
+    }
+
+    let scanned = outcomes.len();
+    let weapons = tally(outcomes);
+    ctx.cache.replace_convoy_rankings(convoy_id, &weapons).await?;
+
+    tracing::info!(
+        convoy_id = %convoy_id,
+        engagements = scanned,
+        weapon_types = weapons.len(),
+        "Rebuilt accuracy rankings"
+    );
+
+    Ok(RebuildAccuracyRankingsResult {
+        convoy_id: convoy_id.into(),
+        engagements_scanned: scanned as i32,
+        weapon_types: weapons.len() as i32,
+        duration_ms: started.elapsed().as_millis() as i32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tally_counts_per_weapon() {
+        let tallies = tally([
+            (WeaponType::Gbu12Paveway, true),
+            (WeaponType::Agm114Hellfire, false),
+            (WeaponType::Gbu12Paveway, false),
+            (WeaponType::Gbu12Paveway, true),
+        ]);
+
+        assert_eq!(tallies.len(), 2);
+        assert_eq!(tallies[0].member, "AGM-114_HELLFIRE");
+        assert_eq!((tallies[0].total_engagements, tallies[0].successful_hits), (1, 0));
+        assert_eq!(tallies[1].member, "GBU-12_PAVEWAY");
+        assert_eq!((tallies[1].total_engagements, tallies[1].successful_hits), (3, 2));
+    }
+}
@@ -0,0 +1,258 @@
+//! # Event Replay
+//!
+//! Recent broadcast events per convoy, kept in a Redis stream so a client
+//! that reconnects can fetch what it missed with `replayEvents`.
+//!
+//! [`ApiContext::publish`] stamps every event with a time-ordered ID (a
+//! version 7 UUID) before broadcasting it, so each subscription payload
+//! carries the cursor a client resumes from. Convoy events are then
+//! appended to the convoy's stream in the background. Only the publishing replica writes,
+//! so events shared over the back-plane are stored once. Telemetry and
+//! sensor tasks are not kept; a reconnecting HUD only needs the latest.
+
+use std::sync::{Arc, Mutex};
+
+use async_graphql::ID;
+use chrono::Utc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::backplane::BroadcastEvent;
+use crate::context::ApiContext;
+use crate::error::{ApiError, ApiResult};
+use crate::schema::{MissedEvent, MissedEventKind};
+
+/// Events queued for the stream writer before new ones are dropped
+const OUTBOUND_CAPACITY: usize = 4096;
+
+/// New time-ordered event ID
+#[must_use]
+pub fn new_event_id() -> ID {
+    ID(Uuid::now_v7().to_string())
+}
+
+/// Writes published convoy events to their convoy's event stream
+pub struct EventHistory {
+    outbound: mpsc::Sender<BroadcastEvent>,
+    /// Taken by [`EventHistory::start`]
+    pending: Mutex<Option<mpsc::Receiver<BroadcastEvent>>>,
+}
+
+impl EventHistory {
+    #[must_use]
+    pub fn new() -> Self {
+        let (outbound, pending) = mpsc::channel(OUTBOUND_CAPACITY);
+        Self {
+            outbound,
+            pending: Mutex::new(Some(pending)),
+        }
+    }
+
+    /// Queue a published event for its convoy's stream.
+    ///
+    /// Never blocks the publisher; events without a convoy are skipped, and
+    /// the event is dropped with a warning when Redis cannot keep up.
+    pub fn record(&self, event: &BroadcastEvent) {
+        if event.convoy_id().is_none() {
+            return;
+        }
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.outbound.try_send(event.clone()) {
+            tracing::warn!("Event replay queue full; event will not be replayable");
+        }
+    }
+
+    /// Append queued events to their convoy streams; call once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer was already started.
+    pub fn start(self: Arc<Self>, ctx: &ApiContext) -> JoinHandle<()> {
+        let mut outbound = self
+            .pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take()
+            .expect("event replay writer already started");
+        let ctx = ctx.clone();
+
+        tokio::spawn(async move {
+            while let Some(event) = outbound.recv().await {
+                let Some(convoy_id) = event.convoy_id().and_then(|id| Uuid::parse_str(id).ok()) else {
+                    continue;
+                };
+                let payload = match serde_json::to_string(&event) {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to encode replay event");
+                        continue;
+                    }
+                };
+                let now_ms = Utc::now().timestamp_millis();
+                if let Err(e) = ctx.cache.append_convoy_event(convoy_id, &payload, now_ms).await {
+                    tracing::warn!(convoy_id = %convoy_id, error = %e, "Failed to store replay event");
+                }
+            }
+        })
+    }
+}
+
+impl Default for EventHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A convoy's retained events after `since`, oldest first, and whether
+/// nothing between `since` and the first of them was lost.
+///
+/// # Errors
+///
+/// Returns an error if the event stream cannot be read.
+pub async fn missed_events(
+    ctx: &ApiContext,
+    convoy_id: Uuid,
+    since: Option<&str>,
+) -> ApiResult<(Vec<MissedEvent>, bool)> {
+    let stored = ctx
+        .cache
+        .get_convoy_events(convoy_id)
+        .await
+        .map_err(ApiError::from)?;
+    let events = stored
+        .iter()
+        .filter_map(|payload| serde_json::from_str::<BroadcastEvent>(payload).ok())
+        .collect();
+
+    let (events, complete) = events_after(events, since);
+    Ok((events.into_iter().filter_map(missed_event).collect(), complete))
+}
+
+/// Events after the one with ID `since`.
+///
+/// When `since` is not in the stream, either because it has not been
+/// written yet or because it was trimmed, fall back to the time in its ID.
+/// The result is complete only if the stream still reaches back that far.
+fn events_after(mut events: Vec<BroadcastEvent>, since: Option<&str>) -> (Vec<BroadcastEvent>, bool) {
+    let Some(since) = since else {
+        return (events, false);
+    };
+    if let Some(at) = events
+        .iter()
+        .position(|e| e.event_id().is_some_and(|id| id.as_str() == since))
+    {
+        return (events.split_off(at + 1), true);
+    }
+    let Some(since_ms) = id_millis(since) else {
+        return (events, false);
+    };
+
+    let complete = events
+        .first()
+        .and_then(BroadcastEvent::event_id)
+        .and_then(|id| id_millis(id))
+        .is_some_and(|first_ms| first_ms <= since_ms);
+    events.retain(|e| {
+        e.event_id()
+            .and_then(|id| id_millis(id))
+            .is_some_and(|ms| ms >= since_ms)
+    });
+    (events, complete)
+}
+
+/// Unix milliseconds embedded in a version 7 UUID event ID
+fn id_millis(id: &str) -> Option<u64> {
+    let (secs, nanos) = Uuid::parse_str(id).ok()?.get_timestamp()?.to_unix();
+    Some(secs * 1000 + u64::from(nanos / 1_000_000))
+}
+
+/// GraphQL shape of a stored event; `None` for kinds that are not kept
+fn missed_event(event: BroadcastEvent) -> Option<MissedEvent> {
+    let event_id = event.event_id()?.clone();
+    let mut missed = MissedEvent {
+        event_id,
+        kind: MissedEventKind::Engagement,
+        engagement: None,
+        leaderboard_update: None,
+        drone_status: None,
+        alert: None,
+        authorization: None,
+    };
+    match event {
+        BroadcastEvent::Engagement(e) => missed.engagement = Some(e),
+        BroadcastEvent::Leaderboard(e) => {
+            missed.kind = MissedEventKind::LeaderboardUpdate;
+            missed.leaderboard_update = Some(e);
+        }
+        BroadcastEvent::DroneStatus(e) => {
+            missed.kind = MissedEventKind::DroneStatus;
+            missed.drone_status = Some(e);
+        }
+        BroadcastEvent::Alert(e) => {
+            missed.kind = MissedEventKind::Alert;
+            missed.alert = Some(e);
+        }
+        BroadcastEvent::Authorization(e) => {
+            missed.kind = MissedEventKind::Authorization;
+            missed.authorization = Some(e);
+        }
+        BroadcastEvent::Telemetry(_) | BroadcastEvent::SensorTask(_) => return None,
+    }
+    Some(missed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{DroneStatus, DroneStatusEvent};
+
+    fn status_event(event_id: &ID) -> BroadcastEvent {
+        DroneStatusEvent {
+            convoy_id: ID::from("convoy-1"),
+            drone_id: ID::from("drone-1"),
+            callsign: "REAPER-01".to_string(),
+            old_status: DroneStatus::Preflight,
+            new_status: DroneStatus::Airborne,
+            timestamp: Utc::now(),
+            event_id: Some(event_id.clone()),
+        }
+        .into()
+    }
+
+    fn ids(events: &[BroadcastEvent]) -> Vec<ID> {
+        events.iter().filter_map(|e| e.event_id().cloned()).collect()
+    }
+
+    #[test]
+    fn test_resumes_after_known_event() {
+        let (a, b, c) = (new_event_id(), new_event_id(), new_event_id());
+        let events = vec![status_event(&a), status_event(&b), status_event(&c)];
+
+        let (after, complete) = events_after(events.clone(), Some(a.as_str()));
+        assert_eq!(ids(&after), vec![b, c]);
+        assert!(complete);
+
+        // Without a cursor everything retained comes back, flagged incomplete
+        let (all, complete) = events_after(events, None);
+        assert_eq!(all.len(), 3);
+        assert!(!complete);
+    }
+
+    #[test]
+    fn test_unknown_cursor_falls_back_to_its_time() {
+        let old = ID(Uuid::new_v7(uuid::Timestamp::from_unix(uuid::NoContext, 1_000, 0)).to_string());
+        let kept = new_event_id();
+        let events = vec![status_event(&kept)];
+
+        // The cursor was trimmed: later events come back, but not complete
+        let (after, complete) = events_after(events.clone(), Some(old.as_str()));
+        assert_eq!(ids(&after), vec![kept.clone()]);
+        assert!(!complete);
+
+        // The cursor is newer than anything stored: nothing to replay
+        let future = ID(Uuid::new_v7(uuid::Timestamp::from_unix(uuid::NoContext, 4_000_000_000, 0)).to_string());
+        let (after, complete) = events_after(events, Some(future.as_str()));
+        assert!(after.is_empty());
+        assert!(complete);
+    }
+}
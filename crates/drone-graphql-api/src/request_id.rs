@@ -0,0 +1,142 @@
+//! # Request Correlation
+//!
+//! Every HTTP request and WebSocket connection gets a request ID: the
+//! caller's `x-request-id` when it sent a usable one, otherwise a new
+//! UUIDv7. The ID is recorded on the request's tracing span, so resolver,
+//! repository and cache logs written while serving it carry it, and it is
+//! echoed in the `x-request-id` response header and in the extensions of
+//! every GraphQL error.
+
+use std::fmt;
+use std::sync::Arc;
+
+use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute};
+use async_graphql::Response as GraphQLResponse;
+use axum::extract::Request;
+use axum::http::{HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::Span;
+use uuid::Uuid;
+
+/// Header carrying the request ID in both directions
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest caller-supplied ID accepted
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Correlation ID of one HTTP request or WebSocket connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Generate a new, time-ordered ID
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(Uuid::now_v7().to_string())
+    }
+
+    /// Reuse a caller's ID if it is short and made of `[A-Za-z0-9._:-]`
+    #[must_use]
+    pub fn from_header(value: &HeaderValue) -> Option<Self> {
+        let value = value.to_str().ok()?;
+        let usable = !value.is_empty()
+            && value.len() <= MAX_REQUEST_ID_LEN
+            && value
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b':' | b'-'));
+        usable.then(|| Self(value.to_string()))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Middleware assigning the request ID; must wrap the trace layer so the
+/// request span can record it
+pub async fn assign(mut req: Request, next: Next) -> Response {
+    let id = req
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(RequestId::from_header)
+        .unwrap_or_else(RequestId::generate);
+    req.extensions_mut().insert(id.clone());
+
+    let mut response = next.run(req).await;
+    if let Ok(value) = HeaderValue::from_str(id.as_str()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Span for one HTTP request, tagged with its request ID
+pub fn make_span<B>(req: &axum::http::Request<B>) -> Span {
+    let id = req.extensions().get::<RequestId>().map(RequestId::as_str).unwrap_or_default();
+    tracing::info_span!(
+        "request",
+        method = %req.method(),
+        uri = %req.uri(),
+        request_id = %id,
+    )
+}
+
+/// Schema extension adding `request_id` to the extensions of every error
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorRequestId;
+
+impl ExtensionFactory for ErrorRequestId {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ErrorRequestIdExtension)
+    }
+}
+
+struct ErrorRequestIdExtension;
+
+#[async_trait::async_trait]
+impl Extension for ErrorRequestIdExtension {
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> GraphQLResponse {
+        let mut response = next.run(ctx, operation_name).await;
+        if let Some(id) = ctx.data_opt::<RequestId>() {
+            for error in &mut response.errors {
+                error
+                    .extensions
+                    .get_or_insert_with(Default::default)
+                    .set("request_id", id.as_str());
+            }
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caller_ids_are_reused_only_when_safe() {
+        let id = |s: &'static str| RequestId::from_header(&HeaderValue::from_static(s));
+
+        assert_eq!(id("abc-123_x.y:z").unwrap().as_str(), "abc-123_x.y:z");
+        assert!(id("").is_none());
+        assert!(id("two words").is_none());
+        assert!(id("line\tbreak").is_none());
+        let long = HeaderValue::from_str(&"a".repeat(MAX_REQUEST_ID_LEN + 1)).unwrap();
+        assert!(RequestId::from_header(&long).is_none());
+
+        let generated = RequestId::generate();
+        assert!(Uuid::parse_str(generated.as_str()).is_ok());
+    }
+}
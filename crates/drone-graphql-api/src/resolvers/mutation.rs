@@ -2,13 +2,30 @@
 //!
 //! Write operations for the drone convoy API.
 
-use async_graphql::{Context, Object, Result, ID};
+use async_graphql::{Context, ErrorExtensions, Json, Object, Result, ID};
 use chrono::Utc;
 use uuid::Uuid;
 
+use crate::artifacts;
+use crate::auth::{self, Role, RoleGuard};
+use crate::authorization;
 use crate::context::ApiContext;
-use crate::error::ApiError;
+use crate::deconfliction;
+use crate::error::{ApiError, ApiResult};
+use crate::formation;
+use crate::projections;
+use crate::rankings;
 use crate::schema::*;
+use crate::search;
+use crate::snapshot::{self, ConvoySnapshot};
+use crate::validation;
+use crate::weather;
+
+/// Attempts to decrement a contended weapon before giving up
+const EXPEND_ATTEMPTS: usize = 3;
+
+/// Largest telemetry `extensions` object accepted, serialized as JSON
+const MAX_TELEMETRY_EXTENSIONS_BYTES: usize = 4096;
 
 /// GraphQL Mutation root
 pub struct MutationRoot;
@@ -22,84 +39,46 @@ impl MutationRoot {
     /// Record a hit/miss engagement for accuracy tracking
     ///
     /// Updates the drone's accuracy counters and recalculates leaderboard position.
-    /// This is the primary mutation for leaderboard updates.
-    #[graphql(name = "recordEngagement")]
+    /// This is the primary mutation for leaderboard updates. A round is taken
+    /// from the drone's inventory of `weaponType`; engagements with an
+    /// expended or jammed weapon are rejected. Requires the OPERATOR role.
+    #[graphql(name = "recordEngagement", guard = "RoleGuard::new(Role::Operator)")]
     async fn record_engagement(
         &self,
         ctx: &Context<'_>,
         input: RecordEngagementInput,
     ) -> Result<RecordEngagementResult> {
         let api_ctx = ctx.data::<ApiContext>()?;
+        validation::check("input", &input).map_err(|e| e.extend())?;
         let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
         let drone_uuid = Uuid::parse_str(&input.drone_id).map_err(ApiError::from)?;
+        api_ctx
+            .authorize_convoy_drone(&auth::claims(ctx), convoy_uuid, drone_uuid)
+            .await?;
 
-        tracing::info!(
-            convoy_id = %convoy_uuid,
-            drone_id = %drone_uuid,
-            hit = input.hit,
-            "Recording engagement"
-        );
-
-        // Use update_entry which handles incrementing counters internally
-        let domain_entry = api_ctx
-            .leaderboard_repo
-            .update_entry(
-                convoy_uuid,
-                drone_uuid,
-                "UNKNOWN", // TODO: Fetch callsign from drone repo
-                drone_domain::PlatformType::Mq9Reaper,
-                input.hit,
-            )
-            .await
-            .map_err(ApiError::from)?;
-
-        // Build GraphQL leaderboard entry from domain entry
-        let entry = LeaderboardEntry::from(domain_entry.clone());
-
-        // Broadcast event for subscriptions
-        let event = EngagementEvent {
-            convoy_id: ID(input.convoy_id.clone()),
-            drone_id: ID(input.drone_id.clone()),
-            callsign: entry.callsign.clone(),
-            hit: input.hit,
-            weapon_type: input.weapon_type.unwrap_or(WeaponType::Agm114Hellfire),
-            new_accuracy_pct: entry.accuracy_pct,
-            timestamp: Utc::now(),
-        };
-        let _ = api_ctx.engagement_tx.send(event);
-
-        // Broadcast leaderboard update
-        let leaderboard_event = LeaderboardUpdateEvent {
-            convoy_id: ID(input.convoy_id.clone()),
-            drone_id: ID(input.drone_id.clone()),
-            callsign: entry.callsign.clone(),
-            new_rank: entry.rank,
-            old_rank: None, // Simplified - not tracking old rank
-            accuracy_pct: entry.accuracy_pct,
-            change_type: RankChangeType::ScoreUpdate,
-            timestamp: Utc::now(),
-        };
-        let _ = api_ctx.leaderboard_tx.send(leaderboard_event);
-
-        Ok(RecordEngagementResult {
-            success: true,
-            entry,
-            new_rank: domain_entry.rank as i32,
-            rank_change: 0, // Simplified
-            new_accuracy_pct: domain_entry.accuracy_pct,
-        })
+        Ok(record_hit(api_ctx, convoy_uuid, input, Uuid::new_v4()).await?)
     }
 
     /// Create a full engagement record with target details
-    #[graphql(name = "createEngagement")]
+    ///
+    /// `authorizationCode` must come from an approved, unexpired engagement
+    /// authorization for the same drone, weapon and target type; it is
+    /// consumed by the engagement. Range and release height must fall
+    /// within the weapon's envelope unless `envelopeOverride` is set for an
+    /// EXERCISE or TEST convoy; violations are written to the journal.
+    /// Requires the OPERATOR role.
+    #[graphql(name = "createEngagement", guard = "RoleGuard::new(Role::Operator)")]
     async fn create_engagement(
         &self,
         ctx: &Context<'_>,
         input: CreateEngagementInput,
     ) -> Result<Engagement> {
         let api_ctx = ctx.data::<ApiContext>()?;
+        validation::check("input", &input).map_err(|e| e.extend())?;
+        let claims = auth::claims(ctx);
         let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
         let drone_uuid = Uuid::parse_str(&input.drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy_drone(&claims, convoy_uuid, drone_uuid).await?;
         let engagement_id = Uuid::new_v4();
 
         tracing::info!(
@@ -111,6 +90,31 @@ impl MutationRoot {
             "Creating engagement record"
         );
 
+        let range_km = calculate_distance(
+            input.shooter_position.latitude,
+            input.shooter_position.longitude,
+            input.target.coordinates.latitude,
+            input.target.coordinates.longitude,
+        );
+
+        // Check before consuming the approval so a jammed or empty weapon,
+        // a stale target, a prohibited location or an impossible shot does
+        // not burn it
+        let flagged = check_geofences(api_ctx, convoy_uuid, &input.target.coordinates)
+            .await
+            .map_err(|e| e.extend())?;
+        let overridden = check_envelope(api_ctx, &claims, convoy_uuid, drone_uuid, &input, range_km)
+            .await
+            .map_err(|e| e.extend())?;
+        ensure_weapon_ready(api_ctx, drone_uuid, input.weapon_type.into()).await?;
+        let target = match input.target_id.as_deref() {
+            Some(target_id) => Some(tracked_target(api_ctx, convoy_uuid, target_id, &input).await?),
+            None => None,
+        };
+
+        let approval =
+            consume_authorization(api_ctx, convoy_uuid, drone_uuid, &input, engagement_id).await?;
+
         // Record the hit/miss for accuracy tracking
         let record_input = RecordEngagementInput {
             convoy_id: input.convoy_id.clone(),
@@ -119,25 +123,115 @@ impl MutationRoot {
             weapon_type: Some(input.weapon_type),
             target_type: Some(input.target.target_type),
             range_km: None,
+            predicted_pk: input.predicted_pk,
         };
-        let _ = self.record_engagement(ctx, record_input).await?;
+        let recorded = record_hit(api_ctx, convoy_uuid, record_input, engagement_id).await?;
 
-        // Calculate range
-        let range_km = calculate_distance(
-            input.shooter_position.latitude,
-            input.shooter_position.longitude,
+        let engaged_at = Utc::now();
+        let damage_assessment = if input.hit {
+            DamageAssessment::PendingBda
+        } else {
+            DamageAssessment::Missed
+        };
+        let target_coords = drone_domain::Coordinates::new(
             input.target.coordinates.latitude,
             input.target.coordinates.longitude,
+            input.target.coordinates.altitude_m,
+        );
+        let mut shooter_coords = drone_domain::Coordinates::new(
+            input.shooter_position.latitude,
+            input.shooter_position.longitude,
+            input.shooter_position.altitude_m,
         );
+        shooter_coords.heading_deg = input.shooter_position.heading_deg as f32;
+        shooter_coords.speed_mps = input.shooter_position.speed_mps as f32;
+
+        let record = drone_domain::Engagement {
+            convoy_id: convoy_uuid,
+            engaged_at,
+            engagement_id,
+            drone_id: drone_uuid,
+            drone_callsign: "UNKNOWN".to_string(),
+            weapon_type: input.weapon_type.into(),
+            weapon_serial: String::new(),
+            target: drone_domain::TargetInfo {
+                target_id: target.as_ref().map_or(Uuid::nil(), |t| t.target_id),
+                target_type: input.target.target_type.into(),
+                coordinates: target_coords,
+                confidence: input.target.confidence as f32,
+                threat_level: input
+                    .target
+                    .threat_level
+                    .map_or(drone_domain::ThreatLevel::Unknown, Into::into),
+            },
+            authorization_code: input.authorization_code.clone(),
+            authorized_by: approval.decided_by.unwrap_or_default(),
+            roe_compliance: input.roe_compliance,
+            // Impact assumed at the target until BDA says otherwise
+            result: drone_domain::EngagementResult {
+                impact_time: engaged_at,
+                impact_coords: target_coords,
+                damage_assessment: if input.hit {
+                    drone_domain::DamageAssessment::PendingBda
+                } else {
+                    drone_domain::DamageAssessment::Missed
+                },
+                collateral_risk: drone_domain::CollateralRisk::None,
+            },
+            hit: input.hit,
+            waypoint_number: 0,
+            shooter_position: shooter_coords,
+            range_to_target_km: range_km as f32,
+            predicted_pk: input.predicted_pk.map(|pk| pk as f32),
+            bda_status: "PENDING".to_string(),
+            bda_notes: None,
+            sequence: recorded.sequence,
+        };
+
+        api_ctx
+            .engagement_repo
+            .record(&record)
+            .await
+            .map_err(ApiError::from)?;
+        search::index_engagement(api_ctx, &record).await;
+        rankings::record(api_ctx, &record).await;
+        if let Some(violation) = &overridden {
+            let text = format!("Envelope override: {violation}");
+            journal_system_entry(api_ctx, convoy_uuid, engagement_id, text).await;
+        }
 
-        // TODO: Persist to engagement repository
+        if let Some(target) = &target {
+            api_ctx
+                .target_repo
+                .record_engagement(
+                    convoy_uuid,
+                    target.target_id,
+                    engagement_id,
+                    target.status_after_engagement(),
+                )
+                .await
+                .map_err(ApiError::from)?;
+        }
+
+        for violation in &flagged {
+            api_ctx.raise_alert(AlertEvent {
+                alert_id: ID(Uuid::new_v4().to_string()),
+                convoy_id: ID(input.convoy_id.clone()),
+                drone_id: Some(ID(input.drone_id.clone())),
+                severity: AlertSeverity::Warning,
+                alert_type: "NO_STRIKE_ZONE".to_string(),
+                message: format!("Engagement {engagement_id}: {violation}"),
+                timestamp: engaged_at,
+                event_id: None,
+            }).await;
+        }
 
         Ok(Engagement {
             engagement_id: ID(engagement_id.to_string()),
             convoy_id: ID(input.convoy_id),
             drone_id: ID(input.drone_id),
             drone_callsign: "UNKNOWN".to_string(),
-            engaged_at: Utc::now(),
+            engaged_at,
             weapon_type: input.weapon_type,
             target_type: input.target.target_type,
             target_coordinates: Coordinates {
@@ -156,223 +250,1349 @@ impl MutationRoot {
             },
             range_km: range_km as f32,
             hit: input.hit,
-            damage_assessment: if input.hit {
-                DamageAssessment::PendingBda
-            } else {
-                DamageAssessment::Missed
-            },
-            authorization_code: input.authorization_code,
+            damage_assessment,
+            authorization_code: Some(input.authorization_code),
             roe_compliant: input.roe_compliance,
+            target_id: target.map(|t| ID(t.target_id.to_string())),
+            sequence: recorded.sequence,
+            predicted_pk: input.predicted_pk.map(|pk| pk as f32),
+            bda_notes: None,
         })
     }
 
-    /// Update battle damage assessment for an engagement
-    #[graphql(name = "updateBda")]
-    async fn update_bda(&self, _ctx: &Context<'_>, input: UpdateBdaInput) -> Result<Engagement> {
+    // =========================================================================
+    // ENGAGEMENT AUTHORIZATION MUTATIONS
+    // =========================================================================
+
+    /// Request approval for an engagement
+    ///
+    /// Creates a pending request for a COMMANDER to approve or deny; the
+    /// approval carries the authorization code `createEngagement` requires.
+    /// Requires the OPERATOR role.
+    #[graphql(
+        name = "requestEngagementAuthorization",
+        guard = "RoleGuard::new(Role::Operator)"
+    )]
+    async fn request_engagement_authorization(
+        &self,
+        ctx: &Context<'_>,
+        input: RequestEngagementAuthorizationInput,
+    ) -> Result<EngagementAuthorization> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        validation::check("input", &input).map_err(|e| e.extend())?;
+        let claims = auth::claims(ctx);
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        let drone_uuid = Uuid::parse_str(&input.drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy_drone(&claims, convoy_uuid, drone_uuid).await?;
+        if input.justification.trim().is_empty() {
+            return Err(ApiError::InvalidInput("justification is required".to_string()).into());
+        }
+
+        let request = drone_domain::EngagementAuthorization {
+            convoy_id: convoy_uuid,
+            request_id: Uuid::new_v4(),
+            drone_id: drone_uuid,
+            weapon_type: input.weapon_type.into(),
+            target_type: input.target.target_type.into(),
+            target_coordinates: drone_domain::Coordinates::new(
+                input.target.coordinates.latitude,
+                input.target.coordinates.longitude,
+                input.target.coordinates.altitude_m,
+            ),
+            requested_by: caller_name(input.requested_by, &claims),
+            justification: input.justification,
+            requested_at: Utc::now(),
+            status: drone_domain::AuthorizationStatus::Pending,
+            decided_by: None,
+            decided_at: None,
+            decision_notes: None,
+            expires_at: None,
+            engagement_id: None,
+        };
+
         tracing::info!(
-            engagement_id = %input.engagement_id,
-            damage_assessment = ?input.damage_assessment,
-            "Updating BDA"
+            request_id = %request.request_id,
+            convoy_id = %convoy_uuid,
+            drone_id = %drone_uuid,
+            weapon = ?input.weapon_type,
+            "Requesting engagement authorization"
         );
 
-        // TODO: Implement with engagement repository
+        api_ctx
+            .authorization_repo
+            .create(&request)
+            .await
+            .map_err(ApiError::from)?;
 
-        Err(async_graphql::Error::new("Not implemented"))
+        let request = EngagementAuthorization::from(request);
+        api_ctx.publish(request.clone());
+        Ok(request)
     }
 
-    // =========================================================================
-    // LEADERBOARD MUTATIONS
-    // =========================================================================
+    /// Approve a pending engagement authorization
+    ///
+    /// Returns the request with its signed authorization code, valid for a
+    /// single engagement until `expiresAt`. Requires the COMMANDER role.
+    #[graphql(
+        name = "approveEngagementAuthorization",
+        guard = "RoleGuard::new(Role::Commander)"
+    )]
+    async fn approve_engagement_authorization(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Authorization request ID")]
+        request_id: ID,
+        #[graphql(desc = "Decision notes")]
+        notes: Option<String>,
+        #[graphql(desc = "Commander approving the request (defaults to the caller's role)")]
+        decided_by: Option<String>,
+    ) -> Result<EngagementAuthorization> {
+        let decision = Decision {
+            status: drone_domain::AuthorizationStatus::Approved,
+            notes,
+            decided_by,
+        };
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        Ok(decide_authorization(api_ctx, &claims, &convoy_id, &request_id, decision).await?)
+    }
 
-    /// Force rebuild of leaderboard cache from source data
-    #[graphql(name = "rebuildLeaderboard")]
-    async fn rebuild_leaderboard(
+    /// Deny a pending engagement authorization
+    ///
+    /// Requires the COMMANDER role.
+    #[graphql(
+        name = "denyEngagementAuthorization",
+        guard = "RoleGuard::new(Role::Commander)"
+    )]
+    async fn deny_engagement_authorization(
         &self,
         ctx: &Context<'_>,
         #[graphql(desc = "Convoy ID")]
         convoy_id: ID,
-    ) -> Result<RebuildLeaderboardResult> {
+        #[graphql(desc = "Authorization request ID")]
+        request_id: ID,
+        #[graphql(desc = "Reason for the denial")]
+        reason: String,
+        #[graphql(desc = "Commander denying the request (defaults to the caller's role)")]
+        decided_by: Option<String>,
+    ) -> Result<EngagementAuthorization> {
+        let decision = Decision {
+            status: drone_domain::AuthorizationStatus::Denied,
+            notes: Some(reason),
+            decided_by,
+        };
         let api_ctx = ctx.data::<ApiContext>()?;
-        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        let claims = auth::claims(ctx);
+        Ok(decide_authorization(api_ctx, &claims, &convoy_id, &request_id, decision).await?)
+    }
 
-        tracing::info!(convoy_id = %convoy_uuid, "Rebuilding leaderboard");
+    /// Update battle damage assessment for an engagement
+    ///
+    /// A DESTROYED assessment marks the engagement's tracked target destroyed.
+    /// Requires the OPERATOR role.
+    #[graphql(name = "updateBda", guard = "RoleGuard::new(Role::Operator)")]
+    async fn update_bda(&self, ctx: &Context<'_>, input: UpdateBdaInput) -> Result<Engagement> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+        let engagement_uuid = Uuid::parse_str(&input.engagement_id).map_err(ApiError::from)?;
 
-        let start = std::time::Instant::now();
+        tracing::info!(
+            engagement_id = %input.engagement_id,
+            damage_assessment = ?input.damage_assessment,
+            "Updating BDA"
+        );
 
-        // Get current entries (rebuild would recalculate from engagements)
-        let entries = api_ctx
-            .leaderboard_repo
-            .get_leaderboard(convoy_uuid, 100)
+        let mut engagement = api_ctx
+            .engagement_repo
+            .get(convoy_uuid, engagement_uuid)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::NotFound {
+                entity_type: "Engagement".to_string(),
+                id: input.engagement_id.clone(),
+            })?;
+
+        let assessment = drone_domain::DamageAssessment::from(input.damage_assessment);
+        let hit_assessed = matches!(
+            assessment,
+            drone_domain::DamageAssessment::Destroyed | drone_domain::DamageAssessment::Damaged
+        );
+        if hit_assessed && !engagement.hit {
+            return Err(ApiError::InvalidInput(format!(
+                "engagement {engagement_uuid} was recorded as a miss"
+            ))
+            .into());
+        }
+
+        projections::record(
+            api_ctx,
+            drone_domain::EngagementLogEvent::new(
+                convoy_uuid,
+                engagement.drone_id,
+                drone_domain::EngagementLogPayload::BdaUpdated {
+                    engagement_id: engagement_uuid,
+                    assessment,
+                },
+            ),
+        )
+        .await?;
+
+        let bda_status = bda_status_str(assessment);
+        api_ctx
+            .engagement_repo
+            .update_bda(&engagement, bda_status, input.notes.as_deref())
             .await
             .map_err(ApiError::from)?;
 
-        let duration_ms = start.elapsed().as_millis() as i64;
+        engagement.bda_status = bda_status.to_string();
+        engagement.bda_notes = input.notes;
+        if engagement.hit {
+            engagement.result.damage_assessment = assessment;
+        }
 
-        Ok(RebuildLeaderboardResult {
-            success: true,
-            entries_processed: entries.len() as i32,
-            duration_ms,
-        })
-    }
+        let target_id = engagement.target.target_id;
+        if !target_id.is_nil() {
+            if let Some(target) = api_ctx
+                .target_repo
+                .get(convoy_uuid, target_id)
+                .await
+                .map_err(ApiError::from)?
+            {
+                let status = target.status_after_assessment(assessment);
+                if status != target.status {
+                    api_ctx
+                        .target_repo
+                        .set_status(convoy_uuid, target_id, status)
+                        .await
+                        .map_err(ApiError::from)?;
+                }
+            }
+        }
 
-    // =========================================================================
-    // DRONE MUTATIONS
-    // =========================================================================
+        Ok(engagement.into())
+    }
 
-    /// Update drone state
-    #[graphql(name = "updateDroneState")]
-    async fn update_drone_state(
+    /// Correct the hit/miss of a logged engagement and rebuild the leaderboard
+    ///
+    /// Appends a correction to the engagement event log, then replays the
+    /// convoy's log. Requires event sourcing and the COMMANDER role.
+    #[graphql(name = "correctEngagement", guard = "RoleGuard::new(Role::Commander)")]
+    async fn correct_engagement(
         &self,
         ctx: &Context<'_>,
-        input: UpdateDroneStateInput,
-    ) -> Result<Drone> {
-        let _api_ctx = ctx.data::<ApiContext>()?;
+        input: CorrectEngagementInput,
+    ) -> Result<RebuildProjectionsResult> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+        let drone_uuid = Uuid::parse_str(&input.drone_id).map_err(ApiError::from)?;
+        let engagement_uuid = Uuid::parse_str(&input.engagement_id).map_err(ApiError::from)?;
+        if !api_ctx.event_sourcing {
+            return Err(ApiError::InvalidInput("event sourcing is disabled".to_string()).into());
+        }
+        if input.reason.trim().is_empty() {
+            return Err(ApiError::InvalidInput("reason is required".to_string()).into());
+        }
+
+        let events = api_ctx
+            .engagement_log_repo
+            .events(convoy_uuid)
+            .await
+            .map_err(ApiError::from)?;
+        if !drone_domain::EngagementProjection::replay(&events).contains(drone_uuid, engagement_uuid) {
+            return Err(ApiError::NotFound {
+                entity_type: "Engagement".to_string(),
+                id: input.engagement_id,
+            }
+            .into());
+        }
 
         tracing::info!(
-            convoy_id = %input.convoy_id,
-            drone_id = %input.drone_id,
-            "Updating drone state"
+            convoy_id = %convoy_uuid,
+            engagement_id = %engagement_uuid,
+            hit = input.hit,
+            "Correcting engagement"
         );
 
-        // TODO: Implement with drone repository
+        projections::record(
+            api_ctx,
+            drone_domain::EngagementLogEvent::new(
+                convoy_uuid,
+                drone_uuid,
+                drone_domain::EngagementLogPayload::CorrectionApplied {
+                    engagement_id: engagement_uuid,
+                    hit: input.hit,
+                    reason: input.reason,
+                    applied_by: caller_name(input.applied_by, &claims),
+                },
+            ),
+        )
+        .await?;
 
-        Ok(Drone {
-            drone_id: input.drone_id.clone(),
-            convoy_id: input.convoy_id.clone(),
-            tail_number: "AF-001".to_string(),
-            callsign: "REAPER-01".to_string(),
-            platform_type: PlatformType::Mq9Reaper,
-            status: input.status.unwrap_or(DroneStatus::Airborne),
-            current_position: input.position.map(|p| Coordinates {
-                latitude: p.latitude,
-                longitude: p.longitude,
-                altitude_m: p.altitude_m,
-                heading_deg: p.heading_deg as f32,
-                speed_mps: p.speed_mps as f32,
-            }).unwrap_or(Coordinates {
-                latitude: 34.5553,
-                longitude: 69.2075,
-                altitude_m: 5000.0,
-                heading_deg: 45.0,
-                speed_mps: 80.0,
-            }),
-            fuel_remaining_pct: input.fuel_pct.unwrap_or(75.0) as f32,
-            accuracy_pct: 92.3,
-            total_engagements: 13,
-            successful_hits: 12,
-            current_waypoint: input.current_waypoint.unwrap_or(15),
-            total_waypoints: 25,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-        })
+        Ok(projections::rebuild_convoy(api_ctx, convoy_uuid).await?)
     }
 
-    // =========================================================================
-    // TELEMETRY MUTATIONS
-    // =========================================================================
-
-    /// Record telemetry data point
-    #[graphql(name = "recordTelemetry")]
-    async fn record_telemetry(
+    /// Rebuild a convoy's leaderboard from its engagement event log
+    ///
+    /// Overwrites the leaderboard entries of every drone in the log.
+    /// Requires event sourcing and the ADMIN role.
+    #[graphql(name = "rebuildProjections", guard = "RoleGuard::new(Role::Admin)")]
+    async fn rebuild_projections(
         &self,
-        _ctx: &Context<'_>,
-        input: CreateTelemetryInput,
-    ) -> Result<TelemetrySnapshot> {
-        tracing::debug!(drone_id = %input.drone_id, "Recording telemetry");
-
-        // TODO: Implement with telemetry repository
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<RebuildProjectionsResult> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
 
-        Ok(TelemetrySnapshot {
-            drone_id: ID(input.drone_id),
-            recorded_at: Utc::now(),
-            position: Coordinates {
-                latitude: input.position.latitude,
-                longitude: input.position.longitude,
-                altitude_m: input.position.altitude_m,
-                heading_deg: input.position.heading_deg as f32,
-                speed_mps: input.position.speed_mps as f32,
-            },
-            fuel_remaining_pct: input.fuel_pct as f32,
-            current_waypoint: input.current_waypoint,
-            velocity_mps: input.velocity_mps as f32,
-            mesh_connectivity: input.mesh_connectivity as f32,
-            distance_to_next_km: 0.0,
-        })
+        Ok(projections::rebuild_convoy(api_ctx, convoy_uuid).await?)
     }
 
     // =========================================================================
-    // CONVOY MUTATIONS
+    // TARGET MUTATIONS
     // =========================================================================
 
-    /// Create a new convoy
-    #[graphql(name = "createConvoy")]
-    async fn create_convoy(&self, _ctx: &Context<'_>, input: CreateConvoyInput) -> Result<Convoy> {
-        let convoy_id = Uuid::new_v4();
+    /// Report a target detection
+    ///
+    /// Creates a DETECTED target, or with `targetId` updates an existing
+    /// track's position and moves a DETECTED target to TRACKED. Requires the
+    /// OPERATOR role.
+    #[graphql(name = "reportTarget", guard = "RoleGuard::new(Role::Operator)")]
+    async fn report_target(&self, ctx: &Context<'_>, input: ReportTargetInput) -> Result<Target> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        validation::check("input", &input).map_err(|e| e.extend())?;
+        let claims = auth::claims(ctx);
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
 
-        tracing::info!(
-            convoy_id = %convoy_id,
-            callsign = %input.callsign,
-            "Creating convoy"
+        let now = Utc::now();
+        let coordinates = drone_domain::Coordinates::new(
+            input.target.coordinates.latitude,
+            input.target.coordinates.longitude,
+            input.target.coordinates.altitude_m,
         );
+        let confidence = input.target.confidence as f32;
+        let threat_level = input
+            .target
+            .threat_level
+            .map_or(drone_domain::ThreatLevel::Unknown, Into::into);
 
-        // TODO: Implement with convoy repository
-
-        Ok(Convoy {
-            convoy_id: ID(convoy_id.to_string()),
-            callsign: input.callsign,
-            mission_type: input.mission_type,
-            status: ConvoyStatus::Planning,
-            aor_name: input.aor_name,
-            aor_center: Coordinates {
-                latitude: input.aor_center.latitude,
-                longitude: input.aor_center.longitude,
-                altitude_m: input.aor_center.altitude_m,
-                heading_deg: 0.0,
-                speed_mps: 0.0,
+        let target = match input.target_id {
+            Some(target_id) => {
+                let target_uuid = Uuid::parse_str(&target_id).map_err(ApiError::from)?;
+                let mut target = api_ctx
+                    .target_repo
+                    .get(convoy_uuid, target_uuid)
+                    .await
+                    .map_err(ApiError::from)?
+                    .ok_or_else(|| ApiError::NotFound {
+                        entity_type: "Target".to_string(),
+                        id: target_id,
+                    })?;
+                target.redetect(coordinates, confidence, threat_level, now);
+                target
+            }
+            None => drone_domain::Target {
+                convoy_id: convoy_uuid,
+                target_id: Uuid::new_v4(),
+                target_type: input.target.target_type.into(),
+                coordinates,
+                confidence,
+                threat_level,
+                status: drone_domain::TargetStatus::Detected,
+                reported_by: caller_name(input.reported_by, &claims),
+                first_detected_at: now,
+                last_updated_at: now,
+                engagement_ids: Vec::new(),
             },
-            aor_radius_km: input.aor_radius_km as f32,
-            drone_count: 0,
-            commanding_unit: input.commanding_unit,
-            mission_start: None,
-            mission_end: None,
-            created_at: Utc::now(),
-        })
-    }
+        };
+
+        api_ctx
+            .target_repo
+            .upsert(&target)
+            .await
+            .map_err(ApiError::from)?;
 
-    /// Update convoy status
-    #[graphql(name = "updateConvoyStatus")]
-    async fn update_convoy_status(
-        &self,
-        _ctx: &Context<'_>,
-        input: UpdateConvoyStatusInput,
-    ) -> Result<Convoy> {
         tracing::info!(
-            convoy_id = %input.convoy_id,
-            status = ?input.status,
-            "Updating convoy status"
+            target_id = %target.target_id,
+            convoy_id = %convoy_uuid,
+            status = ?target.status,
+            "Target reported"
         );
 
-        // TODO: Implement with convoy repository
-
-        Err(async_graphql::Error::new("Not implemented"))
+        Ok(target.into())
     }
 
-    // =========================================================================
-    // WAYPOINT MUTATIONS
-    // =========================================================================
-
-    /// Create waypoints for a drone
-    #[graphql(name = "createWaypoints")]
-    async fn create_waypoints(
+    /// Hand a target track from one drone to another
+    ///
+    /// Both drones must belong to the convoy and be airborne, e.g. when the
+    /// tracking drone goes bingo fuel and a wingman takes over. Requires the
+    /// OPERATOR role.
+    #[graphql(name = "handoffTarget", guard = "RoleGuard::new(Role::Operator)")]
+    async fn handoff_target(
         &self,
-        _ctx: &Context<'_>,
-        input: CreateWaypointsInput,
-    ) -> Result<Vec<Waypoint>> {
-        tracing::info!(
-            drone_id = %input.drone_id,
-            count = input.waypoints.len(),
-            "Creating waypoints"
-        );
+        ctx: &Context<'_>,
+        input: HandoffTargetInput,
+    ) -> Result<TargetHandoff> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        let target_uuid = Uuid::parse_str(&input.target_id).map_err(ApiError::from)?;
+        let from_uuid = Uuid::parse_str(&input.from_drone_id).map_err(ApiError::from)?;
+        let to_uuid = Uuid::parse_str(&input.to_drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+
+        if from_uuid == to_uuid {
+            return Err(ApiError::InvalidInput(
+                "fromDroneId and toDroneId must be different drones".to_string(),
+            )
+            .into());
+        }
+
+        api_ctx
+            .target_repo
+            .get(convoy_uuid, target_uuid)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::NotFound {
+                entity_type: "Target".to_string(),
+                id: input.target_id.clone(),
+            })?;
+
+        for drone_uuid in [from_uuid, to_uuid] {
+            let drone = api_ctx
+                .drone_repo
+                .get_status(convoy_uuid, drone_uuid)
+                .await
+                .map_err(ApiError::from)?
+                .ok_or_else(|| ApiError::NotFound {
+                    entity_type: "Drone".to_string(),
+                    id: drone_uuid.to_string(),
+                })?;
+            if !drone.status.is_airborne() {
+                return Err(ApiError::InvalidInput(format!(
+                    "drone {} is {} and cannot take part in a hand-off",
+                    drone.callsign,
+                    drone.status.as_str()
+                ))
+                .into());
+            }
+        }
+
+        let handoff = drone_domain::TargetHandoff {
+            convoy_id: convoy_uuid,
+            target_id: target_uuid,
+            handed_off_at: Utc::now(),
+            handoff_id: Uuid::new_v4(),
+            from_drone_id: from_uuid,
+            to_drone_id: to_uuid,
+            reason: input.reason,
+            handed_off_by: caller_name(None, &claims),
+        };
+
+        api_ctx
+            .target_repo
+            .record_handoff(&handoff)
+            .await
+            .map_err(ApiError::from)?;
+
+        tracing::info!(
+            target_id = %target_uuid,
+            convoy_id = %convoy_uuid,
+            from_drone_id = %from_uuid,
+            to_drone_id = %to_uuid,
+            "Target handed off"
+        );
+
+        Ok(handoff.into())
+    }
+
+    // =========================================================================
+    // NO-STRIKE ZONE MUTATIONS
+    // =========================================================================
+
+    /// Define a no-strike zone for a convoy
+    ///
+    /// `createEngagement` rejects targets inside BLOCK zones and raises an
+    /// alert for targets inside FLAG zones. Requires the COMMANDER role.
+    #[graphql(name = "createNoStrikeZone", guard = "RoleGuard::new(Role::Commander)")]
+    async fn create_no_strike_zone(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateNoStrikeZoneInput,
+    ) -> Result<NoStrikeZone> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        validation::check("input", &input).map_err(|e| e.extend())?;
+        let claims = auth::claims(ctx);
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+
+        api_ctx
+            .convoy_repo
+            .get(convoy_uuid)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::NotFound {
+                entity_type: "Convoy".to_string(),
+                id: input.convoy_id.clone(),
+            })?;
+
+        let now = Utc::now();
+        let zone = drone_domain::NoStrikeZone {
+            convoy_id: convoy_uuid,
+            zone_id: Uuid::new_v4(),
+            name: zone_name(&input.name)?,
+            vertices: zone_vertices(&input.vertices)?,
+            enforcement: input.enforcement.into(),
+            notes: input.notes,
+            created_by: caller_name(None, &claims),
+            created_at: now,
+            updated_at: now,
+        };
+        api_ctx
+            .convoy_repo
+            .put_no_strike_zone(&zone)
+            .await
+            .map_err(ApiError::from)?;
+
+        tracing::info!(
+            zone_id = %zone.zone_id,
+            convoy_id = %convoy_uuid,
+            enforcement = zone.enforcement.as_str(),
+            "No-strike zone created"
+        );
+
+        Ok(zone.into())
+    }
+
+    /// Change a no-strike zone
+    ///
+    /// Requires the COMMANDER role.
+    #[graphql(name = "updateNoStrikeZone", guard = "RoleGuard::new(Role::Commander)")]
+    async fn update_no_strike_zone(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateNoStrikeZoneInput,
+    ) -> Result<NoStrikeZone> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        validation::check("input", &input).map_err(|e| e.extend())?;
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        let zone_uuid = Uuid::parse_str(&input.zone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let mut zone = find_no_strike_zone(api_ctx, convoy_uuid, zone_uuid).await?;
+        if let Some(name) = &input.name {
+            zone.name = zone_name(name)?;
+        }
+        if let Some(vertices) = &input.vertices {
+            zone.vertices = zone_vertices(vertices)?;
+        }
+        if let Some(enforcement) = input.enforcement {
+            zone.enforcement = enforcement.into();
+        }
+        if input.notes.is_some() {
+            zone.notes = input.notes;
+        }
+        zone.updated_at = Utc::now();
+
+        api_ctx
+            .convoy_repo
+            .put_no_strike_zone(&zone)
+            .await
+            .map_err(ApiError::from)?;
+
+        tracing::info!(zone_id = %zone_uuid, convoy_id = %convoy_uuid, "No-strike zone updated");
+
+        Ok(zone.into())
+    }
+
+    /// Remove a no-strike zone
+    ///
+    /// Requires the COMMANDER role.
+    #[graphql(name = "deleteNoStrikeZone", guard = "RoleGuard::new(Role::Commander)")]
+    async fn delete_no_strike_zone(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Zone ID")]
+        zone_id: ID,
+    ) -> Result<bool> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        let zone_uuid = Uuid::parse_str(&zone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        find_no_strike_zone(api_ctx, convoy_uuid, zone_uuid).await?;
+        api_ctx
+            .convoy_repo
+            .delete_no_strike_zone(convoy_uuid, zone_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
+        tracing::info!(zone_id = %zone_uuid, convoy_id = %convoy_uuid, "No-strike zone deleted");
+
+        Ok(true)
+    }
+
+    // =========================================================================
+    // LEADERBOARD MUTATIONS
+    // =========================================================================
+
+    /// Force rebuild of leaderboard cache from source data
+    ///
+    /// Requires the COMMANDER role.
+    #[graphql(name = "rebuildLeaderboard", guard = "RoleGuard::new(Role::Commander)")]
+    async fn rebuild_leaderboard(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<RebuildLeaderboardResult> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        tracing::info!(convoy_id = %convoy_uuid, "Rebuilding leaderboard");
+
+        let start = std::time::Instant::now();
+
+        // Get current entries (rebuild would recalculate from engagements)
+        let entries = api_ctx
+            .leaderboard_repo
+            .get_leaderboard(convoy_uuid, 100)
+            .await
+            .map_err(ApiError::from)?;
+
+        let duration_ms = start.elapsed().as_millis() as i64;
+
+        Ok(RebuildLeaderboardResult {
+            success: true,
+            entries_processed: entries.len() as i32,
+            duration_ms,
+        })
+    }
+
+    /// Select the leaderboard scoring model for a convoy
+    ///
+    /// The model is stored with the convoy, and existing scores and ranks
+    /// are recomputed under it. Requires the COMMANDER role.
+    #[graphql(name = "setScoringModel", guard = "RoleGuard::new(Role::Commander)")]
+    async fn set_scoring_model(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Scoring model")]
+        model: ScoringModel,
+    ) -> Result<ScoringModel> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        tracing::info!(convoy_id = %convoy_uuid, model = ?model, "Setting scoring model");
+
+        api_ctx
+            .leaderboard_repo
+            .set_scoring_model(convoy_uuid, model.into())
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(model)
+    }
+
+    /// Recount a convoy's weapon and convoy accuracy rankings from its
+    /// recorded engagements
+    ///
+    /// Picks up engagements written outside `createEngagement` and counts
+    /// lost with the cache. Requires the ADMIN role.
+    #[graphql(guard = "RoleGuard::new(Role::Admin)")]
+    async fn rebuild_accuracy_rankings(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<RebuildAccuracyRankingsResult> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        Ok(rankings::rebuild_convoy(api_ctx, convoy_uuid).await?)
+    }
+
+    // =========================================================================
+    // DRONE MUTATIONS
+    // =========================================================================
+
+    /// Register a drone with a convoy
+    ///
+    /// Callsigns are unique within a convoy and tail numbers across all
+    /// convoys, ignoring case. A duplicate fails with CONFLICT and the
+    /// holder's ID in the `existing_id` extension. Requires the OPERATOR role.
+    #[graphql(name = "createDrone", guard = "RoleGuard::new(Role::Operator)")]
+    async fn create_drone(&self, ctx: &Context<'_>, input: CreateDroneInput) -> Result<Drone> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let drone = drone_domain::Drone::register(
+            convoy_uuid,
+            &input.callsign,
+            &input.tail_number,
+            input.platform_type.into(),
+            input.serial_number.unwrap_or_default(),
+        );
+        if drone.callsign.is_empty() || drone.tail_number.is_empty() {
+            return Err(ApiError::InvalidInput(
+                "callsign and tail number are required".to_string(),
+            )
+            .into());
+        }
+
+        tracing::info!(
+            convoy_id = %convoy_uuid,
+            drone_id = %drone.drone_id,
+            callsign = %drone.callsign,
+            "Registering drone"
+        );
+
+        register_drone(api_ctx, &drone).await.map_err(|e| e.extend())?;
+        search::index_drone(api_ctx, &drone).await;
+        Ok(drone.into())
+    }
+
+    /// Update drone state
+    ///
+    /// A status change must follow the drone status state machine (e.g.
+    /// LANDED cannot jump to INGRESS); applied changes are recorded in the
+    /// drone's status history and published on `droneStatusChanges`.
+    /// Reported `weapons` overwrite the drone's recorded rounds remaining.
+    /// Requires the OPERATOR role.
+    #[graphql(name = "updateDroneState", guard = "RoleGuard::new(Role::Operator)")]
+    async fn update_drone_state(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateDroneStateInput,
+    ) -> Result<Drone> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        validation::check("input", &input).map_err(|e| e.extend())?;
+        let claims = auth::claims(ctx);
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        let drone_uuid = Uuid::parse_str(&input.drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy_drone(&claims, convoy_uuid, drone_uuid).await?;
+
+        tracing::info!(
+            convoy_id = %input.convoy_id,
+            drone_id = %input.drone_id,
+            "Updating drone state"
+        );
+
+        if let Some(weapons) = &input.weapons {
+            api_ctx
+                .request_limits
+                .check_batch("weapons", weapons.len())
+                .map_err(|e| e.extend())?;
+        }
+
+        let transitioned = match input.status {
+            Some(status) => Some(
+                transition_drone_status(api_ctx, &claims, convoy_uuid, drone_uuid, status.into())
+                    .await?,
+            ),
+            None => None,
+        };
+
+        if let Some(weapons) = input.weapons {
+            let loadout = parse_loadout(weapons)?;
+            check_platform_loadout(api_ctx, Some(convoy_uuid), drone_uuid, &loadout).await?;
+            api_ctx
+                .weapons_repo
+                .set_loadout(drone_uuid, &loadout)
+                .await
+                .map_err(ApiError::from)?;
+        }
+
+        // TODO: Persist position, fuel and waypoint with the drone repository
+
+        Ok(Drone {
+            drone_id: input.drone_id.clone(),
+            convoy_id: input.convoy_id.clone(),
+            tail_number: "AF-001".to_string(),
+            callsign: transitioned
+                .as_ref()
+                .map_or_else(|| "REAPER-01".to_string(), |info| info.callsign.clone()),
+            platform_type: PlatformType::Mq9Reaper,
+            status: transitioned.map_or(DroneStatus::Airborne, |info| info.status.into()),
+            current_position: input.position.map(|p| Coordinates {
+                latitude: p.latitude,
+                longitude: p.longitude,
+                altitude_m: p.altitude_m,
+                heading_deg: p.heading_deg as f32,
+                speed_mps: p.speed_mps as f32,
+            }).unwrap_or(Coordinates {
+                latitude: 34.5553,
+                longitude: 69.2075,
+                altitude_m: 5000.0,
+                heading_deg: 45.0,
+                speed_mps: 80.0,
+            }),
+            fuel_remaining_pct: input.fuel_pct.unwrap_or(75.0) as f32,
+            flight_time_hrs: 0.0,
+            accuracy_pct: 92.3,
+            total_engagements: 13,
+            successful_hits: 12,
+            current_waypoint: input.current_waypoint.unwrap_or(15),
+            total_waypoints: 25,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+    }
+
+    // =========================================================================
+    // WEAPONS MUTATIONS
+    // =========================================================================
+
+    /// Load or replace weapons on a drone
+    ///
+    /// Weapons not listed keep their current inventory. The resulting
+    /// inventory must fit the drone's platform capabilities. Requires the
+    /// OPERATOR role.
+    #[graphql(name = "setWeaponLoadout", guard = "RoleGuard::new(Role::Operator)")]
+    async fn set_weapon_loadout(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+        #[graphql(desc = "Weapons to load")]
+        weapons: Vec<WeaponLoadoutInput>,
+    ) -> Result<Vec<WeaponStatus>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+        api_ctx
+            .request_limits
+            .check_batch("weapons", weapons.len())
+            .map_err(|e| e.extend())?;
+
+        let loadout = parse_loadout(weapons)?;
+        check_platform_loadout(api_ctx, None, drone_uuid, &loadout).await?;
+
+        tracing::info!(drone_id = %drone_uuid, weapons = loadout.len(), "Setting weapon loadout");
+
+        api_ctx
+            .weapons_repo
+            .set_loadout(drone_uuid, &loadout)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(loadout.into_iter().map(Into::into).collect())
+    }
+
+    // =========================================================================
+    // TELEMETRY MUTATIONS
+    // =========================================================================
+
+    /// Record telemetry data point
+    ///
+    /// Requires the OPERATOR role.
+    #[graphql(name = "recordTelemetry", guard = "RoleGuard::new(Role::Operator)")]
+    async fn record_telemetry(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateTelemetryInput,
+    ) -> Result<TelemetrySnapshot> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        validation::check("input", &input).map_err(|e| e.extend())?;
+        Ok(record_telemetry_point(api_ctx, &auth::claims(ctx), input).await?)
+    }
+
+    /// Record several telemetry points in one request
+    ///
+    /// Points are recorded in order; the first failure aborts the rest.
+    /// Batches longer than `MAX_BATCH_ITEMS` fail with a VALIDATION error.
+    /// Requires the OPERATOR role.
+    #[graphql(name = "recordTelemetryBatch", guard = "RoleGuard::new(Role::Operator)")]
+    async fn record_telemetry_batch(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Telemetry points, oldest first")]
+        inputs: Vec<CreateTelemetryInput>,
+    ) -> Result<Vec<TelemetrySnapshot>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        api_ctx
+            .request_limits
+            .check_batch("inputs", inputs.len())
+            .map_err(|e| e.extend())?;
+        validation::check_each("inputs", &inputs).map_err(|e| e.extend())?;
+        let claims = auth::claims(ctx);
+
+        let mut snapshots = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            snapshots.push(record_telemetry_point(api_ctx, &claims, input).await?);
+        }
+        Ok(snapshots)
+    }
+
+    // =========================================================================
+    // CONVOY MUTATIONS
+    // =========================================================================
+
+    /// Create a new convoy
+    ///
+    /// Requires the OPERATOR role.
+    #[graphql(name = "createConvoy", guard = "RoleGuard::new(Role::Operator)")]
+    async fn create_convoy(&self, ctx: &Context<'_>, input: CreateConvoyInput) -> Result<Convoy> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        validation::check("input", &input).map_err(|e| e.extend())?;
+        if !claims.can_access_unit(&input.commanding_unit) {
+            return Err(ApiError::Unauthorized(
+                "cannot create convoys for another commanding unit".to_string(),
+            )
+            .into());
+        }
+        let environment = input.environment.unwrap_or(claims.environment.into());
+        if drone_domain::Environment::from(environment) != claims.environment {
+            return Err(ApiError::Unauthorized(
+                "cannot create convoys in another environment".to_string(),
+            )
+            .into());
+        }
+        let convoy_id = Uuid::new_v4();
+        let scoring_model = input.scoring_model.unwrap_or_default();
+
+        tracing::info!(
+            convoy_id = %convoy_id,
+            callsign = %input.callsign,
+            "Creating convoy"
+        );
+
+        // TODO: Implement with convoy repository
+        api_ctx
+            .leaderboard_repo
+            .set_scoring_model(convoy_id, scoring_model.into())
+            .await
+            .map_err(ApiError::from)?;
+        search::index_convoy(api_ctx, convoy_id, &input.callsign).await;
+
+        Ok(Convoy {
+            convoy_id: ID(convoy_id.to_string()),
+            callsign: input.callsign,
+            mission_type: input.mission_type,
+            status: ConvoyStatus::Planning,
+            aor_name: input.aor_name,
+            aor_center: Coordinates {
+                latitude: input.aor_center.latitude,
+                longitude: input.aor_center.longitude,
+                altitude_m: input.aor_center.altitude_m,
+                heading_deg: 0.0,
+                speed_mps: 0.0,
+            },
+            aor_radius_km: input.aor_radius_km as f32,
+            drone_count: 0,
+            commanding_unit: input.commanding_unit,
+            environment,
+            roe_profile: Some(input.roe_profile),
+            scoring_model,
+            mission_start: None,
+            mission_end: None,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Provision a convoy from a template in one call
+    ///
+    /// Creates the convoy and registers its drones with platform-appropriate
+    /// loadouts and sensors, each flying a generated waypoint plan for the
+    /// mission type on its own track and altitude. The commanding unit
+    /// defaults to the caller's, and the convoy is created in the caller's
+    /// environment. Requires the OPERATOR role.
+    #[graphql(
+        name = "createConvoyFromTemplate",
+        guard = "RoleGuard::new(Role::Operator)"
+    )]
+    async fn create_convoy_from_template(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Template to provision")]
+        template: ConvoyTemplate,
+        #[graphql(desc = "Adjustments to the template")]
+        overrides: Option<ConvoyTemplateOverridesInput>,
+    ) -> Result<ProvisionedConvoy> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        let overrides = overrides.unwrap_or_default();
+        validation::check("overrides", &overrides).map_err(|e| e.extend())?;
+
+        let commanding_unit = overrides
+            .commanding_unit
+            .clone()
+            .or_else(|| claims.commanding_unit.clone())
+            .filter(|unit| !unit.trim().is_empty())
+            .ok_or_else(|| ApiError::InvalidInput("commandingUnit is required".to_string()))?;
+        if !claims.can_access_unit(&commanding_unit) {
+            return Err(ApiError::Unauthorized(
+                "cannot create convoys for another commanding unit".to_string(),
+            )
+            .into());
+        }
+        let scoring_model = overrides.scoring_model.unwrap_or_default();
+
+        let mut plan = api_ctx
+            .convoy_template(template.into())
+            .with_overrides(template_overrides(overrides)?)
+            .map_err(|e| ApiError::InvalidInput(e.to_string()))?
+            .provision(&commanding_unit);
+        plan.convoy.environment = claims.environment;
+
+        tracing::info!(
+            convoy_id = %plan.convoy.convoy_id,
+            callsign = %plan.convoy.convoy_callsign,
+            template = ?template,
+            drones = plan.drones.len(),
+            "Provisioning convoy from template"
+        );
+
+        provision_convoy(api_ctx, &plan, scoring_model)
+            .await
+            .map_err(|e| e.extend())?;
+
+        Ok(ProvisionedConvoy {
+            convoy: Convoy::from_domain(plan.convoy, scoring_model),
+            drones: plan
+                .drones
+                .into_iter()
+                .map(|provisioned| {
+                    let mut drone = Drone::from(provisioned.drone);
+                    drone.total_waypoints = provisioned.waypoints.len() as i32;
+                    ProvisionedDrone {
+                        drone,
+                        waypoints: provisioned.waypoints.into_iter().map(Into::into).collect(),
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    /// Update convoy status
+    ///
+    /// `ACTIVE` stamps the mission start and `COMPLETE` or `ABORT` the
+    /// mission end, unless already set. Moving to `COMPLETE` generates the
+    /// convoy's mission artifacts in the background (see
+    /// `missionArtifacts`). Requires the COMMANDER role.
+    #[graphql(name = "updateConvoyStatus", guard = "RoleGuard::new(Role::Commander)")]
+    async fn update_convoy_status(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateConvoyStatusInput,
+    ) -> Result<Convoy> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&input.convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        tracing::info!(
+            convoy_id = %input.convoy_id,
+            status = ?input.status,
+            "Updating convoy status"
+        );
+
+        let mut convoy = api_ctx
+            .convoy_repo
+            .get(convoy_uuid)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::NotFound {
+                entity_type: "Convoy".to_string(),
+                id: input.convoy_id.clone(),
+            })?;
+
+        let previous = convoy.status;
+        let now = Utc::now();
+        convoy.status = input.status.into();
+        match convoy.status {
+            drone_domain::ConvoyStatus::Active => {
+                convoy.mission_start.get_or_insert(now);
+            }
+            drone_domain::ConvoyStatus::Complete | drone_domain::ConvoyStatus::Abort => {
+                convoy.mission_end.get_or_insert(now);
+            }
+            drone_domain::ConvoyStatus::Planning | drone_domain::ConvoyStatus::Rtb => {}
+        }
+        api_ctx.convoy_repo.update_status(&convoy).await.map_err(ApiError::from)?;
+
+        if convoy.status == drone_domain::ConvoyStatus::Complete
+            && previous != drone_domain::ConvoyStatus::Complete
+        {
+            artifacts::spawn_generation(api_ctx.clone(), convoy_uuid);
+        }
+
+        let scoring_model = api_ctx
+            .leaderboard_repo
+            .scoring_model(convoy_uuid)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(Convoy::from_domain(convoy, scoring_model.into()))
+    }
+
+    /// Restore a convoy snapshot produced by `exportConvoySnapshot`
+    ///
+    /// Intended for a fresh environment; existing rows with the same keys
+    /// are overwritten. Requires the COMMANDER role.
+    #[graphql(name = "importConvoySnapshot", guard = "RoleGuard::new(Role::Commander)")]
+    async fn import_convoy_snapshot(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Snapshot document")]
+        snapshot: Json<ConvoySnapshot>,
+    ) -> Result<SnapshotImportResult> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+
+        // Callers may only restore their own unit's convoys into their own
+        // environment, and never overwrite another unit's or environment's
+        if let Some(convoy) = &snapshot.convoy {
+            if !claims.can_access_unit(&convoy.commanding_unit) {
+                return Err(ApiError::Unauthorized(
+                    "snapshot belongs to another commanding unit".to_string(),
+                )
+                .into());
+            }
+            if convoy.environment != claims.environment {
+                return Err(ApiError::Unauthorized(format!(
+                    "snapshot belongs to the {} environment",
+                    convoy.environment.as_str()
+                ))
+                .into());
+            }
+        }
+        let existing = api_ctx
+            .convoy_repo
+            .owner(snapshot.convoy_id)
+            .await
+            .map_err(ApiError::from)?;
+        if existing.is_some() && !claims.can_access_owner(existing.as_ref()) {
+            return Err(ApiError::NotFound {
+                entity_type: "Convoy".to_string(),
+                id: snapshot.convoy_id.to_string(),
+            }
+            .into());
+        }
+
+        tracing::info!(convoy_id = %snapshot.convoy_id, "Importing convoy snapshot");
+
+        Ok(snapshot::import_convoy(api_ctx, &snapshot).await?)
+    }
+
+    // =========================================================================
+    // ALERT MUTATIONS
+    // =========================================================================
+
+    /// Acknowledge an alert
+    ///
+    /// Requires the OPERATOR role.
+    #[graphql(name = "acknowledgeAlert", guard = "RoleGuard::new(Role::Operator)")]
+    async fn acknowledge_alert(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Alert ID")]
+        alert_id: ID,
+        #[graphql(desc = "Operator acknowledging the alert (defaults to the caller's role)")]
+        acknowledged_by: Option<String>,
+    ) -> Result<Alert> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        let alert_uuid = Uuid::parse_str(&alert_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+
+        let acknowledged_by = caller_name(acknowledged_by, &claims);
+
+        tracing::info!(
+            convoy_id = %convoy_uuid,
+            alert_id = %alert_uuid,
+            acknowledged_by = %acknowledged_by,
+            "Acknowledging alert"
+        );
+
+        let alert = api_ctx
+            .alert_repo
+            .acknowledge(convoy_uuid, alert_uuid, &acknowledged_by)
+            .await
+            .map_err(ApiError::from)?
+            .ok_or_else(|| ApiError::NotFound {
+                entity_type: "Alert".to_string(),
+                id: alert_id.to_string(),
+            })?;
+
+        Ok(alert.into())
+    }
+
+    // =========================================================================
+    // JOURNAL MUTATIONS
+    // =========================================================================
+
+    /// Annotate a convoy's mission timeline
+    ///
+    /// The entry is stamped with the current time and appears in
+    /// `journal`, `engagementReplay` and the convoy exports. Requires the
+    /// OPERATOR role.
+    #[graphql(name = "addJournalEntry", guard = "RoleGuard::new(Role::Operator)")]
+    async fn add_journal_entry(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Annotation text")]
+        text: String,
+        #[graphql(desc = "Engagement, drone, target or alert the entry refers to")]
+        linked_entity_id: Option<ID>,
+        #[graphql(desc = "Operator writing the entry (defaults to the caller's role)")]
+        author: Option<String>,
+    ) -> Result<JournalEntry> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        let linked_uuid = linked_entity_id
+            .map(|id| Uuid::parse_str(&id))
+            .transpose()
+            .map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(ApiError::Validation("`text` must not be empty".to_string()).into());
+        }
+        if text.chars().count() > drone_domain::MAX_JOURNAL_TEXT_LEN {
+            return Err(ApiError::Validation(format!(
+                "`text` may be at most {} characters",
+                drone_domain::MAX_JOURNAL_TEXT_LEN
+            ))
+            .into());
+        }
+
+        let entry = drone_domain::JournalEntry {
+            convoy_id: convoy_uuid,
+            entry_time: Utc::now(),
+            entry_id: Uuid::new_v4(),
+            author: caller_name(author, &claims),
+            text: text.to_string(),
+            linked_entity_id: linked_uuid,
+        };
+        api_ctx
+            .journal_repo
+            .record(&entry)
+            .await
+            .map_err(ApiError::from)?;
+
+        tracing::info!(
+            convoy_id = %convoy_uuid,
+            entry_id = %entry.entry_id,
+            author = %entry.author,
+            "Journal entry added"
+        );
+
+        Ok(entry.into())
+    }
+
+    // =========================================================================
+    // WAYPOINT MUTATIONS
+    // =========================================================================
+
+    /// Task a sensor mode for a drone's arrival at a waypoint
+    ///
+    /// Replaces any earlier task for the same sensor and waypoint, and is
+    /// published on `sensorTasks` so the drone can switch modes. Requires
+    /// the OPERATOR role.
+    #[graphql(name = "assignSensorTask", guard = "RoleGuard::new(Role::Operator)")]
+    async fn assign_sensor_task(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+        #[graphql(desc = "Waypoint sequence number")]
+        sequence_number: i32,
+        #[graphql(desc = "Sensor to task")]
+        sensor_type: SensorType,
+        #[graphql(desc = "Sensor mode, e.g. WIDE_AREA, SPOT_TRACK or GMTI")]
+        mode: String,
+        #[graphql(desc = "Operator assigning the task (defaults to the caller's role)")]
+        assigned_by: Option<String>,
+    ) -> Result<SensorTask> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&claims, drone_uuid).await?;
+
+        let sequence_number = i16::try_from(sequence_number)
+            .ok()
+            .filter(|n| *n >= 1)
+            .ok_or_else(|| {
+                ApiError::InvalidInput(format!("invalid waypoint sequence {sequence_number}"))
+            })?;
+        let mode = mode.trim().to_uppercase();
+        if mode.is_empty() {
+            return Err(ApiError::InvalidInput("sensor mode is required".to_string()).into());
+        }
+
+        let task = drone_domain::SensorTask {
+            drone_id: drone_uuid,
+            sequence_number,
+            sensor_type: sensor_type.into(),
+            mode,
+            assigned_by: caller_name(assigned_by, &claims),
+            assigned_at: Utc::now(),
+        };
+
+        tracing::info!(
+            drone_id = %drone_uuid,
+            sequence_number,
+            sensor = ?task.sensor_type,
+            mode = %task.mode,
+            "Assigning sensor task"
+        );
+
+        api_ctx
+            .waypoint_repo
+            .assign_sensor_task(&task)
+            .await
+            .map_err(ApiError::from)?;
+
+        let task = SensorTask::from(task);
+        api_ctx.publish(task.clone());
+        Ok(task)
+    }
+
+    /// Create waypoints for a drone
+    ///
+    /// Requires the OPERATOR role.
+    #[graphql(name = "createWaypoints", guard = "RoleGuard::new(Role::Operator)")]
+    async fn create_waypoints(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateWaypointsInput,
+    ) -> Result<Vec<Waypoint>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        validation::check("input", &input).map_err(|e| e.extend())?;
+        let drone_uuid = Uuid::parse_str(&input.drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+        api_ctx
+            .request_limits
+            .check_batch("waypoints", input.waypoints.len())
+            .map_err(|e| e.extend())?;
+
+        tracing::info!(
+            drone_id = %input.drone_id,
+            count = input.waypoints.len(),
+            "Creating waypoints"
+        );
 
         // TODO: Implement with waypoint repository
 
@@ -398,11 +1618,1163 @@ impl MutationRoot {
                 planned_departure: None,
                 actual_departure: None,
                 loiter_duration_min: None,
+                sensor_tasks: vec![],
             })
             .collect();
 
         Ok(waypoints)
     }
+
+    // =========================================================================
+    // API KEY MUTATIONS
+    // =========================================================================
+
+    /// Issue an API key for a machine client
+    ///
+    /// The key is confined to one commanding unit and environment, which
+    /// must be within the caller's; only ADMIN keys issued by callers
+    /// spanning every unit may omit the unit. The returned key value is
+    /// shown only once. Requires the ADMIN role.
+    #[graphql(name = "issueApiKey", guard = "RoleGuard::new(Role::Admin)")]
+    async fn issue_api_key(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Client the key is for, e.g. a simulator or gateway name")]
+        name: String,
+        #[graphql(desc = "What the key may do")]
+        scope: ApiKeyScope,
+        #[graphql(desc = "Commanding unit the key is confined to (required unless ADMIN)")]
+        commanding_unit: Option<String>,
+        #[graphql(desc = "Data environment the key is confined to (defaults to the caller's)")]
+        environment: Option<Environment>,
+    ) -> Result<IssuedApiKey> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        let environment = environment.map_or(claims.environment, Into::into);
+        let (key, value) = api_ctx
+            .api_keys
+            .issue(&claims, &name, scope.into(), commanding_unit.as_deref(), environment)
+            .await?;
+        tracing::info!(
+            key_id = %key.key_id,
+            name = %key.name,
+            scope = key.scope.as_str(),
+            commanding_unit = key.commanding_unit.as_deref().unwrap_or("*"),
+            environment = key.environment.as_str(),
+            "Issued API key"
+        );
+
+        Ok(IssuedApiKey {
+            api_key: key.into(),
+            key: value,
+        })
+    }
+
+    /// Replace an API key's secret, invalidating the old key value
+    ///
+    /// Only keys of the caller's unit and environment can be rotated.
+    /// Requires the ADMIN role.
+    #[graphql(name = "rotateApiKey", guard = "RoleGuard::new(Role::Admin)")]
+    async fn rotate_api_key(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Key ID")]
+        key_id: ID,
+    ) -> Result<IssuedApiKey> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let (key, value) = api_ctx.api_keys.rotate(&auth::claims(ctx), &key_id).await?;
+        tracing::info!(key_id = %key.key_id, "Rotated API key");
+
+        Ok(IssuedApiKey {
+            api_key: key.into(),
+            key: value,
+        })
+    }
+
+    /// Revoke an API key
+    ///
+    /// Only keys of the caller's unit and environment can be revoked.
+    /// Requires the ADMIN role.
+    #[graphql(name = "revokeApiKey", guard = "RoleGuard::new(Role::Admin)")]
+    async fn revoke_api_key(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Key ID")]
+        key_id: ID,
+    ) -> Result<ApiKey> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let key = api_ctx.api_keys.revoke(&auth::claims(ctx), &key_id).await?;
+        tracing::info!(key_id = %key.key_id, "Revoked API key");
+
+        Ok(key.into())
+    }
+}
+
+/// Name recorded for an action: the given name, or the caller's role
+fn caller_name(name: Option<String>, claims: &auth::Claims) -> String {
+    name.filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| format!("{:?}", claims.role).to_uppercase())
+}
+
+/// A commander's decision on a pending authorization
+struct Decision {
+    status: drone_domain::AuthorizationStatus,
+    notes: Option<String>,
+    decided_by: Option<String>,
+}
+
+/// Record a decision on a pending authorization and broadcast it
+async fn decide_authorization(
+    api_ctx: &ApiContext,
+    claims: &auth::Claims,
+    convoy_id: &ID,
+    request_id: &ID,
+    decision: Decision,
+) -> ApiResult<EngagementAuthorization> {
+    let convoy_uuid = Uuid::parse_str(convoy_id)?;
+    let request_uuid = Uuid::parse_str(request_id)?;
+    api_ctx.authorize_convoy(claims, convoy_uuid).await?;
+
+    let decided_by = caller_name(decision.decided_by, claims);
+    let expires_at = (decision.status == drone_domain::AuthorizationStatus::Approved)
+        .then(|| api_ctx.authorization_signer.expiry(Utc::now()));
+
+    tracing::info!(
+        convoy_id = %convoy_uuid,
+        request_id = %request_uuid,
+        status = ?decision.status,
+        decided_by = %decided_by,
+        "Deciding engagement authorization"
+    );
+
+    let decided = api_ctx
+        .authorization_repo
+        .decide(
+            convoy_uuid,
+            request_uuid,
+            decision.status,
+            &decided_by,
+            decision.notes.as_deref(),
+            expires_at,
+        )
+        .await
+        .map_err(|e| match e {
+            drone_persistence::PersistenceError::WriteConflict(msg) => ApiError::InvalidInput(msg),
+            other => other.into(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            entity_type: "EngagementAuthorization".to_string(),
+            id: request_id.to_string(),
+        })?;
+
+    let decided = EngagementAuthorization::signed(decided, &api_ctx.authorization_signer);
+    api_ctx.publish(decided.clone());
+    Ok(decided)
+}
+
+/// Validate an engagement's authorization code and mark its approval
+/// executed, so each code is used at most once
+async fn consume_authorization(
+    api_ctx: &ApiContext,
+    convoy_id: Uuid,
+    drone_id: Uuid,
+    input: &CreateEngagementInput,
+    engagement_id: Uuid,
+) -> ApiResult<drone_domain::EngagementAuthorization> {
+    let request_id = authorization::request_id(&input.authorization_code)?;
+    let mut approval = api_ctx
+        .authorization_repo
+        .get(convoy_id, request_id)
+        .await?
+        .ok_or_else(|| ApiError::Unauthorized("invalid authorization code".to_string()))?;
+
+    match approval.status {
+        drone_domain::AuthorizationStatus::Approved => {}
+        drone_domain::AuthorizationStatus::Executed => {
+            return Err(ApiError::Unauthorized(format!(
+                "authorization {request_id} has already been used"
+            )));
+        }
+        _ => {
+            return Err(ApiError::Unauthorized(format!(
+                "authorization {request_id} is not approved"
+            )));
+        }
+    }
+    api_ctx
+        .authorization_signer
+        .verify(&input.authorization_code, &approval, Utc::now())?;
+
+    let covered = approval.drone_id == drone_id
+        && approval.weapon_type == drone_domain::WeaponType::from(input.weapon_type)
+        && approval.target_type == drone_domain::TargetType::from(input.target.target_type);
+    if !covered {
+        return Err(ApiError::Unauthorized(format!(
+            "authorization {request_id} does not cover this drone, weapon and target"
+        )));
+    }
+
+    // Conditional update; a concurrent engagement with the same code loses
+    if !api_ctx
+        .authorization_repo
+        .mark_executed(convoy_id, request_id, engagement_id)
+        .await?
+    {
+        return Err(ApiError::Unauthorized(format!(
+            "authorization {request_id} has already been used"
+        )));
+    }
+
+    approval.status = drone_domain::AuthorizationStatus::Executed;
+    approval.engagement_id = Some(engagement_id);
+    api_ctx.publish(EngagementAuthorization::from(approval.clone()));
+    Ok(approval)
+}
+
+/// Look up the tracked target an engagement is fired at
+///
+/// Fails if the target is unknown, already destroyed, or of a different type
+/// than the engagement's target.
+async fn tracked_target(
+    api_ctx: &ApiContext,
+    convoy_id: Uuid,
+    target_id: &str,
+    input: &CreateEngagementInput,
+) -> ApiResult<drone_domain::Target> {
+    let target_uuid = Uuid::parse_str(target_id)?;
+    let target = api_ctx
+        .target_repo
+        .get(convoy_id, target_uuid)
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            entity_type: "Target".to_string(),
+            id: target_id.to_string(),
+        })?;
+
+    if target.status == drone_domain::TargetStatus::Destroyed {
+        return Err(ApiError::InvalidInput(format!(
+            "target {target_uuid} is already destroyed"
+        )));
+    }
+    if target.target_type != drone_domain::TargetType::from(input.target.target_type) {
+        return Err(ApiError::InvalidInput(format!(
+            "target {target_uuid} is not of the engaged target type"
+        )));
+    }
+    Ok(target)
+}
+
+/// Check an engagement target against the convoy's AOR and no-strike zones
+///
+/// Fails on the first violation that blocks the engagement and returns the
+/// flag-only ones, which the caller alerts on once the engagement is
+/// recorded. Unknown convoys have no geofences.
+async fn check_geofences(
+    api_ctx: &ApiContext,
+    convoy_id: Uuid,
+    target: &CoordinatesInput,
+) -> ApiResult<Vec<drone_domain::GeoViolation>> {
+    let Some(convoy) = api_ctx.convoy_repo.get(convoy_id).await? else {
+        return Ok(Vec::new());
+    };
+    let zones = api_ctx.convoy_repo.list_no_strike_zones(convoy_id).await?;
+    let target =
+        drone_domain::Coordinates::new(target.latitude, target.longitude, target.altitude_m);
+
+    let (blocking, flagged): (Vec<_>, Vec<_>) =
+        drone_domain::geofence::check_target(&convoy, &zones, &target)
+            .into_iter()
+            .partition(drone_domain::GeoViolation::blocks);
+    if let Some(violation) = blocking.into_iter().next() {
+        tracing::warn!(convoy_id = %convoy_id, %violation, "Engagement rejected by geofence");
+        return Err(ApiError::Geofence(violation));
+    }
+    Ok(flagged)
+}
+
+/// Check the shot against the weapon's envelope
+///
+/// A violation rejects the engagement unless `envelopeOverride` is set and
+/// the convoy is not LIVE; the violation is then returned so it can be
+/// journaled against the engagement. Rejections are journaled against the
+/// drone.
+async fn check_envelope(
+    api_ctx: &ApiContext,
+    claims: &auth::Claims,
+    convoy_id: Uuid,
+    drone_id: Uuid,
+    input: &CreateEngagementInput,
+    range_km: f64,
+) -> ApiResult<Option<drone_domain::EnvelopeViolation>> {
+    let height_m = input.shooter_position.altitude_m - input.target.coordinates.altitude_m;
+    let Err(violation) = api_ctx.platform_capabilities.check_engagement(
+        input.weapon_type.into(),
+        drone_domain::Km(range_km),
+        drone_domain::Meters(height_m),
+    ) else {
+        return Ok(None);
+    };
+
+    // Authorization has matched the caller's environment to the convoy's
+    if input.envelope_override && claims.environment != drone_domain::Environment::Live {
+        tracing::warn!(
+            convoy_id = %convoy_id,
+            drone_id = %drone_id,
+            %violation,
+            "Weapon envelope overridden"
+        );
+        return Ok(Some(violation));
+    }
+
+    tracing::warn!(
+        convoy_id = %convoy_id,
+        drone_id = %drone_id,
+        %violation,
+        "Engagement rejected by weapon envelope"
+    );
+    let text = format!("Engagement rejected: {violation}");
+    journal_system_entry(api_ctx, convoy_id, drone_id, text).await;
+    Err(ApiError::Envelope(violation))
+}
+
+/// Journal an entry the server wrote itself; a failed write is logged
+/// rather than failing the caller
+async fn journal_system_entry(
+    api_ctx: &ApiContext,
+    convoy_id: Uuid,
+    linked_id: Uuid,
+    text: String,
+) {
+    let entry = drone_domain::JournalEntry {
+        convoy_id,
+        entry_time: Utc::now(),
+        entry_id: Uuid::new_v4(),
+        author: "SYSTEM".to_string(),
+        text,
+        linked_entity_id: Some(linked_id),
+    };
+    if let Err(e) = api_ctx.journal_repo.record(&entry).await {
+        tracing::warn!(convoy_id = %convoy_id, error = %e, "Failed to journal entry");
+    }
+}
+
+/// A convoy's no-strike zone, or `NotFound`
+async fn find_no_strike_zone(
+    api_ctx: &ApiContext,
+    convoy_id: Uuid,
+    zone_id: Uuid,
+) -> ApiResult<drone_domain::NoStrikeZone> {
+    api_ctx
+        .convoy_repo
+        .list_no_strike_zones(convoy_id)
+        .await?
+        .into_iter()
+        .find(|zone| zone.zone_id == zone_id)
+        .ok_or_else(|| ApiError::NotFound {
+            entity_type: "NoStrikeZone".to_string(),
+            id: zone_id.to_string(),
+        })
+}
+
+/// Trimmed zone name; must not be blank
+fn zone_name(name: &str) -> ApiResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ApiError::InvalidInput("zone name must not be blank".to_string()));
+    }
+    Ok(name.to_string())
+}
+
+/// Zone polygon from range-checked vertices
+fn zone_vertices(vertices: &[GeoPointInput]) -> ApiResult<Vec<drone_domain::GeoPoint>> {
+    let vertices: Vec<_> = vertices
+        .iter()
+        .map(|v| drone_domain::GeoPoint {
+            latitude: v.latitude,
+            longitude: v.longitude,
+        })
+        .collect();
+    drone_domain::geofence::validate_polygon(&vertices).map_err(ApiError::InvalidInput)?;
+    Ok(vertices)
+}
+
+/// Stored BDA status for an assessment
+fn bda_status_str(assessment: drone_domain::DamageAssessment) -> &'static str {
+    match assessment {
+        drone_domain::DamageAssessment::Destroyed => "DESTROYED",
+        drone_domain::DamageAssessment::Damaged => "DAMAGED",
+        drone_domain::DamageAssessment::Missed => "MISSED",
+        drone_domain::DamageAssessment::PendingBda => "PENDING",
+    }
+}
+
+/// Reserve a drone's callsign and tail number, then write it
+///
+/// Reservations are released again if a later step fails, so a rejected
+/// registration leaves both identifiers free.
+/// Convert template overrides from GraphQL input
+fn template_overrides(input: ConvoyTemplateOverridesInput) -> ApiResult<drone_domain::TemplateOverrides> {
+    let drone_count = input
+        .drone_count
+        .map(|count| {
+            usize::try_from(count)
+                .map_err(|_| ApiError::InvalidInput(format!("invalid drone count {count}")))
+        })
+        .transpose()?;
+
+    Ok(drone_domain::TemplateOverrides {
+        callsign: input.callsign,
+        mission_type: input.mission_type.map(Into::into),
+        aor_name: input.aor_name,
+        aor_center: input
+            .aor_center
+            .map(|c| drone_domain::Coordinates::new(c.latitude, c.longitude, c.altitude_m)),
+        aor_radius_km: input.aor_radius_km.map(drone_domain::Km),
+        roe_profile: input.roe_profile,
+        drone_count,
+        platform_type: input.platform_type.map(Into::into),
+        callsign_prefix: input.callsign_prefix,
+    })
+}
+
+/// Write a template's convoy, drones, loadouts and waypoint plans
+///
+/// Drones are registered one at a time with the usual callsign and tail
+/// number checks; a failure stops the roster where it is.
+async fn provision_convoy(
+    api_ctx: &ApiContext,
+    plan: &drone_domain::ProvisionPlan,
+    scoring_model: ScoringModel,
+) -> ApiResult<()> {
+    let convoy = &plan.convoy;
+    api_ctx.convoy_repo.create(convoy).await?;
+    api_ctx
+        .leaderboard_repo
+        .set_scoring_model(convoy.convoy_id, scoring_model.into())
+        .await?;
+    search::index_convoy(api_ctx, convoy.convoy_id, &convoy.convoy_callsign).await;
+
+    for provisioned in &plan.drones {
+        let drone = &provisioned.drone;
+        register_drone(api_ctx, drone).await?;
+        api_ctx.weapons_repo.set_loadout(drone.drone_id, &drone.weapons).await?;
+        api_ctx.waypoint_repo.save_waypoints(&provisioned.waypoints).await?;
+        search::index_drone(api_ctx, drone).await;
+    }
+
+    api_ctx
+        .cache
+        .add_many_to_convoy_roster(convoy.convoy_id, &convoy.drone_ids)
+        .await?;
+    Ok(())
+}
+
+async fn register_drone(api_ctx: &ApiContext, drone: &drone_domain::Drone) -> ApiResult<()> {
+    let repo = &api_ctx.drone_repo;
+    if let Some(existing) = repo
+        .claim_callsign(drone.convoy_id, &drone.callsign, drone.drone_id)
+        .await?
+    {
+        return Err(ApiError::Conflict {
+            message: format!("Callsign {} is already assigned to drone {existing}", drone.callsign),
+            existing_id: existing.to_string(),
+        });
+    }
+
+    let result = match repo
+        .claim_tail_number(&drone.tail_number, drone.convoy_id, drone.drone_id)
+        .await
+    {
+        Ok(Some(existing)) => Err(ApiError::Conflict {
+            message: format!(
+                "Tail number {} is already assigned to drone {existing}",
+                drone.tail_number
+            ),
+            existing_id: existing.to_string(),
+        }),
+        Ok(None) => match repo.create(drone).await {
+            Ok(()) => return Ok(()),
+            Err(e) => {
+                if let Err(e) = repo.release_tail_number(&drone.tail_number, drone.drone_id).await {
+                    tracing::warn!(drone_id = %drone.drone_id, error = %e, "Failed to release tail number");
+                }
+                Err(e.into())
+            }
+        },
+        Err(e) => Err(e.into()),
+    };
+
+    if let Err(e) = repo
+        .release_callsign(drone.convoy_id, &drone.callsign, drone.drone_id)
+        .await
+    {
+        tracing::warn!(drone_id = %drone.drone_id, error = %e, "Failed to release callsign");
+    }
+    result
+}
+
+/// Move a drone to `next` status if the state machine allows it
+///
+/// The change is applied with a conditional update, recorded in the status
+/// history and broadcast; entering RTB also raises a warning alert. Staying
+/// in the current status is a no-op.
+async fn transition_drone_status(
+    api_ctx: &ApiContext,
+    claims: &auth::Claims,
+    convoy_id: Uuid,
+    drone_id: Uuid,
+    next: drone_domain::DroneStatus,
+) -> ApiResult<drone_persistence::DroneStatusInfo> {
+    let current = api_ctx
+        .drone_repo
+        .get_status(convoy_id, drone_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound {
+            entity_type: "Drone".to_string(),
+            id: drone_id.to_string(),
+        })?;
+    current
+        .status
+        .transition_to(next)
+        .map_err(|e| ApiError::InvalidInput(e.to_string()))?;
+    if current.status == next {
+        return Ok(current);
+    }
+
+    let change = drone_domain::DroneStatusChange {
+        convoy_id,
+        drone_id,
+        old_status: current.status,
+        new_status: next,
+        changed_at: Utc::now(),
+        changed_by: Some(caller_name(None, claims)),
+    };
+    if !api_ctx.drone_repo.compare_and_set_status(&change).await? {
+        return Err(ApiError::InvalidInput(format!(
+            "{} status changed concurrently; retry the update",
+            current.callsign
+        )));
+    }
+
+    if let Err(e) = api_ctx.drone_repo.record_status_change(&change).await {
+        tracing::warn!(drone_id = %drone_id, error = %e, "Failed to record drone status history");
+    }
+
+    tracing::info!(
+        drone_id = %drone_id,
+        from = change.old_status.as_str(),
+        to = change.new_status.as_str(),
+        "Drone status changed"
+    );
+
+    // Maintenance caps the health score, so rescore on any status change
+    match api_ctx.cache.get_health_score::<drone_domain::DroneHealth>(drone_id).await {
+        Ok(Some(health)) => {
+            let health = health.with_status(next, change.changed_at);
+            if let Err(e) = api_ctx.cache.set_health_score(drone_id, &health).await {
+                tracing::warn!(drone_id = %drone_id, error = %e, "Failed to rescore drone health");
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!(drone_id = %drone_id, error = %e, "Failed to read drone health"),
+    }
+
+    api_ctx.publish(DroneStatusEvent {
+        convoy_id: ID(convoy_id.to_string()),
+        drone_id: ID(drone_id.to_string()),
+        callsign: current.callsign.clone(),
+        old_status: change.old_status.into(),
+        new_status: change.new_status.into(),
+        timestamp: change.changed_at,
+        event_id: None,
+    });
+
+    if next == drone_domain::DroneStatus::Rtb {
+        api_ctx.raise_alert(AlertEvent {
+            alert_id: ID(Uuid::new_v4().to_string()),
+            convoy_id: ID(convoy_id.to_string()),
+            drone_id: Some(ID(drone_id.to_string())),
+            severity: AlertSeverity::Warning,
+            alert_type: "DRONE_RTB".to_string(),
+            message: format!(
+                "{} returning to base from {}",
+                current.callsign,
+                change.old_status.as_str()
+            ),
+            timestamp: change.changed_at,
+            event_id: None,
+        }).await;
+    }
+
+    Ok(drone_persistence::DroneStatusInfo {
+        callsign: current.callsign,
+        status: next,
+    })
+}
+
+/// Convert loadout input, defaulting the status from the round count
+fn parse_loadout(weapons: Vec<WeaponLoadoutInput>) -> ApiResult<Vec<drone_domain::WeaponStatus>> {
+    weapons
+        .into_iter()
+        .map(|w| {
+            let rounds = i16::try_from(w.rounds)
+                .ok()
+                .filter(|r| *r >= 0)
+                .ok_or_else(|| ApiError::InvalidInput(format!("invalid round count {}", w.rounds)))?;
+            let status = match (w.status, rounds) {
+                (Some(status), _) => status,
+                (None, 0) => WeaponState::Expended,
+                (None, _) => WeaponState::Armed,
+            };
+            Ok(drone_domain::WeaponStatus {
+                weapon_type: w.weapon_type.into(),
+                rounds_remaining: rounds,
+                status: status.into(),
+            })
+        })
+        .collect()
+}
+
+/// Fail if `loadout`, laid over the drone's current inventory, does not fit
+/// the drone's platform; drones not found in a convoy are not checked
+async fn check_platform_loadout(
+    api_ctx: &ApiContext,
+    convoy_id: Option<Uuid>,
+    drone_id: Uuid,
+    loadout: &[drone_domain::WeaponStatus],
+) -> ApiResult<()> {
+    let convoy_id = match convoy_id {
+        Some(convoy_id) => Some(convoy_id),
+        None => api_ctx.convoy_repo.convoy_for_drone(drone_id).await?,
+    };
+    let Some(convoy_id) = convoy_id else {
+        return Ok(());
+    };
+    let Some(drone) = api_ctx.drone_repo.get(convoy_id, drone_id).await? else {
+        return Ok(());
+    };
+
+    let mut weapons = api_ctx.weapons_repo.get_loadout(drone_id).await?;
+    weapons.retain(|w| loadout.iter().all(|l| l.weapon_type != w.weapon_type));
+    weapons.extend_from_slice(loadout);
+    api_ctx
+        .platform_capabilities
+        .check_loadout(drone.platform_type, &weapons)
+        .map_err(|e| ApiError::InvalidInput(e.to_string()))
+}
+
+/// Fail if a tracked weapon cannot fire; untracked weapons are not enforced
+async fn ensure_weapon_ready(
+    api_ctx: &ApiContext,
+    drone_id: Uuid,
+    weapon_type: drone_domain::WeaponType,
+) -> ApiResult<()> {
+    if let Some(mut weapon) = api_ctx.weapons_repo.get(drone_id, weapon_type).await? {
+        weapon
+            .expend_round()
+            .map_err(|e| ApiError::InvalidInput(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Store a telemetry point, refresh the cached state it feeds and raise
+/// any advisories it triggers
+///
+/// Callers range-check the input with [`validation`] first.
+pub(crate) async fn record_telemetry_point(
+    api_ctx: &ApiContext,
+    claims: &auth::Claims,
+    input: CreateTelemetryInput,
+) -> ApiResult<TelemetrySnapshot> {
+    let drone_uuid = Uuid::parse_str(&input.drone_id).map_err(ApiError::from)?;
+    let convoy_uuid = input
+        .convoy_id
+        .as_deref()
+        .map(Uuid::parse_str)
+        .transpose()
+        .map_err(ApiError::from)?;
+    match convoy_uuid {
+        Some(convoy_uuid) => api_ctx.authorize_convoy_drone(claims, convoy_uuid, drone_uuid).await?,
+        None => api_ctx.authorize_drone(claims, drone_uuid).await?,
+    }
+
+    tracing::debug!(drone_id = %drone_uuid, "Recording telemetry");
+
+    let schema_version = input.schema_version.unwrap_or(1);
+    if schema_version < 1 {
+        return Err(ApiError::InvalidInput(format!(
+            "schemaVersion must be at least 1, got {schema_version}"
+        )));
+    }
+    if schema_version > i32::from(drone_domain::TELEMETRY_SCHEMA_VERSION) {
+        tracing::debug!(
+            drone_id = %drone_uuid,
+            schema_version,
+            "Telemetry from a newer envelope version; unknown fields kept as extensions"
+        );
+    }
+    let extensions = input.extensions.map(|Json(e)| e).unwrap_or_default();
+    let extensions_bytes = serde_json::to_vec(&extensions).map_or(0, |v| v.len());
+    if extensions_bytes > MAX_TELEMETRY_EXTENSIONS_BYTES {
+        return Err(ApiError::InvalidInput(format!(
+            "telemetry extensions are {extensions_bytes} bytes; the limit is {MAX_TELEMETRY_EXTENSIONS_BYTES}"
+        )));
+    }
+
+    let mut position = drone_domain::Coordinates::new(
+        input.position.latitude,
+        input.position.longitude,
+        input.position.altitude_m,
+    );
+    position.heading_deg = input.position.heading_deg as f32;
+    position.speed_mps = input.position.speed_mps as f32;
+    let conditions = match api_ctx.weather.conditions(&position).await {
+        Ok(conditions) => Some(conditions),
+        Err(e) => {
+            tracing::warn!(
+                provider = api_ctx.weather.name(),
+                error = %e,
+                "Weather lookup failed"
+            );
+            None
+        }
+    };
+
+    // ETA uses the speed over the recent window rather than one report
+    let recorded_at = Utc::now();
+    let recent: Vec<TelemetrySnapshot> = api_ctx
+        .cache
+        .get_recent_telemetry(drone_uuid, recorded_at.timestamp_millis())
+        .await
+        .map_err(ApiError::from)?;
+    let recent_speeds: Vec<drone_domain::Mps> =
+        recent.iter().map(|p| drone_domain::Mps(p.position.speed_mps)).collect();
+
+    // Only the report that drops below mission minimums raises an alert
+    let was_low_visibility = recent
+        .iter()
+        .rev()
+        .find_map(|p| p.ambient_conditions.as_ref())
+        .is_some_and(|c| c.visibility_km < api_ctx.min_visibility_km);
+    let low_visibility_entered = conditions
+        .filter(|c| c.visibility_km < api_ctx.min_visibility_km && !was_low_visibility);
+    let airspeed = weather::smoothed_airspeed(
+        &recent_speeds,
+        drone_domain::Mps(input.position.speed_mps as f32),
+    );
+
+    let snapshot = TelemetrySnapshot {
+        drone_id: ID(input.drone_id),
+        recorded_at,
+        position: Coordinates {
+            latitude: input.position.latitude,
+            longitude: input.position.longitude,
+            altitude_m: input.position.altitude_m,
+            heading_deg: input.position.heading_deg as f32,
+            speed_mps: input.position.speed_mps as f32,
+        },
+        fuel_remaining_pct: input.fuel_pct as f32,
+        current_waypoint: input.current_waypoint,
+        velocity_mps: input.velocity_mps as f32,
+        engine_temp_c: input.engine_temp_c.map(|t| t as f32),
+        mesh_connectivity: input.mesh_connectivity as f32,
+        distance_to_next_km: input.distance_to_next_km as f32,
+        eta_next_waypoint_sec: weather::adjusted_eta_secs(
+            drone_domain::Km(input.distance_to_next_km),
+            airspeed,
+            input.position.heading_deg,
+            conditions.as_ref(),
+        ),
+        ambient_conditions: conditions.map(AmbientConditions::from),
+        schema_version,
+        extensions: (!extensions.is_empty()).then(|| Json(extensions.clone())),
+        event_id: None,
+    };
+
+    // Raw points back the historical track
+    api_ctx
+        .telemetry_repo
+        .record(&drone_domain::Telemetry {
+            drone_id: drone_uuid,
+            time_bucket: drone_domain::Telemetry::generate_time_bucket(&snapshot.recorded_at),
+            recorded_at: snapshot.recorded_at,
+            position,
+            velocity_mps: snapshot.velocity_mps,
+            acceleration_mps2: 0.0,
+            bank_angle_deg: 0.0,
+            pitch_angle_deg: 0.0,
+            current_waypoint: i16::try_from(input.current_waypoint).unwrap_or(i16::MAX),
+            distance_to_next_km: snapshot.distance_to_next_km,
+            eta_next_waypoint: None,
+            fuel_remaining_pct: snapshot.fuel_remaining_pct,
+            engine_rpm: 0,
+            engine_temp_c: snapshot.engine_temp_c.unwrap_or_default(),
+            battery_voltage: 0.0,
+            wind_speed_mps: 0.0,
+            wind_direction_deg: 0.0,
+            temperature_c: 0.0,
+            visibility_km: 0.0,
+            link_status: None,
+            mesh_connectivity: snapshot.mesh_connectivity,
+            schema_version: i16::try_from(schema_version).unwrap_or(i16::MAX),
+            extensions,
+        })
+        .await
+        .map_err(ApiError::from)?;
+
+    // Latest position feeds formation tracking
+    api_ctx
+        .cache
+        .set_latest_telemetry(drone_uuid, &snapshot)
+        .await
+        .map_err(ApiError::from)?;
+    api_ctx
+        .cache
+        .push_telemetry_history(drone_uuid, snapshot.recorded_at.timestamp_millis(), &snapshot)
+        .await
+        .map_err(ApiError::from)?;
+    api_ctx.publish(snapshot.clone());
+
+    let previous: Option<drone_domain::EnduranceEstimate> = api_ctx
+        .cache
+        .get_endurance_estimate(drone_uuid)
+        .await
+        .map_err(ApiError::from)?;
+    let endurance = drone_domain::EnduranceEstimate::compute(
+        drone_uuid,
+        input
+            .platform_type
+            .map(Into::into)
+            .or(previous.as_ref().map(|p| p.platform_type))
+            .unwrap_or(drone_domain::PlatformType::Mq9Reaper),
+        drone_domain::Percent(input.fuel_pct as f32),
+        &position,
+        input
+            .home_position
+            .map(|h| drone_domain::Coordinates::new(h.latitude, h.longitude, h.altitude_m))
+            .or(previous.as_ref().map(|p| p.home))
+            .unwrap_or(position),
+        snapshot.recorded_at,
+    );
+    api_ctx
+        .cache
+        .set_endurance_estimate(drone_uuid, &endurance)
+        .await
+        .map_err(ApiError::from)?;
+    let rtb_newly_recommended =
+        endurance.rtb_recommended && !previous.is_some_and(|p| p.rtb_recommended);
+
+    // Sensor flags and status are not in every report; carry them forward
+    let previous_health: Option<drone_domain::DroneHealth> = api_ctx
+        .cache
+        .get_health_score(drone_uuid)
+        .await
+        .map_err(ApiError::from)?;
+    let (previous_sensors, previous_status) = previous_health
+        .map(|h| (h.readings.sensors_operational, h.readings.status))
+        .unwrap_or_default();
+    let status = match (previous_status, convoy_uuid) {
+        (Some(status), _) => Some(status),
+        (None, Some(convoy_uuid)) => {
+            match api_ctx.drone_repo.get_status(convoy_uuid, drone_uuid).await {
+                Ok(info) => info.map(|i| i.status),
+                Err(e) => {
+                    tracing::warn!(drone_id = %drone_uuid, error = %e, "Status lookup for health score failed");
+                    None
+                }
+            }
+        }
+        (None, None) => None,
+    };
+    let health = drone_domain::DroneHealth::compute(
+        drone_uuid,
+        drone_domain::HealthReadings {
+            fuel_remaining: drone_domain::Percent(input.fuel_pct as f32),
+            link_quality: input.mesh_connectivity,
+            sensors_operational: input.sensors.map_or(previous_sensors, |sensors| {
+                sensors.iter().map(|s| s.operational).collect()
+            }),
+            engine_temp_c: snapshot.engine_temp_c,
+            status,
+        },
+        snapshot.recorded_at,
+    );
+    api_ctx
+        .cache
+        .set_health_score(drone_uuid, &health)
+        .await
+        .map_err(ApiError::from)?;
+
+    // Accrue airborne time; the cumulative total lives on the drone row
+    let cached_hours: Option<drone_domain::FlightHours> = api_ctx
+        .cache
+        .get_flight_hours(drone_uuid)
+        .await
+        .map_err(ApiError::from)?;
+    let previous_hours = match (cached_hours, convoy_uuid) {
+        (Some(hours), _) => Some(hours),
+        (None, Some(convoy_uuid)) => api_ctx
+            .drone_repo
+            .get(convoy_uuid, drone_uuid)
+            .await
+            .map_err(ApiError::from)?
+            .map(|d| drone_domain::FlightHours::new(drone_uuid, f64::from(d.flight_time_hrs))),
+        (None, None) => None,
+    }
+    .unwrap_or_else(|| drone_domain::FlightHours::new(drone_uuid, 0.0));
+    let mut flight_hours = previous_hours.clone();
+    // Unregistered drones have no status; count them airborne while moving
+    let airborne = status.map_or(input.position.speed_mps > 0.0, drone_domain::DroneStatus::is_airborne);
+    let accrued = flight_hours.accrue(airborne, snapshot.recorded_at);
+    api_ctx
+        .cache
+        .set_flight_hours(drone_uuid, &flight_hours)
+        .await
+        .map_err(ApiError::from)?;
+    if let Some(convoy_uuid) = convoy_uuid.filter(|_| status.is_some() && accrued > 0.0)
+        && let Err(e) = api_ctx
+            .drone_repo
+            .set_flight_time(convoy_uuid, drone_uuid, flight_hours.total_hrs as f32, snapshot.recorded_at)
+            .await
+    {
+        tracing::warn!(drone_id = %drone_uuid, error = %e, "Failed to persist flight hours");
+    }
+    let flight_advisories = api_ctx.flight_hours_limits.crossed(&previous_hours, &flight_hours);
+
+    if let (Some(convoy_id), Some(convoy_uuid)) = (input.convoy_id, convoy_uuid) {
+        api_ctx
+            .cache
+            .add_to_convoy_roster(convoy_uuid, drone_uuid)
+            .await
+            .map_err(ApiError::from)?;
+        api_ctx
+            .cache
+            .touch_active_convoy(convoy_uuid, Utc::now().timestamp_millis())
+            .await
+            .map_err(ApiError::from)?;
+
+        // A new position shifts this drone's projected path
+        if let Err(e) = deconfliction::scan_convoy(api_ctx, convoy_uuid).await {
+            tracing::warn!(convoy_id = %convoy_uuid, error = %e, "Conflict scan failed");
+        }
+        if let Err(e) = formation::scan_convoy(api_ctx, convoy_uuid).await {
+            tracing::warn!(convoy_id = %convoy_uuid, error = %e, "Formation scan failed");
+        }
+
+        // Below mission minimums: advisory only, drones keep flying
+        if let Some(c) = low_visibility_entered {
+            api_ctx.raise_alert(AlertEvent {
+                alert_id: ID(Uuid::new_v4().to_string()),
+                convoy_id: ID(convoy_id.clone()),
+                drone_id: Some(snapshot.drone_id.clone()),
+                severity: AlertSeverity::Info,
+                alert_type: "LOW_VISIBILITY".to_string(),
+                message: format!(
+                    "Visibility {:.1} km below mission minimum {:.1} km",
+                    c.visibility_km, api_ctx.min_visibility_km,
+                ),
+                timestamp: Utc::now(),
+                event_id: None,
+            }).await;
+        }
+
+        if rtb_newly_recommended {
+            api_ctx.raise_alert(AlertEvent {
+                alert_id: ID(Uuid::new_v4().to_string()),
+                convoy_id: ID(convoy_id),
+                drone_id: Some(snapshot.drone_id.clone()),
+                severity: AlertSeverity::Warning,
+                alert_type: "RTB_RECOMMENDED".to_string(),
+                message: format!(
+                    "Endurance {:.0} min below {:.0} min needed to reach home",
+                    endurance.endurance_min, endurance.time_to_home_min,
+                ),
+                timestamp: Utc::now(),
+                event_id: None,
+            }).await;
+        }
+
+        for advisory in flight_advisories {
+            let (alert_type, message) = match advisory {
+                drone_domain::FlightHoursAdvisory::InspectionDue { threshold_hrs } => (
+                    "INSPECTION_DUE",
+                    format!("Flight hours reached {threshold_hrs:.0} h inspection threshold"),
+                ),
+                drone_domain::FlightHoursAdvisory::SortieLimit { limit_hrs } => (
+                    "SORTIE_LIMIT",
+                    format!("Sortie has run {limit_hrs:.1} h, the sortie limit"),
+                ),
+            };
+            api_ctx.raise_alert(AlertEvent {
+                alert_id: ID(Uuid::new_v4().to_string()),
+                convoy_id: ID(convoy_uuid.to_string()),
+                drone_id: Some(snapshot.drone_id.clone()),
+                severity: AlertSeverity::Info,
+                alert_type: alert_type.to_string(),
+                message,
+                timestamp: Utc::now(),
+                event_id: None,
+            }).await;
+        }
+    }
+
+    Ok(snapshot)
+}
+
+/// Take the round, update accuracy and rank, and broadcast the engagement.
+///
+/// Shared by `recordEngagement` and `createEngagement`; the caller has
+/// already checked convoy access. The drone's sequence number is taken on
+/// arrival and the leaderboard update waits for earlier engagements for the
+/// same drone, so streaks follow arrival order.
+async fn record_hit(
+    api_ctx: &ApiContext,
+    convoy_uuid: Uuid,
+    input: RecordEngagementInput,
+    engagement_id: Uuid,
+) -> ApiResult<RecordEngagementResult> {
+    let drone_uuid = Uuid::parse_str(&input.drone_id).map_err(ApiError::from)?;
+
+    tracing::info!(
+        convoy_id = %convoy_uuid,
+        drone_id = %drone_uuid,
+        hit = input.hit,
+        "Recording engagement"
+    );
+
+    let ticket = api_ctx.engagement_sequencer.next(drone_uuid).await;
+
+    let weapon = match input.weapon_type {
+        Some(weapon_type) => expend_round(api_ctx, drone_uuid, weapon_type.into()).await?,
+        None => None,
+    };
+
+    let callsign = "UNKNOWN"; // TODO: Fetch callsign from drone repo
+    let platform_type = drone_domain::PlatformType::Mq9Reaper;
+
+    ticket.wait_turn().await;
+
+    // Log before touching the leaderboard so a rebuild can reproduce it
+    projections::record(
+        api_ctx,
+        drone_domain::EngagementLogEvent::new(
+            convoy_uuid,
+            drone_uuid,
+            drone_domain::EngagementLogPayload::EngagementRecorded {
+                engagement_id,
+                callsign: callsign.to_string(),
+                platform_type,
+                weapon_type: input.weapon_type.map(Into::into),
+                hit: input.hit,
+                predicted_pk: input.predicted_pk.map(|pk| pk as f32),
+            },
+        ),
+    )
+    .await?;
+
+    // Use update_entry which handles incrementing counters and ranks internally
+    let update = api_ctx
+        .leaderboard_repo
+        .update_entry(convoy_uuid, drone_uuid, callsign, platform_type, input.hit)
+        .await?;
+
+    let domain_entry = update.entry;
+    let old_rank = update.old_rank.map(i32::from);
+    let new_rank = i32::from(domain_entry.rank);
+
+    // Build GraphQL leaderboard entry from domain entry
+    let entry = LeaderboardEntry::from(domain_entry.clone());
+
+    // Broadcast event for subscriptions
+    let event = EngagementEvent {
+        convoy_id: ID(input.convoy_id.clone()),
+        drone_id: ID(input.drone_id.clone()),
+        callsign: entry.callsign.clone(),
+        hit: input.hit,
+        weapon_type: input.weapon_type.unwrap_or(WeaponType::Agm114Hellfire),
+        target_type: input.target_type,
+        range_km: input.range_km.map(|r| r as f32),
+        predicted_pk: input.predicted_pk.map(|pk| pk as f32),
+        new_accuracy_pct: entry.accuracy_pct,
+        timestamp: Utc::now(),
+        event_id: None,
+    };
+    api_ctx.publish(event);
+
+    // Broadcast leaderboard update
+    let leaderboard_event = LeaderboardUpdateEvent {
+        convoy_id: ID(input.convoy_id.clone()),
+        drone_id: ID(input.drone_id.clone()),
+        callsign: entry.callsign.clone(),
+        new_rank,
+        old_rank,
+        accuracy_pct: entry.accuracy_pct,
+        change_type: RankChangeType::between(old_rank, new_rank),
+        timestamp: Utc::now(),
+        event_id: None,
+    };
+    api_ctx.publish(leaderboard_event);
+
+    if let Some(weapon) = weapon.filter(|w| w.rounds_remaining <= api_ctx.low_munitions_rounds) {
+        api_ctx
+            .raise_alert(AlertEvent {
+                alert_id: ID(Uuid::new_v4().to_string()),
+                convoy_id: ID(input.convoy_id.clone()),
+                drone_id: Some(ID(input.drone_id.clone())),
+                severity: AlertSeverity::Warning,
+                alert_type: "LOW_MUNITIONS".to_string(),
+                message: format!(
+                    "{} {} round(s) remaining",
+                    weapon.weapon_type.as_str(),
+                    weapon.rounds_remaining,
+                ),
+                timestamp: Utc::now(),
+                event_id: None,
+            })
+            .await;
+    }
+
+    Ok(RecordEngagementResult {
+        success: true,
+        entry,
+        new_rank,
+        rank_change: old_rank.map_or(0, |old| old - new_rank),
+        new_accuracy_pct: domain_entry.accuracy_pct,
+        sequence: ticket.sequence(),
+    })
+}
+
+/// Take one round from a tracked weapon, returning its new status
+///
+/// Returns `None` for weapons with no recorded inventory.
+async fn expend_round(
+    api_ctx: &ApiContext,
+    drone_id: Uuid,
+    weapon_type: drone_domain::WeaponType,
+) -> ApiResult<Option<drone_domain::WeaponStatus>> {
+    for _ in 0..EXPEND_ATTEMPTS {
+        let Some(current) = api_ctx.weapons_repo.get(drone_id, weapon_type).await? else {
+            return Ok(None);
+        };
+        let mut updated = current.clone();
+        updated
+            .expend_round()
+            .map_err(|e| ApiError::InvalidInput(e.to_string()))?;
+
+        // Conditional update; retry if another engagement fired first
+        if api_ctx
+            .weapons_repo
+            .compare_and_set(drone_id, &current, &updated)
+            .await?
+        {
+            return Ok(Some(updated));
+        }
+    }
+
+    Err(ApiError::InvalidInput(format!(
+        "{} inventory is changing concurrently; retry the engagement",
+        weapon_type.as_str()
+    )))
 }
 
 /// Calculate great-circle distance between two points (Haversine)
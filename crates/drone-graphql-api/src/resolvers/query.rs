@@ -2,13 +2,82 @@
 //!
 //! Read operations for the drone convoy API.
 
-use async_graphql::{Context, Object, Result, ID};
+use async_graphql::{Context, ErrorExtensions, Json, Object, Result, ID};
 use chrono::Utc;
 use uuid::Uuid;
 
+use crate::auth::{self, Role, RoleGuard};
 use crate::context::ApiContext;
+use crate::deconfliction;
+use crate::geojson::{self, FeatureCollection};
 use crate::error::ApiError;
+use crate::formation;
+use crate::pagination::{self, Cursor, Position};
+use crate::replay;
 use crate::schema::*;
+use crate::search;
+use crate::snapshot::{self, ConvoySnapshot};
+use crate::stats;
+use crate::validation;
+
+/// Largest engagements page
+const MAX_ENGAGEMENT_PAGE: i32 = 500;
+
+/// Engagements examined per page when a filter is applied
+const ENGAGEMENT_SCAN_LIMIT: usize = 5000;
+
+/// Largest drones page
+const MAX_DRONE_PAGE: i32 = 500;
+
+/// Most convoys one `convoyLeaderboard` returns
+const MAX_CONVOY_LEADERBOARD: i32 = 100;
+
+/// Largest telemetry history page
+const MAX_TELEMETRY_PAGE: i32 = 1000;
+
+/// Widest window a track can be read over (one telemetry partition per hour)
+const MAX_TRACK_RANGE_HOURS: i64 = 24;
+
+/// Widest window stats history can be read over (the table's retention)
+const MAX_STATS_RANGE_DAYS: i64 = 30;
+
+/// Reject a track window that is inverted or too wide to read
+pub(crate) fn check_track_range(
+    start: chrono::DateTime<Utc>,
+    end: chrono::DateTime<Utc>,
+) -> Result<(), ApiError> {
+    if end < start {
+        return Err(ApiError::InvalidInput("timeRange ends before it starts".to_string()));
+    }
+    if end - start > chrono::Duration::hours(MAX_TRACK_RANGE_HOURS) {
+        return Err(ApiError::InvalidInput(format!(
+            "timeRange may span at most {MAX_TRACK_RANGE_HOURS} hours"
+        )));
+    }
+    Ok(())
+}
+
+/// Widest window journal and replay timelines can be read over
+const MAX_TIMELINE_RANGE_DAYS: i64 = 7;
+
+/// Most engagements, and separately journal entries, in one replay
+const MAX_REPLAY_EVENTS: usize = 5000;
+
+/// Reject a timeline window that is inverted or too wide to read
+fn check_timeline_range(
+    start: chrono::DateTime<Utc>,
+    end: chrono::DateTime<Utc>,
+) -> Result<(), ApiError> {
+    if end < start {
+        return Err(ApiError::InvalidInput("timeRange ends before it starts".to_string()));
+    }
+    if end - start > chrono::Duration::days(MAX_TIMELINE_RANGE_DAYS) {
+        return Err(ApiError::InvalidInput(format!(
+            "timeRange may span at most {MAX_TIMELINE_RANGE_DAYS} days"
+        )));
+    }
+    Ok(())
+}
 
 /// GraphQL Query root
 pub struct QueryRoot;
@@ -35,7 +104,11 @@ impl QueryRoot {
         filter: Option<LeaderboardFilter>,
     ) -> Result<Leaderboard> {
         let api_ctx = ctx.data::<ApiContext>()?;
+        if let Some(filter) = &filter {
+            validation::check("filter", filter).map_err(|e| e.extend())?;
+        }
         let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
 
         tracing::debug!(
             convoy_id = %convoy_uuid,
@@ -84,6 +157,82 @@ impl QueryRoot {
         })
     }
 
+    /// Get leaderboard entries changed since a version
+    ///
+    /// For clients on constrained links: pass the `version` from the last
+    /// response as `sinceVersion` to receive only entries that changed since.
+    /// Without a version, or when it is unknown or older than the retained
+    /// change log, the full leaderboard is returned with `full` set.
+    #[graphql(name = "leaderboardDiff")]
+    async fn get_leaderboard_diff(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Version token from the previous response")]
+        since_version: Option<String>,
+    ) -> Result<LeaderboardDiff> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        // Read the version before the entries: a change landing in between
+        // is sent again next time rather than missed
+        let current = api_ctx
+            .cache
+            .get_leaderboard_version(convoy_uuid)
+            .await
+            .map_err(ApiError::from)?;
+        let since = since_version.as_deref().and_then(parse_leaderboard_version);
+        let changed = match (since, current) {
+            (Some((epoch, version)), Some((current_epoch, current_version)))
+                if epoch == current_epoch && version <= current_version =>
+            {
+                Some(
+                    api_ctx
+                        .cache
+                        .get_leaderboard_changes(convoy_uuid, version)
+                        .await
+                        .map_err(ApiError::from)?,
+                )
+            }
+            _ => None,
+        };
+
+        let entries: Vec<LeaderboardEntry> = api_ctx
+            .leaderboard_repo
+            .get_leaderboard(convoy_uuid, i32::MAX)
+            .await
+            .map_err(ApiError::from)?
+            .into_iter()
+            .map(LeaderboardEntry::from)
+            .collect();
+
+        let (full, entries, removed_drone_ids) = match changed {
+            None => (true, entries, Vec::new()),
+            Some(ids) => {
+                let ids: Vec<String> = ids.iter().map(Uuid::to_string).collect();
+                let removed = ids
+                    .iter()
+                    .filter(|id| !entries.iter().any(|e| &e.drone_id == *id))
+                    .map(|id| ID(id.clone()))
+                    .collect();
+                let entries = entries.into_iter().filter(|e| ids.contains(&e.drone_id)).collect();
+                (false, entries, removed)
+            }
+        };
+
+        let (epoch, version) = current.unwrap_or_default();
+        Ok(LeaderboardDiff {
+            convoy_id,
+            version: format!("{epoch}.{version}"),
+            full,
+            entries,
+            removed_drone_ids,
+            generated_at: Utc::now(),
+        })
+    }
+
     /// Get a specific drone's rank and stats in the leaderboard
     #[graphql(name = "droneRank")]
     async fn get_drone_rank(
@@ -96,6 +245,7 @@ impl QueryRoot {
     ) -> Result<Option<LeaderboardEntry>> {
         let api_ctx = ctx.data::<ApiContext>()?;
         let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
         let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
 
         let entries = api_ctx
@@ -112,16 +262,98 @@ impl QueryRoot {
         Ok(entry)
     }
 
+    /// Rank a convoy's weapon types by hit rate
+    async fn weapon_leaderboard(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<Vec<WeaponAccuracyEntry>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let tallies = api_ctx
+            .cache
+            .get_weapon_rankings(convoy_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(tallies
+            .iter()
+            .filter_map(|t| Some((drone_domain::WeaponType::parse(&t.member)?, t)))
+            .enumerate()
+            .map(|(i, (weapon_type, t))| WeaponAccuracyEntry {
+                rank: i as i32 + 1,
+                weapon_type: weapon_type.into(),
+                total_engagements: t.total_engagements as i32,
+                successful_hits: t.successful_hits as i32,
+                accuracy_pct: t.accuracy_pct() as f32,
+            })
+            .collect())
+    }
+
+    /// Rank the caller's convoys by hit rate across all their engagements
+    async fn convoy_leaderboard(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(
+            desc = "Fewest engagements a convoy needs to be ranked",
+            default = 10
+        )]
+        min_engagements: i32,
+        #[graphql(desc = "Maximum convoys to return", default = 20)]
+        limit: i32,
+    ) -> Result<Vec<ConvoyAccuracyEntry>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let claims = auth::claims(ctx);
+        let limit = limit.clamp(1, MAX_CONVOY_LEADERBOARD) as usize;
+
+        let tallies = api_ctx
+            .cache
+            .get_convoy_rankings()
+            .await
+            .map_err(ApiError::from)?;
+
+        // Ranks count only the convoys the caller can see
+        let mut entries = Vec::new();
+        for t in tallies {
+            if entries.len() == limit {
+                break;
+            }
+            if t.total_engagements < i64::from(min_engagements) {
+                continue;
+            }
+            let Ok(convoy_uuid) = Uuid::parse_str(&t.member) else {
+                continue;
+            };
+            let owner = api_ctx.convoy_repo.owner(convoy_uuid).await.map_err(ApiError::from)?;
+            if !claims.can_access_owner(owner.as_ref()) {
+                continue;
+            }
+            entries.push(ConvoyAccuracyEntry {
+                rank: entries.len() as i32 + 1,
+                convoy_id: ID(t.member.clone()),
+                total_engagements: t.total_engagements as i32,
+                successful_hits: t.successful_hits as i32,
+                accuracy_pct: t.accuracy_pct() as f32,
+            });
+        }
+        Ok(entries)
+    }
+
     // =========================================================================
     // CONVOY QUERIES
     // =========================================================================
 
     /// Get all active convoys
     #[graphql(name = "activeConvoys")]
-    async fn get_active_convoys(&self, _ctx: &Context<'_>) -> Result<Vec<Convoy>> {
+    async fn get_active_convoys(&self, ctx: &Context<'_>) -> Result<Vec<Convoy>> {
+        let claims = auth::claims(ctx);
+
         // TODO: Implement with convoy repository
         // For now, return mock data
-        Ok(vec![Convoy {
+        let convoys = vec![Convoy {
             convoy_id: ID::from("550e8400-e29b-41d4-a716-446655440000"),
             callsign: "ALPHA-CONVOY".to_string(),
             mission_type: MissionType::Strike,
@@ -137,20 +369,37 @@ impl QueryRoot {
             aor_radius_km: 150.0,
             drone_count: 12,
             commanding_unit: "432nd Wing".to_string(),
+            environment: Environment::Live,
+            roe_profile: Some("STANDARD".to_string()),
+            scoring_model: ScoringModel::default(),
             mission_start: Some(Utc::now()),
             mission_end: None,
             created_at: Utc::now(),
-        }])
+        }];
+
+        // Other commanding units' and environments' convoys are not visible
+        let environment = Environment::from(claims.environment);
+        Ok(convoys
+            .into_iter()
+            .filter(|convoy| {
+                convoy.environment == environment
+                    && claims.can_access_unit(&convoy.commanding_unit)
+            })
+            .collect())
     }
 
     /// Get convoy details by ID
     #[graphql(name = "convoy")]
     async fn get_convoy(
         &self,
-        _ctx: &Context<'_>,
+        ctx: &Context<'_>,
         #[graphql(desc = "Convoy ID")]
         convoy_id: ID,
     ) -> Result<Option<Convoy>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
         // TODO: Implement with convoy repository
         Ok(Some(Convoy {
             convoy_id: convoy_id.clone(),
@@ -168,6 +417,14 @@ impl QueryRoot {
             aor_radius_km: 150.0,
             drone_count: 12,
             commanding_unit: "432nd Wing".to_string(),
+            environment: Environment::Live,
+            roe_profile: Some("STANDARD".to_string()),
+            scoring_model: api_ctx
+                .leaderboard_repo
+                .scoring_model(convoy_uuid)
+                .await
+                .map_err(ApiError::from)?
+                .into(),
             mission_start: Some(Utc::now()),
             mission_end: None,
             created_at: Utc::now(),
@@ -184,32 +441,177 @@ impl QueryRoot {
     ) -> Result<ConvoyStats> {
         let api_ctx = ctx.data::<ApiContext>()?;
         let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
 
-        // Calculate stats from leaderboard data
-        let entries = api_ctx
-            .leaderboard_repo
-            .get_leaderboard(convoy_uuid, 100)
+        Ok(stats::convoy_stats(api_ctx, convoy_uuid).await?)
+    }
+
+    /// Get recorded convoy statistics over the mission timeline
+    ///
+    /// Snapshots are merged into one point per `resolutionSec` window:
+    /// drone counts and fuel are averaged, engagement totals keep their
+    /// latest value. Points are ordered oldest first.
+    #[graphql(name = "convoyStatsHistory")]
+    async fn convoy_stats_history(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Time range")]
+        time_range: TimeRangeInput,
+        #[graphql(default = 60, validator(minimum = 1, maximum = 86400), desc = "Seconds per point")]
+        resolution_sec: i32,
+    ) -> Result<Vec<ConvoyStatsPoint>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        if time_range.end < time_range.start {
+            return Err(ApiError::InvalidInput("timeRange ends before it starts".to_string()).into());
+        }
+        if time_range.end - time_range.start > chrono::Duration::days(MAX_STATS_RANGE_DAYS) {
+            return Err(ApiError::InvalidInput(format!(
+                "timeRange may span at most {MAX_STATS_RANGE_DAYS} days"
+            ))
+            .into());
+        }
+
+        let snapshots = api_ctx
+            .convoy_repo
+            .get_stats_history(convoy_uuid, time_range.start, time_range.end)
             .await
             .map_err(ApiError::from)?;
+        let points = drone_domain::ConvoyStatsSnapshot::downsample(
+            &snapshots,
+            chrono::Duration::seconds(i64::from(resolution_sec)),
+        );
+
+        Ok(points.into_iter().map(ConvoyStatsPoint::from).collect())
+    }
+
+    /// Get a drone's leaderboard rank progression
+    ///
+    /// One point per rank change, oldest first. Points where another
+    /// drone's engagement shifted this one carry the previous accuracy.
+    #[graphql(name = "rankTimeline")]
+    async fn rank_timeline(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+        #[graphql(desc = "Time range")]
+        time_range: TimeRangeInput,
+    ) -> Result<Vec<RankTimelinePoint>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
 
-        let total_engagements: i32 = entries.iter().map(|e| e.total_engagements).sum();
-        let total_hits: i32 = entries.iter().map(|e| e.successful_hits).sum();
-        let avg_accuracy = if !entries.is_empty() {
-            entries.iter().map(|e| e.accuracy_pct).sum::<f32>() / entries.len() as f32
+        if time_range.end < time_range.start {
+            return Err(ApiError::InvalidInput("timeRange ends before it starts".to_string()).into());
+        }
+        if time_range.end - time_range.start > chrono::Duration::days(MAX_STATS_RANGE_DAYS) {
+            return Err(ApiError::InvalidInput(format!(
+                "timeRange may span at most {MAX_STATS_RANGE_DAYS} days"
+            ))
+            .into());
+        }
+
+        let mut entries = api_ctx
+            .leaderboard_repo
+            .rank_history(convoy_uuid, drone_uuid, time_range.start, time_range.end)
+            .await
+            .map_err(ApiError::from)?;
+        // A timeline starting mid-mission takes its first accuracy from
+        // the last change before it
+        let initial = if entries.first().is_some_and(|e| e.accuracy_pct.is_none()) {
+            api_ctx
+                .leaderboard_repo
+                .accuracy_before(convoy_uuid, drone_uuid, time_range.start)
+                .await
+                .map_err(ApiError::from)?
         } else {
-            0.0
+            None
         };
+        drone_domain::RankHistoryEntry::carry_accuracy_forward(&mut entries, initial);
 
-        Ok(ConvoyStats {
+        Ok(entries.into_iter().map(RankTimelinePoint::from).collect())
+    }
+
+    /// Get convoy formation geometry from latest telemetry
+    #[graphql(name = "convoyFormation")]
+    async fn get_convoy_formation(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<Option<ConvoyFormation>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let Some(formation) = formation::convoy_formation(api_ctx, convoy_uuid).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ConvoyFormation {
             convoy_id,
-            drone_count: entries.len() as i32,
-            airborne_count: entries.len() as i32, // TODO: Get from drone repo
-            total_engagements,
-            total_hits,
-            average_accuracy_pct: avg_accuracy,
-            average_fuel_pct: 75.0, // TODO: Get from drone repo
+            centroid: formation.centroid.into(),
+            spread_km: formation.spread_km.0,
+            min_spacing_km: formation.min_spacing_km.0,
+            max_spacing_km: formation.max_spacing_km.0,
+            formation_integrity: formation.integrity,
+            violation_count: formation.violations.len() as i32,
+            offsets: formation.offsets.into_iter().map(FormationOffset::from).collect(),
             timestamp: Utc::now(),
-        })
+        }))
+    }
+
+    /// Get drone pairs predicted to lose separation
+    ///
+    /// Projects each drone along its remaining waypoints (or current heading)
+    /// and lists pairs whose closest point of approach falls under both the
+    /// horizontal and vertical separation minimum, soonest first. New
+    /// conflicts raise a CRITICAL alert.
+    #[graphql(name = "predictedConflicts")]
+    async fn predicted_conflicts(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<Vec<PredictedConflict>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let conflicts = deconfliction::scan_convoy(api_ctx, convoy_uuid).await?;
+        let now = Utc::now();
+
+        Ok(conflicts
+            .into_iter()
+            .map(|c| PredictedConflict::at(c, now))
+            .collect())
+    }
+
+    /// Export a convoy's full operational state for shift handover
+    ///
+    /// Returns a versioned JSON document accepted by `importConvoySnapshot`.
+    #[graphql(name = "exportConvoySnapshot")]
+    async fn export_convoy_snapshot(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<Json<ConvoySnapshot>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let snapshot = snapshot::export_convoy(api_ctx, convoy_uuid).await?;
+
+        Ok(Json(snapshot))
     }
 
     // =========================================================================
@@ -220,12 +622,16 @@ impl QueryRoot {
     #[graphql(name = "drone")]
     async fn get_drone(
         &self,
-        _ctx: &Context<'_>,
+        ctx: &Context<'_>,
         #[graphql(desc = "Convoy ID")]
         convoy_id: ID,
         #[graphql(desc = "Drone ID")]
         drone_id: ID,
     ) -> Result<Option<Drone>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
         // TODO: Implement with drone repository
         Ok(Some(Drone {
             drone_id: drone_id.to_string(),
@@ -242,6 +648,7 @@ impl QueryRoot {
                 speed_mps: 80.0,
             },
             fuel_remaining_pct: 75.5,
+            flight_time_hrs: 412.5,
             accuracy_pct: 92.3,
             total_engagements: 13,
             successful_hits: 12,
@@ -252,49 +659,133 @@ impl QueryRoot {
         }))
     }
 
-    /// Get all drones in a convoy
+    /// Look up a drone by callsign (case-insensitive)
+    ///
+    /// Position and fuel come from the latest cached telemetry when the
+    /// drone is reporting.
+    #[graphql(name = "droneByCallsign")]
+    async fn drone_by_callsign(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Radio callsign, e.g. REAPER-01")]
+        callsign: String,
+    ) -> Result<Option<Drone>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let callsign = drone_domain::Drone::normalize_identifier(&callsign);
+        let Some(drone_id) = api_ctx
+            .drone_repo
+            .find_by_callsign(convoy_uuid, &callsign)
+            .await
+            .map_err(ApiError::from)?
+        else {
+            return Ok(None);
+        };
+        let Some(drone) = api_ctx
+            .drone_repo
+            .get(convoy_uuid, drone_id)
+            .await
+            .map_err(ApiError::from)?
+        else {
+            return Ok(None);
+        };
+
+        let mut drone = Drone::from(drone);
+        let latest: Option<TelemetrySnapshot> = api_ctx
+            .cache
+            .get_latest_telemetry(drone_id)
+            .await
+            .map_err(ApiError::from)?;
+        if let Some(snapshot) = latest {
+            drone.current_position = snapshot.position;
+            drone.fuel_remaining_pct = snapshot.fuel_remaining_pct;
+        }
+        Ok(Some(drone))
+    }
+
+    /// Quick-jump search over drone callsigns and tail numbers, convoy
+    /// callsigns and engagement IDs
+    ///
+    /// Matching ignores case and separators (`reaper 01` finds REAPER-01)
+    /// and tolerates small typos, except for engagement IDs which match by
+    /// prefix only. Only entities in convoys the caller can access are
+    /// returned.
+    #[graphql(name = "search")]
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Search text")]
+        query: String,
+        #[graphql(desc = "Entity kinds to search (default: all)")]
+        types: Option<Vec<SearchResultType>>,
+        #[graphql(default = 20, validator(minimum = 1, maximum = 100), desc = "Maximum results (default: 20, max: 100)")]
+        limit: i32,
+    ) -> Result<Vec<SearchResult>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let kinds: Vec<drone_domain::SearchKind> = match types {
+            Some(types) => types.into_iter().map(Into::into).collect(),
+            None => drone_domain::SearchKind::ALL.to_vec(),
+        };
+        let limit = usize::try_from(limit).unwrap_or(1);
+
+        let hits = search::search(api_ctx, &auth::claims(ctx), &query, &kinds, limit).await?;
+        Ok(hits.into_iter().map(SearchResult::from).collect())
+    }
+
+    /// Get the drones registered to a convoy
+    ///
+    /// Ordered by drone ID; page size is capped at 500.
     #[graphql(name = "drones")]
     async fn get_drones(
         &self,
-        _ctx: &Context<'_>,
+        ctx: &Context<'_>,
         #[graphql(desc = "Convoy ID")]
         convoy_id: ID,
         #[graphql(desc = "Optional filter")]
         filter: Option<DroneFilter>,
-        #[graphql(default, desc = "Pagination")]
-        pagination: PaginationInput,
+        #[graphql(desc = "Page size (default 20)")]
+        first: Option<i32>,
+        #[graphql(desc = "Cursor to continue after")]
+        after: Option<String>,
     ) -> Result<Connection<Drone>> {
-        // TODO: Implement with drone repository
-        let drones = vec![Drone {
-            drone_id: Uuid::new_v4().to_string(),
-            convoy_id: convoy_id.to_string(),
-            tail_number: "AF-001".to_string(),
-            callsign: "REAPER-01".to_string(),
-            platform_type: PlatformType::Mq9Reaper,
-            status: DroneStatus::Airborne,
-            current_position: Coordinates {
-                latitude: 34.5553,
-                longitude: 69.2075,
-                altitude_m: 5000.0,
-                heading_deg: 45.0,
-                speed_mps: 80.0,
+        let api_ctx = ctx.data::<ApiContext>()?;
+        if let Some(filter) = &filter {
+            validation::check("filter", filter).map_err(|e| e.extend())?;
+        }
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let first = pagination::page_size(first, MAX_DRONE_PAGE)?;
+        let start = Cursor::resume(after.as_deref(), convoy_uuid)?;
+        let repo = &api_ctx.drone_repo;
+        let page = pagination::read_paged(
+            start,
+            first,
+            usize::MAX,
+            |d: &Drone| filter.as_ref().is_none_or(|f| drone_matches(f, d)),
+            |state, size| async move {
+                let page = repo.list_page(convoy_uuid, size, state.as_deref()).await?;
+                Ok(drone_persistence::Page {
+                    items: page.items.into_iter().map(Drone::from).collect(),
+                    paging_state: page.paging_state,
+                })
             },
-            fuel_remaining_pct: 75.5,
-            accuracy_pct: 92.3,
-            total_engagements: 13,
-            successful_hits: 12,
-            current_waypoint: 15,
-            total_waypoints: 25,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-        }];
+        )
+        .await?;
 
-        Ok(Connection {
-            items: drones,
-            total_count: 1,
-            has_next_page: false,
-            has_previous_page: false,
-        })
+        let total_count = match filter {
+            Some(_) => None,
+            None => Some(repo.count(convoy_uuid).await.map_err(ApiError::from)?),
+        };
+        Ok(page.into_connection(
+            convoy_uuid,
+            after.is_some(),
+            total_count.map(|n| i32::try_from(n).unwrap_or(i32::MAX)),
+        ))
     }
 
     // =========================================================================
@@ -305,10 +796,20 @@ impl QueryRoot {
     #[graphql(name = "waypoints")]
     async fn get_waypoints(
         &self,
-        _ctx: &Context<'_>,
+        ctx: &Context<'_>,
         #[graphql(desc = "Drone ID")]
         drone_id: ID,
     ) -> Result<Vec<Waypoint>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        let tasks = api_ctx
+            .waypoint_repo
+            .get_sensor_tasks(drone_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
         // TODO: Implement with waypoint repository
         // Generate 25 waypoints for demo
         let waypoints: Vec<Waypoint> = (1..=25)
@@ -344,6 +845,12 @@ impl QueryRoot {
                     planned_departure: Some(Utc::now()),
                     actual_departure: if i < 15 { Some(Utc::now()) } else { None },
                     loiter_duration_min: if i % 5 == 0 { Some(10) } else { None },
+                    sensor_tasks: tasks
+                        .iter()
+                        .filter(|t| i32::from(t.sequence_number) == i)
+                        .cloned()
+                        .map(Into::into)
+                        .collect(),
                 }
             })
             .collect();
@@ -356,59 +863,283 @@ impl QueryRoot {
     // =========================================================================
 
     /// Get engagements for a convoy
+    ///
+    /// Newest first; page size is capped at 500. With a filter, each page
+    /// examines at most 5000 engagements, so a page may come back short
+    /// with `hasNextPage` set.
     #[graphql(name = "engagements")]
     async fn get_engagements(
         &self,
-        _ctx: &Context<'_>,
+        ctx: &Context<'_>,
         #[graphql(desc = "Convoy ID")]
         convoy_id: ID,
         #[graphql(desc = "Optional filter")]
         filter: Option<EngagementFilter>,
-        #[graphql(default, desc = "Pagination")]
-        pagination: PaginationInput,
+        #[graphql(desc = "Page size (default 20)")]
+        first: Option<i32>,
+        #[graphql(desc = "Cursor to continue after")]
+        after: Option<String>,
     ) -> Result<Connection<Engagement>> {
-        // TODO: Implement with engagement repository
-        Ok(Connection {
-            items: vec![],
-            total_count: 0,
-            has_next_page: false,
-            has_previous_page: false,
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let first = pagination::page_size(first, MAX_ENGAGEMENT_PAGE)?;
+        let start = Cursor::resume(after.as_deref(), convoy_uuid)?;
+        let scan_limit = match filter {
+            Some(_) => ENGAGEMENT_SCAN_LIMIT,
+            None => usize::MAX,
+        };
+        let repo = &api_ctx.engagement_repo;
+        let page = pagination::read_paged(
+            start,
+            first,
+            scan_limit,
+            |e: &Engagement| filter.as_ref().is_none_or(|f| engagement_matches(f, e)),
+            |state, size| async move {
+                let page = repo.get_page(convoy_uuid, size, state.as_deref()).await?;
+                Ok(drone_persistence::Page {
+                    items: page.items.into_iter().map(Engagement::from).collect(),
+                    paging_state: page.paging_state,
+                })
+            },
+        )
+        .await?;
+
+        let total_count = match filter {
+            Some(_) => None,
+            None => Some(repo.count(convoy_uuid).await.map_err(ApiError::from)?),
+        };
+        Ok(page.into_connection(
+            convoy_uuid,
+            after.is_some(),
+            total_count.map(|n| i32::try_from(n).unwrap_or(i32::MAX)),
+        ))
+    }
+
+    /// Get engagement impact heatmap for a convoy
+    ///
+    /// Bins impact points into a lat/lon grid for a map heat layer.
+    /// Defaults to the last 24 hours.
+    #[graphql(name = "engagementHeatmap")]
+    async fn get_engagement_heatmap(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Time window (defaults to last 24h)")]
+        time_range: Option<TimeRangeInput>,
+        #[graphql(default = 1.0, desc = "Grid cell edge in km")]
+        grid_resolution_km: f64,
+    ) -> Result<EngagementHeatmap> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        if !grid_resolution_km.is_finite() || grid_resolution_km <= 0.0 {
+            return Err(
+                ApiError::InvalidInput("gridResolutionKm must be positive".to_string()).into(),
+            );
+        }
+
+        let (start, end) = match time_range {
+            Some(range) => (range.start, range.end),
+            None => (Utc::now() - chrono::Duration::hours(24), Utc::now()),
+        };
+
+        let points = api_ctx
+            .engagement_repo
+            .get_impact_points(convoy_uuid, start, end)
+            .await
+            .map_err(ApiError::from)?;
+
+        let resolution = grid_resolution_km.max(drone_domain::heatmap::MIN_GRID_RESOLUTION_KM);
+        let cells = drone_domain::bin_impacts(&points, resolution);
+
+        Ok(EngagementHeatmap {
+            convoy_id,
+            grid_resolution_km: resolution,
+            start,
+            end,
+            total_engagements: points.len() as i32,
+            cells: cells.into_iter().map(HeatmapCell::from).collect(),
         })
     }
 
     /// Get engagements for a specific drone
+    ///
+    /// Newest first within the drone's current convoy, read from the
+    /// by-drone engagement table. Paging and filtering work as for
+    /// `engagements`.
     #[graphql(name = "droneEngagements")]
     async fn get_drone_engagements(
         &self,
-        _ctx: &Context<'_>,
+        ctx: &Context<'_>,
         #[graphql(desc = "Drone ID")]
         drone_id: ID,
         #[graphql(desc = "Optional filter")]
         filter: Option<EngagementFilter>,
-        #[graphql(default, desc = "Pagination")]
-        pagination: PaginationInput,
+        #[graphql(desc = "Page size (default 20)")]
+        first: Option<i32>,
+        #[graphql(desc = "Cursor to continue after")]
+        after: Option<String>,
     ) -> Result<Connection<Engagement>> {
-        // TODO: Implement with engagement repository
-        Ok(Connection {
-            items: vec![],
-            total_count: 0,
-            has_next_page: false,
-            has_previous_page: false,
-        })
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        let first = pagination::page_size(first, MAX_ENGAGEMENT_PAGE)?;
+        let start = Cursor::resume(after.as_deref(), drone_uuid)?;
+        let convoy_uuid = api_ctx
+            .convoy_repo
+            .convoy_for_drone(drone_uuid)
+            .await
+            .map_err(ApiError::from)?;
+        let Some(convoy_uuid) = convoy_uuid else {
+            return Ok(Connection::new(Vec::new(), false, after.is_some(), Some(0)));
+        };
+        let scan_limit = match filter {
+            Some(_) => ENGAGEMENT_SCAN_LIMIT,
+            None => usize::MAX,
+        };
+        let repo = &api_ctx.engagement_repo;
+        let page = pagination::read_paged(
+            start,
+            first,
+            scan_limit,
+            |e: &Engagement| filter.as_ref().is_none_or(|f| engagement_matches(f, e)),
+            |state, size| async move {
+                let page = repo
+                    .get_drone_page(convoy_uuid, drone_uuid, size, state.as_deref())
+                    .await?;
+                Ok(drone_persistence::Page {
+                    items: page.items.into_iter().map(Engagement::from).collect(),
+                    paging_state: page.paging_state,
+                })
+            },
+        )
+        .await?;
+
+        Ok(page.into_connection(drone_uuid, after.is_some(), None))
     }
 
     // =========================================================================
-    // TELEMETRY QUERIES
+    // TARGET QUERIES
+    // =========================================================================
+
+    /// Get a convoy's tracked targets, most recently updated first
+    #[graphql(name = "targets")]
+    async fn targets(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Only targets with this status (default: all)")]
+        status: Option<TargetStatus>,
+    ) -> Result<Vec<Target>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let targets = api_ctx
+            .target_repo
+            .list(convoy_uuid, status.map(Into::into))
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(targets.into_iter().map(Target::from).collect())
+    }
+
+    /// Get a convoy's target hand-offs, oldest first
+    #[graphql(name = "targetHandoffs")]
+    async fn target_handoffs(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Only hand-offs of this target (default: all)")]
+        target_id: Option<ID>,
+    ) -> Result<Vec<TargetHandoff>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+        let target_uuid = target_id
+            .map(|id| Uuid::parse_str(&id))
+            .transpose()
+            .map_err(ApiError::from)?;
+
+        let handoffs = api_ctx
+            .target_repo
+            .list_handoffs(convoy_uuid, target_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(handoffs.into_iter().map(TargetHandoff::from).collect())
+    }
+
+    /// Get a convoy's no-strike zones
+    ///
+    /// Also drawn on the `aorGeoJson` overlay.
+    #[graphql(name = "noStrikeZones")]
+    async fn no_strike_zones(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<Vec<NoStrikeZone>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let zones = api_ctx
+            .convoy_repo
+            .list_no_strike_zones(convoy_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(zones.into_iter().map(NoStrikeZone::from).collect())
+    }
+
+    /// Get the documents stored when a convoy completed its mission
+    ///
+    /// Generated in the background after `updateConvoyStatus` sets
+    /// `COMPLETE`; download each from its `downloadUrl`.
+    #[graphql(name = "missionArtifacts")]
+    async fn mission_artifacts(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<Vec<MissionArtifact>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let artifacts = api_ctx
+            .convoy_repo
+            .list_artifacts(convoy_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(artifacts.into_iter().map(MissionArtifact::from).collect())
+    }
+
+    // =========================================================================
+    // TELEMETRY QUERIES
     // =========================================================================
 
     /// Get latest telemetry for a drone
     #[graphql(name = "latestTelemetry")]
     async fn get_latest_telemetry(
         &self,
-        _ctx: &Context<'_>,
+        ctx: &Context<'_>,
         #[graphql(desc = "Drone ID")]
         drone_id: ID,
     ) -> Result<Option<TelemetrySnapshot>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
         // TODO: Implement with telemetry repository
         Ok(Some(TelemetrySnapshot {
             drone_id,
@@ -423,32 +1154,628 @@ impl QueryRoot {
             fuel_remaining_pct: 75.5,
             current_waypoint: 15,
             velocity_mps: 80.0,
+            engine_temp_c: Some(85.0),
             mesh_connectivity: 0.95,
             distance_to_next_km: 12.5,
+            eta_next_waypoint_sec: None,
+            ambient_conditions: None,
+            schema_version: i32::from(drone_domain::TELEMETRY_SCHEMA_VERSION),
+            extensions: None,
+            event_id: None,
         }))
     }
 
+    /// Get the fuel endurance estimate from a drone's latest telemetry
+    #[graphql(name = "enduranceEstimate")]
+    async fn get_endurance_estimate(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+    ) -> Result<Option<EnduranceEstimate>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        let estimate: Option<drone_domain::EnduranceEstimate> = api_ctx
+            .cache
+            .get_endurance_estimate(drone_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(estimate.map(Into::into))
+    }
+
+    /// Get the capabilities of a platform type, including configured
+    /// overrides
+    #[graphql(name = "platformCapabilities")]
+    async fn platform_capabilities(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(name = "type", desc = "Platform type")]
+        platform_type: PlatformType,
+    ) -> Result<PlatformCapabilities> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        Ok(api_ctx.platform_capabilities.get(platform_type.into()).into())
+    }
+
+    /// Get a drone's running flight hours
+    ///
+    /// `None` until the drone has reported telemetry since the cache last
+    /// expired; the cumulative total is also on `Drone.flightTimeHrs`.
+    #[graphql(name = "flightHours")]
+    async fn get_flight_hours(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+    ) -> Result<Option<FlightHours>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        let hours: Option<drone_domain::FlightHours> = api_ctx
+            .cache
+            .get_flight_hours(drone_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(hours.map(|h| FlightHours::new(&h, &api_ctx.flight_hours_limits)))
+    }
+
+    /// Get a drone's health score from its latest telemetry
+    #[graphql(name = "droneHealth")]
+    async fn get_drone_health(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+    ) -> Result<Option<DroneHealth>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        let health: Option<drone_domain::DroneHealth> = api_ctx
+            .cache
+            .get_health_score(drone_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(health.map(Into::into))
+    }
+
+    /// Get the health distribution of a convoy and its weakest drones
+    #[graphql(name = "fleetReadiness")]
+    async fn get_fleet_readiness(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(default = 5, validator(minimum = 0, maximum = 100), desc = "Lowest-scoring drones to return")]
+        lowest: i32,
+    ) -> Result<FleetReadiness> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let roster = api_ctx
+            .cache
+            .get_convoy_roster(convoy_uuid)
+            .await
+            .map_err(ApiError::from)?;
+
+        let mut scores = Vec::with_capacity(roster.len());
+        let mut unscored = 0;
+        for drone_id in roster {
+            let health: Option<drone_domain::DroneHealth> = api_ctx
+                .cache
+                .get_health_score(drone_id)
+                .await
+                .map_err(ApiError::from)?;
+            match health {
+                Some(health) => scores.push(health),
+                None => unscored += 1,
+            }
+        }
+
+        Ok(drone_domain::FleetReadiness::compute(
+            convoy_uuid,
+            scores,
+            unscored,
+            usize::try_from(lowest).unwrap_or_default(),
+        )
+        .into())
+    }
+
+    /// Get a drone's telemetry over the last few minutes, oldest first
+    ///
+    /// Served from the cache; sized for sparklines that redraw on every
+    /// update.
+    #[graphql(name = "recentTelemetry")]
+    async fn get_recent_telemetry(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+    ) -> Result<Vec<TelemetrySnapshot>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        let points = api_ctx
+            .cache
+            .get_recent_telemetry(drone_uuid, Utc::now().timestamp_millis())
+            .await
+            .map_err(ApiError::from)?;
+        Ok(points)
+    }
+
     /// Get telemetry history for a drone
+    ///
+    /// Points come from the rolling per-drone history (one hour by default),
+    /// oldest first. With `resolutionSec`, points are averaged into buckets
+    /// of that width so long windows stay cheap to plot. Page size is capped
+    /// at 1000.
     #[graphql(name = "telemetryHistory")]
     async fn get_telemetry_history(
         &self,
-        _ctx: &Context<'_>,
+        ctx: &Context<'_>,
         #[graphql(desc = "Drone ID")]
         drone_id: ID,
         #[graphql(desc = "Time range")]
         time_range: TimeRangeInput,
-        #[graphql(default, desc = "Pagination")]
-        pagination: PaginationInput,
+        #[graphql(validator(minimum = 1), desc = "Aggregation bucket width in seconds (raw points when omitted)")]
+        resolution_sec: Option<i32>,
+        #[graphql(desc = "Page size (default 20)")]
+        first: Option<i32>,
+        #[graphql(desc = "Cursor to continue after")]
+        after: Option<String>,
     ) -> Result<Connection<TelemetrySnapshot>> {
-        // TODO: Implement with telemetry repository
-        Ok(Connection {
-            items: vec![],
-            total_count: 0,
-            has_next_page: false,
-            has_previous_page: false,
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        let first = pagination::page_size(first, MAX_TELEMETRY_PAGE)?;
+        let after_ms = Cursor::resume_timestamp(after.as_deref(), drone_uuid)?;
+
+        let points: Vec<TelemetrySnapshot> = api_ctx
+            .cache
+            .get_telemetry_history(
+                drone_uuid,
+                time_range.start.timestamp_millis(),
+                time_range.end.timestamp_millis(),
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        let points = match resolution_sec {
+            Some(secs) => downsample_telemetry(points, i64::from(secs)),
+            None => points,
+        };
+
+        // Buckets are computed over the whole window so they line up
+        // across pages; the cursor then skips what was already returned
+        let total_count = i32::try_from(points.len()).unwrap_or(i32::MAX);
+        let mut edges: Vec<(TelemetrySnapshot, String)> = points
+            .into_iter()
+            .filter(|p| after_ms.is_none_or(|ms| p.recorded_at.timestamp_millis() > ms))
+            .take(first + 1)
+            .map(|p| {
+                let position = Position::Timestamp(p.recorded_at.timestamp_millis());
+                let cursor = Cursor::new(drone_uuid, position).encode();
+                (p, cursor)
+            })
+            .collect();
+        let has_next_page = edges.len() > first;
+        edges.truncate(first);
+
+        Ok(Connection::new(edges, has_next_page, after.is_some(), Some(total_count)))
+    }
+
+    /// Get a drone's hits and misses per time bucket
+    ///
+    /// Computed by the analytics engine. Buckets are aligned to the bucket
+    /// width, ordered oldest first and zero-filled, so the series can be
+    /// drawn as stacked bars. `timeRange` may span at most 7 days.
+    #[graphql(name = "engagementTimeline")]
+    async fn engagement_timeline(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+        #[graphql(desc = "Time range")]
+        time_range: TimeRangeInput,
+        #[graphql(default_with = "TimeBucket::FiveMinutes", desc = "Bucket width")]
+        bucket: TimeBucket,
+    ) -> Result<Vec<EngagementTimelineBucket>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        check_timeline_range(time_range.start, time_range.end)?;
+
+        let (start, end) = (time_range.start, time_range.end);
+        let buckets = api_ctx
+            .run_analytics(move |engine| {
+                engine.engagement_timeline(drone_uuid, start, end, bucket.seconds())
+            })
+            .await?;
+
+        Ok(buckets.into_iter().map(EngagementTimelineBucket::from).collect())
+    }
+
+    /// Get a drone's flight track as a simplified polyline
+    ///
+    /// Reads recorded telemetry for the window (at most 24 hours) and applies
+    /// Ramer-Douglas-Peucker simplification, keeping the most significant
+    /// points up to `maxPoints`. Points are ordered oldest first.
+    #[graphql(name = "droneTrack")]
+    async fn drone_track(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+        #[graphql(desc = "Time range")]
+        time_range: TimeRangeInput,
+        #[graphql(default = 500, validator(minimum = 2, maximum = 5000), desc = "Maximum points to return")]
+        max_points: i32,
+    ) -> Result<DroneTrack> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        check_track_range(time_range.start, time_range.end)?;
+
+        let raw = api_ctx
+            .telemetry_repo
+            .get_track(drone_uuid, time_range.start, time_range.end)
+            .await
+            .map_err(ApiError::from)?;
+        let points = drone_domain::simplify_track(&raw, max_points as usize);
+
+        Ok(DroneTrack {
+            drone_id,
+            raw_point_count: raw.len() as i32,
+            points: points.into_iter().map(TrackPoint::from).collect(),
         })
     }
 
+    // =========================================================================
+    // GEOJSON QUERIES
+    // =========================================================================
+
+    /// Get a drone's planned route as a GeoJSON FeatureCollection
+    ///
+    /// A LineString through the waypoints in sequence order plus a Point per
+    /// waypoint. Also served at `/geojson/route/{droneId}`.
+    #[graphql(name = "routeGeoJson")]
+    async fn route_geo_json(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+    ) -> Result<Json<FeatureCollection>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        Ok(Json(geojson::route(api_ctx, drone_uuid).await?))
+    }
+
+    /// Get a convoy's area of responsibility as a GeoJSON FeatureCollection
+    ///
+    /// The AOR circle approximated as a Polygon plus its centre Point, and a
+    /// Polygon per no-strike zone. Also served at `/geojson/aor/{convoyId}`.
+    #[graphql(name = "aorGeoJson")]
+    async fn aor_geo_json(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+    ) -> Result<Json<FeatureCollection>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        Ok(Json(geojson::aor(api_ctx, convoy_uuid).await?))
+    }
+
+    /// Get a drone's flown track as a GeoJSON FeatureCollection
+    ///
+    /// The simplified track as a LineString plus a timestamped Point per
+    /// kept position, under the same window limit as `droneTrack`. Also
+    /// served at `/geojson/track/{droneId}?start=&end=`.
+    #[graphql(name = "trackGeoJson")]
+    async fn track_geo_json(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID")]
+        drone_id: ID,
+        #[graphql(desc = "Time range")]
+        time_range: TimeRangeInput,
+        #[graphql(default = 500, validator(minimum = 2, maximum = 5000), desc = "Maximum points to return")]
+        max_points: i32,
+    ) -> Result<Json<FeatureCollection>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+
+        let collection = geojson::track(
+            api_ctx,
+            drone_uuid,
+            time_range.start,
+            time_range.end,
+            max_points as usize,
+        )
+        .await?;
+        Ok(Json(collection))
+    }
+
+    // =========================================================================
+    // ANALYTICS QUERIES
+    // =========================================================================
+
+    /// Run an ad-hoc read-only SQL query against the analytics store
+    ///
+    /// Restricted to a single SELECT that cannot read files or URLs; results
+    /// are capped and time-limited.
+    /// Requires the ANALYST role and a token not scoped to a commanding unit.
+    #[graphql(name = "analyticsSql", guard = "RoleGuard::new(Role::Analyst)")]
+    async fn analytics_sql(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Single SELECT statement with ? placeholders")]
+        sql: String,
+        #[graphql(desc = "Positional parameters")]
+        params: Option<Json<Vec<serde_json::Value>>>,
+    ) -> Result<Json<Vec<serde_json::Value>>> {
+        auth::claims(ctx).require_all_units()?;
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let limits = api_ctx.analytics_limits;
+        let params = params.map(|p| p.0).unwrap_or_default();
+
+        tracing::info!(sql = %sql, "Executing analyst SQL");
+
+        let rows = api_ctx
+            .run_analytics(move |engine| engine.execute_readonly_sql_with(&sql, &params, &limits))
+            .await?;
+
+        Ok(Json(rows))
+    }
+
+    // =========================================================================
+    // ALERT QUERIES
+    // =========================================================================
+
+    /// Get a convoy's alerts, newest first
+    ///
+    /// Unacknowledged alerts only unless `includeAcknowledged` is set.
+    /// Requires the OPERATOR role.
+    #[graphql(name = "activeAlerts", guard = "RoleGuard::new(Role::Operator)")]
+    async fn active_alerts(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(default = 50, validator(maximum = 500), desc = "Maximum alerts to return")]
+        limit: i32,
+        #[graphql(default = false, desc = "Include alerts already acknowledged")]
+        include_acknowledged: bool,
+    ) -> Result<Vec<Alert>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let alerts = api_ctx
+            .alert_repo
+            .get_recent(convoy_uuid, limit.max(0) as usize, include_acknowledged)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(alerts.into_iter().map(Alert::from).collect())
+    }
+
+    // =========================================================================
+    // JOURNAL & REPLAY QUERIES
+    // =========================================================================
+
+    /// Get a convoy's operator journal entries, oldest first
+    ///
+    /// `timeRange` may span at most 7 days.
+    async fn journal(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Time range")]
+        time_range: TimeRangeInput,
+        #[graphql(default = 500, validator(minimum = 1, maximum = 5000), desc = "Maximum entries to return")]
+        limit: i32,
+    ) -> Result<Vec<JournalEntry>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        check_timeline_range(time_range.start, time_range.end)?;
+
+        let limit = usize::try_from(limit).unwrap_or_default();
+        let entries = api_ctx
+            .journal_repo
+            .get_range(convoy_uuid, time_range.start, time_range.end, limit)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(entries.into_iter().map(JournalEntry::from).collect())
+    }
+
+    /// Get a convoy's engagements and journal entries for after-action replay
+    ///
+    /// Events are interleaved oldest first. Pair with `droneTrack` for the
+    /// drones' positions over the same window. `timeRange` may span at most
+    /// 7 days; each event kind is capped at 5000, keeping the latest.
+    #[graphql(name = "engagementReplay")]
+    async fn engagement_replay(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Time range")]
+        time_range: TimeRangeInput,
+    ) -> Result<EngagementReplay> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        check_timeline_range(time_range.start, time_range.end)?;
+
+        let (engagements, journal) = tokio::try_join!(
+            api_ctx.engagement_repo.get_range(
+                convoy_uuid,
+                time_range.start,
+                time_range.end,
+                MAX_REPLAY_EVENTS,
+            ),
+            api_ctx.journal_repo.get_range(
+                convoy_uuid,
+                time_range.start,
+                time_range.end,
+                MAX_REPLAY_EVENTS,
+            ),
+        )
+        .map_err(ApiError::from)?;
+        let truncated =
+            engagements.len() >= MAX_REPLAY_EVENTS || journal.len() >= MAX_REPLAY_EVENTS;
+
+        Ok(EngagementReplay::new(
+            convoy_id,
+            time_range.start,
+            time_range.end,
+            engagements,
+            journal,
+            truncated,
+        ))
+    }
+
+    /// Get the convoy events a subscriber missed while disconnected
+    ///
+    /// Returns engagement, leaderboard, drone status, alert and authorization
+    /// events broadcast after `sinceEventId`, oldest first, from the last few
+    /// minutes. Resubscribe first, then replay from the `eventId` of the last
+    /// event received, dropping any event already seen. Alerts and
+    /// authorizations are only returned to operators.
+    #[graphql(name = "replayEvents")]
+    async fn replay_events(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Last event received; omit to get everything retained")]
+        since_event_id: Option<ID>,
+    ) -> Result<MissedEvents> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+
+        let since = since_event_id.as_ref().map(|id| id.as_str());
+        let (mut events, complete) = replay::missed_events(api_ctx, convoy_uuid, since).await?;
+        if !claims.role.permits(Role::Operator) {
+            events.retain(|e| !matches!(e.kind, MissedEventKind::Alert | MissedEventKind::Authorization));
+        }
+
+        Ok(MissedEvents {
+            convoy_id,
+            events,
+            complete,
+        })
+    }
+
+    // =========================================================================
+    // ENGAGEMENT AUTHORIZATION QUERIES
+    // =========================================================================
+
+    /// Get a convoy's engagement authorization requests, newest first
+    ///
+    /// Approved requests include their authorization code. Requires the
+    /// OPERATOR role.
+    #[graphql(name = "engagementAuthorizations", guard = "RoleGuard::new(Role::Operator)")]
+    async fn engagement_authorizations(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID")]
+        convoy_id: ID,
+        #[graphql(desc = "Only requests with this status (default: all)")]
+        status: Option<AuthorizationStatus>,
+        #[graphql(default = 50, validator(maximum = 500), desc = "Maximum requests to return")]
+        limit: i32,
+    ) -> Result<Vec<EngagementAuthorization>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&auth::claims(ctx), convoy_uuid).await?;
+
+        let requests = api_ctx
+            .authorization_repo
+            .list(convoy_uuid, status.map(Into::into), limit.max(0) as usize)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(requests
+            .into_iter()
+            .map(|r| EngagementAuthorization::signed(r, &api_ctx.authorization_signer))
+            .collect())
+    }
+
+    // =========================================================================
+    // ADMIN QUERIES
+    // =========================================================================
+
+    /// Current subscription connection counts
+    ///
+    /// Requires the ADMIN role.
+    #[graphql(name = "connections", guard = "RoleGuard::new(Role::Admin)")]
+    async fn connections(&self, ctx: &Context<'_>) -> Result<ConnectionStats> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let tracker = &api_ctx.ws_connections;
+        let limits = tracker.limits();
+
+        Ok(ConnectionStats {
+            active: i32::try_from(tracker.active()).unwrap_or(i32::MAX),
+            max_connections: i32::try_from(limits.max_connections).unwrap_or(i32::MAX),
+            max_connections_per_ip: i32::try_from(limits.max_connections_per_ip)
+                .unwrap_or(i32::MAX),
+            by_ip: tracker
+                .by_ip()
+                .into_iter()
+                .map(|(ip, n)| IpConnectionCount {
+                    ip: ip.to_string(),
+                    connections: i32::try_from(n).unwrap_or(i32::MAX),
+                })
+                .collect(),
+        })
+    }
+
+    /// Machine-client API keys of the caller's unit and environment, oldest
+    /// first
+    ///
+    /// Requires the ADMIN role.
+    #[graphql(name = "apiKeys", guard = "RoleGuard::new(Role::Admin)")]
+    async fn api_keys(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Include revoked keys", default = false)]
+        include_revoked: bool,
+    ) -> Result<Vec<ApiKey>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let keys = api_ctx.api_keys.list(&auth::claims(ctx)).await?;
+
+        Ok(keys
+            .into_iter()
+            .filter(|k| include_revoked || k.is_active())
+            .map(Into::into)
+            .collect())
+    }
+
     // =========================================================================
     // HEALTH CHECK
     // =========================================================================
@@ -465,3 +1792,133 @@ impl QueryRoot {
         Ok(env!("CARGO_PKG_VERSION").to_string())
     }
 }
+
+/// Whether an engagement passes every criterion set on the filter.
+fn engagement_matches(filter: &EngagementFilter, e: &Engagement) -> bool {
+    filter.hit.is_none_or(|hit| e.hit == hit)
+        && filter.weapon_type.is_none_or(|w| e.weapon_type == w)
+        && filter
+            .damage_assessment
+            .is_none_or(|d| e.damage_assessment == d)
+        && filter
+            .time_range
+            .as_ref()
+            .is_none_or(|r| e.engaged_at >= r.start && e.engaged_at <= r.end)
+}
+
+/// Whether a drone passes every criterion set on the filter.
+fn drone_matches(filter: &DroneFilter, d: &Drone) -> bool {
+    filter.status.is_none_or(|s| d.status == s)
+        && filter.platform_type.is_none_or(|p| d.platform_type == p)
+        && filter
+            .min_fuel_pct
+            .is_none_or(|min| f64::from(d.fuel_remaining_pct) >= min)
+}
+
+/// Average telemetry points into `resolution_sec` wide buckets.
+///
+/// Each bucket keeps the last point's position and waypoint context and
+/// replaces the plotted metrics with their mean. Input must be oldest first.
+fn downsample_telemetry(points: Vec<TelemetrySnapshot>, resolution_sec: i64) -> Vec<TelemetrySnapshot> {
+    let resolution_sec = resolution_sec.max(1);
+    let mut buckets: Vec<Vec<TelemetrySnapshot>> = Vec::new();
+    let mut current_bucket = None;
+
+    for point in points {
+        let bucket = point.recorded_at.timestamp().div_euclid(resolution_sec);
+        match buckets.last_mut() {
+            Some(group) if current_bucket == Some(bucket) => group.push(point),
+            _ => {
+                current_bucket = Some(bucket);
+                buckets.push(vec![point]);
+            }
+        }
+    }
+
+    buckets
+        .into_iter()
+        .filter_map(|group| {
+            let n = group.len() as f64;
+            let mean = |f: fn(&TelemetrySnapshot) -> f64| group.iter().map(f).sum::<f64>() / n;
+            let altitude_m = mean(|p| p.position.altitude_m);
+            let speed_mps = mean(|p| f64::from(p.position.speed_mps));
+            let fuel_remaining_pct = mean(|p| f64::from(p.fuel_remaining_pct));
+            let velocity_mps = mean(|p| f64::from(p.velocity_mps));
+            let temps: Vec<f32> = group.iter().filter_map(|p| p.engine_temp_c).collect();
+            let engine_temp_c =
+                (!temps.is_empty()).then(|| temps.iter().sum::<f32>() / temps.len() as f32);
+
+            let mut last = group.into_iter().last()?;
+            last.position.altitude_m = altitude_m;
+            last.position.speed_mps = speed_mps as f32;
+            last.fuel_remaining_pct = fuel_remaining_pct as f32;
+            last.velocity_mps = velocity_mps as f32;
+            last.engine_temp_c = engine_temp_c;
+            Some(last)
+        })
+        .collect()
+}
+
+/// Parse an `epoch.version` leaderboard version token
+fn parse_leaderboard_version(token: &str) -> Option<(i64, i64)> {
+    let (epoch, version) = token.split_once('.')?;
+    Some((epoch.parse().ok()?, version.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone};
+
+    fn point(recorded_at: DateTime<Utc>, altitude_m: f64, engine_temp_c: Option<f32>) -> TelemetrySnapshot {
+        TelemetrySnapshot {
+            drone_id: ID("drone".to_string()),
+            recorded_at,
+            position: Coordinates {
+                latitude: 31.6,
+                longitude: 65.7,
+                altitude_m,
+                heading_deg: 90.0,
+                speed_mps: 80.0,
+            },
+            fuel_remaining_pct: 50.0,
+            current_waypoint: 1,
+            velocity_mps: 80.0,
+            engine_temp_c,
+            mesh_connectivity: 1.0,
+            distance_to_next_km: 1.0,
+            eta_next_waypoint_sec: None,
+            ambient_conditions: None,
+            schema_version: i32::from(drone_domain::TELEMETRY_SCHEMA_VERSION),
+            extensions: None,
+            event_id: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_leaderboard_version() {
+        assert_eq!(parse_leaderboard_version("1700000000000.42"), Some((1_700_000_000_000, 42)));
+        assert_eq!(parse_leaderboard_version("42"), None);
+        assert_eq!(parse_leaderboard_version("a.b"), None);
+    }
+
+    #[test]
+    fn test_downsample_telemetry_averages_buckets() {
+        let t0 = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let at = |secs| t0 + chrono::Duration::seconds(secs);
+        let points = vec![
+            point(at(0), 1000.0, Some(80.0)),
+            point(at(5), 2000.0, None),
+            point(at(9), 3000.0, Some(90.0)),
+            point(at(12), 4000.0, None),
+        ];
+
+        let buckets = downsample_telemetry(points, 10);
+
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].recorded_at, at(9));
+        assert!((buckets[0].position.altitude_m - 2000.0).abs() < 1e-9);
+        assert_eq!(buckets[0].engine_temp_c, Some(85.0));
+        assert_eq!(buckets[1].engine_temp_c, None);
+    }
+}
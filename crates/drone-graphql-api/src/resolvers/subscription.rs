@@ -2,11 +2,26 @@
 //!
 //! Real-time event subscriptions for the drone convoy API.
 
-use async_graphql::{Context, Subscription, ID};
+use std::collections::HashSet;
+use std::time::Duration;
+
+use async_graphql::{Context, Result, Subscription, ID};
 use futures_util::Stream;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::broadcast::Receiver;
+use tokio::time::Instant;
+use uuid::Uuid;
 
+use crate::auth::{self, Role, RoleGuard};
 use crate::context::ApiContext;
+use crate::error::{ApiError, ApiResult};
+use crate::live::LiveLeaderboard;
+use crate::merge::{ReorderBuffer, DEFAULT_REORDER_WINDOW};
 use crate::schema::*;
+use crate::stats;
+
+/// Most drones a subscription's `droneIds` filter may name
+const MAX_FILTER_DRONES: usize = 64;
 
 /// GraphQL Subscription root
 pub struct SubscriptionRoot;
@@ -15,41 +30,68 @@ pub struct SubscriptionRoot;
 impl SubscriptionRoot {
     /// Subscribe to engagement events for a convoy
     ///
-    /// Emits an event whenever a drone records a hit or miss.
+    /// Emits an event whenever a drone records a hit or miss. `droneIds`
+    /// and `hitOnly` narrow the stream on the server and combine: both
+    /// given, only hits by the listed drones are sent. `droneIds` must name
+    /// between 1 and 64 of the convoy's drones.
     #[graphql(name = "engagementEvents")]
     async fn engagement_events(
         &self,
         ctx: &Context<'_>,
         #[graphql(desc = "Convoy ID to filter events for")]
         convoy_id: ID,
-    ) -> impl Stream<Item = EngagementEvent> {
-        let api_ctx = ctx.data::<ApiContext>().unwrap();
+        #[graphql(desc = "Only engagements by these drones (default: all)")]
+        drone_ids: Option<Vec<ID>>,
+        #[graphql(default, desc = "Only engagements that hit")]
+        hit_only: bool,
+    ) -> Result<impl Stream<Item = EngagementEvent>> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+        let drones = drone_filter(api_ctx, convoy_uuid, drone_ids).await?;
         let mut rx = api_ctx.engagement_tx.subscribe();
         let filter_id = convoy_id.to_string();
 
-        async_stream::stream! {
+        Ok(async_stream::stream! {
             while let Ok(event) = rx.recv().await {
-                if event.convoy_id.as_str() == filter_id {
+                if event.convoy_id.as_str() != filter_id || (hit_only && !event.hit) {
+                    continue;
+                }
+                if drones.as_ref().is_none_or(|d| d.contains(event.drone_id.as_str())) {
                     yield event;
                 }
             }
-        }
+        })
     }
 
     /// Subscribe to all engagement events across all convoys
+    ///
+    /// Convoy-scoped tokens only receive events for their convoys, and
+    /// unit-scoped tokens only for their commanding unit's convoys.
     #[graphql(name = "allEngagementEvents")]
     async fn all_engagement_events(
         &self,
         ctx: &Context<'_>,
-    ) -> impl Stream<Item = EngagementEvent> {
-        let api_ctx = ctx.data::<ApiContext>().unwrap();
+    ) -> Result<impl Stream<Item = EngagementEvent>> {
+        let claims = auth::claims(ctx);
+        let api_ctx = ctx.data::<ApiContext>()?.clone();
         let mut rx = api_ctx.engagement_tx.subscribe();
 
-        async_stream::stream! {
+        Ok(async_stream::stream! {
             while let Ok(event) = rx.recv().await {
-                yield event;
+                if !claims.can_access_convoy(&event.convoy_id) {
+                    continue;
+                }
+                let Ok(convoy_uuid) = Uuid::parse_str(&event.convoy_id) else {
+                    continue;
+                };
+                if api_ctx.authorize_convoy(&claims, convoy_uuid).await.is_ok() {
+                    yield event;
+                }
             }
-        }
+        })
     }
 
     /// Subscribe to leaderboard position changes
@@ -61,18 +103,82 @@ impl SubscriptionRoot {
         ctx: &Context<'_>,
         #[graphql(desc = "Convoy ID to filter updates for")]
         convoy_id: ID,
-    ) -> impl Stream<Item = LeaderboardUpdateEvent> {
-        let api_ctx = ctx.data::<ApiContext>().unwrap();
+    ) -> Result<impl Stream<Item = LeaderboardUpdateEvent>> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
         let mut rx = api_ctx.leaderboard_tx.subscribe();
         let filter_id = convoy_id.to_string();
 
-        async_stream::stream! {
+        Ok(async_stream::stream! {
             while let Ok(event) = rx.recv().await {
                 if event.convoy_id.as_str() == filter_id {
                     yield event;
                 }
             }
-        }
+        })
+    }
+
+    /// Subscribe to a convoy's leaderboard as a snapshot followed by deltas
+    ///
+    /// The first message is a SNAPSHOT of the top `limit` entries. Each
+    /// later DELTA carries only the entries whose rank or stats changed, and
+    /// drones that fell out of the top `limit`. `sequence` increases by one
+    /// per message; if the server falls behind it sends a fresh SNAPSHOT.
+    #[graphql(name = "leaderboardLive")]
+    async fn leaderboard_live(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID to follow")]
+        convoy_id: ID,
+        #[graphql(default = 100, validator(minimum = 1, maximum = 100), desc = "Maximum entries to track (default: 100, max: 100)")]
+        limit: i32,
+    ) -> Result<impl Stream<Item = LeaderboardLiveMessage>> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let api_ctx = ctx.data::<ApiContext>()?.clone();
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+
+        // Subscribe before reading the snapshot so no update falls between them
+        let mut rx = api_ctx.leaderboard_tx.subscribe();
+        let entries = api_ctx
+            .leaderboard_repo
+            .get_leaderboard(convoy_uuid, limit)
+            .await
+            .map_err(ApiError::from)?;
+        let filter_id = convoy_id.to_string();
+
+        Ok(async_stream::stream! {
+            let mut live = LiveLeaderboard::new(convoy_uuid);
+            yield live.snapshot(entries);
+
+            loop {
+                let resync = match rx.recv().await {
+                    Ok(event) if event.convoy_id.as_str() == filter_id => false,
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(_)) => true,
+                    Err(RecvError::Closed) => break,
+                };
+                // Updates queued behind this one are covered by the same read
+                drain(&mut rx);
+
+                let entries = match api_ctx.leaderboard_repo.get_leaderboard(convoy_uuid, limit).await {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        tracing::warn!(convoy_id = %convoy_uuid, error = %e, "Failed to read live leaderboard");
+                        continue;
+                    }
+                };
+                if resync {
+                    yield live.snapshot(entries);
+                } else if let Some(delta) = live.delta(entries) {
+                    yield delta;
+                }
+            }
+        })
     }
 
     /// Subscribe to drone status changes
@@ -82,22 +188,100 @@ impl SubscriptionRoot {
         ctx: &Context<'_>,
         #[graphql(desc = "Convoy ID to filter events for")]
         convoy_id: ID,
-    ) -> impl Stream<Item = DroneStatusEvent> {
-        let api_ctx = ctx.data::<ApiContext>().unwrap();
+    ) -> Result<impl Stream<Item = DroneStatusEvent>> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
         let mut rx = api_ctx.drone_status_tx.subscribe();
         let filter_id = convoy_id.to_string();
 
-        async_stream::stream! {
+        Ok(async_stream::stream! {
             while let Ok(event) = rx.recv().await {
                 if event.convoy_id.as_str() == filter_id {
                     yield event;
                 }
             }
-        }
+        })
+    }
+
+    /// Subscribe to recomputed convoy statistics
+    ///
+    /// Emits the current stats immediately, then again after engagements,
+    /// drone status changes, or telemetry from the convoy's drones, at most
+    /// once per `intervalSec`.
+    #[graphql(name = "convoyStatsUpdates")]
+    async fn convoy_stats_updates(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID to compute stats for")]
+        convoy_id: ID,
+        #[graphql(default = 2, validator(minimum = 1, maximum = 300), desc = "Minimum seconds between updates")]
+        interval_sec: i32,
+    ) -> Result<impl Stream<Item = ConvoyStats>> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let api_ctx = ctx.data::<ApiContext>()?.clone();
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+        let mut engagements = api_ctx.engagement_tx.subscribe();
+        let mut statuses = api_ctx.drone_status_tx.subscribe();
+        let mut telemetry = api_ctx.telemetry_tx.subscribe();
+        let interval = Duration::from_secs(interval_sec.unsigned_abs().into());
+        let filter_id = convoy_id.to_string();
+
+        Ok(async_stream::stream! {
+            let mut last_emit: Option<Instant> = None;
+            loop {
+                // Changes arriving while waiting out the interval are covered
+                // by this recomputation
+                if let Some(at) = last_emit {
+                    tokio::time::sleep_until(at + interval).await;
+                }
+                drain(&mut engagements);
+                drain(&mut statuses);
+                drain(&mut telemetry);
+
+                match stats::convoy_stats(&api_ctx, convoy_uuid).await {
+                    Ok(stats) => yield stats,
+                    Err(e) => tracing::warn!(convoy_id = %convoy_uuid, error = %e, "Failed to compute convoy stats"),
+                }
+                last_emit = Some(Instant::now());
+
+                // Telemetry carries no convoy, so match it against the roster
+                let roster: HashSet<String> = api_ctx
+                    .cache
+                    .get_convoy_roster(convoy_uuid)
+                    .await
+                    .map(|r| r.iter().map(Uuid::to_string).collect())
+                    .unwrap_or_default();
+
+                let changed = loop {
+                    let relevant = tokio::select! {
+                        event = engagements.recv() => event.map(|e| e.convoy_id.as_str() == filter_id),
+                        event = statuses.recv() => event.map(|e| e.convoy_id.as_str() == filter_id),
+                        event = telemetry.recv() => event.map(|t| roster.contains(t.drone_id.as_str())),
+                    };
+                    match relevant {
+                        Ok(true) | Err(RecvError::Lagged(_)) => break true,
+                        Ok(false) => {}
+                        Err(RecvError::Closed) => break false,
+                    }
+                };
+                if !changed {
+                    break;
+                }
+            }
+        })
     }
 
     /// Subscribe to alerts for a convoy
-    #[graphql(name = "alerts")]
+    ///
+    /// Restricted to operators with access to the convoy. `minSeverity` and
+    /// `droneIds` combine as for `engagementEvents`; with `droneIds`,
+    /// convoy-wide alerts that name no drone are not sent.
+    #[graphql(name = "alerts", guard = "RoleGuard::new(Role::Operator)")]
     async fn alerts(
         &self,
         ctx: &Context<'_>,
@@ -105,16 +289,31 @@ impl SubscriptionRoot {
         convoy_id: ID,
         #[graphql(desc = "Minimum severity to receive (default: all)")]
         min_severity: Option<AlertSeverity>,
-    ) -> impl Stream<Item = AlertEvent> {
-        let api_ctx = ctx.data::<ApiContext>().unwrap();
+        #[graphql(desc = "Only alerts raised by these drones (default: all)")]
+        drone_ids: Option<Vec<ID>>,
+    ) -> Result<impl Stream<Item = AlertEvent>> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+        let drones = drone_filter(api_ctx, convoy_uuid, drone_ids).await?;
         let mut rx = api_ctx.alert_tx.subscribe();
         let filter_id = convoy_id.to_string();
 
-        async_stream::stream! {
+        Ok(async_stream::stream! {
             while let Ok(event) = rx.recv().await {
                 if event.convoy_id.as_str() != filter_id {
                     continue;
                 }
+                let from_drone = match (&drones, &event.drone_id) {
+                    (None, _) => true,
+                    (Some(drones), Some(drone_id)) => drones.contains(drone_id.as_str()),
+                    (Some(_), None) => false,
+                };
+                if !from_drone {
+                    continue;
+                }
 
                 // Filter by severity if specified
                 let passes_filter = match min_severity {
@@ -132,7 +331,129 @@ impl SubscriptionRoot {
                     yield event;
                 }
             }
-        }
+        })
+    }
+
+    /// Subscribe to every kind of convoy event on one stream
+    ///
+    /// Merges what `engagementEvents`, `leaderboardUpdates`,
+    /// `droneStatusChanges` and `alerts` send, holding each event briefly so
+    /// the stream is ordered by event timestamp. Alerts require the Operator
+    /// role; when `types` is omitted other roles receive everything else.
+    #[graphql(name = "events")]
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID to filter events for")]
+        convoy_id: ID,
+        #[graphql(desc = "Event types to receive (default: all the caller may see)")]
+        types: Option<Vec<EventType>>,
+    ) -> Result<impl Stream<Item = ConvoyEvent>> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let types: HashSet<EventType> = match types {
+            Some(types) => {
+                if types.contains(&EventType::Alert) {
+                    claims.role.require(Role::Operator)?;
+                }
+                types.into_iter().collect()
+            }
+            None => EventType::ALL
+                .into_iter()
+                .filter(|t| *t != EventType::Alert || claims.role.permits(Role::Operator))
+                .collect(),
+        };
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+        let mut engagements = api_ctx.engagement_tx.subscribe();
+        let mut leaderboard = api_ctx.leaderboard_tx.subscribe();
+        let mut statuses = api_ctx.drone_status_tx.subscribe();
+        let mut alerts = api_ctx.alert_tx.subscribe();
+        let filter_id = convoy_id.to_string();
+
+        Ok(async_stream::stream! {
+            let mut buffer = ReorderBuffer::new(DEFAULT_REORDER_WINDOW);
+            loop {
+                let deadline = buffer.next_release();
+                // `None` when the next held event fell due
+                let received = tokio::select! {
+                    event = engagements.recv() => Some(event.map(ConvoyEvent::from)),
+                    event = leaderboard.recv() => Some(event.map(ConvoyEvent::from)),
+                    event = statuses.recv() => Some(event.map(ConvoyEvent::from)),
+                    event = alerts.recv() => Some(event.map(ConvoyEvent::from)),
+                    () = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)),
+                        if deadline.is_some() => None,
+                };
+                match received {
+                    Some(Ok(event)) => {
+                        if types.contains(&event.event_type()) && event.belongs_to(&filter_id) {
+                            buffer.push(event.occurred_at(), event, Instant::now());
+                        }
+                    }
+                    Some(Err(RecvError::Lagged(_))) | None => {}
+                    Some(Err(RecvError::Closed)) => break,
+                }
+                for event in buffer.release(Instant::now()) {
+                    yield event;
+                }
+            }
+        })
+    }
+
+    /// Subscribe to engagement authorization requests for a convoy
+    ///
+    /// Emits new pending requests for the approval queue, followed by each
+    /// decision and execution so clients can drop requests that are no
+    /// longer pending. Restricted to operators and commanders.
+    #[graphql(name = "pendingAuthorizations", guard = "RoleGuard::new(Role::Operator)")]
+    async fn pending_authorizations(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID to filter requests for")]
+        convoy_id: ID,
+    ) -> Result<impl Stream<Item = EngagementAuthorization>> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+        let mut rx = api_ctx.authorization_tx.subscribe();
+        let filter_id = convoy_id.to_string();
+
+        Ok(async_stream::stream! {
+            while let Ok(request) = rx.recv().await {
+                if request.convoy_id.as_str() == filter_id {
+                    yield request;
+                }
+            }
+        })
+    }
+
+    /// Subscribe to sensor tasks assigned to a drone
+    ///
+    /// Emits each assignment as it is made; the drone applies it on
+    /// reaching the waypoint.
+    #[graphql(name = "sensorTasks")]
+    async fn sensor_tasks(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Drone ID to receive tasks for")]
+        drone_id: ID,
+    ) -> Result<impl Stream<Item = SensorTask>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
+        let mut rx = api_ctx.sensor_task_tx.subscribe();
+        let filter_id = drone_id.to_string();
+
+        Ok(async_stream::stream! {
+            while let Ok(task) = rx.recv().await {
+                if task.drone_id.as_str() == filter_id {
+                    yield task;
+                }
+            }
+        })
     }
 
     /// Subscribe to telemetry updates for a specific drone
@@ -142,18 +463,50 @@ impl SubscriptionRoot {
         ctx: &Context<'_>,
         #[graphql(desc = "Drone ID to receive telemetry for")]
         drone_id: ID,
-    ) -> impl Stream<Item = TelemetrySnapshot> {
-        let api_ctx = ctx.data::<ApiContext>().unwrap();
+    ) -> Result<impl Stream<Item = TelemetrySnapshot>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_uuid = Uuid::parse_str(&drone_id).map_err(ApiError::from)?;
+        api_ctx.authorize_drone(&auth::claims(ctx), drone_uuid).await?;
         let mut rx = api_ctx.telemetry_tx.subscribe();
         let filter_id = drone_id.to_string();
 
-        async_stream::stream! {
+        Ok(async_stream::stream! {
             while let Ok(snapshot) = rx.recv().await {
                 if snapshot.drone_id.as_str() == filter_id {
                     yield snapshot;
                 }
             }
-        }
+        })
+    }
+
+    /// Subscribe to analytics insights for a convoy
+    ///
+    /// Emits accuracy anomalies, underperforming weapons and drones trending
+    /// down as the periodic analytics evaluation detects them. Each insight
+    /// is sent once, and again only if it clears and recurs. Nothing is
+    /// emitted when the analytics engine is not configured.
+    #[graphql(name = "insights")]
+    async fn insights(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Convoy ID to receive insights for")]
+        convoy_id: ID,
+    ) -> Result<impl Stream<Item = InsightEvent>> {
+        let claims = auth::claims(ctx);
+        claims.require_convoy(&convoy_id)?;
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let convoy_uuid = Uuid::parse_str(&convoy_id).map_err(ApiError::from)?;
+        api_ctx.authorize_convoy(&claims, convoy_uuid).await?;
+        let mut rx = api_ctx.insight_tx.subscribe();
+        let filter_id = convoy_id.to_string();
+
+        Ok(async_stream::stream! {
+            while let Ok(insight) = rx.recv().await {
+                if insight.convoy_id.as_str() == filter_id {
+                    yield insight;
+                }
+            }
+        })
     }
 
     /// Heartbeat subscription for connection keep-alive
@@ -170,3 +523,77 @@ impl SubscriptionRoot {
         }
     }
 }
+
+/// Resolve a `droneIds` argument against the convoy's drones
+///
+/// `None` when the argument was omitted; otherwise the IDs in the form
+/// events carry them.
+async fn drone_filter(
+    api_ctx: &ApiContext,
+    convoy_id: Uuid,
+    drone_ids: Option<Vec<ID>>,
+) -> ApiResult<Option<HashSet<String>>> {
+    let Some(drone_ids) = drone_ids else {
+        return Ok(None);
+    };
+    let roster = api_ctx
+        .convoy_repo
+        .get(convoy_id)
+        .await?
+        .map(|convoy| convoy.drone_ids)
+        .unwrap_or_default();
+    parse_drone_filter(&drone_ids, &roster).map(Some)
+}
+
+/// Validate requested drone IDs against a convoy roster
+///
+/// An empty list, more than [`MAX_FILTER_DRONES`] IDs, or a drone outside
+/// the convoy is rejected rather than left to silently match nothing.
+fn parse_drone_filter(drone_ids: &[ID], roster: &[Uuid]) -> ApiResult<HashSet<String>> {
+    if drone_ids.is_empty() {
+        return Err(ApiError::InvalidInput(
+            "droneIds must name at least one drone; omit it to receive all".to_string(),
+        ));
+    }
+    if drone_ids.len() > MAX_FILTER_DRONES {
+        return Err(ApiError::InvalidInput(format!(
+            "droneIds may name at most {MAX_FILTER_DRONES} drones"
+        )));
+    }
+
+    drone_ids
+        .iter()
+        .map(|id| {
+            let drone_id = Uuid::parse_str(id)?;
+            if roster.contains(&drone_id) {
+                Ok(drone_id.to_string())
+            } else {
+                Err(ApiError::InvalidInput(format!("drone {drone_id} is not in the convoy")))
+            }
+        })
+        .collect()
+}
+
+/// Discard queued broadcasts, keeping the receiver open
+fn drain<T: Clone>(rx: &mut Receiver<T>) {
+    while !matches!(rx.try_recv(), Err(TryRecvError::Empty | TryRecvError::Closed)) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drone_filter_validation() {
+        let (alpha, bravo) = (Uuid::new_v4(), Uuid::new_v4());
+        let roster = [alpha, bravo];
+
+        let upper = ID(alpha.to_string().to_uppercase());
+        let drones = parse_drone_filter(&[upper, ID(alpha.to_string())], &roster).unwrap();
+        assert_eq!(drones, HashSet::from([alpha.to_string()]));
+
+        assert!(parse_drone_filter(&[], &roster).is_err());
+        assert!(parse_drone_filter(&[ID("REAPER-1".to_string())], &roster).is_err());
+        assert!(parse_drone_filter(&[ID(Uuid::new_v4().to_string())], &roster).is_err());
+    }
+}
@@ -78,6 +78,21 @@ impl From<domain::DroneStatus> for DroneStatus {
     }
 }
 
+impl From<DroneStatus> for domain::DroneStatus {
+    fn from(s: DroneStatus) -> Self {
+        match s {
+            DroneStatus::Preflight => Self::Preflight,
+            DroneStatus::Airborne => Self::Airborne,
+            DroneStatus::Loiter => Self::Loiter,
+            DroneStatus::Ingress => Self::Ingress,
+            DroneStatus::Egress => Self::Egress,
+            DroneStatus::Rtb => Self::Rtb,
+            DroneStatus::Landed => Self::Landed,
+            DroneStatus::Maintenance => Self::Maintenance,
+        }
+    }
+}
+
 /// Mission/convoy status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -106,6 +121,18 @@ impl From<domain::ConvoyStatus> for ConvoyStatus {
     }
 }
 
+impl From<ConvoyStatus> for domain::ConvoyStatus {
+    fn from(s: ConvoyStatus) -> Self {
+        match s {
+            ConvoyStatus::Planning => Self::Planning,
+            ConvoyStatus::Active => Self::Active,
+            ConvoyStatus::Rtb => Self::Rtb,
+            ConvoyStatus::Complete => Self::Complete,
+            ConvoyStatus::Abort => Self::Abort,
+        }
+    }
+}
+
 /// Mission type classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -122,6 +149,85 @@ pub enum MissionType {
     Sar,
 }
 
+impl From<domain::MissionType> for MissionType {
+    fn from(m: domain::MissionType) -> Self {
+        match m {
+            domain::MissionType::Isr => Self::Isr,
+            domain::MissionType::Strike => Self::Strike,
+            domain::MissionType::Escort => Self::Escort,
+            domain::MissionType::Resupply => Self::Resupply,
+            domain::MissionType::Sar => Self::Sar,
+        }
+    }
+}
+
+impl From<MissionType> for domain::MissionType {
+    fn from(m: MissionType) -> Self {
+        match m {
+            MissionType::Isr => Self::Isr,
+            MissionType::Strike => Self::Strike,
+            MissionType::Escort => Self::Escort,
+            MissionType::Resupply => Self::Resupply,
+            MissionType::Sar => Self::Sar,
+        }
+    }
+}
+
+/// Data environment a convoy belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+pub enum Environment {
+    /// Training and rehearsal missions
+    Exercise,
+    /// Operational missions
+    Live,
+    /// Integration and load testing
+    Test,
+}
+
+impl From<domain::Environment> for Environment {
+    fn from(e: domain::Environment) -> Self {
+        match e {
+            domain::Environment::Exercise => Self::Exercise,
+            domain::Environment::Live => Self::Live,
+            domain::Environment::Test => Self::Test,
+        }
+    }
+}
+
+impl From<Environment> for domain::Environment {
+    fn from(e: Environment) -> Self {
+        match e {
+            Environment::Exercise => Self::Exercise,
+            Environment::Live => Self::Live,
+            Environment::Test => Self::Test,
+        }
+    }
+}
+
+/// Quick-start convoy template
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+pub enum ConvoyTemplate {
+    /// Four MQ-9 Reapers on a strike mission
+    #[graphql(name = "STRIKE_4SHIP")]
+    Strike4Ship,
+    /// RQ-4 Global Hawk and MQ-1C Gray Eagle on an ISR mission
+    #[graphql(name = "ISR_2SHIP")]
+    Isr2Ship,
+    /// A single MQ-9 Reaper, shaped by overrides
+    #[graphql(name = "CUSTOM")]
+    Custom,
+}
+
+impl From<ConvoyTemplate> for domain::TemplateKind {
+    fn from(t: ConvoyTemplate) -> Self {
+        match t {
+            ConvoyTemplate::Strike4Ship => Self::Strike4Ship,
+            ConvoyTemplate::Isr2Ship => Self::Isr2Ship,
+            ConvoyTemplate::Custom => Self::Custom,
+        }
+    }
+}
+
 /// Waypoint type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -206,6 +312,91 @@ impl From<domain::WeaponType> for WeaponType {
     }
 }
 
+impl From<WeaponType> for domain::WeaponType {
+    fn from(w: WeaponType) -> Self {
+        match w {
+            WeaponType::Agm114Hellfire => Self::Agm114Hellfire,
+            WeaponType::Gbu12Paveway => Self::Gbu12Paveway,
+            WeaponType::Aim9xSidewinder => Self::Aim9xSidewinder,
+            WeaponType::Gbu38Jdam => Self::Gbu38Jdam,
+            WeaponType::Agm176Griffin => Self::Agm176Griffin,
+        }
+    }
+}
+
+/// Sensor payload type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum SensorType {
+    /// Electro-optical / infrared turret
+    EoIr,
+    /// Synthetic aperture radar
+    Sar,
+    /// Signals intelligence receiver
+    Sigint,
+    /// Laser ranging and mapping
+    Lidar,
+}
+
+impl From<domain::SensorType> for SensorType {
+    fn from(s: domain::SensorType) -> Self {
+        match s {
+            domain::SensorType::EoIr => Self::EoIr,
+            domain::SensorType::Sar => Self::Sar,
+            domain::SensorType::Sigint => Self::Sigint,
+            domain::SensorType::Lidar => Self::Lidar,
+        }
+    }
+}
+
+impl From<SensorType> for domain::SensorType {
+    fn from(s: SensorType) -> Self {
+        match s {
+            SensorType::EoIr => Self::EoIr,
+            SensorType::Sar => Self::Sar,
+            SensorType::Sigint => Self::Sigint,
+            SensorType::Lidar => Self::Lidar,
+        }
+    }
+}
+
+/// Weapon readiness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum, Default)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum WeaponState {
+    /// Ready to fire
+    #[default]
+    Armed,
+    /// Safed; not released
+    Safe,
+    /// Malfunctioned; cannot fire
+    Jammed,
+    /// No rounds remaining
+    Expended,
+}
+
+impl From<domain::WeaponState> for WeaponState {
+    fn from(s: domain::WeaponState) -> Self {
+        match s {
+            domain::WeaponState::Armed => Self::Armed,
+            domain::WeaponState::Safe => Self::Safe,
+            domain::WeaponState::Jammed => Self::Jammed,
+            domain::WeaponState::Expended => Self::Expended,
+        }
+    }
+}
+
+impl From<WeaponState> for domain::WeaponState {
+    fn from(s: WeaponState) -> Self {
+        match s {
+            WeaponState::Armed => Self::Armed,
+            WeaponState::Safe => Self::Safe,
+            WeaponState::Jammed => Self::Jammed,
+            WeaponState::Expended => Self::Expended,
+        }
+    }
+}
+
 /// Battle damage assessment
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -231,6 +422,17 @@ impl From<domain::DamageAssessment> for DamageAssessment {
     }
 }
 
+impl From<DamageAssessment> for domain::DamageAssessment {
+    fn from(d: DamageAssessment) -> Self {
+        match d {
+            DamageAssessment::Destroyed => Self::Destroyed,
+            DamageAssessment::Damaged => Self::Damaged,
+            DamageAssessment::Missed => Self::Missed,
+            DamageAssessment::PendingBda => Self::PendingBda,
+        }
+    }
+}
+
 /// Target type classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -249,6 +451,32 @@ pub enum TargetType {
     Supply,
 }
 
+impl From<domain::TargetType> for TargetType {
+    fn from(t: domain::TargetType) -> Self {
+        match t {
+            domain::TargetType::Vehicle => Self::Vehicle,
+            domain::TargetType::Structure => Self::Structure,
+            domain::TargetType::Personnel => Self::Personnel,
+            domain::TargetType::Radar => Self::Radar,
+            domain::TargetType::AirDefense => Self::AirDefense,
+            domain::TargetType::Supply => Self::Supply,
+        }
+    }
+}
+
+impl From<TargetType> for domain::TargetType {
+    fn from(t: TargetType) -> Self {
+        match t {
+            TargetType::Vehicle => Self::Vehicle,
+            TargetType::Structure => Self::Structure,
+            TargetType::Personnel => Self::Personnel,
+            TargetType::Radar => Self::Radar,
+            TargetType::AirDefense => Self::AirDefense,
+            TargetType::Supply => Self::Supply,
+        }
+    }
+}
+
 /// Threat level classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -263,6 +491,64 @@ pub enum ThreatLevel {
     Unknown,
 }
 
+impl From<domain::ThreatLevel> for ThreatLevel {
+    fn from(t: domain::ThreatLevel) -> Self {
+        match t {
+            domain::ThreatLevel::High => Self::High,
+            domain::ThreatLevel::Medium => Self::Medium,
+            domain::ThreatLevel::Low => Self::Low,
+            domain::ThreatLevel::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<ThreatLevel> for domain::ThreatLevel {
+    fn from(t: ThreatLevel) -> Self {
+        match t {
+            ThreatLevel::High => Self::High,
+            ThreatLevel::Medium => Self::Medium,
+            ThreatLevel::Low => Self::Low,
+            ThreatLevel::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Target lifecycle status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum TargetStatus {
+    /// Reported once
+    Detected,
+    /// Re-acquired by a later report
+    Tracked,
+    /// Engaged, not yet confirmed destroyed
+    Engaged,
+    /// Confirmed destroyed by BDA
+    Destroyed,
+}
+
+impl From<domain::TargetStatus> for TargetStatus {
+    fn from(s: domain::TargetStatus) -> Self {
+        match s {
+            domain::TargetStatus::Detected => Self::Detected,
+            domain::TargetStatus::Tracked => Self::Tracked,
+            domain::TargetStatus::Engaged => Self::Engaged,
+            domain::TargetStatus::Destroyed => Self::Destroyed,
+        }
+    }
+}
+
+impl From<TargetStatus> for domain::TargetStatus {
+    fn from(s: TargetStatus) -> Self {
+        match s {
+            TargetStatus::Detected => Self::Detected,
+            TargetStatus::Tracked => Self::Tracked,
+            TargetStatus::Engaged => Self::Engaged,
+            TargetStatus::Destroyed => Self::Destroyed,
+        }
+    }
+}
+
 /// Alert severity level
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -275,6 +561,340 @@ pub enum AlertSeverity {
     Info,
 }
 
+impl From<domain::AlertSeverity> for AlertSeverity {
+    fn from(s: domain::AlertSeverity) -> Self {
+        match s {
+            domain::AlertSeverity::Critical => Self::Critical,
+            domain::AlertSeverity::Warning => Self::Warning,
+            domain::AlertSeverity::Info => Self::Info,
+        }
+    }
+}
+
+impl From<AlertSeverity> for domain::AlertSeverity {
+    fn from(s: AlertSeverity) -> Self {
+        match s {
+            AlertSeverity::Critical => Self::Critical,
+            AlertSeverity::Warning => Self::Warning,
+            AlertSeverity::Info => Self::Info,
+        }
+    }
+}
+
+/// Outcome of forwarding an alert to an external channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum DeliveryStatus {
+    /// Accepted by the channel
+    Delivered,
+    /// Every retry failed
+    Failed,
+    /// Dropped by the channel's rate limit
+    RateLimited,
+}
+
+impl From<domain::DeliveryStatus> for DeliveryStatus {
+    fn from(s: domain::DeliveryStatus) -> Self {
+        match s {
+            domain::DeliveryStatus::Delivered => Self::Delivered,
+            domain::DeliveryStatus::Failed => Self::Failed,
+            domain::DeliveryStatus::RateLimited => Self::RateLimited,
+        }
+    }
+}
+
+/// What a machine-client API key may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum ApiKeyScope {
+    /// Telemetry ingest endpoint only
+    Ingest,
+    /// Read-only (viewer) access
+    ReadOnly,
+    /// Full access, including key management
+    Admin,
+}
+
+impl From<domain::ApiKeyScope> for ApiKeyScope {
+    fn from(s: domain::ApiKeyScope) -> Self {
+        match s {
+            domain::ApiKeyScope::Ingest => Self::Ingest,
+            domain::ApiKeyScope::ReadOnly => Self::ReadOnly,
+            domain::ApiKeyScope::Admin => Self::Admin,
+        }
+    }
+}
+
+impl From<ApiKeyScope> for domain::ApiKeyScope {
+    fn from(s: ApiKeyScope) -> Self {
+        match s {
+            ApiKeyScope::Ingest => Self::Ingest,
+            ApiKeyScope::ReadOnly => Self::ReadOnly,
+            ApiKeyScope::Admin => Self::Admin,
+        }
+    }
+}
+
+/// Engagement authorization status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum AuthorizationStatus {
+    /// Awaiting a commander's decision
+    Pending,
+    /// Approved; the authorization code may be used once
+    Approved,
+    /// Denied by a commander
+    Denied,
+    /// Approval consumed by an engagement
+    Executed,
+}
+
+impl From<domain::AuthorizationStatus> for AuthorizationStatus {
+    fn from(s: domain::AuthorizationStatus) -> Self {
+        match s {
+            domain::AuthorizationStatus::Pending => Self::Pending,
+            domain::AuthorizationStatus::Approved => Self::Approved,
+            domain::AuthorizationStatus::Denied => Self::Denied,
+            domain::AuthorizationStatus::Executed => Self::Executed,
+        }
+    }
+}
+
+impl From<AuthorizationStatus> for domain::AuthorizationStatus {
+    fn from(s: AuthorizationStatus) -> Self {
+        match s {
+            AuthorizationStatus::Pending => Self::Pending,
+            AuthorizationStatus::Approved => Self::Approved,
+            AuthorizationStatus::Denied => Self::Denied,
+            AuthorizationStatus::Executed => Self::Executed,
+        }
+    }
+}
+
+/// How an engagement targeting inside a no-strike zone is handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum, Default)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum ZoneEnforcement {
+    /// The engagement is rejected
+    #[default]
+    Block,
+    /// The engagement is recorded and an alert raised for review
+    Flag,
+}
+
+impl From<domain::ZoneEnforcement> for ZoneEnforcement {
+    fn from(e: domain::ZoneEnforcement) -> Self {
+        match e {
+            domain::ZoneEnforcement::Block => Self::Block,
+            domain::ZoneEnforcement::Flag => Self::Flag,
+        }
+    }
+}
+
+impl From<ZoneEnforcement> for domain::ZoneEnforcement {
+    fn from(e: ZoneEnforcement) -> Self {
+        match e {
+            ZoneEnforcement::Block => Self::Block,
+            ZoneEnforcement::Flag => Self::Flag,
+        }
+    }
+}
+
+/// Document generated when a convoy completes its mission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum ArtifactKind {
+    /// Analytics report as Markdown
+    AnalyticsReport,
+    /// Final leaderboard as CSV
+    Leaderboard,
+    /// Routes, engagements and journal as KML
+    Kml,
+}
+
+impl From<domain::ArtifactKind> for ArtifactKind {
+    fn from(k: domain::ArtifactKind) -> Self {
+        match k {
+            domain::ArtifactKind::AnalyticsReport => Self::AnalyticsReport,
+            domain::ArtifactKind::Leaderboard => Self::Leaderboard,
+            domain::ArtifactKind::Kml => Self::Kml,
+        }
+    }
+}
+
+/// Leaderboard scoring model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum, Default)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum ScoringModel {
+    /// Raw hit percentage
+    Accuracy,
+    /// Lower bound of the 95% Wilson score interval (default)
+    #[default]
+    WilsonLowerBound,
+    /// Accuracy shrunk towards a fleet-wide prior
+    BayesianAverage,
+    /// Accuracy weighted by log engagement volume
+    WeightedVolume,
+}
+
+impl From<domain::ScoringModel> for ScoringModel {
+    fn from(m: domain::ScoringModel) -> Self {
+        match m {
+            domain::ScoringModel::Accuracy => Self::Accuracy,
+            domain::ScoringModel::WilsonLowerBound => Self::WilsonLowerBound,
+            domain::ScoringModel::BayesianAverage => Self::BayesianAverage,
+            domain::ScoringModel::WeightedVolume => Self::WeightedVolume,
+        }
+    }
+}
+
+impl From<ScoringModel> for domain::ScoringModel {
+    fn from(m: ScoringModel) -> Self {
+        match m {
+            ScoringModel::Accuracy => Self::Accuracy,
+            ScoringModel::WilsonLowerBound => Self::WilsonLowerBound,
+            ScoringModel::BayesianAverage => Self::BayesianAverage,
+            ScoringModel::WeightedVolume => Self::WeightedVolume,
+        }
+    }
+}
+
+/// Entity kind returned by `search`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum SearchResultType {
+    /// Drone, matched by callsign or tail number
+    Drone,
+    /// Convoy, matched by callsign
+    Convoy,
+    /// Engagement, matched by ID prefix
+    Engagement,
+}
+
+impl From<domain::SearchKind> for SearchResultType {
+    fn from(k: domain::SearchKind) -> Self {
+        match k {
+            domain::SearchKind::Drone => Self::Drone,
+            domain::SearchKind::Convoy => Self::Convoy,
+            domain::SearchKind::Engagement => Self::Engagement,
+        }
+    }
+}
+
+impl From<SearchResultType> for domain::SearchKind {
+    fn from(t: SearchResultType) -> Self {
+        match t {
+            SearchResultType::Drone => Self::Drone,
+            SearchResultType::Convoy => Self::Convoy,
+            SearchResultType::Engagement => Self::Engagement,
+        }
+    }
+}
+
+/// Kind of `leaderboardLive` message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum LeaderboardLiveKind {
+    /// Full leaderboard; replaces any state the client holds
+    Snapshot,
+    /// Entries that changed since the previous message
+    Delta,
+}
+
+/// Kind of after-action replay event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum ReplayEventKind {
+    /// Weapon engagement
+    Engagement,
+    /// Operator journal entry
+    Journal,
+}
+
+/// Kind of event returned by `replayEvents`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum MissedEventKind {
+    /// As sent by `engagementEvents`
+    Engagement,
+    /// As sent by `leaderboardUpdates`
+    LeaderboardUpdate,
+    /// As sent by `droneStatusChanges`
+    DroneStatus,
+    /// As sent by `alerts`
+    Alert,
+    /// As sent by `pendingAuthorizations`
+    Authorization,
+}
+
+/// Kind of event sent by the unified `events` subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum EventType {
+    /// As sent by `engagementEvents`
+    Engagement,
+    /// As sent by `leaderboardUpdates`
+    LeaderboardUpdate,
+    /// As sent by `droneStatusChanges`
+    DroneStatus,
+    /// As sent by `alerts`; operators only
+    Alert,
+}
+
+impl EventType {
+    pub const ALL: [Self; 4] = [
+        Self::Engagement,
+        Self::LeaderboardUpdate,
+        Self::DroneStatus,
+        Self::Alert,
+    ];
+}
+
+/// Color band of a drone health score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum HealthBand {
+    /// Mission capable (75 and up)
+    Green,
+    /// Degraded (50 - 74)
+    Amber,
+    /// Recall or ground (below 50)
+    Red,
+}
+
+impl From<domain::HealthBand> for HealthBand {
+    fn from(b: domain::HealthBand) -> Self {
+        match b {
+            domain::HealthBand::Green => Self::Green,
+            domain::HealthBand::Amber => Self::Amber,
+            domain::HealthBand::Red => Self::Red,
+        }
+    }
+}
+
+/// Kind of analytics insight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum InsightKind {
+    /// Convoy accuracy departs from its earlier accuracy
+    AccuracyAnomaly,
+    /// A weapon hits less often than the same weapon in other convoys
+    WeaponUnderperforming,
+    /// A drone hits less often than it did earlier in the mission
+    DroneTrendingDown,
+}
+
+impl From<drone_analytics::insights::InsightKind> for InsightKind {
+    fn from(k: drone_analytics::insights::InsightKind) -> Self {
+        use drone_analytics::insights::InsightKind as Kind;
+        match k {
+            Kind::AccuracyAnomaly => Self::AccuracyAnomaly,
+            Kind::WeaponUnderperforming => Self::WeaponUnderperforming,
+            Kind::DroneTrendingDown => Self::DroneTrendingDown,
+        }
+    }
+}
+
 /// Leaderboard rank change type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -291,6 +911,49 @@ pub enum RankChangeType {
     NoChange,
 }
 
+impl RankChangeType {
+    /// Classify a move between ranks (1 is top)
+    #[must_use]
+    pub fn between(old_rank: Option<i32>, new_rank: i32) -> Self {
+        match old_rank {
+            None => Self::NewEntry,
+            Some(old) if new_rank < old => Self::RankUp,
+            Some(old) if new_rank > old => Self::RankDown,
+            Some(_) => Self::ScoreUpdate,
+        }
+    }
+}
+
+/// Bucket width for time-series queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum TimeBucket {
+    /// One minute
+    Minute,
+    /// Five minutes
+    FiveMinutes,
+    /// Fifteen minutes
+    FifteenMinutes,
+    /// One hour
+    Hour,
+    /// One day
+    Day,
+}
+
+impl TimeBucket {
+    /// Bucket width in seconds
+    #[must_use]
+    pub fn seconds(self) -> i64 {
+        match self {
+            Self::Minute => 60,
+            Self::FiveMinutes => 5 * 60,
+            Self::FifteenMinutes => 15 * 60,
+            Self::Hour => 60 * 60,
+            Self::Day => 24 * 60 * 60,
+        }
+    }
+}
+
 /// Sort order for queries
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Enum, Default)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -301,3 +964,50 @@ pub enum SortOrder {
     #[default]
     Desc,
 }
+
+/// Serialize enums under their GraphQL names so JSON relayed outside the
+/// GraphQL executor (SSE frames, the Redis back-plane) matches subscription
+/// payloads
+macro_rules! serde_as_graphql_name {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl serde::Serialize for $ty {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let name = <$ty as async_graphql::resolver_utils::EnumType>::items()
+                        .iter()
+                        .find(|item| item.value == *self)
+                        .map_or("", |item| item.name);
+                    serializer.serialize_str(name)
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $ty {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let name = String::deserialize(deserializer)?;
+                    <$ty as async_graphql::resolver_utils::EnumType>::items()
+                        .iter()
+                        .find(|item| item.name == name)
+                        .map(|item| item.value)
+                        .ok_or_else(|| {
+                            serde::de::Error::custom(format!(
+                                "unknown {} `{}`",
+                                stringify!($ty),
+                                name
+                            ))
+                        })
+                }
+            }
+        )+
+    };
+}
+
+serde_as_graphql_name!(
+    PlatformType,
+    WeaponType,
+    TargetType,
+    AlertSeverity,
+    RankChangeType,
+    DroneStatus,
+    SensorType,
+    AuthorizationStatus,
+);
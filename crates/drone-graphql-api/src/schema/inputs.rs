@@ -2,8 +2,11 @@
 //!
 //! Input object definitions for mutations and queries.
 
-use async_graphql::InputObject;
+use std::collections::BTreeMap;
+
+use async_graphql::{InputObject, Json};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 use super::enums::*;
 
@@ -12,7 +15,8 @@ use super::enums::*;
 // =============================================================================
 
 /// Geographic coordinates input
-#[derive(Debug, Clone, InputObject)]
+#[derive(Debug, Clone, InputObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CoordinatesInput {
     /// Latitude in decimal degrees (-90 to 90)
     pub latitude: f64,
@@ -20,12 +24,15 @@ pub struct CoordinatesInput {
     pub longitude: f64,
     /// Altitude in meters above sea level
     #[graphql(default)]
+    #[serde(default)]
     pub altitude_m: f64,
     /// Heading in degrees (0-360)
     #[graphql(default)]
+    #[serde(default)]
     pub heading_deg: f64,
     /// Speed in meters per second
     #[graphql(default)]
+    #[serde(default)]
     pub speed_mps: f64,
 }
 
@@ -48,6 +55,8 @@ pub struct RecordEngagementInput {
     pub target_type: Option<TargetType>,
     /// Optional range to target in kilometers
     pub range_km: Option<f64>,
+    /// Probability of kill predicted before the shot (0.0 - 1.0)
+    pub predicted_pk: Option<f64>,
 }
 
 /// Input for creating a full engagement record
@@ -65,11 +74,19 @@ pub struct CreateEngagementInput {
     pub hit: bool,
     /// Shooter position at time of engagement
     pub shooter_position: CoordinatesInput,
-    /// Authorization code for the engagement
+    /// Code from an approved engagement authorization; single use
     pub authorization_code: String,
     /// ROE compliance flag
     #[graphql(default = true)]
     pub roe_compliance: bool,
+    /// Tracked target being engaged (from `reportTarget`)
+    pub target_id: Option<String>,
+    /// Probability of kill predicted before the shot (0.0 - 1.0)
+    pub predicted_pk: Option<f64>,
+    /// Record the engagement even if it is outside the weapon's envelope;
+    /// ignored for LIVE convoys
+    #[graphql(default)]
+    pub envelope_override: bool,
 }
 
 /// Target information input
@@ -87,6 +104,104 @@ pub struct TargetInput {
     pub threat_level: Option<ThreatLevel>,
 }
 
+/// Input for requesting approval before an engagement
+#[derive(Debug, Clone, InputObject)]
+pub struct RequestEngagementAuthorizationInput {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Drone ID that will perform the engagement
+    pub drone_id: String,
+    /// Weapon to be employed
+    pub weapon_type: WeaponType,
+    /// Target information
+    pub target: TargetInput,
+    /// Reason for the engagement
+    pub justification: String,
+    /// Operator requesting approval (defaults to the caller's role)
+    pub requested_by: Option<String>,
+}
+
+/// Input for reporting a target detection
+#[derive(Debug, Clone, InputObject)]
+pub struct ReportTargetInput {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Existing target being re-reported; omit for a new detection
+    pub target_id: Option<String>,
+    /// Target information
+    pub target: TargetInput,
+    /// Who detected the target (defaults to the caller's role)
+    pub reported_by: Option<String>,
+}
+
+/// Input for handing a target track from one drone to another
+#[derive(Debug, Clone, InputObject)]
+pub struct HandoffTargetInput {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Target being handed off
+    pub target_id: String,
+    /// Drone giving up the track
+    pub from_drone_id: String,
+    /// Drone taking over the track
+    pub to_drone_id: String,
+    /// Why the track is handed off, e.g. `BINGO fuel`
+    pub reason: Option<String>,
+}
+
+/// Latitude and longitude of a polygon vertex
+#[derive(Debug, Clone, InputObject)]
+pub struct GeoPointInput {
+    /// Latitude in decimal degrees (-90 to 90)
+    pub latitude: f64,
+    /// Longitude in decimal degrees (-180 to 180)
+    pub longitude: f64,
+}
+
+/// Input for defining a no-strike zone
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateNoStrikeZoneInput {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Zone name, e.g. `CITY HOSPITAL`
+    pub name: String,
+    /// Polygon vertices in order (3 to 256); the ring closes implicitly
+    pub vertices: Vec<GeoPointInput>,
+    /// How engagements targeting inside the zone are handled
+    #[graphql(default)]
+    pub enforcement: ZoneEnforcement,
+    /// Planner notes
+    pub notes: Option<String>,
+}
+
+/// Input for changing a no-strike zone; unset fields are kept
+#[derive(Debug, Clone, InputObject)]
+pub struct UpdateNoStrikeZoneInput {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Zone ID
+    pub zone_id: String,
+    /// New name
+    pub name: Option<String>,
+    /// New polygon vertices
+    pub vertices: Option<Vec<GeoPointInput>>,
+    /// New enforcement
+    pub enforcement: Option<ZoneEnforcement>,
+    /// New planner notes
+    pub notes: Option<String>,
+}
+
+/// Munitions loaded on one of a drone's weapons
+#[derive(Debug, Clone, InputObject)]
+pub struct WeaponLoadoutInput {
+    /// Weapon type
+    pub weapon_type: WeaponType,
+    /// Rounds loaded
+    pub rounds: i32,
+    /// Weapon readiness (defaults to ARMED, or EXPENDED with no rounds)
+    pub status: Option<WeaponState>,
+}
+
 /// Input for updating BDA status
 #[derive(Debug, Clone, InputObject)]
 pub struct UpdateBdaInput {
@@ -100,6 +215,23 @@ pub struct UpdateBdaInput {
     pub notes: Option<String>,
 }
 
+/// Input for correcting a wrongly recorded engagement
+#[derive(Debug, Clone, InputObject)]
+pub struct CorrectEngagementInput {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Drone that fired
+    pub drone_id: String,
+    /// Engagement ID
+    pub engagement_id: String,
+    /// Whether the engagement was actually a hit
+    pub hit: bool,
+    /// Why the original record was wrong
+    pub reason: String,
+    /// Commander applying the correction (defaults to the caller's role)
+    pub applied_by: Option<String>,
+}
+
 // =============================================================================
 // DRONE INPUTS
 // =============================================================================
@@ -119,25 +251,84 @@ pub struct UpdateDroneStateInput {
     pub fuel_pct: Option<f64>,
     /// Current waypoint number
     pub current_waypoint: Option<i32>,
+    /// Rounds remaining per weapon as reported by the aircraft; replaces
+    /// the recorded inventory of the listed weapons
+    pub weapons: Option<Vec<WeaponLoadoutInput>>,
 }
 
-/// Input for creating telemetry record
+/// Input for registering a drone with a convoy
 #[derive(Debug, Clone, InputObject)]
+pub struct CreateDroneInput {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Radio callsign, unique within the convoy (case-insensitive)
+    pub callsign: String,
+    /// Military tail number, unique across all convoys (case-insensitive)
+    pub tail_number: String,
+    /// Platform type
+    pub platform_type: PlatformType,
+    /// Manufacturer serial number
+    pub serial_number: Option<String>,
+}
+
+/// Input for creating telemetry record
+#[derive(Debug, Clone, InputObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateTelemetryInput {
     /// Drone ID
     pub drone_id: String,
+    /// Convoy ID (adds the drone to the convoy roster)
+    pub convoy_id: Option<String>,
     /// Position data
     pub position: CoordinatesInput,
     /// Fuel remaining percentage
     pub fuel_pct: f64,
+    /// Platform type for the fuel model (defaults to the last reported,
+    /// then MQ-9)
+    pub platform_type: Option<PlatformType>,
+    /// Recovery base (defaults to the last reported, then the drone's
+    /// first reported position)
+    pub home_position: Option<CoordinatesInput>,
     /// Current waypoint number
     pub current_waypoint: i32,
+    /// Distance to next waypoint in km
+    #[graphql(default)]
+    #[serde(default)]
+    pub distance_to_next_km: f64,
     /// Velocity in m/s
     #[graphql(default)]
+    #[serde(default)]
     pub velocity_mps: f64,
+    /// Engine temperature in Celsius
+    pub engine_temp_c: Option<f64>,
     /// Mesh connectivity (0.0 - 1.0)
     #[graphql(default = 1.0)]
+    #[serde(default = "full_connectivity")]
     pub mesh_connectivity: f64,
+    /// Sensor operational flags (defaults to the last reported)
+    #[serde(default)]
+    pub sensors: Option<Vec<SensorHealthInput>>,
+    /// Telemetry envelope version (defaults to 1, the pre-envelope fields)
+    #[serde(default)]
+    pub schema_version: Option<i32>,
+    /// Fields this server does not model yet, stored and served as sent
+    #[serde(default)]
+    pub extensions: Option<Json<BTreeMap<String, serde_json::Value>>>,
+}
+
+/// Operational flag for one onboard sensor
+#[derive(Debug, Clone, InputObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorHealthInput {
+    /// Sensor
+    pub sensor_type: SensorType,
+    /// Whether the sensor is working
+    pub operational: bool,
+}
+
+/// Serde default for `meshConnectivity`, matching the GraphQL default
+fn full_connectivity() -> f64 {
+    1.0
 }
 
 // =============================================================================
@@ -161,6 +352,37 @@ pub struct CreateConvoyInput {
     pub commanding_unit: String,
     /// ROE profile name
     pub roe_profile: String,
+    /// Leaderboard scoring model (defaults to Wilson lower bound)
+    pub scoring_model: Option<ScoringModel>,
+    /// Data environment (defaults to the caller's; must match it)
+    pub environment: Option<Environment>,
+}
+
+/// Adjustments to a convoy template; unset fields keep the template's values
+#[derive(Debug, Clone, Default, InputObject)]
+pub struct ConvoyTemplateOverridesInput {
+    /// Convoy callsign
+    pub callsign: Option<String>,
+    /// Mission type; also decides the generated waypoint plans
+    pub mission_type: Option<MissionType>,
+    /// Area of responsibility name
+    pub aor_name: Option<String>,
+    /// AOR center coordinates
+    pub aor_center: Option<CoordinatesInput>,
+    /// AOR radius in kilometers
+    pub aor_radius_km: Option<f64>,
+    /// Commanding unit (defaults to the caller's unit)
+    pub commanding_unit: Option<String>,
+    /// ROE profile name
+    pub roe_profile: Option<String>,
+    /// Number of drones, repeating the template's platforms in order
+    pub drone_count: Option<i32>,
+    /// Fly every drone on this platform
+    pub platform_type: Option<PlatformType>,
+    /// Callsign prefix for every drone (`<prefix>-01`, ...)
+    pub callsign_prefix: Option<String>,
+    /// Leaderboard scoring model (defaults to Wilson lower bound)
+    pub scoring_model: Option<ScoringModel>,
 }
 
 /// Input for updating convoy status
@@ -230,26 +452,6 @@ pub struct TimeRangeInput {
     pub end: DateTime<Utc>,
 }
 
-/// Pagination input
-#[derive(Debug, Clone, InputObject)]
-pub struct PaginationInput {
-    /// Maximum results to return
-    #[graphql(default = 20)]
-    pub limit: i32,
-    /// Number of results to skip
-    #[graphql(default = 0)]
-    pub offset: i32,
-}
-
-impl Default for PaginationInput {
-    fn default() -> Self {
-        Self {
-            limit: 20,
-            offset: 0,
-        }
-    }
-}
-
 /// Leaderboard query filter
 #[derive(Debug, Clone, InputObject, Default)]
 pub struct LeaderboardFilter {
@@ -2,11 +2,16 @@
 //!
 //! Object type definitions for GraphQL responses.
 
-use async_graphql::{ComplexObject, Context, Object, SimpleObject, ID};
+use std::collections::BTreeMap;
+
+use async_graphql::{ComplexObject, Context, Interface, Json, Object, SimpleObject, ID};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use super::enums::*;
-use crate::error::ApiResult;
+use crate::context::ApiContext;
+use crate::error::ApiError;
 use drone_domain as domain;
 
 // =============================================================================
@@ -14,7 +19,7 @@ use drone_domain as domain;
 // =============================================================================
 
 /// Geographic coordinates with flight vector
-#[derive(Debug, Clone, SimpleObject)]
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
 pub struct Coordinates {
     /// Latitude in decimal degrees
     pub latitude: f64,
@@ -40,6 +45,27 @@ impl From<domain::Coordinates> for Coordinates {
     }
 }
 
+/// Munitions remaining for one weapon on a drone
+#[derive(Debug, Clone, SimpleObject)]
+pub struct WeaponStatus {
+    /// Weapon type
+    pub weapon_type: WeaponType,
+    /// Rounds remaining
+    pub rounds_remaining: i32,
+    /// Weapon readiness
+    pub status: WeaponState,
+}
+
+impl From<domain::WeaponStatus> for WeaponStatus {
+    fn from(w: domain::WeaponStatus) -> Self {
+        Self {
+            weapon_type: w.weapon_type.into(),
+            rounds_remaining: i32::from(w.rounds_remaining),
+            status: w.status.into(),
+        }
+    }
+}
+
 // =============================================================================
 // LEADERBOARD TYPES
 // =============================================================================
@@ -53,6 +79,7 @@ pub struct LeaderboardEntry {
     pub platform_type: PlatformType,
     pub rank: i32,
     pub accuracy_pct: f32,
+    pub score: f64,
     pub total_engagements: i32,
     pub successful_hits: i32,
     pub current_streak: i32,
@@ -87,6 +114,11 @@ impl LeaderboardEntry {
         self.accuracy_pct
     }
 
+    /// Ranking score under the convoy's scoring model
+    async fn score(&self) -> f64 {
+        self.score
+    }
+
     /// Total engagement attempts
     async fn total_engagements(&self) -> i32 {
         self.total_engagements
@@ -136,6 +168,7 @@ impl From<domain::LeaderboardEntry> for LeaderboardEntry {
             platform_type: e.platform_type.into(),
             rank: e.rank as i32,
             accuracy_pct: e.accuracy_pct,
+            score: e.score,
             total_engagements: e.total_engagements,
             successful_hits: e.successful_hits,
             current_streak: e.current_streak,
@@ -206,6 +239,24 @@ impl Leaderboard {
     }
 }
 
+/// Leaderboard entries changed since a client's last version
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LeaderboardDiff {
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Token to pass as `sinceVersion` next time
+    pub version: String,
+    /// Whether `entries` is the whole leaderboard because the requested
+    /// version was missing, unknown or too old; replace local state
+    pub full: bool,
+    /// Changed entries (or every entry when `full`), sorted by rank
+    pub entries: Vec<LeaderboardEntry>,
+    /// Drones that left the leaderboard since the requested version
+    pub removed_drone_ids: Vec<ID>,
+    /// Timestamp when the diff was generated
+    pub generated_at: DateTime<Utc>,
+}
+
 // =============================================================================
 // DRONE TYPES
 // =============================================================================
@@ -221,6 +272,7 @@ pub struct Drone {
     pub status: DroneStatus,
     pub current_position: Coordinates,
     pub fuel_remaining_pct: f32,
+    pub flight_time_hrs: f32,
     pub accuracy_pct: f32,
     pub total_engagements: i32,
     pub successful_hits: i32,
@@ -277,6 +329,11 @@ impl Drone {
         self.fuel_remaining_pct < 20.0
     }
 
+    /// Cumulative airborne hours over the airframe's life
+    async fn flight_time_hrs(&self) -> f32 {
+        self.flight_time_hrs
+    }
+
     /// Accuracy percentage
     async fn accuracy_pct(&self) -> f32 {
         self.accuracy_pct
@@ -322,6 +379,22 @@ impl Drone {
         )
     }
 
+    /// Weapons inventory; empty when no loadout has been recorded
+    async fn weapons_status(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<WeaponStatus>> {
+        let api_ctx = ctx.data::<ApiContext>()?;
+        let drone_id = Uuid::parse_str(&self.drone_id).map_err(ApiError::from)?;
+
+        let weapons = api_ctx
+            .weapons_repo
+            .get_loadout(drone_id)
+            .await
+            .map_err(ApiError::from)?;
+        Ok(weapons.into_iter().map(Into::into).collect())
+    }
+
     /// Creation timestamp
     async fn created_at(&self) -> DateTime<Utc> {
         self.created_at
@@ -333,6 +406,29 @@ impl Drone {
     }
 }
 
+impl From<domain::Drone> for Drone {
+    fn from(d: domain::Drone) -> Self {
+        Self {
+            drone_id: d.drone_id.to_string(),
+            convoy_id: d.convoy_id.to_string(),
+            tail_number: d.tail_number,
+            callsign: d.callsign,
+            platform_type: d.platform_type.into(),
+            status: d.status.into(),
+            current_position: d.current_position.into(),
+            fuel_remaining_pct: d.fuel_remaining_pct,
+            flight_time_hrs: d.flight_time_hrs,
+            accuracy_pct: d.accuracy_pct,
+            total_engagements: d.total_engagements,
+            successful_hits: d.successful_hits,
+            current_waypoint: 0,
+            total_waypoints: 0,
+            created_at: d.created_at,
+            updated_at: d.updated_at,
+        }
+    }
+}
+
 // =============================================================================
 // CONVOY TYPES
 // =============================================================================
@@ -359,6 +455,12 @@ pub struct Convoy {
     pub drone_count: i32,
     /// Commanding unit
     pub commanding_unit: String,
+    /// Data environment
+    pub environment: Environment,
+    /// ROE profile name; null for roles without access
+    pub roe_profile: Option<String>,
+    /// Leaderboard scoring model
+    pub scoring_model: ScoringModel,
     /// Mission start time
     pub mission_start: Option<DateTime<Utc>>,
     /// Mission end time
@@ -367,6 +469,48 @@ pub struct Convoy {
     pub created_at: DateTime<Utc>,
 }
 
+impl Convoy {
+    /// GraphQL view of a stored convoy scored with `scoring_model`
+    #[must_use]
+    pub fn from_domain(c: domain::Convoy, scoring_model: ScoringModel) -> Self {
+        Self {
+            convoy_id: ID(c.convoy_id.to_string()),
+            callsign: c.convoy_callsign,
+            mission_type: c.mission_type.into(),
+            status: c.status.into(),
+            aor_name: c.aor_name,
+            aor_center: c.aor_center.into(),
+            aor_radius_km: c.aor_radius_km,
+            drone_count: i32::from(c.drone_count),
+            commanding_unit: c.commanding_unit,
+            environment: c.environment.into(),
+            roe_profile: Some(c.roe_profile),
+            scoring_model,
+            mission_start: c.mission_start,
+            mission_end: c.mission_end,
+            created_at: c.created_at,
+        }
+    }
+}
+
+/// Drone provisioned from a convoy template, with its route
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProvisionedDrone {
+    /// Registered drone; `weaponsStatus` returns its loadout
+    pub drone: Drone,
+    /// Generated waypoint plan
+    pub waypoints: Vec<Waypoint>,
+}
+
+/// Convoy provisioned from a template with its full roster
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ProvisionedConvoy {
+    /// Created convoy
+    pub convoy: Convoy,
+    /// Drones in roster order
+    pub drones: Vec<ProvisionedDrone>,
+}
+
 #[ComplexObject]
 impl Convoy {
     /// Is mission currently active
@@ -404,6 +548,211 @@ pub struct ConvoyStats {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Convoy statistics at one point of the mission timeline
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ConvoyStatsPoint {
+    /// Start of the sampling window
+    pub timestamp: DateTime<Utc>,
+    /// Total drones (window average)
+    pub drone_count: i32,
+    /// Airborne drones (window average)
+    pub airborne_count: i32,
+    /// Average fuel percentage (window average)
+    pub average_fuel_pct: f32,
+    /// Engagements since mission start
+    pub total_engagements: i32,
+    /// Hits since mission start
+    pub total_hits: i32,
+    /// Hit rate over all engagements since mission start
+    pub cumulative_accuracy_pct: f32,
+}
+
+impl From<domain::ConvoyStatsSnapshot> for ConvoyStatsPoint {
+    fn from(s: domain::ConvoyStatsSnapshot) -> Self {
+        Self {
+            timestamp: s.recorded_at,
+            drone_count: s.drone_count,
+            airborne_count: s.airborne_count,
+            average_fuel_pct: s.average_fuel_pct,
+            total_engagements: s.total_engagements,
+            total_hits: s.total_hits,
+            cumulative_accuracy_pct: s.cumulative_accuracy_pct().0,
+        }
+    }
+}
+
+/// A drone's leaderboard rank at one point of the mission timeline
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RankTimelinePoint {
+    /// When the rank changed
+    pub timestamp: DateTime<Utc>,
+    /// Rank from this point on
+    pub rank: i32,
+    /// Accuracy percentage at this point; null before the drone's first
+    /// recorded engagement in the timeline
+    pub accuracy_pct: Option<f32>,
+}
+
+impl From<domain::RankHistoryEntry> for RankTimelinePoint {
+    fn from(e: domain::RankHistoryEntry) -> Self {
+        Self {
+            timestamp: e.recorded_at,
+            rank: i32::from(e.rank),
+            accuracy_pct: e.accuracy_pct,
+        }
+    }
+}
+
+/// Hits and misses for one bucket of a drone's engagement timeline
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EngagementTimelineBucket {
+    /// Start of the bucket (inclusive)
+    pub bucket_start: DateTime<Utc>,
+    /// Engagements that hit
+    pub hits: i32,
+    /// Engagements that missed
+    pub misses: i32,
+}
+
+impl From<drone_analytics::EngagementBucket> for EngagementTimelineBucket {
+    fn from(b: drone_analytics::EngagementBucket) -> Self {
+        Self {
+            bucket_start: b.bucket_start,
+            hits: i32::try_from(b.hits).unwrap_or(i32::MAX),
+            misses: i32::try_from(b.misses).unwrap_or(i32::MAX),
+        }
+    }
+}
+
+/// Analytics insight raised by the periodic evaluation
+#[derive(Debug, Clone, SimpleObject)]
+pub struct InsightEvent {
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// What was detected
+    pub kind: InsightKind,
+    /// `convoy`, the weapon type, or the drone callsign
+    pub subject: String,
+    /// Drone ID, for drone insights
+    pub drone_id: Option<ID>,
+    /// Human-readable summary
+    pub message: String,
+    /// Accuracy over the recent window (0-100)
+    pub recent_accuracy_pct: f64,
+    /// Accuracy of the baseline compared against (0-100)
+    pub baseline_accuracy_pct: f64,
+    /// Engagements over the recent window
+    pub recent_engagements: i32,
+    /// Engagements in the baseline
+    pub baseline_engagements: i32,
+    /// When the insight was raised
+    pub detected_at: DateTime<Utc>,
+}
+
+impl From<drone_analytics::insights::Insight> for InsightEvent {
+    fn from(i: drone_analytics::insights::Insight) -> Self {
+        Self {
+            convoy_id: ID(i.convoy_id.to_string()),
+            kind: i.kind.into(),
+            subject: i.subject,
+            drone_id: i.drone_id.map(|id| ID(id.to_string())),
+            message: i.message,
+            recent_accuracy_pct: i.recent_accuracy_pct,
+            baseline_accuracy_pct: i.baseline_accuracy_pct,
+            recent_engagements: i32::try_from(i.recent_engagements).unwrap_or(i32::MAX),
+            baseline_engagements: i32::try_from(i.baseline_engagements).unwrap_or(i32::MAX),
+            detected_at: Utc::now(),
+        }
+    }
+}
+
+/// Drone position relative to the formation centroid
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FormationOffset {
+    /// Drone ID
+    pub drone_id: ID,
+    /// Offset north of centroid in km (negative = south)
+    pub north_km: f64,
+    /// Offset east of centroid in km (negative = west)
+    pub east_km: f64,
+    /// Altitude above centroid in meters
+    pub altitude_offset_m: f64,
+    /// Distance from centroid in km
+    pub distance_km: f64,
+}
+
+impl From<domain::FormationOffset> for FormationOffset {
+    fn from(o: domain::FormationOffset) -> Self {
+        Self {
+            drone_id: ID(o.drone_id.to_string()),
+            north_km: o.north_km.0,
+            east_km: o.east_km.0,
+            altitude_offset_m: o.altitude_offset_m.0,
+            distance_km: o.distance_km.0,
+        }
+    }
+}
+
+/// Convoy formation snapshot
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ConvoyFormation {
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Formation centroid
+    pub centroid: Coordinates,
+    /// Per-drone offsets from the centroid
+    pub offsets: Vec<FormationOffset>,
+    /// Mean distance from centroid in km
+    pub spread_km: f64,
+    /// Closest pair separation in km
+    pub min_spacing_km: f64,
+    /// Widest pair separation in km
+    pub max_spacing_km: f64,
+    /// Fraction of drone pairs within spacing bounds (0-1)
+    pub formation_integrity: f64,
+    /// Number of drone pairs outside spacing bounds
+    pub violation_count: i32,
+    /// Snapshot timestamp
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Predicted loss of separation between two drones
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PredictedConflict {
+    /// First drone ID
+    pub drone_a_id: ID,
+    /// Second drone ID
+    pub drone_b_id: ID,
+    /// Seconds until the closest point of approach
+    pub time_to_cpa_sec: f64,
+    /// Time of the closest point of approach
+    pub cpa_at: DateTime<Utc>,
+    /// Horizontal separation at CPA in km
+    pub horizontal_separation_km: f64,
+    /// Vertical separation at CPA in meters
+    pub vertical_separation_m: f64,
+    /// First drone's predicted position at CPA
+    pub position_a: Coordinates,
+    /// Second drone's predicted position at CPA
+    pub position_b: Coordinates,
+}
+
+impl PredictedConflict {
+    /// Convert a domain conflict predicted at `now`
+    pub fn at(c: domain::PredictedConflict, now: DateTime<Utc>) -> Self {
+        Self {
+            drone_a_id: ID(c.drone_a.to_string()),
+            drone_b_id: ID(c.drone_b.to_string()),
+            time_to_cpa_sec: c.time_to_cpa_secs,
+            cpa_at: now + chrono::Duration::milliseconds((c.time_to_cpa_secs * 1000.0) as i64),
+            horizontal_separation_km: c.horizontal_km.0,
+            vertical_separation_m: c.vertical_m.0,
+            position_a: c.position_a.into(),
+            position_b: c.position_b.into(),
+        }
+    }
+}
+
 // =============================================================================
 // WAYPOINT TYPES
 // =============================================================================
@@ -436,6 +785,63 @@ pub struct Waypoint {
     pub actual_departure: Option<DateTime<Utc>>,
     /// Loiter duration in minutes
     pub loiter_duration_min: Option<i32>,
+    /// Sensor modes tasked for arrival at this waypoint
+    pub sensor_tasks: Vec<SensorTask>,
+}
+
+impl From<domain::Waypoint> for Waypoint {
+    fn from(w: domain::Waypoint) -> Self {
+        Self {
+            waypoint_id: ID(w.waypoint_id.to_string()),
+            drone_id: ID(w.drone_id.to_string()),
+            sequence_number: i32::from(w.sequence_number),
+            name: w.waypoint_name,
+            waypoint_type: w.waypoint_type.into(),
+            coordinates: w.coordinates.into(),
+            status: w.status.into(),
+            planned_arrival: w.planned_arrival,
+            actual_arrival: w.actual_arrival,
+            planned_departure: w.planned_departure,
+            actual_departure: w.actual_departure,
+            loiter_duration_min: w.loiter_duration_min,
+            sensor_tasks: Vec::new(),
+        }
+    }
+}
+
+/// Sensor mode tasked for a drone's arrival at a waypoint
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorTask {
+    /// Drone ID
+    pub drone_id: ID,
+    /// Waypoint sequence number
+    pub sequence_number: i32,
+    /// Sensor to switch
+    pub sensor_type: SensorType,
+    /// Mode to switch the sensor into
+    pub mode: String,
+    /// Who assigned the task
+    pub assigned_by: String,
+    /// Assignment time
+    pub assigned_at: DateTime<Utc>,
+    /// Event ID when broadcast; sensor tasks are not kept for `replayEvents`
+    #[serde(default)]
+    pub event_id: Option<ID>,
+}
+
+impl From<domain::SensorTask> for SensorTask {
+    fn from(t: domain::SensorTask) -> Self {
+        Self {
+            drone_id: ID(t.drone_id.to_string()),
+            sequence_number: i32::from(t.sequence_number),
+            sensor_type: t.sensor_type.into(),
+            mode: t.mode,
+            assigned_by: t.assigned_by,
+            assigned_at: t.assigned_at,
+            event_id: None,
+        }
+    }
 }
 
 #[ComplexObject]
@@ -486,10 +892,179 @@ pub struct Engagement {
     pub hit: bool,
     /// Damage assessment
     pub damage_assessment: DamageAssessment,
-    /// Authorization code
-    pub authorization_code: String,
+    /// Authorization code; null for roles without access
+    pub authorization_code: Option<String>,
     /// ROE compliant
     pub roe_compliant: bool,
+    /// Tracked target engaged, if any
+    pub target_id: Option<ID>,
+    /// Order the engagement was applied to the drone's leaderboard entry
+    pub sequence: i64,
+    /// Probability of kill predicted before the shot, if reported
+    pub predicted_pk: Option<f32>,
+    /// Battle damage assessment notes; null for roles without access
+    pub bda_notes: Option<String>,
+}
+
+impl From<domain::Engagement> for Engagement {
+    fn from(e: domain::Engagement) -> Self {
+        Self {
+            engagement_id: ID(e.engagement_id.to_string()),
+            convoy_id: ID(e.convoy_id.to_string()),
+            drone_id: ID(e.drone_id.to_string()),
+            drone_callsign: e.drone_callsign,
+            engaged_at: e.engaged_at,
+            weapon_type: e.weapon_type.into(),
+            target_type: e.target.target_type.into(),
+            target_coordinates: e.target.coordinates.into(),
+            shooter_position: e.shooter_position.into(),
+            range_km: e.range_to_target_km,
+            hit: e.hit,
+            damage_assessment: e.result.damage_assessment.into(),
+            authorization_code: Some(e.authorization_code),
+            roe_compliant: e.roe_compliance,
+            target_id: (!e.target.target_id.is_nil()).then(|| ID(e.target.target_id.to_string())),
+            sequence: e.sequence,
+            predicted_pk: e.predicted_pk,
+            bda_notes: e.bda_notes,
+        }
+    }
+}
+
+/// Target tracked across detections and engagements
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Target {
+    /// Target ID
+    pub target_id: ID,
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Target type
+    pub target_type: TargetType,
+    /// Last reported location
+    pub coordinates: Coordinates,
+    /// Detection confidence (0.0 - 1.0)
+    pub confidence: f64,
+    /// Threat level assessment
+    pub threat_level: ThreatLevel,
+    /// Lifecycle status
+    pub status: TargetStatus,
+    /// Who reported the target
+    pub reported_by: String,
+    /// First detection time
+    pub first_detected_at: DateTime<Utc>,
+    /// Last report, engagement or BDA update
+    pub last_updated_at: DateTime<Utc>,
+    /// Engagements fired at this target
+    pub engagement_ids: Vec<ID>,
+}
+
+impl From<domain::Target> for Target {
+    fn from(t: domain::Target) -> Self {
+        Self {
+            target_id: ID(t.target_id.to_string()),
+            convoy_id: ID(t.convoy_id.to_string()),
+            target_type: t.target_type.into(),
+            coordinates: t.coordinates.into(),
+            confidence: f64::from(t.confidence),
+            threat_level: t.threat_level.into(),
+            status: t.status.into(),
+            reported_by: t.reported_by,
+            first_detected_at: t.first_detected_at,
+            last_updated_at: t.last_updated_at,
+            engagement_ids: t.engagement_ids.iter().map(|id| ID(id.to_string())).collect(),
+        }
+    }
+}
+
+/// Hand-off of a target track between drones
+#[derive(Debug, Clone, SimpleObject)]
+pub struct TargetHandoff {
+    /// Hand-off ID
+    pub handoff_id: ID,
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Target handed off
+    pub target_id: ID,
+    /// Drone that gave up the track
+    pub from_drone_id: ID,
+    /// Drone that took over the track
+    pub to_drone_id: ID,
+    /// Why the track was handed off
+    pub reason: Option<String>,
+    /// Who ordered the hand-off
+    pub handed_off_by: String,
+    /// Hand-off time
+    pub handed_off_at: DateTime<Utc>,
+}
+
+impl From<domain::TargetHandoff> for TargetHandoff {
+    fn from(h: domain::TargetHandoff) -> Self {
+        Self {
+            handoff_id: ID(h.handoff_id.to_string()),
+            convoy_id: ID(h.convoy_id.to_string()),
+            target_id: ID(h.target_id.to_string()),
+            from_drone_id: ID(h.from_drone_id.to_string()),
+            to_drone_id: ID(h.to_drone_id.to_string()),
+            reason: h.reason,
+            handed_off_by: h.handed_off_by,
+            handed_off_at: h.handed_off_at,
+        }
+    }
+}
+
+/// Latitude and longitude of a polygon vertex
+#[derive(Debug, Clone, SimpleObject)]
+pub struct GeoPoint {
+    /// Latitude in decimal degrees
+    pub latitude: f64,
+    /// Longitude in decimal degrees
+    pub longitude: f64,
+}
+
+/// Area a convoy must not engage into
+#[derive(Debug, Clone, SimpleObject)]
+pub struct NoStrikeZone {
+    /// Zone ID
+    pub zone_id: ID,
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Zone name, e.g. `CITY HOSPITAL`
+    pub name: String,
+    /// Polygon vertices in order; the ring closes implicitly
+    pub vertices: Vec<GeoPoint>,
+    /// How engagements targeting inside the zone are handled
+    pub enforcement: ZoneEnforcement,
+    /// Planner notes
+    pub notes: Option<String>,
+    /// Who created the zone
+    pub created_by: String,
+    /// Creation time
+    pub created_at: DateTime<Utc>,
+    /// Last change
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<domain::NoStrikeZone> for NoStrikeZone {
+    fn from(z: domain::NoStrikeZone) -> Self {
+        Self {
+            zone_id: ID(z.zone_id.to_string()),
+            convoy_id: ID(z.convoy_id.to_string()),
+            name: z.name,
+            vertices: z
+                .vertices
+                .into_iter()
+                .map(|v| GeoPoint {
+                    latitude: v.latitude,
+                    longitude: v.longitude,
+                })
+                .collect(),
+            enforcement: z.enforcement.into(),
+            notes: z.notes,
+            created_by: z.created_by,
+            created_at: z.created_at,
+            updated_at: z.updated_at,
+        }
+    }
 }
 
 #[ComplexObject]
@@ -500,12 +1075,137 @@ impl Engagement {
     }
 }
 
+/// Pre-engagement authorization request
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngagementAuthorization {
+    /// Request ID
+    pub request_id: ID,
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Drone that will perform the engagement
+    pub drone_id: ID,
+    /// Weapon to be employed
+    pub weapon_type: WeaponType,
+    /// Target type
+    pub target_type: TargetType,
+    /// Target coordinates
+    pub target_coordinates: Coordinates,
+    /// Who requested approval
+    pub requested_by: String,
+    /// Reason for the engagement
+    pub justification: String,
+    /// When approval was requested
+    pub requested_at: DateTime<Utc>,
+    /// Current status
+    pub status: AuthorizationStatus,
+    /// Commander who decided the request
+    pub decided_by: Option<String>,
+    /// When the request was decided
+    pub decided_at: Option<DateTime<Utc>>,
+    /// Decision notes or denial reason
+    pub decision_notes: Option<String>,
+    /// Approval must be executed before this
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Signed code for `createEngagement`; set on approved requests only,
+    /// and null for roles without access
+    pub authorization_code: Option<String>,
+    /// Engagement that consumed the approval
+    pub engagement_id: Option<ID>,
+    /// Event ID when broadcast; pass to `replayEvents` to resume after this event
+    #[serde(default)]
+    pub event_id: Option<ID>,
+}
+
+impl EngagementAuthorization {
+    /// Convert a domain request, attaching its code while approved
+    #[must_use]
+    pub fn signed(
+        auth: domain::EngagementAuthorization,
+        signer: &crate::authorization::AuthorizationSigner,
+    ) -> Self {
+        let authorization_code = signer.issue(&auth);
+        Self {
+            authorization_code,
+            ..auth.into()
+        }
+    }
+}
+
+impl From<domain::EngagementAuthorization> for EngagementAuthorization {
+    fn from(a: domain::EngagementAuthorization) -> Self {
+        Self {
+            request_id: ID(a.request_id.to_string()),
+            convoy_id: ID(a.convoy_id.to_string()),
+            drone_id: ID(a.drone_id.to_string()),
+            weapon_type: a.weapon_type.into(),
+            target_type: a.target_type.into(),
+            target_coordinates: a.target_coordinates.into(),
+            requested_by: a.requested_by,
+            justification: a.justification,
+            requested_at: a.requested_at,
+            status: a.status.into(),
+            decided_by: a.decided_by,
+            decided_at: a.decided_at,
+            decision_notes: a.decision_notes,
+            expires_at: a.expires_at,
+            authorization_code: None,
+            engagement_id: a.engagement_id.map(|id| ID(id.to_string())),
+            event_id: None,
+        }
+    }
+}
+
+/// Engagement heatmap grid cell
+#[derive(Debug, Clone, SimpleObject)]
+pub struct HeatmapCell {
+    /// Cell center latitude
+    pub latitude: f64,
+    /// Cell center longitude
+    pub longitude: f64,
+    /// Engagements in cell
+    pub count: i32,
+    /// Hits in cell
+    pub hits: i32,
+    /// Hit ratio (0-1)
+    pub hit_ratio: f64,
+}
+
+impl From<domain::HeatmapCell> for HeatmapCell {
+    fn from(c: domain::HeatmapCell) -> Self {
+        Self {
+            latitude: c.latitude,
+            longitude: c.longitude,
+            count: c.count as i32,
+            hits: c.hits as i32,
+            hit_ratio: c.hit_ratio(),
+        }
+    }
+}
+
+/// Engagement heatmap for a convoy
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EngagementHeatmap {
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Grid cell edge in km
+    pub grid_resolution_km: f64,
+    /// Window start
+    pub start: DateTime<Utc>,
+    /// Window end
+    pub end: DateTime<Utc>,
+    /// Engagements binned
+    pub total_engagements: i32,
+    /// Non-empty cells, busiest first
+    pub cells: Vec<HeatmapCell>,
+}
+
 // =============================================================================
 // TELEMETRY TYPES
 // =============================================================================
 
 /// Telemetry snapshot
-#[derive(Debug, Clone, SimpleObject)]
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
 pub struct TelemetrySnapshot {
     /// Drone ID
     pub drone_id: ID,
@@ -519,10 +1219,290 @@ pub struct TelemetrySnapshot {
     pub current_waypoint: i32,
     /// Velocity in m/s
     pub velocity_mps: f32,
+    /// Engine temperature in Celsius
+    pub engine_temp_c: Option<f32>,
     /// Mesh connectivity (0-1)
     pub mesh_connectivity: f32,
     /// Distance to next waypoint in km
     pub distance_to_next_km: f32,
+    /// Wind-adjusted time to next waypoint in seconds
+    pub eta_next_waypoint_sec: Option<f64>,
+    /// Ambient conditions at the drone position
+    pub ambient_conditions: Option<AmbientConditions>,
+    /// Telemetry envelope version the sender reported
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: i32,
+    /// Fields this server does not model, as the sender reported them
+    #[serde(default)]
+    pub extensions: Option<Json<BTreeMap<String, serde_json::Value>>>,
+    /// Event ID when broadcast; telemetry is not kept for `replayEvents`
+    #[serde(default)]
+    pub event_id: Option<ID>,
+}
+
+/// Serde default for snapshots cached before the envelope existed
+fn legacy_schema_version() -> i32 {
+    1
+}
+
+/// Recorded position on a drone's track
+#[derive(Debug, Clone, SimpleObject)]
+pub struct TrackPoint {
+    /// Recording timestamp
+    pub recorded_at: DateTime<Utc>,
+    /// Position
+    pub position: Coordinates,
+}
+
+impl From<domain::TrackPoint> for TrackPoint {
+    fn from(p: domain::TrackPoint) -> Self {
+        Self {
+            recorded_at: p.recorded_at,
+            position: p.position.into(),
+        }
+    }
+}
+
+/// Simplified flight track for map trails and replay
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DroneTrack {
+    /// Drone ID
+    pub drone_id: ID,
+    /// Points recorded in the window before simplification
+    pub raw_point_count: i32,
+    /// Ordered polyline, oldest first
+    pub points: Vec<TrackPoint>,
+}
+
+/// Ambient weather conditions
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+pub struct AmbientConditions {
+    /// Wind speed in m/s
+    pub wind_speed_mps: f64,
+    /// Direction the wind is blowing from (0-360)
+    pub wind_direction_deg: f64,
+    /// Visibility in km
+    pub visibility_km: f64,
+    /// Air temperature in Celsius
+    pub temperature_c: f64,
+    /// Observation timestamp
+    pub observed_at: DateTime<Utc>,
+}
+
+impl From<crate::weather::Conditions> for AmbientConditions {
+    fn from(c: crate::weather::Conditions) -> Self {
+        Self {
+            wind_speed_mps: c.wind_speed_mps,
+            wind_direction_deg: c.wind_direction_deg,
+            visibility_km: c.visibility_km,
+            temperature_c: c.temperature_c,
+            observed_at: c.observed_at,
+        }
+    }
+}
+
+/// Fuel endurance estimate from the latest telemetry
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EnduranceEstimate {
+    /// Drone ID
+    pub drone_id: ID,
+    /// Platform the fuel model was evaluated for
+    pub platform_type: PlatformType,
+    /// Telemetry time the estimate is based on
+    pub estimated_at: DateTime<Utc>,
+    /// Fuel remaining in kg
+    pub fuel_remaining_kg: f64,
+    /// Current fuel burn in kg per hour
+    pub burn_rate_kg_per_hr: f64,
+    /// Minutes of flight left before the landing reserve
+    pub endurance_min: f64,
+    /// Recovery base
+    pub home: Coordinates,
+    /// Distance to the recovery base in km
+    pub distance_to_home_km: f64,
+    /// Minutes needed to fly home
+    pub time_to_home_min: f64,
+    /// Minutes until the drone must turn for home
+    pub bingo_min: f64,
+    /// Time the drone must turn for home
+    pub bingo_at: DateTime<Utc>,
+    /// Endurance no longer covers the flight home
+    pub rtb_recommended: bool,
+}
+
+impl From<domain::EnduranceEstimate> for EnduranceEstimate {
+    fn from(e: domain::EnduranceEstimate) -> Self {
+        Self {
+            drone_id: ID(e.drone_id.to_string()),
+            platform_type: e.platform_type.into(),
+            estimated_at: e.estimated_at,
+            fuel_remaining_kg: e.fuel_remaining_kg,
+            burn_rate_kg_per_hr: e.burn_rate_kg_per_hr,
+            endurance_min: e.endurance_min,
+            home: e.home.into(),
+            distance_to_home_km: e.distance_to_home_km.0,
+            time_to_home_min: e.time_to_home_min,
+            bingo_min: e.bingo_min,
+            bingo_at: e.bingo_at,
+            rtb_recommended: e.rtb_recommended,
+        }
+    }
+}
+
+/// What a platform type can fly and carry
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PlatformCapabilities {
+    /// Platform type
+    pub platform_type: PlatformType,
+    /// Service ceiling in meters
+    pub max_altitude_m: f64,
+    /// Hours at cruise on internal fuel, less the landing reserve
+    pub endurance_hrs: f64,
+    /// Cruise airspeed in m/s
+    pub cruise_speed_mps: f64,
+    /// Weapons the platform's stations accept; empty for unarmed platforms
+    pub compatible_weapons: Vec<WeaponType>,
+    /// Most rounds carried across all stations
+    pub max_rounds: i32,
+    /// Sensors the platform carries
+    pub sensors: Vec<SensorType>,
+}
+
+impl From<domain::PlatformCapabilities> for PlatformCapabilities {
+    fn from(c: domain::PlatformCapabilities) -> Self {
+        Self {
+            platform_type: c.platform_type.into(),
+            max_altitude_m: c.max_altitude_m.0,
+            endurance_hrs: c.endurance_hrs,
+            cruise_speed_mps: f64::from(c.cruise_speed_mps.0),
+            compatible_weapons: c.compatible_weapons.into_iter().map(Into::into).collect(),
+            max_rounds: i32::from(c.max_rounds),
+            sensors: c.sensors.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Running flight hours for a drone
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FlightHours {
+    /// Drone ID
+    pub drone_id: ID,
+    /// Cumulative airborne hours over the airframe's life
+    pub total_hours: f64,
+    /// Airborne hours in the current sortie; zero on the ground
+    pub sortie_hours: f64,
+    /// When the current sortie began
+    pub sortie_started_at: Option<DateTime<Utc>>,
+    /// Airborne at the last telemetry report
+    pub airborne: bool,
+    /// Cumulative hours at which the next inspection falls due
+    pub next_inspection_hours: Option<f64>,
+    /// Time of the last telemetry report
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl FlightHours {
+    /// Convert running hours, projecting the next inspection from `limits`
+    #[must_use]
+    pub fn new(hours: &domain::FlightHours, limits: &domain::FlightHoursLimits) -> Self {
+        Self {
+            drone_id: ID(hours.drone_id.to_string()),
+            next_inspection_hours: limits.next_inspection_hrs(hours.total_hrs),
+            total_hours: hours.total_hrs,
+            sortie_hours: hours.sortie_hrs,
+            sortie_started_at: hours.sortie_started_at,
+            airborne: hours.airborne,
+            updated_at: hours.last_sample_at,
+        }
+    }
+}
+
+/// Per-factor health scores, each 0 - 100
+#[derive(Debug, Clone, SimpleObject)]
+pub struct HealthFactors {
+    /// Fuel remaining (weight 30)
+    pub fuel: f64,
+    /// Datalink quality (weight 25)
+    pub link: f64,
+    /// Share of sensors operational (weight 20)
+    pub sensors: f64,
+    /// Engine temperature margin (weight 15)
+    pub engine: f64,
+    /// Zero while in maintenance (weight 10)
+    pub maintenance: f64,
+}
+
+impl From<domain::HealthFactors> for HealthFactors {
+    fn from(f: domain::HealthFactors) -> Self {
+        Self {
+            fuel: f.fuel,
+            link: f.link,
+            sensors: f.sensors,
+            engine: f.engine,
+            maintenance: f.maintenance,
+        }
+    }
+}
+
+/// Drone health score from the latest telemetry
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DroneHealth {
+    /// Drone ID
+    pub drone_id: ID,
+    /// Telemetry time the score is based on
+    pub computed_at: DateTime<Utc>,
+    /// Overall score, 0 - 100
+    pub score: i32,
+    /// Color band of the score
+    pub band: HealthBand,
+    /// Score per factor
+    pub factors: HealthFactors,
+}
+
+impl From<domain::DroneHealth> for DroneHealth {
+    fn from(h: domain::DroneHealth) -> Self {
+        Self {
+            drone_id: ID(h.drone_id.to_string()),
+            computed_at: h.computed_at,
+            score: i32::from(h.score),
+            band: h.band.into(),
+            factors: h.factors.into(),
+        }
+    }
+}
+
+/// Health distribution across a convoy
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FleetReadiness {
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Drones in the GREEN band
+    pub green: i32,
+    /// Drones in the AMBER band
+    pub amber: i32,
+    /// Drones in the RED band
+    pub red: i32,
+    /// Roster drones without telemetry to score yet
+    pub unscored: i32,
+    /// Mean score of the scored drones
+    pub average_score: Option<f64>,
+    /// Lowest-scoring drones, worst first
+    pub lowest: Vec<DroneHealth>,
+}
+
+impl From<domain::FleetReadiness> for FleetReadiness {
+    fn from(r: domain::FleetReadiness) -> Self {
+        let count = |n: usize| i32::try_from(n).unwrap_or(i32::MAX);
+        Self {
+            convoy_id: ID(r.convoy_id.to_string()),
+            green: count(r.green),
+            amber: count(r.amber),
+            red: count(r.red),
+            unscored: count(r.unscored),
+            average_score: r.average_score,
+            lowest: r.lowest.into_iter().map(Into::into).collect(),
+        }
+    }
 }
 
 // =============================================================================
@@ -530,7 +1510,8 @@ pub struct TelemetrySnapshot {
 // =============================================================================
 
 /// Leaderboard update event
-#[derive(Debug, Clone, SimpleObject)]
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct LeaderboardUpdateEvent {
     /// Convoy ID
     pub convoy_id: ID,
@@ -548,10 +1529,14 @@ pub struct LeaderboardUpdateEvent {
     pub change_type: RankChangeType,
     /// Event timestamp
     pub timestamp: DateTime<Utc>,
+    /// Event ID; pass to `replayEvents` to resume after this event
+    #[serde(default)]
+    pub event_id: Option<ID>,
 }
 
 /// Engagement event for real-time updates
-#[derive(Debug, Clone, SimpleObject)]
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EngagementEvent {
     /// Convoy ID
     pub convoy_id: ID,
@@ -563,14 +1548,25 @@ pub struct EngagementEvent {
     pub hit: bool,
     /// Weapon type used
     pub weapon_type: WeaponType,
+    /// Target type, when reported
+    pub target_type: Option<TargetType>,
+    /// Range to target in km, when reported
+    pub range_km: Option<f32>,
+    /// Pre-shot probability of kill, when reported
+    #[serde(default)]
+    pub predicted_pk: Option<f32>,
     /// New accuracy after engagement
     pub new_accuracy_pct: f32,
     /// Event timestamp
     pub timestamp: DateTime<Utc>,
+    /// Event ID; pass to `replayEvents` to resume after this event
+    #[serde(default)]
+    pub event_id: Option<ID>,
 }
 
 /// Drone status change event
-#[derive(Debug, Clone, SimpleObject)]
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DroneStatusEvent {
     /// Convoy ID
     pub convoy_id: ID,
@@ -584,10 +1580,14 @@ pub struct DroneStatusEvent {
     pub new_status: DroneStatus,
     /// Event timestamp
     pub timestamp: DateTime<Utc>,
+    /// Event ID; pass to `replayEvents` to resume after this event
+    #[serde(default)]
+    pub event_id: Option<ID>,
 }
 
 /// Alert event
-#[derive(Debug, Clone, SimpleObject)]
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AlertEvent {
     /// Alert ID
     pub alert_id: ID,
@@ -603,6 +1603,294 @@ pub struct AlertEvent {
     pub message: String,
     /// Event timestamp
     pub timestamp: DateTime<Utc>,
+    /// Event ID when broadcast; pass to `replayEvents` to resume after this event
+    #[serde(default)]
+    pub event_id: Option<ID>,
+}
+
+/// Any event sent by the unified `events` subscription
+#[derive(Debug, Clone, Interface)]
+#[graphql(
+    field(name = "convoy_id", ty = "&ID", desc = "Convoy ID"),
+    field(name = "timestamp", ty = "&DateTime<Utc>", desc = "Event timestamp"),
+    field(
+        name = "event_id",
+        ty = "&Option<ID>",
+        desc = "Event ID; pass to `replayEvents` to resume after this event"
+    )
+)]
+pub enum ConvoyEvent {
+    Engagement(EngagementEvent),
+    LeaderboardUpdate(LeaderboardUpdateEvent),
+    DroneStatus(DroneStatusEvent),
+    Alert(AlertEvent),
+}
+
+impl ConvoyEvent {
+    #[must_use]
+    pub fn event_type(&self) -> EventType {
+        match self {
+            Self::Engagement(_) => EventType::Engagement,
+            Self::LeaderboardUpdate(_) => EventType::LeaderboardUpdate,
+            Self::DroneStatus(_) => EventType::DroneStatus,
+            Self::Alert(_) => EventType::Alert,
+        }
+    }
+
+    /// When the event was stamped by its publisher
+    #[must_use]
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            Self::Engagement(e) => e.timestamp,
+            Self::LeaderboardUpdate(e) => e.timestamp,
+            Self::DroneStatus(e) => e.timestamp,
+            Self::Alert(e) => e.timestamp,
+        }
+    }
+
+    #[must_use]
+    pub fn belongs_to(&self, convoy_id: &str) -> bool {
+        let id = match self {
+            Self::Engagement(e) => &e.convoy_id,
+            Self::LeaderboardUpdate(e) => &e.convoy_id,
+            Self::DroneStatus(e) => &e.convoy_id,
+            Self::Alert(e) => &e.convoy_id,
+        };
+        id.as_str() == convoy_id
+    }
+}
+
+/// Persisted alert with acknowledgement state
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Alert {
+    /// Alert ID
+    pub alert_id: ID,
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Source drone ID
+    pub drone_id: Option<ID>,
+    /// Severity
+    pub severity: AlertSeverity,
+    /// Alert type code
+    pub alert_type: String,
+    /// Human readable message
+    pub message: String,
+    /// When the alert was raised
+    pub timestamp: DateTime<Utc>,
+    /// Whether an operator has acknowledged the alert
+    pub acknowledged: bool,
+    /// Who acknowledged the alert
+    pub acknowledged_by: Option<String>,
+    /// When the alert was acknowledged
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    /// External notification outcomes, one per channel
+    pub deliveries: Vec<AlertDelivery>,
+}
+
+/// Delivery of an alert to one external notification channel
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AlertDelivery {
+    /// Channel name (`webhook`, `slack`, `email`)
+    pub channel: String,
+    /// Delivery outcome
+    pub status: DeliveryStatus,
+    /// Send attempts made, including the first
+    pub attempts: i32,
+    /// Error from the last failed attempt
+    pub last_error: Option<String>,
+}
+
+impl From<domain::AlertDelivery> for AlertDelivery {
+    fn from(d: domain::AlertDelivery) -> Self {
+        Self {
+            channel: d.channel,
+            status: d.status.into(),
+            attempts: d.attempts,
+            last_error: d.last_error,
+        }
+    }
+}
+
+impl From<domain::Alert> for Alert {
+    fn from(a: domain::Alert) -> Self {
+        Self {
+            alert_id: ID(a.alert_id.to_string()),
+            convoy_id: ID(a.convoy_id.to_string()),
+            drone_id: a.source_drone_id.map(|id| ID(id.to_string())),
+            severity: a.severity.into(),
+            alert_type: a.alert_type,
+            message: a.message,
+            timestamp: a.alert_time,
+            acknowledged: a.acknowledged,
+            acknowledged_by: a.acknowledged_by,
+            acknowledged_at: a.acknowledged_at,
+            deliveries: a.deliveries.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Operator annotation on a convoy's mission timeline
+#[derive(Debug, Clone, SimpleObject)]
+pub struct JournalEntry {
+    /// Entry ID
+    pub entry_id: ID,
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// When the entry was written
+    pub timestamp: DateTime<Utc>,
+    /// Operator who wrote the entry
+    pub author: String,
+    /// Annotation text
+    pub text: String,
+    /// Engagement, drone, target or alert the entry refers to
+    pub linked_entity_id: Option<ID>,
+}
+
+impl From<domain::JournalEntry> for JournalEntry {
+    fn from(e: domain::JournalEntry) -> Self {
+        Self {
+            entry_id: ID(e.entry_id.to_string()),
+            convoy_id: ID(e.convoy_id.to_string()),
+            timestamp: e.entry_time,
+            author: e.author,
+            text: e.text,
+            linked_entity_id: e.linked_entity_id.map(|id| ID(id.to_string())),
+        }
+    }
+}
+
+/// Document stored when a convoy completed its mission
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MissionArtifact {
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// What the document holds
+    pub kind: ArtifactKind,
+    /// Lowercase hex SHA-256 of the content
+    pub digest: String,
+    /// Suggested file name
+    pub file_name: String,
+    /// Media type of the content
+    pub content_type: String,
+    /// Content size in bytes
+    pub size_bytes: i64,
+    /// When the document was generated
+    pub created_at: DateTime<Utc>,
+    /// Path to download the content from
+    pub download_url: String,
+}
+
+impl From<domain::MissionArtifact> for MissionArtifact {
+    fn from(a: domain::MissionArtifact) -> Self {
+        Self {
+            download_url: format!("/artifacts/{}/{}", a.convoy_id, a.digest),
+            convoy_id: ID(a.convoy_id.to_string()),
+            kind: a.kind.into(),
+            digest: a.digest,
+            file_name: a.file_name,
+            content_type: a.content_type,
+            size_bytes: a.size_bytes,
+            created_at: a.created_at,
+        }
+    }
+}
+
+/// One event on an after-action replay timeline
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ReplayEvent {
+    /// When the event happened
+    pub timestamp: DateTime<Utc>,
+    /// Which of the payload fields is set
+    pub kind: ReplayEventKind,
+    /// Set for `ENGAGEMENT` events
+    pub engagement: Option<Engagement>,
+    /// Set for `JOURNAL` events
+    pub journal_entry: Option<JournalEntry>,
+}
+
+/// A convoy's engagements and journal entries over a window, in time order
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EngagementReplay {
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Window start
+    pub start: DateTime<Utc>,
+    /// Window end
+    pub end: DateTime<Utc>,
+    /// Events oldest first; engagements precede journal entries at equal times
+    pub events: Vec<ReplayEvent>,
+    /// Whether the window held more events than were returned
+    pub truncated: bool,
+}
+
+impl EngagementReplay {
+    /// Interleave engagements and journal entries, each already oldest first
+    #[must_use]
+    pub fn new(
+        convoy_id: ID,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        engagements: Vec<domain::Engagement>,
+        journal: Vec<domain::JournalEntry>,
+        truncated: bool,
+    ) -> Self {
+        let mut events: Vec<ReplayEvent> = engagements
+            .into_iter()
+            .map(|e| ReplayEvent {
+                timestamp: e.engaged_at,
+                kind: ReplayEventKind::Engagement,
+                engagement: Some(e.into()),
+                journal_entry: None,
+            })
+            .chain(journal.into_iter().map(|j| ReplayEvent {
+                timestamp: j.entry_time,
+                kind: ReplayEventKind::Journal,
+                engagement: None,
+                journal_entry: Some(j.into()),
+            }))
+            .collect();
+        // Stable, so ties keep engagements ahead of the notes about them
+        events.sort_by_key(|event| event.timestamp);
+
+        Self {
+            convoy_id,
+            start,
+            end,
+            events,
+            truncated,
+        }
+    }
+}
+
+/// A broadcast event a subscriber may have missed
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MissedEvent {
+    /// Event ID, as carried by the subscription payload
+    pub event_id: ID,
+    /// Which of the payload fields is set
+    pub kind: MissedEventKind,
+    /// Set for `ENGAGEMENT` events
+    pub engagement: Option<EngagementEvent>,
+    /// Set for `LEADERBOARD_UPDATE` events
+    pub leaderboard_update: Option<LeaderboardUpdateEvent>,
+    /// Set for `DRONE_STATUS` events
+    pub drone_status: Option<DroneStatusEvent>,
+    /// Set for `ALERT` events
+    pub alert: Option<AlertEvent>,
+    /// Set for `AUTHORIZATION` events
+    pub authorization: Option<EngagementAuthorization>,
+}
+
+/// Events broadcast for a convoy after a client's last seen event
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MissedEvents {
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Events oldest first
+    pub events: Vec<MissedEvent>,
+    /// Whether nothing was lost since `sinceEventId`; when false the client
+    /// should refetch convoy state rather than rely on the replay
+    pub complete: bool,
 }
 
 // =============================================================================
@@ -622,6 +1910,8 @@ pub struct RecordEngagementResult {
     pub rank_change: i32,
     /// New accuracy percentage
     pub new_accuracy_pct: f32,
+    /// Per-drone sequence number the engagement was applied in
+    pub sequence: i64,
 }
 
 /// Result of rebuilding leaderboard
@@ -635,22 +1925,273 @@ pub struct RebuildLeaderboardResult {
     pub duration_ms: i64,
 }
 
+/// Result of importing a convoy snapshot
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SnapshotImportResult {
+    /// Restored convoy ID
+    pub convoy_id: ID,
+    /// Whether the convoy record was restored
+    pub convoy_restored: bool,
+    /// Drones added to the roster
+    pub drones_restored: i32,
+    /// Latest telemetry snapshots restored
+    pub telemetry_restored: i32,
+    /// Leaderboard entries restored
+    pub leaderboard_entries_restored: i32,
+    /// Open alerts restored
+    pub alerts_restored: i32,
+    /// Journal entries restored
+    pub journal_entries_restored: i32,
+    /// Waypoints present in the snapshot but not restored
+    pub waypoints_skipped: i32,
+}
+
+/// One `leaderboardLive` message
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LeaderboardLiveMessage {
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Position in this subscription's stream, starting at 1 with no gaps
+    pub sequence: i32,
+    /// Whether this replaces or patches the client's leaderboard
+    pub kind: LeaderboardLiveKind,
+    /// Every entry for a snapshot; only changed entries for a delta
+    pub entries: Vec<LeaderboardEntry>,
+    /// Drones that dropped out of the leaderboard (deltas only)
+    pub removed_drone_ids: Vec<ID>,
+    /// Message timestamp
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Result of rebuilding a convoy's projections from its event log
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RebuildProjectionsResult {
+    /// Rebuilt convoy ID
+    pub convoy_id: ID,
+    /// Log events replayed
+    pub events_replayed: i32,
+    /// Leaderboard entries written
+    pub entries_written: i32,
+    /// Time taken to replay and write, in milliseconds
+    pub duration_ms: i32,
+}
+
+/// Result of recounting a convoy's accuracy rankings from its engagements
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RebuildAccuracyRankingsResult {
+    /// Rebuilt convoy ID
+    pub convoy_id: ID,
+    /// Engagements counted
+    pub engagements_scanned: i32,
+    /// Weapon types ranked
+    pub weapon_types: i32,
+    /// Time taken to count and write, in milliseconds
+    pub duration_ms: i32,
+}
+
+/// One weapon type in a convoy's `weaponLeaderboard`
+#[derive(Debug, Clone, SimpleObject)]
+pub struct WeaponAccuracyEntry {
+    /// Position, 1 for the highest hit rate
+    pub rank: i32,
+    /// Weapon type
+    pub weapon_type: WeaponType,
+    /// Engagements with this weapon
+    pub total_engagements: i32,
+    /// Engagements that hit
+    pub successful_hits: i32,
+    /// Hit rate (0-100)
+    pub accuracy_pct: f32,
+}
+
+/// One convoy in the `convoyLeaderboard`
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ConvoyAccuracyEntry {
+    /// Position, 1 for the highest hit rate
+    pub rank: i32,
+    /// Convoy ID
+    pub convoy_id: ID,
+    /// Engagements across the convoy
+    pub total_engagements: i32,
+    /// Engagements that hit
+    pub successful_hits: i32,
+    /// Hit rate (0-100)
+    pub accuracy_pct: f32,
+}
+
+/// One `search` match
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SearchResult {
+    /// Kind of entity matched
+    #[graphql(name = "type")]
+    pub result_type: SearchResultType,
+    /// Drone, convoy or engagement ID
+    pub id: ID,
+    /// Convoy the entity belongs to
+    pub convoy_id: ID,
+    /// Display text
+    pub label: String,
+    /// Normalized index term that matched
+    pub matched_term: String,
+    /// Relevance from 0 to 1, higher is better
+    pub score: f64,
+}
+
+impl From<domain::SearchHit> for SearchResult {
+    fn from(hit: domain::SearchHit) -> Self {
+        Self {
+            result_type: hit.entry.kind.into(),
+            id: ID(hit.entry.entity_id.to_string()),
+            convoy_id: ID(hit.entry.convoy_id.to_string()),
+            label: hit.entry.label,
+            matched_term: hit.entry.term,
+            score: hit.score,
+        }
+    }
+}
+
+/// Subscription connection counts
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ConnectionStats {
+    /// Open subscription connections
+    pub active: i32,
+    /// Global connection limit
+    pub max_connections: i32,
+    /// Per-IP connection limit
+    pub max_connections_per_ip: i32,
+    /// Open connections per client IP, busiest first
+    pub by_ip: Vec<IpConnectionCount>,
+}
+
+/// Open connections from one client IP
+#[derive(Debug, Clone, SimpleObject)]
+pub struct IpConnectionCount {
+    /// Client IP address
+    pub ip: String,
+    /// Open connections
+    pub connections: i32,
+}
+
+/// Machine-client API key (the secret is never returned)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ApiKey {
+    /// Key ID, the non-secret part of the key value
+    pub key_id: ID,
+    /// Client the key was issued to
+    pub name: String,
+    /// What the key may do
+    pub scope: ApiKeyScope,
+    /// Commanding unit the key is confined to; null grants every unit
+    pub commanding_unit: Option<String>,
+    /// Data environment the key is confined to
+    pub environment: Environment,
+    /// Whether the key can still authenticate
+    pub active: bool,
+    /// When the key was issued
+    pub created_at: DateTime<Utc>,
+    /// When the secret was last rotated
+    pub rotated_at: Option<DateTime<Utc>>,
+    /// When the key was revoked
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<domain::ApiKey> for ApiKey {
+    fn from(k: domain::ApiKey) -> Self {
+        Self {
+            active: k.is_active(),
+            key_id: ID(k.key_id),
+            name: k.name,
+            scope: k.scope.into(),
+            commanding_unit: k.commanding_unit,
+            environment: k.environment.into(),
+            created_at: k.created_at,
+            rotated_at: k.rotated_at,
+            revoked_at: k.revoked_at,
+        }
+    }
+}
+
+/// Newly issued or rotated API key
+#[derive(Debug, Clone, SimpleObject)]
+pub struct IssuedApiKey {
+    /// Key metadata
+    pub api_key: ApiKey,
+    /// Full key value for the `X-API-Key` header; shown only once
+    pub key: String,
+}
+
 // =============================================================================
 // PAGINATED RESPONSE TYPES
 // =============================================================================
 
-/// Paginated list wrapper
+/// Relay-style connection over a cursor-paginated list
 #[derive(Debug, Clone, SimpleObject)]
 #[graphql(concrete(name = "EngagementConnection", params(Engagement)))]
 #[graphql(concrete(name = "DroneConnection", params(Drone)))]
 #[graphql(concrete(name = "TelemetryConnection", params(TelemetrySnapshot)))]
-pub struct Connection<T: async_graphql::OutputType> {
-    /// Items in this page
-    pub items: Vec<T>,
-    /// Total count across all pages
-    pub total_count: i32,
-    /// Has more pages
+pub struct Connection<T: async_graphql::OutputType>
+where
+    Edge<T>: async_graphql::OutputType,
+{
+    /// Items in this page with their cursors
+    pub edges: Vec<Edge<T>>,
+    /// Cursors and flags for fetching adjacent pages
+    pub page_info: PageInfo,
+    /// Total count across all pages (null when a filter is applied and
+    /// counting would need a full scan)
+    pub total_count: Option<i32>,
+}
+
+impl<T: async_graphql::OutputType> Connection<T>
+where
+    Edge<T>: async_graphql::OutputType,
+{
+    /// Build a connection from items and their cursors, in order
+    #[must_use]
+    pub fn new(
+        edges: Vec<(T, String)>,
+        has_next_page: bool,
+        has_previous_page: bool,
+        total_count: Option<i32>,
+    ) -> Self {
+        let edges: Vec<Edge<T>> = edges
+            .into_iter()
+            .map(|(node, cursor)| Edge { cursor, node })
+            .collect();
+        Self {
+            page_info: PageInfo {
+                has_next_page,
+                has_previous_page,
+                start_cursor: edges.first().map(|e| e.cursor.clone()),
+                end_cursor: edges.last().map(|e| e.cursor.clone()),
+            },
+            edges,
+            total_count,
+        }
+    }
+}
+
+/// An item in a connection
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(concrete(name = "EngagementEdge", params(Engagement)))]
+#[graphql(concrete(name = "DroneEdge", params(Drone)))]
+#[graphql(concrete(name = "TelemetryEdge", params(TelemetrySnapshot)))]
+pub struct Edge<T: async_graphql::OutputType> {
+    /// Opaque cursor; pass as `after` to continue after this item
+    pub cursor: String,
+    /// The item
+    pub node: T,
+}
+
+/// Relay page info
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PageInfo {
+    /// More items follow the last edge
     pub has_next_page: bool,
-    /// Has previous pages
+    /// Items precede the first edge (true whenever `after` was given)
     pub has_previous_page: bool,
+    /// Cursor of the first edge
+    pub start_cursor: Option<String>,
+    /// Cursor of the last edge
+    pub end_cursor: Option<String>,
 }
@@ -0,0 +1,116 @@
+//! # Quick-Jump Search
+//!
+//! Maintains the Redis search index over drone callsigns, tail numbers,
+//! convoy callsigns and engagement IDs, and answers the `search` query from
+//! it. Indexing is best effort: a failed write is logged and never fails the
+//! mutation that triggered it.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::auth::Claims;
+use crate::context::ApiContext;
+use crate::error::ApiResult;
+use drone_domain::{normalize_search_term, rank_entries, SearchEntry, SearchHit, SearchKind};
+
+/// Index entries read per kind for typo-tolerant matching
+const MAX_FUZZY_SCAN: usize = 5000;
+
+/// Engagements kept in the index, newest first
+const MAX_ENGAGEMENT_ENTRIES: usize = 10_000;
+
+/// Prefix candidates read per kind, before access filtering
+const PREFIX_CANDIDATES: usize = 200;
+
+/// Index a registered drone under its callsign and tail number
+pub async fn index_drone(ctx: &ApiContext, drone: &drone_domain::Drone) {
+    let label = format!("{} ({})", drone.callsign, drone.tail_number);
+    for term in [&drone.callsign, &drone.tail_number] {
+        let entry = SearchEntry::new(
+            SearchKind::Drone,
+            term,
+            drone.drone_id,
+            drone.convoy_id,
+            label.clone(),
+        );
+        index(ctx, &entry).await;
+    }
+}
+
+/// Index a convoy under its callsign
+pub async fn index_convoy(ctx: &ApiContext, convoy_id: Uuid, callsign: &str) {
+    let entry = SearchEntry::new(SearchKind::Convoy, callsign, convoy_id, convoy_id, callsign);
+    index(ctx, &entry).await;
+}
+
+/// Index an engagement under its ID, evicting the oldest past the cap
+pub async fn index_engagement(ctx: &ApiContext, engagement: &drone_domain::Engagement) {
+    let entry = SearchEntry::new(
+        SearchKind::Engagement,
+        &engagement.engagement_id.to_string(),
+        engagement.engagement_id,
+        engagement.convoy_id,
+        format!(
+            "{} {} {}",
+            engagement.weapon_type.as_str(),
+            if engagement.hit { "HIT" } else { "MISS" },
+            engagement.engaged_at.format("%Y-%m-%d %H:%MZ"),
+        ),
+    );
+    if let Err(e) = ctx
+        .cache
+        .index_recent_search_entry(
+            &entry,
+            engagement.engaged_at.timestamp_millis(),
+            MAX_ENGAGEMENT_ENTRIES,
+        )
+        .await
+    {
+        tracing::warn!(entity_id = %entry.entity_id, error = %e, "Failed to index engagement for search");
+    }
+}
+
+async fn index(ctx: &ApiContext, entry: &SearchEntry) {
+    if let Err(e) = ctx.cache.index_search_entry(entry).await {
+        tracing::warn!(entity_id = %entry.entity_id, error = %e, "Failed to index search entry");
+    }
+}
+
+/// Best matches for `query` among `kinds`, limited to convoys the caller
+/// can see
+pub async fn search(
+    ctx: &ApiContext,
+    claims: &Claims,
+    query: &str,
+    kinds: &[SearchKind],
+    limit: usize,
+) -> ApiResult<Vec<SearchHit>> {
+    let term = normalize_search_term(query);
+    if term.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut candidates = Vec::new();
+    for kind in kinds.iter().copied().collect::<HashSet<_>>() {
+        if kind.is_fuzzy() {
+            candidates.extend(ctx.cache.search_scan(kind, MAX_FUZZY_SCAN).await?);
+        } else {
+            candidates.extend(ctx.cache.search_prefix(kind, &term, PREFIX_CANDIDATES).await?);
+        }
+    }
+
+    let mut hits = Vec::with_capacity(limit);
+    for hit in rank_entries(&term, candidates, usize::MAX) {
+        if hits.len() == limit {
+            break;
+        }
+        let convoy_id = hit.entry.convoy_id;
+        if claims.can_access_convoy(&convoy_id.to_string())
+            && ctx.authorize_convoy(claims, convoy_id).await.is_ok()
+        {
+            hits.push(hit);
+        }
+    }
+    Ok(hits)
+}
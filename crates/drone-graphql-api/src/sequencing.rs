@@ -0,0 +1,202 @@
+//! # Engagement Sequencing
+//!
+//! Keeps streak and rank updates for a drone in arrival order. Each
+//! engagement takes a sequence number from a per-drone Redis counter when it
+//! arrives, and the number is stored on the engagement row. Before touching
+//! the leaderboard the engagement waits for any earlier engagement for the
+//! same drone still in flight on this replica, for at most the reorder
+//! window, so a slow request cannot have its update overtaken and rewrite
+//! the streak out of order.
+//!
+//! The Redis counter keeps numbers unique across replicas; waiting only
+//! covers engagements handled by this replica, so other replicas' numbers
+//! never stall it.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use drone_persistence::SharedCacheClient;
+
+/// Default time an engagement waits for earlier ones for the same drone
+pub const DEFAULT_REORDER_WINDOW: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Default)]
+struct DroneSequence {
+    /// Highest number handed out for the drone on this replica
+    last: i64,
+    /// Numbers taken but not yet applied
+    in_flight: BTreeSet<i64>,
+}
+
+/// Allocates per-drone engagement sequence numbers and orders their updates
+pub struct EngagementSequencer {
+    cache: Option<SharedCacheClient>,
+    drones: Mutex<HashMap<Uuid, DroneSequence>>,
+    applied: Notify,
+    window: Duration,
+}
+
+impl EngagementSequencer {
+    /// Sequencer allocating from Redis, or locally when `cache` is `None`
+    #[must_use]
+    pub fn new(cache: Option<SharedCacheClient>) -> Self {
+        Self {
+            cache,
+            drones: Mutex::new(HashMap::new()),
+            applied: Notify::new(),
+            window: DEFAULT_REORDER_WINDOW,
+        }
+    }
+
+    /// Set how long an engagement waits for earlier ones
+    #[must_use]
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Take the drone's next sequence number
+    ///
+    /// Falls back to a local counter when Redis is unavailable, so
+    /// engagements keep flowing with ordering held on this replica only.
+    pub async fn next(&self, drone_id: Uuid) -> SequenceTicket<'_> {
+        let allocated = match &self.cache {
+            Some(cache) => cache
+                .next_engagement_sequence(drone_id)
+                .await
+                .inspect_err(|e| {
+                    tracing::warn!(%drone_id, error = %e, "Falling back to local engagement sequence");
+                })
+                .ok(),
+            None => None,
+        };
+
+        let mut drones = self.lock();
+        let drone = drones.entry(drone_id).or_default();
+        let sequence = allocated.unwrap_or(drone.last + 1);
+        drone.last = drone.last.max(sequence);
+        drone.in_flight.insert(sequence);
+
+        SequenceTicket {
+            sequencer: self,
+            drone_id,
+            sequence,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<Uuid, DroneSequence>> {
+        self.drones.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Whether no earlier engagement for the drone is still in flight
+    fn is_next(&self, drone_id: Uuid, sequence: i64) -> bool {
+        self.lock()
+            .get(&drone_id)
+            .and_then(|drone| drone.in_flight.first())
+            .is_none_or(|first| *first >= sequence)
+    }
+
+    fn release(&self, drone_id: Uuid, sequence: i64) {
+        if let Some(drone) = self.lock().get_mut(&drone_id) {
+            drone.in_flight.remove(&sequence);
+        }
+        self.applied.notify_waiters();
+    }
+}
+
+/// A drone's sequence number, held until its leaderboard update is done
+///
+/// Dropping the ticket, applied or not, lets later engagements proceed.
+pub struct SequenceTicket<'a> {
+    sequencer: &'a EngagementSequencer,
+    drone_id: Uuid,
+    sequence: i64,
+}
+
+impl SequenceTicket<'_> {
+    /// The allocated sequence number
+    #[must_use]
+    pub fn sequence(&self) -> i64 {
+        self.sequence
+    }
+
+    /// Wait until every earlier engagement for the drone on this replica has
+    /// been applied, or the reorder window has passed
+    pub async fn wait_turn(&self) {
+        let deadline = Instant::now() + self.sequencer.window;
+        loop {
+            // Register before checking so a release in between is not missed
+            let applied = self.sequencer.applied.notified();
+            tokio::pin!(applied);
+            applied.as_mut().enable();
+
+            if self.sequencer.is_next(self.drone_id, self.sequence) {
+                return;
+            }
+            if tokio::time::timeout_at(deadline, applied).await.is_err() {
+                tracing::warn!(
+                    drone_id = %self.drone_id,
+                    sequence = self.sequence,
+                    "Applying engagement ahead of earlier ones still in flight"
+                );
+                return;
+            }
+        }
+    }
+}
+
+impl Drop for SequenceTicket<'_> {
+    fn drop(&mut self) {
+        self.sequencer.release(self.drone_id, self.sequence);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_later_engagement_waits_for_earlier() {
+        let sequencer = EngagementSequencer::new(None);
+        let drone_id = Uuid::new_v4();
+
+        let first = sequencer.next(drone_id).await;
+        let second = sequencer.next(drone_id).await;
+        assert_eq!((first.sequence(), second.sequence()), (1, 2));
+
+        let applied = &Mutex::new(Vec::new());
+        tokio::join!(
+            async move {
+                second.wait_turn().await;
+                applied.lock().unwrap().push(2);
+            },
+            async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                applied.lock().unwrap().push(1);
+                drop(first);
+            },
+        );
+        assert_eq!(*applied.lock().unwrap(), [1, 2]);
+
+        // Other drones count independently
+        assert_eq!(sequencer.next(Uuid::new_v4()).await.sequence(), 1);
+        assert_eq!(sequencer.next(drone_id).await.sequence(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_engagement_only_delays_for_window() {
+        let sequencer =
+            EngagementSequencer::new(None).with_window(Duration::from_millis(10));
+        let drone_id = Uuid::new_v4();
+
+        let _stalled = sequencer.next(drone_id).await;
+        let later = sequencer.next(drone_id).await;
+        tokio::time::timeout(Duration::from_secs(1), later.wait_turn())
+            .await
+            .expect("window bounds the wait");
+    }
+}
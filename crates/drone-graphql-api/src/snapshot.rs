@@ -0,0 +1,221 @@
+//! # Convoy Snapshots
+//!
+//! Versioned JSON dump of a convoy's operational state for shift changes,
+//! and restore of that dump into a fresh environment.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::{ApiError, ApiResult};
+use crate::schema::{SnapshotImportResult, TelemetrySnapshot};
+use drone_domain::{Alert, Convoy, JournalEntry, LeaderboardEntry, ScoringModel, Waypoint};
+
+/// Current snapshot document version
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Upper bound on leaderboard rows exported per convoy
+const MAX_LEADERBOARD_ENTRIES: i32 = 1000;
+
+/// Upper bound on open alerts exported per convoy
+const MAX_OPEN_ALERTS: usize = 500;
+
+/// Upper bound on journal entries exported per convoy
+const MAX_JOURNAL_ENTRIES: usize = 1000;
+
+/// Complete state dump of a single convoy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvoySnapshot {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub convoy_id: Uuid,
+    pub scoring_model: ScoringModel,
+    pub convoy: Option<Convoy>,
+    /// Drones on the convoy roster
+    pub drone_ids: Vec<Uuid>,
+    pub waypoints: Vec<Waypoint>,
+    pub latest_telemetry: Vec<TelemetrySnapshot>,
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub open_alerts: Vec<Alert>,
+    /// Most recent operator journal entries, oldest first
+    #[serde(default)]
+    pub journal: Vec<JournalEntry>,
+}
+
+/// Assemble a snapshot of a convoy from the repositories and cache
+pub async fn export_convoy(ctx: &ApiContext, convoy_id: Uuid) -> ApiResult<ConvoySnapshot> {
+    let convoy = ctx.convoy_repo.get(convoy_id).await?;
+    let drone_ids = ctx.cache.get_convoy_roster(convoy_id).await?;
+
+    let mut waypoints = Vec::new();
+    let mut latest_telemetry = Vec::with_capacity(drone_ids.len());
+    for drone_id in &drone_ids {
+        waypoints.extend(ctx.waypoint_repo.get_waypoints(*drone_id).await?);
+        if let Some(snapshot) = ctx.cache.get_latest_telemetry(*drone_id).await? {
+            latest_telemetry.push(snapshot);
+        }
+    }
+
+    let leaderboard = ctx
+        .leaderboard_repo
+        .get_leaderboard(convoy_id, MAX_LEADERBOARD_ENTRIES)
+        .await?;
+    let open_alerts = ctx.alert_repo.get_open(convoy_id, MAX_OPEN_ALERTS).await?;
+    let mut journal = ctx.journal_repo.get_recent(convoy_id, MAX_JOURNAL_ENTRIES).await?;
+    journal.reverse();
+
+    Ok(ConvoySnapshot {
+        version: SNAPSHOT_VERSION,
+        exported_at: Utc::now(),
+        convoy_id,
+        scoring_model: ctx.leaderboard_repo.scoring_model(convoy_id).await?,
+        convoy,
+        drone_ids,
+        waypoints,
+        latest_telemetry,
+        leaderboard,
+        open_alerts,
+        journal,
+    })
+}
+
+/// Restore a snapshot into this environment.
+///
+/// Existing rows for the same keys are overwritten. Waypoints are reported
+/// as skipped until the waypoint repository supports writes.
+pub async fn import_convoy(
+    ctx: &ApiContext,
+    snapshot: &ConvoySnapshot,
+) -> ApiResult<SnapshotImportResult> {
+    validate(snapshot)?;
+    let convoy_id = snapshot.convoy_id;
+
+    if let Some(ref convoy) = snapshot.convoy {
+        ctx.convoy_repo.create(convoy).await?;
+    }
+    ctx.leaderboard_repo
+        .set_scoring_model(convoy_id, snapshot.scoring_model)
+        .await?;
+
+    ctx.cache
+        .add_many_to_convoy_roster(convoy_id, &snapshot.drone_ids)
+        .await?;
+
+    for telemetry in &snapshot.latest_telemetry {
+        let drone_id = Uuid::parse_str(&telemetry.drone_id)?;
+        ctx.cache.set_latest_telemetry(drone_id, telemetry).await?;
+    }
+
+    for entry in &snapshot.leaderboard {
+        ctx.leaderboard_repo.restore_entry(entry).await?;
+    }
+
+    for alert in &snapshot.open_alerts {
+        ctx.alert_repo.record(alert).await?;
+    }
+
+    for entry in &snapshot.journal {
+        ctx.journal_repo.record(entry).await?;
+    }
+
+    tracing::info!(
+        convoy_id = %convoy_id,
+        version = snapshot.version,
+        exported_at = %snapshot.exported_at,
+        "Convoy snapshot imported"
+    );
+
+    Ok(SnapshotImportResult {
+        convoy_id: convoy_id.into(),
+        convoy_restored: snapshot.convoy.is_some(),
+        drones_restored: snapshot.drone_ids.len() as i32,
+        telemetry_restored: snapshot.latest_telemetry.len() as i32,
+        leaderboard_entries_restored: snapshot.leaderboard.len() as i32,
+        alerts_restored: snapshot.open_alerts.len() as i32,
+        journal_entries_restored: snapshot.journal.len() as i32,
+        waypoints_skipped: snapshot.waypoints.len() as i32,
+    })
+}
+
+/// Reject snapshots from newer builds or with rows for other convoys
+fn validate(snapshot: &ConvoySnapshot) -> ApiResult<()> {
+    if snapshot.version == 0 || snapshot.version > SNAPSHOT_VERSION {
+        return Err(ApiError::InvalidInput(format!(
+            "Unsupported snapshot version {} (supported: 1-{SNAPSHOT_VERSION})",
+            snapshot.version,
+        )));
+    }
+
+    let convoy_id = snapshot.convoy_id;
+    let foreign = snapshot.convoy.as_ref().is_some_and(|c| c.convoy_id != convoy_id)
+        || snapshot.leaderboard.iter().any(|e| e.convoy_id != convoy_id)
+        || snapshot.open_alerts.iter().any(|a| a.convoy_id != convoy_id)
+        || snapshot.journal.iter().any(|j| j.convoy_id != convoy_id);
+    if foreign {
+        return Err(ApiError::InvalidInput(format!(
+            "Snapshot contains records for convoys other than {convoy_id}"
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_snapshot(convoy_id: Uuid) -> ConvoySnapshot {
+        ConvoySnapshot {
+            version: SNAPSHOT_VERSION,
+            exported_at: Utc::now(),
+            convoy_id,
+            scoring_model: ScoringModel::default(),
+            convoy: None,
+            drone_ids: Vec::new(),
+            waypoints: Vec::new(),
+            latest_telemetry: Vec::new(),
+            leaderboard: Vec::new(),
+            open_alerts: Vec::new(),
+            journal: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trips_through_json() {
+        let snapshot = empty_snapshot(Uuid::new_v4());
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: ConvoySnapshot = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.convoy_id, snapshot.convoy_id);
+        assert_eq!(restored.version, SNAPSHOT_VERSION);
+        assert!(validate(&restored).is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_future_version() {
+        let mut snapshot = empty_snapshot(Uuid::new_v4());
+        snapshot.version = SNAPSHOT_VERSION + 1;
+
+        assert!(matches!(validate(&snapshot), Err(ApiError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_journal_is_optional_and_checked_for_convoy() {
+        let convoy_id = Uuid::new_v4();
+        let mut json = serde_json::to_value(empty_snapshot(convoy_id)).unwrap();
+        json.as_object_mut().unwrap().remove("journal");
+        let mut snapshot: ConvoySnapshot = serde_json::from_value(json).unwrap();
+        assert!(snapshot.journal.is_empty());
+
+        snapshot.journal.push(JournalEntry {
+            convoy_id: Uuid::new_v4(),
+            entry_time: Utc::now(),
+            entry_id: Uuid::new_v4(),
+            author: "OPERATOR".to_string(),
+            text: "Weather hold".to_string(),
+            linked_entity_id: None,
+        });
+        assert!(matches!(validate(&snapshot), Err(ApiError::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,323 @@
+//! # Server-Sent Events
+//!
+//! `/events/{convoy_id}` fallback for networks that block WebSockets. Relays
+//! the engagement, leaderboard and alert broadcasts as JSON event frames and
+//! replays what a client missed when it reconnects with `Last-Event-ID`.
+
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{ConnectInfo, Path, Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+use crate::auth::{self, Role};
+use crate::context::ApiContext;
+use crate::error::ApiError;
+use crate::AppState;
+
+/// Events retained for `Last-Event-ID` resume by default
+pub const DEFAULT_REPLAY_CAPACITY: usize = 1024;
+
+/// Header EventSource clients send when reconnecting
+const LAST_EVENT_ID: &str = "last-event-id";
+
+/// Kind of relayed event, sent as the SSE `event:` field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Engagement,
+    Leaderboard,
+    Alert,
+}
+
+impl EventKind {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Engagement => "engagement",
+            Self::Leaderboard => "leaderboard",
+            Self::Alert => "alert",
+        }
+    }
+}
+
+/// Broadcast event stamped with its position in the log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedEvent {
+    /// Monotonic event ID, sent as the SSE `id:` field
+    pub id: u64,
+    pub convoy_id: String,
+    pub kind: EventKind,
+    /// JSON payload, shaped like the matching subscription field
+    pub data: String,
+}
+
+#[derive(Debug)]
+struct LogInner {
+    next_id: u64,
+    events: VecDeque<Arc<LoggedEvent>>,
+}
+
+/// Bounded log of recent broadcast events, replayed on resume
+#[derive(Debug)]
+pub struct EventLog {
+    capacity: usize,
+    inner: Mutex<LogInner>,
+    tx: broadcast::Sender<Arc<LoggedEvent>>,
+}
+
+impl EventLog {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        Self {
+            capacity,
+            inner: Mutex::new(LogInner {
+                next_id: 1,
+                events: VecDeque::with_capacity(capacity),
+            }),
+            tx,
+        }
+    }
+
+    /// Stamp an event, retain it for replay and publish it to open streams
+    pub fn append(&self, convoy_id: &str, kind: EventKind, data: String) -> u64 {
+        let Ok(mut inner) = self.inner.lock() else {
+            return 0;
+        };
+        let event = Arc::new(LoggedEvent {
+            id: inner.next_id,
+            convoy_id: convoy_id.to_string(),
+            kind,
+            data,
+        });
+        inner.next_id += 1;
+        if self.capacity > 0 {
+            if inner.events.len() == self.capacity {
+                inner.events.pop_front();
+            }
+            inner.events.push_back(Arc::clone(&event));
+        }
+        // Published under the lock so `resume` never sees an event twice
+        let _ = self.tx.send(Arc::clone(&event));
+        event.id
+    }
+
+    /// Retained events for `convoy_id` after `last_id`, plus a receiver for
+    /// everything appended afterwards.
+    ///
+    /// An ID the log has not issued yet (the server restarted) replays
+    /// nothing, as does a fresh connection without one.
+    pub fn resume(
+        &self,
+        convoy_id: &str,
+        last_id: Option<u64>,
+    ) -> (Vec<Arc<LoggedEvent>>, broadcast::Receiver<Arc<LoggedEvent>>) {
+        let Ok(inner) = self.inner.lock() else {
+            return (Vec::new(), self.tx.subscribe());
+        };
+        let replay = match last_id {
+            Some(last) if last < inner.next_id => inner
+                .events
+                .iter()
+                .filter(|e| e.id > last && e.convoy_id == convoy_id)
+                .cloned()
+                .collect(),
+            _ => Vec::new(),
+        };
+        (replay, self.tx.subscribe())
+    }
+
+    /// Feed the log from the context's broadcast channels until they close
+    #[must_use]
+    pub fn relay(self: Arc<Self>, ctx: &ApiContext) -> JoinHandle<()> {
+        let mut engagements = ctx.engagement_tx.subscribe();
+        let mut leaderboard = ctx.leaderboard_tx.subscribe();
+        let mut alerts = ctx.alert_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                let closed = tokio::select! {
+                    event = engagements.recv() => self.record(EventKind::Engagement, event, |e| &e.convoy_id),
+                    event = leaderboard.recv() => self.record(EventKind::Leaderboard, event, |e| &e.convoy_id),
+                    event = alerts.recv() => self.record(EventKind::Alert, event, |e| &e.convoy_id),
+                };
+                if closed {
+                    break;
+                }
+            }
+            tracing::info!("SSE event relay stopped");
+        })
+    }
+
+    /// Append one received broadcast; returns `true` once the channel closed
+    fn record<T: Serialize>(
+        &self,
+        kind: EventKind,
+        received: Result<T, RecvError>,
+        convoy_id: impl Fn(&T) -> &async_graphql::ID,
+    ) -> bool {
+        match received {
+            Ok(event) => {
+                match serde_json::to_string(&event) {
+                    Ok(data) => {
+                        self.append(convoy_id(&event), kind, data);
+                    }
+                    Err(e) => tracing::warn!(kind = kind.as_str(), error = %e, "Failed to encode SSE event"),
+                }
+                false
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::warn!(kind = kind.as_str(), skipped, "SSE relay lagged");
+                false
+            }
+            Err(RecvError::Closed) => true,
+        }
+    }
+}
+
+/// Query string for the SSE endpoint
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct EventsQuery {
+    /// Bearer token; EventSource cannot set an `Authorization` header
+    pub token: Option<String>,
+    /// Resume point for clients that cannot send `Last-Event-ID`
+    #[serde(rename = "lastEventId")]
+    pub last_event_id: Option<u64>,
+}
+
+/// Convoy event stream
+///
+/// Alerts are only relayed to operators, as with the `alerts` subscription.
+/// Streams count against the subscription connection limits.
+pub async fn convoy_events(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(convoy_id): Path<String>,
+    Query(params): Query<EventsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let convoy_uuid = uuid::Uuid::parse_str(&convoy_id)?;
+    let tokens = &state.ctx.role_tokens;
+    let claims = auth::claims_from_headers(&headers, tokens)
+        .or_else(|| params.token.as_deref().and_then(|t| auth::claims_from_token(t, tokens)));
+    let claims = match claims {
+        Some(claims) => claims,
+        None if state.ctx.ws_require_auth => {
+            return Err(ApiError::Unauthorized("valid token required".to_string()))
+        }
+        None => auth::Claims::anonymous(Role::Viewer),
+    };
+    claims.require_convoy(&convoy_id)?;
+    state.ctx.authorize_convoy(&claims, convoy_uuid).await?;
+
+    let tracker = state.ctx.ws_connections.clone();
+    let Some(permit) = tracker.try_acquire(addr.ip()) else {
+        tracing::warn!(client = %addr.ip(), active = tracker.active(), "SSE connection rejected");
+        return Err(ApiError::RateLimited {
+            retry_after_secs: tracker.limits().ping_interval.as_secs().max(1),
+        });
+    };
+
+    let last_id = headers
+        .get(LAST_EVENT_ID)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .or(params.last_event_id);
+    let (replay, mut rx) = state.ctx.event_log.resume(&convoy_id, last_id);
+    let with_alerts = claims.role.permits(Role::Operator);
+    let wanted = move |event: &LoggedEvent| {
+        event.convoy_id == convoy_id && (with_alerts || event.kind != EventKind::Alert)
+    };
+
+    tracing::debug!(client = %addr.ip(), replayed = replay.len(), "SSE stream opened");
+    let stream = async_stream::stream! {
+        let _permit = permit;
+        for event in replay.into_iter().filter(|e| wanted(e)) {
+            yield Ok::<_, Infallible>(frame(&event));
+        }
+        loop {
+            match rx.recv().await {
+                Ok(event) if wanted(&event) => yield Ok(frame(&event)),
+                Ok(_) => {}
+                // The client resumes from its last ID and gets the gap replayed
+                Err(RecvError::Lagged(_)) | Err(RecvError::Closed) => break,
+            }
+        }
+    };
+
+    Ok(Sse::new(stream)
+        .keep_alive(KeepAlive::new().interval(tracker.limits().ping_interval))
+        .into_response())
+}
+
+fn frame(event: &LoggedEvent) -> Event {
+    Event::default()
+        .id(event.id.to_string())
+        .event(event.kind.as_str())
+        .data(&event.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_replays_missed_events_for_convoy() {
+        let log = EventLog::new(8);
+        log.append("c1", EventKind::Engagement, "{}".into());
+        let second = log.append("c2", EventKind::Alert, "{}".into());
+        let third = log.append("c1", EventKind::Leaderboard, "{}".into());
+
+        let (replay, _) = log.resume("c1", Some(1));
+        assert_eq!(replay.iter().map(|e| e.id).collect::<Vec<_>>(), vec![third]);
+
+        // Fresh connections and IDs from before a restart replay nothing
+        assert!(log.resume("c1", None).0.is_empty());
+        assert!(log.resume("c2", Some(second + 10)).0.is_empty());
+    }
+
+    #[test]
+    fn test_log_is_bounded_and_streams_new_events() {
+        let log = EventLog::new(2);
+        for _ in 0..3 {
+            log.append("c1", EventKind::Engagement, "{}".into());
+        }
+
+        let (replay, mut rx) = log.resume("c1", Some(0));
+        assert_eq!(replay.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2, 3]);
+
+        let id = log.append("c1", EventKind::Alert, "{\"a\":1}".into());
+        let live = rx.try_recv().unwrap();
+        assert_eq!((live.id, live.kind), (id, EventKind::Alert));
+    }
+
+    #[test]
+    fn test_payload_matches_subscription_shape() {
+        let event = crate::schema::EngagementEvent {
+            convoy_id: "c1".into(),
+            drone_id: "d1".into(),
+            callsign: "REAPER-01".to_string(),
+            hit: true,
+            weapon_type: crate::schema::WeaponType::Agm114Hellfire,
+            target_type: None,
+            range_km: Some(4.5),
+            predicted_pk: None,
+            new_accuracy_pct: 90.0,
+            timestamp: chrono::Utc::now(),
+            event_id: None,
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["weaponType"], "AGM_114_HELLFIRE");
+        assert_eq!(json["newAccuracyPct"], 90.0);
+        assert!(json["targetType"].is_null());
+    }
+}
@@ -0,0 +1,123 @@
+//! # Convoy Statistics
+//!
+//! Computes convoy statistics from cached telemetry and the leaderboard, and
+//! records them periodically so the HUD can chart the mission timeline.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use async_graphql::ID;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::context::ApiContext;
+use crate::error::ApiResult;
+use crate::schema::{ConvoyStats, TelemetrySnapshot};
+use drone_domain::{ConvoyStatsSnapshot, LeaderboardEntry};
+
+/// Airspeed at or above which a reporting drone counts as airborne
+const MIN_AIRBORNE_SPEED_MPS: f32 = 1.0;
+
+/// Leaderboard rows read per convoy
+const MAX_LEADERBOARD_ENTRIES: i32 = 1000;
+
+/// Convoys that reported telemetry within this window are snapshotted
+pub(crate) const ACTIVE_WINDOW: Duration = Duration::from_secs(300);
+
+/// Current statistics for a convoy
+///
+/// Drones are counted from the roster and the leaderboard. A drone is
+/// airborne while its latest telemetry is still cached and shows altitude
+/// or airspeed; fuel is averaged over the cached reports.
+pub async fn snapshot_convoy(
+    ctx: &ApiContext,
+    convoy_id: Uuid,
+    entries: &[LeaderboardEntry],
+) -> ApiResult<ConvoyStatsSnapshot> {
+    let roster = ctx.cache.get_convoy_roster(convoy_id).await?;
+
+    let mut airborne_count = 0;
+    let mut fuel = Vec::with_capacity(roster.len());
+    for drone_id in &roster {
+        let latest: Option<TelemetrySnapshot> = ctx.cache.get_latest_telemetry(*drone_id).await?;
+        let Some(snapshot) = latest else {
+            continue;
+        };
+        if snapshot.position.altitude_m > 0.0
+            || snapshot.position.speed_mps >= MIN_AIRBORNE_SPEED_MPS
+        {
+            airborne_count += 1;
+        }
+        fuel.push(snapshot.fuel_remaining_pct);
+    }
+
+    let drones: HashSet<Uuid> = roster
+        .iter()
+        .copied()
+        .chain(entries.iter().map(|e| e.drone_id))
+        .collect();
+
+    Ok(ConvoyStatsSnapshot {
+        convoy_id,
+        recorded_at: Utc::now(),
+        drone_count: drones.len() as i32,
+        airborne_count,
+        average_fuel_pct: if fuel.is_empty() {
+            0.0
+        } else {
+            fuel.iter().sum::<f32>() / fuel.len() as f32
+        },
+        total_engagements: entries.iter().map(|e| e.total_engagements).sum(),
+        total_hits: entries.iter().map(|e| e.successful_hits).sum(),
+    })
+}
+
+/// Current convoy statistics as served by `convoyStats`
+pub async fn convoy_stats(ctx: &ApiContext, convoy_id: Uuid) -> ApiResult<ConvoyStats> {
+    let entries = ctx
+        .leaderboard_repo
+        .get_leaderboard(convoy_id, MAX_LEADERBOARD_ENTRIES)
+        .await?;
+    let snapshot = snapshot_convoy(ctx, convoy_id, &entries).await?;
+
+    let average_accuracy_pct = if entries.is_empty() {
+        0.0
+    } else {
+        entries.iter().map(|e| e.accuracy_pct).sum::<f32>() / entries.len() as f32
+    };
+
+    Ok(ConvoyStats {
+        convoy_id: ID(convoy_id.to_string()),
+        drone_count: snapshot.drone_count,
+        airborne_count: snapshot.airborne_count,
+        total_engagements: snapshot.total_engagements,
+        total_hits: snapshot.total_hits,
+        average_accuracy_pct,
+        average_fuel_pct: snapshot.average_fuel_pct,
+        timestamp: snapshot.recorded_at,
+    })
+}
+
+/// Record a snapshot of each convoy that reported telemetry recently
+///
+/// Run periodically by the task runner; a failing convoy is logged and
+/// skipped so the others are still recorded.
+pub async fn record_active_convoys(ctx: &ApiContext) -> ApiResult<()> {
+    let since = Utc::now().timestamp_millis() - ACTIVE_WINDOW.as_millis() as i64;
+    for convoy_id in ctx.cache.get_active_convoys(since).await? {
+        if let Err(e) = record_convoy(ctx, convoy_id).await {
+            tracing::warn!(convoy_id = %convoy_id, error = %e, "Failed to record convoy stats");
+        }
+    }
+    Ok(())
+}
+
+async fn record_convoy(ctx: &ApiContext, convoy_id: Uuid) -> ApiResult<()> {
+    let entries = ctx
+        .leaderboard_repo
+        .get_leaderboard(convoy_id, MAX_LEADERBOARD_ENTRIES)
+        .await?;
+    let snapshot = snapshot_convoy(ctx, convoy_id, &entries).await?;
+    ctx.convoy_repo.record_stats(&snapshot).await?;
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! # Storage Backend
+//!
+//! Repository types the API is built on. Scylla by default; the `embedded`
+//! feature swaps in the `Sqlite*` repositories, which share their method
+//! signatures, so `cargo run` needs no database cluster.
+
+#[cfg(not(feature = "embedded"))]
+pub use drone_persistence::{
+    ScyllaAlertRepository as AlertRepository, ScyllaApiKeyRepository as ApiKeyRepository,
+    ScyllaAuthorizationRepository as AuthorizationRepository, ScyllaClient as StoreClient,
+    ScyllaConvoyRepository as ConvoyRepository, ScyllaDroneRepository as DroneRepository,
+    ScyllaEngagementLogRepository as EngagementLogRepository,
+    ScyllaEngagementRepository as EngagementRepository,
+    ScyllaJournalRepository as JournalRepository,
+    ScyllaLeaderboardRepository as LeaderboardRepository,
+    ScyllaTargetRepository as TargetRepository, ScyllaTelemetryRepository as TelemetryRepository,
+    ScyllaWaypointRepository as WaypointRepository, ScyllaWeaponsRepository as WeaponsRepository,
+};
+
+#[cfg(feature = "embedded")]
+pub use drone_persistence::{
+    SqliteAlertRepository as AlertRepository, SqliteApiKeyRepository as ApiKeyRepository,
+    SqliteAuthorizationRepository as AuthorizationRepository, SqliteClient as StoreClient,
+    SqliteConvoyRepository as ConvoyRepository, SqliteDroneRepository as DroneRepository,
+    SqliteEngagementLogRepository as EngagementLogRepository,
+    SqliteEngagementRepository as EngagementRepository,
+    SqliteJournalRepository as JournalRepository,
+    SqliteLeaderboardRepository as LeaderboardRepository,
+    SqliteTargetRepository as TargetRepository, SqliteTelemetryRepository as TelemetryRepository,
+    SqliteWaypointRepository as WaypointRepository, SqliteWeaponsRepository as WeaponsRepository,
+};
+
+/// Circuit breaker name of the primary store; an open one fails health checks
+#[cfg(not(feature = "embedded"))]
+pub const BREAKER_NAME: &str = "scylla";
+
+/// Circuit breaker name of the primary store; an open one fails health checks
+#[cfg(feature = "embedded")]
+pub const BREAKER_NAME: &str = "sqlite";
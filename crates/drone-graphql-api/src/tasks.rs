@@ -0,0 +1,239 @@
+//! # Background Task Runner
+//!
+//! Runs registered jobs on fixed intervals for the lifetime of the server.
+//! Each job starts after a random delay so jobs (and replicas) do not fire in
+//! lockstep, and each run executes in its own task so a panic only ends that
+//! run. Jobs stop between runs once the server shuts down.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use ring::rand::{SecureRandom, SystemRandom};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+/// Upper bound on the random delay before a job's first run
+const MAX_START_JITTER: Duration = Duration::from_secs(30);
+
+/// Counters for one registered job
+#[derive(Debug, Clone, Default)]
+pub struct JobMetrics {
+    pub name: &'static str,
+    pub interval: Duration,
+    /// Completed runs, including failed and panicked ones
+    pub runs: u64,
+    pub failures: u64,
+    pub panics: u64,
+    pub last_duration: Option<Duration>,
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
+/// Periodic jobs sharing the server's lifetime
+#[derive(Debug)]
+pub struct TaskRunner {
+    jobs: Mutex<Vec<Arc<Mutex<JobMetrics>>>>,
+    handles: Mutex<Vec<JoinHandle<()>>>,
+    shutdown: watch::Sender<bool>,
+}
+
+impl Default for TaskRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskRunner {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            jobs: Mutex::new(Vec::new()),
+            handles: Mutex::new(Vec::new()),
+            shutdown: watch::channel(false).0,
+        }
+    }
+
+    /// Run `job` every `interval` until shutdown; a zero interval disables it.
+    ///
+    /// The first run follows a random delay of up to one interval (at most
+    /// 30 s). Runs never overlap: one that overruns the interval delays the
+    /// next. Errors and panics are logged and counted, and the job keeps its
+    /// schedule.
+    pub fn register<F, Fut, E>(&self, name: &'static str, interval: Duration, job: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display + Send + 'static,
+    {
+        if interval.is_zero() {
+            tracing::info!(job = name, "Background job disabled");
+            return;
+        }
+
+        let metrics = Arc::new(Mutex::new(JobMetrics {
+            name,
+            interval,
+            ..JobMetrics::default()
+        }));
+        let mut shutdown = self.shutdown.subscribe();
+
+        let handle = tokio::spawn({
+            let metrics = metrics.clone();
+            async move {
+                let start = tokio::time::Instant::now() + start_jitter(interval);
+                let mut ticker = tokio::time::interval_at(start, interval);
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                loop {
+                    tokio::select! {
+                        _ = ticker.tick() => {}
+                        _ = shutdown.wait_for(|stop| *stop) => break,
+                    }
+                    run_once(name, &job, &metrics).await;
+                }
+                tracing::debug!(job = name, "Background job stopped");
+            }
+        });
+
+        if let Ok(mut jobs) = self.jobs.lock() {
+            jobs.push(metrics);
+        }
+        if let Ok(mut handles) = self.handles.lock() {
+            handles.push(handle);
+        }
+    }
+
+    /// Counters for every registered job, in registration order
+    #[must_use]
+    pub fn metrics(&self) -> Vec<JobMetrics> {
+        self.jobs
+            .lock()
+            .map(|jobs| jobs.iter().filter_map(|m| m.lock().ok().map(|m| m.clone())).collect())
+            .unwrap_or_default()
+    }
+
+    /// Stop all jobs, waiting up to `grace` for in-flight runs to finish.
+    ///
+    /// Jobs still running after `grace` are aborted.
+    pub async fn shutdown(&self, grace: Duration) {
+        self.shutdown.send_replace(true);
+
+        let handles = self
+            .handles
+            .lock()
+            .map(|mut h| std::mem::take(&mut *h))
+            .unwrap_or_default();
+        let aborts: Vec<_> = handles.iter().map(JoinHandle::abort_handle).collect();
+
+        if tokio::time::timeout(grace, futures_util::future::join_all(handles))
+            .await
+            .is_err()
+        {
+            tracing::warn!(grace_secs = grace.as_secs(), "Aborting background jobs after shutdown grace period");
+            aborts.iter().for_each(tokio::task::AbortHandle::abort);
+        }
+    }
+}
+
+/// Run a job once in its own task and record the outcome
+async fn run_once<F, Fut, E>(name: &'static str, job: &F, metrics: &Mutex<JobMetrics>)
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: Display + Send + 'static,
+{
+    let started = Instant::now();
+    // Spawned so a panic unwinds this run only
+    let outcome = tokio::spawn(job()).await;
+    let elapsed = started.elapsed();
+
+    let Ok(mut m) = metrics.lock() else {
+        return;
+    };
+    m.runs += 1;
+    m.last_duration = Some(elapsed);
+    match outcome {
+        Ok(Ok(())) => m.last_success_at = Some(Utc::now()),
+        Ok(Err(e)) => {
+            m.failures += 1;
+            tracing::warn!(job = name, error = %e, "Background job failed");
+        }
+        Err(e) => {
+            m.panics += 1;
+            tracing::error!(job = name, error = %e, "Background job panicked");
+        }
+    }
+}
+
+/// Random delay before a job's first run, up to one interval
+fn start_jitter(interval: Duration) -> Duration {
+    let mut bytes = [0u8; 8];
+    if SystemRandom::new().fill(&mut bytes).is_err() {
+        return Duration::ZERO;
+    }
+    let fraction = u64::from_le_bytes(bytes) as f64 / u64::MAX as f64;
+    interval.min(MAX_START_JITTER).mul_f64(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[tokio::test]
+    async fn test_panicking_run_keeps_schedule() {
+        let runner = TaskRunner::new();
+        let calls = Arc::new(AtomicU64::new(0));
+        runner.register("flaky", Duration::from_millis(10), {
+            let calls = calls.clone();
+            move || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match call {
+                        0 => panic!("first run panics"),
+                        1 => Err("second run fails"),
+                        _ => Ok(()),
+                    }
+                }
+            }
+        });
+
+        // Panic reporting can be slow, so wait for a success rather than a fixed time
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while runner.metrics()[0].last_success_at.is_none() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        runner.shutdown(Duration::from_secs(1)).await;
+
+        let metrics = &runner.metrics()[0];
+        assert_eq!(metrics.name, "flaky");
+        assert_eq!((metrics.panics, metrics.failures), (1, 1));
+        assert!(metrics.runs >= 3);
+        assert!(metrics.last_success_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_jobs() {
+        let runner = TaskRunner::new();
+        let calls = Arc::new(AtomicU64::new(0));
+        runner.register("counter", Duration::from_millis(5), {
+            let calls = calls.clone();
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Ok::<_, String>(()) }
+            }
+        });
+        runner.register("disabled", Duration::ZERO, || async { Ok::<_, String>(()) });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        runner.shutdown(Duration::from_secs(1)).await;
+        let stopped_at = calls.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        assert!(stopped_at > 0);
+        assert_eq!(calls.load(Ordering::SeqCst), stopped_at);
+        assert_eq!(runner.metrics().len(), 1);
+    }
+}
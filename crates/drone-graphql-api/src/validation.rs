@@ -0,0 +1,378 @@
+//! # Input Validation
+//!
+//! Range checks for the coordinate, percentage and distance fields of
+//! GraphQL inputs. Every out-of-range field is reported with its path from
+//! the argument (e.g. `input.target.coordinates.latitude`) so clients can
+//! point at it, and resolvers check before writing anything, so invalid
+//! values never reach the repositories.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::error::{ApiError, ApiResult};
+use crate::schema::*;
+
+/// Highest altitude accepted, in meters above sea level
+pub const MAX_ALTITUDE_M: f64 = 30_000.0;
+
+/// Fastest speed accepted, in meters per second
+pub const MAX_SPEED_MPS: f64 = 400.0;
+
+/// Longest range or distance accepted, in kilometers
+pub const MAX_RANGE_KM: f64 = 2_000.0;
+
+/// One rejected input field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldViolation {
+    /// Dotted path from the argument; list items by index (`inputs.2.fuelPct`)
+    pub path: String,
+    /// What is wrong with the value
+    pub message: String,
+}
+
+impl fmt::Display for FieldViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.path, self.message)
+    }
+}
+
+/// An input whose numeric fields can be range-checked
+pub trait Validate {
+    /// Record every out-of-range field
+    fn validate(&self, v: &mut Validator);
+}
+
+/// Walks an input, collecting violations under the current field path
+#[derive(Debug, Default)]
+pub struct Validator {
+    path: Vec<String>,
+    violations: Vec<FieldViolation>,
+}
+
+impl Validator {
+    /// Require `value` to be a finite number in `min..=max`
+    pub fn range(&mut self, field: &str, value: f64, min: f64, max: f64) {
+        if !(value.is_finite() && (min..=max).contains(&value)) {
+            self.reject(field, format!("must be between {min} and {max}, got {value}"));
+        }
+    }
+
+    /// [`Validator::range`] for an optional field
+    pub fn optional_range(&mut self, field: &str, value: Option<f64>, min: f64, max: f64) {
+        if let Some(value) = value {
+            self.range(field, value, min, max);
+        }
+    }
+
+    /// Require an optional field, when set, to be a finite number
+    pub fn finite(&mut self, field: &str, value: Option<f64>) {
+        if let Some(value) = value.filter(|v| !v.is_finite()) {
+            self.reject(field, format!("must be a finite number, got {value}"));
+        }
+    }
+
+    /// Validate a nested input under `field`
+    pub fn nested<T: Validate>(&mut self, field: &str, value: &T) {
+        self.path.push(field.to_string());
+        value.validate(self);
+        self.path.pop();
+    }
+
+    /// [`Validator::nested`] for an optional field
+    pub fn optional<T: Validate>(&mut self, field: &str, value: Option<&T>) {
+        if let Some(value) = value {
+            self.nested(field, value);
+        }
+    }
+
+    /// Validate each item of a list under `field.<index>`
+    pub fn each<T: Validate>(&mut self, field: &str, values: &[T]) {
+        self.path.push(field.to_string());
+        for (index, value) in values.iter().enumerate() {
+            self.nested(&index.to_string(), value);
+        }
+        self.path.pop();
+    }
+
+    fn reject(&mut self, field: &str, message: String) {
+        let mut path = self.path.join(".");
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(field);
+        self.violations.push(FieldViolation { path, message });
+    }
+
+    fn finish(self) -> ApiResult<()> {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::InvalidFields(self.violations))
+        }
+    }
+}
+
+/// Validate the argument `name`, failing with every violation found
+pub fn check<T: Validate>(name: &str, input: &T) -> ApiResult<()> {
+    let mut v = Validator::default();
+    v.nested(name, input);
+    v.finish()
+}
+
+/// Validate every item of the list argument `name` before any is used
+pub fn check_each<T: Validate>(name: &str, inputs: &[T]) -> ApiResult<()> {
+    let mut v = Validator::default();
+    v.each(name, inputs);
+    v.finish()
+}
+
+// =============================================================================
+// INPUT RULES
+// =============================================================================
+
+impl Validate for CoordinatesInput {
+    fn validate(&self, v: &mut Validator) {
+        v.range("latitude", self.latitude, -90.0, 90.0);
+        v.range("longitude", self.longitude, -180.0, 180.0);
+        v.range("altitudeM", self.altitude_m, 0.0, MAX_ALTITUDE_M);
+        v.range("headingDeg", self.heading_deg, 0.0, 360.0);
+        v.range("speedMps", self.speed_mps, 0.0, MAX_SPEED_MPS);
+    }
+}
+
+impl Validate for RecordEngagementInput {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_range("rangeKm", self.range_km, 0.0, MAX_RANGE_KM);
+        v.optional_range("predictedPk", self.predicted_pk, 0.0, 1.0);
+    }
+}
+
+impl Validate for CreateEngagementInput {
+    fn validate(&self, v: &mut Validator) {
+        v.nested("target", &self.target);
+        v.nested("shooterPosition", &self.shooter_position);
+        v.optional_range("predictedPk", self.predicted_pk, 0.0, 1.0);
+    }
+}
+
+impl Validate for TargetInput {
+    fn validate(&self, v: &mut Validator) {
+        v.nested("coordinates", &self.coordinates);
+        v.range("confidence", self.confidence, 0.0, 1.0);
+    }
+}
+
+impl Validate for RequestEngagementAuthorizationInput {
+    fn validate(&self, v: &mut Validator) {
+        v.nested("target", &self.target);
+    }
+}
+
+impl Validate for ReportTargetInput {
+    fn validate(&self, v: &mut Validator) {
+        v.nested("target", &self.target);
+    }
+}
+
+impl Validate for UpdateDroneStateInput {
+    fn validate(&self, v: &mut Validator) {
+        v.optional("position", self.position.as_ref());
+        v.optional_range("fuelPct", self.fuel_pct, 0.0, 100.0);
+    }
+}
+
+impl Validate for CreateTelemetryInput {
+    fn validate(&self, v: &mut Validator) {
+        v.nested("position", &self.position);
+        v.range("fuelPct", self.fuel_pct, 0.0, 100.0);
+        v.optional("homePosition", self.home_position.as_ref());
+        v.range("distanceToNextKm", self.distance_to_next_km, 0.0, MAX_RANGE_KM);
+        v.range("velocityMps", self.velocity_mps, 0.0, MAX_SPEED_MPS);
+        v.finite("engineTempC", self.engine_temp_c);
+        v.range("meshConnectivity", self.mesh_connectivity, 0.0, 1.0);
+    }
+}
+
+impl Validate for GeoPointInput {
+    fn validate(&self, v: &mut Validator) {
+        v.range("latitude", self.latitude, -90.0, 90.0);
+        v.range("longitude", self.longitude, -180.0, 180.0);
+    }
+}
+
+impl Validate for CreateNoStrikeZoneInput {
+    fn validate(&self, v: &mut Validator) {
+        v.each("vertices", &self.vertices);
+    }
+}
+
+impl Validate for UpdateNoStrikeZoneInput {
+    fn validate(&self, v: &mut Validator) {
+        if let Some(vertices) = &self.vertices {
+            v.each("vertices", vertices);
+        }
+    }
+}
+
+impl Validate for CreateConvoyInput {
+    fn validate(&self, v: &mut Validator) {
+        v.nested("aorCenter", &self.aor_center);
+        v.range("aorRadiusKm", self.aor_radius_km, 0.0, MAX_RANGE_KM);
+    }
+}
+
+impl Validate for ConvoyTemplateOverridesInput {
+    fn validate(&self, v: &mut Validator) {
+        v.optional("aorCenter", self.aor_center.as_ref());
+        v.optional_range("aorRadiusKm", self.aor_radius_km, 0.0, MAX_RANGE_KM);
+    }
+}
+
+impl Validate for CreateWaypointInput {
+    fn validate(&self, v: &mut Validator) {
+        v.nested("coordinates", &self.coordinates);
+    }
+}
+
+impl Validate for CreateWaypointsInput {
+    fn validate(&self, v: &mut Validator) {
+        v.each("waypoints", &self.waypoints);
+    }
+}
+
+impl Validate for WaypointDefinition {
+    fn validate(&self, v: &mut Validator) {
+        v.nested("coordinates", &self.coordinates);
+    }
+}
+
+impl Validate for LeaderboardFilter {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_range("minAccuracy", self.min_accuracy, 0.0, 100.0);
+    }
+}
+
+impl Validate for DroneFilter {
+    fn validate(&self, v: &mut Validator) {
+        v.optional_range("minFuelPct", self.min_fuel_pct, 0.0, 100.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn coordinates(latitude: f64, longitude: f64, altitude_m: f64) -> CoordinatesInput {
+        CoordinatesInput {
+            latitude,
+            longitude,
+            altitude_m,
+            heading_deg: 0.0,
+            speed_mps: 0.0,
+        }
+    }
+
+    fn target(coordinates: CoordinatesInput) -> TargetInput {
+        TargetInput {
+            target_type: TargetType::Vehicle,
+            coordinates,
+            confidence: 0.9,
+            threat_level: None,
+        }
+    }
+
+    #[test]
+    fn test_reports_every_violation_with_its_path() {
+        let input = ReportTargetInput {
+            convoy_id: "c1".to_string(),
+            target_id: None,
+            target: target(coordinates(4000.0, 65.0, -10.0)),
+            reported_by: None,
+        };
+
+        let err = check("input", &input).unwrap_err();
+        assert_eq!(err.error_code(), "INVALID_INPUT");
+        let ApiError::InvalidFields(violations) = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        let paths: Vec<_> = violations.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["input.target.coordinates.latitude", "input.target.coordinates.altitudeM"]
+        );
+        assert_eq!(violations[0].message, "must be between -90 and 90, got 4000");
+    }
+
+    #[test]
+    fn test_check_each_indexes_list_items() {
+        let waypoint = |latitude| WaypointDefinition {
+            sequence_number: 1,
+            name: "WP".to_string(),
+            waypoint_type: WaypointType::Nav,
+            coordinates: coordinates(latitude, 65.0, 1500.0),
+        };
+        let input = CreateWaypointsInput {
+            drone_id: "d1".to_string(),
+            waypoints: vec![waypoint(31.0), waypoint(f64::NAN)],
+        };
+
+        let Err(ApiError::InvalidFields(violations)) = check("input", &input) else {
+            panic!("expected a violation");
+        };
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "input.waypoints.1.coordinates.latitude");
+        assert!(check("input", &CreateWaypointsInput { waypoints: vec![waypoint(31.0)], ..input }).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_coordinates_pass_only_when_in_range(
+            latitude in prop::num::f64::ANY,
+            longitude in prop::num::f64::ANY,
+            altitude_m in prop::num::f64::ANY,
+        ) {
+            let input = coordinates(latitude, longitude, altitude_m);
+            let in_range = (-90.0..=90.0).contains(&latitude)
+                && (-180.0..=180.0).contains(&longitude)
+                && (0.0..=MAX_ALTITUDE_M).contains(&altitude_m);
+
+            prop_assert_eq!(check("input", &input).is_ok(), in_range);
+        }
+
+        #[test]
+        fn prop_accepted_telemetry_is_in_range(
+            latitude in -200.0..200.0f64,
+            longitude in -400.0..400.0f64,
+            altitude_m in -1000.0..40_000.0f64,
+            fuel_pct in -50.0..150.0f64,
+            mesh_connectivity in -1.0..2.0f64,
+        ) {
+            let input = CreateTelemetryInput {
+                drone_id: "d1".to_string(),
+                convoy_id: None,
+                position: coordinates(latitude, longitude, altitude_m),
+                fuel_pct,
+                platform_type: None,
+                home_position: None,
+                current_waypoint: 1,
+                distance_to_next_km: 0.0,
+                velocity_mps: 0.0,
+                engine_temp_c: None,
+                mesh_connectivity,
+                sensors: None,
+                schema_version: None,
+                extensions: None,
+            };
+
+            if check_each("inputs", std::slice::from_ref(&input)).is_ok() {
+                prop_assert!(input.position.latitude.abs() <= 90.0);
+                prop_assert!(input.position.longitude.abs() <= 180.0);
+                prop_assert!(input.position.altitude_m >= 0.0);
+                prop_assert!((0.0..=100.0).contains(&input.fuel_pct));
+                prop_assert!((0.0..=1.0).contains(&input.mesh_connectivity));
+            }
+        }
+    }
+}
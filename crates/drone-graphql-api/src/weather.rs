@@ -0,0 +1,340 @@
+//! # Weather Providers
+//!
+//! Pluggable ambient-conditions lookup used to adjust ETAs, annotate
+//! telemetry snapshots and raise low-visibility alerts. Lookups sit on the
+//! telemetry ingest path, so the HTTP provider answers from a cache and
+//! refreshes it in the background rather than waiting on the network.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::error::{ApiError, ApiResult};
+use drone_domain::{Coordinates, Km, Meters, Mps};
+
+/// Default Open-Meteo forecast endpoint
+pub const OPEN_METEO_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// Grid size (degrees) used to bucket lookups into AOR cells
+const AOR_GRID_DEG: f64 = 0.25;
+
+/// AOR cell index (latitude, longitude) on the [`AOR_GRID_DEG`] grid
+type Cell = (i64, i64);
+
+/// Minimum ground speed used for ETA estimates (m/s)
+const MIN_GROUND_SPEED_MPS: f64 = 1.0;
+
+/// Ambient weather conditions at a location
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Conditions {
+    pub wind_speed_mps: f64,
+    /// Direction the wind is blowing FROM (meteorological convention)
+    pub wind_direction_deg: f64,
+    pub visibility_km: f64,
+    pub temperature_c: f64,
+    pub observed_at: DateTime<Utc>,
+}
+
+impl Default for Conditions {
+    fn default() -> Self {
+        Self {
+            wind_speed_mps: 0.0,
+            wind_direction_deg: 0.0,
+            visibility_km: 10.0,
+            temperature_c: 15.0,
+            observed_at: Utc::now(),
+        }
+    }
+}
+
+impl Conditions {
+    /// Headwind component for an aircraft on `heading_deg` (negative = tailwind)
+    #[must_use]
+    pub fn headwind_mps(&self, heading_deg: f64) -> f64 {
+        self.wind_speed_mps * (self.wind_direction_deg - heading_deg).to_radians().cos()
+    }
+}
+
+/// Estimate seconds to cover `distance` at `airspeed` on `heading_deg`,
+/// correcting for the headwind component when conditions are known.
+#[must_use]
+pub fn adjusted_eta_secs(
+    distance: Km,
+    airspeed: Mps,
+    heading_deg: f64,
+    conditions: Option<&Conditions>,
+) -> Option<f64> {
+    if distance <= Km::ZERO || airspeed <= Mps::ZERO {
+        return None;
+    }
+
+    let headwind = conditions.map_or(0.0, |c| c.headwind_mps(heading_deg));
+    let ground_speed = (f64::from(airspeed.0) - headwind).max(MIN_GROUND_SPEED_MPS);
+
+    Some(Meters::from(distance).0 / ground_speed)
+}
+
+/// Airspeed to project an ETA with: the mean of the recent window's
+/// speeds and the current one, so a turn or gust in a single report doesn't
+/// swing the ETA
+#[must_use]
+pub fn smoothed_airspeed(recent: &[Mps], current: Mps) -> Mps {
+    let total: Mps = recent.iter().copied().sum::<Mps>() + current;
+    total / (recent.len() + 1) as f32
+}
+
+/// Source of ambient conditions
+#[async_trait]
+pub trait WeatherProvider: Send + Sync {
+    /// Provider name for logging
+    fn name(&self) -> &'static str;
+
+    /// Current conditions at a location
+    async fn conditions(&self, location: &Coordinates) -> ApiResult<Conditions>;
+}
+
+/// Shared weather provider handle
+pub type SharedWeatherProvider = Arc<dyn WeatherProvider>;
+
+// =============================================================================
+// STATIC PROVIDER
+// =============================================================================
+
+/// Fixed conditions from configuration (exercises, offline operation)
+#[derive(Debug, Clone, Default)]
+pub struct StaticWeatherProvider {
+    conditions: Conditions,
+}
+
+impl StaticWeatherProvider {
+    pub fn new(conditions: Conditions) -> Self {
+        Self { conditions }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for StaticWeatherProvider {
+    fn name(&self) -> &'static str {
+        "static"
+    }
+
+    async fn conditions(&self, _location: &Coordinates) -> ApiResult<Conditions> {
+        Ok(Conditions {
+            observed_at: Utc::now(),
+            ..self.conditions
+        })
+    }
+}
+
+// =============================================================================
+// OPEN-METEO PROVIDER
+// =============================================================================
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoResponse {
+    current: OpenMeteoCurrent,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoCurrent {
+    wind_speed_10m: f64,
+    wind_direction_10m: f64,
+    /// Visibility in meters
+    visibility: f64,
+    temperature_2m: f64,
+}
+
+/// Open-Meteo HTTP client serving per-AOR-cell conditions from a cache.
+///
+/// A lookup never waits on the network: a cell older than the TTL is
+/// served stale while a background task refreshes it, and a cell never
+/// fetched fails until its first refresh lands.
+pub struct OpenMeteoProvider {
+    client: reqwest::Client,
+    base_url: Arc<str>,
+    ttl: Duration,
+    cache: Arc<RwLock<HashMap<Cell, (Instant, Conditions)>>>,
+    /// Cells with a refresh in flight
+    refreshing: Arc<Mutex<HashSet<Cell>>>,
+}
+
+impl OpenMeteoProvider {
+    /// Create a provider against `base_url`, refreshing each AOR cell after
+    /// `ttl`; requests give up after `connect_timeout` to connect and
+    /// `request_timeout` overall.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the HTTP client cannot be built.
+    pub fn new(
+        base_url: impl Into<String>,
+        ttl: Duration,
+        connect_timeout: Duration,
+        request_timeout: Duration,
+    ) -> reqwest::Result<Self> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(connect_timeout)
+            .timeout(request_timeout)
+            .build()?;
+        Ok(Self {
+            client,
+            base_url: base_url.into().into(),
+            ttl,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+            refreshing: Arc::new(Mutex::new(HashSet::new())),
+        })
+    }
+
+    fn cell(location: &Coordinates) -> Cell {
+        (
+            (location.latitude / AOR_GRID_DEG).floor() as i64,
+            (location.longitude / AOR_GRID_DEG).floor() as i64,
+        )
+    }
+
+    /// Start a background fetch for `cell` unless one is already running
+    fn refresh(&self, cell: Cell, location: Coordinates) {
+        let Ok(mut refreshing) = self.refreshing.lock() else {
+            return;
+        };
+        if !refreshing.insert(cell) {
+            return;
+        }
+        drop(refreshing);
+
+        let client = self.client.clone();
+        let base_url = self.base_url.clone();
+        let cache = self.cache.clone();
+        let refreshing = self.refreshing.clone();
+        tokio::spawn(async move {
+            match fetch(&client, &base_url, &location).await {
+                Ok(conditions) => {
+                    if let Ok(mut cache) = cache.write() {
+                        cache.insert(cell, (Instant::now(), conditions));
+                    }
+                }
+                Err(e) => tracing::warn!(?cell, error = %e, "Weather refresh failed"),
+            }
+            if let Ok(mut refreshing) = refreshing.lock() {
+                refreshing.remove(&cell);
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenMeteoProvider {
+    fn name(&self) -> &'static str {
+        "open-meteo"
+    }
+
+    async fn conditions(&self, location: &Coordinates) -> ApiResult<Conditions> {
+        let cell = Self::cell(location);
+        let cached = self.cache.read().ok().and_then(|cache| cache.get(&cell).copied());
+
+        match cached {
+            Some((fetched_at, conditions)) => {
+                if fetched_at.elapsed() >= self.ttl {
+                    self.refresh(cell, *location);
+                }
+                Ok(conditions)
+            }
+            None => {
+                self.refresh(cell, *location);
+                Err(ApiError::Internal("Weather for this area is still loading".to_string()))
+            }
+        }
+    }
+}
+
+/// Fetch current conditions at a location from Open-Meteo
+async fn fetch(
+    client: &reqwest::Client,
+    base_url: &str,
+    location: &Coordinates,
+) -> ApiResult<Conditions> {
+    let response: OpenMeteoResponse = client
+        .get(base_url)
+        .query(&[
+            ("latitude", location.latitude.to_string()),
+            ("longitude", location.longitude.to_string()),
+            (
+                "current",
+                "wind_speed_10m,wind_direction_10m,visibility,temperature_2m".to_string(),
+            ),
+            ("wind_speed_unit", "ms".to_string()),
+        ])
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| ApiError::Internal(format!("Weather lookup failed: {e}")))?
+        .json()
+        .await
+        .map_err(|e| ApiError::Internal(format!("Weather response invalid: {e}")))?;
+
+    Ok(Conditions {
+        wind_speed_mps: response.current.wind_speed_10m,
+        wind_direction_deg: response.current.wind_direction_10m,
+        visibility_km: response.current.visibility / 1000.0,
+        temperature_c: response.current.temperature_2m,
+        observed_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headwind_slows_eta() {
+        let calm = adjusted_eta_secs(Km(60.0), Mps(60.0), 90.0, None).unwrap();
+        assert!((calm - 1000.0).abs() < 1e-9);
+
+        // Wind from the east, flying east: 10 m/s headwind
+        let headwind = Conditions {
+            wind_speed_mps: 10.0,
+            wind_direction_deg: 90.0,
+            ..Conditions::default()
+        };
+        let slowed = adjusted_eta_secs(Km(60.0), Mps(60.0), 90.0, Some(&headwind)).unwrap();
+        assert!((slowed - 1200.0).abs() < 1e-9);
+
+        // Same wind flying west is a tailwind
+        let boosted = adjusted_eta_secs(Km(60.0), Mps(60.0), 270.0, Some(&headwind)).unwrap();
+        assert!(boosted < calm);
+    }
+
+    #[test]
+    fn test_smoothed_airspeed_averages_recent_window() {
+        assert_eq!(smoothed_airspeed(&[], Mps(60.0)), Mps(60.0));
+        assert_eq!(smoothed_airspeed(&[Mps(50.0), Mps(70.0)], Mps(0.0)), Mps(40.0));
+    }
+
+    #[test]
+    fn test_eta_requires_distance_and_speed() {
+        assert!(adjusted_eta_secs(Km(0.0), Mps(60.0), 0.0, None).is_none());
+        assert!(adjusted_eta_secs(Km(10.0), Mps(0.0), 0.0, None).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_open_meteo_serves_stale_cells_without_waiting() {
+        // Nothing listens on the discard port, so every refresh fails
+        let timeout = Duration::from_millis(200);
+        let provider =
+            OpenMeteoProvider::new("http://127.0.0.1:9", Duration::ZERO, timeout, timeout).unwrap();
+        let location = Coordinates::new(31.6, 65.7, 1000.0);
+
+        assert!(provider.conditions(&location).await.is_err());
+
+        let stale = Conditions {
+            visibility_km: 2.5,
+            ..Conditions::default()
+        };
+        let cell = OpenMeteoProvider::cell(&location);
+        provider.cache.write().unwrap().insert(cell, (Instant::now(), stale));
+        assert_eq!(provider.conditions(&location).await.unwrap(), stale);
+    }
+}
@@ -0,0 +1,400 @@
+//! # WebSocket Subscriptions
+//!
+//! `/graphql/ws` handler with server keep-alive pings, idle-connection
+//! timeouts, global/per-IP connection limits and `connection_init` auth.
+//! A `connection_init` without a valid token closes the socket with 4401
+//! when no token was sent and 4403 when the token is not recognised.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_graphql::Data;
+use async_graphql_axum::{GraphQLProtocol, GraphQLWebSocket};
+use axum::extract::ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{ConnectInfo, State};
+use axum::Extension;
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response};
+use futures_util::{SinkExt, StreamExt};
+use tokio::sync::mpsc;
+use tracing::Instrument;
+
+use crate::auth::{self, Claims, RoleTokens};
+use crate::error::ApiError;
+use crate::request_id::RequestId;
+use crate::{ApiSchema, AppState};
+
+/// Close code sent when a connection has been idle too long
+const CLOSE_IDLE_TIMEOUT: u16 = 4408;
+
+/// Close code sent when `connection_init` carries no token
+pub const CLOSE_UNAUTHORIZED: u16 = 4401;
+
+/// Close code sent when `connection_init` carries an unknown token
+pub const CLOSE_FORBIDDEN: u16 = 4403;
+
+/// How long a finished connection may spend flushing its last frames
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Outbound frames buffered per connection
+const OUTBOUND_CAPACITY: usize = 64;
+
+/// Keep-alive and admission limits for subscription connections
+#[derive(Debug, Clone, Copy)]
+pub struct WsLimits {
+    /// Interval between server pings
+    pub ping_interval: Duration,
+    /// Close connections with no inbound frames for this long
+    pub idle_timeout: Duration,
+    /// Maximum concurrent connections across all clients
+    pub max_connections: usize,
+    /// Maximum concurrent connections from a single IP
+    pub max_connections_per_ip: usize,
+}
+
+impl Default for WsLimits {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(15),
+            idle_timeout: Duration::from_secs(60),
+            max_connections: 1000,
+            max_connections_per_ip: 20,
+        }
+    }
+}
+
+/// Live subscription connection counts
+#[derive(Debug)]
+pub struct ConnectionTracker {
+    limits: WsLimits,
+    total: AtomicUsize,
+    per_ip: Mutex<HashMap<IpAddr, usize>>,
+}
+
+impl ConnectionTracker {
+    #[must_use]
+    pub fn new(limits: WsLimits) -> Self {
+        Self {
+            limits,
+            total: AtomicUsize::new(0),
+            per_ip: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Configured limits
+    #[must_use]
+    pub fn limits(&self) -> WsLimits {
+        self.limits
+    }
+
+    /// Connections currently open
+    #[must_use]
+    pub fn active(&self) -> usize {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    /// Open connections per client IP
+    #[must_use]
+    pub fn by_ip(&self) -> Vec<(IpAddr, usize)> {
+        let mut counts: Vec<_> = self
+            .per_ip
+            .lock()
+            .map(|m| m.iter().map(|(ip, n)| (*ip, *n)).collect())
+            .unwrap_or_default();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Admit a connection from `ip`, or `None` if a limit is reached.
+    ///
+    /// The slot is released when the returned permit is dropped.
+    #[must_use]
+    pub fn try_acquire(self: &Arc<Self>, ip: IpAddr) -> Option<ConnectionPermit> {
+        let mut per_ip = self.per_ip.lock().ok()?;
+        let from_ip = per_ip.get(&ip).copied().unwrap_or(0);
+        if self.active() >= self.limits.max_connections
+            || from_ip >= self.limits.max_connections_per_ip
+        {
+            return None;
+        }
+
+        per_ip.insert(ip, from_ip + 1);
+        self.total.fetch_add(1, Ordering::Relaxed);
+
+        Some(ConnectionPermit {
+            tracker: Arc::clone(self),
+            ip,
+        })
+    }
+
+    fn release(&self, ip: IpAddr) {
+        if let Ok(mut per_ip) = self.per_ip.lock() {
+            if let Some(n) = per_ip.get_mut(&ip) {
+                *n -= 1;
+                if *n == 0 {
+                    per_ip.remove(&ip);
+                }
+            }
+        }
+        self.total.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Admission slot held for the lifetime of a connection
+#[derive(Debug)]
+pub struct ConnectionPermit {
+    tracker: Arc<ConnectionTracker>,
+    ip: IpAddr,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        self.tracker.release(self.ip);
+    }
+}
+
+/// Resolves per-connection claims when the client sends `connection_init`
+#[derive(Debug, Clone)]
+pub struct ConnectionAuth {
+    tokens: Arc<RoleTokens>,
+    header_claims: Option<Claims>,
+    required: bool,
+}
+
+impl ConnectionAuth {
+    /// `header_claims` come from the upgrade request and are used when the
+    /// init payload carries no token
+    #[must_use]
+    pub fn new(tokens: Arc<RoleTokens>, header_claims: Option<Claims>, required: bool) -> Self {
+        Self {
+            tokens,
+            header_claims,
+            required,
+        }
+    }
+
+    /// Build connection data from the init payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns the close frame to send when authentication is required and
+    /// neither the payload nor the upgrade request carried a known token.
+    pub fn resolve(&self, payload: &serde_json::Value) -> Result<Data, CloseFrame> {
+        let claims = match auth::claims_from_init_payload(payload, &self.tokens)
+            .or_else(|| self.header_claims.clone())
+        {
+            Some(claims) => claims,
+            None if self.required => {
+                return Err(match auth::token_from_init_payload(payload) {
+                    Some(_) => CloseFrame {
+                        code: CLOSE_FORBIDDEN,
+                        reason: "unknown token".into(),
+                    },
+                    None => CloseFrame {
+                        code: CLOSE_UNAUTHORIZED,
+                        reason: "valid token required".into(),
+                    },
+                });
+            }
+            None => Claims::anonymous(auth::Role::Viewer),
+        };
+
+        let mut data = Data::default();
+        data.insert(claims.role);
+        data.insert(claims);
+        Ok(data)
+    }
+}
+
+/// GraphQL subscription endpoint
+///
+/// Rejects the upgrade with 429 when the global or per-IP limit is hit.
+pub async fn graphql_ws(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let tracker = state.ctx.ws_connections.clone();
+    let Some(permit) = tracker.try_acquire(addr.ip()) else {
+        tracing::warn!(client = %addr.ip(), active = tracker.active(), "Subscription connection rejected");
+        return ApiError::RateLimited {
+            retry_after_secs: tracker.limits().ping_interval.as_secs().max(1),
+        }
+        .into_response();
+    };
+
+    let tokens = state.ctx.role_tokens.clone();
+    let header_claims = auth::claims_from_headers(&headers, &tokens);
+    let conn_auth = ConnectionAuth::new(tokens, header_claims, state.ctx.ws_require_auth);
+    let limits = tracker.limits();
+    // The connection outlives the upgrade request and its span
+    let span = tracing::info_span!("subscription_connection", request_id = %request_id);
+
+    upgrade
+        .protocols(async_graphql::http::ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |socket| {
+            async move {
+                tracing::debug!(client = %addr.ip(), "Subscription connection opened");
+                serve_connection(socket, state.schema, protocol, conn_auth, request_id, limits)
+                    .await;
+                drop(permit);
+                tracing::debug!(client = %addr.ip(), "Subscription connection closed");
+            }
+            .instrument(span)
+        })
+}
+
+/// Drive one connection until the client leaves or goes idle
+async fn serve_connection(
+    socket: WebSocket,
+    schema: ApiSchema,
+    protocol: GraphQLProtocol,
+    conn_auth: ConnectionAuth,
+    request_id: RequestId,
+    limits: WsLimits,
+) {
+    let (mut sink, stream) = socket.split();
+    let started = Instant::now();
+    let last_seen = Arc::new(AtomicU64::new(0));
+
+    // Any inbound frame, pongs included, counts as activity
+    let seen = last_seen.clone();
+    let stream = stream.inspect(move |_| {
+        let elapsed = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        seen.store(elapsed, Ordering::Relaxed);
+    });
+
+    // GraphQL output and keep-alive pings share the sink through a channel
+    let (tx, mut rx) = mpsc::channel::<Message>(OUTBOUND_CAPACITY);
+    let close = tx.clone();
+    let outbound = futures_util::sink::unfold(tx, |tx, msg: Message| async move {
+        tx.send(msg).await.map(|()| tx)
+    });
+
+    let graphql = GraphQLWebSocket::new_with_pair(outbound, stream, schema, protocol)
+        .on_connection_init(move |payload| async move {
+            match conn_auth.resolve(&payload) {
+                Ok(mut data) => {
+                    data.insert(request_id);
+                    Ok(data)
+                }
+                Err(frame) => {
+                    tracing::warn!(code = frame.code, "Subscription connection_init rejected");
+                    let reason = frame.reason.to_string();
+                    let _ = close.send(Message::Close(Some(frame))).await;
+                    Err(async_graphql::Error::new(reason))
+                }
+            }
+        })
+        .serve();
+
+    let writer = async move {
+        let mut ping = tokio::time::interval(limits.ping_interval);
+        ping.tick().await;
+        loop {
+            tokio::select! {
+                msg = rx.recv() => {
+                    let Some(msg) = msg else { break };
+                    let closing = matches!(msg, Message::Close(_));
+                    if sink.send(msg).await.is_err() || closing {
+                        break;
+                    }
+                }
+                _ = ping.tick() => {
+                    let idle_ms = u64::try_from(started.elapsed().as_millis())
+                        .unwrap_or(u64::MAX)
+                        .saturating_sub(last_seen.load(Ordering::Relaxed));
+                    if Duration::from_millis(idle_ms) >= limits.idle_timeout {
+                        let _ = sink
+                            .send(Message::Close(Some(CloseFrame {
+                                code: CLOSE_IDLE_TIMEOUT,
+                                reason: "idle timeout".into(),
+                            })))
+                            .await;
+                        break;
+                    }
+                    if sink.send(Message::Ping(Vec::new().into())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    };
+
+    tokio::pin!(writer);
+    tokio::select! {
+        () = graphql => {
+            // Let queued frames, such as an auth rejection, reach the client
+            let _ = tokio::time::timeout(FLUSH_TIMEOUT, &mut writer).await;
+        }
+        () = &mut writer => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(max_connections: usize, max_connections_per_ip: usize) -> Arc<ConnectionTracker> {
+        Arc::new(ConnectionTracker::new(WsLimits {
+            max_connections,
+            max_connections_per_ip,
+            ..WsLimits::default()
+        }))
+    }
+
+    #[test]
+    fn test_per_ip_limit() {
+        let tracker = tracker(10, 2);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+
+        let a = tracker.try_acquire(ip).unwrap();
+        let _b = tracker.try_acquire(ip).unwrap();
+        assert!(tracker.try_acquire(ip).is_none());
+        assert!(tracker.try_acquire("10.0.0.2".parse().unwrap()).is_some());
+
+        drop(a);
+        assert!(tracker.try_acquire(ip).is_some());
+    }
+
+    #[test]
+    fn test_connection_init_auth() {
+        let tokens = Arc::new(auth::parse_role_tokens("abc:OPERATOR"));
+        let payload = serde_json::json!({ "Authorization": "Bearer abc" });
+
+        let required = ConnectionAuth::new(tokens.clone(), None, true);
+        assert!(required.resolve(&payload).is_ok());
+        let missing = required.resolve(&serde_json::json!({})).unwrap_err();
+        assert_eq!(missing.code, CLOSE_UNAUTHORIZED);
+        let unknown = required.resolve(&serde_json::json!({ "token": "nope" })).unwrap_err();
+        assert_eq!(unknown.code, CLOSE_FORBIDDEN);
+
+        // Upgrade-request header is the fallback
+        let with_header = ConnectionAuth::new(tokens.clone(), Some(Claims::new(auth::Role::Analyst)), true);
+        assert!(with_header.resolve(&serde_json::json!({})).is_ok());
+
+        // The development opt-out admits anonymous viewers
+        let optional = ConnectionAuth::new(tokens, None, false);
+        assert!(optional.resolve(&serde_json::Value::Null).is_ok());
+    }
+
+    #[test]
+    fn test_global_limit_and_release() {
+        let tracker = tracker(2, 5);
+        let a = tracker.try_acquire("10.0.0.1".parse().unwrap()).unwrap();
+        let _b = tracker.try_acquire("10.0.0.2".parse().unwrap()).unwrap();
+
+        assert!(tracker.try_acquire("10.0.0.3".parse().unwrap()).is_none());
+        assert_eq!(tracker.active(), 2);
+
+        drop(a);
+        assert_eq!(tracker.active(), 1);
+        assert_eq!(tracker.by_ip(), vec![("10.0.0.2".parse().unwrap(), 1)]);
+    }
+}
@@ -0,0 +1,30 @@
+//! # Client Error Types
+
+use thiserror::Error;
+
+/// Errors returned by GraphQL client operations
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The server rejected or failed the operation
+    #[error("GraphQL errors: {}", .0.join(", "))]
+    GraphQL(Vec<String>),
+
+    /// The response carried neither data nor errors
+    #[error("No data in response")]
+    NoData,
+
+    /// The request body could not be serialized
+    #[error("Encode error: {0}")]
+    Encode(String),
+
+    /// The request could not be delivered
+    #[error("Transport error: {0}")]
+    Transport(String),
+
+    /// The response body did not match the expected shape
+    #[error("Decode error: {0}")]
+    Decode(String),
+}
+
+/// Result type for client operations
+pub type Result<T> = std::result::Result<T, ClientError>;
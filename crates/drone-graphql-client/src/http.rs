@@ -0,0 +1,115 @@
+//! # HTTP Transport
+//!
+//! Native client for queries and mutations (feature `reqwest`).
+
+use crate::error::{ClientError, Result};
+use crate::{Batch, GraphQLOperation, GraphQLResponse};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Default maximum operations per batched request, matching the API's default
+pub const DEFAULT_MAX_BATCH_OPERATIONS: usize = 50;
+
+/// GraphQL-over-HTTP client
+#[derive(Debug, Clone)]
+pub struct GraphQLClient {
+    http: reqwest::Client,
+    url: String,
+    token: Option<String>,
+    api_key: Option<String>,
+    max_batch_operations: usize,
+}
+
+impl GraphQLClient {
+    /// Create a client for the given `/graphql` endpoint
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            token: None,
+            api_key: None,
+            max_batch_operations: DEFAULT_MAX_BATCH_OPERATIONS,
+        }
+    }
+
+    /// Send `Authorization: Bearer <token>` with every request
+    #[must_use]
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Send `X-API-Key: <key>` with every request, for machine clients
+    #[must_use]
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Split batches into requests of at most `max` operations
+    #[must_use]
+    pub fn with_max_batch_operations(mut self, max: usize) -> Self {
+        self.max_batch_operations = max.max(1);
+        self
+    }
+
+    /// Execute a typed operation
+    pub async fn execute<O: GraphQLOperation>(
+        &self,
+        variables: O::Variables,
+    ) -> Result<O::ResponseData> {
+        self.post::<_, GraphQLResponse<O::ResponseData>>(&O::build(variables))
+            .await?
+            .into_result()
+    }
+
+    /// Execute a batch of operations, one HTTP request per
+    /// `max_batch_operations` chunk.
+    ///
+    /// Returns one result per operation in push order, so a failed
+    /// operation does not hide the outcome of the others.
+    pub async fn execute_batch(&self, batch: &Batch) -> Result<Vec<Result<serde_json::Value>>> {
+        let mut results = Vec::with_capacity(batch.len());
+        for chunk in batch.requests().chunks(self.max_batch_operations) {
+            let responses = self
+                .post::<_, Vec<GraphQLResponse<serde_json::Value>>>(chunk)
+                .await?;
+            if responses.len() != chunk.len() {
+                return Err(ClientError::Decode(format!(
+                    "expected {} responses in batch, got {}",
+                    chunk.len(),
+                    responses.len()
+                )));
+            }
+            results.extend(responses.into_iter().map(GraphQLResponse::into_result));
+        }
+        Ok(results)
+    }
+
+    /// POST a JSON body to the endpoint and decode the JSON reply
+    async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(&self, body: &B) -> Result<T> {
+        let mut request = self.http.post(&self.url).json(body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(key) = &self.api_key {
+            request = request.header("X-API-Key", key);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ClientError::Transport(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(ClientError::Transport(format!(
+                "API returned status: {}",
+                response.status()
+            )));
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| ClientError::Decode(e.to_string()))
+    }
+}
@@ -0,0 +1,181 @@
+//! # Drone GraphQL Client
+//!
+//! Typed GraphQL operations shared by the simulator and the Leptos frontend.
+//!
+//! Each operation is a unit struct implementing [`GraphQLOperation`], pairing
+//! the query document with its variables and response types so callers never
+//! hand-write query strings or JSON maps. Transport is left to the caller:
+//! the `reqwest` feature provides a native HTTP client, and [`ws`] carries the
+//! `graphql-transport-ws` messages for subscriptions.
+
+#![forbid(unsafe_code)]
+#![warn(clippy::all, missing_docs)]
+
+pub mod error;
+#[cfg(feature = "reqwest")]
+pub mod http;
+pub mod operations;
+pub mod subscriptions;
+pub mod ws;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+pub use error::ClientError;
+#[cfg(feature = "reqwest")]
+pub use http::GraphQLClient;
+
+/// A GraphQL operation with typed variables and response data
+pub trait GraphQLOperation {
+    /// Variables sent alongside the document
+    type Variables: Serialize;
+
+    /// Shape of the `data` field in the response
+    type ResponseData: DeserializeOwned;
+
+    /// Operation name as declared in [`Self::QUERY`]
+    const OPERATION_NAME: &'static str;
+
+    /// GraphQL document
+    const QUERY: &'static str;
+
+    /// Build the request body for this operation
+    fn build(variables: Self::Variables) -> GraphQLRequest<Self::Variables> {
+        GraphQLRequest {
+            query: Self::QUERY,
+            operation_name: Self::OPERATION_NAME,
+            variables,
+        }
+    }
+}
+
+/// GraphQL-over-HTTP request body
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLRequest<V> {
+    /// GraphQL document
+    pub query: &'static str,
+    /// Operation to execute
+    pub operation_name: &'static str,
+    /// Operation variables
+    pub variables: V,
+}
+
+/// Operations of mixed types sent together as one batched HTTP request
+///
+/// The server executes them in order and answers with one response per
+/// operation, so callers on a hot path pay for a single round trip.
+#[derive(Debug, Clone, Default)]
+pub struct Batch {
+    requests: Vec<serde_json::Value>,
+}
+
+impl Batch {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an operation
+    pub fn push<O: GraphQLOperation>(&mut self, variables: O::Variables) -> Result<(), ClientError> {
+        let request = serde_json::to_value(O::build(variables))
+            .map_err(|e| ClientError::Encode(e.to_string()))?;
+        self.requests.push(request);
+        Ok(())
+    }
+
+    /// Number of queued operations
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Whether no operations are queued
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Request bodies in the order they were pushed
+    pub fn requests(&self) -> &[serde_json::Value] {
+        &self.requests
+    }
+}
+
+/// GraphQL response envelope
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQLResponse<T> {
+    /// Result data, absent when execution failed
+    pub data: Option<T>,
+    /// Errors reported by the server
+    pub errors: Option<Vec<GraphQLError>>,
+}
+
+/// Error entry in a GraphQL response
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphQLError {
+    /// Human-readable message
+    pub message: String,
+}
+
+impl<T> GraphQLResponse<T> {
+    /// Unwrap the response data, surfacing server errors first
+    pub fn into_result(self) -> Result<T, ClientError> {
+        if let Some(errors) = self.errors.filter(|e| !e.is_empty()) {
+            return Err(ClientError::GraphQL(
+                errors.into_iter().map(|e| e.message).collect(),
+            ));
+        }
+        self.data.ok_or(ClientError::NoData)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::{GetLeaderboard, GetLeaderboardVariables};
+
+    #[test]
+    fn test_request_serializes_camel_case() {
+        let request = GetLeaderboard::build(GetLeaderboardVariables {
+            convoy_id: "c1".to_string(),
+            limit: 5,
+        });
+        let json = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(json["operationName"], "GetLeaderboard");
+        assert_eq!(json["variables"]["convoyId"], "c1");
+        assert_eq!(json["variables"]["limit"], 5);
+    }
+
+    #[test]
+    fn test_batch_keeps_push_order() {
+        let mut batch = Batch::new();
+        assert!(batch.is_empty());
+        for convoy_id in ["c1", "c2"] {
+            batch
+                .push::<GetLeaderboard>(GetLeaderboardVariables {
+                    convoy_id: convoy_id.to_string(),
+                    limit: 5,
+                })
+                .unwrap();
+        }
+
+        assert_eq!(batch.len(), 2);
+        let json = serde_json::to_value(batch.requests()).unwrap();
+        assert_eq!(json[0]["variables"]["convoyId"], "c1");
+        assert_eq!(json[1]["variables"]["convoyId"], "c2");
+        assert_eq!(json[1]["operationName"], "GetLeaderboard");
+    }
+
+    #[test]
+    fn test_into_result_prefers_errors() {
+        let response: GraphQLResponse<serde_json::Value> = serde_json::from_str(
+            r#"{"data": null, "errors": [{"message": "boom"}, {"message": "bang"}]}"#,
+        )
+        .unwrap();
+
+        match response.into_result() {
+            Err(ClientError::GraphQL(messages)) => assert_eq!(messages, ["boom", "bang"]),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,1219 @@
+//! # Queries and Mutations
+//!
+//! Typed request/response pairs for the operations the simulator and the
+//! HUD issue over HTTP. Enum-valued fields are carried as their GraphQL
+//! names (e.g. `AGM114_HELLFIRE`).
+
+use crate::GraphQLOperation;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// =============================================================================
+// CONNECTIONS
+// =============================================================================
+
+/// Relay `*Edge` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Edge<T> {
+    /// Opaque cursor; pass as `after` to continue after this item
+    pub cursor: String,
+    /// The item
+    pub node: T,
+}
+
+/// `PageInfo` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    /// Whether more items follow
+    pub has_next_page: bool,
+    /// Cursor of the last edge, `None` for an empty page
+    pub end_cursor: Option<String>,
+}
+
+impl PageInfo {
+    /// Cursor to request the next page with, `None` on the last page
+    #[must_use]
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.end_cursor.as_deref().filter(|_| self.has_next_page)
+    }
+}
+
+// =============================================================================
+// LEADERBOARD
+// =============================================================================
+
+/// `leaderboard(convoyId, limit)` query
+pub struct GetLeaderboard;
+
+/// Variables for [`GetLeaderboard`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLeaderboardVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Maximum entries to return
+    pub limit: i32,
+}
+
+/// Response data for [`GetLeaderboard`]
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetLeaderboardData {
+    /// Leaderboard for the convoy
+    pub leaderboard: Leaderboard,
+}
+
+/// Leaderboard selection
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    /// Ranked entries
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// Leaderboard entry selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    /// Drone ID
+    pub drone_id: String,
+    /// Drone callsign
+    pub callsign: String,
+    /// Platform type
+    pub platform_type: String,
+    /// Rank position
+    pub rank: i32,
+    /// Accuracy percentage
+    pub accuracy_pct: f32,
+    /// Total engagements
+    pub total_engagements: i32,
+    /// Successful hits
+    pub successful_hits: i32,
+    /// Current hit streak
+    pub current_streak: i32,
+    /// Best hit streak
+    pub best_streak: i32,
+}
+
+impl GraphQLOperation for GetLeaderboard {
+    type Variables = GetLeaderboardVariables;
+    type ResponseData = GetLeaderboardData;
+
+    const OPERATION_NAME: &'static str = "GetLeaderboard";
+    const QUERY: &'static str = r#"
+        query GetLeaderboard($convoyId: ID!, $limit: Int!) {
+            leaderboard(convoyId: $convoyId, limit: $limit) {
+                entries {
+                    droneId
+                    callsign
+                    platformType
+                    rank
+                    accuracyPct
+                    totalEngagements
+                    successfulHits
+                    currentStreak
+                    bestStreak
+                }
+            }
+        }
+    "#;
+}
+
+/// `rankTimeline(convoyId, droneId, timeRange)` query
+pub struct GetRankTimeline;
+
+/// Variables for [`GetRankTimeline`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRankTimelineVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Drone ID
+    pub drone_id: String,
+    /// Time window
+    pub time_range: TimeRange,
+}
+
+/// Response data for [`GetRankTimeline`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRankTimelineData {
+    /// Rank changes, oldest first
+    pub rank_timeline: Vec<RankTimelinePoint>,
+}
+
+/// `RankTimelinePoint` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankTimelinePoint {
+    /// When the rank changed
+    pub timestamp: DateTime<Utc>,
+    /// Rank from this point on
+    pub rank: i32,
+    /// Accuracy percentage, if known yet
+    pub accuracy_pct: Option<f32>,
+}
+
+impl GraphQLOperation for GetRankTimeline {
+    type Variables = GetRankTimelineVariables;
+    type ResponseData = GetRankTimelineData;
+
+    const OPERATION_NAME: &'static str = "GetRankTimeline";
+    const QUERY: &'static str = r#"
+        query GetRankTimeline($convoyId: ID!, $droneId: ID!, $timeRange: TimeRangeInput!) {
+            rankTimeline(convoyId: $convoyId, droneId: $droneId, timeRange: $timeRange) {
+                timestamp
+                rank
+                accuracyPct
+            }
+        }
+    "#;
+}
+
+// =============================================================================
+// CONVOYS
+// =============================================================================
+
+/// `activeConvoys` query
+pub struct GetActiveConvoys;
+
+/// Response data for [`GetActiveConvoys`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetActiveConvoysData {
+    /// Convoys currently on mission
+    pub active_convoys: Vec<ConvoySummary>,
+}
+
+/// Convoy selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvoySummary {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Convoy callsign
+    pub callsign: String,
+    /// Mission type
+    pub mission_type: String,
+    /// Convoy status
+    pub status: String,
+    /// Number of drones
+    pub drone_count: i32,
+}
+
+impl GraphQLOperation for GetActiveConvoys {
+    type Variables = ();
+    type ResponseData = GetActiveConvoysData;
+
+    const OPERATION_NAME: &'static str = "GetActiveConvoys";
+    const QUERY: &'static str = r#"
+        query GetActiveConvoys {
+            activeConvoys {
+                convoyId
+                callsign
+                missionType
+                status
+                droneCount
+            }
+        }
+    "#;
+}
+
+/// `convoyStats(convoyId)` query
+pub struct GetConvoyStats;
+
+/// Variables for [`GetConvoyStats`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetConvoyStatsVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+}
+
+/// Response data for [`GetConvoyStats`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetConvoyStatsData {
+    /// Aggregate statistics for the convoy
+    pub convoy_stats: ConvoyStats,
+}
+
+/// Convoy statistics selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvoyStats {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Total drones
+    pub drone_count: i32,
+    /// Airborne drones
+    pub airborne_count: i32,
+    /// Total engagements
+    pub total_engagements: i32,
+    /// Total hits
+    pub total_hits: i32,
+    /// Average accuracy percentage
+    pub average_accuracy_pct: f32,
+    /// Average fuel percentage
+    pub average_fuel_pct: f32,
+}
+
+impl GraphQLOperation for GetConvoyStats {
+    type Variables = GetConvoyStatsVariables;
+    type ResponseData = GetConvoyStatsData;
+
+    const OPERATION_NAME: &'static str = "GetConvoyStats";
+    const QUERY: &'static str = r#"
+        query GetConvoyStats($convoyId: ID!) {
+            convoyStats(convoyId: $convoyId) {
+                convoyId
+                droneCount
+                airborneCount
+                totalEngagements
+                totalHits
+                averageAccuracyPct
+                averageFuelPct
+            }
+        }
+    "#;
+}
+
+// =============================================================================
+// ALERTS
+// =============================================================================
+
+/// `activeAlerts(convoyId, limit, includeAcknowledged)` query
+pub struct GetActiveAlerts;
+
+/// Variables for [`GetActiveAlerts`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetActiveAlertsVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Maximum alerts to return
+    pub limit: i32,
+    /// Include alerts already acknowledged
+    pub include_acknowledged: bool,
+}
+
+/// Response data for [`GetActiveAlerts`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetActiveAlertsData {
+    /// Alerts, newest first
+    pub active_alerts: Vec<Alert>,
+}
+
+/// `Alert` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Alert {
+    /// Alert ID
+    pub alert_id: String,
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Source drone ID
+    pub drone_id: Option<String>,
+    /// Severity (`INFO`, `WARNING`, `CRITICAL`)
+    pub severity: String,
+    /// Alert type code
+    pub alert_type: String,
+    /// Human readable message
+    pub message: String,
+    /// When the alert was raised
+    pub timestamp: DateTime<Utc>,
+    /// Whether an operator has acknowledged the alert
+    pub acknowledged: bool,
+    /// Who acknowledged the alert
+    pub acknowledged_by: Option<String>,
+}
+
+impl GraphQLOperation for GetActiveAlerts {
+    type Variables = GetActiveAlertsVariables;
+    type ResponseData = GetActiveAlertsData;
+
+    const OPERATION_NAME: &'static str = "GetActiveAlerts";
+    const QUERY: &'static str = r#"
+        query GetActiveAlerts($convoyId: ID!, $limit: Int!, $includeAcknowledged: Boolean!) {
+            activeAlerts(convoyId: $convoyId, limit: $limit, includeAcknowledged: $includeAcknowledged) {
+                alertId
+                convoyId
+                droneId
+                severity
+                alertType
+                message
+                timestamp
+                acknowledged
+                acknowledgedBy
+            }
+        }
+    "#;
+}
+
+/// `acknowledgeAlert(convoyId, alertId)` mutation
+pub struct AcknowledgeAlert;
+
+/// Variables for [`AcknowledgeAlert`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcknowledgeAlertVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Alert ID
+    pub alert_id: String,
+}
+
+/// Response data for [`AcknowledgeAlert`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcknowledgeAlertData {
+    /// The acknowledged alert
+    pub acknowledge_alert: Alert,
+}
+
+impl GraphQLOperation for AcknowledgeAlert {
+    type Variables = AcknowledgeAlertVariables;
+    type ResponseData = AcknowledgeAlertData;
+
+    const OPERATION_NAME: &'static str = "AcknowledgeAlert";
+    const QUERY: &'static str = r#"
+        mutation AcknowledgeAlert($convoyId: ID!, $alertId: ID!) {
+            acknowledgeAlert(convoyId: $convoyId, alertId: $alertId) {
+                alertId
+                convoyId
+                droneId
+                severity
+                alertType
+                message
+                timestamp
+                acknowledged
+                acknowledgedBy
+            }
+        }
+    "#;
+}
+
+// =============================================================================
+// TELEMETRY
+// =============================================================================
+
+/// `telemetryHistory(droneId, timeRange, resolutionSec, first, after)` query
+pub struct GetTelemetryHistory;
+
+/// Variables for [`GetTelemetryHistory`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTelemetryHistoryVariables {
+    /// Drone ID
+    pub drone_id: String,
+    /// Time window
+    pub time_range: TimeRange,
+    /// Aggregation bucket width in seconds; raw points when `None`
+    pub resolution_sec: Option<i32>,
+    /// Maximum points to return
+    pub first: i32,
+    /// Cursor of the last point already fetched
+    pub after: Option<String>,
+}
+
+/// Response data for [`GetTelemetryHistory`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTelemetryHistoryData {
+    /// History page
+    pub telemetry_history: TelemetryPage,
+}
+
+/// `TelemetryConnection` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryPage {
+    /// Points, oldest first
+    pub edges: Vec<Edge<TelemetryPoint>>,
+    /// Paging cursors
+    pub page_info: PageInfo,
+    /// Points in the window before paging
+    pub total_count: Option<i32>,
+}
+
+/// `TelemetrySnapshot` selection used for charting
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryPoint {
+    /// Drone ID
+    pub drone_id: String,
+    /// Recording timestamp
+    pub recorded_at: DateTime<Utc>,
+    /// Altitude and speed
+    pub position: TelemetryPosition,
+    /// Fuel remaining percentage
+    pub fuel_remaining_pct: f32,
+    /// Velocity in m/s
+    pub velocity_mps: f32,
+    /// Engine temperature in Celsius
+    pub engine_temp_c: Option<f32>,
+    /// Vendor fields passed through from the telemetry envelope
+    #[serde(default)]
+    pub extensions: Option<serde_json::Value>,
+}
+
+/// `Coordinates` selection for [`TelemetryPoint`]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryPosition {
+    /// Altitude in meters
+    pub altitude_m: f64,
+    /// Ground speed in m/s
+    pub speed_mps: f32,
+}
+
+impl GraphQLOperation for GetTelemetryHistory {
+    type Variables = GetTelemetryHistoryVariables;
+    type ResponseData = GetTelemetryHistoryData;
+
+    const OPERATION_NAME: &'static str = "GetTelemetryHistory";
+    const QUERY: &'static str = r#"
+        query GetTelemetryHistory($droneId: ID!, $timeRange: TimeRangeInput!, $resolutionSec: Int, $first: Int!, $after: String) {
+            telemetryHistory(droneId: $droneId, timeRange: $timeRange, resolutionSec: $resolutionSec, first: $first, after: $after) {
+                edges {
+                    cursor
+                    node {
+                        droneId
+                        recordedAt
+                        position {
+                            altitudeM
+                            speedMps
+                        }
+                        fuelRemainingPct
+                        velocityMps
+                        engineTempC
+                        extensions
+                    }
+                }
+                pageInfo {
+                    hasNextPage
+                    endCursor
+                }
+                totalCount
+            }
+        }
+    "#;
+}
+
+// =============================================================================
+// ENGAGEMENTS
+// =============================================================================
+
+/// `engagementHeatmap(convoyId, timeRange, gridResolutionKm)` query
+pub struct GetEngagementHeatmap;
+
+/// Variables for [`GetEngagementHeatmap`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEngagementHeatmapVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Time window
+    pub time_range: TimeRange,
+    /// Grid cell edge in km
+    pub grid_resolution_km: f64,
+}
+
+/// `TimeRangeInput`
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeRange {
+    /// Start time (inclusive)
+    pub start: DateTime<Utc>,
+    /// End time (inclusive)
+    pub end: DateTime<Utc>,
+}
+
+/// Response data for [`GetEngagementHeatmap`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEngagementHeatmapData {
+    /// Binned impact points
+    pub engagement_heatmap: EngagementHeatmap,
+}
+
+/// Heatmap selection
+#[derive(Debug, Clone, Deserialize)]
+pub struct EngagementHeatmap {
+    /// Non-empty grid cells
+    pub cells: Vec<HeatmapCell>,
+}
+
+/// Heatmap cell selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapCell {
+    /// Cell center latitude
+    pub latitude: f64,
+    /// Cell center longitude
+    pub longitude: f64,
+    /// Engagements in the cell
+    pub count: i32,
+    /// Hits in the cell
+    pub hits: i32,
+    /// Hits over engagements
+    pub hit_ratio: f64,
+}
+
+impl GraphQLOperation for GetEngagementHeatmap {
+    type Variables = GetEngagementHeatmapVariables;
+    type ResponseData = GetEngagementHeatmapData;
+
+    const OPERATION_NAME: &'static str = "GetEngagementHeatmap";
+    const QUERY: &'static str = r#"
+        query GetEngagementHeatmap($convoyId: ID!, $timeRange: TimeRangeInput, $gridResolutionKm: Float!) {
+            engagementHeatmap(convoyId: $convoyId, timeRange: $timeRange, gridResolutionKm: $gridResolutionKm) {
+                cells {
+                    latitude
+                    longitude
+                    count
+                    hits
+                    hitRatio
+                }
+            }
+        }
+    "#;
+}
+
+/// `engagements(convoyId, first, after)` query
+pub struct GetEngagements;
+
+/// Variables for [`GetEngagements`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEngagementsVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Page size
+    pub first: i32,
+    /// Cursor of the oldest engagement already fetched; newest first when `None`
+    pub after: Option<String>,
+}
+
+/// Response data for [`GetEngagements`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEngagementsData {
+    /// Engagement page
+    pub engagements: EngagementPage,
+}
+
+/// `EngagementConnection` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngagementPage {
+    /// Engagements, newest first
+    pub edges: Vec<Edge<EngagementRecord>>,
+    /// Paging cursors; `hasNextPage` is set while older engagements remain
+    pub page_info: PageInfo,
+    /// Engagements across all pages
+    pub total_count: Option<i32>,
+}
+
+/// `Engagement` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngagementRecord {
+    /// Engagement ID
+    pub engagement_id: String,
+    /// Drone ID
+    pub drone_id: String,
+    /// Drone callsign
+    pub drone_callsign: String,
+    /// Engagement timestamp
+    pub engaged_at: DateTime<Utc>,
+    /// Weapon type
+    pub weapon_type: String,
+    /// Target type
+    pub target_type: String,
+    /// Target location
+    pub target_coordinates: LatLon,
+    /// Range to target in km
+    pub range_km: f32,
+    /// Whether the engagement was a hit
+    pub hit: bool,
+    /// Battle damage assessment
+    pub damage_assessment: String,
+}
+
+/// Latitude/longitude selection of `Coordinates`
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct LatLon {
+    /// Latitude
+    pub latitude: f64,
+    /// Longitude
+    pub longitude: f64,
+}
+
+impl GraphQLOperation for GetEngagements {
+    type Variables = GetEngagementsVariables;
+    type ResponseData = GetEngagementsData;
+
+    const OPERATION_NAME: &'static str = "GetEngagements";
+    const QUERY: &'static str = r#"
+        query GetEngagements($convoyId: ID!, $first: Int!, $after: String) {
+            engagements(convoyId: $convoyId, first: $first, after: $after) {
+                edges {
+                    cursor
+                    node {
+                        engagementId
+                        droneId
+                        droneCallsign
+                        engagedAt
+                        weaponType
+                        targetType
+                        targetCoordinates {
+                            latitude
+                            longitude
+                        }
+                        rangeKm
+                        hit
+                        damageAssessment
+                    }
+                }
+                pageInfo {
+                    hasNextPage
+                    endCursor
+                }
+                totalCount
+            }
+        }
+    "#;
+}
+
+/// `recordEngagement(input)` mutation
+pub struct RecordEngagement;
+
+/// Variables for [`RecordEngagement`]
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordEngagementVariables {
+    /// Engagement to record
+    pub input: RecordEngagementInput,
+}
+
+/// `RecordEngagementInput`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordEngagementInput {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Drone that performed the engagement
+    pub drone_id: String,
+    /// Whether the engagement was a hit
+    pub hit: bool,
+    /// Weapon type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weapon_type: Option<String>,
+    /// Target type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<String>,
+    /// Range to target in km
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range_km: Option<f64>,
+    /// Probability of kill predicted before the shot (0-1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub predicted_pk: Option<f64>,
+}
+
+/// Response data for [`RecordEngagement`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordEngagementData {
+    /// Mutation result
+    pub record_engagement: RecordEngagementResult,
+}
+
+/// `RecordEngagementResult` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordEngagementResult {
+    /// Success flag
+    pub success: bool,
+    /// New rank position
+    pub new_rank: i32,
+    /// Rank change from previous
+    pub rank_change: i32,
+    /// New accuracy percentage
+    pub new_accuracy_pct: f32,
+}
+
+impl GraphQLOperation for RecordEngagement {
+    type Variables = RecordEngagementVariables;
+    type ResponseData = RecordEngagementData;
+
+    const OPERATION_NAME: &'static str = "RecordEngagement";
+    const QUERY: &'static str = r#"
+        mutation RecordEngagement($input: RecordEngagementInput!) {
+            recordEngagement(input: $input) {
+                success
+                newRank
+                rankChange
+                newAccuracyPct
+            }
+        }
+    "#;
+}
+
+// =============================================================================
+// REPLAY
+// =============================================================================
+
+/// `engagementReplay(convoyId, timeRange)` query
+pub struct GetEngagementReplay;
+
+/// Variables for [`GetEngagementReplay`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEngagementReplayVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Window to replay, at most 7 days
+    pub time_range: TimeRange,
+}
+
+/// Response data for [`GetEngagementReplay`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEngagementReplayData {
+    /// Interleaved events
+    pub engagement_replay: EngagementReplay,
+}
+
+/// `EngagementReplay` selection
+#[derive(Debug, Clone, Deserialize)]
+pub struct EngagementReplay {
+    /// Events, oldest first
+    pub events: Vec<ReplayEvent>,
+    /// Whether the window held more events than were returned
+    pub truncated: bool,
+}
+
+/// `ReplayEvent` selection; exactly one payload is set, per `kind`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayEvent {
+    /// When the event happened
+    pub timestamp: DateTime<Utc>,
+    /// `ENGAGEMENT` or `JOURNAL`
+    pub kind: String,
+    /// Set for `ENGAGEMENT` events
+    pub engagement: Option<EngagementRecord>,
+    /// Set for `JOURNAL` events
+    pub journal_entry: Option<JournalEntryRecord>,
+}
+
+/// `JournalEntry` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalEntryRecord {
+    /// Entry ID
+    pub entry_id: String,
+    /// When the entry was written
+    pub timestamp: DateTime<Utc>,
+    /// Operator who wrote the entry
+    pub author: String,
+    /// Annotation text
+    pub text: String,
+    /// Engagement, drone, target or alert the entry refers to
+    pub linked_entity_id: Option<String>,
+}
+
+impl GraphQLOperation for GetEngagementReplay {
+    type Variables = GetEngagementReplayVariables;
+    type ResponseData = GetEngagementReplayData;
+
+    const OPERATION_NAME: &'static str = "GetEngagementReplay";
+    const QUERY: &'static str = r#"
+        query GetEngagementReplay($convoyId: ID!, $timeRange: TimeRangeInput!) {
+            engagementReplay(convoyId: $convoyId, timeRange: $timeRange) {
+                events {
+                    timestamp
+                    kind
+                    engagement {
+                        engagementId
+                        droneId
+                        droneCallsign
+                        engagedAt
+                        weaponType
+                        targetType
+                        targetCoordinates {
+                            latitude
+                            longitude
+                        }
+                        rangeKm
+                        hit
+                        damageAssessment
+                    }
+                    journalEntry {
+                        entryId
+                        timestamp
+                        author
+                        text
+                        linkedEntityId
+                    }
+                }
+                truncated
+            }
+        }
+    "#;
+}
+
+/// `engagementTimeline(droneId, timeRange, bucket)` query
+pub struct GetEngagementTimeline;
+
+/// Variables for [`GetEngagementTimeline`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEngagementTimelineVariables {
+    /// Drone ID
+    pub drone_id: String,
+    /// Time window
+    pub time_range: TimeRange,
+    /// `TimeBucket` name, e.g. `FIVE_MINUTES`
+    pub bucket: String,
+}
+
+/// Response data for [`GetEngagementTimeline`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEngagementTimelineData {
+    /// Buckets, oldest first
+    pub engagement_timeline: Vec<EngagementTimelineBucket>,
+}
+
+/// `EngagementTimelineBucket` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngagementTimelineBucket {
+    /// Start of the bucket
+    pub bucket_start: DateTime<Utc>,
+    /// Engagements that hit
+    pub hits: i32,
+    /// Engagements that missed
+    pub misses: i32,
+}
+
+impl GraphQLOperation for GetEngagementTimeline {
+    type Variables = GetEngagementTimelineVariables;
+    type ResponseData = GetEngagementTimelineData;
+
+    const OPERATION_NAME: &'static str = "GetEngagementTimeline";
+    const QUERY: &'static str = r#"
+        query GetEngagementTimeline($droneId: ID!, $timeRange: TimeRangeInput!, $bucket: TimeBucket!) {
+            engagementTimeline(droneId: $droneId, timeRange: $timeRange, bucket: $bucket) {
+                bucketStart
+                hits
+                misses
+            }
+        }
+    "#;
+}
+
+/// `droneTrack(droneId, timeRange, maxPoints)` query
+pub struct GetDroneTrack;
+
+/// Variables for [`GetDroneTrack`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDroneTrackVariables {
+    /// Drone ID
+    pub drone_id: String,
+    /// Window to fetch, at most 24 hours
+    pub time_range: TimeRange,
+    /// Maximum points after simplification
+    pub max_points: i32,
+}
+
+/// Response data for [`GetDroneTrack`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDroneTrackData {
+    /// Simplified track
+    pub drone_track: DroneTrack,
+}
+
+/// `DroneTrack` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroneTrack {
+    /// Drone ID
+    pub drone_id: String,
+    /// Polyline, oldest first
+    pub points: Vec<TrackPoint>,
+}
+
+/// `TrackPoint` selection
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackPoint {
+    /// Recording timestamp
+    pub recorded_at: DateTime<Utc>,
+    /// Position
+    pub position: LatLon,
+}
+
+impl GraphQLOperation for GetDroneTrack {
+    type Variables = GetDroneTrackVariables;
+    type ResponseData = GetDroneTrackData;
+
+    const OPERATION_NAME: &'static str = "GetDroneTrack";
+    const QUERY: &'static str = r#"
+        query GetDroneTrack($droneId: ID!, $timeRange: TimeRangeInput!, $maxPoints: Int!) {
+            droneTrack(droneId: $droneId, timeRange: $timeRange, maxPoints: $maxPoints) {
+                droneId
+                points {
+                    recordedAt
+                    position {
+                        latitude
+                        longitude
+                    }
+                }
+            }
+        }
+    "#;
+}
+
+// =============================================================================
+// DRONE STATE
+// =============================================================================
+
+/// `updateDroneState(input)` mutation
+pub struct UpdateDroneState;
+
+/// Variables for [`UpdateDroneState`]
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateDroneStateVariables {
+    /// State to report
+    pub input: UpdateDroneStateInput,
+}
+
+/// `UpdateDroneStateInput`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDroneStateInput {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Drone ID
+    pub drone_id: String,
+    /// New status (e.g. `RTB`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Fuel remaining percentage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fuel_pct: Option<f64>,
+    /// Current waypoint number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_waypoint: Option<i32>,
+    /// Rounds remaining per weapon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weapons: Option<Vec<WeaponLoadoutInput>>,
+}
+
+/// `WeaponLoadoutInput`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponLoadoutInput {
+    /// Weapon type
+    pub weapon_type: String,
+    /// Rounds remaining
+    pub rounds: i32,
+}
+
+/// Response data for [`UpdateDroneState`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDroneStateData {
+    /// Updated drone
+    pub update_drone_state: DroneStateResult,
+}
+
+/// `Drone` selection for [`UpdateDroneState`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroneStateResult {
+    /// Drone ID
+    pub drone_id: String,
+    /// Current status
+    pub status: String,
+}
+
+impl GraphQLOperation for UpdateDroneState {
+    type Variables = UpdateDroneStateVariables;
+    type ResponseData = UpdateDroneStateData;
+
+    const OPERATION_NAME: &'static str = "UpdateDroneState";
+    const QUERY: &'static str = r#"
+        mutation UpdateDroneState($input: UpdateDroneStateInput!) {
+            updateDroneState(input: $input) {
+                droneId
+                status
+            }
+        }
+    "#;
+}
+
+// =============================================================================
+// HEALTH
+// =============================================================================
+
+/// `fleetReadiness(convoyId, lowest)` query
+pub struct GetFleetReadiness;
+
+/// Variables for [`GetFleetReadiness`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFleetReadinessVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Lowest-scoring drones to return
+    pub lowest: i32,
+}
+
+/// Response data for [`GetFleetReadiness`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFleetReadinessData {
+    /// Health distribution for the convoy
+    pub fleet_readiness: FleetReadiness,
+}
+
+/// `FleetReadiness` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FleetReadiness {
+    /// Drones in the GREEN band
+    pub green: i32,
+    /// Drones in the AMBER band
+    pub amber: i32,
+    /// Drones in the RED band
+    pub red: i32,
+    /// Drones without a score yet
+    pub unscored: i32,
+    /// Mean score of the scored drones
+    pub average_score: Option<f64>,
+    /// Lowest-scoring drones, worst first
+    pub lowest: Vec<DroneHealthScore>,
+}
+
+/// `DroneHealth` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroneHealthScore {
+    /// Drone ID
+    pub drone_id: String,
+    /// Overall score, 0 - 100
+    pub score: i32,
+    /// `GREEN`, `AMBER` or `RED`
+    pub band: String,
+}
+
+impl GraphQLOperation for GetFleetReadiness {
+    type Variables = GetFleetReadinessVariables;
+    type ResponseData = GetFleetReadinessData;
+
+    const OPERATION_NAME: &'static str = "GetFleetReadiness";
+    const QUERY: &'static str = r#"
+        query GetFleetReadiness($convoyId: ID!, $lowest: Int!) {
+            fleetReadiness(convoyId: $convoyId, lowest: $lowest) {
+                green
+                amber
+                red
+                unscored
+                averageScore
+                lowest {
+                    droneId
+                    score
+                    band
+                }
+            }
+        }
+    "#;
+}
+
+// =============================================================================
+// SEARCH
+// =============================================================================
+
+/// `search(query, limit)` query
+pub struct Search;
+
+/// Variables for [`Search`]
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchVariables {
+    /// Search text
+    pub query: String,
+    /// Maximum results
+    pub limit: i32,
+}
+
+/// Response data for [`Search`]
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchData {
+    /// Matches, best first
+    pub search: Vec<SearchMatch>,
+}
+
+/// `SearchResult` selection
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatch {
+    /// `DRONE`, `CONVOY` or `ENGAGEMENT`
+    #[serde(rename = "type")]
+    pub result_type: String,
+    /// Drone, convoy or engagement ID
+    pub id: String,
+    /// Convoy the entity belongs to
+    pub convoy_id: String,
+    /// Display text
+    pub label: String,
+    /// Relevance from 0 to 1
+    pub score: f64,
+}
+
+impl GraphQLOperation for Search {
+    type Variables = SearchVariables;
+    type ResponseData = SearchData;
+
+    const OPERATION_NAME: &'static str = "Search";
+    const QUERY: &'static str = r#"
+        query Search($query: String!, $limit: Int!) {
+            search(query: $query, limit: $limit) {
+                type
+                id
+                convoyId
+                label
+                score
+            }
+        }
+    "#;
+}
+
+// =============================================================================
+// SYSTEM
+// =============================================================================
+
+/// `health` query; cheap enough to time round trips with
+pub struct Health;
+
+/// Response data for [`Health`]
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthData {
+    /// `OK` while the API is serving
+    pub health: String,
+}
+
+impl GraphQLOperation for Health {
+    type Variables = ();
+    type ResponseData = HealthData;
+
+    const OPERATION_NAME: &'static str = "Health";
+    const QUERY: &'static str = r#"
+        query Health {
+            health
+        }
+    "#;
+}
@@ -0,0 +1,453 @@
+//! # Subscriptions
+//!
+//! Typed subscription documents; payloads arrive through [`crate::ws`].
+
+use crate::operations::{ConvoyStats, LeaderboardEntry, TelemetryPoint};
+use crate::GraphQLOperation;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Variables shared by the per-convoy subscriptions
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvoyVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+}
+
+/// Variables for the per-drone subscriptions
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroneVariables {
+    /// Drone ID
+    pub drone_id: String,
+}
+
+/// `engagementEvents(convoyId)` subscription
+pub struct EngagementEvents;
+
+/// Payload for [`EngagementEvents`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngagementEventsData {
+    /// Engagement that just happened
+    pub engagement_events: EngagementEvent,
+}
+
+/// `EngagementEvent` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngagementEvent {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Drone ID
+    pub drone_id: String,
+    /// Drone callsign
+    pub callsign: String,
+    /// Was it a hit
+    pub hit: bool,
+    /// Weapon type
+    pub weapon_type: String,
+    /// Target type, when reported
+    pub target_type: Option<String>,
+    /// Range to target in km, when reported
+    pub range_km: Option<f32>,
+    /// New accuracy after engagement
+    pub new_accuracy_pct: f32,
+    /// Event timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Event ID; resume point for `replayEvents` after a reconnect
+    pub event_id: Option<String>,
+}
+
+impl GraphQLOperation for EngagementEvents {
+    type Variables = ConvoyVariables;
+    type ResponseData = EngagementEventsData;
+
+    const OPERATION_NAME: &'static str = "EngagementEvents";
+    const QUERY: &'static str = r#"
+        subscription EngagementEvents($convoyId: ID!) {
+            engagementEvents(convoyId: $convoyId) {
+                convoyId
+                droneId
+                callsign
+                hit
+                weaponType
+                targetType
+                rangeKm
+                newAccuracyPct
+                timestamp
+                eventId
+            }
+        }
+    "#;
+}
+
+/// `leaderboardUpdates(convoyId)` subscription
+pub struct LeaderboardUpdates;
+
+/// Payload for [`LeaderboardUpdates`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardUpdatesData {
+    /// Rank change
+    pub leaderboard_updates: LeaderboardUpdateEvent,
+}
+
+/// `LeaderboardUpdateEvent` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardUpdateEvent {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Drone ID
+    pub drone_id: String,
+    /// Drone callsign
+    pub callsign: String,
+    /// New rank
+    pub new_rank: i32,
+    /// Previous rank, if any
+    pub old_rank: Option<i32>,
+    /// New accuracy percentage
+    pub accuracy_pct: f32,
+    /// Type of rank change
+    pub change_type: String,
+    /// Event timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Event ID; resume point for `replayEvents` after a reconnect
+    pub event_id: Option<String>,
+}
+
+impl GraphQLOperation for LeaderboardUpdates {
+    type Variables = ConvoyVariables;
+    type ResponseData = LeaderboardUpdatesData;
+
+    const OPERATION_NAME: &'static str = "LeaderboardUpdates";
+    const QUERY: &'static str = r#"
+        subscription LeaderboardUpdates($convoyId: ID!) {
+            leaderboardUpdates(convoyId: $convoyId) {
+                convoyId
+                droneId
+                callsign
+                newRank
+                oldRank
+                accuracyPct
+                changeType
+                timestamp
+                eventId
+            }
+        }
+    "#;
+}
+
+/// `leaderboardLive(convoyId, limit)` subscription
+pub struct LeaderboardLive;
+
+/// Variables for [`LeaderboardLive`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardLiveVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Maximum entries to track
+    pub limit: i32,
+}
+
+/// Payload for [`LeaderboardLive`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardLiveData {
+    /// Snapshot or delta
+    pub leaderboard_live: LeaderboardLiveMessage,
+}
+
+/// `LeaderboardLiveMessage` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardLiveMessage {
+    /// Position in the stream, starting at 1
+    pub sequence: i32,
+    /// `SNAPSHOT` or `DELTA`
+    pub kind: String,
+    /// All entries for a snapshot; changed entries for a delta
+    pub entries: Vec<LeaderboardEntry>,
+    /// Drones that dropped out of the leaderboard
+    pub removed_drone_ids: Vec<String>,
+}
+
+impl GraphQLOperation for LeaderboardLive {
+    type Variables = LeaderboardLiveVariables;
+    type ResponseData = LeaderboardLiveData;
+
+    const OPERATION_NAME: &'static str = "LeaderboardLive";
+    const QUERY: &'static str = r#"
+        subscription LeaderboardLive($convoyId: ID!, $limit: Int!) {
+            leaderboardLive(convoyId: $convoyId, limit: $limit) {
+                sequence
+                kind
+                entries {
+                    droneId
+                    callsign
+                    platformType
+                    rank
+                    accuracyPct
+                    totalEngagements
+                    successfulHits
+                    currentStreak
+                    bestStreak
+                }
+                removedDroneIds
+            }
+        }
+    "#;
+}
+
+/// `alerts(convoyId)` subscription
+pub struct Alerts;
+
+/// Payload for [`Alerts`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertsData {
+    /// Alert that was just raised
+    pub alerts: AlertEvent,
+}
+
+/// `AlertEvent` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertEvent {
+    /// Alert ID
+    pub alert_id: String,
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Source drone ID
+    pub drone_id: Option<String>,
+    /// Severity (`INFO`, `WARNING`, `CRITICAL`)
+    pub severity: String,
+    /// Alert type code
+    pub alert_type: String,
+    /// Human readable message
+    pub message: String,
+    /// Event timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Event ID; resume point for `replayEvents` after a reconnect
+    pub event_id: Option<String>,
+}
+
+impl GraphQLOperation for Alerts {
+    type Variables = ConvoyVariables;
+    type ResponseData = AlertsData;
+
+    const OPERATION_NAME: &'static str = "Alerts";
+    const QUERY: &'static str = r#"
+        subscription Alerts($convoyId: ID!) {
+            alerts(convoyId: $convoyId) {
+                alertId
+                convoyId
+                droneId
+                severity
+                alertType
+                message
+                timestamp
+                eventId
+            }
+        }
+    "#;
+}
+
+/// `droneStatusChanges(convoyId)` subscription
+pub struct DroneStatusChanges;
+
+/// Payload for [`DroneStatusChanges`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroneStatusChangesData {
+    /// Status transition that was just applied
+    pub drone_status_changes: DroneStatusEvent,
+}
+
+/// `DroneStatusEvent` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroneStatusEvent {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Drone ID
+    pub drone_id: String,
+    /// Drone callsign
+    pub callsign: String,
+    /// Status before the change
+    pub old_status: String,
+    /// Status after the change
+    pub new_status: String,
+    /// Event timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Event ID; resume point for `replayEvents` after a reconnect
+    pub event_id: Option<String>,
+}
+
+impl GraphQLOperation for DroneStatusChanges {
+    type Variables = ConvoyVariables;
+    type ResponseData = DroneStatusChangesData;
+
+    const OPERATION_NAME: &'static str = "DroneStatusChanges";
+    const QUERY: &'static str = r#"
+        subscription DroneStatusChanges($convoyId: ID!) {
+            droneStatusChanges(convoyId: $convoyId) {
+                convoyId
+                droneId
+                callsign
+                oldStatus
+                newStatus
+                timestamp
+                eventId
+            }
+        }
+    "#;
+}
+
+/// `convoyStatsUpdates(convoyId, intervalSec)` subscription
+pub struct ConvoyStatsUpdates;
+
+/// Variables for [`ConvoyStatsUpdates`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvoyStatsVariables {
+    /// Convoy ID
+    pub convoy_id: String,
+    /// Minimum seconds between updates
+    pub interval_sec: i32,
+}
+
+/// Payload for [`ConvoyStatsUpdates`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvoyStatsUpdatesData {
+    /// Recomputed convoy statistics
+    pub convoy_stats_updates: ConvoyStats,
+}
+
+impl GraphQLOperation for ConvoyStatsUpdates {
+    type Variables = ConvoyStatsVariables;
+    type ResponseData = ConvoyStatsUpdatesData;
+
+    const OPERATION_NAME: &'static str = "ConvoyStatsUpdates";
+    const QUERY: &'static str = r#"
+        subscription ConvoyStatsUpdates($convoyId: ID!, $intervalSec: Int!) {
+            convoyStatsUpdates(convoyId: $convoyId, intervalSec: $intervalSec) {
+                convoyId
+                droneCount
+                airborneCount
+                totalEngagements
+                totalHits
+                averageAccuracyPct
+                averageFuelPct
+            }
+        }
+    "#;
+}
+
+/// `droneTelemetry(droneId)` subscription
+pub struct DroneTelemetry;
+
+/// Payload for [`DroneTelemetry`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroneTelemetryData {
+    /// Telemetry point that was just recorded
+    pub drone_telemetry: TelemetryPoint,
+}
+
+impl GraphQLOperation for DroneTelemetry {
+    type Variables = DroneVariables;
+    type ResponseData = DroneTelemetryData;
+
+    const OPERATION_NAME: &'static str = "DroneTelemetry";
+    const QUERY: &'static str = r#"
+        subscription DroneTelemetry($droneId: ID!) {
+            droneTelemetry(droneId: $droneId) {
+                droneId
+                recordedAt
+                position {
+                    altitudeM
+                    speedMps
+                }
+                fuelRemainingPct
+                velocityMps
+                engineTempC
+                extensions
+            }
+        }
+    "#;
+}
+
+/// `sensorTasks(droneId)` subscription
+pub struct SensorTasks;
+
+/// Payload for [`SensorTasks`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorTasksData {
+    /// Task that was just assigned
+    pub sensor_tasks: SensorTaskEvent,
+}
+
+/// `SensorTask` selection
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorTaskEvent {
+    /// Drone ID
+    pub drone_id: String,
+    /// Waypoint sequence number the task applies at
+    pub sequence_number: i32,
+    /// Sensor type (`EO_IR`, `SAR`, `SIGINT`, `LIDAR`)
+    pub sensor_type: String,
+    /// Mode to switch the sensor into
+    pub mode: String,
+    /// Who assigned the task
+    pub assigned_by: String,
+    /// Assignment time
+    pub assigned_at: DateTime<Utc>,
+}
+
+impl GraphQLOperation for SensorTasks {
+    type Variables = DroneVariables;
+    type ResponseData = SensorTasksData;
+
+    const OPERATION_NAME: &'static str = "SensorTasks";
+    const QUERY: &'static str = r#"
+        subscription SensorTasks($droneId: ID!) {
+            sensorTasks(droneId: $droneId) {
+                droneId
+                sequenceNumber
+                sensorType
+                mode
+                assignedBy
+                assignedAt
+            }
+        }
+    "#;
+}
+
+/// `heartbeat` subscription; the server ticks once a second
+pub struct Heartbeat;
+
+/// Payload for [`Heartbeat`]
+#[derive(Debug, Clone, Deserialize)]
+pub struct HeartbeatData {
+    /// Server time as RFC 3339
+    pub heartbeat: String,
+}
+
+impl GraphQLOperation for Heartbeat {
+    type Variables = ();
+    type ResponseData = HeartbeatData;
+
+    const OPERATION_NAME: &'static str = "Heartbeat";
+    const QUERY: &'static str = r#"
+        subscription Heartbeat {
+            heartbeat
+        }
+    "#;
+}
@@ -0,0 +1,163 @@
+//! # WebSocket Protocol
+//!
+//! Messages for the `graphql-transport-ws` subprotocol spoken on
+//! `/graphql/ws`. The socket itself is owned by the caller.
+
+use crate::error::{ClientError, Result};
+use crate::{GraphQLError, GraphQLOperation, GraphQLRequest, GraphQLResponse};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// WebSocket subprotocol name
+pub const SUBPROTOCOL: &str = "graphql-transport-ws";
+
+/// Client-to-server message
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Open the connection, optionally carrying auth
+    ConnectionInit {
+        /// Connection parameters
+        payload: Value,
+    },
+    /// Start a subscription
+    Subscribe {
+        /// Client-chosen subscription ID
+        id: String,
+        /// Operation to run
+        payload: GraphQLRequest<Value>,
+    },
+    /// Stop a subscription
+    Complete {
+        /// Subscription ID
+        id: String,
+    },
+    /// Keep-alive probe
+    Ping,
+    /// Keep-alive reply
+    Pong,
+}
+
+impl ClientMessage {
+    /// Build a `connection_init` message, authenticating with `token` if given
+    pub fn connection_init(token: Option<&str>) -> Self {
+        let payload = match token {
+            Some(token) => serde_json::json!({ "Authorization": format!("Bearer {token}") }),
+            None => serde_json::json!({}),
+        };
+        Self::ConnectionInit { payload }
+    }
+
+    /// Build a `subscribe` message for a typed operation
+    pub fn subscribe<O: GraphQLOperation>(
+        id: impl Into<String>,
+        variables: O::Variables,
+    ) -> Result<Self> {
+        let variables =
+            serde_json::to_value(variables).map_err(|e| ClientError::Decode(e.to_string()))?;
+        Ok(Self::Subscribe {
+            id: id.into(),
+            payload: GraphQLRequest {
+                query: O::QUERY,
+                operation_name: O::OPERATION_NAME,
+                variables,
+            },
+        })
+    }
+
+    /// Serialize for sending over the socket
+    pub fn to_text(&self) -> String {
+        serde_json::to_string(self).expect("client messages always serialize")
+    }
+}
+
+/// Server-to-client message
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Connection accepted
+    ConnectionAck,
+    /// Subscription result
+    Next {
+        /// Subscription ID
+        id: String,
+        /// Execution result
+        payload: GraphQLResponse<Value>,
+    },
+    /// Subscription failed
+    Error {
+        /// Subscription ID
+        id: String,
+        /// Errors reported by the server
+        payload: Vec<GraphQLError>,
+    },
+    /// Subscription finished
+    Complete {
+        /// Subscription ID
+        id: String,
+    },
+    /// Keep-alive probe
+    Ping,
+    /// Keep-alive reply
+    Pong,
+}
+
+impl ServerMessage {
+    /// Parse a text frame
+    pub fn from_text(text: &str) -> Result<Self> {
+        serde_json::from_str(text).map_err(|e| ClientError::Decode(e.to_string()))
+    }
+}
+
+/// Decode a `next` payload into the operation's response data
+pub fn decode_next<O: GraphQLOperation>(payload: GraphQLResponse<Value>) -> Result<O::ResponseData> {
+    let data = payload.into_result()?;
+    serde_json::from_value(data).map_err(|e| ClientError::Decode(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subscriptions::{ConvoyVariables, EngagementEvents};
+
+    #[test]
+    fn test_subscribe_message_shape() {
+        let msg = ClientMessage::subscribe::<EngagementEvents>(
+            "engagement-sub",
+            ConvoyVariables {
+                convoy_id: "c1".to_string(),
+            },
+        )
+        .unwrap();
+        let json: Value = serde_json::from_str(&msg.to_text()).unwrap();
+
+        assert_eq!(json["type"], "subscribe");
+        assert_eq!(json["id"], "engagement-sub");
+        assert_eq!(json["payload"]["variables"]["convoyId"], "c1");
+    }
+
+    #[test]
+    fn test_connection_init_carries_token() {
+        let json: Value =
+            serde_json::from_str(&ClientMessage::connection_init(Some("abc")).to_text()).unwrap();
+        assert_eq!(json["type"], "connection_init");
+        assert_eq!(json["payload"]["Authorization"], "Bearer abc");
+    }
+
+    #[test]
+    fn test_decode_next_payload() {
+        let text = r#"{"type":"next","id":"engagement-sub","payload":{"data":{"engagementEvents":{
+            "convoyId":"c1","droneId":"d1","callsign":"REAPER-01","hit":true,
+            "weaponType":"AGM114_HELLFIRE","newAccuracyPct":75.0,
+            "timestamp":"2026-01-01T00:00:00Z"}}}}"#;
+
+        let ServerMessage::Next { id, payload } = ServerMessage::from_text(text).unwrap() else {
+            panic!("expected next message");
+        };
+        let data = decode_next::<EngagementEvents>(payload).unwrap();
+
+        assert_eq!(id, "engagement-sub");
+        assert_eq!(data.engagement_events.callsign, "REAPER-01");
+        assert!(data.engagement_events.hit);
+    }
+}
@@ -0,0 +1,258 @@
+//! Historical Engagement Importer
+//!
+//! Backfills engagement logs exported by other systems (CSV or JSONL) into
+//! ScyllaDB and the DuckDB analytics store. Rows are validated and mapped
+//! first; rows repeated within the input, or already held by a store, are
+//! skipped, so an interrupted import can simply be run again. `--dry-run`
+//! does the same checks, including against the stores, without writing.
+
+mod mapping;
+mod report;
+mod source;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use clap::Parser;
+use drone_analytics::AnalyticsEngine;
+use drone_domain::Environment;
+use drone_persistence::{ScyllaClient, ScyllaConfig, ScyllaEngagementRepository};
+use mapping::MappedEngagement;
+use report::{Format, ImportReport, RowIssue, StoreSummary};
+use source::{SourceFormat, SourceRow};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tracing::{info, warn};
+use tracing_subscriber::EnvFilter;
+use uuid::Uuid;
+
+#[derive(Parser, Debug)]
+#[command(name = "drone-importer")]
+#[command(about = "Import historical engagement logs into ScyllaDB and analytics")]
+struct Args {
+    /// Engagement logs (`.csv`, `.jsonl` or `.ndjson`)
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Source format, overriding the file extension
+    #[arg(long, value_enum)]
+    source_format: Option<SourceFormat>,
+
+    /// Environment the engagements belong to (LIVE, EXERCISE or TEST)
+    #[arg(long, default_value = "LIVE")]
+    environment: Environment,
+
+    /// Validate and check for duplicates without writing
+    #[arg(long)]
+    dry_run: bool,
+
+    /// ScyllaDB contact points
+    #[arg(long, env = "SCYLLA_HOSTS", default_value = "127.0.0.1:9042", value_delimiter = ',')]
+    scylla_hosts: Vec<String>,
+
+    /// ScyllaDB keyspace
+    #[arg(long, env = "SCYLLA_KEYSPACE", default_value = "drone_ops")]
+    scylla_keyspace: String,
+
+    /// ScyllaDB username
+    #[arg(long, env = "SCYLLA_USERNAME")]
+    scylla_username: Option<String>,
+
+    /// ScyllaDB password
+    #[arg(long, env = "SCYLLA_PASSWORD", hide_env_values = true)]
+    scylla_password: Option<String>,
+
+    /// Leave ScyllaDB untouched
+    #[arg(long)]
+    no_scylla: bool,
+
+    /// DuckDB analytics database; analytics is skipped when unset
+    #[arg(long, env = "ANALYTICS_DB_PATH")]
+    analytics_db: Option<PathBuf>,
+
+    /// Engagements checked and written in ScyllaDB at once
+    #[arg(long, default_value = "32")]
+    concurrency: usize,
+
+    /// Report format
+    #[arg(long, value_enum, default_value = "json")]
+    format: Format,
+
+    /// Write the report here instead of stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Log to stderr so stdout carries only the report
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            EnvFilter::from_default_env().add_directive("drone_importer=info".parse()?),
+        )
+        .init();
+
+    let args = Args::parse();
+    anyhow::ensure!(
+        args.dry_run || !args.no_scylla || args.analytics_db.is_some(),
+        "nothing to import into: drop --no-scylla or pass --analytics-db"
+    );
+
+    let mut report = ImportReport::new(args.dry_run, args.environment);
+    let batch = read_inputs(&args, &mut report)?;
+    info!(
+        "{} rows: {} to import, {} invalid, {} duplicates",
+        report.rows,
+        report.valid,
+        report.invalid.len(),
+        report.duplicates.len()
+    );
+
+    if !args.no_scylla {
+        let client = ScyllaClient::new(ScyllaConfig {
+            hosts: args.scylla_hosts.clone(),
+            keyspace: args.scylla_keyspace.clone(),
+            username: args.scylla_username.clone(),
+            password: args.scylla_password.clone(),
+            ..ScyllaConfig::default()
+        })
+        .await
+        .context("connecting to ScyllaDB")?;
+        let repo = Arc::new(ScyllaEngagementRepository::new(Arc::new(client)));
+        let summary = import_scylla(&repo, &batch, args.concurrency, args.dry_run).await;
+        info!("ScyllaDB: {summary:?}");
+        report.scylla = Some(summary);
+    }
+
+    if let Some(path) = &args.analytics_db {
+        let engine = AnalyticsEngine::new_persistent(path)
+            .with_context(|| format!("opening analytics database {}", path.display()))?;
+        let summary = import_analytics(&engine, &batch, args.dry_run);
+        info!("Analytics: {summary:?}");
+        report.analytics = Some(summary);
+    }
+
+    let rendered = report.render(args.format);
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, rendered)?;
+            info!("Report written to {}", path.display());
+        }
+        None => println!("{rendered}"),
+    }
+
+    let failed = report.failed();
+    anyhow::ensure!(failed == 0, "{failed} engagements failed to import; run again to retry them");
+    Ok(())
+}
+
+/// Read, validate and map every input row, recording rejects and
+/// duplicates in `report`
+fn read_inputs(args: &Args, report: &mut ImportReport) -> Result<Vec<MappedEngagement>> {
+    let now = Utc::now();
+    let mut batch = Vec::new();
+    let mut seen: HashMap<Uuid, (String, u64)> = HashMap::new();
+
+    for path in &args.inputs {
+        let name = path.display().to_string();
+        let format = args
+            .source_format
+            .or_else(|| SourceFormat::from_path(path))
+            .with_context(|| format!("cannot tell the format of {name}; pass --source-format"))?;
+        let file = File::open(path).with_context(|| format!("opening {name}"))?;
+        let rows = source::read_rows(file, format).with_context(|| format!("reading {name}"))?;
+        info!("{name}: {} rows", rows.len());
+
+        report.files.push(name.clone());
+        report.rows += rows.len();
+        for SourceRow { line, row } in rows {
+            let mapped = row.and_then(|row| mapping::map_row(&row, args.environment, now));
+            match mapped {
+                Ok(mapped) => {
+                    let id = mapped.record.engagement_id;
+                    if let Some((file, first)) = seen.get(&id) {
+                        let reason = format!("duplicate of {file}:{first}");
+                        report.duplicates.push(RowIssue::new(&name, line, reason));
+                    } else {
+                        seen.insert(id, (name.clone(), line));
+                        batch.push(mapped);
+                    }
+                }
+                Err(reason) => report.invalid.push(RowIssue::new(&name, line, reason)),
+            }
+        }
+    }
+
+    report.valid = batch.len();
+    Ok(batch)
+}
+
+/// Write the engagements ScyllaDB does not already hold
+async fn import_scylla(
+    repo: &Arc<ScyllaEngagementRepository>,
+    batch: &[MappedEngagement],
+    concurrency: usize,
+    dry_run: bool,
+) -> StoreSummary {
+    let mut summary = StoreSummary::default();
+    for chunk in batch.chunks(concurrency.max(1)) {
+        let mut writes = JoinSet::new();
+        for mapped in chunk {
+            let (repo, engagement) = (repo.clone(), mapped.engagement.clone());
+            writes.spawn(async move {
+                let id = engagement.engagement_id;
+                let existing = match repo.get(engagement.convoy_id, id).await {
+                    Ok(found) if found.is_some() => Ok(true),
+                    Ok(_) if dry_run => Ok(false),
+                    Ok(_) => repo.record(&engagement).await.map(|()| false),
+                    Err(err) => Err(err),
+                };
+                (id, existing)
+            });
+        }
+        while let Some(joined) = writes.join_next().await {
+            match joined {
+                Ok((_, Ok(true))) => summary.existing += 1,
+                Ok((_, Ok(false))) => summary.written += 1,
+                Ok((id, Err(err))) => {
+                    warn!("ScyllaDB write of {id} failed: {err}");
+                    summary.failed += 1;
+                }
+                Err(err) => {
+                    warn!("ScyllaDB write task failed: {err}");
+                    summary.failed += 1;
+                }
+            }
+        }
+    }
+    summary
+}
+
+/// Ingest the engagements the analytics store does not already hold
+fn import_analytics(
+    engine: &AnalyticsEngine,
+    batch: &[MappedEngagement],
+    dry_run: bool,
+) -> StoreSummary {
+    let mut summary = StoreSummary::default();
+    for MappedEngagement { record, .. } in batch {
+        let ingested = engine.has_engagement(record.engagement_id).and_then(|exists| {
+            if !exists && !dry_run {
+                engine.ingest_engagement(record)?;
+            }
+            Ok(exists)
+        });
+        match ingested {
+            Ok(true) => summary.existing += 1,
+            Ok(false) => summary.written += 1,
+            Err(err) => {
+                warn!("Analytics ingest of {} failed: {err}", record.engagement_id);
+                summary.failed += 1;
+            }
+        }
+    }
+    summary
+}
@@ -0,0 +1,281 @@
+//! # Mapping
+//!
+//! Validates a [`HistoricalEngagement`] and maps it onto the domain
+//! engagement written to ScyllaDB and the record ingested by the analytics
+//! store. Every problem with a row is reported, not just the first.
+//!
+//! Rows without an engagement ID get one derived from the convoy, drone,
+//! time and weapon, so importing the same log twice yields the same IDs and
+//! the second run finds them as duplicates.
+
+use chrono::{DateTime, Utc};
+use drone_analytics::engine::EngagementRecord;
+use drone_domain::{
+    CollateralRisk, Coordinates, DamageAssessment, Engagement, EngagementResult, Environment,
+    PlatformType, TargetInfo, TargetType, ThreatLevel, WeaponType,
+};
+use serde::de::DeserializeOwned;
+use uuid::Uuid;
+
+use crate::source::HistoricalEngagement;
+
+/// A validated row, ready for both stores
+#[derive(Debug, Clone)]
+pub struct MappedEngagement {
+    pub engagement: Engagement,
+    pub record: EngagementRecord,
+}
+
+/// Validate `row` and map it for import
+///
+/// # Errors
+///
+/// Returns every validation failure, joined with `; `.
+pub fn map_row(
+    row: &HistoricalEngagement,
+    environment: Environment,
+    now: DateTime<Utc>,
+) -> Result<MappedEngagement, String> {
+    let mut problems = Vec::new();
+
+    let platform_type = PlatformType::parse(row.platform_type.trim());
+    if platform_type.is_none() {
+        problems.push(format!("unknown platform `{}`", row.platform_type));
+    }
+    let weapon_type = parse_enum::<WeaponType>(&row.weapon_type);
+    if weapon_type.is_none() {
+        problems.push(format!("unknown weapon `{}`", row.weapon_type));
+    }
+    let target_type = match row.target_type.as_deref().filter(|t| !t.trim().is_empty()) {
+        Some(target) => {
+            let parsed = parse_enum::<TargetType>(target);
+            if parsed.is_none() {
+                problems.push(format!("unknown target type `{target}`"));
+            }
+            parsed
+        }
+        None => Some(TargetType::Vehicle),
+    };
+    if row.callsign.trim().is_empty() {
+        problems.push("callsign is empty".to_string());
+    }
+    if row.engaged_at > now {
+        problems.push(format!("engaged_at {} is in the future", row.engaged_at.to_rfc3339()));
+    }
+    if row.predicted_pk.is_some_and(|pk| !(0.0..=1.0).contains(&pk)) {
+        problems.push("predicted_pk must be between 0 and 1".to_string());
+    }
+    if row.range_km.is_some_and(|range| !range.is_finite() || range < 0.0) {
+        problems.push("range_km must not be negative".to_string());
+    }
+    let shooter = position(row.shooter_lat, row.shooter_lon, "shooter", &mut problems);
+    let target = position(row.target_lat, row.target_lon, "target", &mut problems);
+
+    let (Some(platform_type), Some(weapon_type), Some(target_type)) =
+        (platform_type, weapon_type, target_type)
+    else {
+        return Err(problems.join("; "));
+    };
+    if !problems.is_empty() {
+        return Err(problems.join("; "));
+    }
+
+    let mut shooter = shooter.unwrap_or_default();
+    if let Some(altitude_m) = row.shooter_alt_m {
+        shooter.altitude_m = altitude_m;
+    }
+    let target = target.unwrap_or(shooter);
+    let range_km = row.range_km.unwrap_or_else(|| match (row.shooter_lat, row.target_lat) {
+        (Some(_), Some(_)) => shooter.distance_to_km(&target).0,
+        _ => 0.0,
+    });
+    let engagement_id = row.engagement_id.unwrap_or_else(|| derived_id(row));
+
+    let engagement = Engagement {
+        convoy_id: row.convoy_id,
+        engaged_at: row.engaged_at,
+        engagement_id,
+        drone_id: row.drone_id,
+        drone_callsign: row.callsign.trim().to_string(),
+        weapon_type,
+        weapon_serial: String::new(),
+        target: TargetInfo {
+            // Source systems' target IDs do not exist here
+            target_id: Uuid::nil(),
+            target_type,
+            coordinates: target,
+            confidence: 1.0,
+            threat_level: ThreatLevel::Unknown,
+        },
+        authorization_code: row.authorization_code.clone().unwrap_or_default(),
+        authorized_by: row.authorized_by.clone().unwrap_or_default(),
+        roe_compliance: true,
+        result: EngagementResult {
+            impact_time: row.engaged_at,
+            impact_coords: target,
+            damage_assessment: if row.hit {
+                DamageAssessment::PendingBda
+            } else {
+                DamageAssessment::Missed
+            },
+            collateral_risk: CollateralRisk::None,
+        },
+        hit: row.hit,
+        waypoint_number: 0,
+        shooter_position: shooter,
+        range_to_target_km: range_km as f32,
+        predicted_pk: row.predicted_pk.map(|pk| pk as f32),
+        bda_status: row.bda_status.clone().unwrap_or_else(|| "PENDING".to_string()),
+        bda_notes: None,
+        sequence: 0,
+    };
+    let record = EngagementRecord {
+        engagement_id,
+        convoy_id: row.convoy_id,
+        drone_id: row.drone_id,
+        callsign: engagement.drone_callsign.clone(),
+        platform_type: enum_name(&platform_type),
+        hit: row.hit,
+        weapon_type: enum_name(&weapon_type),
+        target_type: Some(enum_name(&target_type)),
+        range_km: Some(range_km),
+        altitude_m: row.shooter_alt_m,
+        timestamp: row.engaged_at,
+        predicted_pk: row.predicted_pk,
+        environment,
+    };
+
+    Ok(MappedEngagement { engagement, record })
+}
+
+/// Parse an enum from its wire name, also accepting the hyphenated
+/// display names other systems log (`AGM-114_HELLFIRE`)
+fn parse_enum<T: DeserializeOwned>(value: &str) -> Option<T> {
+    let name = value.trim().replace('-', "").replace(' ', "_").to_ascii_uppercase();
+    serde_json::from_value(serde_json::Value::String(name)).ok()
+}
+
+/// Wire name of an enum, as the analytics store keeps it
+fn enum_name<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn position(
+    lat: Option<f64>,
+    lon: Option<f64>,
+    name: &str,
+    problems: &mut Vec<String>,
+) -> Option<Coordinates> {
+    match (lat, lon) {
+        (Some(lat), Some(lon)) => {
+            if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) {
+                Some(Coordinates::new(lat, lon, 0.0))
+            } else {
+                problems.push(format!("{name} position ({lat}, {lon}) is out of range"));
+                None
+            }
+        }
+        (None, None) => None,
+        _ => {
+            problems.push(format!("{name} position needs both latitude and longitude"));
+            None
+        }
+    }
+}
+
+/// Stable ID for a row without one
+fn derived_id(row: &HistoricalEngagement) -> Uuid {
+    // FNV-1a, 128-bit: stable across builds, unlike `DefaultHasher`
+    const OFFSET: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+    const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+    let key = format!(
+        "{}|{}|{}|{}",
+        row.convoy_id,
+        row.drone_id,
+        row.engaged_at.timestamp_millis(),
+        row.weapon_type.trim().to_ascii_uppercase()
+    );
+    let hash = key
+        .bytes()
+        .fold(OFFSET, |hash, byte| (hash ^ u128::from(byte)).wrapping_mul(PRIME));
+    Uuid::from_u128(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row() -> HistoricalEngagement {
+        HistoricalEngagement {
+            engagement_id: None,
+            convoy_id: Uuid::from_u128(1),
+            drone_id: Uuid::from_u128(2),
+            callsign: " REAPER-01 ".to_string(),
+            platform_type: "MQ-9_REAPER".to_string(),
+            engaged_at: "2024-03-01T10:00:00Z".parse().unwrap(),
+            weapon_type: "AGM-114_HELLFIRE".to_string(),
+            target_type: Some("air defense".to_string()),
+            hit: true,
+            range_km: None,
+            shooter_lat: Some(34.5),
+            shooter_lon: Some(69.2),
+            shooter_alt_m: Some(6000.0),
+            target_lat: Some(34.55),
+            target_lon: Some(69.2),
+            predicted_pk: Some(0.8),
+            bda_status: None,
+            authorization_code: Some("AUTH-1".to_string()),
+            authorized_by: None,
+        }
+    }
+
+    #[test]
+    fn test_maps_legacy_names_and_derives_range() {
+        let mapped = map_row(&row(), Environment::Exercise, Utc::now()).unwrap();
+        assert_eq!(mapped.engagement.weapon_type, WeaponType::Agm114Hellfire);
+        assert_eq!(mapped.engagement.target.target_type, TargetType::AirDefense);
+        assert_eq!(mapped.engagement.drone_callsign, "REAPER-01");
+        assert!((mapped.engagement.range_to_target_km - 5.56).abs() < 0.01);
+
+        assert_eq!(mapped.record.platform_type, "MQ9_REAPER");
+        assert_eq!(mapped.record.weapon_type, "AGM114_HELLFIRE");
+        assert_eq!(mapped.record.target_type.as_deref(), Some("AIR_DEFENSE"));
+        assert_eq!(mapped.record.altitude_m, Some(6000.0));
+        assert_eq!(mapped.record.environment, Environment::Exercise);
+    }
+
+    #[test]
+    fn test_derived_ids_are_stable() {
+        let first = map_row(&row(), Environment::Live, Utc::now()).unwrap();
+        let again = map_row(&row(), Environment::Live, Utc::now()).unwrap();
+        assert_eq!(first.engagement.engagement_id, again.engagement.engagement_id);
+
+        let mut other = row();
+        other.engaged_at += chrono::Duration::seconds(1);
+        let other = map_row(&other, Environment::Live, Utc::now()).unwrap();
+        assert_ne!(first.engagement.engagement_id, other.engagement.engagement_id);
+
+        let mut given = row();
+        given.engagement_id = Some(Uuid::from_u128(9));
+        let given = map_row(&given, Environment::Live, Utc::now()).unwrap();
+        assert_eq!(given.record.engagement_id, Uuid::from_u128(9));
+    }
+
+    #[test]
+    fn test_reports_every_problem() {
+        let mut bad = row();
+        bad.weapon_type = "LASER".to_string();
+        bad.predicted_pk = Some(1.5);
+        bad.target_lon = None;
+        bad.engaged_at = Utc::now() + chrono::Duration::hours(1);
+
+        let err = map_row(&bad, Environment::Live, Utc::now()).unwrap_err();
+        assert!(err.contains("unknown weapon `LASER`"));
+        assert!(err.contains("predicted_pk"));
+        assert!(err.contains("target position needs both"));
+        assert!(err.contains("in the future"));
+    }
+}
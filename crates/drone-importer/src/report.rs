@@ -0,0 +1,208 @@
+//! # Import Report
+//!
+//! What an import read, rejected, skipped and wrote, as JSON or Markdown.
+//! A dry run produces the same report, counting the rows it would write.
+
+use chrono::{DateTime, Utc};
+use drone_domain::Environment;
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Most rejected rows listed in the Markdown report
+const MARKDOWN_ISSUE_LIMIT: usize = 50;
+
+/// Output format for the report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Machine-readable JSON
+    Json,
+    /// Markdown for change tickets
+    Markdown,
+}
+
+/// A row left out of the import
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RowIssue {
+    pub file: String,
+    /// 1-based line in the file
+    pub line: u64,
+    pub reason: String,
+}
+
+impl RowIssue {
+    pub fn new(file: &str, line: u64, reason: impl Into<String>) -> Self {
+        Self {
+            file: file.to_string(),
+            line,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Outcome for one store
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct StoreSummary {
+    /// Engagements the store already held
+    pub existing: usize,
+    /// Engagements written, or that would be on a dry run
+    pub written: usize,
+    /// Engagements that could not be checked or written
+    pub failed: usize,
+}
+
+/// Outcome of an import run
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub started_at: DateTime<Utc>,
+    pub dry_run: bool,
+    /// Environment the engagements were imported into
+    pub environment: Environment,
+    pub files: Vec<String>,
+    /// Rows read across all files
+    pub rows: usize,
+    /// Rows that passed validation, less duplicates within the input
+    pub valid: usize,
+    /// Rows that failed to parse or validate
+    pub invalid: Vec<RowIssue>,
+    /// Rows repeating an engagement earlier in the input
+    pub duplicates: Vec<RowIssue>,
+    /// `None` when ScyllaDB was skipped
+    pub scylla: Option<StoreSummary>,
+    /// `None` when no analytics database was given
+    pub analytics: Option<StoreSummary>,
+}
+
+impl ImportReport {
+    #[must_use]
+    pub fn new(dry_run: bool, environment: Environment) -> Self {
+        Self {
+            started_at: Utc::now(),
+            dry_run,
+            environment,
+            files: Vec::new(),
+            rows: 0,
+            valid: 0,
+            invalid: Vec::new(),
+            duplicates: Vec::new(),
+            scylla: None,
+            analytics: None,
+        }
+    }
+
+    /// Engagements either store failed to check or write
+    #[must_use]
+    pub fn failed(&self) -> usize {
+        [self.scylla, self.analytics].iter().flatten().map(|s| s.failed).sum()
+    }
+
+    /// Render in `format`
+    #[must_use]
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Json => serde_json::to_string_pretty(self).expect("reports always serialize"),
+            Format::Markdown => self.to_markdown(),
+        }
+    }
+
+    /// Render as Markdown
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let mode = if self.dry_run { " (dry run)" } else { "" };
+        let _ = writeln!(out, "## Engagement import{mode}");
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "Started {} | {} into {} | {} rows, {} valid, {} invalid, {} duplicates",
+            self.started_at.to_rfc3339(),
+            self.files.join(", "),
+            self.environment.as_str(),
+            self.rows,
+            self.valid,
+            self.invalid.len(),
+            self.duplicates.len()
+        );
+        let _ = writeln!(out);
+        let written = if self.dry_run { "Would write" } else { "Written" };
+        let _ = writeln!(out, "| Store | Existing | {written} | Failed |");
+        let _ = writeln!(out, "|---|---:|---:|---:|");
+        for (name, store) in [("ScyllaDB", self.scylla), ("Analytics", self.analytics)] {
+            match store {
+                Some(s) => {
+                    let _ = writeln!(
+                        out,
+                        "| {name} | {} | {} | {} |",
+                        s.existing, s.written, s.failed
+                    );
+                }
+                None => {
+                    let _ = writeln!(out, "| {name} | skipped | | |");
+                }
+            }
+        }
+
+        for (title, issues) in [("Invalid rows", &self.invalid), ("Duplicates", &self.duplicates)] {
+            if issues.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out);
+            let _ = writeln!(out, "### {title}");
+            let _ = writeln!(out);
+            for issue in issues.iter().take(MARKDOWN_ISSUE_LIMIT) {
+                let _ = writeln!(out, "- `{}:{}` {}", issue.file, issue.line, issue.reason);
+            }
+            if issues.len() > MARKDOWN_ISSUE_LIMIT {
+                let _ = writeln!(out, "- ...and {} more", issues.len() - MARKDOWN_ISSUE_LIMIT);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> ImportReport {
+        let mut report = ImportReport::new(true, Environment::Exercise);
+        report.started_at = DateTime::UNIX_EPOCH;
+        report.files = vec!["march.csv".to_string()];
+        report.rows = 4;
+        report.valid = 2;
+        report.invalid = vec![RowIssue::new("march.csv", 3, "unknown weapon `LASER`")];
+        report.duplicates = vec![RowIssue::new("march.csv", 5, "duplicate of march.csv:2")];
+        report.scylla = Some(StoreSummary {
+            existing: 1,
+            written: 1,
+            failed: 0,
+        });
+        report
+    }
+
+    #[test]
+    fn test_markdown_lists_stores_and_issues() {
+        let markdown = report().to_markdown();
+        assert!(markdown.starts_with("## Engagement import (dry run)"));
+        assert!(markdown.contains("| Store | Existing | Would write | Failed |"));
+        assert!(markdown.contains("| ScyllaDB | 1 | 1 | 0 |"));
+        assert!(markdown.contains("| Analytics | skipped | | |"));
+        assert!(markdown.contains("- `march.csv:3` unknown weapon `LASER`"));
+        assert!(markdown.contains("### Duplicates"));
+    }
+
+    #[test]
+    fn test_json_report_and_failures() {
+        let mut report = report();
+        let json: serde_json::Value = serde_json::from_str(&report.render(Format::Json)).unwrap();
+        assert_eq!(json["environment"], "EXERCISE");
+        assert_eq!(json["scylla"]["written"], 1);
+        assert!(json["analytics"].is_null());
+
+        assert_eq!(report.failed(), 0);
+        report.analytics = Some(StoreSummary {
+            failed: 2,
+            ..StoreSummary::default()
+        });
+        assert_eq!(report.failed(), 2);
+    }
+}
@@ -0,0 +1,187 @@
+//! # Source Logs
+//!
+//! Engagement logs exported by other systems. CSV files need a header row
+//! naming the [`HistoricalEngagement`] fields (in any order, extra columns
+//! ignored); JSONL files hold one object per line. Rows that cannot be
+//! parsed are kept with the reason so the report can point at them.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Layout of a source log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SourceFormat {
+    /// Comma-separated values with a header row
+    Csv,
+    /// One JSON object per line
+    Jsonl,
+}
+
+impl SourceFormat {
+    /// Format implied by a file extension (`.csv`, `.jsonl` or `.ndjson`)
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+}
+
+/// One engagement as logged by the source system
+///
+/// Enum-valued fields are free text here; they are checked when the row
+/// is mapped (see [`crate::mapping`]).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct HistoricalEngagement {
+    /// Source engagement ID; derived from the row when absent
+    #[serde(default)]
+    pub engagement_id: Option<Uuid>,
+    pub convoy_id: Uuid,
+    pub drone_id: Uuid,
+    pub callsign: String,
+    /// e.g. `MQ9_REAPER` or `MQ-9_REAPER`
+    pub platform_type: String,
+    pub engaged_at: DateTime<Utc>,
+    /// e.g. `AGM114_HELLFIRE` or `AGM-114_HELLFIRE`
+    pub weapon_type: String,
+    #[serde(default)]
+    pub target_type: Option<String>,
+    pub hit: bool,
+    /// Slant range; computed from the positions when absent
+    #[serde(default)]
+    pub range_km: Option<f64>,
+    #[serde(default)]
+    pub shooter_lat: Option<f64>,
+    #[serde(default)]
+    pub shooter_lon: Option<f64>,
+    #[serde(default)]
+    pub shooter_alt_m: Option<f64>,
+    #[serde(default)]
+    pub target_lat: Option<f64>,
+    #[serde(default)]
+    pub target_lon: Option<f64>,
+    /// Pk predicted before the shot (0-1)
+    #[serde(default)]
+    pub predicted_pk: Option<f64>,
+    #[serde(default)]
+    pub bda_status: Option<String>,
+    #[serde(default)]
+    pub authorization_code: Option<String>,
+    #[serde(default)]
+    pub authorized_by: Option<String>,
+}
+
+/// A source row, or why it could not be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceRow {
+    /// 1-based line in the source file
+    pub line: u64,
+    pub row: Result<HistoricalEngagement, String>,
+}
+
+/// Read every row of a source log
+///
+/// # Errors
+///
+/// Fails only if the input cannot be read (or a CSV header is missing);
+/// malformed rows are returned as [`SourceRow`] errors.
+pub fn read_rows(input: impl Read, format: SourceFormat) -> std::io::Result<Vec<SourceRow>> {
+    match format {
+        SourceFormat::Csv => read_csv(input),
+        SourceFormat::Jsonl => read_jsonl(input),
+    }
+}
+
+fn read_csv(input: impl Read) -> std::io::Result<Vec<SourceRow>> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(input);
+    let headers = reader.headers().map_err(std::io::Error::other)?.clone();
+
+    let mut rows = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        // Header is line 1; fall back to the record count if unknown
+        let fallback = index as u64 + 2;
+        match record {
+            Ok(record) => rows.push(SourceRow {
+                line: record.position().map_or(fallback, csv::Position::line),
+                row: record.deserialize(Some(&headers)).map_err(|e| e.to_string()),
+            }),
+            Err(e) => rows.push(SourceRow {
+                line: e.position().map_or(fallback, csv::Position::line),
+                row: Err(e.to_string()),
+            }),
+        }
+    }
+    Ok(rows)
+}
+
+fn read_jsonl(input: impl Read) -> std::io::Result<Vec<SourceRow>> {
+    let mut rows = Vec::new();
+    for (index, line) in BufReader::new(input).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        rows.push(SourceRow {
+            line: index as u64 + 1,
+            row: serde_json::from_str(&line).map_err(|e| e.to_string()),
+        });
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONVOY: &str = "6f1c1c1e-2b41-4c0e-9a43-1e0c9d4f0a01";
+    const DRONE: &str = "0b7f3c52-7d0e-4d6a-8f0e-3b1a2c4d5e6f";
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(SourceFormat::from_path(Path::new("a/b.CSV")), Some(SourceFormat::Csv));
+        assert_eq!(SourceFormat::from_path(Path::new("log.ndjson")), Some(SourceFormat::Jsonl));
+        assert_eq!(SourceFormat::from_path(Path::new("log.txt")), None);
+    }
+
+    #[test]
+    fn test_csv_rows_with_optional_columns() {
+        let csv = format!(
+            "convoy_id,drone_id,callsign,platform_type,engaged_at,weapon_type,hit,range_km,notes\n\
+             {CONVOY},{DRONE},REAPER-01,MQ-9_REAPER,2024-03-01T10:00:00Z,AGM114_HELLFIRE,true,,x\n\
+             {CONVOY},not-a-uuid,REAPER-02,MQ9_REAPER,2024-03-01T10:05:00Z,GBU12_PAVEWAY,\
+             false,4.2,\n"
+        );
+        let rows = read_rows(csv.as_bytes(), SourceFormat::Csv).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let first = rows[0].row.as_ref().unwrap();
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(first.callsign, "REAPER-01");
+        assert_eq!(first.range_km, None);
+        assert!(first.hit);
+        assert_eq!(rows[1].line, 3);
+        assert!(rows[1].row.is_err());
+    }
+
+    #[test]
+    fn test_jsonl_skips_blank_lines() {
+        let jsonl = format!(
+            "{{\"convoy_id\":\"{CONVOY}\",\"drone_id\":\"{DRONE}\",\"callsign\":\"GRAY-01\",\
+             \"platform_type\":\"MQ1C_GRAY_EAGLE\",\"engaged_at\":\"2024-03-01T10:00:00Z\",\
+             \"weapon_type\":\"AGM176_GRIFFIN\",\"hit\":false,\"predicted_pk\":0.7}}\n\
+             \n\
+             {{\"convoy_id\":\"{CONVOY}\"}}\n"
+        );
+        let rows = read_rows(jsonl.as_bytes(), SourceFormat::Jsonl).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].row.as_ref().unwrap().predicted_pk, Some(0.7));
+        assert_eq!(rows[1].line, 3);
+        assert!(rows[1].row.as_ref().unwrap_err().contains("missing field"));
+    }
+}
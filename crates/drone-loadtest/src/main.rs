@@ -0,0 +1,178 @@
+//! Drone GraphQL API Load Tester
+//!
+//! Drives a mixed workload of engagement mutations, leaderboard queries and
+//! subscriptions against a running API and summarizes latency percentiles
+//! and subscription delivery lag for performance regression tracking.
+
+mod report;
+mod stats;
+mod subscriber;
+mod workload;
+
+use anyhow::Result;
+use chrono::Utc;
+use clap::Parser;
+use drone_graphql_client::GraphQLClient;
+use report::{Format, SubscriptionSummary, Summary};
+use stats::LatencyRecorder;
+use std::path::PathBuf;
+use std::time::Duration;
+use subscriber::Subscriber;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use tracing::{info, warn};
+use tracing_subscriber::EnvFilter;
+use uuid::Uuid;
+use workload::Workload;
+
+#[derive(Parser, Debug)]
+#[command(name = "drone-loadtest")]
+#[command(about = "Load test the drone convoy GraphQL API")]
+struct Args {
+    /// API endpoint
+    #[arg(long, default_value = "http://localhost:8080/graphql")]
+    api_url: String,
+
+    /// Subscription endpoint
+    #[arg(long, default_value = "ws://localhost:8080/graphql/ws")]
+    ws_url: String,
+
+    /// Bearer token for the API and subscriptions
+    #[arg(long, env = "DRONE_LOADTEST_TOKEN")]
+    token: Option<String>,
+
+    /// Convoy to target; a fresh ID when unset
+    #[arg(long)]
+    convoy_id: Option<Uuid>,
+
+    /// Drones to spread engagements across
+    #[arg(long, default_value = "8")]
+    drones: usize,
+
+    /// recordEngagement mutations per second
+    #[arg(long, default_value = "50")]
+    engagements_per_sec: f64,
+
+    /// Leaderboard queries per second
+    #[arg(long, default_value = "20")]
+    leaderboard_per_sec: f64,
+
+    /// Concurrent engagementEvents subscriptions
+    #[arg(long, default_value = "10")]
+    subscriptions: usize,
+
+    /// Run length in seconds
+    #[arg(long, default_value = "30")]
+    duration: u64,
+
+    /// Extra seconds to keep subscriptions open for late events
+    #[arg(long, default_value = "2")]
+    drain: u64,
+
+    /// Summary format
+    #[arg(long, value_enum, default_value = "json")]
+    format: Format,
+
+    /// Write the summary here instead of stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Log to stderr so stdout carries only the summary
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(EnvFilter::from_default_env().add_directive("drone_loadtest=info".parse()?))
+        .init();
+
+    let args = Args::parse();
+    anyhow::ensure!(args.drones > 0, "--drones must be at least 1");
+
+    let workload = Workload {
+        engagements_per_sec: args.engagements_per_sec,
+        leaderboard_per_sec: args.leaderboard_per_sec,
+        subscriptions: args.subscriptions,
+        duration_secs: args.duration,
+        convoy_id: args.convoy_id.unwrap_or_else(Uuid::new_v4).to_string(),
+        drones: args.drones,
+    };
+    let drone_ids: Vec<String> = (0..workload.drones).map(|_| Uuid::new_v4().to_string()).collect();
+
+    let mut client = GraphQLClient::new(&args.api_url);
+    if let Some(token) = &args.token {
+        client = client.with_bearer_token(token);
+    }
+
+    info!("Target: {} ({})", args.api_url, args.ws_url);
+    info!(
+        "Workload: {} recordEngagement/s, {} leaderboard/s, {} subscriptions for {}s on convoy {}",
+        workload.engagements_per_sec,
+        workload.leaderboard_per_sec,
+        workload.subscriptions,
+        workload.duration_secs,
+        workload.convoy_id
+    );
+
+    // Open every subscription before load starts so none miss early events
+    let mut connecting = JoinSet::new();
+    for _ in 0..workload.subscriptions {
+        let (url, token, convoy_id) = (args.ws_url.clone(), args.token.clone(), workload.convoy_id.clone());
+        connecting.spawn(async move { Subscriber::connect(&url, token.as_deref(), &convoy_id).await });
+    }
+    let mut subscribers = Vec::new();
+    let mut lag = LatencyRecorder::default();
+    while let Some(joined) = connecting.join_next().await {
+        match joined? {
+            Ok(subscriber) => subscribers.push(subscriber),
+            Err(err) => {
+                warn!("Subscription failed: {:#}", err);
+                lag.record_error();
+            }
+        }
+    }
+    let connected = subscribers.len();
+    info!("Subscriptions connected: {}/{}", connected, workload.subscriptions);
+
+    let started_at = Utc::now();
+    let start = Instant::now();
+    let collect_until = start + workload.duration() + Duration::from_secs(args.drain);
+    let collectors: Vec<_> = subscribers
+        .into_iter()
+        .map(|s| tokio::spawn(s.collect_until(collect_until)))
+        .collect();
+
+    let (mutations, queries) = tokio::join!(
+        workload::record_engagements(client.clone(), &workload, drone_ids),
+        workload::query_leaderboard(client, &workload),
+    );
+    let elapsed = start.elapsed();
+    for collector in collectors {
+        lag.merge(collector.await?);
+    }
+
+    let summary = Summary {
+        target: args.api_url,
+        started_at,
+        elapsed_secs: elapsed.as_secs_f64(),
+        record_engagement: mutations.summarize(elapsed),
+        leaderboard: queries.summarize(elapsed),
+        subscriptions: SubscriptionSummary {
+            requested: workload.subscriptions,
+            connected,
+            delivery_lag: lag.summarize(elapsed),
+        },
+        workload,
+    };
+
+    let rendered = summary.render(args.format);
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, rendered)?;
+            info!("Summary written to {}", path.display());
+        }
+        None => println!("{rendered}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+//! # Run Summary
+//!
+//! JSON and Markdown renderings of a load test, for diffing between builds.
+
+use crate::stats::LatencySummary;
+use crate::workload::Workload;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Output format for the summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Machine-readable JSON
+    Json,
+    /// Markdown tables for PR comments
+    Markdown,
+}
+
+/// Outcome of a load test run
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+    /// GraphQL HTTP endpoint under test
+    pub target: String,
+    /// When the run started
+    pub started_at: DateTime<Utc>,
+    /// Measured wall-clock duration
+    pub elapsed_secs: f64,
+    /// Offered load
+    pub workload: Workload,
+    /// `recordEngagement` mutation latency
+    pub record_engagement: LatencySummary,
+    /// `leaderboard` query latency
+    pub leaderboard: LatencySummary,
+    /// `engagementEvents` subscriptions
+    pub subscriptions: SubscriptionSummary,
+}
+
+/// Subscription delivery results
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscriptionSummary {
+    /// Connections requested
+    pub requested: usize,
+    /// Connections acknowledged by the server
+    pub connected: usize,
+    /// Delivery lag from server event timestamp to receipt; errors count
+    /// connections that failed or closed early
+    pub delivery_lag: LatencySummary,
+}
+
+impl Summary {
+    /// Render in `format`
+    #[must_use]
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Json => {
+                serde_json::to_string_pretty(self).expect("summaries always serialize")
+            }
+            Format::Markdown => self.to_markdown(),
+        }
+    }
+
+    /// Render as Markdown
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let w = &self.workload;
+        let mut out = String::new();
+        let _ = writeln!(out, "## Load test: {}", self.target);
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "Started {} | {:.1}s | {} recordEngagement/s, {} leaderboard/s, {} subscriptions",
+            self.started_at.to_rfc3339(),
+            self.elapsed_secs,
+            w.engagements_per_sec,
+            w.leaderboard_per_sec,
+            w.subscriptions
+        );
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "| Operation | Count | Errors | Rate/s | Mean ms | p50 ms | p90 ms | p99 ms | Max ms |"
+        );
+        let _ = writeln!(out, "|---|---:|---:|---:|---:|---:|---:|---:|---:|");
+        for (name, s) in [
+            ("recordEngagement", &self.record_engagement),
+            ("leaderboard", &self.leaderboard),
+            ("subscription lag", &self.subscriptions.delivery_lag),
+        ] {
+            let _ = writeln!(
+                out,
+                "| {name} | {} | {} | {:.1} | {:.2} | {:.2} | {:.2} | {:.2} | {:.2} |",
+                s.count,
+                s.errors,
+                s.throughput_per_sec,
+                s.mean_ms,
+                s.p50_ms,
+                s.p90_ms,
+                s.p99_ms,
+                s.max_ms
+            );
+        }
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "Subscriptions connected: {}/{}",
+            self.subscriptions.connected, self.subscriptions.requested
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::LatencyRecorder;
+    use std::time::Duration;
+
+    fn summary() -> Summary {
+        let mut mutations = LatencyRecorder::default();
+        mutations.record(Duration::from_millis(12));
+        mutations.record_error();
+
+        Summary {
+            target: "http://localhost:8080/graphql".to_string(),
+            started_at: DateTime::UNIX_EPOCH,
+            elapsed_secs: 1.0,
+            workload: Workload {
+                engagements_per_sec: 50.0,
+                leaderboard_per_sec: 20.0,
+                subscriptions: 10,
+                duration_secs: 1,
+                convoy_id: "c1".to_string(),
+                drones: 4,
+            },
+            record_engagement: mutations.summarize(Duration::from_secs(1)),
+            leaderboard: LatencyRecorder::default().summarize(Duration::from_secs(1)),
+            subscriptions: SubscriptionSummary {
+                requested: 10,
+                connected: 9,
+                delivery_lag: LatencyRecorder::default().summarize(Duration::from_secs(1)),
+            },
+        }
+    }
+
+    #[test]
+    fn test_json_summary_shape() {
+        let json: serde_json::Value =
+            serde_json::from_str(&summary().render(Format::Json)).unwrap();
+
+        assert_eq!(json["workload"]["engagements_per_sec"], 50.0);
+        assert_eq!(json["record_engagement"]["count"], 1);
+        assert_eq!(json["record_engagement"]["errors"], 1);
+        assert_eq!(json["subscriptions"]["connected"], 9);
+    }
+
+    #[test]
+    fn test_markdown_summary_has_row_per_operation() {
+        let markdown = summary().render(Format::Markdown);
+
+        assert!(markdown.contains("| recordEngagement | 1 | 1 | 1.0 | 12.00 |"));
+        assert!(markdown.contains("| leaderboard | 0 | 0 |"));
+        assert!(markdown.contains("| subscription lag |"));
+        assert!(markdown.contains("Subscriptions connected: 9/10"));
+    }
+}
@@ -0,0 +1,120 @@
+//! # Latency Statistics
+//!
+//! Raw latency samples reduced to percentiles once a run is over.
+
+use serde::Serialize;
+use std::time::Duration;
+
+/// Latency samples and error count for one operation
+#[derive(Debug, Clone, Default)]
+pub struct LatencyRecorder {
+    samples: Vec<Duration>,
+    errors: u64,
+}
+
+impl LatencyRecorder {
+    /// Record a successful operation
+    pub fn record(&mut self, latency: Duration) {
+        self.samples.push(latency);
+    }
+
+    /// Record a failed operation
+    pub fn record_error(&mut self) {
+        self.errors += 1;
+    }
+
+    /// Fold another recorder's samples into this one
+    pub fn merge(&mut self, other: Self) {
+        self.samples.extend(other.samples);
+        self.errors += other.errors;
+    }
+
+    /// Reduce the samples to a summary over `elapsed` wall-clock time
+    #[must_use]
+    pub fn summarize(mut self, elapsed: Duration) -> LatencySummary {
+        self.samples.sort_unstable();
+        let count = self.samples.len() as u64;
+        let secs = elapsed.as_secs_f64();
+        let mean_ms = if self.samples.is_empty() {
+            0.0
+        } else {
+            self.samples.iter().map(|d| d.as_secs_f64()).sum::<f64>() * 1000.0
+                / self.samples.len() as f64
+        };
+
+        LatencySummary {
+            count,
+            errors: self.errors,
+            throughput_per_sec: if secs > 0.0 { count as f64 / secs } else { 0.0 },
+            mean_ms,
+            p50_ms: percentile_ms(&self.samples, 50.0),
+            p90_ms: percentile_ms(&self.samples, 90.0),
+            p99_ms: percentile_ms(&self.samples, 99.0),
+            max_ms: self.samples.last().map_or(0.0, |d| d.as_secs_f64() * 1000.0),
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted samples, in milliseconds
+fn percentile_ms(sorted: &[Duration], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1].as_secs_f64() * 1000.0
+}
+
+/// Percentile summary for one operation
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencySummary {
+    /// Successful operations
+    pub count: u64,
+    /// Failed operations
+    pub errors: u64,
+    /// Successful operations per second
+    pub throughput_per_sec: f64,
+    /// Mean latency
+    pub mean_ms: f64,
+    /// Median latency
+    pub p50_ms: f64,
+    /// 90th percentile latency
+    pub p90_ms: f64,
+    /// 99th percentile latency
+    pub p99_ms: f64,
+    /// Worst latency
+    pub max_ms: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_use_nearest_rank() {
+        let mut recorder = LatencyRecorder::default();
+        for ms in (1..=100).rev() {
+            recorder.record(Duration::from_millis(ms));
+        }
+        recorder.record_error();
+
+        let summary = recorder.summarize(Duration::from_secs(10));
+
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.errors, 1);
+        assert!((summary.throughput_per_sec - 10.0).abs() < 1e-9);
+        assert!((summary.p50_ms - 50.0).abs() < 1e-9);
+        assert!((summary.p90_ms - 90.0).abs() < 1e-9);
+        assert!((summary.p99_ms - 99.0).abs() < 1e-9);
+        assert!((summary.max_ms - 100.0).abs() < 1e-9);
+        assert!((summary.mean_ms - 50.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_recorder_summarizes_to_zero() {
+        let summary = LatencyRecorder::default().summarize(Duration::ZERO);
+
+        assert_eq!(summary.count, 0);
+        assert!(summary.p99_ms.abs() < f64::EPSILON);
+        assert!(summary.throughput_per_sec.abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,141 @@
+//! # Subscription Load
+//!
+//! Holds `engagementEvents` subscriptions open over `graphql-transport-ws`
+//! and measures how long each event takes to arrive.
+
+use crate::stats::LatencyRecorder;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use drone_graphql_client::subscriptions::{ConvoyVariables, EngagementEvents};
+use drone_graphql_client::ws::{self, ClientMessage, ServerMessage};
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time::{timeout, timeout_at, Instant};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// How long to wait for `connection_ack`
+const ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Subscription ID used on every connection
+const SUBSCRIPTION_ID: &str = "loadtest";
+
+/// An acknowledged subscription
+pub struct Subscriber {
+    sink: SplitSink<Socket, Message>,
+    stream: SplitStream<Socket>,
+}
+
+impl Subscriber {
+    /// Connect, authenticate and subscribe to `convoy_id`'s engagements
+    pub async fn connect(url: &str, token: Option<&str>, convoy_id: &str) -> Result<Self> {
+        let mut request = url.into_client_request()?;
+        request.headers_mut().insert(
+            "Sec-WebSocket-Protocol",
+            HeaderValue::from_static(ws::SUBPROTOCOL),
+        );
+        let (socket, _) = connect_async(request)
+            .await
+            .with_context(|| format!("connecting to {url}"))?;
+        let (mut sink, mut stream) = socket.split();
+
+        send(&mut sink, &ClientMessage::connection_init(token)).await?;
+        loop {
+            let frame = timeout(ACK_TIMEOUT, stream.next())
+                .await
+                .context("timed out waiting for connection_ack")?;
+            match parse(frame)? {
+                Some(ServerMessage::ConnectionAck) => break,
+                Some(ServerMessage::Ping) => send(&mut sink, &ClientMessage::Pong).await?,
+                Some(other) => bail!("unexpected message before ack: {other:?}"),
+                None => {}
+            }
+        }
+
+        let subscribe = ClientMessage::subscribe::<EngagementEvents>(
+            SUBSCRIPTION_ID,
+            ConvoyVariables {
+                convoy_id: convoy_id.to_string(),
+            },
+        )?;
+        send(&mut sink, &subscribe).await?;
+
+        Ok(Self { sink, stream })
+    }
+
+    /// Record delivery lag for every event received before `deadline`.
+    ///
+    /// Lag is receipt time minus the server's event timestamp, so client
+    /// and server clocks need to agree. A connection that fails or closes
+    /// early counts as one error.
+    pub async fn collect_until(mut self, deadline: Instant) -> LatencyRecorder {
+        let mut recorder = LatencyRecorder::default();
+
+        while let Ok(frame) = timeout_at(deadline, self.stream.next()).await {
+            let message = match parse(frame) {
+                Ok(Some(message)) => message,
+                Ok(None) => continue,
+                Err(err) => {
+                    tracing::debug!("Subscription closed: {}", err);
+                    recorder.record_error();
+                    return recorder;
+                }
+            };
+            match message {
+                ServerMessage::Next { payload, .. } => {
+                    match ws::decode_next::<EngagementEvents>(payload) {
+                        Ok(data) => {
+                            let lag = Utc::now() - data.engagement_events.timestamp;
+                            recorder.record(lag.to_std().unwrap_or_default());
+                        }
+                        Err(_) => recorder.record_error(),
+                    }
+                }
+                ServerMessage::Ping => {
+                    if send(&mut self.sink, &ClientMessage::Pong).await.is_err() {
+                        recorder.record_error();
+                        return recorder;
+                    }
+                }
+                ServerMessage::Error { .. } | ServerMessage::Complete { .. } => {
+                    recorder.record_error();
+                    return recorder;
+                }
+                ServerMessage::ConnectionAck | ServerMessage::Pong => {}
+            }
+        }
+
+        let _ = send(
+            &mut self.sink,
+            &ClientMessage::Complete {
+                id: SUBSCRIPTION_ID.to_string(),
+            },
+        )
+        .await;
+        let _ = self.sink.close().await;
+        recorder
+    }
+}
+
+async fn send(sink: &mut SplitSink<Socket, Message>, message: &ClientMessage) -> Result<()> {
+    sink.send(Message::text(message.to_text())).await?;
+    Ok(())
+}
+
+/// Decode a frame; `None` for frames that carry no protocol message
+fn parse(
+    frame: Option<Result<Message, tokio_tungstenite::tungstenite::Error>>,
+) -> Result<Option<ServerMessage>> {
+    match frame {
+        Some(Ok(Message::Text(text))) => Ok(Some(ServerMessage::from_text(text.as_str())?)),
+        Some(Ok(Message::Close(_))) | None => bail!("connection closed"),
+        Some(Ok(_)) => Ok(None),
+        Some(Err(err)) => Err(err.into()),
+    }
+}
@@ -0,0 +1,171 @@
+//! # Workload Generation
+//!
+//! Open-loop request pacing: each operation is fired on its own schedule
+//! regardless of how long earlier ones take, so a slow server shows up as
+//! latency rather than as a quietly reduced request rate.
+
+use crate::stats::LatencyRecorder;
+use drone_graphql_client::operations::{
+    GetLeaderboard, GetLeaderboardVariables, RecordEngagement, RecordEngagementInput,
+    RecordEngagementVariables,
+};
+use drone_graphql_client::{ClientError, GraphQLClient};
+use serde::Serialize;
+use std::future::Future;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio::time::{interval, Instant, MissedTickBehavior};
+
+/// Offered load for a run
+#[derive(Debug, Clone, Serialize)]
+pub struct Workload {
+    /// `recordEngagement` mutations per second
+    pub engagements_per_sec: f64,
+    /// `leaderboard` queries per second
+    pub leaderboard_per_sec: f64,
+    /// Concurrent `engagementEvents` subscriptions
+    pub subscriptions: usize,
+    /// Run length
+    pub duration_secs: u64,
+    /// Convoy all operations target
+    pub convoy_id: String,
+    /// Drones engagements are spread across
+    pub drones: usize,
+}
+
+impl Workload {
+    /// Run length
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.duration_secs)
+    }
+}
+
+/// Fire `recordEngagement` at the configured rate, round-robin across
+/// `drone_ids`, with two hits for every miss
+pub async fn record_engagements(
+    client: GraphQLClient,
+    workload: &Workload,
+    drone_ids: Vec<String>,
+) -> LatencyRecorder {
+    let convoy_id = workload.convoy_id.clone();
+    paced(workload.engagements_per_sec, workload.duration(), move |seq| {
+        let client = client.clone();
+        let variables = RecordEngagementVariables {
+            input: RecordEngagementInput {
+                convoy_id: convoy_id.clone(),
+                drone_id: drone_ids[(seq as usize) % drone_ids.len()].clone(),
+                hit: seq % 3 != 2,
+                ..Default::default()
+            },
+        };
+        async move { client.execute::<RecordEngagement>(variables).await.map(drop) }
+    })
+    .await
+}
+
+/// Fire `leaderboard` queries at the configured rate
+pub async fn query_leaderboard(client: GraphQLClient, workload: &Workload) -> LatencyRecorder {
+    let convoy_id = workload.convoy_id.clone();
+    paced(workload.leaderboard_per_sec, workload.duration(), move |_| {
+        let client = client.clone();
+        let variables = GetLeaderboardVariables {
+            convoy_id: convoy_id.clone(),
+            limit: 10,
+        };
+        async move { client.execute::<GetLeaderboard>(variables).await.map(drop) }
+    })
+    .await
+}
+
+/// Start `op` `rate` times per second for `duration`, then wait for every
+/// in-flight call and collect their latencies
+async fn paced<F, Fut>(rate: f64, duration: Duration, mut op: F) -> LatencyRecorder
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<(), ClientError>> + Send + 'static,
+{
+    let mut recorder = LatencyRecorder::default();
+    if rate <= 0.0 {
+        return recorder;
+    }
+
+    let deadline = Instant::now() + duration;
+    let mut ticker = interval(Duration::from_secs_f64(1.0 / rate));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Burst);
+    let mut in_flight = JoinSet::new();
+    let mut seq = 0;
+
+    while ticker.tick().await < deadline {
+        let call = op(seq);
+        seq += 1;
+        in_flight.spawn(async move {
+            let started = Instant::now();
+            call.await.map(|()| started.elapsed())
+        });
+
+        // Reap finished calls so the set doesn't grow for the whole run
+        while let Some(done) = in_flight.try_join_next() {
+            record(&mut recorder, done);
+        }
+    }
+
+    while let Some(done) = in_flight.join_next().await {
+        record(&mut recorder, done);
+    }
+    recorder
+}
+
+fn record(
+    recorder: &mut LatencyRecorder,
+    done: Result<Result<Duration, ClientError>, tokio::task::JoinError>,
+) {
+    match done {
+        Ok(Ok(latency)) => recorder.record(latency),
+        Ok(Err(err)) => {
+            tracing::debug!("Request failed: {}", err);
+            recorder.record_error();
+        }
+        Err(_) => recorder.record_error(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_paced_fires_at_rate_without_waiting_for_responses() {
+        let recorder = paced(20.0, Duration::from_secs(2), |_| async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Ok(())
+        })
+        .await;
+
+        let summary = recorder.summarize(Duration::from_secs(2));
+        assert_eq!(summary.count, 40);
+        assert!((summary.p99_ms - 500.0).abs() < 1.0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_paced_counts_errors() {
+        let recorder = paced(10.0, Duration::from_secs(1), |seq| async move {
+            if seq % 2 == 0 {
+                Err(ClientError::NoData)
+            } else {
+                Ok(())
+            }
+        })
+        .await;
+
+        let summary = recorder.summarize(Duration::from_secs(1));
+        assert_eq!(summary.count, 5);
+        assert_eq!(summary.errors, 5);
+    }
+
+    #[tokio::test]
+    async fn test_zero_rate_sends_nothing() {
+        let recorder = paced(0.0, Duration::from_secs(60), |_| async { Ok(()) }).await;
+        assert_eq!(recorder.summarize(Duration::from_secs(60)).count, 0);
+    }
+}
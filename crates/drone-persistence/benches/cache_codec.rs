@@ -0,0 +1,70 @@
+//! Cache value codec benchmarks.
+//!
+//! Encodes and decodes a telemetry point with extension fields, the hottest
+//! cached value, with every codec compiled in; enable `msgpack` to compare
+//! it with JSON. Throughput is reported against each codec's own payload
+//! size, and the sizes are printed before the run.
+
+use chrono::{DateTime, Utc};
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use drone_domain::{Coordinates, Telemetry};
+use drone_persistence::CacheCodec;
+use drone_persistence::cache::codec;
+use uuid::Uuid;
+
+fn telemetry() -> Telemetry {
+    let recorded_at = DateTime::<Utc>::from_timestamp_millis(1_700_000_000_000)
+        .expect("valid timestamp");
+    Telemetry {
+        drone_id: Uuid::from_u128(100),
+        time_bucket: Telemetry::generate_time_bucket(&recorded_at),
+        recorded_at,
+        position: Coordinates::new(34.512_3, 69.178_4, 7_620.0),
+        velocity_mps: 82.5,
+        acceleration_mps2: 0.4,
+        bank_angle_deg: 12.0,
+        pitch_angle_deg: 1.5,
+        current_waypoint: 4,
+        distance_to_next_km: 18.2,
+        eta_next_waypoint: Some(recorded_at),
+        fuel_remaining_pct: 63.0,
+        engine_rpm: 2_450,
+        engine_temp_c: 88.0,
+        battery_voltage: 27.6,
+        wind_speed_mps: 9.0,
+        wind_direction_deg: 270.0,
+        temperature_c: -21.0,
+        visibility_km: 16.0,
+        link_status: None,
+        mesh_connectivity: 0.92,
+        schema_version: 2,
+        extensions: [
+            ("satcomBeam".to_string(), serde_json::json!(7)),
+            ("icingRisk".to_string(), serde_json::json!(0.9)),
+        ]
+        .into_iter()
+        .collect(),
+    }
+}
+
+fn bench_codecs(c: &mut Criterion) {
+    let point = telemetry();
+
+    let mut group = c.benchmark_group("cache_codec");
+    for &codec in CacheCodec::ALL {
+        let encoded = codec.encode(&point).expect("encode telemetry");
+        println!("{}: {} bytes", codec.as_str(), encoded.len());
+        group.throughput(Throughput::Bytes(encoded.len() as u64));
+
+        group.bench_function(BenchmarkId::new("encode", codec.as_str()), |b| {
+            b.iter(|| codec.encode(black_box(&point)));
+        });
+        group.bench_function(BenchmarkId::new("decode", codec.as_str()), |b| {
+            b.iter(|| codec::decode::<Telemetry>(black_box(&encoded)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_codecs);
+criterion_main!(benches);
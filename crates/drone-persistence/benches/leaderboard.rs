@@ -0,0 +1,150 @@
+//! Leaderboard update path and row → domain conversion benchmarks.
+//!
+//! The update path runs the repository's own bookkeeping against an
+//! in-memory table standing in for Scylla: read the drone's row, tally the
+//! engagement, score, re-rank the convoy and collect the ranks to persist.
+
+use chrono::{DateTime, Utc};
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use drone_domain::{LeaderboardEntry, ScoringModel};
+use drone_persistence::repository::{
+    EngagementFeedRow, LeaderboardRow, LeaderboardTally, leaderboard_entry_from_row, rank_changes,
+};
+use scylla::frame::value::CqlTimestamp;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const CONVOY: Uuid = Uuid::from_u128(1);
+
+fn leaderboard_row(i: u128) -> LeaderboardRow {
+    let total = 20 + (i % 17) as i32;
+    let hits = (total * 3) / 4 - (i % 5) as i32;
+    (
+        CONVOY,
+        Uuid::from_u128(100 + i),
+        format!("REAPER-{i:02}"),
+        "MQ9_REAPER".to_string(),
+        total,
+        hits,
+        hits as f32 / total as f32 * 100.0,
+        (i % 4) as i32,
+        3,
+        (i + 1) as i16,
+    )
+}
+
+fn engagement_row(i: u128) -> EngagementFeedRow {
+    EngagementFeedRow {
+        engaged_at: CqlTimestamp(1_700_000_000_000 + i as i64 * 1_000),
+        engagement_id: Uuid::from_u128(10_000 + i),
+        drone_id: Some(Uuid::from_u128(100 + i % 8)),
+        drone_callsign: Some(format!("REAPER-{:02}", i % 8)),
+        weapon_type: Some("GBU-12_PAVEWAY".to_string()),
+        target_type: Some("VEHICLE".to_string()),
+        target_id: Some(Uuid::from_u128(20_000 + i)),
+        hit: Some(!i.is_multiple_of(3)),
+        impact_lat: Some(31.61),
+        impact_lon: Some(65.71),
+        range_to_target_km: Some(4.2),
+        bda_status: Some("DESTROYED".to_string()),
+        bda_notes: None,
+        authorization_code: Some("AUTH-7".to_string()),
+        roe_compliance: Some(true),
+        shooter_lat: Some(31.65),
+        shooter_lon: Some(65.68),
+        sequence: Some(i as i64 / 8 + 1),
+        predicted_pk: None,
+    }
+}
+
+/// Leaderboard table for one convoy, keyed by drone
+struct MockTable {
+    rows: HashMap<Uuid, LeaderboardEntry>,
+    model: ScoringModel,
+}
+
+impl MockTable {
+    fn new(drones: u128) -> Self {
+        let model = ScoringModel::default();
+        let rows = (0..drones)
+            .map(|i| leaderboard_entry_from_row(leaderboard_row(i), model))
+            .map(|e| (e.drone_id, e))
+            .collect();
+        Self { rows, model }
+    }
+
+    /// The repository's `update_entry` without the network round trips
+    fn update_entry(&mut self, drone_id: Uuid, hit: bool) -> i16 {
+        let tally = LeaderboardTally::after(self.rows.get(&drone_id), hit);
+        let score = self.model.score(
+            i64::from(tally.successful_hits),
+            i64::from(tally.total_engagements),
+        );
+        let entry = self.rows.get_mut(&drone_id).expect("seeded drone");
+        entry.total_engagements = tally.total_engagements;
+        entry.successful_hits = tally.successful_hits;
+        entry.current_streak = tally.current_streak;
+        entry.best_streak = tally.best_streak;
+        entry.accuracy_pct = tally.accuracy_pct().into();
+        entry.score = score;
+        entry.updated_at = DateTime::<Utc>::UNIX_EPOCH;
+
+        let mut entries: Vec<LeaderboardEntry> = self.rows.values().cloned().collect();
+        entries.sort_by(|a, b| b.score.total_cmp(&a.score));
+        let (rank, changed) = rank_changes(&entries, drone_id);
+        for (id, rank) in changed {
+            self.rows.get_mut(&id).expect("ranked drone").rank = rank;
+        }
+        rank
+    }
+}
+
+fn bench_update_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("leaderboard_update");
+    for drones in [4_u128, 16, 64] {
+        let mut table = MockTable::new(drones);
+        let mut seq = 0_u128;
+        group.bench_function(BenchmarkId::from_parameter(drones), |b| {
+            b.iter(|| {
+                seq += 1;
+                table.update_entry(Uuid::from_u128(100 + seq % drones), !seq.is_multiple_of(3))
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_row_conversions(c: &mut Criterion) {
+    let leaderboard: Vec<LeaderboardRow> = (0..64).map(leaderboard_row).collect();
+    let engagements: Vec<EngagementFeedRow> = (0..100).map(engagement_row).collect();
+
+    let mut group = c.benchmark_group("row_to_domain");
+    group.throughput(Throughput::Elements(leaderboard.len() as u64));
+    group.bench_function("leaderboard_entry", |b| {
+        b.iter_batched(
+            || leaderboard.clone(),
+            |rows| {
+                rows.into_iter()
+                    .map(|row| leaderboard_entry_from_row(row, ScoringModel::default()))
+                    .collect::<Vec<_>>()
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+    group.throughput(Throughput::Elements(engagements.len() as u64));
+    group.bench_function("engagement", |b| {
+        b.iter_batched(
+            || engagements.clone(),
+            |rows| {
+                rows.into_iter()
+                    .map(|row| row.into_engagement(black_box(CONVOY)))
+                    .collect::<Vec<_>>()
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_update_path, bench_row_conversions);
+criterion_main!(benches);
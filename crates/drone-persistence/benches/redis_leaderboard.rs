@@ -0,0 +1,78 @@
+//! Redis sorted-set leaderboard benchmarks.
+//!
+//! Needs a live Redis at `REDIS_URL` (default `redis://127.0.0.1:6379`);
+//! the group is skipped when none is reachable. Keys are namespaced under a
+//! fresh convoy ID and expire with the leaderboard TTL.
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use drone_persistence::{CacheClient, CacheConfig};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+
+/// Give up on Redis quickly rather than stall the whole bench run
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+fn bench_sorted_set(c: &mut Criterion) {
+    let rt = Runtime::new().expect("tokio runtime");
+    let config = CacheConfig {
+        url: std::env::var("REDIS_URL").unwrap_or_else(|_| CacheConfig::default().url),
+        ..CacheConfig::default()
+    };
+    let connect = CacheClient::new(config.clone());
+    let cache = match rt.block_on(async { tokio::time::timeout(CONNECT_TIMEOUT, connect).await }) {
+        Ok(Ok(cache)) => cache,
+        Ok(Err(e)) => {
+            eprintln!("Skipping Redis benchmarks, {} unreachable: {e}", config.url);
+            return;
+        }
+        Err(_) => {
+            eprintln!("Skipping Redis benchmarks, {} timed out", config.url);
+            return;
+        }
+    };
+
+    let mut group = c.benchmark_group("redis_leaderboard");
+    for drones in [16_u128, 256] {
+        let convoy_id = Uuid::new_v4();
+        let ids: Vec<Uuid> = (0..drones).map(Uuid::from_u128).collect();
+        rt.block_on(async {
+            let scores: Vec<(Uuid, f64)> =
+                ids.iter().enumerate().map(|(i, id)| (*id, i as f64)).collect();
+            cache
+                .update_leaderboard_scores(convoy_id, &scores)
+                .await
+                .expect("seed leaderboard");
+        });
+
+        let mut seq = 0_usize;
+        group.bench_function(BenchmarkId::new("zadd", drones), |b| {
+            b.to_async(&rt).iter(|| {
+                seq += 1;
+                let id = ids[seq % ids.len()];
+                let cache = &cache;
+                async move {
+                    cache
+                        .update_leaderboard_score(convoy_id, id, seq as f64)
+                        .await
+                }
+            });
+        });
+        group.bench_function(BenchmarkId::new("zrevrank", drones), |b| {
+            b.to_async(&rt).iter(|| {
+                seq += 1;
+                let id = ids[seq % ids.len()];
+                let cache = &cache;
+                async move { cache.get_drone_rank(convoy_id, id).await }
+            });
+        });
+        group.bench_function(BenchmarkId::new("zrevrange_top10", drones), |b| {
+            b.to_async(&rt)
+                .iter(|| async { cache.get_leaderboard(convoy_id, 10).await });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sorted_set);
+criterion_main!(benches);
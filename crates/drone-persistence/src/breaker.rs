@@ -0,0 +1,477 @@
+//! # Circuit Breaker
+//!
+//! Failure-rate circuit breaker wrapped around the Redis and ScyllaDB clients.
+//!
+//! ```text
+//!   Closed ──(failure rate ≥ threshold)──▶ Open
+//!     ▲                                     │
+//!     │                              (open_duration)
+//!     │                                     ▼
+//!     └──────(all probes succeed)──── HalfOpen ──(any probe fails)──▶ Open
+//! ```
+//!
+//! While open, calls fail immediately with [`PersistenceError::CircuitOpen`]
+//! instead of waiting on a dead backend. A half-open probe whose future is
+//! dropped before it finishes gives its slot back for another caller.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::{PersistenceError, Result};
+
+/// Circuit breaker tuning
+#[derive(Debug, Clone, Copy)]
+pub struct BreakerConfig {
+    /// Failure ratio (0.0–1.0) over the window that opens the breaker
+    pub failure_rate_threshold: f64,
+    /// Calls recorded before the failure rate is evaluated
+    pub minimum_calls: usize,
+    /// Number of most recent calls the failure rate is computed over
+    pub window_size: usize,
+    /// How long the breaker stays open before allowing probes
+    pub open_duration: Duration,
+    /// Probe calls allowed (and required to succeed) while half-open
+    pub half_open_probes: usize,
+}
+
+impl Default for BreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            minimum_calls: 10,
+            window_size: 20,
+            open_duration: Duration::from_secs(30),
+            half_open_probes: 3,
+        }
+    }
+}
+
+/// Breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Calls pass through and outcomes are recorded
+    Closed,
+    /// Calls are rejected without reaching the backend
+    Open,
+    /// A limited number of probe calls are let through
+    HalfOpen,
+}
+
+impl BreakerState {
+    /// Lowercase name used in logs and health output
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+        }
+    }
+
+    /// Numeric gauge value (0 closed, 1 half-open, 2 open)
+    #[must_use]
+    pub fn as_gauge(self) -> u8 {
+        match self {
+            Self::Closed => 0,
+            Self::HalfOpen => 1,
+            Self::Open => 2,
+        }
+    }
+}
+
+/// Point-in-time view of a breaker for metrics and health checks
+#[derive(Debug, Clone, Copy)]
+pub struct BreakerSnapshot {
+    pub name: &'static str,
+    pub state: BreakerState,
+    pub failure_rate: f64,
+    pub rejected_total: u64,
+    pub opened_total: u64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: BreakerState,
+    /// Recent outcomes, `true` for failure
+    window: VecDeque<bool>,
+    opened_at: Option<Instant>,
+    probes_started: usize,
+    probes_succeeded: usize,
+    /// Bumped on every transition so stale probes cannot touch a later period
+    generation: u64,
+}
+
+/// How [`CircuitBreaker::admit`] let a call through
+#[derive(Debug, Clone, Copy)]
+enum Admission {
+    /// Closed breaker; outcomes go to the failure-rate window
+    Call,
+    /// Half-open probe taken during the given generation
+    Probe(u64),
+}
+
+/// A call admitted by [`CircuitBreaker::call`] and still in flight.
+///
+/// Dropped without an outcome, e.g. when the caller's future is cancelled,
+/// a probe releases its half-open slot.
+struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    admission: Admission,
+    resolved: bool,
+}
+
+impl Permit<'_> {
+    fn success(mut self) {
+        self.resolved = true;
+        self.breaker.record_success();
+    }
+
+    fn failure(mut self) {
+        self.resolved = true;
+        self.breaker.record_failure();
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if let (false, Admission::Probe(generation)) = (self.resolved, self.admission) {
+            self.breaker.release_probe(generation);
+        }
+    }
+}
+
+/// Failure-rate circuit breaker with half-open probing
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: &'static str,
+    config: BreakerConfig,
+    inner: Mutex<Inner>,
+    rejected: AtomicU64,
+    opened: AtomicU64,
+}
+
+impl CircuitBreaker {
+    /// Create a closed breaker
+    #[must_use]
+    pub fn new(name: &'static str, config: BreakerConfig) -> Self {
+        Self {
+            name,
+            config,
+            inner: Mutex::new(Inner {
+                state: BreakerState::Closed,
+                window: VecDeque::with_capacity(config.window_size),
+                opened_at: None,
+                probes_started: 0,
+                probes_succeeded: 0,
+                generation: 0,
+            }),
+            rejected: AtomicU64::new(0),
+            opened: AtomicU64::new(0),
+        }
+    }
+
+    /// Breaker name ("redis", "scylla")
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Current state, reporting half-open once the open period has elapsed
+    #[must_use]
+    pub fn state(&self) -> BreakerState {
+        let Ok(inner) = self.inner.lock() else {
+            return BreakerState::Closed;
+        };
+        match inner.state {
+            BreakerState::Open if self.open_elapsed(&inner) => BreakerState::HalfOpen,
+            state => state,
+        }
+    }
+
+    /// Whether the breaker is currently rejecting calls
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.state() == BreakerState::Open
+    }
+
+    /// Failure ratio over the current window
+    #[must_use]
+    pub fn failure_rate(&self) -> f64 {
+        self.inner.lock().map(|inner| Self::rate(&inner)).unwrap_or(0.0)
+    }
+
+    /// Snapshot for metrics and health output
+    #[must_use]
+    pub fn snapshot(&self) -> BreakerSnapshot {
+        BreakerSnapshot {
+            name: self.name,
+            state: self.state(),
+            failure_rate: self.failure_rate(),
+            rejected_total: self.rejected.load(Ordering::Relaxed),
+            opened_total: self.opened.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Ask permission for a call, moving to half-open when the open period
+    /// has elapsed. Rejections are counted.
+    pub fn try_acquire(&self) -> bool {
+        self.admit().is_some()
+    }
+
+    fn admit(&self) -> Option<Admission> {
+        let Ok(mut inner) = self.inner.lock() else {
+            return Some(Admission::Call);
+        };
+
+        if inner.state == BreakerState::Open && self.open_elapsed(&inner) {
+            self.transition(&mut inner, BreakerState::HalfOpen);
+        }
+
+        let admission = match inner.state {
+            BreakerState::Closed => Some(Admission::Call),
+            BreakerState::Open => None,
+            BreakerState::HalfOpen if inner.probes_started < self.config.half_open_probes => {
+                inner.probes_started += 1;
+                Some(Admission::Probe(inner.generation))
+            }
+            BreakerState::HalfOpen => None,
+        };
+
+        if admission.is_none() {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+        }
+        admission
+    }
+
+    /// Give back a probe slot taken during `generation` that never finished
+    fn release_probe(&self, generation: u64) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        if inner.state == BreakerState::HalfOpen && inner.generation == generation {
+            inner.probes_started = inner.probes_started.saturating_sub(1);
+        }
+    }
+
+    /// Record a successful call
+    pub fn record_success(&self) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        match inner.state {
+            BreakerState::Closed => self.push(&mut inner, false),
+            BreakerState::HalfOpen => {
+                inner.probes_succeeded += 1;
+                if inner.probes_succeeded >= self.config.half_open_probes {
+                    self.transition(&mut inner, BreakerState::Closed);
+                }
+            }
+            BreakerState::Open => {}
+        }
+    }
+
+    /// Record a failed call
+    pub fn record_failure(&self) {
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        match inner.state {
+            BreakerState::Closed => {
+                self.push(&mut inner, true);
+                if inner.window.len() >= self.config.minimum_calls
+                    && Self::rate(&inner) >= self.config.failure_rate_threshold
+                {
+                    self.transition(&mut inner, BreakerState::Open);
+                }
+            }
+            BreakerState::HalfOpen => self.transition(&mut inner, BreakerState::Open),
+            BreakerState::Open => {}
+        }
+    }
+
+    /// Run `call` through the breaker.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::CircuitOpen`] without polling `call` when
+    /// the breaker rejects it, otherwise the call's own error.
+    pub async fn call<T, E, F>(&self, call: F) -> Result<T>
+    where
+        F: Future<Output = std::result::Result<T, E>>,
+        E: Into<PersistenceError>,
+    {
+        let Some(admission) = self.admit() else {
+            return Err(PersistenceError::CircuitOpen(self.name.to_string()));
+        };
+        let permit = Permit {
+            breaker: self,
+            admission,
+            resolved: false,
+        };
+
+        match call.await {
+            Ok(value) => {
+                permit.success();
+                Ok(value)
+            }
+            Err(e) => {
+                permit.failure();
+                Err(e.into())
+            }
+        }
+    }
+
+    fn open_elapsed(&self, inner: &Inner) -> bool {
+        inner
+            .opened_at
+            .is_some_and(|at| at.elapsed() >= self.config.open_duration)
+    }
+
+    fn push(&self, inner: &mut Inner, failed: bool) {
+        if inner.window.len() >= self.config.window_size.max(1) {
+            inner.window.pop_front();
+        }
+        inner.window.push_back(failed);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn rate(inner: &Inner) -> f64 {
+        if inner.window.is_empty() {
+            return 0.0;
+        }
+        let failures = inner.window.iter().filter(|failed| **failed).count();
+        failures as f64 / inner.window.len() as f64
+    }
+
+    fn transition(&self, inner: &mut Inner, to: BreakerState) {
+        let from = inner.state;
+        inner.state = to;
+        inner.probes_started = 0;
+        inner.probes_succeeded = 0;
+        inner.generation += 1;
+
+        match to {
+            BreakerState::Open => {
+                inner.opened_at = Some(Instant::now());
+                self.opened.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(
+                    breaker = self.name,
+                    from = from.as_str(),
+                    failure_rate = Self::rate(inner),
+                    "Circuit breaker opened"
+                );
+            }
+            BreakerState::HalfOpen => {
+                tracing::info!(breaker = self.name, "Circuit breaker half-open, probing");
+            }
+            BreakerState::Closed => {
+                inner.window.clear();
+                inner.opened_at = None;
+                tracing::info!(breaker = self.name, "Circuit breaker closed");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(open_duration: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(
+            "test",
+            BreakerConfig {
+                failure_rate_threshold: 0.5,
+                minimum_calls: 4,
+                window_size: 4,
+                open_duration,
+                half_open_probes: 2,
+            },
+        )
+    }
+
+    #[test]
+    fn test_opens_at_failure_rate_threshold() {
+        let breaker = breaker(Duration::from_secs(60));
+        breaker.record_success();
+        breaker.record_failure();
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+
+        // Fourth call reaches minimum_calls with 2/4 failures
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert!(!breaker.try_acquire());
+        assert_eq!(breaker.snapshot().rejected_total, 1);
+        assert_eq!(breaker.snapshot().opened_total, 1);
+    }
+
+    #[test]
+    fn test_half_open_probes_close_breaker() {
+        let breaker = breaker(Duration::ZERO);
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+
+        // Only half_open_probes calls are admitted
+        assert!(breaker.try_acquire());
+        assert!(breaker.try_acquire());
+        assert!(!breaker.try_acquire());
+
+        breaker.record_success();
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert!(breaker.failure_rate().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_failed_probe_reopens() {
+        let breaker = breaker(Duration::from_millis(0));
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+        assert!(breaker.try_acquire());
+        breaker.record_failure();
+        assert_eq!(breaker.snapshot().opened_total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_call_short_circuits_when_open() {
+        let breaker = breaker(Duration::from_secs(60));
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+
+        let polled = std::sync::atomic::AtomicBool::new(false);
+        let result = breaker
+            .call(async {
+                polled.store(true, Ordering::Relaxed);
+                Ok::<(), PersistenceError>(())
+            })
+            .await;
+        assert!(matches!(result, Err(PersistenceError::CircuitOpen(name)) if name == "test"));
+        assert!(!polled.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_probe_releases_its_slot() {
+        let breaker = breaker(Duration::ZERO);
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+
+        // The probe is cancelled mid-flight by the timeout
+        let probe = breaker.call(std::future::pending::<Result<()>>());
+        assert!(tokio::time::timeout(Duration::from_millis(10), probe).await.is_err());
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+
+        // Both probe slots are free again
+        assert!(breaker.try_acquire());
+        assert!(breaker.try_acquire());
+        assert!(!breaker.try_acquire());
+    }
+}
@@ -0,0 +1,202 @@
+//! # Cache Value Codecs
+//!
+//! How [`CacheClient`](super::CacheClient) encodes the values it stores.
+//! Every value starts with a one-byte marker naming the codec that wrote it,
+//! and values are decoded by their marker rather than the configured codec,
+//! so switching codecs needs no cache flush: entries written before the
+//! switch are still read until they expire. Values without a marker predate
+//! codecs and are read as JSON.
+//!
+//! MessagePack is behind the `msgpack` feature. It is self-describing, so
+//! it round-trips everything JSON does, including the free-form
+//! `serde_json::Value` extensions on telemetry. Bincode was dropped because
+//! it cannot read those back; values an older replica wrote with it are
+//! treated as failed cache reads until they expire.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::{PersistenceError, Result};
+
+/// Marker for values written as JSON
+const JSON_MARKER: u8 = 0x01;
+
+/// Marker for values written with bincode, which is no longer supported
+const BINCODE_MARKER: u8 = 0x02;
+
+/// Marker for values written as MessagePack
+const MSGPACK_MARKER: u8 = 0x03;
+
+/// Encoding for values written to the cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheCodec {
+    /// Readable with `redis-cli`; the largest and slowest
+    #[default]
+    Json,
+    /// Self-describing binary with named fields
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+}
+
+impl CacheCodec {
+    /// Every codec compiled into this build
+    pub const ALL: &'static [Self] = &[
+        Self::Json,
+        #[cfg(feature = "msgpack")]
+        Self::MessagePack,
+    ];
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => "msgpack",
+        }
+    }
+
+    /// Parse a codec name; `None` when unknown or not compiled in
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|codec| codec.as_str() == s)
+    }
+
+    fn marker(self) -> u8 {
+        match self {
+            Self::Json => JSON_MARKER,
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => MSGPACK_MARKER,
+        }
+    }
+
+    /// Encode a value behind this codec's marker byte
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>> {
+        let mut bytes = vec![self.marker()];
+        match self {
+            Self::Json => serde_json::to_writer(&mut bytes, value)?,
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => {
+                rmp_serde::encode::write_named(&mut bytes, value).map_err(codec_error)?;
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+/// Decode a value written by any codec, or an unmarked legacy JSON value
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    match bytes.split_first() {
+        Some((&JSON_MARKER, rest)) => Ok(serde_json::from_slice(rest)?),
+        Some((&BINCODE_MARKER, _)) => Err(PersistenceError::SerializationError(
+            "cached value was written with the retired bincode codec".to_string(),
+        )),
+        Some((&MSGPACK_MARKER, rest)) => decode_msgpack(rest),
+        _ => Ok(serde_json::from_slice(bytes)?),
+    }
+}
+
+#[cfg(feature = "msgpack")]
+fn decode_msgpack<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    rmp_serde::from_slice(bytes).map_err(codec_error)
+}
+
+#[cfg(not(feature = "msgpack"))]
+fn decode_msgpack<T: DeserializeOwned>(_bytes: &[u8]) -> Result<T> {
+    Err(missing_codec("msgpack"))
+}
+
+#[cfg(feature = "msgpack")]
+fn codec_error(err: impl std::fmt::Display) -> PersistenceError {
+    PersistenceError::SerializationError(err.to_string())
+}
+
+/// A replica built without a codec another replica writes with
+#[cfg(not(feature = "msgpack"))]
+fn missing_codec(name: &str) -> PersistenceError {
+    PersistenceError::SerializationError(format!(
+        "cached value was written with the {name} codec, which this build does not include"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use drone_domain::{Coordinates, Telemetry};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        drone_id: String,
+        altitude_m: f64,
+        fuel_pct: Option<f32>,
+    }
+
+    #[test]
+    fn test_every_codec_round_trips() {
+        let point = Point {
+            drone_id: "REAPER-1".to_string(),
+            altitude_m: 7_620.5,
+            fuel_pct: Some(63.0),
+        };
+
+        for codec in CacheCodec::ALL {
+            let bytes = codec.encode(&point).unwrap();
+            assert_eq!(bytes[0], codec.marker());
+            assert_eq!(decode::<Point>(&bytes).unwrap(), point, "{}", codec.as_str());
+            assert_eq!(CacheCodec::parse(codec.as_str()), Some(*codec));
+        }
+    }
+
+    #[test]
+    fn test_every_codec_round_trips_telemetry_extensions() {
+        let recorded_at = chrono::DateTime::from_timestamp_millis(1_700_000_000_000).unwrap();
+        let telemetry = Telemetry {
+            drone_id: uuid::Uuid::from_u128(100),
+            time_bucket: Telemetry::generate_time_bucket(&recorded_at),
+            recorded_at,
+            position: Coordinates::new(34.5, 69.2, 7_620.0),
+            velocity_mps: 82.5,
+            acceleration_mps2: 0.4,
+            bank_angle_deg: 12.0,
+            pitch_angle_deg: 1.5,
+            current_waypoint: 4,
+            distance_to_next_km: 18.2,
+            eta_next_waypoint: None,
+            fuel_remaining_pct: 63.0,
+            engine_rpm: 2_450,
+            engine_temp_c: 88.0,
+            battery_voltage: 27.6,
+            wind_speed_mps: 9.0,
+            wind_direction_deg: 270.0,
+            temperature_c: -21.0,
+            visibility_km: 16.0,
+            link_status: None,
+            mesh_connectivity: 0.92,
+            schema_version: 2,
+            extensions: [
+                ("satcomBeam".to_string(), serde_json::json!(7)),
+                ("icing".to_string(), serde_json::json!({ "risk": 0.9, "zones": ["A", "B"] })),
+                ("retasked".to_string(), serde_json::Value::Null),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        for codec in CacheCodec::ALL {
+            let bytes = codec.encode(&telemetry).unwrap();
+            assert_eq!(decode::<Telemetry>(&bytes).unwrap(), telemetry, "{}", codec.as_str());
+        }
+    }
+
+    #[test]
+    fn test_retired_bincode_values_fail_to_read() {
+        assert!(decode::<Point>(&[BINCODE_MARKER, 0, 1]).is_err());
+    }
+
+    #[test]
+    fn test_unmarked_values_read_as_json() {
+        let legacy = br#"{"drone_id":"REAPER-1","altitude_m":100.0,"fuel_pct":null}"#;
+        let point: Point = decode(legacy).unwrap();
+        assert_eq!(point.altitude_m, 100.0);
+        assert_eq!(decode::<Vec<i32>>(b"[1,2]").unwrap(), vec![1, 2]);
+    }
+}
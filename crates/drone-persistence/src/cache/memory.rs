@@ -0,0 +1,618 @@
+//! # In-Process Cache Store
+//!
+//! The subset of Redis commands [`CacheClient`](super::CacheClient) relies on,
+//! held in process memory. Used for single-binary deployments that run
+//! without a Redis server; nothing survives a restart and nothing is shared
+//! between replicas.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use tokio::sync::broadcast;
+
+/// Writes between sweeps for expired keys that were never read again
+const SWEEP_INTERVAL: u64 = 1024;
+
+/// Messages buffered per channel for slow subscribers
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Keyspace and Pub/Sub channels shared by every clone of a client
+pub(crate) struct MemoryStore {
+    keys: Mutex<Keyspace>,
+    channels: Mutex<HashMap<String, broadcast::Sender<String>>>,
+}
+
+impl MemoryStore {
+    pub(crate) fn new() -> Self {
+        Self {
+            keys: Mutex::new(Keyspace::default()),
+            channels: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run commands against the keyspace under a single lock, so a group of
+    /// commands is atomic like a `MULTI`/`EXEC` pipeline
+    pub(crate) fn with<T>(&self, f: impl FnOnce(&mut Keyspace) -> T) -> T {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut keys)
+    }
+
+    /// Publish a message; returns how many subscribers received it
+    pub(crate) fn publish(&self, channel: &str, payload: &str) -> i64 {
+        let channels = self.channels.lock().unwrap_or_else(PoisonError::into_inner);
+        channels
+            .get(channel)
+            .and_then(|sender| sender.send(payload.to_string()).ok())
+            .map_or(0, |receivers| i64::try_from(receivers).unwrap_or(i64::MAX))
+    }
+
+    /// Subscribe to a channel
+    pub(crate) fn subscribe(&self, channel: &str) -> broadcast::Receiver<String> {
+        let mut channels = self.channels.lock().unwrap_or_else(PoisonError::into_inner);
+        channels
+            .entry(channel.to_string())
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+}
+
+/// Keys by type; a key lives in at most one of the maps
+#[derive(Default)]
+pub(crate) struct Keyspace {
+    strings: HashMap<String, Vec<u8>>,
+    hashes: HashMap<String, HashMap<String, String>>,
+    sets: HashMap<String, HashSet<String>>,
+    zsets: HashMap<String, SortedSet>,
+    streams: HashMap<String, VecDeque<StreamEntry>>,
+    expires_at: HashMap<String, Instant>,
+    writes: u64,
+}
+
+impl Keyspace {
+    // =========================================================================
+    // KEYS
+    // =========================================================================
+
+    /// Delete keys, returning how many existed
+    pub(crate) fn del<K: AsRef<str>>(&mut self, keys: &[K]) -> i64 {
+        let mut deleted = 0;
+        for key in keys {
+            if self.exists(key.as_ref()) {
+                self.remove(key.as_ref());
+                deleted += 1;
+            }
+        }
+        deleted
+    }
+
+    pub(crate) fn exists(&mut self, key: &str) -> bool {
+        self.expire_if_due(key);
+        self.kind(key).is_some()
+    }
+
+    /// Set a key's time to live; missing keys are left alone
+    pub(crate) fn expire(&mut self, key: &str, ttl: Duration) {
+        if self.exists(key) {
+            self.expires_at.insert(key.to_string(), Instant::now() + ttl);
+            self.note_write();
+        }
+    }
+
+    // =========================================================================
+    // STRINGS
+    // =========================================================================
+
+    pub(crate) fn get(&mut self, key: &str) -> Option<Vec<u8>> {
+        self.expire_if_due(key);
+        self.strings.get(key).cloned()
+    }
+
+    pub(crate) fn set_ex(&mut self, key: &str, value: Vec<u8>, ttl: Duration) {
+        self.remove(key);
+        self.strings.insert(key.to_string(), value);
+        self.expire(key, ttl);
+    }
+
+    /// Add to an integer value, starting from zero; returns the new value
+    pub(crate) fn incr(&mut self, key: &str, by: i64) -> i64 {
+        self.expire_if_due(key);
+        self.claim(key, Kind::String);
+        let value = self.strings.entry(key.to_string()).or_default();
+        let current = std::str::from_utf8(value).ok().and_then(|v| v.parse::<i64>().ok());
+        let next = current.unwrap_or(0).saturating_add(by);
+        *value = next.to_string().into_bytes();
+        next
+    }
+
+    // =========================================================================
+    // HASHES
+    // =========================================================================
+
+    pub(crate) fn hset_multiple(&mut self, key: &str, fields: &[(&str, String)]) {
+        if fields.is_empty() {
+            return;
+        }
+        let hash = self.hash_mut(key);
+        for (field, value) in fields {
+            hash.insert((*field).to_string(), value.clone());
+        }
+    }
+
+    /// Set a field only if it is absent
+    pub(crate) fn hset_nx(&mut self, key: &str, field: &str, value: String) {
+        self.hash_mut(key).entry(field.to_string()).or_insert(value);
+    }
+
+    /// Add to an integer field, starting from zero; returns the new value
+    pub(crate) fn hincr(&mut self, key: &str, field: &str, by: i64) -> i64 {
+        let value = self.hash_mut(key).entry(field.to_string()).or_default();
+        let next = value.parse::<i64>().unwrap_or(0).saturating_add(by);
+        *value = next.to_string();
+        next
+    }
+
+    pub(crate) fn hget(&mut self, key: &str, field: &str) -> Option<String> {
+        self.expire_if_due(key);
+        self.hashes.get(key)?.get(field).cloned()
+    }
+
+    pub(crate) fn hgetall(&mut self, key: &str) -> HashMap<String, String> {
+        self.expire_if_due(key);
+        self.hashes.get(key).cloned().unwrap_or_default()
+    }
+
+    // =========================================================================
+    // SETS
+    // =========================================================================
+
+    /// Add members, returning how many were not already present
+    pub(crate) fn sadd(&mut self, key: &str, members: &[String]) -> i64 {
+        self.expire_if_due(key);
+        self.claim(key, Kind::Set);
+        let set = self.sets.entry(key.to_string()).or_default();
+        let added = members.iter().filter(|m| set.insert((*m).clone())).count();
+        i64::try_from(added).unwrap_or(i64::MAX)
+    }
+
+    pub(crate) fn srem(&mut self, key: &str, member: &str) -> bool {
+        self.expire_if_due(key);
+        let removed = self.sets.get_mut(key).is_some_and(|set| set.remove(member));
+        if self.sets.get(key).is_some_and(HashSet::is_empty) {
+            self.remove(key);
+        }
+        removed
+    }
+
+    pub(crate) fn smembers(&mut self, key: &str) -> Vec<String> {
+        self.expire_if_due(key);
+        self.sets
+            .get(key)
+            .map(|set| set.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    // =========================================================================
+    // SORTED SETS
+    // =========================================================================
+
+    /// Add or rescore a member; returns whether it was new
+    pub(crate) fn zadd(&mut self, key: &str, member: &str, score: f64) -> bool {
+        self.zset_mut(key).insert(member, score)
+    }
+
+    /// Add a member, or rescore it only if the new score is greater (`ZADD GT`)
+    pub(crate) fn zadd_gt(&mut self, key: &str, member: &str, score: f64) {
+        let zset = self.zset_mut(key);
+        if zset.score(member).is_none_or(|current| score > current) {
+            zset.insert(member, score);
+        }
+    }
+
+    /// Remove members, returning how many were present
+    pub(crate) fn zrem<M: AsRef<str>>(&mut self, key: &str, members: &[M]) -> i64 {
+        self.expire_if_due(key);
+        let Some(zset) = self.zsets.get_mut(key) else {
+            return 0;
+        };
+        let removed = members.iter().filter(|m| zset.remove(m.as_ref())).count();
+        self.drop_if_empty(key);
+        i64::try_from(removed).unwrap_or(i64::MAX)
+    }
+
+    pub(crate) fn zcard(&mut self, key: &str) -> usize {
+        self.expire_if_due(key);
+        self.zsets.get(key).map_or(0, SortedSet::len)
+    }
+
+    /// Members ranked `start..=stop` by ascending score, Redis index rules
+    pub(crate) fn zrange(&mut self, key: &str, start: isize, stop: isize) -> Vec<String> {
+        self.ranked(key, start, stop, false)
+            .into_iter()
+            .map(|(member, _)| member)
+            .collect()
+    }
+
+    /// Members and scores ranked `start..=stop` by descending score
+    pub(crate) fn zrevrange_withscores(
+        &mut self,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> Vec<(String, f64)> {
+        self.ranked(key, start, stop, true)
+    }
+
+    /// Members ranked `start..=stop` by descending score
+    pub(crate) fn zrevrange(&mut self, key: &str, start: isize, stop: isize) -> Vec<String> {
+        self.ranked(key, start, stop, true)
+            .into_iter()
+            .map(|(member, _)| member)
+            .collect()
+    }
+
+    /// 0-based rank of a member by descending score
+    pub(crate) fn zrevrank(&mut self, key: &str, member: &str) -> Option<i64> {
+        self.expire_if_due(key);
+        let zset = self.zsets.get(key)?;
+        let score = zset.score(member)?;
+        let below = zset.order.range(..(Score(score), member.to_string())).count();
+        i64::try_from(zset.len() - below - 1).ok()
+    }
+
+    /// Members whose score falls within `scores`, ascending
+    pub(crate) fn zrangebyscore(&mut self, key: &str, scores: (Bound<f64>, Bound<f64>)) -> Vec<String> {
+        self.expire_if_due(key);
+        self.zsets
+            .get(key)
+            .map(|zset| {
+                zset.order
+                    .iter()
+                    .filter(|(score, _)| scores.contains(&score.0))
+                    .map(|(_, member)| member.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Remove members scored at or below `max`
+    pub(crate) fn zrembyscore(&mut self, key: &str, max: f64) {
+        let expired = self.zrangebyscore(key, (Bound::Unbounded, Bound::Included(max)));
+        self.zrem(key, &expired);
+    }
+
+    /// Remove members ranked `start..=stop` by ascending score
+    pub(crate) fn zremrangebyrank(&mut self, key: &str, start: isize, stop: isize) {
+        let ranked = self.zrange(key, start, stop);
+        self.zrem(key, &ranked);
+    }
+
+    /// Up to `limit` members starting with `prefix`, in member order
+    ///
+    /// Equivalent to `ZRANGEBYLEX` over a set whose scores are all equal.
+    pub(crate) fn zrange_prefix(&mut self, key: &str, prefix: &str, limit: usize) -> Vec<String> {
+        self.expire_if_due(key);
+        self.zsets
+            .get(key)
+            .map(|zset| {
+                zset.order
+                    .iter()
+                    .map(|(_, member)| member)
+                    .filter(|member| member.starts_with(prefix))
+                    .take(limit)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // =========================================================================
+    // STREAMS
+    // =========================================================================
+
+    /// Append an entry stamped `at_ms`
+    pub(crate) fn xadd(&mut self, key: &str, at_ms: i64, value: String) {
+        self.expire_if_due(key);
+        self.claim(key, Kind::Stream);
+        self.streams
+            .entry(key.to_string())
+            .or_default()
+            .push_back(StreamEntry { at_ms, value });
+        self.note_write();
+    }
+
+    /// Drop entries stamped before `min_ms` (`XTRIM MINID`)
+    pub(crate) fn xtrim_minid(&mut self, key: &str, min_ms: i64) {
+        if let Some(stream) = self.streams.get_mut(key) {
+            while stream.front().is_some_and(|entry| entry.at_ms < min_ms) {
+                stream.pop_front();
+            }
+        }
+        self.drop_if_empty(key);
+    }
+
+    /// Keep only the newest `max_len` entries (`XTRIM MAXLEN`)
+    pub(crate) fn xtrim_maxlen(&mut self, key: &str, max_len: usize) {
+        if let Some(stream) = self.streams.get_mut(key) {
+            let excess = stream.len().saturating_sub(max_len);
+            stream.drain(..excess);
+        }
+        self.drop_if_empty(key);
+    }
+
+    /// Entry values, oldest first
+    pub(crate) fn xrange(&mut self, key: &str) -> Vec<String> {
+        self.expire_if_due(key);
+        self.streams
+            .get(key)
+            .map(|stream| stream.iter().map(|entry| entry.value.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    // =========================================================================
+    // INTERNALS
+    // =========================================================================
+
+    fn ranked(&mut self, key: &str, start: isize, stop: isize, rev: bool) -> Vec<(String, f64)> {
+        self.expire_if_due(key);
+        let Some(zset) = self.zsets.get(key) else {
+            return Vec::new();
+        };
+        let Some(range) = rank_range(zset.len(), start, stop) else {
+            return Vec::new();
+        };
+        let entries = zset.order.iter().map(|(score, member)| (member.clone(), score.0));
+        let skip = *range.start();
+        let take = range.end() - range.start() + 1;
+        if rev {
+            entries.rev().skip(skip).take(take).collect()
+        } else {
+            entries.skip(skip).take(take).collect()
+        }
+    }
+
+    fn hash_mut(&mut self, key: &str) -> &mut HashMap<String, String> {
+        self.expire_if_due(key);
+        self.claim(key, Kind::Hash);
+        self.hashes.entry(key.to_string()).or_default()
+    }
+
+    fn zset_mut(&mut self, key: &str) -> &mut SortedSet {
+        self.expire_if_due(key);
+        self.claim(key, Kind::SortedSet);
+        self.zsets.entry(key.to_string()).or_default()
+    }
+
+    /// Drop a key held as another type before writing it as `kind`, like
+    /// Redis overwriting rather than failing with `WRONGTYPE`
+    fn claim(&mut self, key: &str, kind: Kind) {
+        if self.kind(key).is_some_and(|held| held != kind) {
+            self.remove(key);
+        }
+    }
+
+    fn kind(&self, key: &str) -> Option<Kind> {
+        if self.strings.contains_key(key) {
+            Some(Kind::String)
+        } else if self.hashes.contains_key(key) {
+            Some(Kind::Hash)
+        } else if self.sets.contains_key(key) {
+            Some(Kind::Set)
+        } else if self.zsets.contains_key(key) {
+            Some(Kind::SortedSet)
+        } else if self.streams.contains_key(key) {
+            Some(Kind::Stream)
+        } else {
+            None
+        }
+    }
+
+    fn drop_if_empty(&mut self, key: &str) {
+        let empty = self.zsets.get(key).is_some_and(|zset| zset.len() == 0)
+            || self.streams.get(key).is_some_and(VecDeque::is_empty);
+        if empty {
+            self.remove(key);
+        }
+    }
+
+    /// Remove a key of any type; returns whether it held a value
+    fn remove(&mut self, key: &str) -> bool {
+        self.expires_at.remove(key);
+        let string = self.strings.remove(key).is_some();
+        let hash = self.hashes.remove(key).is_some();
+        let set = self.sets.remove(key).is_some();
+        let zset = self.zsets.remove(key).is_some();
+        let stream = self.streams.remove(key).is_some();
+        string || hash || set || zset || stream
+    }
+
+    fn expire_if_due(&mut self, key: &str) {
+        if self.expires_at.get(key).is_some_and(|at| *at <= Instant::now()) {
+            self.remove(key);
+        }
+    }
+
+    /// Count a write, sweeping expired keys every [`SWEEP_INTERVAL`] writes
+    fn note_write(&mut self) {
+        self.writes += 1;
+        if self.writes.is_multiple_of(SWEEP_INTERVAL) {
+            let now = Instant::now();
+            let due: Vec<String> = self
+                .expires_at
+                .iter()
+                .filter(|(_, at)| **at <= now)
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in due {
+                self.remove(&key);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    String,
+    Hash,
+    Set,
+    SortedSet,
+    Stream,
+}
+
+/// Stream entry; the timestamp stands in for the Redis entry ID
+struct StreamEntry {
+    at_ms: i64,
+    value: String,
+}
+
+/// Sorted set score for an integer such as a millisecond timestamp
+///
+/// Exact up to 2^53, as in Redis, which is far beyond any timestamp or
+/// counter stored here.
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn int_score(value: i64) -> f64 {
+    value as f64
+}
+
+/// Resolve Redis-style `start..=stop` ranks, where negative indexes count
+/// from the end, against a collection of `len` items
+fn rank_range(len: usize, start: isize, stop: isize) -> Option<RangeInclusive<usize>> {
+    let resolve = |index: isize| {
+        if index < 0 {
+            len.checked_sub(index.unsigned_abs())
+        } else {
+            Some(index.unsigned_abs())
+        }
+    };
+    let start = resolve(start).unwrap_or(0);
+    let stop = resolve(stop)?.min(len.checked_sub(1)?);
+    (start <= stop).then_some(start..=stop)
+}
+
+/// Score with a total order so it can key a `BTreeSet`
+#[derive(Debug, Clone, Copy)]
+struct Score(f64);
+
+impl PartialEq for Score {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Members ordered by `(score, member)`, as Redis orders a sorted set
+#[derive(Default)]
+struct SortedSet {
+    scores: HashMap<String, f64>,
+    order: BTreeSet<(Score, String)>,
+}
+
+impl SortedSet {
+    fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    fn score(&self, member: &str) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    fn insert(&mut self, member: &str, score: f64) -> bool {
+        let previous = self.scores.insert(member.to_string(), score);
+        if let Some(previous) = previous {
+            self.order.remove(&(Score(previous), member.to_string()));
+        }
+        self.order.insert((Score(score), member.to_string()));
+        previous.is_none()
+    }
+
+    fn remove(&mut self, member: &str) -> bool {
+        match self.scores.remove(member) {
+            Some(score) => self.order.remove(&(Score(score), member.to_string())),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_range_follows_redis_indexes() {
+        assert_eq!(rank_range(5, 0, -1), Some(0..=4));
+        assert_eq!(rank_range(5, 0, 9), Some(0..=4));
+        assert_eq!(rank_range(5, -2, -1), Some(3..=4));
+        assert_eq!(rank_range(5, -9, 1), Some(0..=1));
+        assert_eq!(rank_range(5, 0, -6), None);
+        assert_eq!(rank_range(0, 0, -1), None);
+        assert_eq!(rank_range(5, 3, 2), None);
+    }
+
+    #[test]
+    fn test_sorted_set_ranks_and_trims() {
+        let mut keys = Keyspace::default();
+        keys.zadd("z", "a", 3.0);
+        keys.zadd("z", "b", 1.0);
+        keys.zadd("z", "c", 2.0);
+        assert!(!keys.zadd("z", "b", 4.0));
+
+        assert_eq!(keys.zrevrange("z", 0, 1), vec!["b", "a"]);
+        assert_eq!(keys.zrevrank("z", "c"), Some(2));
+        assert_eq!(keys.zrangebyscore("z", (Bound::Excluded(2.0), Bound::Unbounded)), vec!["a", "b"]);
+
+        keys.zadd_gt("z", "a", 1.0);
+        assert_eq!(keys.zrevrank("z", "a"), Some(1));
+
+        // Keep only the newest two, as the telemetry history trim does
+        keys.zremrangebyrank("z", 0, -3);
+        assert_eq!(keys.zrange("z", 0, -1), vec!["a", "b"]);
+
+        keys.zrembyscore("z", 10.0);
+        assert!(!keys.exists("z"));
+    }
+
+    #[test]
+    fn test_stream_trims_by_age_and_length() {
+        let mut keys = Keyspace::default();
+        for (at_ms, value) in [(10, "a"), (20, "b"), (30, "c"), (40, "d")] {
+            keys.xadd("s", at_ms, value.to_string());
+        }
+
+        keys.xtrim_minid("s", 20);
+        assert_eq!(keys.xrange("s"), vec!["b", "c", "d"]);
+        keys.xtrim_maxlen("s", 2);
+        assert_eq!(keys.xrange("s"), vec!["c", "d"]);
+
+        keys.xtrim_minid("s", 100);
+        assert!(!keys.exists("s"));
+    }
+
+    #[test]
+    fn test_keys_expire() {
+        let mut keys = Keyspace::default();
+        keys.set_ex("gone", b"v".to_vec(), Duration::ZERO);
+        keys.set_ex("kept", b"v".to_vec(), Duration::from_mins(1));
+        assert_eq!(keys.get("gone"), None);
+        assert_eq!(keys.get("kept").as_deref(), Some(&b"v"[..]));
+
+        assert_eq!(keys.hincr("h", "n", 2), 2);
+        keys.expire("h", Duration::ZERO);
+        assert!(keys.hgetall("h").is_empty());
+        assert_eq!(keys.del(&["kept", "h"]), 1);
+    }
+}
@@ -1,7 +1,14 @@
 //! # Cache Module
 //!
-//! Redis cache layer for hot-path data access.
+//! Redis cache layer for hot-path data access, with an in-process
+//! fallback for deployments without Redis.
 
+pub mod codec;
+mod memory;
 pub mod redis_client;
 
-pub use redis_client::{CacheClient, CacheConfig, CacheTtl, SharedCacheClient, shared_cache};
+pub use codec::CacheCodec;
+pub use redis_client::{
+    AccuracyTally, CacheBackend, CacheClient, CacheConfig, CacheTtl, SharedCacheClient,
+    shared_cache,
+};
@@ -1,110 +1,329 @@
 //! # Redis Cache Layer
 //!
 //! Redis client wrapper with typed operations for drone convoy caching.
+//! The same operations can run against an in-process store instead, for
+//! deployments without a Redis server.
 
+use futures_util::{Stream, StreamExt};
 use redis::aio::ConnectionManager;
-use redis::{AsyncCommands, Client};
+use redis::{AsyncCommands, Client, FromRedisValue, Pipeline, RedisResult};
 use serde::{de::DeserializeOwned, Serialize};
+use std::future::Future;
+use std::ops::Bound;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
 use uuid::Uuid;
 
+use super::codec::{self, CacheCodec};
+use super::memory::{Keyspace, MemoryStore, int_score};
+use crate::breaker::{BreakerConfig, CircuitBreaker};
+use crate::chaos::{Chaos, ChaosConfig};
 use crate::error::Result;
+use drone_domain::{SearchEntry, SearchKind};
+
+/// Most points kept in a drone's telemetry history, bounding memory when a
+/// drone reports faster than expected
+const TELEMETRY_HISTORY_MAX_POINTS: isize = 10_000;
+
+/// Most events kept in a convoy's event stream, whatever their age
+const EVENT_STREAM_MAX_LEN: usize = 10_000;
+
+/// Sorted set of every convoy ranked by accuracy
+const CONVOY_RANKING_KEY: &str = "leaderboard:convoys";
+
+/// Engagement counts behind [`CONVOY_RANKING_KEY`]
+const CONVOY_TALLY_KEY: &str = "leaderboard:convoys:tally";
+
+/// Count one engagement for `ARGV[1]` in the tally hash `KEYS[1]` and re-score
+/// it in the sorted set `KEYS[2]`, so concurrent writers never leave a score
+/// behind its counts. `ARGV[2]` is 1 for a hit, 0 for a miss.
+const TALLY_ENGAGEMENT_SCRIPT: &str = r"
+local total = redis.call('HINCRBY', KEYS[1], ARGV[1] .. ':total', 1)
+local hits = redis.call('HINCRBY', KEYS[1], ARGV[1] .. ':hits', ARGV[2])
+redis.call('ZADD', KEYS[2], hits * 100 / total, ARGV[1])
+return {total, hits}
+";
 
 /// Cache TTL configuration
 #[derive(Debug, Clone, Copy)]
 pub struct CacheTtl {
     pub telemetry: Duration,
+    pub telemetry_history: Duration,
+    /// Window read by [`CacheClient::get_recent_telemetry`]
+    pub telemetry_recent: Duration,
     pub drone_state: Duration,
     pub leaderboard: Duration,
+    /// Leaderboard version counter and change log; clients idle longer
+    /// than this get a full leaderboard on their next diff
+    pub leaderboard_changes: Duration,
     pub convoy_summary: Duration,
     pub engagement_stats: Duration,
     pub convoy_roster: Duration,
+    /// How long broadcast events stay in a convoy's event stream
+    pub event_stream: Duration,
 }
 
 impl Default for CacheTtl {
     fn default() -> Self {
         Self {
             telemetry: Duration::from_secs(10),
+            telemetry_history: Duration::from_secs(3600),
+            telemetry_recent: Duration::from_secs(300),
             drone_state: Duration::from_secs(60),
             leaderboard: Duration::from_secs(300),
+            leaderboard_changes: Duration::from_secs(3600),
             convoy_summary: Duration::from_secs(120),
             engagement_stats: Duration::from_secs(300),
             convoy_roster: Duration::from_secs(3600),
+            event_stream: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Engagement counts for one entry of an accuracy ranking
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccuracyTally {
+    /// Weapon type code or convoy ID
+    pub member: String,
+    pub total_engagements: i64,
+    pub successful_hits: i64,
+}
+
+impl AccuracyTally {
+    /// Hit rate as a percentage, 0 before any engagement
+    #[must_use]
+    pub fn accuracy_pct(&self) -> f64 {
+        if self.total_engagements > 0 {
+            int_score(self.successful_hits) * 100.0 / int_score(self.total_engagements)
+        } else {
+            0.0
         }
     }
 }
 
+/// Where cached data lives
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheBackend {
+    /// Shared Redis server
+    #[default]
+    Redis,
+    /// In-process store; nothing is shared between replicas or survives a
+    /// restart
+    Memory,
+}
+
 /// Redis cache configuration
 #[derive(Debug, Clone)]
 pub struct CacheConfig {
+    pub backend: CacheBackend,
+    /// Redis URL; unused by the in-memory backend
     pub url: String,
     pub pool_size: usize,
     pub ttl: CacheTtl,
+    /// Encoding for values written with [`CacheClient::set_value`]; values
+    /// are read back with whichever codec wrote them
+    pub codec: CacheCodec,
+    pub breaker: BreakerConfig,
+    /// Faults injected into commands, keyed `redis.<command>`
+    pub chaos: ChaosConfig,
 }
 
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
+            backend: CacheBackend::Redis,
             url: "redis://127.0.0.1:6379".to_string(),
             pool_size: 10,
             ttl: CacheTtl::default(),
+            codec: CacheCodec::default(),
+            breaker: BreakerConfig::default(),
+            chaos: ChaosConfig::default(),
         }
     }
 }
 
 /// Redis cache client with connection pooling
+///
+/// Every command goes through a circuit breaker so a Redis outage fails
+/// fast instead of adding a timeout to each cached read.
 #[derive(Clone)]
 pub struct CacheClient {
-    conn: ConnectionManager,
+    backend: Backend,
     config: CacheConfig,
+    breaker: Arc<CircuitBreaker>,
+    chaos: Arc<Chaos>,
+}
+
+/// The Redis variant is much larger, but a client is built once per
+/// process and shared, so boxing it buys nothing
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+enum Backend {
+    Redis { client: Client, conn: ConnectionManager },
+    Memory(Arc<MemoryStore>),
 }
 
 impl CacheClient {
     /// Create a new cache client
     pub async fn new(config: CacheConfig) -> Result<Self> {
-        let client = Client::open(config.url.as_str())?;
-        let conn = ConnectionManager::new(client).await?;
+        let backend = match config.backend {
+            CacheBackend::Redis => {
+                let client = Client::open(config.url.as_str())?;
+                let conn = ConnectionManager::new(client.clone()).await?;
+                Backend::Redis { client, conn }
+            }
+            CacheBackend::Memory => Backend::Memory(Arc::new(MemoryStore::new())),
+        };
+        let breaker = Arc::new(CircuitBreaker::new("redis", config.breaker));
+        let chaos = Arc::new(Chaos::new(config.chaos.clone()));
+
+        Ok(Self { backend, config, breaker, chaos })
+    }
+
+    /// Which backend this client stores data in
+    #[must_use]
+    pub fn backend(&self) -> CacheBackend {
+        self.config.backend
+    }
+
+    /// Get raw connection for advanced operations; `None` for the
+    /// in-memory backend
+    ///
+    /// Commands issued on it bypass the circuit breaker.
+    #[must_use]
+    pub fn connection(&self) -> Option<ConnectionManager> {
+        match &self.backend {
+            Backend::Redis { conn, .. } => Some(conn.clone()),
+            Backend::Memory(_) => None,
+        }
+    }
 
-        Ok(Self { conn, config })
+    /// Circuit breaker guarding this client
+    pub fn breaker(&self) -> Arc<CircuitBreaker> {
+        self.breaker.clone()
     }
 
-    /// Get raw connection for advanced operations
-    pub fn connection(&self) -> ConnectionManager {
-        self.conn.clone()
+    /// Run a Redis command through the circuit breaker
+    ///
+    /// `op` names the command for chaos rules, e.g. `redis.get`.
+    async fn guarded<T>(&self, op: &str, cmd: impl Future<Output = RedisResult<T>>) -> Result<T> {
+        self.breaker.call(self.chaos.run(op, cmd)).await
+    }
+
+    /// Send a pipeline through the circuit breaker in a single round-trip
+    async fn guarded_pipe<T: FromRedisValue>(
+        &self,
+        op: &str,
+        conn: &mut ConnectionManager,
+        pipe: &Pipeline,
+    ) -> Result<T> {
+        self.guarded(op, pipe.query_async(conn)).await
     }
 
     // =========================================================================
-    // GENERIC OPERATIONS
+    // BATCHED WRITES (PIPELINED MULTI/EXEC)
     // =========================================================================
 
-    /// Get a JSON value from cache
-    pub async fn get_json<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
-        let mut conn = self.conn.clone();
-        let value: Option<String> = conn.get(key).await?;
-
-        match value {
-            Some(json) => {
-                let parsed = serde_json::from_str(&json)?;
-                Ok(Some(parsed))
+    /// Set several hash fields and refresh the key's TTL in one round-trip
+    ///
+    /// Runs as `MULTI`/`EXEC`, so the hash is never left without an expiry.
+    pub async fn hset_with_ttl(
+        &self,
+        key: &str,
+        fields: &[(&str, String)],
+        ttl: Duration,
+    ) -> Result<()> {
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                store.with(|keys| {
+                    keys.hset_multiple(key, fields);
+                    keys.expire(key, ttl);
+                });
+                return Ok(());
             }
-            None => Ok(None),
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        if !fields.is_empty() {
+            pipe.hset_multiple(key, fields).ignore();
         }
+        pipe.expire(key, ttl.as_secs() as i64).ignore();
+
+        self.guarded_pipe("redis.pipeline.hset", &mut conn, &pipe).await
     }
 
-    /// Set a JSON value in cache with TTL
-    pub async fn set_json<T: Serialize>(&self, key: &str, value: &T, ttl: Duration) -> Result<()> {
-        let mut conn = self.conn.clone();
-        let json = serde_json::to_string(value)?;
-        let _: () = conn.set_ex(key, json, ttl.as_secs()).await?;
+    /// Add scored members to a sorted set and refresh its TTL in one round-trip
+    ///
+    /// Members are `(score, member)` pairs, as for `ZADD`.
+    pub async fn zadd_with_ttl(
+        &self,
+        key: &str,
+        members: &[(f64, String)],
+        ttl: Duration,
+    ) -> Result<()> {
+        if members.is_empty() {
+            return Ok(());
+        }
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                store.with(|keys| {
+                    for (score, member) in members {
+                        keys.zadd(key, member, *score);
+                    }
+                    keys.expire(key, ttl);
+                });
+                return Ok(());
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .zadd_multiple(key, members)
+            .ignore()
+            .expire(key, ttl.as_secs() as i64)
+            .ignore();
+
+        self.guarded_pipe("redis.pipeline.zadd", &mut conn, &pipe).await
+    }
+
+    // =========================================================================
+    // GENERIC OPERATIONS
+    // =========================================================================
+
+    /// Get a value from cache, whichever codec wrote it
+    ///
+    /// Plain JSON written outside the client, e.g. with `redis-cli`, is
+    /// read as well.
+    pub async fn get_value<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        let value: Option<Vec<u8>> = match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.get", conn.clone().get(key)).await?,
+            Backend::Memory(store) => store.with(|keys| keys.get(key)),
+        };
+
+        value.map(|bytes| codec::decode(&bytes)).transpose()
+    }
+
+    /// Set a value in cache with TTL, encoded with the configured codec
+    pub async fn set_value<T: Serialize>(&self, key: &str, value: &T, ttl: Duration) -> Result<()> {
+        let bytes = self.config.codec.encode(value)?;
+        match &self.backend {
+            Backend::Redis { conn, .. } => {
+                let _: () = self
+                    .guarded("redis.set_ex", conn.clone().set_ex(key, bytes, ttl.as_secs()))
+                    .await?;
+            }
+            Backend::Memory(store) => store.with(|keys| keys.set_ex(key, bytes, ttl)),
+        }
         Ok(())
     }
 
     /// Delete a key from cache
     pub async fn delete(&self, key: &str) -> Result<bool> {
-        let mut conn = self.conn.clone();
-        let deleted: i64 = conn.del(key).await?;
-        Ok(deleted > 0)
+        Ok(self.delete_many(&[key.to_string()]).await? > 0)
     }
 
     /// Delete multiple keys
@@ -112,35 +331,152 @@ impl CacheClient {
         if keys.is_empty() {
             return Ok(0);
         }
-        let mut conn = self.conn.clone();
-        let deleted: i64 = conn.del(keys).await?;
-        Ok(deleted)
+        match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.del", conn.clone().del(keys)).await,
+            Backend::Memory(store) => Ok(store.with(|space| space.del(keys))),
+        }
     }
 
     /// Check if key exists
     pub async fn exists(&self, key: &str) -> Result<bool> {
-        let mut conn = self.conn.clone();
-        let exists: bool = conn.exists(key).await?;
-        Ok(exists)
+        match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.exists", conn.clone().exists(key)).await,
+            Backend::Memory(store) => Ok(store.with(|keys| keys.exists(key))),
+        }
+    }
+
+    // =========================================================================
+    // PUB/SUB
+    // =========================================================================
+
+    /// Publish a message; returns how many subscribers received it
+    pub async fn publish(&self, channel: &str, payload: &str) -> Result<i64> {
+        match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.publish", conn.clone().publish(channel, payload)).await,
+            Backend::Memory(store) => Ok(store.publish(channel, payload)),
+        }
+    }
+
+    /// Subscribe to a channel on a dedicated connection
+    ///
+    /// The stream ends when the connection drops; callers resubscribe.
+    /// Messages whose payload is not a string are skipped.
+    pub async fn subscribe(&self, channel: &str) -> Result<impl Stream<Item = String> + Send + use<>> {
+        let client = match &self.backend {
+            Backend::Redis { client, .. } => client,
+            Backend::Memory(store) => {
+                // A subscriber that falls behind skips what it missed, like a
+                // Redis subscriber does after a reconnect
+                let rx = store.subscribe(channel);
+                return Ok(futures_util::stream::unfold(rx, |mut rx| async move {
+                    loop {
+                        match rx.recv().await {
+                            Ok(msg) => return Some((msg, rx)),
+                            Err(RecvError::Lagged(_)) => {}
+                            Err(RecvError::Closed) => return None,
+                        }
+                    }
+                })
+                .boxed());
+            }
+        };
+        let mut pubsub = client.get_async_pubsub().await?;
+        pubsub.subscribe(channel).await?;
+        Ok(pubsub
+            .into_on_message()
+            .filter_map(|msg| async move { msg.get_payload::<String>().ok() })
+            .boxed())
+    }
+
+    // =========================================================================
+    // EVENT STREAMS (STREAM)
+    // =========================================================================
+
+    /// Append a broadcast event to a convoy's event stream
+    ///
+    /// Entries older than the event stream TTL, or beyond the newest 10,000,
+    /// are trimmed on write, and an idle convoy's stream expires.
+    pub async fn append_convoy_event(&self, convoy_id: Uuid, event: &str, now_ms: i64) -> Result<()> {
+        let key = format!("convoy:events:{convoy_id}");
+        let retention = self.config.ttl.event_stream;
+        let cutoff = now_ms - retention.as_millis() as i64;
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                store.with(|keys| {
+                    keys.xadd(&key, now_ms, event.to_string());
+                    keys.xtrim_minid(&key, cutoff);
+                    keys.xtrim_maxlen(&key, EVENT_STREAM_MAX_LEN);
+                    keys.expire(&key, retention);
+                });
+                return Ok(());
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .cmd("XADD")
+            .arg(&key)
+            .arg("MINID")
+            .arg("~")
+            .arg(cutoff)
+            .arg("*")
+            .arg("event")
+            .arg(event)
+            .ignore()
+            .cmd("XTRIM")
+            .arg(&key)
+            .arg("MAXLEN")
+            .arg("~")
+            .arg(EVENT_STREAM_MAX_LEN)
+            .ignore()
+            .expire(&key, retention.as_secs() as i64)
+            .ignore();
+
+        self.guarded_pipe("redis.pipeline.convoy_events", &mut conn, &pipe).await
+    }
+
+    /// Events in a convoy's event stream, oldest first
+    pub async fn get_convoy_events(&self, convoy_id: Uuid) -> Result<Vec<String>> {
+        let key = format!("convoy:events:{convoy_id}");
+
+        match &self.backend {
+            Backend::Redis { conn, .. } => {
+                let mut cmd = redis::cmd("XRANGE");
+                cmd.arg(&key).arg("-").arg("+").arg("COUNT").arg(EVENT_STREAM_MAX_LEN);
+                let entries: Vec<(String, Vec<String>)> =
+                    self.guarded("redis.xrange", cmd.query_async(&mut conn.clone())).await?;
+                // Each entry's fields come back flattened as `event, <json>`
+                Ok(entries
+                    .into_iter()
+                    .filter_map(|(_, fields)| fields.into_iter().nth(1))
+                    .collect())
+            }
+            Backend::Memory(store) => Ok(store.with(|keys| keys.xrange(&key))),
+        }
     }
 
     // =========================================================================
     // LEADERBOARD OPERATIONS (SORTED SET)
     // =========================================================================
 
-    /// Get convoy leaderboard (top N by accuracy)
+    /// Get convoy leaderboard (top N by score)
     pub async fn get_leaderboard(
         &self,
         convoy_id: Uuid,
         limit: usize,
     ) -> Result<Vec<(Uuid, f64)>> {
         let key = format!("convoy:leaderboard:{convoy_id}");
-        let mut conn = self.conn.clone();
+        let stop = isize::try_from(limit).unwrap_or(isize::MAX) - 1;
 
-        // ZREVRANGE with scores (highest accuracy first)
-        let results: Vec<(String, f64)> = conn
-            .zrevrange_withscores(&key, 0, (limit - 1) as isize)
-            .await?;
+        // ZREVRANGE with scores (highest score first)
+        let results: Vec<(String, f64)> = match &self.backend {
+            Backend::Redis { conn, .. } => {
+                self.guarded("redis.zrevrange_withscores", conn.clone().zrevrange_withscores(&key, 0, stop))
+                    .await?
+            }
+            Backend::Memory(store) => store.with(|keys| keys.zrevrange_withscores(&key, 0, stop)),
+        };
 
         let parsed: Vec<(Uuid, f64)> = results
             .into_iter()
@@ -152,41 +488,347 @@ impl CacheClient {
         Ok(parsed)
     }
 
-    /// Update drone accuracy in leaderboard
+    /// Update drone score in leaderboard
     pub async fn update_leaderboard_score(
         &self,
         convoy_id: Uuid,
         drone_id: Uuid,
-        accuracy_pct: f64,
+        score: f64,
+    ) -> Result<()> {
+        self.update_leaderboard_scores(convoy_id, &[(drone_id, score)])
+            .await
+    }
+
+    /// Update several drone scores in one round-trip
+    pub async fn update_leaderboard_scores(
+        &self,
+        convoy_id: Uuid,
+        scores: &[(Uuid, f64)],
     ) -> Result<()> {
         let key = format!("convoy:leaderboard:{convoy_id}");
-        let mut conn = self.conn.clone();
+        let members: Vec<(f64, String)> = scores
+            .iter()
+            .map(|(drone_id, score)| (*score, drone_id.to_string()))
+            .collect();
 
-        let _: () = conn.zadd(&key, drone_id.to_string(), accuracy_pct).await?;
-        let _: () = conn.expire(&key, self.config.ttl.leaderboard.as_secs() as i64)
-            .await?;
+        self.zadd_with_ttl(&key, &members, self.config.ttl.leaderboard)
+            .await
+    }
 
-        Ok(())
+    /// Get drone IDs ranked `start..=stop` (0-indexed, negative counts from the end)
+    pub async fn get_leaderboard_range(
+        &self,
+        convoy_id: Uuid,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<Uuid>> {
+        let key = format!("convoy:leaderboard:{convoy_id}");
+
+        let members: Vec<String> = match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.zrevrange", conn.clone().zrevrange(&key, start, stop)).await?,
+            Backend::Memory(store) => store.with(|keys| keys.zrevrange(&key, start, stop)),
+        };
+
+        Ok(members
+            .into_iter()
+            .filter_map(|s| Uuid::parse_str(&s).ok())
+            .collect())
     }
 
     /// Get drone rank in leaderboard (0-indexed, None if not present)
     pub async fn get_drone_rank(&self, convoy_id: Uuid, drone_id: Uuid) -> Result<Option<i64>> {
         let key = format!("convoy:leaderboard:{convoy_id}");
-        let mut conn = self.conn.clone();
 
-        let rank: Option<i64> = conn.zrevrank(&key, drone_id.to_string()).await?;
-        Ok(rank)
+        match &self.backend {
+            Backend::Redis { conn, .. } => {
+                self.guarded("redis.zrevrank", conn.clone().zrevrank(&key, drone_id.to_string())).await
+            }
+            Backend::Memory(store) => Ok(store.with(|keys| keys.zrevrank(&key, &drone_id.to_string()))),
+        }
     }
 
     /// Remove drone from leaderboard
     pub async fn remove_from_leaderboard(&self, convoy_id: Uuid, drone_id: Uuid) -> Result<bool> {
         let key = format!("convoy:leaderboard:{convoy_id}");
-        let mut conn = self.conn.clone();
 
-        let removed: i64 = conn.zrem(&key, drone_id.to_string()).await?;
+        let removed: i64 = match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.zrem", conn.clone().zrem(&key, drone_id.to_string())).await?,
+            Backend::Memory(store) => store.with(|keys| keys.zrem(&key, &[drone_id.to_string()])),
+        };
         Ok(removed > 0)
     }
 
+    // =========================================================================
+    // LEADERBOARD VERSIONS (HASH + SORTED SET)
+    // =========================================================================
+
+    /// Bump a convoy's leaderboard version and log `changed` drones at it
+    ///
+    /// Returns the `(epoch, version)` now current. The epoch is the time the
+    /// counter was created, so a counter that expired and restarted never
+    /// repeats an earlier token. Each drone is logged at the newest version
+    /// it changed in (`ZADD GT`), which keeps the log one member per drone.
+    pub async fn bump_leaderboard_version(
+        &self,
+        convoy_id: Uuid,
+        changed: &[Uuid],
+        now_ms: i64,
+    ) -> Result<(i64, i64)> {
+        let version_key = format!("convoy:leaderboard:version:{convoy_id}");
+        let changes_key = format!("convoy:leaderboard:changes:{convoy_id}");
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                let ttl = self.config.ttl.leaderboard_changes;
+                return Ok(store.with(|keys| {
+                    keys.hset_nx(&version_key, "epoch", now_ms.to_string());
+                    let version = keys.hincr(&version_key, "version", 1);
+                    let epoch = keys
+                        .hget(&version_key, "epoch")
+                        .and_then(|epoch| epoch.parse().ok())
+                        .unwrap_or(now_ms);
+                    keys.expire(&version_key, ttl);
+                    if !changed.is_empty() {
+                        for id in changed {
+                            keys.zadd_gt(&changes_key, &id.to_string(), int_score(version));
+                        }
+                        keys.expire(&changes_key, ttl);
+                    }
+                    (epoch, version)
+                }));
+            }
+        };
+        let ttl = self.config.ttl.leaderboard_changes.as_secs() as i64;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .hset_nx(&version_key, "epoch", now_ms)
+            .ignore()
+            .hincr(&version_key, "version", 1)
+            .hget(&version_key, "epoch")
+            .expire(&version_key, ttl)
+            .ignore();
+        let (version, epoch): (i64, i64) = self.guarded_pipe("redis.pipeline.leaderboard_version", &mut conn, &pipe).await?;
+
+        if !changed.is_empty() {
+            let mut pipe = redis::pipe();
+            let members: Vec<(i64, String)> =
+                changed.iter().map(|id| (version, id.to_string())).collect();
+            pipe.atomic()
+                .cmd("ZADD")
+                .arg(&changes_key)
+                .arg("GT")
+                .arg(members)
+                .ignore()
+                .expire(&changes_key, ttl)
+                .ignore();
+            self.guarded_pipe::<()>("redis.pipeline.leaderboard_changes", &mut conn, &pipe).await?;
+        }
+
+        Ok((epoch, version))
+    }
+
+    /// Current `(epoch, version)` of a convoy's leaderboard, `None` before
+    /// the first change or once the counter has expired
+    pub async fn get_leaderboard_version(&self, convoy_id: Uuid) -> Result<Option<(i64, i64)>> {
+        let key = format!("convoy:leaderboard:version:{convoy_id}");
+
+        let (epoch, version): (Option<i64>, Option<i64>) = match &self.backend {
+            Backend::Redis { conn, .. } => {
+                self.guarded("redis.hget", conn.clone().hget(&key, &["epoch", "version"]))
+                    .await?
+            }
+            Backend::Memory(store) => store.with(|keys| {
+                let field = |keys: &mut Keyspace, name| keys.hget(&key, name).and_then(|v| v.parse().ok());
+                (field(keys, "epoch"), field(keys, "version"))
+            }),
+        };
+        Ok(epoch.zip(version))
+    }
+
+    /// Drones whose leaderboard entry changed after `since_version`
+    pub async fn get_leaderboard_changes(
+        &self,
+        convoy_id: Uuid,
+        since_version: i64,
+    ) -> Result<Vec<Uuid>> {
+        let key = format!("convoy:leaderboard:changes:{convoy_id}");
+
+        let members: Vec<String> = match &self.backend {
+            Backend::Redis { conn, .. } => {
+                self.guarded("redis.zrangebyscore", conn.clone().zrangebyscore(&key, format!("({since_version}"), "+inf"))
+                    .await?
+            }
+            Backend::Memory(store) => store.with(|keys| {
+                keys.zrangebyscore(&key, (Bound::Excluded(int_score(since_version)), Bound::Unbounded))
+            }),
+        };
+
+        Ok(members
+            .into_iter()
+            .filter_map(|s| Uuid::parse_str(&s).ok())
+            .collect())
+    }
+
+    // =========================================================================
+    // ACCURACY RANKINGS (HASH + SORTED SET)
+    // =========================================================================
+    //
+    // Weapon types within a convoy and convoys overall, each a sorted set
+    // scored by accuracy beside a hash of `<member>:total` and `<member>:hits`
+    // counts. They have no TTL: counts only ever accumulate, and
+    // `replace_*` rebuilds them from recorded engagements.
+
+    /// Count an engagement towards its weapon type's and its convoy's ranking
+    pub async fn record_ranked_engagement(
+        &self,
+        convoy_id: Uuid,
+        weapon_type: &str,
+        hit: bool,
+    ) -> Result<()> {
+        let (weapon_tally, weapon_board) = weapon_ranking_keys(convoy_id);
+        self.tally_engagement(&weapon_tally, &weapon_board, weapon_type, hit).await?;
+        self.tally_engagement(CONVOY_TALLY_KEY, CONVOY_RANKING_KEY, &convoy_id.to_string(), hit)
+            .await
+    }
+
+    async fn tally_engagement(
+        &self,
+        tally_key: &str,
+        board_key: &str,
+        member: &str,
+        hit: bool,
+    ) -> Result<()> {
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                store.with(|keys| {
+                    let total = keys.hincr(tally_key, &format!("{member}:total"), 1);
+                    let hits = keys.hincr(tally_key, &format!("{member}:hits"), i64::from(hit));
+                    keys.zadd(board_key, member, int_score(hits) * 100.0 / int_score(total));
+                });
+                return Ok(());
+            }
+        };
+
+        let script = redis::Script::new(TALLY_ENGAGEMENT_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        invocation.key(tally_key).key(board_key).arg(member).arg(i64::from(hit));
+        let _: (i64, i64) =
+            self.guarded("redis.tally_engagement", invocation.invoke_async(&mut conn)).await?;
+        Ok(())
+    }
+
+    /// A convoy's weapon types, most accurate first
+    pub async fn get_weapon_rankings(&self, convoy_id: Uuid) -> Result<Vec<AccuracyTally>> {
+        let (tally_key, board_key) = weapon_ranking_keys(convoy_id);
+        self.get_rankings(&tally_key, &board_key).await
+    }
+
+    /// Every convoy with an engagement, most accurate first
+    pub async fn get_convoy_rankings(&self) -> Result<Vec<AccuracyTally>> {
+        self.get_rankings(CONVOY_TALLY_KEY, CONVOY_RANKING_KEY).await
+    }
+
+    async fn get_rankings(&self, tally_key: &str, board_key: &str) -> Result<Vec<AccuracyTally>> {
+        let (ranked, counts): (Vec<(String, f64)>, std::collections::HashMap<String, i64>) =
+            match &self.backend {
+                Backend::Redis { conn, .. } => {
+                    let mut pipe = redis::pipe();
+                    pipe.atomic().zrevrange_withscores(board_key, 0, -1).hgetall(tally_key);
+                    self.guarded_pipe("redis.pipeline.rankings", &mut conn.clone(), &pipe).await?
+                }
+                Backend::Memory(store) => store.with(|keys| {
+                    let counts = keys
+                        .hgetall(tally_key)
+                        .into_iter()
+                        .filter_map(|(field, count)| Some((field, count.parse().ok()?)))
+                        .collect();
+                    (keys.zrevrange_withscores(board_key, 0, -1), counts)
+                }),
+            };
+
+        let count = |member: &str, field: &str| {
+            counts.get(&format!("{member}:{field}")).copied().unwrap_or(0)
+        };
+        Ok(ranked
+            .into_iter()
+            .map(|(member, _)| AccuracyTally {
+                total_engagements: count(&member, "total"),
+                successful_hits: count(&member, "hits"),
+                member,
+            })
+            .collect())
+    }
+
+    /// Replace a convoy's weapon rankings and its entry in the convoy
+    /// ranking with counts rebuilt from its engagements
+    pub async fn replace_convoy_rankings(
+        &self,
+        convoy_id: Uuid,
+        weapons: &[AccuracyTally],
+    ) -> Result<()> {
+        let (weapon_tally, weapon_board) = weapon_ranking_keys(convoy_id);
+        let convoy = AccuracyTally {
+            member: convoy_id.to_string(),
+            total_engagements: weapons.iter().map(|w| w.total_engagements).sum(),
+            successful_hits: weapons.iter().map(|w| w.successful_hits).sum(),
+        };
+        let tally_fields = |tally: &AccuracyTally| {
+            [
+                (format!("{}:total", tally.member), tally.total_engagements.to_string()),
+                (format!("{}:hits", tally.member), tally.successful_hits.to_string()),
+            ]
+        };
+        let weapon_fields: Vec<(String, String)> = weapons.iter().flat_map(tally_fields).collect();
+        let convoy_fields = tally_fields(&convoy);
+        let weapon_scores: Vec<(f64, &str)> = weapons
+            .iter()
+            .filter(|w| w.total_engagements > 0)
+            .map(|w| (w.accuracy_pct(), w.member.as_str()))
+            .collect();
+
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                let borrowed = |fields: &[(String, String)]| -> Vec<(&str, String)> {
+                    fields.iter().map(|(f, v)| (f.as_str(), v.clone())).collect()
+                };
+                store.with(|keys| {
+                    keys.del(&[&weapon_tally, &weapon_board]);
+                    keys.hset_multiple(&weapon_tally, &borrowed(&weapon_fields));
+                    for (score, member) in &weapon_scores {
+                        keys.zadd(&weapon_board, member, *score);
+                    }
+                    keys.hset_multiple(CONVOY_TALLY_KEY, &borrowed(&convoy_fields));
+                    if convoy.total_engagements > 0 {
+                        keys.zadd(CONVOY_RANKING_KEY, &convoy.member, convoy.accuracy_pct());
+                    } else {
+                        keys.zrem(CONVOY_RANKING_KEY, &[&convoy.member]);
+                    }
+                });
+                return Ok(());
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().del(&[&weapon_tally, &weapon_board]).ignore();
+        if !weapon_scores.is_empty() {
+            pipe.hset_multiple(&weapon_tally, &weapon_fields)
+                .ignore()
+                .zadd_multiple(&weapon_board, &weapon_scores)
+                .ignore();
+        }
+        pipe.hset_multiple(CONVOY_TALLY_KEY, &convoy_fields).ignore();
+        if convoy.total_engagements > 0 {
+            pipe.zadd(CONVOY_RANKING_KEY, &convoy.member, convoy.accuracy_pct()).ignore();
+        } else {
+            pipe.zrem(CONVOY_RANKING_KEY, &convoy.member).ignore();
+        }
+
+        self.guarded_pipe("redis.pipeline.replace_rankings", &mut conn, &pipe).await
+    }
+
     // =========================================================================
     // DRONE STATE OPERATIONS (HASH)
     // =========================================================================
@@ -194,9 +836,11 @@ impl CacheClient {
     /// Get drone state hash
     pub async fn get_drone_state(&self, drone_id: Uuid) -> Result<Option<std::collections::HashMap<String, String>>> {
         let key = format!("drone:state:{drone_id}");
-        let mut conn = self.conn.clone();
 
-        let state: std::collections::HashMap<String, String> = conn.hgetall(&key).await?;
+        let state: std::collections::HashMap<String, String> = match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.hgetall", conn.clone().hgetall(&key)).await?,
+            Backend::Memory(store) => store.with(|keys| keys.hgetall(&key)),
+        };
         
         if state.is_empty() {
             Ok(None)
@@ -212,15 +856,8 @@ impl CacheClient {
         fields: &[(&str, String)],
     ) -> Result<()> {
         let key = format!("drone:state:{drone_id}");
-        let mut conn = self.conn.clone();
-
-        for (field, value) in fields {
-            conn.hset::<_, _, _, ()>(&key, *field, value).await?;
-        }
-        let _: () = conn.expire(&key, self.config.ttl.drone_state.as_secs() as i64)
-            .await?;
-
-        Ok(())
+        self.hset_with_ttl(&key, fields, self.config.ttl.drone_state)
+            .await
     }
 
     /// Increment engagement counter for drone
@@ -230,19 +867,40 @@ impl CacheClient {
         hit: bool,
     ) -> Result<(i64, i64)> {
         let key = format!("stats:engagements:{drone_id}");
-        let mut conn = self.conn.clone();
-
-        let total: i64 = conn.hincr(&key, "total_engagements", 1i64).await?;
-        let hits: i64 = if hit {
-            conn.hincr(&key, "successful_hits", 1i64).await?
-        } else {
-            conn.hget(&key, "successful_hits").await.unwrap_or(0)
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                return Ok(store.with(|keys| {
+                    let total = keys.hincr(&key, "total_engagements", 1);
+                    let hits = keys.hincr(&key, "successful_hits", i64::from(hit));
+                    keys.expire(&key, self.config.ttl.engagement_stats);
+                    (total, hits)
+                }));
+            }
         };
 
-        let _: () = conn.expire(&key, self.config.ttl.engagement_stats.as_secs() as i64)
-            .await?;
+        // Incrementing hits by zero on a miss reads the current count
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .hincr(&key, "total_engagements", 1i64)
+            .hincr(&key, "successful_hits", i64::from(hit))
+            .expire(&key, self.config.ttl.engagement_stats.as_secs() as i64)
+            .ignore();
 
-        Ok((total, hits))
+        self.guarded_pipe("redis.pipeline.engagements", &mut conn, &pipe).await
+    }
+
+    /// Allocate the drone's next engagement sequence number, starting at 1
+    ///
+    /// The counter has no TTL and survives drone invalidation, so numbers
+    /// are never reused.
+    pub async fn next_engagement_sequence(&self, drone_id: Uuid) -> Result<i64> {
+        let key = format!("drone:engagement_seq:{drone_id}");
+
+        match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.incr", conn.clone().incr(&key, 1i64)).await,
+            Backend::Memory(store) => Ok(store.with(|keys| keys.incr(&key, 1))),
+        }
     }
 
     // =========================================================================
@@ -252,9 +910,11 @@ impl CacheClient {
     /// Get all drone IDs in convoy
     pub async fn get_convoy_roster(&self, convoy_id: Uuid) -> Result<Vec<Uuid>> {
         let key = format!("convoy:roster:{convoy_id}");
-        let mut conn = self.conn.clone();
 
-        let members: Vec<String> = conn.smembers(&key).await?;
+        let members: Vec<String> = match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.smembers", conn.clone().smembers(&key)).await?,
+            Backend::Memory(store) => store.with(|keys| keys.smembers(&key)),
+        };
         
         let parsed: Vec<Uuid> = members
             .into_iter()
@@ -266,14 +926,36 @@ impl CacheClient {
 
     /// Add drone to convoy roster
     pub async fn add_to_convoy_roster(&self, convoy_id: Uuid, drone_id: Uuid) -> Result<bool> {
+        Ok(self.add_many_to_convoy_roster(convoy_id, &[drone_id]).await? > 0)
+    }
+
+    /// Add several drones to a convoy roster in one round-trip, returning
+    /// how many were not already on it
+    pub async fn add_many_to_convoy_roster(&self, convoy_id: Uuid, drone_ids: &[Uuid]) -> Result<i64> {
+        if drone_ids.is_empty() {
+            return Ok(0);
+        }
         let key = format!("convoy:roster:{convoy_id}");
-        let mut conn = self.conn.clone();
+        let members: Vec<String> = drone_ids.iter().map(Uuid::to_string).collect();
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                return Ok(store.with(|keys| {
+                    let added = keys.sadd(&key, &members);
+                    keys.expire(&key, self.config.ttl.convoy_roster);
+                    added
+                }));
+            }
+        };
 
-        let added: i64 = conn.sadd(&key, drone_id.to_string()).await?;
-        let _: () = conn.expire(&key, self.config.ttl.convoy_roster.as_secs() as i64)
-            .await?;
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .sadd(&key, members)
+            .expire(&key, self.config.ttl.convoy_roster.as_secs() as i64)
+            .ignore();
 
-        Ok(added > 0)
+        let (added,): (i64,) = self.guarded_pipe("redis.pipeline.roster", &mut conn, &pipe).await?;
+        Ok(added)
     }
 
     /// Remove drone from convoy roster
@@ -283,10 +965,184 @@ impl CacheClient {
         drone_id: Uuid,
     ) -> Result<bool> {
         let key = format!("convoy:roster:{convoy_id}");
-        let mut conn = self.conn.clone();
 
-        let removed: i64 = conn.srem(&key, drone_id.to_string()).await?;
-        Ok(removed > 0)
+        match &self.backend {
+            Backend::Redis { conn, .. } => {
+                let removed: i64 = self.guarded("redis.srem", conn.clone().srem(&key, drone_id.to_string())).await?;
+                Ok(removed > 0)
+            }
+            Backend::Memory(store) => Ok(store.with(|keys| keys.srem(&key, &drone_id.to_string()))),
+        }
+    }
+
+    /// Record that a convoy reported activity at `seen_at_ms`
+    ///
+    /// Convoys idle for longer than the roster TTL are trimmed on write.
+    pub async fn touch_active_convoy(&self, convoy_id: Uuid, seen_at_ms: i64) -> Result<()> {
+        let key = "convoys:active";
+        let retention = self.config.ttl.convoy_roster;
+        let cutoff = seen_at_ms - retention.as_millis() as i64;
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                store.with(|keys| {
+                    keys.zadd(key, &convoy_id.to_string(), int_score(seen_at_ms));
+                    keys.zrembyscore(key, int_score(cutoff));
+                    keys.expire(key, retention);
+                });
+                return Ok(());
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .zadd(key, convoy_id.to_string(), seen_at_ms)
+            .ignore()
+            .zrembyscore(key, "-inf", cutoff)
+            .ignore()
+            .expire(key, retention.as_secs() as i64)
+            .ignore();
+
+        self.guarded_pipe("redis.pipeline.active_convoys", &mut conn, &pipe).await
+    }
+
+    /// Get convoys that reported activity at or after `since_ms`
+    pub async fn get_active_convoys(&self, since_ms: i64) -> Result<Vec<Uuid>> {
+        let members: Vec<String> = match &self.backend {
+            Backend::Redis { conn, .. } => {
+                self.guarded("redis.zrangebyscore", conn.clone().zrangebyscore("convoys:active", since_ms, "+inf"))
+                    .await?
+            }
+            Backend::Memory(store) => store.with(|keys| {
+                keys.zrangebyscore("convoys:active", (Bound::Included(int_score(since_ms)), Bound::Unbounded))
+            }),
+        };
+
+        Ok(members
+            .into_iter()
+            .filter_map(|s| Uuid::parse_str(&s).ok())
+            .collect())
+    }
+
+    // =========================================================================
+    // SEARCH INDEX OPERATIONS (LEXICOGRAPHIC SORTED SETS)
+    // =========================================================================
+
+    /// Add an entry to the quick-jump search index
+    ///
+    /// Each kind is one sorted set with every score 0, ordered by term, so a
+    /// prefix lookup is a single `ZRANGEBYLEX`.
+    pub async fn index_search_entry(&self, entry: &SearchEntry) -> Result<()> {
+        match &self.backend {
+            Backend::Redis { conn, .. } => {
+                let _: () = self
+                    .guarded("redis.zadd", conn.clone().zadd(search_key(entry.kind), encode_search_entry(entry), 0))
+                    .await?;
+            }
+            Backend::Memory(store) => {
+                store.with(|keys| keys.zadd(&search_key(entry.kind), &encode_search_entry(entry), 0.0));
+            }
+        }
+        Ok(())
+    }
+
+    /// Add an entry, keeping only the `cap` most recently recorded of its kind
+    ///
+    /// A companion set scored by `recorded_at_ms` tracks age so the oldest
+    /// entries can be evicted from the term-ordered set.
+    pub async fn index_recent_search_entry(
+        &self,
+        entry: &SearchEntry,
+        recorded_at_ms: i64,
+        cap: usize,
+    ) -> Result<()> {
+        let key = search_key(entry.kind);
+        let recent = format!("{key}:recent");
+        let member = encode_search_entry(entry);
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                store.with(|keys| {
+                    keys.zadd(&key, &member, 0.0);
+                    keys.zadd(&recent, &member, int_score(recorded_at_ms));
+                    let count = keys.zcard(&recent);
+                    if count > cap {
+                        let stop = isize::try_from(count - cap - 1).unwrap_or(isize::MAX);
+                        let evicted = keys.zrange(&recent, 0, stop);
+                        keys.zrem(&key, &evicted);
+                        keys.zrem(&recent, &evicted);
+                    }
+                });
+                return Ok(());
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.zadd(&key, &member, 0)
+            .ignore()
+            .zadd(&recent, &member, recorded_at_ms)
+            .ignore()
+            .zcard(&recent);
+        let (count,): (usize,) = self.guarded_pipe("redis.pipeline.search", &mut conn, &pipe).await?;
+
+        if count > cap {
+            let stop = isize::try_from(count - cap - 1).unwrap_or(isize::MAX);
+            let evicted: Vec<String> = self.guarded("redis.zrange", conn.zrange(&recent, 0, stop)).await?;
+
+            let mut pipe = redis::pipe();
+            pipe.atomic()
+                .zrem(&key, &evicted)
+                .ignore()
+                .zrem(&recent, &evicted)
+                .ignore();
+            self.guarded_pipe::<()>("redis.pipeline.search", &mut conn, &pipe).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Index entries of `kind` whose term starts with `prefix`, in term order
+    pub async fn search_prefix(
+        &self,
+        kind: SearchKind,
+        prefix: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchEntry>> {
+        let min = format!("[{prefix}");
+        // 0xFF never occurs in UTF-8, so it sorts after every continuation
+        let mut max = min.clone().into_bytes();
+        max.push(0xFF);
+        let count = isize::try_from(limit).unwrap_or(isize::MAX);
+
+        let members: Vec<String> = match &self.backend {
+            Backend::Redis { conn, .. } => {
+                self.guarded("redis.zrangebylex_limit", conn.clone().zrangebylex_limit(search_key(kind), min, max, 0, count))
+                    .await?
+            }
+            Backend::Memory(store) => store.with(|keys| keys.zrange_prefix(&search_key(kind), prefix, limit)),
+        };
+
+        Ok(members
+            .iter()
+            .filter_map(|m| decode_search_entry(kind, m))
+            .collect())
+    }
+
+    /// The first `limit` index entries of `kind` in term order
+    ///
+    /// Used for typo-tolerant matching, which cannot narrow by prefix.
+    pub async fn search_scan(&self, kind: SearchKind, limit: usize) -> Result<Vec<SearchEntry>> {
+        let stop = isize::try_from(limit).unwrap_or(isize::MAX) - 1;
+
+        let members: Vec<String> = match &self.backend {
+            Backend::Redis { conn, .. } => self.guarded("redis.zrange", conn.clone().zrange(search_key(kind), 0, stop)).await?,
+            Backend::Memory(store) => store.with(|keys| keys.zrange(&search_key(kind), 0, stop)),
+        };
+
+        Ok(members
+            .iter()
+            .filter_map(|m| decode_search_entry(kind, m))
+            .collect())
     }
 
     // =========================================================================
@@ -300,7 +1156,7 @@ impl CacheClient {
         telemetry: &T,
     ) -> Result<()> {
         let key = format!("telemetry:latest:{drone_id}");
-        self.set_json(&key, telemetry, self.config.ttl.telemetry)
+        self.set_value(&key, telemetry, self.config.ttl.telemetry)
             .await
     }
 
@@ -310,7 +1166,177 @@ impl CacheClient {
         drone_id: Uuid,
     ) -> Result<Option<T>> {
         let key = format!("telemetry:latest:{drone_id}");
-        self.get_json(&key).await
+        self.get_value(&key).await
+    }
+
+    /// Append a telemetry point to the drone's rolling history
+    ///
+    /// Points are scored by recording time; anything older than the
+    /// history TTL, or beyond the newest 10,000 points, is trimmed on write.
+    pub async fn push_telemetry_history<T: Serialize>(
+        &self,
+        drone_id: Uuid,
+        recorded_at_ms: i64,
+        telemetry: &T,
+    ) -> Result<()> {
+        let key = format!("telemetry:history:{drone_id}");
+        let member = serde_json::to_string(telemetry)?;
+        let retention = self.config.ttl.telemetry_history;
+        let cutoff = recorded_at_ms - retention.as_millis() as i64;
+        let mut conn = match &self.backend {
+            Backend::Redis { conn, .. } => conn.clone(),
+            Backend::Memory(store) => {
+                store.with(|keys| {
+                    keys.zadd(&key, &member, int_score(recorded_at_ms));
+                    keys.zrembyscore(&key, int_score(cutoff));
+                    keys.zremrangebyrank(&key, 0, -(TELEMETRY_HISTORY_MAX_POINTS + 1));
+                    keys.expire(&key, retention);
+                });
+                return Ok(());
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .zadd(&key, member, recorded_at_ms)
+            .ignore()
+            .zrembyscore(&key, "-inf", cutoff)
+            .ignore()
+            .zremrangebyrank(&key, 0, -(TELEMETRY_HISTORY_MAX_POINTS + 1))
+            .ignore()
+            .expire(&key, retention.as_secs() as i64)
+            .ignore();
+
+        self.guarded_pipe("redis.pipeline.telemetry_history", &mut conn, &pipe).await
+    }
+
+    /// Get telemetry points recorded within `start_ms..=end_ms`, oldest first
+    pub async fn get_telemetry_history<T: DeserializeOwned>(
+        &self,
+        drone_id: Uuid,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<Vec<T>> {
+        let key = format!("telemetry:history:{drone_id}");
+
+        let members: Vec<String> = match &self.backend {
+            Backend::Redis { conn, .. } => {
+                self.guarded("redis.zrangebyscore", conn.clone().zrangebyscore(&key, start_ms, end_ms))
+                    .await?
+            }
+            Backend::Memory(store) => store.with(|keys| {
+                keys.zrangebyscore(&key, (Bound::Included(int_score(start_ms)), Bound::Included(int_score(end_ms))))
+            }),
+        };
+
+        Ok(members
+            .iter()
+            .filter_map(|m| serde_json::from_str(m).ok())
+            .collect())
+    }
+
+    /// Get the points recorded within the recent window ending at `now_ms`,
+    /// oldest first
+    ///
+    /// Cheap enough to read on every render or ingest, unlike the Scylla
+    /// track.
+    pub async fn get_recent_telemetry<T: DeserializeOwned>(
+        &self,
+        drone_id: Uuid,
+        now_ms: i64,
+    ) -> Result<Vec<T>> {
+        let window = self.config.ttl.telemetry_recent.as_millis() as i64;
+        self.get_telemetry_history(drone_id, now_ms - window, now_ms)
+            .await
+    }
+
+    /// Set the latest endurance estimate for a drone
+    pub async fn set_endurance_estimate<T: Serialize>(
+        &self,
+        drone_id: Uuid,
+        estimate: &T,
+    ) -> Result<()> {
+        let key = format!("endurance:{drone_id}");
+        self.set_value(&key, estimate, self.config.ttl.telemetry_history)
+            .await
+    }
+
+    /// Get the latest endurance estimate for a drone
+    pub async fn get_endurance_estimate<T: DeserializeOwned>(
+        &self,
+        drone_id: Uuid,
+    ) -> Result<Option<T>> {
+        let key = format!("endurance:{drone_id}");
+        self.get_value(&key).await
+    }
+
+    /// Set the latest health score for a drone
+    pub async fn set_health_score<T: Serialize>(&self, drone_id: Uuid, health: &T) -> Result<()> {
+        let key = format!("health:{drone_id}");
+        self.set_value(&key, health, self.config.ttl.telemetry_history)
+            .await
+    }
+
+    /// Get the latest health score for a drone
+    pub async fn get_health_score<T: DeserializeOwned>(&self, drone_id: Uuid) -> Result<Option<T>> {
+        let key = format!("health:{drone_id}");
+        self.get_value(&key).await
+    }
+
+    /// Set the running flight hours for a drone
+    ///
+    /// The cumulative total is also persisted on the drone row; this entry
+    /// carries the sortie state between telemetry reports.
+    pub async fn set_flight_hours<T: Serialize>(&self, drone_id: Uuid, hours: &T) -> Result<()> {
+        let key = format!("flight_hours:{drone_id}");
+        self.set_value(&key, hours, self.config.ttl.telemetry_history)
+            .await
+    }
+
+    /// Get the running flight hours for a drone
+    pub async fn get_flight_hours<T: DeserializeOwned>(&self, drone_id: Uuid) -> Result<Option<T>> {
+        let key = format!("flight_hours:{drone_id}");
+        self.get_value(&key).await
+    }
+
+    /// Set the drone pairs currently predicted to lose separation
+    pub async fn set_path_conflicts<T: Serialize>(
+        &self,
+        convoy_id: Uuid,
+        conflicts: &T,
+    ) -> Result<()> {
+        let key = format!("conflicts:{convoy_id}");
+        self.set_value(&key, conflicts, self.config.ttl.convoy_summary)
+            .await
+    }
+
+    /// Get the drone pairs last predicted to lose separation
+    pub async fn get_path_conflicts<T: DeserializeOwned>(
+        &self,
+        convoy_id: Uuid,
+    ) -> Result<Option<T>> {
+        let key = format!("conflicts:{convoy_id}");
+        self.get_value(&key).await
+    }
+
+    /// Set the drone pairs currently outside the formation spacing bounds
+    pub async fn set_spacing_violations<T: Serialize>(
+        &self,
+        convoy_id: Uuid,
+        violations: &T,
+    ) -> Result<()> {
+        let key = format!("spacing:{convoy_id}");
+        self.set_value(&key, violations, self.config.ttl.convoy_summary)
+            .await
+    }
+
+    /// Get the drone pairs last found outside the formation spacing bounds
+    pub async fn get_spacing_violations<T: DeserializeOwned>(
+        &self,
+        convoy_id: Uuid,
+    ) -> Result<Option<T>> {
+        let key = format!("spacing:{convoy_id}");
+        self.get_value(&key).await
     }
 
     // =========================================================================
@@ -322,6 +1348,10 @@ impl CacheClient {
         let keys = vec![
             format!("drone:state:{drone_id}"),
             format!("telemetry:latest:{drone_id}"),
+            format!("telemetry:history:{drone_id}"),
+            format!("endurance:{drone_id}"),
+            format!("health:{drone_id}"),
+            format!("flight_hours:{drone_id}"),
             format!("stats:engagements:{drone_id}"),
             format!("waypoints:progress:{drone_id}"),
         ];
@@ -334,9 +1364,12 @@ impl CacheClient {
     pub async fn invalidate_convoy(&self, convoy_id: Uuid) -> Result<()> {
         let keys = vec![
             format!("convoy:leaderboard:{convoy_id}"),
+            format!("convoy:leaderboard:version:{convoy_id}"),
+            format!("convoy:leaderboard:changes:{convoy_id}"),
             format!("convoy:roster:{convoy_id}"),
             format!("convoy:summary:{convoy_id}"),
             format!("mesh:topology:{convoy_id}"),
+            format!("conflicts:{convoy_id}"),
         ];
 
         self.delete_many(&keys).await?;
@@ -344,6 +1377,46 @@ impl CacheClient {
     }
 }
 
+/// Sorted set holding the search index for one kind
+fn search_key(kind: SearchKind) -> String {
+    format!("search:{}", kind.as_str())
+}
+
+/// Field separator inside search index members
+const SEARCH_FIELD_SEP: char = '\x1f';
+
+/// Encode an entry as `term, entity, convoy, label`; the leading term gives
+/// the member its sort order
+fn encode_search_entry(entry: &SearchEntry) -> String {
+    format!(
+        "{term}{sep}{entity}{sep}{convoy}{sep}{label}",
+        term = entry.term,
+        entity = entry.entity_id,
+        convoy = entry.convoy_id,
+        label = entry.label,
+        sep = SEARCH_FIELD_SEP,
+    )
+}
+
+fn decode_search_entry(kind: SearchKind, member: &str) -> Option<SearchEntry> {
+    let mut fields = member.splitn(4, SEARCH_FIELD_SEP);
+    Some(SearchEntry {
+        kind,
+        term: fields.next()?.to_string(),
+        entity_id: Uuid::parse_str(fields.next()?).ok()?,
+        convoy_id: Uuid::parse_str(fields.next()?).ok()?,
+        label: fields.next()?.to_string(),
+    })
+}
+
+/// Tally hash and sorted set keys of a convoy's weapon ranking
+fn weapon_ranking_keys(convoy_id: Uuid) -> (String, String) {
+    (
+        format!("convoy:weapons:tally:{convoy_id}"),
+        format!("convoy:weapons:leaderboard:{convoy_id}"),
+    )
+}
+
 /// Shared cache client wrapper
 pub type SharedCacheClient = Arc<CacheClient>;
 
@@ -351,3 +1424,93 @@ pub type SharedCacheClient = Arc<CacheClient>;
 pub fn shared_cache(client: CacheClient) -> SharedCacheClient {
     Arc::new(client)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn memory_client() -> CacheClient {
+        CacheClient::new(CacheConfig {
+            backend: CacheBackend::Memory,
+            ..Default::default()
+        })
+        .await
+        .expect("in-memory cache needs no connection")
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_leaderboard() {
+        let cache = memory_client().await;
+        let convoy_id = Uuid::new_v4();
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        cache
+            .update_leaderboard_scores(convoy_id, &[(a, 10.0), (b, 30.0), (c, 20.0)])
+            .await
+            .unwrap();
+        let top = cache.get_leaderboard(convoy_id, 2).await.unwrap();
+        assert_eq!(top, vec![(b, 30.0), (c, 20.0)]);
+        assert_eq!(cache.get_drone_rank(convoy_id, a).await.unwrap(), Some(2));
+
+        let (epoch, first) = cache.bump_leaderboard_version(convoy_id, &[a], 1_000).await.unwrap();
+        let (same_epoch, second) = cache.bump_leaderboard_version(convoy_id, &[b], 2_000).await.unwrap();
+        assert_eq!((epoch, same_epoch), (1_000, 1_000));
+        assert_eq!(second, first + 1);
+        assert_eq!(cache.get_leaderboard_changes(convoy_id, first).await.unwrap(), vec![b]);
+        assert_eq!(cache.get_leaderboard_version(convoy_id).await.unwrap(), Some((1_000, second)));
+
+        cache.invalidate_convoy(convoy_id).await.unwrap();
+        assert!(cache.get_leaderboard(convoy_id, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_accuracy_rankings() {
+        let cache = memory_client().await;
+        let (alpha, bravo) = (Uuid::new_v4(), Uuid::new_v4());
+
+        for hit in [true, false, true, true] {
+            cache.record_ranked_engagement(alpha, "AGM114_HELLFIRE", hit).await.unwrap();
+        }
+        cache.record_ranked_engagement(alpha, "GBU12_PAVEWAY", true).await.unwrap();
+        cache.record_ranked_engagement(bravo, "GBU12_PAVEWAY", false).await.unwrap();
+
+        let weapons = cache.get_weapon_rankings(alpha).await.unwrap();
+        let ranked: Vec<_> = weapons.iter().map(|w| w.member.as_str()).collect();
+        assert_eq!(ranked, ["GBU12_PAVEWAY", "AGM114_HELLFIRE"]);
+        assert_eq!((weapons[1].total_engagements, weapons[1].successful_hits), (4, 3));
+        assert_eq!(weapons[1].accuracy_pct(), 75.0);
+
+        let convoys = cache.get_convoy_rankings().await.unwrap();
+        assert_eq!(convoys[0].member, alpha.to_string());
+        assert_eq!(convoys[0].total_engagements, 5);
+
+        // A rebuild replaces the convoy's counts outright
+        let rebuilt = AccuracyTally {
+            member: "AGM114_HELLFIRE".to_string(),
+            total_engagements: 2,
+            successful_hits: 0,
+        };
+        cache.replace_convoy_rankings(alpha, &[rebuilt.clone()]).await.unwrap();
+        assert_eq!(cache.get_weapon_rankings(alpha).await.unwrap(), vec![rebuilt]);
+        let convoys = cache.get_convoy_rankings().await.unwrap();
+        assert_eq!(convoys.len(), 2);
+        assert!(convoys.iter().all(|c| c.successful_hits == 0));
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_telemetry_history_and_pubsub() {
+        let cache = memory_client().await;
+        let drone_id = Uuid::new_v4();
+
+        for (at, alt) in [(1_000, 100), (2_000, 200), (3_000, 300)] {
+            cache.push_telemetry_history(drone_id, at, &alt).await.unwrap();
+        }
+        let window: Vec<i32> = cache.get_telemetry_history(drone_id, 1_500, 3_000).await.unwrap();
+        assert_eq!(window, vec![200, 300]);
+
+        let mut events = cache.subscribe("events").await.unwrap();
+        assert_eq!(cache.publish("events", "hello").await.unwrap(), 1);
+        assert_eq!(events.next().await.as_deref(), Some("hello"));
+        assert_eq!(cache.publish("nobody", "hello").await.unwrap(), 0);
+    }
+}
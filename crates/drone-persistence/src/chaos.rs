@@ -0,0 +1,293 @@
+//! # Chaos Injection
+//!
+//! Optional fault injection for resilience testing. Rules name an operation
+//! and the faults to inject into it at a given rate:
+//!
+//! ```text
+//! scylla.select.engagements=error:0.2;redis=latency:0.5@200,drop:0.1
+//! ```
+//!
+//! | Fault                | Effect                                                   |
+//! |----------------------|----------------------------------------------------------|
+//! | `latency:RATE@MS`    | Sleep `MS` milliseconds before the operation             |
+//! | `error:RATE`         | Fail with [`PersistenceError::Unavailable`] before it runs |
+//! | `drop:RATE`          | Run it, then discard the response as a [`PersistenceError::Timeout`] |
+//!
+//! Operations are dotted names (`scylla.<verb>.<table>`, `redis.<command>`,
+//! `http.<route>`); a rule applies to its operation and everything below it,
+//! and the longest matching rule wins. Injected errors are retryable, so they
+//! exercise the retry policies and circuit breakers like a real outage.
+//!
+//! Injection is compiled in only with the `chaos` feature; without it rules
+//! still parse but [`Chaos::run`] passes calls straight through.
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::{PersistenceError, Result};
+
+/// Faults injected into one operation
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ChaosRule {
+    /// Probability (0.0–1.0) of delaying the operation
+    pub latency_rate: f64,
+    /// Delay added when latency is injected
+    pub latency: Duration,
+    /// Probability of failing the operation before it runs
+    pub error_rate: f64,
+    /// Probability of discarding the operation's response after it runs
+    pub drop_rate: f64,
+}
+
+/// Chaos rules keyed by operation prefix
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChaosConfig {
+    pub rules: Vec<(String, ChaosRule)>,
+}
+
+impl ChaosConfig {
+    /// Parse rules in the `op=fault:rate[@ms],...;op=...` format
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::Config`] for an unknown fault, a rate
+    /// outside 0.0–1.0, or a latency fault without a delay.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+        for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let (op, faults) = entry
+                .split_once('=')
+                .ok_or_else(|| invalid(entry, "expected op=faults"))?;
+            let op = op.trim();
+            if op.is_empty() {
+                return Err(invalid(entry, "missing operation"));
+            }
+
+            let mut rule = ChaosRule::default();
+            for fault in faults.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                let (kind, value) = fault
+                    .split_once(':')
+                    .ok_or_else(|| invalid(fault, "expected fault:rate"))?;
+                match kind.trim() {
+                    "latency" => {
+                        let (rate, ms) = value
+                            .split_once('@')
+                            .ok_or_else(|| invalid(fault, "expected latency:rate@ms"))?;
+                        let ms: u64 = ms
+                            .trim()
+                            .trim_end_matches("ms")
+                            .parse()
+                            .map_err(|_| invalid(fault, "invalid delay"))?;
+                        rule.latency_rate = parse_rate(fault, rate)?;
+                        rule.latency = Duration::from_millis(ms);
+                    }
+                    "error" => rule.error_rate = parse_rate(fault, value)?,
+                    "drop" => rule.drop_rate = parse_rate(fault, value)?,
+                    _ => return Err(invalid(fault, "unknown fault")),
+                }
+            }
+            rules.push((op.to_string(), rule));
+        }
+        Ok(Self { rules })
+    }
+
+    /// Whether no rules are configured
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+fn parse_rate(fault: &str, rate: &str) -> Result<f64> {
+    rate.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|r| (0.0..=1.0).contains(r))
+        .ok_or_else(|| invalid(fault, "rate must be between 0.0 and 1.0"))
+}
+
+fn invalid(part: &str, reason: &str) -> PersistenceError {
+    PersistenceError::Config(format!("invalid chaos rule '{part}': {reason}"))
+}
+
+/// Fault injector shared by a client
+#[derive(Debug, Clone, Default)]
+pub struct Chaos {
+    /// Rules, longest operation first so the most specific matches first
+    rules: Vec<(String, ChaosRule)>,
+}
+
+impl Chaos {
+    #[must_use]
+    pub fn new(config: ChaosConfig) -> Self {
+        let mut rules = config.rules;
+        rules.sort_by_key(|(op, _)| std::cmp::Reverse(op.len()));
+        Self { rules }
+    }
+
+    /// Whether any rules are configured
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        !self.rules.is_empty()
+    }
+
+    /// Most specific rule covering `op`
+    #[must_use]
+    pub fn rule(&self, op: &str) -> Option<&ChaosRule> {
+        self.rules
+            .iter()
+            .find(|(prefix, _)| {
+                op.strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            })
+            .map(|(_, rule)| rule)
+    }
+
+    /// Delay or fail `op` before it runs
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PersistenceError::Unavailable`] when an error is injected.
+    #[cfg_attr(not(feature = "chaos"), allow(clippy::unused_async))]
+    pub async fn before(&self, op: &str) -> Result<()> {
+        #[cfg(feature = "chaos")]
+        if let Some(rule) = self.rule(op) {
+            if roll(rule.latency_rate) {
+                tokio::time::sleep(rule.latency).await;
+            }
+            if roll(rule.error_rate) {
+                tracing::debug!(op, "chaos: injecting error");
+                return Err(PersistenceError::Unavailable(format!("chaos: injected error in {op}")));
+            }
+        }
+        #[cfg(not(feature = "chaos"))]
+        let _ = op;
+        Ok(())
+    }
+
+    /// Whether to discard the response of `op` after it ran
+    #[must_use]
+    pub fn drop_response(&self, op: &str) -> bool {
+        #[cfg(feature = "chaos")]
+        if let Some(rule) = self.rule(op)
+            && roll(rule.drop_rate)
+        {
+            tracing::debug!(op, "chaos: dropping response");
+            return true;
+        }
+        #[cfg(not(feature = "chaos"))]
+        let _ = op;
+        false
+    }
+
+    /// Run `call` as operation `op` with the configured faults injected
+    ///
+    /// # Errors
+    ///
+    /// Returns an injected fault or the call's own error.
+    pub async fn run<T, E, F>(&self, op: &str, call: F) -> Result<T>
+    where
+        F: Future<Output = std::result::Result<T, E>>,
+        E: Into<PersistenceError>,
+    {
+        if !self.is_enabled() {
+            return call.await.map_err(Into::into);
+        }
+        self.before(op).await?;
+        let value = call.await.map_err(Into::into)?;
+        if self.drop_response(op) {
+            return Err(PersistenceError::Timeout(format!("chaos: dropped response from {op}")));
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(feature = "chaos")]
+fn roll(rate: f64) -> bool {
+    rate > 0.0 && rand::random::<f64>() < rate
+}
+
+/// Operation name for a CQL statement, e.g. `scylla.select.engagements`
+#[must_use]
+pub fn statement_op(cql: &str) -> String {
+    let mut words = cql.split_whitespace();
+    let verb = words.next().unwrap_or_default().to_ascii_lowercase();
+    let table = match verb.as_str() {
+        "select" | "delete" => words
+            .by_ref()
+            .skip_while(|w| !w.eq_ignore_ascii_case("from"))
+            .nth(1),
+        "insert" => words.nth(1),
+        "update" => words.next(),
+        _ => None,
+    };
+    match table {
+        Some(table) => {
+            let table = table.rsplit('.').next().unwrap_or(table);
+            let table = table.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '_');
+            format!("scylla.{verb}.{}", table.to_ascii_lowercase())
+        }
+        None => format!("scylla.{verb}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules() {
+        let config =
+            ChaosConfig::parse("scylla.select.engagements=error:0.2; redis=latency:0.5@200ms,drop:0.1")
+                .unwrap();
+        assert_eq!(
+            config.rules,
+            vec![
+                (
+                    "scylla.select.engagements".to_string(),
+                    ChaosRule { error_rate: 0.2, ..ChaosRule::default() },
+                ),
+                (
+                    "redis".to_string(),
+                    ChaosRule {
+                        latency_rate: 0.5,
+                        latency: Duration::from_millis(200),
+                        drop_rate: 0.1,
+                        ..ChaosRule::default()
+                    },
+                ),
+            ]
+        );
+
+        assert!(ChaosConfig::parse("").unwrap().is_empty());
+        assert!(ChaosConfig::parse("redis=error:1.5").is_err());
+        assert!(ChaosConfig::parse("redis=latency:0.5").is_err());
+        assert!(ChaosConfig::parse("redis=explode:0.1").is_err());
+    }
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let chaos = Chaos::new(
+            ChaosConfig::parse("scylla=error:0.1;scylla.select=error:0.5;scylla.select.drones=error:1.0")
+                .unwrap(),
+        );
+        let error_rate = |op| chaos.rule(op).map(|r| r.error_rate.to_string());
+        assert_eq!(error_rate("scylla.select.drones").as_deref(), Some("1"));
+        assert_eq!(error_rate("scylla.select.engagements").as_deref(), Some("0.5"));
+        assert_eq!(error_rate("scylla.insert.drones").as_deref(), Some("0.1"));
+        assert!(chaos.rule("scyllax.select").is_none());
+        assert!(chaos.rule("redis.get").is_none());
+    }
+
+    #[test]
+    fn test_statement_op() {
+        assert_eq!(
+            statement_op("SELECT drone_id FROM drone_convoy.engagements WHERE convoy_id = ?"),
+            "scylla.select.engagements"
+        );
+        assert_eq!(statement_op("INSERT INTO drones (a) VALUES (?)"), "scylla.insert.drones");
+        assert_eq!(statement_op("UPDATE leaderboard SET x = ?"), "scylla.update.leaderboard");
+        assert_eq!(statement_op("DELETE FROM waypoints WHERE id = ?"), "scylla.delete.waypoints");
+        assert_eq!(statement_op("BEGIN BATCH"), "scylla.begin");
+    }
+}
@@ -11,8 +11,11 @@ pub enum PersistenceError {
     #[error("Redis error: {0}")]
     Redis(String),
 
+    #[error("SQLite error: {0}")]
+    Sqlite(String),
+
     #[error("Serialization error: {0}")]
-    Serialization(String),
+    SerializationError(String),
 
     #[error("Entity not found: {entity_type} with key {key}")]
     NotFound { entity_type: String, key: String },
@@ -20,8 +23,16 @@ pub enum PersistenceError {
     #[error("Connection pool exhausted")]
     PoolExhausted,
 
-    #[error("Query timeout after {timeout_ms}ms")]
-    Timeout { timeout_ms: u64 },
+    #[error("Query timeout: {0}")]
+    Timeout(String),
+
+    /// Backend unreachable or refusing work (down, overloaded, bootstrapping)
+    #[error("Backend unavailable: {0}")]
+    Unavailable(String),
+
+    /// Stored data or schema does not match what the query expects
+    #[error("Schema mismatch: {0}")]
+    SchemaMismatch(String),
 
     #[error("Invalid query parameters: {0}")]
     InvalidQuery(String),
@@ -31,11 +42,30 @@ pub enum PersistenceError {
 
     #[error("Write conflict: {0}")]
     WriteConflict(String),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("Circuit breaker open: {0}")]
+    CircuitOpen(String),
+}
+
+impl PersistenceError {
+    /// Whether the same request may succeed if retried after a short wait.
+    ///
+    /// An open circuit breaker is not retryable: it is already shedding load.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Timeout(_) | Self::Unavailable(_) | Self::PoolExhausted
+        )
+    }
 }
 
 impl From<serde_json::Error> for PersistenceError {
     fn from(err: serde_json::Error) -> Self {
-        Self::Serialization(err.to_string())
+        Self::SerializationError(err.to_string())
     }
 }
 
@@ -49,15 +79,116 @@ impl From<scylla::transport::errors::NewSessionError> for PersistenceError {
 #[cfg(feature = "scylla")]
 impl From<scylla::transport::errors::QueryError> for PersistenceError {
     fn from(err: scylla::transport::errors::QueryError) -> Self {
-        Self::Scylla(err.to_string())
+        use scylla::transport::errors::{DbError, QueryError};
+
+        let message = err.to_string();
+        match err {
+            QueryError::RequestTimeout(_)
+            | QueryError::TimeoutError
+            | QueryError::DbError(DbError::ReadTimeout { .. } | DbError::WriteTimeout { .. }, _) => {
+                Self::Timeout(message)
+            }
+            QueryError::DbError(
+                DbError::Unavailable { .. } | DbError::Overloaded | DbError::IsBootstrapping,
+                _,
+            )
+            | QueryError::ConnectionPoolError(_)
+            | QueryError::BrokenConnection(_)
+            | QueryError::EmptyPlan => Self::Unavailable(message),
+            QueryError::UnableToAllocStreamId => Self::PoolExhausted,
+            QueryError::DbError(DbError::Invalid, ref reason) if is_schema_reason(reason) => {
+                Self::SchemaMismatch(message)
+            }
+            QueryError::CqlRequestSerialization(_) | QueryError::BadQuery(_) => {
+                Self::SerializationError(message)
+            }
+            _ => Self::Scylla(message),
+        }
+    }
+}
+
+#[cfg(feature = "scylla")]
+impl From<scylla::deserialize::TypeCheckError> for PersistenceError {
+    fn from(err: scylla::deserialize::TypeCheckError) -> Self {
+        Self::SchemaMismatch(err.to_string())
+    }
+}
+
+#[cfg(feature = "scylla")]
+impl From<scylla::deserialize::DeserializationError> for PersistenceError {
+    fn from(err: scylla::deserialize::DeserializationError) -> Self {
+        Self::SerializationError(err.to_string())
+    }
+}
+
+/// Whether an `Invalid` request was rejected for naming unknown schema items
+#[cfg(feature = "scylla")]
+fn is_schema_reason(reason: &str) -> bool {
+    let reason = reason.to_ascii_lowercase();
+    ["undefined column", "unknown identifier", "unconfigured table", "does not exist"]
+        .iter()
+        .any(|needle| reason.contains(needle))
+}
+
+#[cfg(feature = "embedded")]
+impl From<rusqlite::Error> for PersistenceError {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::Sqlite(err.to_string())
     }
 }
 
 #[cfg(feature = "redis")]
 impl From<redis::RedisError> for PersistenceError {
     fn from(err: redis::RedisError) -> Self {
-        Self::Redis(err.to_string())
+        if err.is_timeout() {
+            Self::Timeout(err.to_string())
+        } else if err.is_connection_refusal() || err.is_connection_dropped() || err.is_io_error() {
+            Self::Unavailable(err.to_string())
+        } else if err.kind() == redis::ErrorKind::TypeError {
+            Self::SerializationError(err.to_string())
+        } else {
+            Self::Redis(err.to_string())
+        }
     }
 }
 
 pub type Result<T> = std::result::Result<T, PersistenceError>;
+
+#[cfg(all(test, feature = "scylla"))]
+mod tests {
+    use super::*;
+    use scylla::statement::Consistency;
+    use scylla::transport::errors::{DbError, QueryError};
+
+    #[test]
+    fn test_scylla_errors_are_classified() {
+        let timeout = PersistenceError::from(QueryError::RequestTimeout("12s".to_string()));
+        assert!(matches!(timeout, PersistenceError::Timeout(_)));
+        assert!(timeout.is_retryable());
+
+        let unavailable = PersistenceError::from(QueryError::DbError(
+            DbError::Unavailable {
+                consistency: Consistency::Quorum,
+                required: 2,
+                alive: 1,
+            },
+            "Cannot achieve consistency level".to_string(),
+        ));
+        assert!(matches!(unavailable, PersistenceError::Unavailable(_)));
+        assert!(unavailable.is_retryable());
+
+        let schema = PersistenceError::from(QueryError::DbError(
+            DbError::Invalid,
+            "Undefined column name heading_deg".to_string(),
+        ));
+        assert!(matches!(schema, PersistenceError::SchemaMismatch(_)));
+        assert!(!schema.is_retryable());
+
+        let syntax = PersistenceError::from(QueryError::DbError(
+            DbError::SyntaxError,
+            "line 1:7 no viable alternative".to_string(),
+        ));
+        assert!(matches!(syntax, PersistenceError::Scylla(_)));
+        assert!(!PersistenceError::CircuitOpen("scylla".to_string()).is_retryable());
+    }
+}
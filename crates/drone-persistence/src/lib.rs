@@ -45,21 +45,48 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod breaker;
 pub mod cache;
+pub mod chaos;
 pub mod error;
+pub mod metrics;
 pub mod repository;
+pub mod retry;
 pub mod strategy;
 
 // Re-export commonly used types
-pub use cache::{CacheClient, CacheConfig, SharedCacheClient};
+pub use breaker::{BreakerConfig, BreakerSnapshot, BreakerState, CircuitBreaker};
+pub use chaos::{Chaos, ChaosConfig, ChaosRule};
+pub use cache::{
+    AccuracyTally, CacheBackend, CacheClient, CacheCodec, CacheConfig, CacheTtl, SharedCacheClient,
+};
 pub use error::{PersistenceError, Result};
+pub use metrics::{MetricsSnapshot, RepositoryMetrics};
 pub use repository::{
-    ScyllaClient, ScyllaConfig,
+    ConvoyOwner, DroneStatusInfo, Page, RankedUpdate, ScyllaClient, ScyllaConfig,
     ScyllaLeaderboardRepository, ScyllaEngagementRepository,
+    ScyllaEngagementLogRepository,
     ScyllaTelemetryRepository, ScyllaConvoyRepository,
-    ScyllaWaypointRepository,
+    ScyllaWaypointRepository, ScyllaAlertRepository,
+    ScyllaAuthorizationRepository, ScyllaWeaponsRepository,
+    ScyllaTargetRepository, ScyllaDroneRepository, ScyllaApiKeyRepository,
+    ScyllaJournalRepository,
+};
+#[cfg(feature = "embedded")]
+pub use repository::{
+    SqliteClient, SqliteConfig,
+    SqliteLeaderboardRepository, SqliteEngagementRepository,
+    SqliteEngagementLogRepository,
+    SqliteTelemetryRepository, SqliteConvoyRepository,
+    SqliteWaypointRepository, SqliteAlertRepository,
+    SqliteAuthorizationRepository, SqliteWeaponsRepository,
+    SqliteTargetRepository, SqliteDroneRepository, SqliteApiKeyRepository,
+    SqliteJournalRepository,
+};
+pub use retry::RetryConfig;
+pub use strategy::{
+    DynamicStrategy, ReadStrategy, StrategyRegistry, StrategySource, WriteStrategy,
 };
-pub use strategy::{ReadStrategy, WriteStrategy};
 
 /// Crate version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,259 @@
+//! # Repository Metrics
+//!
+//! Counters and latency histograms kept by each database client:
+//!
+//! - per repository method (`engagement.record`): calls, latency, and
+//!   cache hits and misses for methods that read through a cache
+//! - per statement operation (`scylla.select.engagements`, named like the
+//!   chaos operations): calls, errors, rows returned and latency
+//!
+//! Statements slower than the configured threshold are logged with their
+//! CQL and the Rust types of their bound values, never the values
+//! themselves, so slow-query logs are safe to ship with the rest.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Upper bounds, in seconds, of the latency histogram buckets
+pub const LATENCY_BUCKETS: [f64; 10] =
+    [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+/// Latency distribution in [`LATENCY_BUCKETS`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Histogram {
+    /// Observations per bucket; the extra last bucket is `+Inf`
+    pub buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    pub count: u64,
+    /// Sum of all observations in seconds
+    pub sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|&bound| secs <= bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum += secs;
+    }
+
+    /// Cumulative counts per upper bound, as Prometheus `le` buckets;
+    /// `+Inf` is [`Histogram::count`]
+    pub fn cumulative(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        LATENCY_BUCKETS.iter().zip(self.buckets.iter()).scan(0, |total, (&bound, &n)| {
+            *total += n;
+            Some((bound, *total))
+        })
+    }
+}
+
+/// Totals for one repository method
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MethodStats {
+    pub calls: u64,
+    pub latency: Histogram,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+}
+
+/// Totals for one statement operation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatementStats {
+    pub calls: u64,
+    pub errors: u64,
+    pub rows: u64,
+    /// Calls slower than the slow-query threshold
+    pub slow: u64,
+    pub latency: Histogram,
+}
+
+/// Point-in-time copy of a client's metrics
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    pub methods: BTreeMap<&'static str, MethodStats>,
+    pub statements: BTreeMap<String, StatementStats>,
+}
+
+/// Metrics shared by the repositories of one client
+#[derive(Debug, Default)]
+pub struct RepositoryMetrics {
+    slow_query_threshold: Option<Duration>,
+    inner: Mutex<MetricsSnapshot>,
+}
+
+impl RepositoryMetrics {
+    /// Metrics logging statements slower than `slow_query_threshold`;
+    /// `None` disables slow-query logging
+    #[must_use]
+    pub fn new(slow_query_threshold: Option<Duration>) -> Self {
+        Self {
+            slow_query_threshold,
+            inner: Mutex::default(),
+        }
+    }
+
+    /// Time a repository method call until the returned guard drops
+    #[must_use]
+    pub fn time(&self, method: &'static str) -> MethodTimer<'_> {
+        MethodTimer {
+            metrics: self,
+            method,
+            started: Instant::now(),
+        }
+    }
+
+    /// Record whether a method's cache lookup hit
+    pub fn cache(&self, method: &'static str, hit: bool) {
+        if let Ok(mut inner) = self.inner.lock() {
+            let stats = inner.methods.entry(method).or_default();
+            if hit {
+                stats.cache_hits += 1;
+            } else {
+                stats.cache_misses += 1;
+            }
+        }
+    }
+
+    /// Record one statement; `rows` is `None` when it failed
+    ///
+    /// `shape` describes the bound values (see [`value_shape`]) and is only
+    /// called for slow statements.
+    pub fn record_statement(
+        &self,
+        op: &str,
+        statement: &str,
+        shape: impl FnOnce() -> String,
+        elapsed: Duration,
+        rows: Option<usize>,
+    ) {
+        let slow = self.slow_query_threshold.is_some_and(|threshold| elapsed >= threshold);
+        if slow {
+            tracing::warn!(
+                op,
+                statement = %compact(statement),
+                values = %shape(),
+                elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+                failed = rows.is_none(),
+                "Slow query"
+            );
+        }
+
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        if !inner.statements.contains_key(op) {
+            inner.statements.insert(op.to_string(), StatementStats::default());
+        }
+        let Some(stats) = inner.statements.get_mut(op) else {
+            return;
+        };
+        stats.calls += 1;
+        match rows {
+            Some(rows) => stats.rows += rows as u64,
+            None => stats.errors += 1,
+        }
+        if slow {
+            stats.slow += 1;
+        }
+        stats.latency.observe(elapsed);
+    }
+
+    #[must_use]
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.inner.lock().map(|inner| inner.clone()).unwrap_or_default()
+    }
+}
+
+/// Guard returned by [`RepositoryMetrics::time`]
+pub struct MethodTimer<'a> {
+    metrics: &'a RepositoryMetrics,
+    method: &'static str,
+    started: Instant,
+}
+
+impl Drop for MethodTimer<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.started.elapsed();
+        if let Ok(mut inner) = self.metrics.inner.lock() {
+            let stats = inner.methods.entry(self.method).or_default();
+            stats.calls += 1;
+            stats.latency.observe(elapsed);
+        }
+    }
+}
+
+/// Types of a statement's bound values, e.g. `(Uuid, i32)`
+#[must_use]
+pub fn value_shape<V: ?Sized>(_values: &V) -> String {
+    let name = std::any::type_name::<V>();
+    // Drop module paths: `(uuid::Uuid, &str)` becomes `(Uuid, &str)`
+    let mut shape = String::with_capacity(name.len());
+    let mut segment = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            shape.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            shape.push(c);
+        }
+    }
+    shape.push_str(segment.rsplit("::").next().unwrap_or_default());
+    shape
+}
+
+/// Statement text on one line with runs of whitespace collapsed
+fn compact(statement: &str) -> String {
+    statement.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statements_count_rows_errors_and_slow_calls() {
+        let metrics = RepositoryMetrics::new(Some(Duration::from_millis(100)));
+        let op = "scylla.select.engagements";
+        metrics.record_statement(op, "SELECT", String::new, Duration::from_millis(2), Some(5));
+        metrics.record_statement(op, "SELECT", String::new, Duration::from_millis(300), Some(1));
+        metrics.record_statement(op, "SELECT", String::new, Duration::from_millis(4), None);
+
+        let snapshot = metrics.snapshot();
+        let stats = &snapshot.statements[op];
+        assert_eq!((stats.calls, stats.errors, stats.rows, stats.slow), (3, 1, 6, 1));
+        assert_eq!(stats.latency.count, 3);
+        let cumulative: Vec<_> = stats.latency.cumulative().collect();
+        assert_eq!(cumulative[1], (0.0025, 1));
+        assert_eq!(cumulative[3], (0.01, 2));
+        assert_eq!(cumulative.last(), Some(&(1.0, 3)));
+    }
+
+    #[test]
+    fn test_method_timer_and_cache_outcomes() {
+        let metrics = RepositoryMetrics::default();
+        {
+            let _timer = metrics.time("leaderboard.get_leaderboard");
+            metrics.cache("leaderboard.get_leaderboard", true);
+        }
+        drop(metrics.time("leaderboard.get_leaderboard"));
+        metrics.cache("leaderboard.get_leaderboard", false);
+
+        let snapshot = metrics.snapshot();
+        let stats = &snapshot.methods["leaderboard.get_leaderboard"];
+        assert_eq!((stats.calls, stats.cache_hits, stats.cache_misses), (2, 1, 1));
+        assert_eq!(stats.latency.count, 2);
+    }
+
+    #[test]
+    fn test_value_shape_hides_values_and_paths() {
+        let values = (uuid::Uuid::nil(), 10_i32, "secret");
+        assert_eq!(value_shape(&values), "(Uuid, i32, &str)");
+        assert_eq!(value_shape(&(Some(1.5_f64),)), "(Option<f64>,)");
+        assert_eq!(compact("SELECT a\n    FROM b\n"), "SELECT a FROM b");
+    }
+}
@@ -2,11 +2,32 @@
 //!
 //! Repository pattern implementations for domain entity persistence.
 
+pub mod rows;
 pub mod scylla_impl;
+#[cfg(feature = "embedded")]
+pub mod sqlite_impl;
 
 pub use scylla_impl::{
-    ScyllaClient, ScyllaConfig,
+    ConvoyOwner, DroneStatusInfo, Page, RankedUpdate, ScyllaClient, ScyllaConfig,
     ScyllaLeaderboardRepository, ScyllaEngagementRepository,
+    ScyllaEngagementLogRepository,
     ScyllaTelemetryRepository, ScyllaConvoyRepository,
-    ScyllaWaypointRepository,
+    ScyllaWaypointRepository, ScyllaAlertRepository,
+    ScyllaAuthorizationRepository, ScyllaWeaponsRepository,
+    ScyllaTargetRepository, ScyllaDroneRepository, ScyllaApiKeyRepository,
+    ScyllaJournalRepository,
+};
+#[cfg(feature = "embedded")]
+pub use sqlite_impl::{
+    SqliteClient, SqliteConfig,
+    SqliteLeaderboardRepository, SqliteEngagementRepository,
+    SqliteEngagementLogRepository,
+    SqliteTelemetryRepository, SqliteConvoyRepository,
+    SqliteWaypointRepository, SqliteAlertRepository,
+    SqliteAuthorizationRepository, SqliteWeaponsRepository,
+    SqliteTargetRepository, SqliteDroneRepository, SqliteApiKeyRepository,
+    SqliteJournalRepository,
+};
+pub use rows::{
+    leaderboard_entry_from_row, rank_changes, EngagementFeedRow, LeaderboardRow, LeaderboardTally,
 };
@@ -0,0 +1,242 @@
+//! Row ↔ domain conversions and leaderboard bookkeeping.
+//!
+//! Everything here is free of I/O so the repository's hot paths can be
+//! exercised (and benchmarked) without a cluster.
+
+use chrono::{DateTime, Utc};
+use scylla::frame::value::CqlTimestamp;
+use uuid::Uuid;
+
+use super::scylla_impl::{
+    parse_damage_assessment, parse_platform_type, parse_target_type, parse_weapon_type,
+};
+use drone_domain::{
+    CollateralRisk, Coordinates, Engagement, EngagementResult, LeaderboardEntry, Percent,
+    ScoringModel, TargetInfo, ThreatLevel,
+};
+
+/// Leaderboard columns in select order: convoy, drone, callsign, platform,
+/// total engagements, hits, accuracy, current streak, best streak, rank.
+pub type LeaderboardRow = (Uuid, Uuid, String, String, i32, i32, f32, i32, i32, i16);
+
+/// Build a leaderboard entry from a row, scoring it with `model`.
+#[must_use]
+pub fn leaderboard_entry_from_row(row: LeaderboardRow, model: ScoringModel) -> LeaderboardEntry {
+    let (convoy_id, drone_id, callsign, platform, total, hits, accuracy, streak, best, rank) = row;
+    LeaderboardEntry {
+        convoy_id,
+        drone_id,
+        callsign,
+        platform_type: parse_platform_type(&platform),
+        total_engagements: total,
+        successful_hits: hits,
+        accuracy_pct: accuracy,
+        current_streak: streak,
+        best_streak: best,
+        score: model.score(i64::from(hits), i64::from(total)),
+        rank,
+        updated_at: Utc::now(),
+    }
+}
+
+/// A drone's engagement counters after recording one more engagement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaderboardTally {
+    pub total_engagements: i32,
+    pub successful_hits: i32,
+    pub current_streak: i32,
+    pub best_streak: i32,
+}
+
+impl LeaderboardTally {
+    /// Counters once `hit` is added to `current` (a new drone when `None`).
+    #[must_use]
+    pub fn after(current: Option<&LeaderboardEntry>, hit: bool) -> Self {
+        let hit_count = i32::from(hit);
+        match current {
+            Some(e) => {
+                let current_streak = if hit { e.current_streak + 1 } else { 0 };
+                Self {
+                    total_engagements: e.total_engagements + 1,
+                    successful_hits: e.successful_hits + hit_count,
+                    current_streak,
+                    best_streak: current_streak.max(e.best_streak),
+                }
+            }
+            None => Self {
+                total_engagements: 1,
+                successful_hits: hit_count,
+                current_streak: hit_count,
+                best_streak: hit_count,
+            },
+        }
+    }
+
+    /// Hit percentage.
+    #[must_use]
+    pub fn accuracy_pct(&self) -> Percent {
+        Percent::of(i64::from(self.successful_hits), i64::from(self.total_engagements))
+    }
+}
+
+/// Rank score-ordered `entries` from 1 and work out what changed.
+///
+/// Returns `drone_id`'s rank (0 if absent) and every entry whose stored
+/// rank no longer matches its position.
+#[must_use]
+pub fn rank_changes(entries: &[LeaderboardEntry], drone_id: Uuid) -> (i16, Vec<(Uuid, i16)>) {
+    let mut new_rank = 0;
+    let mut ranks = Vec::new();
+    for (entry, rank) in entries.iter().zip(1_i16..) {
+        if entry.drone_id == drone_id {
+            new_rank = rank;
+        }
+        if entry.rank != rank {
+            ranks.push((entry.drone_id, rank));
+        }
+    }
+    (new_rank, ranks)
+}
+
+/// Row shape selected by the engagement feed queries.
+#[derive(Debug, Clone, scylla::DeserializeRow)]
+pub struct EngagementFeedRow {
+    pub engaged_at: CqlTimestamp,
+    pub engagement_id: Uuid,
+    pub drone_id: Option<Uuid>,
+    pub drone_callsign: Option<String>,
+    pub weapon_type: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<Uuid>,
+    pub hit: Option<bool>,
+    pub impact_lat: Option<f64>,
+    pub impact_lon: Option<f64>,
+    pub range_to_target_km: Option<f32>,
+    pub bda_status: Option<String>,
+    pub bda_notes: Option<String>,
+    pub authorization_code: Option<String>,
+    pub roe_compliance: Option<bool>,
+    pub shooter_lat: Option<f64>,
+    pub shooter_lon: Option<f64>,
+    pub sequence: Option<i64>,
+    pub predicted_pk: Option<f32>,
+}
+
+impl EngagementFeedRow {
+    /// Build the domain engagement for `convoy_id`.
+    #[must_use]
+    pub fn into_engagement(self, convoy_id: Uuid) -> Engagement {
+        let engaged_at = DateTime::from_timestamp_millis(self.engaged_at.0).unwrap_or_default();
+        let hit = self.hit.unwrap_or(false);
+        let bda_status = self.bda_status.unwrap_or_else(|| "PENDING".to_string());
+        let impact_coords = Coordinates::new(
+            self.impact_lat.unwrap_or_default(),
+            self.impact_lon.unwrap_or_default(),
+            0.0,
+        );
+        Engagement {
+            convoy_id,
+            engaged_at,
+            engagement_id: self.engagement_id,
+            drone_id: self.drone_id.unwrap_or_default(),
+            drone_callsign: self.drone_callsign.unwrap_or_default(),
+            weapon_type: parse_weapon_type(self.weapon_type.as_deref().unwrap_or_default()),
+            weapon_serial: String::new(),
+            target: TargetInfo {
+                target_id: self.target_id.unwrap_or_default(),
+                target_type: parse_target_type(self.target_type.as_deref().unwrap_or_default()),
+                coordinates: impact_coords,
+                confidence: 0.0,
+                threat_level: ThreatLevel::Unknown,
+            },
+            authorization_code: self.authorization_code.unwrap_or_default(),
+            authorized_by: String::new(),
+            roe_compliance: self.roe_compliance.unwrap_or(true),
+            result: EngagementResult {
+                impact_time: engaged_at,
+                impact_coords,
+                damage_assessment: parse_damage_assessment(hit, &bda_status),
+                collateral_risk: CollateralRisk::None,
+            },
+            hit,
+            waypoint_number: 0,
+            shooter_position: Coordinates::new(
+                self.shooter_lat.unwrap_or_default(),
+                self.shooter_lon.unwrap_or_default(),
+                0.0,
+            ),
+            range_to_target_km: self.range_to_target_km.unwrap_or_default(),
+            predicted_pk: self.predicted_pk,
+            bda_status,
+            bda_notes: self.bda_notes,
+            sequence: self.sequence.unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use drone_domain::PlatformType;
+
+    fn entry(drone: u128, rank: i16) -> LeaderboardEntry {
+        leaderboard_entry_from_row(
+            (
+                Uuid::nil(),
+                Uuid::from_u128(drone),
+                format!("REAPER-{drone:02}"),
+                "MQ9_REAPER".to_string(),
+                4,
+                3,
+                75.0,
+                2,
+                3,
+                rank,
+            ),
+            ScoringModel::default(),
+        )
+    }
+
+    #[test]
+    fn test_tally_tracks_streaks() {
+        let first = LeaderboardTally::after(None, true);
+        assert_eq!(
+            first,
+            LeaderboardTally {
+                total_engagements: 1,
+                successful_hits: 1,
+                current_streak: 1,
+                best_streak: 1
+            }
+        );
+
+        let current = entry(1, 1);
+        assert_eq!(current.platform_type, PlatformType::Mq9Reaper);
+        let hit = LeaderboardTally::after(Some(&current), true);
+        assert_eq!((hit.total_engagements, hit.successful_hits), (5, 4));
+        assert_eq!((hit.current_streak, hit.best_streak), (3, 3));
+        assert!((hit.accuracy_pct().0 - 80.0).abs() < f32::EPSILON);
+
+        let miss = LeaderboardTally::after(Some(&current), false);
+        assert_eq!((miss.current_streak, miss.best_streak), (0, 3));
+    }
+
+    #[test]
+    fn test_rank_changes_reports_moved_entries() {
+        // Drone 3 climbed from 3rd to 1st
+        let entries = [entry(3, 3), entry(1, 1), entry(2, 2)];
+
+        let (rank, changed) = rank_changes(&entries, Uuid::from_u128(3));
+
+        assert_eq!(rank, 1);
+        assert_eq!(
+            changed,
+            [
+                (Uuid::from_u128(3), 1),
+                (Uuid::from_u128(1), 2),
+                (Uuid::from_u128(2), 3)
+            ]
+        );
+        assert_eq!(rank_changes(&entries, Uuid::from_u128(9)).0, 0);
+    }
+}
@@ -2,17 +2,37 @@
 //!
 //! Provides repository pattern access to ScyllaDB for drone convoy entities.
 
-use chrono::Utc;
-use scylla::{Session, SessionBuilder};
-use std::sync::Arc;
+use chrono::{DateTime, DurationRound, Utc};
+use scylla::frame::response::result::{CqlValue, Row};
+use scylla::frame::value::CqlTimestamp;
+use scylla::query::Query;
+use scylla::statement::{PagingState, PagingStateResponse};
+use scylla::serialize::row::SerializeRow;
+use scylla::{QueryResult, Session, SessionBuilder};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+use crate::breaker::{BreakerConfig, CircuitBreaker};
 use crate::cache::SharedCacheClient;
-use crate::error::Result;
-use crate::strategy::{ReadStrategy, WriteStrategy};
+use crate::chaos::{statement_op, Chaos, ChaosConfig};
+use crate::error::{PersistenceError, Result};
+use crate::metrics::{value_shape, RepositoryMetrics};
+use crate::retry::RetryConfig;
+use crate::strategy::{DynamicStrategy, ReadStrategy, WriteStrategy};
+use super::rows::{
+    leaderboard_entry_from_row, rank_changes, EngagementFeedRow, LeaderboardRow, LeaderboardTally,
+};
 use drone_domain::{
-    Convoy, ConvoyStatus, Engagement, LeaderboardEntry,
-    MissionType, PlatformType, TargetType, Telemetry, Waypoint,
+    Alert, AlertDelivery, AlertSeverity, ApiKey, ApiKeyScope, AuthorizationStatus, Convoy, ConvoyStatsSnapshot,
+    ConvoyStatus, Coordinates, DamageAssessment, Drone, DroneStatus, DroneStatusChange, Engagement,
+    Environment, ArtifactKind, MissionArtifact,
+    EngagementAuthorization, EngagementLogEvent, GeoPoint, ImpactPoint, JournalEntry, NoStrikeZone,
+    LeaderboardEntry, MissionType, PlatformType, RankHistoryEntry, ScoringModel, SensorTask, SensorType, Target,
+    TargetHandoff, TargetStatus, TargetType, Telemetry, ThreatLevel, TrackPoint, Waypoint,
+    WaypointStatus,
+    WaypointType, WeaponState, WeaponStatus, WeaponType, DeliveryStatus, ZoneEnforcement,
 };
 
 // =============================================================================
@@ -26,6 +46,12 @@ pub struct ScyllaConfig {
     pub keyspace: String,
     pub username: Option<String>,
     pub password: Option<String>,
+    pub breaker: BreakerConfig,
+    pub retry: RetryConfig,
+    /// Faults injected into queries, keyed `scylla.<verb>.<table>`
+    pub chaos: ChaosConfig,
+    /// Statements taking at least this long are logged; `None` logs none
+    pub slow_query_threshold: Option<Duration>,
 }
 
 impl Default for ScyllaConfig {
@@ -35,6 +61,10 @@ impl Default for ScyllaConfig {
             keyspace: "drone_ops".to_string(),
             username: None,
             password: None,
+            breaker: BreakerConfig::default(),
+            retry: RetryConfig::default(),
+            chaos: ChaosConfig::default(),
+            slow_query_threshold: None,
         }
     }
 }
@@ -44,8 +74,15 @@ impl Default for ScyllaConfig {
 // =============================================================================
 
 /// ScyllaDB client wrapper.
+///
+/// Repository queries go through [`ScyllaClient::query_unpaged`], which is
+/// guarded by a circuit breaker and retries transient errors, and every
+/// statement is recorded in [`ScyllaClient::metrics`].
 pub struct ScyllaClient {
     session: Arc<Session>,
+    breaker: Arc<CircuitBreaker>,
+    chaos: Arc<Chaos>,
+    metrics: Arc<RepositoryMetrics>,
     pub config: ScyllaConfig,
 }
 
@@ -68,122 +105,336 @@ impl ScyllaClient {
 
         Ok(Self {
             session: Arc::new(session),
+            breaker: Arc::new(CircuitBreaker::new("scylla", config.breaker)),
+            chaos: Arc::new(Chaos::new(config.chaos.clone())),
+            metrics: Arc::new(RepositoryMetrics::new(config.slow_query_threshold)),
             config,
         })
     }
 
     /// Get session reference.
+    ///
+    /// Queries issued on it bypass the circuit breaker.
     pub fn session(&self) -> &Session {
         &self.session
     }
+
+    /// Circuit breaker guarding this client.
+    pub fn breaker(&self) -> Arc<CircuitBreaker> {
+        self.breaker.clone()
+    }
+
+    /// Call, latency and row metrics of the repositories on this client.
+    pub fn metrics(&self) -> Arc<RepositoryMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Record a finished statement under its operation name; `result` is
+    /// `None` when it failed.
+    fn record(
+        &self,
+        op: &str,
+        query: &Query,
+        values: &impl SerializeRow,
+        started: Instant,
+        result: Option<&QueryResult>,
+    ) {
+        let rows = result.map(|result| {
+            result.clone().into_rows_result().map_or(0, |rows| rows.rows_num())
+        });
+        self.metrics.record_statement(
+            op,
+            &query.contents,
+            || value_shape(values),
+            started.elapsed(),
+            rows,
+        );
+    }
+
+    /// Run an unpaged query through the circuit breaker.
+    ///
+    /// Timeouts and unavailability are retried with backoff, so the statement
+    /// must be idempotent; use [`ScyllaClient::query_unpaged_once`] for
+    /// lightweight transactions and list or counter updates.
+    pub async fn query_unpaged(
+        &self,
+        query: impl Into<Query>,
+        values: impl SerializeRow,
+    ) -> Result<QueryResult> {
+        let query = query.into();
+        let op = statement_op(&query.contents);
+        let started = Instant::now();
+        let result = self
+            .config
+            .retry
+            .run(|| {
+                self.breaker.call(
+                    self.chaos
+                        .run(&op, self.session.query_unpaged(query.clone(), &values)),
+                )
+            })
+            .await;
+        self.record(&op, &query, &values, started, result.as_ref().ok());
+        result
+    }
+
+    /// Run an unpaged query through the circuit breaker without retries.
+    pub async fn query_unpaged_once(
+        &self,
+        query: impl Into<Query>,
+        values: impl SerializeRow,
+    ) -> Result<QueryResult> {
+        let query = query.into();
+        let op = statement_op(&query.contents);
+        let started = Instant::now();
+        let result = self
+            .breaker
+            .call(self.chaos.run(&op, self.session.query_unpaged(query.clone(), &values)))
+            .await;
+        self.record(&op, &query, &values, started, result.as_ref().ok());
+        result
+    }
+
+    /// Read one page of a query through the circuit breaker.
+    ///
+    /// Starts from `paging_state` (the first page when `None`) and returns
+    /// the state to resume from, `None` once the result is exhausted. Pages
+    /// may hold fewer than `page_size` rows even when more follow. Retried
+    /// like [`ScyllaClient::query_unpaged`], so the query must be a read.
+    pub async fn query_page(
+        &self,
+        query: impl Into<Query>,
+        values: impl SerializeRow,
+        page_size: usize,
+        paging_state: Option<&[u8]>,
+    ) -> Result<(QueryResult, Option<Vec<u8>>)> {
+        let page_size = i32::try_from(page_size.max(1)).unwrap_or(i32::MAX);
+        let query = query.into().with_page_size(page_size);
+        let start = paging_state.map_or_else(PagingState::start, PagingState::new_from_raw_bytes);
+        let op = statement_op(&query.contents);
+        let started = Instant::now();
+        let outcome = self
+            .config
+            .retry
+            .run(|| {
+                self.breaker.call(self.chaos.run(
+                    &op,
+                    self.session
+                        .query_single_page(query.clone(), &values, start.clone()),
+                ))
+            })
+            .await;
+        self.record(&op, &query, &values, started, outcome.as_ref().ok().map(|(result, _)| result));
+        let (result, response) = outcome?;
+
+        let next = match response {
+            PagingStateResponse::HasMorePages { state } => {
+                state.as_bytes_slice().map(|bytes| bytes.to_vec())
+            }
+            PagingStateResponse::NoMorePages => None,
+        };
+        Ok((result, next))
+    }
+}
+
+/// One page of a partition read with [`ScyllaClient::query_page`].
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Paging state of the following page, `None` on the last page
+    pub paging_state: Option<Vec<u8>>,
 }
 
 // =============================================================================
 // LEADERBOARD REPOSITORY
 // =============================================================================
 
+/// Leaderboard entry after an update, with the rank it moved from.
+#[derive(Debug, Clone)]
+pub struct RankedUpdate {
+    /// Updated entry carrying its new rank
+    pub entry: LeaderboardEntry,
+    /// Rank before the update, `None` for a new entry
+    pub old_rank: Option<i16>,
+}
+
 /// Repository for leaderboard operations.
 pub struct ScyllaLeaderboardRepository {
     client: Arc<ScyllaClient>,
     cache: Option<SharedCacheClient>,
-    read_strategy: ReadStrategy,
-    write_strategy: WriteStrategy,
+    strategy: DynamicStrategy,
+    default_scoring_model: ScoringModel,
 }
 
 impl ScyllaLeaderboardRepository {
     /// Create a new leaderboard repository with default strategies.
     pub fn new(client: Arc<ScyllaClient>, cache: Option<SharedCacheClient>) -> Self {
-        Self {
-            client,
-            cache,
-            read_strategy: ReadStrategy::CacheFirst,
-            write_strategy: WriteStrategy::WriteThrough,
-        }
+        Self::with_strategies(client, cache, ReadStrategy::CacheFirst, WriteStrategy::WriteThrough)
     }
 
     /// Create with custom strategies.
+    ///
+    /// With a cache, the strategies degrade to `DbOnly` while the cache
+    /// circuit breaker is open.
     pub fn with_strategies(
         client: Arc<ScyllaClient>,
         cache: Option<SharedCacheClient>,
         read_strategy: ReadStrategy,
         write_strategy: WriteStrategy,
     ) -> Self {
+        let mut strategy = DynamicStrategy::new(read_strategy, write_strategy);
+        if let Some(cache) = &cache {
+            strategy = strategy.degrade_on(cache.breaker());
+        }
+
         Self {
             client,
             cache,
-            read_strategy,
-            write_strategy,
+            strategy,
+            default_scoring_model: ScoringModel::default(),
         }
     }
 
     /// Set read strategy.
-    pub fn set_read_strategy(&mut self, strategy: ReadStrategy) {
-        self.read_strategy = strategy;
+    pub fn set_read_strategy(&self, strategy: ReadStrategy) {
+        self.strategy.set_read(strategy);
     }
 
     /// Set write strategy.
-    pub fn set_write_strategy(&mut self, strategy: WriteStrategy) {
-        self.write_strategy = strategy;
+    pub fn set_write_strategy(&self, strategy: WriteStrategy) {
+        self.strategy.set_write(strategy);
     }
 
-    /// Get leaderboard for a convoy.
-    pub async fn get_leaderboard(
-        &self,
-        convoy_id: Uuid,
-        limit: i32,
-    ) -> Result<Vec<LeaderboardEntry>> {
-        // Try cache first if available
-        if let Some(ref cache) = self.cache {
-            if let Ok(cached) = cache.get_leaderboard(convoy_id, limit as usize).await {
-                // Cache returns Vec<(Uuid, f64)> - would need to hydrate full entries
-                let _ = cached;
+    /// Shared handle for swapping strategies at runtime.
+    pub fn strategy(&self) -> DynamicStrategy {
+        self.strategy.clone()
+    }
+
+    /// Set the scoring model used for convoys without an explicit selection.
+    pub fn set_default_scoring_model(&mut self, model: ScoringModel) {
+        self.default_scoring_model = model;
+    }
+
+    /// Select the scoring model for a convoy.
+    ///
+    /// The model is stored on the convoy row, and every stored score, rank
+    /// and the cached sorted set are recomputed under it.
+    pub async fn set_scoring_model(&self, convoy_id: Uuid, model: ScoringModel) -> Result<()> {
+        let _timer = self.client.metrics.time("leaderboard.set_scoring_model");
+        self.client
+            .query_unpaged(
+                "UPDATE convoys SET scoring_model = ? WHERE convoy_id = ?",
+                (model.as_str(), convoy_id),
+            )
+            .await?;
+
+        self.rescore(convoy_id, model).await
+    }
+
+    /// Get the scoring model stored on a convoy, or the default.
+    pub async fn scoring_model(&self, convoy_id: Uuid) -> Result<ScoringModel> {
+        let result = self.client
+            .query_unpaged("SELECT scoring_model FROM convoys WHERE convoy_id = ?", (convoy_id,))
+            .await?;
+
+        Ok(result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<(Option<String>,)>().ok().flatten())
+            .and_then(|(model,)| model.as_deref().and_then(ScoringModel::parse))
+            .unwrap_or(self.default_scoring_model))
+    }
+
+    /// Rewrite every score and rank in a convoy under `model`.
+    async fn rescore(&self, convoy_id: Uuid, model: ScoringModel) -> Result<()> {
+        let entries = self.scored_entries(convoy_id, model).await?;
+        for entry in &entries {
+            let update = "UPDATE leaderboard SET score = ? \
+                          WHERE convoy_id = ? AND accuracy_pct = ? AND drone_id = ?";
+            self.client
+                .query_unpaged(update, (entry.score, convoy_id, entry.accuracy_pct, entry.drone_id))
+                .await?;
+        }
+
+        if let Some(cache) = self
+            .cache
+            .as_ref()
+            .filter(|_| self.strategy.write() != WriteStrategy::DbOnly)
+        {
+            let scores: Vec<(Uuid, f64)> = entries.iter().map(|e| (e.drone_id, e.score)).collect();
+            if let Err(e) = cache.update_leaderboard_scores(convoy_id, &scores).await {
+                tracing::warn!(%convoy_id, error = %e, "Failed to re-score leaderboard sorted set");
             }
         }
 
+        let (_, ranks) = rank_changes(&entries, Uuid::nil());
+        let changed: Vec<Uuid> = ranks.iter().map(|(id, _)| *id).collect();
+        self.persist_ranks(convoy_id, Uuid::nil(), ranks);
+        self.bump_version(convoy_id, &changed).await;
+        Ok(())
+    }
+
+    /// Every entry in a convoy scored under `model`, highest score first.
+    async fn scored_entries(
+        &self,
+        convoy_id: Uuid,
+        model: ScoringModel,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        // The table clusters on accuracy, so scoring has to see every row
         let query = r#"
-            SELECT convoy_id, drone_id, callsign, platform_type, 
-                   total_engagements, successful_hits, accuracy_pct, 
-                   current_streak, best_streak, rank, updated_at
+            SELECT convoy_id, drone_id, callsign, platform_type,
+                   total_engagements, successful_hits, accuracy_pct,
+                   current_streak, best_streak, rank
             FROM leaderboard
             WHERE convoy_id = ?
-            LIMIT ?
         "#;
 
-        let result = self.client.session
-            .query_unpaged(query, (convoy_id, limit))
+        let result = self.client
+            .query_unpaged(query, (convoy_id,))
             .await?;
 
         let mut entries = Vec::new();
-        
-        // Get rows result and iterate
         if let Ok(rows_result) = result.into_rows_result() {
-            if let Ok(rows) = rows_result.rows::<(
-                Uuid, Uuid, String, String, i32, i32, f32, i32, i32, i16
-            )>() {
-                for row_result in rows {
-                    if let Ok((cid, did, callsign, platform, total, hits, acc, streak, best, rank)) = row_result {
-                        entries.push(LeaderboardEntry {
-                            convoy_id: cid,
-                            drone_id: did,
-                            callsign,
-                            platform_type: parse_platform_type(&platform),
-                            total_engagements: total,
-                            successful_hits: hits,
-                            accuracy_pct: acc,
-                            current_streak: streak,
-                            best_streak: best,
-                            rank,
-                            updated_at: Utc::now(),
-                        });
-                    }
-                }
+            if let Ok(rows) = rows_result.rows::<LeaderboardRow>() {
+                entries.extend(rows.flatten().map(|row| leaderboard_entry_from_row(row, model)));
+            }
+        }
+
+        entries.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(entries)
+    }
+
+    /// Get leaderboard for a convoy, highest score first.
+    pub async fn get_leaderboard(
+        &self,
+        convoy_id: Uuid,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let _timer = self.client.metrics.time("leaderboard.get_leaderboard");
+        // Try cache first if available and the strategy allows it
+        let use_cache = self.strategy.read() != ReadStrategy::DbOnly;
+        if let Some(cache) = self.cache.as_ref().filter(|_| use_cache) {
+            let cached = cache.get_leaderboard(convoy_id, limit as usize).await;
+            let hit = cached.as_ref().is_ok_and(|c| !c.is_empty());
+            self.client.metrics.cache("leaderboard.get_leaderboard", hit);
+            if let Ok(cached) = cached {
+                // Cache returns Vec<(Uuid, f64)> - would need to hydrate full entries
+                let _ = cached;
             }
         }
 
+        let model = self.scoring_model(convoy_id).await?;
+        let mut entries = self.scored_entries(convoy_id, model).await?;
+        entries.truncate(usize::try_from(limit).unwrap_or_default());
+
         Ok(entries)
     }
 
     /// Update leaderboard entry after engagement.
+    ///
+    /// The new rank comes from the Redis sorted set (`ZREVRANK + 1`) when the
+    /// write strategy maintains it, otherwise from the score order in Scylla.
+    /// Ranks shifted by the move are persisted in the background.
     pub async fn update_entry(
         &self,
         convoy_id: Uuid,
@@ -191,29 +442,36 @@ impl ScyllaLeaderboardRepository {
         callsign: &str,
         platform: PlatformType,
         hit: bool,
-    ) -> Result<LeaderboardEntry> {
+    ) -> Result<RankedUpdate> {
+        let _timer = self.client.metrics.time("leaderboard.update_entry");
         // Get current stats or defaults
         let current = self.get_drone_entry(convoy_id, drone_id).await?;
-        
-        let (total, hits, streak, best) = match current {
-            Some(e) => {
-                let new_streak = if hit { e.current_streak + 1 } else { 0 };
-                let new_best = new_streak.max(e.best_streak);
-                (
-                    e.total_engagements + 1,
-                    if hit { e.successful_hits + 1 } else { e.successful_hits },
-                    new_streak,
-                    new_best,
-                )
-            }
-            None => (1, if hit { 1 } else { 0 }, if hit { 1 } else { 0 }, if hit { 1 } else { 0 }),
-        };
+        let sorted_set = self.cache.as_ref().filter(|_| {
+            matches!(
+                self.strategy.write(),
+                WriteStrategy::WriteThrough | WriteStrategy::WriteBack
+            )
+        });
+
+        let mut old_rank = None;
+        if let Some(cache) = sorted_set {
+            old_rank = cache
+                .get_drone_rank(convoy_id, drone_id)
+                .await
+                .ok()
+                .flatten()
+                .map(one_based_rank);
+        }
+        let old_rank = old_rank.or_else(|| current.as_ref().map(|e| e.rank).filter(|r| *r > 0));
 
-        let accuracy = if total > 0 {
-            (hits as f32 / total as f32) * 100.0
-        } else {
-            0.0
-        };
+        let tally = LeaderboardTally::after(current.as_ref(), hit);
+        let (total, hits) = (tally.total_engagements, tally.successful_hits);
+        let (streak, best) = (tally.current_streak, tally.best_streak);
+        let accuracy = tally.accuracy_pct().0;
+        let score = self
+            .scoring_model(convoy_id)
+            .await?
+            .score(i64::from(hits), i64::from(total));
 
         let update = r#"
             UPDATE leaderboard
@@ -222,19 +480,21 @@ impl ScyllaLeaderboardRepository {
                 total_engagements = ?, 
                 successful_hits = ?, 
                 accuracy_pct = ?,
+                score = ?,
                 current_streak = ?,
                 best_streak = ?,
                 updated_at = toTimestamp(now())
             WHERE convoy_id = ? AND drone_id = ?
         "#;
 
-        self.client.session
+        self.client
             .query_unpaged(update, (
                 callsign,
                 platform.as_str(),
                 total,
                 hits,
                 accuracy,
+                score,
                 streak,
                 best,
                 convoy_id,
@@ -242,26 +502,292 @@ impl ScyllaLeaderboardRepository {
             ))
             .await?;
 
-        // Invalidate cache
+        // Invalidate drone cache and re-key the sorted set on score
         if let Some(ref cache) = self.cache {
-            let _ = cache.invalidate_drone(drone_id).await;
+            match self.strategy.write() {
+                WriteStrategy::DbOnly => {}
+                WriteStrategy::WriteAround => {
+                    let _ = cache.invalidate_drone(drone_id).await;
+                }
+                WriteStrategy::WriteThrough | WriteStrategy::WriteBack => {
+                    let _ = cache.invalidate_drone(drone_id).await;
+                    let _ = cache.update_leaderboard_score(convoy_id, drone_id, score).await;
+                }
+            }
         }
 
-        Ok(LeaderboardEntry {
-            convoy_id,
-            drone_id,
-            callsign: callsign.to_string(),
-            platform_type: platform,
-            total_engagements: total,
-            successful_hits: hits,
-            accuracy_pct: accuracy,
-            current_streak: streak,
-            best_streak: best,
-            rank: 0, // Will be recalculated
-            updated_at: Utc::now(),
+        let (rank, shifted) = self.refresh_ranks(convoy_id, drone_id, old_rank, sorted_set).await;
+        let mut changed = shifted;
+        if !changed.contains(&drone_id) {
+            changed.push(drone_id);
+        }
+        self.bump_version(convoy_id, &changed).await;
+
+        if rank > 0 && old_rank != Some(rank) {
+            let change = RankHistoryEntry {
+                convoy_id,
+                drone_id,
+                recorded_at: Utc::now(),
+                rank,
+                accuracy_pct: Some(accuracy),
+            };
+            if let Err(e) = self.record_rank_change(&change).await {
+                tracing::warn!(%convoy_id, %drone_id, error = %e, "Failed to record rank change");
+            }
+        }
+
+        Ok(RankedUpdate {
+            entry: LeaderboardEntry {
+                convoy_id,
+                drone_id,
+                callsign: callsign.to_string(),
+                platform_type: platform,
+                total_engagements: total,
+                successful_hits: hits,
+                accuracy_pct: accuracy,
+                current_streak: streak,
+                best_streak: best,
+                score,
+                rank,
+                updated_at: Utc::now(),
+            },
+            old_rank,
         })
     }
 
+    /// Work out the drone's new rank and persist every rank the move shifted.
+    ///
+    /// Returns the new rank (0 if it cannot be determined) and the drones
+    /// whose rank was rewritten.
+    async fn refresh_ranks(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        old_rank: Option<i16>,
+        sorted_set: Option<&SharedCacheClient>,
+    ) -> (i16, Vec<Uuid>) {
+        if let Some(cache) = sorted_set {
+            let index = cache.get_drone_rank(convoy_id, drone_id).await;
+            self.client
+                .metrics
+                .cache("leaderboard.update_entry", matches!(index, Ok(Some(_))));
+            if let Ok(Some(index)) = index {
+                let new_rank = one_based_rank(index);
+
+                // Everyone between the old and new position moved by one;
+                // a new entry pushes down the whole tail.
+                let first = old_rank.map_or(new_rank, |old| old.min(new_rank));
+                let stop = old_rank.map_or(-1, |old| isize::from(old.max(new_rank)) - 1);
+                let mut shifted = Vec::new();
+                if let Ok(ids) = cache
+                    .get_leaderboard_range(convoy_id, isize::from(first) - 1, stop)
+                    .await
+                {
+                    shifted.clone_from(&ids);
+                    self.persist_ranks(convoy_id, drone_id, ids.into_iter().zip(first..).collect());
+                }
+                return (new_rank, shifted);
+            }
+        }
+
+        // No sorted set to consult: rank on score from Scylla and persist
+        // whatever no longer matches its position
+        let Ok(entries) = self.get_leaderboard(convoy_id, i32::MAX).await else {
+            return (0, Vec::new());
+        };
+        let (new_rank, ranks) = rank_changes(&entries, drone_id);
+        let shifted = ranks.iter().map(|(id, _)| *id).collect();
+        self.persist_ranks(convoy_id, drone_id, ranks);
+        (new_rank, shifted)
+    }
+
+    /// Bump the convoy's leaderboard version, logging `changed` drones for
+    /// diff readers. Skipped when the write strategy keeps the cache out.
+    async fn bump_version(&self, convoy_id: Uuid, changed: &[Uuid]) {
+        let Some(cache) = self
+            .cache
+            .as_ref()
+            .filter(|_| self.strategy.write() != WriteStrategy::DbOnly)
+        else {
+            return;
+        };
+        if let Err(e) = cache
+            .bump_leaderboard_version(convoy_id, changed, Utc::now().timestamp_millis())
+            .await
+        {
+            tracing::warn!(%convoy_id, error = %e, "Failed to bump leaderboard version");
+        }
+    }
+
+    /// Write ranks to Scylla without blocking the caller.
+    ///
+    /// Every drone other than `mover` gets a rank history row without
+    /// accuracy; the mover's row is written by [`Self::update_entry`].
+    fn persist_ranks(&self, convoy_id: Uuid, mover: Uuid, ranks: Vec<(Uuid, i16)>) {
+        if ranks.is_empty() {
+            return;
+        }
+
+        let client = self.client.clone();
+        let recorded_at = CqlTimestamp(Utc::now().timestamp_millis());
+        tokio::spawn(async move {
+            for (drone_id, rank) in ranks {
+                let update = "UPDATE leaderboard SET rank = ? WHERE convoy_id = ? AND drone_id = ?";
+                if let Err(e) = client.query_unpaged(update, (rank, convoy_id, drone_id)).await {
+                    tracing::warn!(%convoy_id, %drone_id, error = %e, "Failed to persist leaderboard rank");
+                }
+                if drone_id == mover {
+                    continue;
+                }
+                let history = "INSERT INTO leaderboard_history (convoy_id, drone_id, recorded_at, rank) VALUES (?, ?, ?, ?)";
+                if let Err(e) = client.query_unpaged(history, (convoy_id, drone_id, recorded_at, rank)).await {
+                    tracing::warn!(%convoy_id, %drone_id, error = %e, "Failed to record rank change");
+                }
+            }
+        });
+    }
+
+    /// Append a row to a drone's rank history.
+    pub async fn record_rank_change(&self, change: &RankHistoryEntry) -> Result<()> {
+        let _timer = self.client.metrics.time("leaderboard.record_rank_change");
+        let query = r#"
+            INSERT INTO leaderboard_history (convoy_id, drone_id, recorded_at, rank, accuracy_pct)
+            VALUES (?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    change.convoy_id,
+                    change.drone_id,
+                    CqlTimestamp(change.recorded_at.timestamp_millis()),
+                    change.rank,
+                    change.accuracy_pct,
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a drone's rank changes within a time window, oldest first.
+    pub async fn rank_history(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<RankHistoryEntry>> {
+        let _timer = self.client.metrics.time("leaderboard.rank_history");
+        let query = r#"
+            SELECT recorded_at, rank, accuracy_pct
+            FROM leaderboard_history
+            WHERE convoy_id = ? AND drone_id = ? AND recorded_at >= ? AND recorded_at <= ?
+        "#;
+
+        let result = self.client
+            .query_unpaged(
+                query,
+                (
+                    convoy_id,
+                    drone_id,
+                    CqlTimestamp(start.timestamp_millis()),
+                    CqlTimestamp(end.timestamp_millis()),
+                ),
+            )
+            .await?;
+
+        let mut entries = Vec::new();
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(CqlTimestamp, i16, Option<f32>)>() {
+                entries.extend(rows.flatten().map(|(time, rank, accuracy_pct)| RankHistoryEntry {
+                    convoy_id,
+                    drone_id,
+                    recorded_at: DateTime::from_timestamp_millis(time.0).unwrap_or_default(),
+                    rank,
+                    accuracy_pct,
+                }));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Accuracy on a drone's latest rank history row with accuracy before
+    /// `before`, to seed a timeline that starts mid-mission.
+    pub async fn accuracy_before(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<Option<f32>> {
+        let _timer = self.client.metrics.time("leaderboard.accuracy_before");
+        let query = r#"
+            SELECT accuracy_pct
+            FROM leaderboard_history
+            WHERE convoy_id = ? AND drone_id = ? AND recorded_at < ?
+            ORDER BY recorded_at DESC
+        "#;
+
+        let result = self.client
+            .query_unpaged(query, (convoy_id, drone_id, CqlTimestamp(before.timestamp_millis())))
+            .await?;
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(Option<f32>,)>() {
+                return Ok(rows.flatten().find_map(|(accuracy,)| accuracy));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Overwrite a leaderboard entry verbatim (snapshot restore).
+    pub async fn restore_entry(&self, entry: &LeaderboardEntry) -> Result<()> {
+        let _timer = self.client.metrics.time("leaderboard.restore_entry");
+        let update = r#"
+            UPDATE leaderboard
+            SET callsign = ?,
+                platform_type = ?,
+                total_engagements = ?,
+                successful_hits = ?,
+                accuracy_pct = ?,
+                score = ?,
+                current_streak = ?,
+                best_streak = ?,
+                rank = ?,
+                updated_at = ?
+            WHERE convoy_id = ? AND drone_id = ?
+        "#;
+
+        self.client
+            .query_unpaged(update, (
+                &entry.callsign,
+                entry.platform_type.as_str(),
+                entry.total_engagements,
+                entry.successful_hits,
+                entry.accuracy_pct,
+                entry.score,
+                entry.current_streak,
+                entry.best_streak,
+                entry.rank,
+                CqlTimestamp(entry.updated_at.timestamp_millis()),
+                entry.convoy_id,
+                entry.drone_id,
+            ))
+            .await?;
+
+        if let Some(ref cache) = self.cache {
+            let _ = cache.invalidate_drone(entry.drone_id).await;
+            let _ = cache
+                .update_leaderboard_score(entry.convoy_id, entry.drone_id, entry.score)
+                .await;
+        }
+        self.bump_version(entry.convoy_id, &[entry.drone_id]).await;
+
+        Ok(())
+    }
+
     /// Get single drone entry.
     async fn get_drone_entry(
         &self,
@@ -276,35 +802,16 @@ impl ScyllaLeaderboardRepository {
             WHERE convoy_id = ? AND drone_id = ?
         "#;
 
-        let result = self.client.session
+        let result = self.client
             .query_unpaged(query, (convoy_id, drone_id))
             .await?;
 
-        if let Ok(rows_result) = result.into_rows_result() {
-            if let Ok(rows) = rows_result.rows::<(
-                Uuid, Uuid, String, String, i32, i32, f32, i32, i32, i16
-            )>() {
-                for row_result in rows {
-                    if let Ok((cid, did, callsign, platform, total, hits, acc, streak, best, rank)) = row_result {
-                        return Ok(Some(LeaderboardEntry {
-                            convoy_id: cid,
-                            drone_id: did,
-                            callsign,
-                            platform_type: parse_platform_type(&platform),
-                            total_engagements: total,
-                            successful_hits: hits,
-                            accuracy_pct: acc,
-                            current_streak: streak,
-                            best_streak: best,
-                            rank,
-                            updated_at: Utc::now(),
-                        }));
-                    }
-                }
-            }
-        }
-
-        Ok(None)
+        let model = self.scoring_model(convoy_id).await?;
+        Ok(result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<LeaderboardRow>().ok().flatten())
+            .map(|row| leaderboard_entry_from_row(row, model)))
     }
 }
 
@@ -312,6 +819,58 @@ impl ScyllaLeaderboardRepository {
 // ENGAGEMENT REPOSITORY
 // =============================================================================
 
+/// Columns read back into an [`Engagement`] by [`parse_engagements`].
+const ENGAGEMENT_COLUMNS: &str = "engaged_at, engagement_id, drone_id, drone_callsign, \
+    weapon_type, target_type, target_id, hit, impact_lat, impact_lon, range_to_target_km, \
+    bda_status, bda_notes, authorization_code, roe_compliance, shooter_lat, shooter_lon, \
+    sequence, predicted_pk";
+
+/// Columns bound from an [`EngagementRow`], in declaration order.
+const ENGAGEMENT_INSERT_COLUMNS: &str = "convoy_id, engaged_at, engagement_id, drone_id, \
+    drone_callsign, weapon_type, target_type, target_id, hit, impact_lat, impact_lon, \
+    range_to_target_km, bda_status, authorization_code, roe_compliance, shooter_lat, \
+    shooter_lon, sequence, predicted_pk";
+
+/// Bind values for an engagement insert.
+///
+/// A named row rather than a tuple: the insert binds more columns than the
+/// driver's tuple impls cover.
+#[derive(scylla::SerializeRow)]
+struct EngagementRow<'a> {
+    convoy_id: Uuid,
+    engaged_at: CqlTimestamp,
+    engagement_id: Uuid,
+    drone_id: Uuid,
+    drone_callsign: &'a str,
+    weapon_type: &'a str,
+    target_type: &'a str,
+    target_id: Option<Uuid>,
+    hit: bool,
+    impact_lat: f64,
+    impact_lon: f64,
+    range_to_target_km: f32,
+    bda_status: &'a str,
+    authorization_code: &'a str,
+    roe_compliance: bool,
+    shooter_lat: f64,
+    shooter_lon: f64,
+    sequence: i64,
+    predicted_pk: Option<f32>,
+}
+
+/// Build engagements from rows selected with [`ENGAGEMENT_COLUMNS`].
+fn parse_engagements(convoy_id: Uuid, result: QueryResult) -> Vec<Engagement> {
+    let mut engagements = Vec::new();
+
+    if let Ok(rows_result) = result.into_rows_result() {
+        if let Ok(rows) = rows_result.rows::<EngagementFeedRow>() {
+            engagements.extend(rows.flatten().map(|row| row.into_engagement(convoy_id)));
+        }
+    }
+
+    engagements
+}
+
 /// Repository for engagement operations.
 pub struct ScyllaEngagementRepository {
     client: Arc<ScyllaClient>,
@@ -324,30 +883,148 @@ impl ScyllaEngagementRepository {
     }
 
     /// Record a new engagement.
+    ///
+    /// The row is written to `engagements` and then to `engagements_by_drone`,
+    /// which serves drone-scoped history. If the second write fails the
+    /// engagement is still readable by convoy; recording it again is
+    /// idempotent.
     pub async fn record(&self, engagement: &Engagement) -> Result<()> {
+        let _timer = self.client.metrics.time("engagement.record");
+        let target_id = engagement.target.target_id;
+        let row = EngagementRow {
+            convoy_id: engagement.convoy_id,
+            engaged_at: CqlTimestamp(engagement.engaged_at.timestamp_millis()),
+            engagement_id: engagement.engagement_id,
+            drone_id: engagement.drone_id,
+            drone_callsign: &engagement.drone_callsign,
+            weapon_type: engagement.weapon_type.as_str(),
+            target_type: target_type_str(&engagement.target.target_type),
+            target_id: (!target_id.is_nil()).then_some(target_id),
+            hit: engagement.hit,
+            impact_lat: engagement.result.impact_coords.latitude,
+            impact_lon: engagement.result.impact_coords.longitude,
+            range_to_target_km: engagement.range_to_target_km,
+            bda_status: &engagement.bda_status,
+            authorization_code: &engagement.authorization_code,
+            roe_compliance: engagement.roe_compliance,
+            shooter_lat: engagement.shooter_position.latitude,
+            shooter_lon: engagement.shooter_position.longitude,
+            sequence: engagement.sequence,
+            predicted_pk: engagement.predicted_pk,
+        };
+
+        for table in ["engagements", "engagements_by_drone"] {
+            let query = format!(
+                "INSERT INTO {table} ({ENGAGEMENT_INSERT_COLUMNS}) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            );
+            self.client.query_unpaged(query, &row).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get a convoy's most recent engagements, newest first.
+    ///
+    /// Reads the denormalized columns only; weapon serial, shooter altitude
+    /// and collateral details are left at their defaults, and untracked
+    /// engagements carry a nil target ID.
+    pub async fn get_recent(&self, convoy_id: Uuid, limit: usize) -> Result<Vec<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_recent");
+        let query = format!(
+            "SELECT {ENGAGEMENT_COLUMNS} FROM engagements WHERE convoy_id = ? LIMIT ?"
+        );
+
+        let limit = i32::try_from(limit).unwrap_or(i32::MAX);
+        let result = self.client
+            .query_unpaged(query, (convoy_id, limit))
+            .await?;
+
+        Ok(parse_engagements(convoy_id, result))
+    }
+
+    /// Read a page of a convoy's engagements, newest first.
+    ///
+    /// Columns are read as for [`ScyllaEngagementRepository::get_recent`].
+    pub async fn get_page(
+        &self,
+        convoy_id: Uuid,
+        page_size: usize,
+        paging_state: Option<&[u8]>,
+    ) -> Result<Page<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_page");
+        let query = format!("SELECT {ENGAGEMENT_COLUMNS} FROM engagements WHERE convoy_id = ?");
+
+        let (result, paging_state) = self.client
+            .query_page(query, (convoy_id,), page_size, paging_state)
+            .await?;
+
+        Ok(Page {
+            items: parse_engagements(convoy_id, result),
+            paging_state,
+        })
+    }
+
+    /// Get a single engagement by ID.
+    pub async fn get(&self, convoy_id: Uuid, engagement_id: Uuid) -> Result<Option<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get");
+        // Filtering stays within the convoy's partition
+        let query = format!(
+            "SELECT {ENGAGEMENT_COLUMNS} FROM engagements \
+             WHERE convoy_id = ? AND engagement_id = ? ALLOW FILTERING"
+        );
+
+        let result = self.client
+            .query_unpaged(query, (convoy_id, engagement_id))
+            .await?;
+
+        Ok(parse_engagements(convoy_id, result).into_iter().next())
+    }
+
+    /// Record a battle damage assessment against an engagement.
+    ///
+    /// Applied to both engagement tables so drone history shows the same
+    /// assessment as the convoy feed.
+    pub async fn update_bda(
+        &self,
+        engagement: &Engagement,
+        bda_status: &str,
+        bda_notes: Option<&str>,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("engagement.update_bda");
+        let engaged_at = CqlTimestamp(engagement.engaged_at.timestamp_millis());
         let query = r#"
-            INSERT INTO engagements (
-                convoy_id, engaged_at, engagement_id, drone_id, drone_callsign,
-                weapon_type, hit, range_to_target_km, bda_status
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            UPDATE engagements SET bda_status = ?, bda_notes = ?
+            WHERE convoy_id = ? AND engaged_at = ? AND engagement_id = ?
         "#;
 
-        // Convert DateTime to milliseconds for CQL timestamp
-        let engaged_at_ms = engagement.engaged_at.timestamp_millis();
-
-        self.client.session
+        self.client
             .query_unpaged(
                 query,
                 (
+                    bda_status,
+                    bda_notes,
                     engagement.convoy_id,
-                    engaged_at_ms,
+                    engaged_at,
                     engagement.engagement_id,
+                ),
+            )
+            .await?;
+
+        let query = r#"
+            UPDATE engagements_by_drone SET bda_status = ?, bda_notes = ?
+            WHERE drone_id = ? AND engaged_at = ? AND engagement_id = ?
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    bda_status,
+                    bda_notes,
                     engagement.drone_id,
-                    &engagement.drone_callsign,
-                    engagement.weapon_type.as_str(),
-                    engagement.hit,
-                    engagement.range_to_target_km,
-                    &engagement.bda_status,
+                    engaged_at,
+                    engagement.engagement_id,
                 ),
             )
             .await?;
@@ -355,19 +1032,259 @@ impl ScyllaEngagementRepository {
         Ok(())
     }
 
-    /// Get engagements for a drone (stub - returns empty).
-    pub async fn get_by_drone(
-        &self,
-        _convoy_id: Uuid,
-        _drone_id: Uuid,
-        _limit: i32,
-    ) -> Result<Vec<Engagement>> {
-        // TODO: Implement full parsing of complex Engagement type
-        Ok(Vec::new())
-    }
-}
+    /// Count a convoy's engagements.
+    pub async fn count(&self, convoy_id: Uuid) -> Result<i64> {
+        let _timer = self.client.metrics.time("engagement.count");
+        let query = "SELECT COUNT(*) FROM engagements WHERE convoy_id = ?";
 
-// =============================================================================
+        let result = self.client
+            .query_unpaged(query, (convoy_id,))
+            .await?;
+
+        Ok(result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<(i64,)>().ok().flatten())
+            .map_or(0, |(count,)| count))
+    }
+
+    /// Get a convoy's engagements within a time window, oldest first.
+    ///
+    /// Columns are read as for [`ScyllaEngagementRepository::get_recent`].
+    pub async fn get_range(
+        &self,
+        convoy_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_range");
+        let query = format!(
+            "SELECT {ENGAGEMENT_COLUMNS} FROM engagements \
+             WHERE convoy_id = ? AND engaged_at >= ? AND engaged_at <= ? LIMIT ?"
+        );
+
+        let limit = i32::try_from(limit).unwrap_or(i32::MAX);
+        let result = self.client
+            .query_unpaged(
+                query,
+                (
+                    convoy_id,
+                    CqlTimestamp(start.timestamp_millis()),
+                    CqlTimestamp(end.timestamp_millis()),
+                    limit,
+                ),
+            )
+            .await?;
+
+        // Partition clusters newest first
+        let mut engagements = parse_engagements(convoy_id, result);
+        engagements.reverse();
+        Ok(engagements)
+    }
+
+    /// Get engagement impact points for a convoy within a time window.
+    pub async fn get_impact_points(
+        &self,
+        convoy_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ImpactPoint>> {
+        let _timer = self.client.metrics.time("engagement.get_impact_points");
+        let query = r#"
+            SELECT impact_lat, impact_lon, hit
+            FROM engagements
+            WHERE convoy_id = ? AND engaged_at >= ? AND engaged_at <= ?
+        "#;
+
+        let result = self.client
+            .query_unpaged(
+                query,
+                (
+                    convoy_id,
+                    CqlTimestamp(start.timestamp_millis()),
+                    CqlTimestamp(end.timestamp_millis()),
+                ),
+            )
+            .await?;
+
+        let mut points = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(Option<f64>, Option<f64>, Option<bool>)>() {
+                // Rows written before impact columns existed have no location
+                for (lat, lon, hit) in rows.flatten() {
+                    if let (Some(latitude), Some(longitude)) = (lat, lon) {
+                        points.push(ImpactPoint {
+                            latitude,
+                            longitude,
+                            hit: hit.unwrap_or(false),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(points)
+    }
+
+    /// Get a drone's most recent engagements within a convoy, newest first.
+    ///
+    /// Read from `engagements_by_drone`; columns are read as for
+    /// [`ScyllaEngagementRepository::get_recent`].
+    pub async fn get_by_drone(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        limit: i32,
+    ) -> Result<Vec<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_by_drone");
+        // Filtering stays within the drone's partition
+        let query = format!(
+            "SELECT {ENGAGEMENT_COLUMNS} FROM engagements_by_drone \
+             WHERE drone_id = ? AND convoy_id = ? LIMIT ? ALLOW FILTERING"
+        );
+
+        let result = self.client
+            .query_unpaged(query, (drone_id, convoy_id, limit))
+            .await?;
+
+        Ok(parse_engagements(convoy_id, result))
+    }
+
+    /// Read a page of a drone's engagements within a convoy, newest first.
+    ///
+    /// Columns are read as for [`ScyllaEngagementRepository::get_recent`].
+    pub async fn get_drone_page(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        page_size: usize,
+        paging_state: Option<&[u8]>,
+    ) -> Result<Page<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_drone_page");
+        // Filtering stays within the drone's partition
+        let query = format!(
+            "SELECT {ENGAGEMENT_COLUMNS} FROM engagements_by_drone \
+             WHERE drone_id = ? AND convoy_id = ? ALLOW FILTERING"
+        );
+
+        let (result, paging_state) = self.client
+            .query_page(query, (drone_id, convoy_id), page_size, paging_state)
+            .await?;
+
+        Ok(Page {
+            items: parse_engagements(convoy_id, result),
+            paging_state,
+        })
+    }
+}
+
+// =============================================================================
+// ENGAGEMENT EVENT LOG REPOSITORY
+// =============================================================================
+
+/// Repository for the append-only engagement event log.
+///
+/// Events are never updated or deleted; projections are rebuilt by
+/// replaying a convoy's partition in order.
+pub struct ScyllaEngagementLogRepository {
+    client: Arc<ScyllaClient>,
+}
+
+impl ScyllaEngagementLogRepository {
+    /// Create a new engagement event log repository.
+    pub fn new(client: Arc<ScyllaClient>) -> Self {
+        Self { client }
+    }
+
+    /// Append an event to its convoy's log.
+    pub async fn append(&self, event: &EngagementLogEvent) -> Result<()> {
+        let _timer = self.client.metrics.time("engagement_log.append");
+        let query = r#"
+            INSERT INTO engagement_event_log (
+                convoy_id, recorded_at, event_id, event_type, drone_id, payload
+            ) VALUES (?, ?, ?, ?, ?, ?)
+        "#;
+
+        let payload = serde_json::to_string(&event.payload)?;
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    event.convoy_id,
+                    CqlTimestamp(event.recorded_at.timestamp_millis()),
+                    event.event_id,
+                    event.payload.kind().as_str(),
+                    event.drone_id,
+                    payload,
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a convoy's full log, oldest first.
+    pub async fn events(&self, convoy_id: Uuid) -> Result<Vec<EngagementLogEvent>> {
+        let _timer = self.client.metrics.time("engagement_log.events");
+        let query = r#"
+            SELECT recorded_at, event_id, drone_id, payload
+            FROM engagement_event_log
+            WHERE convoy_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (convoy_id,)).await?;
+        parse_log_events(convoy_id, result)
+    }
+
+    /// Get events recorded after `after`, oldest first, for incremental
+    /// consumers such as the analytics sync.
+    pub async fn events_since(
+        &self,
+        convoy_id: Uuid,
+        after: DateTime<Utc>,
+    ) -> Result<Vec<EngagementLogEvent>> {
+        let _timer = self.client.metrics.time("engagement_log.events_since");
+        let query = r#"
+            SELECT recorded_at, event_id, drone_id, payload
+            FROM engagement_event_log
+            WHERE convoy_id = ? AND recorded_at > ?
+        "#;
+
+        let result = self
+            .client
+            .query_unpaged(query, (convoy_id, CqlTimestamp(after.timestamp_millis())))
+            .await?;
+        parse_log_events(convoy_id, result)
+    }
+}
+
+/// Build log events from `recorded_at, event_id, drone_id, payload` rows.
+///
+/// A payload that no longer decodes fails the read rather than being
+/// skipped, since replaying without it would silently change projections.
+fn parse_log_events(convoy_id: Uuid, result: QueryResult) -> Result<Vec<EngagementLogEvent>> {
+    let mut events = Vec::new();
+
+    if let Ok(rows_result) = result.into_rows_result() {
+        if let Ok(rows) = rows_result.rows::<(CqlTimestamp, Uuid, Uuid, String)>() {
+            for (recorded_at, event_id, drone_id, payload) in rows.flatten() {
+                events.push(EngagementLogEvent {
+                    convoy_id,
+                    event_id,
+                    recorded_at: DateTime::from_timestamp_millis(recorded_at.0).unwrap_or_default(),
+                    drone_id,
+                    payload: serde_json::from_str(&payload)?,
+                });
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+// =============================================================================
 // TELEMETRY REPOSITORY
 // =============================================================================
 
@@ -383,26 +1300,31 @@ impl ScyllaTelemetryRepository {
     }
 
     /// Record telemetry snapshot.
+    ///
+    /// Extension fields are stored as a JSON object; none are written when
+    /// the frame carried none.
     pub async fn record(&self, telemetry: &Telemetry) -> Result<()> {
+        let _timer = self.client.metrics.time("telemetry.record");
         let query = r#"
             INSERT INTO telemetry (
                 drone_id, time_bucket, recorded_at,
                 latitude, longitude, altitude_m, heading_deg, speed_mps,
-                velocity_mps, fuel_remaining_pct, engine_rpm, engine_temp_c
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                velocity_mps, fuel_remaining_pct, engine_rpm, engine_temp_c,
+                schema_version, extensions
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             USING TTL 86400
         "#;
+        let extensions = (!telemetry.extensions.is_empty())
+            .then(|| serde_json::to_string(&telemetry.extensions))
+            .transpose()?;
 
-        // Convert DateTime to milliseconds for CQL timestamp
-        let recorded_at_ms = telemetry.recorded_at.timestamp_millis();
-
-        self.client.session
+        self.client
             .query_unpaged(
                 query,
                 (
                     telemetry.drone_id,
                     &telemetry.time_bucket,
-                    recorded_at_ms,
+                    CqlTimestamp(telemetry.recorded_at.timestamp_millis()),
                     telemetry.position.latitude,
                     telemetry.position.longitude,
                     telemetry.position.altitude_m,
@@ -412,6 +1334,8 @@ impl ScyllaTelemetryRepository {
                     telemetry.fuel_remaining_pct,
                     telemetry.engine_rpm,
                     telemetry.engine_temp_c,
+                    telemetry.schema_version,
+                    extensions,
                 ),
             )
             .await?;
@@ -419,8 +1343,71 @@ impl ScyllaTelemetryRepository {
         Ok(())
     }
 
+    /// Get a drone's recorded positions within a time window, oldest first.
+    ///
+    /// Reads one hourly partition per bucket the window touches.
+    pub async fn get_track(
+        &self,
+        drone_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<TrackPoint>> {
+        let _timer = self.client.metrics.time("telemetry.get_track");
+        let query = r#"
+            SELECT recorded_at, latitude, longitude, altitude_m, heading_deg, speed_mps
+            FROM telemetry
+            WHERE drone_id = ? AND time_bucket = ? AND recorded_at >= ? AND recorded_at <= ?
+        "#;
+
+        let mut points = Vec::new();
+        let mut bucket_start = start
+            .duration_trunc(chrono::Duration::hours(1))
+            .unwrap_or(start);
+        while bucket_start <= end {
+            let result = self.client
+                .query_unpaged(
+                    query,
+                    (
+                        drone_id,
+                        Telemetry::generate_time_bucket(&bucket_start),
+                        CqlTimestamp(start.timestamp_millis()),
+                        CqlTimestamp(end.timestamp_millis()),
+                    ),
+                )
+                .await?;
+
+            if let Ok(rows_result) = result.into_rows_result() {
+                if let Ok(rows) = rows_result.rows::<(
+                    CqlTimestamp, Option<f64>, Option<f64>, Option<f64>, Option<f32>, Option<f32>
+                )>() {
+                    for (time, lat, lon, alt, heading, speed) in rows.flatten() {
+                        // Rows written before position columns existed have no location
+                        let (Some(latitude), Some(longitude)) = (lat, lon) else {
+                            continue;
+                        };
+                        let mut position =
+                            Coordinates::new(latitude, longitude, alt.unwrap_or_default());
+                        position.heading_deg = heading.unwrap_or_default();
+                        position.speed_mps = speed.unwrap_or_default();
+                        points.push(TrackPoint {
+                            recorded_at: DateTime::from_timestamp_millis(time.0).unwrap_or_default(),
+                            position,
+                        });
+                    }
+                }
+            }
+
+            bucket_start += chrono::Duration::hours(1);
+        }
+
+        // Partitions cluster newest first
+        points.sort_by_key(|p| p.recorded_at);
+        Ok(points)
+    }
+
     /// Get latest telemetry for a drone (stub - returns None).
     pub async fn get_latest(&self, _drone_id: Uuid) -> Result<Option<Telemetry>> {
+        let _timer = self.client.metrics.time("telemetry.get_latest");
         // TODO: Implement full parsing of complex Telemetry type
         Ok(None)
     }
@@ -430,25 +1417,169 @@ impl ScyllaTelemetryRepository {
 // CONVOY REPOSITORY
 // =============================================================================
 
+/// Tenant and environment a convoy belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvoyOwner {
+    pub commanding_unit: String,
+    pub environment: Environment,
+}
+
+impl ConvoyOwner {
+    fn from_columns(unit: Option<String>, environment: Option<String>) -> Self {
+        Self {
+            commanding_unit: unit.unwrap_or_default(),
+            // Rows written before environments existed are live data
+            environment: environment.and_then(|e| e.parse().ok()).unwrap_or_default(),
+        }
+    }
+}
+
 /// Repository for convoy operations.
 pub struct ScyllaConvoyRepository {
     client: Arc<ScyllaClient>,
+    /// Owner per convoy; set at creation and never reassigned
+    owners: RwLock<HashMap<Uuid, ConvoyOwner>>,
 }
 
 impl ScyllaConvoyRepository {
     /// Create a new convoy repository.
     pub fn new(client: Arc<ScyllaClient>) -> Self {
-        Self { client }
+        Self {
+            client,
+            owners: RwLock::new(HashMap::new()),
+        }
     }
 
-    /// Get convoy by ID (stub - returns None).
-    pub async fn get(&self, _convoy_id: Uuid) -> Result<Option<Convoy>> {
-        // TODO: Implement full parsing of complex Convoy type
-        Ok(None)
+    /// Commanding unit and environment of a convoy, `None` if the convoy
+    /// does not exist.
+    pub async fn owner(&self, convoy_id: Uuid) -> Result<Option<ConvoyOwner>> {
+        let _timer = self.client.metrics.time("convoy.owner");
+        let cached = self.owners.read().ok().and_then(|o| o.get(&convoy_id).cloned());
+        self.client.metrics.cache("convoy.owner", cached.is_some());
+        if let Some(owner) = cached {
+            return Ok(Some(owner));
+        }
+
+        let result = self.client
+            .query_unpaged(
+                "SELECT commanding_unit, environment FROM convoys WHERE convoy_id = ?",
+                (convoy_id,),
+            )
+            .await?;
+
+        let owner = result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| {
+                rows.maybe_first_row::<(Option<String>, Option<String>)>().ok().flatten()
+            })
+            .map(|(unit, environment)| ConvoyOwner::from_columns(unit, environment));
+
+        if let Some(owner) = &owner {
+            self.remember_owner(convoy_id, owner);
+        }
+        Ok(owner)
+    }
+
+    /// Owner of the convoy a drone is assigned to.
+    pub async fn owner_for_drone(&self, drone_id: Uuid) -> Result<Option<ConvoyOwner>> {
+        let _timer = self.client.metrics.time("convoy.owner_for_drone");
+        let result = self.client
+            .query_unpaged(
+                "SELECT convoy_id, commanding_unit, environment FROM convoys WHERE drone_ids CONTAINS ?",
+                (drone_id,),
+            )
+            .await?;
+
+        let row = result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| {
+                rows.maybe_first_row::<(Uuid, Option<String>, Option<String>)>().ok().flatten()
+            });
+
+        Ok(row.map(|(convoy_id, unit, environment)| {
+            let owner = ConvoyOwner::from_columns(unit, environment);
+            self.remember_owner(convoy_id, &owner);
+            owner
+        }))
+    }
+
+    /// ID of the convoy a drone is assigned to.
+    pub async fn convoy_for_drone(&self, drone_id: Uuid) -> Result<Option<Uuid>> {
+        let _timer = self.client.metrics.time("convoy.convoy_for_drone");
+        let result = self.client
+            .query_unpaged("SELECT convoy_id FROM convoys WHERE drone_ids CONTAINS ?", (drone_id,))
+            .await?;
+
+        Ok(result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<(Uuid,)>().ok().flatten())
+            .map(|(convoy_id,)| convoy_id))
+    }
+
+    /// IDs of the convoys a commanding unit owns in one environment.
+    pub async fn list_ids_by_unit(&self, unit: &str, environment: Environment) -> Result<Vec<Uuid>> {
+        let _timer = self.client.metrics.time("convoy.list_ids_by_unit");
+        // Filter environments here rather than with ALLOW FILTERING; a unit
+        // owns few enough convoys that reading them all is cheap
+        let result = self.client
+            .query_unpaged(
+                "SELECT convoy_id, environment FROM convoys WHERE commanding_unit = ?",
+                (unit,),
+            )
+            .await?;
+
+        let mut ids = Vec::new();
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(Uuid, Option<String>)>() {
+                for (id, env) in rows.flatten() {
+                    let owner = ConvoyOwner::from_columns(Some(unit.to_string()), env);
+                    if owner.environment == environment {
+                        ids.push(id);
+                    }
+                    self.remember_owner(id, &owner);
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    fn remember_owner(&self, convoy_id: Uuid, owner: &ConvoyOwner) {
+        if let Ok(mut owners) = self.owners.write() {
+            owners.insert(convoy_id, owner.clone());
+        }
+    }
+
+    /// Get convoy by ID.
+    pub async fn get(&self, convoy_id: Uuid) -> Result<Option<Convoy>> {
+        let _timer = self.client.metrics.time("convoy.get");
+        let query = r#"
+            SELECT convoy_callsign, mission_id, mission_type, status,
+                   created_at, mission_start, mission_end,
+                   aor_name, aor_center, aor_radius_km,
+                   commanding_unit, authorization_level, roe_profile,
+                   drone_ids, drone_count, environment
+            FROM convoys WHERE convoy_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (convoy_id,)).await?;
+        let convoy = result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<ConvoyRow>().ok().flatten())
+            .map(|row| convoy_from_row(convoy_id, row));
+
+        if let Some(convoy) = &convoy {
+            self.remember_owner(convoy_id, &owner_of(convoy));
+        }
+        Ok(convoy)
     }
 
     /// Create a new convoy.
     pub async fn create(&self, convoy: &Convoy) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.create");
         // Convert DateTime to milliseconds for CQL timestamp
         let created_at_ms = convoy.created_at.timestamp_millis();
         let mission_start_ms = convoy.mission_start.map(|dt| dt.timestamp_millis());
@@ -458,13 +1589,13 @@ impl ScyllaConvoyRepository {
             INSERT INTO convoys (
                 convoy_id, convoy_callsign, mission_id, mission_type, status,
                 created_at, mission_start, mission_end,
-                aor_name, aor_center_lat, aor_center_lon, aor_radius_km,
+                aor_name, aor_center, aor_radius_km,
                 commanding_unit, authorization_level, roe_profile,
-                drone_count
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                drone_ids, drone_count, environment
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#;
 
-        self.client.session
+        self.client
             .query_unpaged(
                 query,
                 (
@@ -477,84 +1608,2484 @@ impl ScyllaConvoyRepository {
                     mission_start_ms,
                     mission_end_ms,
                     &convoy.aor_name,
-                    convoy.aor_center.latitude,
-                    convoy.aor_center.longitude,
+                    CoordinatesUdt::from(&convoy.aor_center),
                     convoy.aor_radius_km,
                     &convoy.commanding_unit,
                     &convoy.authorization_level,
                     &convoy.roe_profile,
+                    &convoy.drone_ids,
                     convoy.drone_count,
+                    convoy.environment.as_str(),
                 ),
             )
             .await?;
 
+        self.remember_owner(convoy.convoy_id, &owner_of(convoy));
         Ok(())
     }
-}
 
-// =============================================================================
-// WAYPOINT REPOSITORY  
-// =============================================================================
+    /// Write a convoy's status and mission start and end times.
+    pub async fn update_status(&self, convoy: &Convoy) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.update_status");
+        self.client
+            .query_unpaged(
+                "UPDATE convoys SET status = ?, mission_start = ?, mission_end = ? \
+                 WHERE convoy_id = ?",
+                (
+                    convoy_status_str(&convoy.status),
+                    convoy.mission_start.map(|t| CqlTimestamp(t.timestamp_millis())),
+                    convoy.mission_end.map(|t| CqlTimestamp(t.timestamp_millis())),
+                    convoy.convoy_id,
+                ),
+            )
+            .await?;
+        Ok(())
+    }
 
-/// Repository for waypoint operations.
-pub struct ScyllaWaypointRepository {
-    client: Arc<ScyllaClient>,
-}
+    /// Record a periodic statistics snapshot.
+    pub async fn record_stats(&self, snapshot: &ConvoyStatsSnapshot) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.record_stats");
+        let query = r#"
+            INSERT INTO convoy_stats_history (
+                convoy_id, recorded_at, drone_count, airborne_count,
+                average_fuel_pct, total_engagements, total_hits
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#;
 
-impl ScyllaWaypointRepository {
-    /// Create a new waypoint repository.
-    pub fn new(client: Arc<ScyllaClient>) -> Self {
-        Self { client }
-    }
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    snapshot.convoy_id,
+                    CqlTimestamp(snapshot.recorded_at.timestamp_millis()),
+                    snapshot.drone_count,
+                    snapshot.airborne_count,
+                    snapshot.average_fuel_pct,
+                    snapshot.total_engagements,
+                    snapshot.total_hits,
+                ),
+            )
+            .await?;
 
-    /// Get waypoints for a drone (stub - returns empty).
-    pub async fn get_waypoints(&self, _drone_id: Uuid) -> Result<Vec<Waypoint>> {
-        // TODO: Implement full parsing of complex Waypoint type
-        Ok(Vec::new())
+        Ok(())
     }
-}
 
-// =============================================================================
-// HELPER FUNCTIONS
-// =============================================================================
+    /// Get statistics snapshots recorded within a time window, oldest first.
+    pub async fn get_stats_history(
+        &self,
+        convoy_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ConvoyStatsSnapshot>> {
+        let _timer = self.client.metrics.time("convoy.get_stats_history");
+        let query = r#"
+            SELECT recorded_at, drone_count, airborne_count, average_fuel_pct,
+                   total_engagements, total_hits
+            FROM convoy_stats_history
+            WHERE convoy_id = ? AND recorded_at >= ? AND recorded_at <= ?
+        "#;
 
-fn parse_platform_type(s: &str) -> PlatformType {
-    match s {
-        "MQ-9_REAPER" | "MQ9_REAPER" => PlatformType::Mq9Reaper,
-        "MQ-1C_GRAY_EAGLE" | "MQ1C_GRAY_EAGLE" => PlatformType::Mq1cGrayEagle,
-        "RQ-4_GLOBAL_HAWK" | "RQ4_GLOBAL_HAWK" => PlatformType::Rq4GlobalHawk,
-        "MQ-25_STINGRAY" | "MQ25_STINGRAY" => PlatformType::Mq25Stingray,
-        _ => PlatformType::Mq9Reaper,
-    }
-}
+        let result = self.client
+            .query_unpaged(
+                query,
+                (
+                    convoy_id,
+                    CqlTimestamp(start.timestamp_millis()),
+                    CqlTimestamp(end.timestamp_millis()),
+                ),
+            )
+            .await?;
 
-fn target_type_str(t: &TargetType) -> &'static str {
-    match t {
-        TargetType::Vehicle => "VEHICLE",
-        TargetType::Structure => "STRUCTURE",
-        TargetType::Personnel => "PERSONNEL",
-        TargetType::Radar => "RADAR",
-        TargetType::AirDefense => "AIR_DEFENSE",
-        TargetType::Supply => "SUPPLY",
-    }
-}
+        let mut snapshots = Vec::new();
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(CqlTimestamp, i32, i32, f32, i32, i32)>() {
+                snapshots.extend(rows.flatten().map(
+                    |(time, drones, airborne, fuel, engagements, hits)| ConvoyStatsSnapshot {
+                        convoy_id,
+                        recorded_at: DateTime::from_timestamp_millis(time.0).unwrap_or_default(),
+                        drone_count: drones,
+                        airborne_count: airborne,
+                        average_fuel_pct: fuel,
+                        total_engagements: engagements,
+                        total_hits: hits,
+                    },
+                ));
+            }
+        }
 
-fn mission_type_str(t: &MissionType) -> &'static str {
-    match t {
-        MissionType::Isr => "ISR",
-        MissionType::Strike => "STRIKE",
-        MissionType::Escort => "ESCORT",
-        MissionType::Resupply => "RESUPPLY",
-        MissionType::Sar => "SAR",
+        // Partition clusters newest first
+        snapshots.reverse();
+        Ok(snapshots)
     }
-}
 
-fn convoy_status_str(s: &ConvoyStatus) -> &'static str {
-    match s {
-        ConvoyStatus::Planning => "PLANNING",
-        ConvoyStatus::Active => "ACTIVE",
-        ConvoyStatus::Rtb => "RTB",
-        ConvoyStatus::Complete => "COMPLETE",
-        ConvoyStatus::Abort => "ABORT",
+    /// Create or replace a no-strike zone.
+    pub async fn put_no_strike_zone(&self, zone: &NoStrikeZone) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.put_no_strike_zone");
+        let query = r#"
+            INSERT INTO no_strike_zones (
+                convoy_id, zone_id, zone_name, vertices, enforcement, notes,
+                created_by, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        let vertices: Vec<(f64, f64)> =
+            zone.vertices.iter().map(|v| (v.latitude, v.longitude)).collect();
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    zone.convoy_id,
+                    zone.zone_id,
+                    &zone.name,
+                    vertices,
+                    zone.enforcement.as_str(),
+                    &zone.notes,
+                    &zone.created_by,
+                    CqlTimestamp(zone.created_at.timestamp_millis()),
+                    CqlTimestamp(zone.updated_at.timestamp_millis()),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// List a convoy's no-strike zones.
+    pub async fn list_no_strike_zones(&self, convoy_id: Uuid) -> Result<Vec<NoStrikeZone>> {
+        let _timer = self.client.metrics.time("convoy.list_no_strike_zones");
+        let query = r#"
+            SELECT zone_id, zone_name, vertices, enforcement, notes, created_by,
+                   created_at, updated_at
+            FROM no_strike_zones
+            WHERE convoy_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (convoy_id,)).await?;
+        let mut zones = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(
+                Uuid, Option<String>, Option<Vec<(f64, f64)>>, Option<String>, Option<String>,
+                Option<String>, Option<CqlTimestamp>, Option<CqlTimestamp>,
+            )>() {
+                for (zone_id, name, vertices, enforcement, notes, created_by, created, updated) in
+                    rows.flatten()
+                {
+                    let created_at = created
+                        .and_then(|t| DateTime::from_timestamp_millis(t.0))
+                        .unwrap_or_default();
+                    zones.push(NoStrikeZone {
+                        convoy_id,
+                        zone_id,
+                        name: name.unwrap_or_default(),
+                        vertices: vertices
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|(latitude, longitude)| GeoPoint { latitude, longitude })
+                            .collect(),
+                        enforcement: match enforcement.as_deref() {
+                            Some("FLAG") => ZoneEnforcement::Flag,
+                            _ => ZoneEnforcement::Block,
+                        },
+                        notes,
+                        created_by: created_by.unwrap_or_default(),
+                        created_at,
+                        updated_at: updated
+                            .and_then(|t| DateTime::from_timestamp_millis(t.0))
+                            .unwrap_or(created_at),
+                    });
+                }
+            }
+        }
+
+        Ok(zones)
+    }
+
+    /// Delete a no-strike zone.
+    pub async fn delete_no_strike_zone(&self, convoy_id: Uuid, zone_id: Uuid) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.delete_no_strike_zone");
+        self.client
+            .query_unpaged(
+                "DELETE FROM no_strike_zones WHERE convoy_id = ? AND zone_id = ?",
+                (convoy_id, zone_id),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Store a mission artifact, replacing the convoy's previous one of the
+    /// same kind. Identical content shares one blob row.
+    pub async fn put_artifact(&self, artifact: &MissionArtifact, content: &[u8]) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.put_artifact");
+        self.client
+            .query_unpaged(
+                "INSERT INTO artifact_blobs (digest, content) VALUES (?, ?)",
+                (&artifact.digest, content),
+            )
+            .await?;
+
+        let query = r#"
+            INSERT INTO mission_artifacts (
+                convoy_id, kind, digest, file_name, content_type, size_bytes, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#;
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    artifact.convoy_id,
+                    artifact.kind.as_str(),
+                    &artifact.digest,
+                    &artifact.file_name,
+                    &artifact.content_type,
+                    artifact.size_bytes,
+                    CqlTimestamp(artifact.created_at.timestamp_millis()),
+                ),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// List a convoy's mission artifacts.
+    pub async fn list_artifacts(&self, convoy_id: Uuid) -> Result<Vec<MissionArtifact>> {
+        let _timer = self.client.metrics.time("convoy.list_artifacts");
+        let query = r#"
+            SELECT kind, digest, file_name, content_type, size_bytes, created_at
+            FROM mission_artifacts
+            WHERE convoy_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (convoy_id,)).await?;
+        let mut artifacts = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(
+                String, String, Option<String>, Option<String>, Option<i64>, Option<CqlTimestamp>,
+            )>() {
+                for (kind, digest, file_name, content_type, size_bytes, created_at) in
+                    rows.flatten()
+                {
+                    let Some(kind) = ArtifactKind::parse(&kind) else {
+                        continue;
+                    };
+                    artifacts.push(MissionArtifact {
+                        convoy_id,
+                        kind,
+                        digest,
+                        file_name: file_name.unwrap_or_default(),
+                        content_type: content_type.unwrap_or_default(),
+                        size_bytes: size_bytes.unwrap_or_default(),
+                        created_at: created_at
+                            .and_then(|t| DateTime::from_timestamp_millis(t.0))
+                            .unwrap_or_default(),
+                    });
+                }
+            }
+        }
+
+        Ok(artifacts)
+    }
+
+    /// Content stored under a digest.
+    pub async fn get_artifact_content(&self, digest: &str) -> Result<Option<Vec<u8>>> {
+        let _timer = self.client.metrics.time("convoy.get_artifact_content");
+        let result = self.client
+            .query_unpaged("SELECT content FROM artifact_blobs WHERE digest = ?", (digest,))
+            .await?;
+
+        Ok(result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<(Vec<u8>,)>().ok().flatten())
+            .map(|(content,)| content))
+    }
+}
+
+// =============================================================================
+// WAYPOINT REPOSITORY  
+// =============================================================================
+
+/// Repository for waypoint operations.
+pub struct ScyllaWaypointRepository {
+    client: Arc<ScyllaClient>,
+}
+
+impl ScyllaWaypointRepository {
+    /// Create a new waypoint repository.
+    pub fn new(client: Arc<ScyllaClient>) -> Self {
+        Self { client }
+    }
+
+    /// Get a drone's waypoints in sequence order.
+    pub async fn get_waypoints(&self, drone_id: Uuid) -> Result<Vec<Waypoint>> {
+        let _timer = self.client.metrics.time("waypoint.get_waypoints");
+        let query = r#"
+            SELECT sequence_number, waypoint_id, waypoint_name, waypoint_type, coordinates,
+                   planned_arrival, actual_arrival, planned_departure, actual_departure,
+                   loiter_duration_min, authorized_actions, status
+            FROM waypoints WHERE drone_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (drone_id,)).await?;
+
+        let mut waypoints = Vec::new();
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<WaypointRow>() {
+                waypoints.extend(rows.flatten().map(|row| waypoint_from_row(drone_id, row)));
+            }
+        }
+
+        Ok(waypoints)
+    }
+
+    /// Write a route plan, replacing waypoints with the same sequence numbers.
+    pub async fn save_waypoints(&self, waypoints: &[Waypoint]) -> Result<()> {
+        let _timer = self.client.metrics.time("waypoint.save_waypoints");
+        let query = r#"
+            INSERT INTO waypoints (
+                drone_id, sequence_number, waypoint_id, waypoint_name, waypoint_type,
+                coordinates, planned_arrival, actual_arrival, planned_departure,
+                actual_departure, loiter_duration_min, authorized_actions, status
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        let timestamp = |t: Option<DateTime<Utc>>| t.map(|t| CqlTimestamp(t.timestamp_millis()));
+        for waypoint in waypoints {
+            self.client
+                .query_unpaged(
+                    query,
+                    (
+                        waypoint.drone_id,
+                        waypoint.sequence_number,
+                        waypoint.waypoint_id,
+                        &waypoint.waypoint_name,
+                        waypoint_type_str(waypoint.waypoint_type),
+                        CoordinatesUdt::from(&waypoint.coordinates),
+                        timestamp(waypoint.planned_arrival),
+                        timestamp(waypoint.actual_arrival),
+                        timestamp(waypoint.planned_departure),
+                        timestamp(waypoint.actual_departure),
+                        waypoint.loiter_duration_min,
+                        &waypoint.authorized_actions,
+                        waypoint_status_str(waypoint.status),
+                    ),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Assign a sensor mode for a waypoint, replacing any earlier task for
+    /// the same sensor.
+    pub async fn assign_sensor_task(&self, task: &SensorTask) -> Result<()> {
+        let _timer = self.client.metrics.time("waypoint.assign_sensor_task");
+        let query = r#"
+            INSERT INTO sensor_tasks (
+                drone_id, sequence_number, sensor_type, mode, assigned_by, assigned_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    task.drone_id,
+                    task.sequence_number,
+                    sensor_type_str(task.sensor_type),
+                    &task.mode,
+                    &task.assigned_by,
+                    CqlTimestamp(task.assigned_at.timestamp_millis()),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a drone's sensor tasks in waypoint order.
+    pub async fn get_sensor_tasks(&self, drone_id: Uuid) -> Result<Vec<SensorTask>> {
+        let _timer = self.client.metrics.time("waypoint.get_sensor_tasks");
+        let query = r#"
+            SELECT sequence_number, sensor_type, mode, assigned_by, assigned_at
+            FROM sensor_tasks
+            WHERE drone_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (drone_id,)).await?;
+        let mut tasks = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(
+                i16, String, Option<String>, Option<String>, Option<CqlTimestamp>
+            )>() {
+                for (sequence_number, sensor, mode, assigned_by, assigned_at) in rows.flatten() {
+                    tasks.push(SensorTask {
+                        drone_id,
+                        sequence_number,
+                        sensor_type: parse_sensor_type(&sensor),
+                        mode: mode.unwrap_or_default(),
+                        assigned_by: assigned_by.unwrap_or_default(),
+                        assigned_at: assigned_at
+                            .and_then(|t| DateTime::from_timestamp_millis(t.0))
+                            .unwrap_or_default(),
+                    });
+                }
+            }
+        }
+
+        Ok(tasks)
+    }
+}
+
+// =============================================================================
+// ALERT REPOSITORY
+// =============================================================================
+
+/// Repository for alert operations.
+pub struct ScyllaAlertRepository {
+    client: Arc<ScyllaClient>,
+}
+
+impl ScyllaAlertRepository {
+    /// Create a new alert repository.
+    pub fn new(client: Arc<ScyllaClient>) -> Self {
+        Self { client }
+    }
+
+    /// Record an alert.
+    pub async fn record(&self, alert: &Alert) -> Result<()> {
+        let _timer = self.client.metrics.time("alert.record");
+        let query = r#"
+            INSERT INTO alerts (
+                convoy_id, alert_time, alert_id, severity, alert_type,
+                source_drone_id, message, acknowledged, acknowledged_by,
+                acknowledged_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    alert.convoy_id,
+                    CqlTimestamp(alert.alert_time.timestamp_millis()),
+                    alert.alert_id,
+                    alert_severity_str(&alert.severity),
+                    &alert.alert_type,
+                    alert.source_drone_id,
+                    &alert.message,
+                    alert.acknowledged,
+                    alert.acknowledged_by.as_deref(),
+                    alert.acknowledged_at.map(|dt| CqlTimestamp(dt.timestamp_millis())),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get unacknowledged alerts for a convoy, newest first.
+    pub async fn get_open(&self, convoy_id: Uuid, limit: usize) -> Result<Vec<Alert>> {
+        let _timer = self.client.metrics.time("alert.get_open");
+        self.get_recent(convoy_id, limit, false).await
+    }
+
+    /// Get alerts for a convoy, newest first, optionally including ones
+    /// already acknowledged.
+    pub async fn get_recent(
+        &self,
+        convoy_id: Uuid,
+        limit: usize,
+        include_acknowledged: bool,
+    ) -> Result<Vec<Alert>> {
+        let _timer = self.client.metrics.time("alert.get_recent");
+        let query = r#"
+            SELECT convoy_id, alert_time, alert_id, severity, alert_type,
+                   source_drone_id, message, acknowledged, acknowledged_by,
+                   acknowledged_at, delivery_status, delivery_attempts,
+                   delivery_errors
+            FROM alerts
+            WHERE convoy_id = ?
+        "#;
+
+        let result = self.client
+            .query_unpaged(query, (convoy_id,))
+            .await?;
+
+        let mut alerts = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(
+                Uuid, CqlTimestamp, Uuid, Option<String>, Option<String>,
+                Option<Uuid>, Option<String>, Option<bool>, Option<String>,
+                Option<CqlTimestamp>, Option<HashMap<String, String>>,
+                Option<HashMap<String, i32>>, Option<HashMap<String, String>>
+            )>() {
+                for (
+                    cid, time, aid, severity, alert_type, source, message, acked, acked_by, acked_at,
+                    delivery_status, delivery_attempts, delivery_errors,
+                ) in rows.flatten() {
+                    let acknowledged = acked.unwrap_or(false);
+                    if acknowledged && !include_acknowledged {
+                        continue;
+                    }
+                    alerts.push(Alert {
+                        convoy_id: cid,
+                        alert_time: DateTime::from_timestamp_millis(time.0).unwrap_or_default(),
+                        alert_id: aid,
+                        severity: parse_alert_severity(severity.as_deref().unwrap_or_default()),
+                        alert_type: alert_type.unwrap_or_default(),
+                        source_drone_id: source,
+                        message: message.unwrap_or_default(),
+                        acknowledged,
+                        acknowledged_by: acked_by,
+                        acknowledged_at: acked_at.and_then(|t| DateTime::from_timestamp_millis(t.0)),
+                        deliveries: alert_deliveries(
+                            delivery_status.unwrap_or_default(),
+                            delivery_attempts.unwrap_or_default(),
+                            delivery_errors.unwrap_or_default(),
+                        ),
+                    });
+                    if alerts.len() >= limit {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(alerts)
+    }
+
+    /// Mark an alert acknowledged; `None` if the convoy has no such alert.
+    pub async fn acknowledge(
+        &self,
+        convoy_id: Uuid,
+        alert_id: Uuid,
+        acknowledged_by: &str,
+    ) -> Result<Option<Alert>> {
+        let _timer = self.client.metrics.time("alert.acknowledge");
+        // alert_time is part of the key; find it within the convoy partition
+        let Some(mut alert) = self
+            .get_recent(convoy_id, usize::MAX, true)
+            .await?
+            .into_iter()
+            .find(|a| a.alert_id == alert_id)
+        else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        let query = r#"
+            UPDATE alerts
+            SET acknowledged = true, acknowledged_by = ?, acknowledged_at = ?
+            WHERE convoy_id = ? AND alert_time = ? AND alert_id = ?
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    acknowledged_by,
+                    CqlTimestamp(now.timestamp_millis()),
+                    convoy_id,
+                    CqlTimestamp(alert.alert_time.timestamp_millis()),
+                    alert_id,
+                ),
+            )
+            .await?;
+
+        alert.acknowledged = true;
+        alert.acknowledged_by = Some(acknowledged_by.to_string());
+        alert.acknowledged_at = Some(now);
+        Ok(Some(alert))
+    }
+
+    /// Record the outcome of forwarding an alert to an external channel,
+    /// replacing any earlier outcome for the same channel.
+    pub async fn record_delivery(&self, alert: &Alert, delivery: &AlertDelivery) -> Result<()> {
+        let _timer = self.client.metrics.time("alert.record_delivery");
+        let query = r#"
+            UPDATE alerts
+            SET delivery_status[?] = ?, delivery_attempts[?] = ?, delivery_errors[?] = ?
+            WHERE convoy_id = ? AND alert_time = ? AND alert_id = ?
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    &delivery.channel,
+                    delivery.status.as_str(),
+                    &delivery.channel,
+                    delivery.attempts,
+                    &delivery.channel,
+                    delivery.last_error.as_deref().unwrap_or_default(),
+                    alert.convoy_id,
+                    CqlTimestamp(alert.alert_time.timestamp_millis()),
+                    alert.alert_id,
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Rebuild per-channel deliveries from the alert's delivery maps, ordered
+/// by channel name. An empty error string means the last attempt succeeded.
+fn alert_deliveries(
+    status: HashMap<String, String>,
+    mut attempts: HashMap<String, i32>,
+    mut errors: HashMap<String, String>,
+) -> Vec<AlertDelivery> {
+    let mut deliveries: Vec<AlertDelivery> = status
+        .into_iter()
+        .map(|(channel, status)| AlertDelivery {
+            status: parse_delivery_status(&status),
+            attempts: attempts.remove(&channel).unwrap_or_default(),
+            last_error: errors.remove(&channel).filter(|e| !e.is_empty()),
+            channel,
+        })
+        .collect();
+    deliveries.sort_by(|a, b| a.channel.cmp(&b.channel));
+    deliveries
+}
+
+// =============================================================================
+// ENGAGEMENT AUTHORIZATION REPOSITORY
+// =============================================================================
+
+/// Repository for pre-engagement authorization requests.
+pub struct ScyllaAuthorizationRepository {
+    client: Arc<ScyllaClient>,
+}
+
+impl ScyllaAuthorizationRepository {
+    /// Create a new authorization repository.
+    pub fn new(client: Arc<ScyllaClient>) -> Self {
+        Self { client }
+    }
+
+    /// Record a new authorization request.
+    pub async fn create(&self, auth: &EngagementAuthorization) -> Result<()> {
+        let _timer = self.client.metrics.time("authorization.create");
+        let query = r#"
+            INSERT INTO engagement_authorizations (
+                convoy_id, request_id, drone_id, weapon_type, target_type,
+                target_lat, target_lon, requested_by, justification, requested_at,
+                status, decided_by, decided_at, decision_notes, expires_at,
+                engagement_id
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    auth.convoy_id,
+                    auth.request_id,
+                    auth.drone_id,
+                    auth.weapon_type.as_str(),
+                    target_type_str(&auth.target_type),
+                    auth.target_coordinates.latitude,
+                    auth.target_coordinates.longitude,
+                    &auth.requested_by,
+                    &auth.justification,
+                    CqlTimestamp(auth.requested_at.timestamp_millis()),
+                    authorization_status_str(auth.status),
+                    auth.decided_by.as_deref(),
+                    auth.decided_at.map(|dt| CqlTimestamp(dt.timestamp_millis())),
+                    auth.decision_notes.as_deref(),
+                    auth.expires_at.map(|dt| CqlTimestamp(dt.timestamp_millis())),
+                    auth.engagement_id,
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get one authorization request.
+    pub async fn get(
+        &self,
+        convoy_id: Uuid,
+        request_id: Uuid,
+    ) -> Result<Option<EngagementAuthorization>> {
+        let _timer = self.client.metrics.time("authorization.get");
+        Ok(self
+            .select(convoy_id, Some(request_id))
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    /// Get a convoy's authorization requests, newest first, optionally
+    /// limited to one status.
+    pub async fn list(
+        &self,
+        convoy_id: Uuid,
+        status: Option<AuthorizationStatus>,
+        limit: usize,
+    ) -> Result<Vec<EngagementAuthorization>> {
+        let _timer = self.client.metrics.time("authorization.list");
+        let mut requests = self.select(convoy_id, None).await?;
+        requests.retain(|r| status.is_none_or(|s| r.status == s));
+        requests.sort_by(|a, b| b.requested_at.cmp(&a.requested_at));
+        requests.truncate(limit);
+        Ok(requests)
+    }
+
+    /// Approve or deny a pending request.
+    ///
+    /// Returns `None` if the convoy has no such request; fails with
+    /// `WriteConflict` if it was already decided.
+    pub async fn decide(
+        &self,
+        convoy_id: Uuid,
+        request_id: Uuid,
+        status: AuthorizationStatus,
+        decided_by: &str,
+        notes: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<EngagementAuthorization>> {
+        let _timer = self.client.metrics.time("authorization.decide");
+        let Some(mut auth) = self.get(convoy_id, request_id).await? else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        let query = r#"
+            UPDATE engagement_authorizations
+            SET status = ?, decided_by = ?, decided_at = ?, decision_notes = ?, expires_at = ?
+            WHERE convoy_id = ? AND request_id = ?
+            IF status = 'PENDING'
+        "#;
+
+        let result = self
+            .client
+            .query_unpaged_once(
+                query,
+                (
+                    authorization_status_str(status),
+                    decided_by,
+                    CqlTimestamp(now.timestamp_millis()),
+                    notes,
+                    expires_at.map(|dt| CqlTimestamp(dt.timestamp_millis())),
+                    convoy_id,
+                    request_id,
+                ),
+            )
+            .await?;
+
+        if !lwt_applied(result) {
+            return Err(PersistenceError::WriteConflict(format!(
+                "authorization {request_id} is no longer pending"
+            )));
+        }
+
+        auth.status = status;
+        auth.decided_by = Some(decided_by.to_string());
+        auth.decided_at = Some(now);
+        auth.decision_notes = notes.map(str::to_string);
+        auth.expires_at = expires_at;
+        Ok(Some(auth))
+    }
+
+    /// Consume an approval for an engagement.
+    ///
+    /// Returns `false` if the request is not approved, including when it was
+    /// already executed.
+    pub async fn mark_executed(
+        &self,
+        convoy_id: Uuid,
+        request_id: Uuid,
+        engagement_id: Uuid,
+    ) -> Result<bool> {
+        let _timer = self.client.metrics.time("authorization.mark_executed");
+        let query = r#"
+            UPDATE engagement_authorizations
+            SET status = 'EXECUTED', engagement_id = ?
+            WHERE convoy_id = ? AND request_id = ?
+            IF status = 'APPROVED'
+        "#;
+
+        let result = self
+            .client
+            .query_unpaged_once(query, (engagement_id, convoy_id, request_id))
+            .await?;
+
+        Ok(lwt_applied(result))
+    }
+
+    async fn select(
+        &self,
+        convoy_id: Uuid,
+        request_id: Option<Uuid>,
+    ) -> Result<Vec<EngagementAuthorization>> {
+        let columns = r#"
+            SELECT request_id, drone_id, weapon_type, target_type, target_lat,
+                   target_lon, requested_by, justification, requested_at, status,
+                   decided_by, decided_at, decision_notes, expires_at, engagement_id
+            FROM engagement_authorizations
+            WHERE convoy_id = ?
+        "#;
+
+        let result = match request_id {
+            Some(request_id) => {
+                let query = format!("{columns} AND request_id = ?");
+                self.client.query_unpaged(query, (convoy_id, request_id)).await?
+            }
+            None => self.client.query_unpaged(columns, (convoy_id,)).await?,
+        };
+
+        let mut requests = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(
+                Uuid, Option<Uuid>, Option<String>, Option<String>, Option<f64>,
+                Option<f64>, Option<String>, Option<String>, Option<CqlTimestamp>,
+                Option<String>, Option<String>, Option<CqlTimestamp>, Option<String>,
+                Option<CqlTimestamp>, Option<Uuid>
+            )>() {
+                for (
+                    rid, drone_id, weapon, target, lat, lon, requested_by, justification,
+                    requested_at, status, decided_by, decided_at, notes, expires_at, engagement_id,
+                ) in rows.flatten()
+                {
+                    requests.push(EngagementAuthorization {
+                        convoy_id,
+                        request_id: rid,
+                        drone_id: drone_id.unwrap_or_default(),
+                        weapon_type: parse_weapon_type(weapon.as_deref().unwrap_or_default()),
+                        target_type: parse_target_type(target.as_deref().unwrap_or_default()),
+                        target_coordinates: Coordinates::new(
+                            lat.unwrap_or_default(),
+                            lon.unwrap_or_default(),
+                            0.0,
+                        ),
+                        requested_by: requested_by.unwrap_or_default(),
+                        justification: justification.unwrap_or_default(),
+                        requested_at: requested_at
+                            .and_then(|t| DateTime::from_timestamp_millis(t.0))
+                            .unwrap_or_default(),
+                        status: parse_authorization_status(status.as_deref().unwrap_or_default()),
+                        decided_by,
+                        decided_at: decided_at.and_then(|t| DateTime::from_timestamp_millis(t.0)),
+                        decision_notes: notes,
+                        expires_at: expires_at.and_then(|t| DateTime::from_timestamp_millis(t.0)),
+                        engagement_id,
+                    });
+                }
+            }
+        }
+
+        Ok(requests)
+    }
+}
+
+// =============================================================================
+// WEAPONS INVENTORY REPOSITORY
+// =============================================================================
+
+/// Repository for per-drone munitions.
+pub struct ScyllaWeaponsRepository {
+    client: Arc<ScyllaClient>,
+}
+
+impl ScyllaWeaponsRepository {
+    /// Create a new weapons repository.
+    pub fn new(client: Arc<ScyllaClient>) -> Self {
+        Self { client }
+    }
+
+    /// Get every weapon tracked for a drone.
+    pub async fn get_loadout(&self, drone_id: Uuid) -> Result<Vec<WeaponStatus>> {
+        let _timer = self.client.metrics.time("weapons.get_loadout");
+        let query = r#"
+            SELECT weapon_type, rounds_remaining, status
+            FROM weapons_inventory
+            WHERE drone_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (drone_id,)).await?;
+        let mut weapons = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(String, Option<i16>, Option<String>)>() {
+                for (weapon, rounds, status) in rows.flatten() {
+                    weapons.push(WeaponStatus {
+                        weapon_type: parse_weapon_type(&weapon),
+                        rounds_remaining: rounds.unwrap_or_default(),
+                        status: parse_weapon_state(status.as_deref().unwrap_or_default()),
+                    });
+                }
+            }
+        }
+
+        Ok(weapons)
+    }
+
+    /// Get one weapon's status; `None` if the drone does not track it.
+    pub async fn get(
+        &self,
+        drone_id: Uuid,
+        weapon_type: WeaponType,
+    ) -> Result<Option<WeaponStatus>> {
+        let _timer = self.client.metrics.time("weapons.get");
+        Ok(self
+            .get_loadout(drone_id)
+            .await?
+            .into_iter()
+            .find(|w| w.weapon_type == weapon_type))
+    }
+
+    /// Load or replace weapons on a drone. Weapons not listed are left as-is.
+    pub async fn set_loadout(&self, drone_id: Uuid, weapons: &[WeaponStatus]) -> Result<()> {
+        let _timer = self.client.metrics.time("weapons.set_loadout");
+        let query = r#"
+            INSERT INTO weapons_inventory (
+                drone_id, weapon_type, rounds_remaining, status, updated_at
+            ) VALUES (?, ?, ?, ?, ?)
+        "#;
+
+        let now = CqlTimestamp(Utc::now().timestamp_millis());
+        for weapon in weapons {
+            self.client
+                .query_unpaged(
+                    query,
+                    (
+                        drone_id,
+                        weapon.weapon_type.as_str(),
+                        weapon.rounds_remaining,
+                        weapon_state_str(weapon.status),
+                        now,
+                    ),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Write `updated` only if the stored weapon still matches `previous`.
+    ///
+    /// Returns `false` when another engagement changed it first.
+    pub async fn compare_and_set(
+        &self,
+        drone_id: Uuid,
+        previous: &WeaponStatus,
+        updated: &WeaponStatus,
+    ) -> Result<bool> {
+        let _timer = self.client.metrics.time("weapons.compare_and_set");
+        let query = r#"
+            UPDATE weapons_inventory
+            SET rounds_remaining = ?, status = ?, updated_at = ?
+            WHERE drone_id = ? AND weapon_type = ?
+            IF rounds_remaining = ? AND status = ?
+        "#;
+
+        let result = self
+            .client
+            .query_unpaged_once(
+                query,
+                (
+                    updated.rounds_remaining,
+                    weapon_state_str(updated.status),
+                    CqlTimestamp(Utc::now().timestamp_millis()),
+                    drone_id,
+                    previous.weapon_type.as_str(),
+                    previous.rounds_remaining,
+                    weapon_state_str(previous.status),
+                ),
+            )
+            .await?;
+
+        Ok(lwt_applied(result))
+    }
+}
+
+// =============================================================================
+// TARGET REPOSITORY
+// =============================================================================
+
+/// Repository for tracked targets.
+pub struct ScyllaTargetRepository {
+    client: Arc<ScyllaClient>,
+}
+
+impl ScyllaTargetRepository {
+    /// Create a new target repository.
+    pub fn new(client: Arc<ScyllaClient>) -> Self {
+        Self { client }
+    }
+
+    /// Create or update a target from a detection report.
+    ///
+    /// Linked engagements are left untouched.
+    pub async fn upsert(&self, target: &Target) -> Result<()> {
+        let _timer = self.client.metrics.time("target.upsert");
+        let query = r#"
+            INSERT INTO targets (
+                convoy_id, target_id, target_type, target_lat, target_lon, target_alt_m,
+                confidence, threat_level, status, reported_by, first_detected_at,
+                last_updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    target.convoy_id,
+                    target.target_id,
+                    target_type_str(&target.target_type),
+                    target.coordinates.latitude,
+                    target.coordinates.longitude,
+                    target.coordinates.altitude_m,
+                    target.confidence,
+                    threat_level_str(target.threat_level),
+                    target_status_str(target.status),
+                    &target.reported_by,
+                    CqlTimestamp(target.first_detected_at.timestamp_millis()),
+                    CqlTimestamp(target.last_updated_at.timestamp_millis()),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a target by ID.
+    pub async fn get(&self, convoy_id: Uuid, target_id: Uuid) -> Result<Option<Target>> {
+        let _timer = self.client.metrics.time("target.get");
+        Ok(self
+            .list(convoy_id, None)
+            .await?
+            .into_iter()
+            .find(|t| t.target_id == target_id))
+    }
+
+    /// List a convoy's targets, most recently updated first.
+    pub async fn list(
+        &self,
+        convoy_id: Uuid,
+        status: Option<TargetStatus>,
+    ) -> Result<Vec<Target>> {
+        let _timer = self.client.metrics.time("target.list");
+        let query = r#"
+            SELECT target_id, target_type, target_lat, target_lon, target_alt_m,
+                   confidence, threat_level, status, reported_by, first_detected_at,
+                   last_updated_at, engagement_ids
+            FROM targets
+            WHERE convoy_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (convoy_id,)).await?;
+        let mut targets = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(
+                Uuid, Option<String>, Option<f64>, Option<f64>, Option<f64>,
+                Option<f32>, Option<String>, Option<String>, Option<String>,
+                Option<CqlTimestamp>, Option<CqlTimestamp>, Option<Vec<Uuid>>
+            )>() {
+                for (
+                    tid, target_type, lat, lon, alt, confidence, threat, state, reported_by,
+                    first_detected, last_updated, engagement_ids,
+                ) in rows.flatten()
+                {
+                    let first_detected_at = first_detected
+                        .and_then(|t| DateTime::from_timestamp_millis(t.0))
+                        .unwrap_or_default();
+                    targets.push(Target {
+                        convoy_id,
+                        target_id: tid,
+                        target_type: parse_target_type(target_type.as_deref().unwrap_or_default()),
+                        coordinates: Coordinates::new(
+                            lat.unwrap_or_default(),
+                            lon.unwrap_or_default(),
+                            alt.unwrap_or_default(),
+                        ),
+                        confidence: confidence.unwrap_or_default(),
+                        threat_level: parse_threat_level(threat.as_deref().unwrap_or_default()),
+                        status: parse_target_status(state.as_deref().unwrap_or_default()),
+                        reported_by: reported_by.unwrap_or_default(),
+                        first_detected_at,
+                        last_updated_at: last_updated
+                            .and_then(|t| DateTime::from_timestamp_millis(t.0))
+                            .unwrap_or(first_detected_at),
+                        engagement_ids: engagement_ids.unwrap_or_default(),
+                    });
+                }
+            }
+        }
+
+        if let Some(status) = status {
+            targets.retain(|t| t.status == status);
+        }
+        targets.sort_by(|a, b| b.last_updated_at.cmp(&a.last_updated_at));
+
+        Ok(targets)
+    }
+
+    /// Link an engagement to a target and move it to `status`.
+    pub async fn record_engagement(
+        &self,
+        convoy_id: Uuid,
+        target_id: Uuid,
+        engagement_id: Uuid,
+        status: TargetStatus,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("target.record_engagement");
+        let query = r#"
+            UPDATE targets
+            SET engagement_ids = engagement_ids + ?, status = ?, last_updated_at = ?
+            WHERE convoy_id = ? AND target_id = ?
+        "#;
+
+        self.client
+            .query_unpaged_once(
+                query,
+                (
+                    vec![engagement_id],
+                    target_status_str(status),
+                    CqlTimestamp(Utc::now().timestamp_millis()),
+                    convoy_id,
+                    target_id,
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Move a target to a new lifecycle status.
+    pub async fn set_status(
+        &self,
+        convoy_id: Uuid,
+        target_id: Uuid,
+        status: TargetStatus,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("target.set_status");
+        let query = r#"
+            UPDATE targets SET status = ?, last_updated_at = ?
+            WHERE convoy_id = ? AND target_id = ?
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    target_status_str(status),
+                    CqlTimestamp(Utc::now().timestamp_millis()),
+                    convoy_id,
+                    target_id,
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record a hand-off of a target between drones.
+    pub async fn record_handoff(&self, handoff: &TargetHandoff) -> Result<()> {
+        let _timer = self.client.metrics.time("target.record_handoff");
+        let query = r#"
+            INSERT INTO target_handoffs (
+                convoy_id, handed_off_at, handoff_id, target_id, from_drone_id,
+                to_drone_id, reason, handed_off_by
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    handoff.convoy_id,
+                    CqlTimestamp(handoff.handed_off_at.timestamp_millis()),
+                    handoff.handoff_id,
+                    handoff.target_id,
+                    handoff.from_drone_id,
+                    handoff.to_drone_id,
+                    &handoff.reason,
+                    &handoff.handed_off_by,
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// List a convoy's target hand-offs, oldest first, optionally for one target.
+    pub async fn list_handoffs(
+        &self,
+        convoy_id: Uuid,
+        target_id: Option<Uuid>,
+    ) -> Result<Vec<TargetHandoff>> {
+        let _timer = self.client.metrics.time("target.list_handoffs");
+        let query = r#"
+            SELECT handed_off_at, handoff_id, target_id, from_drone_id, to_drone_id,
+                   reason, handed_off_by
+            FROM target_handoffs
+            WHERE convoy_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (convoy_id,)).await?;
+        let mut handoffs = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(
+                CqlTimestamp, Uuid, Uuid, Option<Uuid>, Option<Uuid>, Option<String>,
+                Option<String>,
+            )>() {
+                for (time, handoff_id, tid, from, to, reason, handed_off_by) in rows.flatten() {
+                    handoffs.push(TargetHandoff {
+                        convoy_id,
+                        target_id: tid,
+                        handed_off_at: DateTime::from_timestamp_millis(time.0).unwrap_or_default(),
+                        handoff_id,
+                        from_drone_id: from.unwrap_or_default(),
+                        to_drone_id: to.unwrap_or_default(),
+                        reason,
+                        handed_off_by: handed_off_by.unwrap_or_default(),
+                    });
+                }
+            }
+        }
+
+        if let Some(target_id) = target_id {
+            handoffs.retain(|h| h.target_id == target_id);
+        }
+        // Partition clusters oldest first
+        Ok(handoffs)
+    }
+}
+
+// =============================================================================
+// DRONE REPOSITORY
+// =============================================================================
+
+/// A drone's current status with the callsign used in status events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DroneStatusInfo {
+    pub callsign: String,
+    pub status: DroneStatus,
+}
+
+/// Repository for drone platform state.
+pub struct ScyllaDroneRepository {
+    client: Arc<ScyllaClient>,
+}
+
+impl ScyllaDroneRepository {
+    /// Create a new drone repository.
+    pub fn new(client: Arc<ScyllaClient>) -> Self {
+        Self { client }
+    }
+
+    /// Write a newly registered drone and add it to its convoy's roster.
+    ///
+    /// Callsign and tail number must already be claimed with
+    /// [`ScyllaDroneRepository::claim_callsign`] and
+    /// [`ScyllaDroneRepository::claim_tail_number`].
+    pub async fn create(&self, drone: &Drone) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.create");
+        let query = r#"
+            INSERT INTO drones (
+                convoy_id, drone_id, tail_number, callsign, platform_type, serial_number,
+                status, fuel_remaining_pct, flight_time_hrs, total_engagements,
+                successful_hits, accuracy_pct, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    drone.convoy_id,
+                    drone.drone_id,
+                    &drone.tail_number,
+                    &drone.callsign,
+                    drone.platform_type.as_str(),
+                    &drone.serial_number,
+                    drone.status.as_str(),
+                    drone.fuel_remaining_pct,
+                    drone.flight_time_hrs,
+                    drone.total_engagements,
+                    drone.successful_hits,
+                    drone.accuracy_pct,
+                    CqlTimestamp(drone.created_at.timestamp_millis()),
+                    CqlTimestamp(drone.updated_at.timestamp_millis()),
+                ),
+            )
+            .await?;
+
+        self.client
+            .query_unpaged(
+                "UPDATE convoys SET drone_ids = drone_ids + ? WHERE convoy_id = ?",
+                (vec![drone.drone_id], drone.convoy_id),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a registered drone's identity, status and counters.
+    ///
+    /// Position, loadout and links are not read; callers overlay live
+    /// telemetry and the weapons inventory.
+    pub async fn get(&self, convoy_id: Uuid, drone_id: Uuid) -> Result<Option<Drone>> {
+        let _timer = self.client.metrics.time("drone.get");
+        let query = format!(
+            "SELECT {DRONE_COLUMNS} FROM drones WHERE convoy_id = ? AND drone_id = ?"
+        );
+
+        let result = self.client.query_unpaged(query, (convoy_id, drone_id)).await?;
+        Ok(result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<DroneRow>().ok().flatten())
+            .map(|row| drone_from_row(convoy_id, row)))
+    }
+
+    /// Get several of a convoy's drones with one query on its partition.
+    ///
+    /// Drones that do not exist are left out. Columns are read as for
+    /// [`ScyllaDroneRepository::get`].
+    pub async fn get_many(&self, convoy_id: Uuid, drone_ids: &[Uuid]) -> Result<Vec<Drone>> {
+        let _timer = self.client.metrics.time("drone.get_many");
+        if drone_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = format!(
+            "SELECT {DRONE_COLUMNS} FROM drones WHERE convoy_id = ? AND drone_id IN ?"
+        );
+
+        let result = self.client.query_unpaged(query, (convoy_id, drone_ids.to_vec())).await?;
+        let mut drones = Vec::with_capacity(drone_ids.len());
+        if let Ok(rows) = result.into_rows_result()
+            && let Ok(rows) = rows.rows::<DroneRow>()
+        {
+            drones.extend(rows.flatten().map(|row| drone_from_row(convoy_id, row)));
+        }
+        Ok(drones)
+    }
+
+    /// Count a convoy's registered drones.
+    pub async fn count(&self, convoy_id: Uuid) -> Result<i64> {
+        let _timer = self.client.metrics.time("drone.count");
+        let query = "SELECT COUNT(*) FROM drones WHERE convoy_id = ?";
+
+        let result = self.client
+            .query_unpaged(query, (convoy_id,))
+            .await?;
+
+        Ok(result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<(i64,)>().ok().flatten())
+            .map_or(0, |(count,)| count))
+    }
+
+    /// Read a page of a convoy's drones in drone ID order.
+    ///
+    /// Columns are read as for [`ScyllaDroneRepository::get`].
+    pub async fn list_page(
+        &self,
+        convoy_id: Uuid,
+        page_size: usize,
+        paging_state: Option<&[u8]>,
+    ) -> Result<Page<Drone>> {
+        let _timer = self.client.metrics.time("drone.list_page");
+        let query = format!("SELECT {DRONE_COLUMNS} FROM drones WHERE convoy_id = ?");
+
+        let (result, paging_state) = self.client
+            .query_page(query, (convoy_id,), page_size, paging_state)
+            .await?;
+
+        let mut items = Vec::new();
+        if let Ok(rows) = result.into_rows_result()
+            && let Ok(rows) = rows.rows::<DroneRow>()
+        {
+            items.extend(rows.flatten().map(|row| drone_from_row(convoy_id, row)));
+        }
+        Ok(Page { items, paging_state })
+    }
+
+    /// Reserve a callsign in a convoy for `drone_id`.
+    ///
+    /// Returns the ID of the drone already holding it, or `None` once the
+    /// callsign is reserved.
+    pub async fn claim_callsign(
+        &self,
+        convoy_id: Uuid,
+        callsign: &str,
+        drone_id: Uuid,
+    ) -> Result<Option<Uuid>> {
+        let _timer = self.client.metrics.time("drone.claim_callsign");
+        let query = r#"
+            INSERT INTO drone_callsigns (convoy_id, callsign, drone_id, claimed_at)
+            VALUES (?, ?, ?, ?)
+            IF NOT EXISTS
+        "#;
+
+        let result = self
+            .client
+            .query_unpaged_once(
+                query,
+                (
+                    convoy_id,
+                    callsign,
+                    drone_id,
+                    CqlTimestamp(Utc::now().timestamp_millis()),
+                ),
+            )
+            .await?;
+
+        if lwt_applied(result) {
+            return Ok(None);
+        }
+        self.find_by_callsign(convoy_id, callsign)
+            .await?
+            .map(Some)
+            .ok_or_else(|| {
+                PersistenceError::WriteConflict(format!("callsign {callsign} is being reassigned"))
+            })
+    }
+
+    /// Reserve a tail number for `drone_id` across all convoys.
+    ///
+    /// Returns the ID of the drone already holding it, or `None` once the
+    /// tail number is reserved.
+    pub async fn claim_tail_number(
+        &self,
+        tail_number: &str,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+    ) -> Result<Option<Uuid>> {
+        let _timer = self.client.metrics.time("drone.claim_tail_number");
+        let query = r#"
+            INSERT INTO drone_tail_numbers (tail_number, drone_id, convoy_id, claimed_at)
+            VALUES (?, ?, ?, ?)
+            IF NOT EXISTS
+        "#;
+
+        let result = self
+            .client
+            .query_unpaged_once(
+                query,
+                (
+                    tail_number,
+                    drone_id,
+                    convoy_id,
+                    CqlTimestamp(Utc::now().timestamp_millis()),
+                ),
+            )
+            .await?;
+
+        if lwt_applied(result) {
+            return Ok(None);
+        }
+        let result = self
+            .client
+            .query_unpaged(
+                "SELECT drone_id FROM drone_tail_numbers WHERE tail_number = ?",
+                (tail_number,),
+            )
+            .await?;
+        result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<(Uuid,)>().ok().flatten())
+            .map(|(holder,)| Some(holder))
+            .ok_or_else(|| {
+                PersistenceError::WriteConflict(format!(
+                    "tail number {tail_number} is being reassigned"
+                ))
+            })
+    }
+
+    /// Release a callsign reserved by `drone_id`; other holders are untouched.
+    pub async fn release_callsign(
+        &self,
+        convoy_id: Uuid,
+        callsign: &str,
+        drone_id: Uuid,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.release_callsign");
+        self.client
+            .query_unpaged_once(
+                "DELETE FROM drone_callsigns WHERE convoy_id = ? AND callsign = ? IF drone_id = ?",
+                (convoy_id, callsign, drone_id),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Release a tail number reserved by `drone_id`; other holders are untouched.
+    pub async fn release_tail_number(&self, tail_number: &str, drone_id: Uuid) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.release_tail_number");
+        self.client
+            .query_unpaged_once(
+                "DELETE FROM drone_tail_numbers WHERE tail_number = ? IF drone_id = ?",
+                (tail_number, drone_id),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Drone holding a callsign in a convoy, if any.
+    pub async fn find_by_callsign(&self, convoy_id: Uuid, callsign: &str) -> Result<Option<Uuid>> {
+        let _timer = self.client.metrics.time("drone.find_by_callsign");
+        let result = self
+            .client
+            .query_unpaged(
+                "SELECT drone_id FROM drone_callsigns WHERE convoy_id = ? AND callsign = ?",
+                (convoy_id, callsign),
+            )
+            .await?;
+
+        Ok(result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| rows.maybe_first_row::<(Uuid,)>().ok().flatten())
+            .map(|(drone_id,)| drone_id))
+    }
+
+    /// Get a drone's current status; `None` if the drone is not registered.
+    pub async fn get_status(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+    ) -> Result<Option<DroneStatusInfo>> {
+        let _timer = self.client.metrics.time("drone.get_status");
+        let query = r#"
+            SELECT callsign, status FROM drones
+            WHERE convoy_id = ? AND drone_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (convoy_id, drone_id)).await?;
+        let row = result
+            .into_rows_result()
+            .ok()
+            .and_then(|rows| {
+                rows.maybe_first_row::<(Option<String>, Option<String>)>()
+                    .ok()
+                    .flatten()
+            });
+
+        Ok(row.map(|(callsign, status)| DroneStatusInfo {
+            callsign: callsign.unwrap_or_default(),
+            status: parse_drone_status(status.as_deref().unwrap_or_default()),
+        }))
+    }
+
+    /// Store a drone's cumulative flight hours.
+    ///
+    /// Only call for registered drones; the update would otherwise create a
+    /// partial row.
+    pub async fn set_flight_time(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        flight_time_hrs: f32,
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.set_flight_time");
+        let query = r#"
+            UPDATE drones SET flight_time_hrs = ?, updated_at = ?
+            WHERE convoy_id = ? AND drone_id = ?
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (flight_time_hrs, CqlTimestamp(at.timestamp_millis()), convoy_id, drone_id),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Apply a status change only if the drone is still in its old status.
+    ///
+    /// Returns `false` when another update changed the status first.
+    pub async fn compare_and_set_status(&self, change: &DroneStatusChange) -> Result<bool> {
+        let _timer = self.client.metrics.time("drone.compare_and_set_status");
+        let query = r#"
+            UPDATE drones SET status = ?, updated_at = ?
+            WHERE convoy_id = ? AND drone_id = ?
+            IF status = ?
+        "#;
+
+        let result = self
+            .client
+            .query_unpaged_once(
+                query,
+                (
+                    change.new_status.as_str(),
+                    CqlTimestamp(change.changed_at.timestamp_millis()),
+                    change.convoy_id,
+                    change.drone_id,
+                    change.old_status.as_str(),
+                ),
+            )
+            .await?;
+
+        Ok(lwt_applied(result))
+    }
+
+    /// Append an applied status change to the drone's history.
+    pub async fn record_status_change(&self, change: &DroneStatusChange) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.record_status_change");
+        let query = r#"
+            INSERT INTO drone_status_history (
+                drone_id, changed_at, convoy_id, old_status, new_status, changed_by
+            ) VALUES (?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    change.drone_id,
+                    CqlTimestamp(change.changed_at.timestamp_millis()),
+                    change.convoy_id,
+                    change.old_status.as_str(),
+                    change.new_status.as_str(),
+                    change.changed_by.as_deref(),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a drone's most recent status changes, newest first.
+    pub async fn get_status_history(
+        &self,
+        drone_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<DroneStatusChange>> {
+        let _timer = self.client.metrics.time("drone.get_status_history");
+        let query = r#"
+            SELECT changed_at, convoy_id, old_status, new_status, changed_by
+            FROM drone_status_history
+            WHERE drone_id = ?
+            LIMIT ?
+        "#;
+
+        let limit = i32::try_from(limit).unwrap_or(i32::MAX);
+        let result = self.client.query_unpaged(query, (drone_id, limit)).await?;
+        let mut changes = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<(
+                CqlTimestamp,
+                Uuid,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+            )>() {
+                for (changed_at, convoy_id, old, new, changed_by) in rows.flatten() {
+                    changes.push(DroneStatusChange {
+                        convoy_id,
+                        drone_id,
+                        old_status: parse_drone_status(old.as_deref().unwrap_or_default()),
+                        new_status: parse_drone_status(new.as_deref().unwrap_or_default()),
+                        changed_at: DateTime::from_timestamp_millis(changed_at.0)
+                            .unwrap_or_default(),
+                        changed_by,
+                    });
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+// =============================================================================
+// API KEY REPOSITORY
+// =============================================================================
+
+/// Repository for machine-client API keys.
+pub struct ScyllaApiKeyRepository {
+    client: Arc<ScyllaClient>,
+}
+
+/// Row tuple shared by the API key reads
+type ApiKeyRow = (
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<Vec<u8>>,
+    Option<CqlTimestamp>,
+    Option<CqlTimestamp>,
+    Option<CqlTimestamp>,
+);
+
+impl ScyllaApiKeyRepository {
+    /// Create a new API key repository.
+    pub fn new(client: Arc<ScyllaClient>) -> Self {
+        Self { client }
+    }
+
+    /// Store a newly issued key.
+    pub async fn create(&self, key: &ApiKey) -> Result<()> {
+        let _timer = self.client.metrics.time("api_key.create");
+        let query = r#"
+            INSERT INTO api_keys (
+                key_id, name, scope, commanding_unit, environment,
+                secret_hash, created_at, rotated_at, revoked_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    &key.key_id,
+                    &key.name,
+                    key.scope.as_str(),
+                    &key.commanding_unit,
+                    key.environment.as_str(),
+                    &key.secret_hash,
+                    CqlTimestamp(key.created_at.timestamp_millis()),
+                    key.rotated_at.map(|dt| CqlTimestamp(dt.timestamp_millis())),
+                    key.revoked_at.map(|dt| CqlTimestamp(dt.timestamp_millis())),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a key by ID, revoked or not.
+    pub async fn get(&self, key_id: &str) -> Result<Option<ApiKey>> {
+        let _timer = self.client.metrics.time("api_key.get");
+        let query = r#"
+            SELECT key_id, name, scope, commanding_unit, environment,
+                   secret_hash, created_at, rotated_at, revoked_at
+            FROM api_keys
+            WHERE key_id = ?
+        "#;
+
+        let result = self.client.query_unpaged(query, (key_id,)).await?;
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(Some(row)) = rows_result.maybe_first_row::<ApiKeyRow>() {
+                return Ok(Some(api_key_from_row(row)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// List every key, ordered by creation time.
+    pub async fn list(&self) -> Result<Vec<ApiKey>> {
+        let _timer = self.client.metrics.time("api_key.list");
+        let query = r#"
+            SELECT key_id, name, scope, commanding_unit, environment,
+                   secret_hash, created_at, rotated_at, revoked_at
+            FROM api_keys
+        "#;
+
+        let result = self.client.query_unpaged(query, ()).await?;
+        let mut keys = Vec::new();
+
+        if let Ok(rows_result) = result.into_rows_result() {
+            if let Ok(rows) = rows_result.rows::<ApiKeyRow>() {
+                keys.extend(rows.flatten().map(api_key_from_row));
+            }
+        }
+
+        keys.sort_by_key(|k| k.created_at);
+        Ok(keys)
+    }
+
+    /// Replace a key's secret hash.
+    pub async fn rotate(&self, key_id: &str, secret_hash: &[u8], at: DateTime<Utc>) -> Result<()> {
+        let _timer = self.client.metrics.time("api_key.rotate");
+        let query = r#"
+            UPDATE api_keys SET secret_hash = ?, rotated_at = ?
+            WHERE key_id = ?
+        "#;
+
+        self.client
+            .query_unpaged(query, (secret_hash, CqlTimestamp(at.timestamp_millis()), key_id))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Mark a key revoked.
+    pub async fn revoke(&self, key_id: &str, at: DateTime<Utc>) -> Result<()> {
+        let _timer = self.client.metrics.time("api_key.revoke");
+        let query = r#"
+            UPDATE api_keys SET revoked_at = ?
+            WHERE key_id = ?
+        "#;
+
+        self.client
+            .query_unpaged(query, (CqlTimestamp(at.timestamp_millis()), key_id))
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn api_key_from_row(row: ApiKeyRow) -> ApiKey {
+    let (
+        key_id,
+        name,
+        scope,
+        commanding_unit,
+        environment,
+        secret_hash,
+        created_at,
+        rotated_at,
+        revoked_at,
+    ) = row;
+    let timestamp = |t: CqlTimestamp| DateTime::from_timestamp_millis(t.0);
+    ApiKey {
+        key_id,
+        name: name.unwrap_or_default(),
+        scope: parse_api_key_scope(scope.as_deref().unwrap_or_default()),
+        commanding_unit,
+        // Keys issued before environments were recorded are live keys
+        environment: environment.and_then(|e| e.parse().ok()).unwrap_or_default(),
+        secret_hash: secret_hash.unwrap_or_default(),
+        created_at: created_at.and_then(timestamp).unwrap_or_default(),
+        rotated_at: rotated_at.and_then(timestamp),
+        revoked_at: revoked_at.and_then(timestamp),
+    }
+}
+
+/// Value of the `coordinates` UDT.
+#[derive(scylla::SerializeValue, scylla::DeserializeValue)]
+struct CoordinatesUdt {
+    latitude: f64,
+    longitude: f64,
+    altitude_m: f64,
+    heading_deg: f32,
+    speed_mps: f32,
+}
+
+impl From<&Coordinates> for CoordinatesUdt {
+    fn from(c: &Coordinates) -> Self {
+        Self {
+            latitude: c.latitude,
+            longitude: c.longitude,
+            altitude_m: c.altitude_m,
+            heading_deg: c.heading_deg,
+            speed_mps: c.speed_mps,
+        }
+    }
+}
+
+impl From<CoordinatesUdt> for Coordinates {
+    fn from(c: CoordinatesUdt) -> Self {
+        Self {
+            latitude: c.latitude,
+            longitude: c.longitude,
+            altitude_m: c.altitude_m,
+            heading_deg: c.heading_deg,
+            speed_mps: c.speed_mps,
+        }
+    }
+}
+
+type ConvoyRow = (
+    Option<String>,
+    Option<Uuid>,
+    Option<String>,
+    Option<String>,
+    Option<CqlTimestamp>,
+    Option<CqlTimestamp>,
+    Option<CqlTimestamp>,
+    Option<String>,
+    Option<CoordinatesUdt>,
+    Option<f32>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<Vec<Uuid>>,
+    Option<i16>,
+    Option<String>,
+);
+
+fn owner_of(convoy: &Convoy) -> ConvoyOwner {
+    ConvoyOwner {
+        commanding_unit: convoy.commanding_unit.clone(),
+        environment: convoy.environment,
+    }
+}
+
+fn convoy_from_row(
+    convoy_id: Uuid,
+    (
+        convoy_callsign,
+        mission_id,
+        mission_type,
+        status,
+        created_at,
+        mission_start,
+        mission_end,
+        aor_name,
+        aor_center,
+        aor_radius_km,
+        commanding_unit,
+        authorization_level,
+        roe_profile,
+        drone_ids,
+        drone_count,
+        environment,
+    ): ConvoyRow,
+) -> Convoy {
+    let timestamp = |t: Option<CqlTimestamp>| t.and_then(|t| DateTime::from_timestamp_millis(t.0));
+    let drone_ids = drone_ids.unwrap_or_default();
+    Convoy {
+        convoy_id,
+        convoy_callsign: convoy_callsign.unwrap_or_default(),
+        mission_id: mission_id.unwrap_or_default(),
+        mission_type: parse_mission_type(mission_type.as_deref().unwrap_or_default()),
+        status: parse_convoy_status(status.as_deref().unwrap_or_default()),
+        created_at: timestamp(created_at).unwrap_or_default(),
+        mission_start: timestamp(mission_start),
+        mission_end: timestamp(mission_end),
+        aor_name: aor_name.unwrap_or_default(),
+        aor_center: aor_center.map_or_else(|| Coordinates::new(0.0, 0.0, 0.0), Into::into),
+        aor_radius_km: aor_radius_km.unwrap_or_default(),
+        commanding_unit: commanding_unit.unwrap_or_default(),
+        authorization_level: authorization_level.unwrap_or_default(),
+        roe_profile: roe_profile.unwrap_or_default(),
+        environment: environment.and_then(|e| e.parse().ok()).unwrap_or_default(),
+        drone_count: drone_count
+            .unwrap_or_else(|| i16::try_from(drone_ids.len()).unwrap_or(i16::MAX)),
+        drone_ids,
+    }
+}
+
+/// Columns read into a [`DroneRow`]
+const DRONE_COLUMNS: &str = "drone_id, tail_number, callsign, platform_type, serial_number, \
+    status, fuel_remaining_pct, flight_time_hrs, total_engagements, successful_hits, \
+    accuracy_pct, created_at, updated_at";
+
+type DroneRow = (
+    Uuid,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<f32>,
+    Option<f32>,
+    Option<i32>,
+    Option<i32>,
+    Option<f32>,
+    Option<CqlTimestamp>,
+    Option<CqlTimestamp>,
+);
+
+fn drone_from_row(
+    convoy_id: Uuid,
+    (
+        drone_id,
+        tail_number,
+        callsign,
+        platform,
+        serial_number,
+        status,
+        fuel,
+        flight_time,
+        engagements,
+        hits,
+        accuracy,
+        created_at,
+        updated_at,
+    ): DroneRow,
+) -> Drone {
+    let timestamp = |ts: Option<CqlTimestamp>| {
+        ts.and_then(|ts| DateTime::from_timestamp_millis(ts.0))
+            .unwrap_or_default()
+    };
+    Drone {
+        convoy_id,
+        drone_id,
+        tail_number: tail_number.unwrap_or_default(),
+        callsign: callsign.unwrap_or_default(),
+        platform_type: parse_platform_type(platform.as_deref().unwrap_or_default()),
+        serial_number: serial_number.unwrap_or_default(),
+        status: parse_drone_status(status.as_deref().unwrap_or_default()),
+        current_position: Coordinates::new(0.0, 0.0, 0.0),
+        fuel_remaining_pct: fuel.unwrap_or_default(),
+        flight_time_hrs: flight_time.unwrap_or_default(),
+        weapons: Vec::new(),
+        sensors: Vec::new(),
+        primary_link: None,
+        backup_link: None,
+        mesh_neighbors: Vec::new(),
+        total_engagements: engagements.unwrap_or_default(),
+        successful_hits: hits.unwrap_or_default(),
+        accuracy_pct: accuracy.unwrap_or_default(),
+        created_at: timestamp(created_at),
+        updated_at: timestamp(updated_at),
+    }
+}
+
+type WaypointRow = (
+    i16,
+    Uuid,
+    Option<String>,
+    Option<String>,
+    Option<CoordinatesUdt>,
+    Option<CqlTimestamp>,
+    Option<CqlTimestamp>,
+    Option<CqlTimestamp>,
+    Option<CqlTimestamp>,
+    Option<i32>,
+    Option<Vec<String>>,
+    Option<String>,
+);
+
+fn waypoint_from_row(
+    drone_id: Uuid,
+    (
+        sequence_number,
+        waypoint_id,
+        waypoint_name,
+        waypoint_type,
+        coordinates,
+        planned_arrival,
+        actual_arrival,
+        planned_departure,
+        actual_departure,
+        loiter_duration_min,
+        authorized_actions,
+        status,
+    ): WaypointRow,
+) -> Waypoint {
+    let timestamp = |t: Option<CqlTimestamp>| t.and_then(|t| DateTime::from_timestamp_millis(t.0));
+    Waypoint {
+        drone_id,
+        sequence_number,
+        waypoint_id,
+        waypoint_name: waypoint_name.unwrap_or_default(),
+        waypoint_type: parse_waypoint_type(waypoint_type.as_deref().unwrap_or_default()),
+        coordinates: coordinates.map_or_else(|| Coordinates::new(0.0, 0.0, 0.0), Into::into),
+        planned_arrival: timestamp(planned_arrival),
+        actual_arrival: timestamp(actual_arrival),
+        planned_departure: timestamp(planned_departure),
+        actual_departure: timestamp(actual_departure),
+        loiter_duration_min,
+        authorized_actions: authorized_actions.unwrap_or_default(),
+        status: parse_waypoint_status(status.as_deref().unwrap_or_default()),
+    }
+}
+
+// =============================================================================
+// JOURNAL REPOSITORY
+// =============================================================================
+
+/// Repository for operator journal entries.
+pub struct ScyllaJournalRepository {
+    client: Arc<ScyllaClient>,
+}
+
+/// Columns read by the journal queries
+const JOURNAL_COLUMNS: &str = "entry_time, entry_id, author, text, linked_entity_id";
+
+/// Row tuple matching [`JOURNAL_COLUMNS`]
+type JournalRow = (CqlTimestamp, Uuid, Option<String>, Option<String>, Option<Uuid>);
+
+impl ScyllaJournalRepository {
+    /// Create a new journal repository.
+    pub fn new(client: Arc<ScyllaClient>) -> Self {
+        Self { client }
+    }
+
+    /// Record a journal entry.
+    pub async fn record(&self, entry: &JournalEntry) -> Result<()> {
+        let _timer = self.client.metrics.time("journal.record");
+        let query = r#"
+            INSERT INTO journal_entries (
+                convoy_id, entry_time, entry_id, author, text, linked_entity_id
+            ) VALUES (?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.client
+            .query_unpaged(
+                query,
+                (
+                    entry.convoy_id,
+                    CqlTimestamp(entry.entry_time.timestamp_millis()),
+                    entry.entry_id,
+                    &entry.author,
+                    &entry.text,
+                    entry.linked_entity_id,
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get a convoy's journal entries within a time window, oldest first.
+    pub async fn get_range(
+        &self,
+        convoy_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<JournalEntry>> {
+        let _timer = self.client.metrics.time("journal.get_range");
+        let query = format!(
+            "SELECT {JOURNAL_COLUMNS} FROM journal_entries \
+             WHERE convoy_id = ? AND entry_time >= ? AND entry_time <= ? LIMIT ?"
+        );
+
+        let limit = i32::try_from(limit).unwrap_or(i32::MAX);
+        let result = self.client
+            .query_unpaged(
+                query,
+                (
+                    convoy_id,
+                    CqlTimestamp(start.timestamp_millis()),
+                    CqlTimestamp(end.timestamp_millis()),
+                    limit,
+                ),
+            )
+            .await?;
+
+        // Partition clusters newest first
+        let mut entries = parse_journal(convoy_id, result);
+        entries.reverse();
+        Ok(entries)
+    }
+
+    /// Get a convoy's most recent journal entries, newest first.
+    pub async fn get_recent(&self, convoy_id: Uuid, limit: usize) -> Result<Vec<JournalEntry>> {
+        let _timer = self.client.metrics.time("journal.get_recent");
+        let query = format!(
+            "SELECT {JOURNAL_COLUMNS} FROM journal_entries WHERE convoy_id = ? LIMIT ?"
+        );
+
+        let limit = i32::try_from(limit).unwrap_or(i32::MAX);
+        let result = self.client
+            .query_unpaged(query, (convoy_id, limit))
+            .await?;
+
+        Ok(parse_journal(convoy_id, result))
+    }
+}
+
+/// Parse rows selected with [`JOURNAL_COLUMNS`]
+fn parse_journal(convoy_id: Uuid, result: QueryResult) -> Vec<JournalEntry> {
+    let Ok(rows_result) = result.into_rows_result() else {
+        return Vec::new();
+    };
+    let Ok(rows) = rows_result.rows::<JournalRow>() else {
+        return Vec::new();
+    };
+
+    rows.flatten()
+        .map(|(time, entry_id, author, text, linked_entity_id)| JournalEntry {
+            convoy_id,
+            entry_time: DateTime::from_timestamp_millis(time.0).unwrap_or_default(),
+            entry_id,
+            author: author.unwrap_or_default(),
+            text: text.unwrap_or_default(),
+            linked_entity_id,
+        })
+        .collect()
+}
+
+// =============================================================================
+// HELPER FUNCTIONS
+// =============================================================================
+
+/// Convert a 0-indexed sorted-set position into a leaderboard rank.
+fn one_based_rank(index: i64) -> i16 {
+    i16::try_from(index.saturating_add(1)).unwrap_or(i16::MAX)
+}
+
+pub(super) fn parse_platform_type(s: &str) -> PlatformType {
+    match s {
+        "MQ-9_REAPER" | "MQ9_REAPER" => PlatformType::Mq9Reaper,
+        "MQ-1C_GRAY_EAGLE" | "MQ1C_GRAY_EAGLE" => PlatformType::Mq1cGrayEagle,
+        "RQ-4_GLOBAL_HAWK" | "RQ4_GLOBAL_HAWK" => PlatformType::Rq4GlobalHawk,
+        "MQ-25_STINGRAY" | "MQ25_STINGRAY" => PlatformType::Mq25Stingray,
+        _ => PlatformType::Mq9Reaper,
+    }
+}
+
+fn parse_drone_status(s: &str) -> DroneStatus {
+    match s {
+        "AIRBORNE" => DroneStatus::Airborne,
+        "LOITER" => DroneStatus::Loiter,
+        "INGRESS" => DroneStatus::Ingress,
+        "EGRESS" => DroneStatus::Egress,
+        "RTB" => DroneStatus::Rtb,
+        "LANDED" => DroneStatus::Landed,
+        "MAINTENANCE" => DroneStatus::Maintenance,
+        _ => DroneStatus::Preflight,
+    }
+}
+
+fn waypoint_type_str(t: WaypointType) -> &'static str {
+    match t {
+        WaypointType::Nav => "NAV",
+        WaypointType::Loiter => "LOITER",
+        WaypointType::Strike => "STRIKE",
+        WaypointType::Refuel => "REFUEL",
+        WaypointType::Rendezvous => "RENDEZVOUS",
+        WaypointType::Checkpoint => "CHECKPOINT",
+    }
+}
+
+fn parse_waypoint_type(s: &str) -> WaypointType {
+    match s {
+        "LOITER" => WaypointType::Loiter,
+        "STRIKE" => WaypointType::Strike,
+        "REFUEL" => WaypointType::Refuel,
+        "RENDEZVOUS" => WaypointType::Rendezvous,
+        "CHECKPOINT" => WaypointType::Checkpoint,
+        _ => WaypointType::Nav,
+    }
+}
+
+fn waypoint_status_str(s: WaypointStatus) -> &'static str {
+    match s {
+        WaypointStatus::Pending => "PENDING",
+        WaypointStatus::Active => "ACTIVE",
+        WaypointStatus::Complete => "COMPLETE",
+        WaypointStatus::Skipped => "SKIPPED",
+    }
+}
+
+fn parse_waypoint_status(s: &str) -> WaypointStatus {
+    match s {
+        "ACTIVE" => WaypointStatus::Active,
+        "COMPLETE" => WaypointStatus::Complete,
+        "SKIPPED" => WaypointStatus::Skipped,
+        _ => WaypointStatus::Pending,
+    }
+}
+
+fn target_type_str(t: &TargetType) -> &'static str {
+    match t {
+        TargetType::Vehicle => "VEHICLE",
+        TargetType::Structure => "STRUCTURE",
+        TargetType::Personnel => "PERSONNEL",
+        TargetType::Radar => "RADAR",
+        TargetType::AirDefense => "AIR_DEFENSE",
+        TargetType::Supply => "SUPPLY",
+    }
+}
+
+fn mission_type_str(t: &MissionType) -> &'static str {
+    match t {
+        MissionType::Isr => "ISR",
+        MissionType::Strike => "STRIKE",
+        MissionType::Escort => "ESCORT",
+        MissionType::Resupply => "RESUPPLY",
+        MissionType::Sar => "SAR",
+    }
+}
+
+fn parse_mission_type(s: &str) -> MissionType {
+    match s {
+        "ISR" => MissionType::Isr,
+        "ESCORT" => MissionType::Escort,
+        "RESUPPLY" => MissionType::Resupply,
+        "SAR" => MissionType::Sar,
+        _ => MissionType::Strike,
+    }
+}
+
+fn convoy_status_str(s: &ConvoyStatus) -> &'static str {
+    match s {
+        ConvoyStatus::Planning => "PLANNING",
+        ConvoyStatus::Active => "ACTIVE",
+        ConvoyStatus::Rtb => "RTB",
+        ConvoyStatus::Complete => "COMPLETE",
+        ConvoyStatus::Abort => "ABORT",
+    }
+}
+
+fn parse_convoy_status(s: &str) -> ConvoyStatus {
+    match s {
+        "ACTIVE" => ConvoyStatus::Active,
+        "RTB" => ConvoyStatus::Rtb,
+        "COMPLETE" => ConvoyStatus::Complete,
+        "ABORT" => ConvoyStatus::Abort,
+        _ => ConvoyStatus::Planning,
+    }
+}
+
+fn alert_severity_str(s: &AlertSeverity) -> &'static str {
+    match s {
+        AlertSeverity::Critical => "CRITICAL",
+        AlertSeverity::Warning => "WARNING",
+        AlertSeverity::Info => "INFO",
+    }
+}
+
+pub(super) fn parse_weapon_type(s: &str) -> WeaponType {
+    match s {
+        "GBU-12_PAVEWAY" => WeaponType::Gbu12Paveway,
+        "AIM-9X_SIDEWINDER" => WeaponType::Aim9xSidewinder,
+        "GBU-38_JDAM" => WeaponType::Gbu38Jdam,
+        "AGM-176_GRIFFIN" => WeaponType::Agm176Griffin,
+        _ => WeaponType::Agm114Hellfire,
+    }
+}
+
+pub(super) fn parse_target_type(s: &str) -> TargetType {
+    match s {
+        "STRUCTURE" => TargetType::Structure,
+        "PERSONNEL" => TargetType::Personnel,
+        "RADAR" => TargetType::Radar,
+        "AIR_DEFENSE" => TargetType::AirDefense,
+        "SUPPLY" => TargetType::Supply,
+        _ => TargetType::Vehicle,
+    }
+}
+
+/// Map a stored BDA status onto the assessment; misses are always `Missed`.
+pub(super) fn parse_damage_assessment(hit: bool, bda_status: &str) -> DamageAssessment {
+    match (hit, bda_status) {
+        (false, _) => DamageAssessment::Missed,
+        (true, "DESTROYED" | "CONFIRMED") => DamageAssessment::Destroyed,
+        (true, "DAMAGED") => DamageAssessment::Damaged,
+        (true, _) => DamageAssessment::PendingBda,
+    }
+}
+
+/// Whether a conditional (`IF ...`) update was applied.
+fn lwt_applied(result: QueryResult) -> bool {
+    result
+        .into_rows_result()
+        .ok()
+        .and_then(|rows| rows.maybe_first_row::<Row>().ok().flatten())
+        .and_then(|row| row.columns.into_iter().next().flatten())
+        .is_some_and(|applied| matches!(applied, CqlValue::Boolean(true)))
+}
+
+fn authorization_status_str(s: AuthorizationStatus) -> &'static str {
+    match s {
+        AuthorizationStatus::Pending => "PENDING",
+        AuthorizationStatus::Approved => "APPROVED",
+        AuthorizationStatus::Denied => "DENIED",
+        AuthorizationStatus::Executed => "EXECUTED",
+    }
+}
+
+fn parse_authorization_status(s: &str) -> AuthorizationStatus {
+    match s {
+        "APPROVED" => AuthorizationStatus::Approved,
+        "DENIED" => AuthorizationStatus::Denied,
+        "EXECUTED" => AuthorizationStatus::Executed,
+        _ => AuthorizationStatus::Pending,
+    }
+}
+
+fn weapon_state_str(s: WeaponState) -> &'static str {
+    match s {
+        WeaponState::Armed => "ARMED",
+        WeaponState::Safe => "SAFE",
+        WeaponState::Jammed => "JAMMED",
+        WeaponState::Expended => "EXPENDED",
+    }
+}
+
+fn parse_weapon_state(s: &str) -> WeaponState {
+    match s {
+        "SAFE" => WeaponState::Safe,
+        "JAMMED" => WeaponState::Jammed,
+        "EXPENDED" => WeaponState::Expended,
+        _ => WeaponState::Armed,
+    }
+}
+
+pub(super) fn sensor_type_str(s: SensorType) -> &'static str {
+    match s {
+        SensorType::EoIr => "EO_IR",
+        SensorType::Sar => "SAR",
+        SensorType::Sigint => "SIGINT",
+        SensorType::Lidar => "LIDAR",
+    }
+}
+
+fn parse_sensor_type(s: &str) -> SensorType {
+    match s {
+        "SAR" => SensorType::Sar,
+        "SIGINT" => SensorType::Sigint,
+        "LIDAR" => SensorType::Lidar,
+        _ => SensorType::EoIr,
+    }
+}
+
+fn parse_alert_severity(s: &str) -> AlertSeverity {
+    match s {
+        "CRITICAL" => AlertSeverity::Critical,
+        "WARNING" => AlertSeverity::Warning,
+        _ => AlertSeverity::Info,
+    }
+}
+
+/// Unknown scopes read as the least privileged one
+fn parse_api_key_scope(s: &str) -> ApiKeyScope {
+    match s {
+        "ADMIN" => ApiKeyScope::Admin,
+        "READ_ONLY" => ApiKeyScope::ReadOnly,
+        _ => ApiKeyScope::Ingest,
+    }
+}
+
+fn parse_delivery_status(s: &str) -> DeliveryStatus {
+    match s {
+        "DELIVERED" => DeliveryStatus::Delivered,
+        "RATE_LIMITED" => DeliveryStatus::RateLimited,
+        _ => DeliveryStatus::Failed,
+    }
+}
+
+fn threat_level_str(t: ThreatLevel) -> &'static str {
+    match t {
+        ThreatLevel::High => "HIGH",
+        ThreatLevel::Medium => "MEDIUM",
+        ThreatLevel::Low => "LOW",
+        ThreatLevel::Unknown => "UNKNOWN",
+    }
+}
+
+fn parse_threat_level(s: &str) -> ThreatLevel {
+    match s {
+        "HIGH" => ThreatLevel::High,
+        "MEDIUM" => ThreatLevel::Medium,
+        "LOW" => ThreatLevel::Low,
+        _ => ThreatLevel::Unknown,
+    }
+}
+
+fn target_status_str(s: TargetStatus) -> &'static str {
+    match s {
+        TargetStatus::Detected => "DETECTED",
+        TargetStatus::Tracked => "TRACKED",
+        TargetStatus::Engaged => "ENGAGED",
+        TargetStatus::Destroyed => "DESTROYED",
+    }
+}
+
+fn parse_target_status(s: &str) -> TargetStatus {
+    match s {
+        "TRACKED" => TargetStatus::Tracked,
+        "ENGAGED" => TargetStatus::Engaged,
+        "DESTROYED" => TargetStatus::Destroyed,
+        _ => TargetStatus::Detected,
     }
 }
@@ -0,0 +1,2575 @@
+//! Embedded repository implementation.
+//!
+//! Stand-ins for the Scylla repositories, built with the `embedded`
+//! feature, so the API runs against a local file with no cluster. Every
+//! `Sqlite*Repository` has the same methods as its `Scylla*` counterpart.
+//!
+//! Rows keep their key and ordering columns next to the domain value stored
+//! as JSON, so new domain fields need no migration. A single connection
+//! serializes all access; this backend is for development, not load.
+
+// Methods stay async to match the Scylla repositories they replace
+#![allow(clippy::unused_async)]
+
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{Connection, OptionalExtension, Params};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::breaker::{BreakerConfig, CircuitBreaker};
+use crate::cache::SharedCacheClient;
+use crate::error::{PersistenceError, Result};
+use crate::metrics::RepositoryMetrics;
+use crate::strategy::{DynamicStrategy, ReadStrategy, WriteStrategy};
+use super::rows::{rank_changes, LeaderboardTally};
+use super::scylla_impl::{sensor_type_str, ConvoyOwner, DroneStatusInfo, Page, RankedUpdate};
+use drone_domain::{
+    Alert, AlertDelivery, ApiKey, AuthorizationStatus, Convoy, ConvoyStatsSnapshot, Drone,
+    DroneStatusChange, Engagement, EngagementAuthorization, EngagementLogEvent, Environment,
+    ImpactPoint, JournalEntry, LeaderboardEntry, MissionArtifact, NoStrikeZone, PlatformType,
+    RankHistoryEntry, ScoringModel,
+    SensorTask, Target, TargetHandoff, TargetStatus, Telemetry, TrackPoint, Waypoint, WeaponStatus,
+    WeaponType,
+};
+
+/// Tables created when a database is opened
+const SCHEMA: &str = r"
+    CREATE TABLE IF NOT EXISTS leaderboard (
+        convoy_id TEXT NOT NULL, drone_id TEXT NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, drone_id)
+    );
+    CREATE TABLE IF NOT EXISTS leaderboard_history (
+        convoy_id TEXT NOT NULL, drone_id TEXT NOT NULL, recorded_at INTEGER NOT NULL,
+        rank INTEGER NOT NULL, accuracy_pct REAL,
+        PRIMARY KEY (convoy_id, drone_id, recorded_at)
+    );
+    CREATE TABLE IF NOT EXISTS engagements (
+        convoy_id TEXT NOT NULL, engagement_id TEXT NOT NULL, engaged_at INTEGER NOT NULL,
+        drone_id TEXT NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, engagement_id)
+    );
+    CREATE INDEX IF NOT EXISTS engagements_by_time ON engagements (convoy_id, engaged_at);
+    CREATE INDEX IF NOT EXISTS engagements_by_drone ON engagements (drone_id, engaged_at);
+    CREATE TABLE IF NOT EXISTS engagement_event_log (
+        convoy_id TEXT NOT NULL, recorded_at INTEGER NOT NULL, event_id TEXT NOT NULL,
+        doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, recorded_at, event_id)
+    );
+    CREATE TABLE IF NOT EXISTS telemetry (
+        drone_id TEXT NOT NULL, recorded_at INTEGER NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (drone_id, recorded_at)
+    );
+    CREATE TABLE IF NOT EXISTS convoys (
+        convoy_id TEXT PRIMARY KEY, commanding_unit TEXT NOT NULL, doc TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS convoy_scoring_models (
+        convoy_id TEXT PRIMARY KEY, model TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS convoy_stats_history (
+        convoy_id TEXT NOT NULL, recorded_at INTEGER NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, recorded_at)
+    );
+    CREATE TABLE IF NOT EXISTS no_strike_zones (
+        convoy_id TEXT NOT NULL, zone_id TEXT NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, zone_id)
+    );
+    CREATE TABLE IF NOT EXISTS waypoints (
+        drone_id TEXT NOT NULL, sequence_number INTEGER NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (drone_id, sequence_number)
+    );
+    CREATE TABLE IF NOT EXISTS sensor_tasks (
+        drone_id TEXT NOT NULL, sequence_number INTEGER NOT NULL, sensor_type TEXT NOT NULL,
+        doc TEXT NOT NULL,
+        PRIMARY KEY (drone_id, sequence_number, sensor_type)
+    );
+    CREATE TABLE IF NOT EXISTS alerts (
+        convoy_id TEXT NOT NULL, alert_id TEXT NOT NULL, alert_time INTEGER NOT NULL,
+        doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, alert_id)
+    );
+    CREATE TABLE IF NOT EXISTS engagement_authorizations (
+        convoy_id TEXT NOT NULL, request_id TEXT NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, request_id)
+    );
+    CREATE TABLE IF NOT EXISTS weapons_inventory (
+        drone_id TEXT NOT NULL, weapon_type TEXT NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (drone_id, weapon_type)
+    );
+    CREATE TABLE IF NOT EXISTS targets (
+        convoy_id TEXT NOT NULL, target_id TEXT NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, target_id)
+    );
+    CREATE TABLE IF NOT EXISTS target_handoffs (
+        convoy_id TEXT NOT NULL, handed_off_at INTEGER NOT NULL, handoff_id TEXT NOT NULL,
+        target_id TEXT NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, handed_off_at, handoff_id)
+    );
+    CREATE TABLE IF NOT EXISTS drones (
+        convoy_id TEXT NOT NULL, drone_id TEXT NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, drone_id)
+    );
+    CREATE TABLE IF NOT EXISTS drone_callsigns (
+        convoy_id TEXT NOT NULL, callsign TEXT NOT NULL, drone_id TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, callsign)
+    );
+    CREATE TABLE IF NOT EXISTS drone_tail_numbers (
+        tail_number TEXT PRIMARY KEY, drone_id TEXT NOT NULL, convoy_id TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS drone_status_history (
+        drone_id TEXT NOT NULL, changed_at INTEGER NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (drone_id, changed_at)
+    );
+    CREATE TABLE IF NOT EXISTS api_keys (
+        key_id TEXT PRIMARY KEY, created_at INTEGER NOT NULL, doc TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS journal_entries (
+        convoy_id TEXT NOT NULL, entry_time INTEGER NOT NULL, entry_id TEXT NOT NULL,
+        doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, entry_time, entry_id)
+    );
+    CREATE TABLE IF NOT EXISTS artifact_blobs (
+        digest TEXT PRIMARY KEY, content BLOB NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS mission_artifacts (
+        convoy_id TEXT NOT NULL, kind TEXT NOT NULL, doc TEXT NOT NULL,
+        PRIMARY KEY (convoy_id, kind)
+    );
+";
+
+/// How long telemetry is kept, matching the Scylla table TTL
+const TELEMETRY_RETENTION_HOURS: i64 = 24;
+
+// =============================================================================
+// SQLITE CONFIGURATION
+// =============================================================================
+
+/// Embedded database configuration.
+#[derive(Debug, Clone)]
+pub struct SqliteConfig {
+    /// Database file, created if missing; `:memory:` keeps nothing on disk
+    pub path: String,
+    pub breaker: BreakerConfig,
+}
+
+impl Default for SqliteConfig {
+    fn default() -> Self {
+        Self {
+            path: "drone_ops.sqlite".to_string(),
+            breaker: BreakerConfig::default(),
+        }
+    }
+}
+
+// =============================================================================
+// SQLITE CLIENT
+// =============================================================================
+
+/// Database connection shared by the embedded repositories.
+///
+/// Only repository method metrics are recorded; statements are not named
+/// or timed individually.
+pub struct SqliteClient {
+    conn: Mutex<Connection>,
+    breaker: Arc<CircuitBreaker>,
+    metrics: Arc<RepositoryMetrics>,
+    pub config: SqliteConfig,
+}
+
+impl SqliteClient {
+    /// Open (or create) the database and its tables.
+    pub fn open(config: SqliteConfig) -> Result<Self> {
+        let conn = Connection::open(&config.path)?;
+        conn.execute_batch(SCHEMA)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            breaker: Arc::new(CircuitBreaker::new("sqlite", config.breaker)),
+            metrics: Arc::new(RepositoryMetrics::default()),
+            config,
+        })
+    }
+
+    /// Open a private in-memory database.
+    pub fn in_memory() -> Result<Self> {
+        Self::open(SqliteConfig {
+            path: ":memory:".to_string(),
+            ..SqliteConfig::default()
+        })
+    }
+
+    /// Circuit breaker guarding this client.
+    pub fn breaker(&self) -> Arc<CircuitBreaker> {
+        self.breaker.clone()
+    }
+
+    /// Call and latency metrics of the repositories on this client.
+    pub fn metrics(&self) -> Arc<RepositoryMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Run `f` on the connection through the circuit breaker.
+    ///
+    /// The connection stays locked for the whole call, so a read followed
+    /// by a write inside `f` cannot interleave with other callers.
+    fn call<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        if !self.breaker.try_acquire() {
+            return Err(PersistenceError::CircuitOpen(self.breaker.name().to_string()));
+        }
+        let outcome = match self.conn.lock() {
+            Ok(conn) => f(&conn),
+            Err(_) => Err(PersistenceError::Unavailable("SQLite connection poisoned".to_string())),
+        };
+        match &outcome {
+            Ok(_) => self.breaker.record_success(),
+            Err(_) => self.breaker.record_failure(),
+        }
+        outcome
+    }
+}
+
+/// Build a [`ConvoyOwner`] from `(commanding_unit, environment)` columns.
+fn owner_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ConvoyOwner> {
+    let environment: Option<String> = row.get(1)?;
+    Ok(ConvoyOwner {
+        commanding_unit: row.get(0)?,
+        environment: environment.and_then(|e| e.parse().ok()).unwrap_or_default(),
+    })
+}
+
+/// Serialize a domain value for a `doc` column.
+fn to_doc<T: Serialize>(value: &T) -> Result<String> {
+    Ok(serde_json::to_string(value)?)
+}
+
+/// Decode the `doc` column of every row `sql` selects.
+fn select_docs<T: DeserializeOwned>(
+    conn: &Connection,
+    sql: &str,
+    params: impl Params,
+) -> Result<Vec<T>> {
+    let mut stmt = conn.prepare_cached(sql)?;
+    let docs = stmt
+        .query_map(params, |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    docs.iter()
+        .map(|doc| serde_json::from_str(doc).map_err(Into::into))
+        .collect()
+}
+
+/// Decode the `doc` column of the first row `sql` selects.
+fn select_doc<T: DeserializeOwned>(
+    conn: &Connection,
+    sql: &str,
+    params: impl Params,
+) -> Result<Option<T>> {
+    Ok(select_docs(conn, sql, params)?.into_iter().next())
+}
+
+/// Read-modify-write the first `table` row matching `filter`.
+///
+/// `update` returns whether to write the value back. Returns the value as
+/// left by `update` and whether it was written, `None` if no row matched.
+fn modify_doc<T: Serialize + DeserializeOwned>(
+    conn: &Connection,
+    table: &str,
+    filter: &str,
+    params: impl Params,
+    update: impl FnOnce(&mut T) -> bool,
+) -> Result<Option<(T, bool)>> {
+    let row = conn
+        .prepare_cached(&format!("SELECT rowid, doc FROM {table} WHERE {filter}"))?
+        .query_row(params, |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+        .optional()?;
+    let Some((rowid, doc)) = row else {
+        return Ok(None);
+    };
+
+    let mut value: T = serde_json::from_str(&doc)?;
+    let write = update(&mut value);
+    if write {
+        conn.prepare_cached(&format!("UPDATE {table} SET doc = ?1 WHERE rowid = ?2"))?
+            .execute((to_doc(&value)?, rowid))?;
+    }
+    Ok(Some((value, write)))
+}
+
+/// Row count for a `LIMIT` bind.
+fn sql_limit(limit: usize) -> i64 {
+    i64::try_from(limit).unwrap_or(i64::MAX)
+}
+
+/// Row offset a page starts at, encoded as the page's paging state.
+fn page_offset(paging_state: Option<&[u8]>) -> Result<i64> {
+    paging_state.map_or(Ok(0), |state| {
+        state
+            .try_into()
+            .map(i64::from_be_bytes)
+            .map_err(|_| PersistenceError::InvalidQuery("malformed paging state".to_string()))
+    })
+}
+
+/// Cut a page from up to `page_size + 1` rows read from `offset`.
+fn into_page<T>(mut items: Vec<T>, offset: i64, page_size: usize) -> Page<T> {
+    let page_size = page_size.max(1);
+    let paging_state = (items.len() > page_size).then(|| {
+        items.truncate(page_size);
+        (offset + sql_limit(page_size)).to_be_bytes().to_vec()
+    });
+    Page { items, paging_state }
+}
+
+// =============================================================================
+// LEADERBOARD REPOSITORY
+// =============================================================================
+
+/// Repository for leaderboard operations.
+///
+/// Ranks always come from stored score order; the cache, when given,
+/// only has its sorted set and version kept up to date for readers.
+pub struct SqliteLeaderboardRepository {
+    client: Arc<SqliteClient>,
+    cache: Option<SharedCacheClient>,
+    strategy: DynamicStrategy,
+    default_scoring_model: ScoringModel,
+}
+
+impl SqliteLeaderboardRepository {
+    /// Create a new leaderboard repository with default strategies.
+    pub fn new(client: Arc<SqliteClient>, cache: Option<SharedCacheClient>) -> Self {
+        Self::with_strategies(client, cache, ReadStrategy::CacheFirst, WriteStrategy::WriteThrough)
+    }
+
+    /// Create with custom strategies.
+    pub fn with_strategies(
+        client: Arc<SqliteClient>,
+        cache: Option<SharedCacheClient>,
+        read_strategy: ReadStrategy,
+        write_strategy: WriteStrategy,
+    ) -> Self {
+        let mut strategy = DynamicStrategy::new(read_strategy, write_strategy);
+        if let Some(cache) = &cache {
+            strategy = strategy.degrade_on(cache.breaker());
+        }
+
+        Self {
+            client,
+            cache,
+            strategy,
+            default_scoring_model: ScoringModel::default(),
+        }
+    }
+
+    /// Set read strategy.
+    pub fn set_read_strategy(&self, strategy: ReadStrategy) {
+        self.strategy.set_read(strategy);
+    }
+
+    /// Set write strategy.
+    pub fn set_write_strategy(&self, strategy: WriteStrategy) {
+        self.strategy.set_write(strategy);
+    }
+
+    /// Shared handle for swapping strategies at runtime.
+    pub fn strategy(&self) -> DynamicStrategy {
+        self.strategy.clone()
+    }
+
+    /// Set the scoring model used for convoys without an explicit selection.
+    pub fn set_default_scoring_model(&mut self, model: ScoringModel) {
+        self.default_scoring_model = model;
+    }
+
+    /// Select the scoring model for a convoy.
+    ///
+    /// The model is stored with the convoy, and every stored score, rank
+    /// and the cached sorted set are recomputed under it.
+    pub async fn set_scoring_model(&self, convoy_id: Uuid, model: ScoringModel) -> Result<()> {
+        let _timer = self.client.metrics.time("leaderboard.set_scoring_model");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO convoy_scoring_models (convoy_id, model) VALUES (?1, ?2)",
+            )?
+            .execute((convoy_id.to_string(), model.as_str()))?;
+            Ok(())
+        })?;
+
+        let entries = self.get_leaderboard(convoy_id, i32::MAX).await?;
+        for entry in &entries {
+            self.write_entry(entry)?;
+        }
+        if let Some(cache) = self
+            .cache
+            .as_ref()
+            .filter(|_| self.strategy.write() != WriteStrategy::DbOnly)
+        {
+            let scores: Vec<(Uuid, f64)> = entries.iter().map(|e| (e.drone_id, e.score)).collect();
+            if let Err(e) = cache.update_leaderboard_scores(convoy_id, &scores).await {
+                tracing::warn!(%convoy_id, error = %e, "Failed to re-score leaderboard sorted set");
+            }
+        }
+
+        let (_, ranks) = rank_changes(&entries, Uuid::nil());
+        self.persist_ranks(convoy_id, Uuid::nil(), &ranks)?;
+        let changed: Vec<Uuid> = ranks.iter().map(|(id, _)| *id).collect();
+        self.bump_version(convoy_id, &changed).await;
+        Ok(())
+    }
+
+    /// Get the scoring model stored for a convoy, or the default.
+    pub async fn scoring_model(&self, convoy_id: Uuid) -> Result<ScoringModel> {
+        let model: Option<String> = self.client.call(|conn| {
+            Ok(conn
+                .prepare_cached("SELECT model FROM convoy_scoring_models WHERE convoy_id = ?1")?
+                .query_row((convoy_id.to_string(),), |row| row.get(0))
+                .optional()?)
+        })?;
+        Ok(model
+            .as_deref()
+            .and_then(ScoringModel::parse)
+            .unwrap_or(self.default_scoring_model))
+    }
+
+    /// Get leaderboard for a convoy, highest score first.
+    pub async fn get_leaderboard(
+        &self,
+        convoy_id: Uuid,
+        limit: i32,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let _timer = self.client.metrics.time("leaderboard.get_leaderboard");
+        let mut entries: Vec<LeaderboardEntry> = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM leaderboard WHERE convoy_id = ?1",
+                (convoy_id.to_string(),),
+            )
+        })?;
+
+        let model = self.scoring_model(convoy_id).await?;
+        for entry in &mut entries {
+            entry.score = model.score(
+                i64::from(entry.successful_hits),
+                i64::from(entry.total_engagements),
+            );
+        }
+        entries.sort_by(|a, b| b.score.total_cmp(&a.score));
+        entries.truncate(usize::try_from(limit).unwrap_or_default());
+
+        Ok(entries)
+    }
+
+    /// Update leaderboard entry after engagement.
+    ///
+    /// Every rank shifted by the move is rewritten before returning.
+    pub async fn update_entry(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        callsign: &str,
+        platform: PlatformType,
+        hit: bool,
+    ) -> Result<RankedUpdate> {
+        let _timer = self.client.metrics.time("leaderboard.update_entry");
+        let current = self.get_drone_entry(convoy_id, drone_id).await?;
+        let old_rank = current.as_ref().map(|e| e.rank).filter(|r| *r > 0);
+
+        let tally = LeaderboardTally::after(current.as_ref(), hit);
+        let score = self.scoring_model(convoy_id).await?.score(
+            i64::from(tally.successful_hits),
+            i64::from(tally.total_engagements),
+        );
+        let mut entry = LeaderboardEntry {
+            convoy_id,
+            drone_id,
+            callsign: callsign.to_string(),
+            platform_type: platform,
+            accuracy_pct: tally.accuracy_pct().0,
+            total_engagements: tally.total_engagements,
+            successful_hits: tally.successful_hits,
+            current_streak: tally.current_streak,
+            best_streak: tally.best_streak,
+            score,
+            rank: old_rank.unwrap_or_default(),
+            updated_at: Utc::now(),
+        };
+        self.write_entry(&entry)?;
+
+        if let Some(ref cache) = self.cache {
+            match self.strategy.write() {
+                WriteStrategy::DbOnly => {}
+                WriteStrategy::WriteAround => {
+                    let _ = cache.invalidate_drone(drone_id).await;
+                }
+                WriteStrategy::WriteThrough | WriteStrategy::WriteBack => {
+                    let _ = cache.invalidate_drone(drone_id).await;
+                    let _ = cache.update_leaderboard_score(convoy_id, drone_id, score).await;
+                }
+            }
+        }
+
+        let entries = self.get_leaderboard(convoy_id, i32::MAX).await?;
+        let (rank, ranks) = rank_changes(&entries, drone_id);
+        self.persist_ranks(convoy_id, drone_id, &ranks)?;
+        entry.rank = rank;
+
+        let mut changed: Vec<Uuid> = ranks.iter().map(|(id, _)| *id).collect();
+        if !changed.contains(&drone_id) {
+            changed.push(drone_id);
+        }
+        self.bump_version(convoy_id, &changed).await;
+
+        if rank > 0 && old_rank != Some(rank) {
+            self.record_rank_change(&RankHistoryEntry {
+                convoy_id,
+                drone_id,
+                recorded_at: entry.updated_at,
+                rank,
+                accuracy_pct: Some(entry.accuracy_pct),
+            })
+            .await?;
+        }
+
+        Ok(RankedUpdate { entry, old_rank })
+    }
+
+    /// Bump the convoy's leaderboard version, logging `changed` drones for
+    /// diff readers. Skipped when the write strategy keeps the cache out.
+    async fn bump_version(&self, convoy_id: Uuid, changed: &[Uuid]) {
+        let Some(cache) = self
+            .cache
+            .as_ref()
+            .filter(|_| self.strategy.write() != WriteStrategy::DbOnly)
+        else {
+            return;
+        };
+        if let Err(e) = cache
+            .bump_leaderboard_version(convoy_id, changed, Utc::now().timestamp_millis())
+            .await
+        {
+            tracing::warn!(%convoy_id, error = %e, "Failed to bump leaderboard version");
+        }
+    }
+
+    /// Store new ranks, with a rank history row for every drone but `mover`.
+    fn persist_ranks(&self, convoy_id: Uuid, mover: Uuid, ranks: &[(Uuid, i16)]) -> Result<()> {
+        let recorded_at = Utc::now().timestamp_millis();
+        self.client.call(|conn| {
+            for (drone_id, rank) in ranks {
+                modify_doc(
+                    conn,
+                    "leaderboard",
+                    "convoy_id = ?1 AND drone_id = ?2",
+                    (convoy_id.to_string(), drone_id.to_string()),
+                    |entry: &mut LeaderboardEntry| {
+                        entry.rank = *rank;
+                        true
+                    },
+                )?;
+                if *drone_id != mover {
+                    conn.prepare_cached(
+                        "INSERT OR REPLACE INTO leaderboard_history \
+                         (convoy_id, drone_id, recorded_at, rank) VALUES (?1, ?2, ?3, ?4)",
+                    )?
+                    .execute((convoy_id.to_string(), drone_id.to_string(), recorded_at, rank))?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Append a row to a drone's rank history.
+    pub async fn record_rank_change(&self, change: &RankHistoryEntry) -> Result<()> {
+        let _timer = self.client.metrics.time("leaderboard.record_rank_change");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO leaderboard_history \
+                 (convoy_id, drone_id, recorded_at, rank, accuracy_pct) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute((
+                change.convoy_id.to_string(),
+                change.drone_id.to_string(),
+                change.recorded_at.timestamp_millis(),
+                change.rank,
+                change.accuracy_pct,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// Get a drone's rank changes within a time window, oldest first.
+    pub async fn rank_history(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<RankHistoryEntry>> {
+        let _timer = self.client.metrics.time("leaderboard.rank_history");
+        self.client.call(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT recorded_at, rank, accuracy_pct FROM leaderboard_history \
+                 WHERE convoy_id = ?1 AND drone_id = ?2 AND recorded_at >= ?3 AND recorded_at <= ?4 \
+                 ORDER BY recorded_at",
+            )?;
+            let rows = stmt.query_map(
+                (
+                    convoy_id.to_string(),
+                    drone_id.to_string(),
+                    start.timestamp_millis(),
+                    end.timestamp_millis(),
+                ),
+                |row| {
+                    Ok(RankHistoryEntry {
+                        convoy_id,
+                        drone_id,
+                        recorded_at: DateTime::from_timestamp_millis(row.get(0)?)
+                            .unwrap_or_default(),
+                        rank: row.get(1)?,
+                        accuracy_pct: row.get(2)?,
+                    })
+                },
+            )?;
+            Ok(rows.collect::<rusqlite::Result<_>>()?)
+        })
+    }
+
+    /// Accuracy on a drone's latest rank history row with accuracy before
+    /// `before`, to seed a timeline that starts mid-mission.
+    pub async fn accuracy_before(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<Option<f32>> {
+        let _timer = self.client.metrics.time("leaderboard.accuracy_before");
+        self.client.call(|conn| {
+            Ok(conn
+                .prepare_cached(
+                    "SELECT accuracy_pct FROM leaderboard_history \
+                     WHERE convoy_id = ?1 AND drone_id = ?2 AND recorded_at < ?3 \
+                     AND accuracy_pct IS NOT NULL ORDER BY recorded_at DESC LIMIT 1",
+                )?
+                .query_row(
+                    (convoy_id.to_string(), drone_id.to_string(), before.timestamp_millis()),
+                    |row| row.get(0),
+                )
+                .optional()?)
+        })
+    }
+
+    /// Overwrite a leaderboard entry verbatim (snapshot restore).
+    pub async fn restore_entry(&self, entry: &LeaderboardEntry) -> Result<()> {
+        let _timer = self.client.metrics.time("leaderboard.restore_entry");
+        self.write_entry(entry)?;
+
+        if let Some(ref cache) = self.cache {
+            let _ = cache.invalidate_drone(entry.drone_id).await;
+            let _ = cache
+                .update_leaderboard_score(entry.convoy_id, entry.drone_id, entry.score)
+                .await;
+        }
+        self.bump_version(entry.convoy_id, &[entry.drone_id]).await;
+
+        Ok(())
+    }
+
+    fn write_entry(&self, entry: &LeaderboardEntry) -> Result<()> {
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO leaderboard (convoy_id, drone_id, doc) VALUES (?1, ?2, ?3)",
+            )?
+            .execute((entry.convoy_id.to_string(), entry.drone_id.to_string(), to_doc(entry)?))?;
+            Ok(())
+        })
+    }
+
+    /// Get single drone entry.
+    async fn get_drone_entry(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+    ) -> Result<Option<LeaderboardEntry>> {
+        self.client.call(|conn| {
+            select_doc(
+                conn,
+                "SELECT doc FROM leaderboard WHERE convoy_id = ?1 AND drone_id = ?2",
+                (convoy_id.to_string(), drone_id.to_string()),
+            )
+        })
+    }
+}
+
+// =============================================================================
+// ENGAGEMENT REPOSITORY
+// =============================================================================
+
+/// Repository for engagement operations.
+pub struct SqliteEngagementRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteEngagementRepository {
+    /// Create a new engagement repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Record a new engagement.
+    pub async fn record(&self, engagement: &Engagement) -> Result<()> {
+        let _timer = self.client.metrics.time("engagement.record");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO engagements \
+                 (convoy_id, engagement_id, engaged_at, drone_id, doc) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute((
+                engagement.convoy_id.to_string(),
+                engagement.engagement_id.to_string(),
+                engagement.engaged_at.timestamp_millis(),
+                engagement.drone_id.to_string(),
+                to_doc(engagement)?,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// Get a convoy's most recent engagements, newest first.
+    pub async fn get_recent(&self, convoy_id: Uuid, limit: usize) -> Result<Vec<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_recent");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM engagements WHERE convoy_id = ?1 \
+                 ORDER BY engaged_at DESC, engagement_id LIMIT ?2",
+                (convoy_id.to_string(), sql_limit(limit)),
+            )
+        })
+    }
+
+    /// Read a page of a convoy's engagements, newest first.
+    pub async fn get_page(
+        &self,
+        convoy_id: Uuid,
+        page_size: usize,
+        paging_state: Option<&[u8]>,
+    ) -> Result<Page<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_page");
+        let offset = page_offset(paging_state)?;
+        let items = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM engagements WHERE convoy_id = ?1 \
+                 ORDER BY engaged_at DESC, engagement_id LIMIT ?2 OFFSET ?3",
+                (convoy_id.to_string(), sql_limit(page_size.max(1)) + 1, offset),
+            )
+        })?;
+        Ok(into_page(items, offset, page_size))
+    }
+
+    /// Get a single engagement by ID.
+    pub async fn get(&self, convoy_id: Uuid, engagement_id: Uuid) -> Result<Option<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get");
+        self.client.call(|conn| {
+            select_doc(
+                conn,
+                "SELECT doc FROM engagements WHERE convoy_id = ?1 AND engagement_id = ?2",
+                (convoy_id.to_string(), engagement_id.to_string()),
+            )
+        })
+    }
+
+    /// Record a battle damage assessment against an engagement.
+    pub async fn update_bda(
+        &self,
+        engagement: &Engagement,
+        bda_status: &str,
+        bda_notes: Option<&str>,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("engagement.update_bda");
+        self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "engagements",
+                "convoy_id = ?1 AND engagement_id = ?2",
+                (engagement.convoy_id.to_string(), engagement.engagement_id.to_string()),
+                |stored: &mut Engagement| {
+                    stored.bda_status = bda_status.to_string();
+                    stored.bda_notes = bda_notes.map(str::to_string);
+                    true
+                },
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Count a convoy's engagements.
+    pub async fn count(&self, convoy_id: Uuid) -> Result<i64> {
+        let _timer = self.client.metrics.time("engagement.count");
+        self.client.call(|conn| {
+            Ok(conn
+                .prepare_cached("SELECT COUNT(*) FROM engagements WHERE convoy_id = ?1")?
+                .query_row((convoy_id.to_string(),), |row| row.get(0))?)
+        })
+    }
+
+    /// Get a convoy's engagements within a time window, oldest first.
+    ///
+    /// When the window holds more than `limit`, the most recent are kept.
+    pub async fn get_range(
+        &self,
+        convoy_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_range");
+        let mut engagements: Vec<Engagement> = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM engagements \
+                 WHERE convoy_id = ?1 AND engaged_at >= ?2 AND engaged_at <= ?3 \
+                 ORDER BY engaged_at DESC, engagement_id LIMIT ?4",
+                (
+                    convoy_id.to_string(),
+                    start.timestamp_millis(),
+                    end.timestamp_millis(),
+                    sql_limit(limit),
+                ),
+            )
+        })?;
+        engagements.reverse();
+        Ok(engagements)
+    }
+
+    /// Get engagement impact points for a convoy within a time window.
+    pub async fn get_impact_points(
+        &self,
+        convoy_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ImpactPoint>> {
+        let _timer = self.client.metrics.time("engagement.get_impact_points");
+        let engagements = self.get_range(convoy_id, start, end, usize::MAX).await?;
+        Ok(engagements
+            .iter()
+            .map(|e| ImpactPoint {
+                latitude: e.result.impact_coords.latitude,
+                longitude: e.result.impact_coords.longitude,
+                hit: e.hit,
+            })
+            .collect())
+    }
+
+    /// Get a drone's most recent engagements, newest first.
+    pub async fn get_by_drone(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        limit: i32,
+    ) -> Result<Vec<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_by_drone");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM engagements WHERE convoy_id = ?1 AND drone_id = ?2 \
+                 ORDER BY engaged_at DESC, engagement_id LIMIT ?3",
+                (convoy_id.to_string(), drone_id.to_string(), i64::from(limit.max(0))),
+            )
+        })
+    }
+
+    /// Read a page of a drone's engagements within a convoy, newest first.
+    pub async fn get_drone_page(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        page_size: usize,
+        paging_state: Option<&[u8]>,
+    ) -> Result<Page<Engagement>> {
+        let _timer = self.client.metrics.time("engagement.get_drone_page");
+        let offset = page_offset(paging_state)?;
+        let items = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM engagements WHERE convoy_id = ?1 AND drone_id = ?2 \
+                 ORDER BY engaged_at DESC, engagement_id LIMIT ?3 OFFSET ?4",
+                (
+                    convoy_id.to_string(),
+                    drone_id.to_string(),
+                    sql_limit(page_size.max(1)) + 1,
+                    offset,
+                ),
+            )
+        })?;
+        Ok(into_page(items, offset, page_size))
+    }
+}
+
+// =============================================================================
+// ENGAGEMENT EVENT LOG REPOSITORY
+// =============================================================================
+
+/// Repository for the append-only engagement event log.
+pub struct SqliteEngagementLogRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteEngagementLogRepository {
+    /// Create a new engagement event log repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Append an event to its convoy's log.
+    pub async fn append(&self, event: &EngagementLogEvent) -> Result<()> {
+        let _timer = self.client.metrics.time("engagement_log.append");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO engagement_event_log \
+                 (convoy_id, recorded_at, event_id, doc) VALUES (?1, ?2, ?3, ?4)",
+            )?
+            .execute((
+                event.convoy_id.to_string(),
+                event.recorded_at.timestamp_millis(),
+                event.event_id.to_string(),
+                to_doc(event)?,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// Get a convoy's full log, oldest first.
+    pub async fn events(&self, convoy_id: Uuid) -> Result<Vec<EngagementLogEvent>> {
+        let _timer = self.client.metrics.time("engagement_log.events");
+        self.events_since(convoy_id, DateTime::<Utc>::MIN_UTC).await
+    }
+
+    /// Get events recorded after `after`, oldest first.
+    pub async fn events_since(
+        &self,
+        convoy_id: Uuid,
+        after: DateTime<Utc>,
+    ) -> Result<Vec<EngagementLogEvent>> {
+        let _timer = self.client.metrics.time("engagement_log.events_since");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM engagement_event_log WHERE convoy_id = ?1 AND recorded_at > ?2 \
+                 ORDER BY recorded_at, event_id",
+                (convoy_id.to_string(), after.timestamp_millis()),
+            )
+        })
+    }
+}
+
+// =============================================================================
+// TELEMETRY REPOSITORY
+// =============================================================================
+
+/// Repository for telemetry operations.
+///
+/// Snapshots older than the Scylla TTL are pruned as new ones arrive.
+pub struct SqliteTelemetryRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteTelemetryRepository {
+    /// Create a new telemetry repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Record telemetry snapshot.
+    pub async fn record(&self, telemetry: &Telemetry) -> Result<()> {
+        let _timer = self.client.metrics.time("telemetry.record");
+        let expired = Utc::now() - Duration::hours(TELEMETRY_RETENTION_HOURS);
+        self.client.call(|conn| {
+            let drone_id = telemetry.drone_id.to_string();
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO telemetry (drone_id, recorded_at, doc) VALUES (?1, ?2, ?3)",
+            )?
+            .execute((&drone_id, telemetry.recorded_at.timestamp_millis(), to_doc(telemetry)?))?;
+            conn.prepare_cached("DELETE FROM telemetry WHERE drone_id = ?1 AND recorded_at < ?2")?
+                .execute((&drone_id, expired.timestamp_millis()))?;
+            Ok(())
+        })
+    }
+
+    /// Get a drone's recorded positions within a time window, oldest first.
+    pub async fn get_track(
+        &self,
+        drone_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<TrackPoint>> {
+        let _timer = self.client.metrics.time("telemetry.get_track");
+        let snapshots: Vec<Telemetry> = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM telemetry \
+                 WHERE drone_id = ?1 AND recorded_at >= ?2 AND recorded_at <= ?3 \
+                 ORDER BY recorded_at",
+                (drone_id.to_string(), start.timestamp_millis(), end.timestamp_millis()),
+            )
+        })?;
+        Ok(snapshots
+            .into_iter()
+            .map(|t| TrackPoint {
+                recorded_at: t.recorded_at,
+                position: t.position,
+            })
+            .collect())
+    }
+
+    /// Get a drone's most recent telemetry snapshot.
+    pub async fn get_latest(&self, drone_id: Uuid) -> Result<Option<Telemetry>> {
+        let _timer = self.client.metrics.time("telemetry.get_latest");
+        self.client.call(|conn| {
+            select_doc(
+                conn,
+                "SELECT doc FROM telemetry WHERE drone_id = ?1 ORDER BY recorded_at DESC LIMIT 1",
+                (drone_id.to_string(),),
+            )
+        })
+    }
+}
+
+// =============================================================================
+// CONVOY REPOSITORY
+// =============================================================================
+
+/// Repository for convoy operations.
+pub struct SqliteConvoyRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteConvoyRepository {
+    /// Create a new convoy repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Commanding unit and environment of a convoy, `None` if the convoy
+    /// does not exist.
+    pub async fn owner(&self, convoy_id: Uuid) -> Result<Option<ConvoyOwner>> {
+        let _timer = self.client.metrics.time("convoy.owner");
+        self.client.call(|conn| {
+            Ok(conn
+                .prepare_cached(
+                    "SELECT commanding_unit, json_extract(doc, '$.environment') \
+                     FROM convoys WHERE convoy_id = ?1",
+                )?
+                .query_row((convoy_id.to_string(),), owner_from_row)
+                .optional()?)
+        })
+    }
+
+    /// Owner of the convoy a drone is assigned to.
+    pub async fn owner_for_drone(&self, drone_id: Uuid) -> Result<Option<ConvoyOwner>> {
+        let _timer = self.client.metrics.time("convoy.owner_for_drone");
+        self.client.call(|conn| {
+            Ok(conn
+                .prepare_cached(
+                    "SELECT commanding_unit, json_extract(convoys.doc, '$.environment') \
+                     FROM convoys, json_each(convoys.doc, '$.drone_ids') \
+                     WHERE json_each.value = ?1 LIMIT 1",
+                )?
+                .query_row((drone_id.to_string(),), owner_from_row)
+                .optional()?)
+        })
+    }
+
+    /// ID of the convoy a drone is assigned to.
+    pub async fn convoy_for_drone(&self, drone_id: Uuid) -> Result<Option<Uuid>> {
+        let _timer = self.client.metrics.time("convoy.convoy_for_drone");
+        self.client.call(|conn| {
+            let id = conn
+                .prepare_cached(
+                    "SELECT convoy_id FROM convoys, json_each(convoys.doc, '$.drone_ids') \
+                     WHERE json_each.value = ?1 LIMIT 1",
+                )?
+                .query_row((drone_id.to_string(),), |row| row.get::<_, String>(0))
+                .optional()?;
+            Ok(id.and_then(|id| Uuid::parse_str(&id).ok()))
+        })
+    }
+
+    /// IDs of the convoys a commanding unit owns in one environment.
+    pub async fn list_ids_by_unit(&self, unit: &str, environment: Environment) -> Result<Vec<Uuid>> {
+        let _timer = self.client.metrics.time("convoy.list_ids_by_unit");
+        self.client.call(|conn| {
+            // Documents written before environments existed are live data
+            let mut stmt = conn.prepare_cached(
+                "SELECT convoy_id FROM convoys WHERE commanding_unit = ?1 \
+                 AND COALESCE(json_extract(doc, '$.environment'), 'LIVE') = ?2",
+            )?;
+            let ids = stmt
+                .query_map((unit, environment.as_str()), |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(ids.iter().filter_map(|id| Uuid::parse_str(id).ok()).collect())
+        })
+    }
+
+    /// Get convoy by ID.
+    pub async fn get(&self, convoy_id: Uuid) -> Result<Option<Convoy>> {
+        let _timer = self.client.metrics.time("convoy.get");
+        self.client.call(|conn| {
+            select_doc(
+                conn,
+                "SELECT doc FROM convoys WHERE convoy_id = ?1",
+                (convoy_id.to_string(),),
+            )
+        })
+    }
+
+    /// Create a new convoy.
+    pub async fn create(&self, convoy: &Convoy) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.create");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO convoys (convoy_id, commanding_unit, doc) VALUES (?1, ?2, ?3)",
+            )?
+            .execute((convoy.convoy_id.to_string(), &convoy.commanding_unit, to_doc(convoy)?))?;
+            Ok(())
+        })
+    }
+
+    /// Write a convoy's status and mission start and end times.
+    pub async fn update_status(&self, convoy: &Convoy) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.update_status");
+        self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "convoys",
+                "convoy_id = ?1",
+                (convoy.convoy_id.to_string(),),
+                |stored: &mut Convoy| {
+                    stored.status = convoy.status;
+                    stored.mission_start = convoy.mission_start;
+                    stored.mission_end = convoy.mission_end;
+                    true
+                },
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Record a periodic statistics snapshot.
+    pub async fn record_stats(&self, snapshot: &ConvoyStatsSnapshot) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.record_stats");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO convoy_stats_history (convoy_id, recorded_at, doc) \
+                 VALUES (?1, ?2, ?3)",
+            )?
+            .execute((
+                snapshot.convoy_id.to_string(),
+                snapshot.recorded_at.timestamp_millis(),
+                to_doc(snapshot)?,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// Get statistics snapshots recorded within a time window, oldest first.
+    pub async fn get_stats_history(
+        &self,
+        convoy_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ConvoyStatsSnapshot>> {
+        let _timer = self.client.metrics.time("convoy.get_stats_history");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM convoy_stats_history \
+                 WHERE convoy_id = ?1 AND recorded_at >= ?2 AND recorded_at <= ?3 \
+                 ORDER BY recorded_at",
+                (convoy_id.to_string(), start.timestamp_millis(), end.timestamp_millis()),
+            )
+        })
+    }
+
+    /// Create or replace a no-strike zone.
+    pub async fn put_no_strike_zone(&self, zone: &NoStrikeZone) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.put_no_strike_zone");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO no_strike_zones (convoy_id, zone_id, doc) \
+                 VALUES (?1, ?2, ?3)",
+            )?
+            .execute((zone.convoy_id.to_string(), zone.zone_id.to_string(), to_doc(zone)?))?;
+            Ok(())
+        })
+    }
+
+    /// List a convoy's no-strike zones.
+    pub async fn list_no_strike_zones(&self, convoy_id: Uuid) -> Result<Vec<NoStrikeZone>> {
+        let _timer = self.client.metrics.time("convoy.list_no_strike_zones");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM no_strike_zones WHERE convoy_id = ?1 ORDER BY zone_id",
+                (convoy_id.to_string(),),
+            )
+        })
+    }
+
+    /// Delete a no-strike zone.
+    pub async fn delete_no_strike_zone(&self, convoy_id: Uuid, zone_id: Uuid) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.delete_no_strike_zone");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "DELETE FROM no_strike_zones WHERE convoy_id = ?1 AND zone_id = ?2",
+            )?
+            .execute((convoy_id.to_string(), zone_id.to_string()))?;
+            Ok(())
+        })
+    }
+
+    /// Store a mission artifact, replacing the convoy's previous one of the
+    /// same kind. Identical content shares one blob row.
+    pub async fn put_artifact(&self, artifact: &MissionArtifact, content: &[u8]) -> Result<()> {
+        let _timer = self.client.metrics.time("convoy.put_artifact");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR IGNORE INTO artifact_blobs (digest, content) VALUES (?1, ?2)",
+            )?
+            .execute((&artifact.digest, content))?;
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO mission_artifacts (convoy_id, kind, doc) \
+                 VALUES (?1, ?2, ?3)",
+            )?
+            .execute((
+                artifact.convoy_id.to_string(),
+                artifact.kind.as_str(),
+                to_doc(artifact)?,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// List a convoy's mission artifacts.
+    pub async fn list_artifacts(&self, convoy_id: Uuid) -> Result<Vec<MissionArtifact>> {
+        let _timer = self.client.metrics.time("convoy.list_artifacts");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM mission_artifacts WHERE convoy_id = ?1 ORDER BY kind",
+                (convoy_id.to_string(),),
+            )
+        })
+    }
+
+    /// Content stored under a digest.
+    pub async fn get_artifact_content(&self, digest: &str) -> Result<Option<Vec<u8>>> {
+        let _timer = self.client.metrics.time("convoy.get_artifact_content");
+        self.client.call(|conn| {
+            Ok(conn
+                .prepare_cached("SELECT content FROM artifact_blobs WHERE digest = ?1")?
+                .query_row((digest,), |row| row.get::<_, Vec<u8>>(0))
+                .optional()?)
+        })
+    }
+}
+
+// =============================================================================
+// WAYPOINT REPOSITORY
+// =============================================================================
+
+/// Repository for waypoint operations.
+pub struct SqliteWaypointRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteWaypointRepository {
+    /// Create a new waypoint repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Get a drone's waypoints in sequence order.
+    pub async fn get_waypoints(&self, drone_id: Uuid) -> Result<Vec<Waypoint>> {
+        let _timer = self.client.metrics.time("waypoint.get_waypoints");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM waypoints WHERE drone_id = ?1 ORDER BY sequence_number",
+                (drone_id.to_string(),),
+            )
+        })
+    }
+
+    /// Write a route plan, replacing waypoints with the same sequence numbers.
+    pub async fn save_waypoints(&self, waypoints: &[Waypoint]) -> Result<()> {
+        let _timer = self.client.metrics.time("waypoint.save_waypoints");
+        self.client.call(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "INSERT OR REPLACE INTO waypoints (drone_id, sequence_number, doc) VALUES (?1, ?2, ?3)",
+            )?;
+            for waypoint in waypoints {
+                stmt.execute((
+                    waypoint.drone_id.to_string(),
+                    waypoint.sequence_number,
+                    to_doc(waypoint)?,
+                ))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Assign a sensor mode for a waypoint, replacing any earlier task for
+    /// the same sensor.
+    pub async fn assign_sensor_task(&self, task: &SensorTask) -> Result<()> {
+        let _timer = self.client.metrics.time("waypoint.assign_sensor_task");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO sensor_tasks (drone_id, sequence_number, sensor_type, doc) \
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?
+            .execute((
+                task.drone_id.to_string(),
+                task.sequence_number,
+                sensor_type_str(task.sensor_type),
+                to_doc(task)?,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// Get a drone's sensor tasks in waypoint order.
+    pub async fn get_sensor_tasks(&self, drone_id: Uuid) -> Result<Vec<SensorTask>> {
+        let _timer = self.client.metrics.time("waypoint.get_sensor_tasks");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM sensor_tasks WHERE drone_id = ?1 \
+                 ORDER BY sequence_number, sensor_type",
+                (drone_id.to_string(),),
+            )
+        })
+    }
+}
+
+// =============================================================================
+// ALERT REPOSITORY
+// =============================================================================
+
+/// Repository for alert operations.
+pub struct SqliteAlertRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteAlertRepository {
+    /// Create a new alert repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Record an alert.
+    pub async fn record(&self, alert: &Alert) -> Result<()> {
+        let _timer = self.client.metrics.time("alert.record");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO alerts (convoy_id, alert_id, alert_time, doc) \
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?
+            .execute((
+                alert.convoy_id.to_string(),
+                alert.alert_id.to_string(),
+                alert.alert_time.timestamp_millis(),
+                to_doc(alert)?,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// Get unacknowledged alerts for a convoy, newest first.
+    pub async fn get_open(&self, convoy_id: Uuid, limit: usize) -> Result<Vec<Alert>> {
+        let _timer = self.client.metrics.time("alert.get_open");
+        self.get_recent(convoy_id, limit, false).await
+    }
+
+    /// Get alerts for a convoy, newest first, optionally including ones
+    /// already acknowledged.
+    pub async fn get_recent(
+        &self,
+        convoy_id: Uuid,
+        limit: usize,
+        include_acknowledged: bool,
+    ) -> Result<Vec<Alert>> {
+        let _timer = self.client.metrics.time("alert.get_recent");
+        let mut alerts: Vec<Alert> = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM alerts WHERE convoy_id = ?1 ORDER BY alert_time DESC, alert_id",
+                (convoy_id.to_string(),),
+            )
+        })?;
+        alerts.retain(|a| include_acknowledged || !a.acknowledged);
+        alerts.truncate(limit);
+        Ok(alerts)
+    }
+
+    /// Mark an alert acknowledged; `None` if the convoy has no such alert.
+    pub async fn acknowledge(
+        &self,
+        convoy_id: Uuid,
+        alert_id: Uuid,
+        acknowledged_by: &str,
+    ) -> Result<Option<Alert>> {
+        let _timer = self.client.metrics.time("alert.acknowledge");
+        let now = Utc::now();
+        let acknowledged = self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "alerts",
+                "convoy_id = ?1 AND alert_id = ?2",
+                (convoy_id.to_string(), alert_id.to_string()),
+                |alert: &mut Alert| {
+                    alert.acknowledged = true;
+                    alert.acknowledged_by = Some(acknowledged_by.to_string());
+                    alert.acknowledged_at = Some(now);
+                    true
+                },
+            )
+        })?;
+        Ok(acknowledged.map(|(alert, _)| alert))
+    }
+
+    /// Record the outcome of forwarding an alert to an external channel,
+    /// replacing any earlier outcome for the same channel.
+    pub async fn record_delivery(&self, alert: &Alert, delivery: &AlertDelivery) -> Result<()> {
+        let _timer = self.client.metrics.time("alert.record_delivery");
+        self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "alerts",
+                "convoy_id = ?1 AND alert_id = ?2",
+                (alert.convoy_id.to_string(), alert.alert_id.to_string()),
+                |stored: &mut Alert| {
+                    stored.deliveries.retain(|d| d.channel != delivery.channel);
+                    stored.deliveries.push(delivery.clone());
+                    stored.deliveries.sort_by(|a, b| a.channel.cmp(&b.channel));
+                    true
+                },
+            )?;
+            Ok(())
+        })
+    }
+}
+
+// =============================================================================
+// ENGAGEMENT AUTHORIZATION REPOSITORY
+// =============================================================================
+
+/// Repository for pre-engagement authorization requests.
+pub struct SqliteAuthorizationRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteAuthorizationRepository {
+    /// Create a new authorization repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Record a new authorization request.
+    pub async fn create(&self, auth: &EngagementAuthorization) -> Result<()> {
+        let _timer = self.client.metrics.time("authorization.create");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO engagement_authorizations (convoy_id, request_id, doc) \
+                 VALUES (?1, ?2, ?3)",
+            )?
+            .execute((auth.convoy_id.to_string(), auth.request_id.to_string(), to_doc(auth)?))?;
+            Ok(())
+        })
+    }
+
+    /// Get one authorization request.
+    pub async fn get(
+        &self,
+        convoy_id: Uuid,
+        request_id: Uuid,
+    ) -> Result<Option<EngagementAuthorization>> {
+        let _timer = self.client.metrics.time("authorization.get");
+        self.client.call(|conn| {
+            select_doc(
+                conn,
+                "SELECT doc FROM engagement_authorizations WHERE convoy_id = ?1 AND request_id = ?2",
+                (convoy_id.to_string(), request_id.to_string()),
+            )
+        })
+    }
+
+    /// Get a convoy's authorization requests, newest first, optionally
+    /// limited to one status.
+    pub async fn list(
+        &self,
+        convoy_id: Uuid,
+        status: Option<AuthorizationStatus>,
+        limit: usize,
+    ) -> Result<Vec<EngagementAuthorization>> {
+        let _timer = self.client.metrics.time("authorization.list");
+        let mut requests: Vec<EngagementAuthorization> = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM engagement_authorizations WHERE convoy_id = ?1",
+                (convoy_id.to_string(),),
+            )
+        })?;
+        requests.retain(|r| status.is_none_or(|s| r.status == s));
+        requests.sort_by_key(|r| std::cmp::Reverse(r.requested_at));
+        requests.truncate(limit);
+        Ok(requests)
+    }
+
+    /// Approve or deny a pending request.
+    ///
+    /// Returns `None` if the convoy has no such request; fails with
+    /// `WriteConflict` if it was already decided.
+    pub async fn decide(
+        &self,
+        convoy_id: Uuid,
+        request_id: Uuid,
+        status: AuthorizationStatus,
+        decided_by: &str,
+        notes: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<EngagementAuthorization>> {
+        let _timer = self.client.metrics.time("authorization.decide");
+        let now = Utc::now();
+        let decided = self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "engagement_authorizations",
+                "convoy_id = ?1 AND request_id = ?2",
+                (convoy_id.to_string(), request_id.to_string()),
+                |auth: &mut EngagementAuthorization| {
+                    if auth.status != AuthorizationStatus::Pending {
+                        return false;
+                    }
+                    auth.status = status;
+                    auth.decided_by = Some(decided_by.to_string());
+                    auth.decided_at = Some(now);
+                    auth.decision_notes = notes.map(str::to_string);
+                    auth.expires_at = expires_at;
+                    true
+                },
+            )
+        })?;
+
+        match decided {
+            Some((_, false)) => Err(PersistenceError::WriteConflict(format!(
+                "authorization {request_id} is no longer pending"
+            ))),
+            decided => Ok(decided.map(|(auth, _)| auth)),
+        }
+    }
+
+    /// Consume an approval for an engagement.
+    ///
+    /// Returns `false` if the request is not approved, including when it was
+    /// already executed.
+    pub async fn mark_executed(
+        &self,
+        convoy_id: Uuid,
+        request_id: Uuid,
+        engagement_id: Uuid,
+    ) -> Result<bool> {
+        let _timer = self.client.metrics.time("authorization.mark_executed");
+        let executed = self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "engagement_authorizations",
+                "convoy_id = ?1 AND request_id = ?2",
+                (convoy_id.to_string(), request_id.to_string()),
+                |auth: &mut EngagementAuthorization| {
+                    if auth.status != AuthorizationStatus::Approved {
+                        return false;
+                    }
+                    auth.status = AuthorizationStatus::Executed;
+                    auth.engagement_id = Some(engagement_id);
+                    true
+                },
+            )
+        })?;
+        Ok(executed.is_some_and(|(_, applied)| applied))
+    }
+}
+
+// =============================================================================
+// WEAPONS INVENTORY REPOSITORY
+// =============================================================================
+
+/// Repository for per-drone munitions.
+pub struct SqliteWeaponsRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteWeaponsRepository {
+    /// Create a new weapons repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Get every weapon tracked for a drone.
+    pub async fn get_loadout(&self, drone_id: Uuid) -> Result<Vec<WeaponStatus>> {
+        let _timer = self.client.metrics.time("weapons.get_loadout");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM weapons_inventory WHERE drone_id = ?1 ORDER BY weapon_type",
+                (drone_id.to_string(),),
+            )
+        })
+    }
+
+    /// Get one weapon's status; `None` if the drone does not track it.
+    pub async fn get(
+        &self,
+        drone_id: Uuid,
+        weapon_type: WeaponType,
+    ) -> Result<Option<WeaponStatus>> {
+        let _timer = self.client.metrics.time("weapons.get");
+        self.client.call(|conn| {
+            select_doc(
+                conn,
+                "SELECT doc FROM weapons_inventory WHERE drone_id = ?1 AND weapon_type = ?2",
+                (drone_id.to_string(), weapon_type.as_str()),
+            )
+        })
+    }
+
+    /// Load or replace weapons on a drone. Weapons not listed are left as-is.
+    pub async fn set_loadout(&self, drone_id: Uuid, weapons: &[WeaponStatus]) -> Result<()> {
+        let _timer = self.client.metrics.time("weapons.set_loadout");
+        self.client.call(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "INSERT OR REPLACE INTO weapons_inventory (drone_id, weapon_type, doc) \
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            for weapon in weapons {
+                stmt.execute((drone_id.to_string(), weapon.weapon_type.as_str(), to_doc(weapon)?))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Write `updated` only if the stored weapon still matches `previous`.
+    ///
+    /// Returns `false` when another engagement changed it first.
+    pub async fn compare_and_set(
+        &self,
+        drone_id: Uuid,
+        previous: &WeaponStatus,
+        updated: &WeaponStatus,
+    ) -> Result<bool> {
+        let _timer = self.client.metrics.time("weapons.compare_and_set");
+        let swapped = self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "weapons_inventory",
+                "drone_id = ?1 AND weapon_type = ?2",
+                (drone_id.to_string(), previous.weapon_type.as_str()),
+                |stored: &mut WeaponStatus| {
+                    if stored.rounds_remaining != previous.rounds_remaining
+                        || stored.status != previous.status
+                    {
+                        return false;
+                    }
+                    stored.rounds_remaining = updated.rounds_remaining;
+                    stored.status = updated.status;
+                    true
+                },
+            )
+        })?;
+        Ok(swapped.is_some_and(|(_, applied)| applied))
+    }
+}
+
+// =============================================================================
+// TARGET REPOSITORY
+// =============================================================================
+
+/// Repository for tracked targets.
+pub struct SqliteTargetRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteTargetRepository {
+    /// Create a new target repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Create or update a target from a detection report.
+    ///
+    /// Linked engagements are left untouched.
+    pub async fn upsert(&self, target: &Target) -> Result<()> {
+        let _timer = self.client.metrics.time("target.upsert");
+        self.client.call(|conn| {
+            let existing: Option<Target> = select_doc(
+                conn,
+                "SELECT doc FROM targets WHERE convoy_id = ?1 AND target_id = ?2",
+                (target.convoy_id.to_string(), target.target_id.to_string()),
+            )?;
+            let target = Target {
+                engagement_ids: existing.map(|t| t.engagement_ids).unwrap_or_default(),
+                ..target.clone()
+            };
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO targets (convoy_id, target_id, doc) VALUES (?1, ?2, ?3)",
+            )?
+            .execute((target.convoy_id.to_string(), target.target_id.to_string(), to_doc(&target)?))?;
+            Ok(())
+        })
+    }
+
+    /// Get a target by ID.
+    pub async fn get(&self, convoy_id: Uuid, target_id: Uuid) -> Result<Option<Target>> {
+        let _timer = self.client.metrics.time("target.get");
+        self.client.call(|conn| {
+            select_doc(
+                conn,
+                "SELECT doc FROM targets WHERE convoy_id = ?1 AND target_id = ?2",
+                (convoy_id.to_string(), target_id.to_string()),
+            )
+        })
+    }
+
+    /// List a convoy's targets, most recently updated first.
+    pub async fn list(
+        &self,
+        convoy_id: Uuid,
+        status: Option<TargetStatus>,
+    ) -> Result<Vec<Target>> {
+        let _timer = self.client.metrics.time("target.list");
+        let mut targets: Vec<Target> = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM targets WHERE convoy_id = ?1",
+                (convoy_id.to_string(),),
+            )
+        })?;
+        if let Some(status) = status {
+            targets.retain(|t| t.status == status);
+        }
+        targets.sort_by_key(|t| std::cmp::Reverse(t.last_updated_at));
+        Ok(targets)
+    }
+
+    /// Link an engagement to a target and move it to `status`.
+    pub async fn record_engagement(
+        &self,
+        convoy_id: Uuid,
+        target_id: Uuid,
+        engagement_id: Uuid,
+        status: TargetStatus,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("target.record_engagement");
+        let now = Utc::now();
+        self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "targets",
+                "convoy_id = ?1 AND target_id = ?2",
+                (convoy_id.to_string(), target_id.to_string()),
+                |target: &mut Target| {
+                    target.engagement_ids.push(engagement_id);
+                    target.status = status;
+                    target.last_updated_at = now;
+                    true
+                },
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Move a target to a new lifecycle status.
+    pub async fn set_status(
+        &self,
+        convoy_id: Uuid,
+        target_id: Uuid,
+        status: TargetStatus,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("target.set_status");
+        let now = Utc::now();
+        self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "targets",
+                "convoy_id = ?1 AND target_id = ?2",
+                (convoy_id.to_string(), target_id.to_string()),
+                |target: &mut Target| {
+                    target.status = status;
+                    target.last_updated_at = now;
+                    true
+                },
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Record a hand-off of a target between drones.
+    pub async fn record_handoff(&self, handoff: &TargetHandoff) -> Result<()> {
+        let _timer = self.client.metrics.time("target.record_handoff");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO target_handoffs \
+                 (convoy_id, handed_off_at, handoff_id, target_id, doc) \
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?
+            .execute((
+                handoff.convoy_id.to_string(),
+                handoff.handed_off_at.timestamp_millis(),
+                handoff.handoff_id.to_string(),
+                handoff.target_id.to_string(),
+                to_doc(handoff)?,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// List a convoy's target hand-offs, oldest first, optionally for one target.
+    pub async fn list_handoffs(
+        &self,
+        convoy_id: Uuid,
+        target_id: Option<Uuid>,
+    ) -> Result<Vec<TargetHandoff>> {
+        let _timer = self.client.metrics.time("target.list_handoffs");
+        let mut handoffs: Vec<TargetHandoff> = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM target_handoffs WHERE convoy_id = ?1 \
+                 ORDER BY handed_off_at, handoff_id",
+                (convoy_id.to_string(),),
+            )
+        })?;
+        if let Some(target_id) = target_id {
+            handoffs.retain(|h| h.target_id == target_id);
+        }
+        Ok(handoffs)
+    }
+}
+
+// =============================================================================
+// DRONE REPOSITORY
+// =============================================================================
+
+/// Repository for drone platform state.
+pub struct SqliteDroneRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteDroneRepository {
+    /// Create a new drone repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Write a newly registered drone and add it to its convoy's roster.
+    ///
+    /// Callsign and tail number must already be claimed with
+    /// [`SqliteDroneRepository::claim_callsign`] and
+    /// [`SqliteDroneRepository::claim_tail_number`].
+    pub async fn create(&self, drone: &Drone) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.create");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO drones (convoy_id, drone_id, doc) VALUES (?1, ?2, ?3)",
+            )?
+            .execute((drone.convoy_id.to_string(), drone.drone_id.to_string(), to_doc(drone)?))?;
+            modify_doc(
+                conn,
+                "convoys",
+                "convoy_id = ?1",
+                (drone.convoy_id.to_string(),),
+                |convoy: &mut Convoy| {
+                    if convoy.drone_ids.contains(&drone.drone_id) {
+                        return false;
+                    }
+                    convoy.drone_ids.push(drone.drone_id);
+                    true
+                },
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Get a registered drone.
+    pub async fn get(&self, convoy_id: Uuid, drone_id: Uuid) -> Result<Option<Drone>> {
+        let _timer = self.client.metrics.time("drone.get");
+        self.client.call(|conn| {
+            select_doc(
+                conn,
+                "SELECT doc FROM drones WHERE convoy_id = ?1 AND drone_id = ?2",
+                (convoy_id.to_string(), drone_id.to_string()),
+            )
+        })
+    }
+
+    /// Get several of a convoy's drones; drones that do not exist are left
+    /// out.
+    pub async fn get_many(&self, convoy_id: Uuid, drone_ids: &[Uuid]) -> Result<Vec<Drone>> {
+        let _timer = self.client.metrics.time("drone.get_many");
+        let drones: Vec<Drone> = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM drones WHERE convoy_id = ?1",
+                (convoy_id.to_string(),),
+            )
+        })?;
+        Ok(drones.into_iter().filter(|d| drone_ids.contains(&d.drone_id)).collect())
+    }
+
+    /// Count a convoy's registered drones.
+    pub async fn count(&self, convoy_id: Uuid) -> Result<i64> {
+        let _timer = self.client.metrics.time("drone.count");
+        self.client.call(|conn| {
+            Ok(conn
+                .prepare_cached("SELECT COUNT(*) FROM drones WHERE convoy_id = ?1")?
+                .query_row((convoy_id.to_string(),), |row| row.get(0))?)
+        })
+    }
+
+    /// Read a page of a convoy's drones in drone ID order.
+    pub async fn list_page(
+        &self,
+        convoy_id: Uuid,
+        page_size: usize,
+        paging_state: Option<&[u8]>,
+    ) -> Result<Page<Drone>> {
+        let _timer = self.client.metrics.time("drone.list_page");
+        let offset = page_offset(paging_state)?;
+        let items = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM drones WHERE convoy_id = ?1 ORDER BY drone_id LIMIT ?2 OFFSET ?3",
+                (convoy_id.to_string(), sql_limit(page_size.max(1)) + 1, offset),
+            )
+        })?;
+        Ok(into_page(items, offset, page_size))
+    }
+
+    /// Reserve a callsign in a convoy for `drone_id`.
+    ///
+    /// Returns the ID of the drone already holding it, or `None` once the
+    /// callsign is reserved.
+    pub async fn claim_callsign(
+        &self,
+        convoy_id: Uuid,
+        callsign: &str,
+        drone_id: Uuid,
+    ) -> Result<Option<Uuid>> {
+        let _timer = self.client.metrics.time("drone.claim_callsign");
+        self.client.call(|conn| {
+            let convoy_id = convoy_id.to_string();
+            claim(
+                conn,
+                "INSERT OR IGNORE INTO drone_callsigns (convoy_id, callsign, drone_id) \
+                 VALUES (?1, ?2, ?3)",
+                (&convoy_id, callsign, drone_id.to_string()),
+                "SELECT drone_id FROM drone_callsigns WHERE convoy_id = ?1 AND callsign = ?2",
+                (&convoy_id, callsign),
+            )
+        })
+    }
+
+    /// Reserve a tail number for `drone_id` across all convoys.
+    ///
+    /// Returns the ID of the drone already holding it, or `None` once the
+    /// tail number is reserved.
+    pub async fn claim_tail_number(
+        &self,
+        tail_number: &str,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+    ) -> Result<Option<Uuid>> {
+        let _timer = self.client.metrics.time("drone.claim_tail_number");
+        self.client.call(|conn| {
+            claim(
+                conn,
+                "INSERT OR IGNORE INTO drone_tail_numbers (tail_number, convoy_id, drone_id) \
+                 VALUES (?1, ?2, ?3)",
+                (tail_number, convoy_id.to_string(), drone_id.to_string()),
+                "SELECT drone_id FROM drone_tail_numbers WHERE tail_number = ?1",
+                (tail_number,),
+            )
+        })
+    }
+
+    /// Release a callsign reserved by `drone_id`; other holders are untouched.
+    pub async fn release_callsign(
+        &self,
+        convoy_id: Uuid,
+        callsign: &str,
+        drone_id: Uuid,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.release_callsign");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "DELETE FROM drone_callsigns WHERE convoy_id = ?1 AND callsign = ?2 AND drone_id = ?3",
+            )?
+            .execute((convoy_id.to_string(), callsign, drone_id.to_string()))?;
+            Ok(())
+        })
+    }
+
+    /// Release a tail number reserved by `drone_id`; other holders are untouched.
+    pub async fn release_tail_number(&self, tail_number: &str, drone_id: Uuid) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.release_tail_number");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "DELETE FROM drone_tail_numbers WHERE tail_number = ?1 AND drone_id = ?2",
+            )?
+            .execute((tail_number, drone_id.to_string()))?;
+            Ok(())
+        })
+    }
+
+    /// Drone holding a callsign in a convoy, if any.
+    pub async fn find_by_callsign(&self, convoy_id: Uuid, callsign: &str) -> Result<Option<Uuid>> {
+        let _timer = self.client.metrics.time("drone.find_by_callsign");
+        self.client.call(|conn| {
+            let holder: Option<String> = conn
+                .prepare_cached(
+                    "SELECT drone_id FROM drone_callsigns WHERE convoy_id = ?1 AND callsign = ?2",
+                )?
+                .query_row((convoy_id.to_string(), callsign), |row| row.get(0))
+                .optional()?;
+            Ok(holder.and_then(|id| Uuid::parse_str(&id).ok()))
+        })
+    }
+
+    /// Get a drone's current status; `None` if the drone is not registered.
+    pub async fn get_status(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+    ) -> Result<Option<DroneStatusInfo>> {
+        let _timer = self.client.metrics.time("drone.get_status");
+        Ok(self.get(convoy_id, drone_id).await?.map(|drone| DroneStatusInfo {
+            callsign: drone.callsign,
+            status: drone.status,
+        }))
+    }
+
+    /// Store a drone's cumulative flight hours.
+    pub async fn set_flight_time(
+        &self,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        flight_time_hrs: f32,
+        at: DateTime<Utc>,
+    ) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.set_flight_time");
+        self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "drones",
+                "convoy_id = ?1 AND drone_id = ?2",
+                (convoy_id.to_string(), drone_id.to_string()),
+                |drone: &mut Drone| {
+                    drone.flight_time_hrs = flight_time_hrs;
+                    drone.updated_at = at;
+                    true
+                },
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Apply a status change only if the drone is still in its old status.
+    ///
+    /// Returns `false` when another update changed the status first.
+    pub async fn compare_and_set_status(&self, change: &DroneStatusChange) -> Result<bool> {
+        let _timer = self.client.metrics.time("drone.compare_and_set_status");
+        let swapped = self.client.call(|conn| {
+            modify_doc(
+                conn,
+                "drones",
+                "convoy_id = ?1 AND drone_id = ?2",
+                (change.convoy_id.to_string(), change.drone_id.to_string()),
+                |drone: &mut Drone| {
+                    if drone.status != change.old_status {
+                        return false;
+                    }
+                    drone.status = change.new_status;
+                    drone.updated_at = change.changed_at;
+                    true
+                },
+            )
+        })?;
+        Ok(swapped.is_some_and(|(_, applied)| applied))
+    }
+
+    /// Append an applied status change to the drone's history.
+    pub async fn record_status_change(&self, change: &DroneStatusChange) -> Result<()> {
+        let _timer = self.client.metrics.time("drone.record_status_change");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO drone_status_history (drone_id, changed_at, doc) \
+                 VALUES (?1, ?2, ?3)",
+            )?
+            .execute((
+                change.drone_id.to_string(),
+                change.changed_at.timestamp_millis(),
+                to_doc(change)?,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// Get a drone's most recent status changes, newest first.
+    pub async fn get_status_history(
+        &self,
+        drone_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<DroneStatusChange>> {
+        let _timer = self.client.metrics.time("drone.get_status_history");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM drone_status_history WHERE drone_id = ?1 \
+                 ORDER BY changed_at DESC LIMIT ?2",
+                (drone_id.to_string(), sql_limit(limit)),
+            )
+        })
+    }
+}
+
+/// Run a reservation `insert` unless its key is taken.
+///
+/// Returns the drone `holder` selects when the key was already reserved,
+/// or `None` once the insert succeeded.
+fn claim(
+    conn: &Connection,
+    insert: &str,
+    insert_params: impl Params,
+    holder: &str,
+    holder_params: impl Params,
+) -> Result<Option<Uuid>> {
+    if conn.prepare_cached(insert)?.execute(insert_params)? == 1 {
+        return Ok(None);
+    }
+    let holder: String = conn.prepare_cached(holder)?.query_row(holder_params, |row| row.get(0))?;
+    Uuid::parse_str(&holder)
+        .map(Some)
+        .map_err(|e| PersistenceError::SerializationError(e.to_string()))
+}
+
+// =============================================================================
+// API KEY REPOSITORY
+// =============================================================================
+
+/// Repository for machine-client API keys.
+pub struct SqliteApiKeyRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteApiKeyRepository {
+    /// Create a new API key repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Store a newly issued key.
+    pub async fn create(&self, key: &ApiKey) -> Result<()> {
+        let _timer = self.client.metrics.time("api_key.create");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO api_keys (key_id, created_at, doc) VALUES (?1, ?2, ?3)",
+            )?
+            .execute((&key.key_id, key.created_at.timestamp_millis(), to_doc(key)?))?;
+            Ok(())
+        })
+    }
+
+    /// Get a key by ID, revoked or not.
+    pub async fn get(&self, key_id: &str) -> Result<Option<ApiKey>> {
+        let _timer = self.client.metrics.time("api_key.get");
+        self.client.call(|conn| {
+            select_doc(conn, "SELECT doc FROM api_keys WHERE key_id = ?1", (key_id,))
+        })
+    }
+
+    /// List every key, ordered by creation time.
+    pub async fn list(&self) -> Result<Vec<ApiKey>> {
+        let _timer = self.client.metrics.time("api_key.list");
+        self.client.call(|conn| {
+            select_docs(conn, "SELECT doc FROM api_keys ORDER BY created_at", ())
+        })
+    }
+
+    /// Replace a key's secret hash.
+    pub async fn rotate(&self, key_id: &str, secret_hash: &[u8], at: DateTime<Utc>) -> Result<()> {
+        let _timer = self.client.metrics.time("api_key.rotate");
+        self.client.call(|conn| {
+            modify_doc(conn, "api_keys", "key_id = ?1", (key_id,), |key: &mut ApiKey| {
+                key.secret_hash = secret_hash.to_vec();
+                key.rotated_at = Some(at);
+                true
+            })?;
+            Ok(())
+        })
+    }
+
+    /// Mark a key revoked.
+    pub async fn revoke(&self, key_id: &str, at: DateTime<Utc>) -> Result<()> {
+        let _timer = self.client.metrics.time("api_key.revoke");
+        self.client.call(|conn| {
+            modify_doc(conn, "api_keys", "key_id = ?1", (key_id,), |key: &mut ApiKey| {
+                key.revoked_at = Some(at);
+                true
+            })?;
+            Ok(())
+        })
+    }
+}
+
+// =============================================================================
+// JOURNAL REPOSITORY
+// =============================================================================
+
+/// Repository for operator journal entries.
+pub struct SqliteJournalRepository {
+    client: Arc<SqliteClient>,
+}
+
+impl SqliteJournalRepository {
+    /// Create a new journal repository.
+    pub fn new(client: Arc<SqliteClient>) -> Self {
+        Self { client }
+    }
+
+    /// Record a journal entry.
+    pub async fn record(&self, entry: &JournalEntry) -> Result<()> {
+        let _timer = self.client.metrics.time("journal.record");
+        self.client.call(|conn| {
+            conn.prepare_cached(
+                "INSERT OR REPLACE INTO journal_entries (convoy_id, entry_time, entry_id, doc) \
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?
+            .execute((
+                entry.convoy_id.to_string(),
+                entry.entry_time.timestamp_millis(),
+                entry.entry_id.to_string(),
+                to_doc(entry)?,
+            ))?;
+            Ok(())
+        })
+    }
+
+    /// Get a convoy's journal entries within a time window, oldest first.
+    ///
+    /// When the window holds more than `limit`, the most recent are kept.
+    pub async fn get_range(
+        &self,
+        convoy_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<JournalEntry>> {
+        let _timer = self.client.metrics.time("journal.get_range");
+        let mut entries: Vec<JournalEntry> = self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM journal_entries \
+                 WHERE convoy_id = ?1 AND entry_time >= ?2 AND entry_time <= ?3 \
+                 ORDER BY entry_time DESC, entry_id LIMIT ?4",
+                (
+                    convoy_id.to_string(),
+                    start.timestamp_millis(),
+                    end.timestamp_millis(),
+                    sql_limit(limit),
+                ),
+            )
+        })?;
+        entries.reverse();
+        Ok(entries)
+    }
+
+    /// Get a convoy's most recent journal entries, newest first.
+    pub async fn get_recent(&self, convoy_id: Uuid, limit: usize) -> Result<Vec<JournalEntry>> {
+        let _timer = self.client.metrics.time("journal.get_recent");
+        self.client.call(|conn| {
+            select_docs(
+                conn,
+                "SELECT doc FROM journal_entries WHERE convoy_id = ?1 \
+                 ORDER BY entry_time DESC, entry_id LIMIT ?2",
+                (convoy_id.to_string(), sql_limit(limit)),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use drone_domain::{
+        ArtifactKind, ConvoyStatus, ConvoyTemplate, Coordinates, GeoPoint, TargetType,
+        TemplateKind, WeaponState, ZoneEnforcement,
+    };
+
+    fn client() -> Arc<SqliteClient> {
+        Arc::new(SqliteClient::in_memory().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_leaderboard_ranks_follow_score() {
+        let repo = SqliteLeaderboardRepository::new(client(), None);
+        let convoy_id = Uuid::new_v4();
+        let (alpha, bravo) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let first = repo
+            .update_entry(convoy_id, alpha, "ALPHA", PlatformType::Mq9Reaper, false)
+            .await
+            .unwrap();
+        assert_eq!(first.entry.rank, 1);
+        assert_eq!(first.old_rank, None);
+
+        let second = repo
+            .update_entry(convoy_id, bravo, "BRAVO", PlatformType::Mq9Reaper, true)
+            .await
+            .unwrap();
+        assert_eq!(second.entry.rank, 1);
+
+        let board = repo.get_leaderboard(convoy_id, 10).await.unwrap();
+        let ranks: Vec<_> = board.iter().map(|e| (e.drone_id, e.rank)).collect();
+        assert_eq!(ranks, vec![(bravo, 1), (alpha, 2)]);
+
+        let history = repo
+            .rank_history(convoy_id, alpha, DateTime::<Utc>::MIN_UTC, Utc::now())
+            .await
+            .unwrap();
+        // Rows share a key when recorded in the same millisecond; the latest wins
+        assert_eq!(history.last().map(|h| h.rank), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_scoring_model_persists_and_rescores() {
+        let client = client();
+        let repo = SqliteLeaderboardRepository::new(client.clone(), None);
+        let convoy_id = Uuid::new_v4();
+        let (alpha, bravo) = (Uuid::new_v4(), Uuid::new_v4());
+
+        repo.set_scoring_model(convoy_id, ScoringModel::Accuracy).await.unwrap();
+        repo.update_entry(convoy_id, alpha, "ALPHA", PlatformType::Mq9Reaper, true)
+            .await
+            .unwrap();
+        for hit in [true, true, true, true, true, true, true, true, true, false] {
+            repo.update_entry(convoy_id, bravo, "BRAVO", PlatformType::Mq9Reaper, hit)
+                .await
+                .unwrap();
+        }
+        let top = repo.get_leaderboard(convoy_id, 1).await.unwrap();
+        assert_eq!(top.iter().map(|e| e.drone_id).collect::<Vec<_>>(), vec![alpha]);
+
+        // A fresh repository reads the stored model back
+        let reopened = SqliteLeaderboardRepository::new(client, None);
+        assert_eq!(reopened.scoring_model(convoy_id).await.unwrap(), ScoringModel::Accuracy);
+
+        reopened
+            .set_scoring_model(convoy_id, ScoringModel::WilsonLowerBound)
+            .await
+            .unwrap();
+        let stored = reopened.get_drone_entry(convoy_id, bravo).await.unwrap().unwrap();
+        assert_eq!(stored.rank, 1);
+        assert!((stored.score - ScoringModel::WilsonLowerBound.score(9, 10)).abs() < 1e-9);
+        let top = reopened.get_leaderboard(convoy_id, 1).await.unwrap();
+        assert_eq!(top[0].drone_id, bravo);
+    }
+
+    #[tokio::test]
+    async fn test_claims_report_existing_holder() {
+        let repo = SqliteDroneRepository::new(client());
+        let convoy_id = Uuid::new_v4();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert_eq!(repo.claim_callsign(convoy_id, "REAPER-1", first).await.unwrap(), None);
+        assert_eq!(
+            repo.claim_callsign(convoy_id, "REAPER-1", second).await.unwrap(),
+            Some(first)
+        );
+        assert_eq!(repo.claim_tail_number("AF-001", convoy_id, first).await.unwrap(), None);
+        assert_eq!(
+            repo.claim_tail_number("AF-001", Uuid::new_v4(), second).await.unwrap(),
+            Some(first)
+        );
+
+        // Only the holder can release
+        repo.release_callsign(convoy_id, "REAPER-1", second).await.unwrap();
+        assert_eq!(repo.find_by_callsign(convoy_id, "REAPER-1").await.unwrap(), Some(first));
+        repo.release_callsign(convoy_id, "REAPER-1", first).await.unwrap();
+        assert_eq!(repo.claim_callsign(convoy_id, "REAPER-1", second).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_convoy_owner_carries_environment() {
+        let repo = SqliteConvoyRepository::new(client());
+        let mut convoy = ConvoyTemplate::builtin(TemplateKind::Strike4Ship).provision("VMU-1").convoy;
+        convoy.environment = Environment::Exercise;
+        repo.create(&convoy).await.unwrap();
+
+        let owner = repo.owner(convoy.convoy_id).await.unwrap().unwrap();
+        assert_eq!(owner.commanding_unit, "VMU-1");
+        assert_eq!(owner.environment, Environment::Exercise);
+        assert_eq!(repo.owner_for_drone(convoy.drone_ids[0]).await.unwrap(), Some(owner));
+
+        assert_eq!(
+            repo.list_ids_by_unit("VMU-1", Environment::Exercise).await.unwrap(),
+            vec![convoy.convoy_id]
+        );
+        assert!(repo.list_ids_by_unit("VMU-1", Environment::Live).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_authorization_decided_once() {
+        let repo = SqliteAuthorizationRepository::new(client());
+        let auth = EngagementAuthorization {
+            convoy_id: Uuid::new_v4(),
+            request_id: Uuid::new_v4(),
+            drone_id: Uuid::new_v4(),
+            weapon_type: WeaponType::Agm114Hellfire,
+            target_type: TargetType::Vehicle,
+            target_coordinates: Coordinates::new(34.5, 69.2, 1800.0),
+            requested_by: "operator".to_string(),
+            justification: "hostile convoy".to_string(),
+            requested_at: Utc::now(),
+            status: AuthorizationStatus::Pending,
+            decided_by: None,
+            decided_at: None,
+            decision_notes: None,
+            expires_at: None,
+            engagement_id: None,
+        };
+        repo.create(&auth).await.unwrap();
+
+        let decided = repo
+            .decide(auth.convoy_id, auth.request_id, AuthorizationStatus::Approved, "cmdr", None, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(decided.status, AuthorizationStatus::Approved);
+        assert_eq!(decided.decided_by.as_deref(), Some("cmdr"));
+
+        let again = repo
+            .decide(auth.convoy_id, auth.request_id, AuthorizationStatus::Denied, "cmdr", None, None)
+            .await;
+        assert!(matches!(again, Err(PersistenceError::WriteConflict(_))));
+
+        let engagement_id = Uuid::new_v4();
+        assert!(repo.mark_executed(auth.convoy_id, auth.request_id, engagement_id).await.unwrap());
+        assert!(!repo.mark_executed(auth.convoy_id, auth.request_id, engagement_id).await.unwrap());
+        let missing = repo
+            .decide(auth.convoy_id, Uuid::new_v4(), AuthorizationStatus::Approved, "cmdr", None, None)
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_weapon_compare_and_set_detects_stale_read() {
+        let repo = SqliteWeaponsRepository::new(client());
+        let drone_id = Uuid::new_v4();
+        let loaded = WeaponStatus {
+            weapon_type: WeaponType::Agm114Hellfire,
+            rounds_remaining: 4,
+            status: WeaponState::Armed,
+        };
+        repo.set_loadout(drone_id, std::slice::from_ref(&loaded)).await.unwrap();
+
+        let fired = WeaponStatus { rounds_remaining: 3, ..loaded.clone() };
+        assert!(repo.compare_and_set(drone_id, &loaded, &fired).await.unwrap());
+        assert!(!repo.compare_and_set(drone_id, &loaded, &fired).await.unwrap());
+
+        let stored = repo.get(drone_id, WeaponType::Agm114Hellfire).await.unwrap().unwrap();
+        assert_eq!(stored.rounds_remaining, 3);
+    }
+
+    #[tokio::test]
+    async fn test_no_strike_zones_replace_and_delete() {
+        let repo = SqliteConvoyRepository::new(client());
+        let convoy_id = Uuid::new_v4();
+        let mut zone = NoStrikeZone {
+            convoy_id,
+            zone_id: Uuid::new_v4(),
+            name: "HOSPITAL".to_string(),
+            vertices: vec![
+                GeoPoint { latitude: 31.0, longitude: 65.0 },
+                GeoPoint { latitude: 31.0, longitude: 65.1 },
+                GeoPoint { latitude: 31.1, longitude: 65.1 },
+            ],
+            enforcement: ZoneEnforcement::Block,
+            notes: None,
+            created_by: "PLANS".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        repo.put_no_strike_zone(&zone).await.unwrap();
+        zone.enforcement = ZoneEnforcement::Flag;
+        repo.put_no_strike_zone(&zone).await.unwrap();
+
+        assert_eq!(repo.list_no_strike_zones(convoy_id).await.unwrap(), vec![zone.clone()]);
+        assert!(repo.list_no_strike_zones(Uuid::new_v4()).await.unwrap().is_empty());
+
+        repo.delete_no_strike_zone(convoy_id, zone.zone_id).await.unwrap();
+        assert!(repo.list_no_strike_zones(convoy_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_status_update_and_artifacts() {
+        let repo = SqliteConvoyRepository::new(client());
+        let mut convoy = ConvoyTemplate::builtin(TemplateKind::Isr2Ship).provision("VMU-1").convoy;
+        repo.create(&convoy).await.unwrap();
+        convoy.status = ConvoyStatus::Complete;
+        convoy.mission_end = Some(Utc::now());
+        repo.update_status(&convoy).await.unwrap();
+        assert_eq!(repo.get(convoy.convoy_id).await.unwrap(), Some(convoy.clone()));
+
+        let artifact = |kind, digest: &str| MissionArtifact {
+            convoy_id: convoy.convoy_id,
+            kind,
+            digest: digest.to_string(),
+            file_name: "leaderboard.csv".to_string(),
+            content_type: "text/csv".to_string(),
+            size_bytes: 3,
+            created_at: Utc::now(),
+        };
+        repo.put_artifact(&artifact(ArtifactKind::Leaderboard, "aa"), b"old").await.unwrap();
+        let latest = artifact(ArtifactKind::Leaderboard, "bb");
+        repo.put_artifact(&latest, b"new").await.unwrap();
+
+        assert_eq!(repo.list_artifacts(convoy.convoy_id).await.unwrap(), vec![latest]);
+        assert_eq!(repo.get_artifact_content("bb").await.unwrap(), Some(b"new".to_vec()));
+        assert_eq!(repo.get_artifact_content("cc").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_handoffs_listed_oldest_first_per_target() {
+        let repo = SqliteTargetRepository::new(client());
+        let convoy_id = Uuid::new_v4();
+        let (target_a, target_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let start = Utc::now();
+        let handoff = |target_id, minutes| TargetHandoff {
+            convoy_id,
+            target_id,
+            handed_off_at: start + chrono::Duration::minutes(minutes),
+            handoff_id: Uuid::new_v4(),
+            from_drone_id: Uuid::new_v4(),
+            to_drone_id: Uuid::new_v4(),
+            reason: Some("BINGO fuel".to_string()),
+            handed_off_by: "OPS".to_string(),
+        };
+        let (late, early) = (handoff(target_a, 10), handoff(target_a, 2));
+        for h in [&late, &early, &handoff(target_b, 5)] {
+            repo.record_handoff(h).await.unwrap();
+        }
+
+        assert_eq!(repo.list_handoffs(convoy_id, Some(target_a)).await.unwrap(), vec![early, late]);
+        assert_eq!(repo.list_handoffs(convoy_id, None).await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,133 @@
+//! # Retry Policy
+//!
+//! Bounded retries with exponential backoff for transient backend errors.
+//! Only errors that report [`PersistenceError::is_retryable`] are retried,
+//! so callers must only wrap idempotent operations.
+//!
+//! [`PersistenceError::is_retryable`]: crate::PersistenceError::is_retryable
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::error::Result;
+
+/// Retry tuning
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    /// Attempts including the first; 1 disables retries
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further retry
+    pub base_delay: Duration,
+    /// Upper bound on a single delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Run `op` until it succeeds, fails with a non-retryable error, or
+    /// runs out of attempts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the last attempt.
+    pub async fn run<T, F, Fut>(&self, mut op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match op().await {
+                Err(e) if e.is_retryable() && attempt < self.max_attempts => {
+                    let delay = self.delay(attempt);
+                    tracing::debug!(attempt, ?delay, error = %e, "Retrying after transient error");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Delay after the `attempt`-th failed attempt
+    fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PersistenceError;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast(max_attempts: u32) -> RetryConfig {
+        RetryConfig {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors() {
+        let calls = AtomicU32::new(0);
+        let result = fast(3)
+            .run(|| async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err(PersistenceError::Timeout("read".to_string())),
+                    1 => Err(PersistenceError::Unavailable("overloaded".to_string())),
+                    n => Ok(n),
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_on_permanent_errors_and_exhaustion() {
+        let calls = AtomicU32::new(0);
+        let result: Result<()> = fast(5)
+            .run(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(PersistenceError::SchemaMismatch("undefined column".to_string()))
+            })
+            .await;
+        assert!(matches!(result, Err(PersistenceError::SchemaMismatch(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<()> = fast(2)
+            .run(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(PersistenceError::Timeout("write".to_string()))
+            })
+            .await;
+        assert!(matches!(result, Err(PersistenceError::Timeout(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let config = RetryConfig {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_millis(300),
+        };
+        let delays: Vec<_> = (1..=5).map(|a| config.delay(a).as_millis()).collect();
+        assert_eq!(delays, vec![50, 100, 200, 300, 300]);
+    }
+}
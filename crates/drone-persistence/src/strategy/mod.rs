@@ -16,6 +16,10 @@
 //! - `WriteBack` - Write cache first, async DB write
 //! - `DbOnly` - Write DB only, no cache interaction
 //!
+//! ### Runtime Switching
+//! Repositories hold a `DynamicStrategy`; the `StrategyRegistry` swaps it
+//! from a JSON file or Redis key without a restart.
+//!
 //! ## Example
 //!
 //! ```rust,ignore
@@ -32,7 +36,9 @@
 //! ```
 
 pub mod read_strategy;
+pub mod registry;
 pub mod write_strategy;
 
 pub use read_strategy::{CacheError, DbError, ReadError, ReadStrategy};
+pub use registry::{DynamicStrategy, StrategyConfig, StrategyPair, StrategyRegistry, StrategySource};
 pub use write_strategy::{WriteError, WriteStrategy};
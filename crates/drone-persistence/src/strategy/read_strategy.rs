@@ -1,10 +1,12 @@
 //! Read strategy implementations using enum dispatch.
 
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::future::Future;
 
 /// Read strategy enum - determines cache/db access pattern.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ReadStrategy {
     /// Check cache first, fall back to DB on miss
     #[default]
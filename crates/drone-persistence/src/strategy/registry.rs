@@ -0,0 +1,290 @@
+//! Runtime-switchable strategies and the registry that reloads them.
+//!
+//! Repositories hold a [`DynamicStrategy`] handle instead of a fixed pair.
+//! The [`StrategyRegistry`] maps repository names to those handles and
+//! applies overrides read from a JSON file or Redis key, e.g.
+//!
+//! ```json
+//! { "leaderboard": { "read": "db_only", "write": "db_only" } }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use super::{ReadStrategy, WriteStrategy};
+use crate::breaker::CircuitBreaker;
+use crate::cache::SharedCacheClient;
+use crate::error::{PersistenceError, Result};
+
+/// Read/write strategy pair for one repository.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StrategyPair {
+    #[serde(default)]
+    pub read: ReadStrategy,
+    #[serde(default)]
+    pub write: WriteStrategy,
+}
+
+/// Strategy overrides keyed by repository name.
+pub type StrategyConfig = HashMap<String, StrategyPair>;
+
+/// Pair used while the cache breaker is open.
+const DEGRADED: StrategyPair = StrategyPair {
+    read: ReadStrategy::DbOnly,
+    write: WriteStrategy::DbOnly,
+};
+
+/// Shared, swappable strategy pair.
+///
+/// Clones observe the same pair, so a swap through the registry is seen by
+/// the repository on its next call. When tied to a cache circuit breaker,
+/// the effective pair drops to `DbOnly` while that breaker is open and
+/// returns to the configured pair once it recovers.
+#[derive(Debug, Clone, Default)]
+pub struct DynamicStrategy {
+    inner: Arc<RwLock<StrategyPair>>,
+    cache_breaker: Option<Arc<CircuitBreaker>>,
+}
+
+impl DynamicStrategy {
+    /// Create a handle starting at the given strategies.
+    #[must_use]
+    pub fn new(read: ReadStrategy, write: WriteStrategy) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(StrategyPair { read, write })),
+            cache_breaker: None,
+        }
+    }
+
+    /// Degrade to `DbOnly` while `breaker` is open.
+    #[must_use]
+    pub fn degrade_on(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.cache_breaker = Some(breaker);
+        self
+    }
+
+    /// Whether the cache breaker is currently forcing `DbOnly`.
+    #[must_use]
+    pub fn is_degraded(&self) -> bool {
+        self.cache_breaker.as_ref().is_some_and(|b| b.is_open())
+    }
+
+    /// Configured strategy pair, ignoring breaker degradation.
+    #[must_use]
+    pub fn configured(&self) -> StrategyPair {
+        self.inner.read().map(|p| *p).unwrap_or_default()
+    }
+
+    /// Effective strategy pair.
+    #[must_use]
+    pub fn get(&self) -> StrategyPair {
+        if self.is_degraded() {
+            DEGRADED
+        } else {
+            self.configured()
+        }
+    }
+
+    /// Current read strategy.
+    #[must_use]
+    pub fn read(&self) -> ReadStrategy {
+        self.get().read
+    }
+
+    /// Current write strategy.
+    #[must_use]
+    pub fn write(&self) -> WriteStrategy {
+        self.get().write
+    }
+
+    /// Replace the pair, returning whether it changed.
+    pub fn set(&self, pair: StrategyPair) -> bool {
+        match self.inner.write() {
+            Ok(mut current) if *current != pair => {
+                *current = pair;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Replace only the read strategy.
+    pub fn set_read(&self, read: ReadStrategy) {
+        self.set(StrategyPair { read, ..self.configured() });
+    }
+
+    /// Replace only the write strategy.
+    pub fn set_write(&self, write: WriteStrategy) {
+        self.set(StrategyPair { write, ..self.configured() });
+    }
+}
+
+/// Where strategy overrides are read from.
+#[derive(Clone)]
+pub enum StrategySource {
+    /// JSON file on disk
+    File(PathBuf),
+    /// JSON value stored under a Redis key
+    Redis { cache: SharedCacheClient, key: String },
+}
+
+/// Named strategy handles that can be swapped at runtime.
+#[derive(Debug, Default)]
+pub struct StrategyRegistry {
+    entries: RwLock<HashMap<String, DynamicStrategy>>,
+}
+
+impl StrategyRegistry {
+    /// Create an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a repository's strategy handle under `name`.
+    pub fn register(&self, name: impl Into<String>, strategy: DynamicStrategy) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.insert(name.into(), strategy);
+        }
+    }
+
+    /// Look up the handle registered under `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<DynamicStrategy> {
+        self.entries.read().ok()?.get(name).cloned()
+    }
+
+    /// Current strategies for every registered repository.
+    #[must_use]
+    pub fn snapshot(&self) -> StrategyConfig {
+        self.entries
+            .read()
+            .map(|entries| entries.iter().map(|(k, v)| (k.clone(), v.get())).collect())
+            .unwrap_or_default()
+    }
+
+    /// Apply overrides, returning how many repositories changed.
+    ///
+    /// Names without a registered repository are logged and skipped.
+    pub fn apply(&self, config: &StrategyConfig) -> usize {
+        let Ok(entries) = self.entries.read() else {
+            return 0;
+        };
+
+        let mut changed = 0;
+        for (name, pair) in config {
+            match entries.get(name) {
+                Some(handle) => {
+                    if handle.set(*pair) {
+                        tracing::info!(
+                            repository = %name,
+                            read = ?pair.read,
+                            write = ?pair.write,
+                            "Persistence strategy switched"
+                        );
+                        changed += 1;
+                    }
+                }
+                None => tracing::warn!(repository = %name, "Unknown repository in strategy config"),
+            }
+        }
+        changed
+    }
+
+    /// Load overrides from `source` and apply them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source cannot be read or parsed. A missing
+    /// Redis key is not an error and leaves strategies unchanged.
+    pub async fn reload(&self, source: &StrategySource) -> Result<usize> {
+        let config: StrategyConfig = match source {
+            StrategySource::File(path) => {
+                let raw = tokio::fs::read_to_string(path).await.map_err(|e| {
+                    PersistenceError::Config(format!("{}: {e}", path.display()))
+                })?;
+                serde_json::from_str(&raw)?
+            }
+            StrategySource::Redis { cache, key } => match cache.get_value(key).await? {
+                Some(config) => config,
+                None => return Ok(0),
+            },
+        };
+        Ok(self.apply(&config))
+    }
+
+    /// Poll `source` every `interval` and apply any changes.
+    ///
+    /// The task runs until aborted; read errors are logged and retried on the
+    /// next tick.
+    pub fn watch(
+        self: Arc<Self>,
+        source: StrategySource,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.reload(&source).await {
+                    tracing::warn!(error = %e, "Failed to reload persistence strategies");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_swaps_registered_handles() {
+        let registry = StrategyRegistry::new();
+        let handle = DynamicStrategy::new(ReadStrategy::CacheFirst, WriteStrategy::WriteThrough);
+        registry.register("leaderboard", handle.clone());
+
+        let config: StrategyConfig = serde_json::from_str(
+            r#"{"leaderboard": {"read": "db_only", "write": "db_only"}, "unknown": {}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(registry.apply(&config), 1);
+        assert_eq!(handle.read(), ReadStrategy::DbOnly);
+        assert_eq!(handle.write(), WriteStrategy::DbOnly);
+
+        // Re-applying the same config is a no-op
+        assert_eq!(registry.apply(&config), 0);
+    }
+
+    #[test]
+    fn test_open_cache_breaker_degrades_to_db_only() {
+        use crate::breaker::BreakerConfig;
+
+        let breaker = Arc::new(CircuitBreaker::new(
+            "redis",
+            BreakerConfig {
+                minimum_calls: 1,
+                ..BreakerConfig::default()
+            },
+        ));
+        let handle = DynamicStrategy::new(ReadStrategy::CacheFirst, WriteStrategy::WriteThrough)
+            .degrade_on(breaker.clone());
+        assert_eq!(handle.read(), ReadStrategy::CacheFirst);
+
+        breaker.record_failure();
+        assert!(handle.is_degraded());
+        assert_eq!(handle.get(), DEGRADED);
+        assert_eq!(handle.configured().write, WriteStrategy::WriteThrough);
+    }
+
+    #[test]
+    fn test_partial_pair_uses_defaults() {
+        let pair: StrategyPair = serde_json::from_str(r#"{"write": "write_around"}"#).unwrap();
+        assert_eq!(pair.read, ReadStrategy::CacheFirst);
+        assert_eq!(pair.write, WriteStrategy::WriteAround);
+    }
+}
@@ -1,12 +1,14 @@
 //! Write strategy implementations using enum dispatch.
 
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::future::Future;
 
 use super::read_strategy::{CacheError, DbError};
 
 /// Write strategy enum - determines cache/db write pattern.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WriteStrategy {
     /// Write to both cache and DB synchronously
     #[default]
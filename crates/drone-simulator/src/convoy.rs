@@ -1,13 +1,41 @@
 //! Convoy-level simulation orchestrating multiple drones.
 
+use crate::dynamics::{PerformanceLimits, Wind};
 use crate::engagement::{EngagementSimulator, SimulatedEngagement};
-use crate::flight::{FlightPathGenerator, Waypoint};
+use crate::flight::{Coordinates, FlightPathGenerator, Waypoint};
+use crate::loadout::{Loadout, LoadoutConfig};
+use crate::mission::{MissionProfile, RouteShape};
+use crate::pk::Weather;
 use crate::telemetry::{TelemetryGenerator, TelemetrySnapshot};
 use chrono::{DateTime, Utc};
+use drone_domain::{
+    PlatformCapabilities, PlatformType, SensorStatus, SensorTask, SensorType,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+/// Sensors a platform carries per its capabilities, EO/IR searching and the
+/// rest on standby; unknown platforms carry an MQ-9's.
+fn platform_sensors(platform_type: &str) -> Vec<SensorStatus> {
+    let platform_type = PlatformType::parse(platform_type).unwrap_or(PlatformType::Mq9Reaper);
+    PlatformCapabilities::builtin(platform_type)
+        .sensors
+        .into_iter()
+        .map(|sensor_type| SensorStatus {
+            sensor_type,
+            operational: true,
+            mode: match sensor_type {
+                SensorType::EoIr => "WIDE_AREA",
+                _ => "STANDBY",
+            }
+            .to_string(),
+        })
+        .collect()
+}
+
 /// Simulated drone in convoy.
 pub struct SimulatedDrone {
     pub drone_id: Uuid,
@@ -18,15 +46,50 @@ pub struct SimulatedDrone {
     pub engagement_sim: EngagementSimulator,
     pub total_engagements: u32,
     pub successful_hits: u32,
+    /// Weapons left; a dry drone stops engaging and flies ISR
+    pub loadout: Loadout,
+    pub sensors: Vec<SensorStatus>,
+    /// Sensor tasks waiting for the drone to reach their waypoint
+    pub pending_sensor_tasks: Vec<SensorTask>,
 }
 
 impl SimulatedDrone {
     /// Create a new simulated drone.
     pub fn new(callsign: &str, platform_type: &str) -> Self {
-        let drone_id = Uuid::new_v4();
-        let mut flight_gen = FlightPathGenerator::kandahar();
-        let waypoints = flight_gen.generate_mission_path(callsign);
-        let telemetry_gen = TelemetryGenerator::new(drone_id, callsign, waypoints.clone());
+        Self::seeded(callsign, platform_type, rand::random())
+    }
+
+    /// Create a simulated drone whose ID, route and randomness all derive
+    /// from `seed`.
+    pub fn seeded(callsign: &str, platform_type: &str, seed: u64) -> Self {
+        Self::for_mission(callsign, platform_type, &MissionProfile::default(), seed)
+    }
+
+    /// Create a seeded drone flying the route `profile` calls for.
+    pub fn for_mission(
+        callsign: &str,
+        platform_type: &str,
+        profile: &MissionProfile,
+        seed: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let waypoints = FlightPathGenerator::kandahar()
+            .with_seed(rng.next_u64())
+            .generate_route(callsign, profile);
+        let mut drone = Self::on_route(callsign, platform_type, waypoints, rng.next_u64());
+        if profile.sensors_wide_area {
+            drone.switch_to_isr();
+        }
+        drone
+    }
+
+    /// Create a seeded drone flying `waypoints`.
+    pub fn on_route(callsign: &str, platform_type: &str, waypoints: Vec<Waypoint>, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let drone_id = crate::random_uuid(&mut rng);
+        let telemetry_gen = TelemetryGenerator::new(drone_id, callsign, waypoints.clone())
+            .with_limits(PerformanceLimits::for_platform(platform_type))
+            .with_seed(rng.next_u64());
 
         Self {
             drone_id,
@@ -34,10 +97,51 @@ impl SimulatedDrone {
             platform_type: platform_type.to_string(),
             waypoints,
             telemetry_gen,
-            engagement_sim: EngagementSimulator::new(),
+            engagement_sim: EngagementSimulator::new().with_seed(rng.next_u64()),
             total_engagements: 0,
             successful_hits: 0,
+            loadout: Loadout::default(),
+            sensors: platform_sensors(platform_type),
+            pending_sensor_tasks: Vec::new(),
+        }
+        .with_loadout(Loadout::for_platform(platform_type))
+    }
+
+    /// Arm the drone with `loadout`, switching to ISR if it carries nothing.
+    #[must_use]
+    pub fn with_loadout(mut self, loadout: Loadout) -> Self {
+        self.loadout = loadout;
+        if self.loadout.is_dry() {
+            self.switch_to_isr();
+        }
+        self
+    }
+
+    /// Retask the sensors for wide-area search once the drone is out of
+    /// weapons.
+    pub fn switch_to_isr(&mut self) {
+        for sensor in &mut self.sensors {
+            sensor.mode = match sensor.sensor_type {
+                SensorType::Sar => "GMTI",
+                _ => "WIDE_AREA",
+            }
+            .to_string();
+        }
+    }
+
+    /// Apply sensor tasks for waypoints up to `current_waypoint`.
+    pub fn apply_sensor_tasks(&mut self, current_waypoint: u32) -> Vec<SensorTask> {
+        let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_sensor_tasks)
+            .into_iter()
+            .partition(|t| {
+                u32::try_from(t.sequence_number).is_ok_and(|seq| seq <= current_waypoint)
+            });
+        self.pending_sensor_tasks = pending;
+
+        for task in &due {
+            task.apply(&mut self.sensors);
         }
+        due
     }
 
     /// Get current accuracy percentage.
@@ -62,6 +166,18 @@ pub struct ConvoyState {
     pub progress_pct: f32,
 }
 
+/// Sensor contact reported by a drone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorContact {
+    pub drone_id: Uuid,
+    pub callsign: String,
+    pub sensor_type: SensorType,
+    pub mode: String,
+    pub position: Coordinates,
+    pub confidence: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
 /// Convoy operational status.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ConvoyStatus {
@@ -77,24 +193,54 @@ pub struct ConvoySimulator {
     pub convoy_id: Uuid,
     pub callsign: String,
     pub mission_type: String,
-    pub drones: HashMap<Uuid, SimulatedDrone>,
+    /// Routing and engagement behavior for `mission_type`
+    pub profile: MissionProfile,
+    pub drones: BTreeMap<Uuid, SimulatedDrone>,
     pub status: ConvoyStatus,
     pub start_time: DateTime<Utc>,
     mission_progress: f64,
+    rng: StdRng,
 }
 
 impl ConvoySimulator {
     /// Create a new convoy simulation.
     pub fn new(callsign: &str, mission_type: &str, drone_count: usize) -> Self {
-        let convoy_id = Uuid::new_v4();
-        let mut drones = HashMap::new();
+        Self::seeded(callsign, mission_type, drone_count, rand::random())
+    }
+
+    /// Create a convoy simulation that replays identically for the same
+    /// `seed`.
+    ///
+    /// Unknown mission types fly the STRIKE profile.
+    pub fn seeded(callsign: &str, mission_type: &str, drone_count: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let convoy_id = crate::random_uuid(&mut rng);
+        let profile = MissionProfile::from_name(mission_type);
+        let mut drones = BTreeMap::new();
+
+        // Escorts share one lead route and hold station on it
+        let mut formation = match profile.route {
+            RouteShape::Formation { spacing_km } => {
+                let mut generator = FlightPathGenerator::kandahar().with_seed(rng.next_u64());
+                let lead = generator.generate_route(callsign, &profile);
+                Some((generator, lead, spacing_km))
+            }
+            RouteShape::Standard | RouteShape::Search { .. } => None,
+        };
 
         // Generate drones with military callsigns
         let platforms = ["MQ9_REAPER", "MQ1C_GRAY_EAGLE", "RQ4_GLOBAL_HAWK"];
         for i in 0..drone_count {
             let drone_callsign = format!("{}-{:02}", callsign, i + 1);
             let platform = platforms[i % platforms.len()];
-            let drone = SimulatedDrone::new(&drone_callsign, platform);
+            let drone = match formation.as_mut() {
+                Some((generator, lead, spacing_km)) => {
+                    let (north_km, east_km) = vee_slot(i, *spacing_km);
+                    let route = generator.offset_route(lead, north_km, east_km);
+                    SimulatedDrone::on_route(&drone_callsign, platform, route, rng.next_u64())
+                }
+                None => SimulatedDrone::for_mission(&drone_callsign, platform, &profile, rng.next_u64()),
+            };
             drones.insert(drone.drone_id, drone);
         }
 
@@ -102,11 +248,45 @@ impl ConvoySimulator {
             convoy_id,
             callsign: callsign.to_string(),
             mission_type: mission_type.to_string(),
+            profile,
             drones,
             status: ConvoyStatus::Active,
             start_time: Utc::now(),
             mission_progress: 0.0,
+            rng,
+        }
+    }
+
+    /// Arm each drone with its platform's loadout from `loadouts`.
+    #[must_use]
+    pub fn with_loadouts(mut self, loadouts: &LoadoutConfig) -> Self {
+        self.drones = std::mem::take(&mut self.drones)
+            .into_iter()
+            .map(|(id, drone)| {
+                let loadout = loadouts.loadout_for(&drone.platform_type);
+                (id, drone.with_loadout(loadout))
+            })
+            .collect();
+        self
+    }
+
+    /// Fly every drone in a steady wind.
+    #[must_use]
+    pub fn with_wind(mut self, wind: Wind) -> Self {
+        for drone in self.drones.values_mut() {
+            drone.telemetry_gen.set_wind(wind);
+            drone.engagement_sim.set_wind(wind);
+        }
+        self
+    }
+
+    /// Engage targets in `weather`.
+    #[must_use]
+    pub fn with_weather(mut self, weather: Weather) -> Self {
+        for drone in self.drones.values_mut() {
+            drone.engagement_sim.set_weather(weather);
         }
+        self
     }
 
     /// Advance mission progress.
@@ -121,6 +301,11 @@ impl ConvoySimulator {
         }
     }
 
+    /// Mission progress from 0 to 1.
+    pub fn progress(&self) -> f64 {
+        self.mission_progress
+    }
+
     /// Get current convoy state.
     pub fn state(&self) -> ConvoyState {
         ConvoyState {
@@ -134,19 +319,40 @@ impl ConvoySimulator {
         }
     }
 
-    /// Generate telemetry for all drones.
+    /// Generate telemetry for all drones, switching sensor modes for any
+    /// tasked waypoint reached.
     pub fn generate_telemetry(&mut self) -> Vec<TelemetrySnapshot> {
         let progress = self.mission_progress;
         self.drones
             .values_mut()
-            .filter_map(|drone| drone.telemetry_gen.next_snapshot(progress))
+            .filter_map(|drone| {
+                let snapshot = drone.telemetry_gen.next_snapshot(progress)?;
+                drone.apply_sensor_tasks(snapshot.current_waypoint);
+                Some(snapshot)
+            })
             .collect()
     }
 
+    /// Queue a sensor task, e.g. from the `sensorTasks` subscription.
+    ///
+    /// Returns `false` if the drone is not in this convoy.
+    pub fn queue_sensor_task(&mut self, task: SensorTask) -> bool {
+        match self.drones.get_mut(&task.drone_id) {
+            Some(drone) => {
+                drone.pending_sensor_tasks.push(task);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Simulate engagements for drones in target area.
+    ///
+    /// Engagements happen only in the mission profile's engagement window.
+    /// Each shot takes a round from the drone's loadout; drones with
+    /// nothing left do not engage.
     pub fn simulate_engagements(&mut self) -> Vec<SimulatedEngagement> {
-        // Only simulate engagements in middle phase of mission
-        if self.mission_progress < 0.25 || self.mission_progress > 0.75 {
+        if !self.profile.in_engagement_window(self.mission_progress) {
             return vec![];
         }
 
@@ -155,21 +361,29 @@ impl ConvoySimulator {
 
         for drone in self.drones.values_mut() {
             // Random chance of engagement per tick
-            if rand::random::<f32>() > 0.3 {
+            if !self.rng.gen_bool(self.profile.engagement_chance) {
                 continue;
             }
+            let Some(weapon) = drone.loadout.pick(&mut self.rng) else {
+                continue;
+            };
 
             let altitude = drone.waypoints
                 .get(drone.telemetry_gen.current_waypoint())
                 .map(|wp| wp.coordinates.altitude_m)
                 .unwrap_or(5000.0);
 
-            let engagement = drone.engagement_sim.simulate_engagement(
+            let engagement = drone.engagement_sim.simulate_engagement_with(
+                weapon,
                 convoy_id,
                 drone.drone_id,
                 &drone.callsign,
                 altitude,
             );
+            drone.loadout.expend(weapon);
+            if drone.loadout.is_dry() {
+                drone.switch_to_isr();
+            }
 
             drone.total_engagements += 1;
             if engagement.hit {
@@ -182,6 +396,40 @@ impl ConvoySimulator {
         engagements
     }
 
+    /// Simulate sensor contacts reported by drones on station, between
+    /// climb-out and RTB.
+    pub fn simulate_sensor_contacts(&mut self) -> Vec<SensorContact> {
+        if !(0.1..0.9).contains(&self.mission_progress) {
+            return vec![];
+        }
+
+        let mut contacts = Vec::new();
+        for drone in self.drones.values() {
+            if !self.rng.gen_bool(self.profile.sensor_contact_chance) {
+                continue;
+            }
+            let operational: Vec<_> = drone.sensors.iter().filter(|s| s.operational).collect();
+            if operational.is_empty() {
+                continue;
+            }
+            let sensor = operational[self.rng.gen_range(0..operational.len())];
+            let Some(waypoint) = drone.waypoints.get(drone.telemetry_gen.current_waypoint()) else {
+                continue;
+            };
+
+            contacts.push(SensorContact {
+                drone_id: drone.drone_id,
+                callsign: drone.callsign.clone(),
+                sensor_type: sensor.sensor_type,
+                mode: sensor.mode.clone(),
+                position: waypoint.coordinates.clone(),
+                confidence: self.rng.gen_range(0.5..0.95),
+                timestamp: Utc::now(),
+            });
+        }
+        contacts
+    }
+
     /// Get leaderboard sorted by accuracy.
     pub fn leaderboard(&self) -> Vec<LeaderboardEntry> {
         let mut entries: Vec<_> = self.drones.values()
@@ -206,6 +454,14 @@ impl ConvoySimulator {
     }
 }
 
+/// Station `slot` in a vee behind the lead (slot 0), alternating left and
+/// right; returns the (north, east) offset in km.
+fn vee_slot(slot: usize, spacing_km: f64) -> (f64, f64) {
+    let rank = slot.div_ceil(2) as f64;
+    let side = if slot % 2 == 1 { -1.0 } else { 1.0 };
+    (-rank * spacing_km, side * rank * spacing_km)
+}
+
 /// Leaderboard entry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaderboardEntry {
@@ -246,4 +502,71 @@ mod tests {
         let telemetry = convoy.generate_telemetry();
         assert_eq!(telemetry.len(), 3);
     }
+
+    #[test]
+    fn test_engagements_stop_when_dry() {
+        let mut convoy = ConvoySimulator::new("ECHO", "STRIKE", 3);
+        convoy.advance(0.5);
+        let shots: usize = (0..200).map(|_| convoy.simulate_engagements().len()).sum();
+
+        // Two armed MQ-9/MQ-1C with six rounds each; the RQ-4 never engages
+        assert_eq!(shots, 12);
+        for drone in convoy.drones.values() {
+            assert!(drone.loadout.is_dry());
+            assert_eq!(drone.sensors[1].mode, "GMTI");
+        }
+    }
+
+    #[test]
+    fn test_mission_type_shapes_behavior() {
+        let shots = |mission: &str| {
+            let mut convoy = ConvoySimulator::seeded("FOXTROT", mission, 3, 11);
+            convoy.advance(0.5);
+            let shots: usize = (0..20).map(|_| convoy.simulate_engagements().len()).sum();
+            let contacts: usize = (0..20).map(|_| convoy.simulate_sensor_contacts().len()).sum();
+            (shots, contacts)
+        };
+
+        let (strike_shots, strike_contacts) = shots("STRIKE");
+        let (isr_shots, isr_contacts) = shots("ISR");
+        assert!(isr_shots < strike_shots);
+        assert!(isr_contacts > strike_contacts);
+        assert_eq!(shots("SAR").0, 0);
+
+        // Wingmen hold their station in the vee on every leg of the lead's route
+        let escort = ConvoySimulator::seeded("GOLF", "ESCORT", 3, 11);
+        let lead = escort.drones.values().find(|d| d.callsign == "GOLF-01").unwrap();
+        for drone in escort.drones.values() {
+            let north_km = |wp: usize| {
+                (drone.waypoints[wp].coordinates.latitude - lead.waypoints[wp].coordinates.latitude)
+                    * 111.0
+            };
+            let expected = if drone.callsign == "GOLF-01" { 0.0 } else { -1.5 };
+            assert!((north_km(5) - expected).abs() < 1e-9);
+            assert!((north_km(12) - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_sensor_task_applied_at_waypoint() {
+        let mut convoy = ConvoySimulator::new("DELTA", "STRIKE", 1);
+        let drone_id = *convoy.drones.keys().next().unwrap();
+        let task = |sequence_number, sensor_type, mode: &str| SensorTask {
+            drone_id,
+            sequence_number,
+            sensor_type,
+            mode: mode.to_string(),
+            assigned_by: "OPERATOR".to_string(),
+            assigned_at: Utc::now(),
+        };
+
+        assert!(convoy.queue_sensor_task(task(0, SensorType::EoIr, "SPOT_TRACK")));
+        assert!(convoy.queue_sensor_task(task(99, SensorType::Sar, "GMTI")));
+        convoy.generate_telemetry();
+
+        let drone = &convoy.drones[&drone_id];
+        assert_eq!(drone.sensors[0].mode, "SPOT_TRACK");
+        assert_eq!(drone.sensors[1].mode, "STANDBY");
+        assert_eq!(drone.pending_sensor_tasks.len(), 1);
+    }
 }
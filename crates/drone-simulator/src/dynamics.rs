@@ -0,0 +1,340 @@
+//! Kinematic flight model for drone simulation.
+//!
+//! Flies a drone toward its next waypoint within its platform's limits:
+//! heading changes no faster than the max turn rate, altitude no faster
+//! than the climb or descent rate or above the service ceiling, and
+//! airspeed no faster than the acceleration limit. Wind is added to the
+//! air velocity, so drones crab into crosswinds and ground speed differs
+//! from airspeed.
+
+use crate::flight::Coordinates;
+use drone_domain::{PlatformCapabilities, PlatformType};
+use serde::{Deserialize, Serialize};
+
+/// Meters per degree of latitude
+const METERS_PER_DEG_LAT: f64 = 111_320.0;
+
+/// Standard gravity in m/s²
+const GRAVITY_MPS2: f64 = 9.81;
+
+/// Performance envelope of a platform.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct PerformanceLimits {
+    pub cruise_speed_mps: f64,
+    pub min_speed_mps: f64,
+    pub max_speed_mps: f64,
+    /// Max rate of turn in degrees per second
+    pub max_turn_rate_dps: f64,
+    pub max_climb_mps: f64,
+    pub max_descent_mps: f64,
+    pub max_accel_mps2: f64,
+    /// Service ceiling from the platform's capabilities
+    pub ceiling_m: f64,
+}
+
+impl PerformanceLimits {
+    /// Limits for a platform; unknown platforms fly like an MQ-9.
+    pub fn for_platform(platform_type: &str) -> Self {
+        let ceiling_m = PlatformCapabilities::builtin(
+            PlatformType::parse(platform_type).unwrap_or(PlatformType::Mq9Reaper),
+        )
+        .max_altitude_m
+        .value();
+        match platform_type {
+            "MQ1C_GRAY_EAGLE" => Self {
+                cruise_speed_mps: 70.0,
+                min_speed_mps: 30.0,
+                max_speed_mps: 85.0,
+                max_turn_rate_dps: 3.0,
+                max_climb_mps: 5.0,
+                max_descent_mps: 7.0,
+                max_accel_mps2: 1.0,
+                ceiling_m,
+            },
+            "RQ4_GLOBAL_HAWK" => Self {
+                cruise_speed_mps: 160.0,
+                min_speed_mps: 90.0,
+                max_speed_mps: 175.0,
+                max_turn_rate_dps: 1.5,
+                max_climb_mps: 12.0,
+                max_descent_mps: 15.0,
+                max_accel_mps2: 1.0,
+                ceiling_m,
+            },
+            _ => Self {
+                cruise_speed_mps: 85.0,
+                min_speed_mps: 40.0,
+                max_speed_mps: 110.0,
+                max_turn_rate_dps: 3.0,
+                max_climb_mps: 7.5,
+                max_descent_mps: 10.0,
+                max_accel_mps2: 1.5,
+                ceiling_m,
+            },
+        }
+    }
+
+    /// Commanded speed within the envelope; zero means cruise.
+    pub fn commanded_speed(&self, speed_mps: f64) -> f64 {
+        if speed_mps <= 0.0 {
+            self.cruise_speed_mps
+        } else {
+            speed_mps.clamp(self.min_speed_mps, self.max_speed_mps)
+        }
+    }
+
+    /// Radius of a max-rate turn at `speed_mps`.
+    pub fn turn_radius_m(&self, speed_mps: f64) -> f64 {
+        speed_mps / self.max_turn_rate_dps.to_radians()
+    }
+}
+
+impl Default for PerformanceLimits {
+    fn default() -> Self {
+        Self::for_platform("MQ9_REAPER")
+    }
+}
+
+/// Steady wind over the mission area.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct Wind {
+    /// Direction the wind blows from, degrees true
+    pub from_deg: f64,
+    pub speed_mps: f64,
+}
+
+impl Wind {
+    /// Create a wind blowing from `from_deg` at `speed_mps`.
+    pub fn new(from_deg: f64, speed_mps: f64) -> Self {
+        Self {
+            from_deg,
+            speed_mps: speed_mps.max(0.0),
+        }
+    }
+
+    /// Drift velocity as (north, east) in m/s.
+    pub fn drift(&self) -> (f64, f64) {
+        let to = (self.from_deg + 180.0).to_radians();
+        (self.speed_mps * to.cos(), self.speed_mps * to.sin())
+    }
+}
+
+/// Position and motion of a simulated drone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KinematicState {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude_m: f64,
+    /// Direction the nose points, degrees true
+    pub heading_deg: f64,
+    pub airspeed_mps: f64,
+    pub vertical_speed_mps: f64,
+    pub bank_deg: f64,
+    /// Direction of travel over the ground, degrees true
+    pub track_deg: f64,
+    pub ground_speed_mps: f64,
+}
+
+impl KinematicState {
+    /// Start at `position` pointing at `heading_deg`.
+    pub fn at(position: &Coordinates, heading_deg: f64, airspeed_mps: f64) -> Self {
+        Self {
+            latitude: position.latitude,
+            longitude: position.longitude,
+            altitude_m: position.altitude_m,
+            heading_deg,
+            airspeed_mps,
+            vertical_speed_mps: 0.0,
+            bank_deg: 0.0,
+            track_deg: heading_deg,
+            ground_speed_mps: airspeed_mps,
+        }
+    }
+
+    /// Current position, with heading and ground speed.
+    pub fn coordinates(&self) -> Coordinates {
+        Coordinates {
+            latitude: self.latitude,
+            longitude: self.longitude,
+            altitude_m: self.altitude_m,
+            heading_deg: self.heading_deg as f32,
+            speed_mps: self.ground_speed_mps as f32,
+        }
+    }
+
+    /// Ground distance and bearing (degrees true) to `target`.
+    pub fn range_and_bearing(&self, target: &Coordinates) -> (f64, f64) {
+        let (north, east) = offset_m(self.latitude, self.longitude, target);
+        (north.hypot(east), wrap_deg(east.atan2(north).to_degrees()))
+    }
+}
+
+/// Flight model for one drone.
+#[derive(Debug, Clone)]
+pub struct FlightModel {
+    pub limits: PerformanceLimits,
+    pub wind: Wind,
+    pub state: KinematicState,
+}
+
+impl FlightModel {
+    /// Create a model in `state`.
+    pub fn new(limits: PerformanceLimits, wind: Wind, state: KinematicState) -> Self {
+        Self {
+            limits,
+            wind,
+            state,
+        }
+    }
+
+    /// Fly toward `target` for `dt` seconds, correcting for wind so the
+    /// ground track points at it.
+    pub fn step_toward(&mut self, target: &Coordinates, dt: f64) {
+        let (_, bearing) = self.state.range_and_bearing(target);
+        let (wind_n, wind_e) = self.wind.drift();
+        let track = bearing.to_radians();
+        // Wind pushing right of the desired track is positive
+        let crosswind = -wind_n * track.sin() + wind_e * track.cos();
+        let correction = (-crosswind / self.state.airspeed_mps.max(1.0))
+            .clamp(-1.0, 1.0)
+            .asin()
+            .to_degrees();
+
+        let speed = self.limits.commanded_speed(f64::from(target.speed_mps));
+        self.advance(bearing + correction, speed, target.altitude_m, dt);
+    }
+
+    /// Orbit right at the max turn rate toward `altitude_m`.
+    pub fn step_orbit(&mut self, speed_mps: f64, altitude_m: f64, dt: f64) {
+        let speed = self.limits.commanded_speed(speed_mps);
+        self.advance(self.state.heading_deg + 90.0, speed, altitude_m, dt);
+    }
+
+    /// Distance at which a waypoint counts as reached; a drone cannot fly
+    /// through points inside its turn circle.
+    pub fn capture_radius_m(&self) -> f64 {
+        self.limits.turn_radius_m(self.state.airspeed_mps).max(250.0)
+    }
+
+    /// Stop on the ground.
+    pub fn land(&mut self) {
+        let state = &mut self.state;
+        state.airspeed_mps = 0.0;
+        state.ground_speed_mps = 0.0;
+        state.vertical_speed_mps = 0.0;
+        state.bank_deg = 0.0;
+    }
+
+    /// Steer toward a heading, speed and altitude within the limits, then
+    /// move for `dt` seconds.
+    fn advance(&mut self, heading_deg: f64, speed_mps: f64, altitude_m: f64, dt: f64) {
+        if dt <= 0.0 {
+            return;
+        }
+        let limits = self.limits;
+        let state = &mut self.state;
+
+        let max_turn = limits.max_turn_rate_dps * dt;
+        let turn = signed_angle_deg(heading_deg - state.heading_deg).clamp(-max_turn, max_turn);
+        state.heading_deg = wrap_deg(state.heading_deg + turn);
+
+        let max_dv = limits.max_accel_mps2 * dt;
+        state.airspeed_mps += (speed_mps - state.airspeed_mps).clamp(-max_dv, max_dv);
+        let turn_rate = (turn / dt).to_radians();
+        state.bank_deg = (state.airspeed_mps * turn_rate / GRAVITY_MPS2).atan().to_degrees();
+
+        let climb = (altitude_m.min(limits.ceiling_m) - state.altitude_m)
+            .clamp(-limits.max_descent_mps * dt, limits.max_climb_mps * dt);
+        state.altitude_m += climb;
+        state.vertical_speed_mps = climb / dt;
+
+        let (wind_n, wind_e) = self.wind.drift();
+        let heading = state.heading_deg.to_radians();
+        let north = state.airspeed_mps * heading.cos() + wind_n;
+        let east = state.airspeed_mps * heading.sin() + wind_e;
+        state.latitude += north * dt / METERS_PER_DEG_LAT;
+        state.longitude += east * dt / (METERS_PER_DEG_LAT * state.latitude.to_radians().cos());
+        state.track_deg = wrap_deg(east.atan2(north).to_degrees());
+        state.ground_speed_mps = north.hypot(east);
+    }
+}
+
+/// (north, east) offset in meters from a position to `to`, flat-earth
+/// approximation good over a mission area.
+pub fn offset_m(latitude: f64, longitude: f64, to: &Coordinates) -> (f64, f64) {
+    let north = (to.latitude - latitude) * METERS_PER_DEG_LAT;
+    let east =
+        (to.longitude - longitude) * METERS_PER_DEG_LAT * latitude.to_radians().cos();
+    (north, east)
+}
+
+/// Normalize to `0..360`.
+fn wrap_deg(deg: f64) -> f64 {
+    deg.rem_euclid(360.0)
+}
+
+/// Normalize to `-180..180`.
+fn signed_angle_deg(deg: f64) -> f64 {
+    (deg + 180.0).rem_euclid(360.0) - 180.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start() -> KinematicState {
+        KinematicState::at(&Coordinates::default(), 0.0, 80.0)
+    }
+
+    #[test]
+    fn test_turn_and_climb_are_rate_limited() {
+        let mut model = FlightModel::new(PerformanceLimits::default(), Wind::default(), start());
+        let mut target = Coordinates::default();
+        target.longitude += 0.5;
+        target.altitude_m += 1000.0;
+
+        model.step_toward(&target, 1.0);
+        assert!((model.state.heading_deg - 3.0).abs() < 1e-9);
+        assert!(model.state.bank_deg > 0.0);
+
+        for _ in 0..9 {
+            model.step_toward(&target, 1.0);
+        }
+        assert!((model.state.altitude_m - 5075.0).abs() < 1e-6);
+        assert!((model.state.vertical_speed_mps - 7.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_climb_stops_at_ceiling() {
+        let limits = PerformanceLimits::for_platform("MQ1C_GRAY_EAGLE");
+        assert_eq!(limits.ceiling_m, 8_840.0);
+        let mut model = FlightModel::new(limits, Wind::default(), start());
+
+        for _ in 0..1000 {
+            model.step_orbit(0.0, 12_000.0, 1.0);
+        }
+        assert!((model.state.altitude_m - 8_840.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_crabs_into_crosswind() {
+        let wind = Wind::new(270.0, 15.0);
+        let mut model = FlightModel::new(PerformanceLimits::default(), wind, start());
+        let mut target = Coordinates::default();
+        target.latitude += 0.2;
+
+        for _ in 0..120 {
+            model.step_toward(&target, 1.0);
+        }
+        // Nose left of north against the westerly, track straight north
+        assert!(model.state.heading_deg > 340.0 && model.state.heading_deg < 355.0);
+        assert!(signed_angle_deg(model.state.track_deg).abs() < 0.5);
+        assert!(model.state.ground_speed_mps < model.state.airspeed_mps);
+
+        let reached = (0..300).any(|_| {
+            model.step_toward(&target, 1.0);
+            model.state.range_and_bearing(&target).0 < model.capture_radius_m()
+        });
+        assert!(reached);
+    }
+}
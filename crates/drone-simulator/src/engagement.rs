@@ -1,11 +1,15 @@
 //! Engagement simulation for drone combat scenarios.
 
 use chrono::{DateTime, Utc};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rand_distr::{Distribution, Normal};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::dynamics::Wind;
+use crate::pk::{PkModel, Shot, Weather};
+
 /// Weapon types available for engagement.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum WeaponType {
@@ -50,9 +54,36 @@ impl WeaponType {
         }
     }
 
+    /// The domain weapon type with the same name.
+    pub fn to_domain(self) -> drone_domain::WeaponType {
+        match self {
+            Self::Agm114Hellfire => drone_domain::WeaponType::Agm114Hellfire,
+            Self::Gbu12Paveway => drone_domain::WeaponType::Gbu12Paveway,
+            Self::Aim9xSidewinder => drone_domain::WeaponType::Aim9xSidewinder,
+            Self::Gbu38Jdam => drone_domain::WeaponType::Gbu38Jdam,
+            Self::Agm176Griffin => drone_domain::WeaponType::Agm176Griffin,
+        }
+    }
+
+    /// Parse the string representation produced by [`Self::as_str`].
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "AGM114_HELLFIRE" => Some(Self::Agm114Hellfire),
+            "GBU12_PAVEWAY" => Some(Self::Gbu12Paveway),
+            "AIM9X_SIDEWINDER" => Some(Self::Aim9xSidewinder),
+            "GBU38_JDAM" => Some(Self::Gbu38Jdam),
+            "AGM176_GRIFFIN" => Some(Self::Agm176Griffin),
+            _ => None,
+        }
+    }
+
     /// Get random weapon type.
     pub fn random() -> Self {
-        let mut rng = rand::thread_rng();
+        Self::random_from(&mut rand::thread_rng())
+    }
+
+    /// Get random weapon type drawn from `rng`.
+    pub fn random_from(rng: &mut impl Rng) -> Self {
         match rng.gen_range(0..5) {
             0 => Self::Agm114Hellfire,
             1 => Self::Gbu12Paveway,
@@ -77,7 +108,11 @@ pub enum TargetType {
 impl TargetType {
     /// Get random target type.
     pub fn random() -> Self {
-        let mut rng = rand::thread_rng();
+        Self::random_from(&mut rand::thread_rng())
+    }
+
+    /// Get random target type drawn from `rng`.
+    pub fn random_from(rng: &mut impl Rng) -> Self {
         match rng.gen_range(0..6) {
             0 => Self::Vehicle,
             1 => Self::Personnel,
@@ -112,6 +147,8 @@ pub struct SimulatedEngagement {
     pub target_type: TargetType,
     pub range_km: f64,
     pub altitude_m: f64,
+    /// Pk the model predicted before the shot (0-1)
+    pub predicted_pk: f64,
     pub hit: bool,
     pub timestamp: DateTime<Utc>,
 }
@@ -122,7 +159,10 @@ pub struct EngagementSimulator {
     skill_modifier: f64,
     /// Environmental modifier
     env_modifier: f64,
-    rng: rand::rngs::ThreadRng,
+    pk_model: PkModel,
+    weather: Weather,
+    wind: Wind,
+    rng: StdRng,
     range_noise: Normal<f64>,
 }
 
@@ -132,11 +172,21 @@ impl EngagementSimulator {
         Self {
             skill_modifier: 1.0,
             env_modifier: 1.0,
-            rng: rand::thread_rng(),
+            pk_model: PkModel::new(),
+            weather: Weather::default(),
+            wind: Wind::default(),
+            rng: StdRng::from_entropy(),
             range_noise: Normal::new(0.0, 1.5).unwrap(),
         }
     }
 
+    /// Draw outcomes from a fixed seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
     /// Create with custom skill modifier.
     pub fn with_skill(skill: f64) -> Self {
         Self {
@@ -150,7 +200,24 @@ impl EngagementSimulator {
         self.env_modifier = modifier.clamp(0.7, 1.0);
     }
 
-    /// Simulate an engagement.
+    /// Predict Pk with a custom model.
+    #[must_use]
+    pub fn with_pk_model(mut self, model: PkModel) -> Self {
+        self.pk_model = model;
+        self
+    }
+
+    /// Set the weather at the target.
+    pub fn set_weather(&mut self, weather: Weather) {
+        self.weather = weather;
+    }
+
+    /// Set the wind weapons fly through.
+    pub fn set_wind(&mut self, wind: Wind) {
+        self.wind = wind;
+    }
+
+    /// Simulate an engagement with a random weapon.
     pub fn simulate_engagement(
         &mut self,
         convoy_id: Uuid,
@@ -158,18 +225,35 @@ impl EngagementSimulator {
         callsign: &str,
         altitude_m: f64,
     ) -> SimulatedEngagement {
-        let weapon = WeaponType::random();
-        let target = TargetType::random();
+        let weapon = WeaponType::random_from(&mut self.rng);
+        self.simulate_engagement_with(weapon, convoy_id, drone_id, callsign, altitude_m)
+    }
+
+    /// Simulate an engagement with a specific weapon.
+    pub fn simulate_engagement_with(
+        &mut self,
+        weapon: WeaponType,
+        convoy_id: Uuid,
+        drone_id: Uuid,
+        callsign: &str,
+        altitude_m: f64,
+    ) -> SimulatedEngagement {
+        let target = TargetType::random_from(&mut self.rng);
 
         // Calculate range with noise
         let base_range = weapon.typical_range_km();
         let range = (base_range + self.range_noise.sample(&mut self.rng)).max(0.5);
 
-        // Calculate hit probability
-        let hit = self.calculate_hit(weapon, range, altitude_m);
+        let predicted_pk = self.predict_pk(&Shot {
+            weapon,
+            target,
+            range_km: range,
+            altitude_m,
+        });
+        let hit = self.rng.gen_bool(predicted_pk);
 
         SimulatedEngagement {
-            engagement_id: Uuid::new_v4(),
+            engagement_id: crate::random_uuid(&mut self.rng),
             convoy_id,
             drone_id,
             callsign: callsign.to_string(),
@@ -177,35 +261,16 @@ impl EngagementSimulator {
             target_type: target,
             range_km: range,
             altitude_m,
+            predicted_pk,
             hit,
             timestamp: Utc::now(),
         }
     }
 
-    /// Calculate if engagement results in hit.
-    fn calculate_hit(&mut self, weapon: WeaponType, range_km: f64, altitude_m: f64) -> bool {
-        let base_acc = weapon.base_accuracy();
-        let typical_range = weapon.typical_range_km();
-
-        // Range penalty (accuracy drops at extreme ranges)
-        let range_factor = if range_km <= typical_range {
-            1.0
-        } else {
-            (typical_range / range_km).powf(0.5)
-        };
-
-        // Altitude factor (slightly worse at very high or low altitudes)
-        let alt_factor = if altitude_m >= 3000.0 && altitude_m <= 6000.0 {
-            1.0
-        } else {
-            0.95
-        };
-
-        // Final probability
-        let hit_probability =
-            base_acc * range_factor * alt_factor * self.skill_modifier * self.env_modifier;
-
-        self.rng.gen_bool(hit_probability.clamp(0.1, 0.99))
+    /// Pk of `shot` under current conditions.
+    fn predict_pk(&self, shot: &Shot) -> f64 {
+        let skill = self.skill_modifier * self.env_modifier;
+        self.pk_model.predict(shot, &self.weather, self.wind, skill)
     }
 
     /// Simulate multiple engagements.
@@ -1,6 +1,9 @@
 //! Flight path generation for drone simulation.
 
-use rand::Rng;
+use crate::mission::{MissionProfile, RouteShape};
+use drone_domain::MissionType;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rand_distr::{Distribution, Normal};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -58,7 +61,7 @@ pub struct FlightPathGenerator {
     /// Base altitude in meters
     base_altitude: f64,
     /// RNG
-    rng: rand::rngs::ThreadRng,
+    rng: StdRng,
 }
 
 impl FlightPathGenerator {
@@ -69,10 +72,17 @@ impl FlightPathGenerator {
             center,
             radius_km,
             base_altitude,
-            rng: rand::thread_rng(),
+            rng: StdRng::from_entropy(),
         }
     }
 
+    /// Generate paths from a fixed seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
     /// Create generator for Kandahar AOR.
     pub fn kandahar() -> Self {
         Self::new(
@@ -126,7 +136,7 @@ impl FlightPathGenerator {
         }
 
         // RTB
-        for i in 21..=23 {
+        for i in 21..=22 {
             let name = format!("RTB-{}", i - 20);
             waypoints.push(self.create_waypoint(i, &name, WaypointType::Rtb, None));
         }
@@ -138,6 +148,86 @@ impl FlightPathGenerator {
         waypoints
     }
 
+    /// Generate the route a drone flies for `profile`.
+    ///
+    /// ISR routes orbit every operating-area point instead of running in on
+    /// targets; loiter times are scaled by the profile.
+    pub fn generate_route(&mut self, callsign: &str, profile: &MissionProfile) -> Vec<Waypoint> {
+        let mut waypoints = match profile.route {
+            RouteShape::Search { legs, track_spacing_km } => {
+                self.generate_search_path(legs, track_spacing_km)
+            }
+            RouteShape::Standard | RouteShape::Formation { .. } => {
+                self.generate_mission_path(callsign)
+            }
+        };
+
+        for wp in &mut waypoints {
+            if profile.mission_type == MissionType::Isr && wp.waypoint_type == WaypointType::Target {
+                wp.waypoint_type = WaypointType::Loiter;
+                wp.coordinates.speed_mps = 45.0;
+                wp.loiter_time_sec = Some(self.rng.gen_range(300..900));
+            }
+            if let Some(loiter) = wp.loiter_time_sec.as_mut() {
+                *loiter = (f64::from(*loiter) * profile.loiter_scale).round() as u32;
+            }
+        }
+        waypoints
+    }
+
+    /// Generate a parallel-track search ladder: `legs` east-west tracks
+    /// `track_spacing_km` apart across the mission area, flown low and slow.
+    pub fn generate_search_path(&mut self, legs: u32, track_spacing_km: f64) -> Vec<Waypoint> {
+        let mut waypoints = Vec::with_capacity(legs as usize * 2 + 6);
+        let search_altitude = self.base_altitude * 0.6;
+        let half_width_km = self.radius_km * 0.6;
+        let first_track_km = -f64::from(legs.saturating_sub(1)) * track_spacing_km / 2.0;
+
+        waypoints.push(self.create_waypoint(0, "TAKEOFF", WaypointType::Takeoff, None));
+        waypoints.push(self.create_waypoint(1, "CLIMB", WaypointType::Navigation, None));
+
+        let mut sequence = 2;
+        for leg in 0..legs {
+            let north_km = first_track_km + f64::from(leg) * track_spacing_km;
+            // Alternate direction so each leg starts where the last ended
+            let (start_east, heading) = if leg % 2 == 0 {
+                (-half_width_km, 90.0)
+            } else {
+                (half_width_km, 270.0)
+            };
+            for (end, east_km) in [("A", start_east), ("B", -start_east)] {
+                let coords = self.offset_coordinates(north_km, east_km, search_altitude, heading, 55.0);
+                let name = format!("SEARCH-{}{}", leg + 1, end);
+                waypoints.push(self.waypoint_at(sequence, &name, WaypointType::Navigation, coords));
+                sequence += 1;
+            }
+        }
+
+        waypoints.push(self.create_waypoint(sequence, "RTB", WaypointType::Rtb, None));
+        waypoints.push(self.create_waypoint(sequence + 1, "DESCENT", WaypointType::Navigation, None));
+        waypoints.push(self.create_waypoint(sequence + 2, "LANDING", WaypointType::Landing, None));
+
+        waypoints
+    }
+
+    /// Copy `route` shifted `north_km`/`east_km`, e.g. for a wingman holding
+    /// station on a lead. Waypoints get fresh IDs.
+    pub fn offset_route(&mut self, route: &[Waypoint], north_km: f64, east_km: f64) -> Vec<Waypoint> {
+        route
+            .iter()
+            .map(|wp| {
+                let mut coordinates = wp.coordinates.clone();
+                coordinates.latitude += north_km / 111.0;
+                coordinates.longitude += east_km / (111.0 * coordinates.latitude.to_radians().cos());
+                Waypoint {
+                    id: crate::random_uuid(&mut self.rng),
+                    coordinates,
+                    ..wp.clone()
+                }
+            })
+            .collect()
+    }
+
     /// Create a single waypoint.
     fn create_waypoint(
         &mut self,
@@ -149,7 +239,7 @@ impl FlightPathGenerator {
         let coords = self.random_coordinates_in_area(waypoint_type);
 
         Waypoint {
-            id: Uuid::new_v4(),
+            id: crate::random_uuid(&mut self.rng),
             sequence,
             name: name.to_string(),
             coordinates: coords,
@@ -158,6 +248,43 @@ impl FlightPathGenerator {
         }
     }
 
+    /// Waypoint at fixed coordinates.
+    fn waypoint_at(
+        &mut self,
+        sequence: u32,
+        name: &str,
+        waypoint_type: WaypointType,
+        coordinates: Coordinates,
+    ) -> Waypoint {
+        Waypoint {
+            id: crate::random_uuid(&mut self.rng),
+            sequence,
+            name: name.to_string(),
+            coordinates,
+            waypoint_type,
+            loiter_time_sec: None,
+        }
+    }
+
+    /// Coordinates offset from the mission area center.
+    fn offset_coordinates(
+        &self,
+        north_km: f64,
+        east_km: f64,
+        altitude_m: f64,
+        heading_deg: f32,
+        speed_mps: f32,
+    ) -> Coordinates {
+        Coordinates {
+            latitude: self.center.latitude + north_km / 111.0,
+            longitude: self.center.longitude
+                + east_km / (111.0 * self.center.latitude.to_radians().cos()),
+            altitude_m,
+            heading_deg,
+            speed_mps,
+        }
+    }
+
     /// Generate random coordinates within mission area.
     fn random_coordinates_in_area(&mut self, wp_type: WaypointType) -> Coordinates {
         // Offset from center based on waypoint type
@@ -256,6 +383,23 @@ mod tests {
         assert!(matches!(path[24].waypoint_type, WaypointType::Landing));
     }
 
+    #[test]
+    fn test_search_ladder_and_isr_orbits() {
+        let mut generator = FlightPathGenerator::kandahar().with_seed(3);
+        let sar = generator.generate_route("SAR-01", &MissionProfile::from_name("SAR"));
+        let search: Vec<_> = sar.iter().filter(|wp| wp.name.starts_with("SEARCH-")).collect();
+        assert_eq!(search.len(), 16);
+        assert!(sar.iter().enumerate().all(|(i, wp)| wp.sequence as usize == i));
+        // Tracks 3 km apart, flown below the transit altitude
+        let spacing_km = (search[2].coordinates.latitude - search[0].coordinates.latitude) * 111.0;
+        assert!((spacing_km - 3.0).abs() < 1e-9);
+        assert!(search.iter().all(|wp| wp.coordinates.altitude_m < 5000.0));
+
+        let isr = generator.generate_route("ISR-01", &MissionProfile::from_name("ISR"));
+        assert!(!isr.iter().any(|wp| wp.waypoint_type == WaypointType::Target));
+        assert!(isr.iter().filter_map(|wp| wp.loiter_time_sec).all(|t| t >= 600));
+    }
+
     #[test]
     fn test_interpolate() {
         let generator = FlightPathGenerator::kandahar();
@@ -5,19 +5,46 @@
 //! ## Features
 //!
 //! - Realistic drone flight path generation
+//! - Kinematic flight model with platform turn, climb and speed limits and
+//!   wind drift
 //! - Telemetry data streaming
-//! - Randomized engagement simulation
+//! - Randomized engagement simulation with a probability-of-kill model
+//!   (weapon-target pairing, target type and weather)
+//! - Mission-type behavior: ISR orbits, STRIKE target windows, ESCORT
+//!   formation and SAR search ladders
+//! - Per-platform weapon loadouts with ammunition depletion
 //! - Configurable convoy scenarios
+//! - Library-driven runs with manual ticks and an injectable clock
 
 #![forbid(unsafe_code)]
 #![warn(clippy::all)]
 
 pub mod convoy;
+pub mod dynamics;
 pub mod engagement;
 pub mod flight;
+pub mod loadout;
+pub mod mission;
+pub mod pk;
+pub mod run;
 pub mod telemetry;
 
 pub use convoy::ConvoySimulator;
+pub use dynamics::{PerformanceLimits, Wind};
 pub use engagement::EngagementSimulator;
 pub use flight::FlightPathGenerator;
+pub use loadout::{Loadout, LoadoutConfig};
+pub use mission::MissionProfile;
+pub use pk::{PkModel, Weather};
+pub use run::{SimulationConfig, SimulationRun};
 pub use telemetry::TelemetryGenerator;
+
+use rand::RngCore;
+use uuid::Uuid;
+
+/// Random v4 UUID drawn from `rng`, so seeded runs get repeatable IDs.
+pub(crate) fn random_uuid(rng: &mut impl RngCore) -> Uuid {
+    let mut bytes = [0u8; 16];
+    rng.fill_bytes(&mut bytes);
+    uuid::Builder::from_random_bytes(bytes).into_uuid()
+}
@@ -0,0 +1,219 @@
+//! Weapon loadouts and ammunition depletion.
+//!
+//! Each platform carries a fixed set of stores (an MQ-9 flies with four
+//! Hellfires and two GBU-12s by default). Weapons are picked from what is
+//! left, and a drone with nothing left stops engaging and flies ISR.
+//! Overrides must fit the platform's capabilities: an MQ-1C cannot be
+//! loaded with GBU-38s.
+
+use crate::engagement::WeaponType;
+use drone_domain::{PlatformCapabilities, PlatformType, WeaponState, WeaponStatus};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Rounds of one weapon type carried by a drone.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WeaponStore {
+    pub weapon_type: WeaponType,
+    pub rounds: u32,
+}
+
+/// Weapons carried by a drone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Loadout {
+    pub stores: Vec<WeaponStore>,
+}
+
+impl Loadout {
+    /// Create a loadout from `(weapon, rounds)` pairs.
+    pub fn new(stores: &[(WeaponType, u32)]) -> Self {
+        Self {
+            stores: stores
+                .iter()
+                .map(|&(weapon_type, rounds)| WeaponStore { weapon_type, rounds })
+                .collect(),
+        }
+    }
+
+    /// Default loadout for a platform; unarmed platforms get none.
+    pub fn for_platform(platform_type: &str) -> Self {
+        match platform_type {
+            "MQ9_REAPER" => Self::new(&[
+                (WeaponType::Agm114Hellfire, 4),
+                (WeaponType::Gbu12Paveway, 2),
+            ]),
+            "MQ1C_GRAY_EAGLE" => Self::new(&[
+                (WeaponType::Agm114Hellfire, 4),
+                (WeaponType::Agm176Griffin, 2),
+            ]),
+            _ => Self::default(),
+        }
+    }
+
+    /// Parse a loadout such as `AGM114_HELLFIRE:4,GBU12_PAVEWAY:2`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut loadout = Self::default();
+        for item in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let (weapon, rounds) = item
+                .split_once(':')
+                .ok_or_else(|| format!("expected WEAPON:ROUNDS, got `{item}`"))?;
+            let weapon_type = WeaponType::parse(weapon.trim())
+                .ok_or_else(|| format!("unknown weapon `{weapon}`"))?;
+            let rounds = rounds
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid round count `{rounds}`"))?;
+            loadout.stores.push(WeaponStore { weapon_type, rounds });
+        }
+        Ok(loadout)
+    }
+
+    /// Rounds left of one weapon type.
+    pub fn rounds(&self, weapon_type: WeaponType) -> u32 {
+        self.stores
+            .iter()
+            .filter(|s| s.weapon_type == weapon_type)
+            .map(|s| s.rounds)
+            .sum()
+    }
+
+    /// Rounds left across all weapons.
+    pub fn total_rounds(&self) -> u32 {
+        self.stores.iter().map(|s| s.rounds).sum()
+    }
+
+    /// Stores as domain weapon statuses.
+    pub fn weapon_statuses(&self) -> Vec<WeaponStatus> {
+        self.stores
+            .iter()
+            .map(|s| WeaponStatus {
+                weapon_type: s.weapon_type.to_domain(),
+                rounds_remaining: i16::try_from(s.rounds).unwrap_or(i16::MAX),
+                status: if s.rounds == 0 {
+                    WeaponState::Expended
+                } else {
+                    WeaponState::Armed
+                },
+            })
+            .collect()
+    }
+
+    /// Whether every weapon is expended.
+    pub fn is_dry(&self) -> bool {
+        self.total_rounds() == 0
+    }
+
+    /// Pick a weapon with rounds left, weighted by rounds remaining.
+    pub fn pick(&self, rng: &mut impl Rng) -> Option<WeaponType> {
+        let total = self.total_rounds();
+        if total == 0 {
+            return None;
+        }
+        let mut roll = rng.gen_range(0..total);
+        self.stores.iter().find_map(|s| {
+            if roll < s.rounds {
+                Some(s.weapon_type)
+            } else {
+                roll -= s.rounds;
+                None
+            }
+        })
+    }
+
+    /// Take one round of `weapon_type`; `false` if none are left.
+    pub fn expend(&mut self, weapon_type: WeaponType) -> bool {
+        match self
+            .stores
+            .iter_mut()
+            .find(|s| s.weapon_type == weapon_type && s.rounds > 0)
+        {
+            Some(store) => {
+                store.rounds -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Loadouts per platform, falling back to [`Loadout::for_platform`].
+#[derive(Debug, Clone, Default)]
+pub struct LoadoutConfig {
+    overrides: HashMap<String, Loadout>,
+}
+
+impl LoadoutConfig {
+    /// Override the loadout for one platform.
+    #[must_use]
+    pub fn with_platform(mut self, platform_type: &str, loadout: Loadout) -> Self {
+        self.overrides.insert(platform_type.to_string(), loadout);
+        self
+    }
+
+    /// Parse a platform override such as
+    /// `MQ9_REAPER=AGM114_HELLFIRE:4,GBU12_PAVEWAY:2`, rejecting loadouts
+    /// the platform cannot carry.
+    pub fn parse_override(spec: &str) -> Result<(String, Loadout), String> {
+        let (platform, loadout) = spec
+            .split_once('=')
+            .ok_or_else(|| format!("expected PLATFORM=LOADOUT, got `{spec}`"))?;
+        let platform = platform.trim();
+        let platform_type = PlatformType::parse(platform)
+            .ok_or_else(|| format!("unknown platform `{platform}`"))?;
+        let loadout = Loadout::parse(loadout)?;
+        PlatformCapabilities::builtin(platform_type)
+            .check_loadout(&loadout.weapon_statuses())
+            .map_err(|e| e.to_string())?;
+        Ok((platform.to_string(), loadout))
+    }
+
+    /// Loadout for a newly launched drone of `platform_type`.
+    pub fn loadout_for(&self, platform_type: &str) -> Loadout {
+        self.overrides
+            .get(platform_type)
+            .cloned()
+            .unwrap_or_else(|| Loadout::for_platform(platform_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loadout_depletes() {
+        let mut loadout = Loadout::for_platform("MQ9_REAPER");
+        assert_eq!(loadout.rounds(WeaponType::Agm114Hellfire), 4);
+        assert!(Loadout::for_platform("RQ4_GLOBAL_HAWK").is_dry());
+
+        let mut rng = rand::thread_rng();
+        while let Some(weapon) = loadout.pick(&mut rng) {
+            assert!(loadout.expend(weapon));
+        }
+        assert!(loadout.is_dry());
+        assert!(!loadout.expend(WeaponType::Gbu12Paveway));
+    }
+
+    #[test]
+    fn test_parse_override() {
+        let (platform, loadout) =
+            LoadoutConfig::parse_override("MQ9_REAPER=AGM114_HELLFIRE:2, GBU38_JDAM:1").unwrap();
+        let config = LoadoutConfig::default().with_platform(&platform, loadout);
+
+        let reaper = config.loadout_for("MQ9_REAPER");
+        assert_eq!(reaper.total_rounds(), 3);
+        assert_eq!(reaper.rounds(WeaponType::Gbu38Jdam), 1);
+        assert_eq!(config.loadout_for("MQ1C_GRAY_EAGLE").total_rounds(), 6);
+        assert!(Loadout::parse("HELLFIRE:2").is_err());
+    }
+
+    #[test]
+    fn test_override_must_fit_platform() {
+        assert!(LoadoutConfig::parse_override("MQ1C_GRAY_EAGLE=GBU38_JDAM:1").is_err());
+        assert!(LoadoutConfig::parse_override("MQ9_REAPER=AGM114_HELLFIRE:12").is_err());
+        assert!(LoadoutConfig::parse_override("RQ4_GLOBAL_HAWK=AGM114_HELLFIRE:1").is_err());
+        assert!(LoadoutConfig::parse_override("X47_PEGASUS=AGM114_HELLFIRE:1").is_err());
+        assert!(LoadoutConfig::parse_override("MQ1C_GRAY_EAGLE=AGM176_GRIFFIN:4").is_ok());
+    }
+}
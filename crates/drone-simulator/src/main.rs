@@ -4,9 +4,15 @@
 
 use anyhow::Result;
 use clap::Parser;
-use drone_simulator::ConvoySimulator;
-use reqwest::Client;
-use serde_json::json;
+use drone_graphql_client::operations::{
+    RecordEngagement, RecordEngagementInput, RecordEngagementVariables, UpdateDroneState,
+    UpdateDroneStateInput, UpdateDroneStateVariables, WeaponLoadoutInput,
+};
+use drone_graphql_client::{Batch, GraphQLClient};
+use drone_simulator::convoy::SimulatedDrone;
+use drone_simulator::run::{SimulationEvent, SystemClock};
+use drone_simulator::mission::parse_mission_type;
+use drone_simulator::{Loadout, LoadoutConfig, SimulationConfig, SimulationRun, Weather, Wind};
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{info, warn};
@@ -20,8 +26,8 @@ struct Args {
     #[arg(short, long, default_value = "ALPHA")]
     callsign: String,
 
-    /// Mission type
-    #[arg(short, long, default_value = "STRIKE")]
+    /// Mission type: ISR, STRIKE, ESCORT, RESUPPLY or SAR
+    #[arg(short, long, default_value = "STRIKE", value_parser = parse_mission)]
     mission: String,
 
     /// Number of drones
@@ -40,6 +46,36 @@ struct Args {
     #[arg(long, default_value = "300")]
     duration: u32,
 
+    /// Platform loadout override, e.g.
+    /// `MQ9_REAPER=AGM114_HELLFIRE:4,GBU12_PAVEWAY:2` (repeatable)
+    #[arg(long = "loadout", value_parser = LoadoutConfig::parse_override)]
+    loadouts: Vec<(String, Loadout)>,
+
+    /// Direction the wind blows from, degrees true
+    #[arg(long, default_value = "315")]
+    wind_from_deg: f64,
+
+    /// Wind speed in m/s (0 for calm)
+    #[arg(long, default_value = "8")]
+    wind_speed_mps: f64,
+
+    /// Visibility at the target in km; laser and IR guidance degrade below 5 km
+    #[arg(long, default_value = "10")]
+    visibility_km: f64,
+
+    /// Precipitation rate at the target in mm/hr
+    #[arg(long, default_value = "0")]
+    precipitation_mm_hr: f64,
+
+    /// Most mutations per batched request; keep at or below the API's
+    /// `MAX_BATCH_OPERATIONS`
+    #[arg(long, default_value = "50")]
+    max_batch: usize,
+
+    /// Seed for a repeatable run
+    #[arg(long)]
+    seed: Option<u64>,
+
     /// Dry run (don't post to API)
     #[arg(long)]
     dry_run: bool,
@@ -59,51 +95,102 @@ async fn main() -> Result<()> {
         args.callsign, args.drones, args.mission
     );
 
-    let mut convoy = ConvoySimulator::new(&args.callsign, &args.mission, args.drones);
-    let client = Client::new();
-    let progress_per_tick = 1.0 / args.duration as f64;
+    let loadouts = args
+        .loadouts
+        .iter()
+        .cloned()
+        .fold(LoadoutConfig::default(), |config, (platform, loadout)| {
+            config.with_platform(&platform, loadout)
+        });
+    let mut config = SimulationConfig::new(&args.callsign, &args.mission, args.drones)
+        .with_ticks(args.duration)
+        .with_tick_interval(Duration::from_millis(args.tick_ms))
+        .with_wind(Wind::new(args.wind_from_deg, args.wind_speed_mps))
+        .with_weather(Weather::new(args.visibility_km, args.precipitation_mm_hr))
+        .with_loadouts(loadouts);
+    if let Some(seed) = args.seed {
+        config = config.with_seed(seed);
+    }
+    let mut run = SimulationRun::new(config, SystemClock);
+    let convoy_id = run.convoy().convoy_id;
+    let client = GraphQLClient::new(&args.api_url).with_max_batch_operations(args.max_batch);
 
-    info!("Convoy ID: {}", convoy.convoy_id);
+    info!("Convoy ID: {}", convoy_id);
     info!("API: {}", args.api_url);
     info!("Tick: {}ms, Duration: {} ticks", args.tick_ms, args.duration);
+    info!("Wind: {:03.0}° at {:.1} m/s", args.wind_from_deg, args.wind_speed_mps);
+    info!(
+        "Weather: {:.1} km visibility, {:.1} mm/hr precipitation",
+        args.visibility_km, args.precipitation_mm_hr
+    );
 
-    for tick in 0..args.duration {
-        // Advance mission
-        convoy.advance(progress_per_tick);
-        let state = convoy.state();
+    // Report initial loadouts so the API tracks each drone's inventory
+    if !args.dry_run {
+        let mut batch = Batch::new();
+        for drone in run.convoy().drones.values() {
+            queue_drone_state(&mut batch, convoy_id, drone)?;
+        }
+        flush(&client, &mut batch).await;
+    }
 
-        // Generate telemetry
-        let telemetry = convoy.generate_telemetry();
+    let mut events = Vec::new();
+    let mut batch = Batch::new();
+    while run.tick(&mut events) {
+        let tick = run.current_tick();
+        let convoy = run.convoy();
+        let state = convoy.state();
+        let telemetry = events
+            .iter()
+            .filter(|e| matches!(e, SimulationEvent::Telemetry(_)))
+            .count();
         info!(
             "Tick {}/{} | Progress: {:.1}% | Status: {:?} | Telemetry: {} snapshots",
-            tick + 1,
+            tick,
             args.duration,
             state.progress_pct,
             state.status,
-            telemetry.len()
+            telemetry
         );
 
-        // Simulate engagements
-        let engagements = convoy.simulate_engagements();
-        if !engagements.is_empty() {
-            for e in &engagements {
-                let result = if e.hit { "HIT" } else { "MISS" };
-                info!(
-                    "  {} {} | {} | {} @ {:.1}km",
-                    e.callsign, result, e.weapon_type.as_str(), e.target_type.as_str(), e.range_km
-                );
+        for event in events.drain(..) {
+            match event {
+                SimulationEvent::Engagement(e) => {
+                    let result = if e.hit { "HIT" } else { "MISS" };
+                    info!(
+                        "  {} {} | {} | {} @ {:.1}km",
+                        e.callsign, result, e.weapon_type.as_str(), e.target_type.as_str(), e.range_km
+                    );
 
-                // Post engagement to API
-                if !args.dry_run {
-                    if let Err(err) = post_engagement(&client, &args.api_url, e).await {
-                        warn!("Failed to post engagement: {}", err);
+                    // Queue engagement and remaining rounds for this tick's batch
+                    if !args.dry_run {
+                        queue_engagement(&mut batch, &e)?;
+                        let drone = &convoy.drones[&e.drone_id];
+                        queue_drone_state(&mut batch, convoy_id, drone)?;
                     }
                 }
+                SimulationEvent::SensorContact(c) => {
+                    info!(
+                        "  {} CONTACT | {:?} {} | {:.4},{:.4} ({:.0}%)",
+                        c.callsign,
+                        c.sensor_type,
+                        c.mode,
+                        c.position.latitude,
+                        c.position.longitude,
+                        c.confidence * 100.0
+                    );
+                }
+                SimulationEvent::WeaponsExpended { callsign, .. } => {
+                    info!("  {} WINCHESTER | switching to ISR", callsign);
+                }
+                SimulationEvent::StatusChanged { .. } | SimulationEvent::Telemetry(_) => {}
             }
         }
 
+        // One round trip for everything this tick produced
+        flush(&client, &mut batch).await;
+
         // Show leaderboard periodically
-        if tick % 30 == 0 && tick > 0 {
+        if tick.is_multiple_of(30) && !run.is_finished() {
             let leaderboard = convoy.leaderboard();
             info!("--- LEADERBOARD ---");
             for entry in leaderboard.iter().take(5) {
@@ -115,13 +202,13 @@ async fn main() -> Result<()> {
             }
         }
 
-        sleep(Duration::from_millis(args.tick_ms)).await;
+        sleep(run.tick_interval()).await;
     }
 
     info!("Mission complete!");
 
     // Final leaderboard
-    let leaderboard = convoy.leaderboard();
+    let leaderboard = run.convoy().leaderboard();
     info!("=== FINAL LEADERBOARD ===");
     for entry in &leaderboard {
         info!(
@@ -138,46 +225,75 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// Post engagement to GraphQL API.
-async fn post_engagement(
-    client: &Client,
-    api_url: &str,
-    engagement: &drone_simulator::engagement::SimulatedEngagement,
-) -> Result<()> {
-    let query = r#"
-        mutation RecordEngagement($input: RecordEngagementInput!) {
-            recordEngagement(input: $input) {
-                success
-                newRank
-                rankChange
-                newAccuracyPct
+/// Validate `--mission`, normalised to upper case.
+fn parse_mission(value: &str) -> Result<String, String> {
+    parse_mission_type(value).map(|_| value.trim().to_ascii_uppercase())
+}
+
+/// Post queued mutations as one batched request and log any failures.
+async fn flush(client: &GraphQLClient, batch: &mut Batch) {
+    if batch.is_empty() {
+        return;
+    }
+    match client.execute_batch(batch).await {
+        Ok(results) => {
+            for err in results.into_iter().filter_map(Result::err) {
+                warn!("Batched mutation failed: {}", err);
             }
         }
-    "#;
-
-    let variables = json!({
-        "input": {
-            "convoyId": engagement.convoy_id.to_string(),
-            "droneId": engagement.drone_id.to_string(),
-            "hit": engagement.hit,
-            "weaponType": engagement.weapon_type.as_str(),
-            "targetType": engagement.target_type.as_str(),
-            "rangeKm": engagement.range_km
-        }
-    });
-
-    let response = client
-        .post(api_url)
-        .json(&json!({
-            "query": query,
-            "variables": variables
-        }))
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        warn!("API returned status: {}", response.status());
+        Err(err) => warn!("Failed to post {} batched mutations: {}", batch.len(), err),
     }
+    *batch = Batch::new();
+}
+
+/// Queue an engagement for the GraphQL API.
+fn queue_engagement(
+    batch: &mut Batch,
+    engagement: &drone_simulator::engagement::SimulatedEngagement,
+) -> Result<()> {
+    let variables = RecordEngagementVariables {
+        input: RecordEngagementInput {
+            convoy_id: engagement.convoy_id.to_string(),
+            drone_id: engagement.drone_id.to_string(),
+            hit: engagement.hit,
+            weapon_type: Some(engagement.weapon_type.as_str().to_string()),
+            target_type: Some(engagement.target_type.as_str().to_string()),
+            range_km: Some(engagement.range_km),
+            predicted_pk: Some(engagement.predicted_pk),
+        },
+    };
+
+    batch.push::<RecordEngagement>(variables)?;
+
+    Ok(())
+}
+
+/// Queue a drone's remaining rounds for the GraphQL API.
+fn queue_drone_state(
+    batch: &mut Batch,
+    convoy_id: uuid::Uuid,
+    drone: &SimulatedDrone,
+) -> Result<()> {
+    let weapons = drone
+        .loadout
+        .stores
+        .iter()
+        .map(|store| WeaponLoadoutInput {
+            weapon_type: store.weapon_type.as_str().to_string(),
+            rounds: i32::try_from(store.rounds).unwrap_or(i32::MAX),
+        })
+        .collect();
+    let variables = UpdateDroneStateVariables {
+        input: UpdateDroneStateInput {
+            convoy_id: convoy_id.to_string(),
+            drone_id: drone.drone_id.to_string(),
+            current_waypoint: i32::try_from(drone.telemetry_gen.current_waypoint()).ok(),
+            weapons: Some(weapons),
+            ..Default::default()
+        },
+    };
+
+    batch.push::<UpdateDroneState>(variables)?;
 
     Ok(())
 }
@@ -0,0 +1,138 @@
+//! Per-mission-type behavior profiles.
+//!
+//! The mission type decides how drones route and when they shoot: ISR
+//! orbits and reports sensor contacts with rare engagements, STRIKE
+//! concentrates fire in a narrow target window, ESCORT flies formation
+//! offsets from a lead and SAR flies a search ladder without engaging.
+
+use drone_domain::MissionType;
+use std::ops::Range;
+
+/// Route shape flown for a mission.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RouteShape {
+    /// Ingress, operating area, egress
+    Standard,
+    /// Wingmen hold a vee behind the lead's route
+    Formation { spacing_km: f64 },
+    /// Parallel-track search ladder over the operating area
+    Search { legs: u32, track_spacing_km: f64 },
+}
+
+/// How a convoy behaves for its mission type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissionProfile {
+    pub mission_type: MissionType,
+    pub route: RouteShape,
+    /// Mission progress during which drones may engage
+    pub engagement_window: Range<f64>,
+    /// Chance per tick that an armed drone in the window engages
+    pub engagement_chance: f64,
+    /// Chance per tick that a drone reports a sensor contact
+    pub sensor_contact_chance: f64,
+    /// Multiplier on loiter orbit times
+    pub loiter_scale: f64,
+    /// Retask sensors for wide-area search from launch
+    pub sensors_wide_area: bool,
+}
+
+impl MissionProfile {
+    /// Profile for a mission type.
+    pub fn for_mission(mission_type: MissionType) -> Self {
+        let base = Self {
+            mission_type,
+            route: RouteShape::Standard,
+            engagement_window: 0.25..0.75,
+            engagement_chance: 0.3,
+            sensor_contact_chance: 0.02,
+            loiter_scale: 1.0,
+            sensors_wide_area: false,
+        };
+        match mission_type {
+            MissionType::Strike => Self {
+                engagement_window: 0.35..0.65,
+                engagement_chance: 0.5,
+                ..base
+            },
+            MissionType::Isr => Self {
+                engagement_chance: 0.02,
+                sensor_contact_chance: 0.2,
+                loiter_scale: 2.0,
+                sensors_wide_area: true,
+                ..base
+            },
+            MissionType::Escort => Self {
+                route: RouteShape::Formation { spacing_km: 1.5 },
+                engagement_window: 0.1..0.9,
+                engagement_chance: 0.05,
+                ..base
+            },
+            MissionType::Resupply => Self {
+                engagement_chance: 0.02,
+                ..base
+            },
+            MissionType::Sar => Self {
+                route: RouteShape::Search {
+                    legs: 8,
+                    track_spacing_km: 3.0,
+                },
+                engagement_window: 0.0..0.0,
+                engagement_chance: 0.0,
+                sensor_contact_chance: 0.1,
+                sensors_wide_area: true,
+                ..base
+            },
+        }
+    }
+
+    /// Profile for a mission type name such as `ISR`; STRIKE when the name
+    /// is not recognised.
+    pub fn from_name(name: &str) -> Self {
+        Self::for_mission(parse_mission_type(name).unwrap_or(MissionType::Strike))
+    }
+
+    /// Whether drones may engage at mission `progress`.
+    pub fn in_engagement_window(&self, progress: f64) -> bool {
+        self.engagement_chance > 0.0 && self.engagement_window.contains(&progress)
+    }
+}
+
+impl Default for MissionProfile {
+    fn default() -> Self {
+        Self::for_mission(MissionType::Strike)
+    }
+}
+
+/// Parse a mission type name (`ISR`, `STRIKE`, `ESCORT`, `RESUPPLY`, `SAR`),
+/// ignoring case.
+pub fn parse_mission_type(name: &str) -> Result<MissionType, String> {
+    match name.trim().to_ascii_uppercase().as_str() {
+        "ISR" => Ok(MissionType::Isr),
+        "STRIKE" => Ok(MissionType::Strike),
+        "ESCORT" => Ok(MissionType::Escort),
+        "RESUPPLY" => Ok(MissionType::Resupply),
+        "SAR" => Ok(MissionType::Sar),
+        other => Err(format!(
+            "unknown mission type `{other}`; expected ISR, STRIKE, ESCORT, RESUPPLY or SAR"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_by_mission() {
+        assert_eq!(parse_mission_type(" isr "), Ok(MissionType::Isr));
+        assert!(parse_mission_type("CAS").is_err());
+        assert_eq!(MissionProfile::from_name("CAS").mission_type, MissionType::Strike);
+
+        let strike = MissionProfile::from_name("STRIKE");
+        let isr = MissionProfile::from_name("ISR");
+        assert!(isr.engagement_chance < strike.engagement_chance);
+        assert!(isr.sensor_contact_chance > strike.sensor_contact_chance);
+        assert!(strike.in_engagement_window(0.5) && !strike.in_engagement_window(0.3));
+        assert!(!MissionProfile::from_name("SAR").in_engagement_window(0.5));
+    }
+}
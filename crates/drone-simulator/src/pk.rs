@@ -0,0 +1,294 @@
+//! Probability-of-kill (Pk) model for simulated engagements.
+//!
+//! Pk starts from the weapon's base accuracy and is scaled by how well the
+//! weapon suits the target (pairing matrix), how hard the target is to kill,
+//! shot geometry and the weather along the weapon's guidance path.
+
+use crate::dynamics::Wind;
+use crate::engagement::{TargetType, WeaponType};
+
+/// Lowest Pk the model will predict.
+pub const MIN_PK: f64 = 0.1;
+
+/// Highest Pk the model will predict.
+pub const MAX_PK: f64 = 0.99;
+
+/// How a weapon finds its target, which decides what weather hurts it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Guidance {
+    /// Semi-active laser; needs a clear line of sight to the designator spot
+    Laser,
+    /// GPS/INS; blind to visibility, pushed around by wind on the way down
+    Gps,
+    /// Imaging infrared; degraded by moisture in the air
+    Infrared,
+}
+
+impl WeaponType {
+    /// Guidance the weapon relies on.
+    pub fn guidance(&self) -> Guidance {
+        match self {
+            Self::Agm114Hellfire | Self::Gbu12Paveway | Self::Agm176Griffin => Guidance::Laser,
+            Self::Gbu38Jdam => Guidance::Gps,
+            Self::Aim9xSidewinder => Guidance::Infrared,
+        }
+    }
+
+    /// Row in [`PAIRING`].
+    fn pairing_index(&self) -> usize {
+        match self {
+            Self::Agm114Hellfire => 0,
+            Self::Gbu12Paveway => 1,
+            Self::Aim9xSidewinder => 2,
+            Self::Gbu38Jdam => 3,
+            Self::Agm176Griffin => 4,
+        }
+    }
+}
+
+impl TargetType {
+    /// Pk multiplier for how hard the target is to hit and kill.
+    ///
+    /// Small or moving targets score below 1, large fixed ones above.
+    pub fn pk_modifier(&self) -> f64 {
+        match self {
+            Self::Vehicle => 0.95,
+            Self::Personnel => 0.85,
+            Self::Structure => 1.05,
+            Self::Artillery => 1.0,
+            Self::Radar => 1.0,
+            Self::Aircraft => 0.8,
+        }
+    }
+
+    /// Column in [`PAIRING`].
+    fn pairing_index(&self) -> usize {
+        match self {
+            Self::Vehicle => 0,
+            Self::Personnel => 1,
+            Self::Structure => 2,
+            Self::Artillery => 3,
+            Self::Radar => 4,
+            Self::Aircraft => 5,
+        }
+    }
+}
+
+/// Weapon-target pairing effectiveness.
+///
+/// Rows follow [`WeaponType`], columns [`TargetType`]: vehicle, personnel,
+/// structure, artillery, radar, aircraft.
+pub const PAIRING: [[f64; 6]; 5] = [
+    // Hellfire: anti-armour, precise enough for point targets
+    [1.0, 0.9, 0.85, 1.0, 0.95, 0.6],
+    // Paveway: bunker and building busting, poor against movers
+    [0.85, 0.8, 1.0, 0.95, 0.9, 0.5],
+    // Sidewinder: air-to-air; heat-seeking against ground targets is marginal
+    [0.7, 0.6, 0.6, 0.7, 0.7, 1.0],
+    // JDAM: fixed targets at known coordinates
+    [0.8, 0.75, 1.0, 0.95, 0.95, 0.45],
+    // Griffin: low-collateral, light targets
+    [0.95, 1.0, 0.8, 0.85, 0.9, 0.6],
+];
+
+/// Weather at the target beyond wind, which comes from the flight model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Weather {
+    pub visibility_km: f64,
+    pub precipitation_mm_hr: f64,
+}
+
+impl Default for Weather {
+    /// Clear and dry.
+    fn default() -> Self {
+        Self {
+            visibility_km: 10.0,
+            precipitation_mm_hr: 0.0,
+        }
+    }
+}
+
+impl Weather {
+    /// Create weather with `visibility_km` and `precipitation_mm_hr`.
+    pub fn new(visibility_km: f64, precipitation_mm_hr: f64) -> Self {
+        Self {
+            visibility_km: visibility_km.max(0.0),
+            precipitation_mm_hr: precipitation_mm_hr.max(0.0),
+        }
+    }
+
+    /// Pk multiplier for a weapon using `guidance` in this weather and `wind`.
+    pub fn degradation(&self, guidance: Guidance, wind: Wind) -> f64 {
+        let factor = match guidance {
+            Guidance::Laser => {
+                // Spot tracking falls off below 5 km visibility
+                let visibility = (self.visibility_km / 5.0).min(1.0);
+                let rain = 1.0 - (self.precipitation_mm_hr / 50.0).min(0.3);
+                0.5 + 0.5 * visibility * rain
+            }
+            Guidance::Gps => 1.0 - (wind.speed_mps / 100.0).min(0.15),
+            Guidance::Infrared => {
+                let visibility = (self.visibility_km / 3.0).min(1.0);
+                let rain = 1.0 - (self.precipitation_mm_hr / 25.0).min(0.4);
+                0.7 + 0.3 * visibility * rain
+            }
+        };
+        factor.clamp(0.0, 1.0)
+    }
+}
+
+/// One shot as the model sees it.
+#[derive(Debug, Clone, Copy)]
+pub struct Shot {
+    pub weapon: WeaponType,
+    pub target: TargetType,
+    pub range_km: f64,
+    pub altitude_m: f64,
+}
+
+/// Pk model with an adjustable pairing matrix.
+#[derive(Debug, Clone)]
+pub struct PkModel {
+    pairing: [[f64; 6]; 5],
+}
+
+impl PkModel {
+    /// Model using [`PAIRING`].
+    pub fn new() -> Self {
+        Self { pairing: PAIRING }
+    }
+
+    /// Override one weapon-target pairing.
+    #[must_use]
+    pub fn with_pairing(mut self, weapon: WeaponType, target: TargetType, factor: f64) -> Self {
+        self.pairing[weapon.pairing_index()][target.pairing_index()] = factor.max(0.0);
+        self
+    }
+
+    /// Pairing effectiveness of `weapon` against `target`.
+    pub fn pairing(&self, weapon: WeaponType, target: TargetType) -> f64 {
+        self.pairing[weapon.pairing_index()][target.pairing_index()]
+    }
+
+    /// Predicted Pk for `shot`, between [`MIN_PK`] and [`MAX_PK`].
+    ///
+    /// `skill` scales the result for operator proficiency (1.0 = nominal).
+    pub fn predict(&self, shot: &Shot, weather: &Weather, wind: Wind, skill: f64) -> f64 {
+        let typical_range = shot.weapon.typical_range_km();
+
+        // Accuracy drops beyond the weapon's typical range
+        let range_factor = if shot.range_km <= typical_range {
+            1.0
+        } else {
+            (typical_range / shot.range_km).powf(0.5)
+        };
+
+        // Slightly worse at very high or low altitudes
+        let alt_factor = if (3000.0..=6000.0).contains(&shot.altitude_m) {
+            1.0
+        } else {
+            0.95
+        };
+
+        let pk = shot.weapon.base_accuracy()
+            * self.pairing(shot.weapon, shot.target)
+            * shot.target.pk_modifier()
+            * range_factor
+            * alt_factor
+            * weather.degradation(shot.weapon.guidance(), wind)
+            * skill;
+
+        pk.clamp(MIN_PK, MAX_PK)
+    }
+}
+
+impl Default for PkModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shot(weapon: WeaponType, target: TargetType) -> Shot {
+        Shot {
+            weapon,
+            target,
+            range_km: weapon.typical_range_km(),
+            altitude_m: 5000.0,
+        }
+    }
+
+    #[test]
+    fn test_pairing_favours_matched_weapons() {
+        let model = PkModel::new();
+        let clear = Weather::default();
+        let calm = Wind::default();
+
+        let air_to_air = model.predict(
+            &shot(WeaponType::Aim9xSidewinder, TargetType::Aircraft),
+            &clear,
+            calm,
+            1.0,
+        );
+        let bomb_on_aircraft = model.predict(
+            &shot(WeaponType::Gbu38Jdam, TargetType::Aircraft),
+            &clear,
+            calm,
+            1.0,
+        );
+        assert!(air_to_air > bomb_on_aircraft);
+
+        let tuned = model.with_pairing(WeaponType::Gbu38Jdam, TargetType::Aircraft, 1.0);
+        assert!(
+            tuned.predict(
+                &shot(WeaponType::Gbu38Jdam, TargetType::Aircraft),
+                &clear,
+                calm,
+                1.0
+            ) > bomb_on_aircraft
+        );
+    }
+
+    #[test]
+    fn test_weather_degrades_by_guidance() {
+        let fog = Weather::new(1.0, 0.0);
+        let calm = Wind::default();
+
+        // Laser guidance loses the spot in fog; GPS does not care
+        assert!(fog.degradation(Guidance::Laser, calm) < 0.7);
+        assert!((fog.degradation(Guidance::Gps, calm) - 1.0).abs() < f64::EPSILON);
+
+        // Wind pushes GPS bombs off their aim point
+        assert!(Weather::default().degradation(Guidance::Gps, Wind::new(0.0, 20.0)) < 1.0);
+        assert!((Weather::default().degradation(Guidance::Laser, calm) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_prediction_is_clamped() {
+        let model = PkModel::new();
+        let blizzard = Weather::new(0.0, 100.0);
+        let pk = model.predict(
+            &Shot {
+                weapon: WeaponType::Gbu12Paveway,
+                target: TargetType::Aircraft,
+                range_km: 40.0,
+                altitude_m: 9000.0,
+            },
+            &blizzard,
+            Wind::default(),
+            0.5,
+        );
+        assert!((pk - MIN_PK).abs() < f64::EPSILON);
+
+        let pk = model.predict(
+            &shot(WeaponType::Agm114Hellfire, TargetType::Structure),
+            &Weather::default(),
+            Wind::default(),
+            1.5,
+        );
+        assert!(pk <= MAX_PK);
+    }
+}
@@ -0,0 +1,371 @@
+//! Library-driven simulation runs.
+//!
+//! [`SimulationRun`] steps a convoy one tick at a time without sleeping,
+//! stamps every event from an injectable [`Clock`] and hands it to an
+//! [`EventSink`]. Seeded runs on a [`ManualClock`] replay identically, so
+//! integration tests can drive thousands of ticks in milliseconds.
+//!
+//! ```
+//! use drone_simulator::run::{ManualClock, SimulationConfig, SimulationEvent, SimulationRun};
+//!
+//! let config = SimulationConfig::new("ALPHA", "STRIKE", 4)
+//!     .with_ticks(1_000)
+//!     .with_seed(7);
+//! let mut run = SimulationRun::new(config, ManualClock::default());
+//! let mut events = Vec::new();
+//! run.run_to_end(&mut events);
+//!
+//! assert!(run.is_finished());
+//! assert!(events.iter().any(|e| matches!(e, SimulationEvent::Engagement(_))));
+//! ```
+
+use crate::convoy::{ConvoySimulator, ConvoyStatus, SensorContact};
+use crate::dynamics::Wind;
+use crate::engagement::SimulatedEngagement;
+use crate::loadout::LoadoutConfig;
+use crate::pk::Weather;
+use crate::telemetry::TelemetrySnapshot;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Source of event timestamps.
+pub trait Clock {
+    /// Current time.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Called once per tick; manual clocks step forward by `interval`.
+    fn tick(&mut self, _interval: Duration) {}
+}
+
+/// Wall-clock time, for live runs paced by the caller.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Simulated time that advances exactly one tick interval per tick.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: DateTime<Utc>,
+}
+
+impl ManualClock {
+    /// Start the clock at `start`.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self { now: start }
+    }
+}
+
+impl Default for ManualClock {
+    /// Start at the Unix epoch.
+    fn default() -> Self {
+        Self::new(DateTime::UNIX_EPOCH)
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+
+    fn tick(&mut self, interval: Duration) {
+        self.now += chrono::Duration::from_std(interval).unwrap_or(chrono::Duration::MAX);
+    }
+}
+
+/// Something that happened during a tick.
+#[derive(Debug, Clone)]
+pub enum SimulationEvent {
+    /// Convoy status changed, e.g. ACTIVE to RTB
+    StatusChanged { from: ConvoyStatus, to: ConvoyStatus },
+    Telemetry(TelemetrySnapshot),
+    Engagement(SimulatedEngagement),
+    SensorContact(SensorContact),
+    /// A drone fired its last round and switched to ISR
+    WeaponsExpended { drone_id: Uuid, callsign: String },
+}
+
+/// Receiver of simulation events.
+pub trait EventSink {
+    /// Handle one event from tick `tick` (1-based).
+    fn handle(&mut self, tick: u32, event: SimulationEvent);
+}
+
+/// Collect events in order.
+impl EventSink for Vec<SimulationEvent> {
+    fn handle(&mut self, _tick: u32, event: SimulationEvent) {
+        self.push(event);
+    }
+}
+
+/// Scenario for a [`SimulationRun`].
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub callsign: String,
+    pub mission_type: String,
+    pub drone_count: usize,
+    /// Ticks from launch to mission complete
+    pub ticks: u32,
+    /// Simulated time per tick
+    pub tick_interval: Duration,
+    /// Seed for a repeatable run; random when unset
+    pub seed: Option<u64>,
+    pub wind: Wind,
+    /// Weather at the target, degrading weapon Pk
+    pub weather: Weather,
+    pub loadouts: LoadoutConfig,
+}
+
+impl SimulationConfig {
+    /// 300 one-second ticks in calm, clear air with default loadouts.
+    pub fn new(callsign: &str, mission_type: &str, drone_count: usize) -> Self {
+        Self {
+            callsign: callsign.to_string(),
+            mission_type: mission_type.to_string(),
+            drone_count,
+            ticks: 300,
+            tick_interval: Duration::from_secs(1),
+            seed: None,
+            wind: Wind::default(),
+            weather: Weather::default(),
+            loadouts: LoadoutConfig::default(),
+        }
+    }
+
+    /// Set the mission length in ticks.
+    #[must_use]
+    pub fn with_ticks(mut self, ticks: u32) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    /// Set the simulated time per tick.
+    #[must_use]
+    pub fn with_tick_interval(mut self, interval: Duration) -> Self {
+        self.tick_interval = interval;
+        self
+    }
+
+    /// Make the run repeatable.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Fly in a steady wind.
+    #[must_use]
+    pub fn with_wind(mut self, wind: Wind) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    /// Engage targets in `weather`.
+    #[must_use]
+    pub fn with_weather(mut self, weather: Weather) -> Self {
+        self.weather = weather;
+        self
+    }
+
+    /// Override platform loadouts.
+    #[must_use]
+    pub fn with_loadouts(mut self, loadouts: LoadoutConfig) -> Self {
+        self.loadouts = loadouts;
+        self
+    }
+}
+
+/// A convoy mission stepped tick by tick.
+pub struct SimulationRun<C: Clock = SystemClock> {
+    convoy: ConvoySimulator,
+    clock: C,
+    ticks: u32,
+    tick: u32,
+    tick_interval: Duration,
+}
+
+impl<C: Clock> SimulationRun<C> {
+    /// Launch the convoy described by `config`.
+    pub fn new(config: SimulationConfig, clock: C) -> Self {
+        let convoy = match config.seed {
+            Some(seed) => ConvoySimulator::seeded(
+                &config.callsign,
+                &config.mission_type,
+                config.drone_count,
+                seed,
+            ),
+            None => ConvoySimulator::new(&config.callsign, &config.mission_type, config.drone_count),
+        };
+        let mut convoy = convoy
+            .with_loadouts(&config.loadouts)
+            .with_wind(config.wind)
+            .with_weather(config.weather);
+        convoy.start_time = clock.now();
+
+        Self {
+            convoy,
+            clock,
+            ticks: config.ticks,
+            tick: 0,
+            tick_interval: config.tick_interval,
+        }
+    }
+
+    /// Advance one tick, sending its events to `sink`.
+    ///
+    /// Returns `false` without doing anything once the mission is over.
+    pub fn tick(&mut self, sink: &mut impl EventSink) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.tick += 1;
+        self.clock.tick(self.tick_interval);
+        let now = self.clock.now();
+        let tick = self.tick;
+
+        // Set progress from the tick count so it lands exactly on 1.0
+        let from = self.convoy.status;
+        let target = f64::from(tick) / f64::from(self.ticks);
+        self.convoy.advance(target - self.convoy.progress());
+        if self.convoy.status != from {
+            let to = self.convoy.status;
+            sink.handle(tick, SimulationEvent::StatusChanged { from, to });
+        }
+
+        for mut snapshot in self.convoy.generate_telemetry() {
+            snapshot.timestamp = now;
+            sink.handle(tick, SimulationEvent::Telemetry(snapshot));
+        }
+        for mut contact in self.convoy.simulate_sensor_contacts() {
+            contact.timestamp = now;
+            sink.handle(tick, SimulationEvent::SensorContact(contact));
+        }
+
+        let armed: Vec<Uuid> = self
+            .convoy
+            .drones
+            .values()
+            .filter(|d| !d.loadout.is_dry())
+            .map(|d| d.drone_id)
+            .collect();
+        for mut engagement in self.convoy.simulate_engagements() {
+            engagement.timestamp = now;
+            sink.handle(tick, SimulationEvent::Engagement(engagement));
+        }
+        for drone_id in armed {
+            let drone = &self.convoy.drones[&drone_id];
+            if drone.loadout.is_dry() {
+                sink.handle(
+                    tick,
+                    SimulationEvent::WeaponsExpended {
+                        drone_id,
+                        callsign: drone.callsign.clone(),
+                    },
+                );
+            }
+        }
+
+        true
+    }
+
+    /// Tick until the mission is over; returns the ticks run.
+    pub fn run_to_end(&mut self, sink: &mut impl EventSink) -> u32 {
+        let start = self.tick;
+        while self.tick(sink) {}
+        self.tick - start
+    }
+
+    /// Ticks run so far.
+    pub fn current_tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Mission length in ticks.
+    pub fn total_ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    /// Whether every tick has run.
+    pub fn is_finished(&self) -> bool {
+        self.tick >= self.ticks
+    }
+
+    /// Simulated time per tick.
+    pub fn tick_interval(&self) -> Duration {
+        self.tick_interval
+    }
+
+    /// The simulated convoy.
+    pub fn convoy(&self) -> &ConvoySimulator {
+        &self.convoy
+    }
+
+    /// The simulated convoy, e.g. to queue sensor tasks.
+    pub fn convoy_mut(&mut self) -> &mut ConvoySimulator {
+        &mut self.convoy
+    }
+
+    /// The run's clock.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(events: &[SimulationEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| match event {
+                SimulationEvent::Telemetry(t) => format!(
+                    "T {} {} {:.6} {:.6} {:.1}",
+                    t.drone_id, t.timestamp, t.position.latitude, t.position.longitude, t.position.altitude_m
+                ),
+                SimulationEvent::Engagement(e) => {
+                    format!("E {} {} {} {}", e.engagement_id, e.drone_id, e.weapon_type.as_str(), e.hit)
+                }
+                other => format!("{other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_seeded_runs_replay_identically() {
+        let config = SimulationConfig::new("ALPHA", "STRIKE", 4)
+            .with_ticks(5_000)
+            .with_seed(42)
+            .with_wind(Wind::new(315.0, 8.0));
+
+        let mut first = Vec::new();
+        let mut run = SimulationRun::new(config.clone(), ManualClock::default());
+        assert_eq!(run.run_to_end(&mut first), 5_000);
+        assert!(!run.tick(&mut first));
+        assert_eq!(run.convoy().status, ConvoyStatus::Complete);
+        assert_eq!(
+            run.clock().now(),
+            DateTime::UNIX_EPOCH + chrono::Duration::seconds(5_000)
+        );
+
+        let mut second = Vec::new();
+        SimulationRun::new(config, ManualClock::default()).run_to_end(&mut second);
+        assert_eq!(summary(&first), summary(&second));
+
+        let expended = first
+            .iter()
+            .filter(|e| matches!(e, SimulationEvent::WeaponsExpended { .. }))
+            .count();
+        let armed = run.convoy().drones.values().filter(|d| d.platform_type != "RQ4_GLOBAL_HAWK").count();
+        assert_eq!(expended, armed);
+        assert!(first.iter().any(|e| matches!(
+            e,
+            SimulationEvent::StatusChanged { to: ConvoyStatus::Rtb, .. }
+        )));
+    }
+}
@@ -1,8 +1,13 @@
 //! Telemetry data generation for drone simulation.
+//!
+//! Each drone flies its route through a [`FlightModel`], so tracks turn,
+//! climb and drift with the wind instead of jumping between waypoints.
 
-use crate::flight::{Coordinates, FlightPathGenerator, Waypoint};
+use crate::dynamics::{offset_m, FlightModel, KinematicState, PerformanceLimits, Wind};
+use crate::flight::{Coordinates, Waypoint};
 use chrono::{DateTime, Utc};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use rand_distr::{Distribution, Normal};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -29,22 +34,45 @@ pub struct TelemetrySnapshot {
     pub distance_to_waypoint_m: f64,
 }
 
+/// Longest integration step in seconds
+const MAX_STEP_SEC: f64 = 1.0;
+
+/// Allowance over straight-leg flight time for turns, climbs and wind
+const ROUTE_TIME_MARGIN: f64 = 1.2;
+
 /// Telemetry generator for a single drone.
 pub struct TelemetryGenerator {
     drone_id: Uuid,
     callsign: String,
     waypoints: Vec<Waypoint>,
+    /// Last waypoint reached
     current_waypoint_idx: usize,
     fuel_remaining: f32,
     base_fuel_burn: f32,
-    flight_gen: FlightPathGenerator,
-    rng: rand::rngs::ThreadRng,
+    model: FlightModel,
+    /// Orbit time left at the current loiter waypoint
+    loiter_remaining_sec: f64,
+    /// Mission progress at the last snapshot
+    last_progress: f64,
+    /// Flight time the whole mission represents; estimated from the route
+    /// when unset
+    mission_duration_sec: Option<f64>,
+    rng: StdRng,
     noise: Normal<f64>,
 }
 
 impl TelemetryGenerator {
     /// Create a new telemetry generator for a drone.
     pub fn new(drone_id: Uuid, callsign: &str, waypoints: Vec<Waypoint>) -> Self {
+        let limits = PerformanceLimits::default();
+        let start = waypoints
+            .first()
+            .map_or_else(Coordinates::default, |wp| wp.coordinates.clone());
+        let heading = waypoints
+            .get(1)
+            .map_or(0.0, |wp| KinematicState::at(&start, 0.0, 0.0).range_and_bearing(&wp.coordinates).1);
+        let state = KinematicState::at(&start, heading, limits.min_speed_mps);
+
         Self {
             drone_id,
             callsign: callsign.to_string(),
@@ -52,36 +80,126 @@ impl TelemetryGenerator {
             current_waypoint_idx: 0,
             fuel_remaining: 100.0,
             base_fuel_burn: 0.02,
-            flight_gen: FlightPathGenerator::kandahar(),
-            rng: rand::thread_rng(),
+            model: FlightModel::new(limits, Wind::default(), state),
+            loiter_remaining_sec: 0.0,
+            last_progress: 0.0,
+            mission_duration_sec: None,
+            rng: StdRng::from_entropy(),
             noise: Normal::new(0.0, 1.0).unwrap(),
         }
     }
 
+    /// Fly with a platform's performance limits; set before the first
+    /// snapshot.
+    #[must_use]
+    pub fn with_limits(mut self, limits: PerformanceLimits) -> Self {
+        self.model.limits = limits;
+        self.model.state.airspeed_mps = limits.min_speed_mps;
+        self.model.state.ground_speed_mps = limits.min_speed_mps;
+        self
+    }
+
+    /// Draw sensor noise from a fixed seed.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Fly in a steady wind.
+    #[must_use]
+    pub fn with_wind(mut self, wind: Wind) -> Self {
+        self.set_wind(wind);
+        self
+    }
+
+    /// Set the flight time the whole mission represents.
+    #[must_use]
+    pub fn with_mission_duration(mut self, seconds: f64) -> Self {
+        self.mission_duration_sec = Some(seconds.max(0.0));
+        self
+    }
+
+    /// Change the wind.
+    pub fn set_wind(&mut self, wind: Wind) {
+        self.model.wind = wind;
+    }
+
+    /// Estimated time to fly the route at commanded speeds, including
+    /// loiter orbits.
+    pub fn estimated_route_duration_sec(&self) -> f64 {
+        let limits = &self.model.limits;
+        let legs: f64 = self
+            .waypoints
+            .windows(2)
+            .map(|leg| {
+                let (north, east) = offset_m(
+                    leg[0].coordinates.latitude,
+                    leg[0].coordinates.longitude,
+                    &leg[1].coordinates,
+                );
+                north.hypot(east) / limits.commanded_speed(f64::from(leg[1].coordinates.speed_mps))
+            })
+            .sum();
+        let loiter: u32 = self.waypoints.iter().filter_map(|wp| wp.loiter_time_sec).sum();
+        (legs + f64::from(loiter)) * ROUTE_TIME_MARGIN
+    }
+
+    /// Fly the route for `seconds`.
+    fn fly(&mut self, seconds: f64) {
+        let mut remaining = seconds;
+        while remaining > 0.0 {
+            let dt = remaining.min(MAX_STEP_SEC);
+            remaining -= dt;
+
+            let reached = &self.waypoints[self.current_waypoint_idx].coordinates;
+            if self.loiter_remaining_sec > 0.0 {
+                let altitude = self.model.state.altitude_m;
+                self.model.step_orbit(f64::from(reached.speed_mps), altitude, dt);
+                self.loiter_remaining_sec -= dt;
+                continue;
+            }
+
+            let Some(target) = self.waypoints.get(self.current_waypoint_idx + 1) else {
+                // Route flown: spiral down over the field, then stop
+                if self.model.state.airspeed_mps == 0.0 {
+                    break;
+                }
+                if self.model.state.altitude_m - reached.altitude_m > 1.0 {
+                    self.model.step_orbit(f64::from(reached.speed_mps), reached.altitude_m, dt);
+                } else {
+                    self.model.land();
+                }
+                continue;
+            };
+            self.model.step_toward(&target.coordinates, dt);
+
+            if self.model.state.range_and_bearing(&target.coordinates).0 < self.model.capture_radius_m() {
+                self.current_waypoint_idx += 1;
+                self.loiter_remaining_sec = f64::from(target.loiter_time_sec.unwrap_or(0));
+            }
+        }
+    }
+
     /// Generate next telemetry snapshot.
     pub fn next_snapshot(&mut self, progress: f64) -> Option<TelemetrySnapshot> {
         if self.waypoints.is_empty() {
             return None;
         }
 
-        // Update waypoint index based on progress
-        let total_segments = self.waypoints.len() - 1;
-        let segment_progress = progress * total_segments as f64;
-        self.current_waypoint_idx = (segment_progress as usize).min(total_segments);
+        // Fly for the share of the mission elapsed since the last snapshot
+        let duration = self
+            .mission_duration_sec
+            .unwrap_or_else(|| self.estimated_route_duration_sec());
+        let progress = progress.clamp(0.0, 1.0);
+        self.fly((progress - self.last_progress).max(0.0) * duration);
+        self.last_progress = self.last_progress.max(progress);
 
-        // Get current and next waypoint
-        let current_wp = &self.waypoints[self.current_waypoint_idx];
+        let state = self.model.state.clone();
+        let position = state.coordinates();
+        let current_sequence = self.waypoints[self.current_waypoint_idx].sequence;
         let next_wp = self.waypoints.get(self.current_waypoint_idx + 1);
 
-        // Interpolate position
-        let position = if let Some(next) = next_wp {
-            let local_progress = segment_progress.fract();
-            self.flight_gen
-                .interpolate(&current_wp.coordinates, &next.coordinates, local_progress)
-        } else {
-            current_wp.coordinates.clone()
-        };
-
         // Update fuel
         self.fuel_remaining -= self.base_fuel_burn * (1.0 + self.noise.sample(&mut self.rng) as f32 * 0.1);
         self.fuel_remaining = self.fuel_remaining.max(0.0);
@@ -95,15 +213,16 @@ impl TelemetryGenerator {
             fuel_burn_rate: self.base_fuel_burn + self.noise.sample(&mut self.rng) as f32 * 0.005,
             engine_rpm: 5500 + self.rng.gen_range(0..500),
             engine_temp_c: 85.0 + self.noise.sample(&mut self.rng) as f32 * 5.0,
-            airspeed_mps: position.speed_mps + self.noise.sample(&mut self.rng) as f32 * 2.0,
-            ground_speed_mps: position.speed_mps * 0.95 + self.noise.sample(&mut self.rng) as f32 * 3.0,
-            vertical_speed_mps: self.noise.sample(&mut self.rng) as f32 * 5.0,
-            roll_deg: self.noise.sample(&mut self.rng) as f32 * 3.0,
-            pitch_deg: self.noise.sample(&mut self.rng) as f32 * 2.0,
+            airspeed_mps: (state.airspeed_mps + self.noise.sample(&mut self.rng) * 0.5) as f32,
+            ground_speed_mps: (state.ground_speed_mps + self.noise.sample(&mut self.rng) * 0.5) as f32,
+            vertical_speed_mps: (state.vertical_speed_mps + self.noise.sample(&mut self.rng) * 0.2) as f32,
+            roll_deg: (state.bank_deg + self.noise.sample(&mut self.rng) * 0.5) as f32,
+            pitch_deg: (state.vertical_speed_mps.atan2(state.airspeed_mps.max(1.0)).to_degrees()
+                + self.noise.sample(&mut self.rng) * 0.3) as f32,
             yaw_deg: position.heading_deg,
             gps_satellites: self.rng.gen_range(8..14),
             signal_strength_dbm: -60 + self.rng.gen_range(-15..5),
-            current_waypoint: current_wp.sequence,
+            current_waypoint: current_sequence,
             distance_to_waypoint_m: self.calculate_distance_to_waypoint(&position, next_wp),
         };
 
@@ -146,6 +265,7 @@ impl TelemetryGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::flight::FlightPathGenerator;
 
     #[test]
     fn test_telemetry_generation() {
@@ -160,4 +280,28 @@ mod tests {
         let snapshot = telem_gen.next_snapshot(0.5).unwrap();
         assert!(snapshot.current_waypoint > 0);
     }
+
+    #[test]
+    fn test_track_is_continuous() {
+        let mut flight_gen = FlightPathGenerator::kandahar();
+        let waypoints = flight_gen.generate_mission_path("TEST-01");
+        let limits = PerformanceLimits::default();
+        let mut telem_gen = TelemetryGenerator::new(Uuid::new_v4(), "TEST-01", waypoints)
+            .with_limits(limits)
+            .with_wind(Wind::new(315.0, 10.0));
+        let step_sec = telem_gen.estimated_route_duration_sec() / 300.0;
+
+        let mut prev = telem_gen.next_snapshot(0.0).unwrap();
+        for i in 1..=300 {
+            let snapshot = telem_gen.next_snapshot(f64::from(i) / 300.0).unwrap();
+            let (moved_m, _) = KinematicState::at(&prev.position, 0.0, 0.0)
+                .range_and_bearing(&snapshot.position);
+            let climbed_m = (snapshot.position.altitude_m - prev.position.altitude_m).abs();
+
+            assert!(moved_m <= (limits.max_speed_mps + 10.0) * step_sec + 1.0);
+            assert!(climbed_m <= limits.max_descent_mps * step_sec + 1e-6);
+            prev = snapshot;
+        }
+        assert!(prev.current_waypoint > 10);
+    }
 }